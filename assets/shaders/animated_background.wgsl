@@ -0,0 +1,18 @@
+struct AnimatedBackgroundMaterial {
+    color: vec4<f32>,
+    time: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: AnimatedBackgroundMaterial;
+
+@fragment
+fn fragment(
+    #import bevy_ui::ui_vertex_output
+) -> @location(0) vec4<f32> {
+    // Diagonal stripes moving over time, in logical pixels so that they don't stretch with the node
+    let position = uv * size;
+    let stripe = sin((position.x + position.y) * 0.05 - material.time * 2.0);
+    let brightness = 0.8 + 0.2 * smoothstep(-0.1, 0.1, stripe);
+    return vec4<f32>(material.color.rgb * brightness, material.color.a);
+}
@@ -0,0 +1,13 @@
+struct DebugColor {
+    color: vec4<f32>,
+};
+
+@group(2) @binding(0)
+var<uniform> debug_color: DebugColor;
+
+@fragment
+fn fragment(
+    #import bevy_pbr::mesh_vertex_output
+) -> @location(0) vec4<f32> {
+    return debug_color.color;
+}
@@ -0,0 +1,43 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::pbr_bindings
+#import bevy_pbr::mesh_bindings
+
+#import bevy_pbr::utils
+#import bevy_pbr::clustered_forward
+#import bevy_pbr::lighting
+#import bevy_pbr::shadows
+#import bevy_pbr::pbr_functions
+#import bevy_pbr::parallax_mapping
+#import bevy_pbr::fog
+#import bevy_pbr::decal
+#import bevy_pbr::pbr_fragment
+
+struct DissolveExtension {
+    edge_color: vec4<f32>,
+};
+
+@group(1) @binding(100)
+var<uniform> dissolve: DissolveExtension;
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    // Builds the PbrInput the StandardMaterial would light
+    var pbr_input = pbr_input_from_standard_material(in);
+
+    // A random value for each cell of a grid in the local space of the mesh, the cells under the
+    // threshold are dissolved. Post-multiplying by the inverse transpose of the model transforms
+    // by its inverse.
+    let local_position = (vec4<f32>(in.world_position.xyz, 1.0) * mesh.inverse_transpose_model).xyz;
+    let cell = floor(local_position * 8.0);
+    let noise = fract(sin(dot(cell, vec3<f32>(12.9898, 78.233, 37.719))) * 43758.5453);
+    let threshold = 0.5 + 0.5 * sin(globals.time);
+    if (noise < threshold) {
+        discard;
+    }
+    // The cells about to dissolve glow
+    if (noise < threshold + 0.05) {
+        pbr_input.material.emissive = dissolve.edge_color;
+    }
+
+    return pbr_output_color(pbr_input);
+}
@@ -0,0 +1,26 @@
+struct ProgressBarMaterial {
+    color: vec4<f32>,
+    background_color: vec4<f32>,
+    progress: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: ProgressBarMaterial;
+
+@fragment
+fn fragment(
+    #import bevy_ui::ui_vertex_output
+) -> @location(0) vec4<f32> {
+    // The ends of the bar are rounded, with a radius of half its height
+    let position = uv * size;
+    let radius = 0.5 * size.y;
+    let x = clamp(position.x, radius, size.x - radius);
+    let distance = length(position - vec2<f32>(x, radius)) - radius;
+    let alpha = 1.0 - smoothstep(-0.5, 0.5, distance);
+
+    var color = material.background_color;
+    if (uv.x < material.progress) {
+        color = material.color;
+    }
+    return vec4<f32>(color.rgb, color.a * alpha);
+}
@@ -0,0 +1,86 @@
+use bevy_app::App;
+use bevy_asset::{AddAsset, AssetPlugin, Assets};
+use bevy_core::CorePlugin;
+use bevy_debug_draw::labels::{
+    DebugLabelGlyphs, DebugLabels, DebugLabelsConfig, DebugLabelsPlugin,
+};
+use bevy_math::Vec3;
+use bevy_render::{
+    camera::{camera_system, Camera, PerspectiveProjection},
+    color::Color,
+    texture::Image,
+};
+use bevy_sprite::TextureAtlas;
+use bevy_text::{Font, FontAtlasSet, TextPipeline, TextSettings};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_window::{Window, WindowCreated, WindowDescriptor, WindowId, WindowResized, Windows};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+criterion_group!(benches, labels_1000);
+criterion_main!(benches);
+
+const LABELS: usize = 1000;
+
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_plugin(CorePlugin)
+        .add_plugin(AssetPlugin)
+        .add_asset::<Image>()
+        .add_asset::<Font>()
+        .add_asset::<FontAtlasSet>()
+        .add_asset::<TextureAtlas>()
+        .init_resource::<TextPipeline>()
+        .init_resource::<TextSettings>()
+        .add_event::<WindowCreated>()
+        .add_event::<WindowResized>()
+        .init_resource::<Windows>()
+        .add_system(camera_system::<PerspectiveProjection>)
+        .add_plugin(DebugLabelsPlugin);
+
+    app.world.resource_mut::<Windows>().add(Window::new(
+        WindowId::primary(),
+        &WindowDescriptor::default(),
+        1280,
+        720,
+        1.0,
+        None,
+        None,
+    ));
+    let font =
+        Font::try_from_bytes(include_bytes!("../../../assets/fonts/FiraMono-Medium.ttf").to_vec())
+            .unwrap();
+    let font = app.world.resource_mut::<Assets<Font>>().add(font);
+    app.world.resource_mut::<DebugLabelsConfig>().font = font;
+    app.world.spawn((
+        Camera::default(),
+        PerspectiveProjection::default(),
+        GlobalTransform::from(Transform::from_xyz(0.0, 0.0, 10.0)),
+    ));
+    app
+}
+
+fn add_labels(app: &mut App) {
+    let mut labels = app.world.resource_mut::<DebugLabels>();
+    for i in 0..LABELS {
+        let position = Vec3::new(
+            (i % 40) as f32 * 0.2 - 4.0,
+            (i / 40) as f32 * 0.2 - 2.5,
+            0.0,
+        );
+        labels.label(position, format!("vertex {i}"), Color::WHITE);
+    }
+}
+
+fn labels_1000(c: &mut Criterion) {
+    let mut app = setup();
+    add_labels(&mut app);
+    app.update();
+    assert_eq!(app.world.resource::<DebugLabelGlyphs>().batch_count(), 1);
+
+    c.bench_function("debug_labels_1000", |b| {
+        b.iter(|| {
+            add_labels(&mut app);
+            app.update();
+        });
+    });
+}
@@ -2,7 +2,11 @@
 
 #![warn(missing_docs)]
 
-use std::ops::Deref;
+mod property;
+
+pub use property::*;
+
+use std::{ops::Deref, sync::Arc, time::Duration};
 
 use bevy_app::{App, CoreStage, Plugin};
 use bevy_asset::{AddAsset, Assets, Handle};
@@ -10,14 +14,15 @@ use bevy_core::Name;
 use bevy_ecs::{
     change_detection::DetectChanges,
     entity::Entity,
+    event::EventWriter,
     prelude::Component,
     reflect::ReflectComponent,
     schedule::IntoSystemDescriptor,
-    system::{Query, Res},
+    system::{Query, Res, ResMut},
 };
 use bevy_hierarchy::Children;
 use bevy_math::{Quat, Vec3};
-use bevy_reflect::{Reflect, TypeUuid};
+use bevy_reflect::{FromReflect, Reflect, TypeUuid};
 use bevy_time::Time;
 use bevy_transform::{prelude::Transform, TransformSystem};
 use bevy_utils::{tracing::warn, HashMap};
@@ -26,11 +31,13 @@ use bevy_utils::{tracing::warn, HashMap};
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes, VariableCurve,
+        AnimationClip, AnimationEvent, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes,
+        PropertyKeyframes, VariableCurve,
     };
 }
 
-/// List of keyframes for one of the attribute of a [`Transform`].
+/// List of keyframes for one of the attribute of a [`Transform`], or for a reflected property of
+/// any component.
 #[derive(Clone, Debug)]
 pub enum Keyframes {
     /// Keyframes for rotation.
@@ -39,9 +46,11 @@ pub enum Keyframes {
     Translation(Vec<Vec3>),
     /// Keyframes for scale.
     Scale(Vec<Vec3>),
+    /// Keyframes for a reflected property of a component.
+    Property(PropertyKeyframes),
 }
 
-/// Describes how an attribute of a [`Transform`] should be animated.
+/// Describes how an attribute of a [`Transform`] or a reflected property should be animated.
 ///
 /// `keyframe_timestamps` and `keyframes` should have the same length.
 #[derive(Clone, Debug)]
@@ -64,9 +73,22 @@ pub struct EntityPath {
 #[uuid = "d81b7179-0448-4eb0-89fe-c067222725bf"]
 pub struct AnimationClip {
     curves: HashMap<EntityPath, Vec<VariableCurve>>,
+    events: Vec<AnimationClipEvent>,
     duration: f32,
 }
 
+/// A named event at a time of an [`AnimationClip`], sent as an [`AnimationEvent`] when an
+/// [`AnimationPlayer`] playing the clip crosses that time.
+#[derive(Clone, Debug)]
+pub struct AnimationClipEvent {
+    /// Time of the event in the clip, represented in seconds
+    pub time: f32,
+    /// Name of the event
+    pub name: String,
+    /// Data sent with the event
+    pub payload: Option<Arc<dyn Reflect>>,
+}
+
 impl AnimationClip {
     #[inline]
     /// Hashmap of the [`VariableCurve`]s per [`EntityPath`].
@@ -88,23 +110,122 @@ impl AnimationClip {
             .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
         self.curves.entry(path).or_default().push(curve);
     }
+
+    /// List of the [`AnimationClipEvent`]s of the clip.
+    #[inline]
+    pub fn events(&self) -> &[AnimationClipEvent] {
+        &self.events
+    }
+
+    /// Add a named event at a time of the clip, represented in seconds.
+    pub fn add_event(&mut self, time: f32, name: impl Into<String>) {
+        self.push_event(AnimationClipEvent {
+            time,
+            name: name.into(),
+            payload: None,
+        });
+    }
+
+    /// Add a named event with a reflected payload at a time of the clip, represented in seconds.
+    pub fn add_event_with_payload(
+        &mut self,
+        time: f32,
+        name: impl Into<String>,
+        payload: impl Reflect,
+    ) {
+        self.push_event(AnimationClipEvent {
+            time,
+            name: name.into(),
+            payload: Some(Arc::new(payload)),
+        });
+    }
+
+    fn push_event(&mut self, event: AnimationClipEvent) {
+        // Update the duration of the animation so that it includes the event
+        self.duration = self.duration.max(event.time);
+        self.events.push(event);
+    }
+
+    /// Returns the events crossed when playing the clip from `start` to `end`, which are elapsed
+    /// times that may be outside of the clip when it repeats, in the order they are crossed.
+    ///
+    /// An event at the `start` time is crossed but not one at the `end` time, so that playing
+    /// the clip over consecutive ranges crosses each event once.
+    fn crossed_events(&self, start: f32, end: f32, repeat: bool) -> Vec<&AnimationClipEvent> {
+        let forward = end >= start;
+        let is_crossed = |time: f32| {
+            if forward {
+                start <= time && time < end
+            } else {
+                end < time && time <= start
+            }
+        };
+        let mut crossed = Vec::new();
+        for event in &self.events {
+            if repeat && self.duration > 0.0 {
+                // Look for the repetitions of the event within the range
+                let mut repetition = ((start - event.time) / self.duration).floor();
+                if forward && event.time + repetition * self.duration < start {
+                    repetition += 1.0;
+                }
+                loop {
+                    let time = event.time + repetition * self.duration;
+                    if !is_crossed(time) {
+                        break;
+                    }
+                    crossed.push((time, event));
+                    repetition += if forward { 1.0 } else { -1.0 };
+                }
+            } else if is_crossed(event.time) {
+                crossed.push((event.time, event));
+            }
+        }
+        // At the time a clip repeats, the events at its end are crossed before the ones at its
+        // start when playing forward
+        crossed.sort_by(|(a, a_event), (b, b_event)| {
+            let ordering = a
+                .partial_cmp(b)
+                .unwrap()
+                .then(b_event.time.partial_cmp(&a_event.time).unwrap());
+            if forward {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        crossed.into_iter().map(|(_, event)| event).collect()
+    }
 }
 
-/// Animation controls
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-pub struct AnimationPlayer {
-    paused: bool,
+/// Sent when an [`AnimationPlayer`] crosses the time of an [`AnimationClipEvent`] of its main
+/// animation while it plays it, including when the animation repeats or plays backward.
+///
+/// Seeking with [`AnimationPlayer::set_elapsed`] doesn't send the events between the previous
+/// and the new time.
+#[derive(Clone, Debug)]
+pub struct AnimationEvent {
+    /// The entity of the [`AnimationPlayer`]
+    pub entity: Entity,
+    /// A weak handle to the animation clip of the event
+    pub animation_clip: Handle<AnimationClip>,
+    /// Name of the event
+    pub name: String,
+    /// Data sent with the event
+    pub payload: Option<Arc<dyn Reflect>>,
+}
+
+/// An animation clip played by an [`AnimationPlayer`], with its playback state.
+#[derive(Reflect, FromReflect)]
+struct PlayingAnimation {
     repeat: bool,
     speed: f32,
     elapsed: f32,
     animation_clip: Handle<AnimationClip>,
 }
 
-impl Default for AnimationPlayer {
+impl Default for PlayingAnimation {
     fn default() -> Self {
         Self {
-            paused: false,
             repeat: false,
             speed: 1.0,
             elapsed: 0.0,
@@ -113,25 +234,124 @@ impl Default for AnimationPlayer {
     }
 }
 
+/// An animation clip blended with a weight over the main animation of an [`AnimationPlayer`].
+#[derive(Reflect, FromReflect)]
+struct BlendedAnimation {
+    weight: f32,
+    elapsed: f32,
+    animation_clip: Handle<AnimationClip>,
+}
+
+/// An animation fading out after an [`AnimationPlayer`] started playing another one with
+/// [`AnimationPlayer::play_with_transition`].
+#[derive(Reflect, FromReflect)]
+struct AnimationTransition {
+    current_weight: f32,
+    weight_decline_per_sec: f32,
+    animation: PlayingAnimation,
+}
+
+/// Animation controls
+///
+/// The player plays a main animation, over which other animations can be blended with a weight
+/// using [`AnimationPlayer::blend`]. Switching animations with
+/// [`AnimationPlayer::play_with_transition`] cross-fades from the previous animation to the new
+/// one.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct AnimationPlayer {
+    paused: bool,
+    animation: PlayingAnimation,
+    blended: Vec<BlendedAnimation>,
+    transitions: Vec<AnimationTransition>,
+}
+
 impl AnimationPlayer {
     /// Start playing an animation, resetting state of the player
     pub fn play(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
         *self = Self {
-            animation_clip: handle,
+            animation: PlayingAnimation {
+                animation_clip: handle,
+                ..Default::default()
+            },
             ..Default::default()
         };
         self
     }
 
+    /// Start playing an animation, cross-fading from the current one over the duration of the
+    /// transition
+    ///
+    /// The previous animation keeps playing while it fades out. Unlike [`AnimationPlayer::play`],
+    /// this keeps the blended animations and the paused state of the player.
+    pub fn play_with_transition(
+        &mut self,
+        handle: Handle<AnimationClip>,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        let animation = std::mem::replace(
+            &mut self.animation,
+            PlayingAnimation {
+                animation_clip: handle,
+                ..Default::default()
+            },
+        );
+        if !transition_duration.is_zero() {
+            self.transitions.push(AnimationTransition {
+                current_weight: 1.0,
+                weight_decline_per_sec: 1.0 / transition_duration.as_secs_f32(),
+                animation,
+            });
+        }
+        self
+    }
+
+    /// Blend an animation over the main one with a weight between `0.0` and `1.0`, or change its
+    /// weight if it is already blended
+    ///
+    /// Each blended animation is interpolated by its weight from the result of the main animation
+    /// and of the animations blended before it. Blended animations follow the speed and
+    /// repetition of the main animation.
+    pub fn blend(&mut self, handle: Handle<AnimationClip>, weight: f32) -> &mut Self {
+        match self
+            .blended
+            .iter_mut()
+            .find(|blended| blended.animation_clip == handle)
+        {
+            Some(blended) => blended.weight = weight,
+            None => self.blended.push(BlendedAnimation {
+                weight,
+                elapsed: 0.0,
+                animation_clip: handle,
+            }),
+        }
+        self
+    }
+
+    /// Weight of a blended animation, or `None` if it isn't blended
+    pub fn blend_weight(&self, handle: &Handle<AnimationClip>) -> Option<f32> {
+        self.blended
+            .iter()
+            .find(|blended| blended.animation_clip == *handle)
+            .map(|blended| blended.weight)
+    }
+
+    /// Stop blending an animation over the main one
+    pub fn stop_blending(&mut self, handle: &Handle<AnimationClip>) -> &mut Self {
+        self.blended
+            .retain(|blended| blended.animation_clip != *handle);
+        self
+    }
+
     /// Set the animation to repeat
     pub fn repeat(&mut self) -> &mut Self {
-        self.repeat = true;
+        self.animation.repeat = true;
         self
     }
 
     /// Stop the animation from repeating
     pub fn stop_repeating(&mut self) -> &mut Self {
-        self.repeat = false;
+        self.animation.repeat = false;
         self
     }
 
@@ -152,29 +372,45 @@ impl AnimationPlayer {
 
     /// Speed of the animation playback
     pub fn speed(&self) -> f32 {
-        self.speed
+        self.animation.speed
     }
 
     /// Set the speed of the animation playback
     pub fn set_speed(&mut self, speed: f32) -> &mut Self {
-        self.speed = speed;
+        self.animation.speed = speed;
         self
     }
 
     /// Time elapsed playing the animation
     pub fn elapsed(&self) -> f32 {
-        self.elapsed
+        self.animation.elapsed
     }
 
     /// Seek to a specific time in the animation
     pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
-        self.elapsed = elapsed;
+        self.animation.elapsed = elapsed;
         self
     }
+
+    /// Advance the animations of the player by the time elapsed since the last update, and fade
+    /// out the transitions
+    fn update(&mut self, delta: f32) {
+        let animation = &mut self.animation;
+        animation.elapsed += delta * animation.speed;
+        for blended in &mut self.blended {
+            blended.elapsed += delta * animation.speed;
+        }
+        self.transitions.retain_mut(|transition| {
+            transition.animation.elapsed += delta * transition.animation.speed;
+            transition.current_weight -= delta * transition.weight_decline_per_sec;
+            transition.current_weight > 0.0
+        });
+    }
 }
 
 /// System that will play all animations, using any entity with a [`AnimationPlayer`]
 /// and a [`Handle<AnimationClip>`] as an animation root
+#[allow(clippy::too_many_arguments)]
 pub fn animation_player(
     time: Res<Time>,
     animations: Res<Assets<AnimationClip>>,
@@ -182,105 +418,198 @@ pub fn animation_player(
     names: Query<&Name>,
     mut transforms: Query<&mut Transform>,
     children: Query<&Children>,
+    mut animation_events: EventWriter<AnimationEvent>,
+    mut animated_properties: ResMut<AnimatedProperties>,
 ) {
     for (entity, mut player) in &mut animation_players {
-        if let Some(animation_clip) = animations.get(&player.animation_clip) {
-            // Continue if paused unless the `AnimationPlayer` was changed
-            // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
-            if player.paused && !player.is_changed() {
-                continue;
+        // Continue if paused unless the `AnimationPlayer` was changed
+        // This allow the animation to still be updated if the player.elapsed field was manually updated in pause
+        if player.paused && !player.is_changed() {
+            continue;
+        }
+        if !player.paused {
+            let previous_elapsed = player.elapsed();
+            player.update(time.delta_seconds());
+
+            let animation = &player.animation;
+            if let Some(animation_clip) = animations.get(&animation.animation_clip) {
+                for event in animation_clip.crossed_events(
+                    previous_elapsed,
+                    animation.elapsed,
+                    animation.repeat,
+                ) {
+                    animation_events.send(AnimationEvent {
+                        entity,
+                        animation_clip: animation.animation_clip.clone_weak(),
+                        name: event.name.clone(),
+                        payload: event.payload.clone(),
+                    });
+                }
             }
-            if !player.paused {
-                player.elapsed += time.delta_seconds() * player.speed;
+        }
+
+        let mut apply = |animation_clip: &Handle<AnimationClip>, elapsed, repeat, weight| {
+            if let Some(animation_clip) = animations.get(animation_clip) {
+                apply_animation(
+                    animation_clip,
+                    elapsed,
+                    repeat,
+                    weight,
+                    entity,
+                    &names,
+                    &mut transforms,
+                    &children,
+                    &mut animated_properties,
+                );
             }
-            let mut elapsed = player.elapsed;
-            if player.repeat {
-                elapsed %= animation_clip.duration;
+        };
+        let animation = &player.animation;
+        apply(
+            &animation.animation_clip,
+            animation.elapsed,
+            animation.repeat,
+            1.0,
+        );
+        // Blended animations follow the repetition of the main animation
+        for blended in &player.blended {
+            apply(
+                &blended.animation_clip,
+                blended.elapsed,
+                animation.repeat,
+                blended.weight,
+            );
+        }
+        // The animations fading out are applied last, so that they are gradually replaced by
+        // the result of the current ones
+        for transition in &player.transitions {
+            let animation = &transition.animation;
+            apply(
+                &animation.animation_clip,
+                animation.elapsed,
+                animation.repeat,
+                transition.current_weight,
+            );
+        }
+    }
+}
+
+/// Applies an animation clip at the elapsed time to the entities under the root, interpolating
+/// their [`Transform`] from its current value by the weight, and queues the values of its
+/// reflected properties for the [`animate_properties`] system
+#[allow(clippy::too_many_arguments)]
+fn apply_animation(
+    animation_clip: &AnimationClip,
+    mut elapsed: f32,
+    repeat: bool,
+    weight: f32,
+    root: Entity,
+    names: &Query<&Name>,
+    transforms: &mut Query<&mut Transform>,
+    children: &Query<&Children>,
+    animated_properties: &mut AnimatedProperties,
+) {
+    if repeat {
+        elapsed %= animation_clip.duration;
+    }
+    if elapsed < 0.0 {
+        elapsed += animation_clip.duration;
+    }
+    'entity: for (path, curves) in &animation_clip.curves {
+        // PERF: finding the target entity can be optimised
+        let mut current_entity = root;
+        // Ignore the first name, it is the root node which we already have
+        for part in path.parts.iter().skip(1) {
+            let mut found = false;
+            if let Ok(children) = children.get(current_entity) {
+                for child in children.deref() {
+                    if let Ok(name) = names.get(*child) {
+                        if name == part {
+                            // Found a children with the right name, continue to the next part
+                            current_entity = *child;
+                            found = true;
+                            break;
+                        }
+                    }
+                }
             }
-            if elapsed < 0.0 {
-                elapsed += animation_clip.duration;
+            if !found {
+                warn!("Entity not found for path {:?} on part {:?}", path, part);
+                continue 'entity;
             }
-            'entity: for (path, curves) in &animation_clip.curves {
-                // PERF: finding the target entity can be optimised
-                let mut current_entity = entity;
-                // Ignore the first name, it is the root node which we already have
-                for part in path.parts.iter().skip(1) {
-                    let mut found = false;
-                    if let Ok(children) = children.get(current_entity) {
-                        for child in children.deref() {
-                            if let Ok(name) = names.get(*child) {
-                                if name == part {
-                                    // Found a children with the right name, continue to the next part
-                                    current_entity = *child;
-                                    found = true;
-                                    break;
-                                }
-                            }
-                        }
+        }
+        let mut transform = transforms.get_mut(current_entity).ok();
+        for curve in curves {
+            // Some curves have only one keyframe used to set a transform
+            if curve.keyframe_timestamps.len() == 1 {
+                match (&curve.keyframes, &mut transform) {
+                    (Keyframes::Rotation(keyframes), Some(transform)) => {
+                        transform.rotation = transform.rotation.slerp(keyframes[0], weight);
                     }
-                    if !found {
-                        warn!("Entity not found for path {:?} on part {:?}", path, part);
-                        continue 'entity;
+                    (Keyframes::Translation(keyframes), Some(transform)) => {
+                        transform.translation = transform.translation.lerp(keyframes[0], weight);
                     }
+                    (Keyframes::Scale(keyframes), Some(transform)) => {
+                        transform.scale = transform.scale.lerp(keyframes[0], weight);
+                    }
+                    (Keyframes::Property(keyframes), _) => {
+                        keyframes.sample(0, 0.0, weight, current_entity, animated_properties);
+                    }
+                    _ => {}
                 }
-                if let Ok(mut transform) = transforms.get_mut(current_entity) {
-                    for curve in curves {
-                        // Some curves have only one keyframe used to set a transform
-                        if curve.keyframe_timestamps.len() == 1 {
-                            match &curve.keyframes {
-                                Keyframes::Rotation(keyframes) => transform.rotation = keyframes[0],
-                                Keyframes::Translation(keyframes) => {
-                                    transform.translation = keyframes[0];
-                                }
-                                Keyframes::Scale(keyframes) => transform.scale = keyframes[0],
-                            }
-                            continue;
-                        }
+                continue;
+            }
 
-                        // Find the current keyframe
-                        // PERF: finding the current keyframe can be optimised
-                        let step_start = match curve
-                            .keyframe_timestamps
-                            .binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap())
-                        {
-                            Ok(n) if n >= curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                            Ok(i) => i,
-                            Err(0) => continue, // this curve isn't started yet
-                            Err(n) if n > curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                            Err(i) => i - 1,
-                        };
-                        let ts_start = curve.keyframe_timestamps[step_start];
-                        let ts_end = curve.keyframe_timestamps[step_start + 1];
-                        let lerp = (elapsed - ts_start) / (ts_end - ts_start);
-
-                        // Apply the keyframe
-                        match &curve.keyframes {
-                            Keyframes::Rotation(keyframes) => {
-                                let rot_start = keyframes[step_start];
-                                let mut rot_end = keyframes[step_start + 1];
-                                // Choose the smallest angle for the rotation
-                                if rot_end.dot(rot_start) < 0.0 {
-                                    rot_end = -rot_end;
-                                }
-                                // Rotations are using a spherical linear interpolation
-                                transform.rotation =
-                                    rot_start.normalize().slerp(rot_end.normalize(), lerp);
-                            }
-                            Keyframes::Translation(keyframes) => {
-                                let translation_start = keyframes[step_start];
-                                let translation_end = keyframes[step_start + 1];
-                                let result = translation_start.lerp(translation_end, lerp);
-                                transform.translation = result;
-                            }
-                            Keyframes::Scale(keyframes) => {
-                                let scale_start = keyframes[step_start];
-                                let scale_end = keyframes[step_start + 1];
-                                let result = scale_start.lerp(scale_end, lerp);
-                                transform.scale = result;
-                            }
-                        }
+            // Find the current keyframe
+            // PERF: finding the current keyframe can be optimised
+            let step_start = match curve
+                .keyframe_timestamps
+                .binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap())
+            {
+                Ok(n) if n >= curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
+                Ok(i) => i,
+                Err(0) => continue, // this curve isn't started yet
+                Err(n) if n > curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
+                Err(i) => i - 1,
+            };
+            let ts_start = curve.keyframe_timestamps[step_start];
+            let ts_end = curve.keyframe_timestamps[step_start + 1];
+            let lerp = (elapsed - ts_start) / (ts_end - ts_start);
+
+            // Apply the keyframe
+            match (&curve.keyframes, &mut transform) {
+                (Keyframes::Rotation(keyframes), Some(transform)) => {
+                    let rot_start = keyframes[step_start];
+                    let mut rot_end = keyframes[step_start + 1];
+                    // Choose the smallest angle for the rotation
+                    if rot_end.dot(rot_start) < 0.0 {
+                        rot_end = -rot_end;
                     }
+                    // Rotations are using a spherical linear interpolation
+                    let rot = rot_start.normalize().slerp(rot_end.normalize(), lerp);
+                    transform.rotation = transform.rotation.slerp(rot, weight);
+                }
+                (Keyframes::Translation(keyframes), Some(transform)) => {
+                    let translation_start = keyframes[step_start];
+                    let translation_end = keyframes[step_start + 1];
+                    let result = translation_start.lerp(translation_end, lerp);
+                    transform.translation = transform.translation.lerp(result, weight);
                 }
+                (Keyframes::Scale(keyframes), Some(transform)) => {
+                    let scale_start = keyframes[step_start];
+                    let scale_end = keyframes[step_start + 1];
+                    let result = scale_start.lerp(scale_end, lerp);
+                    transform.scale = transform.scale.lerp(result, weight);
+                }
+                (Keyframes::Property(keyframes), _) => {
+                    keyframes.sample(
+                        step_start,
+                        lerp,
+                        weight,
+                        current_entity,
+                        animated_properties,
+                    );
+                }
+                _ => {}
             }
         }
     }
@@ -293,10 +622,189 @@ pub struct AnimationPlugin {}
 impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<AnimationClip>()
+            .add_event::<AnimationEvent>()
+            .init_resource::<AnimatedProperties>()
             .register_type::<AnimationPlayer>()
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 animation_player.before(TransformSystem::TransformPropagate),
+            )
+            // Exclusive systems can't be ordered with the parallel ones, reflected properties are
+            // applied at the end of the stage
+            .add_system_to_stage(CoreStage::PostUpdate, animate_properties.at_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::AssetPlugin;
+    use bevy_core::CorePlugin;
+    use bevy_ecs::world::Mut;
+
+    use super::*;
+
+    fn crossed_event_names(clip: &AnimationClip, start: f32, end: f32, repeat: bool) -> Vec<&str> {
+        clip.crossed_events(start, end, repeat)
+            .into_iter()
+            .map(|event| event.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn crossed_events() {
+        let mut clip = AnimationClip::default();
+        clip.add_event(0.0, "start");
+        clip.add_event(0.5, "step");
+        clip.add_event(2.0, "end");
+
+        assert_eq!(crossed_event_names(&clip, 0.0, 0.1, false), ["start"]);
+        assert!(crossed_event_names(&clip, 0.1, 0.5, false).is_empty());
+        assert_eq!(crossed_event_names(&clip, 0.5, 2.5, false), ["step", "end"]);
+        assert!(crossed_event_names(&clip, 2.5, 4.5, false).is_empty());
+
+        // Looping crosses the events of the next repetitions
+        assert_eq!(
+            crossed_event_names(&clip, 1.9, 4.6, true),
+            ["end", "start", "step", "end", "start", "step"]
+        );
+
+        // Playing backward crosses the events in reverse order
+        assert_eq!(
+            crossed_event_names(&clip, 2.5, 0.4, true),
+            ["step", "start", "end", "step"]
+        );
+        assert_eq!(crossed_event_names(&clip, 2.5, 0.4, false), ["end", "step"]);
+    }
+
+    #[test]
+    fn update_advances_blends_and_transitions() {
+        let mut player = AnimationPlayer::default();
+        player
+            .blend(Handle::default(), 0.25)
+            .set_speed(2.0)
+            .update(0.5);
+        assert_eq!(player.elapsed(), 1.0);
+        assert_eq!(player.blended[0].elapsed, 1.0);
+
+        // Blending the same animation again only changes its weight
+        let handle = Handle::default();
+        player.blend(handle.clone(), 0.75);
+        assert_eq!(player.blended.len(), 1);
+        assert_eq!(player.blend_weight(&handle), Some(0.75));
+        player.stop_blending(&handle);
+        assert_eq!(player.blend_weight(&handle), None);
+
+        // The previous animation keeps playing at its own speed while it fades out
+        player.play_with_transition(Handle::default(), Duration::from_secs(2));
+        assert_eq!(player.elapsed(), 0.0);
+        assert_eq!(player.speed(), 1.0);
+        player.update(0.5);
+        assert_eq!(player.elapsed(), 0.5);
+        let transition = &player.transitions[0];
+        assert_eq!(transition.animation.elapsed, 2.0);
+        assert_eq!(transition.current_weight, 0.75);
+
+        // The transition completes once its weight reaches zero
+        player.update(1.0);
+        assert_eq!(player.transitions[0].current_weight, 0.25);
+        player.update(0.5);
+        assert!(player.transitions.is_empty());
+
+        // Transitions without a duration switch animations immediately
+        player.play_with_transition(Handle::default(), Duration::ZERO);
+        assert!(player.transitions.is_empty());
+    }
+
+    struct TestApp {
+        app: App,
+        player: Entity,
+    }
+
+    impl TestApp {
+        fn new() -> Self {
+            let mut app = App::new();
+            let mut time = Time::default();
+            time.update();
+            app.add_plugin(CorePlugin)
+                .add_plugin(AssetPlugin)
+                .insert_resource(time)
+                .add_plugin(AnimationPlugin::default());
+            let player = app
+                .world
+                .spawn((Transform::default(), AnimationPlayer::default()))
+                .id();
+            Self { app, player }
+        }
+
+        /// A clip moving the root of the player to the translation.
+        fn clip(&mut self, translation: Vec3) -> Handle<AnimationClip> {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root")],
+                },
+                VariableCurve {
+                    keyframe_timestamps: vec![0.0],
+                    keyframes: Keyframes::Translation(vec![translation]),
+                },
             );
+            self.app
+                .world
+                .resource_mut::<Assets<AnimationClip>>()
+                .add(clip)
+        }
+
+        fn player(&mut self) -> Mut<'_, AnimationPlayer> {
+            self.app
+                .world
+                .get_mut::<AnimationPlayer>(self.player)
+                .unwrap()
+        }
+
+        /// Updates the app after `seconds`, returning the translation of the root.
+        fn update(&mut self, seconds: f32) -> Vec3 {
+            let mut time = self.app.world.resource_mut::<Time>();
+            let last_update = time.last_update().unwrap();
+            time.update_with_instant(last_update + Duration::from_secs_f32(seconds));
+            self.app.update();
+            self.app
+                .world
+                .get::<Transform>(self.player)
+                .unwrap()
+                .translation
+        }
+    }
+
+    #[test]
+    fn blended_animations_are_weighted() {
+        let mut app = TestApp::new();
+        let main = app.clip(Vec3::new(4.0, 0.0, 0.0));
+        let up = app.clip(Vec3::new(0.0, 4.0, 0.0));
+        let back = app.clip(Vec3::new(0.0, 0.0, 4.0));
+        app.player().play(main).blend(up.clone(), 0.25);
+        assert_eq!(app.update(0.0), Vec3::new(3.0, 1.0, 0.0));
+
+        // Each blended animation is interpolated from the result of the previous ones
+        app.player().blend(up.clone(), 0.5).blend(back, 0.5);
+        assert_eq!(app.update(0.0), Vec3::new(1.0, 1.0, 2.0));
+
+        app.player().stop_blending(&up);
+        assert_eq!(app.update(0.0), Vec3::new(2.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn transitions_cross_fade_until_completed() {
+        let mut app = TestApp::new();
+        let previous = app.clip(Vec3::new(4.0, 0.0, 0.0));
+        let next = app.clip(Vec3::ZERO);
+        app.player().play(previous);
+        assert_eq!(app.update(0.0), Vec3::new(4.0, 0.0, 0.0));
+
+        app.player()
+            .play_with_transition(next, Duration::from_secs(1));
+        assert_eq!(app.update(0.25), Vec3::new(3.0, 0.0, 0.0));
+        assert_eq!(app.update(0.5), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(app.update(0.25), Vec3::ZERO);
+        assert!(app.player().transitions.is_empty());
     }
 }
@@ -0,0 +1,279 @@
+use std::{any::TypeId, sync::Arc};
+
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    reflect::ReflectComponent,
+    system::Resource,
+    world::{Mut, World},
+};
+use bevy_math::{DQuat, Quat};
+use bevy_reflect::{GetPath, Reflect, ReflectMut, ReflectRef};
+use bevy_utils::tracing::warn;
+
+/// Keyframes for a property of a component, reached with a reflection path such as
+/// `"intensity"` on a `PointLight` or `"color"` on a `Sprite`.
+///
+/// The values between two keyframes are interpolated field by field: floats are interpolated
+/// linearly, quaternions spherically, and the other values keep the one of the previous keyframe
+/// until the next one is reached. The component must be registered in the
+/// [`AppTypeRegistry`] with its `ReflectComponent` type data.
+#[derive(Clone, Debug)]
+pub struct PropertyKeyframes {
+    component: TypeId,
+    component_name: &'static str,
+    path: String,
+    keyframes: Vec<Arc<dyn Reflect>>,
+}
+
+impl PropertyKeyframes {
+    /// Creates keyframes for the property at the reflection path of the component `C`.
+    pub fn new<C: Component + Reflect, T: Reflect>(
+        path: impl Into<String>,
+        keyframes: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Self {
+            component: TypeId::of::<C>(),
+            component_name: std::any::type_name::<C>(),
+            path: path.into(),
+            keyframes: keyframes
+                .into_iter()
+                .map(|keyframe| Arc::new(keyframe) as Arc<dyn Reflect>)
+                .collect(),
+        }
+    }
+
+    /// Reflection path of the animated property in the component.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// List of the keyframes.
+    #[inline]
+    pub fn keyframes(&self) -> &[Arc<dyn Reflect>] {
+        &self.keyframes
+    }
+
+    /// Queues the value of the property between two keyframes to be applied to an entity.
+    pub(crate) fn sample(
+        &self,
+        step_start: usize,
+        lerp: f32,
+        weight: f32,
+        entity: Entity,
+        animated_properties: &mut AnimatedProperties,
+    ) {
+        let value = match self.keyframes.get(step_start + 1) {
+            Some(end) => interpolate(self.keyframes[step_start].as_ref(), end.as_ref(), lerp),
+            None => self.keyframes[step_start].clone_value(),
+        };
+        animated_properties.0.push(AnimatedProperty {
+            entity,
+            component: self.component,
+            component_name: self.component_name,
+            path: self.path.clone(),
+            value,
+            weight,
+        });
+    }
+}
+
+struct AnimatedProperty {
+    entity: Entity,
+    component: TypeId,
+    component_name: &'static str,
+    path: String,
+    value: Box<dyn Reflect>,
+    weight: f32,
+}
+
+/// The values of the [`PropertyKeyframes`] sampled by the
+/// [`animation_player`](crate::animation_player) system, in the order they are applied by the
+/// [`animate_properties`] system.
+#[derive(Resource, Default)]
+pub struct AnimatedProperties(Vec<AnimatedProperty>);
+
+/// System that applies the values of the [`PropertyKeyframes`] sampled by the
+/// [`animation_player`](crate::animation_player) system, interpolating each property from its
+/// current value by the weight of its animation
+pub fn animate_properties(world: &mut World) {
+    world.resource_scope(|world, mut animated_properties: Mut<AnimatedProperties>| {
+        if animated_properties.0.is_empty() {
+            return;
+        }
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        for property in animated_properties.0.drain(..) {
+            let reflect_component = match type_registry
+                .get(property.component)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            {
+                Some(reflect_component) => reflect_component,
+                None => {
+                    warn!(
+                        "Component {} isn't registered with ReflectComponent, its properties can't be animated",
+                        property.component_name
+                    );
+                    continue;
+                }
+            };
+            let mut component = match reflect_component.reflect_mut(world, property.entity) {
+                Some(component) => component,
+                None => continue,
+            };
+            match component.path_mut(&property.path) {
+                Ok(current) if property.weight >= 1.0 => current.apply(property.value.as_ref()),
+                Ok(current) => {
+                    let value = interpolate(current, property.value.as_ref(), property.weight);
+                    current.apply(value.as_ref());
+                }
+                Err(err) => warn!(
+                    "Property {:?} of {} can't be animated: {}",
+                    property.path, property.component_name, err
+                ),
+            }
+        }
+    });
+}
+
+/// Interpolates between two reflected values of the same type.
+fn interpolate(start: &dyn Reflect, end: &dyn Reflect, lerp: f32) -> Box<dyn Reflect> {
+    let mut value = start.clone_value();
+    interpolate_in_place(value.as_mut(), end, lerp);
+    value
+}
+
+fn interpolate_in_place(value: &mut dyn Reflect, end: &dyn Reflect, lerp: f32) {
+    if let (Some(value), Some(end)) = (value.downcast_mut::<f32>(), end.downcast_ref::<f32>()) {
+        *value += (end - *value) * lerp;
+        return;
+    }
+    if let (Some(value), Some(end)) = (value.downcast_mut::<f64>(), end.downcast_ref::<f64>()) {
+        *value += (end - *value) * lerp as f64;
+        return;
+    }
+    if let (Some(value), Some(end)) = (value.downcast_mut::<Quat>(), end.downcast_ref::<Quat>()) {
+        *value = value.slerp(*end, lerp);
+        return;
+    }
+    if let (Some(value), Some(end)) = (value.downcast_mut::<DQuat>(), end.downcast_ref::<DQuat>()) {
+        *value = value.slerp(*end, lerp as f64);
+        return;
+    }
+
+    let interpolated = match (value.reflect_mut(), end.reflect_ref()) {
+        (ReflectMut::Struct(value), ReflectRef::Struct(end)) => {
+            for (index, end) in end.iter_fields().enumerate() {
+                if let Some(value) = value.field_at_mut(index) {
+                    interpolate_in_place(value, end, lerp);
+                }
+            }
+            true
+        }
+        (ReflectMut::TupleStruct(value), ReflectRef::TupleStruct(end)) => {
+            for (index, end) in end.iter_fields().enumerate() {
+                if let Some(value) = value.field_mut(index) {
+                    interpolate_in_place(value, end, lerp);
+                }
+            }
+            true
+        }
+        (ReflectMut::Tuple(value), ReflectRef::Tuple(end)) => {
+            for (index, end) in end.iter_fields().enumerate() {
+                if let Some(value) = value.field_mut(index) {
+                    interpolate_in_place(value, end, lerp);
+                }
+            }
+            true
+        }
+        (ReflectMut::Array(value), ReflectRef::Array(end)) if value.len() == end.len() => {
+            for (index, end) in end.iter().enumerate() {
+                if let Some(value) = value.get_mut(index) {
+                    interpolate_in_place(value, end, lerp);
+                }
+            }
+            true
+        }
+        (ReflectMut::List(value), ReflectRef::List(end)) if value.len() == end.len() => {
+            for (index, end) in end.iter().enumerate() {
+                if let Some(value) = value.get_mut(index) {
+                    interpolate_in_place(value, end, lerp);
+                }
+            }
+            true
+        }
+        (ReflectMut::Enum(value), ReflectRef::Enum(end))
+            if value.variant_name() == end.variant_name() =>
+        {
+            for (index, end) in end.iter_fields().enumerate() {
+                if let Some(value) = value.field_at_mut(index) {
+                    interpolate_in_place(value, end.value(), lerp);
+                }
+            }
+            true
+        }
+        _ => false,
+    };
+    // Values that can't be interpolated keep the start value until the end is reached
+    if !interpolated && lerp >= 1.0 {
+        value.apply(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpolate;
+    use bevy_math::{Quat, Vec3};
+    use bevy_reflect::{FromReflect, Reflect};
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    enum Shade {
+        Gray(f32),
+        Named(String),
+    }
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    struct Light {
+        intensity: f32,
+        offset: Vec3,
+        rotation: Quat,
+        shade: Shade,
+        enabled: bool,
+    }
+
+    #[test]
+    fn interpolate_reflected_values() {
+        let start = Light {
+            intensity: 100.0,
+            offset: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            shade: Shade::Gray(0.0),
+            enabled: false,
+        };
+        let end = Light {
+            intensity: 200.0,
+            offset: Vec3::new(2.0, 4.0, 0.0),
+            rotation: Quat::from_rotation_y(1.0),
+            shade: Shade::Gray(1.0),
+            enabled: true,
+        };
+
+        let value = Light::from_reflect(interpolate(&start, &end, 0.25).as_ref()).unwrap();
+        assert_eq!(value.intensity, 125.0);
+        assert_eq!(value.offset, Vec3::new(0.5, 1.0, 0.0));
+        assert!(value
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(0.25), 1e-6));
+        assert_eq!(value.shade, Shade::Gray(0.25));
+        // Values that can't be interpolated are stepped
+        assert!(!value.enabled);
+        let value = Light::from_reflect(interpolate(&start, &end, 1.0).as_ref()).unwrap();
+        assert!(value.enabled);
+
+        // Enum variants are stepped too
+        let value = interpolate(&Shade::Gray(0.0), &Shade::Named("red".to_string()), 0.5);
+        assert_eq!(Shade::from_reflect(value.as_ref()), Some(Shade::Gray(0.0)));
+    }
+}
@@ -1,12 +1,15 @@
-use crate::{CoreStage, Plugin, PluginGroup, PluginGroupBuilder, StartupSchedule, StartupStage};
+use crate::{
+    CoreStage, Plugin, PluginGroup, PluginGroupBuilder, PluginLabel, Plugins, StartupSchedule,
+    StartupStage,
+};
 pub use bevy_derive::AppLabel;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
-    event::{Event, Events},
+    event::{Event, EventCleanup, Events},
     prelude::FromWorld,
     schedule::{
-        IntoSystemDescriptor, Schedule, ShouldRun, Stage, StageLabel, State, StateData, SystemSet,
-        SystemStage,
+        DisabledSystems, IntoSystemDescriptor, Schedule, ShouldRun, Stage, StageLabel, State,
+        StateData, SystemSet, SystemStage,
     },
     system::Resource,
     world::World,
@@ -68,6 +71,8 @@ pub struct App {
     /// A container of [`Stage`]s set to be run in a linear order.
     pub schedule: Schedule,
     sub_apps: HashMap<AppLabelId, SubApp>,
+    /// The labels of the plugins being built, see [`PluginLabel`].
+    plugin_labels: Vec<PluginLabel>,
 }
 
 impl Debug for App {
@@ -102,7 +107,8 @@ impl Default for App {
         #[cfg(feature = "bevy_reflect")]
         app.init_resource::<AppTypeRegistry>();
 
-        app.add_default_stages()
+        app.init_resource::<DisabledSystems>()
+            .add_default_stages()
             .add_event::<AppExit>()
             .add_system_to_stage(CoreStage::Last, World::clear_trackers);
 
@@ -131,6 +137,7 @@ impl App {
             schedule: Default::default(),
             runner: Box::new(run_once),
             sub_apps: HashMap::default(),
+            plugin_labels: Vec::new(),
         }
     }
 
@@ -416,6 +423,10 @@ impl App {
             stage_label.type_id() != TypeId::of::<StartupStage>(),
             "use `add_startup_system_to_stage` instead of `add_system_to_stage` to add a system to a StartupStage"
         );
+        let mut system = system.into_descriptor();
+        for label in &self.plugin_labels {
+            system = system.label(*label);
+        }
         self.schedule.add_system_to_stage(stage_label, system);
         self
     }
@@ -451,6 +462,10 @@ impl App {
             stage_label.type_id() != TypeId::of::<StartupStage>(),
             "use `add_startup_system_set_to_stage` instead of `add_system_set_to_stage` to add system sets to a StartupStage"
         );
+        let mut system_set = system_set;
+        for label in &self.plugin_labels {
+            system_set = system_set.label(*label);
+        }
         self.schedule
             .add_system_set_to_stage(stage_label, system_set);
         self
@@ -590,8 +605,12 @@ impl App {
     where
         T: StateData,
     {
-        self.insert_resource(State::new(initial))
-            .add_system_set_to_stage(stage, State::<T>::get_driver())
+        // The driver isn't labeled with the plugin adding the state, to keep it running while
+        // the plugin is disabled
+        self.insert_resource(State::new(initial));
+        self.schedule
+            .add_system_set_to_stage(stage, State::<T>::get_driver());
+        self
     }
 
     /// Adds utility stages to the [`Schedule`], giving it a standardized structure.
@@ -668,12 +687,42 @@ impl App {
         T: Event,
     {
         if !self.world.contains_resource::<Events<T>>() {
-            self.init_resource::<Events<T>>()
+            // The update system isn't labeled with the plugin adding the events, to keep them
+            // from accumulating while the plugin is disabled
+            self.init_resource::<Events<T>>();
+            self.schedule
                 .add_system_to_stage(CoreStage::First, Events::<T>::update_system);
         }
         self
     }
 
+    /// Setup the application to manage events of type `T`, dropped following the given
+    /// [`EventCleanup`] strategy.
+    ///
+    /// This is the same as [`add_event`](Self::add_event), and sets the strategy of the events
+    /// if they were already added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event::EventCleanup, prelude::*};
+    /// #
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// // The events are kept until every `EventReader<MyEvent>` has read them
+    /// app.add_event_with_cleanup::<MyEvent>(EventCleanup::UntilConsumed);
+    /// ```
+    pub fn add_event_with_cleanup<T>(&mut self, cleanup: EventCleanup) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        self.world.resource_mut::<Events<T>>().set_cleanup(cleanup);
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -826,7 +875,80 @@ impl App {
         T: Plugin,
     {
         debug!("added plugin: {}", plugin.name());
+        self.build_plugin(&plugin);
+        self
+    }
+
+    /// Registers the plugin in the [`Plugins`] and builds it, labeling the systems it adds with
+    /// its [`PluginLabel`].
+    pub(crate) fn build_plugin(&mut self, plugin: &dyn Plugin) {
+        let label = self
+            .world
+            .get_resource_or_insert_with(Plugins::default)
+            .register(plugin);
+        self.plugin_labels.push(label);
         plugin.build(self);
+        self.plugin_labels.pop();
+    }
+
+    /// Returns `true` if a [`Plugin`] of type `T` was added, see [`Plugins`].
+    pub fn is_plugin_added<T: Plugin>(&self) -> bool {
+        self.world
+            .get_resource::<Plugins>()
+            .map_or(false, Plugins::contains::<T>)
+    }
+
+    /// Enables or disables the systems added by the [`Plugin`] of type `T`, see [`PluginLabel`].
+    ///
+    /// The systems can also be disabled at runtime, with the [`DisabledSystems`] resource and the
+    /// [`PluginLabel`] registered in the [`Plugins`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::{prelude::*, Plugins};
+    /// # use bevy_ecs::{prelude::*, schedule::DisabledSystems};
+    /// #
+    /// struct DevToolsPlugin;
+    ///
+    /// impl Plugin for DevToolsPlugin {
+    ///     fn build(&self, app: &mut App) {
+    ///         app.add_system(draw_inspector);
+    ///     }
+    /// }
+    ///
+    /// # fn draw_inspector() {}
+    /// #
+    /// fn toggle_dev_tools(plugins: Res<Plugins>, mut disabled: ResMut<DisabledSystems>) {
+    ///     let label = plugins.get::<DevToolsPlugin>().unwrap().label();
+    ///     let enabled = disabled.is_disabled(label);
+    ///     disabled.set_enabled(label, enabled);
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugin(DevToolsPlugin)
+    ///     .set_plugin_enabled::<DevToolsPlugin>(false)
+    ///     .add_system(toggle_dev_tools);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if no plugin of type `T` was added.
+    pub fn set_plugin_enabled<T: Plugin>(&mut self, enabled: bool) -> &mut Self {
+        let label = match self
+            .world
+            .get_resource::<Plugins>()
+            .and_then(Plugins::get::<T>)
+        {
+            Some(info) => info.label(),
+            None => panic!(
+                "cannot enable or disable plugin {}, it was not added",
+                std::any::type_name::<T>()
+            ),
+        };
+        self.world
+            .get_resource_or_insert_with(DisabledSystems::default)
+            .set_enabled(label, enabled);
         self
     }
 
@@ -6,6 +6,8 @@ mod app;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
+#[cfg(feature = "bevy_reflect")]
+mod settings;
 
 #[cfg(feature = "bevy_ci_testing")]
 mod ci_testing;
@@ -15,6 +17,8 @@ pub use bevy_derive::DynamicPlugin;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
+#[cfg(feature = "bevy_reflect")]
+pub use settings::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
@@ -1,5 +1,7 @@
 use crate::App;
-use std::any::Any;
+use bevy_ecs::schedule::SystemLabel;
+use bevy_ecs::system::Resource;
+use std::any::{Any, TypeId};
 
 /// A collection of Bevy app logic and configuration.
 ///
@@ -19,3 +21,211 @@ pub trait Plugin: Any + Send + Sync {
 ///
 /// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
+
+/// The [`SystemLabel`] of the systems added to the stages of an [`App`] by a [`Plugin`], including
+/// the plugins it adds itself.
+///
+/// The systems of a plugin can be skipped at runtime by disabling its label in the
+/// [`DisabledSystems`](bevy_ecs::schedule::DisabledSystems) resource, see
+/// [`App::set_plugin_enabled`]. The systems updating events and states are never labeled, so that
+/// they keep running while the plugin is disabled.
+///
+/// The label is derived from the [`Plugin::name`], use [`Plugins::get`] to get the label of a
+/// plugin type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PluginLabel(&'static str);
+
+impl PluginLabel {
+    /// The label of the plugin with the [`Plugin::name`], leaking the name.
+    fn from_name(name: &str) -> Self {
+        Self(Box::leak(name.to_string().into_boxed_str()))
+    }
+}
+
+impl SystemLabel for PluginLabel {
+    fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+/// A [`Plugin`] registered in the [`Plugins`] of an [`App`].
+#[derive(Clone, Debug)]
+pub struct PluginInfo {
+    type_id: TypeId,
+    label: PluginLabel,
+}
+
+impl PluginInfo {
+    /// The [`Plugin::name`] of the plugin.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.label.0
+    }
+
+    /// The label of the systems added by the plugin.
+    #[inline]
+    pub fn label(&self) -> PluginLabel {
+        self.label
+    }
+
+    /// Returns `true` if the plugin is of type `T`.
+    #[inline]
+    pub fn is<T: Plugin>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+}
+
+/// The [`Plugin`]s built by an [`App`], in the order they were added.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Plugins {
+    plugins: Vec<PluginInfo>,
+}
+
+impl Plugins {
+    /// Registers a plugin, returning the label of its systems.
+    pub(crate) fn register(&mut self, plugin: &dyn Plugin) -> PluginLabel {
+        let type_id = plugin.type_id();
+        let label = match self
+            .plugins
+            .iter()
+            .find(|info| info.name() == plugin.name())
+        {
+            Some(info) => info.label,
+            None => PluginLabel::from_name(plugin.name()),
+        };
+        self.plugins.push(PluginInfo { type_id, label });
+        label
+    }
+
+    /// Returns `true` if a plugin of type `T` was added.
+    pub fn contains<T: Plugin>(&self) -> bool {
+        self.plugins.iter().any(PluginInfo::is::<T>)
+    }
+
+    /// Returns the plugin of type `T`, if it was added.
+    pub fn get<T: Plugin>(&self) -> Option<&PluginInfo> {
+        self.plugins.iter().find(|info| info.is::<T>())
+    }
+
+    /// Returns the plugin with the [`Plugin::name`], if it was added.
+    pub fn get_with_name(&self, name: &str) -> Option<&PluginInfo> {
+        self.plugins.iter().find(|info| info.name() == name)
+    }
+
+    /// Iterates over the added plugins.
+    pub fn iter(&self) -> impl Iterator<Item = &PluginInfo> {
+        self.plugins.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Plugins;
+    use crate::{App, Plugin};
+    use bevy_ecs::{prelude::*, schedule::DisabledSystems};
+
+    #[derive(Resource, Default)]
+    struct Counter(Vec<&'static str>);
+
+    struct OuterPlugin;
+    impl Plugin for OuterPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_plugin(InnerPlugin)
+                .add_event::<()>()
+                .add_system(|mut counter: ResMut<Counter>| counter.0.push("outer"));
+        }
+    }
+
+    struct InnerPlugin;
+    impl Plugin for InnerPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_system(|mut counter: ResMut<Counter>| counter.0.push("inner"));
+        }
+    }
+
+    fn run(app: &mut App) -> Vec<&'static str> {
+        app.update();
+        let mut ran = std::mem::take(&mut app.world.resource_mut::<Counter>().0);
+        ran.sort_unstable();
+        ran
+    }
+
+    #[test]
+    fn plugins_are_registered() {
+        let mut app = App::new();
+        app.add_plugin(OuterPlugin);
+        assert!(app.is_plugin_added::<OuterPlugin>());
+        assert!(app.is_plugin_added::<InnerPlugin>());
+
+        let plugins = app.world.resource::<Plugins>();
+        let names: Vec<_> = plugins.iter().map(|info| info.name()).collect();
+        assert_eq!(
+            names,
+            [
+                std::any::type_name::<OuterPlugin>(),
+                std::any::type_name::<InnerPlugin>()
+            ]
+        );
+        let inner = plugins
+            .get_with_name(std::any::type_name::<InnerPlugin>())
+            .unwrap();
+        assert!(inner.is::<InnerPlugin>());
+        assert_eq!(plugins.get::<InnerPlugin>().unwrap().label(), inner.label());
+    }
+
+    #[test]
+    fn disable_plugin_systems() {
+        let mut app = App::new();
+        app.init_resource::<Counter>().add_plugin(OuterPlugin);
+        assert_eq!(run(&mut app), ["inner", "outer"]);
+
+        app.set_plugin_enabled::<InnerPlugin>(false);
+        assert_eq!(run(&mut app), ["outer"]);
+
+        // Disabling a plugin disables the plugins it added
+        app.set_plugin_enabled::<InnerPlugin>(true)
+            .set_plugin_enabled::<OuterPlugin>(false);
+        assert!(run(&mut app).is_empty());
+        // The events of a disabled plugin are still updated
+        app.world.resource_mut::<Events<()>>().send(());
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Events<()>>().is_empty());
+
+        let outer = app
+            .world
+            .resource::<Plugins>()
+            .get::<OuterPlugin>()
+            .unwrap();
+        let label = outer.label();
+        app.world.resource_mut::<DisabledSystems>().enable(label);
+        assert_eq!(run(&mut app), ["inner", "outer"]);
+    }
+
+    struct NamedPlugin;
+    impl Plugin for NamedPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_system(|mut counter: ResMut<Counter>| counter.0.push("named"));
+        }
+
+        fn name(&self) -> &str {
+            "named"
+        }
+    }
+
+    #[test]
+    fn disable_plugin_with_custom_name() {
+        let mut app = App::new();
+        app.init_resource::<Counter>().add_plugin(NamedPlugin);
+        let named = app
+            .world
+            .resource::<Plugins>()
+            .get::<NamedPlugin>()
+            .unwrap();
+        assert_eq!(named.name(), "named");
+        assert_eq!(run(&mut app), ["named"]);
+
+        app.set_plugin_enabled::<NamedPlugin>(false);
+        assert!(run(&mut app).is_empty());
+    }
+}
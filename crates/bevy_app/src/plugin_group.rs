@@ -132,7 +132,7 @@ impl PluginGroupBuilder {
             if let Some(entry) = self.plugins.get(ty) {
                 if entry.enabled {
                     debug!("added plugin: {}", entry.plugin.name());
-                    entry.plugin.build(app);
+                    app.build_plugin(&*entry.plugin);
                 }
             }
         }
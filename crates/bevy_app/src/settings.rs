@@ -0,0 +1,196 @@
+use crate::{App, AppTypeRegistry};
+use bevy_ecs::reflect::ReflectResource;
+use bevy_reflect::{serde::TypedReflectDeserializer, Reflect, TypeRegistry};
+use serde::de::{DeserializeSeed, Error, MapAccess, Visitor};
+use std::{any::TypeId, fmt, path::Path};
+use thiserror::Error;
+
+/// An error loading settings with [`App::load_settings`].
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    /// The settings file couldn't be read.
+    #[error("could not read the settings file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The settings couldn't be deserialized.
+    #[error("could not deserialize the settings: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    /// The type of the settings isn't registered with `#[reflect(Resource)]`.
+    #[error("settings type {0} is not registered with `#[reflect(Resource)]`")]
+    NotAResource(String),
+}
+
+impl App {
+    /// Inserts the resources of a RON settings file, or applies them to the existing resources.
+    ///
+    /// The file is a map from the type names of the resources to their values, which only need to
+    /// contain the fields to set. The types must be registered in the [`AppTypeRegistry`] with
+    /// `#[reflect(Resource)]`, and the settings read by plugins when they are built must be
+    /// loaded before the plugins are added.
+    ///
+    /// ```ron
+    /// {
+    ///     "my_game::DevToolsSettings": (
+    ///         show_fps: true,
+    ///     ),
+    /// }
+    /// ```
+    pub fn load_settings(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, SettingsError> {
+        let settings = std::fs::read_to_string(path)?;
+        self.load_settings_from_str(&settings)
+    }
+
+    /// Inserts the resources of RON settings, or applies them to the existing resources, see
+    /// [`load_settings`](Self::load_settings).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// #
+    /// #[derive(Resource, Reflect, Default)]
+    /// #[reflect(Resource)]
+    /// struct DevToolsSettings {
+    ///     show_fps: bool,
+    ///     font_size: f32,
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.register_type::<DevToolsSettings>()
+    ///     .load_settings_from_str(&format!(
+    ///         "{{ \"{}\": (show_fps: true) }}",
+    ///         std::any::type_name::<DevToolsSettings>()
+    ///     ))
+    ///     .unwrap();
+    /// assert!(app.world.resource::<DevToolsSettings>().show_fps);
+    /// ```
+    pub fn load_settings_from_str(&mut self, settings: &str) -> Result<&mut Self, SettingsError> {
+        let type_registry = self.world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let mut deserializer = ron::de::Deserializer::from_str(settings)?;
+        let settings = SettingsDeserializer {
+            registry: &type_registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|err| deserializer.span_error(err))?;
+
+        for (type_id, value) in settings {
+            let registration = type_registry.get(type_id).unwrap();
+            match registration.data::<ReflectResource>() {
+                Some(reflect_resource) => {
+                    reflect_resource.apply_or_insert(&mut self.world, value.as_ref());
+                }
+                None => {
+                    return Err(SettingsError::NotAResource(
+                        registration.type_name().to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+struct SettingsDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SettingsDeserializer<'a> {
+    type Value = Vec<(TypeId, Box<dyn Reflect>)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SettingsDeserializer<'a> {
+    type Value = Vec<(TypeId, Box<dyn Reflect>)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of settings")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut settings = Vec::new();
+        while let Some(type_name) = map.next_key::<String>()? {
+            let registration = self.registry.get_with_name(&type_name).ok_or_else(|| {
+                Error::custom(format_args!("no registration found for type {type_name}"))
+            })?;
+            let value =
+                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?;
+            settings.push((registration.type_id(), value));
+        }
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SettingsError;
+    use crate::App;
+    use bevy_ecs::prelude::*;
+    use bevy_reflect::Reflect;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct WindowSettings {
+        title: String,
+        vsync: bool,
+    }
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    struct NotAResource;
+
+    fn settings(type_name: &str, value: &str) -> String {
+        format!("{{ \"{type_name}\": {value} }}")
+    }
+
+    #[test]
+    fn load_settings() {
+        let mut app = App::new();
+        app.register_type::<WindowSettings>()
+            .register_type::<String>();
+        let type_name = std::any::type_name::<WindowSettings>();
+        app.load_settings_from_str(&settings(type_name, "(title: \"Game\")"))
+            .unwrap();
+        assert_eq!(
+            app.world.resource::<WindowSettings>(),
+            &WindowSettings {
+                title: "Game".to_string(),
+                vsync: false
+            }
+        );
+
+        // The settings are applied to the existing resources
+        app.load_settings_from_str(&settings(type_name, "(vsync: true)"))
+            .unwrap();
+        assert_eq!(
+            app.world.resource::<WindowSettings>(),
+            &WindowSettings {
+                title: "Game".to_string(),
+                vsync: true
+            }
+        );
+    }
+
+    #[test]
+    fn load_invalid_settings() {
+        let mut app = App::new();
+        app.register_type::<NotAResource>();
+        assert!(matches!(
+            app.load_settings_from_str(&settings("Unknown", "()")),
+            Err(SettingsError::Ron(_))
+        ));
+        assert!(matches!(
+            app.load_settings_from_str(&settings(std::any::type_name::<NotAResource>(), "()")),
+            Err(SettingsError::NotAResource(_))
+        ));
+    }
+}
@@ -1,17 +1,24 @@
 use crate::{
+    embedded::{strip_embedded_prefix, EmbeddedAssets},
     path::{AssetPath, AssetPathId, SourcePathId},
+    processor::processed_path,
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetProcessor, AssetSaver, AssetSaverDynamic, Assets, Handle, HandleId,
+    HandleUntyped, ImportSettings, ImportSettingsError, LabelId, LoadContext, LoadResult,
+    LoadResultChannel, LoadState, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_log::warn;
-use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_reflect::TypeUuid;
+use bevy_tasks::{IoTaskPool, Task};
+use bevy_utils::{Entry, HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -41,6 +48,27 @@ pub enum AssetServerError {
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+
+    /// No asset saver was found for the type of the asset and the specified extensions.
+    #[error("no `AssetSaver` found{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver {
+        /// The list of extensions detected on the asset path that failed to save.
+        ///
+        /// The list may be empty if the asset path is invalid or doesn't have an extension.
+        extensions: Vec<String>,
+    },
+
+    /// Encountered an error while serializing an asset.
+    #[error("encountered an error while saving an asset: {0}")]
+    AssetSaverError(anyhow::Error),
+
+    /// Encountered an error while processing an asset source.
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(anyhow::Error),
+
+    /// Encountered an error while reading the import settings of an asset source.
+    #[error("encountered an error while reading the import settings of an asset: {0}")]
+    ImportSettingsError(#[from] ImportSettingsError),
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -72,7 +100,12 @@ pub struct AssetServerInternal {
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    savers: RwLock<HashMap<(Uuid, String), Arc<dyn AssetSaverDynamic>>>,
+    processors: RwLock<HashMap<String, Arc<dyn AssetProcessor>>>,
+    embedded_assets: EmbeddedAssets,
+    sources: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    pub(crate) load_results: LoadResultChannel,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -128,9 +161,14 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                savers: Default::default(),
+                processors: Default::default(),
+                embedded_assets: Default::default(),
+                sources: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                load_results: Default::default(),
                 asset_lifecycles: Default::default(),
                 asset_io,
             }),
@@ -178,6 +216,75 @@ impl AssetServer {
         loaders.push(Arc::new(loader));
     }
 
+    /// Adds the provided asset saver to the server.
+    ///
+    /// If `saver` has one or more supported extensions in conflict with savers of the same asset
+    /// type that came before it, it will replace them.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        let saver = Arc::new(saver);
+        let mut savers = self.server.savers.write();
+        for extension in saver.extensions() {
+            savers.insert(
+                (T::Asset::TYPE_UUID, extension.to_string()),
+                saver.clone() as Arc<dyn AssetSaverDynamic>,
+            );
+        }
+    }
+
+    /// Adds the provided asset processor to the server.
+    ///
+    /// If `processor` has one or more supported extensions in conflict with processors that came
+    /// before it, it will replace them.
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let processor = Arc::new(processor);
+        let mut processors = self.server.processors.write();
+        for extension in processor.extensions() {
+            processors.insert(
+                extension.to_string(),
+                processor.clone() as Arc<dyn AssetProcessor>,
+            );
+        }
+    }
+
+    /// Adds an asset I/O providing the asset sources of the paths starting with `scheme://`.
+    ///
+    /// For example, the paths like `http://cdn/level1.gltf` are loaded by the asset I/O added
+    /// for the `http` scheme, which gets the path following the scheme, `cdn/level1.gltf`. The
+    /// assets are loaded and tracked like the ones of the default asset I/O, and the paths of
+    /// their dependencies keep the scheme. Schemes are case insensitive, and adding an asset I/O
+    /// for a scheme replaces the previous one.
+    ///
+    /// Web builds can load the `http` and `https` schemes with [`fetch()`] by default. The
+    /// `embedded` scheme is reserved for the
+    /// [embedded asset sources](AssetServer::add_embedded_asset).
+    ///
+    /// [`fetch()`]: https://developer.mozilla.org/en-US/docs/Web/API/fetch
+    pub fn add_source<T: AssetIo>(&self, scheme: &str, asset_io: T) {
+        self.server
+            .sources
+            .write()
+            .insert(scheme.to_ascii_lowercase(), Arc::new(asset_io));
+    }
+
+    /// Adds an asset source embedded in the executable, loaded from its path prefixed with
+    /// [`EMBEDDED_ASSET_PREFIX`](crate::EMBEDDED_ASSET_PREFIX).
+    ///
+    /// For example, an asset source added at `icons/logo.png` is loaded from
+    /// `embedded://icons/logo.png`. In debug builds, it can be overridden by a file of the
+    /// [`AssetIo`] in the [`EMBEDDED_OVERRIDES_FOLDER`](crate::EMBEDDED_OVERRIDES_FOLDER).
+    ///
+    /// See the [`embedded_asset!`](crate::embedded_asset) macro to embed a file read at compile
+    /// time.
+    pub fn add_embedded_asset<P: Into<PathBuf>>(&self, path: P, bytes: &'static [u8]) {
+        self.server.embedded_assets.insert(path.into(), bytes);
+    }
+
     /// Gets a strong handle for an asset with the provided id.
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
@@ -233,6 +340,59 @@ impl AssetServer {
         })
     }
 
+    fn get_path_asset_saver<T: Asset>(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn AssetSaverDynamic>, AssetServerError> {
+        let s = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|s| s.to_lowercase())
+            .ok_or(AssetServerError::MissingAssetSaver {
+                extensions: Vec::new(),
+            })?;
+
+        let savers = self.server.savers.read();
+        let mut exts = Vec::new();
+        let mut ext = s.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            if let Some(saver) = savers.get(&(T::TYPE_UUID, ext.to_string())) {
+                return Ok(saver.clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
+    /// Gets the asset processor for the provided path, with the extension it was found for.
+    fn get_path_asset_processor(&self, path: &Path) -> Option<(Arc<dyn AssetProcessor>, String)> {
+        let s = path.file_name()?.to_str()?.to_lowercase();
+        let processors = self.server.processors.read();
+        let mut ext = s.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(processor) = processors.get(ext) {
+                return Some((processor.clone(), ext.to_string()));
+            }
+        }
+        None
+    }
+
+    /// Returns the asset I/O added for the scheme of the path, and the path following the scheme.
+    fn get_path_asset_source<'a>(&self, path: &'a Path) -> Option<(Arc<dyn AssetIo>, &'a Path)> {
+        let scheme = path.components().next()?.as_os_str();
+        let asset_io = self
+            .server
+            .sources
+            .read()
+            .get(&scheme.to_str()?.strip_suffix(':')?.to_ascii_lowercase())?
+            .clone();
+        Some((asset_io, path.strip_prefix(scheme).ok()?))
+    }
+
     /// Gets the source path of an asset from the provided handle.
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
@@ -279,6 +439,61 @@ impl AssetServer {
         load_state
     }
 
+    /// Gets the load state of an asset together with all of its dependencies, recursively.
+    ///
+    /// The dependencies are the asset paths added by the [`AssetLoader`] with
+    /// [`LoadedAsset::with_dependency`](crate::LoadedAsset::with_dependency), such as the meshes
+    /// and textures of a scene. This method will only return [`LoadState::Loaded`] once the
+    /// asset and all of its dependencies were loaded successfully.
+    ///
+    /// To be notified when this happens, use the
+    /// [`AssetDependenciesLoaded`](crate::AssetDependenciesLoaded) event.
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        let id = match handle.into() {
+            HandleId::AssetPathId(id) => id,
+            HandleId::Id(_, _) => return LoadState::NotLoaded,
+        };
+
+        let asset_sources = self.server.asset_sources.read();
+        let mut load_state = LoadState::Loaded;
+        let mut visited = HashSet::default();
+        let mut to_visit = vec![id.source_path_id()];
+        while let Some(source_path_id) = to_visit.pop() {
+            if !visited.insert(source_path_id) {
+                continue;
+            }
+            let source_info = match asset_sources.get(&source_path_id) {
+                Some(source_info) => source_info,
+                // dependencies are queued for loading when their dependant is loaded
+                None if source_path_id != id.source_path_id() => {
+                    load_state = LoadState::Loading;
+                    continue;
+                }
+                None => return LoadState::NotLoaded,
+            };
+            match source_info.load_state {
+                LoadState::Loaded => {}
+                LoadState::Loading => {
+                    load_state = LoadState::Loading;
+                    continue;
+                }
+                other => return other,
+            }
+            if let Some(meta) = &source_info.meta {
+                for asset_meta in &meta.assets {
+                    to_visit.extend(
+                        asset_meta
+                            .dependencies
+                            .iter()
+                            .map(|dependency| dependency.get_id().source_path_id()),
+                    );
+                }
+            }
+        }
+
+        load_state
+    }
+
     /// Queues an [`Asset`] at the provided relative path for asynchronous loading.
     ///
     /// The absolute path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`. Its extension is then
@@ -307,6 +522,38 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
+    /// Saves an [`Asset`] at the provided relative path, with the [asset saver] of its type for
+    /// the extension of the path.
+    ///
+    /// The path is resolved the same way as in [`AssetServer::load`], and the asset source is
+    /// written by the [`AssetIo`] of the server. The asset is serialized right away, but written in
+    /// the background: the returned task needs to be awaited or [detached](Task::detach), as
+    /// dropping it cancels the writing.
+    ///
+    /// If the server watches for changes, the assets loaded from this path will be reloaded.
+    ///
+    /// [asset saver]: AssetSaver
+    #[must_use = "dropping the returned task cancels the saving, detach it to save in the background"]
+    pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+        asset: &T,
+    ) -> Task<Result<(), AssetServerError>> {
+        let asset_path: AssetPath = path.into();
+        let path = asset_path.path().to_owned();
+        let bytes = self.get_path_asset_saver::<T>(&path).and_then(|saver| {
+            saver
+                .save_dynamic(asset)
+                .map_err(AssetServerError::AssetSaverError)
+        });
+        let server = self.server.clone();
+        IoTaskPool::get().spawn(async move {
+            let bytes = bytes?;
+            server.asset_io.save_path(&path, &bytes).await?;
+            Ok(())
+        })
+    }
+
     async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
@@ -347,30 +594,57 @@ impl AssetServer {
             source_info.version
         };
 
-        let set_asset_failed = || {
+        let set_asset_failed = |err: AssetServerError| {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = asset_sources
                 .get_mut(&asset_path_id.source_path_id())
                 .expect("`AssetSource` should exist at this point.");
             source_info.load_state = LoadState::Failed;
+            let path = AssetPath::new(source_info.path.clone(), None);
+            self.server
+                .load_results
+                .sender
+                .send(LoadResult::Failed(path, err.to_string()))
+                .unwrap();
+            err
         };
 
         // get the according asset loader
         let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
             Ok(loader) => loader,
-            Err(err) => {
-                set_asset_failed();
-                return Err(err);
-            }
+            Err(err) => return Err(set_asset_failed(err)),
         };
 
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
+        let bytes = match self.load_source(asset_path.path()).await {
             Ok(bytes) => bytes,
-            Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
+            Err(err) => return Err(set_asset_failed(AssetServerError::AssetIoError(err))),
+        };
+
+        // load the import settings from the `.meta` file of the asset source, if it has one
+        let meta_path = ImportSettings::meta_path(asset_path.path());
+        let import_settings = match self.load_source(&meta_path).await {
+            Ok(meta) => ImportSettings::from_bytes(meta),
+            Err(AssetIoError::NotFound(_)) => Ok(ImportSettings::default()),
+            Err(err) => return Err(set_asset_failed(AssetServerError::AssetIoError(err))),
+        };
+        let import_settings = match import_settings {
+            Ok(import_settings) => import_settings,
+            Err(err) => return Err(set_asset_failed(AssetServerError::ImportSettingsError(err))),
+        };
+
+        // process the asset source if there is an AssetProcessor for it
+        let bytes = match self.get_path_asset_processor(asset_path.path()) {
+            Some((processor, extension)) => {
+                match self
+                    .process_asset(&*processor, bytes, &import_settings, &extension)
+                    .await
+                {
+                    Ok(bytes) => bytes,
+                    Err(err) => return Err(set_asset_failed(err)),
+                }
             }
+            None => bytes,
         };
 
         // load the asset source using the corresponding AssetLoader
@@ -378,6 +652,7 @@ impl AssetServer {
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
             self.asset_io(),
+            &import_settings,
             version,
         );
 
@@ -386,8 +661,7 @@ impl AssetServer {
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
-            set_asset_failed();
-            return Err(err);
+            return Err(set_asset_failed(err));
         }
 
         // if version has changed since we loaded and grabbed a lock, return. theres is a newer
@@ -424,13 +698,79 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        self.watch_source_for_changes(asset_path.path()).unwrap();
+        if import_settings.exists() {
+            self.watch_source_for_changes(&meta_path).unwrap();
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    /// Loads the bytes of an asset source, from the executable if it is embedded or from the
+    /// asset I/O added for the scheme of its path.
+    async fn load_source(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        if strip_embedded_prefix(path).is_some() {
+            return self
+                .server
+                .embedded_assets
+                .load(path, self.asset_io())
+                .await;
+        }
+        match self.get_path_asset_source(path) {
+            Some((asset_io, source_path)) => asset_io.load_path(source_path).await,
+            None => self.asset_io().load_path(path).await,
+        }
+    }
+
+    /// Watches an asset source for changes, or the override of an embedded asset source.
+    fn watch_source_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        match strip_embedded_prefix(path) {
+            Some(embedded_path) => {
+                match self
+                    .server
+                    .embedded_assets
+                    .override_path(embedded_path, self.asset_io())
+                {
+                    Some(override_path) => self.asset_io().watch_path_for_changes(&override_path),
+                    None => Ok(()),
+                }
+            }
+            None => match self.get_path_asset_source(path) {
+                Some((asset_io, source_path)) => asset_io.watch_path_for_changes(source_path),
+                None => self.asset_io().watch_path_for_changes(path),
+            },
+        }
+    }
+
+    /// Processes an asset source, reading the output from the [`PROCESSED_ASSETS_FOLDER`] if it
+    /// was already processed with the same settings.
+    async fn process_asset(
+        &self,
+        processor: &dyn AssetProcessor,
+        bytes: Vec<u8>,
+        import_settings: &ImportSettings,
+        extension: &str,
+    ) -> Result<Vec<u8>, AssetServerError> {
+        let processed_path =
+            processed_path(&bytes, import_settings, extension, processor.version());
+        if let Ok(processed) = self.asset_io().load_path(&processed_path).await {
+            return Ok(processed);
+        }
+
+        let processed = processor
+            .process(&bytes, import_settings)
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+        match self.asset_io().save_path(&processed_path, &processed).await {
+            Ok(()) | Err(AssetIoError::SaveNotSupported(_)) => {}
+            Err(err) => warn!(
+                "Failed to cache the processed asset at {:?}: {}",
+                processed_path, err
+            ),
+        }
+        Ok(processed)
+    }
+
     /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
     ///
     /// See [`load`](AssetServer::load).
@@ -606,6 +946,12 @@ impl AssetServer {
                                 source_info.committed_assets.insert(id.label_id());
                                 if source_info.is_loaded() {
                                     source_info.load_state = LoadState::Loaded;
+                                    let path = AssetPath::new(source_info.path.clone(), None);
+                                    self.server
+                                        .load_results
+                                        .sender
+                                        .send(LoadResult::Loaded(path))
+                                        .unwrap();
                                 }
                             }
                         }
@@ -646,7 +992,11 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use crate::{
+        asset_load_events_system, loader::LoadedAsset, update_asset_storage_system,
+        AssetDependenciesLoaded, AssetLoadFailed, AssetLoaded, EMBEDDED_OVERRIDES_FOLDER,
+        PROCESSED_ASSETS_FOLDER,
+    };
     use bevy_app::App;
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
@@ -672,6 +1022,26 @@ mod test {
         }
     }
 
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "0d5ff9f1-8a3c-4a5e-a1a6-5b2f7c4c8e61"]
+    struct SceneAsset;
+
+    struct FakeSceneLoader;
+    impl AssetLoader for FakeSceneLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(LoadedAsset::new(SceneAsset).with_dependency("fake.png".into()));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scene"]
+        }
+    }
+
     struct FailingLoader;
     impl AssetLoader for FailingLoader {
         fn load<'a>(
@@ -702,6 +1072,64 @@ mod test {
         }
     }
 
+    struct FakePngSaver;
+    impl AssetSaver for FakePngSaver {
+        type Asset = PngAsset;
+
+        fn save(&self, _: &PngAsset) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(b"fake png".to_vec())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["png"]
+        }
+    }
+
+    #[derive(Default, serde::Deserialize)]
+    #[serde(default)]
+    struct UppercaseSettings {
+        suffix: String,
+    }
+
+    struct ProcessedTextLoader;
+    impl AssetLoader for ProcessedTextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                anyhow::ensure!(bytes == b"HELLO!", "the text wasn't processed");
+                anyhow::ensure!(ctx.import_settings().exists(), "the settings weren't read");
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["upper"]
+        }
+    }
+
+    struct UppercaseProcessor;
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            settings: &'a ImportSettings,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            Box::pin(async move {
+                let settings = settings.get::<UppercaseSettings>()?;
+                let mut processed = bytes.to_ascii_uppercase();
+                processed.extend(settings.suffix.as_bytes());
+                Ok(processed)
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["upper"]
+        }
+    }
+
     fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
         use crate::FileAssetIo;
         IoTaskPool::init(Default::default);
@@ -843,6 +1271,115 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[test]
+    fn test_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_saver(FakePngSaver);
+
+        futures_lite::future::block_on(asset_server.save("images/saved.png", &PngAsset)).unwrap();
+        let bytes = std::fs::read(dir.path().join("images/saved.png")).unwrap();
+        assert_eq!(bytes, b"fake png");
+
+        let err =
+            futures_lite::future::block_on(asset_server.save("saved.jpg", &PngAsset)).unwrap_err();
+        assert!(match err {
+            AssetServerError::MissingAssetSaver { extensions } => extensions == ["jpg"],
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_embedded_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        asset_server.add_embedded_asset("icons/fake.png", b"embedded");
+
+        let load_source =
+            |path: &str| futures_lite::future::block_on(asset_server.load_source(Path::new(path)));
+        assert_eq!(
+            load_source("embedded://icons/fake.png").unwrap(),
+            b"embedded"
+        );
+        assert!(matches!(
+            load_source("embedded://icons/missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        // only paths with the prefix are embedded
+        assert!(load_source("icons/fake.png").is_err());
+
+        let path: AssetPath = "embedded://icons/fake.png".into();
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+
+        // the override on disk replaces the embedded asset in debug builds
+        let override_dir = dir.path().join(EMBEDDED_OVERRIDES_FOLDER).join("icons");
+        std::fs::create_dir_all(&override_dir).unwrap();
+        std::fs::write(override_dir.join("fake.png"), b"override").unwrap();
+        let expected: &[u8] = if cfg!(debug_assertions) {
+            b"override"
+        } else {
+            b"embedded"
+        };
+        assert_eq!(load_source("embedded://icons/fake.png").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_asset_source() {
+        use crate::FileAssetIo;
+        let dir = create_dir_and_file("fake.png");
+        let source_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(source_dir.path().join("cdn")).unwrap();
+        std::fs::write(source_dir.path().join("cdn/fake.png"), b"remote").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        asset_server.add_source("Remote", FileAssetIo::new(source_dir.path(), false));
+
+        let load_source =
+            |path: &str| futures_lite::future::block_on(asset_server.load_source(Path::new(path)));
+        assert_eq!(load_source("remote://cdn/fake.png").unwrap(), b"remote");
+        assert_eq!(load_source("REMOTE://cdn/fake.png").unwrap(), b"remote");
+        assert!(matches!(
+            load_source("remote://fake.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        // paths without a scheme are loaded from the default asset I/O
+        assert_eq!(load_source("fake.png").unwrap(), b"");
+
+        let path: AssetPath = "remote://cdn/fake.png".into();
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(
+            asset_server.get_load_state(path.get_id()),
+            LoadState::Loading
+        );
+    }
+
+    #[test]
+    fn test_processing_with_import_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("text.upper"), "hello").unwrap();
+        std::fs::write(dir.path().join("text.upper.meta"), "(suffix: \"!\")").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(ProcessedTextLoader);
+        asset_server.add_processor(UppercaseProcessor);
+
+        let path: AssetPath = "text.upper".into();
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        let processed = std::fs::read_dir(dir.path().join(PROCESSED_ASSETS_FOLDER))
+            .unwrap()
+            .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(processed, ["HELLO!"]);
+
+        // invalid import settings make the processing fail
+        std::fs::write(dir.path().join("text.upper.meta"), "(suffix: 3)").unwrap();
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+            .unwrap_err();
+        assert!(matches!(err, AssetServerError::AssetProcessorError(_)));
+    }
+
     #[test]
     fn test_asset_lifecycle() {
         let dir = create_dir_and_file("fake.png");
@@ -920,6 +1457,66 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn test_load_events() {
+        let dir = create_dir_and_file("fake.png");
+        std::fs::write(dir.path().join("fake.scene"), []).unwrap();
+        std::fs::write(dir.path().join("fake.fail"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeSceneLoader);
+        asset_server.add_loader(FailingLoader);
+
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<PngAsset>());
+        app.insert_resource(asset_server.register_asset_type::<SceneAsset>());
+        app.insert_resource(asset_server.clone());
+        app.add_event::<AssetLoaded>();
+        app.add_event::<AssetLoadFailed>();
+        app.add_event::<AssetDependenciesLoaded>();
+        // the png storage is only updated later, to keep the dependency of the scene loading
+        app.add_system(update_asset_storage_system::<SceneAsset>.before(asset_load_events_system));
+        app.add_system(asset_load_events_system);
+
+        fn sent<E: bevy_ecs::event::Event>(app: &App, is: impl Fn(&E) -> bool) -> bool {
+            let events = app.world.resource::<Events<E>>();
+            events.iter_current_update_events().any(is)
+        }
+
+        let load = |path: &str| {
+            let path: AssetPath = path.into();
+            futures_lite::future::block_on(asset_server.load_async(path.clone(), false))
+                .map(|id| asset_server.get_handle_untyped(id))
+        };
+        let png = load("fake.png").unwrap();
+        let scene = load("fake.scene").unwrap();
+        assert!(load("fake.fail").is_err());
+        let fail = asset_server.get_handle_untyped(AssetPath::from("fake.fail").get_id());
+
+        app.update();
+        assert_eq!(asset_server.get_load_state(&scene), LoadState::Loaded);
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&scene),
+            LoadState::Loading
+        );
+        assert!(sent(&app, |e: &AssetLoaded| e.is(&scene)));
+        assert!(!sent(&app, |e: &AssetLoaded| e.is(&png)));
+        assert!(!sent(&app, |_: &AssetDependenciesLoaded| true));
+        assert!(sent(&app, |e: &AssetLoadFailed| e.is(&fail)
+            && e.error.contains("failed")));
+
+        app.add_system(update_asset_storage_system::<PngAsset>.before(asset_load_events_system));
+        app.update();
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&scene),
+            LoadState::Loaded
+        );
+        assert!(sent(&app, |e: &AssetLoaded| e.is(&png)));
+        assert!(sent(&app, |e: &AssetDependenciesLoaded| e.is(&scene)));
+        assert!(sent(&app, |e: &AssetDependenciesLoaded| e.is(&png)));
+        assert!(!sent(&app, |_: &AssetLoadFailed| true));
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,6 +1,6 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    update_asset_storage_system, Asset, AssetIo, AssetLoader, AssetProcessor, AssetSaver,
+    AssetServer, AssetStage, Handle, HandleId, RefChange,
 };
 use bevy_app::App;
 use bevy_ecs::{
@@ -310,6 +310,36 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Adds the provided asset processor to the application.
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+
+    /// Adds an asset I/O providing the asset sources of the paths starting with `scheme://`.
+    ///
+    /// See [`AssetServer::add_source`].
+    fn add_asset_source<T>(&mut self, scheme: &str, asset_io: T) -> &mut Self
+    where
+        T: AssetIo;
+
+    /// Adds an asset source embedded in the executable.
+    ///
+    /// See [`AssetServer::add_embedded_asset`] and the [`embedded_asset!`](crate::embedded_asset)
+    /// macro.
+    fn add_embedded_asset(&mut self, path: &str, bytes: &'static [u8]) -> &mut Self;
+
+    /// Adds an asset saver `T` using default values.
+    ///
+    /// The default values may come from the `World` or from `T::default()`.
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+
+    /// Adds the provided asset saver to the application.
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
 }
 
 impl AddAsset for App {
@@ -377,6 +407,49 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .add_processor(processor);
+        self
+    }
+
+    fn add_asset_source<T>(&mut self, scheme: &str, asset_io: T) -> &mut Self
+    where
+        T: AssetIo,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .add_source(scheme, asset_io);
+        self
+    }
+
+    fn add_embedded_asset(&mut self, path: &str, bytes: &'static [u8]) -> &mut Self {
+        self.world
+            .resource::<AssetServer>()
+            .add_embedded_asset(path, bytes);
+        self
+    }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world.resource::<AssetServer>().add_saver(saver);
+        self
+    }
 }
 
 /// Loads an internal asset.
@@ -417,6 +490,26 @@ macro_rules! load_internal_asset {
     }};
 }
 
+/// Embeds an asset source in the executable, to be loaded from its path prefixed with
+/// `embedded://`.
+///
+/// The file is read at compile time, relative to the current file like with [`include_bytes!`].
+/// In debug builds, the embedded asset can be overridden on disk, see
+/// [`EMBEDDED_OVERRIDES_FOLDER`](crate::EMBEDDED_OVERRIDES_FOLDER).
+///
+/// ```ignore
+/// // loaded with `asset_server.load("embedded://my_plugin/icon.png")`
+/// embedded_asset!(app, "my_plugin/icon.png", "../assets/icon.png");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path_str: expr, $file: expr) => {{
+        $app.world
+            .resource::<$crate::AssetServer>()
+            .add_embedded_asset($path_str, include_bytes!($file));
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_app::App;
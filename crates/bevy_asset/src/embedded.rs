@@ -0,0 +1,82 @@
+use crate::{AssetIo, AssetIoError};
+use bevy_utils::HashMap;
+use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
+
+/// The prefix of the paths of the asset sources embedded in the executable, such as
+/// `embedded://icons/logo.png`.
+///
+/// Embedded asset sources are added with
+/// [`AssetServer::add_embedded_asset`](crate::AssetServer::add_embedded_asset) or the
+/// [`embedded_asset!`](crate::embedded_asset) macro.
+pub const EMBEDDED_ASSET_PREFIX: &str = "embedded://";
+
+/// The folder of the [`AssetIo`] where embedded asset sources can be overridden in debug builds.
+///
+/// When built with debug assertions, `embedded://icons/logo.png` is loaded from
+/// `embedded/icons/logo.png` if that file exists, which allows iterating on the asset without
+/// rebuilding the executable. The override is hot reloaded when the asset server watches for
+/// changes.
+pub const EMBEDDED_OVERRIDES_FOLDER: &str = "embedded";
+
+/// The asset sources embedded in the executable, keyed on their path without the
+/// [`EMBEDDED_ASSET_PREFIX`].
+#[derive(Default)]
+pub(crate) struct EmbeddedAssets {
+    sources: RwLock<HashMap<PathBuf, &'static [u8]>>,
+}
+
+impl EmbeddedAssets {
+    pub fn insert(&self, path: PathBuf, bytes: &'static [u8]) {
+        self.sources.write().insert(path, bytes);
+    }
+
+    /// Loads the embedded asset source at the provided path, or its override in debug builds.
+    pub async fn load(&self, path: &Path, asset_io: &dyn AssetIo) -> Result<Vec<u8>, AssetIoError> {
+        let embedded_path = match strip_embedded_prefix(path) {
+            Some(embedded_path) => embedded_path,
+            None => return Err(AssetIoError::NotFound(path.to_owned())),
+        };
+
+        if let Some(override_path) = self.override_path(embedded_path, asset_io) {
+            return asset_io.load_path(&override_path).await;
+        }
+
+        self.sources
+            .read()
+            .get(embedded_path)
+            .map(|bytes| bytes.to_vec())
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+    }
+
+    /// Returns the path of the override of an embedded asset source, if the build has debug
+    /// assertions and the override exists.
+    pub fn override_path(&self, embedded_path: &Path, asset_io: &dyn AssetIo) -> Option<PathBuf> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+        let override_path = Path::new(EMBEDDED_OVERRIDES_FOLDER).join(embedded_path);
+        if asset_io.is_file(&override_path) {
+            Some(override_path)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the path of an embedded asset source without the [`EMBEDDED_ASSET_PREFIX`], or `None`
+/// if the path isn't the one of an embedded asset source.
+pub(crate) fn strip_embedded_prefix(path: &Path) -> Option<&Path> {
+    path.strip_prefix(EMBEDDED_ASSET_PREFIX).ok()
+}
+
+/// Returns the path of the embedded asset source overridden by the file at the provided path, or
+/// `None` if the path isn't in the [`EMBEDDED_OVERRIDES_FOLDER`].
+#[cfg(feature = "filesystem_watcher")]
+pub(crate) fn overridden_embedded_path(path: &Path) -> Option<PathBuf> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    let embedded_path = path.strip_prefix(EMBEDDED_OVERRIDES_FOLDER).ok()?;
+    Some(Path::new(EMBEDDED_ASSET_PREFIX).join(embedded_path))
+}
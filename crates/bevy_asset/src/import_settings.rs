@@ -0,0 +1,89 @@
+use serde::de::DeserializeOwned;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The extension of the files holding the [`ImportSettings`] of asset sources, appended to the
+/// extension of the asset source.
+pub const META_FILE_EXTENSION: &str = "meta";
+
+/// Errors that occur while reading [`ImportSettings`].
+#[derive(Error, Debug)]
+pub enum ImportSettingsError {
+    /// The `.meta` file isn't valid UTF-8.
+    #[error("the meta file is not valid UTF-8: {0}")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+
+    /// The `.meta` file doesn't match the settings read from it.
+    #[error("invalid import settings: {0}")]
+    InvalidSettings(#[from] ron::error::SpannedError),
+}
+
+/// The import settings of an asset source, read from the `.meta` file next to it.
+///
+/// The `.meta` file of `textures/player.png` is `textures/player.png.meta`. It contains the
+/// settings of the [`AssetLoader`](crate::AssetLoader) and the
+/// [`AssetProcessor`](crate::AssetProcessor) of the asset in RON, such as:
+///
+/// ```ron
+/// (
+///     is_srgb: false,
+///     sampler: Nearest,
+/// )
+/// ```
+///
+/// Changing the `.meta` file reloads the asset when the asset server watches for changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSettings {
+    ron: Option<String>,
+}
+
+impl ImportSettings {
+    /// Creates the import settings from the contents of a `.meta` file.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, ImportSettingsError> {
+        Ok(Self {
+            ron: Some(String::from_utf8(bytes)?),
+        })
+    }
+
+    /// Returns the path of the `.meta` file of the asset source at the provided path.
+    pub fn meta_path(path: &Path) -> PathBuf {
+        let mut meta_path = OsString::from(path);
+        meta_path.push(".");
+        meta_path.push(META_FILE_EXTENSION);
+        meta_path.into()
+    }
+
+    /// Returns the path of the asset source of the `.meta` file at the provided path, or `None`
+    /// if the path isn't the one of a `.meta` file.
+    pub fn source_path(meta_path: &Path) -> Option<PathBuf> {
+        if meta_path.extension()? != META_FILE_EXTENSION {
+            return None;
+        }
+        Some(meta_path.with_extension(""))
+    }
+
+    /// Returns `true` if the asset source has a `.meta` file.
+    pub fn exists(&self) -> bool {
+        self.ron.is_some()
+    }
+
+    /// Returns the contents of the `.meta` file, if the asset source has one.
+    pub fn as_str(&self) -> Option<&str> {
+        self.ron.as_deref()
+    }
+
+    /// Reads the settings from the `.meta` file, or returns their default values if the asset
+    /// source doesn't have one.
+    ///
+    /// The settings type should use `#[serde(default)]`, so that `.meta` files only need to list
+    /// the settings they change.
+    pub fn get<T: DeserializeOwned + Default>(&self) -> Result<T, ImportSettingsError> {
+        match &self.ron {
+            Some(ron) => Ok(ron::de::from_str(ron)?),
+            None => Ok(T::default()),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
+use crate::{
+    embedded::overridden_embedded_path, filesystem_watcher::FilesystemWatcher, AssetServer,
+    ImportSettings,
+};
 use crate::{AssetIo, AssetIoError, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
@@ -105,6 +108,21 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -189,7 +207,13 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 for path in &paths {
                     if !changed.contains(path) {
                         let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+                        // Changing the import settings of an asset reloads the asset
+                        let relative_path = ImportSettings::source_path(relative_path)
+                            .unwrap_or_else(|| relative_path.to_owned());
+                        // Changing the override of an embedded asset reloads the embedded asset
+                        let relative_path =
+                            overridden_embedded_path(&relative_path).unwrap_or(relative_path);
+                        let _ = asset_server.load_untracked(relative_path.as_path().into(), true);
                     }
                 }
                 changed.extend(paths);
@@ -39,6 +39,10 @@ pub enum AssetIoError {
     /// Failed to watch path.
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+
+    /// The asset I/O can't save assets.
+    #[error("saving is not supported: {0}")]
+    SaveNotSupported(PathBuf),
 }
 
 /// A storage provider for an [`AssetServer`].
@@ -56,6 +60,19 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Returns a future to load the full file data at the provided path.
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+    /// Returns a future to save the full file data at the provided path, replacing the file if
+    /// it exists.
+    ///
+    /// Asset I/Os that can't write to their storage don't need to implement this, it returns
+    /// [`AssetIoError::SaveNotSupported`] by default.
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::SaveNotSupported(path.to_owned())) })
+    }
+
     /// Returns an iterator of directory entry names at the provided path.
     fn read_directory(
         &self,
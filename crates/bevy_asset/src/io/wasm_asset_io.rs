@@ -14,7 +14,7 @@ use web_sys::Response;
 ///
 /// Implementation details:
 ///
-/// - `load_path` makes [fetch()] requests.
+/// - `load_path` makes [fetch()] requests, and fails if the response doesn't have a success status.
 /// - `read_directory` always returns an empty iterator.
 /// - `get_metadata` will always return an error.
 /// - Watching for changes is not supported. The watcher methods will do nothing.
@@ -42,6 +42,16 @@ impl AssetIo for WasmAssetIo {
                 .await
                 .unwrap();
             let resp: Response = resp_value.dyn_into().unwrap();
+            match resp.status() {
+                404 => return Err(AssetIoError::NotFound(path)),
+                status if !resp.ok() => {
+                    return Err(AssetIoError::Io(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!("request for {:?} failed with status {}", path, status),
+                    )))
+                }
+                _ => {}
+            }
             let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
             let bytes = Uint8Array::new(&data).to_vec();
             Ok(bytes)
@@ -15,16 +15,21 @@ mod assets;
 #[cfg(feature = "debug_asset_server")]
 pub mod debug_asset_server;
 pub mod diagnostic;
+mod embedded;
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 mod filesystem_watcher;
 mod handle;
+mod import_settings;
 mod info;
 mod io;
+mod load_events;
 mod loader;
 mod path;
+mod processor;
+mod saver;
 
 /// The `bevy_asset` prelude.
 pub mod prelude {
@@ -38,11 +43,16 @@ pub use anyhow::Error;
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use embedded::*;
 pub use handle::*;
+pub use import_settings::*;
 pub use info::*;
 pub use io::*;
+pub use load_events::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::{
@@ -114,6 +124,13 @@ impl Plugin for AssetPlugin {
             app.insert_resource(asset_server);
         }
 
+        #[cfg(target_arch = "wasm32")]
+        {
+            let asset_server = app.world.resource::<AssetServer>();
+            asset_server.add_source("http", WasmAssetIo::new("http://"));
+            asset_server.add_source("https", WasmAssetIo::new("https://"));
+        }
+
         app.add_stage_before(
             bevy_app::CoreStage::PreUpdate,
             AssetStage::LoadAssets,
@@ -125,6 +142,10 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .add_event::<AssetLoaded>()
+        .add_event::<AssetLoadFailed>()
+        .add_event::<AssetDependenciesLoaded>()
+        .add_system_to_stage(AssetStage::AssetEvents, asset_load_events_system)
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
@@ -0,0 +1,147 @@
+use crate::{AssetPath, AssetServer, HandleId, HandleUntyped, LoadState, SourcePathId};
+use bevy_ecs::{
+    event::EventWriter,
+    system::{Local, Res},
+};
+use bevy_utils::HashMap;
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+
+/// Sent when an asset source was loaded, and all of its labeled assets were added to their
+/// [`Assets`](crate::Assets) collections.
+///
+/// The dependencies of the assets may still be loading, see [`AssetDependenciesLoaded`].
+#[derive(Debug)]
+pub struct AssetLoaded {
+    /// A weak handle to the default asset of the source.
+    pub handle: HandleUntyped,
+    /// The path of the asset source.
+    pub path: AssetPath<'static>,
+}
+
+impl AssetLoaded {
+    /// Returns `true` if the event is about the source of the asset of the handle, which may be
+    /// one of its labeled assets.
+    pub fn is<H: Into<HandleId>>(&self, handle: H) -> bool {
+        is_same_source(&self.handle, handle.into())
+    }
+}
+
+/// Sent when an asset source failed to load.
+#[derive(Debug)]
+pub struct AssetLoadFailed {
+    /// A weak handle to the default asset of the source.
+    pub handle: HandleUntyped,
+    /// The path of the asset source.
+    pub path: AssetPath<'static>,
+    /// The error that made the load fail.
+    pub error: String,
+}
+
+impl AssetLoadFailed {
+    /// Returns `true` if the event is about the source of the asset of the handle, which may be
+    /// one of its labeled assets.
+    pub fn is<H: Into<HandleId>>(&self, handle: H) -> bool {
+        is_same_source(&self.handle, handle.into())
+    }
+}
+
+/// Sent when an asset source and all of its dependencies, recursively, were loaded.
+///
+/// For example, a scene is only fully loaded once the meshes and textures it depends on are
+/// loaded too. This event is not sent if one of the dependencies failed to load, an
+/// [`AssetLoadFailed`] event is sent for that dependency instead.
+///
+/// See [`AssetServer::get_recursive_dependency_load_state`].
+#[derive(Debug)]
+pub struct AssetDependenciesLoaded {
+    /// A weak handle to the default asset of the source.
+    pub handle: HandleUntyped,
+    /// The path of the asset source.
+    pub path: AssetPath<'static>,
+}
+
+impl AssetDependenciesLoaded {
+    /// Returns `true` if the event is about the source of the asset of the handle, which may be
+    /// one of its labeled assets.
+    pub fn is<H: Into<HandleId>>(&self, handle: H) -> bool {
+        is_same_source(&self.handle, handle.into())
+    }
+}
+
+fn is_same_source(source_handle: &HandleUntyped, handle: HandleId) -> bool {
+    match (source_handle.id, handle) {
+        (HandleId::AssetPathId(source_id), HandleId::AssetPathId(id)) => {
+            source_id.source_path_id() == id.source_path_id()
+        }
+        _ => false,
+    }
+}
+
+/// The outcome of loading an asset source, sent by the asset server to the
+/// [`asset_load_events_system`].
+pub(crate) enum LoadResult {
+    Loaded(AssetPath<'static>),
+    Failed(AssetPath<'static>, String),
+}
+
+pub(crate) struct LoadResultChannel {
+    pub sender: Sender<LoadResult>,
+    pub receiver: Receiver<LoadResult>,
+}
+
+impl Default for LoadResultChannel {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        LoadResultChannel { sender, receiver }
+    }
+}
+
+/// A system that sends the [`AssetLoaded`], [`AssetLoadFailed`] and [`AssetDependenciesLoaded`]
+/// events.
+pub fn asset_load_events_system(
+    asset_server: Res<AssetServer>,
+    mut waiting_for_dependencies: Local<HashMap<SourcePathId, AssetPath<'static>>>,
+    mut loaded_events: EventWriter<AssetLoaded>,
+    mut failed_events: EventWriter<AssetLoadFailed>,
+    mut dependencies_loaded_events: EventWriter<AssetDependenciesLoaded>,
+) {
+    let receiver = &asset_server.server.load_results.receiver;
+    loop {
+        match receiver.try_recv() {
+            Ok(LoadResult::Loaded(path)) => {
+                let id = path.get_id();
+                waiting_for_dependencies.insert(id.source_path_id(), path.clone());
+                loaded_events.send(AssetLoaded {
+                    handle: HandleUntyped::weak(id.into()),
+                    path,
+                });
+            }
+            Ok(LoadResult::Failed(path, error)) => {
+                let id = path.get_id();
+                waiting_for_dependencies.remove(&id.source_path_id());
+                failed_events.send(AssetLoadFailed {
+                    handle: HandleUntyped::weak(id.into()),
+                    path,
+                    error,
+                });
+            }
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => panic!("LoadResult channel disconnected."),
+        }
+    }
+
+    waiting_for_dependencies.retain(|_, path| {
+        let id = path.get_id();
+        match asset_server.get_recursive_dependency_load_state(id) {
+            LoadState::Loaded => {
+                dependencies_loaded_events.send(AssetDependenciesLoaded {
+                    handle: HandleUntyped::weak(id.into()),
+                    path: path.clone(),
+                });
+                false
+            }
+            LoadState::Loading => true,
+            LoadState::NotLoaded | LoadState::Failed | LoadState::Unloaded => false,
+        }
+    });
+}
@@ -1,6 +1,6 @@
 use crate::{
     path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    ImportSettings, RefChangeChannel,
 };
 use anyhow::Error;
 use anyhow::Result;
@@ -124,6 +124,7 @@ pub struct LoadContext<'a> {
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
+    pub(crate) import_settings: &'a ImportSettings,
     pub(crate) version: usize,
 }
 
@@ -132,6 +133,7 @@ impl<'a> LoadContext<'a> {
         path: &'a Path,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
+        import_settings: &'a ImportSettings,
         version: usize,
     ) -> Self {
         Self {
@@ -140,6 +142,7 @@ impl<'a> LoadContext<'a> {
             labeled_assets: Default::default(),
             version,
             path,
+            import_settings,
         }
     }
 
@@ -148,6 +151,11 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the import settings of the asset source, read from its `.meta` file.
+    pub fn import_settings(&self) -> &ImportSettings {
+        self.import_settings
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
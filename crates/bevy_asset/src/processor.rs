@@ -0,0 +1,49 @@
+use crate::ImportSettings;
+use anyhow::Error;
+use bevy_utils::{BoxedFuture, FixedState};
+use std::{
+    hash::{BuildHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// The folder of the asset source where the outputs of the [`AssetProcessor`]s are cached.
+pub const PROCESSED_ASSETS_FOLDER: &str = ".processed";
+
+/// A processor for an asset source.
+///
+/// Types implementing this trait transform asset sources into the engine-ready bytes read by the
+/// [`AssetLoader`](crate::AssetLoader) of the same extension, following the [`ImportSettings`]
+/// of the asset. The output is cached in the [`PROCESSED_ASSETS_FOLDER`], keyed on the hash of the
+/// source and its settings, so an asset is only processed again when one of them changes.
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the bytes of the asset source in an asynchronous closure.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        settings: &'a ImportSettings,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions supported by this asset processor, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// The version of the processor, to change when its output changes so that the cached
+    /// outputs are processed again.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// Returns the path where the output of the processor for an asset source is cached.
+pub(crate) fn processed_path(
+    bytes: &[u8],
+    settings: &ImportSettings,
+    extension: &str,
+    version: u32,
+) -> PathBuf {
+    let mut hasher = FixedState.build_hasher();
+    bytes.hash(&mut hasher);
+    settings.as_str().hash(&mut hasher);
+    extension.hash(&mut hasher);
+    version.hash(&mut hasher);
+    Path::new(PROCESSED_ASSETS_FOLDER).join(format!("{:016x}.{}", hasher.finish(), extension))
+}
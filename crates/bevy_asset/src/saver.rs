@@ -0,0 +1,32 @@
+use crate::{Asset, AssetDynamic};
+use anyhow::Error;
+
+/// A saver for an asset source.
+///
+/// Types implementing this trait are used by the asset server to serialize assets back to their
+/// asset source with [`AssetServer::save`](crate::AssetServer::save), in a format that an
+/// [`AssetLoader`](crate::AssetLoader) can load.
+pub trait AssetSaver: Send + Sync + 'static {
+    /// The type of asset this saver serializes.
+    type Asset: Asset;
+
+    /// Serializes the asset into the bytes of its asset source.
+    fn save(&self, asset: &Self::Asset) -> Result<Vec<u8>, Error>;
+
+    /// Returns a list of extensions supported by this asset saver, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+}
+
+/// An untyped version of the [`AssetSaver`] trait, to store the savers of all the asset types.
+pub(crate) trait AssetSaverDynamic: Send + Sync + 'static {
+    fn save_dynamic(&self, asset: &dyn AssetDynamic) -> Result<Vec<u8>, Error>;
+}
+
+impl<T: AssetSaver> AssetSaverDynamic for T {
+    fn save_dynamic(&self, asset: &dyn AssetDynamic) -> Result<Vec<u8>, Error> {
+        let asset = asset
+            .downcast_ref::<T::Asset>()
+            .expect("Asset saver called with the wrong asset type");
+        self.save(asset)
+    }
+}
@@ -1,8 +1,8 @@
-use crate::{AudioSink, AudioSource, Decodable};
+use crate::{AudioBusId, AudioSink, AudioSource, AudioStream, Decodable};
 use bevy_asset::{Asset, Handle, HandleId};
 use bevy_ecs::system::Resource;
-use parking_lot::RwLock;
-use std::{collections::VecDeque, fmt};
+use parking_lot::{Mutex, RwLock};
+use std::{collections::VecDeque, fmt, time::Duration};
 
 /// Use this [`Resource`] to play audio.
 ///
@@ -21,6 +21,8 @@ where
 {
     /// Queue for playing audio from asset handles
     pub(crate) queue: RwLock<VecDeque<AudioToPlay<Source>>>,
+    /// Queue for playing audio streams
+    pub(crate) streams: Mutex<VecDeque<StreamToPlay>>,
 }
 
 impl<Source: Asset> fmt::Debug for Audio<Source>
@@ -28,7 +30,10 @@ where
     Source: Decodable,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Audio").field("queue", &self.queue).finish()
+        f.debug_struct("Audio")
+            .field("queue", &self.queue)
+            .field("streams", &self.streams.lock().len())
+            .finish()
     }
 }
 
@@ -39,6 +44,7 @@ where
     fn default() -> Self {
         Self {
             queue: Default::default(),
+            streams: Default::default(),
         }
     }
 }
@@ -119,6 +125,27 @@ where
         self.queue.write().push_back(config);
         Handle::<AudioSink>::weak(id)
     }
+
+    /// Play the samples generated by an [`AudioStream`], with [`PlaybackSettings`] that allow
+    /// changing volume from the start
+    ///
+    /// A stream can't be repeated, so [`PlaybackSettings::repeat`] is ignored, and it can only be
+    /// moved forward with [`AudioSink::seek`].
+    ///
+    /// See [`Self::play`] on how to control playback once it's started.
+    pub fn play_stream(
+        &self,
+        stream: impl AudioStream,
+        settings: PlaybackSettings,
+    ) -> Handle<AudioSink> {
+        let id = HandleId::random::<AudioSink>();
+        self.streams.lock().push_back(StreamToPlay {
+            settings,
+            sink_handle: id,
+            stream: Box::new(stream),
+        });
+        Handle::<AudioSink>::weak(id)
+    }
 }
 
 /// Settings to control playback from the start.
@@ -130,6 +157,15 @@ pub struct PlaybackSettings {
     pub volume: f32,
     /// Speed to play at.
     pub speed: f32,
+    /// The bus in the [`AudioBuses`](crate::AudioBuses) to play on.
+    pub bus: AudioBusId,
+    /// Duration of the fade in from silence at the start of playback.
+    pub fade_in: Duration,
+    /// Keep the decoded samples of the sound, so that it can go back to an earlier position or
+    /// loop between two positions once it plays.
+    ///
+    /// The samples of the sounds played in repeat are always kept.
+    pub seekable: bool,
 }
 
 impl Default for PlaybackSettings {
@@ -144,6 +180,9 @@ impl PlaybackSettings {
         repeat: false,
         volume: 1.0,
         speed: 1.0,
+        bus: AudioBusId::MASTER,
+        fade_in: Duration::ZERO,
+        seekable: false,
     };
 
     /// Will play the associate audio source in a loop.
@@ -151,6 +190,9 @@ impl PlaybackSettings {
         repeat: true,
         volume: 1.0,
         speed: 1.0,
+        bus: AudioBusId::MASTER,
+        fade_in: Duration::ZERO,
+        seekable: false,
     };
 
     /// Helper to set the volume from start of playback.
@@ -164,6 +206,24 @@ impl PlaybackSettings {
         self.speed = speed;
         self
     }
+
+    /// Helper to fade in from silence at the start of playback.
+    pub const fn with_fade_in(mut self, duration: Duration) -> Self {
+        self.fade_in = duration;
+        self
+    }
+
+    /// Helper to keep the decoded samples of the sound, to go back in it.
+    pub const fn with_seekable(mut self, seekable: bool) -> Self {
+        self.seekable = seekable;
+        self
+    }
+
+    /// Helper to set the bus to play on.
+    pub fn with_bus(mut self, bus: impl Into<AudioBusId>) -> Self {
+        self.bus = bus.into();
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -188,3 +248,9 @@ where
             .finish()
     }
 }
+
+pub(crate) struct StreamToPlay {
+    pub(crate) sink_handle: HandleId,
+    pub(crate) stream: Box<dyn AudioStream>,
+    pub(crate) settings: PlaybackSettings,
+}
@@ -1,10 +1,14 @@
-use crate::{Audio, AudioSource, Decodable};
+use crate::{
+    Audio, AudioBuses, AudioSource, Decodable, Playback, PlaybackControl, PlaybackSettings,
+    StreamSource,
+};
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::system::{NonSend, Res, ResMut};
+use bevy_math::Vec3;
 use bevy_reflect::TypeUuid;
 use bevy_utils::tracing::warn;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
-use std::marker::PhantomData;
+use rodio::{OutputStream, OutputStreamHandle, Sink, SpatialSink};
+use std::{marker::PhantomData, time::Duration};
 
 /// Used internally to play audio on the current "audio device"
 pub struct AudioOutput<Source = AudioSource>
@@ -42,15 +46,47 @@ impl<Source> AudioOutput<Source>
 where
     Source: Asset + Decodable,
 {
-    fn play_source(&self, audio_source: &Source, repeat: bool) -> Option<Sink> {
+    fn play_source(
+        &self,
+        audio_source: &Source,
+        settings: &PlaybackSettings,
+        audio_buses: &AudioBuses,
+    ) -> Option<AudioSink> {
         self.stream_handle.as_ref().map(|stream_handle| {
             let sink = Sink::try_new(stream_handle).unwrap();
-            if repeat {
-                sink.append(audio_source.decoder().repeat_infinite());
-            } else {
-                sink.append(audio_source.decoder());
+            let (playback, control) = Playback::new(audio_source.decoder(), settings);
+            sink.append(audio_buses.apply(&settings.bus, playback));
+            sink.set_speed(settings.speed);
+            sink.set_volume(settings.volume);
+            AudioSink {
+                sink: Some(sink),
+                control,
             }
-            sink
+        })
+    }
+
+    /// Plays a source from a position, heard from the positions of two ears
+    pub(crate) fn play_spatial_source(
+        &self,
+        audio_source: &Source,
+        settings: &PlaybackSettings,
+        audio_buses: &AudioBuses,
+        emitter: Vec3,
+        (left_ear, right_ear): (Vec3, Vec3),
+    ) -> Option<(SpatialSink, PlaybackControl)> {
+        self.stream_handle.as_ref().map(|stream_handle| {
+            let sink = SpatialSink::try_new(
+                stream_handle,
+                emitter.to_array(),
+                left_ear.to_array(),
+                right_ear.to_array(),
+            )
+            .unwrap();
+            let (playback, control) = Playback::new(audio_source.decoder(), settings);
+            sink.append(audio_buses.apply(&settings.bus, playback));
+            sink.set_speed(settings.speed);
+            sink.set_volume(settings.volume);
+            (sink, control)
         })
     }
 
@@ -59,6 +95,7 @@ where
         audio_sources: &Assets<Source>,
         audio: &mut Audio<Source>,
         sinks: &mut Assets<AudioSink>,
+        audio_buses: &AudioBuses,
     ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
@@ -66,12 +103,9 @@ where
         while i < len {
             let config = queue.pop_front().unwrap();
             if let Some(audio_source) = audio_sources.get(&config.source_handle) {
-                if let Some(sink) = self.play_source(audio_source, config.settings.repeat) {
-                    sink.set_speed(config.settings.speed);
-                    sink.set_volume(config.settings.volume);
-
+                if let Some(sink) = self.play_source(audio_source, &config.settings, audio_buses) {
                     // don't keep the strong handle. there is no way to return it to the user here as it is async
-                    let _ = sinks.set(config.sink_handle, AudioSink { sink: Some(sink) });
+                    let _ = sinks.set(config.sink_handle, sink);
                 }
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
@@ -80,17 +114,47 @@ where
             i += 1;
         }
     }
+
+    fn play_queued_streams(
+        &self,
+        audio: &mut Audio<Source>,
+        sinks: &mut Assets<AudioSink>,
+        audio_buses: &AudioBuses,
+    ) {
+        let stream_handle = match &self.stream_handle {
+            Some(stream_handle) => stream_handle,
+            None => return,
+        };
+        for config in audio.streams.get_mut().drain(..) {
+            let sink = Sink::try_new(stream_handle).unwrap();
+            let source = StreamSource::new(config.stream);
+            let (playback, control) = Playback::forward_only(source, &config.settings);
+            sink.append(audio_buses.apply(&config.settings.bus, playback));
+            sink.set_speed(config.settings.speed);
+            sink.set_volume(config.settings.volume);
+
+            // don't keep the strong handle. there is no way to return it to the user here as it is async
+            let sink = AudioSink {
+                sink: Some(sink),
+                control,
+            };
+            let _ = sinks.set(config.sink_handle, sink);
+        }
+    }
 }
 
-/// Plays audio currently queued in the [`Audio`] resource through the [`AudioOutput`] resource
+/// Plays audio and streams currently queued in the [`Audio`] resource through the [`AudioOutput`]
+/// resource
 pub fn play_queued_audio_system<Source: Asset + Decodable>(
     audio_output: NonSend<AudioOutput<Source>>,
     audio_sources: Option<Res<Assets<Source>>>,
     mut audio: ResMut<Audio<Source>>,
     mut sinks: ResMut<Assets<AudioSink>>,
+    audio_buses: Res<AudioBuses>,
 ) {
+    audio_output.play_queued_streams(&mut audio, &mut sinks, &audio_buses);
     if let Some(audio_sources) = audio_sources {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio, &mut *sinks);
+        audio_output.try_play_queued(&*audio_sources, &mut *audio, &mut *sinks, &audio_buses);
     };
 }
 
@@ -122,6 +186,7 @@ pub struct AudioSink {
     // This field is an Option in order to allow us to have a safe drop that will detach the sink.
     // It will never be None during its life
     sink: Option<Sink>,
+    control: PlaybackControl,
 }
 
 impl Drop for AudioSink {
@@ -191,4 +256,61 @@ impl AudioSink {
     pub fn stop(&self) {
         self.sink.as_ref().unwrap().stop();
     }
+
+    /// The position in the sound, from its start.
+    pub fn position(&self) -> Duration {
+        self.control.position()
+    }
+
+    /// Moves the sound to a position, from its start.
+    ///
+    /// Only the sounds played in repeat or with
+    /// [`PlaybackSettings::seekable`](crate::PlaybackSettings::seekable) can go back, the
+    /// others and the [`AudioStream`](crate::AudioStream)s can only be moved forward.
+    pub fn seek(&self, position: Duration) {
+        self.control.seek(position);
+    }
+
+    /// The start and the end of the section of the sound that is repeated, if it loops.
+    pub fn loop_points(&self) -> Option<(Duration, Option<Duration>)> {
+        self.control.loop_points()
+    }
+
+    /// Repeats the section of the sound between two positions, going back to `start` when
+    /// reaching `end`, or the end of the sound if `None`.
+    ///
+    /// The sound keeps playing from its current position, so an intro before `start` is only
+    /// played once. Only the sounds played in repeat or with
+    /// [`PlaybackSettings::seekable`](crate::PlaybackSettings::seekable) can go back to `start`.
+    pub fn set_loop_points(&self, start: Duration, end: Option<Duration>) {
+        self.control.set_loop_points(start, end);
+    }
+
+    /// Stops repeating the sound, letting it play until its end.
+    pub fn stop_looping(&self) {
+        self.control.stop_looping();
+    }
+
+    /// Gets the multiplier of the volume changed by the fades, from `0.0` to `1.0`.
+    pub fn fade_volume(&self) -> f32 {
+        self.control.fade_volume()
+    }
+
+    /// Fades the sound in from silence over a duration.
+    pub fn fade_in(&self, duration: Duration) {
+        self.control.fade(Some(0.0), 1.0, duration, false);
+    }
+
+    /// Fades the sound out over a duration, then stops it.
+    ///
+    /// Fading a sound in while fading another one out crossfades them.
+    pub fn fade_out(&self, duration: Duration) {
+        self.control.fade(None, 0.0, duration, true);
+    }
+
+    /// Fades the multiplier of the volume to a value over a duration, without changing the
+    /// [`volume`](Self::volume).
+    pub fn fade_to(&self, volume: f32, duration: Duration) {
+        self.control.fade(None, volume, duration, false);
+    }
 }
@@ -0,0 +1,187 @@
+use parking_lot::Mutex;
+use rodio::Source;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+/// A type implementing this trait generates samples while it plays, without an asset, to be
+/// played with [`Audio::play_stream`](crate::Audio::play_stream)
+///
+/// The samples are generated on the audio thread, as they are needed.
+///
+/// ```
+/// # use bevy_audio::AudioStream;
+/// /// A sine wave playing for a second
+/// struct Beep {
+///     frequency: f32,
+///     sample: u32,
+/// }
+///
+/// impl AudioStream for Beep {
+///     fn channels(&self) -> u16 {
+///         1
+///     }
+///
+///     fn sample_rate(&self) -> u32 {
+///         44100
+///     }
+///
+///     fn next_sample(&mut self) -> Option<f32> {
+///         if self.sample == 44100 {
+///             return None;
+///         }
+///         self.sample += 1;
+///         let t = self.sample as f32 / 44100.0;
+///         Some((t * self.frequency * std::f32::consts::TAU).sin())
+///     }
+/// }
+/// ```
+pub trait AudioStream: Send + 'static {
+    /// The number of channels of the samples, read once when the stream starts playing
+    fn channels(&self) -> u16;
+
+    /// The number of samples per second for each channel, read once when the stream starts
+    /// playing
+    fn sample_rate(&self) -> u32;
+
+    /// Generates the next sample, between `-1.0` and `1.0`, or `None` to end the sound
+    ///
+    /// The samples of the channels are interleaved: with two channels, the samples alternate
+    /// between the left and the right one.
+    fn next_sample(&mut self) -> Option<f32>;
+}
+
+/// The samples written to a [`BufferedAudioStream`] and not played yet
+#[derive(Default)]
+struct StreamBuffer {
+    samples: VecDeque<f32>,
+    closed: bool,
+}
+
+/// An [`AudioStream`] playing the samples written to it from another place, like the voice
+/// chat or a network stream, with an [`AudioStreamWriter`]
+///
+/// While no samples are written, the stream plays silence. It ends once its writer is dropped
+/// and all the samples are played.
+///
+/// ```
+/// # use bevy_ecs::system::{Commands, Res, Resource};
+/// # use bevy_audio::{Audio, AudioStreamWriter, BufferedAudioStream, PlaybackSettings};
+/// /// Where the received voice chat is written
+/// #[derive(Resource)]
+/// struct VoiceChat(AudioStreamWriter);
+///
+/// fn start_voice_chat(mut commands: Commands, audio: Res<Audio>) {
+///     let (stream, writer) = BufferedAudioStream::new(1, 48000);
+///     audio.play_stream(stream, PlaybackSettings::ONCE);
+///     commands.insert_resource(VoiceChat(writer));
+/// }
+/// ```
+pub struct BufferedAudioStream {
+    channels: u16,
+    sample_rate: u32,
+    shared: Arc<Mutex<StreamBuffer>>,
+    /// The samples taken from the shared buffer, to play them without locking it
+    samples: VecDeque<f32>,
+}
+
+impl BufferedAudioStream {
+    /// Creates a stream with a number of channels and a sample rate, and the writer to send it
+    /// samples
+    pub fn new(channels: u16, sample_rate: u32) -> (Self, AudioStreamWriter) {
+        let shared = Arc::new(Mutex::new(StreamBuffer::default()));
+        let stream = Self {
+            channels,
+            sample_rate,
+            shared: shared.clone(),
+            samples: VecDeque::new(),
+        };
+        (stream, AudioStreamWriter { shared })
+    }
+}
+
+impl AudioStream for BufferedAudioStream {
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn next_sample(&mut self) -> Option<f32> {
+        if self.samples.is_empty() {
+            let mut shared = self.shared.lock();
+            if shared.samples.is_empty() {
+                return if shared.closed { None } else { Some(0.0) };
+            }
+            std::mem::swap(&mut self.samples, &mut shared.samples);
+        }
+        self.samples.pop_front()
+    }
+}
+
+/// Writes samples to a [`BufferedAudioStream`], ending it when dropped
+pub struct AudioStreamWriter {
+    shared: Arc<Mutex<StreamBuffer>>,
+}
+
+impl AudioStreamWriter {
+    /// Adds samples to be played after the ones already written, interleaved between the
+    /// channels of the stream
+    pub fn write(&self, samples: &[f32]) {
+        self.shared.lock().samples.extend(samples);
+    }
+
+    /// The number of samples written and not played yet, to keep the latency in check
+    pub fn buffered(&self) -> usize {
+        self.shared.lock().samples.len()
+    }
+}
+
+impl Drop for AudioStreamWriter {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+    }
+}
+
+/// Plays an [`AudioStream`] as a rodio source
+pub(crate) struct StreamSource {
+    stream: Box<dyn AudioStream>,
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl StreamSource {
+    pub(crate) fn new(stream: Box<dyn AudioStream>) -> Self {
+        Self {
+            channels: stream.channels().max(1),
+            sample_rate: stream.sample_rate().max(1),
+            stream,
+        }
+    }
+}
+
+impl Iterator for StreamSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.stream.next_sample()
+    }
+}
+
+impl Source for StreamSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
@@ -0,0 +1,367 @@
+use bevy_ecs::system::{Res, Resource};
+use bevy_utils::{tracing::warn, HashMap};
+use parking_lot::RwLock;
+use rodio::{cpal::Sample as _, Sample, Source};
+use std::{borrow::Cow, f32::consts::TAU, sync::Arc, time::Duration};
+
+/// The name of an audio bus in the [`AudioBuses`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AudioBusId(pub Cow<'static, str>);
+
+impl AudioBusId {
+    /// The bus every other bus goes through
+    pub const MASTER: AudioBusId = AudioBusId(Cow::Borrowed("master"));
+    /// A bus for the music
+    pub const MUSIC: AudioBusId = AudioBusId(Cow::Borrowed("music"));
+    /// A bus for the sound effects
+    pub const SFX: AudioBusId = AudioBusId(Cow::Borrowed("sfx"));
+    /// A bus for the voices
+    pub const VOICE: AudioBusId = AudioBusId(Cow::Borrowed("voice"));
+}
+
+impl From<&'static str> for AudioBusId {
+    fn from(name: &'static str) -> Self {
+        AudioBusId(Cow::Borrowed(name))
+    }
+}
+
+impl From<String> for AudioBusId {
+    fn from(name: String) -> Self {
+        AudioBusId(Cow::Owned(name))
+    }
+}
+
+/// The volume and the effects of an audio bus, applied to every sound played on it or on the
+/// buses going through it
+#[derive(Clone, Debug)]
+pub struct AudioBusSettings {
+    /// The volume of the bus, multiplying the samples going through it
+    pub volume: f32,
+    /// Silences the bus, keeping its volume
+    pub muted: bool,
+    /// The cutoff frequency of a low-pass filter, in Hz, to muffle the sounds
+    pub low_pass: Option<f32>,
+    /// How much of the sounds is sent to a reverb, from `0.0` for none to `1.0` for as much as the
+    /// dry sound
+    pub reverb: f32,
+}
+
+impl Default for AudioBusSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            low_pass: None,
+            reverb: 0.0,
+        }
+    }
+}
+
+struct AudioBus {
+    parent: Option<AudioBusId>,
+    settings: AudioBusSettings,
+    /// The settings read by the sounds playing on the audio thread
+    shared: Arc<RwLock<AudioBusSettings>>,
+}
+
+/// The audio buses that the sounds are mixed through, set with
+/// [`PlaybackSettings::with_bus`](crate::PlaybackSettings::with_bus)
+///
+/// Each bus goes through its parent, up to [`AudioBusId::MASTER`]. The music, sound effect and
+/// voice buses are there by default.
+///
+/// ```
+/// # use bevy_ecs::system::ResMut;
+/// # use bevy_audio::{AudioBuses, AudioBusId};
+/// // Muffles everything while the game is paused
+/// fn pause(mut audio_buses: ResMut<AudioBuses>) {
+///     let master = audio_buses.get_mut(&AudioBusId::MASTER).unwrap();
+///     master.low_pass = Some(600.0);
+///     master.reverb = 0.3;
+/// }
+/// ```
+#[derive(Resource)]
+pub struct AudioBuses {
+    buses: HashMap<AudioBusId, AudioBus>,
+}
+
+impl Default for AudioBuses {
+    fn default() -> Self {
+        let mut buses = Self {
+            buses: HashMap::default(),
+        };
+        buses.insert(AudioBusId::MASTER, None);
+        buses.add(AudioBusId::MUSIC, AudioBusId::MASTER);
+        buses.add(AudioBusId::SFX, AudioBusId::MASTER);
+        buses.add(AudioBusId::VOICE, AudioBusId::MASTER);
+        buses
+    }
+}
+
+impl AudioBuses {
+    /// Adds a bus going through a parent one, or moves an existing bus under it
+    ///
+    /// The sounds already playing keep going through the previous parent.
+    pub fn add(&mut self, id: impl Into<AudioBusId>, parent: impl Into<AudioBusId>) {
+        let id = id.into();
+        let mut parent = parent.into();
+        if id == AudioBusId::MASTER {
+            warn!("The master audio bus can't have a parent");
+            return;
+        }
+        if !self.buses.contains_key(&parent) || self.ancestors(&parent).any(|bus| *bus == id) {
+            warn!(
+                "Can't add the audio bus {:?} under {:?}, adding it under the master bus",
+                id.0, parent.0
+            );
+            parent = AudioBusId::MASTER;
+        }
+        self.insert(id, Some(parent));
+    }
+
+    /// The settings of a bus
+    pub fn get(&self, id: &AudioBusId) -> Option<&AudioBusSettings> {
+        self.buses.get(id).map(|bus| &bus.settings)
+    }
+
+    /// The settings of a bus, to change its volume or effects
+    pub fn get_mut(&mut self, id: &AudioBusId) -> Option<&mut AudioBusSettings> {
+        self.buses.get_mut(id).map(|bus| &mut bus.settings)
+    }
+
+    /// The bus that a bus goes through, `None` for the master bus
+    pub fn parent(&self, id: &AudioBusId) -> Option<&AudioBusId> {
+        self.buses.get(id).and_then(|bus| bus.parent.as_ref())
+    }
+
+    /// The ids of all the buses
+    pub fn ids(&self) -> impl Iterator<Item = &AudioBusId> {
+        self.buses.keys()
+    }
+
+    fn insert(&mut self, id: AudioBusId, parent: Option<AudioBusId>) {
+        match self.buses.get_mut(&id) {
+            Some(bus) => bus.parent = parent,
+            None => {
+                let settings = AudioBusSettings::default();
+                self.buses.insert(
+                    id,
+                    AudioBus {
+                        parent,
+                        shared: Arc::new(RwLock::new(settings.clone())),
+                        settings,
+                    },
+                );
+            }
+        }
+    }
+
+    /// A bus and the ones it goes through
+    fn ancestors<'a>(&'a self, id: &'a AudioBusId) -> impl Iterator<Item = &'a AudioBusId> {
+        std::iter::successors(Some(id), |id| self.parent(id))
+    }
+
+    /// Applies the effects of a bus and of its ancestors to a sound, or of the master bus if it
+    /// doesn't exist
+    pub(crate) fn apply<S>(&self, id: &AudioBusId, input: S) -> BusEffects<S>
+    where
+        S: Source,
+        S::Item: Sample,
+    {
+        let id = if self.buses.contains_key(id) {
+            id
+        } else {
+            warn!("Unknown audio bus {:?}, playing on the master bus", id.0);
+            &AudioBusId::MASTER
+        };
+        let chain = self
+            .ancestors(id)
+            .map(|id| BusState::new(self.buses[id].shared.clone()))
+            .collect();
+        BusEffects::new(input, chain)
+    }
+}
+
+/// Sends the changes to the [`AudioBuses`] to the sounds that are playing
+pub fn update_audio_buses_system(audio_buses: Res<AudioBuses>) {
+    if !audio_buses.is_changed() {
+        return;
+    }
+    for bus in audio_buses.buses.values() {
+        *bus.shared.write() = bus.settings.clone();
+    }
+}
+
+/// How many samples are processed between two reads of the settings of the buses
+const SETTINGS_REFRESH_SAMPLES: usize = 1024;
+
+/// The delays of the comb filters of the reverb, in seconds, chosen not to have common multiples
+const REVERB_DELAYS: [f32; 4] = [0.0297, 0.0371, 0.0411, 0.0437];
+
+/// How much of the output of a comb filter is fed back into it, setting the length of the reverb
+const REVERB_FEEDBACK: f32 = 0.75;
+
+/// A bus that a sound goes through, with the state of its effects for this sound
+///
+/// The effects being linear, applying them to each sound gives the same result as applying them
+/// once to the mix of the sounds of the bus.
+struct BusState {
+    shared: Arc<RwLock<AudioBusSettings>>,
+    settings: AudioBusSettings,
+    /// The last output of the low-pass filter, for each channel
+    low_pass: Vec<f32>,
+    /// The delay lines of the comb filters of the reverb for each channel, allocated when the
+    /// reverb is first used
+    reverb: Vec<[CombFilter; 4]>,
+}
+
+impl BusState {
+    fn new(shared: Arc<RwLock<AudioBusSettings>>) -> Self {
+        let settings = shared.read().clone();
+        Self {
+            settings,
+            shared,
+            low_pass: Vec::new(),
+            reverb: Vec::new(),
+        }
+    }
+
+    fn process(
+        &mut self,
+        mut sample: f32,
+        channel: usize,
+        channels: usize,
+        sample_rate: u32,
+    ) -> f32 {
+        let settings = &self.settings;
+        if let Some(cutoff) = settings.low_pass {
+            self.low_pass.resize(channels, 0.0);
+            let alpha = 1.0 - (-TAU * cutoff / sample_rate as f32).exp();
+            let last = &mut self.low_pass[channel];
+            *last += alpha * (sample - *last);
+            sample = *last;
+        }
+        if settings.reverb > 0.0 {
+            if self.reverb.len() != channels {
+                self.reverb = (0..channels)
+                    .map(|_| REVERB_DELAYS.map(|delay| CombFilter::new(delay, sample_rate)))
+                    .collect();
+            }
+            let wet: f32 = self.reverb[channel]
+                .iter_mut()
+                .map(|comb| comb.process(sample))
+                .sum();
+            sample += wet / REVERB_DELAYS.len() as f32 * settings.reverb;
+        }
+        if settings.muted {
+            0.0
+        } else {
+            sample * settings.volume
+        }
+    }
+}
+
+/// A delay line feeding its output back into itself
+struct CombFilter {
+    buffer: Vec<f32>,
+    position: usize,
+}
+
+impl CombFilter {
+    fn new(delay: f32, sample_rate: u32) -> Self {
+        Self {
+            buffer: vec![0.0; ((delay * sample_rate as f32) as usize).max(1)],
+            position: 0,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let output = self.buffer[self.position];
+        self.buffer[self.position] = input + output * REVERB_FEEDBACK;
+        self.position = (self.position + 1) % self.buffer.len();
+        output
+    }
+}
+
+/// A sound going through the effects of audio buses
+pub(crate) struct BusEffects<S> {
+    input: S,
+    chain: Vec<BusState>,
+    /// The channel of the next sample
+    channel: usize,
+    channels: usize,
+    sample_rate: u32,
+    samples_until_refresh: usize,
+}
+
+impl<S> BusEffects<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn new(input: S, chain: Vec<BusState>) -> Self {
+        Self {
+            channels: input.channels() as usize,
+            sample_rate: input.sample_rate(),
+            input,
+            chain,
+            channel: 0,
+            samples_until_refresh: SETTINGS_REFRESH_SAMPLES,
+        }
+    }
+}
+
+impl<S> Iterator for BusEffects<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        // The settings and the format of the input are read between two samples of the first
+        // channel, so that all the channels of a frame are processed with the same ones
+        if self.channel == 0 && self.samples_until_refresh == 0 {
+            self.channels = self.input.channels() as usize;
+            self.sample_rate = self.input.sample_rate();
+            for bus in &mut self.chain {
+                bus.settings = bus.shared.read().clone();
+            }
+            self.samples_until_refresh = SETTINGS_REFRESH_SAMPLES;
+        }
+        self.samples_until_refresh = self.samples_until_refresh.saturating_sub(1);
+
+        let mut sample = self.input.next()?.to_f32();
+        for bus in &mut self.chain {
+            sample = bus.process(sample, self.channel, self.channels, self.sample_rate);
+        }
+        self.channel = (self.channel + 1) % self.channels.max(1);
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for BusEffects<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
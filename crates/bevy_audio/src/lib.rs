@@ -25,23 +25,37 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod audio_stream;
+mod bus;
+mod playback;
+mod spatial;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable, PlaybackSettings};
+    pub use crate::{
+        Audio, AudioBusId, AudioBuses, AudioListener, AudioOutput, AudioSource, AudioStream,
+        Decodable, PlaybackSettings, SpatialAudioSink, SpatialAudioSource,
+    };
 }
 
 pub use audio::*;
 pub use audio_output::*;
 pub use audio_source::*;
+pub use audio_stream::*;
+pub use bus::*;
+use playback::*;
+pub use spatial::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
+use bevy_ecs::schedule::IntoSystemDescriptor;
+use bevy_transform::TransformSystem;
 
 /// Adds support for audio playback to a Bevy Application
 ///
-/// Use the [`Audio`] resource to play audio.
+/// Use the [`Audio`] resource to play audio, or the [`SpatialAudioSource`] component to play it
+/// from the position of an entity. Generated samples are played as an [`AudioStream`].
 #[derive(Default)]
 pub struct AudioPlugin;
 
@@ -51,9 +65,21 @@ impl Plugin for AudioPlugin {
             .add_asset::<AudioSource>()
             .add_asset::<AudioSink>()
             .init_resource::<Audio<AudioSource>>()
+            .init_resource::<AudioBuses>()
+            .add_system_to_stage(CoreStage::PostUpdate, update_audio_buses_system)
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                play_queued_audio_system::<AudioSource>,
+                play_queued_audio_system::<AudioSource>.after(update_audio_buses_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                play_spatial_audio_system::<AudioSource>
+                    .after(update_audio_buses_system)
+                    .after(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_spatial_audio_system.after(TransformSystem::TransformPropagate),
             );
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
@@ -0,0 +1,428 @@
+use crate::PlaybackSettings;
+use parking_lot::Mutex;
+use rodio::{cpal::Sample as _, Sample, Source};
+use std::{sync::Arc, time::Duration};
+
+/// How many frames are played between two synchronizations with the [`PlaybackControl`]
+const SYNC_FRAMES: usize = 512;
+
+/// The section of a sound that is repeated, the end being the end of the sound if `None`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LoopPoints {
+    start: Duration,
+    end: Option<Duration>,
+}
+
+/// A change of the volume over time
+#[derive(Clone, Copy, Debug)]
+struct Fade {
+    /// The volume to start from, or the current one if `None`
+    from: Option<f32>,
+    target: f32,
+    duration: Duration,
+    /// Ends the sound once the target is reached
+    stop: bool,
+}
+
+/// The playback state shared between a sink and its sound on the audio thread
+#[derive(Debug)]
+struct SharedPlayback {
+    /// The position of the sound, updated by the audio thread
+    position: Duration,
+    /// The fade multiplier of the volume, updated by the audio thread
+    gain: f32,
+    loop_points: Option<LoopPoints>,
+    /// A position to go to, taken by the audio thread
+    seek: Option<Duration>,
+    /// A fade to start, taken by the audio thread
+    fade: Option<Fade>,
+}
+
+/// Controls the position, loop points and fades of a sound playing on the audio thread, shared
+/// by the [`AudioSink`](crate::AudioSink) and the [`SpatialAudioSink`](crate::SpatialAudioSink)
+pub(crate) struct PlaybackControl {
+    shared: Arc<Mutex<SharedPlayback>>,
+}
+
+impl PlaybackControl {
+    pub(crate) fn position(&self) -> Duration {
+        self.shared.lock().position
+    }
+
+    pub(crate) fn seek(&self, position: Duration) {
+        let mut shared = self.shared.lock();
+        shared.position = position;
+        shared.seek = Some(position);
+    }
+
+    pub(crate) fn loop_points(&self) -> Option<(Duration, Option<Duration>)> {
+        self.shared
+            .lock()
+            .loop_points
+            .map(|points| (points.start, points.end))
+    }
+
+    pub(crate) fn set_loop_points(&self, start: Duration, end: Option<Duration>) {
+        self.shared.lock().loop_points = Some(LoopPoints { start, end });
+    }
+
+    pub(crate) fn stop_looping(&self) {
+        self.shared.lock().loop_points = None;
+    }
+
+    pub(crate) fn fade_volume(&self) -> f32 {
+        self.shared.lock().gain
+    }
+
+    pub(crate) fn fade(&self, from: Option<f32>, target: f32, duration: Duration, stop: bool) {
+        self.shared.lock().fade = Some(Fade {
+            from,
+            target,
+            duration,
+            stop,
+        });
+    }
+}
+
+/// A sound that can be moved to another position, looped between two points and faded, following
+/// its [`PlaybackControl`]
+pub(crate) struct Playback<S> {
+    current: S,
+    /// Creates the sound again from its start, to go back in it, or `None` if it can't be
+    restart: Option<Box<dyn Fn() -> S + Send>>,
+    shared: Arc<Mutex<SharedPlayback>>,
+    loop_points: Option<LoopPoints>,
+    /// The number of samples played since the start of the sound, for all the channels
+    played: u64,
+    gain: f32,
+    fade: Option<Fade>,
+    /// How much the gain changes at each frame to reach the target of the fade
+    fade_step: f32,
+    /// The channel of the next sample
+    channel: u16,
+    frames_until_sync: usize,
+}
+
+impl<T> Playback<Box<dyn Source<Item = T> + Send>>
+where
+    T: Sample + Send + Sync + 'static,
+{
+    /// Plays a sound with its settings, keeping the samples that were decoded to go back in it
+    /// if it repeats or is seekable, and only moving it forward otherwise
+    pub(crate) fn new<I>(source: I, settings: &PlaybackSettings) -> (Self, PlaybackControl)
+    where
+        I: Source<Item = T> + Send + 'static,
+    {
+        if settings.repeat || settings.seekable {
+            let start = source.buffered();
+            let restart = move || Box::new(start.clone()) as Box<dyn Source<Item = T> + Send>;
+            Self::with_restart(restart(), Some(Box::new(restart)), settings)
+        } else {
+            Self::with_restart(Box::new(source), None, settings)
+        }
+    }
+}
+
+impl<S> Playback<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    /// Plays a sound that can't be played again from its start, like a stream, which can only
+    /// be faded and moved forward
+    pub(crate) fn forward_only(source: S, settings: &PlaybackSettings) -> (Self, PlaybackControl) {
+        Self::with_restart(source, None, settings)
+    }
+
+    fn with_restart(
+        current: S,
+        restart: Option<Box<dyn Fn() -> S + Send>>,
+        settings: &PlaybackSettings,
+    ) -> (Self, PlaybackControl) {
+        let loop_points = settings.repeat.then_some(LoopPoints {
+            start: Duration::ZERO,
+            end: None,
+        });
+        let (gain, fade) = if settings.fade_in.is_zero() {
+            (1.0, None)
+        } else {
+            let fade = Fade {
+                from: None,
+                target: 1.0,
+                duration: settings.fade_in,
+                stop: false,
+            };
+            (0.0, Some(fade))
+        };
+        let shared = Arc::new(Mutex::new(SharedPlayback {
+            position: Duration::ZERO,
+            gain,
+            loop_points,
+            seek: None,
+            fade: None,
+        }));
+        let mut playback = Self {
+            current,
+            restart,
+            shared: shared.clone(),
+            loop_points,
+            played: 0,
+            gain,
+            fade: None,
+            fade_step: 0.0,
+            channel: 0,
+            frames_until_sync: SYNC_FRAMES,
+        };
+        if let Some(fade) = fade {
+            playback.start_fade(fade);
+        }
+        (playback, PlaybackControl { shared })
+    }
+
+    fn frame_rate(&self) -> f64 {
+        self.current.sample_rate().max(1) as f64
+    }
+
+    fn channels(&self) -> u64 {
+        self.current.channels().max(1) as u64
+    }
+
+    fn played_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.played as f64 / self.channels() as f64 / self.frame_rate())
+    }
+
+    /// Reads the changes made through the [`PlaybackControl`], and shares the state of the sound
+    fn sync(&mut self) {
+        let (seek, fade) = {
+            let mut shared = self.shared.lock();
+            self.loop_points = shared.loop_points;
+            (shared.seek.take(), shared.fade.take())
+        };
+        if let Some(position) = seek {
+            self.seek(position);
+        }
+        if let Some(fade) = fade {
+            self.start_fade(fade);
+        }
+        let mut shared = self.shared.lock();
+        if shared.seek.is_none() {
+            shared.position = self.played_duration();
+        }
+        shared.gain = self.gain;
+    }
+
+    fn start_fade(&mut self, fade: Fade) {
+        if let Some(from) = fade.from {
+            self.gain = from;
+        }
+        let frames = fade.duration.as_secs_f64() * self.frame_rate();
+        self.fade_step = if frames < 1.0 {
+            f32::INFINITY
+        } else {
+            ((fade.target - self.gain).abs() as f64 / frames) as f32
+        };
+        self.fade = Some(fade);
+    }
+
+    /// Moves the sound to a position, going back to its start first if the position is before
+    /// the current one
+    fn seek(&mut self, position: Duration) {
+        let channels = self.channels();
+        let target = (position.as_secs_f64() * self.frame_rate()) as u64 * channels;
+        if target < self.played {
+            match &self.restart {
+                Some(restart) => {
+                    self.current = restart();
+                    self.played = 0;
+                }
+                None => return,
+            }
+        }
+        while self.played < target && self.current.next().is_some() {
+            self.played += 1;
+        }
+        self.channel = 0;
+    }
+
+    /// Goes back to the start of the loop, returning `false` if the sound doesn't loop
+    fn restart_loop(&mut self) -> bool {
+        match self.loop_points {
+            Some(loop_points) if self.restart.is_some() => {
+                self.seek(loop_points.start);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the gain towards the target of the fade, returning `false` if the sound ends
+    fn step_fade(&mut self) -> bool {
+        if let Some(fade) = self.fade {
+            if (fade.target - self.gain).abs() <= self.fade_step {
+                self.gain = fade.target;
+                self.fade = None;
+                return !fade.stop;
+            }
+            self.gain += self.fade_step.copysign(fade.target - self.gain);
+        }
+        true
+    }
+}
+
+impl<S> Iterator for Playback<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            if self.frames_until_sync == 0 {
+                self.sync();
+                self.frames_until_sync = SYNC_FRAMES;
+            }
+            self.frames_until_sync -= 1;
+
+            if !self.step_fade() {
+                return None;
+            }
+            if let Some(LoopPoints {
+                start,
+                end: Some(end),
+            }) = self.loop_points
+            {
+                if end > start && self.played_duration() >= end {
+                    self.restart_loop();
+                }
+            }
+        }
+
+        let sample = match self.current.next() {
+            Some(sample) => sample,
+            // Only tries to loop once, in case the start of the loop is past the end of the sound
+            None if self.restart_loop() => self.current.next()?,
+            None => return None,
+        };
+        self.played += 1;
+        self.channel = (self.channel + 1) % self.channels() as u16;
+        Some(sample.to_f32() * self.gain)
+    }
+}
+
+impl<S> Source for Playback<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.current.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.current.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.current.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        if self.loop_points.is_some() {
+            None
+        } else {
+            self.current.total_duration()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    /// A mono sound at 1000 Hz whose samples are their index
+    fn counting_sound(len: usize) -> SamplesBuffer<f32> {
+        SamplesBuffer::new(1, 1000, (0..len).map(|i| i as f32).collect::<Vec<_>>())
+    }
+
+    /// Plays enough samples for the changes made through the control to be read
+    fn play_until_sync<S: Iterator<Item = f32>>(playback: &mut S) -> Vec<f32> {
+        playback.take(SYNC_FRAMES).collect()
+    }
+
+    #[test]
+    fn only_seekable_sounds_go_back() {
+        for seekable in [false, true] {
+            let settings = PlaybackSettings::ONCE.with_seekable(seekable);
+            let (mut playback, control) = Playback::new(counting_sound(2000), &settings);
+            play_until_sync(&mut playback);
+            assert_eq!(control.position(), Duration::ZERO);
+            playback.next();
+            assert_eq!(
+                control.position(),
+                Duration::from_millis(SYNC_FRAMES as u64)
+            );
+
+            // The seek is read before the last sample
+            control.seek(Duration::from_millis(10));
+            let played = play_until_sync(&mut playback);
+            let expected = if seekable { 10.0 } else { 1024.0 };
+            assert_eq!(played.last(), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn repeated_sounds_loop() {
+        let (playback, control) = Playback::new(counting_sound(100), &PlaybackSettings::LOOP);
+        assert_eq!(playback.total_duration(), None);
+        let played: Vec<f32> = playback.take(250).collect();
+        let expected: Vec<f32> = (0..250).map(|i| (i % 100) as f32).collect();
+        assert_eq!(played, expected);
+        assert_eq!(control.loop_points(), Some((Duration::ZERO, None)));
+    }
+
+    #[test]
+    fn loop_points_repeat_a_section() {
+        let settings = PlaybackSettings::ONCE.with_seekable(true);
+        let (mut playback, control) = Playback::new(counting_sound(2000), &settings);
+        control.set_loop_points(Duration::from_millis(100), Some(Duration::from_millis(200)));
+        // The loop points are read after the first samples, which are past the end of the loop
+        let played = play_until_sync(&mut playback);
+        assert_eq!(played.last(), Some(&511.0));
+        let looped: Vec<f32> = playback.by_ref().take(200).collect();
+        let expected: Vec<f32> = (0..200).map(|i| (100 + i % 100) as f32).collect();
+        assert_eq!(looped, expected);
+
+        control.stop_looping();
+        play_until_sync(&mut playback);
+        let rest: Vec<f32> = playback.collect();
+        assert_eq!(rest.last(), Some(&1999.0));
+    }
+
+    #[test]
+    fn fade_in_ramps_the_volume() {
+        let settings = PlaybackSettings::ONCE.with_fade_in(Duration::from_millis(100));
+        let sound = SamplesBuffer::new(1, 1000, vec![1.0; 200]);
+        let (playback, _) = Playback::new(sound, &settings);
+        let played: Vec<f32> = playback.collect();
+        assert_eq!(played.len(), 200);
+        assert!(played[0] > 0.0 && played[0] < 0.1);
+        assert!(played.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!((played[99] - 1.0).abs() < 1e-4);
+        assert_eq!(played[199], 1.0);
+    }
+
+    #[test]
+    fn fade_out_stops_the_sound() {
+        let sound = SamplesBuffer::new(1, 1000, vec![1.0; 2000]);
+        let (mut playback, control) = Playback::new(sound, &PlaybackSettings::ONCE);
+        control.fade(None, 0.0, Duration::from_millis(100), true);
+        play_until_sync(&mut playback);
+        // The fade starts once read, and the sound ends with it
+        let rest: Vec<f32> = playback.collect();
+        assert!((99..=101).contains(&rest.len()));
+        assert!(rest.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!(rest[rest.len() - 1] < 0.02);
+    }
+}
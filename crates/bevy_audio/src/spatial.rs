@@ -0,0 +1,291 @@
+use crate::{AudioBuses, AudioOutput, AudioSource, Decodable, PlaybackControl, PlaybackSettings};
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::{ChangeTrackers, With, Without},
+    system::{Commands, NonSend, Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_transform::components::GlobalTransform;
+use rodio::SpatialSink;
+use std::time::Duration;
+
+/// Plays a sound from the position of its entity, heard louder as the [`AudioListener`] gets
+/// closer, and from the side of the listener the entity is on
+///
+/// The sound starts playing once its source is loaded, and a [`SpatialAudioSink`] is then added
+/// to the entity to control the playback. Changing the source plays the new one instead, and
+/// removing this component stops the sound.
+///
+/// ```
+/// # use bevy_ecs::system::{Commands, Res};
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{PlaybackSettings, SpatialAudioSource};
+/// fn spawn_waterfall(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn(SpatialAudioSource::new(
+///         asset_server.load("waterfall.ogg"),
+///         PlaybackSettings::LOOP,
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct SpatialAudioSource<Source = AudioSource>
+where
+    Source: Asset + Decodable,
+{
+    /// The sound played
+    pub source: Handle<Source>,
+    /// How the sound is played
+    pub settings: PlaybackSettings,
+}
+
+impl SpatialAudioSource {
+    /// Plays an [`AudioSource`] from the position of the entity with some [`PlaybackSettings`]
+    pub fn new(source: Handle<AudioSource>, settings: PlaybackSettings) -> Self {
+        Self { source, settings }
+    }
+}
+
+/// The entity hearing the [`SpatialAudioSource`]s, usually the camera
+///
+/// Only one listener is supported. Without any, the sounds are heard from the origin of the
+/// world.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AudioListener {
+    /// The distance between the ears of the listener, along its local X axis, which can't be zero
+    pub ear_gap: f32,
+    /// A multiplier applied to the distances to the sounds
+    ///
+    /// The volume of a sound falls with the square of its distance past one unit, so a scale of
+    /// `0.1` keeps the sounds within 10 units of the listener at their full volume.
+    pub distance_scale: f32,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        Self {
+            ear_gap: 0.2,
+            distance_scale: 1.0,
+        }
+    }
+}
+
+/// Controls the playback of a [`SpatialAudioSource`], added to its entity once it starts playing
+///
+/// Dropping it, by despawning its entity or removing its [`SpatialAudioSource`], stops the sound.
+#[derive(Component)]
+pub struct SpatialAudioSink {
+    sink: SpatialSink,
+    control: PlaybackControl,
+}
+
+impl SpatialAudioSink {
+    /// Gets the volume of the sound, before it's attenuated by the distance to the listener.
+    ///
+    /// The value `1.0` is the "normal" volume (unfiltered input). Any value other than `1.0`
+    /// will multiply each sample by this value.
+    pub fn volume(&self) -> f32 {
+        self.sink.volume()
+    }
+
+    /// Changes the volume of the sound, before it's attenuated by the distance to the listener.
+    ///
+    /// The value `1.0` is the "normal" volume (unfiltered input). Any value other than `1.0`
+    /// will multiply each sample by this value.
+    pub fn set_volume(&self, volume: f32) {
+        self.sink.set_volume(volume);
+    }
+
+    /// Gets the speed of the sound.
+    ///
+    /// The value `1.0` is the "normal" speed (unfiltered input). Any value other than `1.0`
+    /// will change the play speed of the sound.
+    pub fn speed(&self) -> f32 {
+        self.sink.speed()
+    }
+
+    /// Changes the speed of the sound.
+    ///
+    /// The value `1.0` is the "normal" speed (unfiltered input). Any value other than `1.0`
+    /// will change the play speed of the sound.
+    pub fn set_speed(&self, speed: f32) {
+        self.sink.set_speed(speed);
+    }
+
+    /// Resumes playback of a paused sink.
+    ///
+    /// No effect if not paused.
+    pub fn play(&self) {
+        self.sink.play();
+    }
+
+    /// Pauses playback of this sink.
+    ///
+    /// No effect if already paused.
+    /// A paused sink can be resumed with [`play`](Self::play).
+    pub fn pause(&self) {
+        self.sink.pause();
+    }
+
+    /// Is this sink paused?
+    ///
+    /// Sinks can be paused and resumed using [`pause`](Self::pause) and [`play`](Self::play).
+    pub fn is_paused(&self) -> bool {
+        self.sink.is_paused()
+    }
+
+    /// Stops the sink.
+    ///
+    /// It won't be possible to restart it afterwards.
+    pub fn stop(&self) {
+        self.sink.stop();
+    }
+
+    /// Has the sound finished playing?
+    pub fn empty(&self) -> bool {
+        self.sink.empty()
+    }
+
+    /// The position in the sound, from its start.
+    pub fn position(&self) -> Duration {
+        self.control.position()
+    }
+
+    /// Moves the sound to a position, from its start.
+    ///
+    /// Only the sounds played in repeat or with
+    /// [`PlaybackSettings::seekable`](crate::PlaybackSettings::seekable) can go back, the
+    /// others can only be moved forward.
+    pub fn seek(&self, position: Duration) {
+        self.control.seek(position);
+    }
+
+    /// The start and the end of the section of the sound that is repeated, if it loops.
+    pub fn loop_points(&self) -> Option<(Duration, Option<Duration>)> {
+        self.control.loop_points()
+    }
+
+    /// Repeats the section of the sound between two positions, going back to `start` when
+    /// reaching `end`, or the end of the sound if `None`.
+    ///
+    /// The sound keeps playing from its current position, so an intro before `start` is only
+    /// played once. Only the sounds played in repeat or with
+    /// [`PlaybackSettings::seekable`](crate::PlaybackSettings::seekable) can go back to `start`.
+    pub fn set_loop_points(&self, start: Duration, end: Option<Duration>) {
+        self.control.set_loop_points(start, end);
+    }
+
+    /// Stops repeating the sound, letting it play until its end.
+    pub fn stop_looping(&self) {
+        self.control.stop_looping();
+    }
+
+    /// Gets the multiplier of the volume changed by the fades, from `0.0` to `1.0`.
+    pub fn fade_volume(&self) -> f32 {
+        self.control.fade_volume()
+    }
+
+    /// Fades the sound in from silence over a duration.
+    pub fn fade_in(&self, duration: Duration) {
+        self.control.fade(Some(0.0), 1.0, duration, false);
+    }
+
+    /// Fades the sound out over a duration, then stops it.
+    ///
+    /// Fading a sound in while fading another one out crossfades them.
+    pub fn fade_out(&self, duration: Duration) {
+        self.control.fade(None, 0.0, duration, true);
+    }
+
+    /// Fades the multiplier of the volume to a value over a duration, without changing the
+    /// [`volume`](Self::volume).
+    pub fn fade_to(&self, volume: f32, duration: Duration) {
+        self.control.fade(None, volume, duration, false);
+    }
+
+    fn set_positions(&self, emitter: Vec3, (left_ear, right_ear): (Vec3, Vec3)) {
+        self.sink.set_emitter_position(emitter.to_array());
+        self.sink.set_left_ear_position(left_ear.to_array());
+        self.sink.set_right_ear_position(right_ear.to_array());
+    }
+}
+
+/// The positions of the ears of the listener, scaled by its distance scale
+fn ear_positions(listener: Option<(&AudioListener, &GlobalTransform)>) -> (Vec3, Vec3, f32) {
+    let (listener, transform) = match listener {
+        Some((listener, transform)) => (*listener, *transform),
+        None => (AudioListener::default(), GlobalTransform::IDENTITY),
+    };
+    let scale = listener.distance_scale;
+    let offset = transform.right() * listener.ear_gap * scale / 2.0;
+    let center = transform.translation() * scale;
+    (center - offset, center + offset, scale)
+}
+
+/// Starts playing the [`SpatialAudioSource`]s whose source is loaded, adding a
+/// [`SpatialAudioSink`] to their entity, and stops the ones that were removed
+pub fn play_spatial_audio_system<Source: Asset + Decodable>(
+    mut commands: Commands,
+    audio_output: NonSend<AudioOutput<Source>>,
+    audio_sources: Option<Res<Assets<Source>>>,
+    audio_buses: Res<AudioBuses>,
+    listener_query: Query<(&AudioListener, &GlobalTransform)>,
+    source_query: Query<(
+        Entity,
+        &SpatialAudioSource<Source>,
+        ChangeTrackers<SpatialAudioSource<Source>>,
+        Option<&SpatialAudioSink>,
+        Option<&GlobalTransform>,
+    )>,
+    removed_query: Query<Entity, (With<SpatialAudioSink>, Without<SpatialAudioSource<Source>>)>,
+) {
+    for entity in &removed_query {
+        commands.entity(entity).remove::<SpatialAudioSink>();
+    }
+
+    let audio_sources = match audio_sources {
+        Some(audio_sources) => audio_sources,
+        None => return,
+    };
+    let (left_ear, right_ear, scale) = ear_positions(listener_query.iter().next());
+    for (entity, spatial_source, tracker, sink, transform) in &source_query {
+        if sink.is_some() && !tracker.is_changed() {
+            continue;
+        }
+        match audio_sources.get(&spatial_source.source) {
+            Some(audio_source) => {
+                let emitter = transform.map_or(Vec3::ZERO, GlobalTransform::translation) * scale;
+                let sink = audio_output.play_spatial_source(
+                    audio_source,
+                    &spatial_source.settings,
+                    &audio_buses,
+                    emitter,
+                    (left_ear, right_ear),
+                );
+                if let Some((sink, control)) = sink {
+                    commands
+                        .entity(entity)
+                        .insert(SpatialAudioSink { sink, control });
+                }
+            }
+            // The new source hasn't loaded yet, stop the previous one until then
+            None if sink.is_some() => {
+                commands.entity(entity).remove::<SpatialAudioSink>();
+            }
+            None => {}
+        }
+    }
+}
+
+/// Moves the [`SpatialAudioSink`]s and the ears of the [`AudioListener`] to the positions of
+/// their entities
+pub fn update_spatial_audio_system(
+    listener_query: Query<(&AudioListener, &GlobalTransform)>,
+    sink_query: Query<(&SpatialAudioSink, &GlobalTransform)>,
+) {
+    let (left_ear, right_ear, scale) = ear_positions(listener_query.iter().next());
+    for (sink, transform) in &sink_query {
+        sink.set_positions(transform.translation() * scale, (left_ear, right_ear));
+    }
+}
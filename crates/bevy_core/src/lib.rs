@@ -2,17 +2,25 @@
 //! This crate provides core functionality for Bevy Engine.
 
 mod name;
+#[cfg(not(target_arch = "wasm32"))]
+mod task_commands;
 mod task_pool_options;
 
 use bevy_ecs::system::Resource;
 pub use bytemuck::{bytes_of, cast_slice, Pod, Zeroable};
 pub use name::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use task_commands::*;
 pub use task_pool_options::*;
 
 pub mod prelude {
     //! The Bevy Core Prelude.
     #[doc(hidden)]
     pub use crate::{DefaultTaskPoolOptions, Name};
+
+    #[doc(hidden)]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use crate::{EntityTaskCommandsExt, TaskCommandsExt};
 }
 
 use bevy_app::prelude::*;
@@ -45,6 +53,9 @@ impl Plugin for CorePlugin {
         register_math_types(app);
 
         app.init_resource::<FrameCount>();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_system_to_stage(CoreStage::PreUpdate, apply_completed_tasks);
     }
 }
 
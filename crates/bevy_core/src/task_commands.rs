@@ -0,0 +1,224 @@
+use bevy_ecs::{
+    prelude::*,
+    system::{CommandQueue, EntityCommands},
+};
+use bevy_tasks::{AsyncComputeTaskPool, Task};
+use futures_lite::future;
+use std::future::Future;
+
+type ApplyTask = Box<dyn FnOnce(&mut World, Entity) + Send + Sync>;
+
+/// A task running on the [`AsyncComputeTaskPool`], whose result is applied to the [`World`] by
+/// the [`apply_completed_tasks`] system once it completes.
+///
+/// Added by the methods of [`TaskCommandsExt`] and [`EntityTaskCommandsExt`]. Removing the
+/// component or despawning its entity cancels the task.
+#[derive(Component)]
+pub struct PendingTask(Task<ApplyTask>);
+
+impl PendingTask {
+    fn spawn(future: impl Future<Output = ApplyTask> + Send + 'static) -> Self {
+        Self(AsyncComputeTaskPool::get().spawn(future))
+    }
+}
+
+/// Extension trait for [`Commands`] spawning entities that run a task on the
+/// [`AsyncComputeTaskPool`].
+///
+/// ```
+/// # use bevy_core::TaskCommandsExt;
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Path(Vec<(i32, i32)>);
+///
+/// fn find_path(mut commands: Commands) {
+///     commands.spawn_task(async {
+///         // Compute the path over several frames
+///         Path(vec![(0, 0), (1, 0), (1, 1)])
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(find_path);
+/// ```
+pub trait TaskCommandsExt<'w, 's> {
+    /// Spawns an entity running the future, which inserts its output bundle on the entity when
+    /// it completes.
+    fn spawn_task<'a, B: Bundle>(
+        &'a mut self,
+        future: impl Future<Output = B> + Send + 'static,
+    ) -> EntityCommands<'w, 's, 'a>;
+
+    /// Spawns an entity running the future, which applies its output commands to the world when
+    /// it completes.
+    fn spawn_task_with_commands<'a>(
+        &'a mut self,
+        future: impl Future<Output = CommandQueue> + Send + 'static,
+    ) -> EntityCommands<'w, 's, 'a>;
+}
+
+impl<'w, 's> TaskCommandsExt<'w, 's> for Commands<'w, 's> {
+    fn spawn_task<'a, B: Bundle>(
+        &'a mut self,
+        future: impl Future<Output = B> + Send + 'static,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut entity = self.spawn_empty();
+        entity.insert_task(future);
+        entity
+    }
+
+    fn spawn_task_with_commands<'a>(
+        &'a mut self,
+        future: impl Future<Output = CommandQueue> + Send + 'static,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut entity = self.spawn_empty();
+        entity.insert_task_with_commands(future);
+        entity
+    }
+}
+
+/// Extension trait for [`EntityCommands`] running a task on the [`AsyncComputeTaskPool`] for an
+/// existing entity.
+///
+/// An entity runs a single task at a time: inserting another one cancels the previous task.
+pub trait EntityTaskCommandsExt {
+    /// Runs the future, which inserts its output bundle on the entity when it completes.
+    fn insert_task<B: Bundle>(
+        &mut self,
+        future: impl Future<Output = B> + Send + 'static,
+    ) -> &mut Self;
+
+    /// Runs the future, which applies its output commands to the world when it completes.
+    fn insert_task_with_commands(
+        &mut self,
+        future: impl Future<Output = CommandQueue> + Send + 'static,
+    ) -> &mut Self;
+}
+
+impl<'w, 's, 'a> EntityTaskCommandsExt for EntityCommands<'w, 's, 'a> {
+    fn insert_task<B: Bundle>(
+        &mut self,
+        future: impl Future<Output = B> + Send + 'static,
+    ) -> &mut Self {
+        self.insert(PendingTask::spawn(async move {
+            let bundle = future.await;
+            Box::new(move |world: &mut World, entity| {
+                if let Some(mut entity) = world.get_entity_mut(entity) {
+                    entity.insert(bundle);
+                }
+            }) as ApplyTask
+        }))
+    }
+
+    fn insert_task_with_commands(
+        &mut self,
+        future: impl Future<Output = CommandQueue> + Send + 'static,
+    ) -> &mut Self {
+        self.insert(PendingTask::spawn(async move {
+            let mut commands = future.await;
+            Box::new(move |world: &mut World, _| commands.apply(world)) as ApplyTask
+        }))
+    }
+}
+
+/// System that polls the [`PendingTask`]s, removing the completed ones from their entities and
+/// applying their results.
+pub fn apply_completed_tasks(mut commands: Commands, mut tasks: Query<(Entity, &mut PendingTask)>) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(apply) = future::block_on(future::poll_once(&mut task.0)) {
+            commands.entity(entity).remove::<PendingTask>();
+            commands.add(move |world: &mut World| apply(world, entity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CorePlugin;
+    use bevy_app::App;
+    use bevy_utils::{Duration, Instant};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Answer(u32);
+
+    #[derive(Resource)]
+    struct Done;
+
+    fn update_until_completed(app: &mut App, entity: Entity) {
+        while app.world.get::<PendingTask>(entity).is_some() {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn task_inserts_bundle() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin);
+        let entity = app.world.spawn_empty().id();
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &app.world)
+            .entity(entity)
+            .insert_task(async { Answer(42) });
+        queue.apply(&mut app.world);
+
+        update_until_completed(&mut app, entity);
+        assert_eq!(app.world.get::<Answer>(entity), Some(&Answer(42)));
+    }
+
+    #[test]
+    fn task_applies_commands() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin);
+        let mut queue = CommandQueue::default();
+        let entity = Commands::new(&mut queue, &app.world)
+            .spawn_task_with_commands(async {
+                let mut queue = CommandQueue::default();
+                queue.push(|world: &mut World| world.insert_resource(Done));
+                queue
+            })
+            .id();
+        queue.apply(&mut app.world);
+
+        update_until_completed(&mut app, entity);
+        assert!(app.world.contains_resource::<Done>());
+    }
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn despawn_cancels_task() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin);
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+        let mut queue = CommandQueue::default();
+        let entity = Commands::new(&mut queue, &app.world)
+            .spawn_task(async move {
+                let _guard = guard;
+                future::pending::<Answer>().await
+            })
+            .id();
+        queue.apply(&mut app.world);
+        app.update();
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        app.world.despawn(entity);
+        let start = Instant::now();
+        while !dropped.load(Ordering::SeqCst) {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "task wasn't canceled"
+            );
+            std::thread::yield_now();
+        }
+    }
+}
@@ -0,0 +1,70 @@
+struct ColorGrading {
+    lift: vec3<f32>,
+    gamma: vec3<f32>,
+    gain: vec3<f32>,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> color_grading: ColorGrading;
+#ifdef LUT
+@group(0) @binding(2)
+var lut_texture: texture_3d<f32>;
+#endif
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+fn srgb_from_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+fn linear_from_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+#ifdef LUT
+// Interpolates the lookup texture between the 8 texels around `color`
+fn sample_lut(color: vec3<f32>) -> vec3<f32> {
+    let size = textureDimensions(lut_texture);
+    let position = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)) * vec3<f32>(size - 1);
+    let base = min(vec3<i32>(position), size - 2);
+    let t = position - vec3<f32>(base);
+    let c000 = textureLoad(lut_texture, base, 0).rgb;
+    let c100 = textureLoad(lut_texture, base + vec3<i32>(1, 0, 0), 0).rgb;
+    let c010 = textureLoad(lut_texture, base + vec3<i32>(0, 1, 0), 0).rgb;
+    let c110 = textureLoad(lut_texture, base + vec3<i32>(1, 1, 0), 0).rgb;
+    let c001 = textureLoad(lut_texture, base + vec3<i32>(0, 0, 1), 0).rgb;
+    let c101 = textureLoad(lut_texture, base + vec3<i32>(1, 0, 1), 0).rgb;
+    let c011 = textureLoad(lut_texture, base + vec3<i32>(0, 1, 1), 0).rgb;
+    let c111 = textureLoad(lut_texture, base + vec3<i32>(1, 1, 1), 0).rgb;
+    let c00 = mix(c000, c100, t.x);
+    let c10 = mix(c010, c110, t.x);
+    let c01 = mix(c001, c101, t.x);
+    let c11 = mix(c011, c111, t.x);
+    return mix(mix(c00, c10, t.y), mix(c01, c11, t.y), t.z);
+}
+#endif
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let input = textureLoad(input_texture, vec2<i32>(position.xy), 0);
+
+    // The target decodes and encodes sRGB, the grading works on the encoded colors
+    var color = srgb_from_linear(clamp(input.rgb, vec3<f32>(0.0), vec3<f32>(1.0)));
+    color = color_grading.gain * (color + color_grading.lift * (1.0 - color));
+    color = pow(max(color, vec3<f32>(0.0)), 1.0 / color_grading.gamma);
+#ifdef LUT
+    color = sample_lut(color);
+#endif
+    return vec4<f32>(linear_from_srgb(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0))), input.a);
+}
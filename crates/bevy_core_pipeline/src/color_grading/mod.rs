@@ -0,0 +1,368 @@
+//! Color grading, for the cameras that opt into it with [`ColorGrading`].
+//!
+//! The main 3d pass of these cameras renders to an intermediate texture instead of their
+//! [`ViewTarget`], which the [`ColorGradingNode`] then grades onto the [`ViewTarget`] after all
+//! the other post-processing except FXAA. The colors are graded after they are tonemapped, in the sRGB
+//! color space grading tools author lookup tables in: first with the lift, gamma and gain of
+//! [`ColorGrading`], then with its lookup texture if it has one.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::Vec3;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, DynamicUniformBuffer, Extent3d,
+        FragmentState, MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor,
+        Shader, ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
+    texture::{Image, TextureCache},
+    view::{Msaa, ViewTarget},
+    RenderApp, RenderStage,
+};
+
+use crate::core_3d::Camera3d;
+
+pub const COLOR_GRADING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1259999056817053574);
+
+/// Add this component to a [`Camera3d`] to grade the colors it renders.
+///
+/// The default values leave the colors unchanged.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct ColorGrading {
+    /// Raises the darkest colors, with `1.0` raising black to white.
+    pub lift: Vec3,
+    /// The inverse exponent applied to the colors after their lift and gain: above `1.0`, it
+    /// brightens the midtones.
+    pub gamma: Vec3,
+    /// Scales the colors, and mostly the brightest ones.
+    pub gain: Vec3,
+    /// A 3D lookup texture mapping the graded colors to their final colors, with the red,
+    /// green and blue of a color as its coordinates.
+    ///
+    /// Lookup tables exported from grading tools can be loaded from `.cube` files, or from
+    /// KTX2 files with the `ktx2` feature. The texture has to be an uncompressed float or
+    /// normalized format, and the colors are graded without it until it is loaded.
+    pub lut: Option<Handle<Image>>,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            lift: Vec3::ZERO,
+            gamma: Vec3::ONE,
+            gain: Vec3::ONE,
+            lut: None,
+        }
+    }
+}
+
+impl ExtractComponent for ColorGrading {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+/// Grades the colors of the cameras with [`ColorGrading`].
+pub struct ColorGradingPlugin;
+
+impl Plugin for ColorGradingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            COLOR_GRADING_SHADER_HANDLE,
+            "color_grading.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ColorGrading>()
+            .add_plugin(ExtractComponentPlugin::<ColorGrading>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<ColorGradingPipeline>()
+            .init_resource::<SpecializedRenderPipelines<ColorGradingPipeline>>()
+            .init_resource::<ColorGradingUniforms>()
+            .add_system_to_stage(RenderStage::Prepare, prepare_color_grading_textures)
+            .add_system_to_stage(RenderStage::Prepare, prepare_color_grading_uniforms)
+            .add_system_to_stage(RenderStage::Queue, queue_color_grading_bind_groups);
+    }
+}
+
+/// The textures of the color grading of a view.
+#[derive(Component)]
+pub struct ViewColorGradingTextures {
+    /// The target the main 3d pass renders to instead of the [`ViewTarget`] of the view.
+    pub main_target: ViewTarget,
+}
+
+pub fn prepare_color_grading_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ExtractedCamera, &Msaa), With<ColorGrading>>,
+) {
+    for (entity, camera, msaa) in &views {
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let mut get_texture = |label, sample_count| {
+            texture_cache
+                .get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some(label),
+                        size: Extent3d {
+                            depth_or_array_layers: 1,
+                            width: physical_target_size.x,
+                            height: physical_target_size.y,
+                        },
+                        mip_level_count: 1,
+                        sample_count,
+                        dimension: TextureDimension::D2,
+                        format: **texture_format,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    },
+                )
+                .default_view
+        };
+        let input = get_texture("color_grading_input_texture", 1);
+        // The main pass resolves its samples to the input
+        let sampled_target =
+            (msaa.samples > 1).then(|| get_texture("color_grading_sampled_texture", msaa.samples));
+        commands.entity(entity).insert(ViewColorGradingTextures {
+            main_target: ViewTarget {
+                view: input,
+                sampled_target,
+            },
+        });
+    }
+}
+
+#[derive(Clone, ShaderType)]
+struct ColorGradingUniform {
+    lift: Vec3,
+    gamma: Vec3,
+    gain: Vec3,
+}
+
+#[derive(Resource, Default)]
+pub struct ColorGradingUniforms {
+    uniforms: DynamicUniformBuffer<ColorGradingUniform>,
+}
+
+#[derive(Component)]
+pub struct ColorGradingUniformOffset {
+    pub offset: u32,
+}
+
+pub fn prepare_color_grading_uniforms(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut color_grading_uniforms: ResMut<ColorGradingUniforms>,
+    views: Query<(Entity, &ColorGrading)>,
+) {
+    color_grading_uniforms.uniforms.clear();
+    for (entity, color_grading) in &views {
+        let offset = color_grading_uniforms.uniforms.push(ColorGradingUniform {
+            lift: color_grading.lift,
+            gamma: color_grading.gamma,
+            gain: color_grading.gain,
+        });
+        commands
+            .entity(entity)
+            .insert(ColorGradingUniformOffset { offset });
+    }
+    color_grading_uniforms
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// The pipeline and bind group grading the colors of a view.
+#[derive(Component)]
+pub struct ViewColorGradingPass {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct ColorGradingPipeline {
+    layout: BindGroupLayout,
+    lut_layout: BindGroupLayout,
+}
+
+impl ColorGradingPipeline {
+    fn layout(&self, lut: bool) -> &BindGroupLayout {
+        if lut {
+            &self.lut_layout
+        } else {
+            &self.layout
+        }
+    }
+}
+
+impl FromWorld for ColorGradingPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        // NOTE: The textures are loaded without a sampler, as float formats may not be filterable
+        let texture_entry = |binding, view_dimension| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled: false,
+                sample_type: TextureSampleType::Float { filterable: false },
+                view_dimension,
+            },
+            count: None,
+        };
+        let entries = [
+            texture_entry(0, TextureViewDimension::D2),
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ColorGradingUniform::min_size()),
+                },
+                count: None,
+            },
+            // Lookup texture
+            texture_entry(2, TextureViewDimension::D3),
+        ];
+        ColorGradingPipeline {
+            layout: render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("color_grading_layout"),
+                entries: &entries[..2],
+            }),
+            lut_layout: render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("color_grading_lut_layout"),
+                entries: &entries,
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorGradingPipelineKey {
+    pub format: TextureFormat,
+    /// Whether the colors are mapped with a lookup texture.
+    pub lut: bool,
+}
+
+impl SpecializedRenderPipeline for ColorGradingPipeline {
+    type Key = ColorGradingPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.lut {
+            shader_defs.push(String::from("LUT"));
+        }
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: COLOR_GRADING_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: COLOR_GRADING_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout(key.lut).clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("color_grading_pipeline".into()),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_color_grading_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    color_grading_pipeline: Res<ColorGradingPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ColorGradingPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    color_grading_uniforms: Res<ColorGradingUniforms>,
+    texture_format: Res<RenderTextureFormat>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<(Entity, &ViewColorGradingTextures, &ColorGrading)>,
+) {
+    let uniforms = match color_grading_uniforms.uniforms.binding() {
+        Some(uniforms) => uniforms,
+        None => return,
+    };
+    for (entity, textures, color_grading) in &views {
+        let lut = color_grading
+            .lut
+            .as_ref()
+            .and_then(|lut| images.get(lut))
+            .map(|lut| &lut.texture_view);
+        let pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &color_grading_pipeline,
+            ColorGradingPipelineKey {
+                format: **texture_format,
+                lut: lut.is_some(),
+            },
+        );
+        let mut entries = vec![
+            BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&textures.main_target.view),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: uniforms.clone(),
+            },
+        ];
+        if let Some(lut) = lut {
+            entries.push(BindGroupEntry {
+                binding: 2,
+                resource: BindingResource::TextureView(lut),
+            });
+        }
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("color_grading_bind_group"),
+            layout: color_grading_pipeline.layout(lut.is_some()),
+            entries: &entries,
+        });
+        commands.entity(entity).insert(ViewColorGradingPass {
+            pipeline,
+            bind_group,
+        });
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    color_grading::{ColorGradingUniformOffset, ViewColorGradingPass},
+    fxaa::ViewFxaaTextures,
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Grades the main 3d pass of the views with
+/// [`ColorGrading`](crate::color_grading::ColorGrading) onto their target, after the rest of
+/// their post-processing but before their FXAA if they have it.
+pub struct ColorGradingNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewColorGradingPass,
+            &'static ColorGradingUniformOffset,
+            Option<&'static ViewFxaaTextures>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl ColorGradingNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for ColorGradingNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(ColorGradingNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, target, pass, uniform_offset, fxaa_textures) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                Err(_) => {
+                    return Ok(());
+                } // No color grading for this view
+            };
+        // The FXAA is applied after the color grading
+        let output = fxaa_textures.map_or(&target.view, |textures| &textures.main_target.view);
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pass.pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _color_grading_pass_3d_span = info_span!("color_grading_pass_3d").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("color_grading_pass_3d"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(0, &pass.bind_group, &[uniform_offset.offset]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -8,12 +8,14 @@ pub mod graph {
     }
     pub mod node {
         pub const MAIN_PASS: &str = "main_pass";
+        pub const UPSCALING: &str = "upscaling";
     }
 }
 
 pub use camera_2d::*;
 pub use main_pass_2d_node::*;
 
+use crate::upscaling::UpscalingNode;
 use bevy_app::{App, Plugin};
 use bevy_ecs::prelude::*;
 use bevy_render::{
@@ -52,10 +54,12 @@ impl Plugin for Core2dPlugin {
             );
 
         let pass_node_2d = MainPass2dNode::new(&mut render_app.world);
+        let upscaling_node = UpscalingNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
 
         let mut draw_2d_graph = RenderGraph::default();
         draw_2d_graph.add_node(graph::node::MAIN_PASS, pass_node_2d);
+        draw_2d_graph.add_node(graph::node::UPSCALING, upscaling_node);
         let input_node_id = draw_2d_graph.set_input(vec![SlotInfo::new(
             graph::input::VIEW_ENTITY,
             SlotType::Entity,
@@ -68,6 +72,17 @@ impl Plugin for Core2dPlugin {
                 MainPass2dNode::IN_VIEW,
             )
             .unwrap();
+        draw_2d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::UPSCALING,
+                UpscalingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_2d_graph
+            .add_node_edge(graph::node::MAIN_PASS, graph::node::UPSCALING)
+            .unwrap();
         graph.add_sub_graph(graph::NAME, draw_2d_graph);
     }
 }
@@ -136,3 +151,88 @@ pub fn extract_core_2d_camera_phases(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_core::CorePlugin;
+    use bevy_math::UVec2;
+    use bevy_render::{
+        camera::RenderTarget,
+        color::Color,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        testing::render_single_frame_pixel,
+        texture::{BevyDefault, Image},
+        RenderPlugin,
+    };
+    use bevy_time::TimePlugin;
+    use bevy_window::{WindowPlugin, WindowSettings};
+
+    use super::*;
+    use crate::{clear_color::ClearColorConfig, CorePipelinePlugin};
+
+    /// An app with a 4x4 image cleared by a 2d camera to `color`.
+    fn clear_color_app(color: Color) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(TimePlugin)
+            .add_plugin(AssetPlugin)
+            .insert_resource(WindowSettings {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            })
+            .add_plugin(WindowPlugin)
+            .add_plugin(RenderPlugin)
+            .add_plugin(CorePipelinePlugin);
+
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            // Multisampled views resolve to textures of the default format
+            TextureFormat::bevy_default(),
+        );
+        image.texture_descriptor.usage |=
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
+        let image = app.world.resource_mut::<Assets<Image>>().add(image);
+
+        let camera = app
+            .world
+            .spawn(Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(image),
+                    ..Default::default()
+                },
+                camera_2d: Camera2d {
+                    clear_color: ClearColorConfig::Custom(color),
+                },
+                ..Default::default()
+            })
+            .id();
+        (app, camera)
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn renders_clear_color_to_image() {
+        let color = Color::rgb_u8(255, 0, 128);
+        let (mut app, camera) = clear_color_app(color);
+        assert_eq!(
+            render_single_frame_pixel(&mut app, camera, UVec2::new(2, 1)),
+            color
+        );
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    #[should_panic(expected = "outside of the 4x4 target image")]
+    fn reading_a_pixel_outside_of_the_image_panics() {
+        let (mut app, camera) = clear_color_app(Color::BLACK);
+        render_single_frame_pixel(&mut app, camera, UVec2::new(4, 0));
+    }
+}
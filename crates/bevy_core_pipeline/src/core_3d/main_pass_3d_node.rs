@@ -1,15 +1,29 @@
 use crate::{
     clear_color::{ClearColor, ClearColorConfig},
-    core_3d::{AlphaMask3d, Camera3d, Opaque3d, Transparent3d},
+    color_grading::ViewColorGradingTextures,
+    core_3d::{AlphaMask3d, Camera3d, Opaque3d, Transmissive3d, Transparent3d},
+    dof::ViewDepthOfFieldTextures,
+    fxaa::ViewFxaaTextures,
+    motion_blur::ViewMotionBlurTextures,
+    oit::{ViewOitComposite, ViewOitTextures},
+    prepass::Opaque3dPrepass,
+    skybox::ViewSkyboxPass,
+    ssr::ViewScreenSpaceReflectionsTextures,
+    taa::ViewTaaTextures,
+    transmission::ViewTransmissionTexture,
 };
 use bevy_ecs::prelude::*;
 use bevy_render::{
     camera::ExtractedCamera,
+    color::Color,
     render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
-    render_phase::{DrawFunctions, RenderPhase, TrackedRenderPass},
-    render_resource::{LoadOp, Operations, RenderPassDepthStencilAttachment, RenderPassDescriptor},
+    render_phase::{DrawFunctions, RenderPhase},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment,
+        RenderPassDepthStencilAttachment, RenderPassDescriptor,
+    },
     renderer::RenderContext,
-    view::{ExtractedView, ViewDepthTexture, ViewTarget},
+    view::{ExtractedView, ViewDepthTexture, ViewTarget, ViewUniformOffset},
 };
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -18,12 +32,28 @@ pub struct MainPass3dNode {
     query: QueryState<
         (
             &'static ExtractedCamera,
-            &'static RenderPhase<Opaque3d>,
-            &'static RenderPhase<AlphaMask3d>,
-            &'static RenderPhase<Transparent3d>,
+            (
+                &'static RenderPhase<Opaque3d>,
+                &'static RenderPhase<AlphaMask3d>,
+                &'static RenderPhase<Transmissive3d>,
+                &'static RenderPhase<Transparent3d>,
+            ),
             &'static Camera3d,
             &'static ViewTarget,
             &'static ViewDepthTexture,
+            Option<With<RenderPhase<Opaque3dPrepass>>>,
+            Option<&'static ViewOitTextures>,
+            Option<&'static ViewOitComposite>,
+            Option<(&'static ViewSkyboxPass, &'static ViewUniformOffset)>,
+            Option<&'static ViewTransmissionTexture>,
+            Option<&'static ViewScreenSpaceReflectionsTextures>,
+            Option<&'static ViewTaaTextures>,
+            Option<&'static ViewDepthOfFieldTextures>,
+            Option<&'static ViewMotionBlurTextures>,
+            (
+                Option<&'static ViewColorGradingTextures>,
+                Option<&'static ViewFxaaTextures>,
+            ),
         ),
         With<ExtractedView>,
     >,
@@ -55,13 +85,55 @@ impl Node for MainPass3dNode {
         world: &World,
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
-        let (camera, opaque_phase, alpha_mask_phase, transparent_phase, camera_3d, target, depth) =
-            match self.query.get_manual(world, view_entity) {
-                Ok(query) => query,
-                Err(_) => {
-                    return Ok(());
-                } // No window
-            };
+        let (
+            camera,
+            (opaque_phase, alpha_mask_phase, transmissive_phase, transparent_phase),
+            camera_3d,
+            target,
+            depth,
+            prepass,
+            oit_textures,
+            oit_composite,
+            skybox,
+            transmission_texture,
+            ssr_textures,
+            taa_textures,
+            dof_textures,
+            motion_blur_textures,
+            (color_grading_textures, fxaa_textures),
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No window
+        };
+        // With screen space reflections, temporal anti-aliasing, depth of field, motion blur,
+        // color grading or FXAA, the main pass renders to an intermediate target, resolved onto
+        // the view target by the `ScreenSpaceReflectionsCopyNode`, the `TaaNode`, the
+        // `DepthOfFieldNode`, the `MotionBlurNode`, the `ColorGradingNode` then the `FxaaNode`
+        let target = ssr_textures
+            .map(|textures| &textures.main_target)
+            .or_else(|| taa_textures.map(|textures| &textures.main_target))
+            .or_else(|| dof_textures.map(|textures| &textures.main_target))
+            .or_else(|| motion_blur_textures.map(|textures| &textures.main_target))
+            .or_else(|| color_grading_textures.map(|textures| &textures.main_target))
+            .or_else(|| fxaa_textures.map(|textures| &textures.main_target))
+            .unwrap_or(target);
+
+        // With transmissive meshes, the opaque scene is rendered to the transmission texture they
+        // read, then copied onto the target before they are drawn
+        let transmission = match transmission_texture {
+            Some(transmission_texture) if !transmissive_phase.items.is_empty() => world
+                .resource::<PipelineCache>()
+                .get_render_pipeline(transmission_texture.copy_pipeline)
+                .map(|copy_pipeline| (transmission_texture, copy_pipeline)),
+            _ => None,
+        };
+        let transmission_target = transmission.map(|(transmission_texture, _)| ViewTarget {
+            view: transmission_texture.view.clone(),
+            sampled_target: target.sampled_target.clone(),
+        });
+        let opaque_target = transmission_target.as_ref().unwrap_or(target);
 
         // Always run opaque pass to ensure screen is cleared
         {
@@ -73,7 +145,7 @@ impl Node for MainPass3dNode {
                 label: Some("main_opaque_pass_3d"),
                 // NOTE: The opaque pass loads the color
                 // buffer as well as writing to it.
-                color_attachments: &[Some(target.get_color_attachment(Operations {
+                color_attachments: &[Some(opaque_target.get_color_attachment(Operations {
                     load: match camera_3d.clear_color {
                         ClearColorConfig::Default => {
                             LoadOp::Clear(world.resource::<ClearColor>().0.into())
@@ -88,7 +160,12 @@ impl Node for MainPass3dNode {
                     // NOTE: The opaque main pass loads the depth buffer and possibly overwrites it
                     depth_ops: Some(Operations {
                         // NOTE: 0.0 is the far plane due to bevy's use of reverse-z projections.
-                        load: camera_3d.depth_load_op.clone().into(),
+                        // The prepass already cleared the depth buffer if there is one.
+                        load: if prepass.is_some() {
+                            LoadOp::Load
+                        } else {
+                            camera_3d.depth_load_op.clone().into()
+                        },
                         store: true,
                     }),
                     stencil_ops: None,
@@ -97,11 +174,8 @@ impl Node for MainPass3dNode {
 
             let draw_functions = world.resource::<DrawFunctions<Opaque3d>>();
 
-            let render_pass = render_context
-                .command_encoder
-                .begin_render_pass(&pass_descriptor);
             let mut draw_functions = draw_functions.write();
-            let mut tracked_pass = TrackedRenderPass::new(render_pass);
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
             if let Some(viewport) = camera.viewport.as_ref() {
                 tracked_pass.set_camera_viewport(viewport);
             }
@@ -119,7 +193,7 @@ impl Node for MainPass3dNode {
             let pass_descriptor = RenderPassDescriptor {
                 label: Some("main_alpha_mask_pass_3d"),
                 // NOTE: The alpha_mask pass loads the color buffer as well as overwriting it where appropriate.
-                color_attachments: &[Some(target.get_color_attachment(Operations {
+                color_attachments: &[Some(opaque_target.get_color_attachment(Operations {
                     load: LoadOp::Load,
                     store: true,
                 }))],
@@ -136,11 +210,8 @@ impl Node for MainPass3dNode {
 
             let draw_functions = world.resource::<DrawFunctions<AlphaMask3d>>();
 
-            let render_pass = render_context
-                .command_encoder
-                .begin_render_pass(&pass_descriptor);
             let mut draw_functions = draw_functions.write();
-            let mut tracked_pass = TrackedRenderPass::new(render_pass);
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
             if let Some(viewport) = camera.viewport.as_ref() {
                 tracked_pass.set_camera_viewport(viewport);
             }
@@ -150,7 +221,110 @@ impl Node for MainPass3dNode {
             }
         }
 
-        if !transparent_phase.items.is_empty() {
+        if let Some((skybox, view_uniform_offset)) = skybox {
+            if let Some(pipeline) = world
+                .resource::<PipelineCache>()
+                .get_render_pipeline(skybox.pipeline)
+            {
+                // Run the skybox pass, behind everything drawn so far
+                // NOTE: Scoped to drop the mutable borrow of render_context
+                #[cfg(feature = "trace")]
+                let _main_skybox_pass_3d_span = info_span!("main_skybox_pass_3d").entered();
+                let pass_descriptor = RenderPassDescriptor {
+                    label: Some("main_skybox_pass_3d"),
+                    color_attachments: &[Some(opaque_target.get_color_attachment(Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    }))],
+                    depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                        view: &depth.view,
+                        // NOTE: The skybox pass only tests against the depth buffer
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Load,
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    }),
+                };
+
+                let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+                if let Some(viewport) = camera.viewport.as_ref() {
+                    tracked_pass.set_camera_viewport(viewport);
+                }
+                tracked_pass.set_render_pipeline(pipeline);
+                tracked_pass.set_bind_group(0, &skybox.bind_group, &[view_uniform_offset.offset]);
+                tracked_pass.draw(0..3, 0..1);
+            }
+        }
+
+        if let Some((transmission_texture, copy_pipeline)) = transmission {
+            // NOTE: With MSAA, the samples of the opaque scene are kept in the sampled target,
+            // and resolved onto the target again by the transmissive pass
+            if target.sampled_target.is_none() {
+                // NOTE: Scoped to drop the mutable borrow of render_context
+                #[cfg(feature = "trace")]
+                let _main_transmission_copy_pass_3d_span =
+                    info_span!("main_transmission_copy_pass_3d").entered();
+                let pass_descriptor = RenderPassDescriptor {
+                    label: Some("main_transmission_copy_pass_3d"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: &target.view,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Load,
+                            store: true,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                };
+
+                let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+                if let Some(viewport) = camera.viewport.as_ref() {
+                    tracked_pass.set_camera_viewport(viewport);
+                }
+                tracked_pass.set_render_pipeline(copy_pipeline);
+                tracked_pass.set_bind_group(0, &transmission_texture.copy_bind_group, &[]);
+                tracked_pass.draw(0..3, 0..1);
+            }
+        }
+
+        if !transmissive_phase.items.is_empty() {
+            // Run the transmissive pass, sorted back-to-front
+            // NOTE: Scoped to drop the mutable borrow of render_context
+            #[cfg(feature = "trace")]
+            let _main_transmissive_pass_3d_span = info_span!("main_transmissive_pass_3d").entered();
+            let pass_descriptor = RenderPassDescriptor {
+                label: Some("main_transmissive_pass_3d"),
+                // NOTE: The transmissive pass loads the color buffer as well as overwriting it where appropriate.
+                color_attachments: &[Some(target.get_color_attachment(Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                }))],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &depth.view,
+                    // NOTE: The transmissive pass loads the depth buffer and possibly overwrites it
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            };
+
+            let draw_functions = world.resource::<DrawFunctions<Transmissive3d>>();
+
+            let mut draw_functions = draw_functions.write();
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+            if let Some(viewport) = camera.viewport.as_ref() {
+                tracked_pass.set_camera_viewport(viewport);
+            }
+            for item in &transmissive_phase.items {
+                let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
+                draw_function.draw(world, &mut tracked_pass, view_entity, item);
+            }
+        }
+
+        if !transparent_phase.items.is_empty() && oit_textures.is_none() {
             // Run the transparent pass, sorted back-to-front
             // NOTE: Scoped to drop the mutable borrow of render_context
             #[cfg(feature = "trace")]
@@ -180,11 +354,8 @@ impl Node for MainPass3dNode {
 
             let draw_functions = world.resource::<DrawFunctions<Transparent3d>>();
 
-            let render_pass = render_context
-                .command_encoder
-                .begin_render_pass(&pass_descriptor);
             let mut draw_functions = draw_functions.write();
-            let mut tracked_pass = TrackedRenderPass::new(render_pass);
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
             if let Some(viewport) = camera.viewport.as_ref() {
                 tracked_pass.set_camera_viewport(viewport);
             }
@@ -194,6 +365,89 @@ impl Node for MainPass3dNode {
             }
         }
 
+        if let (Some(oit_textures), Some(oit_composite)) = (oit_textures, oit_composite) {
+            if !transparent_phase.items.is_empty() {
+                // Run the order-independent transparent pass, then composite its result
+                // onto the target
+                #[cfg(feature = "trace")]
+                let _main_oit_pass_3d_span = info_span!("main_oit_pass_3d").entered();
+                let pipeline_cache = world.resource::<PipelineCache>();
+                // The transparent pipelines only draw to the order-independent targets, so
+                // the phase can't be drawn until the composite pipeline is ready
+                if let Some(composite_pipeline) =
+                    pipeline_cache.get_render_pipeline(oit_composite.pipeline)
+                {
+                    {
+                        // NOTE: Scoped to drop the mutable borrow of render_context
+                        let pass_descriptor = RenderPassDescriptor {
+                            label: Some("main_oit_pass_3d"),
+                            // NOTE: Nothing transparent is accumulated yet: no color, fully
+                            // revealed.
+                            color_attachments: &[
+                                Some(RenderPassColorAttachment {
+                                    view: &oit_textures.accum,
+                                    resolve_target: None,
+                                    ops: Operations {
+                                        load: LoadOp::Clear(Color::NONE.into()),
+                                        store: true,
+                                    },
+                                }),
+                                Some(RenderPassColorAttachment {
+                                    view: &oit_textures.revealage,
+                                    resolve_target: None,
+                                    ops: Operations {
+                                        load: LoadOp::Clear(Color::WHITE.into()),
+                                        store: true,
+                                    },
+                                }),
+                            ],
+                            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                                view: &depth.view,
+                                // NOTE: As for the sorted transparent pass, the depth buffer
+                                // is loaded so that opaque meshes occlude transparent ones.
+                                depth_ops: Some(Operations {
+                                    load: LoadOp::Load,
+                                    store: true,
+                                }),
+                                stencil_ops: None,
+                            }),
+                        };
+
+                        let draw_functions = world.resource::<DrawFunctions<Transparent3d>>();
+
+                        let mut draw_functions = draw_functions.write();
+                        let mut tracked_pass =
+                            render_context.begin_tracked_render_pass(&pass_descriptor);
+                        if let Some(viewport) = camera.viewport.as_ref() {
+                            tracked_pass.set_camera_viewport(viewport);
+                        }
+                        // NOTE: The order of the items doesn't matter here
+                        for item in &transparent_phase.items {
+                            let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
+                            draw_function.draw(world, &mut tracked_pass, view_entity, item);
+                        }
+                    }
+
+                    let pass_descriptor = RenderPassDescriptor {
+                        label: Some("main_oit_composite_pass_3d"),
+                        color_attachments: &[Some(target.get_color_attachment(Operations {
+                            load: LoadOp::Load,
+                            store: true,
+                        }))],
+                        depth_stencil_attachment: None,
+                    };
+                    let mut tracked_pass =
+                        render_context.begin_tracked_render_pass(&pass_descriptor);
+                    if let Some(viewport) = camera.viewport.as_ref() {
+                        tracked_pass.set_camera_viewport(viewport);
+                    }
+                    tracked_pass.set_render_pipeline(composite_pipeline);
+                    tracked_pass.set_bind_group(0, &oit_composite.bind_group, &[]);
+                    tracked_pass.draw(0..3, 0..1);
+                }
+            }
+        }
+
         // WebGL2 quirk: if ending with a render pass with a custom viewport, the viewport isn't
         // reset for the next render pass so add an empty render pass without a custom viewport
         #[cfg(feature = "webgl")]
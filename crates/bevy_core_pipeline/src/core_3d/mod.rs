@@ -7,7 +7,16 @@ pub mod graph {
         pub const VIEW_ENTITY: &str = "view_entity";
     }
     pub mod node {
+        pub const PREPASS: &str = "prepass";
+        pub const SCREEN_SPACE_REFLECTIONS: &str = "screen_space_reflections";
         pub const MAIN_PASS: &str = "main_pass";
+        pub const SCREEN_SPACE_REFLECTIONS_COPY: &str = "screen_space_reflections_copy";
+        pub const TAA: &str = "taa";
+        pub const DEPTH_OF_FIELD: &str = "depth_of_field";
+        pub const MOTION_BLUR: &str = "motion_blur";
+        pub const COLOR_GRADING: &str = "color_grading";
+        pub const FXAA: &str = "fxaa";
+        pub const UPSCALING: &str = "upscaling";
     }
 }
 
@@ -16,11 +25,27 @@ use std::cmp::Reverse;
 pub use camera_3d::*;
 pub use main_pass_3d_node::*;
 
+use crate::{
+    color_grading::ColorGradingNode,
+    dof::DepthOfFieldNode,
+    fxaa::FxaaNode,
+    motion_blur::MotionBlurNode,
+    prepass::{
+        prepare_motion_vector_textures, DepthPrepass, MotionVectorPrepass, Opaque3dPrepass,
+        PrepassNode,
+    },
+    ssr::{ScreenSpaceReflectionsCopyNode, ScreenSpaceReflectionsNode},
+    taa::TaaNode,
+    upscaling::UpscalingNode,
+};
+
 use bevy_app::{App, Plugin};
 use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
 use bevy_render::{
     camera::{Camera, ExtractedCamera},
     extract_component::ExtractComponentPlugin,
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
     prelude::Msaa,
     render_graph::{RenderGraph, SlotInfo, SlotType},
     render_phase::{
@@ -44,7 +69,14 @@ impl Plugin for Core3dPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera3d>()
             .register_type::<Camera3dDepthLoadOp>()
-            .add_plugin(ExtractComponentPlugin::<Camera3d>::default());
+            .register_type::<DepthPrepass>()
+            .register_type::<MotionVectorPrepass>()
+            .register_type::<TransparentSortConfig>()
+            .init_resource::<TransparentSortConfig>()
+            .add_plugin(ExtractComponentPlugin::<Camera3d>::default())
+            .add_plugin(ExtractComponentPlugin::<DepthPrepass>::default())
+            .add_plugin(ExtractComponentPlugin::<MotionVectorPrepass>::default())
+            .add_plugin(ExtractResourcePlugin::<TransparentSortConfig>::default());
 
         let render_app = match app.get_sub_app_mut(RenderApp) {
             Ok(render_app) => render_app,
@@ -52,24 +84,63 @@ impl Plugin for Core3dPlugin {
         };
 
         render_app
+            .init_resource::<DrawFunctions<Opaque3dPrepass>>()
             .init_resource::<DrawFunctions<Opaque3d>>()
             .init_resource::<DrawFunctions<AlphaMask3d>>()
+            .init_resource::<DrawFunctions<Transmissive3d>>()
             .init_resource::<DrawFunctions<Transparent3d>>()
             .add_system_to_stage(RenderStage::Extract, extract_core_3d_camera_phases)
             .add_system_to_stage(RenderStage::Prepare, prepare_core_3d_depth_textures)
+            .add_system_to_stage(RenderStage::Prepare, prepare_motion_vector_textures)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Opaque3dPrepass>)
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Opaque3d>)
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<AlphaMask3d>)
-            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transparent3d>);
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transmissive3d>)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_transparent_3d_phases);
 
+        let prepass_node = PrepassNode::new(&mut render_app.world);
+        let ssr_node = ScreenSpaceReflectionsNode::new(&mut render_app.world);
         let pass_node_3d = MainPass3dNode::new(&mut render_app.world);
+        let ssr_copy_node = ScreenSpaceReflectionsCopyNode::new(&mut render_app.world);
+        let taa_node = TaaNode::new(&mut render_app.world);
+        let depth_of_field_node = DepthOfFieldNode::new(&mut render_app.world);
+        let motion_blur_node = MotionBlurNode::new(&mut render_app.world);
+        let color_grading_node = ColorGradingNode::new(&mut render_app.world);
+        let fxaa_node = FxaaNode::new(&mut render_app.world);
+        let upscaling_node = UpscalingNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
 
         let mut draw_3d_graph = RenderGraph::default();
+        draw_3d_graph.add_node(graph::node::PREPASS, prepass_node);
+        draw_3d_graph.add_node(graph::node::SCREEN_SPACE_REFLECTIONS, ssr_node);
         draw_3d_graph.add_node(graph::node::MAIN_PASS, pass_node_3d);
+        draw_3d_graph.add_node(graph::node::SCREEN_SPACE_REFLECTIONS_COPY, ssr_copy_node);
+        draw_3d_graph.add_node(graph::node::TAA, taa_node);
+        draw_3d_graph.add_node(graph::node::DEPTH_OF_FIELD, depth_of_field_node);
+        draw_3d_graph.add_node(graph::node::MOTION_BLUR, motion_blur_node);
+        draw_3d_graph.add_node(graph::node::COLOR_GRADING, color_grading_node);
+        draw_3d_graph.add_node(graph::node::FXAA, fxaa_node);
+        draw_3d_graph.add_node(graph::node::UPSCALING, upscaling_node);
         let input_node_id = draw_3d_graph.set_input(vec![SlotInfo::new(
             graph::input::VIEW_ENTITY,
             SlotType::Entity,
         )]);
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::PREPASS,
+                PrepassNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::SCREEN_SPACE_REFLECTIONS,
+                ScreenSpaceReflectionsNode::IN_VIEW,
+            )
+            .unwrap();
         draw_3d_graph
             .add_slot_edge(
                 input_node_id,
@@ -78,6 +149,95 @@ impl Plugin for Core3dPlugin {
                 MainPass3dNode::IN_VIEW,
             )
             .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::SCREEN_SPACE_REFLECTIONS_COPY,
+                ScreenSpaceReflectionsCopyNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::TAA,
+                TaaNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::DEPTH_OF_FIELD,
+                DepthOfFieldNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::MOTION_BLUR,
+                MotionBlurNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::COLOR_GRADING,
+                ColorGradingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::FXAA,
+                FxaaNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::UPSCALING,
+                UpscalingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::PREPASS, graph::node::SCREEN_SPACE_REFLECTIONS)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(
+                graph::node::SCREEN_SPACE_REFLECTIONS,
+                graph::node::MAIN_PASS,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(
+                graph::node::MAIN_PASS,
+                graph::node::SCREEN_SPACE_REFLECTIONS_COPY,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::SCREEN_SPACE_REFLECTIONS_COPY, graph::node::TAA)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::TAA, graph::node::DEPTH_OF_FIELD)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::DEPTH_OF_FIELD, graph::node::MOTION_BLUR)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::MOTION_BLUR, graph::node::COLOR_GRADING)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::COLOR_GRADING, graph::node::FXAA)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::FXAA, graph::node::UPSCALING)
+            .unwrap();
         graph.add_sub_graph(graph::NAME, draw_3d_graph);
     }
 }
@@ -166,6 +326,51 @@ impl CachedRenderPipelinePhaseItem for AlphaMask3d {
     }
 }
 
+/// The opaque meshes whose materials read the [`ViewTransmissionTexture`], drawn after the
+/// [`Opaque3d`] and [`AlphaMask3d`] phases which the texture is a copy of.
+///
+/// [`ViewTransmissionTexture`]: crate::transmission::ViewTransmissionTexture
+pub struct Transmissive3d {
+    pub distance: f32,
+    pub pipeline: CachedRenderPipelineId,
+    pub entity: Entity,
+    pub draw_function: DrawFunctionId,
+}
+
+impl PhaseItem for Transmissive3d {
+    // NOTE: Values increase towards the camera. Back-to-front ordering for transmissive means we need an ascending sort.
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        FloatOrd(self.distance)
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        radsort::sort_by_key(items, |item| item.distance);
+    }
+}
+
+impl EntityPhaseItem for Transmissive3d {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for Transmissive3d {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
 pub struct Transparent3d {
     pub distance: f32,
     pub pipeline: CachedRenderPipelineId,
@@ -193,6 +398,33 @@ impl PhaseItem for Transparent3d {
     }
 }
 
+/// Configures how the [`Transparent3d`] phase is sorted.
+#[derive(Resource, Clone, Copy, Debug, Default, ExtractResource, Reflect)]
+#[reflect(Resource)]
+pub struct TransparentSortConfig {
+    /// Whether transparent items at the same distance from the view are drawn in the order of
+    /// their entity index.
+    ///
+    /// Otherwise they are drawn in the order they were queued, which can change from frame to
+    /// frame and make overlapping transparent meshes flicker.
+    pub stable_ties: bool,
+}
+
+/// Sorts the [`Transparent3d`] phases back to front, breaking ties by entity index if
+/// [`TransparentSortConfig::stable_ties`] is set.
+pub fn sort_transparent_3d_phases(
+    config: Res<TransparentSortConfig>,
+    mut render_phases: Query<&mut RenderPhase<Transparent3d>>,
+) {
+    for mut phase in &mut render_phases {
+        if config.stable_ties {
+            // radsort is stable, so the order by entity is kept between items at the same distance
+            radsort::sort_by_key(&mut phase.items, |item| item.entity.id());
+        }
+        phase.sort();
+    }
+}
+
 impl EntityPhaseItem for Transparent3d {
     #[inline]
     fn entity(&self) -> Entity {
@@ -209,15 +441,30 @@ impl CachedRenderPipelinePhaseItem for Transparent3d {
 
 pub fn extract_core_3d_camera_phases(
     mut commands: Commands,
-    cameras_3d: Extract<Query<(Entity, &Camera), With<Camera3d>>>,
+    cameras_3d: Extract<
+        Query<
+            (
+                Entity,
+                &Camera,
+                Option<&DepthPrepass>,
+                Option<&MotionVectorPrepass>,
+            ),
+            With<Camera3d>,
+        >,
+    >,
 ) {
-    for (entity, camera) in &cameras_3d {
+    for (entity, camera, depth_prepass, motion_vector_prepass) in &cameras_3d {
         if camera.is_active {
-            commands.get_or_spawn(entity).insert((
+            let mut entity = commands.get_or_spawn(entity);
+            entity.insert((
                 RenderPhase::<Opaque3d>::default(),
                 RenderPhase::<AlphaMask3d>::default(),
+                RenderPhase::<Transmissive3d>::default(),
                 RenderPhase::<Transparent3d>::default(),
             ));
+            if depth_prepass.is_some() || motion_vector_prepass.is_some() {
+                entity.insert(RenderPhase::<Opaque3dPrepass>::default());
+            }
         }
     }
 }
@@ -225,10 +472,9 @@ pub fn extract_core_3d_camera_phases(
 pub fn prepare_core_3d_depth_textures(
     mut commands: Commands,
     mut texture_cache: ResMut<TextureCache>,
-    msaa: Res<Msaa>,
     render_device: Res<RenderDevice>,
     views_3d: Query<
-        (Entity, &ExtractedCamera),
+        (Entity, &ExtractedCamera, &Msaa),
         (
             With<RenderPhase<Opaque3d>>,
             With<RenderPhase<AlphaMask3d>>,
@@ -237,10 +483,12 @@ pub fn prepare_core_3d_depth_textures(
     >,
 ) {
     let mut textures = HashMap::default();
-    for (entity, camera) in &views_3d {
+    for (entity, camera, msaa) in &views_3d {
         if let Some(physical_target_size) = camera.physical_target_size {
+            // The cameras rendering to the same target share their depth texture, if they render
+            // at the same size with the same MSAA
             let cached_texture = textures
-                .entry(camera.target.clone())
+                .entry((camera.target.clone(), physical_target_size, msaa.samples))
                 .or_insert_with(|| {
                     texture_cache.get(
                         &render_device,
@@ -256,7 +504,9 @@ pub fn prepare_core_3d_depth_textures(
                             dimension: TextureDimension::D2,
                             format: TextureFormat::Depth32Float, /* PERF: vulkan docs recommend using 24
                                                                   * bit depth for better performance */
-                            usage: TextureUsages::RENDER_ATTACHMENT,
+                            // NOTE: Temporal anti-aliasing reprojects the pixels from their depth
+                            usage: TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
                         },
                     )
                 })
@@ -268,3 +518,71 @@ pub fn prepare_core_3d_depth_textures(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::schedule::{Stage, SystemStage};
+    use bevy_render::render_phase::{Draw, TrackedRenderPass};
+
+    use super::*;
+
+    struct NoDraw;
+
+    impl Draw<Transparent3d> for NoDraw {
+        fn draw<'w>(
+            &mut self,
+            _world: &'w World,
+            _pass: &mut TrackedRenderPass<'w>,
+            _view: Entity,
+            _item: &Transparent3d,
+        ) {
+        }
+    }
+
+    #[test]
+    fn coincident_transparent_items_keep_their_order() {
+        let mut render_world = World::new();
+        render_world.insert_resource(TransparentSortConfig { stable_ties: true });
+        let draw_function = DrawFunctions::<Transparent3d>::default()
+            .write()
+            .add(NoDraw);
+        let view = render_world
+            .spawn(RenderPhase::<Transparent3d>::default())
+            .id();
+        let entities: Vec<Entity> = (0..3).map(|_| render_world.spawn_empty().id()).collect();
+        let mut sort = SystemStage::single(sort_transparent_3d_phases);
+
+        // Visible entities are queued in an order that can change between frames
+        let mut draw_orders = Vec::new();
+        for queued in [[0, 1, 2], [2, 1, 0], [1, 2, 0]] {
+            let mut phase = render_world
+                .get_mut::<RenderPhase<Transparent3d>>(view)
+                .unwrap();
+            phase.items.clear();
+            for i in queued {
+                phase.add(Transparent3d {
+                    // The last entity is the furthest away, the other two are coincident
+                    distance: if i == 2 { -2.0 } else { -1.0 },
+                    pipeline: CachedRenderPipelineId::INVALID,
+                    entity: entities[i],
+                    draw_function,
+                });
+            }
+            sort.run(&mut render_world);
+
+            let phase = render_world
+                .get::<RenderPhase<Transparent3d>>(view)
+                .unwrap();
+            draw_orders.push(
+                phase
+                    .items
+                    .iter()
+                    .map(|item| item.entity)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        for draw_order in &draw_orders {
+            assert_eq!(*draw_order, [entities[2], entities[0], entities[1]]);
+        }
+    }
+}
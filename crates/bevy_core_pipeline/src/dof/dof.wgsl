@@ -0,0 +1,74 @@
+struct DepthOfField {
+    inverse_projection: mat4x4<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+    focal_distance: f32,
+    // The diameter in pixels of the circle of confusion of a point infinitely far away
+    coc_scale: f32,
+    max_coc: f32,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+#ifdef MULTISAMPLED
+@group(0) @binding(1)
+var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(1)
+var depth_texture: texture_depth_2d;
+#endif
+@group(0) @binding(2)
+var<uniform> dof: DepthOfField;
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+// Returns the diameter in pixels of the circle of confusion of the pixel at `coords`, and the
+// inverse of its distance from the view
+fn circle_of_confusion(coords: vec2<i32>) -> vec2<f32> {
+    let depth = textureLoad(depth_texture, coords, 0);
+    let uv = (vec2<f32>(coords) + 0.5 - dof.viewport.xy) / dof.viewport.zw;
+    let view_position = dof.inverse_projection * vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    // NOTE: w is 0.0 at the far plane of infinite projections
+    let inverse_distance = view_position.w / -view_position.z;
+    let coc = dof.coc_scale * abs(1.0 - dof.focal_distance * inverse_distance);
+    return vec2<f32>(min(coc, dof.max_coc), inverse_distance);
+}
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(input_texture));
+    let coords = vec2<i32>(position.xy);
+#ifdef VERTICAL
+    let direction = vec2<i32>(0, 1);
+#else
+    let direction = vec2<i32>(1, 0);
+#endif
+
+    let center = circle_of_confusion(coords);
+    let max_radius = i32(ceil(dof.max_coc * 0.5));
+    var color = vec4<f32>(0.0);
+    var total_weight = 0.0;
+    for (var i: i32 = -max_radius; i <= max_radius; i = i + 1) {
+        let sample_coords = clamp(coords + direction * i, vec2<i32>(0), size - 1);
+        let sample = circle_of_confusion(sample_coords);
+        // Each sample is spread over its own circle of confusion, but a blurry background
+        // doesn't spread over what is in front of it
+        var radius = sample.x * 0.5;
+        if (sample.y < center.y) {
+            radius = min(radius, center.x * 0.5);
+        }
+        let offset = f32(abs(i));
+        if (offset <= radius) {
+            let sigma = max(radius * 0.5, 0.5);
+            let weight = exp(-offset * offset / (2.0 * sigma * sigma)) / sigma;
+            color = color + textureLoad(input_texture, sample_coords, 0) * weight;
+            total_weight = total_weight + weight;
+        }
+    }
+    return color / total_weight;
+}
@@ -0,0 +1,436 @@
+//! Depth of field, for the cameras that opt into it with [`DepthOfField`].
+//!
+//! The main 3d pass of these cameras renders to an intermediate texture instead of their
+//! [`ViewTarget`], which the [`DepthOfFieldNode`] then blurs onto the [`ViewTarget`] with a
+//! separable gaussian blur. The blur of each pixel follows the diameter of its circle of
+//! confusion, computed from its depth with the thin lens model of a camera focused at
+//! [`DepthOfField::focal_distance`].
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::{Mat4, Vec4};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, DynamicUniformBuffer, Extent3d,
+        FragmentState, MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor,
+        Shader, ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureView, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
+    texture::TextureCache,
+    view::{ExtractedView, Msaa, ViewDepthTexture, ViewTarget},
+    RenderApp, RenderStage,
+};
+
+use crate::core_3d::Camera3d;
+
+pub const DOF_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1753147520617272624);
+
+/// Add this component to a [`Camera3d`] to blur what is out of focus, as a physical camera
+/// would.
+///
+/// The blur is computed for perspective projections: orthographic cameras stay sharp.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct DepthOfField {
+    /// The distance from the camera that is in focus, in world units.
+    pub focal_distance: f32,
+    /// The f-number of the aperture: the smaller it is, the wider the aperture and the blurrier
+    /// what is out of focus.
+    pub aperture_f_stops: f32,
+    /// The height of the sensor of the camera in world units, with its field of view giving
+    /// the focal length of the lens.
+    ///
+    /// Defaults to the 24 millimeters of a full frame sensor.
+    pub sensor_height: f32,
+    /// The largest diameter of the blur, in pixels.
+    ///
+    /// The cost of the blur grows with it.
+    pub max_circle_of_confusion_diameter: f32,
+}
+
+impl Default for DepthOfField {
+    fn default() -> Self {
+        Self {
+            focal_distance: 10.0,
+            aperture_f_stops: 1.0,
+            sensor_height: 0.024,
+            max_circle_of_confusion_diameter: 32.0,
+        }
+    }
+}
+
+impl DepthOfField {
+    /// Returns the diameter in pixels of the circle of confusion of points infinitely far away,
+    /// seen with `projection` on a viewport `viewport_height` pixels high.
+    ///
+    /// The circle of confusion of a point at a distance `d` is this diameter scaled by
+    /// `|1 - focal_distance / d|`.
+    pub fn circle_of_confusion_scale(&self, projection: &Mat4, viewport_height: f32) -> f32 {
+        if projection.w_axis.w == 1.0 {
+            // Orthographic projections don't have a focal length
+            return 0.0;
+        }
+        // `y_axis.y` is the inverse of the tangent of half the vertical field of view
+        let focal_length = 0.5 * self.sensor_height * projection.y_axis.y;
+        let coc = focal_length * focal_length
+            / (self.aperture_f_stops * (self.focal_distance - focal_length).max(f32::EPSILON));
+        coc / self.sensor_height * viewport_height
+    }
+}
+
+impl ExtractComponent for DepthOfField {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Blurs the main 3d pass of the cameras with [`DepthOfField`].
+pub struct DepthOfFieldPlugin;
+
+impl Plugin for DepthOfFieldPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, DOF_SHADER_HANDLE, "dof.wgsl", Shader::from_wgsl);
+
+        app.register_type::<DepthOfField>()
+            .add_plugin(ExtractComponentPlugin::<DepthOfField>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<DepthOfFieldPipeline>()
+            .init_resource::<SpecializedRenderPipelines<DepthOfFieldPipeline>>()
+            .init_resource::<DepthOfFieldUniforms>()
+            .add_system_to_stage(RenderStage::Prepare, prepare_depth_of_field_textures)
+            .add_system_to_stage(RenderStage::Prepare, prepare_depth_of_field_uniforms)
+            .add_system_to_stage(RenderStage::Queue, queue_depth_of_field_bind_groups);
+    }
+}
+
+/// The textures of the depth of field of a view.
+#[derive(Component)]
+pub struct ViewDepthOfFieldTextures {
+    /// The target the main 3d pass renders to instead of the [`ViewTarget`] of the view.
+    pub main_target: ViewTarget,
+    /// The horizontally blurred color, blurred vertically onto the [`ViewTarget`].
+    pub intermediate: TextureView,
+}
+
+pub fn prepare_depth_of_field_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ExtractedCamera, &Msaa), With<DepthOfField>>,
+) {
+    for (entity, camera, msaa) in &views {
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let mut get_texture = |label, sample_count| {
+            texture_cache
+                .get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some(label),
+                        size: Extent3d {
+                            depth_or_array_layers: 1,
+                            width: physical_target_size.x,
+                            height: physical_target_size.y,
+                        },
+                        mip_level_count: 1,
+                        sample_count,
+                        dimension: TextureDimension::D2,
+                        format: **texture_format,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    },
+                )
+                .default_view
+        };
+        let input = get_texture("depth_of_field_input_texture", 1);
+        // The main pass resolves its samples to the input
+        let sampled_target =
+            (msaa.samples > 1).then(|| get_texture("depth_of_field_sampled_texture", msaa.samples));
+        let intermediate = get_texture("depth_of_field_intermediate_texture", 1);
+        commands.entity(entity).insert(ViewDepthOfFieldTextures {
+            main_target: ViewTarget {
+                view: input,
+                sampled_target,
+            },
+            intermediate,
+        });
+    }
+}
+
+#[derive(Clone, ShaderType)]
+struct DepthOfFieldUniform {
+    inverse_projection: Mat4,
+    viewport: Vec4,
+    focal_distance: f32,
+    coc_scale: f32,
+    max_coc: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct DepthOfFieldUniforms {
+    uniforms: DynamicUniformBuffer<DepthOfFieldUniform>,
+}
+
+#[derive(Component)]
+pub struct DepthOfFieldUniformOffset {
+    pub offset: u32,
+}
+
+pub fn prepare_depth_of_field_uniforms(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut dof_uniforms: ResMut<DepthOfFieldUniforms>,
+    views: Query<(Entity, &ExtractedView, &DepthOfField)>,
+) {
+    dof_uniforms.uniforms.clear();
+    for (entity, view, dof) in &views {
+        let offset = dof_uniforms.uniforms.push(DepthOfFieldUniform {
+            inverse_projection: view.projection.inverse(),
+            viewport: view.viewport.as_vec4(),
+            focal_distance: dof.focal_distance,
+            coc_scale: dof.circle_of_confusion_scale(&view.projection, view.viewport.w as f32),
+            max_coc: dof.max_circle_of_confusion_diameter,
+        });
+        commands
+            .entity(entity)
+            .insert(DepthOfFieldUniformOffset { offset });
+    }
+    dof_uniforms
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// The pipelines and bind groups of the two blur passes of the depth of field of a view.
+#[derive(Component)]
+pub struct ViewDepthOfFieldPasses {
+    pub horizontal_pipeline: CachedRenderPipelineId,
+    pub horizontal_bind_group: BindGroup,
+    pub vertical_pipeline: CachedRenderPipelineId,
+    pub vertical_bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct DepthOfFieldPipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+}
+
+impl DepthOfFieldPipeline {
+    fn layout(&self, multisampled: bool) -> &BindGroupLayout {
+        if multisampled {
+            &self.multisampled_layout
+        } else {
+            &self.layout
+        }
+    }
+}
+
+impl FromWorld for DepthOfFieldPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let create_layout = |label, multisampled| {
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[
+                    // Color
+                    BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            multisampled: false,
+                            sample_type: TextureSampleType::Float { filterable: false },
+                            view_dimension: TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    // Depth
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            multisampled,
+                            sample_type: TextureSampleType::Depth,
+                            view_dimension: TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: Some(DepthOfFieldUniform::min_size()),
+                        },
+                        count: None,
+                    },
+                ],
+            })
+        };
+        DepthOfFieldPipeline {
+            layout: create_layout("depth_of_field_layout", false),
+            multisampled_layout: create_layout("depth_of_field_multisampled_layout", true),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DepthOfFieldPipelineKey {
+    pub format: TextureFormat,
+    /// Whether the depth texture is multisampled.
+    pub multisampled: bool,
+    /// Whether the pass blurs vertically rather than horizontally.
+    pub vertical: bool,
+}
+
+impl SpecializedRenderPipeline for DepthOfFieldPipeline {
+    type Key = DepthOfFieldPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.multisampled {
+            shader_defs.push(String::from("MULTISAMPLED"));
+        }
+        if key.vertical {
+            shader_defs.push(String::from("VERTICAL"));
+        }
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: DOF_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: DOF_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout(key.multisampled).clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("depth_of_field_pipeline".into()),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_depth_of_field_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    dof_pipeline: Res<DepthOfFieldPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<DepthOfFieldPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    dof_uniforms: Res<DepthOfFieldUniforms>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ViewDepthOfFieldTextures, &ViewDepthTexture, &Msaa)>,
+) {
+    let uniforms = match dof_uniforms.uniforms.binding() {
+        Some(uniforms) => uniforms,
+        None => return,
+    };
+    for (entity, textures, depth, msaa) in &views {
+        let multisampled = msaa.samples > 1;
+        let layout = dof_pipeline.layout(multisampled);
+        let mut specialize = |vertical| {
+            pipelines.specialize(
+                &mut pipeline_cache,
+                &dof_pipeline,
+                DepthOfFieldPipelineKey {
+                    format: **texture_format,
+                    multisampled,
+                    vertical,
+                },
+            )
+        };
+        let horizontal_pipeline = specialize(false);
+        let vertical_pipeline = specialize(true);
+        let create_bind_group = |color| {
+            render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("depth_of_field_bind_group"),
+                layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(color),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::TextureView(&depth.view),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: uniforms.clone(),
+                    },
+                ],
+            })
+        };
+        commands.entity(entity).insert(ViewDepthOfFieldPasses {
+            horizontal_pipeline,
+            horizontal_bind_group: create_bind_group(&textures.main_target.view),
+            vertical_pipeline,
+            vertical_bind_group: create_bind_group(&textures.intermediate),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_render::camera::{CameraProjection, OrthographicProjection, PerspectiveProjection};
+
+    use super::*;
+
+    #[test]
+    fn circle_of_confusion_follows_the_thin_lens() {
+        let dof = DepthOfField {
+            focal_distance: 5.0,
+            aperture_f_stops: 2.0,
+            sensor_height: 0.024,
+            ..Default::default()
+        };
+        let projection = PerspectiveProjection {
+            fov: 2.0 * (0.5f32).atan(),
+            ..Default::default()
+        }
+        .get_projection_matrix();
+        // A field of view of 2 * atan(1/2) makes the focal length the height of the sensor
+        let focal_length = 0.024;
+        let expected = focal_length * focal_length / (2.0 * (5.0 - focal_length)) / 0.024 * 1080.0;
+        let scale = dof.circle_of_confusion_scale(&projection, 1080.0);
+        assert!((scale - expected).abs() < 1e-4);
+
+        let orthographic = OrthographicProjection::default().get_projection_matrix();
+        assert_eq!(dof.circle_of_confusion_scale(&orthographic, 1080.0), 0.0);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    color_grading::ViewColorGradingTextures,
+    dof::{DepthOfFieldUniformOffset, ViewDepthOfFieldPasses, ViewDepthOfFieldTextures},
+    fxaa::ViewFxaaTextures,
+    motion_blur::ViewMotionBlurTextures,
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        BindGroup, CachedRenderPipelineId, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor, TextureView,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Blurs the main 3d pass of the views with [`DepthOfField`](crate::dof::DepthOfField) onto
+/// their target, after their temporal anti-aliasing if they have it and before their motion
+/// blur, color grading and FXAA if they have them.
+pub struct DepthOfFieldNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewDepthOfFieldTextures,
+            &'static ViewDepthOfFieldPasses,
+            &'static DepthOfFieldUniformOffset,
+            Option<&'static ViewMotionBlurTextures>,
+            Option<&'static ViewColorGradingTextures>,
+            Option<&'static ViewFxaaTextures>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl DepthOfFieldNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for DepthOfFieldNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(DepthOfFieldNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (
+            camera,
+            target,
+            textures,
+            passes,
+            uniform_offset,
+            motion_blur_textures,
+            color_grading_textures,
+            fxaa_textures,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No depth of field for this view
+        };
+        // The motion blur, color grading and FXAA are applied after the depth of field
+        let output = motion_blur_textures
+            .map(|textures| &textures.main_target.view)
+            .or_else(|| color_grading_textures.map(|textures| &textures.main_target.view))
+            .or_else(|| fxaa_textures.map(|textures| &textures.main_target.view))
+            .unwrap_or(&target.view);
+
+        #[cfg(feature = "trace")]
+        let _depth_of_field_pass_3d_span = info_span!("depth_of_field_pass_3d").entered();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let mut run_pass = |label,
+                            pipeline: CachedRenderPipelineId,
+                            bind_group: &BindGroup,
+                            output: &TextureView| {
+            let pipeline = match pipeline_cache.get_render_pipeline(pipeline) {
+                Some(pipeline) => pipeline,
+                None => return,
+            };
+            let pass_descriptor = RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: output,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            };
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+            if let Some(viewport) = camera.viewport.as_ref() {
+                tracked_pass.set_camera_viewport(viewport);
+            }
+            tracked_pass.set_render_pipeline(pipeline);
+            tracked_pass.set_bind_group(0, bind_group, &[uniform_offset.offset]);
+            tracked_pass.draw(0..3, 0..1);
+        };
+        run_pass(
+            "depth_of_field_horizontal_pass_3d",
+            passes.horizontal_pipeline,
+            &passes.horizontal_bind_group,
+            &textures.intermediate,
+        );
+        run_pass(
+            "depth_of_field_vertical_pass_3d",
+            passes.vertical_pipeline,
+            &passes.vertical_bind_group,
+            output,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+// How much the blur direction is reduced in flat areas
+let REDUCE_MIN: f32 = 0.0078125;
+let REDUCE_MUL: f32 = 0.125;
+// The maximum length of the blur, in pixels
+let SPAN_MAX: f32 = 8.0;
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+// The edges are detected on the perceived brightness, approximated from the linear colors
+fn luma(color: vec3<f32>) -> f32 {
+    return sqrt(dot(color, vec3<f32>(0.299, 0.587, 0.114)));
+}
+
+fn sample_color(uv: vec2<f32>) -> vec3<f32> {
+    return textureSampleLevel(input_texture, input_sampler, uv, 0.0).rgb;
+}
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(input_texture));
+    let uv = position.xy * texel;
+    let center = textureSampleLevel(input_texture, input_sampler, uv, 0.0);
+
+    let luma_nw = luma(sample_color(uv + vec2<f32>(-1.0, -1.0) * texel));
+    let luma_ne = luma(sample_color(uv + vec2<f32>(1.0, -1.0) * texel));
+    let luma_sw = luma(sample_color(uv + vec2<f32>(-1.0, 1.0) * texel));
+    let luma_se = luma(sample_color(uv + vec2<f32>(1.0, 1.0) * texel));
+    let luma_m = luma(center.rgb);
+    let luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    let luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    // The blur runs along the edge, perpendicular to the luma gradient
+    var direction = vec2<f32>(
+        (luma_sw + luma_se) - (luma_nw + luma_ne),
+        (luma_nw + luma_sw) - (luma_ne + luma_se),
+    );
+    let reduce = max((luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * REDUCE_MUL, REDUCE_MIN);
+    let scale = 1.0 / (min(abs(direction.x), abs(direction.y)) + reduce);
+    direction = clamp(direction * scale, vec2<f32>(-SPAN_MAX), vec2<f32>(SPAN_MAX)) * texel;
+
+    let color_a = 0.5 * (sample_color(uv + direction * (1.0 / 3.0 - 0.5))
+        + sample_color(uv + direction * (2.0 / 3.0 - 0.5)));
+    let color_b = color_a * 0.5 + 0.25 * (sample_color(uv - direction * 0.5)
+        + sample_color(uv + direction * 0.5));
+    // The wider blur is only kept when it didn't cross another edge
+    let luma_b = luma(color_b);
+    if luma_b < luma_min || luma_b > luma_max {
+        return vec4<f32>(color_a, center.a);
+    }
+    return vec4<f32>(color_b, center.a);
+}
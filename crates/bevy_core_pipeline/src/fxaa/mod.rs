@@ -0,0 +1,237 @@
+//! Fast approximate anti-aliasing, for the cameras that opt into it with [`Fxaa`].
+//!
+//! The main 3d pass of these cameras renders to an intermediate texture instead of their
+//! [`ViewTarget`], which the [`FxaaNode`] then copies onto the [`ViewTarget`] after all the
+//! other post-processing, blurring the pixels along the edges it detects in their brightness.
+//! It is much cheaper than MSAA, but also blurs the edges inside textures.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState, MultisampleState,
+        PipelineCache, PrimitiveState, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+        SamplerDescriptor, Shader, ShaderStages, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureSampleType, TextureUsages, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderTextureFormat},
+    texture::TextureCache,
+    view::{Msaa, ViewTarget},
+    RenderApp, RenderStage,
+};
+
+use crate::core_3d::Camera3d;
+
+pub const FXAA_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4182761465141723543);
+
+/// Add this component to a [`Camera3d`] to anti-alias the image it renders with fast
+/// approximate anti-aliasing.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct Fxaa;
+
+impl ExtractComponent for Fxaa {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Anti-aliases the cameras with [`Fxaa`].
+pub struct FxaaPlugin;
+
+impl Plugin for FxaaPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, FXAA_SHADER_HANDLE, "fxaa.wgsl", Shader::from_wgsl);
+
+        app.register_type::<Fxaa>()
+            .add_plugin(ExtractComponentPlugin::<Fxaa>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<FxaaPipeline>()
+            .init_resource::<SpecializedRenderPipelines<FxaaPipeline>>()
+            .add_system_to_stage(RenderStage::Prepare, prepare_fxaa_textures)
+            .add_system_to_stage(RenderStage::Queue, queue_fxaa_bind_groups);
+    }
+}
+
+/// The textures of the fast approximate anti-aliasing of a view.
+#[derive(Component)]
+pub struct ViewFxaaTextures {
+    /// The target the main 3d pass renders to instead of the [`ViewTarget`] of the view.
+    pub main_target: ViewTarget,
+}
+
+pub fn prepare_fxaa_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ExtractedCamera, &Msaa), With<Fxaa>>,
+) {
+    for (entity, camera, msaa) in &views {
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let mut get_texture = |label, sample_count| {
+            texture_cache
+                .get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some(label),
+                        size: Extent3d {
+                            depth_or_array_layers: 1,
+                            width: physical_target_size.x,
+                            height: physical_target_size.y,
+                        },
+                        mip_level_count: 1,
+                        sample_count,
+                        dimension: TextureDimension::D2,
+                        format: **texture_format,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    },
+                )
+                .default_view
+        };
+        let input = get_texture("fxaa_input_texture", 1);
+        // The main pass resolves its samples to the input
+        let sampled_target =
+            (msaa.samples > 1).then(|| get_texture("fxaa_sampled_texture", msaa.samples));
+        commands.entity(entity).insert(ViewFxaaTextures {
+            main_target: ViewTarget {
+                view: input,
+                sampled_target,
+            },
+        });
+    }
+}
+
+/// The pipeline and bind group anti-aliasing a view.
+#[derive(Component)]
+pub struct ViewFxaaPass {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct FxaaPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for FxaaPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("fxaa_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        // The edges are blurred by sampling between the pixels
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("fxaa_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        FxaaPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for FxaaPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: FXAA_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: FXAA_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("fxaa_pipeline".into()),
+        }
+    }
+}
+
+pub fn queue_fxaa_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    fxaa_pipeline: Res<FxaaPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<FxaaPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ViewFxaaTextures)>,
+) {
+    for (entity, textures) in &views {
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &fxaa_pipeline, **texture_format);
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("fxaa_bind_group"),
+            layout: &fxaa_pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&textures.main_target.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&fxaa_pipeline.sampler),
+                },
+            ],
+        });
+        commands.entity(entity).insert(ViewFxaaPass {
+            pipeline,
+            bind_group,
+        });
+    }
+}
@@ -0,0 +1,93 @@
+use crate::fxaa::ViewFxaaPass;
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Anti-aliases the main 3d pass of the views with [`Fxaa`](crate::fxaa::Fxaa) onto their
+/// target, after the rest of their post-processing.
+pub struct FxaaNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewFxaaPass,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl FxaaNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for FxaaNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(FxaaNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, target, pass) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No fast approximate anti-aliasing for this view
+        };
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pass.pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _fxaa_pass_3d_span = info_span!("fxaa_pass_3d").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("fxaa_pass_3d"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(0, &pass.bind_group, &[]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
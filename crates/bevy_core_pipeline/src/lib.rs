@@ -1,20 +1,50 @@
 pub mod clear_color;
+pub mod color_grading;
 pub mod core_2d;
 pub mod core_3d;
+pub mod dof;
+pub mod fxaa;
+pub mod motion_blur;
+pub mod oit;
+pub mod prepass;
+pub mod skybox;
+pub mod ssr;
+pub mod taa;
+pub mod transmission;
+pub mod upscaling;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         clear_color::ClearColor,
+        color_grading::ColorGrading,
         core_2d::{Camera2d, Camera2dBundle},
         core_3d::{Camera3d, Camera3dBundle},
+        dof::DepthOfField,
+        fxaa::Fxaa,
+        motion_blur::MotionBlur,
+        oit::OrderIndependentTransparency,
+        prepass::{DepthPrepass, MotionVectorPrepass},
+        skybox::Skybox,
+        ssr::ScreenSpaceReflections,
+        taa::TemporalAntiAliasing,
     };
 }
 
 use crate::{
     clear_color::{ClearColor, ClearColorConfig},
+    color_grading::ColorGradingPlugin,
     core_2d::Core2dPlugin,
     core_3d::Core3dPlugin,
+    dof::DepthOfFieldPlugin,
+    fxaa::FxaaPlugin,
+    motion_blur::MotionBlurPlugin,
+    oit::OitPlugin,
+    skybox::SkyboxPlugin,
+    ssr::ScreenSpaceReflectionsPlugin,
+    taa::TemporalAntiAliasPlugin,
+    transmission::TransmissionPlugin,
+    upscaling::UpscalingPlugin,
 };
 use bevy_app::{App, Plugin};
 use bevy_render::extract_resource::ExtractResourcePlugin;
@@ -29,6 +59,16 @@ impl Plugin for CorePipelinePlugin {
             .init_resource::<ClearColor>()
             .add_plugin(ExtractResourcePlugin::<ClearColor>::default())
             .add_plugin(Core2dPlugin)
-            .add_plugin(Core3dPlugin);
+            .add_plugin(Core3dPlugin)
+            .add_plugin(OitPlugin)
+            .add_plugin(SkyboxPlugin)
+            .add_plugin(TransmissionPlugin)
+            .add_plugin(ScreenSpaceReflectionsPlugin)
+            .add_plugin(TemporalAntiAliasPlugin)
+            .add_plugin(DepthOfFieldPlugin)
+            .add_plugin(MotionBlurPlugin)
+            .add_plugin(ColorGradingPlugin)
+            .add_plugin(FxaaPlugin)
+            .add_plugin(UpscalingPlugin);
     }
 }
@@ -0,0 +1,420 @@
+//! Motion blur, for the cameras that opt into it with [`MotionBlur`].
+//!
+//! The main 3d pass of these cameras renders to an intermediate texture instead of their
+//! [`ViewTarget`], which the [`MotionBlurNode`] then blurs onto the [`ViewTarget`] along the
+//! motion of each pixel since the previous frame. The motion of the camera is reprojected from
+//! the depth of each pixel with the view projection of the previous frame, and the motion of
+//! the meshes is read from the [`ViewMotionVectorTexture`] written by the
+//! [`MotionVectorPrepass`] these cameras require.
+//!
+//! Only opaque meshes have motion vectors: the other meshes are only blurred by the motion of
+//! the camera.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_phase::RenderPhase,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, DynamicUniformBuffer, Extent3d,
+        FilterMode, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+        RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, Shader,
+        ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
+    texture::TextureCache,
+    view::{Msaa, ViewDepthTexture, ViewTarget, ViewUniform, ViewUniforms},
+    RenderApp, RenderStage,
+};
+use bevy_utils::tracing::warn;
+
+use crate::{
+    core_3d::Camera3d,
+    prepass::{MotionVectorPrepass, Opaque3dPrepass, ViewMotionVectorTexture},
+};
+
+pub const MOTION_BLUR_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16286017321994669374);
+
+/// Add this component to a [`Camera3d`] to blur what moves on screen, as a physical camera
+/// would while its shutter is open.
+///
+/// Motion blur requires a [`MotionVectorPrepass`] on the camera.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct MotionBlur {
+    /// The fraction of the time between two frames the shutter is open for, which scales the
+    /// length of the blur.
+    ///
+    /// Defaults to the half of a frame of the 180 degree shutter of film cameras.
+    pub shutter_angle: f32,
+    /// The number of samples taken along the motion of each pixel.
+    ///
+    /// The cost of the blur grows with it.
+    pub samples: u32,
+}
+
+impl Default for MotionBlur {
+    fn default() -> Self {
+        Self {
+            shutter_angle: 0.5,
+            samples: 8,
+        }
+    }
+}
+
+impl ExtractComponent for MotionBlur {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Blurs the main 3d pass of the cameras with [`MotionBlur`].
+pub struct MotionBlurPlugin;
+
+impl Plugin for MotionBlurPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            MOTION_BLUR_SHADER_HANDLE,
+            "motion_blur.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<MotionBlur>()
+            .add_plugin(ExtractComponentPlugin::<MotionBlur>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<MotionBlurPipeline>()
+            .init_resource::<SpecializedRenderPipelines<MotionBlurPipeline>>()
+            .init_resource::<MotionBlurUniforms>()
+            .add_system_to_stage(RenderStage::Prepare, prepare_motion_blur_textures)
+            .add_system_to_stage(RenderStage::Prepare, prepare_motion_blur_uniforms)
+            .add_system_to_stage(RenderStage::Queue, queue_motion_blur_bind_groups);
+    }
+}
+
+/// The textures of the motion blur of a view.
+#[derive(Component)]
+pub struct ViewMotionBlurTextures {
+    /// The target the main 3d pass renders to instead of the [`ViewTarget`] of the view.
+    pub main_target: ViewTarget,
+}
+
+pub fn prepare_motion_blur_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    mut warned: Local<bool>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedCamera,
+            &Msaa,
+            Option<&MotionVectorPrepass>,
+            Option<&RenderPhase<Opaque3dPrepass>>,
+        ),
+        With<MotionBlur>,
+    >,
+) {
+    for (entity, camera, msaa, motion_vector_prepass, prepass_phase) in &views {
+        if motion_vector_prepass.is_none() || prepass_phase.is_none() {
+            if !*warned {
+                warn!("Motion blur is skipped on cameras without a `MotionVectorPrepass`");
+                *warned = true;
+            }
+            continue;
+        }
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let mut get_texture = |label, sample_count| {
+            texture_cache
+                .get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some(label),
+                        size: Extent3d {
+                            depth_or_array_layers: 1,
+                            width: physical_target_size.x,
+                            height: physical_target_size.y,
+                        },
+                        mip_level_count: 1,
+                        sample_count,
+                        dimension: TextureDimension::D2,
+                        format: **texture_format,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    },
+                )
+                .default_view
+        };
+        let input = get_texture("motion_blur_input_texture", 1);
+        // The main pass resolves its samples to the input
+        let sampled_target =
+            (msaa.samples > 1).then(|| get_texture("motion_blur_sampled_texture", msaa.samples));
+        commands.entity(entity).insert(ViewMotionBlurTextures {
+            main_target: ViewTarget {
+                view: input,
+                sampled_target,
+            },
+        });
+    }
+}
+
+#[derive(Clone, ShaderType)]
+struct MotionBlurUniform {
+    shutter_angle: f32,
+    samples: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct MotionBlurUniforms {
+    uniforms: DynamicUniformBuffer<MotionBlurUniform>,
+}
+
+#[derive(Component)]
+pub struct MotionBlurUniformOffset {
+    pub offset: u32,
+}
+
+pub fn prepare_motion_blur_uniforms(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut motion_blur_uniforms: ResMut<MotionBlurUniforms>,
+    views: Query<(Entity, &MotionBlur)>,
+) {
+    motion_blur_uniforms.uniforms.clear();
+    for (entity, motion_blur) in &views {
+        let offset = motion_blur_uniforms.uniforms.push(MotionBlurUniform {
+            shutter_angle: motion_blur.shutter_angle,
+            samples: motion_blur.samples.max(1),
+        });
+        commands
+            .entity(entity)
+            .insert(MotionBlurUniformOffset { offset });
+    }
+    motion_blur_uniforms
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// The pipeline and bind group blurring the motion of a view.
+#[derive(Component)]
+pub struct ViewMotionBlurPass {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct MotionBlurPipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl MotionBlurPipeline {
+    fn layout(&self, multisampled: bool) -> &BindGroupLayout {
+        if multisampled {
+            &self.multisampled_layout
+        } else {
+            &self.layout
+        }
+    }
+}
+
+impl FromWorld for MotionBlurPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let texture_entry = |binding, multisampled, sample_type| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled,
+                sample_type,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        };
+        let uniform_entry = |binding, min_binding_size| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: true,
+                min_binding_size: Some(min_binding_size),
+            },
+            count: None,
+        };
+        let create_layout = |label, multisampled| {
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[
+                    // Color
+                    texture_entry(0, false, TextureSampleType::Float { filterable: true }),
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    // Depth
+                    texture_entry(2, multisampled, TextureSampleType::Depth),
+                    // Motion vectors
+                    texture_entry(
+                        3,
+                        multisampled,
+                        TextureSampleType::Float { filterable: false },
+                    ),
+                    uniform_entry(4, ViewUniform::min_size()),
+                    uniform_entry(5, MotionBlurUniform::min_size()),
+                ],
+            })
+        };
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("motion_blur_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        MotionBlurPipeline {
+            layout: create_layout("motion_blur_layout", false),
+            multisampled_layout: create_layout("motion_blur_multisampled_layout", true),
+            sampler,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MotionBlurPipelineKey {
+    pub format: TextureFormat,
+    /// Whether the depth and motion vector textures are multisampled.
+    pub multisampled: bool,
+}
+
+impl SpecializedRenderPipeline for MotionBlurPipeline {
+    type Key = MotionBlurPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.multisampled {
+            shader_defs.push(String::from("MULTISAMPLED"));
+        }
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: MOTION_BLUR_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: MOTION_BLUR_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout(key.multisampled).clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("motion_blur_pipeline".into()),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_motion_blur_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    motion_blur_pipeline: Res<MotionBlurPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<MotionBlurPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    view_uniforms: Res<ViewUniforms>,
+    motion_blur_uniforms: Res<MotionBlurUniforms>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(
+        Entity,
+        &ViewMotionBlurTextures,
+        &ViewDepthTexture,
+        &ViewMotionVectorTexture,
+        &Msaa,
+    )>,
+) {
+    let (view_uniforms, uniforms) = match (
+        view_uniforms.uniforms.binding(),
+        motion_blur_uniforms.uniforms.binding(),
+    ) {
+        (Some(view_uniforms), Some(uniforms)) => (view_uniforms, uniforms),
+        _ => return,
+    };
+    for (entity, textures, depth, motion_vectors, msaa) in &views {
+        let multisampled = msaa.samples > 1;
+        let pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &motion_blur_pipeline,
+            MotionBlurPipelineKey {
+                format: **texture_format,
+                multisampled,
+            },
+        );
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("motion_blur_bind_group"),
+            layout: motion_blur_pipeline.layout(multisampled),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&textures.main_target.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&motion_blur_pipeline.sampler),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::TextureView(&depth.view),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(&motion_vectors.view),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: view_uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: uniforms.clone(),
+                },
+            ],
+        });
+        commands.entity(entity).insert(ViewMotionBlurPass {
+            pipeline,
+            bind_group,
+        });
+    }
+}
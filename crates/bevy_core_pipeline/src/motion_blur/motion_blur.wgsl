@@ -0,0 +1,79 @@
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
+};
+
+struct MotionBlur {
+    // The fraction of the time between two frames the shutter is open for
+    shutter_angle: f32,
+    samples: u32,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+#ifdef MULTISAMPLED
+@group(0) @binding(2)
+var depth_texture: texture_depth_multisampled_2d;
+@group(0) @binding(3)
+var motion_vector_texture: texture_multisampled_2d<f32>;
+#else
+@group(0) @binding(2)
+var depth_texture: texture_depth_2d;
+@group(0) @binding(3)
+var motion_vector_texture: texture_2d<f32>;
+#endif
+@group(0) @binding(4)
+var<uniform> view: View;
+@group(0) @binding(5)
+var<uniform> motion_blur: MotionBlur;
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(input_texture));
+    let coords = vec2<i32>(position.xy);
+
+    // Finds where the pixel was in the previous view from its depth, for the motion of the
+    // camera
+    let depth = textureLoad(depth_texture, coords, 0);
+    let uv = (position.xy - view.viewport.xy) / view.viewport.zw;
+    let world_position = view.inverse_view_proj * vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let previous_clip = view.previous_view_proj * world_position;
+    var motion = vec2<f32>(0.0);
+    // NOTE: What was behind the previous view didn't move on screen
+    if (previous_clip.w > 0.0) {
+        motion = uv - (vec2<f32>(0.5, -0.5) * previous_clip.xy / previous_clip.w + 0.5);
+    }
+    // The motion of the mesh on the pixel
+    motion = motion + textureLoad(motion_vector_texture, coords, 0).xy;
+
+    // Averages the colors along the motion of the pixel while the shutter was open, centered
+    // on the pixel
+    let blur = motion * view.viewport.zw * motion_blur.shutter_angle;
+    let viewport_min = view.viewport.xy + 0.5;
+    let viewport_max = view.viewport.xy + view.viewport.zw - 0.5;
+    var color = vec4<f32>(0.0);
+    for (var i: u32 = 0u; i < motion_blur.samples; i = i + 1u) {
+        let offset = (f32(i) + 0.5) / f32(motion_blur.samples) - 0.5;
+        let sample_position = clamp(position.xy + blur * offset, viewport_min, viewport_max);
+        color = color + textureSampleLevel(input_texture, input_sampler, sample_position / size, 0.0);
+    }
+    return color / f32(motion_blur.samples);
+}
@@ -0,0 +1,119 @@
+use crate::{
+    color_grading::ViewColorGradingTextures,
+    fxaa::ViewFxaaTextures,
+    motion_blur::{MotionBlurUniformOffset, ViewMotionBlurPass},
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget, ViewUniformOffset},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Blurs the main 3d pass of the views with [`MotionBlur`](crate::motion_blur::MotionBlur) onto
+/// their target, after their temporal anti-aliasing and depth of field and before their color
+/// grading and FXAA if they have them.
+pub struct MotionBlurNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewMotionBlurPass,
+            &'static ViewUniformOffset,
+            &'static MotionBlurUniformOffset,
+            Option<&'static ViewColorGradingTextures>,
+            Option<&'static ViewFxaaTextures>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl MotionBlurNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for MotionBlurNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(MotionBlurNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (
+            camera,
+            target,
+            pass,
+            view_uniform_offset,
+            uniform_offset,
+            color_grading_textures,
+            fxaa_textures,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No motion blur for this view
+        };
+        // The color grading and FXAA are applied after the motion blur
+        let output = color_grading_textures
+            .map(|textures| &textures.main_target.view)
+            .or_else(|| fxaa_textures.map(|textures| &textures.main_target.view))
+            .unwrap_or(&target.view);
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pass.pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _motion_blur_pass_3d_span = info_span!("motion_blur_pass_3d").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("motion_blur_pass_3d"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(
+            0,
+            &pass.bind_group,
+            &[view_uniform_offset.offset, uniform_offset.offset],
+        );
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+//! Weighted blended order-independent transparency, for the cameras that opt into it with
+//! [`OrderIndependentTransparency`].
+//!
+//! Instead of blending the [`Transparent3d`] phase back to front into the view target, the
+//! transparent pass accumulates the weighted colors of its fragments into an accumulation
+//! texture and their coverage into a revealage texture, which are then composited onto the
+//! view target. The result doesn't depend on the order the fragments are drawn in, so
+//! intersecting transparent meshes such as foliage and particles don't pop as they are sorted.
+//!
+//! Fragments are weighted by their distance from the view, which is an approximation: layers
+//! with a high alpha don't fully hide the layers behind them.
+//!
+//! Every pipeline queued in the [`Transparent3d`] phase of these cameras has to draw to the
+//! [`oit_color_targets`], with a fragment shader returning the `OitFragmentOutput` of
+//! `oit_fragment_output`, both imported from `bevy_core_pipeline::oit`.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_phase::RenderPhase,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BlendComponent, BlendFactor,
+        BlendOperation, BlendState, CachedRenderPipelineId, ColorTargetState, ColorWrites,
+        Extent3d, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+        RenderPipelineDescriptor, Shader, ShaderStages, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureSampleType, TextureUsages, TextureView, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderTextureFormat},
+    texture::TextureCache,
+    view::Msaa,
+    RenderApp, RenderStage,
+};
+use bevy_utils::HashMap;
+
+use crate::core_3d::Transparent3d;
+
+pub const OIT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1295517811116730427);
+pub const OIT_COMPOSITE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3708880191053331870);
+
+/// The format of the texture accumulating the weighted colors of transparent fragments.
+pub const OIT_ACCUM_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+/// The format of the texture accumulating the coverage of transparent fragments.
+pub const OIT_REVEALAGE_FORMAT: TextureFormat = TextureFormat::R8Unorm;
+
+/// Add this component to a [`Camera3d`](crate::core_3d::Camera3d) to render its transparent
+/// meshes with weighted blended order-independent transparency, rather than sorted alpha
+/// blending.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct OrderIndependentTransparency;
+
+impl ExtractComponent for OrderIndependentTransparency {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Returns the color targets of the pipelines drawing in the [`Transparent3d`] phase of cameras
+/// with [`OrderIndependentTransparency`].
+pub fn oit_color_targets() -> Vec<Option<ColorTargetState>> {
+    vec![
+        Some(ColorTargetState {
+            format: OIT_ACCUM_FORMAT,
+            blend: Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+            write_mask: ColorWrites::ALL,
+        }),
+        Some(ColorTargetState {
+            format: OIT_REVEALAGE_FORMAT,
+            // The revealage is the product of `1 - alpha` of all the fragments
+            blend: Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::OneMinusSrc,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent::REPLACE,
+            }),
+            write_mask: ColorWrites::ALL,
+        }),
+    ]
+}
+
+/// Renders the transparent meshes of the cameras with [`OrderIndependentTransparency`].
+pub struct OitPlugin;
+
+impl Plugin for OitPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, OIT_SHADER_HANDLE, "oit.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            OIT_COMPOSITE_SHADER_HANDLE,
+            "oit_composite.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<OrderIndependentTransparency>()
+            .add_plugin(ExtractComponentPlugin::<OrderIndependentTransparency>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<OitCompositePipeline>()
+            .init_resource::<SpecializedRenderPipelines<OitCompositePipeline>>()
+            .add_system_to_stage(RenderStage::Prepare, prepare_oit_textures)
+            .add_system_to_stage(RenderStage::Queue, queue_oit_composite);
+    }
+}
+
+/// The textures the transparent pass of a view with [`OrderIndependentTransparency`] draws to.
+#[derive(Component)]
+pub struct ViewOitTextures {
+    pub accum: TextureView,
+    pub revealage: TextureView,
+}
+
+/// The pipeline and bind group compositing the [`ViewOitTextures`] of a view onto its target.
+#[derive(Component)]
+pub struct ViewOitComposite {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct OitCompositePipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+}
+
+impl OitCompositePipeline {
+    fn layout(&self, samples: u32) -> &BindGroupLayout {
+        if samples > 1 {
+            &self.multisampled_layout
+        } else {
+            &self.layout
+        }
+    }
+}
+
+impl FromWorld for OitCompositePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let create_layout = |label, multisampled| {
+            let entry = |binding| BindGroupLayoutEntry {
+                binding,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled,
+                    sample_type: TextureSampleType::Float { filterable: false },
+                    view_dimension: TextureViewDimension::D2,
+                },
+                count: None,
+            };
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[entry(0), entry(1)],
+            })
+        };
+        OitCompositePipeline {
+            layout: create_layout("oit_composite_layout", false),
+            multisampled_layout: create_layout("oit_composite_multisampled_layout", true),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OitCompositePipelineKey {
+    pub samples: u32,
+    pub format: TextureFormat,
+}
+
+impl SpecializedRenderPipeline for OitCompositePipeline {
+    type Key = OitCompositePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.samples > 1 {
+            shader_defs.push(String::from("MULTISAMPLED"));
+        }
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: OIT_COMPOSITE_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: OIT_COMPOSITE_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout(key.samples).clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.samples,
+                ..Default::default()
+            },
+            label: Some("oit_composite_pipeline".into()),
+        }
+    }
+}
+
+pub fn prepare_oit_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views_3d: Query<
+        (Entity, &ExtractedCamera, &Msaa),
+        (
+            With<OrderIndependentTransparency>,
+            With<RenderPhase<Transparent3d>>,
+        ),
+    >,
+) {
+    let mut textures = HashMap::default();
+    for (entity, camera, msaa) in &views_3d {
+        if let Some(physical_target_size) = camera.physical_target_size {
+            let (accum, revealage) = textures
+                .entry((camera.target.clone(), physical_target_size, msaa.samples))
+                .or_insert_with(|| {
+                    let mut get_texture = |label, format| {
+                        texture_cache
+                            .get(
+                                &render_device,
+                                TextureDescriptor {
+                                    label: Some(label),
+                                    size: Extent3d {
+                                        depth_or_array_layers: 1,
+                                        width: physical_target_size.x,
+                                        height: physical_target_size.y,
+                                    },
+                                    mip_level_count: 1,
+                                    sample_count: msaa.samples,
+                                    dimension: TextureDimension::D2,
+                                    format,
+                                    usage: TextureUsages::RENDER_ATTACHMENT
+                                        | TextureUsages::TEXTURE_BINDING,
+                                },
+                            )
+                            .default_view
+                    };
+                    (
+                        get_texture("view_oit_accum_texture", OIT_ACCUM_FORMAT),
+                        get_texture("view_oit_revealage_texture", OIT_REVEALAGE_FORMAT),
+                    )
+                })
+                .clone();
+            commands
+                .entity(entity)
+                .insert(ViewOitTextures { accum, revealage });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_oit_composite(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    composite_pipeline: Res<OitCompositePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<OitCompositePipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ViewOitTextures, &Msaa)>,
+) {
+    for (entity, textures, msaa) in &views {
+        let pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &composite_pipeline,
+            OitCompositePipelineKey {
+                samples: msaa.samples,
+                format: texture_format.0,
+            },
+        );
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("oit_composite_bind_group"),
+            layout: composite_pipeline.layout(msaa.samples),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&textures.accum),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&textures.revealage),
+                },
+            ],
+        });
+        commands.entity(entity).insert(ViewOitComposite {
+            pipeline,
+            bind_group,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oit_targets_match_texture_formats() {
+        let targets = oit_color_targets();
+        let formats: Vec<_> = targets
+            .iter()
+            .map(|target| target.as_ref().unwrap().format)
+            .collect();
+        assert_eq!(formats, [OIT_ACCUM_FORMAT, OIT_REVEALAGE_FORMAT]);
+    }
+}
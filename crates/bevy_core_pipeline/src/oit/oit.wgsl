@@ -0,0 +1,24 @@
+#define_import_path bevy_core_pipeline::oit
+
+// The outputs of a fragment shader drawing to the targets of weighted blended
+// order-independent transparency
+struct OitFragmentOutput {
+    @location(0) accum: vec4<f32>,
+    @location(1) revealage: f32,
+};
+
+// Weights `color` by its coverage and its distance from the view, so that the fragments
+// closest to the view dominate the blended color regardless of the order they are drawn in.
+// See "Weighted Blended Order-Independent Transparency", McGuire and Bavoil, 2013.
+fn oit_fragment_output(color: vec4<f32>, view_distance: f32) -> OitFragmentOutput {
+    let d = max(view_distance, 0.0);
+    let weight = color.a * clamp(
+        10.0 / (1e-5 + pow(d / 5.0, 2.0) + pow(d / 200.0, 6.0)),
+        1e-2,
+        3e3
+    );
+    var out: OitFragmentOutput;
+    out.accum = vec4<f32>(color.rgb * color.a, color.a) * weight;
+    out.revealage = color.a;
+    return out;
+}
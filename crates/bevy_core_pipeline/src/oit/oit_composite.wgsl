@@ -0,0 +1,42 @@
+#ifdef MULTISAMPLED
+@group(0) @binding(0)
+var accum_texture: texture_multisampled_2d<f32>;
+@group(0) @binding(1)
+var revealage_texture: texture_multisampled_2d<f32>;
+#else
+@group(0) @binding(0)
+var accum_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var revealage_texture: texture_2d<f32>;
+#endif
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+@fragment
+#ifdef MULTISAMPLED
+fn fragment(
+    @builtin(position) position: vec4<f32>,
+    @builtin(sample_index) sample_index: u32,
+) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(position.xy);
+    let accum = textureLoad(accum_texture, coords, i32(sample_index));
+    let revealage = textureLoad(revealage_texture, coords, i32(sample_index)).r;
+#else
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(position.xy);
+    let accum = textureLoad(accum_texture, coords, 0);
+    let revealage = textureLoad(revealage_texture, coords, 0).r;
+#endif
+    // Nothing transparent was drawn on this pixel
+    if (revealage >= 1.0) {
+        discard;
+    }
+    // The weighted average of the colors, blended with the coverage of all the fragments
+    let color = accum.rgb / max(accum.a, 1e-5);
+    return vec4<f32>(color, 1.0 - revealage);
+}
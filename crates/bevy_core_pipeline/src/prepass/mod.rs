@@ -0,0 +1,229 @@
+//! A pass run before the main 3d pass, for the cameras that opt into it with [`DepthPrepass`]
+//! or [`MotionVectorPrepass`].
+//!
+//! The prepass writes the depth of opaque meshes to the [`ViewDepthTexture`](bevy_render::view::ViewDepthTexture),
+//! which the main opaque pass then loads instead of clearing. The draw functions filling the
+//! [`Opaque3dPrepass`] phase are provided by the renderers, such as `bevy_pbr`.
+//!
+//! With a [`MotionVectorPrepass`], the prepass also writes the motion of opaque meshes since the
+//! previous frame to a [`ViewMotionVectorTexture`]. The motion of the camera isn't included: it
+//! is reprojected from the depth of each pixel by the effects reading the motion vectors, so
+//! that it also covers what isn't drawn in the prepass.
+
+mod node;
+
+pub use node::*;
+
+use std::cmp::Reverse;
+
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::ExtractComponent,
+    render_phase::{
+        CachedRenderPipelinePhaseItem, DrawFunctionId, EntityPhaseItem, PhaseItem, RenderPhase,
+    },
+    render_resource::{
+        CachedRenderPipelineId, Extent3d, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureUsages, TextureView,
+    },
+    renderer::RenderDevice,
+    texture::TextureCache,
+    view::Msaa,
+};
+use bevy_utils::{tracing::warn, FloatOrd, HashMap};
+
+/// The format of the [`ViewMotionVectorTexture`].
+pub const MOTION_VECTOR_PREPASS_FORMAT: TextureFormat = TextureFormat::Rg16Float;
+
+/// Add this component to a [`Camera3d`](crate::core_3d::Camera3d) to render the depth of
+/// opaque meshes in a prepass, before the main pass.
+///
+/// Features reading the depth of the scene before the main pass, such as occlusion-aware debug
+/// overlays, require this component on the camera, and warn about the cameras missing it with
+/// [`warn_missing_depth_prepass`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct DepthPrepass;
+
+impl ExtractComponent for DepthPrepass {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Warns once if a camera has the component `C` of a feature reading the depth of the prepass,
+/// but no [`DepthPrepass`].
+///
+/// The plugins of these features add this system, and skip the cameras without a prepass.
+pub fn warn_missing_depth_prepass<C: Component>(
+    mut warned: Local<bool>,
+    cameras: Query<(), (With<C>, With<Camera>, Without<DepthPrepass>)>,
+) {
+    if !*warned && !cameras.is_empty() {
+        warn!(
+            "`{}` is skipped on cameras without a `DepthPrepass`",
+            std::any::type_name::<C>()
+        );
+        *warned = true;
+    }
+}
+
+/// Add this component to a [`Camera3d`](crate::core_3d::Camera3d) to render the motion vectors
+/// of opaque meshes in the prepass, along with their depth.
+///
+/// Temporal effects following the motion of meshes, such as
+/// [`MotionBlur`](crate::motion_blur::MotionBlur), require this component on the camera.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct MotionVectorPrepass;
+
+impl ExtractComponent for MotionVectorPrepass {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// The motion vectors of the opaque meshes of a view with a [`MotionVectorPrepass`].
+///
+/// Each pixel holds the motion of the mesh drawn on it since the previous frame, in UV
+/// coordinates of the viewport, without the motion of the camera: its position in the previous
+/// frame is its position reprojected to the previous view, minus its motion vector. Pixels
+/// without an opaque mesh have no motion.
+#[derive(Component)]
+pub struct ViewMotionVectorTexture {
+    pub view: TextureView,
+}
+
+pub fn prepare_motion_vector_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views_3d: Query<
+        (Entity, &ExtractedCamera, &Msaa),
+        (
+            With<MotionVectorPrepass>,
+            With<RenderPhase<Opaque3dPrepass>>,
+        ),
+    >,
+) {
+    let mut textures = HashMap::default();
+    for (entity, camera, msaa) in &views_3d {
+        if let Some(physical_target_size) = camera.physical_target_size {
+            let view = textures
+                .entry((camera.target.clone(), physical_target_size, msaa.samples))
+                .or_insert_with(|| {
+                    texture_cache
+                        .get(
+                            &render_device,
+                            TextureDescriptor {
+                                label: Some("view_motion_vector_texture"),
+                                size: Extent3d {
+                                    depth_or_array_layers: 1,
+                                    width: physical_target_size.x,
+                                    height: physical_target_size.y,
+                                },
+                                mip_level_count: 1,
+                                sample_count: msaa.samples,
+                                dimension: TextureDimension::D2,
+                                format: MOTION_VECTOR_PREPASS_FORMAT,
+                                usage: TextureUsages::RENDER_ATTACHMENT
+                                    | TextureUsages::TEXTURE_BINDING,
+                            },
+                        )
+                        .default_view
+                })
+                .clone();
+            commands
+                .entity(entity)
+                .insert(ViewMotionVectorTexture { view });
+        }
+    }
+}
+
+/// Opaque meshes rendered in the prepass.
+pub struct Opaque3dPrepass {
+    pub distance: f32,
+    pub pipeline: CachedRenderPipelineId,
+    pub entity: Entity,
+    pub draw_function: DrawFunctionId,
+}
+
+impl PhaseItem for Opaque3dPrepass {
+    // NOTE: Values increase towards the camera. Front-to-back ordering for opaque means we need a descending sort.
+    type SortKey = Reverse<FloatOrd>;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        Reverse(FloatOrd(self.distance))
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        // Key negated to match reversed SortKey ordering
+        radsort::sort_by_key(items, |item| -item.distance);
+    }
+}
+
+impl EntityPhaseItem for Opaque3dPrepass {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for Opaque3dPrepass {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::schedule::{Stage, SystemStage};
+    use bevy_render::MainWorld;
+
+    use super::*;
+    use crate::core_3d::{extract_core_3d_camera_phases, Camera3d, Opaque3d};
+
+    #[test]
+    fn prepass_phase_is_extracted_for_opted_in_cameras() {
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        let with_prepass = main_world
+            .spawn((Camera::default(), Camera3d::default(), DepthPrepass))
+            .id();
+        let with_motion_vectors = main_world
+            .spawn((Camera::default(), Camera3d::default(), MotionVectorPrepass))
+            .id();
+        let without_prepass = main_world
+            .spawn((Camera::default(), Camera3d::default()))
+            .id();
+
+        SystemStage::single(extract_core_3d_camera_phases).run(&mut render_world);
+
+        let with_prepass = render_world.entity(with_prepass);
+        assert!(with_prepass.contains::<RenderPhase<Opaque3dPrepass>>());
+        assert!(with_prepass.contains::<RenderPhase<Opaque3d>>());
+        let with_motion_vectors = render_world.entity(with_motion_vectors);
+        assert!(with_motion_vectors.contains::<RenderPhase<Opaque3dPrepass>>());
+        let without_prepass = render_world.entity(without_prepass);
+        assert!(!without_prepass.contains::<RenderPhase<Opaque3dPrepass>>());
+        assert!(without_prepass.contains::<RenderPhase<Opaque3d>>());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    core_3d::Camera3d,
+    prepass::{Opaque3dPrepass, ViewMotionVectorTexture},
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    color::Color,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_phase::{DrawFunctions, RenderPhase},
+    render_resource::{
+        LoadOp, Operations, RenderPassColorAttachment, RenderPassDepthStencilAttachment,
+        RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewDepthTexture},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Renders the [`Opaque3dPrepass`] phase of the views that have one, i.e. the cameras with a
+/// [`DepthPrepass`](crate::prepass::DepthPrepass) or a
+/// [`MotionVectorPrepass`](crate::prepass::MotionVectorPrepass).
+pub struct PrepassNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static RenderPhase<Opaque3dPrepass>,
+            &'static Camera3d,
+            &'static ViewDepthTexture,
+            Option<&'static ViewMotionVectorTexture>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl PrepassNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for PrepassNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(PrepassNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, opaque_prepass_phase, camera_3d, depth, motion_vectors) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                Err(_) => {
+                    return Ok(());
+                } // No prepass for this view
+            };
+
+        // Always run the prepass when enabled, as the main pass loads the depth it writes
+        #[cfg(feature = "trace")]
+        let _opaque_prepass_span = info_span!("opaque_prepass").entered();
+        // The pipelines of the prepass only have a color target when it writes motion vectors
+        let color_attachments: Vec<_> = motion_vectors
+            .map(|motion_vectors| {
+                Some(RenderPassColorAttachment {
+                    view: &motion_vectors.view,
+                    resolve_target: None,
+                    ops: Operations {
+                        // NOTE: What isn't drawn in the prepass has no motion
+                        load: LoadOp::Clear(Color::NONE.into()),
+                        store: true,
+                    },
+                })
+            })
+            .into_iter()
+            .collect();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("opaque_prepass"),
+            color_attachments: &color_attachments,
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view: &depth.view,
+                depth_ops: Some(Operations {
+                    // NOTE: The prepass takes over the depth load op of the main pass
+                    load: camera_3d.depth_load_op.clone().into(),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        };
+
+        let draw_functions = world.resource::<DrawFunctions<Opaque3dPrepass>>();
+
+        let mut draw_functions = draw_functions.write();
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        for item in &opaque_prepass_phase.items {
+            let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
+            draw_function.draw(world, &mut tracked_pass, view_entity, item);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+//! Skyboxes, for the cameras that opt into it with [`Skybox`].
+//!
+//! The skybox is drawn by the [`MainPass3dNode`](crate::core_3d::MainPass3dNode) after the
+//! opaque and alpha mask phases, on the far plane, so it only covers what they left empty.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, CompareFunction, DepthBiasState,
+        DepthStencilState, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+        RenderPipelineDescriptor, SamplerBindingType, Shader, ShaderStages, ShaderType,
+        SpecializedRenderPipeline, SpecializedRenderPipelines, StencilState, TextureFormat,
+        TextureSampleType, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderTextureFormat},
+    texture::Image,
+    view::{Msaa, ViewUniform, ViewUniforms},
+    RenderApp, RenderStage,
+};
+
+use crate::core_3d::Camera3d;
+
+pub const SKYBOX_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 55594763423201431);
+
+/// Add this component to a [`Camera3d`] to draw a cubemap behind everything it renders.
+///
+/// The image has to be a cubemap, such as the ones loaded from KTX2 files with the `ktx2`
+/// feature, with a filterable format. Nothing is drawn until it is loaded.
+///
+/// To light the scene with the same surroundings, see `EnvironmentMapLight` in `bevy_pbr`.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct Skybox(pub Handle<Image>);
+
+impl ExtractComponent for Skybox {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+/// Draws the [`Skybox`] of the cameras that have one.
+pub struct SkyboxPlugin;
+
+impl Plugin for SkyboxPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SKYBOX_SHADER_HANDLE, "skybox.wgsl", Shader::from_wgsl);
+
+        app.register_type::<Skybox>()
+            .add_plugin(ExtractComponentPlugin::<Skybox>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<SkyboxPipeline>()
+            .init_resource::<SpecializedRenderPipelines<SkyboxPipeline>>()
+            .add_system_to_stage(RenderStage::Queue, queue_skybox_bind_groups);
+    }
+}
+
+/// The pipeline and bind group drawing the [`Skybox`] of a view.
+#[derive(Component)]
+pub struct ViewSkyboxPass {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_skybox_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    skybox_pipeline: Res<SkyboxPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    view_uniforms: Res<ViewUniforms>,
+    texture_format: Res<RenderTextureFormat>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<(Entity, &Skybox, &Msaa)>,
+) {
+    let view_uniforms = match view_uniforms.uniforms.binding() {
+        Some(view_uniforms) => view_uniforms,
+        None => return,
+    };
+    for (entity, skybox, msaa) in &views {
+        let skybox = match images.get(&skybox.0) {
+            Some(skybox) => skybox,
+            None => continue,
+        };
+        let pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &skybox_pipeline,
+            SkyboxPipelineKey {
+                format: **texture_format,
+                samples: msaa.samples,
+            },
+        );
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("skybox_bind_group"),
+            layout: &skybox_pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&skybox.texture_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&skybox.sampler),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: view_uniforms.clone(),
+                },
+            ],
+        });
+        commands.entity(entity).insert(ViewSkyboxPass {
+            pipeline,
+            bind_group,
+        });
+    }
+}
+
+#[derive(Resource)]
+pub struct SkyboxPipeline {
+    layout: BindGroupLayout,
+}
+
+impl FromWorld for SkyboxPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("skybox_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::Cube,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(ViewUniform::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        });
+        SkyboxPipeline { layout }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SkyboxPipelineKey {
+    pub format: TextureFormat,
+    pub samples: u32,
+}
+
+impl SpecializedRenderPipeline for SkyboxPipeline {
+    type Key = SkyboxPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: SKYBOX_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: SKYBOX_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            // NOTE: The depth is reversed, the skybox only passes where the depth was left
+            // cleared to the far plane
+            depth_stencil: Some(DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState {
+                count: key.samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("skybox_pipeline".into()),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var skybox: texture_cube<f32>;
+@group(0) @binding(1)
+var skybox_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> view: View;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) clip_position: vec2<f32>,
+};
+
+// A triangle covering the whole target, on the far plane
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    let clip_position = uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0);
+    var out: VertexOutput;
+    // NOTE: The depth is reversed, the far plane is at 0
+    out.position = vec4<f32>(clip_position, 0.0, 1.0);
+    out.clip_position = clip_position;
+    return out;
+}
+
+// Samples the skybox in the direction the view looks through each pixel
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let near = view.inverse_view_proj * vec4<f32>(in.clip_position, 1.0, 1.0);
+    let far = view.inverse_view_proj * vec4<f32>(in.clip_position, 0.001, 1.0);
+    let direction = far.xyz / far.w - near.xyz / near.w;
+    return textureSample(skybox, skybox_sampler, direction);
+}
@@ -0,0 +1,530 @@
+//! Screen space reflections, for the cameras that opt into it with [`ScreenSpaceReflections`].
+//!
+//! The main 3d pass of these cameras renders to a texture kept between frames instead of their
+//! [`ViewTarget`], which the [`ScreenSpaceReflectionsCopyNode`] then copies onto the rest of
+//! their post-processing. On the next frame, between the prepass and the main pass, the
+//! [`ScreenSpaceReflectionsNode`] traces the reflection of each pixel through the depth written
+//! by the [`DepthPrepass`] these cameras require, and reads the color of what it hits from the
+//! main pass of the previous frame, reprojected with the previous view projection. The normals
+//! are reconstructed from the depth.
+//!
+//! The reflected colors, with how confident the trace is in them, are left in the
+//! [`ViewScreenSpaceReflectionsTextures`] for the renderers, such as `bevy_pbr`, to blend into
+//! the specular light of smooth surfaces. What isn't on screen can't be reflected, and is left
+//! to the other sources of specular light.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::UVec2;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_phase::RenderPhase,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, DynamicUniformBuffer, Extent3d,
+        FilterMode, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+        RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, Shader,
+        ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureView, TextureViewDescriptor, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
+    texture::TextureCache,
+    view::{Msaa, ViewDepthTexture, ViewTarget, ViewUniform, ViewUniforms},
+    RenderApp, RenderStage,
+};
+use bevy_utils::HashMap;
+
+use crate::{
+    core_3d::Camera3d,
+    prepass::{warn_missing_depth_prepass, DepthPrepass, Opaque3dPrepass},
+};
+
+pub const SSR_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4470951389275623307);
+
+/// The format of the [`ViewScreenSpaceReflectionsTextures::reflections`]: the reflected color
+/// in rgb, and how confident the trace is in it in alpha.
+pub const SCREEN_SPACE_REFLECTIONS_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Add this component to a [`Camera3d`] to reflect what is on screen in the smooth surfaces
+/// of the scene, including what moves.
+///
+/// Screen space reflections require a [`DepthPrepass`] on the camera. Materials can opt out of
+/// them, see `StandardMaterial::screen_space_reflections` in `bevy_pbr`.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct ScreenSpaceReflections {
+    /// The number of steps each reflection is traced with.
+    ///
+    /// The cost of the reflections grows with it, and thin objects may be missed with too few.
+    pub steps: u32,
+    /// The distance in world units the reflections are traced for.
+    pub max_distance: f32,
+    /// How thick the surfaces are assumed to be behind their depth, in world units.
+    ///
+    /// The reflections hit what they pass behind by less than this.
+    pub thickness: f32,
+}
+
+impl Default for ScreenSpaceReflections {
+    fn default() -> Self {
+        Self {
+            steps: 32,
+            max_distance: 10.0,
+            thickness: 0.25,
+        }
+    }
+}
+
+impl ExtractComponent for ScreenSpaceReflections {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Traces the screen space reflections of the cameras with [`ScreenSpaceReflections`].
+pub struct ScreenSpaceReflectionsPlugin;
+
+impl Plugin for ScreenSpaceReflectionsPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SSR_SHADER_HANDLE, "ssr.wgsl", Shader::from_wgsl);
+
+        app.register_type::<ScreenSpaceReflections>()
+            .add_plugin(ExtractComponentPlugin::<ScreenSpaceReflections>::default())
+            .add_system(warn_missing_depth_prepass::<ScreenSpaceReflections>);
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<ScreenSpaceReflectionsPipeline>()
+            .init_resource::<SpecializedRenderPipelines<ScreenSpaceReflectionsPipeline>>()
+            .init_resource::<ScreenSpaceReflectionsUniforms>()
+            .init_resource::<ScreenSpaceReflectionsHistories>()
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_screen_space_reflections_textures,
+            )
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_screen_space_reflections_uniforms
+                    .after(prepare_screen_space_reflections_textures),
+            )
+            .add_system_to_stage(
+                RenderStage::Queue,
+                queue_screen_space_reflections_bind_groups,
+            );
+    }
+}
+
+/// The main pass of a view kept for its next frame.
+struct ScreenSpaceReflectionsHistory {
+    texture: Option<(UVec2, TextureView)>,
+    /// Whether the history was rendered by the previous frame.
+    valid: bool,
+}
+
+/// The history of each view with [`ScreenSpaceReflections`], dropped with the view.
+#[derive(Resource, Default)]
+pub struct ScreenSpaceReflectionsHistories(HashMap<Entity, ScreenSpaceReflectionsHistory>);
+
+/// The textures of the screen space reflections of a view.
+#[derive(Component)]
+pub struct ViewScreenSpaceReflectionsTextures {
+    /// The target the main 3d pass renders to instead of the [`ViewTarget`] of the view, read by
+    /// the reflections of the next frame.
+    pub main_target: ViewTarget,
+    /// The reflected color of each pixel, in [`SCREEN_SPACE_REFLECTIONS_FORMAT`].
+    pub reflections: TextureView,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_screen_space_reflections_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    mut histories: ResMut<ScreenSpaceReflectionsHistories>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedCamera,
+            &Msaa,
+            Option<&DepthPrepass>,
+            Option<&RenderPhase<Opaque3dPrepass>>,
+        ),
+        With<ScreenSpaceReflections>,
+    >,
+) {
+    let mut views_with_history = Vec::new();
+    for (entity, camera, msaa, depth_prepass, prepass_phase) in &views {
+        if depth_prepass.is_none() || prepass_phase.is_none() {
+            continue;
+        }
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let size = Extent3d {
+            depth_or_array_layers: 1,
+            width: physical_target_size.x,
+            height: physical_target_size.y,
+        };
+
+        let history = histories
+            .0
+            .entry(entity)
+            .or_insert_with(|| ScreenSpaceReflectionsHistory {
+                texture: None,
+                valid: false,
+            });
+        if matches!(&history.texture, Some((history_size, _)) if *history_size == physical_target_size)
+        {
+            history.valid = true;
+        } else {
+            let texture = render_device.create_texture(&TextureDescriptor {
+                label: Some("screen_space_reflections_history_texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: **texture_format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            });
+            history.texture = Some((
+                physical_target_size,
+                texture.create_view(&TextureViewDescriptor::default()),
+            ));
+            history.valid = false;
+        }
+        let (_, history_view) = history.texture.as_ref().unwrap();
+        views_with_history.push(entity);
+
+        let mut get_texture = |label, sample_count, format| {
+            texture_cache
+                .get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some(label),
+                        size,
+                        mip_level_count: 1,
+                        sample_count,
+                        dimension: TextureDimension::D2,
+                        format,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    },
+                )
+                .default_view
+        };
+        // The main pass resolves its samples to the history
+        let sampled_target = (msaa.samples > 1).then(|| {
+            get_texture(
+                "screen_space_reflections_sampled_texture",
+                msaa.samples,
+                **texture_format,
+            )
+        });
+        let reflections = get_texture(
+            "screen_space_reflections_texture",
+            1,
+            SCREEN_SPACE_REFLECTIONS_FORMAT,
+        );
+        commands
+            .entity(entity)
+            .insert(ViewScreenSpaceReflectionsTextures {
+                main_target: ViewTarget {
+                    view: history_view.clone(),
+                    sampled_target,
+                },
+                reflections,
+            });
+    }
+    histories
+        .0
+        .retain(|entity, _| views_with_history.contains(entity));
+}
+
+#[derive(Clone, ShaderType)]
+struct ScreenSpaceReflectionsUniform {
+    max_distance: f32,
+    thickness: f32,
+    steps: u32,
+    /// Whether the main pass of the previous frame can be read, 0 or 1.
+    history_valid: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct ScreenSpaceReflectionsUniforms {
+    uniforms: DynamicUniformBuffer<ScreenSpaceReflectionsUniform>,
+}
+
+#[derive(Component)]
+pub struct ScreenSpaceReflectionsUniformOffset {
+    pub offset: u32,
+}
+
+pub fn prepare_screen_space_reflections_uniforms(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    histories: Res<ScreenSpaceReflectionsHistories>,
+    mut ssr_uniforms: ResMut<ScreenSpaceReflectionsUniforms>,
+    views: Query<(Entity, &ScreenSpaceReflections)>,
+) {
+    ssr_uniforms.uniforms.clear();
+    for (entity, ssr) in &views {
+        let history_valid = matches!(histories.0.get(&entity), Some(history) if history.valid);
+        let offset = ssr_uniforms.uniforms.push(ScreenSpaceReflectionsUniform {
+            max_distance: ssr.max_distance.max(0.0),
+            thickness: ssr.thickness.max(0.0),
+            steps: ssr.steps.max(1),
+            history_valid: history_valid as u32,
+        });
+        commands
+            .entity(entity)
+            .insert(ScreenSpaceReflectionsUniformOffset { offset });
+    }
+    ssr_uniforms
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// The pipelines and bind groups tracing the screen space reflections of a view and copying
+/// its main pass onto the rest of its post-processing.
+#[derive(Component)]
+pub struct ViewScreenSpaceReflectionsPasses {
+    pub trace_pipeline: CachedRenderPipelineId,
+    pub trace_bind_group: BindGroup,
+    pub copy_pipeline: CachedRenderPipelineId,
+    pub copy_bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct ScreenSpaceReflectionsPipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+    copy_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl ScreenSpaceReflectionsPipeline {
+    fn layout(&self, key: ScreenSpaceReflectionsPipelineKey) -> &BindGroupLayout {
+        match key {
+            ScreenSpaceReflectionsPipelineKey { copy: true, .. } => &self.copy_layout,
+            ScreenSpaceReflectionsPipelineKey {
+                multisampled: true, ..
+            } => &self.multisampled_layout,
+            _ => &self.layout,
+        }
+    }
+}
+
+impl FromWorld for ScreenSpaceReflectionsPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let texture_entry = |binding, multisampled, sample_type| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled,
+                sample_type,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        };
+        let uniform_entry = |binding, min_binding_size| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: true,
+                min_binding_size: Some(min_binding_size),
+            },
+            count: None,
+        };
+        let create_layout = |label, multisampled| {
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[
+                    // Depth
+                    texture_entry(0, multisampled, TextureSampleType::Depth),
+                    // History
+                    texture_entry(1, false, TextureSampleType::Float { filterable: true }),
+                    BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    uniform_entry(3, ViewUniform::min_size()),
+                    uniform_entry(4, ScreenSpaceReflectionsUniform::min_size()),
+                ],
+            })
+        };
+        let copy_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("screen_space_reflections_copy_layout"),
+            entries: &[
+                // Main pass
+                texture_entry(0, false, TextureSampleType::Float { filterable: false }),
+            ],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("screen_space_reflections_history_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        ScreenSpaceReflectionsPipeline {
+            layout: create_layout("screen_space_reflections_layout", false),
+            multisampled_layout: create_layout(
+                "screen_space_reflections_multisampled_layout",
+                true,
+            ),
+            copy_layout,
+            sampler,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScreenSpaceReflectionsPipelineKey {
+    pub format: TextureFormat,
+    /// Whether the depth texture is multisampled.
+    pub multisampled: bool,
+    /// Whether the pass copies the main pass onto the rest of the post-processing rather than
+    /// tracing the reflections, in which case the depth texture isn't read.
+    pub copy: bool,
+}
+
+impl SpecializedRenderPipeline for ScreenSpaceReflectionsPipeline {
+    type Key = ScreenSpaceReflectionsPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.multisampled {
+            shader_defs.push(String::from("MULTISAMPLED"));
+        }
+        if key.copy {
+            shader_defs.push(String::from("COPY"));
+        }
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: SSR_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: SSR_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout(key).clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("screen_space_reflections_pipeline".into()),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_screen_space_reflections_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    ssr_pipeline: Res<ScreenSpaceReflectionsPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ScreenSpaceReflectionsPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    view_uniforms: Res<ViewUniforms>,
+    ssr_uniforms: Res<ScreenSpaceReflectionsUniforms>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(
+        Entity,
+        &ViewScreenSpaceReflectionsTextures,
+        &ViewDepthTexture,
+        &Msaa,
+    )>,
+) {
+    let (view_uniforms, uniforms) = match (
+        view_uniforms.uniforms.binding(),
+        ssr_uniforms.uniforms.binding(),
+    ) {
+        (Some(view_uniforms), Some(uniforms)) => (view_uniforms, uniforms),
+        _ => return,
+    };
+    let copy_key = ScreenSpaceReflectionsPipelineKey {
+        format: **texture_format,
+        multisampled: false,
+        copy: true,
+    };
+    for (entity, textures, depth, msaa) in &views {
+        let trace_key = ScreenSpaceReflectionsPipelineKey {
+            format: SCREEN_SPACE_REFLECTIONS_FORMAT,
+            multisampled: msaa.samples > 1,
+            copy: false,
+        };
+        let trace_pipeline = pipelines.specialize(&mut pipeline_cache, &ssr_pipeline, trace_key);
+        let copy_pipeline = pipelines.specialize(&mut pipeline_cache, &ssr_pipeline, copy_key);
+        let trace_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("screen_space_reflections_bind_group"),
+            layout: ssr_pipeline.layout(trace_key),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&depth.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&textures.main_target.view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&ssr_pipeline.sampler),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: view_uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: uniforms.clone(),
+                },
+            ],
+        });
+        let copy_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("screen_space_reflections_copy_bind_group"),
+            layout: ssr_pipeline.layout(copy_key),
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&textures.main_target.view),
+            }],
+        });
+        commands
+            .entity(entity)
+            .insert(ViewScreenSpaceReflectionsPasses {
+                trace_pipeline,
+                trace_bind_group,
+                copy_pipeline,
+                copy_bind_group,
+            });
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    color_grading::ViewColorGradingTextures,
+    dof::ViewDepthOfFieldTextures,
+    fxaa::ViewFxaaTextures,
+    motion_blur::ViewMotionBlurTextures,
+    ssr::{
+        ScreenSpaceReflectionsUniformOffset, ViewScreenSpaceReflectionsPasses,
+        ViewScreenSpaceReflectionsTextures,
+    },
+    taa::ViewTaaTextures,
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    color::Color,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget, ViewUniformOffset},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Traces the reflections of the views with
+/// [`ScreenSpaceReflections`](crate::ssr::ScreenSpaceReflections) through the depth of their
+/// prepass, before their main pass.
+pub struct ScreenSpaceReflectionsNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewScreenSpaceReflectionsTextures,
+            &'static ViewScreenSpaceReflectionsPasses,
+            &'static ViewUniformOffset,
+            &'static ScreenSpaceReflectionsUniformOffset,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl ScreenSpaceReflectionsNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for ScreenSpaceReflectionsNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(
+            ScreenSpaceReflectionsNode::IN_VIEW,
+            SlotType::Entity,
+        )]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, textures, passes, view_uniform_offset, uniform_offset) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                Err(_) => {
+                    return Ok(());
+                } // No screen space reflections for this view
+            };
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(passes.trace_pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _ssr_pass_3d_span = info_span!("screen_space_reflections_pass_3d").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("screen_space_reflections_pass_3d"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &textures.reflections,
+                resolve_target: None,
+                ops: Operations {
+                    // NOTE: Nothing is reflected outside of the viewport
+                    load: LoadOp::Clear(Color::NONE.into()),
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(
+            0,
+            &passes.trace_bind_group,
+            &[view_uniform_offset.offset, uniform_offset.offset],
+        );
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+/// Copies the main 3d pass of the views with
+/// [`ScreenSpaceReflections`](crate::ssr::ScreenSpaceReflections), kept for their next frame,
+/// onto their target, before the rest of their post-processing if they have it.
+pub struct ScreenSpaceReflectionsCopyNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewScreenSpaceReflectionsPasses,
+            Option<&'static ViewTaaTextures>,
+            Option<&'static ViewDepthOfFieldTextures>,
+            Option<&'static ViewMotionBlurTextures>,
+            Option<&'static ViewColorGradingTextures>,
+            Option<&'static ViewFxaaTextures>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl ScreenSpaceReflectionsCopyNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for ScreenSpaceReflectionsCopyNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(
+            ScreenSpaceReflectionsCopyNode::IN_VIEW,
+            SlotType::Entity,
+        )]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (
+            camera,
+            target,
+            passes,
+            taa_textures,
+            dof_textures,
+            motion_blur_textures,
+            color_grading_textures,
+            fxaa_textures,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No screen space reflections for this view
+        };
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(passes.copy_pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+        // The rest of the post-processing is applied after the copy
+        let output = taa_textures
+            .map(|textures| &textures.main_target.view)
+            .or_else(|| dof_textures.map(|textures| &textures.main_target.view))
+            .or_else(|| motion_blur_textures.map(|textures| &textures.main_target.view))
+            .or_else(|| color_grading_textures.map(|textures| &textures.main_target.view))
+            .or_else(|| fxaa_textures.map(|textures| &textures.main_target.view))
+            .unwrap_or(&target.view);
+
+        #[cfg(feature = "trace")]
+        let _ssr_copy_pass_3d_span = info_span!("screen_space_reflections_copy_pass_3d").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("screen_space_reflections_copy_pass_3d"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(0, &passes.copy_bind_group, &[]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+#ifdef COPY
+@group(0) @binding(0)
+var main_pass_texture: texture_2d<f32>;
+#else
+
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
+};
+
+struct ScreenSpaceReflections {
+    // The distance in world units the reflections are traced for
+    max_distance: f32,
+    thickness: f32,
+    steps: u32,
+    // Whether the history holds the main pass of the previous frame, 0 or 1
+    history_valid: u32,
+};
+
+#ifdef MULTISAMPLED
+@group(0) @binding(0)
+var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(0)
+var depth_texture: texture_depth_2d;
+#endif
+@group(0) @binding(1)
+var history_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var history_sampler: sampler;
+@group(0) @binding(3)
+var<uniform> view: View;
+@group(0) @binding(4)
+var<uniform> ssr: ScreenSpaceReflections;
+#endif
+
+// The number of steps refining each hit between the last two steps of its trace
+let REFINE_STEPS: u32 = 4u;
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+#ifdef COPY
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    return textureLoad(main_pass_texture, vec2<i32>(position.xy), 0);
+}
+#else
+fn load_depth(coords: vec2<i32>) -> f32 {
+    // NOTE: With MSAA, the first sample stands for the whole pixel
+    return textureLoad(depth_texture, coords, 0);
+}
+
+// Returns the view space position of the pixel at `coords`
+fn view_position(coords: vec2<i32>) -> vec3<f32> {
+    let uv = (vec2<f32>(coords) + 0.5 - view.viewport.xy) / view.viewport.zw;
+    let clip = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, load_depth(coords), 1.0);
+    let position = view.inverse_projection * clip;
+    return position.xyz / position.w;
+}
+
+// Reconstructs the view space normal of the pixel at `coords` from the positions of its
+// neighbours, picking on each axis the one closest in depth to stay on the same surface
+fn view_normal(coords: vec2<i32>, position: vec3<f32>) -> vec3<f32> {
+    let left = view_position(coords - vec2<i32>(1, 0));
+    let right = view_position(coords + vec2<i32>(1, 0));
+    let up = view_position(coords - vec2<i32>(0, 1));
+    let down = view_position(coords + vec2<i32>(0, 1));
+    var dx = right - position;
+    if (abs(position.z - left.z) < abs(right.z - position.z)) {
+        dx = position - left;
+    }
+    var dy = position - down;
+    if (abs(position.z - up.z) < abs(down.z - position.z)) {
+        dy = up - position;
+    }
+    return normalize(cross(dx, dy));
+}
+
+// Interleaved gradient noise, from Jimenez 2014, "Next Generation Post Processing in Call of
+// Duty: Advanced Warfare"
+fn noise(position: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(position, vec2<f32>(0.06711056, 0.00583715))));
+}
+
+// Returns the uv in the viewport of a view space position, and whether it is in front of the
+// view
+fn project(position: vec3<f32>) -> vec3<f32> {
+    let clip = view.projection * vec4<f32>(position, 1.0);
+    if (clip.w <= 0.0) {
+        return vec3<f32>(-1.0, -1.0, 0.0);
+    }
+    let ndc = clip.xy / clip.w;
+    return vec3<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5, 1.0);
+}
+
+fn in_viewport(uv: vec2<f32>) -> bool {
+    return all(uv >= vec2<f32>(0.0)) && all(uv < vec2<f32>(1.0));
+}
+
+// How far behind the depth of the scene a view space position is, negative in front of it
+fn depth_difference(position: vec3<f32>, uv: vec2<f32>) -> f32 {
+    let coords = vec2<i32>(view.viewport.xy + uv * view.viewport.zw);
+    return view_position(coords).z - position.z;
+}
+
+// Fades out what is close to the edges of the viewport, whose reflections are cut off
+fn edge_fade(uv: vec2<f32>) -> f32 {
+    let edge_distance = min(min(uv.x, 1.0 - uv.x), min(uv.y, 1.0 - uv.y));
+    return clamp(edge_distance * 10.0, 0.0, 1.0);
+}
+
+// Marches the reflected ray of each pixel in view space, comparing it to the depth of the
+// scene, then reads the color of what it hits from the main pass of the previous frame
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(position.xy);
+    // NOTE: The depth is reversed, what has no geometry is at 0
+    if (ssr.history_valid == 0u || load_depth(coords) == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    let pixel_position = view_position(coords);
+    let normal = view_normal(coords, pixel_position);
+    var view_direction = normalize(pixel_position);
+    // Orthographic projections look straight ahead
+    if (view.projection[3][3] == 1.0) {
+        view_direction = vec3<f32>(0.0, 0.0, -1.0);
+    }
+    let reflected = reflect(view_direction, normal);
+
+    let step_length = ssr.max_distance / f32(ssr.steps);
+    let jitter = noise(position.xy);
+    var previous_distance = 0.0;
+    var hit_distance = -1.0;
+    for (var i: u32 = 0u; i < ssr.steps; i = i + 1u) {
+        let ray_distance = (f32(i) + jitter) * step_length;
+        let sample_position = pixel_position + reflected * ray_distance;
+        let projected = project(sample_position);
+        if (projected.z == 0.0 || !in_viewport(projected.xy)) {
+            break;
+        }
+        let difference = depth_difference(sample_position, projected.xy);
+        if (difference > 0.0 && difference < ssr.thickness) {
+            hit_distance = ray_distance;
+            break;
+        }
+        previous_distance = ray_distance;
+    }
+    if (hit_distance < 0.0) {
+        return vec4<f32>(0.0);
+    }
+
+    // Binary search for the surface between the last step in front of it and the hit
+    var near = previous_distance;
+    var far = hit_distance;
+    for (var i: u32 = 0u; i < REFINE_STEPS; i = i + 1u) {
+        let middle = (near + far) * 0.5;
+        let sample_position = pixel_position + reflected * middle;
+        if (depth_difference(sample_position, project(sample_position).xy) > 0.0) {
+            far = middle;
+        } else {
+            near = middle;
+        }
+    }
+    let hit_position = pixel_position + reflected * far;
+    let hit_uv = project(hit_position).xy;
+
+    // Finds where the hit was in the previous frame, for the motion of the camera
+    let world_position = view.inverse_view * vec4<f32>(hit_position, 1.0);
+    let previous_clip = view.previous_view_proj * world_position;
+    if (previous_clip.w <= 0.0) {
+        return vec4<f32>(0.0);
+    }
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+    let previous_uv = vec2<f32>(previous_ndc.x * 0.5 + 0.5, 0.5 - previous_ndc.y * 0.5);
+    if (!in_viewport(previous_uv)) {
+        return vec4<f32>(0.0);
+    }
+    let size = vec2<f32>(textureDimensions(history_texture));
+    let history_uv = (view.viewport.xy + previous_uv * view.viewport.zw) / size;
+    let color = textureSampleLevel(history_texture, history_sampler, history_uv, 0.0).rgb;
+
+    // Rays coming back towards the view hit the back of what is on screen, which isn't rendered
+    let facing_fade = 1.0 - smoothstep(0.0, 0.5, dot(reflected, -view_direction));
+    let distance_fade = 1.0 - far / ssr.max_distance;
+    let confidence = edge_fade(hit_uv) * edge_fade(previous_uv) * facing_fade * distance_fade;
+    return vec4<f32>(color, clamp(confidence, 0.0, 1.0));
+}
+#endif
@@ -0,0 +1,504 @@
+//! Temporal anti-aliasing, for the cameras that opt into it with [`TemporalAntiAliasing`].
+//!
+//! The projection of these cameras is jittered by a different subpixel offset every frame, and
+//! their main 3d pass renders to an intermediate texture instead of their
+//! [`ViewTarget`]. The [`TaaNode`] then blends it with the history of the previous frames,
+//! reprojected from the depth of each pixel with the previous view projection, and writes the
+//! result to the [`ViewTarget`] and to the history of the next frame.
+//!
+//! Only the motion of the camera is reprojected: the history of moving meshes is rejected by
+//! clamping it to the colors around each pixel, which can blur them slightly. Temporal
+//! anti-aliasing replaces MSAA, and is skipped on the cameras whose [`Msaa`] uses more than one
+//! sample.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::{Mat4, UVec2, Vec2, Vec4};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState,
+        MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor, Sampler,
+        SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, ShaderType,
+        SpecializedRenderPipeline, SpecializedRenderPipelines, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor,
+        TextureViewDimension, UniformBuffer, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
+    texture::TextureCache,
+    view::{ExtractedView, Msaa, ViewDepthTexture, ViewSystem, ViewTarget},
+    RenderApp, RenderStage,
+};
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::core_3d::Camera3d;
+
+pub const TAA_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2422638669717727108);
+
+/// The format of the history textures, which keep the anti-aliased color of the previous frame.
+pub const TAA_HISTORY_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The number of subpixel offsets the projection cycles through.
+const JITTER_SAMPLES: u32 = 8;
+/// The weight of the current frame in the color blended with the history.
+const CURRENT_FRAME_WEIGHT: f32 = 0.1;
+
+/// Add this component to a [`Camera3d`] to anti-alias its main pass by accumulating the
+/// jittered frames it renders over time.
+///
+/// Temporal anti-aliasing requires the [`Msaa`] of the camera to use a single sample.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct TemporalAntiAliasing {
+    /// Discards the history of the previous frames on the next frame, for instance when the
+    /// camera is teleported.
+    ///
+    /// It is set back to `false` at the start of the following frame.
+    pub reset: bool,
+}
+
+impl ExtractComponent for TemporalAntiAliasing {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Renders the main 3d pass of the cameras with [`TemporalAntiAliasing`] with temporal
+/// anti-aliasing.
+pub struct TemporalAntiAliasPlugin;
+
+impl Plugin for TemporalAntiAliasPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, TAA_SHADER_HANDLE, "taa.wgsl", Shader::from_wgsl);
+
+        app.register_type::<TemporalAntiAliasing>()
+            .add_plugin(ExtractComponentPlugin::<TemporalAntiAliasing>::default())
+            .add_system_to_stage(CoreStage::First, clear_taa_resets);
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<TaaPipeline>()
+            .init_resource::<SpecializedRenderPipelines<TaaPipeline>>()
+            .init_resource::<TaaHistories>()
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_taa_jitter.before(ViewSystem::PrepareUniforms),
+            )
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_taa_textures.after(prepare_taa_jitter),
+            )
+            .add_system_to_stage(RenderStage::Queue, queue_taa_bind_groups);
+    }
+}
+
+fn clear_taa_resets(mut cameras: Query<&mut TemporalAntiAliasing>) {
+    for mut taa in &mut cameras {
+        if taa.reset {
+            taa.reset = false;
+        }
+    }
+}
+
+/// Returns the `index`th number of the Halton sequence of `base`, in `[0, 1)`.
+fn halton(mut index: u32, base: u32) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+/// Returns the subpixel offset of the projection on `frame`, in `[-0.5, 0.5)` pixels.
+pub fn taa_jitter(frame: u32) -> Vec2 {
+    let index = frame % JITTER_SAMPLES + 1;
+    Vec2::new(halton(index, 2), halton(index, 3)) - 0.5
+}
+
+/// Offsets the image of `projection` by `jitter` pixels of a viewport of `size` pixels.
+pub fn jitter_projection(projection: &mut Mat4, jitter: Vec2, size: Vec2) {
+    // Pixels go down while clip space goes up
+    let offset = jitter * Vec2::new(2.0, -2.0) / size;
+    if projection.w_axis.w == 1.0 {
+        // Orthographic projections keep w at 1, so the offset is translated as is
+        projection.w_axis.x += offset.x;
+        projection.w_axis.y += offset.y;
+    } else {
+        // Perspective projections divide by w = -z
+        projection.z_axis.x -= offset.x;
+        projection.z_axis.y -= offset.y;
+    }
+}
+
+/// The history of the temporal anti-aliasing of a view, kept between frames.
+struct TaaHistory {
+    /// The two history textures, one read and the other written every frame.
+    textures: Option<(UVec2, [TextureView; 2])>,
+    /// The index of the texture written this frame.
+    write: usize,
+    frame: u32,
+    view_proj: Mat4,
+    previous_view_proj: Mat4,
+    /// Whether the history can't be used this frame.
+    invalid: bool,
+    uniform: UniformBuffer<TaaUniform>,
+}
+
+/// The history of each view with [`TemporalAntiAliasing`], dropped with the view.
+#[derive(Resource, Default)]
+pub struct TaaHistories(HashMap<Entity, TaaHistory>);
+
+#[derive(Clone, Default, ShaderType)]
+struct TaaUniform {
+    reprojection: Mat4,
+    viewport: Vec4,
+    current_weight: f32,
+}
+
+pub fn prepare_taa_jitter(
+    mut histories: ResMut<TaaHistories>,
+    mut views: Query<(Entity, &mut ExtractedView, &TemporalAntiAliasing, &Msaa)>,
+) {
+    let mut views_with_history = Vec::new();
+    for (entity, mut view, taa, msaa) in &mut views {
+        if msaa.samples > 1 {
+            continue;
+        }
+        let view_proj = view.projection * view.transform.compute_matrix().inverse();
+        let history = histories.0.entry(entity).or_insert_with(|| TaaHistory {
+            textures: None,
+            write: 0,
+            frame: 0,
+            view_proj,
+            previous_view_proj: view_proj,
+            invalid: false,
+            uniform: UniformBuffer::default(),
+        });
+        history.frame = history.frame.wrapping_add(1);
+        history.previous_view_proj = history.view_proj;
+        history.view_proj = view_proj;
+        history.invalid = taa.reset;
+
+        let size = Vec2::new(view.viewport.z as f32, view.viewport.w as f32);
+        jitter_projection(&mut view.projection, taa_jitter(history.frame), size);
+        views_with_history.push(entity);
+    }
+    histories
+        .0
+        .retain(|entity, _| views_with_history.contains(entity));
+}
+
+/// The textures of the temporal anti-aliasing of a view.
+#[derive(Component)]
+pub struct ViewTaaTextures {
+    /// The target the main 3d pass renders to instead of the [`ViewTarget`] of the view.
+    pub main_target: ViewTarget,
+    pub history_read: TextureView,
+    pub history_write: TextureView,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_taa_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    mut histories: ResMut<TaaHistories>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    mut warned: Local<bool>,
+    views: Query<(Entity, &ExtractedCamera, &ExtractedView, &Msaa), With<TemporalAntiAliasing>>,
+) {
+    for (entity, camera, view, msaa) in &views {
+        if msaa.samples > 1 {
+            if !*warned {
+                warn!("Temporal anti-aliasing is skipped on cameras with MSAA enabled");
+                *warned = true;
+            }
+            continue;
+        }
+        let (physical_target_size, history) =
+            match (camera.physical_target_size, histories.0.get_mut(&entity)) {
+                (Some(size), Some(history)) => (size, history),
+                _ => continue,
+            };
+        let size = Extent3d {
+            depth_or_array_layers: 1,
+            width: physical_target_size.x,
+            height: physical_target_size.y,
+        };
+
+        if !matches!(&history.textures, Some((history_size, _)) if *history_size == physical_target_size)
+        {
+            let create_history_texture = || {
+                render_device
+                    .create_texture(&TextureDescriptor {
+                        label: Some("taa_history_texture"),
+                        size,
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: TextureDimension::D2,
+                        format: TAA_HISTORY_FORMAT,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    })
+                    .create_view(&TextureViewDescriptor::default())
+            };
+            history.textures = Some((
+                physical_target_size,
+                [create_history_texture(), create_history_texture()],
+            ));
+            history.invalid = true;
+        }
+        history.write = 1 - history.write;
+        let (_, textures) = history.textures.as_ref().unwrap();
+
+        let input = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("taa_input_texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: **texture_format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            },
+        );
+
+        history.uniform.set(TaaUniform {
+            reprojection: history.previous_view_proj * history.view_proj.inverse(),
+            viewport: view.viewport.as_vec4(),
+            current_weight: if history.invalid {
+                1.0
+            } else {
+                CURRENT_FRAME_WEIGHT
+            },
+        });
+
+        commands.entity(entity).insert(ViewTaaTextures {
+            main_target: ViewTarget {
+                view: input.default_view,
+                sampled_target: None,
+            },
+            history_read: textures[1 - history.write].clone(),
+            history_write: textures[history.write].clone(),
+        });
+    }
+}
+
+/// The pipeline and bind group resolving the temporal anti-aliasing of a view.
+#[derive(Component)]
+pub struct ViewTaaResolve {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct TaaPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for TaaPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let texture_entry = |binding, sample_type| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled: false,
+                sample_type,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        };
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("taa_layout"),
+            entries: &[
+                // Input
+                texture_entry(0, TextureSampleType::Float { filterable: false }),
+                // History
+                texture_entry(1, TextureSampleType::Float { filterable: true }),
+                // Depth
+                texture_entry(2, TextureSampleType::Depth),
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(TaaUniform::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("taa_history_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        TaaPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for TaaPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TAA_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: TAA_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![
+                    Some(ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    }),
+                    Some(ColorTargetState {
+                        format: TAA_HISTORY_FORMAT,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    }),
+                ],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("taa_pipeline".into()),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_taa_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    taa_pipeline: Res<TaaPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TaaPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut histories: ResMut<TaaHistories>,
+    texture_format: Res<RenderTextureFormat>,
+    views: Query<(Entity, &ViewTaaTextures, &ViewDepthTexture)>,
+) {
+    for (entity, textures, depth) in &views {
+        let history = match histories.0.get_mut(&entity) {
+            Some(history) => history,
+            None => continue,
+        };
+        history.uniform.write_buffer(&render_device, &render_queue);
+        let uniform = match history.uniform.binding() {
+            Some(uniform) => uniform,
+            None => continue,
+        };
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &taa_pipeline, **texture_format);
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("taa_bind_group"),
+            layout: &taa_pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&textures.main_target.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&textures.history_read),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::TextureView(&depth.view),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::Sampler(&taa_pipeline.sampler),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: uniform,
+                },
+            ],
+        });
+        commands.entity(entity).insert(ViewTaaResolve {
+            pipeline,
+            bind_group,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+    use bevy_render::camera::{CameraProjection, OrthographicProjection, PerspectiveProjection};
+
+    use super::*;
+
+    fn jittered_ndc(mut projection: Mat4, point: Vec3) -> Vec2 {
+        jitter_projection(
+            &mut projection,
+            Vec2::new(0.5, 0.25),
+            Vec2::new(100.0, 50.0),
+        );
+        projection.project_point3(point).truncate()
+    }
+
+    #[test]
+    fn jitter_offsets_projections_by_pixels() {
+        let perspective = PerspectiveProjection::default().get_projection_matrix();
+        let mut orthographic = OrthographicProjection::default();
+        orthographic.update(100.0, 50.0);
+        let orthographic = orthographic.get_projection_matrix();
+        for projection in [perspective, orthographic] {
+            for point in [Vec3::new(0.0, 0.0, -10.0), Vec3::new(2.0, -1.0, -3.0)] {
+                let offset =
+                    jittered_ndc(projection, point) - projection.project_point3(point).truncate();
+                // Half a pixel right and a quarter of a pixel down
+                assert!((offset - Vec2::new(0.01, -0.01)).length() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn jitter_cycles_through_subpixel_offsets() {
+        let offsets: Vec<Vec2> = (0..JITTER_SAMPLES).map(taa_jitter).collect();
+        for (i, offset) in offsets.iter().enumerate() {
+            assert!(offset.abs().max_element() < 0.5);
+            assert!(!offsets[..i].contains(offset));
+        }
+        assert_eq!(taa_jitter(JITTER_SAMPLES), offsets[0]);
+    }
+}
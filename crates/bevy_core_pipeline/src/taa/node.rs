@@ -0,0 +1,132 @@
+use crate::{
+    color_grading::ViewColorGradingTextures,
+    dof::ViewDepthOfFieldTextures,
+    fxaa::ViewFxaaTextures,
+    motion_blur::ViewMotionBlurTextures,
+    taa::{ViewTaaResolve, ViewTaaTextures},
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Resolves the temporal anti-aliasing of the views with
+/// [`TemporalAntiAliasing`](crate::taa::TemporalAntiAliasing), after their main 3d pass.
+pub struct TaaNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewTaaTextures,
+            &'static ViewTaaResolve,
+            Option<&'static ViewDepthOfFieldTextures>,
+            Option<&'static ViewMotionBlurTextures>,
+            Option<&'static ViewColorGradingTextures>,
+            Option<&'static ViewFxaaTextures>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl TaaNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for TaaNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(TaaNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (
+            camera,
+            target,
+            textures,
+            resolve,
+            dof_textures,
+            motion_blur_textures,
+            color_grading_textures,
+            fxaa_textures,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No temporal anti-aliasing for this view
+        };
+        // The depth of field, motion blur, color grading and FXAA are applied after the temporal
+        // anti-aliasing
+        let output = dof_textures
+            .map(|textures| &textures.main_target.view)
+            .or_else(|| motion_blur_textures.map(|textures| &textures.main_target.view))
+            .or_else(|| color_grading_textures.map(|textures| &textures.main_target.view))
+            .or_else(|| fxaa_textures.map(|textures| &textures.main_target.view))
+            .unwrap_or(&target.view);
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(resolve.pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _taa_pass_3d_span = info_span!("taa_pass_3d").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("taa_pass_3d"),
+            // NOTE: The whole viewport is overwritten, in the target and in the history
+            color_attachments: &[
+                Some(RenderPassColorAttachment {
+                    view: output,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                }),
+                Some(RenderPassColorAttachment {
+                    view: &textures.history_write,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                }),
+            ],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(0, &resolve.bind_group, &[]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
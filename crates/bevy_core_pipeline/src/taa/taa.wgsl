@@ -0,0 +1,73 @@
+struct Taa {
+    // From the clip space of the current frame to the clip space of the previous frame
+    reprojection: mat4x4<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+    // The weight of the current frame in the blended color, 1.0 without history
+    current_weight: f32,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var history_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var depth_texture: texture_depth_2d;
+@group(0) @binding(3)
+var history_sampler: sampler;
+@group(0) @binding(4)
+var<uniform> taa: Taa;
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+struct TaaOutput {
+    @location(0) view_target: vec4<f32>,
+    @location(1) history: vec4<f32>,
+};
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> TaaOutput {
+    let size = vec2<i32>(textureDimensions(input_texture));
+    let coords = vec2<i32>(position.xy);
+    let current = textureLoad(input_texture, coords, 0);
+
+    // The history is clamped to the colors around the pixel, to reject the history of what
+    // isn't visible on it anymore
+    var color_min = current.rgb;
+    var color_max = current.rgb;
+    for (var x: i32 = -1; x <= 1; x = x + 1) {
+        for (var y: i32 = -1; y <= 1; y = y + 1) {
+            let neighbor_coords = clamp(coords + vec2<i32>(x, y), vec2<i32>(0), size - 1);
+            let neighbor = textureLoad(input_texture, neighbor_coords, 0).rgb;
+            color_min = min(color_min, neighbor);
+            color_max = max(color_max, neighbor);
+        }
+    }
+
+    // Finds where the pixel was in the previous frame from its depth, assuming the scene
+    // didn't move
+    let depth = textureLoad(depth_texture, coords, 0);
+    let uv = (position.xy - taa.viewport.xy) / taa.viewport.zw;
+    let clip = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let previous_clip = taa.reprojection * clip;
+    let previous_uv = vec2<f32>(0.5, -0.5) * previous_clip.xy / previous_clip.w + 0.5;
+    let history_uv = (taa.viewport.xy + previous_uv * taa.viewport.zw) / vec2<f32>(size);
+
+    var current_weight = taa.current_weight;
+    if (any(previous_uv < vec2<f32>(0.0)) || any(previous_uv > vec2<f32>(1.0))) {
+        // The pixel wasn't visible in the previous frame
+        current_weight = 1.0;
+    }
+    let history = textureSampleLevel(history_texture, history_sampler, history_uv, 0.0).rgb;
+    let color = mix(clamp(history, color_min, color_max), current.rgb, current_weight);
+
+    var out: TaaOutput;
+    out.view_target = vec4<f32>(color, current.a);
+    out.history = vec4<f32>(color, 1.0);
+    return out;
+}
@@ -0,0 +1,192 @@
+//! Screen space specular transmission, for the materials drawn in the [`Transmissive3d`] phase.
+//!
+//! When the [`Transmissive3d`] phase of a view has items, its [`Opaque3d`] and [`AlphaMask3d`]
+//! phases are rendered to a [`ViewTransmissionTexture`] rather than the target of the main pass,
+//! which is then copied onto that target before the transmissive phase is drawn, or resolved onto
+//! it again by that phase with MSAA. The materials of the transmissive phase read that texture,
+//! behind them and offset by their refraction, for the light coming through them.
+//!
+//! Only the opaque scene is seen through transmissive materials: neither the other transmissive
+//! meshes nor the transparent ones are in the texture.
+//!
+//! [`Opaque3d`]: crate::core_3d::Opaque3d
+//! [`AlphaMask3d`]: crate::core_3d::AlphaMask3d
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_phase::RenderPhase,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState, MultisampleState,
+        PipelineCache, PrimitiveState, RenderPipelineDescriptor, Sampler, SamplerDescriptor,
+        Shader, ShaderStages, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureView, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderTextureFormat},
+    texture::TextureCache,
+    RenderApp, RenderStage,
+};
+
+use crate::core_3d::Transmissive3d;
+
+pub const TRANSMISSION_COPY_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8102354924187652239);
+
+/// Prepares the [`ViewTransmissionTexture`] of the views drawing transmissive materials.
+pub struct TransmissionPlugin;
+
+impl Plugin for TransmissionPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TRANSMISSION_COPY_SHADER_HANDLE,
+            "transmission_copy.wgsl",
+            Shader::from_wgsl
+        );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<TransmissionCopyPipeline>()
+            .init_resource::<SpecializedRenderPipelines<TransmissionCopyPipeline>>()
+            .add_system_to_stage(RenderStage::Queue, queue_transmission_textures);
+    }
+}
+
+/// The opaque scene of a view, read by the materials of its [`Transmissive3d`] phase.
+///
+/// Only the views whose transmissive phase has items when [`queue_transmission_textures`] runs
+/// have one, so the systems queuing the phase have to run before it.
+#[derive(Component)]
+pub struct ViewTransmissionTexture {
+    pub view: TextureView,
+    /// A linear sampler to read the texture with.
+    pub sampler: Sampler,
+    /// The pipeline and bind group copying the texture onto the target of the main pass.
+    pub copy_pipeline: CachedRenderPipelineId,
+    pub copy_bind_group: BindGroup,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_transmission_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    texture_format: Res<RenderTextureFormat>,
+    copy_pipeline: Res<TransmissionCopyPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TransmissionCopyPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    views: Query<(Entity, &ExtractedCamera, &RenderPhase<Transmissive3d>)>,
+) {
+    for (entity, camera, transmissive_phase) in &views {
+        if transmissive_phase.items.is_empty() {
+            continue;
+        }
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("view_transmission_texture"),
+                size: Extent3d {
+                    depth_or_array_layers: 1,
+                    width: physical_target_size.x,
+                    height: physical_target_size.y,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: **texture_format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            },
+        );
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &copy_pipeline, **texture_format);
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("transmission_copy_bind_group"),
+            layout: &copy_pipeline.layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&texture.default_view),
+            }],
+        });
+        commands.entity(entity).insert(ViewTransmissionTexture {
+            view: texture.default_view,
+            sampler: copy_pipeline.sampler.clone(),
+            copy_pipeline: pipeline,
+            copy_bind_group: bind_group,
+        });
+    }
+}
+
+#[derive(Resource)]
+pub struct TransmissionCopyPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for TransmissionCopyPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("transmission_copy_layout"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable: false },
+                    view_dimension: TextureViewDimension::D2,
+                },
+                count: None,
+            }],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("view_transmission_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        TransmissionCopyPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for TransmissionCopyPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TRANSMISSION_COPY_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: TRANSMISSION_COPY_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("transmission_copy_pipeline".into()),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Upscaling, for the cameras rendering at a fraction of their resolution with
+//! [`Camera::render_scale`](bevy_render::camera::Camera::render_scale).
+//!
+//! These cameras render to an intermediate texture of the scaled size of their viewport instead
+//! of their target, which the [`UpscalingNode`] then resamples to their viewport in their target
+//! after all the other post-processing. The UI is rendered after, at the full resolution.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    render_resource::{
+        AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+        BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, FilterMode, FragmentState,
+        MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor, Sampler,
+        SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, TextureFormat, TextureSampleType, TextureViewDimension,
+        VertexState,
+    },
+    renderer::RenderDevice,
+    view::{ViewTarget, ViewUpscalingTarget},
+    RenderApp, RenderStage,
+};
+
+pub const UPSCALING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8215947320588125019);
+
+/// Upscales the cameras with a [`Camera::render_scale`](bevy_render::camera::Camera::render_scale)
+/// other than `1.0` to their target.
+pub struct UpscalingPlugin;
+
+impl Plugin for UpscalingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            UPSCALING_SHADER_HANDLE,
+            "upscaling.wgsl",
+            Shader::from_wgsl
+        );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<UpscalingPipeline>()
+            .init_resource::<SpecializedRenderPipelines<UpscalingPipeline>>()
+            .add_system_to_stage(RenderStage::Queue, queue_upscaling_bind_groups);
+    }
+}
+
+/// The pipeline and bind group upscaling a view to its target.
+#[derive(Component)]
+pub struct ViewUpscalingPass {
+    pub pipeline: CachedRenderPipelineId,
+    pub bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct UpscalingPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for UpscalingPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("upscaling_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("upscaling_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        UpscalingPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for UpscalingPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: UPSCALING_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: UPSCALING_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("upscaling_pipeline".into()),
+        }
+    }
+}
+
+pub fn queue_upscaling_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    upscaling_pipeline: Res<UpscalingPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UpscalingPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    views: Query<(Entity, &ViewTarget, &ViewUpscalingTarget)>,
+) {
+    for (entity, target, upscaling_target) in &views {
+        let pipeline = pipelines.specialize(
+            &mut pipeline_cache,
+            &upscaling_pipeline,
+            upscaling_target.format,
+        );
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("upscaling_bind_group"),
+            layout: &upscaling_pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&target.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&upscaling_pipeline.sampler),
+                },
+            ],
+        });
+        commands.entity(entity).insert(ViewUpscalingPass {
+            pipeline,
+            bind_group,
+        });
+    }
+}
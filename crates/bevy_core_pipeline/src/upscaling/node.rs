@@ -0,0 +1,85 @@
+use crate::upscaling::ViewUpscalingPass;
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewUpscalingTarget},
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+/// Upscales the intermediate texture of the views with a
+/// [`Camera::render_scale`](bevy_render::camera::Camera::render_scale) other than `1.0` to their
+/// viewport in their target, after the rest of their post-processing.
+pub struct UpscalingNode {
+    query:
+        QueryState<(&'static ViewUpscalingTarget, &'static ViewUpscalingPass), With<ExtractedView>>,
+}
+
+impl UpscalingNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for UpscalingNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(UpscalingNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (target, pass) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // The view isn't scaled
+        };
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pass.pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        #[cfg(feature = "trace")]
+        let _upscaling_pass_span = info_span!("upscaling_pass").entered();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("upscaling_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        tracked_pass.set_camera_viewport(&target.viewport);
+        tracked_pass.set_render_pipeline(pipeline);
+        tracked_pass.set_bind_group(0, &pass.bind_group, &[]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+//! Helpers for visualizing axis-aligned bounding boxes.
+
+use std::{fmt::Write as _, fs, path::PathBuf};
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{Assets, Handle};
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_math::{Vec3, Vec3A};
+use bevy_render::{color::Color, mesh::Mesh, primitives::Aabb, view::ComputedVisibility};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+use bevy_utils::tracing::{error, info};
+
+use crate::draw::{DebugDraw, DebugDrawSystem};
+
+/// Which AABB the debug box of an entity visualizes.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub enum AabbSource {
+    /// The entity's [`Aabb`] component, as used for frustum culling.
+    #[default]
+    FromComponent,
+    /// A fixed AABB in world space, unaffected by the entity's transform.
+    Custom(Aabb),
+    /// The AABB of a mesh, computed with [`Mesh::compute_aabb`] and placed with the
+    /// entity's transform.
+    FromMesh(Handle<Mesh>),
+}
+
+impl AabbSource {
+    /// Returns the transform of a unit cube that covers the AABB selected by this source.
+    ///
+    /// `component` is the entity's [`Aabb`] component and `transform` its [`GlobalTransform`].
+    /// Returns `None` if the selected AABB isn't available, e.g. because the entity has no
+    /// [`Aabb`] or the mesh isn't loaded yet.
+    pub fn box_transform(
+        &self,
+        component: Option<&Aabb>,
+        transform: &GlobalTransform,
+        meshes: &Assets<Mesh>,
+    ) -> Option<Transform> {
+        match self {
+            AabbSource::FromComponent => Some(aabb_box_transform(component?, transform)),
+            AabbSource::Custom(aabb) => Some(aabb_box_transform(aabb, &GlobalTransform::IDENTITY)),
+            AabbSource::FromMesh(handle) => {
+                let aabb = meshes.get(handle)?.compute_aabb()?;
+                Some(aabb_box_transform(&aabb, transform))
+            }
+        }
+    }
+}
+
+/// Returns the transform of a unit cube that covers `aabb`, given in the local space of
+/// `transform`.
+pub fn aabb_box_transform(aabb: &Aabb, transform: &GlobalTransform) -> Transform {
+    let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+    Transform {
+        translation: translation + Vec3::from(aabb.center),
+        rotation,
+        scale: scale * Vec3::from(aabb.half_extents * 2.0),
+    }
+}
+
+/// Returns the smallest world-space AABB containing `aabb`, given in the local space of
+/// `transform`.
+pub fn world_aabb(aabb: &Aabb, transform: &GlobalTransform) -> Aabb {
+    let matrix = transform.compute_matrix();
+    let (mut min, mut max) = (Vec3A::splat(f32::MAX), Vec3A::splat(f32::MIN));
+    for corner in 0..8 {
+        let sign = Vec3A::new(
+            if corner & 1 == 0 { -1.0 } else { 1.0 },
+            if corner & 2 == 0 { -1.0 } else { 1.0 },
+            if corner & 4 == 0 { -1.0 } else { 1.0 },
+        );
+        let point = matrix.transform_point3a(aabb.center + sign * aabb.half_extents);
+        min = min.min(point);
+        max = max.max(point);
+    }
+    Aabb::from_min_max(min.into(), max.into())
+}
+
+/// Adds the [`DrawAabbs`] resource, drawing the world-space AABB of every visible entity with
+/// an [`Aabb`].
+///
+/// Requires the [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin).
+#[derive(Default)]
+pub struct DrawAabbsPlugin;
+
+impl Plugin for DrawAabbsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DrawAabbs>().add_system_to_stage(
+            CoreStage::PostUpdate,
+            draw_aabbs
+                .after(TransformSystem::TransformPropagate)
+                .before(DebugDrawSystem::UpdateMesh),
+        );
+    }
+}
+
+/// Configuration of the [`DrawAabbsPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct DrawAabbs {
+    /// Whether the AABBs are drawn.
+    pub enabled: bool,
+    /// The color of the AABBs.
+    pub color: Color,
+}
+
+impl Default for DrawAabbs {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            color: Color::GREEN,
+        }
+    }
+}
+
+fn draw_aabbs(
+    config: Res<DrawAabbs>,
+    mut draw: ResMut<DebugDraw>,
+    query: Query<(&Aabb, &GlobalTransform, &ComputedVisibility)>,
+) {
+    if !config.enabled {
+        return;
+    }
+    for (aabb, transform, visibility) in &query {
+        if visibility.is_visible_in_hierarchy() {
+            draw.aabb(&world_aabb(aabb, transform), config.color);
+        }
+    }
+}
+
+/// Adds the [`DumpAabbs`] event, writing the world-space AABB of every entity to a file.
+#[derive(Default)]
+pub struct DumpAabbsPlugin;
+
+impl Plugin for DumpAabbsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DumpAabbs>().add_system_to_stage(
+            CoreStage::PostUpdate,
+            dump_aabbs.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Send this event to write the world-space AABB of every entity with an [`Aabb`] to `path`,
+/// at the end of the frame.
+///
+/// The file is a CSV table with a `name,min_x,min_y,min_z,max_x,max_y,max_z` header and one
+/// row per entity, sorted by name so dumps of different runs can be diffed. Entities are named
+/// by their [`Name`], or `entity <id>` if they don't have one.
+#[derive(Debug, Clone)]
+pub struct DumpAabbs {
+    /// The file to write, which is replaced if it exists.
+    pub path: PathBuf,
+}
+
+fn dump_aabbs(
+    mut events: EventReader<DumpAabbs>,
+    query: Query<(Entity, &Aabb, &GlobalTransform, Option<&Name>)>,
+) {
+    for event in events.iter() {
+        let mut rows: Vec<(String, Aabb)> = query
+            .iter()
+            .map(|(entity, aabb, transform, name)| {
+                let name = match name {
+                    Some(name) => name.to_string(),
+                    None => format!("entity {}", entity.id()),
+                };
+                (name, world_aabb(aabb, transform))
+            })
+            .collect();
+        rows.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut csv = String::from("name,min_x,min_y,min_z,max_x,max_y,max_z\n");
+        for (name, aabb) in &rows {
+            let (min, max) = (aabb.min(), aabb.max());
+            writeln!(
+                csv,
+                "\"{}\",{},{},{},{},{},{}",
+                name.replace('"', "\"\""),
+                min.x,
+                min.y,
+                min.z,
+                max.x,
+                max.y,
+                max.z
+            )
+            .unwrap();
+        }
+
+        match fs::write(&event.path, csv) {
+            Ok(()) => info!("Dumped {} AABBs to {}", rows.len(), event.path.display()),
+            Err(err) => error!("Failed to dump AABBs to {}: {}", event.path.display(), err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_render::mesh::shape;
+
+    use super::*;
+
+    fn mesh_assets() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>();
+        app
+    }
+
+    #[test]
+    fn custom_source_ignores_entity_scale() {
+        let app = mesh_assets();
+        let meshes = app.world.resource::<Assets<Mesh>>();
+        let custom = Aabb::from_min_max(Vec3::ZERO, Vec3::new(2.0, 4.0, 6.0));
+        let source = AabbSource::Custom(custom.clone());
+        let component = Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5));
+
+        let mut transform = Transform::from_xyz(5.0, 0.0, 0.0);
+        let expected = Transform::from_xyz(1.0, 2.0, 3.0).with_scale(Vec3::new(2.0, 4.0, 6.0));
+        for scale in [1.0, 3.0, 0.25] {
+            transform.scale = Vec3::splat(scale);
+            let global = GlobalTransform::from(transform);
+            assert_eq!(
+                source.box_transform(Some(&component), &global, meshes),
+                Some(expected)
+            );
+            // The default source follows the entity's scale
+            assert_eq!(
+                AabbSource::default().box_transform(Some(&component), &global, meshes),
+                Some(transform)
+            );
+        }
+    }
+
+    #[test]
+    fn mesh_source_uses_mesh_aabb() {
+        let mut app = mesh_assets();
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let handle = meshes.add(shape::Box::new(2.0, 4.0, 6.0).into());
+        let source = AabbSource::FromMesh(handle);
+
+        let transform = GlobalTransform::from(Transform::from_scale(Vec3::splat(2.0)));
+        assert_eq!(
+            source.box_transform(None, &transform, &meshes),
+            Some(Transform::from_scale(Vec3::new(4.0, 8.0, 12.0)))
+        );
+        assert_eq!(
+            AabbSource::FromComponent.box_transform(None, &transform, &meshes),
+            None
+        );
+    }
+
+    #[test]
+    fn dumps_world_aabbs_of_all_entities() {
+        let mut app = App::new();
+        app.add_plugin(DumpAabbsPlugin);
+
+        let unit = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        app.world.spawn((
+            unit.clone(),
+            GlobalTransform::from(Transform::from_xyz(10.0, 0.0, 0.0)),
+            Name::new("crate, \"large\""),
+        ));
+        let unnamed = app
+            .world
+            .spawn((
+                unit,
+                GlobalTransform::from(Transform::from_scale(Vec3::new(1.0, 2.0, 3.0))),
+            ))
+            .id();
+
+        let path = std::env::temp_dir().join(format!("dump_aabbs_{}.csv", std::process::id()));
+        app.world.send_event(DumpAabbs { path: path.clone() });
+        app.update();
+        let csv = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("name,min_x,min_y,min_z,max_x,max_y,max_z")
+        );
+        let rows: Vec<(String, Vec<f32>)> = lines
+            .map(|line| {
+                let (name, values) = line.rsplit_once('"').unwrap();
+                let values = values
+                    .trim_start_matches(',')
+                    .split(',')
+                    .map(|value| value.parse().unwrap())
+                    .collect();
+                (name[1..].replace("\"\"", "\""), values)
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    String::from("crate, \"large\""),
+                    vec![9.0, -1.0, -1.0, 11.0, 1.0, 1.0]
+                ),
+                (
+                    format!("entity {}", unnamed.id()),
+                    vec![-1.0, -2.0, -3.0, 1.0, 2.0, 3.0]
+                ),
+            ]
+        );
+    }
+}
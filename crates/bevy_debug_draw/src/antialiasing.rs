@@ -0,0 +1,185 @@
+//! A runtime toggle between anti-aliasing modes, to compare how debug visuals look with each.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_core_pipeline::{core_3d::Camera3d, fxaa::Fxaa};
+use bevy_ecs::prelude::*;
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_render::view::Msaa;
+use bevy_utils::tracing::info;
+
+/// Adds the [`AntiAliasing`] resource, and cycles through its modes when
+/// [`AntiAliasingConfig::cycle_key`] is pressed.
+///
+/// The initial mode is taken from the [`Msaa`] resource if it was inserted before this plugin.
+#[derive(Default)]
+pub struct AntiAliasingPlugin;
+
+impl Plugin for AntiAliasingPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<AntiAliasing>() {
+            let samples = app
+                .world
+                .get_resource::<Msaa>()
+                .cloned()
+                .unwrap_or_default()
+                .samples;
+            app.insert_resource(AntiAliasing::from_msaa_samples(samples));
+        }
+        app.init_resource::<AntiAliasingConfig>()
+            .add_system(cycle_anti_aliasing)
+            .add_system_to_stage(CoreStage::PostUpdate, apply_anti_aliasing);
+    }
+}
+
+/// The anti-aliasing mode used for rendering.
+///
+/// Changing this resource reconfigures [`Msaa`], which every mesh pipeline, including the
+/// wireframe and [`DebugDraw`](crate::draw::DebugDraw) ones, is specialized on, and adds or
+/// removes the [`Fxaa`] of the 3d cameras, so the new mode applies from the next frame without
+/// a restart.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AntiAliasing {
+    /// No anti-aliasing.
+    Off,
+    /// Fast Approximate Anti-Aliasing, a post-processing pass on the 3d cameras.
+    Fxaa,
+    /// Multi-Sample Anti-Aliasing with 4 samples.
+    #[default]
+    Msaa4,
+}
+
+impl AntiAliasing {
+    /// Returns the mode matching a [`Msaa::samples`] count.
+    pub fn from_msaa_samples(samples: u32) -> Self {
+        if samples > 1 {
+            AntiAliasing::Msaa4
+        } else {
+            AntiAliasing::Off
+        }
+    }
+
+    /// The [`Msaa::samples`] count used by this mode.
+    pub fn msaa_samples(self) -> u32 {
+        match self {
+            AntiAliasing::Off | AntiAliasing::Fxaa => 1,
+            AntiAliasing::Msaa4 => 4,
+        }
+    }
+
+    /// The mode after this one when cycling through them.
+    pub fn next(self) -> Self {
+        match self {
+            AntiAliasing::Off => AntiAliasing::Fxaa,
+            AntiAliasing::Fxaa => AntiAliasing::Msaa4,
+            AntiAliasing::Msaa4 => AntiAliasing::Off,
+        }
+    }
+}
+
+/// Configuration of the [`AntiAliasingPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct AntiAliasingConfig {
+    /// The key cycling through the [`AntiAliasing`] modes, or `None` to only change them
+    /// from code.
+    pub cycle_key: Option<KeyCode>,
+}
+
+impl Default for AntiAliasingConfig {
+    fn default() -> Self {
+        Self {
+            cycle_key: Some(KeyCode::F2),
+        }
+    }
+}
+
+fn cycle_anti_aliasing(
+    config: Res<AntiAliasingConfig>,
+    input: Res<Input<KeyCode>>,
+    mut anti_aliasing: ResMut<AntiAliasing>,
+) {
+    if let Some(key) = config.cycle_key {
+        if input.just_pressed(key) {
+            *anti_aliasing = anti_aliasing.next();
+            info!("Anti-aliasing: {:?}", *anti_aliasing);
+        }
+    }
+}
+
+fn apply_anti_aliasing(
+    mut commands: Commands,
+    anti_aliasing: Res<AntiAliasing>,
+    mut msaa: ResMut<Msaa>,
+    cameras_without_fxaa: Query<Entity, (With<Camera3d>, Without<Fxaa>)>,
+    cameras_with_fxaa: Query<Entity, (With<Camera3d>, With<Fxaa>)>,
+) {
+    let samples = anti_aliasing.msaa_samples();
+    if msaa.samples != samples {
+        msaa.samples = samples;
+    }
+    // Also checked every frame for the cameras spawned since the mode changed
+    if *anti_aliasing == AntiAliasing::Fxaa {
+        for camera in &cameras_without_fxaa {
+            commands.entity(camera).insert(Fxaa);
+        }
+    } else {
+        for camera in &cameras_with_fxaa {
+            commands.entity(camera).remove::<Fxaa>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press_cycle_key(app: &mut App) {
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        input.release(KeyCode::F2);
+        input.press(KeyCode::F2);
+        app.update();
+        // Nothing clears the input without the `InputPlugin`
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+    }
+
+    fn has_fxaa(app: &App, camera: Entity) -> bool {
+        app.world.get::<Fxaa>(camera).is_some()
+    }
+
+    #[test]
+    fn cycling_reconfigures_msaa_and_fxaa() {
+        let mut app = App::new();
+        app.insert_resource(Msaa { samples: 4 })
+            .init_resource::<Input<KeyCode>>()
+            .add_plugin(AntiAliasingPlugin);
+        let camera = app.world.spawn(Camera3d::default()).id();
+        app.update();
+        assert_eq!(*app.world.resource::<AntiAliasing>(), AntiAliasing::Msaa4);
+        assert_eq!(app.world.resource::<Msaa>().samples, 4);
+        assert!(!has_fxaa(&app, camera));
+
+        press_cycle_key(&mut app);
+        assert_eq!(*app.world.resource::<AntiAliasing>(), AntiAliasing::Off);
+        assert_eq!(app.world.resource::<Msaa>().samples, 1);
+        assert!(!has_fxaa(&app, camera));
+
+        // Holding the key doesn't cycle again
+        app.update();
+        assert_eq!(*app.world.resource::<AntiAliasing>(), AntiAliasing::Off);
+
+        press_cycle_key(&mut app);
+        assert_eq!(*app.world.resource::<AntiAliasing>(), AntiAliasing::Fxaa);
+        assert_eq!(app.world.resource::<Msaa>().samples, 1);
+        assert!(has_fxaa(&app, camera));
+
+        // Cameras spawned later get the current mode too
+        let other_camera = app.world.spawn(Camera3d::default()).id();
+        app.update();
+        assert!(has_fxaa(&app, other_camera));
+
+        press_cycle_key(&mut app);
+        assert_eq!(*app.world.resource::<AntiAliasing>(), AntiAliasing::Msaa4);
+        assert_eq!(app.world.resource::<Msaa>().samples, 4);
+        assert!(!has_fxaa(&app, camera));
+        assert!(!has_fxaa(&app, other_camera));
+    }
+}
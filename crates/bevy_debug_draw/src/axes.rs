@@ -0,0 +1,52 @@
+//! Arrows along the world axes, to tell directions apart in the scene.
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_render::color::Color;
+
+use crate::draw::DebugDraw;
+
+/// Adds the [`DebugAxes`] resource, drawing arrows along the X (red), Y (green) and Z (blue)
+/// axes from the world origin.
+///
+/// Requires the [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin).
+#[derive(Default)]
+pub struct DebugAxesPlugin;
+
+impl Plugin for DebugAxesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugAxes>().add_system(draw_axes);
+    }
+}
+
+/// Configuration of the [`DebugAxesPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct DebugAxes {
+    /// Whether the axes are drawn.
+    pub enabled: bool,
+    /// The length of the arrows, in world units.
+    pub length: f32,
+}
+
+impl Default for DebugAxes {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            length: 1.0,
+        }
+    }
+}
+
+fn draw_axes(config: Res<DebugAxes>, mut draw: ResMut<DebugDraw>) {
+    if !config.enabled {
+        return;
+    }
+    for (axis, color) in [
+        (Vec3::X, Color::RED),
+        (Vec3::Y, Color::GREEN),
+        (Vec3::Z, Color::BLUE),
+    ] {
+        draw.arrow(Vec3::ZERO, axis * config.length, color);
+    }
+}
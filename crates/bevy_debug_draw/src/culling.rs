@@ -0,0 +1,129 @@
+//! Diagnostics counting the entities drawn and frustum culled by cameras.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::Camera,
+    view::{VisibilitySystems, VisibleEntities},
+};
+
+/// Adds the [`ENTITIES_VISIBLE`](CullingDiagnosticsPlugin::ENTITIES_VISIBLE) and
+/// [`ENTITIES_CULLED`](CullingDiagnosticsPlugin::ENTITIES_CULLED) diagnostics, summed over all
+/// cameras.
+///
+/// The counts of a single camera are in its [`VisibleEntities`]. An entity seen by two cameras
+/// counts twice.
+#[derive(Default)]
+pub struct CullingDiagnosticsPlugin;
+
+impl Plugin for CullingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                Self::diagnostic_system.after(VisibilitySystems::CheckVisibility),
+            );
+    }
+}
+
+impl CullingDiagnosticsPlugin {
+    /// The number of entities visible from cameras this frame.
+    pub const ENTITIES_VISIBLE: DiagnosticId =
+        DiagnosticId::from_u128(272019398217402368939087066011540497237);
+    /// The number of entities culled from cameras this frame.
+    pub const ENTITIES_CULLED: DiagnosticId =
+        DiagnosticId::from_u128(80461281367094135446196417582463380573);
+
+    /// Registers the diagnostics.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::ENTITIES_VISIBLE,
+            "entities_visible",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::ENTITIES_CULLED,
+            "entities_culled",
+            20,
+        ));
+    }
+
+    /// Measures the number of visible and culled entities of all cameras.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        views: Query<&VisibleEntities, With<Camera>>,
+    ) {
+        diagnostics.add_measurement(Self::ENTITIES_VISIBLE, || {
+            views.iter().map(VisibleEntities::len).sum::<usize>() as f64
+        });
+        diagnostics.add_measurement(Self::ENTITIES_CULLED, || {
+            views.iter().map(|view| view.culled).sum::<usize>() as f64
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_math::Vec3;
+    use bevy_render::{
+        camera::OrthographicProjection,
+        mesh::Mesh,
+        prelude::SpatialBundle,
+        primitives::{Aabb, Frustum},
+        view::VisibilityPlugin,
+    };
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::*;
+
+    #[test]
+    fn counts_visible_and_culled_entities() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .init_resource::<Diagnostics>()
+            .add_plugin(VisibilityPlugin)
+            .add_plugin(CullingDiagnosticsPlugin);
+
+        // Sees from -1 to 1 along X and Y, looking down -Z
+        let camera = app
+            .world
+            .spawn((
+                Camera::default(),
+                OrthographicProjection {
+                    left: -1.0,
+                    right: 1.0,
+                    bottom: -1.0,
+                    top: 1.0,
+                    ..Default::default()
+                },
+                GlobalTransform::default(),
+                Frustum::default(),
+                VisibleEntities::default(),
+            ))
+            .id();
+        for x in [0.0, 0.5, 5.0, -5.0, 10.0] {
+            app.world.spawn((
+                SpatialBundle {
+                    global_transform: Transform::from_xyz(x, 0.0, -10.0).into(),
+                    ..Default::default()
+                },
+                Aabb::from_min_max(Vec3::splat(-0.1), Vec3::splat(0.1)),
+            ));
+        }
+        app.update();
+
+        let visible_entities = app.world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(visible_entities.len(), 2);
+        assert_eq!(visible_entities.culled, 3);
+
+        let diagnostics = app.world.resource::<Diagnostics>();
+        let value = |id| diagnostics.get(id).unwrap().value().unwrap();
+        assert_eq!(value(CullingDiagnosticsPlugin::ENTITIES_VISIBLE), 2.0);
+        assert_eq!(value(CullingDiagnosticsPlugin::ENTITIES_CULLED), 3.0);
+    }
+}
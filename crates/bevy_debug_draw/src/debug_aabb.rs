@@ -0,0 +1,639 @@
+//! Translucent boxes showing the AABB of each entity, outlined with wireframes.
+
+use std::ops::Range;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{Assets, Handle};
+use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_ecs::prelude::*;
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_math::{Vec3, Vec3A};
+use bevy_pbr::{
+    wireframe::Wireframe, AlphaMode, NotShadowCaster, NotShadowReceiver, PbrBundle,
+    StandardMaterial,
+};
+use bevy_render::{
+    color::Color,
+    mesh::{shape, Mesh},
+    primitives::Aabb,
+    view::{ComputedVisibility, RenderLayers, Visibility},
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{aabb::AabbSource, draw::DebugDraw, palette::DebugPalette};
+
+/// Adds a translucent box around every entity with an [`Aabb`] or an [`AabbSource`], as
+/// selected by the [`DebugAabbConfig`].
+///
+/// The edges of the boxes are drawn with the
+/// [`WireframePlugin`](bevy_pbr::wireframe::WireframePlugin), which has to be added as well.
+/// The [`DebugBoxStyle::Cage`] style requires the [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin).
+#[derive(Default)]
+pub struct DebugAabbPlugin;
+
+impl Plugin for DebugAabbPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugAabbConfig>()
+            .add_system(toggle_debug_aabbs)
+            .add_system(add_debug_aabbs.after(toggle_debug_aabbs))
+            .add_system(update_debug_aabbs.after(add_debug_aabbs))
+            .add_system(update_debug_aabb_colors.after(add_debug_aabbs))
+            .add_system(draw_debug_aabb_cages.after(update_debug_aabbs));
+    }
+}
+
+/// Configuration of the [`DebugAabbPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct DebugAabbConfig {
+    /// Whether the debug boxes are shown.
+    pub visible: bool,
+    /// The key toggling [`DebugAabbConfig::visible`], if any.
+    pub toggle_key: Option<KeyCode>,
+    /// Which entities get a debug box.
+    pub filter: DebugAabbFilter,
+    /// Keep showing the debug boxes of entities that are hidden, e.g. through their
+    /// [`Visibility`] or the one of an ancestor.
+    pub show_for_hidden: bool,
+    /// The colors the debug boxes are tinted with, one per entity.
+    pub palette: DebugPalette,
+    /// Fill the faces of the debug boxes with a translucent tint from the
+    /// [`palette`](Self::palette). The faces are invisible otherwise.
+    pub tint_faces: bool,
+    /// A single color for all debug boxes, used instead of the [`palette`](Self::palette).
+    ///
+    /// The faces of the boxes use the alpha of the color, while their cage lines are opaque.
+    pub color: Option<Color>,
+    /// Hide the debug boxes a 3d camera is inside of, which would otherwise cover the view.
+    pub hide_when_camera_inside: bool,
+    /// How the debug boxes are outlined.
+    pub box_style: DebugBoxStyle,
+}
+
+impl Default for DebugAabbConfig {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            toggle_key: Some(KeyCode::F7),
+            filter: DebugAabbFilter::All,
+            show_for_hidden: true,
+            palette: DebugPalette::Default,
+            tint_faces: false,
+            color: None,
+            hide_when_camera_inside: false,
+            box_style: DebugBoxStyle::Edges,
+        }
+    }
+}
+
+impl DebugAabbConfig {
+    /// Number of colors the debug boxes cycle through.
+    const BOX_COLORS: usize = 7;
+
+    /// The color of the faces of the debug box of `parent`, which stays the same for the
+    /// lifetime of `parent`.
+    pub fn box_color(&self, parent: Entity) -> Color {
+        if let Some(color) = self.color {
+            return color;
+        }
+        if !self.tint_faces {
+            return Color::NONE;
+        }
+        let mut color = self.palette_color(parent);
+        color.set_a(0.15);
+        color
+    }
+
+    /// The opaque color of the debug box of `parent` in the [`palette`](Self::palette), or the
+    /// [`color`](Self::color) of all debug boxes.
+    pub fn line_color(&self, parent: Entity) -> Color {
+        let mut color = self.color.unwrap_or_else(|| self.palette_color(parent));
+        color.set_a(1.0);
+        color
+    }
+
+    fn palette_color(&self, parent: Entity) -> Color {
+        self.palette.colors(Self::BOX_COLORS)[parent.id() as usize % Self::BOX_COLORS]
+    }
+
+    /// The alpha mode of the faces of the debug boxes, which are discarded when they are
+    /// invisible.
+    fn box_alpha_mode(&self) -> AlphaMode {
+        if self.color.is_none() && !self.tint_faces {
+            AlphaMode::Mask(0.5)
+        } else {
+            AlphaMode::Blend
+        }
+    }
+}
+
+/// Which entities get a debug box from the [`DebugAabbPlugin`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DebugAabbFilter {
+    /// Every entity with an [`Aabb`] or an [`AabbSource`].
+    #[default]
+    All,
+    /// Only the entities marked with [`ShowAabb`].
+    Marked,
+    /// Only the entities on one of these layers, with entities without [`RenderLayers`] being
+    /// on the default layer.
+    ///
+    /// The debug boxes are put on the layers of their entity, so that they are seen by the
+    /// same cameras.
+    RenderLayers(RenderLayers),
+}
+
+impl DebugAabbFilter {
+    /// Whether an entity with these components gets a debug box.
+    pub fn includes(&self, marked: bool, layers: Option<&RenderLayers>) -> bool {
+        match self {
+            DebugAabbFilter::All => true,
+            DebugAabbFilter::Marked => marked,
+            DebugAabbFilter::RenderLayers(filter) => {
+                filter.intersects(layers.unwrap_or(&RenderLayers::default()))
+            }
+        }
+    }
+}
+
+/// Marks the entities getting a debug box with [`DebugAabbFilter::Marked`].
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ShowAabb;
+
+/// Makes the debug box of an entity only enclose the part of its mesh drawn by this index range.
+#[derive(Component, Debug, Clone)]
+pub struct DebugAabbRange(pub Range<usize>);
+
+/// The lines drawn for each debug box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugBoxStyle {
+    /// The wireframe of the box mesh only.
+    #[default]
+    Edges,
+    /// A cage of lines over the edges: each face also gets a grid of `subdivisions` lines in
+    /// both directions, which shows the shape of large boxes better.
+    Cage {
+        /// Number of lines across each face, in both directions.
+        subdivisions: u32,
+    },
+}
+
+/// A debug box, added by the [`DebugAabbPlugin`] for the entity it contains.
+#[derive(Component, Debug)]
+pub struct DebugAabb(pub Entity);
+
+/// Marks the entities that have a debug box.
+#[derive(Component, Debug)]
+struct DebugAabbParent;
+
+/// The components of the debug box of an entity.
+///
+/// The box is a unit cube, scaled to the AABB of `debug_aabb`'s entity by
+/// `update_debug_aabbs`. Its wireframe is drawn on top of `pbr`, without shadows.
+#[derive(Bundle)]
+struct DebugAabbBundle {
+    pbr: PbrBundle,
+    not_shadow_caster: NotShadowCaster,
+    not_shadow_receiver: NotShadowReceiver,
+    wireframe: Wireframe,
+    debug_aabb: DebugAabb,
+}
+
+impl DebugAabbBundle {
+    /// A debug box for `parent`, with a default mesh and material.
+    fn new(parent: Entity) -> Self {
+        Self {
+            pbr: Default::default(),
+            not_shadow_caster: NotShadowCaster,
+            not_shadow_receiver: NotShadowReceiver,
+            wireframe: Wireframe,
+            debug_aabb: DebugAabb(parent),
+        }
+    }
+}
+
+fn toggle_debug_aabbs(input: Option<Res<Input<KeyCode>>>, mut config: ResMut<DebugAabbConfig>) {
+    if let (Some(input), Some(key)) = (input, config.toggle_key) {
+        if input.just_pressed(key) {
+            config.visible = !config.visible;
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn add_debug_aabbs(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<DebugAabbConfig>,
+    aabb_query: Query<
+        (Entity, Option<&ShowAabb>, Option<&RenderLayers>),
+        (
+            Or<(With<Aabb>, With<AabbSource>)>,
+            Without<DebugAabb>,
+            Without<DebugAabbParent>,
+        ),
+    >,
+) {
+    for (parent_entity, marked, layers) in &aabb_query {
+        if !config.filter.includes(marked.is_some(), layers) {
+            continue;
+        }
+
+        let mut debug_aabb = commands.spawn(DebugAabbBundle {
+            pbr: PbrBundle {
+                mesh: meshes.add(shape::Cube::default().into()),
+                material: materials.add(
+                    StandardMaterial::default()
+                        .with_base_color(config.box_color(parent_entity))
+                        .with_alpha_mode(config.box_alpha_mode())
+                        .with_double_sided(true)
+                        .with_cull_mode(None)
+                        .with_unlit(true),
+                ),
+                visibility: Visibility {
+                    is_visible: config.visible,
+                },
+                ..Default::default()
+            },
+            ..DebugAabbBundle::new(parent_entity)
+        });
+        if let Some(layers) = layers {
+            debug_aabb.insert(*layers);
+        }
+
+        commands.entity(parent_entity).insert(DebugAabbParent);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_debug_aabbs(
+    mut commands: Commands,
+    config: Res<DebugAabbConfig>,
+    meshes: Res<Assets<Mesh>>,
+    mut debug_aabb_query: Query<(Entity, &DebugAabb, &mut Transform, &mut Visibility)>,
+    cameras: Query<&GlobalTransform, With<Camera3d>>,
+    aabb_query: Query<
+        (
+            Option<&Aabb>,
+            &GlobalTransform,
+            Option<&AabbSource>,
+            Option<&Handle<Mesh>>,
+            Option<&DebugAabbRange>,
+            Option<&ComputedVisibility>,
+            Option<&ShowAabb>,
+            Option<&RenderLayers>,
+        ),
+        With<DebugAabbParent>,
+    >,
+) {
+    let default_source = AabbSource::default();
+    for (debug_aabb_entity, debug_aabb, mut transform, mut visibility) in &mut debug_aabb_query {
+        let (
+            aabb,
+            aabb_transform,
+            source,
+            mesh_handle,
+            aabb_range,
+            computed_visibility,
+            marked,
+            layers,
+        ) = match aabb_query.get(debug_aabb.0) {
+            Ok(x) => x,
+            Err(_) => {
+                commands.try_despawn(debug_aabb_entity);
+                continue;
+            }
+        };
+
+        // The filter changed: the entity gets a new box if it's included again later
+        if !config.filter.includes(marked.is_some(), layers) {
+            commands.entity(debug_aabb_entity).despawn();
+            commands.entity(debug_aabb.0).remove::<DebugAabbParent>();
+            continue;
+        }
+
+        // Only the hierarchy matters here: a parent culled by the frustum should keep its box
+        let parent_hidden =
+            computed_visibility.map_or(false, |computed| !computed.is_visible_in_hierarchy());
+        visibility.is_visible = config.visible && (config.show_for_hidden || !parent_hidden);
+
+        let range_aabb = aabb_range.zip(mesh_handle).and_then(|(range, handle)| {
+            meshes
+                .get(handle)
+                .and_then(|mesh| mesh.compute_aabb_range(range.0.clone()))
+        });
+        let aabb = range_aabb.as_ref().or(aabb);
+
+        if let Some(box_transform) =
+            source
+                .unwrap_or(&default_source)
+                .box_transform(aabb, aabb_transform, &meshes)
+        {
+            *transform = box_transform;
+        }
+
+        if config.hide_when_camera_inside {
+            // The box is a unit cube in its local space
+            let unit_cube = Aabb {
+                center: Vec3A::ZERO,
+                half_extents: Vec3A::splat(0.5),
+            };
+            let world_to_box = transform.compute_matrix().inverse();
+            let camera_inside = cameras.iter().any(|camera| {
+                unit_cube.contains_point(world_to_box.transform_point3a(camera.translation_vec3a()))
+            });
+            visibility.is_visible &= !camera_inside;
+        }
+    }
+}
+
+fn draw_debug_aabb_cages(
+    draw: Option<ResMut<DebugDraw>>,
+    config: Res<DebugAabbConfig>,
+    query: Query<(&DebugAabb, &Transform, &Visibility)>,
+) {
+    let subdivisions = match config.box_style {
+        DebugBoxStyle::Edges => return,
+        DebugBoxStyle::Cage { subdivisions } => subdivisions,
+    };
+    let mut draw = match draw {
+        Some(draw) => draw,
+        None => return,
+    };
+    for (debug_aabb, transform, visibility) in &query {
+        if !visibility.is_visible {
+            continue;
+        }
+        let color = config.line_color(debug_aabb.0);
+        let matrix = transform.compute_matrix();
+        // Each face of the unit cube, as its normal and the two axes spanning it
+        for (normal, u, v) in [
+            (Vec3::X, Vec3::Y, Vec3::Z),
+            (Vec3::Y, Vec3::Z, Vec3::X),
+            (Vec3::Z, Vec3::X, Vec3::Y),
+        ] {
+            for side in [-0.5, 0.5] {
+                let center = normal * side;
+                for i in 1..=subdivisions {
+                    let t = i as f32 / (subdivisions + 1) as f32 - 0.5;
+                    for (along, across) in [(u, v), (v, u)] {
+                        let start = center + along * t - across * 0.5;
+                        let end = center + along * t + across * 0.5;
+                        draw.line(
+                            matrix.transform_point3(start),
+                            matrix.transform_point3(end),
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn update_debug_aabb_colors(
+    config: Res<DebugAabbConfig>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<(&DebugAabb, &Handle<StandardMaterial>)>,
+) {
+    if !config.is_changed() {
+        return;
+    }
+    for (debug_aabb, handle) in &query {
+        if let Some(material) = materials.get_mut(handle) {
+            material.base_color = config.box_color(debug_aabb.0);
+            material.alpha_mode = config.box_alpha_mode();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_render::{prelude::SpatialBundle, view::VisibilityPlugin};
+
+    use super::*;
+
+    fn app(config: DebugAabbConfig) -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .init_resource::<DebugDraw>()
+            .insert_resource(config)
+            .add_plugin(DebugAabbPlugin);
+        app
+    }
+
+    fn box_visibility(app: &mut App) -> bool {
+        let mut query = app.world.query_filtered::<&Visibility, With<DebugAabb>>();
+        query.single(&app.world).is_visible
+    }
+
+    fn boxed_entities(app: &mut App) -> Vec<Entity> {
+        let mut query = app.world.query::<&DebugAabb>();
+        let mut entities: Vec<_> = query
+            .iter(&app.world)
+            .map(|debug_aabb| debug_aabb.0)
+            .collect();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn boxes_of_hidden_parents_follow_config() {
+        for show_for_hidden in [true, false] {
+            let mut app = app(DebugAabbConfig {
+                show_for_hidden,
+                ..Default::default()
+            });
+            app.add_plugin(VisibilityPlugin);
+
+            let parent = app
+                .world
+                .spawn((SpatialBundle::default(), Aabb::default()))
+                .id();
+            app.update();
+            app.update();
+            assert!(box_visibility(&mut app));
+
+            app.world.get_mut::<Visibility>(parent).unwrap().is_visible = false;
+            // The parent's `ComputedVisibility` is updated at the end of the frame
+            app.update();
+            app.update();
+            assert_eq!(box_visibility(&mut app), show_for_hidden);
+        }
+    }
+
+    #[test]
+    fn toggle_key_hides_boxes() {
+        let mut app = app(DebugAabbConfig::default());
+        app.init_resource::<Input<KeyCode>>();
+        app.world.spawn((SpatialBundle::default(), Aabb::default()));
+        app.update();
+        app.update();
+        assert!(box_visibility(&mut app));
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::F7);
+        app.update();
+        assert!(!app.world.resource::<DebugAabbConfig>().visible);
+        assert!(!box_visibility(&mut app));
+    }
+
+    #[test]
+    fn boxes_hide_when_camera_is_inside() {
+        let mut app = app(DebugAabbConfig {
+            hide_when_camera_inside: true,
+            ..Default::default()
+        });
+
+        app.world.spawn((
+            SpatialBundle {
+                global_transform: Transform::from_xyz(10.0, 0.0, 0.0).into(),
+                ..Default::default()
+            },
+            Aabb::from_min_max(Vec3::splat(-2.0), Vec3::splat(2.0)),
+        ));
+        let camera = app
+            .world
+            .spawn((
+                Camera3d::default(),
+                GlobalTransform::from(Transform::from_xyz(0.0, 0.0, 0.0)),
+            ))
+            .id();
+        app.update();
+        app.update();
+        assert!(box_visibility(&mut app));
+
+        *app.world.get_mut::<GlobalTransform>(camera).unwrap() =
+            Transform::from_xyz(11.0, 1.0, -1.5).into();
+        app.update();
+        assert!(!box_visibility(&mut app));
+
+        *app.world.get_mut::<GlobalTransform>(camera).unwrap() =
+            Transform::from_xyz(11.0, 1.0, -2.5).into();
+        app.update();
+        assert!(box_visibility(&mut app));
+    }
+
+    #[test]
+    fn debug_aabb_bundle_has_all_components() {
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        let debug_aabb = world.spawn(DebugAabbBundle::new(parent)).id();
+
+        let entity = world.entity(debug_aabb);
+        assert!(entity.contains::<Handle<Mesh>>());
+        assert!(entity.contains::<Handle<StandardMaterial>>());
+        assert!(entity.contains::<Transform>());
+        assert!(entity.contains::<GlobalTransform>());
+        assert!(entity.contains::<Visibility>());
+        assert!(entity.contains::<ComputedVisibility>());
+        assert!(entity.contains::<NotShadowCaster>());
+        assert!(entity.contains::<NotShadowReceiver>());
+        assert!(entity.contains::<Wireframe>());
+        assert_eq!(entity.get::<DebugAabb>().unwrap().0, parent);
+    }
+
+    #[test]
+    fn filter_selects_boxed_entities() {
+        let mut app = app(DebugAabbConfig {
+            filter: DebugAabbFilter::Marked,
+            ..Default::default()
+        });
+        let plain = app.world.spawn(Aabb::default()).id();
+        let marked = app.world.spawn((Aabb::default(), ShowAabb)).id();
+        let layer_1 = app
+            .world
+            .spawn((Aabb::default(), RenderLayers::layer(1)))
+            .id();
+        for entity in [plain, marked, layer_1] {
+            app.world
+                .entity_mut(entity)
+                .insert(GlobalTransform::default());
+        }
+        app.update();
+        assert_eq!(boxed_entities(&mut app), vec![marked]);
+
+        // Boxes follow the parent's layers
+        app.world.resource_mut::<DebugAabbConfig>().filter =
+            DebugAabbFilter::RenderLayers(RenderLayers::layer(1));
+        app.update();
+        app.update();
+        assert_eq!(boxed_entities(&mut app), vec![layer_1]);
+        let mut query = app.world.query_filtered::<&RenderLayers, With<DebugAabb>>();
+        assert_eq!(*query.single(&app.world), RenderLayers::layer(1));
+
+        app.world.resource_mut::<DebugAabbConfig>().filter = DebugAabbFilter::All;
+        app.update();
+        app.update();
+        assert_eq!(boxed_entities(&mut app), vec![plain, marked, layer_1]);
+    }
+
+    #[test]
+    fn cage_style_adds_grid_lines() {
+        let mut app = app(DebugAabbConfig::default());
+
+        for x in [0.0, 5.0] {
+            app.world.spawn((
+                SpatialBundle {
+                    global_transform: Transform::from_xyz(x, 0.0, 0.0).into(),
+                    ..Default::default()
+                },
+                Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0)),
+            ));
+        }
+        app.update();
+        app.update();
+        assert!(app.world.resource::<DebugDraw>().lines().is_empty());
+
+        app.world.resource_mut::<DebugAabbConfig>().box_style =
+            DebugBoxStyle::Cage { subdivisions: 3 };
+        app.update();
+        // 3 lines in both directions on the 6 faces of both boxes
+        let lines = app.world.resource::<DebugDraw>().lines();
+        assert_eq!(lines.len(), 2 * 6 * 2 * 3);
+        // The lines stay on the surface of the boxes
+        for line in lines {
+            for point in [line.start, line.end] {
+                let local = point - Vec3::X * if point.x > 2.5 { 5.0 } else { 0.0 };
+                assert!((local.abs().max_element() - 1.0).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn faces_are_only_tinted_when_opted_in() {
+        let mut app = app(DebugAabbConfig::default());
+        let parent = app
+            .world
+            .spawn((SpatialBundle::default(), Aabb::default()))
+            .id();
+        app.update();
+
+        let face_material = |app: &mut App| {
+            let mut query = app
+                .world
+                .query_filtered::<&Handle<StandardMaterial>, With<DebugAabb>>();
+            let handle = query.single(&app.world);
+            let material = app.world.resource::<Assets<StandardMaterial>>().get(handle);
+            let material = material.unwrap();
+            (material.base_color, material.alpha_mode)
+        };
+        assert_eq!(face_material(&mut app), (Color::NONE, AlphaMode::Mask(0.5)));
+
+        app.world.resource_mut::<DebugAabbConfig>().tint_faces = true;
+        app.update();
+        let (mut color, alpha_mode) = face_material(&mut app);
+        assert_eq!(color.a(), 0.15);
+        assert_eq!(alpha_mode, AlphaMode::Blend);
+        // The lines keep the opaque color of the tint
+        color.set_a(1.0);
+        let config = app.world.resource::<DebugAabbConfig>();
+        assert_eq!(config.line_color(parent), color);
+    }
+}
@@ -0,0 +1,261 @@
+//! A screen-space panel of debug text lines, cleared every frame.
+
+use std::fmt::{self, Write};
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_render::color::Color;
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_ui::{entity::TextBundle, PositionType, Style, UiRect, UiSystem, Val};
+use bevy_utils::default;
+
+/// Adds a [`DebugText`] resource whose lines are displayed in the top-left corner of the
+/// primary window.
+///
+/// Requires the [`UiPlugin`](bevy_ui::UiPlugin) and [`TextPlugin`](bevy_text::TextPlugin)
+/// for the text to actually be rendered.
+#[derive(Default)]
+pub struct DebugTextPlugin;
+
+impl Plugin for DebugTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugText>()
+            .init_resource::<DebugTextConfig>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_debug_text_panel
+                    .label(DebugTextSystem)
+                    .before(UiSystem::Flex),
+            );
+    }
+}
+
+/// Label for the system that moves the lines of [`DebugText`] into the on-screen panel.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub struct DebugTextSystem;
+
+/// Lines of text to show in the debug text panel this frame.
+///
+/// Lines are displayed in the order they were added. Every frame, the lines added up to
+/// [`DebugTextSystem`] (which runs in [`CoreStage::PostUpdate`]) are displayed and then
+/// cleared, so systems have to re-add their lines each frame they want them visible.
+/// Lines added after [`DebugTextSystem`] are displayed the following frame.
+///
+/// Lines can be added with [`DebugText::println`], the [`debug_text!`](crate::debug_text!)
+/// macro or [`writeln!`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_debug_draw::{debug_text, debug_text::DebugText};
+/// # use std::fmt::Write;
+/// fn show_entity_count(mut text: ResMut<DebugText>, query: Query<Entity>) {
+///     debug_text!(text, "entities: {}", query.iter().len());
+///     // equivalent to
+///     writeln!(text, "entities: {}", query.iter().len()).unwrap();
+/// }
+/// # bevy_ecs::system::assert_is_system(show_entity_count);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct DebugText {
+    lines: Vec<String>,
+    /// Partial line written through [`fmt::Write`] that hasn't been terminated by a newline yet.
+    pending: String,
+}
+
+impl DebugText {
+    /// Adds a line to the panel.
+    ///
+    /// Values containing newlines are split into multiple lines.
+    pub fn println(&mut self, line: impl fmt::Display) {
+        // Ignoring the result is fine as writing to a `String` never fails.
+        let _ = writeln!(self, "{line}");
+    }
+
+    /// The lines that will be shown in the panel this frame.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Removes all lines.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.pending.clear();
+    }
+
+    fn take_lines(&mut self) -> Vec<String> {
+        if !self.pending.is_empty() {
+            self.lines.push(std::mem::take(&mut self.pending));
+        }
+        std::mem::take(&mut self.lines)
+    }
+}
+
+impl Write for DebugText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(newline) = rest.find('\n') {
+            self.pending.push_str(&rest[..newline]);
+            self.lines.push(std::mem::take(&mut self.pending));
+            rest = &rest[newline + 1..];
+        }
+        self.pending.push_str(rest);
+        Ok(())
+    }
+}
+
+/// Adds a formatted line to a [`DebugText`].
+///
+/// `debug_text!(text, "fps: {}", fps)` is a shorthand for `text.println(format_args!("fps: {}", fps))`.
+#[macro_export]
+macro_rules! debug_text {
+    ($debug_text:expr, $($arg:tt)*) => {
+        $debug_text.println(::std::format_args!($($arg)*))
+    };
+}
+
+/// Appearance of the debug text panel.
+#[derive(Resource, Clone, Debug)]
+pub struct DebugTextConfig {
+    /// The font used for the panel.
+    ///
+    /// Bevy doesn't ship a default font, so this has to be set for the text to be visible.
+    pub font: Handle<Font>,
+    /// The size of the font.
+    pub font_size: f32,
+    /// The color of the text.
+    pub color: Color,
+    /// Distance of the panel from the top-left corner of the window.
+    pub margin: Val,
+}
+
+impl Default for DebugTextConfig {
+    fn default() -> Self {
+        Self {
+            font: default(),
+            font_size: 16.0,
+            color: Color::WHITE,
+            margin: Val::Px(5.0),
+        }
+    }
+}
+
+/// Marker for the entity displaying the [`DebugText`] panel.
+#[derive(Component, Debug, Default)]
+pub struct DebugTextPanel;
+
+fn update_debug_text_panel(
+    mut commands: Commands,
+    mut debug_text: ResMut<DebugText>,
+    config: Res<DebugTextConfig>,
+    mut panels: Query<(&mut Text, &mut Style), With<DebugTextPanel>>,
+) {
+    let style = TextStyle {
+        font: config.font.clone(),
+        font_size: config.font_size,
+        color: config.color,
+        ..Default::default()
+    };
+    let sections = debug_text
+        .take_lines()
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let value = if i == 0 { line } else { format!("\n{line}") };
+            TextSection::new(value, style.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let panel_style = Style {
+        position_type: PositionType::Absolute,
+        position: UiRect {
+            top: config.margin,
+            left: config.margin,
+            ..default()
+        },
+        ..default()
+    };
+
+    match panels.get_single_mut() {
+        Ok((mut text, mut style)) => {
+            // Avoid triggering change detection, and with it text relayout, when nothing changed.
+            let unchanged = text.sections.len() == sections.len()
+                && text
+                    .sections
+                    .iter()
+                    .zip(&sections)
+                    .all(|(old, new)| old.value == new.value);
+            if !unchanged || config.is_changed() {
+                text.sections = sections;
+            }
+            if config.is_changed() {
+                *style = panel_style;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_sections(sections),
+                    style: panel_style,
+                    ..default()
+                },
+                DebugTextPanel,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panel_text(app: &mut App) -> Vec<String> {
+        let mut query = app.world.query_filtered::<&Text, With<DebugTextPanel>>();
+        query
+            .single(&app.world)
+            .sections
+            .iter()
+            .map(|section| section.value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn lines_accumulate_and_clear_each_frame() {
+        #[derive(Resource)]
+        struct Frame(usize);
+
+        fn print_lines(mut text: ResMut<DebugText>, mut frame: ResMut<Frame>) {
+            if frame.0 == 0 {
+                text.println("first");
+                debug_text!(text, "second: {}", 2);
+                write!(text, "third, ").unwrap();
+                write!(text, "unterminated").unwrap();
+            }
+            frame.0 += 1;
+        }
+
+        let mut app = App::new();
+        app.add_plugin(DebugTextPlugin)
+            .insert_resource(Frame(0))
+            .add_system(print_lines);
+
+        app.update();
+        assert_eq!(
+            panel_text(&mut app),
+            ["first", "\nsecond: 2", "\nthird, unterminated"]
+        );
+        assert!(app.world.resource::<DebugText>().lines().is_empty());
+
+        app.update();
+        assert!(panel_text(&mut app).is_empty());
+    }
+
+    #[test]
+    fn multi_line_values_are_split() {
+        let mut text = DebugText::default();
+        text.println("a\nb");
+        assert_eq!(text.lines(), ["a", "b"]);
+        text.clear();
+        assert!(text.lines().is_empty());
+    }
+}
@@ -0,0 +1,753 @@
+//! Immediate-mode drawing of debug lines and filled shapes in world space.
+
+use std::{
+    f32::consts::{PI, TAU},
+    ops::{Deref, DerefMut},
+};
+
+use bevy_app::{App, CoreStage, Plugin, StartupStage};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{Vec3, Vec4};
+use bevy_pbr::{AlphaMode, PbrBundle, SimulationLightSystems, StandardMaterial};
+use bevy_render::{
+    color::Color,
+    mesh::{Mesh, PrimitiveTopology},
+    primitives::{Aabb, Frustum},
+    view::{NoFrustumCulling, Visibility, VisibilitySystems},
+};
+use bevy_transform::components::Transform;
+use bevy_utils::{default, HashSet};
+
+use crate::{frustum::draw_frusta, tbn::draw_tbn};
+
+/// Adds a [`DebugDraw`] resource whose lines and triangles are rendered every frame, and the
+/// systems drawing the built-in debug visualizations such as [`ShowTbn`](crate::tbn::ShowTbn)
+/// and [`ShowFrustum`](crate::frustum::ShowFrustum).
+///
+/// Requires the [`PbrPlugin`](bevy_pbr::PbrPlugin), as lines and triangles are rendered as
+/// unlit [`StandardMaterial`] meshes.
+#[derive(Default)]
+pub struct DebugDrawPlugin;
+
+impl Plugin for DebugDrawPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugDraw>()
+            .init_resource::<DebugDrawConfig>()
+            .add_startup_system_to_stage(StartupStage::PreStartup, setup_debug_draw_mesh)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                draw_tbn.before(DebugDrawSystem::UpdateMesh),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                draw_frusta
+                    .after(VisibilitySystems::CheckVisibility)
+                    .after(SimulationLightSystems::UpdateLightFrusta)
+                    .before(DebugDrawSystem::UpdateMesh),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_debug_draw_mesh
+                    .label(DebugDrawSystem::UpdateMesh)
+                    .after(bevy_transform::TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Labels for the systems of the [`DebugDrawPlugin`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum DebugDrawSystem {
+    /// Moves the lines and triangles of [`DebugDraw`] into the meshes that render them.
+    UpdateMesh,
+}
+
+/// A line segment drawn by [`DebugDraw`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLine {
+    /// World-space start of the line.
+    pub start: Vec3,
+    /// World-space end of the line.
+    pub end: Vec3,
+    /// Color of the line.
+    pub color: Color,
+    /// The group the line was drawn in, see [`DebugDraw::group`].
+    pub group: &'static str,
+}
+
+/// A filled triangle drawn by [`DebugDraw`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugTriangle {
+    /// World-space corners of the triangle.
+    pub vertices: [Vec3; 3],
+    /// Color of the triangle, usually translucent.
+    pub color: Color,
+    /// The group the triangle was drawn in, see [`DebugDraw::group`].
+    pub group: &'static str,
+}
+
+/// Debug lines and filled triangles to draw this frame.
+///
+/// Every frame, the shapes added up to [`DebugDrawSystem::UpdateMesh`] (which runs in
+/// [`CoreStage::PostUpdate`]) are rendered and then cleared, so systems have to re-add
+/// their shapes each frame they want them visible. No entity is spawned per shape, so this
+/// suits transient visuals like raycasts: all lines, and all triangles, share a single mesh.
+///
+/// Lines are opaque and drawn with the rest of the opaque geometry. Triangles are alpha
+/// blended in the transparent pass: they are depth tested against the scene and the lines
+/// but don't write depth, so they never hide lines. All triangles are rendered as a single
+/// mesh, so they are blended in the order they were added rather than sorted by depth.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_debug_draw::draw::DebugDraw;
+/// # use bevy_render::color::Color;
+/// # use bevy_transform::prelude::*;
+/// fn draw_forward(mut draw: ResMut<DebugDraw>, query: Query<&GlobalTransform>) {
+///     for transform in &query {
+///         let start = transform.translation();
+///         draw.line(start, start + transform.forward(), Color::YELLOW);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(draw_forward);
+/// ```
+///
+/// Shapes can be drawn in named groups with [`DebugDraw::group`], which can then be hidden
+/// together with [`DebugDrawConfig::group_enabled`].
+#[derive(Resource, Debug)]
+pub struct DebugDraw {
+    lines: Vec<DebugLine>,
+    triangles: Vec<DebugTriangle>,
+    group: &'static str,
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            triangles: Vec::new(),
+            group: Self::DEFAULT_GROUP,
+        }
+    }
+}
+
+impl DebugDraw {
+    /// The group of the shapes drawn outside of [`DebugDraw::group`].
+    pub const DEFAULT_GROUP: &'static str = "default";
+
+    /// Number of lines in each circle of [`DebugDraw::sphere`].
+    pub const SPHERE_SEGMENTS: usize = 32;
+
+    /// Returns a drawer adding its shapes to the group `name` instead of the default one.
+    ///
+    /// ```
+    /// # use bevy_debug_draw::draw::DebugDraw;
+    /// # use bevy_math::Vec3;
+    /// # use bevy_render::color::Color;
+    /// let mut draw = DebugDraw::default();
+    /// draw.group("physics").line(Vec3::ZERO, Vec3::Y, Color::RED);
+    /// assert_eq!(draw.lines()[0].group, "physics");
+    /// ```
+    pub fn group(&mut self, name: &'static str) -> DebugDrawGroup<'_> {
+        let previous = std::mem::replace(&mut self.group, name);
+        DebugDrawGroup {
+            draw: self,
+            previous,
+        }
+    }
+
+    /// Draws a line from `start` to `end`.
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Color) {
+        self.lines.push(DebugLine {
+            start,
+            end,
+            color,
+            group: self.group,
+        });
+    }
+
+    /// Draws a line from `start` in the direction and length of `vector`.
+    pub fn ray(&mut self, start: Vec3, vector: Vec3, color: Color) {
+        self.line(start, start + vector, color);
+    }
+
+    /// Draws an arrow from `start` to `end`, with a head a quarter of the arrow's length.
+    ///
+    /// See [`DebugDraw::arrow_with`] for how the head is drawn.
+    pub fn arrow(&mut self, start: Vec3, end: Vec3, color: Color) {
+        let head_length = 0.25 * start.distance(end);
+        self.arrow_with(start, end, color, head_length, PI / 6.0);
+    }
+
+    /// Draws an arrow from `start` to `end` whose head is made of 4 lines, each `head_length`
+    /// long along the arrow and opening by `head_angle` (in radians) from the shaft.
+    ///
+    /// The head is shortened to the arrow's length if it is longer, and nothing is drawn for
+    /// arrows whose `start` and `end` are too close to define a direction.
+    pub fn arrow_with(
+        &mut self,
+        start: Vec3,
+        end: Vec3,
+        color: Color,
+        head_length: f32,
+        head_angle: f32,
+    ) {
+        let vector = end - start;
+        let length = vector.length();
+        if length <= f32::EPSILON {
+            return;
+        }
+        self.line(start, end, color);
+
+        let direction = vector / length;
+        let head_length = head_length.min(length);
+        let (u, v) = direction.any_orthonormal_pair();
+        let back = end - direction * head_length;
+        let spread = head_length * head_angle.tan();
+        for side in [u, v, -u, -v] {
+            self.line(end, back + side * spread, color);
+        }
+    }
+
+    /// Draws a circle of `radius` around `center` in the plane perpendicular to `normal`,
+    /// approximated by `segments` lines.
+    ///
+    /// If `normal` is too close to zero to define a plane, the circle is drawn in the XZ plane.
+    pub fn circle(
+        &mut self,
+        center: Vec3,
+        normal: Vec3,
+        radius: f32,
+        color: Color,
+        segments: usize,
+    ) {
+        let normal = if normal.length_squared() > f32::EPSILON {
+            normal.normalize()
+        } else {
+            Vec3::Y
+        };
+        let (u, v) = normal.any_orthonormal_pair();
+        let point = |i: usize| {
+            let angle = i as f32 * TAU / segments as f32;
+            center + radius * (angle.cos() * u + angle.sin() * v)
+        };
+        for i in 0..segments {
+            self.line(point(i), point(i + 1), color);
+        }
+    }
+
+    /// Draws a wireframe sphere of `radius` around `center`, as the three circles where it
+    /// crosses the planes of the axes.
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: Color) {
+        for normal in [Vec3::X, Vec3::Y, Vec3::Z] {
+            self.circle(center, normal, radius, color, Self::SPHERE_SEGMENTS);
+        }
+    }
+
+    /// Draws the 12 edges of `aabb`, in world space.
+    pub fn aabb(&mut self, aabb: &Aabb, color: Color) {
+        let (min, max) = (Vec3::from(aabb.min()), Vec3::from(aabb.max()));
+        self.box_edges(
+            |i| {
+                Vec3::new(
+                    if i & 1 == 0 { min.x } else { max.x },
+                    if i & 2 == 0 { min.y } else { max.y },
+                    if i & 4 == 0 { min.z } else { max.z },
+                )
+            },
+            color,
+        );
+    }
+
+    /// Draws the 12 edges of a cube of size 1 centered on the origin, moved, rotated and
+    /// scaled by `transform`.
+    pub fn cuboid(&mut self, transform: Transform, color: Color) {
+        let matrix = transform.compute_matrix();
+        self.box_edges(
+            |i| {
+                matrix.transform_point3(Vec3::new(
+                    if i & 1 == 0 { -0.5 } else { 0.5 },
+                    if i & 2 == 0 { -0.5 } else { 0.5 },
+                    if i & 4 == 0 { -0.5 } else { 0.5 },
+                ))
+            },
+            color,
+        );
+    }
+
+    /// Draws the 12 edges of `frustum`, between the corners where its planes meet.
+    ///
+    /// Nothing is drawn if the frustum is degenerate, e.g. the default [`Frustum`] of a
+    /// camera whose projection hasn't been computed yet.
+    pub fn frustum(&mut self, frustum: &Frustum, color: Color) {
+        let [left, right, bottom, top, near, far] = frustum.planes.map(|plane| plane.normal_d());
+        let corners = [
+            (left, bottom, near),
+            (right, bottom, near),
+            (left, top, near),
+            (right, top, near),
+            (left, bottom, far),
+            (right, bottom, far),
+            (left, top, far),
+            (right, top, far),
+        ]
+        .map(|(a, b, c)| planes_intersection(a, b, c));
+        if corners.iter().any(|corner| !corner.is_finite()) {
+            return;
+        }
+        self.box_edges(|i| corners[i], color);
+    }
+
+    /// Draws the edges of a box whose corner `i` is at `corner(i)`, with the bits of `i`
+    /// selecting the minimum or maximum along X, Y and Z.
+    fn box_edges(&mut self, corner: impl Fn(usize) -> Vec3, color: Color) {
+        // Each edge joins two corners whose indices differ by one bit
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    self.line(corner(i), corner(i | bit), color);
+                }
+            }
+        }
+    }
+
+    /// Draws a filled triangle with corners `a`, `b` and `c`, visible from both sides.
+    ///
+    /// Use a translucent `color` to see the geometry behind it.
+    pub fn tri(&mut self, a: Vec3, b: Vec3, c: Vec3, color: Color) {
+        self.triangles.push(DebugTriangle {
+            vertices: [a, b, c],
+            color,
+            group: self.group,
+        });
+    }
+
+    /// Draws a filled quad with corners `a`, `b`, `c` and `d` in winding order, as two triangles.
+    pub fn quad(&mut self, a: Vec3, b: Vec3, c: Vec3, d: Vec3, color: Color) {
+        self.tri(a, b, c, color);
+        self.tri(a, c, d, color);
+    }
+
+    /// The lines that will be rendered this frame.
+    pub fn lines(&self) -> &[DebugLine] {
+        &self.lines
+    }
+
+    /// The filled triangles that will be rendered this frame.
+    pub fn triangles(&self) -> &[DebugTriangle] {
+        &self.triangles
+    }
+
+    /// Removes all lines and triangles.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.triangles.clear();
+    }
+}
+
+/// The point where 3 planes meet, given as their normal and signed distance from the origin.
+///
+/// Its coordinates aren't finite if two planes are parallel.
+fn planes_intersection(a: Vec4, b: Vec4, c: Vec4) -> Vec3 {
+    let (na, nb, nc) = (a.truncate(), b.truncate(), c.truncate());
+    let denominator = na.dot(nb.cross(nc));
+    -(a.w * nb.cross(nc) + b.w * nc.cross(na) + c.w * na.cross(nb)) / denominator
+}
+
+/// A [`DebugDraw`] adding its shapes to a named group, returned by [`DebugDraw::group`].
+///
+/// All the drawing methods of [`DebugDraw`] are available through [`DerefMut`].
+pub struct DebugDrawGroup<'a> {
+    draw: &'a mut DebugDraw,
+    previous: &'static str,
+}
+
+impl<'a> Deref for DebugDrawGroup<'a> {
+    type Target = DebugDraw;
+
+    fn deref(&self) -> &DebugDraw {
+        self.draw
+    }
+}
+
+impl<'a> DerefMut for DebugDrawGroup<'a> {
+    fn deref_mut(&mut self) -> &mut DebugDraw {
+        self.draw
+    }
+}
+
+impl<'a> Drop for DebugDrawGroup<'a> {
+    fn drop(&mut self) {
+        self.draw.group = self.previous;
+    }
+}
+
+/// Which groups of [`DebugDraw`] shapes are rendered.
+///
+/// All groups are enabled by default.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DebugDrawConfig {
+    disabled_groups: HashSet<&'static str>,
+}
+
+impl DebugDrawConfig {
+    /// Shows or hides the shapes drawn in the group `name`, starting from the next time
+    /// [`DebugDrawSystem::UpdateMesh`] runs.
+    ///
+    /// Shapes drawn outside of [`DebugDraw::group`] are in [`DebugDraw::DEFAULT_GROUP`].
+    pub fn group_enabled(&mut self, name: &'static str, enabled: bool) {
+        if enabled {
+            self.disabled_groups.remove(name);
+        } else {
+            self.disabled_groups.insert(name);
+        }
+    }
+
+    /// Whether the shapes drawn in the group `name` are rendered.
+    pub fn is_group_enabled(&self, name: &str) -> bool {
+        !self.disabled_groups.contains(name)
+    }
+}
+
+/// Marker for the entity rendering the lines of [`DebugDraw`].
+#[derive(Component)]
+pub struct DebugDrawMesh;
+
+/// Marker for the entity rendering the filled triangles of [`DebugDraw`].
+#[derive(Component)]
+pub struct DebugDrawFillMesh;
+
+fn setup_debug_draw_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(line_mesh(&[])),
+            material: materials.add(StandardMaterial {
+                unlit: true,
+                ..default()
+            }),
+            visibility: Visibility::INVISIBLE,
+            ..default()
+        },
+        // The lines are in world space and change every frame, so the mesh `Aabb`
+        // computed once on spawn would be wrong.
+        NoFrustumCulling,
+        DebugDrawMesh,
+    ));
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(triangle_mesh(&[])),
+            material: materials.add(StandardMaterial {
+                unlit: true,
+                alpha_mode: AlphaMode::Blend,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            }),
+            visibility: Visibility::INVISIBLE,
+            ..default()
+        },
+        NoFrustumCulling,
+        DebugDrawFillMesh,
+    ));
+}
+
+fn update_debug_draw_mesh(
+    mut draw: ResMut<DebugDraw>,
+    config: Res<DebugDrawConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut lines: Query<
+        (&Handle<Mesh>, &mut Visibility),
+        (With<DebugDrawMesh>, Without<DebugDrawFillMesh>),
+    >,
+    mut fills: Query<(&Handle<Mesh>, &mut Visibility), With<DebugDrawFillMesh>>,
+) {
+    if !config.disabled_groups.is_empty() {
+        draw.lines
+            .retain(|line| config.is_group_enabled(line.group));
+        draw.triangles
+            .retain(|triangle| config.is_group_enabled(triangle.group));
+    }
+    for (handle, mut visibility) in &mut lines {
+        // Avoid touching the mesh, and re-uploading it, when there was and is nothing to draw.
+        if draw.lines.is_empty() && !visibility.is_visible {
+            continue;
+        }
+        visibility.is_visible = !draw.lines.is_empty();
+        if let Some(mesh) = meshes.get_mut(handle) {
+            *mesh = line_mesh(&draw.lines);
+        }
+    }
+    for (handle, mut visibility) in &mut fills {
+        if draw.triangles.is_empty() && !visibility.is_visible {
+            continue;
+        }
+        visibility.is_visible = !draw.triangles.is_empty();
+        if let Some(mesh) = meshes.get_mut(handle) {
+            *mesh = triangle_mesh(&draw.triangles);
+        }
+    }
+    draw.clear();
+}
+
+/// Builds a [`PrimitiveTopology::LineList`] mesh with a vertex color per line end.
+fn line_mesh(lines: &[DebugLine]) -> Mesh {
+    let mut positions = Vec::with_capacity(lines.len() * 2);
+    let mut colors = Vec::with_capacity(lines.len() * 2);
+    for line in lines {
+        positions.push(line.start.to_array());
+        positions.push(line.end.to_array());
+        let color = line.color.as_linear_rgba_f32();
+        colors.extend([color, color]);
+    }
+    // Normals aren't used by the unlit material, but the mesh pipeline requires them.
+    let normals = vec![[0.0, 1.0, 0.0]; positions.len()];
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh
+}
+
+/// Builds a [`PrimitiveTopology::TriangleList`] mesh with a vertex color per triangle corner.
+fn triangle_mesh(triangles: &[DebugTriangle]) -> Mesh {
+    let mut positions = Vec::with_capacity(triangles.len() * 3);
+    let mut colors = Vec::with_capacity(triangles.len() * 3);
+    for triangle in triangles {
+        positions.extend(triangle.vertices.map(|vertex| vertex.to_array()));
+        let color = triangle.color.as_linear_rgba_f32();
+        colors.extend([color; 3]);
+    }
+    // Normals aren't used by the unlit material, but the mesh pipeline requires them.
+    let normals = vec![[0.0, 1.0, 0.0]; positions.len()];
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Quat;
+
+    use super::*;
+
+    #[test]
+    fn line_mesh_has_two_vertices_per_line() {
+        let mut draw = DebugDraw::default();
+        draw.line(Vec3::ZERO, Vec3::X, Color::RED);
+        draw.ray(Vec3::Y, Vec3::Z, Color::BLUE);
+        assert_eq!(draw.lines()[1].end, Vec3::new(0.0, 1.0, 1.0));
+
+        let mesh = line_mesh(draw.lines());
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::LineList);
+
+        draw.clear();
+        assert!(draw.lines().is_empty());
+    }
+
+    #[test]
+    fn circle_lies_in_plane() {
+        let mut draw = DebugDraw::default();
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        let normal = Vec3::new(1.0, 1.0, 0.0);
+        draw.circle(center, normal, 2.0, Color::GREEN, 12);
+
+        assert_eq!(draw.lines().len(), 12);
+        for line in draw.lines() {
+            for point in [line.start, line.end] {
+                assert!((point - center).dot(normal.normalize()).abs() < 1e-5);
+                assert!(((point - center).length() - 2.0).abs() < 1e-5);
+            }
+        }
+        // The segments form a closed loop
+        assert!(draw.lines()[11]
+            .end
+            .abs_diff_eq(draw.lines()[0].start, 1e-5));
+
+        // A degenerate normal falls back to the XZ plane
+        draw.clear();
+        draw.circle(center, Vec3::splat(1e-20), 1.0, Color::GREEN, 8);
+        assert_eq!(draw.lines().len(), 8);
+        assert!(draw.lines().iter().all(|line| line.start.y == center.y));
+    }
+
+    #[test]
+    fn sphere_and_cuboid_lie_on_their_surface() {
+        let mut draw = DebugDraw::default();
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        draw.sphere(center, 2.0, Color::GREEN);
+        assert_eq!(draw.lines().len(), 3 * DebugDraw::SPHERE_SEGMENTS);
+        for line in draw.lines() {
+            assert!((line.start.distance(center) - 2.0).abs() < 1e-5);
+            assert!((line.end.distance(center) - 2.0).abs() < 1e-5);
+        }
+
+        draw.clear();
+        let transform = Transform::from_translation(center)
+            .with_rotation(Quat::from_rotation_y(0.5))
+            .with_scale(Vec3::new(2.0, 4.0, 6.0));
+        draw.cuboid(transform, Color::RED);
+        assert_eq!(draw.lines().len(), 12);
+        let to_local = transform.compute_matrix().inverse();
+        for line in draw.lines() {
+            let (start, end) = (
+                to_local.transform_point3(line.start),
+                to_local.transform_point3(line.end),
+            );
+            // Edges join corners of the unit cube differing along a single axis
+            assert!(start.abs().abs_diff_eq(Vec3::splat(0.5), 1e-5));
+            assert!(end.abs().abs_diff_eq(Vec3::splat(0.5), 1e-5));
+            assert!(((end - start).abs().length() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn arrow_head_scales_with_length() {
+        let heads = |length: f32| {
+            let mut draw = DebugDraw::default();
+            draw.arrow(Vec3::ZERO, Vec3::new(0.0, 0.0, length), Color::RED);
+            // The shaft is drawn first, then the lines of the head start from the tip
+            assert_eq!(draw.lines().len(), 5);
+            assert_eq!(draw.lines()[0].end, Vec3::new(0.0, 0.0, length));
+            draw.lines()[1..]
+                .iter()
+                .map(|line| line.end)
+                .collect::<Vec<_>>()
+        };
+        let short = heads(1.0);
+        let long = heads(4.0);
+        for (short, long) in short.iter().zip(&long) {
+            assert!((*short * 4.0).abs_diff_eq(*long, 1e-5));
+        }
+        // The head is a quarter of the arrow, opening by 30 degrees
+        for end in &long {
+            assert!((end.z - 3.0).abs() < 1e-5);
+            assert!((end.truncate().length() - (PI / 6.0).tan()).abs() < 1e-5);
+        }
+
+        // Heads longer than the arrow are shortened, and degenerate arrows aren't drawn
+        let mut draw = DebugDraw::default();
+        draw.arrow_with(Vec3::ZERO, Vec3::X, Color::RED, 5.0, PI / 4.0);
+        assert!(draw.lines()[1..]
+            .iter()
+            .all(|line| line.end.x.abs() < 1e-5 && (line.end.length() - 1.0).abs() < 1e-5));
+        draw.clear();
+        draw.arrow(Vec3::ONE, Vec3::ONE, Color::RED);
+        assert!(draw.lines().is_empty());
+    }
+
+    #[test]
+    fn filled_shapes_are_blended_and_cleared_each_frame() {
+        use bevy_asset::{AddAsset, AssetPlugin};
+        use bevy_core::CorePlugin;
+
+        #[derive(Resource)]
+        struct Frame(usize);
+
+        fn draw_fills(mut draw: ResMut<DebugDraw>, mut frame: ResMut<Frame>) {
+            if frame.0 == 0 {
+                let color = Color::rgba(1.0, 0.0, 0.0, 0.5);
+                draw.tri(Vec3::ZERO, Vec3::X, Vec3::Y, color);
+                draw.quad(
+                    Vec3::ZERO,
+                    Vec3::X,
+                    Vec3::new(1.0, 0.0, 1.0),
+                    Vec3::Z,
+                    color,
+                );
+            }
+            frame.0 += 1;
+        }
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .add_plugin(DebugDrawPlugin)
+            .insert_resource(Frame(0))
+            .add_system(draw_fills);
+
+        app.update();
+        let mut query = app.world.query_filtered::<(
+            &Handle<Mesh>,
+            &Handle<StandardMaterial>,
+            &Visibility,
+        ), With<DebugDrawFillMesh>>();
+        let (mesh, material, visibility) = query.single(&app.world);
+        assert!(visibility.is_visible);
+        // Blended materials are queued in the transparent phase
+        let material = app
+            .world
+            .resource::<Assets<StandardMaterial>>()
+            .get(material)
+            .unwrap();
+        assert_eq!(material.alpha_mode, AlphaMode::Blend);
+        let mesh = app.world.resource::<Assets<Mesh>>().get(mesh).unwrap();
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::TriangleList);
+        assert_eq!(mesh.count_vertices(), 9);
+        assert!(app.world.resource::<DebugDraw>().triangles().is_empty());
+
+        app.update();
+        let (mesh, _, visibility) = query.single(&app.world);
+        assert!(!visibility.is_visible);
+        let mesh = app.world.resource::<Assets<Mesh>>().get(mesh).unwrap();
+        assert_eq!(mesh.count_vertices(), 0);
+    }
+
+    #[test]
+    fn disabled_groups_are_hidden() {
+        use bevy_asset::{AddAsset, AssetPlugin};
+        use bevy_core::CorePlugin;
+
+        fn draw_groups(mut draw: ResMut<DebugDraw>) {
+            draw.group("physics").line(Vec3::ZERO, Vec3::X, Color::RED);
+            {
+                let mut navigation = draw.group("navigation");
+                navigation.line(Vec3::ZERO, Vec3::Y, Color::GREEN);
+                navigation.arrow(Vec3::ZERO, Vec3::Z, Color::GREEN);
+            }
+            draw.line(Vec3::ZERO, -Vec3::X, Color::WHITE);
+        }
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .add_plugin(DebugDrawPlugin)
+            .add_system(draw_groups.before(DebugDrawSystem::UpdateMesh));
+
+        let mut query = app
+            .world
+            .query_filtered::<&Handle<Mesh>, With<DebugDrawMesh>>();
+        let mut rendered_vertices = |app: &mut App| {
+            app.update();
+            let mesh = query.single(&app.world);
+            let mesh = app.world.resource::<Assets<Mesh>>().get(mesh).unwrap();
+            mesh.count_vertices()
+        };
+
+        // 1 physics line, 6 navigation lines and 1 line in the default group
+        assert_eq!(rendered_vertices(&mut app), 16);
+
+        let mut config = app.world.resource_mut::<DebugDrawConfig>();
+        config.group_enabled("navigation", false);
+        assert!(config.is_group_enabled("physics"));
+        assert!(!config.is_group_enabled("navigation"));
+        assert_eq!(rendered_vertices(&mut app), 4);
+
+        let mut config = app.world.resource_mut::<DebugDrawConfig>();
+        config.group_enabled(DebugDraw::DEFAULT_GROUP, false);
+        config.group_enabled("navigation", true);
+        assert_eq!(rendered_vertices(&mut app), 14);
+    }
+}
@@ -0,0 +1,101 @@
+//! A free-flying camera controller, to look at the scene from any point of view.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    keyboard::KeyCode,
+    mouse::{MouseButton, MouseMotion},
+    Input,
+};
+use bevy_math::{EulerRot, Quat, Vec2, Vec3};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+/// Adds the [`fly_camera`] system, moving every entity with an enabled [`FlyCamera`].
+#[derive(Default)]
+pub struct FlyCameraPlugin;
+
+impl Plugin for FlyCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(fly_camera);
+    }
+}
+
+/// Moves the entity's [`Transform`] with the keyboard and mouse.
+///
+/// W, A, S and D move forward, left, back and right, E and Q up and down, and holding shift
+/// moves faster. Dragging with the right mouse button held looks around.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FlyCamera {
+    /// Whether the entity reacts to the input.
+    pub enabled: bool,
+    /// The movement speed, in world units per second.
+    pub speed: f32,
+    /// The rotation per pixel of mouse motion, in radians.
+    pub sensitivity: f32,
+}
+
+impl Default for FlyCamera {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            speed: 5.0,
+            sensitivity: 0.003,
+        }
+    }
+}
+
+/// Moves and turns each entity with an enabled [`FlyCamera`] from this frame's input.
+pub fn fly_camera(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut query: Query<(&FlyCamera, &mut Transform)>,
+) {
+    let motion = mouse_motion
+        .iter()
+        .fold(Vec2::ZERO, |sum, motion| sum + motion.delta);
+    let looking = mouse_input.pressed(MouseButton::Right);
+
+    let mut direction = Vec3::ZERO;
+    for (key, axis) in [
+        (KeyCode::W, Vec3::NEG_Z),
+        (KeyCode::S, Vec3::Z),
+        (KeyCode::A, Vec3::NEG_X),
+        (KeyCode::D, Vec3::X),
+        (KeyCode::E, Vec3::Y),
+        (KeyCode::Q, Vec3::NEG_Y),
+    ] {
+        if keyboard_input.pressed(key) {
+            direction += axis;
+        }
+    }
+    let boost = if keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]) {
+        4.0
+    } else {
+        1.0
+    };
+
+    for (fly_camera, mut transform) in &mut query {
+        if !fly_camera.enabled {
+            continue;
+        }
+        if looking && motion != Vec2::ZERO {
+            let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            let yaw = yaw - motion.x * fly_camera.sensitivity;
+            // Stop just short of looking straight up or down, where the yaw flips
+            let pitch = (pitch - motion.y * fly_camera.sensitivity)
+                .clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+            transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0);
+        }
+        if direction != Vec3::ZERO {
+            // Movement is relative to where the camera looks, except up and down
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            let movement = Quat::from_rotation_y(yaw) * direction.normalize();
+            transform.translation += movement * fly_camera.speed * boost * time.delta_seconds();
+        }
+    }
+}
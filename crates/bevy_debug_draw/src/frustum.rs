@@ -0,0 +1,73 @@
+//! Visualization of the frusta of cameras and lights, for debugging culling and shadows.
+
+use bevy_ecs::prelude::*;
+use bevy_render::{color::Color, primitives::Frustum};
+
+use crate::draw::DebugDraw;
+
+/// Draws the edges of the entity's [`Frustum`], using the
+/// [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin).
+///
+/// Works on cameras, as well as on directional and spot lights whose frustum bounds the
+/// entities casting shadows. The frustum of the camera rendering the view can't be seen
+/// from inside, so this is meant for other cameras, or a camera watched from a second one.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShowFrustum {
+    /// Color of the edges.
+    pub color: Color,
+}
+
+impl Default for ShowFrustum {
+    fn default() -> Self {
+        ShowFrustum {
+            color: Color::YELLOW,
+        }
+    }
+}
+
+pub(crate) fn draw_frusta(mut draw: ResMut<DebugDraw>, query: Query<(&ShowFrustum, &Frustum)>) {
+    for (show_frustum, frustum) in &query {
+        draw.frustum(frustum, show_frustum.color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_math::{Mat4, Vec3};
+
+    use super::*;
+
+    #[test]
+    fn frustum_edges_join_its_corners() {
+        let mut app = App::new();
+        app.init_resource::<DebugDraw>().add_system(draw_frusta);
+
+        // An orthographic view from (0, 0, 5) down -Z, seeing a 2x4x10 box, with reversed
+        // depth like the cameras
+        let view = Mat4::from_translation(Vec3::Z * 5.0);
+        let projection = Mat4::orthographic_rh(-1.0, 1.0, -2.0, 2.0, 10.0, 0.0);
+        let frustum = Frustum::from_view_projection(
+            &(projection * view.inverse()),
+            &Vec3::new(0.0, 0.0, 5.0),
+            &Vec3::Z,
+            10.0,
+        );
+        app.world.spawn((frustum, ShowFrustum::default()));
+        // Not drawn without a computed frustum
+        app.world.spawn(ShowFrustum::default());
+        app.update();
+
+        let lines = app.world.resource::<DebugDraw>().lines();
+        assert_eq!(lines.len(), 12);
+        for line in lines {
+            assert_eq!(line.color, Color::YELLOW);
+            for point in [line.start, line.end] {
+                let corner = Vec3::new(1.0, 2.0, 0.0) * point.signum();
+                assert!(point.truncate().abs_diff_eq(corner.truncate(), 1e-4));
+                assert!((point.z - 5.0).abs() < 1e-4 || (point.z + 5.0).abs() < 1e-4);
+            }
+            assert!((line.end - line.start).length() > 1.9);
+        }
+    }
+}
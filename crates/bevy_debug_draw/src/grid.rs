@@ -0,0 +1,66 @@
+//! A grid on the ground plane, to judge distances and sizes in the scene.
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_render::color::Color;
+
+use crate::draw::DebugDraw;
+
+/// Adds the [`DebugGrid`] resource, drawing a square grid on the XZ plane centered on the
+/// world origin.
+///
+/// Requires the [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin).
+#[derive(Default)]
+pub struct DebugGridPlugin;
+
+impl Plugin for DebugGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugGrid>().add_system(draw_grid);
+    }
+}
+
+/// Configuration of the [`DebugGridPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct DebugGrid {
+    /// Whether the grid is drawn.
+    pub enabled: bool,
+    /// The distance between two lines, in world units.
+    pub spacing: f32,
+    /// The number of cells from the origin to each side of the grid.
+    pub half_cells: u32,
+    /// The color of the lines.
+    pub color: Color,
+}
+
+impl Default for DebugGrid {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spacing: 1.0,
+            half_cells: 10,
+            color: Color::rgb(0.3, 0.3, 0.3),
+        }
+    }
+}
+
+fn draw_grid(config: Res<DebugGrid>, mut draw: ResMut<DebugDraw>) {
+    if !config.enabled {
+        return;
+    }
+    let extent = config.half_cells as f32 * config.spacing;
+    let half_cells = config.half_cells as i32;
+    for i in -half_cells..=half_cells {
+        let offset = i as f32 * config.spacing;
+        draw.line(
+            Vec3::new(offset, 0.0, -extent),
+            Vec3::new(offset, 0.0, extent),
+            config.color,
+        );
+        draw.line(
+            Vec3::new(-extent, 0.0, offset),
+            Vec3::new(extent, 0.0, offset),
+            config.color,
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! The depth of entities in the transform hierarchy, to color debug visuals by nesting level.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{Children, Parent};
+use bevy_utils::{tracing::warn, HashSet};
+
+/// Adds the [`update_hierarchy_depth`] system, keeping a [`HierarchyDepth`] on every entity
+/// with a [`Parent`] or [`Children`].
+#[derive(Default)]
+pub struct HierarchyDepthPlugin;
+
+impl Plugin for HierarchyDepthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(CoreStage::PostUpdate, update_hierarchy_depth);
+    }
+}
+
+/// The number of ancestors of an entity: 0 for roots, 1 for their children, and so on.
+///
+/// Entities leaving the hierarchy keep the component, with a depth of 0.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HierarchyDepth(pub u32);
+
+/// Updates the [`HierarchyDepth`] of all entities whenever an entity is reparented.
+///
+/// Parenting is done with commands, so entities are reparented in
+/// [`CoreStage::PostUpdate`] at the latest, before this system runs.
+///
+/// Hierarchies shouldn't contain cycles, but if they do, the depth of the entities in the cycle
+/// stops counting where the cycle closes, and a warning is logged.
+pub fn update_hierarchy_depth(
+    mut commands: Commands,
+    changed: Query<(), Or<(Changed<Parent>, Changed<Children>)>>,
+    removed: RemovedComponents<Parent>,
+    mut entities: Query<
+        (Entity, Option<&mut HierarchyDepth>),
+        Or<(With<Parent>, With<Children>, With<HierarchyDepth>)>,
+    >,
+    parents: Query<&Parent>,
+) {
+    if changed.is_empty() && removed.iter().next().is_none() {
+        return;
+    }
+
+    let mut ancestors = HashSet::default();
+    for (entity, depth) in &mut entities {
+        ancestors.clear();
+        ancestors.insert(entity);
+        let mut ancestor = entity;
+        while let Ok(parent) = parents.get(ancestor) {
+            ancestor = parent.get();
+            if !ancestors.insert(ancestor) {
+                warn!("{entity:?} is its own ancestor, its hierarchy depth is wrong");
+                break;
+            }
+        }
+        let new_depth = HierarchyDepth(ancestors.len() as u32 - 1);
+        match depth {
+            // Avoid triggering change detection on unchanged depths
+            Some(mut depth) => {
+                if *depth != new_depth {
+                    *depth = new_depth;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(new_depth);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_hierarchy::BuildWorldChildren;
+
+    use super::*;
+
+    #[test]
+    fn depths_follow_reparenting() {
+        let mut app = App::new();
+        app.add_plugin(HierarchyDepthPlugin);
+
+        let root = app.world.spawn_empty().id();
+        let child = app.world.spawn_empty().id();
+        let grandchild = app.world.spawn_empty().id();
+        let unrelated = app.world.spawn_empty().id();
+        app.world.entity_mut(root).push_children(&[child]);
+        app.world.entity_mut(child).push_children(&[grandchild]);
+        app.update();
+
+        let depth = |app: &App, entity| app.world.get::<HierarchyDepth>(entity).copied();
+        assert_eq!(depth(&app, root), Some(HierarchyDepth(0)));
+        assert_eq!(depth(&app, child), Some(HierarchyDepth(1)));
+        assert_eq!(depth(&app, grandchild), Some(HierarchyDepth(2)));
+        assert_eq!(depth(&app, unrelated), None);
+
+        // Moving the middle entity updates its descendants too
+        app.world.entity_mut(root).remove_children(&[child]);
+        app.update();
+        assert_eq!(depth(&app, root), Some(HierarchyDepth(0)));
+        assert_eq!(depth(&app, child), Some(HierarchyDepth(0)));
+        assert_eq!(depth(&app, grandchild), Some(HierarchyDepth(1)));
+
+        app.world.entity_mut(grandchild).push_children(&[root]);
+        app.update();
+        assert_eq!(depth(&app, child), Some(HierarchyDepth(0)));
+        assert_eq!(depth(&app, grandchild), Some(HierarchyDepth(1)));
+        assert_eq!(depth(&app, root), Some(HierarchyDepth(2)));
+
+        // Reparenting doesn't check for cycles
+        app.world.entity_mut(root).push_children(&[child]);
+        app.update();
+        assert!(depth(&app, child).unwrap().0 <= 2);
+    }
+}
@@ -0,0 +1,395 @@
+//! Text labels anchored at world-space positions, batched into a single draw.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{Mat4, Rect, Vec2, Vec3};
+use bevy_render::{
+    camera::{Camera, CameraUpdateSystem, RenderTarget},
+    color::Color,
+    texture::Image,
+    Extract, RenderApp, RenderStage,
+};
+use bevy_sprite::TextureAtlas;
+use bevy_text::{
+    Font, FontAtlasSet, FontFallbacks, TextAlignment, TextError, TextLayoutInfo, TextPipeline,
+    TextSection, TextSettings, TextStyle, YAxisOrientation,
+};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_ui::{ExtractedUiNode, ExtractedUiNodes, RenderUiSystem};
+use bevy_utils::{tracing::warn, HashMap};
+use bevy_window::{ModifiesWindows, WindowId, Windows};
+
+/// Adds a [`DebugLabels`] resource whose labels are displayed every frame, over the view of the
+/// camera rendering on top of the primary window.
+///
+/// Requires the [`TextPlugin`](bevy_text::TextPlugin) and [`UiPlugin`](bevy_ui::UiPlugin):
+/// the glyphs of all labels are laid out in the shared font atlas of
+/// [`DebugLabelsConfig::font`] and drawn by the UI pass, in a single draw as long as they fit
+/// in one atlas texture.
+#[derive(Default)]
+pub struct DebugLabelsPlugin;
+
+impl Plugin for DebugLabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugLabels>()
+            .init_resource::<DebugLabelsConfig>()
+            .init_resource::<DebugLabelGlyphs>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                layout_debug_labels
+                    .label(DebugLabelsSystem)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(CameraUpdateSystem)
+                    .after(ModifiesWindows),
+            );
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_system_to_stage(
+                RenderStage::Extract,
+                extract_debug_labels.after(RenderUiSystem::ExtractNode),
+            );
+        }
+    }
+}
+
+/// Label for the system that lays out the glyphs of [`DebugLabels`] on screen.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub struct DebugLabelsSystem;
+
+/// A text label drawn by [`DebugLabels`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugLabel {
+    /// World-space position the label is centered on.
+    pub position: Vec3,
+    /// The text of the label.
+    pub text: String,
+    /// Color of the text.
+    pub color: Color,
+}
+
+/// Text labels to draw this frame, such as vertex indices or entity names.
+///
+/// Every frame, the labels added up to [`DebugLabelsSystem`] (which runs in
+/// [`CoreStage::PostUpdate`]) are displayed and then cleared, so systems have to re-add
+/// their labels each frame they want them visible.
+///
+/// Labels are drawn flat on the screen, over the 3D scene and under the UI, and aren't hidden
+/// by the geometry in front of them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_debug_draw::labels::DebugLabels;
+/// # use bevy_core::Name;
+/// # use bevy_render::color::Color;
+/// # use bevy_transform::prelude::*;
+/// fn label_names(mut labels: ResMut<DebugLabels>, query: Query<(&Name, &GlobalTransform)>) {
+///     for (name, transform) in &query {
+///         labels.label(transform.translation(), name.as_str(), Color::WHITE);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(label_names);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct DebugLabels {
+    labels: Vec<DebugLabel>,
+}
+
+impl DebugLabels {
+    /// Draws `text` centered on `position`.
+    pub fn label(&mut self, position: Vec3, text: impl Into<String>, color: Color) {
+        self.labels.push(DebugLabel {
+            position,
+            text: text.into(),
+            color,
+        });
+    }
+
+    /// The labels that will be displayed this frame.
+    pub fn labels(&self) -> &[DebugLabel] {
+        &self.labels
+    }
+
+    /// Removes all labels.
+    pub fn clear(&mut self) {
+        self.labels.clear();
+    }
+}
+
+/// Appearance of the [`DebugLabels`].
+#[derive(Resource, Clone, Debug)]
+pub struct DebugLabelsConfig {
+    /// The font used for the labels.
+    ///
+    /// Bevy doesn't ship a default font, so this has to be set for the labels to be visible.
+    pub font: Handle<Font>,
+    /// The size of the font.
+    pub font_size: f32,
+}
+
+impl Default for DebugLabelsConfig {
+    fn default() -> Self {
+        Self {
+            font: Default::default(),
+            font_size: 12.0,
+        }
+    }
+}
+
+/// A glyph of a [`DebugLabel`], placed on the screen.
+#[derive(Debug, Clone)]
+pub struct DebugLabelGlyph {
+    /// Transform of the glyph in the logical UI space of the primary window, scaled down from
+    /// the physical size of `rect`.
+    pub transform: Mat4,
+    /// Area of the glyph in its font atlas texture.
+    pub rect: Rect,
+    /// The font atlas texture containing the glyph.
+    pub texture: Handle<Image>,
+    /// Size of the font atlas texture.
+    pub atlas_size: Vec2,
+    /// Color of the glyph.
+    pub color: Color,
+}
+
+/// The glyphs of this frame's [`DebugLabels`], laid out by [`DebugLabelsSystem`].
+#[derive(Resource, Debug, Default)]
+pub struct DebugLabelGlyphs {
+    glyphs: Vec<DebugLabelGlyph>,
+}
+
+impl DebugLabelGlyphs {
+    /// The glyphs that will be rendered this frame.
+    pub fn glyphs(&self) -> &[DebugLabelGlyph] {
+        &self.glyphs
+    }
+
+    /// The number of draws the glyphs are rendered with: one per run of glyphs sharing a font
+    /// atlas texture.
+    pub fn batch_count(&self) -> usize {
+        let mut batches = 0;
+        let mut texture = None;
+        for glyph in &self.glyphs {
+            if texture != Some(&glyph.texture) {
+                batches += 1;
+                texture = Some(&glyph.texture);
+            }
+        }
+        batches
+    }
+}
+
+/// Layouts of the label texts of the previous frame, as labels are usually redrawn with
+/// the same texts.
+#[derive(Default)]
+struct LabelLayoutCache {
+    layouts: HashMap<String, TextLayoutInfo>,
+    scale_factor: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn layout_debug_labels(
+    mut labels: ResMut<DebugLabels>,
+    mut label_glyphs: ResMut<DebugLabelGlyphs>,
+    mut cache: Local<LabelLayoutCache>,
+    config: Res<DebugLabelsConfig>,
+    windows: Res<Windows>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    fonts: Res<Assets<Font>>,
+    mut text_pipeline: ResMut<TextPipeline>,
+    mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut textures: ResMut<Assets<Image>>,
+    text_settings: Res<TextSettings>,
+    font_fallbacks: Res<FontFallbacks>,
+) {
+    label_glyphs.glyphs.clear();
+    let labels = std::mem::take(&mut labels.labels);
+
+    let scale_factor = windows.scale_factor(WindowId::primary());
+    #[allow(clippy::float_cmp)]
+    if config.is_changed() || font_fallbacks.is_changed() || cache.scale_factor != scale_factor {
+        cache.layouts.clear();
+        cache.scale_factor = scale_factor;
+    }
+
+    let camera = cameras
+        .iter()
+        .filter(|(camera, _)| {
+            camera.is_active && camera.target == RenderTarget::Window(WindowId::primary())
+        })
+        .max_by_key(|(camera, _)| camera.priority);
+    let (camera, camera_transform, viewport_size) = match camera
+        .and_then(|(camera, transform)| Some((camera, transform, camera.logical_viewport_size()?)))
+    {
+        Some(camera) => camera,
+        None => return,
+    };
+
+    let mut layouts = HashMap::default();
+    for label in labels {
+        let anchor = match camera.world_to_viewport(camera_transform, label.position) {
+            Some(anchor) => anchor,
+            None => continue,
+        };
+        if anchor.cmplt(Vec2::ZERO).any() || anchor.cmpgt(viewport_size).any() {
+            continue;
+        }
+        // Viewport coordinates go up from the bottom, UI ones down from the top
+        let anchor = Vec2::new(anchor.x, viewport_size.y - anchor.y);
+
+        if !layouts.contains_key(&label.text) {
+            let layout = match cache.layouts.remove(&label.text) {
+                Some(layout) => layout,
+                None => match text_pipeline.queue_text(
+                    &fonts,
+                    &[TextSection::new(
+                        label.text.clone(),
+                        TextStyle {
+                            font: config.font.clone(),
+                            font_size: config.font_size,
+                            color: label.color,
+                            ..Default::default()
+                        },
+                    )],
+                    scale_factor,
+                    TextAlignment::CENTER,
+                    Vec2::splat(f32::MAX),
+                    &mut font_atlas_set_storage,
+                    &mut texture_atlases,
+                    &mut textures,
+                    &text_settings,
+                    &font_fallbacks,
+                    YAxisOrientation::TopToBottom,
+                ) {
+                    Ok(layout) => layout,
+                    // The font isn't loaded yet
+                    Err(TextError::NoSuchFont) => return,
+                    Err(error) => {
+                        warn!("Failed to lay out debug label {:?}: {}", label.text, error);
+                        continue;
+                    }
+                },
+            };
+            layouts.insert(label.text.clone(), layout);
+        }
+        let layout = &layouts[&label.text];
+
+        let transform = Mat4::from_translation(anchor.extend(0.0))
+            * Mat4::from_scale(Vec3::splat(scale_factor.recip() as f32));
+        for glyph in &layout.glyphs {
+            let atlas = match texture_atlases.get(&glyph.atlas_info.texture_atlas) {
+                Some(atlas) => atlas,
+                None => continue,
+            };
+            label_glyphs.glyphs.push(DebugLabelGlyph {
+                transform: transform
+                    * Mat4::from_translation((glyph.position - layout.size / 2.0).extend(0.0)),
+                rect: atlas.textures[glyph.atlas_info.glyph_index],
+                texture: atlas.texture.clone_weak(),
+                atlas_size: atlas.size,
+                color: label.color,
+            });
+        }
+    }
+    cache.layouts = layouts;
+}
+
+fn extract_debug_labels(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    label_glyphs: Extract<Res<DebugLabelGlyphs>>,
+) {
+    // All glyphs are at z = 0, below the UI nodes, so that they stay contiguous when the UI
+    // nodes are sorted by z and are batched together.
+    for glyph in &label_glyphs.glyphs {
+        extracted_uinodes.uinodes.push(ExtractedUiNode {
+            transform: glyph.transform,
+            background_color: glyph.color.as_rgba_linear(),
+            rect: glyph.rect,
+            image: glyph.texture.clone_weak(),
+            atlas_size: Some(glyph.atlas_size),
+            clip: None,
+            shape: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_render::camera::{camera_system, PerspectiveProjection};
+    use bevy_transform::components::Transform;
+    use bevy_window::{Window, WindowCreated, WindowDescriptor, WindowResized};
+
+    use super::*;
+
+    #[test]
+    fn labels_share_a_single_batch() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Image>()
+            .add_asset::<Font>()
+            .add_asset::<FontAtlasSet>()
+            .add_asset::<TextureAtlas>()
+            .init_resource::<TextPipeline>()
+            .init_resource::<TextSettings>()
+            .init_resource::<FontFallbacks>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .init_resource::<Windows>()
+            .add_system(camera_system::<PerspectiveProjection>)
+            .add_plugin(DebugLabelsPlugin);
+
+        app.world.resource_mut::<Windows>().add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+            None,
+        ));
+        let font = Font::try_from_bytes(
+            include_bytes!("../../../assets/fonts/FiraMono-Medium.ttf").to_vec(),
+        )
+        .unwrap();
+        let font = app.world.resource_mut::<Assets<Font>>().add(font);
+        app.world.resource_mut::<DebugLabelsConfig>().font = font;
+        app.world.spawn((
+            Camera::default(),
+            PerspectiveProjection::default(),
+            GlobalTransform::from(Transform::from_xyz(0.0, 0.0, 10.0)),
+        ));
+
+        // A label behind the camera isn't displayed
+        app.world.resource_mut::<DebugLabels>().label(
+            Vec3::new(0.0, 0.0, 20.0),
+            "hidden",
+            Color::RED,
+        );
+        let mut expected_glyphs = 0;
+        for i in 0..1000 {
+            let text = format!("v{i}");
+            expected_glyphs += text.len();
+            let position = Vec3::new(
+                (i % 40) as f32 * 0.2 - 4.0,
+                (i / 40) as f32 * 0.2 - 2.5,
+                0.0,
+            );
+            app.world
+                .resource_mut::<DebugLabels>()
+                .label(position, text, Color::WHITE);
+        }
+        app.update();
+
+        let glyphs = app.world.resource::<DebugLabelGlyphs>();
+        assert_eq!(glyphs.glyphs().len(), expected_glyphs);
+        assert_eq!(glyphs.batch_count(), 1);
+        assert!(app.world.resource::<DebugLabels>().labels().is_empty());
+
+        app.update();
+        assert_eq!(app.world.resource::<DebugLabelGlyphs>().batch_count(), 0);
+    }
+}
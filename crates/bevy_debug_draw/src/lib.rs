@@ -0,0 +1,52 @@
+#![warn(missing_docs)]
+//! Debug drawing and visualization tools for Bevy apps.
+//!
+//! None of the plugins in this crate are part of `DefaultPlugins`: add the ones you need
+//! while debugging.
+
+pub mod aabb;
+pub mod antialiasing;
+pub mod axes;
+pub mod culling;
+pub mod debug_aabb;
+pub mod debug_text;
+pub mod draw;
+pub mod fly_camera;
+pub mod frustum;
+pub mod grid;
+pub mod hierarchy;
+pub mod labels;
+pub mod palette;
+pub mod rotator;
+pub mod skeleton;
+pub mod solo;
+pub mod tbn;
+pub mod toolkit;
+pub mod viewport;
+
+/// Most commonly used re-exported types.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        aabb::{AabbSource, DrawAabbs, DrawAabbsPlugin, DumpAabbs, DumpAabbsPlugin},
+        antialiasing::{AntiAliasing, AntiAliasingPlugin},
+        axes::{DebugAxes, DebugAxesPlugin},
+        culling::CullingDiagnosticsPlugin,
+        debug_aabb::{DebugAabbConfig, DebugAabbPlugin, DebugAabbRange, ShowAabb},
+        debug_text,
+        debug_text::{DebugText, DebugTextConfig, DebugTextPlugin},
+        draw::{DebugDraw, DebugDrawConfig, DebugDrawPlugin},
+        fly_camera::{FlyCamera, FlyCameraPlugin},
+        frustum::ShowFrustum,
+        grid::{DebugGrid, DebugGridPlugin},
+        hierarchy::{HierarchyDepth, HierarchyDepthPlugin},
+        labels::{DebugLabels, DebugLabelsConfig, DebugLabelsPlugin},
+        palette::DebugPalette,
+        rotator::{Rotator, RotatorPlugin},
+        skeleton::{BoneStyle, DebugSkeletonConfig, DebugSkeletonPlugin},
+        solo::{SoloPlugin, SoloTarget},
+        tbn::ShowTbn,
+        toolkit::{DebugToolkitConfig, DebugToolkitPlugin},
+        viewport::Viewports,
+    };
+}
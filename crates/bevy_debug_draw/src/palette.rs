@@ -0,0 +1,108 @@
+//! Color schemes for debug visuals, including ones for people with color vision deficiencies.
+
+use bevy_render::color::Color;
+
+/// A set of colors used to tell debug visuals apart.
+///
+/// The default palette spreads hues evenly, which puts reds and greens next to each other.
+/// The other palettes keep their colors distinguishable for a type of color vision deficiency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugPalette {
+    /// Saturated colors with evenly spaced hues.
+    #[default]
+    Default,
+    /// Colors for people with reduced sensitivity to green.
+    Deuteranopia,
+    /// Colors for people with reduced sensitivity to red.
+    Protanopia,
+    /// Colors for people with reduced sensitivity to blue.
+    Tritanopia,
+}
+
+/// Colors avoiding the blue-green and yellow-violet confusions of tritanopia.
+const TRITANOPIA: [(u8, u8, u8); 7] = [
+    (230, 159, 0),   // orange
+    (0, 114, 178),   // blue
+    (204, 51, 17),   // red
+    (86, 180, 233),  // sky blue
+    (240, 228, 66),  // yellow
+    (187, 187, 187), // grey
+    (136, 34, 85),   // wine
+];
+
+impl DebugPalette {
+    /// Returns `n` colors from this palette.
+    ///
+    /// The [`Default`](DebugPalette::Default) palette always returns `n` different colors,
+    /// while the others have 7 colors that repeat if `n` is larger.
+    pub fn colors(self, n: usize) -> Vec<Color> {
+        match self {
+            DebugPalette::Default => (0..n)
+                .map(|i| Color::hsl(360.0 * i as f32 / n as f32, 0.8, 0.5))
+                .collect(),
+            DebugPalette::Deuteranopia | DebugPalette::Protanopia => Color::distinct_palette_cvd(n),
+            DebugPalette::Tritanopia => TRITANOPIA
+                .iter()
+                .cycle()
+                .take(n)
+                .map(|&(r, g, b)| Color::rgb_u8(r, g, b))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Mat3, Vec3};
+
+    use super::*;
+
+    /// Linear RGB transforms simulating how colors are perceived with each deficiency.
+    fn simulation(palette: DebugPalette) -> Mat3 {
+        match palette {
+            DebugPalette::Default => Mat3::IDENTITY,
+            DebugPalette::Deuteranopia => Mat3::from_cols_array(&[
+                0.29275, 0.29275, -0.02234, 0.70725, 0.70725, 0.02234, 0.0, 0.0, 1.0,
+            ]),
+            DebugPalette::Protanopia => Mat3::from_cols_array(&[
+                0.11238, 0.11238, 0.00401, 0.88762, 0.88762, -0.00401, 0.0, 0.0, 1.0,
+            ]),
+            DebugPalette::Tritanopia => Mat3::from_cols_array(&[
+                1.0, 0.0, 0.0, 0.1273, 0.8739, 0.8739, -0.1273, 0.1261, 0.1261,
+            ]),
+        }
+    }
+
+    #[test]
+    fn palettes_stay_distinct_for_their_deficiency() {
+        for palette in [
+            DebugPalette::Default,
+            DebugPalette::Deuteranopia,
+            DebugPalette::Protanopia,
+            DebugPalette::Tritanopia,
+        ] {
+            let colors = palette.colors(7);
+            assert_eq!(colors.len(), 7);
+            let perceived: Vec<Vec3> = colors
+                .iter()
+                .map(|color| {
+                    let [r, g, b, _] = color.as_linear_rgba_f32();
+                    simulation(palette) * Vec3::new(r, g, b)
+                })
+                .collect();
+            for (i, a) in perceived.iter().enumerate() {
+                for b in &perceived[i + 1..] {
+                    assert!(a.distance(*b) > 0.08, "{palette:?} has similar colors");
+                }
+            }
+        }
+
+        // Larger palettes repeat
+        let colors = DebugPalette::Tritanopia.colors(8);
+        assert_eq!(colors[7], colors[0]);
+        assert_eq!(
+            Color::distinct_palette_cvd(9)[8],
+            Color::distinct_palette_cvd(2)[1]
+        );
+    }
+}
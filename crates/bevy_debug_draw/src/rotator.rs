@@ -0,0 +1,121 @@
+//! A component spinning entities at a constant rate, to look at objects from every side.
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec3};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+/// Adds the [`rotate`] system, turning every entity with a [`Rotator`].
+#[derive(Default)]
+pub struct RotatorPlugin;
+
+impl Plugin for RotatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(rotate);
+    }
+}
+
+/// Rotates the entity's [`Transform`] around `axis` at a constant speed.
+///
+/// The axis is in world space and goes through the entity's translation.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Rotator {
+    /// The axis to rotate around. It doesn't need to be normalized, and the entity isn't
+    /// rotated while it is zero.
+    pub axis: Vec3,
+    /// The rotation speed, counterclockwise when looking down `axis`.
+    pub radians_per_second: f32,
+}
+
+impl Default for Rotator {
+    fn default() -> Self {
+        Rotator {
+            axis: Vec3::Y,
+            radians_per_second: 0.5,
+        }
+    }
+}
+
+/// Turns each entity with a [`Rotator`] by the rotation accumulated over the last
+/// [`Time::delta_seconds`].
+pub fn rotate(mut query: Query<(&Rotator, &mut Transform)>, time: Res<Time>) {
+    for (rotator, mut transform) in &mut query {
+        let axis = match rotator.axis.try_normalize() {
+            Some(axis) => axis,
+            None => continue,
+        };
+        let angle = rotator.radians_per_second * time.delta_seconds();
+        transform.rotate(Quat::from_axis_angle(axis, angle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use bevy_app::App;
+
+    use super::*;
+
+    #[test]
+    fn rotation_accumulates_over_time() {
+        let mut app = App::new();
+        app.add_plugin(RotatorPlugin).init_resource::<Time>();
+
+        let around_y = app
+            .world
+            .spawn((Rotator::default(), Transform::default()))
+            .id();
+        let around_diagonal = app
+            .world
+            .spawn((
+                Rotator {
+                    axis: Vec3::new(1.0, 1.0, 0.0),
+                    radians_per_second: -2.0,
+                },
+                Transform::from_xyz(1.0, 2.0, 3.0),
+            ))
+            .id();
+        let around_zero = app
+            .world
+            .spawn((
+                Rotator {
+                    axis: Vec3::ZERO,
+                    ..Default::default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        let start = Instant::now();
+        for frame in 0..=30 {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_millis(frame * 50));
+            app.update();
+        }
+
+        // 1.5 seconds elapsed
+        let expected = [
+            (around_y, Quat::from_axis_angle(Vec3::Y, 0.5 * 1.5)),
+            (
+                around_diagonal,
+                Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0).normalize(), -2.0 * 1.5),
+            ),
+            // A zero axis doesn't rotate, instead of making the rotation NaN
+            (around_zero, Quat::IDENTITY),
+        ];
+        for (entity, rotation) in expected {
+            let transform = app.world.get::<Transform>(entity).unwrap();
+            assert!(transform.rotation.abs_diff_eq(rotation, 1e-4));
+        }
+        assert_eq!(
+            app.world
+                .get::<Transform>(around_diagonal)
+                .unwrap()
+                .translation,
+            Vec3::new(1.0, 2.0, 3.0)
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Visualization of the joints of skinned meshes, for debugging skeletons and their animation.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_math::Vec3;
+use bevy_render::{color::Color, mesh::skinning::SkinnedMesh};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_utils::HashSet;
+
+use crate::{
+    draw::{DebugDraw, DebugDrawSystem},
+    labels::{DebugLabels, DebugLabelsSystem},
+};
+
+/// Draws the bones of every [`SkinnedMesh`], between each joint and its parent joint.
+///
+/// Requires the [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin). The names of the joints
+/// are shown with [`DebugSkeletonConfig::joint_names`] if the
+/// [`DebugLabelsPlugin`](crate::labels::DebugLabelsPlugin) is added as well.
+#[derive(Default)]
+pub struct DebugSkeletonPlugin;
+
+impl Plugin for DebugSkeletonPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugSkeletonConfig>()
+            .add_system(toggle_debug_skeletons)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                draw_skeletons
+                    .after(TransformSystem::TransformPropagate)
+                    .before(DebugDrawSystem::UpdateMesh)
+                    .before(DebugLabelsSystem),
+            );
+    }
+}
+
+/// Configuration of the [`DebugSkeletonPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct DebugSkeletonConfig {
+    /// Whether the skeletons are drawn.
+    pub visible: bool,
+    /// The key toggling [`DebugSkeletonConfig::visible`], if any.
+    pub toggle_key: Option<KeyCode>,
+    /// How the bones are drawn.
+    pub bone_style: BoneStyle,
+    /// Color of the bones.
+    pub color: Color,
+    /// Label each joint with its [`Name`], if it has one.
+    pub joint_names: bool,
+}
+
+impl Default for DebugSkeletonConfig {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            toggle_key: Some(KeyCode::F8),
+            bone_style: BoneStyle::Octahedron,
+            color: Color::ORANGE,
+            joint_names: false,
+        }
+    }
+}
+
+/// The lines drawn for each bone by the [`DebugSkeletonPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoneStyle {
+    /// A single line from the parent joint to the child joint.
+    Line,
+    /// The edges of an octahedron, widest near the parent joint, showing which way the bone
+    /// points.
+    #[default]
+    Octahedron,
+}
+
+fn toggle_debug_skeletons(
+    input: Option<Res<Input<KeyCode>>>,
+    mut config: ResMut<DebugSkeletonConfig>,
+) {
+    if let (Some(input), Some(key)) = (input, config.toggle_key) {
+        if input.just_pressed(key) {
+            config.visible = !config.visible;
+        }
+    }
+}
+
+fn draw_skeletons(
+    config: Res<DebugSkeletonConfig>,
+    mut draw: ResMut<DebugDraw>,
+    labels: Option<ResMut<DebugLabels>>,
+    skinned_meshes: Query<&SkinnedMesh>,
+    joints: Query<(&GlobalTransform, Option<&Parent>, Option<&Name>)>,
+) {
+    if !config.visible {
+        return;
+    }
+
+    // The meshes of a glTF node with several primitives share the same joints
+    let skeleton_joints: HashSet<Entity> = skinned_meshes
+        .iter()
+        .flat_map(|skinned_mesh| skinned_mesh.joints.iter().copied())
+        .collect();
+
+    for &joint in &skeleton_joints {
+        let (transform, parent, _) = match joints.get(joint) {
+            Ok(joint) => joint,
+            Err(_) => continue,
+        };
+        let parent = match parent {
+            Some(parent) if skeleton_joints.contains(&parent.get()) => parent.get(),
+            _ => continue,
+        };
+        if let Ok((parent_transform, _, _)) = joints.get(parent) {
+            draw_bone(
+                &mut draw,
+                parent_transform.translation(),
+                transform.translation(),
+                config.bone_style,
+                config.color,
+            );
+        }
+    }
+
+    if let (true, Some(mut labels)) = (config.joint_names, labels) {
+        for &joint in &skeleton_joints {
+            if let Ok((transform, _, Some(name))) = joints.get(joint) {
+                labels.label(transform.translation(), name.as_str(), config.color);
+            }
+        }
+    }
+}
+
+fn draw_bone(draw: &mut DebugDraw, head: Vec3, tail: Vec3, style: BoneStyle, color: Color) {
+    match style {
+        BoneStyle::Line => draw.line(head, tail, color),
+        BoneStyle::Octahedron => {
+            let length = head.distance(tail);
+            if length == 0.0 {
+                return;
+            }
+            let direction = (tail - head) / length;
+            let (u, v) = direction.any_orthonormal_pair();
+            let (u, v) = (u * 0.1 * length, v * 0.1 * length);
+            let middle = head + direction * 0.1 * length;
+            let ring = [middle + u, middle + v, middle - u, middle - v];
+            for (i, &point) in ring.iter().enumerate() {
+                draw.line(head, point, color);
+                draw.line(point, tail, color);
+                draw.line(point, ring[(i + 1) % ring.len()], color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_transform::components::Transform;
+
+    use super::*;
+
+    #[test]
+    fn bones_join_parent_joints() {
+        let mut app = App::new();
+        app.init_resource::<DebugDraw>()
+            .init_resource::<DebugLabels>()
+            .add_plugin(DebugSkeletonPlugin);
+
+        let joint = |app: &mut App, translation: Vec3, name: &str| {
+            app.world
+                .spawn((
+                    Transform::from_translation(translation),
+                    GlobalTransform::from_translation(translation),
+                    Name::new(name.to_string()),
+                ))
+                .id()
+        };
+        let root = joint(&mut app, Vec3::ZERO, "root");
+        let arm = joint(&mut app, Vec3::Y, "arm");
+        let hand = joint(&mut app, Vec3::new(1.0, 1.0, 0.0), "hand");
+        // A parent that isn't part of the skeleton doesn't get a bone
+        let armature = app.world.spawn(GlobalTransform::IDENTITY).id();
+        app.world.entity_mut(armature).push_children(&[root]);
+        app.world.entity_mut(root).push_children(&[arm]);
+        app.world.entity_mut(arm).push_children(&[hand]);
+        for joints in [vec![root, arm, hand], vec![root, arm]] {
+            app.world.spawn(SkinnedMesh {
+                inverse_bindposes: Default::default(),
+                joints,
+            });
+        }
+
+        app.world.resource_mut::<DebugSkeletonConfig>().bone_style = BoneStyle::Line;
+        app.update();
+        let mut lines: Vec<_> = app
+            .world
+            .resource::<DebugDraw>()
+            .lines()
+            .iter()
+            .map(|line| (line.start, line.end))
+            .collect();
+        lines.sort_by(|a, b| a.0.y.total_cmp(&b.0.y));
+        assert_eq!(
+            lines,
+            [(Vec3::ZERO, Vec3::Y), (Vec3::Y, Vec3::new(1.0, 1.0, 0.0))]
+        );
+        assert!(app.world.resource::<DebugLabels>().labels().is_empty());
+
+        app.world.resource_mut::<DebugDraw>().clear();
+        let mut config = app.world.resource_mut::<DebugSkeletonConfig>();
+        config.bone_style = BoneStyle::Octahedron;
+        config.joint_names = true;
+        app.update();
+        let lines = app.world.resource::<DebugDraw>().lines();
+        assert_eq!(lines.len(), 24);
+        // Each octahedron has 4 edges from its parent joint and 4 edges to its child joint
+        let at_joints =
+            |position: Vec3| [Vec3::ZERO, Vec3::Y, Vec3::new(1.0, 1.0, 0.0)].contains(&position);
+        assert_eq!(lines.iter().filter(|line| at_joints(line.start)).count(), 8);
+        assert_eq!(lines.iter().filter(|line| at_joints(line.end)).count(), 8);
+        let mut names: Vec<_> = app
+            .world
+            .resource::<DebugLabels>()
+            .labels()
+            .iter()
+            .map(|label| label.text.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, ["arm", "hand", "root"]);
+
+        app.world.resource_mut::<DebugDraw>().clear();
+        app.world.resource_mut::<DebugSkeletonConfig>().visible = false;
+        app.update();
+        assert!(app.world.resource::<DebugDraw>().lines().is_empty());
+    }
+}
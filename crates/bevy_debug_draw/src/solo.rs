@@ -0,0 +1,175 @@
+//! Isolating a single entity by hiding every other mesh in the scene.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::{entity::Entities, prelude::*};
+use bevy_hierarchy::{Children, Parent};
+use bevy_render::{
+    mesh::Mesh,
+    view::{Visibility, VisibilitySystems},
+};
+use bevy_utils::HashSet;
+
+use crate::draw::{DebugDrawFillMesh, DebugDrawMesh};
+
+/// Adds the [`SoloTarget`] resource and the [`update_solo`] system applying it.
+#[derive(Default)]
+pub struct SoloPlugin;
+
+impl Plugin for SoloPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoloTarget>().add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_solo.before(VisibilitySystems::VisibilityPropagate),
+        );
+    }
+}
+
+/// The entity to show on its own, hiding all the other meshes, or `None` to show them all.
+///
+/// The descendants of the target stay visible. Its ancestors do too, since hiding them would
+/// hide the target as well. The meshes of [`DebugDraw`](crate::draw::DebugDraw) are never
+/// hidden. The target is reset to `None` once it is despawned, showing all the meshes again.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SoloTarget(pub Option<Entity>);
+
+/// Marker for the entities hidden by [`SoloTarget`], made visible again when they stop being
+/// hidden.
+///
+/// Entities that were already hidden are left untouched, so they stay hidden afterwards.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct SoloHidden;
+
+/// Hides the meshes outside of the [`SoloTarget`], and shows the ones it hid before that
+/// shouldn't be hidden anymore.
+pub fn update_solo(
+    mut commands: Commands,
+    mut target: ResMut<SoloTarget>,
+    entities: &Entities,
+    mut meshes: Query<
+        (Entity, &mut Visibility, Option<&SoloHidden>),
+        (
+            With<Handle<Mesh>>,
+            Without<DebugDrawMesh>,
+            Without<DebugDrawFillMesh>,
+        ),
+    >,
+    hidden: Query<(), With<SoloHidden>>,
+    children: Query<&Children>,
+    parents: Query<&Parent>,
+) {
+    if matches!(target.0, Some(entity) if !entities.contains(entity)) {
+        target.0 = None;
+    }
+    if target.0.is_none() && hidden.is_empty() {
+        return;
+    }
+
+    let mut shown = HashSet::default();
+    if let Some(target) = target.0 {
+        let mut ancestor = target;
+        while let Ok(parent) = parents.get(ancestor) {
+            ancestor = parent.get();
+            if !shown.insert(ancestor) {
+                break;
+            }
+        }
+        let mut stack = vec![target];
+        while let Some(entity) = stack.pop() {
+            if shown.insert(entity) {
+                if let Ok(children) = children.get(entity) {
+                    stack.extend(children.iter());
+                }
+            }
+        }
+    }
+
+    for (entity, mut visibility, solo_hidden) in &mut meshes {
+        let hide = target.0.is_some() && !shown.contains(&entity);
+        match solo_hidden {
+            None if hide && visibility.is_visible => {
+                visibility.is_visible = false;
+                commands.entity(entity).insert(SoloHidden);
+            }
+            Some(_) if !hide => {
+                visibility.is_visible = true;
+                commands.entity(entity).remove::<SoloHidden>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_hierarchy::BuildWorldChildren;
+
+    use super::*;
+
+    #[test]
+    fn solo_hides_other_meshes_and_restores_them() {
+        let mut app = App::new();
+        app.add_plugin(SoloPlugin);
+
+        let mut spawn_mesh = |is_visible| {
+            app.world
+                .spawn((Handle::<Mesh>::default(), Visibility { is_visible }))
+                .id()
+        };
+        let parent = spawn_mesh(true);
+        let target = spawn_mesh(true);
+        let child = spawn_mesh(true);
+        let other = spawn_mesh(true);
+        let already_hidden = spawn_mesh(false);
+        app.world.entity_mut(parent).push_children(&[target]);
+        app.world.entity_mut(target).push_children(&[child]);
+
+        let visible = |app: &App| {
+            [parent, target, child, other, already_hidden]
+                .map(|entity| app.world.get::<Visibility>(entity).unwrap().is_visible)
+        };
+
+        app.world.resource_mut::<SoloTarget>().0 = Some(target);
+        app.update();
+        assert_eq!(visible(&app), [true, true, true, false, false]);
+
+        // Switching the target shows the meshes of the new one
+        app.world.resource_mut::<SoloTarget>().0 = Some(other);
+        app.update();
+        assert_eq!(visible(&app), [false, false, false, true, false]);
+
+        app.world.resource_mut::<SoloTarget>().0 = None;
+        app.update();
+        assert_eq!(visible(&app), [true, true, true, true, false]);
+        assert!(app
+            .world
+            .query_filtered::<(), With<SoloHidden>>()
+            .iter(&app.world)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn despawning_the_target_clears_solo() {
+        let mut app = App::new();
+        app.add_plugin(SoloPlugin);
+
+        let target = app
+            .world
+            .spawn((Handle::<Mesh>::default(), Visibility::VISIBLE))
+            .id();
+        let other = app
+            .world
+            .spawn((Handle::<Mesh>::default(), Visibility::VISIBLE))
+            .id();
+        app.world.resource_mut::<SoloTarget>().0 = Some(target);
+        app.update();
+        assert!(!app.world.get::<Visibility>(other).unwrap().is_visible);
+
+        app.world.despawn(target);
+        app.update();
+        assert_eq!(*app.world.resource::<SoloTarget>(), SoloTarget(None));
+        assert!(app.world.get::<Visibility>(other).unwrap().is_visible);
+        assert!(app.world.get::<SoloHidden>(other).is_none());
+    }
+}
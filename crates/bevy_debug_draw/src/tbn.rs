@@ -0,0 +1,162 @@
+//! Visualization of the tangent space of meshes, for debugging normal mapping.
+
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::prelude::*;
+use bevy_math::{Vec3, Vec3A, Vec4};
+use bevy_render::{
+    color::Color,
+    mesh::{Mesh, VertexAttributeValues},
+    view::ComputedVisibility,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+use crate::draw::DebugDraw;
+
+/// Draws the tangent (red), bitangent (green) and normal (blue) of every vertex of the
+/// entity's [`Mesh`], using the [`DebugDrawPlugin`](crate::draw::DebugDrawPlugin).
+///
+/// The bitangent is computed the same way as in the PBR shader, so it matches the frame
+/// normal maps are sampled in. If the mesh doesn't have a [`Mesh::ATTRIBUTE_TANGENT`],
+/// tangents are generated with [`Mesh::generate_tangents`] without modifying the mesh.
+/// Meshes for which tangents can't be generated aren't drawn.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShowTbn {
+    /// Length of the drawn lines, in world units.
+    pub length: f32,
+}
+
+impl Default for ShowTbn {
+    fn default() -> Self {
+        ShowTbn { length: 0.1 }
+    }
+}
+
+pub(crate) fn draw_tbn(
+    mut draw: ResMut<DebugDraw>,
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut generated_tangents: Local<HashMap<HandleId, Option<Vec<[f32; 4]>>>>,
+    query: Query<(
+        &ShowTbn,
+        &Handle<Mesh>,
+        &GlobalTransform,
+        &ComputedVisibility,
+    )>,
+) {
+    for event in mesh_events.iter() {
+        match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                generated_tangents.remove(&handle.id());
+            }
+            AssetEvent::Created { .. } => {}
+        }
+    }
+
+    for (show_tbn, handle, transform, visibility) in &query {
+        if !visibility.is_visible_in_hierarchy() {
+            continue;
+        }
+        let mesh = match meshes.get(handle) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let tangents = match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
+            Some(VertexAttributeValues::Float32x4(tangents)) => tangents,
+            _ => match generated_tangents
+                .entry(handle.id())
+                .or_insert_with(|| generate_tangents(mesh))
+            {
+                Some(tangents) => tangents,
+                None => continue,
+            },
+        };
+        draw_mesh_tbn(&mut draw, mesh, tangents, transform, show_tbn.length);
+    }
+}
+
+/// Generates tangents for a copy of `mesh`, returning `None` if it isn't possible.
+fn generate_tangents(mesh: &Mesh) -> Option<Vec<[f32; 4]>> {
+    let mut mesh = mesh.clone();
+    mesh.generate_tangents().ok()?;
+    match mesh.remove_attribute(Mesh::ATTRIBUTE_TANGENT) {
+        Some(VertexAttributeValues::Float32x4(tangents)) => Some(tangents),
+        _ => None,
+    }
+}
+
+fn draw_mesh_tbn(
+    draw: &mut DebugDraw,
+    mesh: &Mesh,
+    tangents: &[[f32; 4]],
+    transform: &GlobalTransform,
+    length: f32,
+) {
+    let (positions, normals) = match (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+        mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+    ) {
+        (
+            Some(VertexAttributeValues::Float32x3(positions)),
+            Some(VertexAttributeValues::Float32x3(normals)),
+        ) => (positions, normals),
+        _ => return,
+    };
+
+    let model = transform.affine();
+    // Normals are transformed by the inverse transpose to stay perpendicular to the surface
+    // under non-uniform scale.
+    let normal_matrix = model.matrix3.inverse().transpose();
+    for ((position, normal), tangent) in positions.iter().zip(normals).zip(tangents) {
+        let tangent = Vec4::from(*tangent);
+        let origin = model.transform_point3(Vec3::from(*position));
+        let normal = Vec3::from(normal_matrix * Vec3A::from(*normal)).normalize_or_zero();
+        let tangent_xyz = model
+            .transform_vector3(tangent.truncate())
+            .normalize_or_zero();
+        let bitangent = tangent.w * normal.cross(tangent_xyz);
+
+        draw.ray(origin, tangent_xyz * length, Color::RED);
+        draw.ray(origin, bitangent * length, Color::GREEN);
+        draw.ray(origin, normal * length, Color::BLUE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Quat;
+    use bevy_render::mesh::shape;
+    use bevy_transform::components::Transform;
+
+    use super::*;
+
+    #[test]
+    fn drawn_tangents_are_perpendicular_to_normals() {
+        let mesh = Mesh::from(shape::Cube::default());
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_TANGENT).is_none());
+        let tangents = generate_tangents(&mesh).unwrap();
+
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(1.0, 2.0, 3.0)
+                .with_rotation(Quat::from_rotation_y(0.7))
+                .with_scale(Vec3::new(1.0, 3.0, 0.5)),
+        );
+        let mut draw = DebugDraw::default();
+        draw_mesh_tbn(&mut draw, &mesh, &tangents, &transform, 0.2);
+
+        assert_eq!(draw.lines().len(), mesh.count_vertices() * 3);
+        for tbn in draw.lines().chunks(3) {
+            let [t, b, n] = [tbn[0], tbn[1], tbn[2]].map(|line| line.end - line.start);
+            assert!((t.length() - 0.2).abs() < 1e-4);
+            assert!(t.normalize().dot(n.normalize()).abs() < 1e-4);
+            assert!(b.normalize().dot(n.normalize()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn tangents_cant_be_generated_without_uvs() {
+        let mut mesh = Mesh::from(shape::Cube::default());
+        mesh.remove_attribute(Mesh::ATTRIBUTE_UV_0);
+        assert!(generate_tangents(&mesh).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! A preset of debug plugins toggled with the function keys, for a quick debug environment.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_utils::tracing::info;
+
+use crate::{
+    aabb::{DrawAabbs, DrawAabbsPlugin},
+    axes::{DebugAxes, DebugAxesPlugin},
+    draw::DebugDrawPlugin,
+    fly_camera::{FlyCamera, FlyCameraPlugin},
+    grid::{DebugGrid, DebugGridPlugin},
+};
+
+/// Adds the [`DebugDrawPlugin`], [`DrawAabbsPlugin`], [`DebugAxesPlugin`],
+/// [`DebugGridPlugin`] and [`FlyCameraPlugin`], all controlled by a [`DebugToolkitConfig`].
+///
+/// The fly camera only moves the cameras a [`FlyCamera`] is added to. Don't add the
+/// toolkit's plugins separately as well, as their systems would run twice.
+#[derive(Default)]
+pub struct DebugToolkitPlugin;
+
+impl Plugin for DebugToolkitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(DebugDrawPlugin)
+            .add_plugin(DrawAabbsPlugin)
+            .add_plugin(DebugAxesPlugin)
+            .add_plugin(DebugGridPlugin)
+            .add_plugin(FlyCameraPlugin)
+            .init_resource::<DebugToolkitConfig>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_debug_toolkit);
+    }
+}
+
+/// Which features of the [`DebugToolkitPlugin`] are enabled, and the keys toggling them.
+///
+/// A key set to `None` leaves its feature to be toggled from code.
+#[derive(Resource, Debug, Clone)]
+pub struct DebugToolkitConfig {
+    /// Whether the AABBs are drawn, see [`DrawAabbs`].
+    pub aabbs: bool,
+    /// Whether the world axes are drawn, see [`DebugAxes`].
+    pub axes: bool,
+    /// Whether the ground grid is drawn, see [`DebugGrid`].
+    pub grid: bool,
+    /// Whether the cameras with a [`FlyCamera`] react to the input. Only applied to the cameras
+    /// when it changes, so a single camera can still be enabled with [`FlyCamera::enabled`].
+    pub fly_camera: bool,
+    /// The key toggling [`DebugToolkitConfig::aabbs`].
+    pub aabbs_key: Option<KeyCode>,
+    /// The key toggling [`DebugToolkitConfig::axes`].
+    pub axes_key: Option<KeyCode>,
+    /// The key toggling [`DebugToolkitConfig::grid`].
+    pub grid_key: Option<KeyCode>,
+    /// The key toggling [`DebugToolkitConfig::fly_camera`].
+    pub fly_camera_key: Option<KeyCode>,
+}
+
+impl Default for DebugToolkitConfig {
+    fn default() -> Self {
+        Self {
+            aabbs: true,
+            axes: true,
+            grid: true,
+            fly_camera: true,
+            aabbs_key: Some(KeyCode::F3),
+            axes_key: Some(KeyCode::F4),
+            grid_key: Some(KeyCode::F5),
+            fly_camera_key: Some(KeyCode::F6),
+        }
+    }
+}
+
+fn update_debug_toolkit(
+    mut config: ResMut<DebugToolkitConfig>,
+    input: Option<Res<Input<KeyCode>>>,
+    mut aabbs: ResMut<DrawAabbs>,
+    mut axes: ResMut<DebugAxes>,
+    mut grid: ResMut<DebugGrid>,
+    mut fly_cameras: Query<&mut FlyCamera>,
+) {
+    if let Some(input) = input {
+        let pressed = |key: Option<KeyCode>| matches!(key, Some(key) if input.just_pressed(key));
+        let keys = [
+            config.aabbs_key,
+            config.axes_key,
+            config.grid_key,
+            config.fly_camera_key,
+        ];
+        // Only borrow the config mutably when a key is pressed, to keep its change detection
+        if keys.into_iter().any(pressed) {
+            let config = &mut *config;
+            for (key, enabled, name) in [
+                (config.aabbs_key, &mut config.aabbs, "AABBs"),
+                (config.axes_key, &mut config.axes, "Axes"),
+                (config.grid_key, &mut config.grid, "Grid"),
+                (config.fly_camera_key, &mut config.fly_camera, "Fly camera"),
+            ] {
+                if pressed(key) {
+                    *enabled = !*enabled;
+                    info!("{name}: {}", if *enabled { "on" } else { "off" });
+                }
+            }
+        }
+    }
+
+    // The features are only set when the config changes, so that they can also be toggled
+    // individually, such as enabling a single fly camera
+    if !config.is_changed() {
+        return;
+    }
+    // Avoid triggering change detection when nothing changed
+    if aabbs.enabled != config.aabbs {
+        aabbs.enabled = config.aabbs;
+    }
+    if axes.enabled != config.axes {
+        axes.enabled = config.axes;
+    }
+    if grid.enabled != config.grid {
+        grid.enabled = config.grid;
+    }
+    for mut fly_camera in &mut fly_cameras {
+        if fly_camera.enabled != config.fly_camera {
+            fly_camera.enabled = config.fly_camera;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_input::mouse::{MouseButton, MouseMotion};
+    use bevy_pbr::StandardMaterial;
+    use bevy_render::mesh::Mesh;
+    use bevy_time::Time;
+
+    use super::*;
+    use crate::draw::DebugDraw;
+
+    #[test]
+    fn toolkit_adds_and_toggles_every_feature() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .init_resource::<Time>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<MouseButton>>()
+            .add_event::<MouseMotion>()
+            .add_plugin(DebugToolkitPlugin);
+        let camera = app.world.spawn(FlyCamera::default()).id();
+        app.update();
+
+        assert!(
+            app.world.contains_resource::<DebugDraw>(),
+            "DebugDraw missing"
+        );
+        assert!(
+            app.world.contains_resource::<DrawAabbs>(),
+            "DrawAabbs missing"
+        );
+        assert!(
+            app.world.contains_resource::<DebugAxes>(),
+            "DebugAxes missing"
+        );
+        assert!(
+            app.world.contains_resource::<DebugGrid>(),
+            "DebugGrid missing"
+        );
+        assert!(
+            app.world.contains_resource::<DebugToolkitConfig>(),
+            "DebugToolkitConfig missing"
+        );
+
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        input.press(KeyCode::F3);
+        input.press(KeyCode::F5);
+        input.press(KeyCode::F6);
+        app.update();
+        let config = app.world.resource::<DebugToolkitConfig>();
+        assert!(!config.aabbs && config.axes && !config.grid && !config.fly_camera);
+        assert!(!app.world.resource::<DrawAabbs>().enabled);
+        assert!(app.world.resource::<DebugAxes>().enabled);
+        assert!(!app.world.resource::<DebugGrid>().enabled);
+        assert!(!app.world.get::<FlyCamera>(camera).unwrap().enabled);
+
+        // Features can also be toggled from code
+        app.world.resource_mut::<DebugToolkitConfig>().axes = false;
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        app.update();
+        assert!(!app.world.resource::<DebugAxes>().enabled);
+
+        // A single fly camera can be enabled while the toolkit's are off
+        app.world.get_mut::<FlyCamera>(camera).unwrap().enabled = true;
+        app.update();
+        app.update();
+        assert!(app.world.get::<FlyCamera>(camera).unwrap().enabled);
+
+        // Until the toolkit toggles them again
+        let press_fly_camera_key = |app: &mut App| {
+            let mut input = app.world.resource_mut::<Input<KeyCode>>();
+            input.reset_all();
+            input.press(KeyCode::F6);
+            app.update();
+        };
+        press_fly_camera_key(&mut app);
+        assert!(app.world.resource::<DebugToolkitConfig>().fly_camera);
+        press_fly_camera_key(&mut app);
+        assert!(!app.world.get::<FlyCamera>(camera).unwrap().enabled);
+    }
+}
@@ -0,0 +1,176 @@
+//! Convenient access to the window and camera viewport sizes from debug systems.
+
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Rect, UVec2, Vec2};
+use bevy_render::camera::{Camera, RenderTarget};
+use bevy_window::{WindowId, Windows};
+
+/// The area a camera renders to on its [`RenderTarget`], as returned by [`Viewports`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraViewport {
+    /// The camera entity.
+    pub camera: Entity,
+    /// The viewport in logical pixels, with the origin in the top-left corner of the target.
+    pub logical: Rect,
+    /// The (minimum, maximum) corners of the viewport in physical pixels.
+    pub physical: (UVec2, UVec2),
+}
+
+/// A [`SystemParam`] giving the viewport of each [`Camera`] and the size of the primary window.
+///
+/// The viewports are only known once the camera's target size has been computed, during
+/// [`CoreStage::PostUpdate`](bevy_app::CoreStage::PostUpdate) of the frame the camera is spawned.
+///
+/// ```
+/// # use bevy_debug_draw::viewport::Viewports;
+/// fn print_viewport(viewports: Viewports) {
+///     if let Some(viewport) = viewports.primary() {
+///         println!("rendering {:?} of {:?}", viewport.logical, viewports.primary_window_size());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_viewport);
+/// ```
+#[derive(SystemParam)]
+pub struct Viewports<'w, 's> {
+    windows: Res<'w, Windows>,
+    cameras: Query<'w, 's, (Entity, &'static Camera)>,
+}
+
+impl<'w, 's> Viewports<'w, 's> {
+    /// The logical size of the primary window, if there is one.
+    pub fn primary_window_size(&self) -> Option<Vec2> {
+        let window = self.windows.get_primary()?;
+        Some(Vec2::new(window.width(), window.height()))
+    }
+
+    /// The physical size of the primary window, if there is one.
+    pub fn primary_window_physical_size(&self) -> Option<UVec2> {
+        let window = self.windows.get_primary()?;
+        Some(UVec2::new(
+            window.physical_width(),
+            window.physical_height(),
+        ))
+    }
+
+    /// The viewport of `camera`, if it is a camera whose target size is known.
+    pub fn get(&self, camera: Entity) -> Option<CameraViewport> {
+        let (entity, camera) = self.cameras.get(camera).ok()?;
+        camera_viewport(entity, camera)
+    }
+
+    /// The viewports of all cameras whose target size is known.
+    pub fn iter(&self) -> impl Iterator<Item = CameraViewport> + '_ {
+        self.cameras
+            .iter()
+            .filter_map(|(entity, camera)| camera_viewport(entity, camera))
+    }
+
+    /// The viewport of the active camera rendering on top of the primary window, i.e. the one
+    /// with the highest [`Camera::priority`].
+    pub fn primary(&self) -> Option<CameraViewport> {
+        self.cameras
+            .iter()
+            .filter(|(_, camera)| {
+                camera.is_active && camera.target == RenderTarget::Window(WindowId::primary())
+            })
+            .max_by_key(|(_, camera)| camera.priority)
+            .and_then(|(entity, camera)| camera_viewport(entity, camera))
+    }
+}
+
+fn camera_viewport(entity: Entity, camera: &Camera) -> Option<CameraViewport> {
+    let (min, max) = camera.logical_viewport_rect()?;
+    Some(CameraViewport {
+        camera: entity,
+        logical: Rect { min, max },
+        physical: camera.physical_viewport_rect()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_render::{
+        camera::{camera_system, OrthographicProjection, Viewport},
+        texture::Image,
+    };
+    use bevy_window::{Window, WindowCreated, WindowDescriptor, WindowResized};
+
+    use super::*;
+
+    #[test]
+    fn reports_window_and_camera_viewports() {
+        #[derive(Resource, Default)]
+        struct Reported(Option<Vec2>, Option<UVec2>, Option<CameraViewport>, usize);
+
+        fn report(viewports: Viewports, mut reported: ResMut<Reported>) {
+            *reported = Reported(
+                viewports.primary_window_size(),
+                viewports.primary_window_physical_size(),
+                viewports.primary(),
+                viewports.iter().count(),
+            );
+        }
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Image>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .init_resource::<Windows>()
+            .init_resource::<Reported>()
+            .add_system(camera_system::<OrthographicProjection>)
+            .add_system(report.after(camera_system::<OrthographicProjection>));
+
+        // A 800x600 window with a scale factor of 2
+        let descriptor = WindowDescriptor {
+            width: 800.0,
+            height: 600.0,
+            ..Default::default()
+        };
+        app.world.resource_mut::<Windows>().add(Window::new(
+            WindowId::primary(),
+            &descriptor,
+            1600,
+            1200,
+            2.0,
+            None,
+            None,
+        ));
+
+        app.world
+            .spawn((Camera::default(), OrthographicProjection::default()));
+        let overlay = app
+            .world
+            .spawn((
+                Camera {
+                    priority: 1,
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::new(100, 200),
+                        physical_size: UVec2::new(400, 300),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                OrthographicProjection::default(),
+            ))
+            .id();
+        app.update();
+
+        let reported = app.world.resource::<Reported>();
+        assert_eq!(reported.0, Some(Vec2::new(800.0, 600.0)));
+        assert_eq!(reported.1, Some(UVec2::new(1600, 1200)));
+        assert_eq!(
+            reported.2,
+            Some(CameraViewport {
+                camera: overlay,
+                logical: Rect::new(50.0, 100.0, 250.0, 250.0),
+                physical: (UVec2::new(100, 200), UVec2::new(500, 500)),
+            })
+        );
+        assert_eq!(reported.3, 2);
+    }
+}
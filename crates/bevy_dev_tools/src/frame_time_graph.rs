@@ -0,0 +1,306 @@
+//! An on-screen FPS counter with a scrolling graph of the last frame times.
+
+use std::collections::VecDeque;
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::Handle;
+use bevy_diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_render::color::Color;
+use bevy_text::{Font, Text, TextStyle};
+use bevy_ui::{
+    entity::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, Display, FlexDirection, PositionType, Size, Style, UiRect,
+    UiSystem, Val,
+};
+use bevy_utils::default;
+
+/// Displays the FPS and a graph of the frame times in the top-right corner of the primary
+/// window, shown and hidden with [`FrameTimeGraphConfig::toggle_key`].
+///
+/// Reads the diagnostics of the [`FrameTimeDiagnosticsPlugin`], which has to be added as well.
+/// Requires the [`UiPlugin`](bevy_ui::UiPlugin) and [`TextPlugin`](bevy_text::TextPlugin)
+/// for the overlay to actually be rendered.
+#[derive(Default)]
+pub struct FrameTimeGraphPlugin;
+
+impl Plugin for FrameTimeGraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FrameTimeGraphConfig>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                toggle_frame_time_graph.before(update_frame_time_graph),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_frame_time_graph.before(UiSystem::Flex),
+            );
+    }
+}
+
+/// Settings of the overlay of the [`FrameTimeGraphPlugin`].
+///
+/// The overlay is rebuilt when this resource changes.
+#[derive(Resource, Clone, Debug)]
+pub struct FrameTimeGraphConfig {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+    /// The key toggling [`FrameTimeGraphConfig::enabled`], or `None` to only toggle it from code.
+    pub toggle_key: Option<KeyCode>,
+    /// The font of the FPS counter.
+    ///
+    /// Bevy doesn't ship a default font, so this has to be set for the counter to be visible.
+    pub font: Handle<Font>,
+    /// The size of the font.
+    pub font_size: f32,
+    /// The number of frames in the graph, each drawn as a bar.
+    pub frames: usize,
+    /// The frame time the game aims for, in milliseconds. Longer frames are drawn in yellow,
+    /// and frames taking more than twice as long in red.
+    pub target_frame_time: f32,
+    /// The frame time at the top of the graph, in milliseconds. Longer frames are cut.
+    pub max_frame_time: f32,
+    /// The size of the graph, in logical pixels.
+    pub size: (f32, f32),
+}
+
+impl Default for FrameTimeGraphConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_key: Some(KeyCode::F9),
+            font: default(),
+            font_size: 16.0,
+            frames: 100,
+            target_frame_time: 1000.0 / 60.0,
+            max_frame_time: 50.0,
+            size: (200.0, 50.0),
+        }
+    }
+}
+
+/// Marker for the root entity of the overlay of the [`FrameTimeGraphPlugin`].
+#[derive(Component, Debug, Default)]
+pub struct FrameTimeGraph;
+
+/// Marker for the text of the FPS counter.
+#[derive(Component, Debug, Default)]
+struct FrameTimeText;
+
+/// A bar of the graph, showing the frame time `frames` ago, counting from the left.
+#[derive(Component, Debug)]
+struct FrameTimeBar(usize);
+
+fn toggle_frame_time_graph(
+    mut config: ResMut<FrameTimeGraphConfig>,
+    input: Option<Res<Input<KeyCode>>>,
+) {
+    if let Some(input) = input {
+        if matches!(config.toggle_key, Some(key) if input.just_pressed(key)) {
+            config.enabled = !config.enabled;
+        }
+    }
+}
+
+fn update_frame_time_graph(
+    mut commands: Commands,
+    config: Res<FrameTimeGraphConfig>,
+    diagnostics: Option<Res<Diagnostics>>,
+    mut frame_times: Local<VecDeque<f64>>,
+    roots: Query<Entity, With<FrameTimeGraph>>,
+    mut texts: Query<&mut Text, With<FrameTimeText>>,
+    mut bars: Query<(&FrameTimeBar, &mut Style, &mut BackgroundColor)>,
+) {
+    if let Some(diagnostics) = &diagnostics {
+        if let Some(frame_time) =
+            diagnostics.get_measurement(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        {
+            frame_times.push_back(frame_time.value);
+        }
+    }
+    while frame_times.len() > config.frames {
+        frame_times.pop_front();
+    }
+
+    if config.is_changed() {
+        for root in &roots {
+            commands.entity(root).despawn_recursive();
+        }
+        spawn_frame_time_graph(&mut commands, &config);
+        // The new graph is updated next frame
+        return;
+    }
+
+    let fps = diagnostics
+        .as_ref()
+        .and_then(|diagnostics| diagnostics.get(FrameTimeDiagnosticsPlugin::FPS))
+        .and_then(|fps| fps.average());
+    for mut text in &mut texts {
+        text.sections[0].value = match (fps, frame_times.back()) {
+            (Some(fps), Some(frame_time)) => format!("{fps:.0} FPS ({frame_time:.2} ms)"),
+            _ => "-- FPS".to_string(),
+        };
+    }
+
+    // The newest frame is on the right
+    let empty_bars = config.frames - frame_times.len();
+    for (bar, mut style, mut color) in &mut bars {
+        match bar.0.checked_sub(empty_bars) {
+            Some(frame) => {
+                let frame_time = frame_times[frame] as f32;
+                let height = (frame_time / config.max_frame_time).min(1.0) * 100.0;
+                style.size.height = Val::Percent(height);
+                color.0 = if frame_time <= config.target_frame_time {
+                    Color::GREEN
+                } else if frame_time <= config.target_frame_time * 2.0 {
+                    Color::YELLOW
+                } else {
+                    Color::RED
+                };
+            }
+            None => style.size.height = Val::Percent(0.0),
+        }
+    }
+}
+
+fn spawn_frame_time_graph(commands: &mut Commands, config: &FrameTimeGraphConfig) {
+    let (width, height) = config.size;
+    let bar_width = width / config.frames as f32;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: if config.enabled {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(5.0),
+                        right: Val::Px(5.0),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            FrameTimeGraph,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: config.font.clone(),
+                        font_size: config.font_size,
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                ),
+                FrameTimeText,
+            ));
+            root.spawn(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Px(width), Val::Px(height)),
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+                ..default()
+            })
+            .with_children(|graph| {
+                for frame in 0..config.frames {
+                    graph.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(bar_width), Val::Percent(0.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        FrameTimeBar(frame),
+                    ));
+                }
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_diagnostic::{Diagnostic, DiagnosticsPlugin};
+
+    use super::*;
+
+    fn bar_heights(app: &mut App) -> Vec<Val> {
+        let mut query = app.world.query::<(&FrameTimeBar, &Style)>();
+        let mut bars: Vec<_> = query
+            .iter(&app.world)
+            .map(|(bar, style)| (bar.0, style.size.height))
+            .collect();
+        bars.sort_by_key(|(frame, _)| *frame);
+        bars.into_iter().map(|(_, height)| height).collect()
+    }
+
+    #[test]
+    fn graph_shows_frame_times_and_toggles() {
+        #[derive(Resource)]
+        struct NextFrameTime(f64);
+
+        fn measure(mut diagnostics: ResMut<Diagnostics>, mut next: ResMut<NextFrameTime>) {
+            diagnostics.add_measurement(FrameTimeDiagnosticsPlugin::FRAME_TIME, || next.0);
+            diagnostics.add_measurement(FrameTimeDiagnosticsPlugin::FPS, || 1000.0 / next.0);
+            next.0 *= 2.0;
+        }
+
+        let mut app = App::new();
+        app.add_plugin(DiagnosticsPlugin)
+            .add_plugin(FrameTimeGraphPlugin)
+            .insert_resource(FrameTimeGraphConfig {
+                frames: 3,
+                max_frame_time: 40.0,
+                ..default()
+            })
+            .init_resource::<Input<KeyCode>>()
+            .insert_resource(NextFrameTime(10.0))
+            .add_system(measure);
+        let mut diagnostics = app.world.resource_mut::<Diagnostics>();
+        diagnostics.add(Diagnostic::new(
+            FrameTimeDiagnosticsPlugin::FRAME_TIME,
+            "",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(FrameTimeDiagnosticsPlugin::FPS, "", 1));
+
+        // The graph is spawned on the first frame
+        app.update();
+        app.update();
+        assert_eq!(
+            bar_heights(&mut app),
+            [Val::Percent(0.0), Val::Percent(25.0), Val::Percent(50.0)]
+        );
+        app.update();
+        app.update();
+        // The oldest frame scrolled out, and the longest is cut
+        assert_eq!(
+            bar_heights(&mut app),
+            [Val::Percent(50.0), Val::Percent(100.0), Val::Percent(100.0)]
+        );
+        let mut texts = app.world.query_filtered::<&Text, With<FrameTimeText>>();
+        assert_eq!(
+            texts.single(&app.world).sections[0].value,
+            "12 FPS (80.00 ms)"
+        );
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::F9);
+        app.update();
+        assert!(!app.world.resource::<FrameTimeGraphConfig>().enabled);
+        let mut roots = app.world.query_filtered::<&Style, With<FrameTimeGraph>>();
+        assert_eq!(roots.single(&app.world).display, Display::None);
+    }
+}
@@ -0,0 +1,13 @@
+#![warn(missing_docs)]
+//! Development tools for Bevy apps, such as performance overlays.
+//!
+//! None of the plugins in this crate are part of `DefaultPlugins`: add the ones you need
+//! while developing.
+
+pub mod frame_time_graph;
+
+/// Most commonly used re-exported types.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::frame_time_graph::{FrameTimeGraphConfig, FrameTimeGraphPlugin};
+}
@@ -0,0 +1,47 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    schedule::SystemExecutionTimes,
+    system::{Local, ResMut},
+};
+use bevy_utils::{get_short_name, HashMap};
+
+/// Adds a diagnostic for each system, measuring how long it runs every frame in milliseconds.
+///
+/// The diagnostics are named after the systems, and added the first time each system runs.
+/// Measuring systems has a small cost, so this plugin is better left out of release builds.
+#[derive(Default)]
+pub struct SystemExecutionTimeDiagnosticsPlugin;
+
+impl Plugin for SystemExecutionTimeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SystemExecutionTimes>()
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system);
+    }
+}
+
+impl SystemExecutionTimeDiagnosticsPlugin {
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut execution_times: ResMut<SystemExecutionTimes>,
+        mut ids: Local<HashMap<String, DiagnosticId>>,
+    ) {
+        for (name, time) in execution_times.iter() {
+            let id = match ids.get(name) {
+                Some(&id) => id,
+                None => {
+                    let id = DiagnosticId::default();
+                    let short_name: String = get_short_name(name)
+                        .chars()
+                        .take(MAX_DIAGNOSTIC_NAME_WIDTH)
+                        .collect();
+                    diagnostics.add(Diagnostic::new(id, short_name, 20).with_suffix("ms"));
+                    ids.insert(name.to_string(), id);
+                    id
+                }
+            };
+            diagnostics.add_measurement(id, || time.as_secs_f64() * 1000.);
+        }
+        execution_times.clear();
+    }
+}
@@ -32,6 +32,18 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     };
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
+    let register_required = if attrs.require.is_empty() {
+        TokenStream2::new()
+    } else {
+        let require = &attrs.require;
+        quote! {
+            fn register_required_components(
+                required: &mut #bevy_ecs_path::component::RequiredComponents,
+            ) {
+                #(required.register::<#require>();)*
+            }
+        }
+    };
 
     ast.generics
         .make_where_clause()
@@ -44,15 +56,18 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             type Storage = #storage;
+            #register_required
         }
     })
 }
 
 pub const COMPONENT: Symbol = Symbol("component");
 pub const STORAGE: Symbol = Symbol("storage");
+pub const REQUIRE: Symbol = Symbol("require");
 
 struct Attrs {
     storage: StorageTy,
+    require: Vec<Path>,
 }
 
 #[derive(Clone, Copy)]
@@ -70,11 +85,12 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
 
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        require: Vec::new(),
     };
 
     for meta in meta_items {
         use syn::{
-            Meta::NameValue,
+            Meta::{List, NameValue},
             NestedMeta::{Lit, Meta},
         };
         match meta {
@@ -93,6 +109,19 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
             }
+            Meta(List(m)) if m.path == REQUIRE => {
+                for nested in m.nested {
+                    match nested {
+                        Meta(syn::Meta::Path(path)) => attrs.require.push(path),
+                        other => {
+                            return Err(Error::new_spanned(
+                                other,
+                                "expected a component type in `require(...)`",
+                            ))
+                        }
+                    }
+                }
+            }
             Meta(meta_item) => {
                 return Err(Error::new_spanned(
                     meta_item.path(),
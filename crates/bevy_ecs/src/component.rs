@@ -2,8 +2,10 @@
 
 use crate::{
     change_detection::MAX_CHANGE_AGE,
+    entity::Entity,
     storage::{SparseSetIndex, Storages},
     system::Resource,
+    world::{EntityMut, World},
 };
 pub use bevy_ecs_macros::Component;
 use bevy_ptr::OwningPtr;
@@ -84,6 +86,26 @@ use std::{
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
+/// # Required components
+///
+/// A component can require other components, which are then inserted with their [`Default`]
+/// value whenever it is added to an entity that doesn't already have them.
+/// This is declared with a `#[component(require(...))]` attribute:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component, Default)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component)]
+/// #[component(require(Velocity))]
+/// struct Player;
+///
+/// let mut world = World::new();
+/// let player = world.spawn(Player).id();
+/// assert_eq!(world.get::<Velocity>(player).unwrap().0, 0.0);
+/// ```
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -111,6 +133,46 @@ use std::{
 /// [newtype pattern]: https://doc.rust-lang.org/book/ch19-03-advanced-traits.html#using-the-newtype-pattern-to-implement-external-traits-on-external-types
 pub trait Component: Send + Sync + 'static {
     type Storage: ComponentStorage;
+
+    /// Registers the components inserted alongside this one when it is added to an entity
+    /// missing them. See [Required components](Component#required-components).
+    fn register_required_components(_required: &mut RequiredComponents) {}
+}
+
+/// The components required by a [`Component`], registered in
+/// [`Component::register_required_components`].
+#[derive(Default)]
+pub struct RequiredComponents {
+    components: Vec<RequiredComponentRegistration>,
+}
+
+struct RequiredComponentRegistration {
+    init: fn(&mut Components, &mut Storages) -> ComponentId,
+    insert_default: fn(&mut EntityMut),
+}
+
+impl RequiredComponents {
+    /// Requires `R`, inserted with its [`Default`] value on entities missing it.
+    pub fn register<R: Component + Default>(&mut self) -> &mut Self {
+        fn init<R: Component>(components: &mut Components, storages: &mut Storages) -> ComponentId {
+            components.init_component::<R>(storages)
+        }
+        fn insert_default<R: Component + Default>(entity: &mut EntityMut) {
+            entity.insert(R::default());
+        }
+        self.components.push(RequiredComponentRegistration {
+            init: init::<R>,
+            insert_default: insert_default::<R>,
+        });
+        self
+    }
+}
+
+/// A component required by another one, with how to insert it. See [`RequiredComponents`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RequiredComponent {
+    pub(crate) id: ComponentId,
+    pub(crate) insert_default: fn(&mut EntityMut),
 }
 
 pub struct TableStorage;
@@ -159,6 +221,8 @@ pub enum StorageType {
 pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
+    hooks: ComponentHooks,
+    pub(crate) required_components: Vec<RequiredComponent>,
 }
 
 impl ComponentInfo {
@@ -203,8 +267,101 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// The hooks run when the component is added to or removed from an entity.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    /// The components inserted alongside this one when it is added to an entity missing them.
+    /// See [Required components](Component#required-components).
+    pub fn required_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.required_components.iter().map(|required| required.id)
+    }
+
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
-        ComponentInfo { id, descriptor }
+        ComponentInfo {
+            id,
+            descriptor,
+            hooks: ComponentHooks::default(),
+            required_components: Vec::new(),
+        }
+    }
+}
+
+/// A function run for an entity when a component is added to or removed from it.
+/// See [`ComponentHooks`].
+pub type ComponentHook = fn(&mut World, Entity);
+
+/// The functions run whenever a component is added to or removed from any entity, registered
+/// with [`World::register_component_hooks`](crate::world::World::register_component_hooks).
+///
+/// Hooks keep something outside of the [`World`] in sync with a component, such as a physics
+/// body or an audio voice, without polling [`Added`](crate::query::Added) or
+/// [`RemovedComponents`](crate::system::RemovedComponents) every frame.
+///
+/// Hooks run with the whole [`World`], right after the structural change for `on_add` and right
+/// before it for `on_remove`, so both can read the component on the entity. They must not
+/// despawn the entity, except an `on_remove` hook run because the entity is being despawned.
+/// Hooks are not run by [`World::clear_entities`](crate::world::World::clear_entities).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Body;
+///
+/// #[derive(Resource, Default)]
+/// struct PhysicsBodies(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// world.init_resource::<PhysicsBodies>();
+/// world
+///     .register_component_hooks::<Body>()
+///     .on_add(|world, entity| world.resource_mut::<PhysicsBodies>().0.push(entity))
+///     .on_remove(|world, entity| world.resource_mut::<PhysicsBodies>().0.retain(|&e| e != entity));
+///
+/// let entity = world.spawn(Body).id();
+/// assert_eq!(world.resource::<PhysicsBodies>().0, [entity]);
+/// world.despawn(entity);
+/// assert!(world.resource::<PhysicsBodies>().0.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<ComponentHook>,
+    pub(crate) on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Registers a hook run when the component is added to an entity that didn't have it.
+    /// Replacing the value of the component doesn't run it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component already has an `on_add` hook.
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        assert!(
+            self.on_add.is_none(),
+            "The component already has an on_add hook."
+        );
+        self.on_add = Some(hook);
+        self
+    }
+
+    /// Registers a hook run when the component is removed from an entity, including when the
+    /// entity is despawned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component already has an `on_remove` hook.
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        assert!(
+            self.on_remove.is_none(),
+            "The component already has an on_remove hook."
+        );
+        self.on_remove = Some(hook);
+        self
     }
 }
 
@@ -373,16 +530,31 @@ impl Components {
     #[inline]
     pub fn init_component<T: Component>(&mut self, storages: &mut Storages) -> ComponentId {
         let type_id = TypeId::of::<T>();
+        if let Some(&index) = self.indices.get(&type_id) {
+            return ComponentId(index);
+        }
 
-        let Components {
-            indices,
-            components,
-            ..
-        } = self;
-        let index = indices.entry(type_id).or_insert_with(|| {
-            Components::init_component_inner(components, storages, ComponentDescriptor::new::<T>())
-        });
-        ComponentId(*index)
+        let index = Components::init_component_inner(
+            &mut self.components,
+            storages,
+            ComponentDescriptor::new::<T>(),
+        );
+        // Registered before its required components, which may require it back
+        self.indices.insert(type_id, index);
+        let mut required = RequiredComponents::default();
+        T::register_required_components(&mut required);
+        if !required.components.is_empty() {
+            let required_components = required
+                .components
+                .into_iter()
+                .map(|registration| RequiredComponent {
+                    id: (registration.init)(self, storages),
+                    insert_default: registration.insert_default,
+                })
+                .collect();
+            self.components[index].required_components = required_components;
+        }
+        ComponentId(index)
     }
 
     pub fn init_component_with_descriptor(
@@ -424,6 +596,45 @@ impl Components {
         self.components.get(id.0)
     }
 
+    #[inline]
+    pub(crate) fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        self.components.get_mut(id.0).map(|info| &mut info.hooks)
+    }
+
+    /// Whether adding the component to an entity runs an `on_add` hook or inserts required
+    /// components, which need the whole [`World`].
+    pub(crate) fn has_add_effects(&self, id: ComponentId) -> bool {
+        match self.get_info(id) {
+            Some(info) => info.hooks.on_add.is_some() || !info.required_components.is_empty(),
+            None => false,
+        }
+    }
+
+    /// Whether spawning an entity with exactly the components `ids` runs an `on_add` hook or
+    /// inserts a required component missing from `ids`, which need the whole [`World`].
+    pub(crate) fn bundle_has_add_effects(&self, ids: &[ComponentId]) -> bool {
+        ids.iter().any(|&id| match self.get_info(id) {
+            Some(info) => {
+                info.hooks.on_add.is_some()
+                    || info
+                        .required_components
+                        .iter()
+                        .any(|required| !ids.contains(&required.id))
+            }
+            None => false,
+        })
+    }
+
+    /// The `on_remove` hooks of the given components, in order.
+    pub(crate) fn on_remove_hooks(
+        &self,
+        ids: impl IntoIterator<Item = ComponentId>,
+    ) -> Vec<ComponentHook> {
+        ids.into_iter()
+            .filter_map(|id| self.get_info(id)?.hooks.on_remove)
+            .collect()
+    }
+
     /// # Safety
     ///
     /// `id` must be a valid [`ComponentId`]
@@ -0,0 +1,76 @@
+//! Disabling entities without despawning them.
+//!
+//! An entity with the [`Disabled`] component is skipped by every [`Query`](crate::system::Query)
+//! that doesn't mention `Disabled` itself, e.g. with `With<Disabled>`, `Option<&Disabled>` or
+//! `&Disabled`. This deactivates the entity, such as a pooled enemy or an inactive UI screen,
+//! while keeping all of its components for when it is enabled again by removing `Disabled`.
+//!
+//! Only queries skip disabled entities: they can still be accessed directly through the
+//! [`World`](crate::world::World), and disabling an entity doesn't disable its children.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::entity_disabling::Disabled;
+//! #[derive(Component)]
+//! struct Enemy;
+//!
+//! let mut world = World::new();
+//! let enemy = world.spawn(Enemy).id();
+//! world.entity_mut(enemy).insert(Disabled);
+//!
+//! let mut enemies = world.query_filtered::<Entity, With<Enemy>>();
+//! assert_eq!(enemies.iter(&world).count(), 0);
+//! let mut disabled_enemies = world.query_filtered::<Entity, (With<Enemy>, With<Disabled>)>();
+//! assert_eq!(disabled_enemies.single(&world), enemy);
+//!
+//! world.entity_mut(enemy).remove::<Disabled>();
+//! assert_eq!(enemies.single(&world), enemy);
+//! ```
+
+use crate as bevy_ecs;
+use crate::component::Component;
+
+/// Marks an entity as disabled, so that it is skipped by queries not mentioning `Disabled`.
+/// See the [module documentation](self).
+//
+// Stored in tables, so that dense queries iterating whole tables never see disabled entities
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Disabled;
+
+#[cfg(test)]
+mod tests {
+    use super::Disabled;
+    use crate as bevy_ecs;
+    use crate::{prelude::*, schedule::SystemStage};
+
+    #[derive(Component)]
+    struct A;
+
+    #[test]
+    fn queries_skip_disabled_entities() {
+        let mut world = World::new();
+        let enabled = world.spawn(A).id();
+        let disabled = world.spawn((A, Disabled)).id();
+
+        let mut query = world.query::<(Entity, &A)>();
+        assert_eq!(query.single(&world).0, enabled);
+        assert!(query.get(&world, disabled).is_err());
+        // The entity is still there
+        assert!(world.entity(disabled).contains::<A>());
+
+        let mut query = world.query::<(Entity, Option<&Disabled>)>();
+        let mut entities: Vec<_> = query.iter(&world).collect();
+        entities.sort_by_key(|(entity, _)| *entity);
+        assert_eq!(entities, [(enabled, None), (disabled, Some(&Disabled))]);
+
+        // Queries of disabled and enabled entities don't conflict
+        fn enabled_system(_query: Query<&mut A>) {}
+        fn disabled_system(_query: Query<&mut A, With<Disabled>>) {}
+        let mut stage = SystemStage::parallel()
+            .with_system(enabled_system)
+            .with_system(disabled_system);
+        stage.run(&mut world);
+    }
+}
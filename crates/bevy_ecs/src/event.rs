@@ -2,8 +2,13 @@
 
 use crate as bevy_ecs;
 use crate::system::{Local, Res, ResMut, Resource, SystemParam};
+use crate::world::{FromWorld, World};
 use bevy_utils::tracing::{trace, warn};
 use std::ops::{Deref, DerefMut};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, Weak,
+};
 use std::{fmt, hash::Hash, marker::PhantomData};
 
 /// A type that can be stored in an [`Events<E>`] resource
@@ -121,6 +126,11 @@ struct EventInstance<E: Event> {
 /// but can be done by adding your event as a resource instead of using
 /// [`add_event`](https://docs.rs/bevy/*/bevy/app/struct.App.html#method.add_event).
 ///
+/// Events that must outlive two updates, for example when they are read on a fixed timestep or
+/// in another state than the one they were sent in, can instead be kept by another
+/// [`EventCleanup`] strategy, set with [`Events::with_cleanup`] or
+/// [`add_event_with_cleanup`](https://docs.rs/bevy/*/bevy/app/struct.App.html#method.add_event_with_cleanup).
+///
 /// [Example usage.](https://github.com/bevyengine/bevy/blob/latest/examples/ecs/event.rs)
 /// [Example usage standalone.](https://github.com/bevyengine/bevy/blob/latest/crates/bevy_ecs/examples/events.rs)
 ///
@@ -132,6 +142,9 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     events_b: EventSequence<E>,
     event_count: usize,
+    cleanup: EventCleanup,
+    /// The last event counts of the readers registered with [`Events::register_reader`].
+    readers: Mutex<Vec<Weak<AtomicUsize>>>,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -141,11 +154,32 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            cleanup: Default::default(),
+            readers: Default::default(),
         }
     }
 }
 
 impl<E: Event> Events<E> {
+    /// Creates an empty collection whose events are cleaned up by the given strategy.
+    pub fn with_cleanup(cleanup: EventCleanup) -> Self {
+        Self {
+            cleanup,
+            ..Default::default()
+        }
+    }
+
+    /// The strategy cleaning up the events on [`update`](Events::update).
+    #[inline]
+    pub fn cleanup(&self) -> EventCleanup {
+        self.cleanup
+    }
+
+    /// Sets the strategy cleaning up the events on [`update`](Events::update).
+    pub fn set_cleanup(&mut self, cleanup: EventCleanup) {
+        self.cleanup = cleanup;
+    }
+
     pub fn oldest_event_count(&self) -> usize {
         self.events_a
             .start_event_count
@@ -153,6 +187,24 @@ impl<E: Event> Events<E> {
     }
 }
 
+/// How the events of an [`Events`] collection are dropped by [`Events::update`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EventCleanup {
+    /// Events are dropped on the second update after they were sent, which lets the readers
+    /// running once per frame see each event regardless of their ordering with the writers.
+    #[default]
+    DoubleBuffered,
+    /// Events are never dropped by [`Events::update`], only by [`Events::clear`] or
+    /// [`Events::drain`].
+    Manual,
+    /// Events are dropped once they have been read by all the registered readers.
+    ///
+    /// The [`EventReader`]s of the systems are registered when they are initialized, and the
+    /// [`ManualEventReader`]s when they are created with [`Events::register_reader`]. Dropped
+    /// readers are unregistered. Events are dropped on update when there are no readers.
+    UntilConsumed,
+}
+
 #[derive(Debug)]
 struct EventSequence<E: Event> {
     events: Vec<EventInstance<E>>,
@@ -184,12 +236,44 @@ impl<E: Event> DerefMut for EventSequence<E> {
 }
 
 /// Reads events of type `T` in order and tracks which events have already been read.
+///
+/// The reader is registered with the [`Events`] when its system is initialized, which keeps the
+/// events it hasn't read yet if they are cleaned up by [`EventCleanup::UntilConsumed`].
 #[derive(SystemParam)]
 pub struct EventReader<'w, 's, E: Event> {
-    reader: Local<'s, ManualEventReader<E>>,
+    reader: Local<'s, RegisteredEventReader<E>>,
     events: Res<'w, Events<E>>,
 }
 
+/// The [`ManualEventReader`] of an [`EventReader`], registered with the [`Events`] it reads.
+#[doc(hidden)]
+pub struct RegisteredEventReader<E: Event>(ManualEventReader<E>);
+
+impl<E: Event> FromWorld for RegisteredEventReader<E> {
+    fn from_world(world: &mut World) -> Self {
+        Self(
+            world
+                .get_resource::<Events<E>>()
+                .map(Events::register_reader)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl<E: Event> Deref for RegisteredEventReader<E> {
+    type Target = ManualEventReader<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E: Event> DerefMut for RegisteredEventReader<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     /// Iterates over the events this [`EventReader`] has not seen yet. This updates the
     /// [`EventReader`]'s event counter, which means subsequent event reads will not include events
@@ -324,6 +408,8 @@ impl<'w, 's, E: Event> EventWriter<'w, 's, E> {
 #[derive(Debug)]
 pub struct ManualEventReader<E: Event> {
     last_event_count: usize,
+    /// Shares the last event count with the [`Events`] the reader is registered with.
+    registration: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<E>,
 }
 
@@ -331,6 +417,7 @@ impl<E: Event> Default for ManualEventReader<E> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            registration: None,
             _marker: Default::default(),
         }
     }
@@ -369,13 +456,23 @@ impl<E: Event> ManualEventReader<E> {
         let unread_count = a.len() + b.len();
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, self.len(events));
-        self.last_event_count = events.event_count - unread_count;
+        self.set_last_event_count(events.event_count - unread_count);
         // Iterate the oldest first, then the newer events
         let iterator = a.iter().chain(b.iter());
         iterator
             .map(|e| (&e.event, e.event_id))
             .with_exact_size(unread_count)
-            .inspect(move |(_, id)| self.last_event_count = (id.id + 1).max(self.last_event_count))
+            .inspect(move |(_, id)| {
+                self.set_last_event_count((id.id + 1).max(self.last_event_count));
+            })
+    }
+
+    #[inline]
+    fn set_last_event_count(&mut self, last_event_count: usize) {
+        self.last_event_count = last_event_count;
+        if let Some(registration) = &self.registration {
+            registration.store(last_event_count, Ordering::Relaxed);
+        }
     }
 
     /// See [`EventReader::len`]
@@ -495,9 +592,35 @@ impl<E: Event> Events<E> {
         }
     }
 
-    /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
-    /// called once per frame/update.
+    /// Gets a new [`ManualEventReader`] registered with this collection, which keeps the events it
+    /// hasn't read yet while they are cleaned up by [`EventCleanup::UntilConsumed`]. This will
+    /// include all events already in the event buffers.
+    pub fn register_reader(&self) -> ManualEventReader<E> {
+        let registration = Arc::new(AtomicUsize::new(0));
+        let mut readers = self.readers.lock().unwrap();
+        // The dropped readers are otherwise only pruned by `EventCleanup::UntilConsumed`
+        readers.retain(|reader| reader.strong_count() > 0);
+        readers.push(Arc::downgrade(&registration));
+        ManualEventReader {
+            registration: Some(registration),
+            ..Default::default()
+        }
+    }
+
+    /// Drops the events following the [`EventCleanup`] strategy of this collection. In general,
+    /// this should be called once per frame/update.
+    ///
+    /// With [`EventCleanup::DoubleBuffered`], this swaps the event buffers and clears the oldest
+    /// event buffer.
     pub fn update(&mut self) {
+        match self.cleanup {
+            EventCleanup::DoubleBuffered => self.swap_buffers(),
+            EventCleanup::Manual => {}
+            EventCleanup::UntilConsumed => self.drop_consumed(),
+        }
+    }
+
+    fn swap_buffers(&mut self) {
         std::mem::swap(&mut self.events_a, &mut self.events_b);
         self.events_b.clear();
         self.events_b.start_event_count = self.event_count;
@@ -507,6 +630,27 @@ impl<E: Event> Events<E> {
         );
     }
 
+    /// Drops the events read by all the registered readers, keeping the others in the oldest
+    /// buffer.
+    fn drop_consumed(&mut self) {
+        let readers = self.readers.get_mut().unwrap();
+        readers.retain(|reader| reader.strong_count() > 0);
+        let consumed = readers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|reader| reader.load(Ordering::Relaxed))
+            .min()
+            .unwrap_or(self.event_count)
+            .max(self.events_a.start_event_count);
+
+        let events_b = std::mem::take(&mut self.events_b.events);
+        self.events_a.extend(events_b);
+        let dropped = (consumed - self.events_a.start_event_count).min(self.events_a.len());
+        self.events_a.drain(..dropped);
+        self.events_a.start_event_count = self.event_count - self.events_a.len();
+        self.events_b.start_event_count = self.event_count;
+    }
+
     /// A system that calls [`Events::update`] once per frame.
     pub fn update_system(mut events: ResMut<Self>) {
         events.update();
@@ -844,6 +988,96 @@ mod tests {
         assert!(is_empty, "EventReader should be empty");
     }
 
+    #[test]
+    fn test_events_manual_cleanup() {
+        let mut events = Events::<TestEvent>::with_cleanup(EventCleanup::Manual);
+        let mut reader = events.get_reader();
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.update();
+        events.send(TestEvent { i: 1 });
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+
+        events.clear();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_events_until_consumed_cleanup() {
+        let mut events = Events::<TestEvent>::with_cleanup(EventCleanup::UntilConsumed);
+        let mut fast_reader = events.register_reader();
+        let mut slow_reader = events.register_reader();
+        // Unregistered readers don't keep the events
+        let unregistered_reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+        assert_eq!(fast_reader.iter(&events).count(), 2);
+        events.update();
+        events.update();
+        events.send(TestEvent { i: 2 });
+        events.update();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            get_events(&events, &mut slow_reader),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }, TestEvent { i: 2 }]
+        );
+
+        // Only the event the fast reader hasn't read is kept
+        events.update();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            get_events(&events, &mut fast_reader),
+            vec![TestEvent { i: 2 }]
+        );
+        assert_eq!(unregistered_reader.missed_events(&events), 2);
+
+        // Dropped readers are unregistered
+        drop(slow_reader);
+        events.send(TestEvent { i: 3 });
+        events.update();
+        assert_eq!(events.len(), 1);
+        drop(fast_reader);
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_dropped_readers_are_pruned() {
+        let events = Events::<TestEvent>::default();
+        let reader = events.register_reader();
+        for _ in 0..10 {
+            drop(events.register_reader());
+        }
+        assert_eq!(events.readers.lock().unwrap().len(), 2);
+        drop(reader);
+        drop(events.register_reader());
+        assert_eq!(events.readers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_event_reader_is_registered() {
+        let mut world = World::new();
+        world.insert_resource(Events::<TestEvent>::with_cleanup(
+            EventCleanup::UntilConsumed,
+        ));
+        let mut state = SystemState::<EventReader<TestEvent>>::new(&mut world);
+
+        let mut events = world.resource_mut::<Events<TestEvent>>();
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.update();
+        assert_eq!(events.len(), 1);
+
+        assert_eq!(state.get_mut(&mut world).iter().count(), 1);
+        world.resource_mut::<Events<TestEvent>>().update();
+        assert!(world.resource::<Events<TestEvent>>().is_empty());
+    }
+
     #[derive(Clone, PartialEq, Debug, Default)]
     struct EmptyTestEvent;
 
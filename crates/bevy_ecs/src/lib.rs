@@ -9,6 +9,7 @@ pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
+pub mod entity_disabling;
 pub mod event;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
@@ -56,6 +57,7 @@ mod tests {
         bundle::Bundle,
         component::{Component, ComponentId},
         entity::Entity,
+        entity_disabling::Disabled,
         query::{
             Added, ChangeTrackers, Changed, FilteredAccess, ReadOnlyWorldQuery, With, Without,
         },
@@ -1229,6 +1231,9 @@ mod tests {
         let b_id = world.components.get_id(TypeId::of::<B>()).unwrap();
         expected.add_write(a_id);
         expected.add_read(b_id);
+        // Queries skip disabled entities by default
+        let disabled_id = world.component_id::<Disabled>().unwrap();
+        expected.add_without(disabled_id);
         assert!(
             query.component_access.eq(&expected),
             "ComponentId access from query fetch and query filter should be combined"
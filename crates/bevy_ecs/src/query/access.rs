@@ -227,6 +227,13 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.without.insert(index.sparse_set_index());
     }
 
+    /// Returns `true` if the element given by `index` is accessed, or filtered for or against.
+    pub fn mentions(&self, index: T) -> bool {
+        self.with.contains(index.sparse_set_index())
+            || self.without.contains(index.sparse_set_index())
+            || self.access.has_read(index)
+    }
+
     pub fn extend_intersect_filter(&mut self, other: &FilteredAccess<T>) {
         self.without.intersect_with(&other.without);
         self.with.intersect_with(&other.with);
@@ -0,0 +1,522 @@
+use std::any::TypeId;
+
+use crate::{
+    archetype::Archetype,
+    change_detection::{MutUntyped, Ticks},
+    component::ComponentId,
+    entity::{Entity, EntityLocation},
+    entity_disabling::Disabled,
+    world::{get_component_and_ticks, World, WorldId},
+};
+use bevy_ptr::{Ptr, UnsafeCellDeref};
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::change_detection::Mut,
+    bevy_reflect::{Reflect, ReflectFromPtr, TypeRegistry},
+};
+
+/// Builds a [`DynamicQuery`] from components only known at runtime, such as the components
+/// used by a scripting language or an editor.
+///
+/// Components are given by [`ComponentId`] or by [`TypeId`], and are fetched in the order
+/// they are added: reads as [`Ptr`]s in [`DynamicQueryItem::reads`], and writes as
+/// [`MutUntyped`]s in [`DynamicQueryItem::writes`].
+///
+/// Like other queries, the built query skips [`Disabled`] entities unless it mentions
+/// `Disabled`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::{change_detection::DetectChanges, query::QueryBuilder};
+/// # use std::any::TypeId;
+/// #[derive(Component)]
+/// struct Position(f32);
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// let mut world = World::new();
+/// world.spawn((Position(0.0), Velocity(1.0)));
+/// let velocity = world.init_component::<Velocity>();
+///
+/// let query = QueryBuilder::new(&mut world)
+///     .write_type_id(TypeId::of::<Position>())
+///     .read(velocity)
+///     .build();
+/// for mut item in query.iter(&mut world) {
+///     let mut position = item.writes.remove(0);
+///     // `MutUntyped::into_inner` doesn't mark the component as changed
+///     position.set_changed();
+///     // SAFETY: the components have the types they were requested with
+///     let velocity = unsafe { item.reads[0].deref::<Velocity>() };
+///     let position = unsafe { position.into_inner().deref_mut::<Position>() };
+///     position.0 += velocity.0;
+/// }
+/// ```
+pub struct QueryBuilder<'w> {
+    world: &'w mut World,
+    reads: Vec<ComponentId>,
+    writes: Vec<ComponentId>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+}
+
+impl<'w> QueryBuilder<'w> {
+    /// Starts building a query for the components of `world`.
+    pub fn new(world: &'w mut World) -> Self {
+        Self {
+            world,
+            reads: Vec::new(),
+            writes: Vec::new(),
+            with: Vec::new(),
+            without: Vec::new(),
+        }
+    }
+
+    /// Fetches the component with `id` as a [`Ptr`], in [`DynamicQueryItem::reads`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a component of the world.
+    pub fn read(mut self, id: ComponentId) -> Self {
+        self.reads.push(self.validate(id));
+        self
+    }
+
+    /// Fetches the component with `id` as a [`MutUntyped`], in [`DynamicQueryItem::writes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a component of the world.
+    pub fn write(mut self, id: ComponentId) -> Self {
+        self.writes.push(self.validate(id));
+        self
+    }
+
+    /// Only matches entities having the component with `id`, without fetching it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a component of the world.
+    pub fn with(mut self, id: ComponentId) -> Self {
+        self.with.push(self.validate(id));
+        self
+    }
+
+    /// Only matches entities without the component with `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a component of the world.
+    pub fn without(mut self, id: ComponentId) -> Self {
+        self.without.push(self.validate(id));
+        self
+    }
+
+    /// Like [`QueryBuilder::read`], for the component of type `type_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not registered as a component of the world.
+    pub fn read_type_id(self, type_id: TypeId) -> Self {
+        let id = self.component_id(type_id);
+        self.read(id)
+    }
+
+    /// Like [`QueryBuilder::write`], for the component of type `type_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not registered as a component of the world.
+    pub fn write_type_id(self, type_id: TypeId) -> Self {
+        let id = self.component_id(type_id);
+        self.write(id)
+    }
+
+    /// Like [`QueryBuilder::with`], for the component of type `type_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not registered as a component of the world.
+    pub fn with_type_id(self, type_id: TypeId) -> Self {
+        let id = self.component_id(type_id);
+        self.with(id)
+    }
+
+    /// Like [`QueryBuilder::without`], for the component of type `type_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not registered as a component of the world.
+    pub fn without_type_id(self, type_id: TypeId) -> Self {
+        let id = self.component_id(type_id);
+        self.without(id)
+    }
+
+    /// Builds the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component is written more than once, or both read and written, as the
+    /// query would then alias a mutable borrow.
+    pub fn build(mut self) -> DynamicQuery {
+        for (i, &id) in self.writes.iter().enumerate() {
+            assert!(
+                !self.writes[..i].contains(&id) && !self.reads.contains(&id),
+                "The component {} is written more than once, or both read and written, by a DynamicQuery.",
+                self.name(id),
+            );
+        }
+
+        // Disabled entities are skipped, unless the query explicitly asks for them
+        let disabled = self.world.init_component::<Disabled>();
+        if !self
+            .reads
+            .iter()
+            .chain(&self.writes)
+            .chain(&self.with)
+            .chain(&self.without)
+            .any(|&id| id == disabled)
+        {
+            self.without.push(disabled);
+        }
+
+        DynamicQuery {
+            world_id: self.world.id(),
+            reads: self.reads,
+            writes: self.writes,
+            with: self.with,
+            without: self.without,
+        }
+    }
+
+    fn validate(&self, id: ComponentId) -> ComponentId {
+        assert!(
+            self.world.components().get_info(id).is_some(),
+            "{id:?} is not a component of this World."
+        );
+        id
+    }
+
+    fn component_id(&self, type_id: TypeId) -> ComponentId {
+        match self.world.components().get_id(type_id) {
+            Some(id) => id,
+            None => panic!("{type_id:?} is not registered as a component of this World."),
+        }
+    }
+
+    fn name(&self, id: ComponentId) -> &str {
+        // SAFETY: the ids are validated when added
+        unsafe { self.world.components().get_info_unchecked(id) }.name()
+    }
+}
+
+/// A query built at runtime by a [`QueryBuilder`].
+///
+/// Unlike a [`QueryState`](crate::query::QueryState), it doesn't cache the archetypes it
+/// matches, and always takes the world mutably, as it may write to components.
+#[derive(Debug, Clone)]
+pub struct DynamicQuery {
+    world_id: WorldId,
+    reads: Vec<ComponentId>,
+    writes: Vec<ComponentId>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+}
+
+/// The components of an entity matched by a [`DynamicQuery`].
+pub struct DynamicQueryItem<'w> {
+    /// The matched entity.
+    pub entity: Entity,
+    /// The components read by the query, in the order of [`QueryBuilder::read`].
+    pub reads: Vec<Ptr<'w>>,
+    /// The components written by the query, in the order of [`QueryBuilder::write`].
+    pub writes: Vec<MutUntyped<'w>>,
+}
+
+/// The components of an entity matched by a [`DynamicQuery`], as [`Reflect`] values.
+/// See [`DynamicQuery::iter_reflect`].
+#[cfg(feature = "bevy_reflect")]
+pub struct DynamicReflectItem<'w> {
+    /// The matched entity.
+    pub entity: Entity,
+    /// The components read by the query, in the order of [`QueryBuilder::read`].
+    pub reads: Vec<&'w dyn Reflect>,
+    /// The components written by the query, in the order of [`QueryBuilder::write`].
+    pub writes: Vec<Mut<'w, dyn Reflect>>,
+}
+
+impl DynamicQuery {
+    /// The components read by the query, in the order of [`DynamicQueryItem::reads`].
+    pub fn reads(&self) -> &[ComponentId] {
+        &self.reads
+    }
+
+    /// The components written by the query, in the order of [`DynamicQueryItem::writes`].
+    pub fn writes(&self) -> &[ComponentId] {
+        &self.writes
+    }
+
+    /// Iterates over the entities matching the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `world` is not the world the query was built for.
+    pub fn iter<'w>(
+        &'w self,
+        world: &'w mut World,
+    ) -> impl Iterator<Item = DynamicQueryItem<'w>> + 'w {
+        self.validate_world(world);
+        let world: &World = world;
+        world
+            .archetypes()
+            .iter()
+            .filter(|archetype| self.matches(archetype))
+            .flat_map(move |archetype| {
+                archetype
+                    .entities()
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, &entity)| {
+                        let location = EntityLocation {
+                            archetype_id: archetype.id(),
+                            index,
+                        };
+                        // SAFETY: the world is borrowed mutably, and each entity is only
+                        // visited once, so the mutable components are never aliased
+                        unsafe { self.fetch(world, entity, location) }
+                    })
+            })
+    }
+
+    /// Gets the components of `entity`, if it matches the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `world` is not the world the query was built for.
+    pub fn get<'w>(&self, world: &'w mut World, entity: Entity) -> Option<DynamicQueryItem<'w>> {
+        self.validate_world(world);
+        let location = world.entities().get(entity)?;
+        if !self.matches(&world.archetypes()[location.archetype_id]) {
+            return None;
+        }
+        // SAFETY: the world is borrowed mutably, for a single entity
+        Some(unsafe { self.fetch(world, entity, location) })
+    }
+
+    /// Iterates over the entities matching the query, with their components as [`Reflect`]
+    /// values, converted with the [`ReflectFromPtr`] of their type in `registry`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `world` is not the world the query was built for, or if a fetched component
+    /// is not registered in `registry`.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn iter_reflect<'w>(
+        &'w self,
+        world: &'w mut World,
+        registry: &TypeRegistry,
+    ) -> impl Iterator<Item = DynamicReflectItem<'w>> + 'w {
+        let from_ptr = |&id| {
+            let info = world.components().get_info(id).unwrap();
+            match info
+                .type_id()
+                .and_then(|type_id| registry.get_type_data::<ReflectFromPtr>(type_id))
+            {
+                Some(from_ptr) => from_ptr.clone(),
+                None => panic!(
+                    "The component {} is not registered as reflected in the TypeRegistry.",
+                    info.name()
+                ),
+            }
+        };
+        let reflect_reads: Vec<ReflectFromPtr> = self.reads.iter().map(from_ptr).collect();
+        let reflect_writes: Vec<ReflectFromPtr> = self.writes.iter().map(from_ptr).collect();
+        self.iter(world).map(move |item| DynamicReflectItem {
+            entity: item.entity,
+            reads: item
+                .reads
+                .into_iter()
+                .zip(&reflect_reads)
+                // SAFETY: the `ReflectFromPtr` was registered for the type of the component
+                .map(|(value, from_ptr)| unsafe { from_ptr.as_reflect_ptr(value) })
+                .collect(),
+            writes: item
+                .writes
+                .into_iter()
+                .zip(&reflect_writes)
+                .map(|(value, from_ptr)| Mut {
+                    // SAFETY: the `ReflectFromPtr` was registered for the type of the component
+                    value: unsafe { from_ptr.as_reflect_ptr_mut(value.value) },
+                    ticks: value.ticks,
+                })
+                .collect(),
+        })
+    }
+
+    fn validate_world(&self, world: &World) {
+        assert!(
+            world.id() == self.world_id,
+            "Attempted to use a DynamicQuery with a mismatched World. DynamicQueries can only be used with the World they were built for.",
+        );
+    }
+
+    fn matches(&self, archetype: &Archetype) -> bool {
+        self.reads
+            .iter()
+            .chain(&self.writes)
+            .chain(&self.with)
+            .all(|&id| archetype.contains(id))
+            && !self.without.iter().any(|&id| archetype.contains(id))
+    }
+
+    /// # Safety
+    ///
+    /// - `location` must be the location of `entity`, in an archetype matching the query
+    /// - the caller must have unique access to the written components of `entity`
+    unsafe fn fetch<'w>(
+        &self,
+        world: &'w World,
+        entity: Entity,
+        location: EntityLocation,
+    ) -> DynamicQueryItem<'w> {
+        let fetch = |id| {
+            get_component_and_ticks(world, id, entity, location)
+                .expect("a matched archetype contains the components of the query")
+        };
+        DynamicQueryItem {
+            entity,
+            reads: self.reads.iter().map(|&id| fetch(id).0).collect(),
+            writes: self
+                .writes
+                .iter()
+                .map(|&id| {
+                    let (value, ticks) = fetch(id);
+                    MutUntyped {
+                        value: value.assert_unique(),
+                        ticks: Ticks {
+                            component_ticks: ticks.deref_mut(),
+                            last_change_tick: world.last_change_tick(),
+                            change_tick: world.read_change_tick(),
+                        },
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use crate as bevy_ecs;
+    use crate::{
+        change_detection::DetectChanges, entity_disabling::Disabled, prelude::*,
+        query::QueryBuilder,
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(u32);
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn dynamic_query_reads_and_writes() {
+        let mut world = World::new();
+        let e1 = world.spawn((A(1), B(10))).id();
+        let e2 = world.spawn((A(2), B(20), C)).id();
+        let e3 = world.spawn(A(3)).id();
+        let a = world.init_component::<A>();
+        let c = world.init_component::<C>();
+
+        let query = QueryBuilder::new(&mut world)
+            .read(a)
+            .write_type_id(TypeId::of::<B>())
+            .without(c)
+            .build();
+        world.clear_trackers();
+        let mut entities = Vec::new();
+        for mut item in query.iter(&mut world) {
+            let mut b = item.writes.remove(0);
+            b.set_changed();
+            // SAFETY: the components are read and written with their types
+            let a = unsafe { item.reads[0].deref::<A>() }.0;
+            // SAFETY: as above
+            unsafe { b.into_inner().deref_mut::<B>() }.0 += a;
+            entities.push(item.entity);
+        }
+        assert_eq!(entities, [e1]);
+        assert_eq!(world.get::<B>(e1), Some(&B(11)));
+        assert_eq!(world.get::<B>(e2), Some(&B(20)));
+        let changed: Vec<Entity> = world
+            .query_filtered::<Entity, Changed<B>>()
+            .iter(&world)
+            .collect();
+        assert_eq!(changed, [e1]);
+
+        let query = QueryBuilder::new(&mut world).read(a).with(c).build();
+        assert!(query.get(&mut world, e1).is_none());
+        assert!(query.get(&mut world, e3).is_none());
+        assert_eq!(query.get(&mut world, e2).unwrap().entity, e2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dynamic_query_write_aliasing_panics() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        QueryBuilder::new(&mut world).read(a).write(a).build();
+    }
+
+    #[test]
+    fn dynamic_query_skips_disabled_entities() {
+        let mut world = World::new();
+        let enabled = world.spawn(A(1)).id();
+        let disabled = world.spawn((A(2), Disabled)).id();
+        let a = world.init_component::<A>();
+        let disabled_id = world.init_component::<Disabled>();
+
+        let query = QueryBuilder::new(&mut world).read(a).build();
+        let entities: Vec<Entity> = query.iter(&mut world).map(|item| item.entity).collect();
+        assert_eq!(entities, [enabled]);
+        assert!(query.get(&mut world, disabled).is_none());
+        assert_eq!(query.get(&mut world, enabled).unwrap().entity, enabled);
+
+        // Queries mentioning `Disabled` match disabled entities
+        let query = QueryBuilder::new(&mut world)
+            .read(a)
+            .with(disabled_id)
+            .build();
+        let entities: Vec<Entity> = query.iter(&mut world).map(|item| item.entity).collect();
+        assert_eq!(entities, [disabled]);
+        assert_eq!(query.get(&mut world, disabled).unwrap().entity, disabled);
+        assert!(query.get(&mut world, enabled).is_none());
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn dynamic_query_reflects_components() {
+        use bevy_reflect::{Reflect, TypeRegistry};
+
+        #[derive(Component, Reflect, Default)]
+        struct Health(f32);
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Health>();
+        let mut world = World::new();
+        let entity = world.spawn(Health(1.0)).id();
+
+        let query = QueryBuilder::new(&mut world)
+            .write_type_id(TypeId::of::<Health>())
+            .build();
+        for mut item in query.iter_reflect(&mut world, &registry) {
+            item.writes[0].apply(&Health(2.0));
+        }
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 2.0);
+    }
+}
@@ -1,10 +1,12 @@
 mod access;
+mod builder;
 mod fetch;
 mod filter;
 mod iter;
 mod state;
 
 pub use access::*;
+pub use builder::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
@@ -2,6 +2,7 @@ use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
     component::ComponentId,
     entity::Entity,
+    entity_disabling::Disabled,
     prelude::FromWorld,
     query::{Access, FilteredAccess, QueryCombinationIter, QueryIter, WorldQuery},
     storage::TableId,
@@ -33,6 +34,8 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    /// The [`Disabled`] component, unless the query mentions it.
+    pub(crate) skipped_component: Option<ComponentId>,
 }
 
 impl<Q: WorldQuery, F: ReadOnlyWorldQuery> std::fmt::Debug for QueryState<Q, F> {
@@ -109,6 +112,15 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        // Disabled entities are skipped, unless the query explicitly asks for them
+        let disabled = world.init_component::<Disabled>();
+        let skipped_component = if component_access.mentions(disabled) {
+            None
+        } else {
+            component_access.add_without(disabled);
+            Some(disabled)
+        };
+
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -116,6 +128,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             matched_archetype_ids: Vec::new(),
             fetch_state,
             filter_state,
+            skipped_component,
             component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
@@ -168,6 +181,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     pub fn new_archetype(&mut self, archetype: &Archetype) {
         if Q::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
+            && !matches!(self.skipped_component, Some(id) if archetype.contains(id))
         {
             Q::update_archetype_component_access(
                 &self.fetch_state,
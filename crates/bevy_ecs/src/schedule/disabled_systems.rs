@@ -0,0 +1,84 @@
+use crate::{
+    self as bevy_ecs,
+    schedule::{SystemLabel, SystemLabelId},
+};
+use bevy_ecs_macros::Resource;
+use bevy_utils::HashSet;
+
+/// When this resource is present in the [`World`](crate::world::World), the systems with one
+/// of its labels are skipped by the [`SystemStage`](crate::schedule::SystemStage)s, as if their
+/// run criteria returned [`ShouldRun::No`](crate::schedule::ShouldRun::No).
+///
+/// The other systems keep their ordering relative to the disabled systems.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::DisabledSystems;
+/// #[derive(SystemLabel)]
+/// struct DebugOverlay;
+///
+/// #[derive(Resource, Default)]
+/// struct Frames(u32);
+///
+/// fn draw_overlay(mut frames: ResMut<Frames>) {
+///     frames.0 += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Frames>();
+/// let mut stage = SystemStage::parallel().with_system(draw_overlay.label(DebugOverlay));
+///
+/// let mut disabled = DisabledSystems::default();
+/// disabled.disable(DebugOverlay);
+/// world.insert_resource(disabled);
+/// stage.run(&mut world);
+/// assert_eq!(world.resource::<Frames>().0, 0);
+///
+/// world.resource_mut::<DisabledSystems>().enable(DebugOverlay);
+/// stage.run(&mut world);
+/// assert_eq!(world.resource::<Frames>().0, 1);
+/// ```
+#[derive(Resource, Debug, Default, Clone)]
+pub struct DisabledSystems {
+    labels: HashSet<SystemLabelId>,
+}
+
+impl DisabledSystems {
+    /// Skips the systems with the label.
+    pub fn disable(&mut self, label: impl SystemLabel) -> &mut Self {
+        self.labels.insert(label.as_label());
+        self
+    }
+
+    /// Runs the systems with the label again, unless they have another disabled label.
+    pub fn enable(&mut self, label: impl SystemLabel) -> &mut Self {
+        self.labels.remove(&label.as_label());
+        self
+    }
+
+    /// Enables or disables the systems with the label.
+    pub fn set_enabled(&mut self, label: impl SystemLabel, enabled: bool) -> &mut Self {
+        if enabled {
+            self.enable(label)
+        } else {
+            self.disable(label)
+        }
+    }
+
+    /// Returns `true` if the systems with the label are skipped.
+    pub fn is_disabled(&self, label: impl SystemLabel) -> bool {
+        self.labels.contains(&label.as_label())
+    }
+
+    /// Returns `true` if any of the labels is disabled.
+    pub fn any_disabled(&self, labels: &[SystemLabelId]) -> bool {
+        !self.labels.is_empty() && labels.iter().any(|label| self.labels.contains(label))
+    }
+
+    /// Iterates over the disabled labels.
+    pub fn iter(&self) -> impl Iterator<Item = SystemLabelId> + '_ {
+        self.labels.iter().copied()
+    }
+}
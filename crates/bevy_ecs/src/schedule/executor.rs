@@ -29,7 +29,7 @@ impl ParallelSystemExecutor for SingleThreadedExecutor {
                 #[cfg(feature = "trace")]
                 let _system_span =
                     bevy_utils::tracing::info_span!("system", name = &*system.name()).entered();
-                system.system_mut().run((), world);
+                system.run_timed(|system| system.run((), world));
             }
         }
     }
@@ -204,7 +204,6 @@ impl ParallelExecutor {
             // Spawn the system task.
             self.should_run.insert(index);
             let finish_sender = self.finish_sender.clone();
-            let system = system.system_mut();
             #[cfg(feature = "trace")] // NB: outside the task to get the TLS current span
             let system_span = bevy_utils::tracing::info_span!("system", name = &*system.name());
             #[cfg(feature = "trace")]
@@ -215,7 +214,7 @@ impl ParallelExecutor {
                 #[cfg(feature = "trace")]
                 let _system_guard = system_span.enter();
                 // SAFETY: the executor prevents two systems with conflicting access from running simultaneously.
-                unsafe { system.run_unsafe((), world) };
+                system.run_timed(|system| unsafe { system.run_unsafe((), world) });
             };
 
             if can_start {
@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use crate::{
+    schedule::{
+        ambiguity_detection::find_ambiguities, GraphNode, Schedule, SystemContainer, SystemStage,
+    },
+    world::World,
+};
+
+impl SystemStage {
+    /// Returns the graph of the systems of this stage in the [DOT] format of Graphviz, to
+    /// understand in which order they run.
+    ///
+    /// Each part of the stage is drawn as a cluster of systems, showing their labels, with an
+    /// arrow from each system to the systems that must run after it. Systems with an ambiguous
+    /// execution order are linked by dashed red lines, listing the components they conflict on.
+    ///
+    /// The graph is only complete once the stage has been run on `world`, which initializes and
+    /// orders its systems.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn first() {}
+    /// fn second() {}
+    ///
+    /// let mut world = World::new();
+    /// let mut stage = SystemStage::parallel()
+    ///     .with_system(first)
+    ///     .with_system(second.after(first));
+    /// stage.run(&mut world);
+    /// let dot = stage.dot_graph(&world);
+    /// assert!(dot.starts_with("digraph {"));
+    /// // Save it to `stage.dot`, and render it with `dot -Tsvg stage.dot > stage.svg`
+    /// println!("{dot}");
+    /// ```
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn dot_graph(&self, world: &World) -> String {
+        let mut dot = String::from("digraph {\n    compound=true;\n");
+        self.write_dot(&mut dot, world, "s");
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the clusters and edges of this stage, with node ids starting with `prefix`.
+    fn write_dot(&self, dot: &mut String, world: &World, prefix: &str) {
+        let segments = [
+            (
+                "at_start",
+                "Exclusive systems at start of stage",
+                &self.exclusive_at_start,
+            ),
+            ("parallel", "Parallel systems", &self.parallel),
+            (
+                "before_commands",
+                "Exclusive systems before commands of stage",
+                &self.exclusive_before_commands,
+            ),
+            (
+                "at_end",
+                "Exclusive systems at end of stage",
+                &self.exclusive_at_end,
+            ),
+        ];
+        for (segment, description, systems) in segments {
+            if systems.is_empty() {
+                continue;
+            }
+            let id = |index: usize| format!("{prefix}_{segment}_{index}");
+            writeln!(dot, "    subgraph cluster_{prefix}_{segment} {{").unwrap();
+            writeln!(dot, "        label={description:?};").unwrap();
+            for (index, system) in systems.iter().enumerate() {
+                writeln!(
+                    dot,
+                    "        {} [label={:?}, shape=box];",
+                    id(index),
+                    system_label(system)
+                )
+                .unwrap();
+            }
+            dot.push_str("    }\n");
+
+            for (index, system) in systems.iter().enumerate() {
+                for &dependency in system.dependencies() {
+                    writeln!(dot, "    {} -> {};", id(dependency), id(index)).unwrap();
+                }
+            }
+            for (a, b, conflicts) in find_ambiguities(systems) {
+                let conflicts: Vec<_> = conflicts
+                    .iter()
+                    .map(|&id| world.components().get_info(id).unwrap().name())
+                    .collect();
+                writeln!(
+                    dot,
+                    "    {} -> {} [dir=none, style=dashed, color=red, label={:?}];",
+                    id(a),
+                    id(b),
+                    conflicts.join("\n")
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+fn system_label(system: &SystemContainer) -> String {
+    let mut label = system.name().into_owned();
+    // Function systems are labeled with their own name
+    let labels: Vec<_> = system
+        .labels()
+        .iter()
+        .map(|label| format!("{label:?}"))
+        .filter(|system_label| *system_label != label)
+        .collect();
+    if !labels.is_empty() {
+        write!(label, "\nlabels: {}", labels.join(", ")).unwrap();
+    }
+    label
+}
+
+impl Schedule {
+    /// Returns the graph of the systems of this schedule in the [DOT] format of Graphviz, with
+    /// a cluster for each of its [`SystemStage`]s. See [`SystemStage::dot_graph`].
+    ///
+    /// Stages of other types are drawn as a single node.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn dot_graph(&self, world: &World) -> String {
+        let mut dot = String::from("digraph {\n    compound=true;\n");
+        for (index, (label, stage)) in self.iter_stages().enumerate() {
+            let prefix = format!("s{index}");
+            match stage.downcast_ref::<SystemStage>() {
+                Some(stage) => {
+                    writeln!(dot, "    subgraph cluster_{prefix} {{").unwrap();
+                    writeln!(dot, "        label={:?};", format!("{label:?}")).unwrap();
+                    stage.write_dot(&mut dot, world, &prefix);
+                    dot.push_str("    }\n");
+                }
+                None => {
+                    writeln!(dot, "    {prefix} [label={:?}];", format!("{label:?}")).unwrap();
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    fn first() {}
+    fn second() {}
+    fn write_a(_query: Query<&mut A>) {}
+    fn also_write_a(_query: Query<&mut A>) {}
+
+    #[test]
+    fn dot_graph_of_stage() {
+        let mut world = World::new();
+        let mut stage = SystemStage::parallel()
+            .with_system(first.label("first"))
+            .with_system(second.after("first"))
+            .with_system(write_a)
+            .with_system(also_write_a);
+        stage.run(&mut world);
+
+        let dot = stage.dot_graph(&world);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("cluster_s_parallel"));
+        let node = |name: &str| {
+            let line = dot
+                .lines()
+                .find(|line| line.contains(&format!("::{name}")))
+                .unwrap();
+            line.split_whitespace().next().unwrap().to_string()
+        };
+        assert!(dot.contains("first\\nlabels: first\""));
+        assert!(dot.contains(&format!("{} -> {};", node("first"), node("second"))));
+        let ambiguity = dot
+            .lines()
+            .find(|line| line.contains("style=dashed"))
+            .unwrap();
+        assert!(ambiguity.contains(&node("write_a")));
+        assert!(ambiguity.contains(&node("also_write_a")));
+        assert!(ambiguity.contains("graph_dot::tests::A"));
+    }
+}
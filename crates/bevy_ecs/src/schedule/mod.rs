@@ -4,17 +4,21 @@
 //!  [`Stage`], which then lives within a [`Schedule`].
 
 mod ambiguity_detection;
+mod disabled_systems;
 mod executor;
 mod executor_parallel;
+mod graph_dot;
 pub mod graph_utils;
 mod label;
 mod run_criteria;
 mod stage;
 mod state;
+mod stepping;
 mod system_container;
 mod system_descriptor;
 mod system_set;
 
+pub use disabled_systems::*;
 pub use executor::*;
 pub use executor_parallel::*;
 pub use graph_utils::GraphNode;
@@ -22,13 +26,14 @@ pub use label::*;
 pub use run_criteria::*;
 pub use stage::*;
 pub use state::*;
+pub use stepping::*;
 pub use system_container::*;
 pub use system_descriptor::*;
 pub use system_set::*;
 
 use std::fmt::Debug;
 
-use crate::{system::IntoSystem, world::World};
+use crate::{change_detection::DetectChanges, system::IntoSystem, world::World};
 use bevy_utils::HashMap;
 
 /// A container of [`Stage`]s set to be run in a linear order.
@@ -366,6 +371,9 @@ impl Schedule {
         for label in &self.stage_order {
             #[cfg(feature = "trace")]
             let _stage_span = bevy_utils::tracing::info_span!("stage", name = ?label).entered();
+            if let Some(mut stepping) = world.get_resource_mut::<Stepping>() {
+                stepping.bypass_change_detection().set_current_stage(*label);
+            }
             let stage = self.stages.get_mut(label).unwrap();
             stage.run(world);
         }
@@ -5,17 +5,19 @@ use crate::{
     prelude::IntoSystem,
     schedule::{
         graph_utils::{self, DependencyGraphError},
-        BoxedRunCriteria, DuplicateLabelStrategy, ExclusiveInsertionPoint, GraphNode,
-        ParallelExecutor, ParallelSystemExecutor, RunCriteriaContainer, RunCriteriaDescriptor,
-        RunCriteriaDescriptorOrLabel, RunCriteriaInner, RunCriteriaLabelId, ShouldRun,
-        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemLabelId, SystemSet,
+        BoxedRunCriteria, DisabledSystems, DuplicateLabelStrategy, ExclusiveInsertionPoint,
+        GraphNode, ParallelExecutor, ParallelSystemExecutor, RunCriteriaContainer,
+        RunCriteriaDescriptor, RunCriteriaDescriptorOrLabel, RunCriteriaInner, RunCriteriaLabelId,
+        ShouldRun, SingleThreadedExecutor, Stepping, SystemContainer, SystemDescriptor,
+        SystemLabelId, SystemSet,
     },
     world::{World, WorldId},
 };
 use bevy_ecs_macros::Resource;
-use bevy_utils::{tracing::warn, HashMap, HashSet};
+use bevy_utils::{tracing::warn, Duration, HashMap, HashSet};
 use core::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
+use std::borrow::Cow;
 
 use super::{IntoSystemDescriptor, Schedule};
 
@@ -62,6 +64,40 @@ impl_downcast!(Stage);
 #[derive(Resource, Default)]
 pub struct ReportExecutionOrderAmbiguities;
 
+/// When this resource is present in the [`World`], [`SystemStage`]s measure how long each of
+/// their systems runs, and add it to the time of the system here.
+///
+/// The times add up until they are [cleared](SystemExecutionTimes::clear), usually once per
+/// frame by the `SystemExecutionTimeDiagnosticsPlugin` of `bevy_diagnostic`.
+/// Run criteria and the application of commands aren't included.
+#[derive(Resource, Debug, Default)]
+pub struct SystemExecutionTimes {
+    times: HashMap<Cow<'static, str>, Duration>,
+}
+
+impl SystemExecutionTimes {
+    /// How long the system named `name` ran since the times were last cleared, or `None` if it
+    /// didn't run.
+    pub fn get(&self, name: &str) -> Option<Duration> {
+        self.times.get(name).copied()
+    }
+
+    /// Iterates over the names of the systems that ran since the times were last cleared, and
+    /// how long they ran.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.times.iter().map(|(name, &time)| (&**name, time))
+    }
+
+    /// Forgets all times.
+    pub fn clear(&mut self) {
+        self.times.clear();
+    }
+
+    fn add(&mut self, name: Cow<'static, str>, time: Duration) {
+        *self.times.entry(name).or_default() += time;
+    }
+}
+
 /// Stores and executes systems. Execution order is not defined unless explicitly specified;
 /// see `SystemDescriptor` documentation.
 pub struct SystemStage {
@@ -511,6 +547,49 @@ impl SystemStage {
         }
     }
 
+    /// Returns the systems [`Stepping`] allows to run, if it steps through this stage. They are
+    /// indexed in the order the systems run in with a single-threaded executor.
+    fn stepped_systems(&self, world: &mut World) -> Option<std::ops::Range<usize>> {
+        let mut stepping = world.get_resource_mut::<Stepping>()?;
+        if !stepping.is_stepping_current_stage() {
+            return None;
+        }
+        let systems: Vec<&SystemContainer> = self
+            .exclusive_at_start
+            .iter()
+            .chain(&self.parallel)
+            .chain(&self.exclusive_before_commands)
+            .chain(&self.exclusive_at_end)
+            .collect();
+        Some(stepping.systems_to_run(systems.len(), |index| systems[index].name()))
+    }
+
+    /// Returns the indices of the systems with a label disabled by [`DisabledSystems`], in the
+    /// same order as [`Self::stepped_systems`].
+    fn disabled_systems(&self, world: &World) -> Vec<usize> {
+        let disabled = match world.get_resource::<DisabledSystems>() {
+            Some(disabled) => disabled,
+            None => return Vec::new(),
+        };
+        self.exclusive_at_start
+            .iter()
+            .chain(&self.parallel)
+            .chain(&self.exclusive_before_commands)
+            .chain(&self.exclusive_at_end)
+            .enumerate()
+            .filter(|(_, container)| disabled.any_disabled(container.labels()))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn systems_mut(&mut self) -> impl Iterator<Item = &mut SystemContainer> {
+        self.exclusive_at_start
+            .iter_mut()
+            .chain(&mut self.parallel)
+            .chain(&mut self.exclusive_before_commands)
+            .chain(&mut self.exclusive_at_end)
+    }
+
     /// All system and component change ticks are scanned once the world counter has incremented
     /// at least [`CHECK_TICK_THRESHOLD`](crate::change_detection::CHECK_TICK_THRESHOLD)
     /// times since the previous `check_tick` scan.
@@ -718,17 +797,29 @@ impl Stage for SystemStage {
             self.executor_modified = false;
         }
 
+        let measure_run_times = world.contains_resource::<SystemExecutionTimes>();
+        for container in self.systems_mut() {
+            container.measure_run_time = measure_run_times;
+            container.run_time = None;
+        }
+
+        // The systems allowed to run by `Stepping`, see `Self::stepped_systems`
+        let mut stepped_systems = None;
+        let disabled_systems = self.disabled_systems(world);
         let mut run_stage_loop = true;
         while run_stage_loop {
             let should_run = self.stage_run_criteria.should_run(world);
             match should_run {
-                ShouldRun::No => return,
+                ShouldRun::No => break,
                 ShouldRun::NoAndCheckAgain => continue,
                 ShouldRun::YesAndCheckAgain => (),
                 ShouldRun::Yes => {
                     run_stage_loop = false;
                 }
             };
+            if stepped_systems.is_none() {
+                stepped_systems = Some(self.stepped_systems(world));
+            }
 
             // Evaluate system run criteria.
             for index in 0..self.run_criteria.len() {
@@ -769,9 +860,21 @@ impl Stage for SystemStage {
                     )
                 }
 
+                let enabled = |index: usize| {
+                    !disabled_systems.contains(&index)
+                        && match &stepped_systems {
+                            Some(Some(systems)) => systems.contains(&index),
+                            _ => true,
+                        }
+                };
+                let mut index = 0;
+
                 // Run systems that want to be at the start of stage.
                 for container in &mut self.exclusive_at_start {
-                    if should_run(container, &self.run_criteria, default_should_run) {
+                    index += 1;
+                    if enabled(index - 1)
+                        && should_run(container, &self.run_criteria, default_should_run)
+                    {
                         {
                             #[cfg(feature = "trace")]
                             let _system_span = bevy_utils::tracing::info_span!(
@@ -779,7 +882,7 @@ impl Stage for SystemStage {
                                 name = &*container.name()
                             )
                             .entered();
-                            container.system_mut().run((), world);
+                            container.run_timed(|system| system.run((), world));
                         }
                         {
                             #[cfg(feature = "trace")]
@@ -796,14 +899,18 @@ impl Stage for SystemStage {
                 // Run parallel systems using the executor.
                 // TODO: hard dependencies, nested sets, whatever... should be evaluated here.
                 for container in &mut self.parallel {
-                    container.should_run =
-                        should_run(container, &self.run_criteria, default_should_run);
+                    index += 1;
+                    container.should_run = enabled(index - 1)
+                        && should_run(container, &self.run_criteria, default_should_run);
                 }
                 self.executor.run_systems(&mut self.parallel, world);
 
                 // Run systems that want to be between parallel systems and their command buffers.
                 for container in &mut self.exclusive_before_commands {
-                    if should_run(container, &self.run_criteria, default_should_run) {
+                    index += 1;
+                    if enabled(index - 1)
+                        && should_run(container, &self.run_criteria, default_should_run)
+                    {
                         {
                             #[cfg(feature = "trace")]
                             let _system_span = bevy_utils::tracing::info_span!(
@@ -811,7 +918,7 @@ impl Stage for SystemStage {
                                 name = &*container.name()
                             )
                             .entered();
-                            container.system_mut().run((), world);
+                            container.run_timed(|system| system.run((), world));
                         }
                         {
                             #[cfg(feature = "trace")]
@@ -842,7 +949,10 @@ impl Stage for SystemStage {
 
                 // Run systems that want to be at the end of stage.
                 for container in &mut self.exclusive_at_end {
-                    if should_run(container, &self.run_criteria, default_should_run) {
+                    index += 1;
+                    if enabled(index - 1)
+                        && should_run(container, &self.run_criteria, default_should_run)
+                    {
                         {
                             #[cfg(feature = "trace")]
                             let _system_span = bevy_utils::tracing::info_span!(
@@ -850,7 +960,7 @@ impl Stage for SystemStage {
                                 name = &*container.name()
                             )
                             .entered();
-                            container.system_mut().run((), world);
+                            container.run_timed(|system| system.run((), world));
                         }
                         {
                             #[cfg(feature = "trace")]
@@ -864,6 +974,11 @@ impl Stage for SystemStage {
                     }
                 }
 
+                // A stepped system only runs once per step
+                if let Some(Some(systems)) = &mut stepped_systems {
+                    *systems = 0..0;
+                }
+
                 // Check for old component and system change ticks
                 self.check_change_ticks(world);
 
@@ -905,6 +1020,14 @@ impl Stage for SystemStage {
                 default_should_run = ShouldRun::No;
             }
         }
+
+        if let Some(mut execution_times) = world.get_resource_mut::<SystemExecutionTimes>() {
+            for container in self.systems_mut() {
+                if let Some(run_time) = container.run_time.take() {
+                    execution_times.add(container.name(), run_time);
+                }
+            }
+        }
     }
 }
 
@@ -915,7 +1038,8 @@ mod tests {
     use crate::{
         schedule::{
             IntoSystemDescriptor, RunCriteria, RunCriteriaDescriptorCoercion, ShouldRun,
-            SingleThreadedExecutor, Stage, SystemLabel, SystemSet, SystemStage,
+            SingleThreadedExecutor, Stage, SystemExecutionTimes, SystemLabel, SystemSet,
+            SystemStage,
         },
         system::{In, Local, Query, ResMut},
         world::World,
@@ -1647,4 +1771,40 @@ mod tests {
         stage_spawn.run(&mut world);
         assert_eq!(world.resource::<EntityCount>().0, vec![0, 2]);
     }
+
+    #[test]
+    fn system_execution_times() {
+        fn sleep() {
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        fn sleep_exclusive(_world: &mut World) {
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        fn skipped() {}
+
+        let mut world = World::new();
+        let mut stage = SystemStage::parallel()
+            .with_system(sleep)
+            .with_system(sleep_exclusive.at_end())
+            .with_system(skipped.with_run_criteria(|| ShouldRun::No));
+        // Not measured without the resource
+        stage.run(&mut world);
+        world.init_resource::<SystemExecutionTimes>();
+        stage.run(&mut world);
+        stage.run(&mut world);
+
+        let times = world.resource::<SystemExecutionTimes>();
+        let time = |name: &str| {
+            times
+                .iter()
+                .find(|(system, _)| system.ends_with(name))
+                .map(|(_, time)| time)
+        };
+        assert!(time("::sleep").unwrap() >= std::time::Duration::from_millis(4));
+        assert!(time("::sleep_exclusive").unwrap() >= std::time::Duration::from_millis(4));
+        assert_eq!(time("::skipped"), None);
+
+        world.resource_mut::<SystemExecutionTimes>().clear();
+        assert_eq!(world.resource::<SystemExecutionTimes>().iter().count(), 0);
+    }
 }
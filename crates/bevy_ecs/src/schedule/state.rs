@@ -23,6 +23,45 @@ impl<T> StateData for T where T: Send + Sync + Clone + Eq + Debug + Hash + 'stat
 /// * Pop removes the current state, and unpauses the last paused state
 /// * Set replaces the active state with a new one
 /// * Replace unwinds the state stack, and replaces the entire stack with a single new state
+///
+/// ### Sub-states
+///
+/// A state can nest sub-states, such as `AppState::InGame(GameState::Paused)`. Systems can run
+/// for a whole group of states with [`State::on_enter_matching`], [`State::on_exit_matching`]
+/// and [`State::on_update_matching`], so a pause menu pushed over the game doesn't exit it:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum GameState {
+///     Running,
+///     Paused,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum AppState {
+///     Menu,
+///     InGame(GameState),
+/// }
+///
+/// fn in_game(state: &AppState) -> bool {
+///     matches!(state, AppState::InGame(_))
+/// }
+///
+/// fn spawn_level() {}
+/// fn show_pause_menu() {}
+///
+/// let mut stage = SystemStage::parallel()
+///     .with_system_set(State::<AppState>::get_driver())
+///     // Runs once when the game starts, not when it is resumed after a pause
+///     .with_system_set(State::on_enter_matching_set(in_game).with_system(spawn_level))
+///     .with_system_set(
+///         State::on_enter_set(AppState::InGame(GameState::Paused)).with_system(show_pause_menu),
+///     );
+/// # let mut world = World::new();
+/// # world.insert_resource(State::new(AppState::Menu));
+/// # stage.run(&mut world);
+/// ```
 #[derive(Debug, Resource)]
 pub struct State<T: StateData> {
     transition: Option<StateTransition<T>>,
@@ -180,6 +219,61 @@ where
         .after(DriverLabel::of::<T>())
     }
 
+    /// Runs while the active state matches `matches`, which can select a group of sub-states,
+    /// e.g. `|state| matches!(state, AppState::InGame(_))`.
+    pub fn on_update_matching(
+        matches: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> RunCriteriaDescriptor {
+        (move |state: Res<State<T>>| {
+            matches(state.stack.last().unwrap()) && state.transition.is_none()
+        })
+        .pipe(should_run_adapter::<T>)
+        .after(DriverLabel::of::<T>())
+    }
+
+    /// Runs when entering a state matching `matches` from a state that doesn't, so that moving
+    /// between sub-states of the group, e.g. by pushing `AppState::InGame(GameState::Paused)`
+    /// over `AppState::InGame(GameState::Running)`, doesn't enter the group again.
+    pub fn on_enter_matching(
+        matches: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> RunCriteriaDescriptor {
+        (move |state: Res<State<T>>| {
+            state
+                .transition
+                .as_ref()
+                .map_or(false, |transition| match transition {
+                    StateTransition::Entering(leaving, entering) => {
+                        matches(entering) && !matches(leaving)
+                    }
+                    StateTransition::Startup => matches(state.stack.last().unwrap()),
+                    _ => false,
+                })
+        })
+        .pipe(should_run_adapter::<T>)
+        .after(DriverLabel::of::<T>())
+    }
+
+    /// Runs when exiting a state matching `matches` for a state that doesn't.
+    /// See [`State::on_enter_matching`].
+    pub fn on_exit_matching(
+        matches: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> RunCriteriaDescriptor {
+        (move |state: Res<State<T>>| {
+            state
+                .transition
+                .as_ref()
+                .map_or(false, |transition| match transition {
+                    StateTransition::ExitingToResume(exiting, entering)
+                    | StateTransition::ExitingFull(exiting, entering) => {
+                        matches(exiting) && !matches(entering)
+                    }
+                    _ => false,
+                })
+        })
+        .pipe(should_run_adapter::<T>)
+        .after(DriverLabel::of::<T>())
+    }
+
     pub fn on_update_set(s: T) -> SystemSet {
         SystemSet::new().with_run_criteria(Self::on_update(s))
     }
@@ -204,6 +298,22 @@ where
         SystemSet::new().with_run_criteria(Self::on_resume(s))
     }
 
+    pub fn on_update_matching_set(
+        matches: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> SystemSet {
+        SystemSet::new().with_run_criteria(Self::on_update_matching(matches))
+    }
+
+    pub fn on_enter_matching_set(
+        matches: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> SystemSet {
+        SystemSet::new().with_run_criteria(Self::on_enter_matching(matches))
+    }
+
+    pub fn on_exit_matching_set(matches: impl Fn(&T) -> bool + Send + Sync + 'static) -> SystemSet {
+        SystemSet::new().with_run_criteria(Self::on_exit_matching(matches))
+    }
+
     /// Creates a driver set for the State.
     ///
     /// Important note: this set must be inserted **before** all other state-dependant sets to work
@@ -754,4 +864,76 @@ mod test {
             &LoadState::Finish
         );
     }
+
+    #[test]
+    fn sub_states() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum GameState {
+            Running,
+            Paused,
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum AppState {
+            Menu,
+            InGame(GameState),
+        }
+
+        #[derive(Resource, Default)]
+        struct NameList(Vec<&'static str>);
+
+        fn in_game(state: &AppState) -> bool {
+            matches!(state, AppState::InGame(_))
+        }
+
+        let mut world = World::new();
+        world.init_resource::<NameList>();
+        world.insert_resource(State::new(AppState::Menu));
+        let mut stage = SystemStage::parallel()
+            .with_system_set(State::<AppState>::get_driver())
+            .with_system_set(
+                State::on_enter_matching_set(in_game)
+                    .with_system(|mut r: ResMut<NameList>| r.0.push("enter game")),
+            )
+            .with_system_set(
+                State::on_exit_matching_set(in_game)
+                    .with_system(|mut r: ResMut<NameList>| r.0.push("exit game")),
+            )
+            .with_system_set(
+                State::on_update_matching_set(in_game)
+                    .with_system(|mut r: ResMut<NameList>| r.0.push("update game")),
+            )
+            .with_system_set(
+                State::on_enter_set(AppState::InGame(GameState::Paused))
+                    .with_system(|mut r: ResMut<NameList>| r.0.push("enter paused")),
+            );
+        let mut run = |world: &mut World, change: fn(&mut State<AppState>)| {
+            change(&mut world.resource_mut::<State<AppState>>());
+            stage.run(world);
+            std::mem::take(&mut world.resource_mut::<NameList>().0)
+        };
+
+        assert!(run(&mut world, |_| {}).is_empty());
+        assert_eq!(
+            run(&mut world, |state| state
+                .set(AppState::InGame(GameState::Running))
+                .unwrap()),
+            ["enter game", "update game"]
+        );
+        // Pausing and resuming moves between sub-states without leaving the game
+        assert_eq!(
+            run(&mut world, |state| state
+                .push(AppState::InGame(GameState::Paused))
+                .unwrap()),
+            ["enter paused", "update game"]
+        );
+        assert_eq!(
+            run(&mut world, |state| state.pop().unwrap()),
+            ["update game"]
+        );
+        assert_eq!(
+            run(&mut world, |state| state.set(AppState::Menu).unwrap()),
+            ["exit game"]
+        );
+    }
 }
@@ -0,0 +1,296 @@
+use std::{borrow::Cow, ops::Range};
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{StageLabel, StageLabelId},
+};
+use bevy_ecs_macros::Resource;
+
+/// When this resource is present in the [`World`](crate::world::World), the systems of the
+/// [`SystemStage`](crate::schedule::SystemStage)s added with [`Stepping::add_stage`] can be
+/// paused and run one at a time, to debug bugs depending on the order of systems.
+///
+/// The systems of a stage are stepped through in the order they run in a single-threaded
+/// stage: exclusive systems at the start, parallel systems, exclusive systems before
+/// commands, then exclusive systems at the end. Stages that weren't added keep running
+/// every frame, so the systems controlling the stepping should be in one of those.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::Stepping;
+/// #[derive(StageLabel)]
+/// struct Update;
+///
+/// fn first() {}
+/// fn second() {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_stage(
+///     Update,
+///     SystemStage::single_threaded()
+///         .with_system(first)
+///         .with_system(second.after(first)),
+/// );
+///
+/// let mut stepping = Stepping::default();
+/// stepping.add_stage(Update).enable();
+/// world.insert_resource(stepping);
+///
+/// // While waiting for a step, no system of the stage runs
+/// schedule.run(&mut world);
+/// assert!(world.resource::<Stepping>().next_system().unwrap().ends_with("first"));
+///
+/// world.resource_mut::<Stepping>().step_system();
+/// schedule.run(&mut world);
+/// assert!(world.resource::<Stepping>().next_system().unwrap().ends_with("second"));
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct Stepping {
+    enabled: bool,
+    stages: Vec<StageLabelId>,
+    action: Action,
+    /// The stage and the index of the next system to run, or `None` for the first system of the
+    /// next stage run.
+    cursor: Option<(StageLabelId, usize)>,
+    next_system: Option<Cow<'static, str>>,
+    /// The stage being run, set by [`Schedule`](crate::schedule::Schedule).
+    current_stage: Option<StageLabelId>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    #[default]
+    Wait,
+    StepSystem,
+    StepStage,
+}
+
+impl Stepping {
+    /// Makes the systems of the stage `label` steppable.
+    pub fn add_stage(&mut self, label: impl StageLabel) -> &mut Self {
+        let label = label.as_label();
+        if !self.stages.contains(&label) {
+            self.stages.push(label);
+        }
+        self
+    }
+
+    /// Lets the systems of the stage `label` run every frame again.
+    pub fn remove_stage(&mut self, label: impl StageLabel) -> &mut Self {
+        let label = label.as_label();
+        self.stages.retain(|&stage| stage != label);
+        if matches!(self.cursor, Some((stage, _)) if stage == label) {
+            self.reset_cursor();
+        }
+        self
+    }
+
+    /// Pauses the systems of the steppable stages, until they are stepped through.
+    pub fn enable(&mut self) -> &mut Self {
+        self.enabled = true;
+        self
+    }
+
+    /// Lets the systems of all stages run every frame again, and forgets where the stepping was.
+    pub fn disable(&mut self) -> &mut Self {
+        self.enabled = false;
+        self.action = Action::Wait;
+        self.reset_cursor();
+        self
+    }
+
+    /// Whether the systems of the steppable stages are paused.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Runs the next system the next time its stage runs.
+    pub fn step_system(&mut self) -> &mut Self {
+        self.action = Action::StepSystem;
+        self
+    }
+
+    /// Runs the next system and the rest of its stage the next time the stage runs.
+    pub fn step_stage(&mut self) -> &mut Self {
+        self.action = Action::StepStage;
+        self
+    }
+
+    /// The name of the system that runs on the next step, or `None` if it is the first system of
+    /// the next steppable stage, which isn't known until that stage runs.
+    pub fn next_system(&self) -> Option<&str> {
+        self.next_system.as_deref()
+    }
+
+    /// The stage of the system that runs on the next step, if known. See
+    /// [`Stepping::next_system`].
+    pub fn next_stage(&self) -> Option<StageLabelId> {
+        self.cursor.map(|(stage, _)| stage)
+    }
+
+    pub(crate) fn set_current_stage(&mut self, label: StageLabelId) {
+        self.current_stage = Some(label);
+    }
+
+    /// Whether the stage being run has its systems stepped through.
+    pub(crate) fn is_stepping_current_stage(&self) -> bool {
+        match self.current_stage {
+            Some(stage) => self.enabled && self.stages.contains(&stage),
+            None => false,
+        }
+    }
+
+    /// Returns the indices of the systems of the stage being run that may run, and moves the
+    /// cursor past them.
+    pub(crate) fn systems_to_run(
+        &mut self,
+        system_count: usize,
+        system_name: impl Fn(usize) -> Cow<'static, str>,
+    ) -> Range<usize> {
+        let stage = match self.current_stage {
+            Some(stage) => stage,
+            None => return 0..system_count,
+        };
+        let index = match self.cursor {
+            Some((cursor_stage, index)) if cursor_stage == stage => index,
+            Some(_) => return 0..0,
+            None => 0,
+        };
+        if index >= system_count {
+            // The stage has no systems, or lost some since the cursor was set
+            self.reset_cursor();
+            return 0..0;
+        }
+
+        let systems = match self.action {
+            Action::Wait => index..index,
+            Action::StepSystem => index..index + 1,
+            Action::StepStage => index..system_count,
+        };
+        self.action = Action::Wait;
+        if systems.end < system_count {
+            self.cursor = Some((stage, systems.end));
+            self.next_system = Some(system_name(systems.end));
+        } else {
+            self.reset_cursor();
+        }
+        systems
+    }
+
+    fn reset_cursor(&mut self) {
+        self.cursor = None;
+        self.next_system = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{prelude::*, schedule::Stepping};
+
+    #[derive(StageLabel)]
+    enum TestStage {
+        Control,
+        A,
+        B,
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn control(mut log: ResMut<Log>) {
+        log.0.push("control");
+    }
+    fn a0(mut log: ResMut<Log>) {
+        log.0.push("a0");
+    }
+    fn a1(mut log: ResMut<Log>) {
+        log.0.push("a1");
+    }
+    fn a2(mut log: ResMut<Log>) {
+        log.0.push("a2");
+    }
+    fn b1(mut log: ResMut<Log>) {
+        log.0.push("b1");
+    }
+
+    #[test]
+    fn step_through_systems_and_stages() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::default();
+        schedule
+            .add_stage(TestStage::Control, SystemStage::single(control))
+            .add_stage(
+                TestStage::A,
+                SystemStage::single_threaded()
+                    .with_system(a1)
+                    .with_system(a2.after(a1))
+                    .with_system(a0.at_start()),
+            )
+            .add_stage(TestStage::B, SystemStage::parallel().with_system(b1));
+        let mut stepping = Stepping::default();
+        stepping
+            .add_stage(TestStage::A)
+            .add_stage(TestStage::B)
+            .enable();
+        world.insert_resource(stepping);
+        let mut run = |world: &mut World, step: fn(&mut Stepping)| {
+            step(&mut world.resource_mut::<Stepping>());
+            schedule.run(world);
+            std::mem::take(&mut world.resource_mut::<Log>().0)
+        };
+        let next = |world: &World| {
+            let stepping = world.resource::<Stepping>();
+            (
+                stepping.next_stage(),
+                stepping
+                    .next_system()
+                    .map(|name| name.rsplit("::").next().unwrap().to_string()),
+            )
+        };
+
+        assert_eq!(run(&mut world, |_| {}), ["control"]);
+        assert_eq!(
+            next(&world),
+            (Some(TestStage::A.as_label()), Some("a0".into()))
+        );
+
+        let step_system: fn(&mut Stepping) = |stepping| {
+            stepping.step_system();
+        };
+        assert_eq!(run(&mut world, step_system), ["control", "a0"]);
+        assert_eq!(run(&mut world, step_system), ["control", "a1"]);
+        assert_eq!(
+            next(&world),
+            (Some(TestStage::A.as_label()), Some("a2".into()))
+        );
+        // The cursor moves on to the next steppable stage in the same frame
+        assert_eq!(run(&mut world, step_system), ["control", "a2"]);
+        assert_eq!(
+            next(&world),
+            (Some(TestStage::B.as_label()), Some("b1".into()))
+        );
+        assert_eq!(run(&mut world, step_system), ["control", "b1"]);
+        // The next stage is only known once it runs
+        assert_eq!(next(&world), (None, None));
+
+        // Back to the first steppable stage
+        assert_eq!(run(&mut world, step_system), ["control", "a0"]);
+        let step_stage: fn(&mut Stepping) = |stepping| {
+            stepping.step_stage();
+        };
+        assert_eq!(run(&mut world, step_stage), ["control", "a1", "a2"]);
+
+        let disable: fn(&mut Stepping) = |stepping| {
+            stepping.disable();
+        };
+        assert_eq!(
+            run(&mut world, disable),
+            ["control", "a0", "a1", "a2", "b1"]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     },
     system::System,
 };
+use bevy_utils::{Duration, Instant};
 use core::fmt::Debug;
 use std::borrow::Cow;
 
@@ -20,6 +21,10 @@ pub struct SystemContainer {
     before: Vec<SystemLabelId>,
     after: Vec<SystemLabelId>,
     pub(crate) ambiguity_detection: AmbiguityDetection,
+    /// Whether the stage measures how long the system runs, see [`SystemExecutionTimes`](crate::schedule::SystemExecutionTimes).
+    pub(crate) measure_run_time: bool,
+    /// How long the system ran since the stage started running, if it ran and is measured.
+    pub(crate) run_time: Option<Duration>,
 }
 
 impl SystemContainer {
@@ -35,6 +40,8 @@ impl SystemContainer {
             after: descriptor.after,
             ambiguity_detection: descriptor.ambiguity_detection,
             is_exclusive: descriptor.exclusive_insertion_point.is_some(),
+            measure_run_time: false,
+            run_time: None,
         }
     }
 
@@ -82,6 +89,17 @@ impl SystemContainer {
     pub fn is_exclusive(&self) -> bool {
         self.is_exclusive
     }
+
+    /// Runs the system with `run`, adding to its run time if it is measured.
+    pub(crate) fn run_timed(&mut self, run: impl FnOnce(&mut dyn System<In = (), Out = ()>)) {
+        if self.measure_run_time {
+            let start = Instant::now();
+            run(&mut *self.system);
+            *self.run_time.get_or_insert(Duration::ZERO) += start.elapsed();
+        } else {
+            run(&mut *self.system);
+        }
+    }
 }
 
 impl Debug for SystemContainer {
@@ -9,9 +9,9 @@ use crate::{
 use bevy_utils::tracing::{error, info, warn};
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData};
 
-use super::Resource;
+use super::{Resource, RunSystem, SystemId};
 
 /// A [`World`] mutation.
 ///
@@ -98,6 +98,7 @@ pub trait Command: Send + 'static {
 pub struct Commands<'w, 's> {
     queue: &'s mut CommandQueue,
     entities: &'w Entities,
+    error_handler: CommandErrorHandler,
 }
 
 impl<'w, 's> Commands<'w, 's> {
@@ -107,10 +108,7 @@ impl<'w, 's> Commands<'w, 's> {
     ///
     /// [system parameter]: crate::system::SystemParam
     pub fn new(queue: &'s mut CommandQueue, world: &'w World) -> Self {
-        Self {
-            queue,
-            entities: world.entities(),
-        }
+        Self::new_from_entities(queue, world.entities())
     }
 
     /// Returns a new `Commands` instance from a [`CommandQueue`] and an [`Entities`] reference.
@@ -119,7 +117,39 @@ impl<'w, 's> Commands<'w, 's> {
     ///
     /// [system parameter]: crate::system::SystemParam
     pub fn new_from_entities(queue: &'s mut CommandQueue, entities: &'w Entities) -> Self {
-        Self { queue, entities }
+        Self {
+            queue,
+            entities,
+            error_handler: error_handler::ignore,
+        }
+    }
+
+    /// Sets the function called when a fallible command queued afterwards by these `Commands`,
+    /// such as [`EntityCommands::try_insert`] or [`Commands::try_despawn`], fails. Errors are
+    /// ignored by default.
+    ///
+    /// The handler only applies to this `Commands`, i.e. to the current run of the system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::error_handler;
+    /// #[derive(Component)]
+    /// struct Burning;
+    ///
+    /// fn ignite(mut commands: Commands, targets: Query<Entity>) {
+    ///     commands.set_error_handler(error_handler::warn);
+    ///     for target in &targets {
+    ///         // The target may be despawned by an earlier command
+    ///         commands.entity(target).try_insert(Burning);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(ignite);
+    /// ```
+    pub fn set_error_handler(&mut self, handler: CommandErrorHandler) -> &mut Self {
+        self.error_handler = handler;
+        self
     }
 
     /// Pushes a [`Command`] to the queue for creating a new empty [`Entity`],
@@ -499,6 +529,69 @@ impl<'w, 's> Commands<'w, 's> {
         });
     }
 
+    /// Despawns `entity` if it still exists when the command is applied, and does nothing otherwise.
+    ///
+    /// Unlike [`EntityCommands::despawn`], this doesn't require the entity to exist when it is queued
+    /// and doesn't log a warning if it was already despawned, e.g. by another system or by an earlier
+    /// command in the same queue, unless another handler is set with
+    /// [`Commands::set_error_handler`]. This makes it the safe choice for debug and cleanup code that may
+    /// run several times for the same entity before commands are applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component)]
+    /// struct DebugMarker {
+    ///     target: Entity,
+    /// }
+    ///
+    /// fn clear_debug_markers(mut commands: Commands, markers: Query<(Entity, &DebugMarker)>) {
+    ///     for (entity, marker) in &markers {
+    ///         // Several markers may share the same target.
+    ///         commands.try_despawn(marker.target);
+    ///         commands.try_despawn(entity);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(clear_debug_markers);
+    /// ```
+    pub fn try_despawn(&mut self, entity: Entity) {
+        self.queue.push(TryDespawn {
+            entity,
+            on_error: self.error_handler,
+        });
+    }
+
+    /// Runs the system registered as `id` with [`World::register_system`], when the commands
+    /// are applied.
+    ///
+    /// The commands of the system are applied right after it runs, before the commands queued
+    /// after this one. A warning is logged if the system isn't registered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, system::SystemId};
+    /// #[derive(Component)]
+    /// struct Button {
+    ///     on_click: SystemId,
+    ///     clicked: bool,
+    /// }
+    ///
+    /// fn click_buttons(mut commands: Commands, buttons: Query<&Button>) {
+    ///     for button in &buttons {
+    ///         if button.clicked {
+    ///             commands.run_system(button.on_click);
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(click_buttons);
+    /// ```
+    pub fn run_system(&mut self, id: SystemId) {
+        self.queue.push(RunSystem { system_id: id });
+    }
+
     /// Pushes a generic [`Command`] to the command queue.
     ///
     /// `command` can be a built-in command, custom struct that implements [`Command`] or a closure
@@ -628,6 +721,38 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self.insert(bundle)
     }
 
+    /// Adds a [`Bundle`] of components to the entity if it still exists when the command is
+    /// applied.
+    ///
+    /// Unlike [`EntityCommands::insert`], this doesn't panic if the entity was despawned in the
+    /// meantime, but calls the handler set with [`Commands::set_error_handler`], which ignores the
+    /// error by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Hit;
+    ///
+    /// fn mark_hit(mut commands: Commands, targets: Query<Entity>) {
+    ///     for target in &targets {
+    ///         // Another system may despawn the target before the commands are applied
+    ///         commands.entity(target).try_insert(Hit);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(mark_hit);
+    /// ```
+    pub fn try_insert(&mut self, bundle: impl Bundle) -> &mut Self {
+        let on_error = self.commands.error_handler;
+        self.commands.add(TryInsert {
+            entity: self.entity,
+            bundle,
+            on_error,
+        });
+        self
+    }
+
     /// Removes a [`Bundle`] of components from the entity.
     ///
     /// See [`EntityMut::remove`](crate::world::EntityMut::remove) for more
@@ -719,6 +844,13 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         });
     }
 
+    /// Despawns the entity if it still exists when the command is applied, and does nothing otherwise.
+    ///
+    /// See [`Commands::try_despawn`] for more details.
+    pub fn try_despawn(&mut self) {
+        self.commands.try_despawn(self.entity);
+    }
+
     /// Logs the components of the entity at the info level.
     ///
     /// # Panics
@@ -826,6 +958,26 @@ impl Command for Despawn {
     }
 }
 
+/// A [`Command`] that despawns an entity if it exists, and calls `on_error` otherwise.
+pub struct TryDespawn {
+    pub entity: Entity,
+    pub on_error: CommandErrorHandler,
+}
+
+impl Command for TryDespawn {
+    fn write(self, world: &mut World) {
+        if !world.despawn(self.entity) {
+            (self.on_error)(
+                world,
+                CommandError::NoSuchEntity {
+                    entity: self.entity,
+                    command: std::any::type_name::<Self>(),
+                },
+            );
+        }
+    }
+}
+
 pub struct Insert<T> {
     pub entity: Entity,
     pub bundle: T,
@@ -844,6 +996,32 @@ where
     }
 }
 
+/// A [`Command`] that inserts a bundle if the entity exists, and calls `on_error` otherwise.
+pub struct TryInsert<T> {
+    pub entity: Entity,
+    pub bundle: T,
+    pub on_error: CommandErrorHandler,
+}
+
+impl<T> Command for TryInsert<T>
+where
+    T: Bundle + 'static,
+{
+    fn write(self, world: &mut World) {
+        if let Some(mut entity) = world.get_entity_mut(self.entity) {
+            entity.insert(self.bundle);
+        } else {
+            (self.on_error)(
+                world,
+                CommandError::NoSuchEntity {
+                    entity: self.entity,
+                    command: std::any::type_name::<Self>(),
+                },
+            );
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Remove<T> {
     pub entity: Entity,
@@ -909,13 +1087,68 @@ impl Command for LogComponents {
     }
 }
 
+/// An error applying a fallible [`Command`], passed to its [`CommandErrorHandler`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The entity the command applies to doesn't exist, e.g. because it was despawned by an
+    /// earlier command.
+    NoSuchEntity {
+        /// The missing entity.
+        entity: Entity,
+        /// The type name of the command.
+        command: &'static str,
+    },
+}
+
+impl std::error::Error for CommandError {}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::NoSuchEntity { entity, command } => write!(
+                f,
+                "Could not apply {command} to entity {entity:?} because it doesn't exist in this World."
+            ),
+        }
+    }
+}
+
+/// A function called when a fallible [`Command`] fails. See [`Commands::set_error_handler`],
+/// and [`error_handler`] for the built-in handlers.
+pub type CommandErrorHandler = fn(&mut World, CommandError);
+
+/// Built-in [`CommandErrorHandler`]s.
+pub mod error_handler {
+    use super::CommandError;
+    use crate::world::World;
+    use bevy_utils::tracing::{error, warn};
+
+    /// Ignores the error. This is the default handler.
+    pub fn ignore(_world: &mut World, _error: CommandError) {}
+
+    /// Logs the error as a warning.
+    pub fn warn(_world: &mut World, error: CommandError) {
+        warn!("{error}");
+    }
+
+    /// Logs the error as an error.
+    pub fn error(_world: &mut World, error: CommandError) {
+        error!("{error}");
+    }
+
+    /// Panics with the error, like the infallible commands.
+    pub fn panic(_world: &mut World, error: CommandError) {
+        panic!("{error}");
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp, clippy::approx_constant)]
 mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{CommandQueue, Commands, Resource},
+        system::{CommandError, CommandQueue, Commands, Resource},
         world::World,
     };
     use std::sync::{
@@ -1000,6 +1233,68 @@ mod tests {
         assert_eq!(results3, vec![(42u32, 0u64), (0u32, 42u64)]);
     }
 
+    #[test]
+    fn try_despawn() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn(W(1u32)).id();
+        let other = world.spawn(W(2u32)).id();
+        {
+            let mut commands = Commands::new(&mut command_queue, &world);
+            commands.try_despawn(entity);
+            commands.entity(entity).try_despawn();
+        }
+        command_queue.apply(&mut world);
+        assert!(world.get_entity(entity).is_none());
+        assert!(world.get_entity(other).is_some());
+
+        // despawning an entity that no longer exists is a no-op
+        Commands::new(&mut command_queue, &world).try_despawn(entity);
+        command_queue.apply(&mut world);
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    fn try_insert_with_error_handler() {
+        #[derive(Resource, Default)]
+        struct Errors(Vec<CommandError>);
+
+        let mut world = World::default();
+        world.init_resource::<Errors>();
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        {
+            let mut commands = Commands::new(&mut command_queue, &world);
+            commands.entity(entity).despawn();
+            // Errors are ignored until a handler is set
+            commands.entity(entity).try_insert(W(1u32));
+            commands.set_error_handler(|world, error| {
+                world.resource_mut::<Errors>().0.push(error);
+            });
+            commands.entity(entity).try_insert(W(2u32));
+            commands.try_despawn(entity);
+        }
+        command_queue.apply(&mut world);
+
+        let errors = &world.resource::<Errors>().0;
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            CommandError::NoSuchEntity { entity: e, command } if e == entity && command.contains("TryInsert")
+        ));
+        assert!(matches!(
+            errors[1],
+            CommandError::NoSuchEntity { entity: e, command } if e == entity && command.contains("TryDespawn")
+        ));
+
+        let entity = world.spawn_empty().id();
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .try_insert(W(3u32));
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 3);
+    }
+
     #[test]
     fn remove_components() {
         let mut world = World::default();
@@ -82,6 +82,10 @@ unsafe impl SystemParamState for ParallelCommandsState {
 }
 
 impl<'w, 's> ParallelCommands<'w, 's> {
+    /// Runs `f` with [`Commands`] writing to a queue local to the current thread, so that it
+    /// can be called from the tasks of [`Query::par_for_each`](crate::system::Query::par_for_each).
+    ///
+    /// The queues of all threads are applied, one after the other, when the system's buffers are.
     pub fn command_scope<R>(&self, f: impl FnOnce(Commands) -> R) -> R {
         let store = &self.state.thread_local_storage;
         let command_queue_cell = store.get_or_default();
@@ -96,3 +100,48 @@ impl<'w, 's> ParallelCommands<'w, 's> {
         r
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{prelude::*, system::SystemState};
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+    #[derive(Component)]
+    struct Particle(u32);
+
+    #[derive(Component)]
+    struct Spark(u32);
+
+    #[derive(Component)]
+    struct Emitted;
+
+    #[test]
+    fn spawn_and_insert_from_par_for_each() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::new();
+        world.spawn_batch((0..100).map(Particle));
+
+        let mut state =
+            SystemState::<(Query<(Entity, &Particle)>, ParallelCommands)>::new(&mut world);
+        let (query, par_commands) = state.get_mut(&mut world);
+        query.par_for_each(8, |(entity, particle)| {
+            par_commands.command_scope(|mut commands| {
+                commands.entity(entity).insert(Emitted);
+                commands.spawn(Spark(particle.0));
+            });
+        });
+        state.apply(&mut world);
+
+        assert_eq!(
+            world
+                .query_filtered::<(), (With<Particle>, With<Emitted>)>()
+                .iter(&world)
+                .count(),
+            100
+        );
+        let mut sparks: Vec<u32> = world.query::<&Spark>().iter(&world).map(|s| s.0).collect();
+        sparks.sort_unstable();
+        assert_eq!(sparks, (0..100).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+
+use crate as bevy_ecs;
+use crate::{
+    entity::Entity,
+    system::{BoxedSystem, Command, IntoSystem},
+    world::World,
+};
+use bevy_ecs_macros::Component;
+use bevy_utils::tracing::warn;
+
+/// A system registered with [`World::register_system`], stored on its own entity.
+#[derive(Component)]
+struct RegisteredSystem {
+    initialized: bool,
+    system: BoxedSystem,
+}
+
+/// An identifier for a system registered with [`World::register_system`].
+///
+/// The system is stored on an entity of the world it was registered in, and runs with
+/// [`World::run_system`] or [`Commands::run_system`](crate::system::Commands::run_system).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemId(Entity);
+
+impl SystemId {
+    /// The entity the system is stored on.
+    pub fn entity(self) -> Entity {
+        self.0
+    }
+}
+
+impl World {
+    /// Registers `system` to be run on demand with [`World::run_system`] or
+    /// [`Commands::run_system`](crate::system::Commands::run_system), rather than every frame
+    /// in a [`Schedule`](crate::schedule::Schedule).
+    ///
+    /// The system keeps its state, such as its [`Local`](crate::system::Local)s and the
+    /// change ticks of its queries, from one run to the next. It is initialized on its first run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Counter(u32);
+    ///
+    /// fn increment(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Counter>();
+    /// let increment = world.register_system(increment);
+    /// world.run_system(increment).unwrap();
+    /// world.run_system(increment).unwrap();
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn register_system<Params>(
+        &mut self,
+        system: impl IntoSystem<(), (), Params> + 'static,
+    ) -> SystemId {
+        SystemId(
+            self.spawn(RegisteredSystem {
+                initialized: false,
+                system: Box::new(IntoSystem::into_system(system)),
+            })
+            .id(),
+        )
+    }
+
+    /// Removes a system registered with [`World::register_system`].
+    ///
+    /// Returns an error if there is no such system, or if it is removed while it is running.
+    pub fn remove_system(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        match self.get_entity_mut(id.0) {
+            Some(mut entity) => {
+                if entity.remove::<RegisteredSystem>().is_none() {
+                    return Err(RegisteredSystemError::SystemRunning(id));
+                }
+                entity.despawn();
+                Ok(())
+            }
+            None => Err(RegisteredSystemError::SystemIdNotRegistered(id)),
+        }
+    }
+
+    /// Runs a system registered with [`World::register_system`], and applies its
+    /// [`Commands`](crate::system::Commands) right away.
+    ///
+    /// Returns an error if there is no such system, or if the system tries to run itself,
+    /// directly or through other registered systems.
+    pub fn run_system(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        // The system is taken out of its entity while it runs, as it needs the whole world
+        let mut registered = match self.get_entity_mut(id.0) {
+            Some(mut entity) => match entity.remove::<RegisteredSystem>() {
+                Some(registered) => registered,
+                None => return Err(RegisteredSystemError::Recursive(id)),
+            },
+            None => return Err(RegisteredSystemError::SystemIdNotRegistered(id)),
+        };
+
+        if !registered.initialized {
+            registered.system.initialize(self);
+            registered.initialized = true;
+        }
+        // The system may not have run for long enough for its change ticks to wrap around
+        registered.system.check_change_tick(self.change_tick());
+        registered.system.run((), self);
+        registered.system.apply_buffers(self);
+
+        // The system may have despawned its own entity, or removed itself
+        if let Some(mut entity) = self.get_entity_mut(id.0) {
+            entity.insert(registered);
+        }
+        Ok(())
+    }
+}
+
+/// An error when running or removing a system registered with [`World::register_system`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisteredSystemError {
+    /// There is no system with this id, or it was removed.
+    SystemIdNotRegistered(SystemId),
+    /// The system tried to run itself, directly or through other registered systems.
+    Recursive(SystemId),
+    /// The system tried to remove itself while running.
+    SystemRunning(SystemId),
+}
+
+impl std::error::Error for RegisteredSystemError {}
+
+impl fmt::Display for RegisteredSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegisteredSystemError::SystemIdNotRegistered(id) => {
+                write!(f, "System {id:?} is not registered.")
+            }
+            RegisteredSystemError::Recursive(id) => {
+                write!(f, "System {id:?} tried to run itself recursively.")
+            }
+            RegisteredSystemError::SystemRunning(id) => {
+                write!(f, "System {id:?} tried to remove itself while running.")
+            }
+        }
+    }
+}
+
+/// A [`Command`] running a system registered with [`World::register_system`].
+/// See [`Commands::run_system`](crate::system::Commands::run_system).
+#[derive(Debug, Clone, Copy)]
+pub struct RunSystem {
+    /// The registered system to run, returned by [`World::register_system`].
+    pub system_id: SystemId,
+}
+
+impl Command for RunSystem {
+    fn write(self, world: &mut World) {
+        if let Err(err) = world.run_system(self.system_id) {
+            warn!("Could not run a registered system: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        prelude::*,
+        system::{RegisteredSystemError, SystemId},
+    };
+
+    #[derive(Resource, Default, PartialEq, Debug)]
+    struct Counter(u32);
+
+    #[test]
+    fn registered_systems_keep_their_state() {
+        fn count_changes(
+            mut last: Local<u32>,
+            mut counter: ResMut<Counter>,
+            query: Query<(), Changed<Marker>>,
+        ) {
+            *last += 1;
+            counter.0 = *last * 10 + query.iter().count() as u32;
+        }
+
+        #[derive(Component)]
+        struct Marker;
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.spawn(Marker);
+        let id = world.register_system(count_changes);
+
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(11));
+        // The marker was already seen by the previous run
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(20));
+
+        world.remove_system(id).unwrap();
+        assert_eq!(
+            world.run_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+        assert_eq!(
+            world.remove_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+    }
+
+    #[test]
+    fn commands_run_registered_systems() {
+        #[derive(Resource)]
+        struct Callbacks(SystemId, SystemId);
+
+        fn increment(mut commands: Commands, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+            // Commands of registered systems are applied right after they run
+            commands.insert_resource(Counter(counter.0 * 100));
+        }
+
+        fn run_callbacks(mut commands: Commands, callbacks: Res<Callbacks>) {
+            commands.run_system(callbacks.0);
+            commands.run_system(callbacks.1);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let increment = world.register_system(increment);
+        let recursive = world.register_system(run_callbacks);
+        world.insert_resource(Callbacks(increment, recursive));
+
+        // The recursive call is refused with a warning, and doesn't stop the other commands
+        world.run_system(recursive).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(100));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::single(move |mut commands: Commands| commands.run_system(increment)),
+        );
+        schedule.run(&mut world);
+        assert_eq!(*world.resource::<Counter>(), Counter(10100));
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
     bundle::{Bundle, BundleInfo},
     change_detection::{MutUntyped, Ticks},
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{Component, ComponentHook, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
     storage::{SparseSet, Storages},
     world::{Mut, World},
@@ -255,6 +255,13 @@ impl<'w> EntityMut<'w> {
             .world
             .bundles
             .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let added: Vec<ComponentId> = bundle_info
+            .component_ids
+            .iter()
+            .copied()
+            .filter(|&id| !archetype.contains(id) && self.world.components.has_add_effects(id))
+            .collect();
         let mut bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -267,10 +274,56 @@ impl<'w> EntityMut<'w> {
         unsafe {
             self.location = bundle_inserter.insert(self.entity, self.location.index, bundle);
         }
+        self.components_added(&added);
 
         self
     }
 
+    /// Inserts the missing required components of components just added to this entity, then
+    /// runs their `on_add` hooks.
+    pub(crate) fn components_added(&mut self, added: &[ComponentId]) {
+        for &id in added {
+            let required_count = self
+                .world
+                .components
+                .get_info(id)
+                .unwrap()
+                .required_components
+                .len();
+            for index in 0..required_count {
+                let required = self
+                    .world
+                    .components
+                    .get_info(id)
+                    .unwrap()
+                    .required_components[index];
+                if !self.contains_id(required.id) {
+                    (required.insert_default)(self);
+                }
+            }
+        }
+        let on_add: Vec<ComponentHook> = added
+            .iter()
+            .filter_map(|&id| self.world.components.get_info(id).unwrap().hooks().on_add)
+            .collect();
+        self.run_hooks(&on_add);
+    }
+
+    /// Runs component hooks for this entity, which may move it to another archetype.
+    pub(crate) fn run_hooks(&mut self, hooks: &[ComponentHook]) {
+        if hooks.is_empty() {
+            return;
+        }
+        for hook in hooks {
+            hook(self.world, self.entity);
+        }
+        self.location = self
+            .world
+            .entities
+            .get(self.entity)
+            .expect("component hooks should not despawn the entity they run for");
+    }
+
     #[deprecated(
         since = "0.9.0",
         note = "Use `remove` instead, which now accepts bundles, components, and tuples of bundles and components."
@@ -284,6 +337,23 @@ impl<'w> EntityMut<'w> {
     ///
     /// Returns `None` if the entity does not contain the bundle.
     pub fn remove<T: Bundle>(&mut self) -> Option<T> {
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        if bundle_info
+            .component_ids
+            .iter()
+            .all(|&id| archetype.contains(id))
+        {
+            let on_remove = self
+                .world
+                .components
+                .on_remove_hooks(bundle_info.component_ids.iter().copied());
+            self.run_hooks(&on_remove);
+        }
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -417,6 +487,20 @@ impl<'w> EntityMut<'w> {
     // TODO: move to BundleInfo
     /// Remove any components in the bundle that the entity has.
     pub fn remove_intersection<T: Bundle>(&mut self) {
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let on_remove = self.world.components.on_remove_hooks(
+            bundle_info
+                .component_ids
+                .iter()
+                .copied()
+                .filter(|&id| archetype.contains(id)),
+        );
+        self.run_hooks(&on_remove);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -481,6 +565,16 @@ impl<'w> EntityMut<'w> {
 
     pub fn despawn(self) {
         let world = self.world;
+        let on_remove = world
+            .components
+            .on_remove_hooks(world.archetypes[self.location.archetype_id].components());
+        for hook in on_remove {
+            hook(world, self.entity);
+        }
+        // The hooks may have despawned the entity already
+        if world.entities.get(self.entity).is_none() {
+            return;
+        }
         world.flush();
         let location = world
             .entities
@@ -622,7 +716,7 @@ pub(crate) unsafe fn get_component(
 /// # Safety
 /// Caller must ensure that `component_id` is valid
 #[inline]
-unsafe fn get_component_and_ticks(
+pub(crate) unsafe fn get_component_and_ticks(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
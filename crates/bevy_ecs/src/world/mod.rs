@@ -12,8 +12,8 @@ use crate::{
     bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
     change_detection::{MutUntyped, Ticks},
     component::{
-        Component, ComponentDescriptor, ComponentId, ComponentInfo, ComponentTicks, Components,
-        StorageType,
+        Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, ComponentTicks,
+        Components, StorageType,
     },
     entity::{AllocAtWithoutReplacement, Entities, Entity},
     query::{QueryState, ReadOnlyWorldQuery, WorldQuery},
@@ -172,6 +172,23 @@ impl World {
             .init_component_with_descriptor(&mut self.storages, descriptor)
     }
 
+    /// Returns the [`ComponentHooks`] of the [`Component`] type `T`, to register functions run
+    /// whenever it is added to or removed from an entity.
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let id = self.init_component::<T>();
+        self.components.get_hooks_mut(id).unwrap()
+    }
+
+    /// Returns the [`ComponentHooks`] of the component with the given id, if it exists.
+    ///
+    /// See [`World::register_component_hooks`].
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        self.components.get_hooks_mut(id)
+    }
+
     /// Returns the [`ComponentId`] of the given [`Component`] type `T`.
     ///
     /// The returned `ComponentId` is specific to the `World` instance
@@ -450,10 +467,17 @@ impl World {
     pub fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityMut {
         self.flush();
         let entity = self.entities.alloc();
+        let added: Vec<ComponentId>;
         let entity_location = {
             let bundle_info = self
                 .bundles
                 .init_info::<B>(&mut self.components, &mut self.storages);
+            added = bundle_info
+                .components()
+                .iter()
+                .copied()
+                .filter(|&id| self.components.has_add_effects(id))
+                .collect();
             let mut spawner = bundle_info.get_bundle_spawner(
                 &mut self.entities,
                 &mut self.archetypes,
@@ -467,7 +491,9 @@ impl World {
         };
 
         // SAFETY: entity and location are valid, as they were just created above
-        unsafe { EntityMut::new(self, entity, entity_location) }
+        let mut entity = unsafe { EntityMut::new(self, entity, entity_location) };
+        entity.components_added(&added);
+        entity
     }
 
     /// # Safety
@@ -1056,6 +1082,28 @@ impl World {
         let bundle_info = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
+        if self
+            .components
+            .bundle_has_add_effects(bundle_info.components())
+        {
+            // Hooks and required components missing from the bundle need the whole world, so
+            // the entities are handled one by one
+            let mut invalid_entities = Vec::new();
+            for (entity, bundle) in iter {
+                match self.get_or_spawn(entity) {
+                    Some(mut entity) => {
+                        entity.insert(bundle);
+                    }
+                    None => invalid_entities.push(entity),
+                }
+            }
+            return if invalid_entities.is_empty() {
+                Ok(())
+            } else {
+                Err(invalid_entities)
+            };
+        }
+
         enum SpawnOrInsert<'a, 'b> {
             Spawn(BundleSpawner<'a, 'b>),
             Insert(BundleInserter<'a, 'b>, ArchetypeId),
@@ -1640,6 +1688,7 @@ mod tests {
     use crate::{
         change_detection::DetectChanges,
         component::{ComponentDescriptor, ComponentInfo, StorageType},
+        entity::Entity,
         ptr::OwningPtr,
         system::Resource,
     };
@@ -1986,4 +2035,118 @@ mod tests {
 
         assert_eq!(entity_counters.len(), 0);
     }
+
+    #[test]
+    fn component_hooks() {
+        #[derive(Component)]
+        struct Body(u32);
+        #[derive(Component)]
+        struct Other;
+        #[derive(Resource, Default)]
+        struct Log(Vec<String>);
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world
+            .register_component_hooks::<Body>()
+            .on_add(|world, entity| {
+                let body = world.get::<Body>(entity).unwrap().0;
+                world.resource_mut::<Log>().0.push(format!("add {body}"));
+                // Hooks can change the entity, moving it to another archetype
+                world.entity_mut(entity).insert(Other);
+            })
+            .on_remove(|world, entity| {
+                let body = world.get::<Body>(entity).unwrap().0;
+                world.resource_mut::<Log>().0.push(format!("remove {body}"));
+            });
+        let take_log = |world: &mut World| std::mem::take(&mut world.resource_mut::<Log>().0);
+
+        let mut entity = world.spawn(Body(0));
+        assert!(entity.contains::<Other>());
+        // Replacing the component doesn't run the hook
+        entity.insert(Body(1));
+        entity.remove::<Body>();
+        // Removing a bundle the entity doesn't have doesn't run the hook either
+        entity.remove::<(Body, Other)>();
+        entity.insert(Body(2));
+        entity.remove_intersection::<(Body, Other)>();
+        let entity = entity.id();
+        assert_eq!(
+            take_log(&mut world),
+            ["add 0", "remove 1", "add 2", "remove 2"]
+        );
+
+        let entities: Vec<Entity> = world.spawn_batch([Body(3), Body(4)]).collect();
+        world
+            .insert_or_spawn_batch([(entity, Body(5)), (entities[0], Body(6))])
+            .unwrap();
+        assert!(world.entity(entities[1]).contains::<Other>());
+        assert_eq!(take_log(&mut world), ["add 3", "add 4", "add 5"]);
+
+        for entity in entities {
+            world.despawn(entity);
+        }
+        world.entity_mut(entity).despawn();
+        assert_eq!(take_log(&mut world), ["remove 6", "remove 4", "remove 5"]);
+    }
+
+    #[test]
+    fn required_components() {
+        #[derive(Component, Default)]
+        #[component(require(Position, Visible))]
+        struct Mesh;
+        #[derive(Component, Default, Debug, PartialEq)]
+        #[component(require(GlobalPosition))]
+        struct Position(u32);
+        #[derive(Component, Default)]
+        struct GlobalPosition;
+        #[derive(Component, Default, Debug, PartialEq)]
+        // Requirements can be cyclic
+        #[component(require(Mesh))]
+        struct Visible(bool);
+
+        let mut world = World::new();
+        let spawned = world.spawn(Mesh).id();
+        let entity = world.entity(spawned);
+        assert_eq!(entity.get::<Position>(), Some(&Position(0)));
+        assert_eq!(entity.get::<Visible>(), Some(&Visible(false)));
+        // Requirements are transitive
+        assert!(entity.contains::<GlobalPosition>());
+
+        // Components already on the entity or in the bundle are kept
+        let inserted = world.spawn(Position(3)).insert(Mesh).id();
+        assert_eq!(world.get::<Position>(inserted), Some(&Position(3)));
+        let bundled = world.spawn((Mesh, Visible(true))).id();
+        assert_eq!(world.get::<Visible>(bundled), Some(&Visible(true)));
+
+        let batch: Vec<Entity> = world.spawn_batch([Mesh, Mesh]).collect();
+        for entity in batch {
+            assert!(world.entity(entity).contains::<GlobalPosition>());
+        }
+
+        // Bundles containing their required components are spawned in batches
+        let ids = [
+            world.init_component::<Position>(),
+            world.init_component::<GlobalPosition>(),
+        ];
+        assert!(!world.components().bundle_has_add_effects(&ids));
+        assert!(world.components().bundle_has_add_effects(&ids[..1]));
+        let batch: Vec<Entity> = world.spawn_batch([(Position(1), GlobalPosition)]).collect();
+        assert_eq!(world.get::<Position>(batch[0]), Some(&Position(1)));
+
+        let mesh_id = world.init_component::<Mesh>();
+        let required: Vec<_> = world
+            .components()
+            .get_info(mesh_id)
+            .unwrap()
+            .required_components()
+            .collect();
+        assert_eq!(
+            required,
+            [
+                world.init_component::<Position>(),
+                world.init_component::<Visible>()
+            ]
+        );
+    }
 }
@@ -11,7 +11,14 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    spawner: BundleSpawner<'w, 'w>,
+    spawner: Spawner<'w>,
+}
+
+enum Spawner<'w> {
+    Batch(BundleSpawner<'w, 'w>),
+    // Component hooks and required components missing from the bundle need the whole world, so
+    // the entities are spawned one by one
+    OneByOne(&'w mut World),
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -28,6 +35,19 @@ where
         let (lower, upper) = iter.size_hint();
         let length = upper.unwrap_or(lower);
 
+        let bundle_info = world
+            .bundles
+            .init_info::<I::Item>(&mut world.components, &mut world.storages);
+        if world
+            .components
+            .bundle_has_add_effects(bundle_info.components())
+        {
+            return Self {
+                inner: iter,
+                spawner: Spawner::OneByOne(world),
+            };
+        }
+
         let bundle_info = world
             .bundles
             .init_info::<I::Item>(&mut world.components, &mut world.storages);
@@ -43,7 +63,7 @@ where
 
         Self {
             inner: iter,
-            spawner,
+            spawner: Spawner::Batch(spawner),
         }
     }
 }
@@ -67,8 +87,11 @@ where
 
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
-        // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        match &mut self.spawner {
+            // SAFETY: bundle matches spawner type
+            Spawner::Batch(spawner) => unsafe { Some(spawner.spawn(bundle)) },
+            Spawner::OneByOne(world) => Some(world.spawn(bundle).id()),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
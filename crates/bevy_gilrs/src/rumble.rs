@@ -0,0 +1,126 @@
+use bevy_ecs::{
+    event::EventReader,
+    system::{NonSendMut, Res},
+};
+use bevy_input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_time::Time;
+use bevy_utils::{
+    tracing::{debug, warn},
+    Duration, HashMap,
+};
+use gilrs::{
+    ff::{self, BaseEffect, BaseEffectType, Replay, Ticks},
+    GamepadId, Gilrs,
+};
+
+/// A rumble effect that is currently playing, stopped when dropped
+struct RunningRumble {
+    /// The time since startup at which the rumble ends
+    deadline: Duration,
+    #[allow(dead_code)]
+    effect: ff::Effect,
+}
+
+/// The rumble effects playing on each gamepad
+#[derive(Default)]
+pub(crate) struct RunningRumbleEffects {
+    rumbles: HashMap<GamepadId, Vec<RunningRumble>>,
+}
+
+fn to_gilrs_magnitude(intensity: f32) -> u16 {
+    (intensity.clamp(0.0, 1.0) * u16::MAX as f32) as u16
+}
+
+fn get_base_effects(intensity: GamepadRumbleIntensity, duration: Duration) -> Vec<BaseEffect> {
+    let scheduling = Replay {
+        play_for: Ticks::from(duration),
+        ..Default::default()
+    };
+    let mut effects = Vec::new();
+    if intensity.strong_motor > 0.0 {
+        effects.push(BaseEffect {
+            kind: BaseEffectType::Strong {
+                magnitude: to_gilrs_magnitude(intensity.strong_motor),
+            },
+            scheduling,
+            ..Default::default()
+        });
+    }
+    if intensity.weak_motor > 0.0 {
+        effects.push(BaseEffect {
+            kind: BaseEffectType::Weak {
+                magnitude: to_gilrs_magnitude(intensity.weak_motor),
+            },
+            scheduling,
+            ..Default::default()
+        });
+    }
+    effects
+}
+
+fn find_gilrs_gamepad(gilrs: &Gilrs, gamepad: Gamepad) -> Option<GamepadId> {
+    gilrs
+        .gamepads()
+        .map(|(id, _)| id)
+        .find(|id| usize::from(*id) == gamepad.id)
+}
+
+/// Starts and stops the rumbles requested with [`GamepadRumbleRequest`]s, and drops the ones
+/// that ended
+pub(crate) fn play_gilrs_rumble(
+    time: Res<Time>,
+    mut gilrs: NonSendMut<Gilrs>,
+    mut requests: EventReader<GamepadRumbleRequest>,
+    mut running_rumbles: NonSendMut<RunningRumbleEffects>,
+) {
+    let current_time = time.time_since_startup();
+    for rumbles in running_rumbles.rumbles.values_mut() {
+        rumbles.retain(|rumble| rumble.deadline >= current_time);
+    }
+    running_rumbles
+        .rumbles
+        .retain(|_, rumbles| !rumbles.is_empty());
+
+    for request in requests.iter() {
+        let gamepad = request.gamepad();
+        let gamepad_id = match find_gilrs_gamepad(&gilrs, gamepad) {
+            Some(gamepad_id) => gamepad_id,
+            None => {
+                warn!("Can't rumble {:?}, it isn't connected", gamepad);
+                continue;
+            }
+        };
+        match *request {
+            GamepadRumbleRequest::Stop { .. } => {
+                running_rumbles.rumbles.remove(&gamepad_id);
+            }
+            GamepadRumbleRequest::Add {
+                duration,
+                intensity,
+                ..
+            } => {
+                let mut builder = ff::EffectBuilder::new();
+                for effect in get_base_effects(intensity, duration) {
+                    builder.add_effect(effect);
+                }
+                let effect = builder
+                    .gamepads(&[gamepad_id])
+                    .finish(&mut gilrs)
+                    .and_then(|effect| effect.play().map(|_| effect));
+                match effect {
+                    Ok(effect) => {
+                        running_rumbles
+                            .rumbles
+                            .entry(gamepad_id)
+                            .or_default()
+                            .push(RunningRumble {
+                                deadline: current_time + duration,
+                                effect,
+                            });
+                    }
+                    Err(err) => debug!("Failed to rumble {:?}: {}", gamepad, err),
+                }
+            }
+        }
+    }
+}
@@ -7,7 +7,11 @@ pub use loader::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Handle};
-use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_ecs::{
+    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    prelude::Component,
+    reflect::{ReflectComponent, ReflectMapEntities},
+};
 use bevy_pbr::StandardMaterial;
 use bevy_reflect::{Reflect, TypeUuid};
 use bevy_render::mesh::Mesh;
@@ -21,6 +25,7 @@ impl Plugin for GltfPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset_loader::<GltfLoader>()
             .register_type::<GltfExtras>()
+            .register_type::<GltfSceneInstance>()
             .add_asset::<Gltf>()
             .add_asset::<GltfNode>()
             .add_asset::<GltfPrimitive>()
@@ -77,3 +82,53 @@ pub struct GltfPrimitive {
 pub struct GltfExtras {
     pub value: String,
 }
+
+/// The entities of a glTF scene, keyed on the names of their glTF node, mesh or material.
+///
+/// This component is on the root entity of the [`Scene`]s loaded from glTF files, and refers to
+/// the entities of each spawned instance of the scene, which helps finding attachment points
+/// without walking the hierarchy.
+#[derive(Clone, Debug, Default, Reflect, Component)]
+#[reflect(Component, MapEntities)]
+pub struct GltfSceneInstance {
+    /// The entity of each named node.
+    pub nodes: HashMap<String, Entity>,
+    /// The entities of the primitives of each named mesh, for all the nodes using the mesh.
+    pub meshes: HashMap<String, Vec<Entity>>,
+    /// The entities of the primitives using each named material.
+    pub materials: HashMap<String, Vec<Entity>>,
+}
+
+impl GltfSceneInstance {
+    /// Returns the entity of the node with the given name.
+    pub fn node(&self, name: &str) -> Option<Entity> {
+        self.nodes.get(name).copied()
+    }
+
+    /// Returns the entities of the primitives of the mesh with the given name.
+    pub fn mesh_entities(&self, name: &str) -> &[Entity] {
+        self.meshes.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the entities of the primitives using the material with the given name.
+    pub fn material_entities(&self, name: &str) -> &[Entity] {
+        self.materials.get(name).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl MapEntities for GltfSceneInstance {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        for entity in self.nodes.values_mut() {
+            *entity = entity_map.get(*entity)?;
+        }
+        for entity in self
+            .meshes
+            .values_mut()
+            .chain(self.materials.values_mut())
+            .flatten()
+        {
+            *entity = entity_map.get(*entity)?;
+        }
+        Ok(())
+    }
+}
@@ -43,7 +43,7 @@ use gltf::{
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
-use crate::{Gltf, GltfNode};
+use crate::{Gltf, GltfNode, GltfSceneInstance};
 
 /// An error that occurs when loading a glTF file.
 #[derive(Error, Debug)]
@@ -71,6 +71,10 @@ pub enum GltfError {
 }
 
 /// Loads glTF files with all of their data as their corresponding bevy representations.
+///
+/// The parts of a glTF file can also be loaded on their own with a label, either by index such as
+/// `model.gltf#Node2`, or by name such as `model.gltf#Node:Turret` for the nodes, meshes,
+/// materials and animations. The assets loaded by name are copies of the ones loaded by index.
 pub struct GltfLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
@@ -219,6 +223,12 @@ async fn load_gltf<'a, 'b>(
                     );
                 }
             }
+            if let Some(name) = animation.name() {
+                load_context.set_labeled_asset(
+                    &named_label("Animation", name),
+                    LoadedAsset::new(animation_clip.clone()),
+                );
+            }
             let handle = load_context.set_labeled_asset(
                 &format!("Animation{}", animation.index()),
                 LoadedAsset::new(animation_clip),
@@ -263,6 +273,13 @@ async fn load_gltf<'a, 'b>(
                 mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vertex_attribute);
             }
 
+            if let Some(vertex_attribute) = reader
+                .read_tex_coords(1)
+                .map(|v| VertexAttributeValues::Float32x2(v.into_f32().collect()))
+            {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, vertex_attribute);
+            }
+
             if let Some(vertex_attribute) = reader
                 .read_colors(0)
                 .map(|v| VertexAttributeValues::Float32x4(v.into_rgba_f32().collect()))
@@ -332,10 +349,15 @@ async fn load_gltf<'a, 'b>(
             });
         }
 
-        let handle = load_context.set_labeled_asset(
-            &mesh_label(&mesh),
-            LoadedAsset::new(super::GltfMesh { primitives }),
-        );
+        let gltf_mesh = super::GltfMesh { primitives };
+        if let Some(name) = mesh.name() {
+            load_context.set_labeled_asset(
+                &named_label("Mesh", name),
+                LoadedAsset::new(gltf_mesh.clone()),
+            );
+        }
+        let handle =
+            load_context.set_labeled_asset(&mesh_label(&mesh), LoadedAsset::new(gltf_mesh));
         if let Some(name) = mesh.name() {
             named_meshes.insert(name.to_string(), handle.clone());
         }
@@ -377,7 +399,14 @@ async fn load_gltf<'a, 'b>(
             named_nodes_intermediate.insert(name, node.index());
         }
     }
-    let nodes = resolve_node_hierarchy(nodes_intermediate, load_context.path())
+    let resolved_nodes = resolve_node_hierarchy(nodes_intermediate, load_context.path());
+    for (name, &index) in &named_nodes_intermediate {
+        if let Some((_, node)) = resolved_nodes.get(index) {
+            load_context
+                .set_labeled_asset(&named_label("Node", name), LoadedAsset::new(node.clone()));
+        }
+    }
+    let nodes = resolved_nodes
         .into_iter()
         .map(|(label, node)| load_context.set_labeled_asset(&label, LoadedAsset::new(node)))
         .collect::<Vec<bevy_asset::Handle<GltfNode>>>();
@@ -463,8 +492,9 @@ async fn load_gltf<'a, 'b>(
         let mut world = World::default();
         let mut node_index_to_entity_map = HashMap::new();
         let mut entity_to_skin_index_map = HashMap::new();
+        let mut scene_instance = GltfSceneInstance::default();
 
-        world
+        let root = world
             .spawn(SpatialBundle::VISIBLE_IDENTITY)
             .with_children(|parent| {
                 for node in scene.nodes() {
@@ -475,6 +505,7 @@ async fn load_gltf<'a, 'b>(
                         &buffer_data,
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
+                        &mut scene_instance,
                         &mut active_camera_found,
                     );
                     if result.is_err() {
@@ -482,10 +513,12 @@ async fn load_gltf<'a, 'b>(
                         return;
                     }
                 }
-            });
+            })
+            .id();
         if let Some(Err(err)) = err {
             return Err(err);
         }
+        world.entity_mut(root).insert(scene_instance);
 
         #[cfg(feature = "bevy_animation")]
         {
@@ -659,6 +692,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     });
 
     let emissive = material.emissive_factor();
+    let emissive_strength = material.emissive_strength().unwrap_or(1.0);
     let emissive_texture = material.emissive_texture().map(|info| {
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
@@ -667,32 +701,47 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         load_context.get_handle(path)
     });
 
-    load_context.set_labeled_asset(
-        &material_label,
-        LoadedAsset::new(StandardMaterial {
-            base_color: Color::rgba(color[0], color[1], color[2], color[3]),
-            base_color_texture,
-            perceptual_roughness: pbr.roughness_factor(),
-            metallic: pbr.metallic_factor(),
-            metallic_roughness_texture,
-            normal_map_texture,
-            double_sided: material.double_sided(),
-            cull_mode: if material.double_sided() {
-                None
-            } else {
-                Some(Face::Back)
-            },
-            occlusion_texture,
-            emissive: Color::rgba(emissive[0], emissive[1], emissive[2], 1.0),
-            emissive_texture,
-            unlit: material.unlit(),
-            alpha_mode: alpha_mode(material),
-            ..Default::default()
-        }),
-    )
+    let (reflectance, specular_tint) = specular(material);
+
+    let standard_material = StandardMaterial {
+        base_color: Color::rgba(color[0], color[1], color[2], color[3]),
+        base_color_texture,
+        perceptual_roughness: pbr.roughness_factor(),
+        metallic: pbr.metallic_factor(),
+        metallic_roughness_texture,
+        reflectance,
+        specular_tint,
+        ior: material.ior().unwrap_or(1.5),
+        normal_map_texture,
+        double_sided: material.double_sided(),
+        cull_mode: if material.double_sided() {
+            None
+        } else {
+            Some(Face::Back)
+        },
+        occlusion_texture,
+        emissive: Color::rgba(
+            emissive[0] * emissive_strength,
+            emissive[1] * emissive_strength,
+            emissive[2] * emissive_strength,
+            1.0,
+        ),
+        emissive_texture,
+        unlit: material.unlit(),
+        alpha_mode: alpha_mode(material),
+        ..Default::default()
+    };
+    if let Some(name) = material.name() {
+        load_context.set_labeled_asset(
+            &named_label("Material", name),
+            LoadedAsset::new(standard_material.clone()),
+        );
+    }
+    load_context.set_labeled_asset(&material_label, LoadedAsset::new(standard_material))
 }
 
 /// Loads a glTF node.
+#[allow(clippy::too_many_arguments)]
 fn load_node(
     gltf_node: &gltf::Node,
     world_builder: &mut WorldChildBuilder,
@@ -700,6 +749,7 @@ fn load_node(
     buffer_data: &[Vec<u8>],
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut HashMap<Entity, usize>,
+    scene_instance: &mut GltfSceneInstance,
     active_camera_found: &mut bool,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
@@ -709,6 +759,9 @@ fn load_node(
     )));
 
     node.insert(node_name(gltf_node));
+    if let Some(name) = gltf_node.name() {
+        scene_instance.nodes.insert(name.to_string(), node.id());
+    }
 
     if let Some(extras) = gltf_node.extras() {
         node.insert(super::GltfExtras {
@@ -803,6 +856,18 @@ fn load_node(
                 }
                 if let Some(name) = mesh.name() {
                     mesh_entity.insert(Name::new(name.to_string()));
+                    scene_instance
+                        .meshes
+                        .entry(name.to_string())
+                        .or_default()
+                        .push(mesh_entity.id());
+                }
+                if let Some(name) = material.name() {
+                    scene_instance
+                        .materials
+                        .entry(name.to_string())
+                        .or_default()
+                        .push(mesh_entity.id());
                 }
                 // Mark for adding skinned mesh
                 if let Some(skin) = gltf_node.skin() {
@@ -896,6 +961,7 @@ fn load_node(
                 buffer_data,
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
+                scene_instance,
                 active_camera_found,
             ) {
                 gltf_error = Some(err);
@@ -910,6 +976,25 @@ fn load_node(
     }
 }
 
+/// Returns the [reflectance](StandardMaterial::reflectance) and the
+/// [specular tint](StandardMaterial::specular_tint) of a glTF material, from its index of
+/// refraction and its `KHR_materials_specular` extension.
+fn specular(material: &Material) -> (f32, Color) {
+    let ior = material.ior().unwrap_or(1.5);
+    let (specular_factor, specular_color) =
+        material.specular().map_or((1.0, [1.0; 3]), |specular| {
+            (specular.specular_factor(), specular.specular_color_factor())
+        });
+    // glTF derives the reflectance at normal incidence of non-metals from their index of
+    // refraction, which bevy remaps to `0.16 * reflectance^2`
+    let f0 = ((ior - 1.0) / (ior + 1.0)).powi(2) * specular_factor;
+    let reflectance = (f0 / 0.16).sqrt();
+    (
+        reflectance,
+        Color::rgb_linear(specular_color[0], specular_color[1], specular_color[2]),
+    )
+}
+
 /// Returns the label for the `mesh`.
 fn mesh_label(mesh: &gltf::Mesh) -> String {
     format!("Mesh{}", mesh.index())
@@ -948,6 +1033,12 @@ fn skin_label(skin: &gltf::Skin) -> String {
     format!("Skin{}", skin.index())
 }
 
+/// Returns the label for the asset of the given `kind` with the provided `name`, such as
+/// `Node:Turret`.
+fn named_label(kind: &str, name: &str) -> String {
+    format!("{}:{}", kind, name)
+}
+
 /// Extracts the texture sampler data from the glTF texture.
 fn texture_sampler<'a>(texture: &gltf::Texture) -> SamplerDescriptor<'a> {
     let gltf_sampler = texture.sampler();
@@ -1161,7 +1252,10 @@ mod test {
     use std::path::PathBuf;
 
     use super::resolve_node_hierarchy;
-    use crate::GltfNode;
+    use crate::{GltfNode, GltfSceneInstance};
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::world::World;
+    use bevy_scene::Scene;
 
     impl GltfNode {
         fn empty() -> Self {
@@ -1172,6 +1266,69 @@ mod test {
             }
         }
     }
+    #[test]
+    fn scene_instance_refers_to_spawned_entities() {
+        let mut scene_world = World::new();
+        let turret = scene_world.spawn_empty().id();
+        let barrel = scene_world.spawn_empty().id();
+        scene_world.spawn(GltfSceneInstance {
+            nodes: [("Turret".to_string(), turret)].into_iter().collect(),
+            meshes: [("Barrel".to_string(), vec![barrel])].into_iter().collect(),
+            materials: [("Metal".to_string(), vec![turret, barrel])]
+                .into_iter()
+                .collect(),
+        });
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<GltfSceneInstance>();
+
+        let mut world = World::new();
+        world.spawn_empty();
+        let instance_info = Scene::new(scene_world)
+            .write_to_world_with(&mut world, &type_registry)
+            .unwrap();
+
+        let turret = instance_info.entity_map.get(turret).unwrap();
+        let barrel = instance_info.entity_map.get(barrel).unwrap();
+        let scene_instance = world.query::<&GltfSceneInstance>().single(&world);
+        assert_eq!(scene_instance.node("Turret"), Some(turret));
+        assert_eq!(scene_instance.node("Hull"), None);
+        assert_eq!(scene_instance.mesh_entities("Barrel"), &[barrel]);
+        assert_eq!(scene_instance.material_entities("Metal"), &[turret, barrel]);
+    }
+
+    #[test]
+    fn material_specular() {
+        let gltf = gltf::Gltf::from_slice(
+            br#"{
+                "asset": { "version": "2.0" },
+                "extensionsUsed": ["KHR_materials_ior", "KHR_materials_specular"],
+                "materials": [
+                    {},
+                    {
+                        "extensions": {
+                            "KHR_materials_ior": { "ior": 2.0 },
+                            "KHR_materials_specular": {
+                                "specularFactor": 0.5,
+                                "specularColorFactor": [1.0, 0.5, 0.0]
+                            }
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut materials = gltf.materials();
+
+        let (reflectance, specular_tint) = super::specular(&materials.next().unwrap());
+        assert!((reflectance - 0.5).abs() < 1e-6);
+        assert_eq!(specular_tint.as_linear_rgba_f32(), [1.0, 1.0, 1.0, 1.0]);
+
+        let (reflectance, specular_tint) = super::specular(&materials.next().unwrap());
+        let f0 = (1.0f32 / 3.0).powi(2) * 0.5;
+        assert!((0.16 * reflectance * reflectance - f0).abs() < 1e-6);
+        assert_eq!(specular_tint.as_linear_rgba_f32(), [1.0, 0.5, 0.0, 1.0]);
+    }
+
     #[test]
     fn node_hierarchy_single_node() {
         let result = resolve_node_hierarchy(
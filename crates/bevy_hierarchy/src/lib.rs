@@ -16,6 +16,9 @@ pub use child_builder::*;
 mod events;
 pub use events::*;
 
+mod query_extension;
+pub use query_extension::*;
+
 mod valid_parent_check_plugin;
 pub use valid_parent_check_plugin::*;
 
@@ -23,7 +26,8 @@ pub use valid_parent_check_plugin::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        child_builder::*, components::*, hierarchy::*, HierarchyPlugin, ValidParentCheckPlugin,
+        child_builder::*, components::*, hierarchy::*, query_extension::*, HierarchyPlugin,
+        ValidParentCheckPlugin,
     };
 }
 
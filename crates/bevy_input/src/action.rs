@@ -0,0 +1,529 @@
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, Input, InputSystem,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{
+    prelude::Component,
+    schedule::IntoSystemDescriptor,
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy_utils::HashMap;
+use std::{hash::Hash, marker::PhantomData};
+
+/// An input that can trigger an action of an [`InputMap`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputBinding {
+    /// A key of the keyboard.
+    Keyboard(KeyCode),
+    /// A button of the mouse.
+    Mouse(MouseButton),
+    /// A button of the gamepad, its value being how far it's pressed for analog buttons.
+    GamepadButton(GamepadButtonType),
+    /// A gamepad axis in both directions, its value going from `-1.0` to `1.0`.
+    ///
+    /// The axis is ignored until it's moved past the `deadzone`.
+    GamepadAxis {
+        /// The axis of the gamepad.
+        axis: GamepadAxisType,
+        /// How far the axis must be moved, from `0.0` to `1.0`, to trigger the action.
+        deadzone: f32,
+    },
+    /// A gamepad axis moved in one direction, its value going from `0.0` to `1.0`, like a stick
+    /// pushed to the right.
+    GamepadAxisDirection {
+        /// The axis of the gamepad.
+        axis: GamepadAxisType,
+        /// `true` for the positive direction of the axis, `false` for the negative one.
+        positive: bool,
+        /// How far the axis must be moved, from `0.0` to `1.0`, to trigger the action.
+        deadzone: f32,
+    },
+    /// Several inputs pressed together, like `Ctrl + S`, its value being the one of the input
+    /// moved the least.
+    Chord(Vec<InputBinding>),
+}
+
+impl InputBinding {
+    /// A gamepad axis moved in one direction, with a deadzone of `0.1`.
+    pub fn axis_direction(axis: GamepadAxisType, positive: bool) -> Self {
+        Self::GamepadAxisDirection {
+            axis,
+            positive,
+            deadzone: 0.1,
+        }
+    }
+
+    /// Several inputs pressed together.
+    pub fn chord(inputs: impl IntoIterator<Item = impl Into<InputBinding>>) -> Self {
+        Self::Chord(inputs.into_iter().map(Into::into).collect())
+    }
+
+    /// The value of the input, `0.0` if it isn't pressed.
+    fn value(&self, inputs: &ActionInputs, gamepad: Option<Gamepad>) -> f32 {
+        match self {
+            InputBinding::Keyboard(key) => inputs.keyboard.pressed(*key) as u8 as f32,
+            InputBinding::Mouse(button) => inputs.mouse.pressed(*button) as u8 as f32,
+            InputBinding::GamepadButton(button_type) => {
+                inputs.max_gamepad_value(gamepad, |gamepad| {
+                    let button = GamepadButton::new(gamepad, *button_type);
+                    if inputs.gamepad_buttons.pressed(button) {
+                        inputs.gamepad_button_axes.get(button).unwrap_or(1.0)
+                    } else {
+                        0.0
+                    }
+                })
+            }
+            InputBinding::GamepadAxis { axis, deadzone } => {
+                inputs.max_gamepad_value(gamepad, |gamepad| {
+                    let value = inputs.gamepad_axis_value(gamepad, *axis);
+                    if value.abs() > *deadzone {
+                        value
+                    } else {
+                        0.0
+                    }
+                })
+            }
+            InputBinding::GamepadAxisDirection {
+                axis,
+                positive,
+                deadzone,
+            } => inputs.max_gamepad_value(gamepad, |gamepad| {
+                let value = inputs.gamepad_axis_value(gamepad, *axis);
+                let value = if *positive { value } else { -value };
+                if value > *deadzone {
+                    value
+                } else {
+                    0.0
+                }
+            }),
+            InputBinding::Chord(bindings) => bindings
+                .iter()
+                .map(|binding| binding.value(inputs, gamepad))
+                .reduce(|a, b| if b.abs() < a.abs() { b } else { a })
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        InputBinding::Keyboard(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        InputBinding::Mouse(button)
+    }
+}
+
+impl From<GamepadButtonType> for InputBinding {
+    fn from(button: GamepadButtonType) -> Self {
+        InputBinding::GamepadButton(button)
+    }
+}
+
+/// The inputs read to update the [`ActionState`]s.
+struct ActionInputs<'a> {
+    keyboard: &'a Input<KeyCode>,
+    mouse: &'a Input<MouseButton>,
+    gamepads: &'a Gamepads,
+    gamepad_buttons: &'a Input<GamepadButton>,
+    gamepad_button_axes: &'a Axis<GamepadButton>,
+    gamepad_axes: &'a Axis<GamepadAxis>,
+}
+
+impl ActionInputs<'_> {
+    /// The value of an input on a gamepad, or the one with the largest magnitude on all the
+    /// gamepads if `None`
+    fn max_gamepad_value(&self, gamepad: Option<Gamepad>, value: impl Fn(Gamepad) -> f32) -> f32 {
+        match gamepad {
+            Some(gamepad) => value(gamepad),
+            None => {
+                self.gamepads
+                    .iter()
+                    .map(value)
+                    .fold(0.0, |a, b| if b.abs() > a.abs() { b } else { a })
+            }
+        }
+    }
+
+    fn gamepad_axis_value(&self, gamepad: Gamepad, axis_type: GamepadAxisType) -> f32 {
+        self.gamepad_axes
+            .get(GamepadAxis::new(gamepad, axis_type))
+            .unwrap_or(0.0)
+    }
+}
+
+/// Binds the actions of type `A`, like jumping or moving, to the inputs triggering them.
+///
+/// ## Usage
+///
+/// An action can be bound to several inputs, any of them triggering it. The [`ActionState`] of
+/// the actions is updated by the [`InputMapPlugin`], either from an [`InputMap`] resource into the
+/// [`ActionState`] resource, or from an [`InputMap`] component into the [`ActionState`]
+/// component of the same entity, to give each local player their own controls.
+///
+/// The bindings can be changed at any time, to let the players rebind their controls.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_input::{action::{InputBinding, InputMap}, gamepad::GamepadButtonType, keyboard::KeyCode};
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Jump,
+///     Save,
+/// }
+///
+/// let input_map = InputMap::default()
+///     .with(Action::Jump, KeyCode::Space)
+///     .with(Action::Jump, GamepadButtonType::South)
+///     .with(
+///         Action::Save,
+///         InputBinding::chord([KeyCode::LControl, KeyCode::S]),
+///     );
+/// ```
+#[derive(Debug, Clone, Resource, Component)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap<A: Copy + Eq + Hash + Send + Sync + 'static> {
+    bindings: HashMap<A, Vec<InputBinding>>,
+    /// The gamepad whose inputs are read, or `None` to read the inputs of all the gamepads.
+    pub gamepad: Option<Gamepad>,
+}
+
+impl<A: Copy + Eq + Hash + Send + Sync + 'static> Default for InputMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::default(),
+            gamepad: None,
+        }
+    }
+}
+
+impl<A> InputMap<A>
+where
+    A: Copy + Eq + Hash + Send + Sync + 'static,
+{
+    /// Binds an input to an action, in addition to its other bindings.
+    pub fn insert(&mut self, action: A, binding: impl Into<InputBinding>) -> &mut Self {
+        self.bindings
+            .entry(action)
+            .or_default()
+            .push(binding.into());
+        self
+    }
+
+    /// Returns the map with an input bound to an action.
+    #[must_use]
+    pub fn with(mut self, action: A, binding: impl Into<InputBinding>) -> Self {
+        self.insert(action, binding);
+        self
+    }
+
+    /// Returns the map reading the inputs of a single gamepad.
+    #[must_use]
+    pub fn with_gamepad(mut self, gamepad: Gamepad) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Returns the inputs bound to an action.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Unbinds an input from an action, returning `true` if it was bound.
+    pub fn remove(&mut self, action: A, binding: &InputBinding) -> bool {
+        match self.bindings.get_mut(&action) {
+            Some(bindings) => {
+                let len = bindings.len();
+                bindings.retain(|other| other != binding);
+                bindings.len() != len
+            }
+            None => false,
+        }
+    }
+
+    /// Unbinds all the inputs of an action.
+    pub fn clear_action(&mut self, action: A) {
+        self.bindings.remove(&action);
+    }
+
+    /// Returns an iterator over the actions and their bindings.
+    pub fn iter(&self) -> impl Iterator<Item = (A, &[InputBinding])> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (*action, bindings.as_slice()))
+    }
+
+    /// The value of an action, being the one of its binding with the largest magnitude.
+    fn value(&self, action: A, inputs: &ActionInputs) -> f32 {
+        self.bindings(action)
+            .iter()
+            .map(|binding| binding.value(inputs, self.gamepad))
+            .fold(0.0, |a, b| if b.abs() > a.abs() { b } else { a })
+    }
+}
+
+/// The state of the actions of type `A`, updated from their [`InputMap`].
+///
+/// ## Usage
+///
+/// Like an [`Input`], an action is pressed while any of its bindings is, and is just pressed or
+/// just released for one frame. Its value tells how far it's pressed, for the analog inputs.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_input::action::ActionState;
+/// # #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// # enum Action {
+/// #     Jump,
+/// # }
+/// fn jump(action_state: Res<ActionState<Action>>) {
+///     if action_state.just_pressed(Action::Jump) {
+///         // Jump
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Resource, Component)]
+pub struct ActionState<A: Copy + Eq + Hash + Send + Sync + 'static> {
+    input: Input<A>,
+    values: HashMap<A, f32>,
+}
+
+impl<A: Copy + Eq + Hash + Send + Sync + 'static> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            input: Input::default(),
+            values: HashMap::default(),
+        }
+    }
+}
+
+impl<A> ActionState<A>
+where
+    A: Copy + Eq + Hash + Send + Sync + 'static,
+{
+    /// Returns `true` if the `action` is pressed.
+    pub fn pressed(&self, action: A) -> bool {
+        self.input.pressed(action)
+    }
+
+    /// Returns `true` if the `action` has just been pressed.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.input.just_pressed(action)
+    }
+
+    /// Returns `true` if the `action` has just been released.
+    pub fn just_released(&self, action: A) -> bool {
+        self.input.just_released(action)
+    }
+
+    /// Returns how far the `action` is pressed, `1.0` for a pressed button, or the position of
+    /// an axis, and `0.0` if it isn't pressed.
+    pub fn value(&self, action: A) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    /// An iterator visiting every pressed action in arbitrary order.
+    pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.input.get_pressed()
+    }
+
+    /// Updates the state of the actions from the current inputs.
+    fn update(&mut self, input_map: &InputMap<A>, inputs: &ActionInputs) {
+        self.input.clear();
+        self.values.clear();
+        for (action, _) in input_map.iter() {
+            let value = input_map.value(action, inputs);
+            if value != 0.0 {
+                self.values.insert(action, value);
+                self.input.press(action);
+            }
+        }
+        let released: Vec<A> = self
+            .input
+            .get_pressed()
+            .filter(|action| !self.values.contains_key(action))
+            .copied()
+            .collect();
+        for action in released {
+            self.input.release(action);
+        }
+    }
+}
+
+/// Updates the [`ActionState`] resource from the [`InputMap`] resource, and the [`ActionState`]
+/// components from the [`InputMap`] components of their entities.
+#[allow(clippy::too_many_arguments)]
+pub fn action_state_system<A: Copy + Eq + Hash + Send + Sync + 'static>(
+    keyboard: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_button_axes: Res<Axis<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    input_map: Option<Res<InputMap<A>>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    mut query: Query<(&InputMap<A>, &mut ActionState<A>)>,
+) {
+    let inputs = ActionInputs {
+        keyboard: &keyboard,
+        mouse: &mouse,
+        gamepads: &gamepads,
+        gamepad_buttons: &gamepad_buttons,
+        gamepad_button_axes: &gamepad_button_axes,
+        gamepad_axes: &gamepad_axes,
+    };
+    if let (Some(input_map), Some(mut action_state)) = (input_map, action_state) {
+        action_state.update(&input_map, &inputs);
+    }
+    for (input_map, mut action_state) in &mut query {
+        action_state.update(input_map, &inputs);
+    }
+}
+
+/// Updates the [`ActionState`] of the actions of type `A` from their [`InputMap`].
+///
+/// The [`ActionState`] resource is added by the plugin, to be updated once an [`InputMap`]
+/// resource is inserted.
+pub struct InputMapPlugin<A>(PhantomData<A>);
+
+impl<A> Default for InputMapPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A> Plugin for InputMapPlugin<A>
+where
+    A: Copy + Eq + Hash + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState<A>>().add_system_to_stage(
+            CoreStage::PreUpdate,
+            action_state_system::<A>.after(InputSystem),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    enum Action {
+        Jump,
+        Move,
+        Save,
+    }
+
+    #[derive(Default)]
+    struct TestInputs {
+        keyboard: Input<KeyCode>,
+        mouse: Input<MouseButton>,
+        gamepads: Gamepads,
+        gamepad_buttons: Input<GamepadButton>,
+        gamepad_button_axes: Axis<GamepadButton>,
+        gamepad_axes: Axis<GamepadAxis>,
+    }
+
+    impl TestInputs {
+        fn update(&mut self, input_map: &InputMap<Action>, action_state: &mut ActionState<Action>) {
+            action_state.update(
+                input_map,
+                &ActionInputs {
+                    keyboard: &self.keyboard,
+                    mouse: &self.mouse,
+                    gamepads: &self.gamepads,
+                    gamepad_buttons: &self.gamepad_buttons,
+                    gamepad_button_axes: &self.gamepad_button_axes,
+                    gamepad_axes: &self.gamepad_axes,
+                },
+            );
+            self.keyboard.clear();
+            self.mouse.clear();
+        }
+    }
+
+    #[test]
+    fn buttons_and_chords() {
+        let input_map = InputMap::default()
+            .with(Action::Jump, KeyCode::Space)
+            .with(Action::Jump, MouseButton::Right)
+            .with(
+                Action::Save,
+                InputBinding::chord([KeyCode::LControl, KeyCode::S]),
+            );
+        let mut action_state = ActionState::default();
+        let mut inputs = TestInputs::default();
+
+        inputs.mouse.press(MouseButton::Right);
+        inputs.keyboard.press(KeyCode::S);
+        inputs.update(&input_map, &mut action_state);
+        assert!(action_state.just_pressed(Action::Jump));
+        assert_eq!(action_state.value(Action::Jump), 1.0);
+        assert!(!action_state.pressed(Action::Save));
+
+        // Still pressed with another binding
+        inputs.mouse.release(MouseButton::Right);
+        inputs.keyboard.press(KeyCode::Space);
+        inputs.keyboard.press(KeyCode::LControl);
+        inputs.update(&input_map, &mut action_state);
+        assert!(action_state.pressed(Action::Jump));
+        assert!(!action_state.just_pressed(Action::Jump));
+        assert!(action_state.just_pressed(Action::Save));
+
+        inputs.keyboard.release(KeyCode::Space);
+        inputs.update(&input_map, &mut action_state);
+        assert!(action_state.just_released(Action::Jump));
+        assert_eq!(action_state.value(Action::Jump), 0.0);
+        assert!(action_state.pressed(Action::Save));
+    }
+
+    #[test]
+    fn gamepad_axes() {
+        let gamepad = Gamepad::new(0);
+        let mut input_map = InputMap::default()
+            .with(
+                Action::Move,
+                InputBinding::GamepadAxis {
+                    axis: GamepadAxisType::LeftStickX,
+                    deadzone: 0.2,
+                },
+            )
+            .with_gamepad(gamepad);
+        let mut action_state = ActionState::default();
+        let mut inputs = TestInputs::default();
+        let stick = GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX);
+
+        inputs.gamepad_axes.set(stick, -0.1);
+        inputs.update(&input_map, &mut action_state);
+        assert!(!action_state.pressed(Action::Move));
+
+        inputs.gamepad_axes.set(stick, -0.5);
+        inputs.update(&input_map, &mut action_state);
+        assert!(action_state.just_pressed(Action::Move));
+        assert_eq!(action_state.value(Action::Move), -0.5);
+
+        // Rebinding to the positive direction only
+        input_map.clear_action(Action::Move);
+        input_map.insert(
+            Action::Move,
+            InputBinding::axis_direction(GamepadAxisType::LeftStickX, true),
+        );
+        inputs.update(&input_map, &mut action_state);
+        assert!(action_state.just_released(Action::Move));
+
+        inputs.gamepad_axes.set(stick, 0.7);
+        inputs.update(&input_map, &mut action_state);
+        assert_eq!(action_state.value(Action::Move), 0.7);
+    }
+}
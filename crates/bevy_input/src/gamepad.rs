@@ -2,6 +2,7 @@ use crate::{Axis, Input};
 use bevy_ecs::event::{EventReader, EventWriter};
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_utils::{tracing::info, HashMap, HashSet};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that occur when setting axis settings for gamepad input.
@@ -436,6 +437,106 @@ impl GamepadAxis {
     }
 }
 
+/// The intensity of the two motors of a gamepad rumble, from `0.0` to `1.0`.
+///
+/// The strong motor is the low frequency one, usually on the left, and the weak motor is the
+/// high frequency one, usually on the right.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadRumbleIntensity {
+    /// The intensity of the strong, low frequency motor.
+    pub strong_motor: f32,
+    /// The intensity of the weak, high frequency motor.
+    pub weak_motor: f32,
+}
+
+impl GamepadRumbleIntensity {
+    /// Rumbles both motors at their maximum intensity.
+    pub const MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 1.0,
+    };
+
+    /// Rumbles the weak motor at its maximum intensity.
+    pub const WEAK_MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 0.0,
+        weak_motor: 1.0,
+    };
+
+    /// Rumbles the strong motor at its maximum intensity.
+    pub const STRONG_MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 0.0,
+    };
+
+    /// Creates a new [`GamepadRumbleIntensity`] only rumbling the weak motor.
+    pub const fn weak_motor(intensity: f32) -> Self {
+        Self {
+            strong_motor: 0.0,
+            weak_motor: intensity,
+        }
+    }
+
+    /// Creates a new [`GamepadRumbleIntensity`] only rumbling the strong motor.
+    pub const fn strong_motor(intensity: f32) -> Self {
+        Self {
+            strong_motor: intensity,
+            weak_motor: 0.0,
+        }
+    }
+}
+
+/// An event to make a [`Gamepad`] rumble, or to stop its rumbles.
+///
+/// ## Usage
+///
+/// The rumbles are played by the gamepad backend, like the `bevy_gilrs` crate, if the gamepad
+/// supports force feedback. Rumbles requested for the same gamepad play together, the intensity
+/// of each motor being the sum of the intensities of the rumbles, capped at `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_input::gamepad::{Gamepad, Gamepads, GamepadRumbleIntensity, GamepadRumbleRequest};
+/// # use bevy_ecs::{event::EventWriter, system::Res};
+/// # use std::time::Duration;
+/// fn rumble_on_hit(gamepads: Res<Gamepads>, mut rumble_requests: EventWriter<GamepadRumbleRequest>) {
+///     for gamepad in gamepads.iter() {
+///         rumble_requests.send(GamepadRumbleRequest::Add {
+///             gamepad,
+///             duration: Duration::from_millis(300),
+///             intensity: GamepadRumbleIntensity::strong_motor(0.8),
+///         });
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadRumbleRequest {
+    /// Adds a rumble to the ones the gamepad is playing.
+    Add {
+        /// The gamepad to rumble.
+        gamepad: Gamepad,
+        /// How long the rumble lasts.
+        duration: Duration,
+        /// How strong the rumble is.
+        intensity: GamepadRumbleIntensity,
+    },
+    /// Stops all the rumbles of the gamepad.
+    Stop {
+        /// The gamepad to stop rumbling.
+        gamepad: Gamepad,
+    },
+}
+
+impl GamepadRumbleRequest {
+    /// Returns the gamepad the request is for.
+    pub fn gamepad(&self) -> Gamepad {
+        match self {
+            Self::Add { gamepad, .. } | Self::Stop { gamepad } => *gamepad,
+        }
+    }
+}
+
 /// Settings for all [`Gamepad`]s.
 ///
 /// ## Usage
@@ -1,3 +1,4 @@
+pub mod action;
 mod axis;
 pub mod gamepad;
 mod input;
@@ -12,13 +13,14 @@ pub use input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionState, InputBinding, InputMap, InputMapPlugin},
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
-            GamepadEventType, Gamepads,
+            GamepadEventType, GamepadRumbleIntensity, GamepadRumbleRequest, Gamepads,
         },
         keyboard::{KeyCode, ScanCode},
         mouse::MouseButton,
-        touch::{TouchInput, Touches},
+        touch::{PinchGesture, RotationGesture, TapGesture, TouchInput, Touches},
         Axis, Input,
     };
 }
@@ -27,11 +29,14 @@ use bevy_app::prelude::*;
 use keyboard::{keyboard_input_system, KeyCode, KeyboardInput, ScanCode};
 use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
 use prelude::Gamepads;
-use touch::{touch_screen_input_system, TouchInput, Touches};
+use touch::{
+    touch_gesture_system, touch_screen_input_system, PinchGesture, RotationGesture, TapGesture,
+    TouchGestureSettings, TouchInput, Touches,
+};
 
 use gamepad::{
     gamepad_connection_system, gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent,
-    GamepadEventRaw, GamepadSettings,
+    GamepadEventRaw, GamepadRumbleRequest, GamepadSettings,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -64,6 +69,7 @@ impl Plugin for InputPlugin {
             // gamepad
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
+            .add_event::<GamepadRumbleRequest>()
             .init_resource::<GamepadSettings>()
             .init_resource::<Gamepads>()
             .init_resource::<Input<GamepadButton>>()
@@ -79,10 +85,18 @@ impl Plugin for InputPlugin {
             )
             // touch
             .add_event::<TouchInput>()
+            .add_event::<PinchGesture>()
+            .add_event::<RotationGesture>()
+            .add_event::<TapGesture>()
             .init_resource::<Touches>()
+            .init_resource::<TouchGestureSettings>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 touch_screen_input_system.label(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                touch_gesture_system.after(InputSystem),
             );
     }
 }
@@ -1,7 +1,7 @@
-use bevy_ecs::event::EventReader;
-use bevy_ecs::system::{ResMut, Resource};
+use bevy_ecs::event::{EventReader, EventWriter};
+use bevy_ecs::system::{Local, Res, ResMut, Resource};
 use bevy_math::Vec2;
-use bevy_utils::HashMap;
+use bevy_utils::{Duration, HashMap, Instant};
 
 /// A touch input event.
 ///
@@ -350,6 +350,223 @@ pub fn touch_screen_input_system(
     }
 }
 
+/// A pinch of two touches moving apart or closer, usually to zoom.
+///
+/// ## Usage
+///
+/// The event is sent by the [`touch_gesture_system`] at each frame the two first touches of the
+/// [`Touches`] move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinchGesture {
+    /// The ratio between the distance of the touches and their distance at the previous frame,
+    /// greater than `1.0` when they move apart.
+    pub scale: f32,
+    /// How fast the distance of the touches changes, as the change of the ratio per second.
+    pub velocity: f32,
+    /// The point between the two touches, in logical pixels.
+    pub center: Vec2,
+}
+
+/// A rotation of two touches around each other.
+///
+/// ## Usage
+///
+/// The event is sent by the [`touch_gesture_system`] at each frame the two first touches of the
+/// [`Touches`] move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotationGesture {
+    /// The angle the touches turned by since the previous frame, in radians, positive when they
+    /// turn clockwise on the screen.
+    pub delta: f32,
+    /// How fast the touches turn, in radians per second.
+    pub velocity: f32,
+    /// The point between the two touches, in logical pixels.
+    pub center: Vec2,
+}
+
+/// A short touch that didn't move, sent when the touch is released.
+///
+/// ## Usage
+///
+/// The event is sent by the [`touch_gesture_system`]. Taps following each other quickly at the
+/// same place are counted, so a double tap is a tap with a `count` of `2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TapGesture {
+    /// The position of the tap, in logical pixels.
+    pub position: Vec2,
+    /// The number of taps in a row at this place, including this one.
+    pub count: u32,
+}
+
+/// The thresholds used by the [`touch_gesture_system`] to recognize the [`TapGesture`]s.
+#[derive(Debug, Clone, Resource)]
+pub struct TouchGestureSettings {
+    /// How long a touch can last to be a tap.
+    pub tap_max_duration: Duration,
+    /// How far a touch can move, in logical pixels, to be a tap.
+    pub tap_max_distance: f32,
+    /// How long after a tap the next one is counted with it, like for a double tap.
+    pub multi_tap_max_interval: Duration,
+    /// How far from a tap, in logical pixels, the next one is counted with it.
+    pub multi_tap_max_distance: f32,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: Duration::from_millis(300),
+            tap_max_distance: 10.0,
+            multi_tap_max_interval: Duration::from_millis(300),
+            multi_tap_max_distance: 30.0,
+        }
+    }
+}
+
+/// The gestures recognized during a frame.
+#[derive(Debug, Default, PartialEq)]
+struct Gestures {
+    pinch: Option<PinchGesture>,
+    rotation: Option<RotationGesture>,
+    taps: Vec<TapGesture>,
+}
+
+/// The touches followed across frames to recognize the gestures.
+#[derive(Default)]
+pub struct TouchGestureRecognizer {
+    last_update: Option<Instant>,
+    /// The ids and the positions of the two touches of the pinch and the rotation at the
+    /// previous frame
+    pair: Option<[(u64, Vec2); 2]>,
+    /// When each touch started, and whether other touches were pressed with it, which prevents
+    /// it from being a tap
+    starts: HashMap<u64, (Instant, bool)>,
+    /// When and where the last tap happened, and how many taps were counted with it
+    last_tap: Option<(Instant, Vec2, u32)>,
+}
+
+impl TouchGestureRecognizer {
+    fn update(
+        &mut self,
+        touches: &Touches,
+        settings: &TouchGestureSettings,
+        now: Instant,
+    ) -> Gestures {
+        let mut gestures = Gestures::default();
+        let delta_seconds = self
+            .last_update
+            .map_or(0.0, |last_update| (now - last_update).as_secs_f32());
+        self.last_update = Some(now);
+
+        for touch in touches.iter_just_pressed() {
+            self.starts.insert(touch.id, (now, false));
+        }
+        let mut pressed: Vec<&Touch> = touches.iter().collect();
+        if pressed.len() > 1 {
+            for touch in &pressed {
+                if let Some((_, multi_touch)) = self.starts.get_mut(&touch.id) {
+                    *multi_touch = true;
+                }
+            }
+        }
+
+        // The pinch and the rotation follow the two first touches
+        pressed.sort_by_key(|touch| touch.id);
+        let pair = match pressed[..] {
+            [a, b, ..] => Some([(a.id, a.position), (b.id, b.position)]),
+            _ => None,
+        };
+        if let (Some([(a, a_position), (b, b_position)]), Some(previous)) = (pair, self.pair) {
+            let [(previous_a, previous_a_position), (previous_b, previous_b_position)] = previous;
+            let (offset, previous_offset) = (
+                b_position - a_position,
+                previous_b_position - previous_a_position,
+            );
+            if a == previous_a
+                && b == previous_b
+                && offset != previous_offset
+                && previous_offset != Vec2::ZERO
+            {
+                let center = (a_position + b_position) / 2.0;
+                let per_second = |value: f32| {
+                    if delta_seconds > 0.0 {
+                        value / delta_seconds
+                    } else {
+                        0.0
+                    }
+                };
+                let scale = offset.length() / previous_offset.length();
+                if scale != 1.0 {
+                    gestures.pinch = Some(PinchGesture {
+                        scale,
+                        velocity: per_second(scale - 1.0),
+                        center,
+                    });
+                }
+                let delta = previous_offset.angle_between(offset);
+                if delta != 0.0 && !delta.is_nan() {
+                    gestures.rotation = Some(RotationGesture {
+                        delta,
+                        velocity: per_second(delta),
+                        center,
+                    });
+                }
+            }
+        }
+        self.pair = pair;
+
+        for touch in touches.iter_just_cancelled() {
+            self.starts.remove(&touch.id);
+        }
+        for touch in touches.iter_just_released() {
+            let (start, multi_touch) = self.starts.remove(&touch.id).unwrap_or((now, false));
+            if multi_touch
+                || now - start > settings.tap_max_duration
+                || touch.distance().length() > settings.tap_max_distance
+            {
+                continue;
+            }
+            let count = match self.last_tap {
+                Some((time, position, count))
+                    if now - time <= settings.multi_tap_max_interval
+                        && position.distance(touch.position) <= settings.multi_tap_max_distance =>
+                {
+                    count + 1
+                }
+                _ => 1,
+            };
+            self.last_tap = Some((now, touch.position, count));
+            gestures.taps.push(TapGesture {
+                position: touch.position,
+                count,
+            });
+        }
+        gestures
+    }
+}
+
+/// Recognizes the [`PinchGesture`]s, [`RotationGesture`]s and [`TapGesture`]s from the
+/// [`Touches`].
+pub fn touch_gesture_system(
+    touches: Res<Touches>,
+    settings: Res<TouchGestureSettings>,
+    mut recognizer: Local<TouchGestureRecognizer>,
+    mut pinch_events: EventWriter<PinchGesture>,
+    mut rotation_events: EventWriter<RotationGesture>,
+    mut tap_events: EventWriter<TapGesture>,
+) {
+    let gestures = recognizer.update(&touches, &settings, Instant::now());
+    if let Some(pinch) = gestures.pinch {
+        pinch_events.send(pinch);
+    }
+    if let Some(rotation) = gestures.rotation {
+        rotation_events.send(rotation);
+    }
+    tap_events.send_batch(gestures.taps);
+}
+
 #[cfg(test)]
 mod test {
 
@@ -527,4 +744,132 @@ mod test {
         assert!(touches.just_cancelled(touch_event.id));
         assert_eq!(touches.iter_just_cancelled().count(), 1);
     }
+
+    #[test]
+    fn pinch_and_rotation_gestures() {
+        use crate::{
+            touch::{TouchGestureRecognizer, TouchGestureSettings, TouchPhase},
+            TouchInput, Touches,
+        };
+        use bevy_math::Vec2;
+        use bevy_utils::{Duration, Instant};
+
+        let mut touches = Touches::default();
+        let mut recognizer = TouchGestureRecognizer::default();
+        let settings = TouchGestureSettings::default();
+        let mut now = Instant::now();
+        let mut frame = |touches: &mut Touches, events: &[(u64, TouchPhase, Vec2)]| {
+            touches.update();
+            for (id, phase, position) in events {
+                touches.process_touch_event(&TouchInput {
+                    phase: *phase,
+                    position: *position,
+                    force: None,
+                    id: *id,
+                });
+            }
+            now += Duration::from_millis(100);
+            recognizer.update(touches, &settings, now)
+        };
+
+        let gestures = frame(
+            &mut touches,
+            &[
+                (1, TouchPhase::Started, Vec2::new(100.0, 100.0)),
+                (2, TouchPhase::Started, Vec2::new(200.0, 100.0)),
+            ],
+        );
+        assert_eq!(gestures.pinch, None);
+
+        // Moving apart
+        let gestures = frame(
+            &mut touches,
+            &[
+                (1, TouchPhase::Moved, Vec2::new(50.0, 100.0)),
+                (2, TouchPhase::Moved, Vec2::new(250.0, 100.0)),
+            ],
+        );
+        let pinch = gestures.pinch.unwrap();
+        assert_eq!(pinch.scale, 2.0);
+        assert!((pinch.velocity - 10.0).abs() < 1e-3);
+        assert_eq!(pinch.center, Vec2::new(150.0, 100.0));
+        assert!(gestures.taps.is_empty());
+
+        // Turning a quarter clockwise on the screen, where y goes down
+        let gestures = frame(
+            &mut touches,
+            &[
+                (1, TouchPhase::Moved, Vec2::new(150.0, 0.0)),
+                (2, TouchPhase::Moved, Vec2::new(150.0, 200.0)),
+            ],
+        );
+        let rotation = gestures.rotation.unwrap();
+        assert!((rotation.delta - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+        assert!(gestures.pinch.is_none());
+
+        // Releasing the touches of a pinch isn't a tap
+        let gestures = frame(
+            &mut touches,
+            &[
+                (1, TouchPhase::Ended, Vec2::new(150.0, 0.0)),
+                (2, TouchPhase::Ended, Vec2::new(150.0, 200.0)),
+            ],
+        );
+        assert_eq!(gestures, Default::default());
+    }
+
+    #[test]
+    fn tap_gestures() {
+        use crate::{
+            touch::{TapGesture, TouchGestureRecognizer, TouchGestureSettings, TouchPhase},
+            TouchInput, Touches,
+        };
+        use bevy_math::Vec2;
+        use bevy_utils::{Duration, Instant};
+
+        let mut touches = Touches::default();
+        let mut recognizer = TouchGestureRecognizer::default();
+        let settings = TouchGestureSettings::default();
+        let mut now = Instant::now();
+        let mut tap = |touches: &mut Touches, id: u64, position: Vec2, duration: u64| {
+            touches.update();
+            touches.process_touch_event(&TouchInput {
+                phase: TouchPhase::Started,
+                position,
+                force: None,
+                id,
+            });
+            recognizer.update(touches, &settings, now);
+            now += Duration::from_millis(duration);
+            touches.update();
+            touches.process_touch_event(&TouchInput {
+                phase: TouchPhase::Ended,
+                position,
+                force: None,
+                id,
+            });
+            let gestures = recognizer.update(touches, &settings, now);
+            now += Duration::from_millis(100);
+            gestures.taps
+        };
+
+        let position = Vec2::new(100.0, 100.0);
+        assert_eq!(
+            tap(&mut touches, 1, position, 100),
+            vec![TapGesture { position, count: 1 }]
+        );
+        let position = Vec2::new(105.0, 100.0);
+        assert_eq!(
+            tap(&mut touches, 2, position, 100),
+            vec![TapGesture { position, count: 2 }]
+        );
+        // Too long to be a tap
+        assert_eq!(tap(&mut touches, 3, position, 500), vec![]);
+        // Too far to be counted with the previous tap
+        let position = Vec2::new(200.0, 100.0);
+        assert_eq!(
+            tap(&mut touches, 4, position, 100),
+            vec![TapGesture { position, count: 1 }]
+        );
+    }
 }
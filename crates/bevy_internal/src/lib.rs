@@ -115,6 +115,18 @@ pub mod core_pipeline {
     pub use bevy_core_pipeline::*;
 }
 
+#[cfg(feature = "bevy_debug_draw")]
+pub mod debug_draw {
+    //! Debug drawing and visualization tools.
+    pub use bevy_debug_draw::*;
+}
+
+#[cfg(feature = "bevy_dev_tools")]
+pub mod dev_tools {
+    //! Development tools, such as performance overlays.
+    pub use bevy_dev_tools::*;
+}
+
 #[cfg(feature = "bevy_gilrs")]
 pub mod gilrs {
     //! Bevy interface with `GilRs` - "Game Input Library for Rust" - to handle gamepad inputs.
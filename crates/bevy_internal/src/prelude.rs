@@ -23,6 +23,14 @@ pub use crate::animation::prelude::*;
 #[cfg(feature = "bevy_core_pipeline")]
 pub use crate::core_pipeline::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_debug_draw")]
+pub use crate::debug_draw::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_dev_tools")]
+pub use crate::dev_tools::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_pbr")]
 pub use crate::pbr::prelude::*;
@@ -6,9 +6,11 @@
 
 #![warn(missing_docs)]
 
+mod plane;
 mod ray;
 mod rect;
 
+pub use plane::Plane3d;
 pub use ray::Ray;
 pub use rect::Rect;
 
@@ -16,8 +18,8 @@ pub use rect::Rect;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        BVec2, BVec3, BVec4, EulerRot, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat, Ray, Rect,
-        UVec2, UVec3, UVec4, Vec2, Vec3, Vec4,
+        BVec2, BVec3, BVec4, EulerRot, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Plane3d, Quat, Ray,
+        Rect, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4,
     };
 }
 
@@ -0,0 +1,182 @@
+use crate::{Ray, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// An infinite plane in 3D space.
+///
+/// The plane contains every point `p` for which `normal.dot(p) == distance`, so `distance` is the
+/// signed distance of the plane from the origin along `normal`. The normal is always normalized,
+/// which the constructors ensure; upholding this invariant is up to the user when assigning the
+/// fields directly.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Plane3d {
+    /// The unit normal of the plane.
+    pub normal: Vec3,
+    /// The signed distance of the plane from the origin along [`Plane3d::normal`].
+    pub distance: f32,
+}
+
+impl Default for Plane3d {
+    /// The `XZ` plane, facing up.
+    fn default() -> Self {
+        Self {
+            normal: Vec3::Y,
+            distance: 0.0,
+        }
+    }
+}
+
+impl Plane3d {
+    /// Creates a plane from a normal and its signed distance from the origin along that normal.
+    ///
+    /// The normal is normalized, and doesn't need to be of unit length.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `normal` is zero or not finite.
+    #[inline]
+    pub fn new(normal: Vec3, distance: f32) -> Self {
+        let length = normal.length();
+        debug_assert!(
+            length.is_finite() && length > 0.0,
+            "the normal of a plane can't be zero or non-finite"
+        );
+        Self {
+            normal: normal / length,
+            distance: distance / length,
+        }
+    }
+
+    /// Creates a plane passing through `point`, facing in the direction of `normal`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `normal` is zero or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Plane3d, Vec3};
+    /// let plane = Plane3d::from_point_normal(Vec3::new(0., 2., 0.), Vec3::Y);
+    /// assert_eq!(plane.distance, 2.);
+    /// ```
+    #[inline]
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            distance: normal.dot(point),
+        }
+    }
+
+    /// The point of the plane closest to the origin.
+    #[inline]
+    pub fn origin(&self) -> Vec3 {
+        self.normal * self.distance
+    }
+
+    /// The signed distance from the plane to `point`.
+    ///
+    /// This is positive if the point is on the side the normal points to, and negative otherwise.
+    #[inline]
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) - self.distance
+    }
+
+    /// Projects `point` onto the plane, returning the closest point on the plane.
+    #[inline]
+    pub fn project_point(&self, point: Vec3) -> Vec3 {
+        point - self.normal * self.signed_distance(point)
+    }
+
+    /// Returns the distance along `ray` at which it intersects this plane, if it does.
+    ///
+    /// Returns `None` if the ray is parallel to the plane or points away from it. The
+    /// intersection point can be computed with [`Ray::get_point`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Plane3d, Ray, Vec3};
+    /// let ground = Plane3d::default();
+    /// let ray = Ray {
+    ///     origin: Vec3::new(1., 4., 0.),
+    ///     direction: Vec3::NEG_Y,
+    /// };
+    /// let distance = ground.intersect_ray(ray).unwrap();
+    /// assert_eq!(ray.get_point(distance), Vec3::new(1., 0., 0.));
+    /// ```
+    #[inline]
+    pub fn intersect_ray(&self, ray: Ray) -> Option<f32> {
+        let denominator = self.normal.dot(ray.direction);
+        if denominator.abs() <= f32::EPSILON {
+            return None;
+        }
+        let distance = -self.signed_distance(ray.origin) / denominator;
+        (distance >= 0.0).then_some(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_normalizes() {
+        let plane = Plane3d::new(Vec3::new(0., 2., 0.), 4.);
+        assert_eq!(plane.normal, Vec3::Y);
+        assert_eq!(plane.distance, 2.);
+        assert_eq!(plane.origin(), Vec3::new(0., 2., 0.));
+    }
+
+    #[test]
+    fn signed_distance_and_projection() {
+        let plane = Plane3d::from_point_normal(Vec3::new(0., 0., 3.), Vec3::new(0., 0., -5.));
+        assert_eq!(plane.normal, Vec3::NEG_Z);
+        assert_eq!(plane.distance, -3.);
+
+        let point = Vec3::new(1., 2., 1.);
+        assert!((plane.signed_distance(point) - 2.).abs() < 1e-5);
+        assert!((plane.signed_distance(Vec3::new(0., 0., 5.)) + 2.).abs() < 1e-5);
+
+        let projected = plane.project_point(point);
+        assert!(projected.abs_diff_eq(Vec3::new(1., 2., 3.), 1e-5));
+        assert!(plane.signed_distance(projected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ray_intersection() {
+        let plane = Plane3d::new(Vec3::ONE, 1.);
+        let ray = Ray {
+            origin: Vec3::new(5., 5., 5.),
+            direction: Vec3::new(-1., -1., -1.).normalize(),
+        };
+        let distance = plane.intersect_ray(ray).unwrap();
+        let hit = ray.get_point(distance);
+        assert!(plane.signed_distance(hit).abs() < 1e-5);
+        assert!(hit.abs_diff_eq(Vec3::splat(1. / 3.), 1e-5));
+
+        // Pointing away from the plane.
+        let away = Ray {
+            origin: ray.origin,
+            direction: -ray.direction,
+        };
+        assert_eq!(plane.intersect_ray(away), None);
+    }
+
+    #[test]
+    fn parallel_ray_misses() {
+        let plane = Plane3d::default();
+        let ray = Ray {
+            origin: Vec3::new(0., 1., 0.),
+            direction: Vec3::X,
+        };
+        assert_eq!(plane.intersect_ray(ray), None);
+
+        // Also when it lies in the plane.
+        let ray = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::Z,
+        };
+        assert_eq!(plane.intersect_ray(ray), None);
+    }
+}
@@ -9,3 +9,143 @@ pub struct Ray {
     /// The direction of the ray.
     pub direction: Vec3,
 }
+
+impl Ray {
+    /// Returns the point at the given distance along the ray.
+    #[inline]
+    pub fn get_point(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Returns the distance along the ray at which it enters the axis-aligned box between `min`
+    /// and `max`, if it does.
+    ///
+    /// The distance is `0.0` if the origin of the ray is inside the box. Distances are in units of
+    /// the length of [`Ray::direction`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Ray, Vec3};
+    /// let ray = Ray {
+    ///     origin: Vec3::new(0., 0., 5.),
+    ///     direction: Vec3::NEG_Z,
+    /// };
+    /// assert_eq!(ray.intersect_box(Vec3::splat(-1.), Vec3::ONE), Some(4.));
+    /// ```
+    #[inline]
+    pub fn intersect_box(&self, min: Vec3, max: Vec3) -> Option<f32> {
+        let inverse_direction = self.direction.recip();
+        let t1 = (min - self.origin) * inverse_direction;
+        let t2 = (max - self.origin) * inverse_direction;
+        // NaNs from rays parallel to a face on its plane are ignored by `min`/`max`
+        let entry = t1.min(t2).max_element().max(0.0);
+        let exit = t1.max(t2).min_element();
+        (entry <= exit).then_some(entry)
+    }
+
+    /// Returns the distance along the ray at which it intersects the triangle with the vertices
+    /// `a`, `b` and `c`, if it does.
+    ///
+    /// Both faces of the triangle are hit. Distances are in units of the length of
+    /// [`Ray::direction`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Ray, Vec3};
+    /// let ray = Ray {
+    ///     origin: Vec3::new(0.2, 0.2, 1.),
+    ///     direction: Vec3::NEG_Z,
+    /// };
+    /// let distance = ray.intersect_triangle(Vec3::ZERO, Vec3::X, Vec3::Y);
+    /// assert_eq!(distance, Some(1.));
+    /// ```
+    #[inline]
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        // Möller–Trumbore intersection
+        let edge_ab = b - a;
+        let edge_ac = c - a;
+        let p = self.direction.cross(edge_ac);
+        let determinant = edge_ab.dot(p);
+        if determinant.abs() <= f32::EPSILON {
+            return None;
+        }
+        let inverse_determinant = determinant.recip();
+        let to_origin = self.origin - a;
+        let u = to_origin.dot(p) * inverse_determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = to_origin.cross(edge_ab);
+        let v = self.direction.dot(q) * inverse_determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let distance = edge_ac.dot(q) * inverse_determinant;
+        (distance >= 0.0).then_some(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_intersection() {
+        let (min, max) = (Vec3::new(-1., 0., -1.), Vec3::new(1., 2., 1.));
+        let ray = Ray {
+            origin: Vec3::new(-5., 1., 0.),
+            direction: Vec3::new(2., 0., 0.),
+        };
+        assert_eq!(ray.intersect_box(min, max), Some(2.));
+
+        // From inside the box.
+        let inside = Ray {
+            origin: Vec3::new(0., 1., 0.),
+            direction: Vec3::Y,
+        };
+        assert_eq!(inside.intersect_box(min, max), Some(0.));
+
+        // Behind, and beside the box.
+        let away = Ray {
+            origin: ray.origin,
+            direction: -ray.direction,
+        };
+        assert_eq!(away.intersect_box(min, max), None);
+        let beside = Ray {
+            origin: Vec3::new(-5., 3., 0.),
+            direction: Vec3::X,
+        };
+        assert_eq!(beside.intersect_box(min, max), None);
+    }
+
+    #[test]
+    fn triangle_intersection() {
+        let (a, b, c) = (Vec3::ZERO, Vec3::new(2., 0., 0.), Vec3::new(0., 2., 0.));
+        let ray = Ray {
+            origin: Vec3::new(0.5, 0.5, -3.),
+            direction: Vec3::Z,
+        };
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(3.));
+        // Both faces are hit.
+        assert_eq!(ray.intersect_triangle(a, c, b), Some(3.));
+
+        // Outside of the triangle, behind it, and parallel to it.
+        let outside = Ray {
+            origin: Vec3::new(1.5, 1.5, -3.),
+            direction: Vec3::Z,
+        };
+        assert_eq!(outside.intersect_triangle(a, b, c), None);
+        let behind = Ray {
+            origin: Vec3::new(0.5, 0.5, 3.),
+            direction: Vec3::Z,
+        };
+        assert_eq!(behind.intersect_triangle(a, b, c), None);
+        let parallel = Ray {
+            origin: Vec3::new(0.5, 0.5, 0.),
+            direction: Vec3::X,
+        };
+        assert_eq!(parallel.intersect_triangle(a, b, c), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{DirectionalLight, Material, PointLight, SpotLight, StandardMaterial};
+use crate::{Decal, DirectionalLight, Material, PointLight, SpotLight, StandardMaterial};
 use bevy_asset::Handle;
 use bevy_ecs::{bundle::Bundle, component::Component, reflect::ReflectComponent};
 use bevy_reflect::Reflect;
@@ -104,3 +104,16 @@ pub struct DirectionalLightBundle {
     /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
     pub computed_visibility: ComputedVisibility,
 }
+
+/// A component bundle for [`Decal`] entities.
+#[derive(Debug, Bundle, Default)]
+pub struct DecalBundle {
+    pub decal: Decal,
+    /// The box the decal is projected through is the unit cube transformed by this
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// Enables or disables the decal
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
@@ -0,0 +1,63 @@
+#define_import_path bevy_pbr::decal
+
+// Blends the images of the decals of the cluster of the fragment over its base color `color`,
+// inside their boxes and on the surfaces facing them. Decals require storage buffers.
+fn apply_decals(
+    color: vec4<f32>,
+    frag_coord: vec4<f32>,
+    world_position: vec4<f32>,
+    world_normal: vec3<f32>,
+    is_orthographic: bool,
+) -> vec4<f32> {
+#ifdef NO_STORAGE_BUFFERS_SUPPORT
+    return color;
+#else
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), world_position);
+    let cluster_index = fragment_cluster_index(frag_coord.xy, view_z, is_orthographic);
+    let offset_and_counts = cluster_offsets_and_counts.data[cluster_index];
+    // The decals are after the point and spot lights of the cluster
+    let first_decal = offset_and_counts.x + offset_and_counts.y + offset_and_counts.z;
+
+    var rgb = color.rgb;
+    for (var i: u32 = first_decal; i < first_decal + offset_and_counts.w; i = i + 1u) {
+        let decal = decals.data[get_light_id(i)];
+        if (decal.texture_index == 0u || (decal.render_layers & mesh.render_layers) == 0u) {
+            continue;
+        }
+        let local_position = (decal.local_from_world * world_position).xyz;
+        if (any(abs(local_position) > vec3<f32>(0.5))) {
+            continue;
+        }
+        // The image is projected along -Z, so it only covers the surfaces facing +Z of the box
+        let box_normal = vec3<f32>(
+            decal.local_from_world[0].z,
+            decal.local_from_world[1].z,
+            decal.local_from_world[2].z
+        );
+        if (dot(world_normal, box_normal) <= 0.0) {
+            continue;
+        }
+        let uv = vec2<f32>(local_position.x + 0.5, 0.5 - local_position.y);
+
+        // NOTE: The decal loop is non-uniform control flow, so the level variant is used
+#ifdef NO_ARRAY_TEXTURES_SUPPORT
+        let texel = textureSampleLevel(spot_light_textures, light_textures_sampler, uv, 0.0);
+#else
+        let texel = textureSampleLevel(
+            spot_light_textures,
+            light_textures_sampler,
+            uv,
+            i32(decal.texture_index - 1u),
+            0.0
+        );
+#endif
+        rgb = mix(rgb, texel.rgb, texel.a);
+    }
+    return vec4<f32>(rgb, color.a);
+#endif
+}
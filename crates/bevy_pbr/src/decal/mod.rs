@@ -0,0 +1,219 @@
+//! Clustered decals, projecting the image of a [`Decal`] onto the opaque surfaces inside its box.
+//!
+//! The decals are assigned to the clusters of the views after their lights, and their images
+//! share the layers of the spot light textures of the [`LightTextures`]. Each fragment blends the
+//! decals of its cluster over the base color of its material, before it's lit.
+
+use std::num::NonZeroU64;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_math::Mat4;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    render_resource::{
+        BindingResource, BufferBindingType, Shader, ShaderType, StorageBuffer, UniformBuffer,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::Image,
+    view::{ComputedVisibility, RenderLayers},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+use crate::{
+    prepare_light_textures, LightTextures, RenderLightSystems,
+    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+};
+
+pub const DECAL_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6285107146593728044);
+
+/// Add this component to an entity with a transform to project an image onto the opaque and
+/// alpha masked surfaces inside its box, such as bullet holes or a blob shadow, without modifying
+/// their meshes.
+///
+/// The box is the unit cube around the origin of the entity, scaled, rotated and translated by
+/// its [`GlobalTransform`]. The image is projected along its local `-Z` axis, with its top
+/// towards `+Y`, onto the surfaces facing it, and blended over their base color with its alpha
+/// before they're lit. Like the [`SpotLightTexture`](crate::SpotLightTexture)s, the image is
+/// copied to a layer [`LIGHT_TEXTURE_SIZE`](crate::LIGHT_TEXTURE_SIZE) texels wide, it has to be a
+/// 2D image with a filterable format, and the decal isn't drawn until it's loaded.
+///
+/// The decal only covers the meshes with intersecting [`RenderLayers`], which are layer `0` for
+/// entities without the component. Decals require storage buffers, so they aren't drawn on
+/// `WebGL2`.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct Decal {
+    pub image: Handle<Image>,
+}
+
+/// Draws the [`Decal`]s in the clusters of the views.
+pub struct DecalPlugin;
+
+impl Plugin for DecalPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, DECAL_SHADER_HANDLE, "decal.wgsl", Shader::from_wgsl);
+
+        app.register_type::<Decal>();
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<DecalMeta>()
+            .add_system_to_stage(RenderStage::Extract, extract_decals)
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                // NOTE: The indices of the decals have to be known by prepare_clusters
+                prepare_decals
+                    .after(prepare_light_textures)
+                    .before(RenderLightSystems::PrepareClusters),
+            );
+    }
+}
+
+#[derive(Component)]
+pub struct ExtractedDecal {
+    local_from_world: Mat4,
+    image: Handle<Image>,
+    render_layers: RenderLayers,
+}
+
+pub fn extract_decals(
+    mut commands: Commands,
+    decals: Extract<
+        Query<(
+            Entity,
+            &Decal,
+            &GlobalTransform,
+            &ComputedVisibility,
+            Option<&RenderLayers>,
+        )>,
+    >,
+    mut previous_decals_len: Local<usize>,
+) {
+    let mut decals_values = Vec::with_capacity(*previous_decals_len);
+    for (entity, decal, transform, visibility, render_layers) in &decals {
+        if !visibility.is_visible() {
+            continue;
+        }
+        decals_values.push((
+            entity,
+            ExtractedDecal {
+                local_from_world: transform.compute_matrix().inverse(),
+                image: decal.image.clone_weak(),
+                render_layers: render_layers.copied().unwrap_or_default(),
+            },
+        ));
+    }
+    *previous_decals_len = decals_values.len();
+    commands.insert_or_spawn_batch(decals_values);
+}
+
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuDecal {
+    // Transforms the world positions to the box of the decal, the unit cube around the origin
+    local_from_world: Mat4,
+    // The layer of the image of the decal in the spot light textures plus one, 0 while it isn't
+    // loaded
+    texture_index: u32,
+    // The RenderLayers of the decal, as a mask of the meshes it covers
+    render_layers: u32,
+}
+
+#[derive(ShaderType, Default)]
+pub struct GpuDecalsStorage {
+    #[size(runtime)]
+    data: Vec<GpuDecal>,
+}
+
+pub enum GpuDecals {
+    /// The decals aren't assigned to the clusters without storage buffers, this only binds a
+    /// placeholder to the mesh view bind group.
+    Uniform(UniformBuffer<GpuDecal>),
+    Storage(StorageBuffer<GpuDecalsStorage>),
+}
+
+impl GpuDecals {
+    fn new(buffer_binding_type: BufferBindingType) -> Self {
+        match buffer_binding_type {
+            BufferBindingType::Storage { .. } => Self::Storage(StorageBuffer::default()),
+            BufferBindingType::Uniform => Self::Uniform(UniformBuffer::default()),
+        }
+    }
+
+    pub fn binding(&self) -> Option<BindingResource> {
+        match self {
+            GpuDecals::Uniform(buffer) => buffer.binding(),
+            GpuDecals::Storage(buffer) => buffer.binding(),
+        }
+    }
+
+    pub fn min_size(buffer_binding_type: BufferBindingType) -> NonZeroU64 {
+        match buffer_binding_type {
+            BufferBindingType::Storage { .. } => GpuDecalsStorage::min_size(),
+            BufferBindingType::Uniform => GpuDecal::min_size(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct DecalMeta {
+    pub gpu_decals: GpuDecals,
+    /// The index of each decal in the [`GpuDecals`], for the cluster index lists.
+    pub entity_to_index: HashMap<Entity, usize>,
+}
+
+impl FromWorld for DecalMeta {
+    fn from_world(world: &mut World) -> Self {
+        DecalMeta {
+            gpu_decals: GpuDecals::new(
+                world
+                    .resource::<RenderDevice>()
+                    .get_supported_read_only_binding_type(CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT),
+            ),
+            entity_to_index: HashMap::default(),
+        }
+    }
+}
+
+pub fn prepare_decals(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    light_textures: Res<LightTextures>,
+    mut decal_meta: ResMut<DecalMeta>,
+    decals: Query<(Entity, &ExtractedDecal)>,
+) {
+    let DecalMeta {
+        gpu_decals,
+        entity_to_index,
+    } = &mut *decal_meta;
+
+    entity_to_index.clear();
+    match gpu_decals {
+        GpuDecals::Uniform(buffer) => buffer.write_buffer(&render_device, &render_queue),
+        GpuDecals::Storage(buffer) => {
+            let data = &mut buffer.get_mut().data;
+            data.clear();
+            for (index, (entity, decal)) in decals.iter().enumerate() {
+                let texture_index = match light_textures.spot_light_indices.get(&decal.image) {
+                    Some(index) => index + 1,
+                    None => 0,
+                };
+                data.push(GpuDecal {
+                    local_from_world: decal.local_from_world,
+                    texture_index,
+                    render_layers: decal.render_layers.bits(),
+                });
+                entity_to_index.insert(entity, index);
+            }
+            buffer.write_buffer(&render_device, &render_queue);
+        }
+    }
+}
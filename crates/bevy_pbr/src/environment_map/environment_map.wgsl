@@ -0,0 +1,21 @@
+#define_import_path bevy_pbr::environment_map
+
+struct EnvironmentMapLight {
+    diffuse: vec3<f32>,
+    specular: vec3<f32>,
+};
+
+// Reads the light coming from the surroundings of the view: the irradiance around the normal
+// `N`, and the radiance around the reflection `R`, prefiltered for the roughness of the surface.
+// See Karis 2013, "Real Shading in Unreal Engine 4"
+fn environment_map_light(perceptual_roughness: f32, N: vec3<f32>, R: vec3<f32>) -> EnvironmentMapLight {
+    // The mip levels of the specular map are prefiltered for increasing perceptual roughness,
+    // from 0 for mirrors to the smallest one for fully rough surfaces
+    let smallest_specular_mip_level = f32(textureNumLevels(environment_map_specular)) - 1.0;
+    let specular_mip_level = perceptual_roughness * smallest_specular_mip_level;
+
+    var out: EnvironmentMapLight;
+    out.diffuse = textureSample(environment_map_diffuse, environment_map_sampler, N).rgb;
+    out.specular = textureSampleLevel(environment_map_specular, environment_map_sampler, R, specular_mip_level).rgb;
+    return out;
+}
@@ -0,0 +1,80 @@
+//! Image based lighting, for the cameras that opt into it with [`EnvironmentMapLight`].
+//!
+//! The cubemaps of the environment map are bound to the mesh view bind group, and the meshes
+//! drawn by these cameras are specialized with the `ENVIRONMENT_MAP` shader def to add their
+//! light to the ambient light. Views without a loaded environment map bind a black cubemap
+//! instead.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::Shader,
+    texture::Image,
+};
+
+pub const ENVIRONMENT_MAP_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 154476556247605696);
+
+/// Add this component to a camera to light the scene with its surroundings, as seen from
+/// infinitely far away.
+///
+/// This is the indirect light of physically based rendering, that metals mostly reflect: unlike
+/// the flat [`AmbientLight`](crate::AmbientLight), it changes with the direction surfaces face
+/// and reflect. It adds to the ambient light, and is occluded by the same occlusion textures and
+/// screen space ambient occlusion.
+///
+/// Both maps have to be cubemaps with a filterable format, such as the ones loaded from KTX2
+/// files with the `ktx2` feature, and are usually prefiltered from the same surroundings by
+/// tools such as [glTF IBL Sampler](https://github.com/KhronosGroup/glTF-IBL-Sampler). The
+/// camera isn't lit by them until both are loaded.
+///
+/// To draw the surroundings behind the scene as well, see `Skybox` in `bevy_core_pipeline`.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct EnvironmentMapLight {
+    /// The irradiance of the surroundings around each direction, lighting diffuse surfaces.
+    pub diffuse_map: Handle<Image>,
+    /// The radiance of the surroundings in each direction, reflected by specular surfaces.
+    ///
+    /// Its mip levels have to be prefiltered for increasing perceptual roughness, from the
+    /// surroundings as is at level 0, for mirrors, to fully rough at the smallest level.
+    pub specular_map: Handle<Image>,
+}
+
+impl EnvironmentMapLight {
+    /// Whether both maps are loaded, and light the camera.
+    pub fn is_loaded(&self, images: &RenderAssets<Image>) -> bool {
+        images.get(&self.diffuse_map).is_some() && images.get(&self.specular_map).is_some()
+    }
+}
+
+impl ExtractComponent for EnvironmentMapLight {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+/// Lights the cameras with an [`EnvironmentMapLight`].
+pub struct EnvironmentMapPlugin;
+
+impl Plugin for EnvironmentMapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            ENVIRONMENT_MAP_SHADER_HANDLE,
+            "environment_map.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<EnvironmentMapLight>()
+            .add_plugin(ExtractComponentPlugin::<EnvironmentMapLight>::default());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    AlphaMode, Material, MaterialPipeline, MaterialPipelineKey, MeshPipeline, MeshPipelineKey,
+};
+use bevy_asset::Handle;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::{MeshVertexAttribute, MeshVertexBufferLayout},
+    prelude::Image,
+    render_asset::RenderAssets,
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry,
+        RenderPipelineDescriptor, Shader, ShaderRef, SpecializedMeshPipelineError,
+        UnpreparedBindGroup,
+    },
+    renderer::RenderDevice,
+    texture::FallbackImage,
+};
+use std::marker::PhantomData;
+
+/// Extends a base [`Material`] with extra bindings and shaders, used with the
+/// [`ExtendedMaterial`] combining them.
+///
+/// The bindings of the extension are added to the material bind group of the base material, so
+/// their indices must not overlap the ones of the base material. The bindings of the
+/// [`StandardMaterial`](crate::StandardMaterial) are below `100`, so the extensions of it can
+/// start from there.
+///
+/// The shaders of the extension replace the ones of the base material. Extensions of the
+/// [`StandardMaterial`](crate::StandardMaterial) can reuse its lighting without copying its
+/// shader: their fragment shader imports `bevy_pbr::pbr_fragment`, builds the `PbrInput` of the
+/// fragment with `pbr_input_from_standard_material(in)`, modifies it, and lights it with
+/// `pbr_output_color(pbr_input)`. Like `bevy_pbr::pbr`, it also imports the modules
+/// `bevy_pbr::pbr_fragment` depends on, and returns an `OitFragmentOutput` with the `OIT` shader
+/// def.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_pbr::{ExtendedMaterial, MaterialExtension, MaterialMeshBundle, StandardMaterial};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypeUuid;
+/// # use bevy_render::{render_resource::{AsBindGroup, ShaderRef}, color::Color};
+/// # use bevy_asset::Assets;
+///
+/// #[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+/// #[uuid = "5c2f4a3e-3b1e-4b4f-9b3a-0d6c3e2f7a51"]
+/// pub struct Dissolve {
+///     // Past the bindings of the StandardMaterial
+///     #[uniform(100)]
+///     threshold: f32,
+/// }
+///
+/// impl MaterialExtension for Dissolve {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/dissolve.wgsl".into()
+///     }
+/// }
+///
+/// fn setup(
+///     mut commands: Commands,
+///     mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, Dissolve>>>,
+/// ) {
+///     commands.spawn(MaterialMeshBundle {
+///         material: materials.add(ExtendedMaterial {
+///             base: StandardMaterial {
+///                 base_color: Color::RED,
+///                 ..Default::default()
+///             },
+///             extension: Dissolve { threshold: 0.5 },
+///         }),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+pub trait MaterialExtension:
+    AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'static
+{
+    /// Returns this extension's vertex shader. If [`ShaderRef::Default`] is returned, the vertex
+    /// shader of the base material will be used.
+    fn vertex_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns this extension's fragment shader. If [`ShaderRef::Default`] is returned, the
+    /// fragment shader of the base material will be used.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns the custom [`MeshVertexAttribute`]s this extension's vertex shader reads, at the
+    /// shader locations following the [`Material::vertex_attributes`] of the base material.
+    fn vertex_attributes() -> Vec<MeshVertexAttribute> {
+        Vec::new()
+    }
+
+    /// Customizes the [`RenderPipelineDescriptor`] of the [`ExtendedMaterial`], after the base
+    /// material specialized it.
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(
+        pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayout,
+        key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        Ok(())
+    }
+}
+
+/// The render pipeline data of an [`ExtendedMaterial`], passed to
+/// [`MaterialExtension::specialize`].
+pub struct MaterialExtensionPipeline {
+    pub mesh_pipeline: MeshPipeline,
+    pub material_layout: BindGroupLayout,
+    pub vertex_shader: Option<Handle<Shader>>,
+    pub fragment_shader: Option<Handle<Shader>>,
+}
+
+/// The key of the specialized pipeline of an [`ExtendedMaterial`], passed to
+/// [`MaterialExtension::specialize`].
+pub struct MaterialExtensionKey<E: MaterialExtension> {
+    pub mesh_key: MeshPipelineKey,
+    pub bind_group_data: E::Data,
+}
+
+/// A [`Material`] extending a base material with a [`MaterialExtension`], which adds its
+/// bindings to the ones of the base material and replaces its shaders.
+#[derive(Clone, TypeUuid)]
+#[uuid = "b5c5a0b3-6f2a-4d3c-8f4e-2a7d3c1e9b60"]
+pub struct ExtendedMaterial<B: Material, E: MaterialExtension> {
+    pub base: B,
+    pub extension: E,
+}
+
+impl<B: Material, E: MaterialExtension> AsBindGroup for ExtendedMaterial<B, E> {
+    type Data = (B::Data, E::Data);
+
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<Image>,
+        fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self>, AsBindGroupError> {
+        let UnpreparedBindGroup {
+            mut bindings,
+            data: base_data,
+        } = B::unprepared_bind_group(&self.base, layout, render_device, images, fallback_image)?;
+        let UnpreparedBindGroup {
+            bindings: extension_bindings,
+            data: extension_data,
+        } = E::unprepared_bind_group(
+            &self.extension,
+            layout,
+            render_device,
+            images,
+            fallback_image,
+        )?;
+
+        bindings.extend(extension_bindings);
+
+        Ok(UnpreparedBindGroup {
+            bindings,
+            data: (base_data, extension_data),
+        })
+    }
+
+    fn bind_group_layout_entries(render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry> {
+        let mut entries = B::bind_group_layout_entries(render_device);
+        entries.extend(E::bind_group_layout_entries(render_device));
+        entries
+    }
+}
+
+impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
+    fn vertex_shader() -> ShaderRef {
+        match E::vertex_shader() {
+            ShaderRef::Default => B::vertex_shader(),
+            specified => specified,
+        }
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        match E::fragment_shader() {
+            ShaderRef::Default => B::fragment_shader(),
+            specified => specified,
+        }
+    }
+
+    fn vertex_attributes() -> Vec<MeshVertexAttribute> {
+        let mut vertex_attributes = B::vertex_attributes();
+        vertex_attributes.extend(E::vertex_attributes());
+        vertex_attributes
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        B::alpha_mode(&self.base)
+    }
+
+    fn depth_bias(&self) -> f32 {
+        B::depth_bias(&self.base)
+    }
+
+    fn reads_view_transmission_texture(&self) -> bool {
+        B::reads_view_transmission_texture(&self.base)
+    }
+
+    fn specialize(
+        pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayout,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let MaterialPipeline {
+            mesh_pipeline,
+            material_layout,
+            vertex_shader,
+            fragment_shader,
+            ..
+        } = pipeline.clone();
+        let (base_data, extension_data) = key.bind_group_data;
+
+        let base_pipeline = MaterialPipeline::<B> {
+            mesh_pipeline,
+            material_layout,
+            vertex_shader,
+            fragment_shader,
+            marker: PhantomData,
+        };
+        B::specialize(
+            &base_pipeline,
+            descriptor,
+            layout,
+            MaterialPipelineKey {
+                mesh_key: key.mesh_key,
+                bind_group_data: base_data,
+            },
+        )?;
+
+        let MaterialPipeline {
+            mesh_pipeline,
+            material_layout,
+            vertex_shader,
+            fragment_shader,
+            ..
+        } = base_pipeline;
+        let extension_pipeline = MaterialExtensionPipeline {
+            mesh_pipeline,
+            material_layout,
+            vertex_shader,
+            fragment_shader,
+        };
+        E::specialize(
+            &extension_pipeline,
+            descriptor,
+            layout,
+            MaterialExtensionKey {
+                mesh_key: key.mesh_key,
+                bind_group_data: extension_data,
+            },
+        )
+    }
+}
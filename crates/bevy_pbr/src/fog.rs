@@ -0,0 +1,107 @@
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_render::{camera::Camera, color::Color, extract_component::ExtractComponent};
+
+/// Add this component to a camera to fade what it renders into a fog with distance, such as
+/// haze, mist or smoke.
+///
+/// The fog is applied by the fragment shader of [`StandardMaterial`](crate::StandardMaterial),
+/// after its lighting. What isn't drawn with it, such as the clear color, isn't fogged: set the
+/// [`ClearColor`](bevy_core_pipeline::clear_color::ClearColor) to the fog color to blend the
+/// fog into the background.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct FogSettings {
+    /// The color of the fog, with its alpha scaling how opaque it gets.
+    ///
+    /// Defaults to [`Color::WHITE`].
+    pub color: Color,
+    /// The color of the light of the [`DirectionalLight`](crate::DirectionalLight) scattered by
+    /// the fog towards the view, with its alpha scaling it.
+    ///
+    /// The scattered light is added to the fog color when looking towards the light, such as a
+    /// glow around the sun. Defaults to [`Color::NONE`], which scatters no light.
+    pub directional_light_color: Color,
+    /// How focused the scattered light of the directional light is around its direction, with
+    /// higher values for a smaller glow.
+    ///
+    /// Defaults to `8.0`.
+    pub directional_light_exponent: f32,
+    /// How the fog thickens with the distance from the view.
+    pub falloff: FogFalloff,
+    /// How the fog thins out with height, or `None` for a fog as thick at every height.
+    pub height_fog: Option<HeightFog>,
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        FogSettings {
+            color: Color::WHITE,
+            directional_light_color: Color::NONE,
+            directional_light_exponent: 8.0,
+            falloff: FogFalloff::default(),
+            height_fog: None,
+        }
+    }
+}
+
+impl ExtractComponent for FogSettings {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+/// How the fog of [`FogSettings`] thickens with the distance from the view.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub enum FogFalloff {
+    /// The fog thickens linearly from none at `start` to fully opaque at `end`, both distances
+    /// in world units.
+    ///
+    /// This is the easiest to tune to the size of a scene, but doesn't look physical.
+    Linear { start: f32, end: f32 },
+    /// The visibility through the fog decays exponentially with the distance, by a factor `e`
+    /// every `1.0 / density` world units.
+    ///
+    /// This is how light is absorbed by a uniform medium.
+    Exponential { density: f32 },
+    /// The visibility through the fog decays exponentially with the square of the distance times
+    /// `density`.
+    ///
+    /// This keeps what is close clearer than [`Exponential`](FogFalloff::Exponential), before
+    /// thickening faster.
+    ExponentialSquared { density: f32 },
+}
+
+impl Default for FogFalloff {
+    fn default() -> Self {
+        FogFalloff::Linear {
+            start: 0.0,
+            end: 100.0,
+        }
+    }
+}
+
+/// How the fog of [`FogSettings`] thins out with height, such as mist in a valley.
+///
+/// The density of the fog decays exponentially above `base_height`, by a factor `e` every
+/// `1.0 / falloff` world units, and grows below it. The fog is integrated along the view ray,
+/// which sees through less of it when looking up.
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq)]
+pub struct HeightFog {
+    /// The height in world units where the fog has the density of its [`FogFalloff`].
+    pub base_height: f32,
+    /// How fast the fog thins out with height.
+    pub falloff: f32,
+}
+
+impl Default for HeightFog {
+    fn default() -> Self {
+        HeightFog {
+            base_height: 0.0,
+            falloff: 0.1,
+        }
+    }
+}
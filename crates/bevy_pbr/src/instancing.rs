@@ -0,0 +1,392 @@
+use crate::{
+    wireframe::WireframeOnly, AlphaMode, DrawMeshInstanced, EnvironmentMapLight, Material,
+    MaterialPipeline, MaterialPipelineKey, MeshPipelineKey, MeshUniform, RenderMaterials,
+    SetMaterialBindGroup, SetMeshBindGroup, SetMeshViewBindGroup, SetTransmissiveMeshViewBindGroup,
+    ShadowFilteringMethod,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_core_pipeline::{
+    core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d},
+    oit::OrderIndependentTransparency,
+    prepass::{MotionVectorPrepass, Opaque3dPrepass},
+    transmission::queue_transmission_textures,
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    mesh::{
+        instance_buffer_layout, push_instance, skinning::SkinnedMesh, InstanceBatch,
+        InstanceBatches, InstanceData, InstancedMesh, Mesh, MeshVertexBufferLayout,
+    },
+    render_asset::RenderAssets,
+    render_phase::{AddRenderCommand, DrawFunctions, RenderPhase, SetItemPipeline},
+    render_resource::{
+        BufferInitDescriptor, BufferUsages, PipelineCache, RenderPipelineDescriptor,
+        SpecializedMeshPipeline, SpecializedMeshPipelineError, SpecializedMeshPipelines,
+    },
+    renderer::RenderDevice,
+    texture::Image,
+    view::{ComputedVisibility, ExtractedView, Msaa, VisibleEntities},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_utils::{tracing::error, FloatOrd, HashMap};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// The shader location of the first column of the model matrix of each instance, followed by
+/// the other columns and then by the fields of the [`InstanceData`].
+pub const INSTANCE_LOCATION: u32 = 8;
+
+/// Draws the visible entities with a [`Material`] `M` and an [`InstanceData`] `I` in instanced
+/// batches, one for each mesh and material they share.
+///
+/// The [`MaterialPlugin`](crate::MaterialPlugin) of `M` has to be added as well. The default
+/// vertex shader reads the model of each instance, with the `MESH_INSTANCED` shader def, and
+/// custom vertex shaders can read the data of `I` starting from [`INSTANCE_LOCATION`] + 4, after
+/// the [`Material::vertex_attributes`] of `M`.
+/// [`Material::specialize`] can tell instanced pipelines apart with
+/// [`MeshPipelineKey::INSTANCED`].
+///
+/// The instances of a batch are drawn in order of distance to the view, but the batch as a
+/// whole is sorted with its first instance. Skinned meshes aren't batched, and shadows are
+/// still cast by each entity on its own.
+pub struct InstancedMaterialPlugin<M: Material, I: InstanceData>(PhantomData<(M, I)>);
+
+impl<M: Material, I: InstanceData> Default for InstancedMaterialPlugin<M, I> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Material, I: InstanceData> Plugin for InstancedMaterialPlugin<M, I>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<Transparent3d, DrawInstancedMaterial<M>>()
+                .add_render_command::<Opaque3d, DrawInstancedMaterial<M>>()
+                .add_render_command::<Opaque3dPrepass, DrawInstancedMaterial<M>>()
+                .add_render_command::<AlphaMask3d, DrawInstancedMaterial<M>>()
+                .add_render_command::<Transmissive3d, DrawTransmissiveInstancedMaterial<M>>()
+                .init_resource::<InstancedMaterialPipeline<M, I>>()
+                .init_resource::<SpecializedMeshPipelines<InstancedMaterialPipeline<M, I>>>()
+                .add_system_to_stage(RenderStage::Extract, extract_instances::<M, I>)
+                .add_system_to_stage(
+                    RenderStage::Queue,
+                    queue_instanced_material_meshes::<M, I>.before(queue_transmission_textures),
+                );
+        }
+    }
+}
+
+/// The [`MaterialPipeline`] of `M`, with the instances of `I` bound to a second vertex buffer.
+#[derive(Resource)]
+pub struct InstancedMaterialPipeline<M: Material, I: InstanceData> {
+    pub material_pipeline: MaterialPipeline<M>,
+    marker: PhantomData<I>,
+}
+
+impl<M: Material, I: InstanceData> FromWorld for InstancedMaterialPipeline<M, I> {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            material_pipeline: world.resource::<MaterialPipeline<M>>().clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Material, I: InstanceData> SpecializedMeshPipeline for InstancedMaterialPipeline<M, I>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = MaterialPipelineKey<M>;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayout,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.material_pipeline.specialize(key, layout)?;
+        let mut instance_layout = instance_buffer_layout::<I>(INSTANCE_LOCATION);
+        // The data of the instances follows the vertex attributes of the material, after the 4
+        // columns of the model
+        let material_attribute_count = M::vertex_attributes().len() as u32;
+        for attribute in instance_layout.attributes.iter_mut().skip(4) {
+            attribute.shader_location += material_attribute_count;
+        }
+        descriptor.vertex.buffers.push(instance_layout);
+        Ok(descriptor)
+    }
+}
+
+type DrawInstancedMaterial<M> = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMaterialBindGroup<M, 1>,
+    // The mesh uniform of the first instance is bound for its flags, shared by the batch
+    SetMeshBindGroup<2>,
+    DrawMeshInstanced,
+);
+
+type DrawTransmissiveInstancedMaterial<M> = (
+    SetItemPipeline,
+    SetTransmissiveMeshViewBindGroup<0>,
+    SetMaterialBindGroup<M, 1>,
+    SetMeshBindGroup<2>,
+    DrawMeshInstanced,
+);
+
+fn extract_instances<M: Material, I: InstanceData>(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    query: Extract<
+        Query<(Entity, &ComputedVisibility, &I), (With<Handle<M>>, Without<SkinnedMesh>)>,
+    >,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, computed_visibility, instance) in &query {
+        if computed_visibility.is_visible() {
+            values.push((entity, (*instance, InstancedMesh)));
+        }
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_instanced_material_meshes<M: Material, I: InstanceData>(
+    opaque_prepass_draw_functions: Res<DrawFunctions<Opaque3dPrepass>>,
+    opaque_draw_functions: Res<DrawFunctions<Opaque3d>>,
+    alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3d>>,
+    transmissive_draw_functions: Res<DrawFunctions<Transmissive3d>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    instanced_pipeline: Res<InstancedMaterialPipeline<M, I>>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<InstancedMaterialPipeline<M, I>>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    mut instance_batches: ResMut<InstanceBatches>,
+    render_meshes: Res<RenderAssets<Mesh>>,
+    render_materials: Res<RenderMaterials<M>>,
+    images: Res<RenderAssets<Image>>,
+    instanced_meshes: Query<(&Handle<M>, &Handle<Mesh>, &MeshUniform, &I)>,
+    mut views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            &Msaa,
+            &VisibleEntities,
+            &mut RenderPhase<Opaque3d>,
+            &mut RenderPhase<AlphaMask3d>,
+            &mut RenderPhase<Transmissive3d>,
+            &mut RenderPhase<Transparent3d>,
+            Option<&mut RenderPhase<Opaque3dPrepass>>,
+            Option<&MotionVectorPrepass>,
+            Option<&OrderIndependentTransparency>,
+            Option<&EnvironmentMapLight>,
+            Option<&ShadowFilteringMethod>,
+        ),
+        Without<WireframeOnly>,
+    >,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if instanced_meshes.is_empty() {
+        return;
+    }
+    for (
+        view_entity,
+        view,
+        msaa,
+        visible_entities,
+        mut opaque_phase,
+        mut alpha_mask_phase,
+        mut transmissive_phase,
+        mut transparent_phase,
+        mut opaque_prepass_phase,
+        motion_vector_prepass,
+        order_independent_transparency,
+        environment_map,
+        shadow_filtering_method,
+    ) in &mut views
+    {
+        let draw_opaque_prepass = opaque_prepass_draw_functions
+            .read()
+            .get_id::<DrawInstancedMaterial<M>>()
+            .unwrap();
+        let draw_opaque = opaque_draw_functions
+            .read()
+            .get_id::<DrawInstancedMaterial<M>>()
+            .unwrap();
+        let draw_alpha_mask = alpha_mask_draw_functions
+            .read()
+            .get_id::<DrawInstancedMaterial<M>>()
+            .unwrap();
+        let draw_transmissive = transmissive_draw_functions
+            .read()
+            .get_id::<DrawTransmissiveInstancedMaterial<M>>()
+            .unwrap();
+        let draw_transparent = transparent_draw_functions
+            .read()
+            .get_id::<DrawInstancedMaterial<M>>()
+            .unwrap();
+
+        let rangefinder = view.rangefinder3d();
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
+        if let Some(environment_map) = environment_map {
+            if environment_map.is_loaded(&images) {
+                view_key |= MeshPipelineKey::ENVIRONMENT_MAP;
+            }
+        }
+        if let Some(shadow_filtering_method) = shadow_filtering_method {
+            view_key |= MeshPipelineKey::from_shadow_filtering_method(*shadow_filtering_method);
+        }
+        if opaque_prepass_phase.is_some() {
+            view_key |= MeshPipelineKey::LOADS_PREPASS_DEPTH;
+        }
+        let mut prepass_key = MeshPipelineKey::DEPTH_PREPASS;
+        if motion_vector_prepass.is_some() {
+            prepass_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+        }
+
+        // Batches the instances sharing their mesh, material and flags, which are read from
+        // the mesh uniform of the first instance
+        let mut batches = HashMap::<_, Vec<_>>::default();
+        for visible_entity in &visible_entities.entities {
+            if let Ok((material_handle, mesh_handle, mesh_uniform, _)) =
+                instanced_meshes.get(*visible_entity)
+            {
+                let distance = rangefinder.distance(&mesh_uniform.transform);
+                batches
+                    .entry((material_handle, mesh_handle, mesh_uniform.flags))
+                    .or_default()
+                    .push((*visible_entity, distance));
+            }
+        }
+
+        for ((material_handle, mesh_handle, _), mut instances) in batches {
+            let (material, mesh) = match (
+                render_materials.get(material_handle),
+                render_meshes.get(mesh_handle),
+            ) {
+                (Some(material), Some(mesh)) => (material, mesh),
+                _ => continue,
+            };
+            let mut mesh_key = MeshPipelineKey::from_primitive_topology(mesh.primitive_topology)
+                | view_key
+                | MeshPipelineKey::INSTANCED;
+            let alpha_mode = material.properties.alpha_mode;
+            let transmissive = material.properties.reads_view_transmission_texture
+                && !matches!(alpha_mode, AlphaMode::Blend);
+            if let AlphaMode::Blend = alpha_mode {
+                mesh_key |= MeshPipelineKey::TRANSPARENT_MAIN_PASS;
+                if order_independent_transparency.is_some() {
+                    mesh_key |= MeshPipelineKey::ORDER_INDEPENDENT_TRANSPARENCY;
+                }
+            }
+            if transmissive || matches!(alpha_mode, AlphaMode::Blend) {
+                // Back to front
+                instances.sort_by_key(|(_, distance)| FloatOrd(*distance));
+            } else {
+                // Front to back
+                instances.sort_by_key(|(_, distance)| std::cmp::Reverse(FloatOrd(*distance)));
+            }
+
+            let pipeline_id = pipelines.specialize(
+                &mut pipeline_cache,
+                &instanced_pipeline,
+                MaterialPipelineKey {
+                    mesh_key,
+                    bind_group_data: material.key.clone(),
+                },
+                &mesh.layout,
+            );
+            let pipeline_id = match pipeline_id {
+                Ok(id) => id,
+                Err(err) => {
+                    error!("{}", err);
+                    continue;
+                }
+            };
+
+            let mut contents = Vec::new();
+            for (entity, _) in &instances {
+                let (_, _, mesh_uniform, instance) = instanced_meshes.get(*entity).unwrap();
+                push_instance(&mut contents, &mesh_uniform.transform, instance);
+            }
+            let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("mesh_instance_buffer"),
+                contents: &contents,
+                usage: BufferUsages::VERTEX,
+            });
+            let (entity, distance) = instances[0];
+            instance_batches.insert(
+                view_entity,
+                entity,
+                InstanceBatch {
+                    buffer,
+                    count: instances.len() as u32,
+                },
+            );
+
+            let distance = distance + material.properties.depth_bias;
+            match alpha_mode {
+                // The transmissive meshes are left out of the prepass, so that what is behind
+                // them is in the opaque scene they read
+                AlphaMode::Opaque | AlphaMode::Mask(_) if transmissive => {
+                    transmissive_phase.add(Transmissive3d {
+                        entity,
+                        draw_function: draw_transmissive,
+                        pipeline: pipeline_id,
+                        distance,
+                    });
+                }
+                AlphaMode::Opaque => {
+                    if let Some(opaque_prepass_phase) = &mut opaque_prepass_phase {
+                        let prepass_pipeline_id = pipelines.specialize(
+                            &mut pipeline_cache,
+                            &instanced_pipeline,
+                            MaterialPipelineKey {
+                                mesh_key: mesh_key | prepass_key,
+                                bind_group_data: material.key.clone(),
+                            },
+                            &mesh.layout,
+                        );
+                        match prepass_pipeline_id {
+                            Ok(id) => opaque_prepass_phase.add(Opaque3dPrepass {
+                                entity,
+                                draw_function: draw_opaque_prepass,
+                                pipeline: id,
+                                distance,
+                            }),
+                            Err(err) => error!("{}", err),
+                        }
+                    }
+                    opaque_phase.add(Opaque3d {
+                        entity,
+                        draw_function: draw_opaque,
+                        pipeline: pipeline_id,
+                        distance,
+                    });
+                }
+                AlphaMode::Mask(_) => {
+                    alpha_mask_phase.add(AlphaMask3d {
+                        entity,
+                        draw_function: draw_alpha_mask,
+                        pipeline: pipeline_id,
+                        distance,
+                    });
+                }
+                AlphaMode::Blend => {
+                    transparent_phase.add(Transparent3d {
+                        entity,
+                        draw_function: draw_transparent,
+                        pipeline: pipeline_id,
+                        distance,
+                    });
+                }
+            }
+        }
+    }
+}
@@ -2,17 +2,39 @@ pub mod wireframe;
 
 mod alpha;
 mod bundle;
+mod decal;
+mod environment_map;
+mod extended_material;
+mod fog;
+mod instancing;
 mod light;
+mod light_texture;
+mod lightmap;
+mod lod;
 mod material;
+mod parallax;
 mod pbr_material;
+mod polyline;
 mod render;
+mod ssao;
 
 pub use alpha::*;
 pub use bundle::*;
+pub use decal::*;
+pub use environment_map::*;
+pub use extended_material::*;
+pub use fog::*;
+pub use instancing::*;
 pub use light::*;
+pub use light_texture::*;
+pub use lightmap::*;
+pub use lod::*;
 pub use material::*;
+pub use parallax::*;
 pub use pbr_material::*;
+pub use polyline::*;
 pub use render::*;
+pub use ssao::*;
 
 use bevy_window::ModifiesWindows;
 
@@ -21,19 +43,38 @@ pub mod prelude {
     pub use crate::{
         alpha::AlphaMode,
         bundle::{
-            DirectionalLightBundle, MaterialMeshBundle, PbrBundle, PointLightBundle,
+            DecalBundle, DirectionalLightBundle, MaterialMeshBundle, PbrBundle, PointLightBundle,
             SpotLightBundle,
         },
-        light::{AmbientLight, DirectionalLight, PointLight, SpotLight},
+        decal::Decal,
+        environment_map::EnvironmentMapLight,
+        extended_material::{ExtendedMaterial, MaterialExtension},
+        fog::{FogFalloff, FogSettings, HeightFog},
+        light::{AmbientLight, DirectionalLight, PointLight, ShadowFilteringMethod, SpotLight},
+        light_texture::{PointLightTexture, SpotLightTexture},
+        lightmap::Lightmap,
+        lod::{LodSelection, MeshLod, MeshLodLevel},
         material::{Material, MaterialPlugin},
+        parallax::ParallaxMappingMethod,
         pbr_material::StandardMaterial,
+        polyline::{Polyline, PolylineBundle, PolylineMaterial, PolylineTopology},
+        ssao::{ScreenSpaceAmbientOcclusion, ScreenSpaceAmbientOcclusionQuality},
     };
 }
 
+pub mod main_graph {
+    pub mod node {
+        /// Label for the node copying the light textures.
+        pub const LIGHT_TEXTURES: &str = "light_textures";
+    }
+}
+
 pub mod draw_3d_graph {
     pub mod node {
         /// Label for the shadow pass node.
         pub const SHADOW_PASS: &str = "shadow_pass";
+        /// Label for the screen space ambient occlusion node.
+        pub const SCREEN_SPACE_AMBIENT_OCCLUSION: &str = "screen_space_ambient_occlusion";
     }
 }
 
@@ -43,6 +84,7 @@ use bevy_ecs::prelude::*;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     camera::CameraUpdateSystem,
+    extract_component::ExtractComponentPlugin,
     extract_resource::ExtractResourcePlugin,
     prelude::Color,
     render_graph::RenderGraph,
@@ -69,8 +111,12 @@ pub const PBR_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4805239651767701046);
 pub const PBR_FUNCTIONS_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16550102964439850292);
+pub const PBR_FRAGMENT_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2295049283805286543);
 pub const SHADOW_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1836745567947005696);
+pub const PARALLAX_MAPPING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 17035894873630133905);
 
 /// Sets up the entire PBR infrastructure of bevy.
 #[derive(Default)]
@@ -115,6 +161,18 @@ impl Plugin for PbrPlugin {
             "render/pbr_functions.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            PBR_FRAGMENT_HANDLE,
+            "render/pbr_fragment.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            PARALLAX_MAPPING_SHADER_HANDLE,
+            "render/parallax_mapping.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(app, PBR_SHADER_HANDLE, "render/pbr.wgsl", Shader::from_wgsl);
         load_internal_asset!(
             app,
@@ -127,8 +185,17 @@ impl Plugin for PbrPlugin {
             .register_type::<DirectionalLight>()
             .register_type::<PointLight>()
             .register_type::<SpotLight>()
+            .register_type::<ShadowFilteringMethod>()
             .add_plugin(MeshRenderPlugin)
             .add_plugin(MaterialPlugin::<StandardMaterial>::default())
+            .add_plugin(EnvironmentMapPlugin)
+            .add_plugin(FogPlugin)
+            .add_plugin(LightmapPlugin)
+            .add_plugin(LightTexturePlugin)
+            .add_plugin(DecalPlugin)
+            .add_plugin(MeshLodPlugin)
+            .add_plugin(PolylinePlugin)
+            .add_plugin(ScreenSpaceAmbientOcclusionPlugin)
             .register_type::<AmbientLight>()
             .register_type::<DirectionalLightShadowMap>()
             .register_type::<PointLightShadowMap>()
@@ -137,6 +204,7 @@ impl Plugin for PbrPlugin {
             .init_resource::<DirectionalLightShadowMap>()
             .init_resource::<PointLightShadowMap>()
             .add_plugin(ExtractResourcePlugin::<AmbientLight>::default())
+            .add_plugin(ExtractComponentPlugin::<ShadowFilteringMethod>::default())
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 // NOTE: Clusters need to have been added before update_clusters is run so
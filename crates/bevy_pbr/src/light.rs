@@ -1,11 +1,12 @@
 use std::collections::HashSet;
 
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_math::{Mat4, UVec2, UVec3, Vec2, Vec3, Vec3A, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_reflect::prelude::*;
 use bevy_render::{
     camera::{Camera, CameraProjection, OrthographicProjection},
     color::Color,
+    extract_component::ExtractComponent,
     extract_resource::ExtractResource,
     primitives::{Aabb, CubemapFrusta, Frustum, Plane, Sphere},
     render_resource::BufferBindingType,
@@ -17,7 +18,7 @@ use bevy_utils::tracing::warn;
 
 use crate::{
     calculate_cluster_factors, spot_light_projection_matrix, spot_light_view_matrix, CubeMapFace,
-    CubemapVisibleEntities, ViewClusterBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    CubemapVisibleEntities, Decal, ViewClusterBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
     CUBE_MAP_FACES, MAX_UNIFORM_BUFFER_POINT_LIGHTS, POINT_LIGHT_NEAR_Z,
 };
 
@@ -38,6 +39,9 @@ use crate::{
 /// | 4000 | 300 |    | 75-100 | 40.5  |
 ///
 /// Source: [Wikipedia](https://en.wikipedia.org/wiki/Lumen_(unit)#Lighting)
+///
+/// The light only illuminates, and casts shadows from, the meshes with intersecting
+/// [`RenderLayers`], which are layer `0` for entities without the component.
 #[derive(Component, Debug, Clone, Copy, Reflect)]
 #[reflect(Component, Default)]
 pub struct PointLight {
@@ -51,6 +55,12 @@ pub struct PointLight {
     /// shadow map's texel size so that it can be small close to the camera and gets larger further
     /// away.
     pub shadow_normal_bias: f32,
+    /// The width in texels of the faces of the shadow map of this light, overriding
+    /// [`PointLightShadowMap::size`].
+    ///
+    /// Defaults to `None`. The shadow maps of all the point lights share the same texture, as
+    /// large as the largest one, so a smaller size is only cheaper to render.
+    pub shadow_map_size: Option<usize>,
 }
 
 impl Default for PointLight {
@@ -64,6 +74,7 @@ impl Default for PointLight {
             shadows_enabled: false,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_map_size: None,
         }
     }
 }
@@ -73,6 +84,8 @@ impl PointLight {
     pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.6;
 }
 
+/// The default width in texels of the faces of the shadow maps of the point lights, unless
+/// overridden by their [`PointLight::shadow_map_size`].
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct PointLightShadowMap {
@@ -89,6 +102,9 @@ impl Default for PointLightShadowMap {
 /// Behaves like a point light in a perfectly absorbant housing that
 /// shines light only in a given direction. The direction is taken from
 /// the transform, and can be specified with [`Transform::looking_at`](bevy_transform::components::Transform::looking_at).
+///
+/// The light only illuminates, and casts shadows from, the meshes with intersecting
+/// [`RenderLayers`], which are layer `0` for entities without the component.
 #[derive(Component, Debug, Clone, Copy, Reflect)]
 #[reflect(Component, Default)]
 pub struct SpotLight {
@@ -113,6 +129,13 @@ pub struct SpotLight {
     /// Light is attenuated from `inner_angle` to `outer_angle` to give a smooth falloff.
     /// `inner_angle` should be <= `outer_angle`
     pub inner_angle: f32,
+    /// The width in texels of the shadow map of this light, overriding
+    /// [`DirectionalLightShadowMap::size`].
+    ///
+    /// Defaults to `None`. The shadow maps of the spot lights share the same texture as the
+    /// directional lights, as large as the largest one, so a smaller size is only cheaper to
+    /// render.
+    pub shadow_map_size: Option<usize>,
 }
 
 impl SpotLight {
@@ -134,6 +157,7 @@ impl Default for SpotLight {
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
             inner_angle: 0.0,
             outer_angle: std::f32::consts::FRAC_PI_4,
+            shadow_map_size: None,
         }
     }
 }
@@ -167,6 +191,9 @@ impl Default for SpotLight {
 /// | 32,000–100,000    | Direct sunlight                                |
 ///
 /// Source: [Wikipedia](https://en.wikipedia.org/wiki/Lux)
+///
+/// The light only illuminates, and casts shadows from, the meshes with intersecting
+/// [`RenderLayers`], which are layer `0` for entities without the component.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Default)]
 pub struct DirectionalLight {
@@ -179,6 +206,12 @@ pub struct DirectionalLight {
     /// A bias applied along the direction of the fragment's surface normal. It is scaled to the
     /// shadow map's texel size so that it is automatically adjusted to the orthographic projection.
     pub shadow_normal_bias: f32,
+    /// The width in texels of the shadow map of this light, overriding
+    /// [`DirectionalLightShadowMap::size`].
+    ///
+    /// Defaults to `None`. The shadow maps of the directional lights share the same texture as
+    /// the spot lights, as large as the largest one, so a smaller size is only cheaper to render.
+    pub shadow_map_size: Option<usize>,
 }
 
 impl Default for DirectionalLight {
@@ -199,6 +232,7 @@ impl Default for DirectionalLight {
             },
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_map_size: None,
         }
     }
 }
@@ -208,6 +242,8 @@ impl DirectionalLight {
     pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.6;
 }
 
+/// The default width in texels of the shadow maps of the directional and spot lights, unless
+/// overridden by their `shadow_map_size`.
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct DirectionalLightShadowMap {
@@ -223,6 +259,40 @@ impl Default for DirectionalLightShadowMap {
     }
 }
 
+/// Add this component to a camera to choose how the shadows of the directional and spot lights
+/// are filtered in what it renders.
+///
+/// The shadows of the point lights are always filtered with
+/// [`Hardware2x2`](ShadowFilteringMethod::Hardware2x2).
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, Default)]
+pub enum ShadowFilteringMethod {
+    /// A single bilinear comparison of the 2x2 texels around the fragment, filtered by the GPU.
+    ///
+    /// This is the cheapest, but shows the texels of the shadow map as blocky edges.
+    #[default]
+    Hardware2x2,
+    /// A 13 texel wide Gaussian filter, in 9 bilinear comparisons.
+    ///
+    /// This softens the edges of the shadows without any noise.
+    Gaussian,
+    /// 8 bilinear comparisons in a spiral, rotated randomly for each fragment and frame.
+    ///
+    /// This is about as soft as [`Gaussian`](ShadowFilteringMethod::Gaussian) for fewer
+    /// samples, but is noisy, and meant to be smoothed by
+    /// [`TemporalAntiAliasing`](bevy_core_pipeline::taa::TemporalAntiAliasing).
+    Temporal,
+}
+
+impl ExtractComponent for ShadowFilteringMethod {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
 /// An ambient light, which lights the entire scene equally.
 #[derive(Resource, Clone, Debug, ExtractResource, Reflect)]
 #[reflect(Resource)]
@@ -476,6 +546,9 @@ pub struct VisiblePointLights {
     pub(crate) entities: Vec<Entity>,
     pub point_light_count: usize,
     pub spot_light_count: usize,
+    /// The [`Decal`]s, after the lights, which are only assigned to the clusters with storage
+    /// buffers.
+    pub decal_count: usize,
 }
 
 impl VisiblePointLights {
@@ -762,6 +835,7 @@ pub(crate) struct PointLightAssignmentData {
     range: f32,
     shadows_enabled: bool,
     spot_light_angle: Option<f32>,
+    decal: bool,
 }
 
 impl PointLightAssignmentData {
@@ -806,6 +880,7 @@ pub(crate) fn assign_lights_to_clusters(
     )>,
     point_lights_query: Query<(Entity, &GlobalTransform, &PointLight, &ComputedVisibility)>,
     spot_lights_query: Query<(Entity, &GlobalTransform, &SpotLight, &ComputedVisibility)>,
+    decals_query: Query<(Entity, &GlobalTransform, &Decal, &ComputedVisibility)>,
     mut lights: Local<Vec<PointLightAssignmentData>>,
     mut cluster_aabb_spheres: Local<Vec<Option<Sphere>>>,
     mut max_point_lights_warning_emitted: Local<bool>,
@@ -830,6 +905,7 @@ pub(crate) fn assign_lights_to_clusters(
                     shadows_enabled: point_light.shadows_enabled,
                     range: point_light.range,
                     spot_light_angle: None,
+                    decal: false,
                 },
             ),
     );
@@ -844,6 +920,7 @@ pub(crate) fn assign_lights_to_clusters(
                     shadows_enabled: spot_light.shadows_enabled,
                     range: spot_light.range,
                     spot_light_angle: Some(spot_light.outer_angle),
+                    decal: false,
                 },
             ),
     );
@@ -854,6 +931,25 @@ pub(crate) fn assign_lights_to_clusters(
         clustered_forward_buffer_binding_type,
         BufferBindingType::Storage { .. }
     );
+    // NOTE: The decals are assigned after the lights, to be sorted after them in the clusters,
+    // and only with storage buffers, as the uniform buffers have no room for their count
+    if supports_storage_buffers {
+        lights.extend(
+            decals_query
+                .iter()
+                .filter(|(.., visibility)| visibility.is_visible())
+                .map(
+                    |(entity, transform, _decal, _visibility)| PointLightAssignmentData {
+                        entity,
+                        transform: GlobalTransform::from_translation(transform.translation()),
+                        shadows_enabled: false,
+                        range: decal_bounding_radius(transform),
+                        spot_light_angle: None,
+                        decal: true,
+                    },
+                ),
+        );
+    }
     if lights.len() > MAX_UNIFORM_BUFFER_POINT_LIGHTS && !supports_storage_buffers {
         lights.sort_by(|light_1, light_2| {
             point_light_order(
@@ -1057,6 +1153,7 @@ pub(crate) fn assign_lights_to_clusters(
             lights.entities.clear();
             lights.point_light_count = 0;
             lights.spot_light_count = 0;
+            lights.decal_count = 0;
         }
         let cluster_count =
             (clusters.dimensions.x * clusters.dimensions.y * clusters.dimensions.z) as usize;
@@ -1135,8 +1232,10 @@ pub(crate) fn assign_lights_to_clusters(
                 }
 
                 // NOTE: The light intersects the frustum so it must be visible and part of the global set
-                global_lights.entities.insert(light.entity);
-                visible_lights.push(light.entity);
+                if !light.decal {
+                    global_lights.entities.insert(light.entity);
+                    visible_lights.push(light.entity);
+                }
 
                 // note: caching seems to be slower than calling twice for this aabb calculation
                 let (light_aabb_xy_ndc_z_view_min, light_aabb_xy_ndc_z_view_max) =
@@ -1330,9 +1429,13 @@ pub(crate) fn assign_lights_to_clusters(
                             }
                         } else {
                             for _ in min_x..=max_x {
-                                // all clusters within range are affected by point lights
+                                // all clusters within range are affected by point lights and decals
                                 clusters.lights[cluster_index].entities.push(light.entity);
-                                clusters.lights[cluster_index].point_light_count += 1;
+                                if light.decal {
+                                    clusters.lights[cluster_index].decal_count += 1;
+                                } else {
+                                    clusters.lights[cluster_index].point_light_count += 1;
+                                }
                                 cluster_index += clusters.dimensions.z as usize;
                             }
                         }
@@ -1356,6 +1459,18 @@ pub(crate) fn assign_lights_to_clusters(
     }
 }
 
+/// The radius of the sphere bounding the box of a [`Decal`], around its translation.
+fn decal_bounding_radius(transform: &GlobalTransform) -> f32 {
+    let axes = transform.affine().matrix3;
+    let (x, y, z) = (axes.x_axis, axes.y_axis, axes.z_axis);
+    // The corners of the box are half of the sums of its axes, opposite corners are as far
+    [x + y + z, x + y - z, x - y + z, x - y - z]
+        .iter()
+        .map(|diagonal| diagonal.length())
+        .fold(0.0, f32::max)
+        * 0.5
+}
+
 // NOTE: This exploits the fact that a x-plane normal has only x and z components
 fn get_distance_x(plane: Plane, point: Vec3A, is_orthographic: bool) -> f32 {
     if is_orthographic {
@@ -1790,4 +1905,22 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn decal_bounding_radius_reaches_the_corners() {
+        let transform = GlobalTransform::from(
+            Transform::from_scale(Vec3::new(2.0, 4.0, 4.0))
+                .with_rotation(bevy_math::Quat::from_rotation_y(0.5)),
+        );
+        assert!((decal_bounding_radius(&transform) - 3.0).abs() < 1e-5);
+
+        // A sheared box is bounded by its longest diagonal
+        let transform = GlobalTransform::from(bevy_math::Affine3A::from_cols(
+            Vec3A::X,
+            Vec3A::new(1.0, 1.0, 0.0),
+            Vec3A::Z,
+            Vec3A::ZERO,
+        ));
+        assert!((decal_bounding_radius(&transform) - 0.5 * 6.0_f32.sqrt()).abs() < 1e-5);
+    }
 }
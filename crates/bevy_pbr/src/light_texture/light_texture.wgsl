@@ -0,0 +1,84 @@
+#define_import_path bevy_pbr::light_texture
+
+// The color the light texture of the spot light `light_id` multiplies its light by at
+// `world_position`, projected like its shadow map. White for lights without a texture.
+fn spot_light_texture(light_id: u32, world_position: vec3<f32>) -> vec3<f32> {
+    let light = point_lights.data[light_id];
+    let texture_index = light.flags >> POINT_LIGHT_FLAGS_TEXTURE_INDEX_SHIFT;
+    if (texture_index == 0u) {
+        return vec3<f32>(1.0);
+    }
+
+    // reconstruct spot dir from x/z and y-direction flag
+    var spot_dir = vec3<f32>(light.light_custom_data.x, 0.0, light.light_custom_data.y);
+    spot_dir.y = sqrt(max(0.0, 1.0 - spot_dir.x * spot_dir.x - spot_dir.z * spot_dir.z));
+    if ((light.flags & POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE) != 0u) {
+        spot_dir.y = -spot_dir.y;
+    }
+
+    // the same basis as the view of the shadow map of the light, see fetch_spot_shadow
+    let fwd = -spot_dir;
+    var sign = -1.0;
+    if (fwd.z >= 0.0) {
+        sign = 1.0;
+    }
+    let a = -1.0 / (fwd.z + sign);
+    let b = fwd.x * fwd.y * a;
+    let up_dir = vec3<f32>(1.0 + sign * fwd.x * fwd.x * a, sign * b, -sign * fwd.x);
+    let right_dir = vec3<f32>(-b, -sign - fwd.y * fwd.y * a, fwd.y);
+    let light_inv_rot = mat3x3<f32>(right_dir, up_dir, fwd);
+    let projected_position = (world_position - light.position_radius.xyz) * light_inv_rot;
+    if (projected_position.z >= 0.0) {
+        // Behind the light, which doesn't light it
+        return vec3<f32>(0.0);
+    }
+
+    // The tangent of the outer angle of the light, from its spot_scale and spot_offset
+    let cos_outer = -light.light_custom_data.w / light.light_custom_data.z;
+    let tan_outer = sqrt(1.0 - cos_outer * cos_outer) / cos_outer;
+    let xy_ndc = projected_position.xy / (tan_outer * -projected_position.z);
+    let uv = xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
+
+    // NOTE: The light loops are non-uniform control flow, so the level variant is used
+#ifdef NO_ARRAY_TEXTURES_SUPPORT
+    return textureSampleLevel(spot_light_textures, light_textures_sampler, uv, 0.0).rgb;
+#else
+    return textureSampleLevel(
+        spot_light_textures,
+        light_textures_sampler,
+        uv,
+        i32(texture_index - 1u),
+        0.0
+    ).rgb;
+#endif
+}
+
+// The color the light texture of the point light `light_id` multiplies its light by at
+// `world_position`, in the direction from the light rotated by its rotation. White for lights
+// without a texture.
+fn point_light_texture(light_id: u32, world_position: vec3<f32>) -> vec3<f32> {
+    let light = point_lights.data[light_id];
+    let texture_index = light.flags >> POINT_LIGHT_FLAGS_TEXTURE_INDEX_SHIFT;
+    if (texture_index == 0u) {
+        return vec3<f32>(1.0);
+    }
+
+    // Rotate the direction by the inverse of the rotation of the light, a quaternion whose
+    // vector part is negated
+    let direction = world_position - light.position_radius.xyz;
+    let axis = -light.light_custom_data.xyz;
+    let t = 2.0 * cross(axis, direction);
+    let local_direction = direction + light.light_custom_data.w * t + cross(axis, t);
+
+#ifdef NO_ARRAY_TEXTURES_SUPPORT
+    return textureSampleLevel(point_light_textures, light_textures_sampler, local_direction, 0.0).rgb;
+#else
+    return textureSampleLevel(
+        point_light_textures,
+        light_textures_sampler,
+        local_direction,
+        i32(texture_index - 1u),
+        0.0
+    ).rgb;
+#endif
+}
@@ -0,0 +1,504 @@
+//! Light textures, also known as cookies or gobos, modulating the light of the [`SpotLight`]s
+//! with a [`SpotLightTexture`] and of the [`PointLight`]s with a [`PointLightTexture`].
+//!
+//! The images of the lights are copied into the layers of a texture array for each kind of
+//! light, [`LIGHT_TEXTURE_SIZE`] texels wide, which are bound to the mesh view bind group. The
+//! flags of each light store the index of its layer, for the meshes to multiply its light by the
+//! texel it projects onto them. The images of the [`Decal`]s share the layers of the spot lights.
+
+use std::num::NonZeroU32;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    color::Color,
+    main_graph::node::CAMERA_DRIVER,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState, LoadOp,
+        MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+        SamplerDescriptor, Shader, ShaderStages, Texture, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureId, TextureSampleType, TextureUsages, TextureView,
+        TextureViewDescriptor, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{GpuImage, Image},
+    view::ComputedVisibility,
+    Extract, RenderApp, RenderStage,
+};
+use bevy_utils::HashMap;
+
+use crate::{main_graph, Decal, PointLight, RenderLightSystems, SpotLight};
+
+pub const LIGHT_TEXTURE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3527410295831576207);
+pub const LIGHT_TEXTURE_COPY_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 11420360915743016489);
+
+/// The width and height in texels of the layers the light textures are copied to.
+pub const LIGHT_TEXTURE_SIZE: u32 = 512;
+
+/// The format of the layers the light textures are copied to.
+pub const LIGHT_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+/// Add this component to a [`SpotLight`] to multiply its light by an image projected along its
+/// cone, such as the pattern of a window or the shape of the beam of a flashlight.
+///
+/// The image covers the square the cone of the light fits in, as seen from the light, and is
+/// oriented like its shadow map: it doesn't turn with the roll of the light around its
+/// direction. It has to be a 2D image with a filterable format, and the light isn't textured
+/// until it's loaded.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct SpotLightTexture {
+    pub image: Handle<Image>,
+}
+
+/// Add this component to a [`PointLight`] to multiply its light by a cubemap in each direction
+/// from the light, such as caustics or the holes of a lantern.
+///
+/// The cubemap turns with the rotation of the light. It has to be a cubemap with a filterable
+/// format, like the maps of an [`EnvironmentMapLight`](crate::EnvironmentMapLight), and the light
+/// isn't textured until it's loaded.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct PointLightTexture {
+    pub image: Handle<Image>,
+}
+
+/// Copies the textures of the lights with a [`SpotLightTexture`] or a [`PointLightTexture`] to
+/// the [`LightTextures`].
+pub struct LightTexturePlugin;
+
+impl Plugin for LightTexturePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LIGHT_TEXTURE_SHADER_HANDLE,
+            "light_texture.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            LIGHT_TEXTURE_COPY_SHADER_HANDLE,
+            "light_texture_copy.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<SpotLightTexture>()
+            .register_type::<PointLightTexture>();
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<ExtractedLightTextures>()
+            .init_resource::<LightTextureCopyPipeline>()
+            .init_resource::<LightTextures>()
+            .add_system_to_stage(RenderStage::Extract, extract_light_textures)
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                // NOTE: The layers of the textures have to be known by prepare_lights, which runs
+                // at the start of the stage
+                prepare_light_textures
+                    .at_start()
+                    .before(RenderLightSystems::PrepareLights),
+            );
+
+        // The textures are copied before the cameras are rendered, so that they are lit by them
+        // this frame
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+        graph.add_node(main_graph::node::LIGHT_TEXTURES, LightTexturesNode);
+        graph
+            .add_node_edge(main_graph::node::LIGHT_TEXTURES, CAMERA_DRIVER)
+            .unwrap();
+    }
+}
+
+/// The images of the visible lights with a light texture, sorted and without duplicates.
+#[derive(Resource, Default)]
+pub struct ExtractedLightTextures {
+    /// Also the images of the visible [`Decal`]s.
+    pub spot_light_images: Vec<Handle<Image>>,
+    /// Only the images with the 6 layers of a cubemap.
+    pub point_light_images: Vec<Handle<Image>>,
+}
+
+pub fn extract_light_textures(
+    mut extracted_light_textures: ResMut<ExtractedLightTextures>,
+    images: Extract<Res<Assets<Image>>>,
+    spot_lights: Extract<Query<(&SpotLightTexture, &ComputedVisibility), With<SpotLight>>>,
+    point_lights: Extract<Query<(&PointLightTexture, &ComputedVisibility), With<PointLight>>>,
+    decals: Extract<Query<(&Decal, &ComputedVisibility)>>,
+) {
+    let ExtractedLightTextures {
+        spot_light_images,
+        point_light_images,
+    } = &mut *extracted_light_textures;
+
+    spot_light_images.clear();
+    spot_light_images.extend(
+        spot_lights
+            .iter()
+            .filter(|(_, visibility)| visibility.is_visible())
+            .map(|(texture, _)| texture.image.clone_weak()),
+    );
+    spot_light_images.extend(
+        decals
+            .iter()
+            .filter(|(_, visibility)| visibility.is_visible())
+            .map(|(decal, _)| decal.image.clone_weak()),
+    );
+    spot_light_images.sort();
+    spot_light_images.dedup();
+
+    point_light_images.clear();
+    point_light_images.extend(
+        point_lights
+            .iter()
+            .filter(|(texture, visibility)| {
+                // NOTE: The images not loaded yet are skipped when the textures are prepared
+                let is_cubemap = match images.get(&texture.image) {
+                    Some(image) => image.texture_descriptor.size.depth_or_array_layers == 6,
+                    None => true,
+                };
+                visibility.is_visible() && is_cubemap
+            })
+            .map(|(texture, _)| texture.image.clone_weak()),
+    );
+    point_light_images.sort();
+    point_light_images.dedup();
+}
+
+/// The layers the light textures are copied to, bound to the mesh view bind group.
+#[derive(Resource)]
+pub struct LightTextures {
+    /// The layers of the spot light textures, and of the images of the [`Decal`]s.
+    pub spot_light_textures: TextureView,
+    /// The cubemaps of the point light textures.
+    pub point_light_textures: TextureView,
+    /// A linear sampler to read the textures with.
+    pub sampler: Sampler,
+    /// The index of the layer each spot light or decal image is copied to.
+    pub spot_light_indices: HashMap<Handle<Image>, u32>,
+    /// The index of the cubemap each point light image is copied to.
+    pub point_light_indices: HashMap<Handle<Image>, u32>,
+    /// The textures of the spot and point light images copied to the layers, to copy them again
+    /// when they change.
+    sources: (Vec<TextureId>, Vec<TextureId>),
+    /// The copies of this frame, run by the [`LightTexturesNode`].
+    copies: Vec<LightTextureCopy>,
+}
+
+struct LightTextureCopy {
+    target: TextureView,
+    bind_group: BindGroup,
+}
+
+impl FromWorld for LightTextures {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("light_textures_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        LightTextures {
+            spot_light_textures: create_light_texture_array(render_device, 1, 1, false).1,
+            point_light_textures: create_light_texture_array(render_device, 1, 1, true).1,
+            sampler,
+            spot_light_indices: HashMap::default(),
+            point_light_indices: HashMap::default(),
+            sources: Default::default(),
+            copies: Vec::new(),
+        }
+    }
+}
+
+/// Creates a texture with `count` layers, or cubemaps if `cube`, `size` texels wide, and the
+/// view binding all of them to the meshes.
+fn create_light_texture_array(
+    render_device: &RenderDevice,
+    count: u32,
+    size: u32,
+    cube: bool,
+) -> (Texture, TextureView) {
+    let (label, layers_per_texture) = if cube {
+        ("point_light_textures", 6)
+    } else {
+        ("spot_light_textures", 1)
+    };
+    let texture = render_device.create_texture(&TextureDescriptor {
+        label: Some(label),
+        size: Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: count * layers_per_texture,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: LIGHT_TEXTURE_FORMAT,
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+    });
+    let dimension = match cube {
+        #[cfg(not(feature = "webgl"))]
+        true => TextureViewDimension::CubeArray,
+        #[cfg(feature = "webgl")]
+        true => TextureViewDimension::Cube,
+        #[cfg(not(feature = "webgl"))]
+        false => TextureViewDimension::D2Array,
+        #[cfg(feature = "webgl")]
+        false => TextureViewDimension::D2,
+    };
+    let view = texture.create_view(&TextureViewDescriptor {
+        label: Some(label),
+        dimension: Some(dimension),
+        ..Default::default()
+    });
+    (texture, view)
+}
+
+/// A view of the layer `layer` of `texture` alone.
+fn layer_view(texture: &Texture, layer: u32) -> TextureView {
+    texture.create_view(&TextureViewDescriptor {
+        label: Some("light_texture_layer_view"),
+        dimension: Some(TextureViewDimension::D2),
+        base_array_layer: layer,
+        array_layer_count: NonZeroU32::new(1),
+        ..Default::default()
+    })
+}
+
+/// Copies the loaded light textures to new [`LightTextures`] when they change.
+pub fn prepare_light_textures(
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    pipeline_cache: Res<PipelineCache>,
+    copy_pipeline: Res<LightTextureCopyPipeline>,
+    extracted_light_textures: Res<ExtractedLightTextures>,
+    mut light_textures: ResMut<LightTextures>,
+) {
+    light_textures.copies.clear();
+    if pipeline_cache
+        .get_render_pipeline(copy_pipeline.pipeline)
+        .is_none()
+    {
+        return;
+    }
+
+    #[cfg(not(feature = "webgl"))]
+    let max_texture_array_layers = render_device.limits().max_texture_array_layers as usize;
+    #[cfg(feature = "webgl")]
+    let max_texture_array_layers = 1;
+
+    let spot_light_images: Vec<_> = extracted_light_textures
+        .spot_light_images
+        .iter()
+        .filter_map(|handle| Some((handle, images.get(handle)?)))
+        .take(max_texture_array_layers)
+        .collect();
+    let point_light_images: Vec<_> = extracted_light_textures
+        .point_light_images
+        .iter()
+        .filter_map(|handle| Some((handle, images.get(handle)?)))
+        .take(max_texture_array_layers / 6)
+        .collect();
+    let texture_ids = |images: &[(&Handle<Image>, &GpuImage)]| {
+        images
+            .iter()
+            .map(|(_, image)| image.texture.id())
+            .collect::<Vec<_>>()
+    };
+    let sources = (
+        texture_ids(&spot_light_images),
+        texture_ids(&point_light_images),
+    );
+    if sources == light_textures.sources {
+        return;
+    }
+
+    let mut copies = Vec::new();
+    let mut copy_layers =
+        |source: &Texture, target: &Texture, source_layers: u32, first_target_layer: u32| {
+            for layer in 0..source_layers {
+                let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("light_texture_copy_bind_group"),
+                    layout: &copy_pipeline.layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(&layer_view(source, layer)),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&copy_pipeline.sampler),
+                        },
+                    ],
+                });
+                copies.push(LightTextureCopy {
+                    target: layer_view(target, first_target_layer + layer),
+                    bind_group,
+                });
+            }
+        };
+
+    let size = |count| if count == 0 { 1 } else { LIGHT_TEXTURE_SIZE };
+    let (spot_light_texture, spot_light_textures) = create_light_texture_array(
+        &render_device,
+        spot_light_images.len().max(1) as u32,
+        size(spot_light_images.len()),
+        false,
+    );
+    let (point_light_texture, point_light_textures) = create_light_texture_array(
+        &render_device,
+        point_light_images.len().max(1) as u32,
+        size(point_light_images.len()),
+        true,
+    );
+    let mut spot_light_indices = HashMap::default();
+    for (index, (handle, image)) in spot_light_images.iter().enumerate() {
+        copy_layers(&image.texture, &spot_light_texture, 1, index as u32);
+        spot_light_indices.insert((*handle).clone_weak(), index as u32);
+    }
+    let mut point_light_indices = HashMap::default();
+    for (index, (handle, image)) in point_light_images.iter().enumerate() {
+        copy_layers(&image.texture, &point_light_texture, 6, 6 * index as u32);
+        point_light_indices.insert((*handle).clone_weak(), index as u32);
+    }
+
+    *light_textures = LightTextures {
+        spot_light_textures,
+        point_light_textures,
+        sampler: light_textures.sampler.clone(),
+        spot_light_indices,
+        point_light_indices,
+        sources,
+        copies,
+    };
+}
+
+/// The pipeline copying a layer of a light texture to a layer of the [`LightTextures`].
+#[derive(Resource)]
+pub struct LightTextureCopyPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    pipeline: CachedRenderPipelineId,
+}
+
+impl FromWorld for LightTextureCopyPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("light_texture_copy_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        // NOTE: The images larger than the layers are read from their mipmaps, if they have any
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("light_texture_copy_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        let pipeline =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    vertex: VertexState {
+                        shader: LIGHT_TEXTURE_COPY_SHADER_HANDLE.typed::<Shader>(),
+                        entry_point: "vertex".into(),
+                        shader_defs: vec![],
+                        buffers: vec![],
+                    },
+                    fragment: Some(FragmentState {
+                        shader: LIGHT_TEXTURE_COPY_SHADER_HANDLE.typed::<Shader>(),
+                        shader_defs: vec![],
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: LIGHT_TEXTURE_FORMAT,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    layout: Some(vec![layout.clone()]),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    label: Some("light_texture_copy_pipeline".into()),
+                });
+        LightTextureCopyPipeline {
+            layout,
+            sampler,
+            pipeline,
+        }
+    }
+}
+
+/// Runs the copies of the light textures prepared this frame.
+pub struct LightTexturesNode;
+
+impl Node for LightTexturesNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let light_textures = world.resource::<LightTextures>();
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(world.resource::<LightTextureCopyPipeline>().pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        for copy in &light_textures.copies {
+            let pass_descriptor = RenderPassDescriptor {
+                label: Some("light_texture_copy_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &copy.target,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::BLACK.into()),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            };
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+            tracked_pass.set_render_pipeline(pipeline);
+            tracked_pass.set_bind_group(0, &copy.bind_group, &[]);
+            tracked_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+#define_import_path bevy_pbr::lightmap
+
+@group(3) @binding(0)
+var lightmap_texture: texture_2d<f32>;
+@group(3) @binding(1)
+var lightmap_sampler: sampler;
+
+// Returns the light baked in the lightmap of the mesh at `uv`, in its second UV set, scaled by
+// `exposure`
+fn lightmap(uv: vec2<f32>, exposure: f32) -> vec3<f32> {
+    let lightmap_uv = mix(mesh.lightmap_uv_rect.xy, mesh.lightmap_uv_rect.zw, uv);
+    return textureSample(lightmap_texture, lightmap_sampler, lightmap_uv).rgb * exposure;
+}
@@ -0,0 +1,168 @@
+//! Baked global illumination, for the meshes that opt into it with [`Lightmap`].
+//!
+//! The lightmap of each mesh is bound after its other bind groups, and the meshes are
+//! specialized with the `LIGHTMAP` shader def to replace their diffuse ambient light by the light
+//! baked in it, sampled with their second UV set.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::{
+    prelude::*,
+    query::QueryItem,
+    system::{
+        lifetimeless::{Read, SQuery, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    mesh::Mesh,
+    render_asset::RenderAssets,
+    render_phase::{EntityRenderCommand, RenderCommandResult, TrackedRenderPass},
+    render_resource::{BindGroup, BindGroupDescriptor, BindGroupEntry, BindingResource, Shader},
+    renderer::RenderDevice,
+    texture::Image,
+    RenderApp, RenderStage,
+};
+use bevy_utils::HashMap;
+
+use crate::MeshPipeline;
+
+pub const LIGHTMAP_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9078539174620411862);
+
+/// Add this component to a mesh drawn with a [`StandardMaterial`](crate::StandardMaterial) to
+/// light it with the indirect light baked into an image, such as by an offline renderer.
+///
+/// The lightmap is sampled with the second UV set of the mesh,
+/// [`Mesh::ATTRIBUTE_UV_1`], which is loaded from the `TEXCOORD_1` of glTF files. Its light
+/// replaces the diffuse [`AmbientLight`](crate::AmbientLight) of the mesh, scaled by the
+/// [`lightmap_exposure`](crate::StandardMaterial::lightmap_exposure) of its material, and is
+/// still occluded by its occlusion texture. The direct light of the lights in the scene is added
+/// as usual, so it shouldn't be baked as well.
+///
+/// The mesh isn't lightmapped until the image is loaded, nor without a second UV set. Meshes
+/// drawn in instanced batches aren't lightmapped.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Lightmap {
+    /// The image the light is baked into.
+    pub image: Handle<Image>,
+    /// The rect of the image the second UV set of the mesh maps to, in UV coordinates.
+    ///
+    /// This lets meshes share the same atlas of lightmaps. Defaults to the whole image.
+    pub uv_rect: Rect,
+}
+
+impl Default for Lightmap {
+    fn default() -> Self {
+        Lightmap {
+            image: Handle::default(),
+            uv_rect: Rect::from_corners(Vec2::ZERO, Vec2::ONE),
+        }
+    }
+}
+
+impl ExtractComponent for Lightmap {
+    type Query = &'static Self;
+    type Filter = With<Handle<Mesh>>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+/// Lights the meshes with a [`Lightmap`].
+pub struct LightmapPlugin;
+
+impl Plugin for LightmapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LIGHTMAP_SHADER_HANDLE,
+            "lightmap.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Lightmap>()
+            .add_plugin(ExtractComponentPlugin::<Lightmap>::extract_visible());
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<LightmapBindGroups>()
+                .add_system_to_stage(RenderStage::Queue, queue_lightmap_bind_groups);
+        }
+    }
+}
+
+/// The bind groups of the loaded lightmaps, shared by the meshes with the same image.
+#[derive(Resource, Default)]
+pub struct LightmapBindGroups {
+    pub bind_groups: HashMap<Handle<Image>, BindGroup>,
+}
+
+pub fn queue_lightmap_bind_groups(
+    render_device: Res<RenderDevice>,
+    mesh_pipeline: Res<MeshPipeline>,
+    images: Res<RenderAssets<Image>>,
+    mut lightmap_bind_groups: ResMut<LightmapBindGroups>,
+    lightmaps: Query<&Lightmap>,
+) {
+    lightmap_bind_groups.bind_groups.clear();
+    for lightmap in &lightmaps {
+        if lightmap_bind_groups
+            .bind_groups
+            .contains_key(&lightmap.image)
+        {
+            continue;
+        }
+        let image = match images.get(&lightmap.image) {
+            Some(image) => image,
+            None => continue,
+        };
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("lightmap_bind_group"),
+            layout: &mesh_pipeline.lightmap_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&image.texture_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&image.sampler),
+                },
+            ],
+        });
+        lightmap_bind_groups
+            .bind_groups
+            .insert(lightmap.image.clone_weak(), bind_group);
+    }
+}
+
+/// Sets the bind group of the [`Lightmap`] of the mesh at the configured `I` index, if it has a
+/// loaded one.
+pub struct SetLightmapBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetLightmapBindGroup<I> {
+    type Param = (SRes<LightmapBindGroups>, SQuery<Read<Lightmap>>);
+    #[inline]
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (lightmap_bind_groups, lightmap_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        if let Ok(lightmap) = lightmap_query.get_inner(item) {
+            if let Some(bind_group) = lightmap_bind_groups
+                .into_inner()
+                .bind_groups
+                .get(&lightmap.image)
+            {
+                pass.set_bind_group(I, bind_group, &[]);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
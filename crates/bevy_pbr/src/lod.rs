@@ -0,0 +1,265 @@
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::Handle;
+use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_ecs::prelude::*;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_render::{
+    camera::{Camera, CameraUpdateSystem},
+    mesh::{skinning::SkinnedMesh, Mesh},
+    primitives::Aabb,
+    view::{ExtractedView, VisibleEntities},
+    RenderApp, RenderStage,
+};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_utils::HashMap;
+
+/// Switches the [`Handle<Mesh>`] of an entity between levels of detail, with the distance or the
+/// screen size of the entity in the active 3D cameras, so that the meshes far away aren't drawn
+/// at full resolution.
+///
+/// Each level is used from its threshold until the one of the next level, and the most detailed
+/// level needed by any camera is drawn. The entity needs a [`Handle<Mesh>`], such as the one of a
+/// [`PbrBundle`](crate::PbrBundle), which is replaced by the mesh of the selected level. Its
+/// [`Aabb`] is computed from its first mesh and isn't updated, so the levels should have the same
+/// bounds.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct MeshLod {
+    /// The levels of detail, from the most detailed one. The threshold of the first level is
+    /// ignored.
+    pub levels: Vec<MeshLodLevel>,
+    /// What the thresholds of the levels are compared with.
+    pub selection: LodSelection,
+    /// The fraction of its threshold over which each level cross-fades with the previous one,
+    /// with a dithering pattern, instead of replacing it at once. The previous level is drawn as
+    /// well during the cross-fade, without casting shadows, and both are left out of the depth
+    /// prepass. Skinned meshes don't cross-fade.
+    pub cross_fade: Option<f32>,
+    #[reflect(ignore)]
+    level: usize,
+    #[reflect(ignore)]
+    fade: Option<f32>,
+}
+
+impl MeshLod {
+    /// The index of the level of detail currently drawn.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// How far the current level is into its cross-fade with the previous one, from `0.0` to
+    /// `1.0`, or `None` if it isn't cross-fading.
+    pub fn cross_fade_progress(&self) -> Option<f32> {
+        self.fade
+    }
+
+    /// The mesh of the previous level while the current one cross-fades with it, and the progress
+    /// of the cross-fade.
+    pub(crate) fn fading_out(&self) -> Option<(&Handle<Mesh>, f32)> {
+        let fade = self.fade?;
+        let previous = self.levels.get(self.level.checked_sub(1)?)?;
+        Some((&previous.mesh, fade))
+    }
+}
+
+/// A level of detail of a [`MeshLod`].
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct MeshLodLevel {
+    pub mesh: Handle<Mesh>,
+    /// With [`LodSelection::Distance`], the distance to the camera from which the level is used.
+    /// With [`LodSelection::ScreenSize`], the screen size under which the level is used.
+    pub threshold: f32,
+}
+
+/// What the thresholds of the levels of a [`MeshLod`] are compared with.
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LodSelection {
+    /// The distance in world units from the camera to the origin of the entity.
+    #[default]
+    Distance,
+    /// The fraction of the height of the viewport covered by the bounding sphere of the [`Aabb`]
+    /// of the entity. Entities without an [`Aabb`] keep their first level.
+    ScreenSize,
+}
+
+/// Selects the levels of detail of the [`MeshLod`]s.
+pub struct MeshLodPlugin;
+
+impl Plugin for MeshLodPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MeshLod>().add_system_to_stage(
+            CoreStage::PostUpdate,
+            select_mesh_lods
+                .after(CameraUpdateSystem)
+                .after(TransformSystem::TransformPropagate),
+        );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app.add_system_to_stage(RenderStage::Prepare, prepare_mesh_lod_fade_outs);
+    }
+}
+
+/// Returns the level of detail of `levels` to draw for a camera, and the progress of its
+/// cross-fade with the previous level.
+fn select_lod_level(
+    levels: &[MeshLodLevel],
+    selection: LodSelection,
+    cross_fade: Option<f32>,
+    distance: f32,
+    screen_size: f32,
+) -> (usize, Option<f32>) {
+    for (index, level) in levels.iter().enumerate().skip(1).rev() {
+        // How far past the threshold the camera is, over 1.0 once the level is used
+        let ratio = match selection {
+            LodSelection::Distance => distance / level.threshold,
+            LodSelection::ScreenSize => level.threshold / screen_size,
+        };
+        if ratio >= 1.0 {
+            let fade = cross_fade
+                .map(|cross_fade| (ratio - 1.0) / cross_fade)
+                .filter(|fade| *fade < 1.0);
+            return (index, fade);
+        }
+    }
+    (0, None)
+}
+
+pub fn select_mesh_lods(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut meshes: Query<(
+        &mut MeshLod,
+        &mut Handle<Mesh>,
+        &GlobalTransform,
+        Option<&Aabb>,
+        Option<With<SkinnedMesh>>,
+    )>,
+) {
+    for (mut lod, mut mesh, transform, aabb, skinned) in &mut meshes {
+        if lod.levels.is_empty() {
+            continue;
+        }
+        let cross_fade = if skinned.is_some() {
+            None
+        } else {
+            lod.cross_fade
+        };
+
+        // The bounding sphere of the Aabb, scaled by the largest scale of the entity. The entities
+        // without an Aabb cover the whole screen, so they keep their first level.
+        let (scale, _, _) = transform.to_scale_rotation_translation();
+        let radius = aabb.map_or(f32::INFINITY, |aabb| {
+            aabb.half_extents.length() * scale.abs().max_element()
+        });
+
+        let mut selected: Option<(usize, Option<f32>)> = None;
+        for (camera, camera_transform) in &cameras {
+            if !camera.is_active {
+                continue;
+            }
+            let distance = transform
+                .translation()
+                .distance(camera_transform.translation());
+            let projection = camera.projection_matrix();
+            // The ratio of the radius to half the height of the view at the distance
+            let screen_size = if projection.w_axis.w == 1.0 {
+                radius * projection.y_axis.y
+            } else {
+                radius * projection.y_axis.y / distance
+            };
+            let (level, fade) = select_lod_level(
+                &lod.levels,
+                lod.selection,
+                cross_fade,
+                distance,
+                screen_size,
+            );
+
+            // Keeps the most detailed level, and the least faded in one
+            let is_more_detailed = match selected {
+                Some((selected_level, selected_fade)) => {
+                    (level, fade.unwrap_or(1.0)) < (selected_level, selected_fade.unwrap_or(1.0))
+                }
+                None => true,
+            };
+            if is_more_detailed {
+                selected = Some((level, fade));
+            }
+        }
+
+        let (level, fade) = match selected {
+            Some(selected) => selected,
+            None => continue,
+        };
+        if lod.level != level || lod.fade != fade {
+            lod.level = level;
+            lod.fade = fade;
+        }
+        if *mesh != lod.levels[level].mesh {
+            *mesh = lod.levels[level].mesh.clone();
+        }
+    }
+}
+
+/// The render entities drawing the previous levels of detail of the [`MeshLod`]s while they
+/// cross-fade, spawned by [`extract_meshes`](crate::extract_meshes) each frame.
+#[derive(Resource, Default)]
+pub struct MeshLodFadeOuts {
+    /// The entity of each [`MeshLod`], and the entity drawing its previous level.
+    pub entities: Vec<(Entity, Entity)>,
+}
+
+/// Adds the entities drawing the previous levels of the cross-fading [`MeshLod`]s to the views
+/// their [`MeshLod`] is visible in.
+pub fn prepare_mesh_lod_fade_outs(
+    fade_outs: Res<MeshLodFadeOuts>,
+    mut views: Query<&mut VisibleEntities, With<ExtractedView>>,
+) {
+    if fade_outs.entities.is_empty() {
+        return;
+    }
+    let fade_outs: HashMap<Entity, Entity> = fade_outs.entities.iter().copied().collect();
+    for mut visible_entities in &mut views {
+        let visible_fade_outs = visible_entities
+            .entities
+            .iter()
+            .filter_map(|entity| fade_outs.get(entity).copied())
+            .collect::<Vec<_>>();
+        visible_entities.entities.extend(visible_fade_outs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lod_level_selection() {
+        let levels = [0.0, 8.0, 16.0].map(|threshold| MeshLodLevel {
+            mesh: Handle::default(),
+            threshold,
+        });
+        let distance = |distance, cross_fade| {
+            select_lod_level(&levels, LodSelection::Distance, cross_fade, distance, 0.0)
+        };
+        assert_eq!(distance(4.0, None), (0, None));
+        assert_eq!(distance(8.0, None), (1, None));
+        assert_eq!(distance(20.0, None), (2, None));
+        assert_eq!(distance(9.0, Some(0.25)), (1, Some(0.5)));
+        assert_eq!(distance(10.0, Some(0.25)), (1, None));
+
+        let levels = [1.0, 0.5, 0.1].map(|threshold| MeshLodLevel {
+            mesh: Handle::default(),
+            threshold,
+        });
+        let screen_size = |screen_size| {
+            select_lod_level(&levels, LodSelection::ScreenSize, None, 0.0, screen_size)
+        };
+        assert_eq!(screen_size(0.8), (0, None));
+        assert_eq!(screen_size(0.3), (1, None));
+        assert_eq!(screen_size(0.05), (2, None));
+    }
+}
@@ -1,15 +1,23 @@
 use crate::{
-    AlphaMode, DrawMesh, MeshPipeline, MeshPipelineKey, MeshUniform, SetMeshBindGroup,
-    SetMeshViewBindGroup,
+    extract_meshes, wireframe::WireframeOnly, AlphaMode, DrawMesh, EnvironmentMapLight, Lightmap,
+    MeshLodFadeOuts, MeshPipeline, MeshPipelineKey, MeshUniform, SetLightmapBindGroup,
+    SetMeshBindGroup, SetMeshViewBindGroup, SetTransmissiveMeshViewBindGroup,
+    ShadowFilteringMethod,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle};
-use bevy_core_pipeline::core_3d::{AlphaMask3d, Opaque3d, Transparent3d};
+use bevy_core_pipeline::{
+    core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d},
+    oit::OrderIndependentTransparency,
+    prepass::{MotionVectorPrepass, Opaque3dPrepass},
+    transmission::queue_transmission_textures,
+};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     entity::Entity,
     event::EventReader,
     prelude::World,
+    query::Without,
     schedule::IntoSystemDescriptor,
     system::{
         lifetimeless::{Read, SQuery, SRes},
@@ -20,7 +28,7 @@ use bevy_ecs::{
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     extract_component::ExtractComponentPlugin,
-    mesh::{Mesh, MeshVertexBufferLayout},
+    mesh::{InstancedMesh, Mesh, MeshVertexAttribute, MeshVertexBufferLayout},
     prelude::Image,
     render_asset::{PrepareAssetLabel, RenderAssets},
     render_phase::{
@@ -111,12 +119,19 @@ use std::marker::PhantomData;
 pub trait Material: AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'static {
     /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default mesh vertex shader
     /// will be used.
+    ///
+    /// The vertex shader is also used in the prepass, where the motion vectors of cameras with a
+    /// [`MotionVectorPrepass`] are computed from the `world_position` it outputs at location 0.
     fn vertex_shader() -> ShaderRef {
         ShaderRef::Default
     }
 
     /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the default mesh fragment shader
     /// will be used.
+    ///
+    /// Materials using [`AlphaMode::Blend`] are drawn by cameras with
+    /// [`OrderIndependentTransparency`] with the `OIT` shader def, and their fragment shader then
+    /// has to return the `OitFragmentOutput` of `oit_fragment_output`, from `bevy_core_pipeline::oit`.
     #[allow(unused_variables)]
     fn fragment_shader() -> ShaderRef {
         ShaderRef::Default
@@ -135,6 +150,28 @@ pub trait Material: AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'stat
         0.0
     }
 
+    /// Returns whether this material reads the
+    /// [`ViewTransmissionTexture`](bevy_core_pipeline::transmission::ViewTransmissionTexture),
+    /// the opaque scene behind it. Defaults to `false`.
+    ///
+    /// Such materials, unless they use [`AlphaMode::Blend`], are drawn in the [`Transmissive3d`]
+    /// phase after the rest of the opaque scene, and are left out of the prepass.
+    #[inline]
+    fn reads_view_transmission_texture(&self) -> bool {
+        false
+    }
+
+    /// Returns the custom [`MeshVertexAttribute`]s this material's vertex shader reads, which are
+    /// added to the vertex buffer layout of the default attributes at the shader locations
+    /// following [`MATERIAL_VERTEX_ATTRIBUTE_LOCATION`], in order.
+    ///
+    /// The meshes without any of these attributes can't be drawn with the material, and the
+    /// specialization of their pipeline fails with a
+    /// [`MissingVertexAttributeError`](bevy_render::mesh::MissingVertexAttributeError).
+    fn vertex_attributes() -> Vec<MeshVertexAttribute> {
+        Vec::new()
+    }
+
     /// Customizes the default [`RenderPipelineDescriptor`] for a specific entity using the entity's
     /// [`MaterialPipelineKey`] and [`MeshVertexBufferLayout`] as input.
     #[allow(unused_variables)]
@@ -149,6 +186,11 @@ pub trait Material: AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'stat
     }
 }
 
+/// The shader location of the first of the [`Material::vertex_attributes`], after the ones of the
+/// default attributes and of the model of the instances of an
+/// [`InstancedMaterialPlugin`](crate::InstancedMaterialPlugin).
+pub const MATERIAL_VERTEX_ATTRIBUTE_LOCATION: u32 = 12;
+
 /// Adds the necessary ECS resources and render logic to enable rendering entities using the given [`Material`]
 /// asset type.
 pub struct MaterialPlugin<M: Material>(PhantomData<M>);
@@ -170,17 +212,26 @@ where
             render_app
                 .add_render_command::<Transparent3d, DrawMaterial<M>>()
                 .add_render_command::<Opaque3d, DrawMaterial<M>>()
+                .add_render_command::<Opaque3dPrepass, DrawMaterial<M>>()
                 .add_render_command::<AlphaMask3d, DrawMaterial<M>>()
+                .add_render_command::<Transmissive3d, DrawTransmissiveMaterial<M>>()
                 .init_resource::<MaterialPipeline<M>>()
                 .init_resource::<ExtractedMaterials<M>>()
                 .init_resource::<RenderMaterials<M>>()
                 .init_resource::<SpecializedMeshPipelines<MaterialPipeline<M>>>()
                 .add_system_to_stage(RenderStage::Extract, extract_materials::<M>)
+                .add_system_to_stage(
+                    RenderStage::Extract,
+                    extract_mesh_lod_fade_out_materials::<M>.after(extract_meshes),
+                )
                 .add_system_to_stage(
                     RenderStage::Prepare,
                     prepare_materials::<M>.after(PrepareAssetLabel::PreAssetPrepare),
                 )
-                .add_system_to_stage(RenderStage::Queue, queue_material_meshes::<M>);
+                .add_system_to_stage(
+                    RenderStage::Queue,
+                    queue_material_meshes::<M>.before(queue_transmission_textures),
+                );
         }
     }
 }
@@ -225,13 +276,13 @@ where
 }
 
 /// Render pipeline data for a given [`Material`].
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct MaterialPipeline<M: Material> {
     pub mesh_pipeline: MeshPipeline,
     pub material_layout: BindGroupLayout,
     pub vertex_shader: Option<Handle<Shader>>,
     pub fragment_shader: Option<Handle<Shader>>,
-    marker: PhantomData<M>,
+    pub(crate) marker: PhantomData<M>,
 }
 
 impl<M: Material> SpecializedMeshPipeline for MaterialPipeline<M>
@@ -246,12 +297,29 @@ where
         layout: &MeshVertexBufferLayout,
     ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
         let mut descriptor = self.mesh_pipeline.specialize(key.mesh_key, layout)?;
+        let vertex_attributes = M::vertex_attributes()
+            .into_iter()
+            .zip(MATERIAL_VERTEX_ATTRIBUTE_LOCATION..)
+            .map(|(attribute, location)| attribute.at_shader_location(location))
+            .collect::<Vec<_>>();
+        if !vertex_attributes.is_empty() {
+            let material_layout = layout.get_layout(&vertex_attributes)?;
+            descriptor.vertex.buffers[0]
+                .attributes
+                .extend(material_layout.attributes);
+        }
         if let Some(vertex_shader) = &self.vertex_shader {
             descriptor.vertex.shader = vertex_shader.clone();
         }
 
-        if let Some(fragment_shader) = &self.fragment_shader {
-            descriptor.fragment.as_mut().unwrap().shader = fragment_shader.clone();
+        // NOTE: The fragment shader of the prepass only writes motion vectors, it isn't the
+        // material's
+        if let (Some(fragment_shader), Some(fragment)) =
+            (&self.fragment_shader, descriptor.fragment.as_mut())
+        {
+            if !key.mesh_key.contains(MeshPipelineKey::DEPTH_PREPASS) {
+                fragment.shader = fragment_shader.clone();
+            }
         }
 
         // MeshPipeline::specialize's current implementation guarantees that the returned
@@ -292,6 +360,16 @@ type DrawMaterial<M> = (
     SetMeshViewBindGroup<0>,
     SetMaterialBindGroup<M, 1>,
     SetMeshBindGroup<2>,
+    SetLightmapBindGroup<3>,
+    DrawMesh,
+);
+
+type DrawTransmissiveMaterial<M> = (
+    SetItemPipeline,
+    SetTransmissiveMeshViewBindGroup<0>,
+    SetMaterialBindGroup<M, 1>,
+    SetMeshBindGroup<2>,
+    SetLightmapBindGroup<3>,
     DrawMesh,
 );
 
@@ -312,31 +390,82 @@ impl<M: Material, const I: usize> EntityRenderCommand for SetMaterialBindGroup<M
     }
 }
 
+/// Adds the material of each cross-fading [`MeshLod`](crate::MeshLod) to the entity drawing its
+/// previous level.
+fn extract_mesh_lod_fade_out_materials<M: Material>(
+    mut commands: Commands,
+    lod_fade_outs: Res<MeshLodFadeOuts>,
+    materials: Extract<Query<&Handle<M>>>,
+) {
+    let fade_out_materials = lod_fade_outs
+        .entities
+        .iter()
+        .filter_map(|(entity, fade_out)| {
+            let material = materials.get(*entity).ok()?;
+            Some((*fade_out, (material.clone_weak(),)))
+        })
+        .collect::<Vec<_>>();
+    commands.insert_or_spawn_batch(fade_out_materials);
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn queue_material_meshes<M: Material>(
+    opaque_prepass_draw_functions: Res<DrawFunctions<Opaque3dPrepass>>,
     opaque_draw_functions: Res<DrawFunctions<Opaque3d>>,
     alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3d>>,
+    transmissive_draw_functions: Res<DrawFunctions<Transmissive3d>>,
     transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
     material_pipeline: Res<MaterialPipeline<M>>,
     mut pipelines: ResMut<SpecializedMeshPipelines<MaterialPipeline<M>>>,
     mut pipeline_cache: ResMut<PipelineCache>,
-    msaa: Res<Msaa>,
     render_meshes: Res<RenderAssets<Mesh>>,
     render_materials: Res<RenderMaterials<M>>,
-    material_meshes: Query<(&Handle<M>, &Handle<Mesh>, &MeshUniform)>,
-    mut views: Query<(
-        &ExtractedView,
-        &VisibleEntities,
-        &mut RenderPhase<Opaque3d>,
-        &mut RenderPhase<AlphaMask3d>,
-        &mut RenderPhase<Transparent3d>,
-    )>,
+    images: Res<RenderAssets<Image>>,
+    // Instanced meshes are drawn in batches by the `InstancedMaterialPlugin`
+    material_meshes: Query<
+        (&Handle<M>, &Handle<Mesh>, &MeshUniform, Option<&Lightmap>),
+        Without<InstancedMesh>,
+    >,
+    mut views: Query<
+        (
+            &ExtractedView,
+            &Msaa,
+            &VisibleEntities,
+            &mut RenderPhase<Opaque3d>,
+            &mut RenderPhase<AlphaMask3d>,
+            &mut RenderPhase<Transmissive3d>,
+            &mut RenderPhase<Transparent3d>,
+            Option<&mut RenderPhase<Opaque3dPrepass>>,
+            Option<&MotionVectorPrepass>,
+            Option<&OrderIndependentTransparency>,
+            Option<&EnvironmentMapLight>,
+            Option<&ShadowFilteringMethod>,
+        ),
+        // Views rendering wireframes only don't draw materials
+        Without<WireframeOnly>,
+    >,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
-    for (view, visible_entities, mut opaque_phase, mut alpha_mask_phase, mut transparent_phase) in
-        &mut views
+    for (
+        view,
+        msaa,
+        visible_entities,
+        mut opaque_phase,
+        mut alpha_mask_phase,
+        mut transmissive_phase,
+        mut transparent_phase,
+        mut opaque_prepass_phase,
+        motion_vector_prepass,
+        order_independent_transparency,
+        environment_map,
+        shadow_filtering_method,
+    ) in &mut views
     {
+        let draw_opaque_prepass_pbr = opaque_prepass_draw_functions
+            .read()
+            .get_id::<DrawMaterial<M>>()
+            .unwrap();
         let draw_opaque_pbr = opaque_draw_functions
             .read()
             .get_id::<DrawMaterial<M>>()
@@ -345,26 +474,55 @@ pub fn queue_material_meshes<M: Material>(
             .read()
             .get_id::<DrawMaterial<M>>()
             .unwrap();
+        let draw_transmissive_pbr = transmissive_draw_functions
+            .read()
+            .get_id::<DrawTransmissiveMaterial<M>>()
+            .unwrap();
         let draw_transparent_pbr = transparent_draw_functions
             .read()
             .get_id::<DrawMaterial<M>>()
             .unwrap();
 
         let rangefinder = view.rangefinder3d();
-        let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
+        if let Some(environment_map) = environment_map {
+            if environment_map.is_loaded(&images) {
+                view_key |= MeshPipelineKey::ENVIRONMENT_MAP;
+            }
+        }
+        if let Some(shadow_filtering_method) = shadow_filtering_method {
+            view_key |= MeshPipelineKey::from_shadow_filtering_method(*shadow_filtering_method);
+        }
+        if opaque_prepass_phase.is_some() {
+            view_key |= MeshPipelineKey::LOADS_PREPASS_DEPTH;
+        }
+        let mut prepass_key = MeshPipelineKey::DEPTH_PREPASS;
+        if motion_vector_prepass.is_some() {
+            prepass_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+        }
 
         for visible_entity in &visible_entities.entities {
-            if let Ok((material_handle, mesh_handle, mesh_uniform)) =
+            if let Ok((material_handle, mesh_handle, mesh_uniform, lightmap)) =
                 material_meshes.get(*visible_entity)
             {
                 if let Some(material) = render_materials.get(material_handle) {
                     if let Some(mesh) = render_meshes.get(mesh_handle) {
                         let mut mesh_key =
                             MeshPipelineKey::from_primitive_topology(mesh.primitive_topology)
-                                | msaa_key;
+                                | view_key;
+                        if let Some(lightmap) = lightmap {
+                            if images.get(&lightmap.image).is_some()
+                                && mesh.layout.contains(Mesh::ATTRIBUTE_UV_1)
+                            {
+                                mesh_key |= MeshPipelineKey::LIGHTMAPPED;
+                            }
+                        }
                         let alpha_mode = material.properties.alpha_mode;
                         if let AlphaMode::Blend = alpha_mode {
                             mesh_key |= MeshPipelineKey::TRANSPARENT_MAIN_PASS;
+                            if order_independent_transparency.is_some() {
+                                mesh_key |= MeshPipelineKey::ORDER_INDEPENDENT_TRANSPARENCY;
+                            }
                         }
 
                         let pipeline_id = pipelines.specialize(
@@ -387,7 +545,44 @@ pub fn queue_material_meshes<M: Material>(
                         let distance = rangefinder.distance(&mesh_uniform.transform)
                             + material.properties.depth_bias;
                         match alpha_mode {
+                            // The transmissive meshes are left out of the prepass, so that what
+                            // is behind them is in the opaque scene they read
+                            AlphaMode::Opaque | AlphaMode::Mask(_)
+                                if material.properties.reads_view_transmission_texture =>
+                            {
+                                transmissive_phase.add(Transmissive3d {
+                                    entity: *visible_entity,
+                                    draw_function: draw_transmissive_pbr,
+                                    pipeline: pipeline_id,
+                                    distance,
+                                });
+                            }
                             AlphaMode::Opaque => {
+                                // The MeshLod levels are only dithered while they cross-fade in
+                                // the main pass, so they are left out of the prepass
+                                let opaque_prepass_phase = opaque_prepass_phase
+                                    .as_mut()
+                                    .filter(|_| mesh_uniform.lod_fade >= 1.0);
+                                if let Some(opaque_prepass_phase) = opaque_prepass_phase {
+                                    let prepass_pipeline_id = pipelines.specialize(
+                                        &mut pipeline_cache,
+                                        &material_pipeline,
+                                        MaterialPipelineKey {
+                                            mesh_key: mesh_key | prepass_key,
+                                            bind_group_data: material.key.clone(),
+                                        },
+                                        &mesh.layout,
+                                    );
+                                    match prepass_pipeline_id {
+                                        Ok(id) => opaque_prepass_phase.add(Opaque3dPrepass {
+                                            entity: *visible_entity,
+                                            draw_function: draw_opaque_prepass_pbr,
+                                            pipeline: id,
+                                            distance,
+                                        }),
+                                        Err(err) => error!("{}", err),
+                                    }
+                                }
                                 opaque_phase.add(Opaque3d {
                                     entity: *visible_entity,
                                     draw_function: draw_opaque_pbr,
@@ -426,6 +621,9 @@ pub struct MaterialProperties {
     /// Add a bias to the view depth of the mesh which can be used to force a specific render order
     /// for meshes with equal depth, to avoid z-fighting.
     pub depth_bias: f32,
+    /// Whether the material reads the
+    /// [`ViewTransmissionTexture`](bevy_core_pipeline::transmission::ViewTransmissionTexture).
+    pub reads_view_transmission_texture: bool,
 }
 
 /// Data prepared for a [`Material`] instance.
@@ -579,6 +777,7 @@ fn prepare_material<M: Material>(
         properties: MaterialProperties {
             alpha_mode: material.alpha_mode(),
             depth_bias: material.depth_bias(),
+            reads_view_transmission_texture: material.reads_view_transmission_texture(),
         },
     })
 }
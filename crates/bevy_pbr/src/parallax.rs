@@ -0,0 +1,34 @@
+use bevy_reflect::Reflect;
+
+/// How a [`StandardMaterial`] with a [`depth_map`] finds where the view ray hits its relief.
+///
+/// Both methods first step along the ray through [`max_parallax_layer_count`] layers of the
+/// depth map, then refine the hit between the last two layers.
+///
+/// [`StandardMaterial`]: crate::StandardMaterial
+/// [`depth_map`]: crate::StandardMaterial::depth_map
+/// [`max_parallax_layer_count`]: crate::StandardMaterial::max_parallax_layer_count
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum ParallaxMappingMethod {
+    /// Interpolates between the depths of the last two layers, with a single extra sample.
+    ///
+    /// This is cheap, but the steps between layers may show on very steep relief.
+    #[default]
+    Occlusion,
+    /// Binary searches the surface between the last two layers, with `max_steps` extra samples.
+    ///
+    /// This is more accurate than [`Occlusion`](ParallaxMappingMethod::Occlusion), for a higher
+    /// cost per step.
+    Relief { max_steps: u32 },
+}
+
+impl ParallaxMappingMethod {
+    /// The number of binary search steps of [`Relief`](ParallaxMappingMethod::Relief) mapping,
+    /// `0` for [`Occlusion`](ParallaxMappingMethod::Occlusion).
+    pub fn max_steps(&self) -> u32 {
+        match self {
+            ParallaxMappingMethod::Occlusion => 0,
+            ParallaxMappingMethod::Relief { max_steps } => *max_steps,
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{AlphaMode, Material, MaterialPipeline, MaterialPipelineKey, PBR_SHADER_HANDLE};
+use crate::{
+    AlphaMode, Material, MaterialPipeline, MaterialPipelineKey, ParallaxMappingMethod,
+    PBR_SHADER_HANDLE,
+};
 use bevy_asset::Handle;
 use bevy_math::Vec4;
 use bevy_reflect::TypeUuid;
@@ -128,6 +131,39 @@ pub struct StandardMaterial {
     #[doc(alias = "specular_intensity")]
     pub reflectance: f32,
 
+    /// The color of the specular highlight of non-metals, multiplying the intensity set by
+    /// [`reflectance`](StandardMaterial::reflectance).
+    ///
+    /// Defaults to [`Color::WHITE`], which doesn't tint the highlight.
+    pub specular_tint: Color,
+
+    /// The amount of light transmitted through the material, rather than diffused by it, within
+    /// `[0.0, 1.0]`.
+    ///
+    /// Defaults to `0.0`. Glass and liquids are close to `1.0`.
+    ///
+    /// Opaque and masked materials with transmission are drawn after the rest of the opaque
+    /// scene, which is seen through them, refracted according to [`ior`] and [`thickness`].
+    /// Neither the other transmissive materials nor the transparent ones are seen through them.
+    /// The transmitted light is tinted by the [`base_color`].
+    ///
+    /// [`ior`]: StandardMaterial::ior
+    /// [`thickness`]: StandardMaterial::thickness
+    /// [`base_color`]: StandardMaterial::base_color
+    #[doc(alias = "refraction")]
+    pub specular_transmission: f32,
+
+    /// The distance in world units the transmitted light travels through the material, used to
+    /// offset what is seen through it by its refraction.
+    ///
+    /// Defaults to `0.0`, for thin surfaces such as window panes, which don't offset it.
+    pub thickness: f32,
+
+    /// The index of refraction of the material, bending the transmitted light.
+    ///
+    /// Defaults to `1.5`, the index of refraction of glass. Water is `1.33`.
+    pub ior: f32,
+
     /// Used to fake the lighting of bumps and dents on a material.
     ///
     /// A typical usage would be faking cobblestones on a flat plane mesh in 3D.
@@ -168,6 +204,58 @@ pub struct StandardMaterial {
     #[sampler(8)]
     pub occlusion_texture: Option<Handle<Image>>,
 
+    /// The depth of the relief of the material, faked by offsetting its textures along the view
+    /// with parallax mapping.
+    ///
+    /// The red channel holds how far below the surface of the mesh each point is, from `0.0` on
+    /// the surface to `1.0` at [`parallax_depth_scale`] below it. Other channels are unused.
+    ///
+    /// Unlike a [`normal_map_texture`], which only changes the lighting, this shifts the other
+    /// textures so bumps hide what is behind them. It is well suited to bricks, tiles and
+    /// terrain seen up close, though the silhouette of the mesh is left unchanged.
+    ///
+    /// # Notes
+    ///
+    /// Parallax mapping requires vertex UVs and vertex tangents, see [`normal_map_texture`].
+    ///
+    /// [`parallax_depth_scale`]: StandardMaterial::parallax_depth_scale
+    /// [`normal_map_texture`]: StandardMaterial::normal_map_texture
+    #[texture(11)]
+    #[sampler(12)]
+    #[doc(alias = "displacement_map", alias = "height_map")]
+    pub depth_map: Option<Handle<Image>>,
+
+    /// The depth in UV space of the deepest point of the [`depth_map`], scaling its relief.
+    ///
+    /// Defaults to `0.1`. Large values flatten out at grazing angles, where fewer layers are
+    /// sampled than the relief needs.
+    ///
+    /// [`depth_map`]: StandardMaterial::depth_map
+    pub parallax_depth_scale: f32,
+
+    /// How the view ray is intersected with the relief of the [`depth_map`].
+    ///
+    /// Defaults to [`ParallaxMappingMethod::Occlusion`].
+    ///
+    /// [`depth_map`]: StandardMaterial::depth_map
+    pub parallax_mapping_method: ParallaxMappingMethod,
+
+    /// The number of layers the [`depth_map`] is stepped through when the mesh is seen at a
+    /// grazing angle, down to `1.0` when it is seen head on.
+    ///
+    /// Defaults to `16.0`. Each layer is a texture sample, so this trades the accuracy of the
+    /// relief for performance. Below `1.0`, parallax mapping is disabled.
+    ///
+    /// [`depth_map`]: StandardMaterial::depth_map
+    pub max_parallax_layer_count: f32,
+
+    /// Scales the light baked in the [`Lightmap`](crate::Lightmap) of the meshes drawn with
+    /// this material.
+    ///
+    /// Defaults to `1.0`. This is useful when the lightmaps were baked with a different exposure
+    /// than the scene is rendered with.
+    pub lightmap_exposure: f32,
+
     /// Support two-sided lighting by automatically flipping the normals for "back" faces
     /// within the PBR lighting shader.
     ///
@@ -222,6 +310,14 @@ pub struct StandardMaterial {
     ///
     /// [z-fighting]: https://en.wikipedia.org/wiki/Z-fighting
     pub depth_bias: f32,
+
+    /// Whether to reflect what is on screen in this material, with the
+    /// `ScreenSpaceReflections` of the camera.
+    ///
+    /// Only the smooth parts of opaque materials have screen space reflections.
+    /// Set this to `false` to opt out of them, for example on surfaces whose
+    /// reflections would be mostly off screen. Defaults to `true`.
+    pub screen_space_reflections: bool,
 }
 
 impl Default for StandardMaterial {
@@ -245,18 +341,170 @@ impl Default for StandardMaterial {
             // Expressed in a linear scale and equivalent to 4% reflectance see
             // <https://google.github.io/filament/Material%20Properties.pdf>
             reflectance: 0.5,
+            specular_tint: Color::WHITE,
+            specular_transmission: 0.0,
+            thickness: 0.0,
+            ior: 1.5,
             occlusion_texture: None,
             normal_map_texture: None,
+            depth_map: None,
+            parallax_depth_scale: 0.1,
+            parallax_mapping_method: ParallaxMappingMethod::Occlusion,
+            max_parallax_layer_count: 16.0,
+            lightmap_exposure: 1.0,
             flip_normal_map_y: false,
             double_sided: false,
             cull_mode: Some(Face::Back),
             unlit: false,
             alpha_mode: AlphaMode::Opaque,
             depth_bias: 0.0,
+            screen_space_reflections: true,
         }
     }
 }
 
+impl StandardMaterial {
+    /// Returns this material with a new [base color](StandardMaterial::base_color).
+    #[inline]
+    #[must_use]
+    pub fn with_base_color(mut self, base_color: Color) -> Self {
+        self.base_color = base_color;
+        self
+    }
+
+    /// Returns this material with a new [base color texture](StandardMaterial::base_color_texture).
+    #[inline]
+    #[must_use]
+    pub fn with_base_color_texture(mut self, base_color_texture: Option<Handle<Image>>) -> Self {
+        self.base_color_texture = base_color_texture;
+        self
+    }
+
+    /// Returns this material with a new [emissive color](StandardMaterial::emissive).
+    #[inline]
+    #[must_use]
+    pub fn with_emissive(mut self, emissive: Color) -> Self {
+        self.emissive = emissive;
+        self
+    }
+
+    /// Returns this material with a new [emissive texture](StandardMaterial::emissive_texture).
+    #[inline]
+    #[must_use]
+    pub fn with_emissive_texture(mut self, emissive_texture: Option<Handle<Image>>) -> Self {
+        self.emissive_texture = emissive_texture;
+        self
+    }
+
+    /// Returns this material with a new [perceptual roughness](StandardMaterial::perceptual_roughness).
+    #[inline]
+    #[must_use]
+    pub fn with_perceptual_roughness(mut self, perceptual_roughness: f32) -> Self {
+        self.perceptual_roughness = perceptual_roughness;
+        self
+    }
+
+    /// Returns this material with a new [metallic value](StandardMaterial::metallic).
+    #[inline]
+    #[must_use]
+    pub fn with_metallic(mut self, metallic: f32) -> Self {
+        self.metallic = metallic;
+        self
+    }
+
+    /// Returns this material with a new
+    /// [metallic roughness texture](StandardMaterial::metallic_roughness_texture).
+    #[inline]
+    #[must_use]
+    pub fn with_metallic_roughness_texture(
+        mut self,
+        metallic_roughness_texture: Option<Handle<Image>>,
+    ) -> Self {
+        self.metallic_roughness_texture = metallic_roughness_texture;
+        self
+    }
+
+    /// Returns this material with a new [reflectance](StandardMaterial::reflectance).
+    #[inline]
+    #[must_use]
+    pub fn with_reflectance(mut self, reflectance: f32) -> Self {
+        self.reflectance = reflectance;
+        self
+    }
+
+    /// Returns this material with a new [specular tint](StandardMaterial::specular_tint).
+    #[inline]
+    #[must_use]
+    pub fn with_specular_tint(mut self, specular_tint: Color) -> Self {
+        self.specular_tint = specular_tint;
+        self
+    }
+
+    /// Returns this material with a new [normal map texture](StandardMaterial::normal_map_texture).
+    #[inline]
+    #[must_use]
+    pub fn with_normal_map_texture(mut self, normal_map_texture: Option<Handle<Image>>) -> Self {
+        self.normal_map_texture = normal_map_texture;
+        self
+    }
+
+    /// Returns this material with a new [normal map y flip](StandardMaterial::flip_normal_map_y).
+    #[inline]
+    #[must_use]
+    pub fn with_flip_normal_map_y(mut self, flip_normal_map_y: bool) -> Self {
+        self.flip_normal_map_y = flip_normal_map_y;
+        self
+    }
+
+    /// Returns this material with a new [occlusion texture](StandardMaterial::occlusion_texture).
+    #[inline]
+    #[must_use]
+    pub fn with_occlusion_texture(mut self, occlusion_texture: Option<Handle<Image>>) -> Self {
+        self.occlusion_texture = occlusion_texture;
+        self
+    }
+
+    /// Returns this material with a new [double sided lighting](StandardMaterial::double_sided).
+    #[inline]
+    #[must_use]
+    pub fn with_double_sided(mut self, double_sided: bool) -> Self {
+        self.double_sided = double_sided;
+        self
+    }
+
+    /// Returns this material with a new [cull mode](StandardMaterial::cull_mode).
+    #[inline]
+    #[must_use]
+    pub fn with_cull_mode(mut self, cull_mode: Option<Face>) -> Self {
+        self.cull_mode = cull_mode;
+        self
+    }
+
+    /// Returns this material with a new [unlit flag](StandardMaterial::unlit).
+    #[inline]
+    #[must_use]
+    pub fn with_unlit(mut self, unlit: bool) -> Self {
+        self.unlit = unlit;
+        self
+    }
+
+    /// Returns this material with a new [alpha mode](StandardMaterial::alpha_mode).
+    #[inline]
+    #[must_use]
+    pub fn with_alpha_mode(mut self, alpha_mode: AlphaMode) -> Self {
+        self.alpha_mode = alpha_mode;
+        self
+    }
+
+    /// Returns this material with a new [depth bias](StandardMaterial::depth_bias).
+    #[inline]
+    #[must_use]
+    pub fn with_depth_bias(mut self, depth_bias: f32) -> Self {
+        self.depth_bias = depth_bias;
+        self
+    }
+}
+
 impl From<Color> for StandardMaterial {
     fn from(color: Color) -> Self {
         StandardMaterial {
@@ -297,6 +545,7 @@ bitflags::bitflags! {
         const ALPHA_MODE_BLEND           = (1 << 8);
         const TWO_COMPONENT_NORMAL_MAP   = (1 << 9);
         const FLIP_NORMAL_MAP_Y          = (1 << 10);
+        const NO_SCREEN_SPACE_REFLECTIONS = (1 << 11);
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -311,6 +560,8 @@ pub struct StandardMaterialUniform {
     // Use a color for user friendliness even though we technically don't use the alpha channel
     // Might be used in the future for exposure correction in HDR
     pub emissive: Vec4,
+    /// The linear color of the specular highlight of non-metals
+    pub specular_tint: Vec4,
     /// Linear perceptual roughness, clamped to [0.089, 1.0] in the shader
     /// Defaults to minimum of 0.089
     pub roughness: f32,
@@ -324,6 +575,20 @@ pub struct StandardMaterialUniform {
     /// When the alpha mode mask flag is set, any base color alpha above this cutoff means fully opaque,
     /// and any below means fully transparent.
     pub alpha_cutoff: f32,
+    /// From [0.0, 1.0], the amount of light transmitted through the material
+    pub specular_transmission: f32,
+    /// The distance in world units the transmitted light travels through the material
+    pub thickness: f32,
+    /// The index of refraction of the material
+    pub ior: f32,
+    /// The depth in UV space of the deepest point of the depth map
+    pub parallax_depth_scale: f32,
+    /// The number of layers the depth map is stepped through at grazing angles
+    pub max_parallax_layer_count: f32,
+    /// The number of binary search steps of relief mapping
+    pub max_relief_mapping_search_steps: u32,
+    /// The scale of the light baked in lightmaps
+    pub lightmap_exposure: f32,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -365,6 +630,9 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
                 flags |= StandardMaterialFlags::FLIP_NORMAL_MAP_Y;
             }
         }
+        if !self.screen_space_reflections {
+            flags |= StandardMaterialFlags::NO_SCREEN_SPACE_REFLECTIONS;
+        }
         // NOTE: 0.5 is from the glTF default - do we want this?
         let mut alpha_cutoff = 0.5;
         match self.alpha_mode {
@@ -379,11 +647,19 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         StandardMaterialUniform {
             base_color: self.base_color.as_linear_rgba_f32().into(),
             emissive: self.emissive.into(),
+            specular_tint: self.specular_tint.as_linear_rgba_f32().into(),
             roughness: self.perceptual_roughness,
             metallic: self.metallic,
             reflectance: self.reflectance,
             flags: flags.bits(),
             alpha_cutoff,
+            specular_transmission: self.specular_transmission,
+            thickness: self.thickness,
+            ior: self.ior,
+            parallax_depth_scale: self.parallax_depth_scale,
+            max_parallax_layer_count: self.max_parallax_layer_count,
+            max_relief_mapping_search_steps: self.parallax_mapping_method.max_steps(),
+            lightmap_exposure: self.lightmap_exposure,
         }
     }
 }
@@ -391,6 +667,8 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct StandardMaterialKey {
     normal_map: bool,
+    depth_map: bool,
+    relief_mapping: bool,
     cull_mode: Option<Face>,
 }
 
@@ -398,6 +676,11 @@ impl From<&StandardMaterial> for StandardMaterialKey {
     fn from(material: &StandardMaterial) -> Self {
         StandardMaterialKey {
             normal_map: material.normal_map_texture.is_some(),
+            depth_map: material.depth_map.is_some(),
+            relief_mapping: matches!(
+                material.parallax_mapping_method,
+                ParallaxMappingMethod::Relief { .. }
+            ),
             cull_mode: material.cull_mode,
         }
     }
@@ -411,12 +694,21 @@ impl Material for StandardMaterial {
         key: MaterialPipelineKey<Self>,
     ) -> Result<(), SpecializedMeshPipelineError> {
         if key.bind_group_data.normal_map {
-            descriptor
-                .fragment
-                .as_mut()
-                .unwrap()
-                .shader_defs
-                .push(String::from("STANDARDMATERIAL_NORMAL_MAP"));
+            if let Some(fragment) = descriptor.fragment.as_mut() {
+                fragment
+                    .shader_defs
+                    .push(String::from("STANDARDMATERIAL_NORMAL_MAP"));
+            }
+        }
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            if key.bind_group_data.depth_map {
+                fragment
+                    .shader_defs
+                    .push(String::from("STANDARDMATERIAL_DEPTH_MAP"));
+            }
+            if key.bind_group_data.relief_mapping {
+                fragment.shader_defs.push(String::from("RELIEF_MAPPING"));
+            }
         }
         descriptor.primitive.cull_mode = key.bind_group_data.cull_mode;
         if let Some(label) = &mut descriptor.label {
@@ -438,4 +730,79 @@ impl Material for StandardMaterial {
     fn depth_bias(&self) -> f32 {
         self.depth_bias
     }
+
+    #[inline]
+    fn reads_view_transmission_texture(&self) -> bool {
+        self.specular_transmission > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chained_setters_set_fields() {
+        let base = StandardMaterial {
+            perceptual_roughness: 0.7,
+            ..Default::default()
+        };
+        let material = base
+            .clone()
+            .with_base_color(Color::RED)
+            .with_emissive(Color::BLUE)
+            .with_alpha_mode(AlphaMode::Blend)
+            .with_cull_mode(None)
+            .with_double_sided(true)
+            .with_unlit(true)
+            .with_depth_bias(1.5)
+            .with_metallic_roughness_texture(Some(Handle::default()))
+            .with_occlusion_texture(Some(Handle::default()))
+            .with_flip_normal_map_y(true);
+
+        assert_eq!(material.base_color, Color::RED);
+        assert_eq!(material.emissive, Color::BLUE);
+        assert_eq!(material.alpha_mode, AlphaMode::Blend);
+        assert_eq!(material.cull_mode, None);
+        assert!(material.double_sided);
+        assert!(material.unlit);
+        assert_eq!(material.depth_bias, 1.5);
+        assert!(material.metallic_roughness_texture.is_some());
+        assert!(material.occlusion_texture.is_some());
+        assert!(material.flip_normal_map_y);
+        // Other fields are kept from the base material
+        assert_eq!(material.perceptual_roughness, 0.7);
+        assert_eq!(material.metallic, base.metallic);
+        assert_eq!(base.base_color, Color::WHITE);
+    }
+
+    #[test]
+    fn transmissive_materials_read_view_transmission_texture() {
+        let material = StandardMaterial::default();
+        assert!(!material.reads_view_transmission_texture());
+
+        let material = StandardMaterial {
+            specular_transmission: 0.9,
+            ..Default::default()
+        };
+        assert!(material.reads_view_transmission_texture());
+    }
+
+    #[test]
+    fn depth_map_key_follows_parallax_mapping_method() {
+        let key = StandardMaterialKey::from(&StandardMaterial::default());
+        assert!(!key.depth_map);
+        assert!(!key.relief_mapping);
+
+        let material = StandardMaterial {
+            depth_map: Some(Handle::default()),
+            parallax_mapping_method: ParallaxMappingMethod::Relief { max_steps: 5 },
+            ..Default::default()
+        };
+        let key = StandardMaterialKey::from(&material);
+        assert!(key.depth_map);
+        assert!(key.relief_mapping);
+        assert_eq!(material.parallax_mapping_method.max_steps(), 5);
+        assert_eq!(ParallaxMappingMethod::Occlusion.max_steps(), 0);
+    }
 }
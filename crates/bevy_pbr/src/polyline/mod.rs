@@ -0,0 +1,259 @@
+//! Lines with a width, drawn as a [`Mesh`] made from a [`Polyline`] with a [`PolylineMaterial`].
+//!
+//! The meshes with a [`PrimitiveTopology::LineList`] or a [`PrimitiveTopology::LineStrip`] are
+//! always drawn one pixel wide. Each segment of a [`Polyline`] is instead a quad, whose sides are
+//! pushed apart in the vertex shader of the [`PolylineMaterial`] to cover its width on screen.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_math::{Vec3, Vec4};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    color::Color,
+    mesh::{Indices, Mesh, MeshVertexAttribute, MeshVertexBufferLayout, PrimitiveTopology},
+    render_asset::RenderAssets,
+    render_resource::{
+        AsBindGroup, AsBindGroupShaderType, RenderPipelineDescriptor, Shader, ShaderRef,
+        ShaderType, SpecializedMeshPipelineError, VertexFormat,
+    },
+    texture::Image,
+};
+
+use crate::{
+    AlphaMode, Material, MaterialMeshBundle, MaterialPipeline, MaterialPipelineKey, MaterialPlugin,
+};
+
+pub const POLYLINE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2953128746102385761);
+
+/// A component bundle for entities with a [`Mesh`] made from a [`Polyline`] and a
+/// [`PolylineMaterial`].
+pub type PolylineBundle = MaterialMeshBundle<PolylineMaterial>;
+
+/// How the vertices of a [`Polyline`] are joined into segments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PolylineTopology {
+    /// Each vertex is joined to the next one, like a [`PrimitiveTopology::LineStrip`].
+    #[default]
+    Strip,
+    /// The vertices are joined in pairs, like a [`PrimitiveTopology::LineList`].
+    List,
+}
+
+/// The points of lines to draw with a [`PolylineMaterial`], such as a trajectory, converted to a
+/// [`Mesh`] with [`Mesh::from`].
+///
+/// Each segment is drawn as its own quad, so the corners of wide strips are left open.
+#[derive(Clone, Debug, Default)]
+pub struct Polyline {
+    pub vertices: Vec<Vec3>,
+    /// The color of each vertex, multiplied by the color of the material and interpolated along
+    /// the segments. Leave it empty to only use the color of the material.
+    pub colors: Vec<Color>,
+    pub topology: PolylineTopology,
+}
+
+impl Polyline {
+    /// The position of the other end of the segment of each vertex of the [`Mesh`] of a
+    /// [`Polyline`], and which side of the segment the vertex is pushed to, `1.0` or `-1.0`.
+    pub const ATTRIBUTE_SEGMENT: MeshVertexAttribute = MeshVertexAttribute::new(
+        "Vertex_PolylineSegment",
+        2953128746,
+        VertexFormat::Float32x4,
+    );
+
+    /// The indices of the vertices of each segment.
+    fn segments(&self) -> Vec<(usize, usize)> {
+        match self.topology {
+            PolylineTopology::Strip => (1..self.vertices.len()).map(|i| (i - 1, i)).collect(),
+            PolylineTopology::List => (0..self.vertices.len() / 2)
+                .map(|i| (2 * i, 2 * i + 1))
+                .collect(),
+        }
+    }
+}
+
+impl From<Polyline> for Mesh {
+    fn from(polyline: Polyline) -> Self {
+        let segments = polyline.segments();
+        let mut positions = Vec::with_capacity(segments.len() * 4);
+        let mut other_ends = Vec::with_capacity(segments.len() * 4);
+        let mut colors = Vec::with_capacity(segments.len() * 4);
+        let mut indices = Vec::with_capacity(segments.len() * 6);
+        for (start, end) in segments {
+            if !polyline.colors.is_empty() {
+                let (start, end) = (polyline.colors[start], polyline.colors[end]);
+                colors.extend([start, start, end, end].map(|color| color.as_linear_rgba_f32()));
+            }
+            let (start, end) = (polyline.vertices[start], polyline.vertices[end]);
+            let index = positions.len() as u32;
+            positions.extend([start, start, end, end].map(|position| position.to_array()));
+            // The sides are seen from each end of the segment, so the ones of the end vertices
+            // are flipped
+            other_ends.extend(
+                [(end, 1.0), (end, -1.0), (start, 1.0), (start, -1.0)]
+                    .map(|(other_end, side)| other_end.extend(side).to_array()),
+            );
+            indices.extend([index, index + 1, index + 2, index, index + 2, index + 3]);
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Polyline::ATTRIBUTE_SEGMENT, other_ends);
+        if !colors.is_empty() {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        }
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh
+    }
+}
+
+/// An unlit material drawing the [`Mesh`] of a [`Polyline`] as lines of a given width.
+///
+/// The lines don't cast shadows, and meshes without [`Polyline::ATTRIBUTE_SEGMENT`] can't be
+/// drawn with it.
+#[derive(AsBindGroup, Debug, Clone, TypeUuid)]
+#[uuid = "ef24f159-2984-4d39-b593-0ba38000259a"]
+#[uniform(0, PolylineMaterialUniform)]
+pub struct PolylineMaterial {
+    pub color: Color,
+    /// The width of the lines in pixels, or in world units with `perspective`.
+    pub width: f32,
+    /// Whether the lines get thinner with their distance to the camera, instead of keeping the
+    /// same width on screen.
+    pub perspective: bool,
+    pub alpha_mode: AlphaMode,
+    pub depth_bias: f32,
+}
+
+impl Default for PolylineMaterial {
+    fn default() -> Self {
+        PolylineMaterial {
+            color: Color::WHITE,
+            width: 2.0,
+            perspective: false,
+            alpha_mode: AlphaMode::Opaque,
+            depth_bias: 0.0,
+        }
+    }
+}
+
+impl From<Color> for PolylineMaterial {
+    fn from(color: Color) -> Self {
+        PolylineMaterial {
+            color,
+            alpha_mode: if color.a() < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// The GPU representation of the uniform data of a [`PolylineMaterial`].
+#[derive(Clone, Default, ShaderType)]
+pub struct PolylineMaterialUniform {
+    pub color: Vec4,
+    pub width: f32,
+    /// `1` if the width is in world units, `0` if it's in pixels.
+    pub perspective: u32,
+    /// Any alpha of the color under this cutoff is discarded.
+    pub alpha_cutoff: f32,
+}
+
+impl AsBindGroupShaderType<PolylineMaterialUniform> for PolylineMaterial {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<Image>) -> PolylineMaterialUniform {
+        PolylineMaterialUniform {
+            color: self.color.as_linear_rgba_f32().into(),
+            width: self.width,
+            perspective: self.perspective as u32,
+            alpha_cutoff: match self.alpha_mode {
+                AlphaMode::Mask(cutoff) => cutoff,
+                _ => 0.0,
+            },
+        }
+    }
+}
+
+impl Material for PolylineMaterial {
+    fn vertex_shader() -> ShaderRef {
+        POLYLINE_SHADER_HANDLE.typed().into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        POLYLINE_SHADER_HANDLE.typed().into()
+    }
+
+    fn vertex_attributes() -> Vec<MeshVertexAttribute> {
+        vec![Polyline::ATTRIBUTE_SEGMENT]
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // The quads can face either way, depending on the side the segments are seen from
+        descriptor.primitive.cull_mode = None;
+        if let Some(label) = &mut descriptor.label {
+            *label = format!("polyline_{}", *label).into();
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
+
+    #[inline]
+    fn depth_bias(&self) -> f32 {
+        self.depth_bias
+    }
+}
+
+/// Draws the meshes with a [`PolylineMaterial`].
+pub struct PolylinePlugin;
+
+impl Plugin for PolylinePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            POLYLINE_SHADER_HANDLE,
+            "polyline.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugin(MaterialPlugin::<PolylineMaterial>::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polyline_mesh() {
+        let vertices = vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z, Vec3::ONE];
+        let strip = Mesh::from(Polyline {
+            vertices: vertices.clone(),
+            colors: vec![Color::RED; 5],
+            topology: PolylineTopology::Strip,
+        });
+        assert_eq!(strip.count_vertices(), 16);
+        assert_eq!(strip.indices().unwrap().len(), 24);
+        assert!(strip.attribute(Mesh::ATTRIBUTE_COLOR).is_some());
+
+        let list = Mesh::from(Polyline {
+            vertices,
+            topology: PolylineTopology::List,
+            ..Default::default()
+        });
+        assert_eq!(list.count_vertices(), 8);
+        assert_eq!(list.indices().unwrap().len(), 12);
+        assert!(list.attribute(Mesh::ATTRIBUTE_COLOR).is_none());
+    }
+}
@@ -0,0 +1,112 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+
+// NOTE: Bindings must come before functions that use them!
+#import bevy_pbr::mesh_functions
+
+#import bevy_core_pipeline::oit
+
+struct PolylineMaterial {
+    color: vec4<f32>,
+    width: f32,
+    perspective: u32,
+    alpha_cutoff: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: PolylineMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+#ifdef VERTEX_COLORS
+    @location(4) color: vec4<f32>,
+#endif
+    // The other end of the segment of the vertex, and the side of the segment it's pushed to
+    @location(12) segment: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+#ifdef VERTEX_COLORS
+    @location(4) color: vec4<f32>,
+#endif
+};
+
+// Moves the end of a segment behind the near plane along the segment to the near plane, where
+// the near plane is at z == w with the reversed depth
+fn clip_to_near_plane(clip: vec4<f32>, other_end: vec4<f32>) -> vec4<f32> {
+    let distance = clip.w - clip.z;
+    if (distance >= 0.0) {
+        return clip;
+    }
+    let other_distance = other_end.w - other_end.z;
+    return mix(clip, other_end, distance / (distance - other_distance));
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    let other_end = mesh_position_local_to_clip(mesh.model, vec4<f32>(vertex.segment.xyz, 1.0));
+    var clip = mesh_position_world_to_clip(world_position);
+    let other_clip = clip_to_near_plane(other_end, clip);
+    clip = clip_to_near_plane(clip, other_end);
+
+    // The direction of the segment on screen, in pixels
+    let half_viewport = view.viewport.zw * 0.5;
+    var direction = other_clip.xy / other_clip.w * half_viewport - clip.xy / clip.w * half_viewport;
+    if (dot(direction, direction) == 0.0) {
+        direction = vec2<f32>(1.0, 0.0);
+    }
+    direction = normalize(direction);
+
+    var width = material.width;
+    if (material.perspective != 0u) {
+        // The size in pixels of a world unit at the depth of the vertex
+        width = width * view.projection[1][1] * half_viewport.y / clip.w;
+    }
+    let offset = vec2<f32>(-direction.y, direction.x) * vertex.segment.w * width * 0.5;
+    out.clip_position = vec4<f32>(clip.xy + offset / half_viewport * clip.w, clip.zw);
+    out.world_position = world_position;
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) world_position: vec4<f32>,
+#ifdef VERTEX_COLORS
+    @location(4) color: vec4<f32>,
+#endif
+};
+
+@fragment
+#ifdef OIT
+fn fragment(in: FragmentInput) -> OitFragmentOutput {
+#else
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+#endif
+    var color = material.color;
+#ifdef VERTEX_COLORS
+    color = color * in.color;
+#endif
+    if (color.a < material.alpha_cutoff) {
+        discard;
+    }
+#ifdef OIT
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), in.world_position);
+    return oit_fragment_output(color, -view_z);
+#else
+    return color;
+#endif
+}
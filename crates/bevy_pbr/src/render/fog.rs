@@ -0,0 +1,130 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec4;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    camera::ExtractedCamera,
+    extract_component::ExtractComponentPlugin,
+    render_resource::{DynamicUniformBuffer, Shader, ShaderType},
+    renderer::{RenderDevice, RenderQueue},
+    RenderApp, RenderStage,
+};
+
+use crate::{FogFalloff, FogSettings, HeightFog};
+
+pub const FOG_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4913569193382610166);
+
+// NOTE: These must match the constants in bevy_pbr/src/render/mesh_view_types.wgsl!
+const GPU_FOG_MODE_OFF: u32 = 0;
+const GPU_FOG_MODE_LINEAR: u32 = 1;
+const GPU_FOG_MODE_EXPONENTIAL: u32 = 2;
+const GPU_FOG_MODE_EXPONENTIAL_SQUARED: u32 = 3;
+
+/// Fogs what the cameras with [`FogSettings`] render.
+pub struct FogPlugin;
+
+impl Plugin for FogPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, FOG_SHADER_HANDLE, "fog.wgsl", Shader::from_wgsl);
+
+        app.register_type::<FogSettings>()
+            .add_plugin(ExtractComponentPlugin::<FogSettings>::default());
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<FogMeta>()
+                .add_system_to_stage(RenderStage::Prepare, prepare_fog);
+        }
+    }
+}
+
+/// The GPU representation of the [`FogSettings`] of a view.
+#[derive(Copy, Clone, Debug, Default, ShaderType)]
+pub struct GpuFog {
+    base_color: Vec4,
+    directional_light_color: Vec4,
+    directional_light_exponent: f32,
+    mode: u32,
+    // The distances of linear fog
+    start: f32,
+    end: f32,
+    // The density of exponential fogs
+    density: f32,
+    base_height: f32,
+    // 0.0 for a fog as thick at every height
+    height_falloff: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct FogMeta {
+    pub gpu_fogs: DynamicUniformBuffer<GpuFog>,
+}
+
+/// The offset of the [`GpuFog`] of a view in the [`FogMeta`] buffer.
+#[derive(Component)]
+pub struct ViewFogUniformOffset {
+    pub offset: u32,
+}
+
+/// Writes the [`GpuFog`] of every view, turned off in the views without [`FogSettings`].
+pub fn prepare_fog(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut fog_meta: ResMut<FogMeta>,
+    views: Query<(Entity, Option<&FogSettings>), With<ExtractedCamera>>,
+) {
+    fog_meta.gpu_fogs.clear();
+    for (entity, fog) in &views {
+        let gpu_fog = match fog {
+            Some(fog) => {
+                let mut gpu_fog = GpuFog {
+                    base_color: fog.color.as_linear_rgba_f32().into(),
+                    directional_light_color: fog
+                        .directional_light_color
+                        .as_linear_rgba_f32()
+                        .into(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    ..Default::default()
+                };
+                match fog.falloff {
+                    FogFalloff::Linear { start, end } => {
+                        gpu_fog.mode = GPU_FOG_MODE_LINEAR;
+                        gpu_fog.start = start;
+                        gpu_fog.end = end;
+                    }
+                    FogFalloff::Exponential { density } => {
+                        gpu_fog.mode = GPU_FOG_MODE_EXPONENTIAL;
+                        gpu_fog.density = density;
+                    }
+                    FogFalloff::ExponentialSquared { density } => {
+                        gpu_fog.mode = GPU_FOG_MODE_EXPONENTIAL_SQUARED;
+                        gpu_fog.density = density;
+                    }
+                }
+                if let Some(HeightFog {
+                    base_height,
+                    falloff,
+                }) = fog.height_fog
+                {
+                    gpu_fog.base_height = base_height;
+                    gpu_fog.height_falloff = falloff.max(0.0);
+                }
+                gpu_fog
+            }
+            None => GpuFog {
+                mode: GPU_FOG_MODE_OFF,
+                ..Default::default()
+            },
+        };
+        let offset = fog_meta.gpu_fogs.push(gpu_fog);
+        commands
+            .entity(entity)
+            .insert(ViewFogUniformOffset { offset });
+    }
+    fog_meta
+        .gpu_fogs
+        .write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,51 @@
+#define_import_path bevy_pbr::fog
+
+// Returns how far the view ray sees through the fog, scaling its length by the average density of
+// the height fog along it
+fn fog_optical_distance(view_to_world: vec3<f32>, view_height: f32) -> f32 {
+    let ray_length = length(view_to_world);
+    if (fog.height_falloff <= 0.0) {
+        return ray_length;
+    }
+    // NOTE: The exponent is clamped so that views far below the base height don't overflow
+    let view_density = exp(min(-fog.height_falloff * (view_height - fog.base_height), 16.0));
+    // The integral of the exponential density along the ray, over its length
+    let height_delta = fog.height_falloff * view_to_world.y;
+    var average_density = 1.0;
+    if (abs(height_delta) > 0.0001) {
+        average_density = (1.0 - exp(-height_delta)) / height_delta;
+    }
+    return ray_length * view_density * average_density;
+}
+
+// Fades `input_color` into the fog of the view, from the view at `view_world_position` to the
+// fragment at `fragment_world_position`
+fn apply_fog(input_color: vec4<f32>, fragment_world_position: vec3<f32>, view_world_position: vec3<f32>) -> vec4<f32> {
+    let view_to_world = fragment_world_position - view_world_position;
+    let optical_distance = fog_optical_distance(view_to_world, view_world_position.y);
+
+    var fog_amount = 0.0;
+    if (fog.mode == FOG_MODE_LINEAR) {
+        fog_amount = clamp((optical_distance - fog.start) / (fog.end - fog.start), 0.0, 1.0);
+    } else if (fog.mode == FOG_MODE_EXPONENTIAL) {
+        fog_amount = 1.0 - exp(-optical_distance * fog.density);
+    } else if (fog.mode == FOG_MODE_EXPONENTIAL_SQUARED) {
+        let density_distance = optical_distance * fog.density;
+        fog_amount = 1.0 - exp(-density_distance * density_distance);
+    }
+
+    // The light of the directional lights scattered towards the view by the fog, when looking
+    // towards them
+    var fog_color = fog.base_color.rgb;
+    if (fog.directional_light_color.a > 0.0) {
+        let view_direction = normalize(view_to_world);
+        for (var i: u32 = 0u; i < lights.n_directional_lights; i = i + 1u) {
+            let light = lights.directional_lights[i];
+            let scattering = pow(max(dot(view_direction, light.direction_to_light), 0.0), fog.directional_light_exponent);
+            fog_color = fog_color + scattering * light.color.rgb * fog.directional_light_color.rgb
+                * fog.directional_light_color.a;
+        }
+    }
+
+    return vec4<f32>(mix(input_color.rgb, fog_color, fog_amount * fog.base_color.a), input_color.a);
+}
@@ -1,8 +1,8 @@
 use crate::{
-    point_light_order, AmbientLight, Clusters, CubemapVisibleEntities, DirectionalLight,
-    DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, MeshPipeline, NotShadowCaster,
-    PointLight, PointLightShadowMap, SetMeshBindGroup, SpotLight, VisiblePointLights,
-    SHADOW_SHADER_HANDLE,
+    point_light_order, AmbientLight, Clusters, CubemapVisibleEntities, DecalMeta, DirectionalLight,
+    DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, LightTextures, MeshPipeline,
+    NotShadowCaster, PointLight, PointLightShadowMap, PointLightTexture, SetMeshBindGroup,
+    SpotLight, SpotLightTexture, VisiblePointLights, SHADOW_SHADER_HANDLE,
 };
 use bevy_asset::Handle;
 use bevy_core_pipeline::core_3d::Transparent3d;
@@ -26,8 +26,8 @@ use bevy_render::{
     renderer::{RenderContext, RenderDevice, RenderQueue},
     texture::*,
     view::{
-        ComputedVisibility, ExtractedView, ViewUniform, ViewUniformOffset, ViewUniforms,
-        VisibleEntities,
+        ComputedVisibility, ExtractedView, RenderLayers, ViewUniform, ViewUniformOffset,
+        ViewUniforms, VisibleEntities,
     },
     Extract,
 };
@@ -59,7 +59,11 @@ pub struct ExtractedPointLight {
     shadows_enabled: bool,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    shadow_map_size: usize,
     spot_light_angles: Option<(f32, f32)>,
+    render_layers: RenderLayers,
+    /// The image of the [`SpotLightTexture`] or [`PointLightTexture`] of the light.
+    texture: Option<Handle<Image>>,
 }
 
 #[derive(Component)]
@@ -71,19 +75,27 @@ pub struct ExtractedDirectionalLight {
     shadows_enabled: bool,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    shadow_map_size: usize,
+    render_layers: RenderLayers,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuPointLight {
-    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
+    // For point lights: the rotation of the light, as a quaternion
     // For spot lights: 2 components of the direction (x,z), spot_scale and spot_offset
     light_custom_data: Vec4,
     color_inverse_square_range: Vec4,
     position_radius: Vec4,
     flags: u32,
-    shadow_depth_bias: f32,
-    shadow_normal_bias: f32,
-    spot_light_tan_angle: f32,
+    // The shadow depth bias and shadow normal bias as half precision floats, packed so the light
+    // still fits in 64 bytes for the MAX_UNIFORM_BUFFER_POINT_LIGHTS of the uniform buffer
+    shadow_biases: u32,
+    // For point lights: the fraction of the width of the shadow map faces the light is rendered to
+    // For spot lights: the tangent of the outer angle, divided by the fraction of the width of the
+    // shadow map the light is rendered to
+    shadow_custom_data: f32,
+    // The RenderLayers of the light, as a mask
+    render_layers: u32,
 }
 
 #[derive(ShaderType)]
@@ -174,6 +186,10 @@ bitflags::bitflags! {
     }
 }
 
+/// The upper bits of the flags of a [`GpuPointLight`] are the index of its light texture in the
+/// [`LightTextures`] plus one, 0 without one.
+const POINT_LIGHT_FLAGS_TEXTURE_INDEX_SHIFT: u32 = 16;
+
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuDirectionalLight {
     view_projection: Mat4,
@@ -182,6 +198,10 @@ pub struct GpuDirectionalLight {
     flags: u32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    // The fraction of the width of the shadow map the light is rendered to
+    shadow_map_scale: f32,
+    // The RenderLayers of the light, as a mask
+    render_layers: u32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -419,6 +439,8 @@ pub fn extract_lights(
             &CubemapVisibleEntities,
             &GlobalTransform,
             &ComputedVisibility,
+            Option<&RenderLayers>,
+            Option<&PointLightTexture>,
         )>,
     >,
     spot_lights: Extract<
@@ -427,6 +449,8 @@ pub fn extract_lights(
             &VisibleEntities,
             &GlobalTransform,
             &ComputedVisibility,
+            Option<&RenderLayers>,
+            Option<&SpotLightTexture>,
         )>,
     >,
     directional_lights: Extract<
@@ -437,6 +461,7 @@ pub fn extract_lights(
                 &VisibleEntities,
                 &GlobalTransform,
                 &ComputedVisibility,
+                Option<&RenderLayers>,
             ),
             Without<SpotLight>,
         >,
@@ -459,12 +484,16 @@ pub fn extract_lights(
     // point_light_texel_size = 2.0 / cube face width in texels
     // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
     // https://catlikecoding.com/unity/tutorials/custom-srp/point-and-spot-shadows/
-    let point_light_texel_size = 2.0 / point_light_shadow_map.size as f32;
-
     let mut point_lights_values = Vec::with_capacity(*previous_point_lights_len);
     for entity in global_point_lights.iter().copied() {
-        if let Ok((point_light, cubemap_visible_entities, transform, visibility)) =
-            point_lights.get(entity)
+        if let Ok((
+            point_light,
+            cubemap_visible_entities,
+            transform,
+            visibility,
+            render_layers,
+            texture,
+        )) = point_lights.get(entity)
         {
             if !visibility.is_visible() {
                 continue;
@@ -472,6 +501,10 @@ pub fn extract_lights(
             // TODO: This is very much not ideal. We should be able to re-use the vector memory.
             // However, since exclusive access to the main world in extract is ill-advised, we just clone here.
             let render_cubemap_visible_entities = cubemap_visible_entities.clone();
+            let shadow_map_size = point_light
+                .shadow_map_size
+                .unwrap_or(point_light_shadow_map.size);
+            let point_light_texel_size = 2.0 / shadow_map_size as f32;
             point_lights_values.push((
                 entity,
                 (
@@ -490,7 +523,10 @@ pub fn extract_lights(
                         shadow_normal_bias: point_light.shadow_normal_bias
                             * point_light_texel_size
                             * std::f32::consts::SQRT_2,
+                        shadow_map_size,
                         spot_light_angles: None,
+                        render_layers: render_layers.copied().unwrap_or_default(),
+                        texture: texture.map(|texture| texture.image.clone_weak()),
                     },
                     render_cubemap_visible_entities,
                 ),
@@ -502,15 +538,19 @@ pub fn extract_lights(
 
     let mut spot_lights_values = Vec::with_capacity(*previous_spot_lights_len);
     for entity in global_point_lights.iter().copied() {
-        if let Ok((spot_light, visible_entities, transform, visibility)) = spot_lights.get(entity) {
+        if let Ok((spot_light, visible_entities, transform, visibility, render_layers, texture)) =
+            spot_lights.get(entity)
+        {
             if !visibility.is_visible() {
                 continue;
             }
             // TODO: This is very much not ideal. We should be able to re-use the vector memory.
             // However, since exclusive access to the main world in extract is ill-advised, we just clone here.
             let render_visible_entities = visible_entities.clone();
-            let texel_size =
-                2.0 * spot_light.outer_angle.tan() / directional_light_shadow_map.size as f32;
+            let shadow_map_size = spot_light
+                .shadow_map_size
+                .unwrap_or(directional_light_shadow_map.size);
+            let texel_size = 2.0 * spot_light.outer_angle.tan() / shadow_map_size as f32;
 
             spot_lights_values.push((
                 entity,
@@ -533,7 +573,10 @@ pub fn extract_lights(
                         shadow_normal_bias: spot_light.shadow_normal_bias
                             * texel_size
                             * std::f32::consts::SQRT_2,
+                        shadow_map_size,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
+                        render_layers: render_layers.copied().unwrap_or_default(),
+                        texture: texture.map(|texture| texture.image.clone_weak()),
                     },
                     render_visible_entities,
                 ),
@@ -543,7 +586,7 @@ pub fn extract_lights(
     *previous_spot_lights_len = spot_lights_values.len();
     commands.insert_or_spawn_batch(spot_lights_values);
 
-    for (entity, directional_light, visible_entities, transform, visibility) in
+    for (entity, directional_light, visible_entities, transform, visibility, render_layers) in
         directional_lights.iter()
     {
         if !visibility.is_visible() {
@@ -560,8 +603,10 @@ pub fn extract_lights(
                 directional_light.shadow_projection.top
                     - directional_light.shadow_projection.bottom,
             );
-        let directional_light_texel_size =
-            largest_dimension / directional_light_shadow_map.size as f32;
+        let shadow_map_size = directional_light
+            .shadow_map_size
+            .unwrap_or(directional_light_shadow_map.size);
+        let directional_light_texel_size = largest_dimension / shadow_map_size as f32;
         // TODO: As above
         let render_visible_entities = visible_entities.clone();
         commands.get_or_spawn(entity).insert((
@@ -576,6 +621,8 @@ pub fn extract_lights(
                 shadow_normal_bias: directional_light.shadow_normal_bias
                     * directional_light_texel_size
                     * std::f32::consts::SQRT_2,
+                shadow_map_size,
+                render_layers: render_layers.copied().unwrap_or_default(),
             },
             render_visible_entities,
         ));
@@ -659,9 +706,10 @@ pub struct ViewLightsUniformOffset {
     pub offset: u32,
 }
 
-// NOTE: Clustered-forward rendering requires 3 storage buffer bindings so check that
-// at least that many are supported using this constant and SupportedBindingType::from_device()
-pub const CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT: u32 = 3;
+// NOTE: Clustered-forward rendering requires 4 storage buffer bindings, including the decals, so
+// check that at least that many are supported using this constant and
+// SupportedBindingType::from_device()
+pub const CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT: u32 = 4;
 
 #[derive(Resource)]
 pub struct GlobalLightMeta {
@@ -756,6 +804,49 @@ pub(crate) fn spot_light_projection_matrix(angle: f32) -> Mat4 {
     Mat4::perspective_infinite_reverse_rh(angle * 2.0, 1.0, POINT_LIGHT_NEAR_Z)
 }
 
+/// The viewport of a light with a `shadow_map_size` in a shadow map texture `texture_size` texels
+/// wide, centered in it.
+///
+/// Its width keeps the parity of the texture's, so that it is centered on texel boundaries.
+fn shadow_map_viewport(shadow_map_size: usize, texture_size: u32) -> UVec4 {
+    let mut size = (shadow_map_size as u32).clamp(1, texture_size);
+    size += (texture_size - size) % 2;
+    let offset = (texture_size - size) / 2;
+    UVec4::new(offset, offset, size, size)
+}
+
+/// The fraction of the width of a shadow map texture `texture_size` texels wide the
+/// [`shadow_map_viewport`] of a light covers.
+fn shadow_map_scale(shadow_map_size: usize, texture_size: u32) -> f32 {
+    shadow_map_viewport(shadow_map_size, texture_size).z as f32 / texture_size as f32
+}
+
+/// Packs `low` and `high` into the low and high bits of a `u32` as half precision floats, to be
+/// unpacked with `unpack2x16float` in WGSL.
+fn pack_half_floats(low: f32, high: f32) -> u32 {
+    f32_to_f16_bits(low) as u32 | (f32_to_f16_bits(high) as u32) << 16
+}
+
+/// The bits of the half precision float closest to `value`, rounding to even. The values too
+/// small to be normal half precision floats are flushed to zero.
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = (bits >> 16) as u16 & 0x8000;
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    // Round the 23 bits of the mantissa to 10, which may carry into the exponent
+    let rounded = (bits & 0x7fff_ffff) + 0x0fff + ((bits >> 13) & 1);
+    let exponent = (rounded >> 23) as i32 - 127 + 15;
+    if exponent >= 0x1f {
+        sign | 0x7c00
+    } else if exponent <= 0 {
+        sign
+    } else {
+        sign | (exponent as u16) << 10 | ((rounded >> 13) & 0x3ff) as u16
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn prepare_lights(
     mut commands: Commands,
@@ -769,8 +860,7 @@ pub fn prepare_lights(
         With<RenderPhase<Transparent3d>>,
     >,
     ambient_light: Res<AmbientLight>,
-    point_light_shadow_map: Res<PointLightShadowMap>,
-    directional_light_shadow_map: Res<DirectionalLightShadowMap>,
+    light_textures: Res<LightTextures>,
     point_lights: Query<(Entity, &ExtractedPointLight)>,
     directional_lights: Query<(Entity, &ExtractedDirectionalLight)>,
 ) {
@@ -846,6 +936,35 @@ pub fn prepare_lights(
             .reserve(point_lights.len());
     }
 
+    // The shadow maps of each kind of light share a texture as large as the largest one, the
+    // smaller ones are rendered to its centre.
+    // When no light casts shadows, the shadow map textures are only bound for the shaders, so
+    // they are kept as small as possible. This lets the texture cache drop the full size ones as
+    // soon as the last light stops casting shadows.
+    let max_texture_dimension_2d = render_device.limits().max_texture_dimension_2d;
+    let point_light_shadow_map_size = point_lights
+        .iter()
+        .take(point_light_shadow_maps_count)
+        .map(|(_, light)| light.shadow_map_size as u32)
+        .max()
+        .unwrap_or(1)
+        .min(max_texture_dimension_2d);
+    let directional_light_shadow_map_size = directional_lights
+        .iter()
+        .filter(|(_, light)| light.shadows_enabled)
+        .map(|(_, light)| light.shadow_map_size)
+        .chain(
+            point_lights
+                .iter()
+                .skip(point_light_count)
+                .take(spot_light_shadow_maps_count)
+                .map(|(_, light)| light.shadow_map_size),
+        )
+        .map(|shadow_map_size| shadow_map_size as u32)
+        .max()
+        .unwrap_or(1)
+        .min(max_texture_dimension_2d);
+
     let mut gpu_point_lights = Vec::new();
     for (index, &(entity, light)) in point_lights.iter().enumerate() {
         let mut flags = PointLightFlags::NONE;
@@ -859,7 +978,7 @@ pub fn prepare_lights(
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
 
-        let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
+        let (light_custom_data, shadow_custom_data) = match light.spot_light_angles {
             Some((inner, outer)) => {
                 let light_direction = light.transform.forward();
                 if light_direction.y.is_sign_negative() {
@@ -873,24 +992,34 @@ pub fn prepare_lights(
                 (
                     // For spot lights: the direction (x,z), spot_scale and spot_offset
                     light_direction.xz().extend(spot_scale).extend(spot_offset),
-                    outer.tan(),
+                    outer.tan()
+                        / shadow_map_scale(
+                            light.shadow_map_size,
+                            directional_light_shadow_map_size,
+                        ),
                 )
             }
             None => {
                 (
-                    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
-                    Vec4::new(
-                        cube_face_projection.z_axis.z,
-                        cube_face_projection.z_axis.w,
-                        cube_face_projection.w_axis.z,
-                        cube_face_projection.w_axis.w,
-                    ),
-                    // unused
-                    0.0,
+                    // For point lights: the rotation of the light, as a quaternion
+                    Vec4::from(light.transform.to_scale_rotation_translation().1),
+                    shadow_map_scale(light.shadow_map_size, point_light_shadow_map_size),
                 )
             }
         };
 
+        let texture_indices = match light.spot_light_angles {
+            Some(_) => &light_textures.spot_light_indices,
+            None => &light_textures.point_light_indices,
+        };
+        if let Some(&texture_index) = light
+            .texture
+            .as_ref()
+            .and_then(|texture| texture_indices.get(texture))
+        {
+            flags.bits |= (texture_index + 1) << POINT_LIGHT_FLAGS_TEXTURE_INDEX_SHIFT;
+        }
+
         gpu_point_lights.push(GpuPointLight {
             light_custom_data,
             // premultiply color by intensity
@@ -901,9 +1030,9 @@ pub fn prepare_lights(
                 .extend(1.0 / (light.range * light.range)),
             position_radius: light.transform.translation().extend(light.radius),
             flags: flags.bits,
-            shadow_depth_bias: light.shadow_depth_bias,
-            shadow_normal_bias: light.shadow_normal_bias,
-            spot_light_tan_angle,
+            shadow_biases: pack_half_floats(light.shadow_depth_bias, light.shadow_normal_bias),
+            shadow_custom_data,
+            render_layers: light.render_layers.bits(),
         });
         global_light_meta.entity_to_index.insert(entity, index);
     }
@@ -919,8 +1048,8 @@ pub fn prepare_lights(
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
-                    width: point_light_shadow_map.size as u32,
-                    height: point_light_shadow_map.size as u32,
+                    width: point_light_shadow_map_size,
+                    height: point_light_shadow_map_size,
                     depth_or_array_layers: point_light_shadow_maps_count.max(1) as u32 * 6,
                 },
                 mip_level_count: 1,
@@ -935,10 +1064,8 @@ pub fn prepare_lights(
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
-                    width: (directional_light_shadow_map.size as u32)
-                        .min(render_device.limits().max_texture_dimension_2d),
-                    height: (directional_light_shadow_map.size as u32)
-                        .min(render_device.limits().max_texture_dimension_2d),
+                    width: directional_light_shadow_map_size,
+                    height: directional_light_shadow_map_size,
                     depth_or_array_layers: (directional_shadow_maps_count
                         + spot_light_shadow_maps_count)
                         .max(1) as u32,
@@ -1023,11 +1150,9 @@ pub fn prepare_lights(
                             ),
                         },
                         ExtractedView {
-                            viewport: UVec4::new(
-                                0,
-                                0,
-                                point_light_shadow_map.size as u32,
-                                point_light_shadow_map.size as u32,
+                            viewport: shadow_map_viewport(
+                                light.shadow_map_size,
+                                point_light_shadow_map_size,
                             ),
                             transform: view_translation * *view_rotation,
                             projection: cube_face_projection,
@@ -1078,11 +1203,9 @@ pub fn prepare_lights(
                         pass_name: format!("shadow pass spot light {}", light_index,),
                     },
                     ExtractedView {
-                        viewport: UVec4::new(
-                            0,
-                            0,
-                            directional_light_shadow_map.size as u32,
-                            directional_light_shadow_map.size as u32,
+                        viewport: shadow_map_viewport(
+                            light.shadow_map_size,
+                            directional_light_shadow_map_size,
                         ),
                         transform: spot_view_transform,
                         projection: spot_projection,
@@ -1137,6 +1260,11 @@ pub fn prepare_lights(
                 flags: flags.bits,
                 shadow_depth_bias: light.shadow_depth_bias,
                 shadow_normal_bias: light.shadow_normal_bias,
+                shadow_map_scale: shadow_map_scale(
+                    light.shadow_map_size,
+                    directional_light_shadow_map_size,
+                ),
+                render_layers: light.render_layers.bits(),
             };
 
             if light.shadows_enabled {
@@ -1161,11 +1289,9 @@ pub fn prepare_lights(
                             pass_name: format!("shadow pass directional light {}", i),
                         },
                         ExtractedView {
-                            viewport: UVec4::new(
-                                0,
-                                0,
-                                directional_light_shadow_map.size as u32,
-                                directional_light_shadow_map.size as u32,
+                            viewport: shadow_map_viewport(
+                                light.shadow_map_size,
+                                directional_light_shadow_map_size,
                             ),
                             transform: GlobalTransform::from(view.inverse()),
                             projection,
@@ -1375,7 +1501,15 @@ impl ViewClusterBindings {
         }
     }
 
-    pub fn push_offset_and_counts(&mut self, offset: usize, point_count: usize, spot_count: usize) {
+    /// Pushes the offset and counts of the next cluster. The decal count is dropped with
+    /// uniform buffers, which have no room for it.
+    pub fn push_offset_and_counts(
+        &mut self,
+        offset: usize,
+        point_count: usize,
+        spot_count: usize,
+        decal_count: usize,
+    ) {
         match &mut self.buffers {
             ViewClusterBuffers::Uniform {
                 cluster_offsets_and_counts,
@@ -1399,7 +1533,7 @@ impl ViewClusterBindings {
                     offset as u32,
                     point_count as u32,
                     spot_count as u32,
-                    0,
+                    decal_count as u32,
                 ));
             }
         }
@@ -1506,6 +1640,7 @@ pub fn prepare_clusters(
     render_queue: Res<RenderQueue>,
     mesh_pipeline: Res<MeshPipeline>,
     global_light_meta: Res<GlobalLightMeta>,
+    decal_meta: Res<DecalMeta>,
     views: Query<
         (
             Entity,
@@ -1537,11 +1672,16 @@ pub fn prepare_clusters(
                         offset,
                         cluster_lights.point_light_count,
                         cluster_lights.spot_light_count,
+                        cluster_lights.decal_count,
                     );
 
                     if !indices_full {
                         for entity in cluster_lights.iter() {
-                            if let Some(light_index) = global_light_meta.entity_to_index.get(entity)
+                            // NOTE: The indices of the decals are into the decals rather than the lights
+                            if let Some(light_index) = global_light_meta
+                                .entity_to_index
+                                .get(entity)
+                                .or_else(|| decal_meta.entity_to_index.get(entity))
                             {
                                 if view_clusters_bindings.n_indices()
                                     >= ViewClusterBindings::MAX_INDICES
@@ -1699,7 +1839,11 @@ impl CachedRenderPipelinePhaseItem for Shadow {
 
 pub struct ShadowPassNode {
     main_view_query: QueryState<&'static ViewLightEntities>,
-    view_light_query: QueryState<(&'static ShadowView, &'static RenderPhase<Shadow>)>,
+    view_light_query: QueryState<(
+        &'static ShadowView,
+        &'static ExtractedView,
+        &'static RenderPhase<Shadow>,
+    )>,
 }
 
 impl ShadowPassNode {
@@ -1732,7 +1876,7 @@ impl Node for ShadowPassNode {
         let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
         if let Ok(view_lights) = self.main_view_query.get_manual(world, view_entity) {
             for view_light_entity in view_lights.lights.iter().copied() {
-                let (view_light, shadow_phase) = self
+                let (view_light, extracted_view, shadow_phase) = self
                     .view_light_query
                     .get_manual(world, view_light_entity)
                     .unwrap();
@@ -1755,11 +1899,10 @@ impl Node for ShadowPassNode {
                 };
 
                 let draw_functions = world.resource::<DrawFunctions<Shadow>>();
-                let render_pass = render_context
-                    .command_encoder
-                    .begin_render_pass(&pass_descriptor);
                 let mut draw_functions = draw_functions.write();
-                let mut tracked_pass = TrackedRenderPass::new(render_pass);
+                let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+                let viewport = extracted_view.viewport.as_vec4();
+                tracked_pass.set_viewport(viewport.x, viewport.y, viewport.z, viewport.w, 0.0, 1.0);
                 for item in &shadow_phase.items {
                     let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
                     draw_function.draw(world, &mut tracked_pass, view_light_entity, item);
@@ -1802,3 +1945,149 @@ impl<const I: usize> EntityRenderCommand for SetShadowViewBindGroup<I> {
         RenderCommandResult::Success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_core::CorePlugin;
+    use bevy_core_pipeline::{core_3d::Camera3dBundle, CorePipelinePlugin};
+    use bevy_render::{camera::RenderTarget, RenderApp, RenderPlugin, RenderStage};
+    use bevy_time::TimePlugin;
+    use bevy_window::{WindowPlugin, WindowSettings};
+
+    use super::*;
+    use crate::{PbrPlugin, PointLightBundle};
+
+    /// The shadow views of the point light and the point light shadow map of the last frame,
+    /// recorded before the render world is cleared.
+    #[derive(Resource, Default)]
+    struct PointLightShadows {
+        faces: usize,
+        texture: Option<TextureId>,
+    }
+
+    fn record_point_light_shadows(
+        mut shadows: ResMut<PointLightShadows>,
+        views: Query<(&ViewLightEntities, &ViewShadowBindings)>,
+        lights: Query<&LightEntity>,
+    ) {
+        let (view_lights, shadow_bindings) = views.single();
+        shadows.faces = lights
+            .iter_many(&view_lights.lights)
+            .filter(|light| matches!(light, LightEntity::Point { .. }))
+            .count();
+        shadows.texture = Some(shadow_bindings.point_light_depth_texture.id());
+    }
+
+    #[test]
+    fn shadow_map_viewports_are_centered() {
+        assert_eq!(
+            shadow_map_viewport(4096, 4096),
+            UVec4::new(0, 0, 4096, 4096)
+        );
+        assert_eq!(
+            shadow_map_viewport(1024, 4096),
+            UVec4::new(1536, 1536, 1024, 1024)
+        );
+        // The viewport keeps the parity of the texture, to stay centered on texel boundaries
+        assert_eq!(
+            shadow_map_viewport(1023, 4096),
+            UVec4::new(1536, 1536, 1024, 1024)
+        );
+        assert_eq!(shadow_map_viewport(0, 2), UVec4::new(0, 0, 2, 2));
+        assert_eq!(
+            shadow_map_viewport(8192, 4096),
+            UVec4::new(0, 0, 4096, 4096)
+        );
+        assert_eq!(shadow_map_scale(1024, 4096), 0.25);
+    }
+
+    #[test]
+    fn half_floats_are_packed() {
+        assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+        assert_eq!(f32_to_f16_bits(1.0), 0x3c00);
+        assert_eq!(f32_to_f16_bits(-2.0), 0xc000);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16_bits(1e6), 0x7c00);
+        assert_eq!(f32_to_f16_bits(1e-8), 0x0000);
+        // 1 + 2^-11 is halfway between two half precision floats, and rounds to the even one
+        assert_eq!(f32_to_f16_bits(1.0 + 2f32.powi(-11)), 0x3c00);
+        assert_eq!(f32_to_f16_bits(1.0 + 3.0 * 2f32.powi(-11)), 0x3c02);
+        // Rounding the largest mantissa carries into the exponent
+        assert_eq!(f32_to_f16_bits(2.0 - 2f32.powi(-12)), 0x4000);
+        assert_eq!(pack_half_floats(1.0, -2.0), 0xc000_3c00);
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn disabling_shadows_releases_the_shadow_map() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(TimePlugin)
+            .add_plugin(AssetPlugin)
+            .insert_resource(WindowSettings {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            })
+            .add_plugin(WindowPlugin)
+            .add_plugin(RenderPlugin)
+            .add_plugin(CorePipelinePlugin)
+            .add_plugin(PbrPlugin);
+        app.sub_app_mut(RenderApp)
+            .init_resource::<PointLightShadows>()
+            .add_system_to_stage(RenderStage::Cleanup, record_point_light_shadows);
+
+        let mut image = Image::default();
+        image.resize(Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        });
+        image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+        let image = app.world.resource_mut::<Assets<Image>>().add(image);
+        app.world.spawn(Camera3dBundle {
+            camera: Camera {
+                target: RenderTarget::Image(image),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 5.0),
+            ..Default::default()
+        });
+        let light = app
+            .world
+            .spawn(PointLightBundle {
+                point_light: PointLight {
+                    shadows_enabled: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+
+        let shadows = |app: &mut App| {
+            app.update();
+            let shadows = app.sub_app(RenderApp).world.resource::<PointLightShadows>();
+            (shadows.faces, shadows.texture.unwrap())
+        };
+        let (faces, shadow_map) = shadows(&mut app);
+        assert_eq!(faces, 6);
+
+        // The shadow views are gone the same frame, and the shadow map is replaced by a
+        // placeholder, letting the texture cache free it
+        app.world
+            .get_mut::<PointLight>(light)
+            .unwrap()
+            .shadows_enabled = false;
+        let (faces, placeholder) = shadows(&mut app);
+        assert_eq!(faces, 0);
+        assert_ne!(placeholder, shadow_map);
+
+        app.world
+            .get_mut::<PointLight>(light)
+            .unwrap()
+            .shadows_enabled = true;
+        assert_eq!(shadows(&mut app).0, 6);
+    }
+}
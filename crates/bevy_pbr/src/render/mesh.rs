@@ -1,32 +1,39 @@
 use crate::{
-    GlobalLightMeta, GpuLights, GpuPointLights, LightMeta, NotShadowCaster, NotShadowReceiver,
-    ShadowPipeline, ViewClusterBindings, ViewLightsUniformOffset, ViewShadowBindings,
-    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    DecalMeta, EnvironmentMapLight, FogMeta, GlobalLightMeta, GpuDecals, GpuFog, GpuLights,
+    GpuPointLights, LightMeta, LightTextures, Lightmap, MeshLod, MeshLodFadeOuts, NotShadowCaster,
+    NotShadowReceiver, ShadowFilteringMethod, ShadowPipeline, ViewClusterBindings,
+    ViewFogUniformOffset, ViewLightsUniformOffset, ViewScreenSpaceAmbientOcclusionTextures,
+    ViewShadowBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
 };
 use bevy_app::Plugin;
 use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
+use bevy_core_pipeline::{
+    oit::oit_color_targets, prepass::MOTION_VECTOR_PREPASS_FORMAT,
+    ssr::ViewScreenSpaceReflectionsTextures, transmission::ViewTransmissionTexture,
+};
 use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
-use bevy_math::{Mat3A, Mat4, Vec2};
+use bevy_math::{Mat3A, Mat4, Rect, Vec2, Vec4};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
     globals::{GlobalsBuffer, GlobalsUniform},
     mesh::{
         skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
-        GpuBufferInfo, Mesh, MeshVertexBufferLayout,
+        GpuBufferInfo, InstanceBatches, Mesh, MeshVertexBufferLayout,
     },
     render_asset::RenderAssets,
     render_phase::{EntityRenderCommand, RenderCommandResult, TrackedRenderPass},
     render_resource::*,
     renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
     texture::{DefaultImageSampler, GpuImage, Image, ImageSampler, TextureFormatPixelInfo},
-    view::{ComputedVisibility, ViewUniform, ViewUniformOffset, ViewUniforms},
+    view::{ComputedVisibility, RenderLayers, ViewUniform, ViewUniformOffset, ViewUniforms},
     Extract, RenderApp, RenderStage,
 };
 use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
 use std::num::NonZeroU64;
 
 #[derive(Default)]
@@ -52,6 +59,8 @@ pub const MESH_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3252377289100772450);
 pub const SKINNING_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13215291596265391738);
+pub const PREPASS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6277286914685523211);
 
 impl Plugin for MeshRenderPlugin {
     fn build(&self, app: &mut bevy_app::App) {
@@ -88,6 +97,12 @@ impl Plugin for MeshRenderPlugin {
         );
         load_internal_asset!(app, MESH_SHADER_HANDLE, "mesh.wgsl", Shader::from_wgsl);
         load_internal_asset!(app, SKINNING_HANDLE, "skinning.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            PREPASS_SHADER_HANDLE,
+            "prepass.wgsl",
+            Shader::from_wgsl
+        );
 
         app.add_plugin(UniformComponentPlugin::<MeshUniform>::default());
 
@@ -95,6 +110,7 @@ impl Plugin for MeshRenderPlugin {
             render_app
                 .init_resource::<MeshPipeline>()
                 .init_resource::<SkinnedMeshUniform>()
+                .init_resource::<MeshLodFadeOuts>()
                 .add_system_to_stage(RenderStage::Extract, extract_meshes)
                 .add_system_to_stage(RenderStage::Extract, extract_skinned_meshes)
                 .add_system_to_stage(RenderStage::Prepare, prepare_skinned_meshes)
@@ -108,7 +124,18 @@ impl Plugin for MeshRenderPlugin {
 pub struct MeshUniform {
     pub transform: Mat4,
     pub inverse_transpose_model: Mat4,
+    /// The transform of the previous frame, or the current one on the first frame the mesh is
+    /// visible.
+    pub previous_transform: Mat4,
+    /// The [`Lightmap::uv_rect`] of the mesh, as `min.xy` and `max.xy`.
+    pub lightmap_uv_rect: Vec4,
     pub flags: u32,
+    /// The [`RenderLayers`] of the mesh, as a mask. Only the lights with intersecting layers
+    /// illuminate it.
+    pub render_layers: u32,
+    /// The progress of the cross-fade of the [`MeshLod`] level of the mesh, `1.0` when it isn't
+    /// cross-fading. The mesh fades out instead of in with the `LOD_FADE_OUT` flag.
+    pub lod_fade: f32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_types.wgsl!
@@ -116,6 +143,8 @@ bitflags::bitflags! {
     #[repr(transparent)]
     struct MeshFlags: u32 {
         const SHADOW_RECEIVER            = (1 << 0);
+        // Set on the previous level of a cross-fading MeshLod
+        const LOD_FADE_OUT               = (1 << 1);
         // Indicates the sign of the determinant of the 3x3 model matrix. If the sign is positive,
         // then the flag should be set, else it should not be set.
         const SIGN_DETERMINANT_MODEL_3X3 = (1 << 31);
@@ -128,12 +157,17 @@ pub fn extract_meshes(
     mut commands: Commands,
     mut prev_caster_commands_len: Local<usize>,
     mut prev_not_caster_commands_len: Local<usize>,
+    mut previous_transforms: Local<HashMap<Entity, Mat4>>,
+    mut lod_fade_outs: ResMut<MeshLodFadeOuts>,
     meshes_query: Extract<
         Query<(
             Entity,
             &ComputedVisibility,
             &GlobalTransform,
             &Handle<Mesh>,
+            Option<&Lightmap>,
+            Option<&RenderLayers>,
+            Option<&MeshLod>,
             Option<With<NotShadowReceiver>>,
             Option<With<NotShadowCaster>>,
         )>,
@@ -141,10 +175,15 @@ pub fn extract_meshes(
 ) {
     let mut caster_commands = Vec::with_capacity(*prev_caster_commands_len);
     let mut not_caster_commands = Vec::with_capacity(*prev_not_caster_commands_len);
+    let mut transforms = HashMap::with_capacity(previous_transforms.len());
     let visible_meshes = meshes_query.iter().filter(|(_, vis, ..)| vis.is_visible());
+    lod_fade_outs.entities.clear();
 
-    for (entity, _, transform, handle, not_receiver, not_caster) in visible_meshes {
+    for (entity, _, transform, handle, lightmap, render_layers, lod, not_receiver, not_caster) in
+        visible_meshes
+    {
         let transform = transform.compute_matrix();
+        transforms.insert(entity, transform);
         let mut flags = if not_receiver.is_some() {
             MeshFlags::empty()
         } else {
@@ -153,11 +192,36 @@ pub fn extract_meshes(
         if Mat3A::from_mat4(transform).determinant().is_sign_positive() {
             flags |= MeshFlags::SIGN_DETERMINANT_MODEL_3X3;
         }
-        let uniform = MeshUniform {
+        let mut uniform = MeshUniform {
             flags: flags.bits,
             transform,
             inverse_transpose_model: transform.inverse().transpose(),
+            previous_transform: previous_transforms
+                .get(&entity)
+                .copied()
+                .unwrap_or(transform),
+            lightmap_uv_rect: lightmap.map_or(Vec4::ZERO, |lightmap| {
+                let Rect { min, max } = lightmap.uv_rect;
+                Vec4::new(min.x, min.y, max.x, max.y)
+            }),
+            render_layers: render_layers.copied().unwrap_or_default().bits(),
+            lod_fade: 1.0,
         };
+        // The previous level of a cross-fading MeshLod is drawn by another entity, which doesn't
+        // cast shadows
+        if let Some((previous_mesh, fade)) = lod.and_then(MeshLod::fading_out) {
+            uniform.lod_fade = fade;
+            let mut fade_out_uniform = uniform.clone();
+            fade_out_uniform.flags |= MeshFlags::LOD_FADE_OUT.bits;
+            let fade_out = commands
+                .spawn((
+                    previous_mesh.clone_weak(),
+                    fade_out_uniform,
+                    NotShadowCaster,
+                ))
+                .id();
+            lod_fade_outs.entities.push((entity, fade_out));
+        }
         if not_caster.is_some() {
             not_caster_commands.push((entity, (handle.clone_weak(), uniform, NotShadowCaster)));
         } else {
@@ -166,6 +230,7 @@ pub fn extract_meshes(
     }
     *prev_caster_commands_len = caster_commands.len();
     *prev_not_caster_commands_len = not_caster_commands.len();
+    *previous_transforms = transforms;
     commands.insert_or_spawn_batch(caster_commands);
     commands.insert_or_spawn_batch(not_caster_commands);
 }
@@ -257,8 +322,17 @@ pub struct MeshPipeline {
     pub view_layout: BindGroupLayout,
     pub mesh_layout: BindGroupLayout,
     pub skinned_mesh_layout: BindGroupLayout,
+    /// The layout of the [`Lightmap`] of the meshes specialized with
+    /// [`MeshPipelineKey::LIGHTMAPPED`], bound after the other bind groups.
+    pub lightmap_layout: BindGroupLayout,
     // This dummy white texture is to be used in place of optional StandardMaterial textures
     pub dummy_white_gpu_image: GpuImage,
+    // This dummy transparent texture is to be used in place of the screen space reflections of
+    // the views without them
+    pub dummy_transparent_gpu_image: GpuImage,
+    // This dummy black cubemap is to be used in place of the environment map of the views
+    // without one
+    pub dummy_black_cube_gpu_image: GpuImage,
     pub clustered_forward_buffer_binding_type: BufferBindingType,
 }
 
@@ -394,6 +468,134 @@ impl FromWorld for MeshPipeline {
                     },
                     count: None,
                 },
+                // Screen space ambient occlusion
+                BindGroupLayoutEntry {
+                    binding: 10,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                // Screen space reflections
+                BindGroupLayoutEntry {
+                    binding: 11,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                // Transmission texture
+                BindGroupLayoutEntry {
+                    binding: 12,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                // Transmission sampler
+                BindGroupLayoutEntry {
+                    binding: 13,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Environment map diffuse
+                BindGroupLayoutEntry {
+                    binding: 14,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::Cube,
+                    },
+                    count: None,
+                },
+                // Environment map specular
+                BindGroupLayoutEntry {
+                    binding: 15,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::Cube,
+                    },
+                    count: None,
+                },
+                // Environment map sampler
+                BindGroupLayoutEntry {
+                    binding: 16,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Fog
+                BindGroupLayoutEntry {
+                    binding: 17,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(GpuFog::min_size()),
+                    },
+                    count: None,
+                },
+                // Spot Light Textures
+                BindGroupLayoutEntry {
+                    binding: 18,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        #[cfg(not(feature = "webgl"))]
+                        view_dimension: TextureViewDimension::D2Array,
+                        #[cfg(feature = "webgl")]
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                // Point Light Textures
+                BindGroupLayoutEntry {
+                    binding: 19,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        #[cfg(not(feature = "webgl"))]
+                        view_dimension: TextureViewDimension::CubeArray,
+                        #[cfg(feature = "webgl")]
+                        view_dimension: TextureViewDimension::Cube,
+                    },
+                    count: None,
+                },
+                // Light Textures Sampler
+                BindGroupLayoutEntry {
+                    binding: 20,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Decals
+                BindGroupLayoutEntry {
+                    binding: 21,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: clustered_forward_buffer_binding_type,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GpuDecals::min_size(
+                            clustered_forward_buffer_binding_type,
+                        )),
+                    },
+                    count: None,
+                },
             ],
             label: Some("mesh_view_layout"),
         });
@@ -432,12 +634,42 @@ impl FromWorld for MeshPipeline {
                 label: Some("skinned_mesh_layout"),
             });
 
-        // A 1x1x1 'all 1.0' texture to use as a dummy texture to use in place of optional StandardMaterial textures
-        let dummy_white_gpu_image = {
+        let lightmap_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            label: Some("lightmap_layout"),
+        });
+
+        // A 1x1 texture filled with `pixel` to use as a dummy texture, with a layer for each face
+        // of cubemaps
+        let create_dummy_gpu_image = |pixel: &[u8], view_dimension| {
+            let depth_or_array_layers = match view_dimension {
+                TextureViewDimension::Cube => 6,
+                _ => 1,
+            };
             let image = Image::new_fill(
-                Extent3d::default(),
+                Extent3d {
+                    depth_or_array_layers,
+                    ..Default::default()
+                },
                 TextureDimension::D2,
-                &[255u8; 4],
+                pixel,
                 first_available_texture_format.0,
             );
             let texture = render_device.create_texture(&image.texture_descriptor);
@@ -463,12 +695,15 @@ impl FromWorld for MeshPipeline {
                         )
                         .unwrap(),
                     ),
-                    rows_per_image: None,
+                    rows_per_image: std::num::NonZeroU32::new(image.texture_descriptor.size.height),
                 },
                 image.texture_descriptor.size,
             );
 
-            let texture_view = texture.create_view(&TextureViewDescriptor::default());
+            let texture_view = texture.create_view(&TextureViewDescriptor {
+                dimension: Some(view_dimension),
+                ..Default::default()
+            });
             GpuImage {
                 texture,
                 texture_view,
@@ -480,13 +715,22 @@ impl FromWorld for MeshPipeline {
                 ),
             }
         };
+        // A 1x1x1 'all 1.0' texture to use as a dummy texture to use in place of optional StandardMaterial textures
+        let dummy_white_gpu_image = create_dummy_gpu_image(&[255u8; 4], TextureViewDimension::D2);
+        let dummy_transparent_gpu_image =
+            create_dummy_gpu_image(&[0u8; 4], TextureViewDimension::D2);
+        let dummy_black_cube_gpu_image =
+            create_dummy_gpu_image(&[0, 0, 0, 255], TextureViewDimension::Cube);
 
         MeshPipeline {
             view_layout,
             mesh_layout,
             skinned_mesh_layout,
+            lightmap_layout,
             clustered_forward_buffer_binding_type,
             dummy_white_gpu_image,
+            dummy_transparent_gpu_image,
+            dummy_black_cube_gpu_image,
         }
     }
 }
@@ -509,6 +753,20 @@ impl MeshPipeline {
     }
 }
 
+/// The depth test of the mesh pipeline specialized for `key`.
+///
+/// Values increase towards the camera, so the closer fragments pass. The main passes of views
+/// with a depth prepass also let through the fragments at the depth written by the prepass.
+fn depth_compare(key: MeshPipelineKey) -> CompareFunction {
+    if key.contains(MeshPipelineKey::LOADS_PREPASS_DEPTH)
+        && !key.contains(MeshPipelineKey::DEPTH_PREPASS)
+    {
+        CompareFunction::GreaterEqual
+    } else {
+        CompareFunction::Greater
+    }
+}
+
 bitflags::bitflags! {
     #[repr(transparent)]
     // NOTE: Apparently quadro drivers support up to 64x MSAA.
@@ -516,6 +774,15 @@ bitflags::bitflags! {
     pub struct MeshPipelineKey: u32 {
         const NONE                        = 0;
         const TRANSPARENT_MAIN_PASS       = (1 << 0);
+        const DEPTH_PREPASS               = (1 << 1);
+        const INSTANCED                   = (1 << 2);
+        const ORDER_INDEPENDENT_TRANSPARENCY = (1 << 3);
+        const MOTION_VECTOR_PREPASS       = (1 << 4);
+        const ENVIRONMENT_MAP             = (1 << 5);
+        const LIGHTMAPPED                 = (1 << 6);
+        const SHADOW_FILTER_GAUSSIAN      = (1 << 7);
+        const SHADOW_FILTER_TEMPORAL      = (1 << 8);
+        const LOADS_PREPASS_DEPTH         = (1 << 9);
         const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS = Self::PRIMITIVE_TOPOLOGY_MASK_BITS << Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
     }
@@ -537,6 +804,14 @@ impl MeshPipelineKey {
         1 << ((self.bits >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
     }
 
+    pub fn from_shadow_filtering_method(shadow_filtering_method: ShadowFilteringMethod) -> Self {
+        match shadow_filtering_method {
+            ShadowFilteringMethod::Hardware2x2 => MeshPipelineKey::NONE,
+            ShadowFilteringMethod::Gaussian => MeshPipelineKey::SHADOW_FILTER_GAUSSIAN,
+            ShadowFilteringMethod::Temporal => MeshPipelineKey::SHADOW_FILTER_TEMPORAL,
+        }
+    }
+
     pub fn from_primitive_topology(primitive_topology: PrimitiveTopology) -> Self {
         let primitive_topology_bits = ((primitive_topology as u32)
             & Self::PRIMITIVE_TOPOLOGY_MASK_BITS)
@@ -594,6 +869,11 @@ impl SpecializedMeshPipeline for MeshPipeline {
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(4));
         }
 
+        if layout.contains(Mesh::ATTRIBUTE_UV_1) {
+            shader_defs.push(String::from("VERTEX_UVS_1"));
+            vertex_attributes.push(Mesh::ATTRIBUTE_UV_1.at_shader_location(7));
+        }
+
         let mut bind_group_layout = vec![self.view_layout.clone()];
         if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
             && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
@@ -606,10 +886,46 @@ impl SpecializedMeshPipeline for MeshPipeline {
             bind_group_layout.push(self.mesh_layout.clone());
         };
 
+        if key.contains(MeshPipelineKey::INSTANCED) {
+            shader_defs.push(String::from("MESH_INSTANCED"));
+        }
+
+        let order_independent = key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS)
+            && key.contains(MeshPipelineKey::ORDER_INDEPENDENT_TRANSPARENCY);
+        if order_independent {
+            shader_defs.push(String::from("OIT"));
+        }
+
+        if key.contains(MeshPipelineKey::ENVIRONMENT_MAP) {
+            shader_defs.push(String::from("ENVIRONMENT_MAP"));
+        }
+
+        if key.contains(MeshPipelineKey::SHADOW_FILTER_GAUSSIAN) {
+            shader_defs.push(String::from("SHADOW_FILTER_METHOD_GAUSSIAN"));
+        } else if key.contains(MeshPipelineKey::SHADOW_FILTER_TEMPORAL) {
+            shader_defs.push(String::from("SHADOW_FILTER_METHOD_TEMPORAL"));
+        }
+
+        if key.contains(MeshPipelineKey::LIGHTMAPPED) {
+            shader_defs.push(String::from("LIGHTMAP"));
+            bind_group_layout.push(self.lightmap_layout.clone());
+        }
+
         let vertex_buffer_layout = layout.get_layout(&vertex_attributes)?;
 
         let (label, blend, depth_write_enabled);
-        if key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS) {
+        if key.contains(MeshPipelineKey::DEPTH_PREPASS) {
+            label = "depth_prepass_mesh_pipeline".into();
+            blend = None;
+            // The depth prepass only writes the depth buffer, and the motion vectors with a
+            // fragment shader if the view has them
+            depth_write_enabled = true;
+        } else if order_independent {
+            label = "oit_transparent_mesh_pipeline".into();
+            // The blending of the order-independent targets is set by `oit_color_targets`
+            blend = None;
+            depth_write_enabled = false;
+        } else if key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS) {
             label = "transparent_mesh_pipeline".into();
             blend = Some(BlendState::ALPHA_BLENDING);
             // For the transparent pass, fragments that are closer will be alpha blended
@@ -624,23 +940,43 @@ impl SpecializedMeshPipeline for MeshPipeline {
             depth_write_enabled = true;
         }
 
+        let fragment = if key.contains(MeshPipelineKey::DEPTH_PREPASS) {
+            key.contains(MeshPipelineKey::MOTION_VECTOR_PREPASS)
+                .then(|| FragmentState {
+                    shader: PREPASS_SHADER_HANDLE.typed::<Shader>(),
+                    shader_defs: shader_defs.clone(),
+                    entry_point: "fragment".into(),
+                    targets: vec![Some(ColorTargetState {
+                        format: MOTION_VECTOR_PREPASS_FORMAT,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                })
+        } else {
+            Some(FragmentState {
+                shader: MESH_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: shader_defs.clone(),
+                entry_point: "fragment".into(),
+                targets: if order_independent {
+                    oit_color_targets()
+                } else {
+                    vec![Some(ColorTargetState {
+                        format: self.dummy_white_gpu_image.texture_format,
+                        blend,
+                        write_mask: ColorWrites::ALL,
+                    })]
+                },
+            })
+        };
+
         Ok(RenderPipelineDescriptor {
             vertex: VertexState {
                 shader: MESH_SHADER_HANDLE.typed::<Shader>(),
                 entry_point: "vertex".into(),
-                shader_defs: shader_defs.clone(),
+                shader_defs,
                 buffers: vec![vertex_buffer_layout],
             },
-            fragment: Some(FragmentState {
-                shader: MESH_SHADER_HANDLE.typed::<Shader>(),
-                shader_defs,
-                entry_point: "fragment".into(),
-                targets: vec![Some(ColorTargetState {
-                    format: self.dummy_white_gpu_image.texture_format,
-                    blend,
-                    write_mask: ColorWrites::ALL,
-                })],
-            }),
+            fragment,
             layout: Some(bind_group_layout),
             primitive: PrimitiveState {
                 front_face: FrontFace::Ccw,
@@ -654,7 +990,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
             depth_stencil: Some(DepthStencilState {
                 format: TextureFormat::Depth32Float,
                 depth_write_enabled,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: depth_compare(key),
                 stencil: StencilState {
                     front: StencilFaceState::IGNORE,
                     back: StencilFaceState::IGNORE,
@@ -771,6 +1107,9 @@ pub fn prepare_skinned_meshes(
 #[derive(Component)]
 pub struct MeshViewBindGroup {
     pub value: BindGroup,
+    /// The bind group of the [`Transmissive3d`](bevy_core_pipeline::core_3d::Transmissive3d)
+    /// phase, with the [`ViewTransmissionTexture`] the other phases render to.
+    pub transmissive_value: Option<BindGroup>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -782,71 +1121,193 @@ pub fn queue_mesh_view_bind_groups(
     light_meta: Res<LightMeta>,
     global_light_meta: Res<GlobalLightMeta>,
     view_uniforms: Res<ViewUniforms>,
-    views: Query<(Entity, &ViewShadowBindings, &ViewClusterBindings)>,
+    views: Query<(
+        Entity,
+        &ViewShadowBindings,
+        &ViewClusterBindings,
+        Option<&ViewScreenSpaceAmbientOcclusionTextures>,
+        Option<&ViewScreenSpaceReflectionsTextures>,
+        Option<&ViewTransmissionTexture>,
+        Option<&EnvironmentMapLight>,
+    )>,
     globals_buffer: Res<GlobalsBuffer>,
+    images: Res<RenderAssets<Image>>,
+    fog_meta: Res<FogMeta>,
+    light_textures: Res<LightTextures>,
+    decal_meta: Res<DecalMeta>,
 ) {
-    if let (Some(view_binding), Some(light_binding), Some(point_light_binding), Some(globals)) = (
+    if let (
+        Some(view_binding),
+        Some(light_binding),
+        Some(point_light_binding),
+        Some(globals),
+        Some(fog_binding),
+        Some(decals_binding),
+    ) = (
         view_uniforms.uniforms.binding(),
         light_meta.view_gpu_lights.binding(),
         global_light_meta.gpu_point_lights.binding(),
         globals_buffer.buffer.binding(),
+        fog_meta.gpu_fogs.binding(),
+        decal_meta.gpu_decals.binding(),
     ) {
-        for (entity, view_shadow_bindings, view_cluster_bindings) in &views {
-            let view_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
-                entries: &[
-                    BindGroupEntry {
-                        binding: 0,
-                        resource: view_binding.clone(),
-                    },
-                    BindGroupEntry {
-                        binding: 1,
-                        resource: light_binding.clone(),
-                    },
-                    BindGroupEntry {
-                        binding: 2,
-                        resource: BindingResource::TextureView(
-                            &view_shadow_bindings.point_light_depth_texture_view,
-                        ),
-                    },
-                    BindGroupEntry {
-                        binding: 3,
-                        resource: BindingResource::Sampler(&shadow_pipeline.point_light_sampler),
-                    },
-                    BindGroupEntry {
-                        binding: 4,
-                        resource: BindingResource::TextureView(
-                            &view_shadow_bindings.directional_light_depth_texture_view,
-                        ),
-                    },
-                    BindGroupEntry {
-                        binding: 5,
-                        resource: BindingResource::Sampler(
-                            &shadow_pipeline.directional_light_sampler,
-                        ),
-                    },
-                    BindGroupEntry {
-                        binding: 6,
-                        resource: point_light_binding.clone(),
-                    },
-                    BindGroupEntry {
-                        binding: 7,
-                        resource: view_cluster_bindings.light_index_lists_binding().unwrap(),
-                    },
-                    BindGroupEntry {
-                        binding: 8,
-                        resource: view_cluster_bindings.offsets_and_counts_binding().unwrap(),
-                    },
-                    BindGroupEntry {
-                        binding: 9,
-                        resource: globals.clone(),
-                    },
-                ],
-                label: Some("mesh_view_bind_group"),
-                layout: &mesh_pipeline.view_layout,
-            });
+        for (
+            entity,
+            view_shadow_bindings,
+            view_cluster_bindings,
+            ssao_textures,
+            ssr_textures,
+            transmission_texture,
+            environment_map,
+        ) in &views
+        {
+            // NOTE: The white texture stands for no occlusion in the views without screen space
+            // ambient occlusion
+            let ssao_view = ssao_textures.map_or(
+                &mesh_pipeline.dummy_white_gpu_image.texture_view,
+                |textures| &textures.ambient_occlusion,
+            );
+            // NOTE: The transparent texture stands for no confidence in any reflection in the
+            // views without screen space reflections
+            let ssr_view = ssr_textures.map_or(
+                &mesh_pipeline.dummy_transparent_gpu_image.texture_view,
+                |textures| &textures.reflections,
+            );
+            // NOTE: The black cubemap stands for no environment map light in the views without a
+            // loaded one, which aren't specialized to read it
+            let dummy_environment_map = &mesh_pipeline.dummy_black_cube_gpu_image;
+            let (diffuse_map, specular_map) = match environment_map.map(|environment_map| {
+                (
+                    images.get(&environment_map.diffuse_map),
+                    images.get(&environment_map.specular_map),
+                )
+            }) {
+                Some((Some(diffuse_map), Some(specular_map))) => (diffuse_map, specular_map),
+                _ => (dummy_environment_map, dummy_environment_map),
+            };
+            let create_view_bind_group = |transmission_view, transmission_sampler| {
+                render_device.create_bind_group(&BindGroupDescriptor {
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: view_binding.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: light_binding.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: BindingResource::TextureView(
+                                &view_shadow_bindings.point_light_depth_texture_view,
+                            ),
+                        },
+                        BindGroupEntry {
+                            binding: 3,
+                            resource: BindingResource::Sampler(
+                                &shadow_pipeline.point_light_sampler,
+                            ),
+                        },
+                        BindGroupEntry {
+                            binding: 4,
+                            resource: BindingResource::TextureView(
+                                &view_shadow_bindings.directional_light_depth_texture_view,
+                            ),
+                        },
+                        BindGroupEntry {
+                            binding: 5,
+                            resource: BindingResource::Sampler(
+                                &shadow_pipeline.directional_light_sampler,
+                            ),
+                        },
+                        BindGroupEntry {
+                            binding: 6,
+                            resource: point_light_binding.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 7,
+                            resource: view_cluster_bindings.light_index_lists_binding().unwrap(),
+                        },
+                        BindGroupEntry {
+                            binding: 8,
+                            resource: view_cluster_bindings.offsets_and_counts_binding().unwrap(),
+                        },
+                        BindGroupEntry {
+                            binding: 9,
+                            resource: globals.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 10,
+                            resource: BindingResource::TextureView(ssao_view),
+                        },
+                        BindGroupEntry {
+                            binding: 11,
+                            resource: BindingResource::TextureView(ssr_view),
+                        },
+                        BindGroupEntry {
+                            binding: 12,
+                            resource: BindingResource::TextureView(transmission_view),
+                        },
+                        BindGroupEntry {
+                            binding: 13,
+                            resource: BindingResource::Sampler(transmission_sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 14,
+                            resource: BindingResource::TextureView(&diffuse_map.texture_view),
+                        },
+                        BindGroupEntry {
+                            binding: 15,
+                            resource: BindingResource::TextureView(&specular_map.texture_view),
+                        },
+                        BindGroupEntry {
+                            binding: 16,
+                            resource: BindingResource::Sampler(&specular_map.sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 17,
+                            resource: fog_binding.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 18,
+                            resource: BindingResource::TextureView(
+                                &light_textures.spot_light_textures,
+                            ),
+                        },
+                        BindGroupEntry {
+                            binding: 19,
+                            resource: BindingResource::TextureView(
+                                &light_textures.point_light_textures,
+                            ),
+                        },
+                        BindGroupEntry {
+                            binding: 20,
+                            resource: BindingResource::Sampler(&light_textures.sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 21,
+                            resource: decals_binding.clone(),
+                        },
+                    ],
+                    label: Some("mesh_view_bind_group"),
+                    layout: &mesh_pipeline.view_layout,
+                })
+            };
 
+            // NOTE: The phases rendering to the transmission texture can't read it, so they bind
+            // the transparent texture in its place
+            let dummy_transmission = &mesh_pipeline.dummy_transparent_gpu_image;
             commands.entity(entity).insert(MeshViewBindGroup {
-                value: view_bind_group,
+                value: create_view_bind_group(
+                    &dummy_transmission.texture_view,
+                    &dummy_transmission.sampler,
+                ),
+                transmissive_value: transmission_texture.map(|transmission_texture| {
+                    create_view_bind_group(
+                        &transmission_texture.view,
+                        &transmission_texture.sampler,
+                    )
+                }),
             });
         }
     }
@@ -857,6 +1318,7 @@ impl<const I: usize> EntityRenderCommand for SetMeshViewBindGroup<I> {
     type Param = SQuery<(
         Read<ViewUniformOffset>,
         Read<ViewLightsUniformOffset>,
+        Read<ViewFogUniformOffset>,
         Read<MeshViewBindGroup>,
     )>;
     #[inline]
@@ -866,11 +1328,44 @@ impl<const I: usize> EntityRenderCommand for SetMeshViewBindGroup<I> {
         view_query: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let (view_uniform, view_lights, mesh_view_bind_group) = view_query.get_inner(view).unwrap();
+        let (view_uniform, view_lights, view_fog, mesh_view_bind_group) =
+            view_query.get_inner(view).unwrap();
         pass.set_bind_group(
             I,
             &mesh_view_bind_group.value,
-            &[view_uniform.offset, view_lights.offset],
+            &[view_uniform.offset, view_lights.offset, view_fog.offset],
+        );
+
+        RenderCommandResult::Success
+    }
+}
+
+/// Sets the [`MeshViewBindGroup`] of the transmissive phase, reading the
+/// [`ViewTransmissionTexture`] if the view has one.
+pub struct SetTransmissiveMeshViewBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetTransmissiveMeshViewBindGroup<I> {
+    type Param = SQuery<(
+        Read<ViewUniformOffset>,
+        Read<ViewLightsUniformOffset>,
+        Read<ViewFogUniformOffset>,
+        Read<MeshViewBindGroup>,
+    )>;
+    #[inline]
+    fn render<'w>(
+        view: Entity,
+        _item: Entity,
+        view_query: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let (view_uniform, view_lights, view_fog, mesh_view_bind_group) =
+            view_query.get_inner(view).unwrap();
+        pass.set_bind_group(
+            I,
+            mesh_view_bind_group
+                .transmissive_value
+                .as_ref()
+                .unwrap_or(&mesh_view_bind_group.value),
+            &[view_uniform.offset, view_lights.offset, view_fog.offset],
         );
 
         RenderCommandResult::Success
@@ -944,13 +1439,79 @@ impl EntityRenderCommand for DrawMesh {
     }
 }
 
+/// Draws the instanced batch of the phase item, whose instances are bound to the vertex buffer
+/// at index 1.
+pub struct DrawMeshInstanced;
+impl EntityRenderCommand for DrawMeshInstanced {
+    type Param = (
+        SRes<RenderAssets<Mesh>>,
+        SRes<InstanceBatches>,
+        SQuery<Read<Handle<Mesh>>>,
+    );
+    #[inline]
+    fn render<'w>(
+        view: Entity,
+        item: Entity,
+        (meshes, batches, mesh_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let mesh_handle = mesh_query.get(item).unwrap();
+        let (gpu_mesh, batch) = match (
+            meshes.into_inner().get(mesh_handle),
+            batches.into_inner().get(view, item),
+        ) {
+            (Some(gpu_mesh), Some(batch)) => (gpu_mesh, batch),
+            _ => return RenderCommandResult::Failure,
+        };
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, batch.buffer.slice(..));
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, 0..batch.count);
+            }
+            GpuBufferInfo::NonIndexed { vertex_count } => {
+                pass.draw(0..*vertex_count, 0..batch.count);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::MeshPipelineKey;
+    use super::{depth_compare, MeshPipelineKey};
+    use bevy_render::render_resource::CompareFunction;
+
     #[test]
     fn mesh_key_msaa_samples() {
         for i in [1, 2, 4, 8, 16, 32, 64, 128] {
             assert_eq!(MeshPipelineKey::from_msaa_samples(i).msaa_samples(), i);
         }
     }
+
+    #[test]
+    fn depth_compare_depends_on_prepass() {
+        let key = MeshPipelineKey::from_msaa_samples(4);
+        assert_eq!(depth_compare(key), CompareFunction::Greater);
+        assert_eq!(
+            depth_compare(key | MeshPipelineKey::TRANSPARENT_MAIN_PASS),
+            CompareFunction::Greater
+        );
+        // Only the main passes of views with a depth prepass draw at the prepass depth
+        let key = key | MeshPipelineKey::LOADS_PREPASS_DEPTH;
+        assert_eq!(depth_compare(key), CompareFunction::GreaterEqual);
+        assert_eq!(
+            depth_compare(key | MeshPipelineKey::TRANSPARENT_MAIN_PASS),
+            CompareFunction::GreaterEqual
+        );
+        assert_eq!(
+            depth_compare(key | MeshPipelineKey::DEPTH_PREPASS),
+            CompareFunction::Greater
+        );
+    }
 }
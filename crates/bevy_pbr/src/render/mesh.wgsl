@@ -4,6 +4,8 @@
 // NOTE: Bindings must come before functions that use them!
 #import bevy_pbr::mesh_functions
 
+#import bevy_core_pipeline::oit
+
 struct Vertex {
 #ifdef VERTEX_POSITIONS
     @location(0) position: vec3<f32>,
@@ -24,6 +26,15 @@ struct Vertex {
     @location(5) joint_indices: vec4<u32>,
     @location(6) joint_weights: vec4<f32>,
 #endif
+#ifdef VERTEX_UVS_1
+    @location(7) uv_1: vec2<f32>,
+#endif
+#ifdef MESH_INSTANCED
+    @location(8) instance_model_0: vec4<f32>,
+    @location(9) instance_model_1: vec4<f32>,
+    @location(10) instance_model_2: vec4<f32>,
+    @location(11) instance_model_3: vec4<f32>,
+#endif
 };
 
 struct VertexOutput {
@@ -39,11 +50,21 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 #ifdef SKINNED
     var model = skin_model(vertex.joint_indices, vertex.joint_weights);
     out.world_normal = skin_normals(model, vertex.normal);
+#else
+#ifdef MESH_INSTANCED
+    var model = mat4x4<f32>(
+        vertex.instance_model_0,
+        vertex.instance_model_1,
+        vertex.instance_model_2,
+        vertex.instance_model_3
+    );
+    out.world_normal = mesh_normal_local_to_world_with_model(model, vertex.normal);
 #else
     var model = mesh.model;
     out.world_normal = mesh_normal_local_to_world(vertex.normal);
 #endif
 #endif
+#endif
 
 #ifdef VERTEX_POSITIONS
     out.world_position = mesh_position_local_to_world(model, vec4<f32>(vertex.position, 1.0));
@@ -54,6 +75,10 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     out.uv = vertex.uv;
 #endif
 
+#ifdef VERTEX_UVS_1
+    out.uv_1 = vertex.uv_1;
+#endif
+
 #ifdef VERTEX_TANGENTS
     out.world_tangent = mesh_tangent_local_to_world(model, vertex.tangent);
 #endif
@@ -71,10 +96,25 @@ struct FragmentInput {
 };
 
 @fragment
+#ifdef OIT
+fn fragment(in: FragmentInput) -> OitFragmentOutput {
+#else
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+#endif
 #ifdef VERTEX_COLORS
-    return in.color;
+    let color = in.color;
+#else
+    let color = vec4<f32>(1.0, 0.0, 1.0, 1.0);
+#endif
+#ifdef OIT
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), in.world_position);
+    return oit_fragment_output(color, -view_z);
 #else
-    return vec4<f32>(1.0, 0.0, 1.0, 1.0);
+    return color;
 #endif
 }
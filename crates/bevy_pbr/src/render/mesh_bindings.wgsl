@@ -9,3 +9,6 @@ var<uniform> mesh: Mesh;
 var<uniform> joint_matrices: SkinnedMesh;
 #import bevy_pbr::skinning
 #endif
+#ifdef LIGHTMAP
+#import bevy_pbr::lightmap
+#endif
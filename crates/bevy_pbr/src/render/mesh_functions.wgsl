@@ -32,6 +32,19 @@ fn mesh_normal_local_to_world(vertex_normal: vec3<f32>) -> vec3<f32> {
     );
 }
 
+// Same as mesh_normal_local_to_world, for a model matrix that isn't the one of the mesh uniform,
+// such as the model of an instance. The inverse transpose of a model made of a rotation, a scale
+// and a translation is the model divided by the square of its scale.
+fn mesh_normal_local_to_world_with_model(model: mat4x4<f32>, vertex_normal: vec3<f32>) -> vec3<f32> {
+    let model_3x3 = mat3x3<f32>(model[0].xyz, model[1].xyz, model[2].xyz);
+    let scale_squared = vec3<f32>(
+        dot(model_3x3[0], model_3x3[0]),
+        dot(model_3x3[1], model_3x3[1]),
+        dot(model_3x3[2], model_3x3[2])
+    );
+    return normalize(model_3x3 * (vertex_normal / scale_squared));
+}
+
 // Calculates the sign of the determinant of the 3x3 model matrix based on a
 // mesh flag
 fn sign_determinant_model_3x3() -> f32 {
@@ -3,8 +3,17 @@
 struct Mesh {
     model: mat4x4<f32>,
     inverse_transpose_model: mat4x4<f32>,
+    // The model of the previous frame, or the current one on the first frame the mesh is visible
+    previous_model: mat4x4<f32>,
+    // The rect of the lightmap of the mesh its second UV set maps to, as min.xy and max.xy
+    lightmap_uv_rect: vec4<f32>,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
+    // The RenderLayers of the mesh, as a mask of the lights that illuminate it
+    render_layers: u32,
+    // The progress of the cross-fade of the level of detail of the mesh, 1.0 when it isn't
+    // cross-fading
+    lod_fade: f32,
 };
 
 #ifdef SKINNED
@@ -14,5 +23,7 @@ struct SkinnedMesh {
 #endif
 
 let MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u;
+// Set on the previous level of detail of a cross-fading mesh
+let MESH_FLAGS_LOD_FADE_OUT_BIT: u32 = 2u;
 // 2^31 - if the flag is set, the sign is positive, else it is negative
 let MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT: u32 = 2147483648u;
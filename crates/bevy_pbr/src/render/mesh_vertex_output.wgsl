@@ -11,3 +11,6 @@
 #ifdef VERTEX_COLORS
 @location(4) color: vec4<f32>,
 #endif
+#ifdef VERTEX_UVS_1
+@location(5) uv_1: vec2<f32>,
+#endif
@@ -43,3 +43,37 @@ var<storage> cluster_offsets_and_counts: ClusterOffsetsAndCounts;
 
 @group(0) @binding(9)
 var<uniform> globals: Globals;
+@group(0) @binding(10)
+var screen_space_ambient_occlusion_texture: texture_2d<f32>;
+@group(0) @binding(11)
+var screen_space_reflections_texture: texture_2d<f32>;
+@group(0) @binding(12)
+var view_transmission_texture: texture_2d<f32>;
+@group(0) @binding(13)
+var view_transmission_sampler: sampler;
+@group(0) @binding(14)
+var environment_map_diffuse: texture_cube<f32>;
+@group(0) @binding(15)
+var environment_map_specular: texture_cube<f32>;
+@group(0) @binding(16)
+var environment_map_sampler: sampler;
+@group(0) @binding(17)
+var<uniform> fog: Fog;
+#ifdef NO_ARRAY_TEXTURES_SUPPORT
+@group(0) @binding(18)
+var spot_light_textures: texture_2d<f32>;
+@group(0) @binding(19)
+var point_light_textures: texture_cube<f32>;
+#else
+@group(0) @binding(18)
+var spot_light_textures: texture_2d_array<f32>;
+@group(0) @binding(19)
+var point_light_textures: texture_cube_array<f32>;
+#endif
+@group(0) @binding(20)
+var light_textures_sampler: sampler;
+#ifdef NO_STORAGE_BUFFERS_SUPPORT
+#else
+@group(0) @binding(21)
+var<storage> decals: Decals;
+#endif
@@ -10,23 +10,32 @@ struct View {
     world_position: vec3<f32>,
     // viewport(x_origin, y_origin, width, height)
     viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
 };
 
 struct PointLight {
-    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
+    // For point lights: the rotation of the light, as a quaternion
     // For spot lights: the direction (x,z), spot_scale and spot_offset
     light_custom_data: vec4<f32>,
     color_inverse_square_range: vec4<f32>,
     position_radius: vec4<f32>,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
-    shadow_depth_bias: f32,
-    shadow_normal_bias: f32,
-    spot_light_tan_angle: f32,
+    // The shadow depth bias and shadow normal bias, packed as half precision floats
+    shadow_biases: u32,
+    // For point lights: the fraction of the width of the shadow map faces the light is rendered to
+    // For spot lights: the tangent of the outer angle, divided by the fraction of the width of the
+    // shadow map the light is rendered to
+    shadow_custom_data: f32,
+    // The RenderLayers of the light, as a mask
+    render_layers: u32,
 };
 
 let POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32   = 1u;
 let POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+// The upper bits of the flags are the index of the light texture of the light plus one, 0 without
+let POINT_LIGHT_FLAGS_TEXTURE_INDEX_SHIFT: u32   = 16u;
 
 struct DirectionalLight {
     view_projection: mat4x4<f32>,
@@ -36,6 +45,10 @@ struct DirectionalLight {
     flags: u32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    // The fraction of the width of the shadow map the light is rendered to
+    shadow_map_scale: f32,
+    // The RenderLayers of the light, as a mask
+    render_layers: u32,
 };
 
 let DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
@@ -82,8 +95,21 @@ struct ClusterLightIndexLists {
     data: array<u32>,
 };
 struct ClusterOffsetsAndCounts {
+    // the offset, the point light count, the spot light count and the decal count
     data: array<vec4<u32>>,
 };
+struct Decal {
+    // Transforms the world positions to the box of the decal, the unit cube around the origin
+    local_from_world: mat4x4<f32>,
+    // The layer of the image of the decal in the spot light textures plus one, 0 while it isn't
+    // loaded
+    texture_index: u32,
+    // The RenderLayers of the decal, as a mask of the meshes it covers
+    render_layers: u32,
+};
+struct Decals {
+    data: array<Decal>,
+};
 #endif
 
 struct Globals {
@@ -96,3 +122,24 @@ struct Globals {
     // It wraps to zero when it reaches the maximum value of a u32.
     frame_count: u32,
 }
+
+struct Fog {
+    base_color: vec4<f32>,
+    directional_light_color: vec4<f32>,
+    directional_light_exponent: f32,
+    mode: u32,
+    // The distances of linear fog
+    start: f32,
+    end: f32,
+    // The density of exponential fogs
+    density: f32,
+    base_height: f32,
+    // 0.0 for a fog as thick at every height
+    height_falloff: f32,
+};
+
+// NOTE: These must match the constants in bevy_pbr/src/render/fog.rs!
+let FOG_MODE_OFF: u32                   = 0u;
+let FOG_MODE_LINEAR: u32                = 1u;
+let FOG_MODE_EXPONENTIAL: u32           = 2u;
+let FOG_MODE_EXPONENTIAL_SQUARED: u32   = 3u;
@@ -1,5 +1,7 @@
+mod fog;
 mod light;
 mod mesh;
 
+pub use fog::*;
 pub use light::*;
 pub use mesh::*;
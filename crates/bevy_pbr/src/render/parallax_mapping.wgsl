@@ -0,0 +1,70 @@
+#define_import_path bevy_pbr::parallax_mapping
+
+fn sample_depth_map(uv: vec2<f32>) -> f32 {
+    // NOTE: textureSampleLevel rather than textureSample, as implicit derivatives aren't allowed
+    // in the non-uniform control flow of the loops below
+    return textureSampleLevel(depth_map_texture, depth_map_sampler, uv, 0.0).r;
+}
+
+// Offsets `uv` to where the view ray hits the relief of the depth map, with steep parallax
+// mapping refined by parallax occlusion mapping, or relief mapping with RELIEF_MAPPING.
+// `Vt` is the direction from the view to the fragment, in tangent space.
+// See <https://en.wikipedia.org/wiki/Parallax_mapping>
+fn parallaxed_uv(
+    depth_scale: f32,
+    max_layer_count: f32,
+    max_steps: u32,
+    original_uv: vec2<f32>,
+    Vt: vec3<f32>,
+) -> vec2<f32> {
+    if (max_layer_count < 1.0) {
+        return original_uv;
+    }
+    var uv = original_uv;
+
+    // Steep parallax mapping: step along the ray through layers of the depth map until it is
+    // below the relief, with more layers at grazing angles where the ray crosses more texels
+    let view_steepness = abs(Vt.z);
+    // NOTE: Mixing down to 1.0 rather than 0.0 avoids dividing by zero on surfaces seen edge on
+    let layer_count = mix(max_layer_count, 1.0, view_steepness);
+    let layer_depth = 1.0 / layer_count;
+    var delta_uv = depth_scale * layer_depth * Vt.xy * vec2<f32>(1.0, -1.0) / view_steepness;
+
+    var current_layer_depth = 0.0;
+    var texture_depth = sample_depth_map(uv);
+    for (var i: i32 = 0; texture_depth > current_layer_depth && i <= i32(layer_count); i = i + 1) {
+        current_layer_depth = current_layer_depth + layer_depth;
+        uv = uv + delta_uv;
+        texture_depth = sample_depth_map(uv);
+    }
+
+#ifdef RELIEF_MAPPING
+    // Relief mapping: binary search the relief between the last two layers
+    delta_uv = delta_uv * 0.5;
+    var delta_depth = 0.5 * layer_depth;
+    uv = uv - delta_uv;
+    current_layer_depth = current_layer_depth - delta_depth;
+    for (var i: u32 = 0u; i < max_steps; i = i + 1u) {
+        texture_depth = sample_depth_map(uv);
+        delta_uv = delta_uv * 0.5;
+        delta_depth = delta_depth * 0.5;
+        if (texture_depth > current_layer_depth) {
+            uv = uv + delta_uv;
+            current_layer_depth = current_layer_depth + delta_depth;
+        } else {
+            uv = uv - delta_uv;
+            current_layer_depth = current_layer_depth - delta_depth;
+        }
+    }
+#else
+    // Parallax occlusion mapping: interpolate between the last two layers by how far they are
+    // from the relief, with a single extra sample
+    let previous_uv = uv - delta_uv;
+    let next_depth = texture_depth - current_layer_depth;
+    let previous_depth = sample_depth_map(previous_uv) - current_layer_depth + layer_depth;
+    let weight = next_depth / (next_depth - previous_depth);
+    uv = mix(uv, previous_uv, weight);
+#endif
+
+    return uv;
+}
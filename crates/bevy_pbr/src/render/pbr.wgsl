@@ -7,91 +7,31 @@
 #import bevy_pbr::lighting
 #import bevy_pbr::shadows
 #import bevy_pbr::pbr_functions
+#import bevy_pbr::parallax_mapping
+#import bevy_pbr::fog
+#import bevy_pbr::decal
+#import bevy_pbr::pbr_fragment
 
-struct FragmentInput {
-    @builtin(front_facing) is_front: bool,
-    @builtin(position) frag_coord: vec4<f32>,
-    #import bevy_pbr::mesh_vertex_output
-};
+#import bevy_core_pipeline::oit
 
 @fragment
+#ifdef OIT
+fn fragment(in: FragmentInput) -> OitFragmentOutput {
+#else
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
-    var output_color: vec4<f32> = material.base_color;
-#ifdef VERTEX_COLORS
-    output_color = output_color * in.color;
 #endif
-#ifdef VERTEX_UVS
-    if ((material.flags & STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
-        output_color = output_color * textureSample(base_color_texture, base_color_sampler, in.uv);
-    }
-#endif
-
-    // NOTE: Unlit bit not set means == 0 is true, so the true case is if lit
-    if ((material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
-        // Prepare a 'processed' StandardMaterial by sampling all textures to resolve
-        // the material members
-        var pbr_input: PbrInput;
-
-        pbr_input.material.base_color = output_color;
-        pbr_input.material.reflectance = material.reflectance;
-        pbr_input.material.flags = material.flags;
-        pbr_input.material.alpha_cutoff = material.alpha_cutoff;
-
-        // TODO use .a for exposure compensation in HDR
-        var emissive: vec4<f32> = material.emissive;
-#ifdef VERTEX_UVS
-        if ((material.flags & STANDARD_MATERIAL_FLAGS_EMISSIVE_TEXTURE_BIT) != 0u) {
-            emissive = vec4<f32>(emissive.rgb * textureSample(emissive_texture, emissive_sampler, in.uv).rgb, 1.0);
-        }
-#endif
-        pbr_input.material.emissive = emissive;
-
-        var metallic: f32 = material.metallic;
-        var perceptual_roughness: f32 = material.perceptual_roughness;
-#ifdef VERTEX_UVS
-        if ((material.flags & STANDARD_MATERIAL_FLAGS_METALLIC_ROUGHNESS_TEXTURE_BIT) != 0u) {
-            let metallic_roughness = textureSample(metallic_roughness_texture, metallic_roughness_sampler, in.uv);
-            // Sampling from GLTF standard channels for now
-            metallic = metallic * metallic_roughness.b;
-            perceptual_roughness = perceptual_roughness * metallic_roughness.g;
-        }
-#endif
-        pbr_input.material.metallic = metallic;
-        pbr_input.material.perceptual_roughness = perceptual_roughness;
-
-        var occlusion: f32 = 1.0;
-#ifdef VERTEX_UVS
-        if ((material.flags & STANDARD_MATERIAL_FLAGS_OCCLUSION_TEXTURE_BIT) != 0u) {
-            occlusion = textureSample(occlusion_texture, occlusion_sampler, in.uv).r;
-        }
-#endif
-        pbr_input.occlusion = occlusion;
-
-        pbr_input.frag_coord = in.frag_coord;
-        pbr_input.world_position = in.world_position;
-        pbr_input.world_normal = in.world_normal;
-
-        pbr_input.is_orthographic = view.projection[3].w == 1.0;
-
-        pbr_input.N = prepare_normal(
-            material.flags,
-            in.world_normal,
-#ifdef VERTEX_TANGENTS
-#ifdef STANDARDMATERIAL_NORMAL_MAP
-            in.world_tangent,
-#endif
-#endif
-#ifdef VERTEX_UVS
-            in.uv,
-#endif
-            in.is_front,
-        );
-        pbr_input.V = calculate_view(in.world_position, pbr_input.is_orthographic);
-
-        output_color = tone_mapping(pbr(pbr_input));
-    } else {
-        output_color = alpha_discard(material, output_color);
-    }
-
+    let pbr_input = pbr_input_from_standard_material(in);
+    let output_color = pbr_output_color(pbr_input);
+
+#ifdef OIT
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), in.world_position);
+    return oit_fragment_output(output_color, -view_z);
+#else
     return output_color;
+#endif
 }
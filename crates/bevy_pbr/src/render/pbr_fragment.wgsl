@@ -0,0 +1,142 @@
+#define_import_path bevy_pbr::pbr_fragment
+
+// The input of the fragment shader of the StandardMaterial, and of the materials extending it
+struct FragmentInput {
+    @builtin(front_facing) is_front: bool,
+    @builtin(position) frag_coord: vec4<f32>,
+    #import bevy_pbr::mesh_vertex_output
+};
+
+// Prepares a 'processed' StandardMaterial by sampling all textures to resolve the material
+// members, and blending the decals over its base color. The fragment shaders of the
+// MaterialExtensions can modify the returned PbrInput before passing it to pbr_output_color.
+fn pbr_input_from_standard_material(in: FragmentInput) -> PbrInput {
+    var pbr_input: PbrInput = pbr_input_new();
+    pbr_input.material = material;
+
+    let is_orthographic = view.projection[3].w == 1.0;
+#ifdef VERTEX_UVS
+    var uv = in.uv;
+#ifdef VERTEX_TANGENTS
+#ifdef STANDARDMATERIAL_DEPTH_MAP
+    // Offsets every texture of the material to where the view ray hits the relief of its depth map
+    let N = in.world_normal;
+    let T = in.world_tangent.xyz;
+    let B = in.world_tangent.w * cross(N, T);
+    let V = calculate_view(in.world_position, is_orthographic);
+    uv = parallaxed_uv(
+        material.parallax_depth_scale,
+        material.max_parallax_layer_count,
+        material.max_relief_mapping_search_steps,
+        uv,
+        -vec3<f32>(dot(V, T), dot(V, B), dot(V, N)),
+    );
+#endif
+#endif
+#endif
+
+    var base_color: vec4<f32> = material.base_color;
+#ifdef VERTEX_COLORS
+    base_color = base_color * in.color;
+#endif
+#ifdef VERTEX_UVS
+    if ((material.flags & STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
+        base_color = base_color * textureSample(base_color_texture, base_color_sampler, uv);
+    }
+#endif
+
+    // The decals only cover the opaque and alpha masked surfaces
+    if ((material.flags & (STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE | STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK)) != 0u) {
+        base_color = apply_decals(
+            base_color,
+            in.frag_coord,
+            in.world_position,
+            in.world_normal,
+            is_orthographic,
+        );
+    }
+    pbr_input.material.base_color = base_color;
+
+    pbr_input.frag_coord = in.frag_coord;
+    pbr_input.world_position = in.world_position;
+    pbr_input.world_normal = in.world_normal;
+
+    pbr_input.is_orthographic = is_orthographic;
+
+    // NOTE: Unlit bit not set means == 0 is true, so the true case is if lit
+    if ((material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
+        // TODO use .a for exposure compensation in HDR
+        var emissive: vec4<f32> = material.emissive;
+#ifdef VERTEX_UVS
+        if ((material.flags & STANDARD_MATERIAL_FLAGS_EMISSIVE_TEXTURE_BIT) != 0u) {
+            emissive = vec4<f32>(emissive.rgb * textureSample(emissive_texture, emissive_sampler, uv).rgb, 1.0);
+        }
+#endif
+        pbr_input.material.emissive = emissive;
+
+        var metallic: f32 = material.metallic;
+        var perceptual_roughness: f32 = material.perceptual_roughness;
+#ifdef VERTEX_UVS
+        if ((material.flags & STANDARD_MATERIAL_FLAGS_METALLIC_ROUGHNESS_TEXTURE_BIT) != 0u) {
+            let metallic_roughness = textureSample(metallic_roughness_texture, metallic_roughness_sampler, uv);
+            // Sampling from GLTF standard channels for now
+            metallic = metallic * metallic_roughness.b;
+            perceptual_roughness = perceptual_roughness * metallic_roughness.g;
+        }
+#endif
+        pbr_input.material.metallic = metallic;
+        pbr_input.material.perceptual_roughness = perceptual_roughness;
+
+        var occlusion: f32 = 1.0;
+#ifdef VERTEX_UVS
+        if ((material.flags & STANDARD_MATERIAL_FLAGS_OCCLUSION_TEXTURE_BIT) != 0u) {
+            occlusion = textureSample(occlusion_texture, occlusion_sampler, uv).r;
+        }
+#endif
+        pbr_input.occlusion = occlusion;
+
+#ifdef LIGHTMAP
+#ifdef VERTEX_UVS_1
+        pbr_input.lightmap_light = lightmap(in.uv_1, material.lightmap_exposure);
+#endif
+#endif
+
+        pbr_input.N = prepare_normal(
+            material.flags,
+            in.world_normal,
+#ifdef VERTEX_TANGENTS
+#ifdef STANDARDMATERIAL_NORMAL_MAP
+            in.world_tangent,
+#endif
+#endif
+#ifdef VERTEX_UVS
+            uv,
+#endif
+            in.is_front,
+        );
+        pbr_input.V = calculate_view(in.world_position, is_orthographic);
+    }
+
+    return pbr_input;
+}
+
+// Lights the PbrInput and applies the fog of the view, or only discards its alpha with the unlit
+// flag of its material
+fn pbr_output_color(pbr_input: PbrInput) -> vec4<f32> {
+    lod_fade_discard(pbr_input.frag_coord);
+
+    var output_color: vec4<f32>;
+    // NOTE: Unlit bit not set means == 0 is true, so the true case is if lit
+    if ((pbr_input.material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
+        output_color = tone_mapping(pbr(pbr_input));
+    } else {
+        output_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+    }
+
+    // NOTE: The fog is applied after tone mapping, so that its color is displayed as is
+    if (fog.mode != FOG_MODE_OFF) {
+        output_color = apply_fog(output_color, pbr_input.world_position.xyz, view.world_position.xyz);
+    }
+
+    return output_color;
+}
@@ -1,5 +1,8 @@
 #define_import_path bevy_pbr::pbr_functions
 
+#import bevy_pbr::environment_map
+#import bevy_pbr::light_texture
+
 fn alpha_discard(material: StandardMaterial, output_color: vec4<f32>) -> vec4<f32>{
     var color = output_color;
     if ((material.flags & STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE) != 0u) {
@@ -20,6 +23,19 @@ fn alpha_discard(material: StandardMaterial, output_color: vec4<f32>) -> vec4<f3
 
 // NOTE: This ensures that the world_normal is normalized and if
 // vertex tangents and normal maps then normal mapping may be applied.
+// Discards the fragments of a mesh cross-fading between levels of detail with a dithering
+// pattern, the previous level keeping the fragments the current one discards
+fn lod_fade_discard(frag_coord: vec4<f32>) {
+    if (mesh.lod_fade >= 1.0) {
+        return;
+    }
+    let noise = interleaved_gradient_noise(frag_coord.xy, 0u);
+    let fading_out = (mesh.flags & MESH_FLAGS_LOD_FADE_OUT_BIT) != 0u;
+    if ((noise < mesh.lod_fade) == fading_out) {
+        discard;
+    }
+}
+
 fn prepare_normal(
     standard_material_flags: u32,
     world_normal: vec3<f32>,
@@ -124,6 +140,9 @@ struct PbrInput {
     // view world position
     V: vec3<f32>,
     is_orthographic: bool,
+    // The light baked in the lightmap of the mesh, which replaces the diffuse ambient light with
+    // the LIGHTMAP shader def
+    lightmap_light: vec3<f32>,
 };
 
 // Creates a PbrInput with default values
@@ -142,6 +161,8 @@ fn pbr_input_new() -> PbrInput {
     pbr_input.N = vec3<f32>(0.0, 0.0, 1.0);
     pbr_input.V = vec3<f32>(1.0, 0.0, 0.0);
 
+    pbr_input.lightmap_light = vec3<f32>(0.0);
+
     return pbr_input;
 }
 
@@ -159,6 +180,7 @@ fn pbr(
     let roughness = perceptualRoughnessToRoughness(perceptual_roughness);
 
     let occlusion = in.occlusion;
+    let specular_transmission = in.material.specular_transmission;
 
     output_color = alpha_discard(in.material, output_color);
 
@@ -168,10 +190,12 @@ fn pbr(
     // Remapping [0,1] reflectance to F0
     // See https://google.github.io/filament/Filament.html#materialsystem/parameterization/remapping
     let reflectance = in.material.reflectance;
-    let F0 = 0.16 * reflectance * reflectance * (1.0 - metallic) + output_color.rgb * metallic;
+    let F0 = 0.16 * reflectance * reflectance * in.material.specular_tint.rgb * (1.0 - metallic)
+        + output_color.rgb * metallic;
 
-    // Diffuse strength inversely related to metallicity
-    let diffuse_color = output_color.rgb * (1.0 - metallic);
+    // Diffuse strength inversely related to metallicity, and to the light transmitted through
+    // the surface
+    let diffuse_color = output_color.rgb * (1.0 - metallic) * (1.0 - specular_transmission);
 
     let R = reflect(-in.V, in.N);
 
@@ -191,12 +215,16 @@ fn pbr(
     for (var i: u32 = offset_and_counts[0]; i < offset_and_counts[0] + offset_and_counts[1]; i = i + 1u) {
         let light_id = get_light_id(i);
         let light = point_lights.data[light_id];
+        if ((light.render_layers & mesh.render_layers) == 0u) {
+            continue;
+        }
         var shadow: f32 = 1.0;
         if ((mesh.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (light.flags & POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = fetch_point_shadow(light_id, in.world_position, in.world_normal);
         }
-        let light_contrib = point_light(in.world_position.xyz, light, roughness, NdotV, in.N, in.V, R, F0, diffuse_color);
+        let light_contrib = point_light(in.world_position.xyz, light, roughness, NdotV, in.N, in.V, R, F0, diffuse_color)
+            * point_light_texture(light_id, in.world_position.xyz);
         light_accum = light_accum + light_contrib * shadow;
     }
 
@@ -204,18 +232,25 @@ fn pbr(
     for (var i: u32 = offset_and_counts[0] + offset_and_counts[1]; i < offset_and_counts[0] + offset_and_counts[1] + offset_and_counts[2]; i = i + 1u) {
         let light_id = get_light_id(i);
         let light = point_lights.data[light_id];
+        if ((light.render_layers & mesh.render_layers) == 0u) {
+            continue;
+        }
         var shadow: f32 = 1.0;
         if ((mesh.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (light.flags & POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = fetch_spot_shadow(light_id, in.world_position, in.world_normal);
         }
-        let light_contrib = spot_light(in.world_position.xyz, light, roughness, NdotV, in.N, in.V, R, F0, diffuse_color);
+        let light_contrib = spot_light(in.world_position.xyz, light, roughness, NdotV, in.N, in.V, R, F0, diffuse_color)
+            * spot_light_texture(light_id, in.world_position.xyz);
         light_accum = light_accum + light_contrib * shadow;
     }
 
     let n_directional_lights = lights.n_directional_lights;
     for (var i: u32 = 0u; i < n_directional_lights; i = i + 1u) {
         let light = lights.directional_lights[i];
+        if ((light.render_layers & mesh.render_layers) == 0u) {
+            continue;
+        }
         var shadow: f32 = 1.0;
         if ((mesh.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (light.flags & DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
@@ -228,9 +263,73 @@ fn pbr(
     let diffuse_ambient = EnvBRDFApprox(diffuse_color, 1.0, NdotV);
     let specular_ambient = EnvBRDFApprox(F0, perceptual_roughness, NdotV);
 
+    // The screen space ambient occlusion is estimated from the depth prepass, which only has the
+    // opaque meshes, without the transmissive ones
+    let in_prepass = (in.material.flags & STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE) != 0u
+        && specular_transmission <= 0.0;
+    var ambient_occlusion = occlusion;
+    if (in_prepass) {
+        // NOTE: Clamping the coordinates reads the 1x1 white texture bound to the views without
+        // screen space ambient occlusion
+        let ssao_size = vec2<i32>(textureDimensions(screen_space_ambient_occlusion_texture));
+        let ssao_coords = min(vec2<i32>(in.frag_coord.xy), ssao_size - 1);
+        ambient_occlusion = ambient_occlusion
+            * textureLoad(screen_space_ambient_occlusion_texture, ssao_coords, 0).r;
+    }
+
+#ifdef LIGHTMAP
+    var ambient_diffuse_light = in.lightmap_light * ambient_occlusion;
+#else
+    var ambient_diffuse_light = lights.ambient_color.rgb * ambient_occlusion;
+#endif
+    var ambient_specular_light = lights.ambient_color.rgb * ambient_occlusion;
+#ifdef ENVIRONMENT_MAP
+    // The environment map lights the surface on top of the ambient light
+    let environment_light = environment_map_light(perceptual_roughness, in.N, R);
+    ambient_diffuse_light = ambient_diffuse_light + environment_light.diffuse * ambient_occlusion;
+    ambient_specular_light = ambient_specular_light + environment_light.specular * ambient_occlusion;
+#endif
+
+    // The screen space reflections replace the ambient specular light of the smooth opaque
+    // surfaces, as far as the trace is confident in them
+    if (in_prepass
+            && (in.material.flags & STANDARD_MATERIAL_FLAGS_NO_SCREEN_SPACE_REFLECTIONS) == 0u) {
+        // NOTE: Clamping the coordinates reads the 1x1 transparent texture bound to the views
+        // without screen space reflections
+        let ssr_size = vec2<i32>(textureDimensions(screen_space_reflections_texture));
+        let ssr_coords = min(vec2<i32>(in.frag_coord.xy), ssr_size - 1);
+        let reflection = textureLoad(screen_space_reflections_texture, ssr_coords, 0);
+        let smoothness = 1.0 - smoothstep(0.2, 0.5, perceptual_roughness);
+        ambient_specular_light = mix(ambient_specular_light, reflection.rgb, reflection.a * smoothness);
+    }
+
+    // The specular transmission lets the light of the opaque scene behind the surface through it,
+    // offset by its refraction over its thickness and tinted by its base color. Blended materials
+    // already show what is behind them.
+    var transmitted_light = vec3<f32>(0.0);
+    if (specular_transmission > 0.0
+            && (in.material.flags & STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND) == 0u) {
+        // Snell's law, for the view direction entering the surface
+        let eta = 1.0 / in.material.ior;
+        let cos_incident = dot(in.N, in.V);
+        let k = max(1.0 - eta * eta * (1.0 - cos_incident * cos_incident), 0.0);
+        let refracted = (eta * cos_incident - sqrt(k)) * in.N - eta * in.V;
+        let exit_position = in.world_position.xyz + refracted * in.material.thickness;
+        let exit_clip = view.view_proj * vec4<f32>(exit_position, 1.0);
+        let exit_ndc = exit_clip.xy / max(exit_clip.w, 0.0001);
+        let exit_uv = clamp(vec2<f32>(exit_ndc.x * 0.5 + 0.5, 0.5 - exit_ndc.y * 0.5), vec2<f32>(0.0), vec2<f32>(1.0));
+        let transmission_size = vec2<f32>(textureDimensions(view_transmission_texture));
+        let transmission_uv = (view.viewport.xy + exit_uv * view.viewport.zw) / transmission_size;
+        let background = textureSampleLevel(view_transmission_texture, view_transmission_sampler, transmission_uv, 0.0).rgb;
+        transmitted_light = background * output_color.rgb * (1.0 - metallic) * specular_transmission
+            * (vec3<f32>(1.0) - specular_ambient);
+    }
+
     output_color = vec4<f32>(
         light_accum +
-            (diffuse_ambient + specular_ambient) * lights.ambient_color.rgb * occlusion +
+            diffuse_ambient * ambient_diffuse_light +
+            specular_ambient * ambient_specular_light +
+            transmitted_light +
             emissive.rgb * output_color.a,
         output_color.a);
 
@@ -3,12 +3,20 @@
 struct StandardMaterial {
     base_color: vec4<f32>,
     emissive: vec4<f32>,
+    specular_tint: vec4<f32>,
     perceptual_roughness: f32,
     metallic: f32,
     reflectance: f32,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     alpha_cutoff: f32,
+    specular_transmission: f32,
+    thickness: f32,
+    ior: f32,
+    parallax_depth_scale: f32,
+    max_parallax_layer_count: f32,
+    max_relief_mapping_search_steps: u32,
+    lightmap_exposure: f32,
 };
 
 let STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT: u32         = 1u;
@@ -22,6 +30,7 @@ let STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK: u32                = 128u;
 let STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND: u32               = 256u;
 let STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_NORMAL_MAP: u32       = 512u;
 let STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y: u32              = 1024u;
+let STANDARD_MATERIAL_FLAGS_NO_SCREEN_SPACE_REFLECTIONS: u32    = 2048u;
 
 // Creates a StandardMaterial with default values
 fn standard_material_new() -> StandardMaterial {
@@ -30,11 +39,19 @@ fn standard_material_new() -> StandardMaterial {
     // NOTE: Keep in-sync with src/pbr_material.rs!
     material.base_color = vec4<f32>(1.0, 1.0, 1.0, 1.0);
     material.emissive = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    material.specular_tint = vec4<f32>(1.0, 1.0, 1.0, 1.0);
     material.perceptual_roughness = 0.089;
     material.metallic = 0.01;
     material.reflectance = 0.5;
     material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE;
     material.alpha_cutoff = 0.5;
+    material.specular_transmission = 0.0;
+    material.thickness = 0.0;
+    material.ior = 1.5;
+    material.parallax_depth_scale = 0.1;
+    material.max_parallax_layer_count = 16.0;
+    material.max_relief_mapping_search_steps = 0u;
+    material.lightmap_exposure = 1.0;
 
     return material;
 }
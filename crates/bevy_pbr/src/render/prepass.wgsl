@@ -0,0 +1,25 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+
+struct FragmentInput {
+    @location(0) world_position: vec4<f32>,
+};
+
+// Returns the motion of the fragment since the previous frame in UV coordinates, without the
+// motion of the camera: both positions are projected with the previous view
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec2<f32> {
+#ifdef MESH_INSTANCED
+    // NOTE: The previous models of instances aren't kept
+    return vec2<f32>(0.0);
+#else
+    // The transpose of the inverse transpose of the model is its inverse
+    let local_position = transpose(mesh.inverse_transpose_model) * in.world_position;
+    let previous_world_position = mesh.previous_model * local_position;
+    let clip = view.previous_view_proj * in.world_position;
+    let previous_clip = view.previous_view_proj * previous_world_position;
+    let motion = clip.xy / clip.w - previous_clip.xy / previous_clip.w;
+    // Clip space goes up while UVs go down
+    return motion * vec2<f32>(0.5, -0.5);
+#endif
+}
@@ -1,7 +1,97 @@
 #define_import_path bevy_pbr::shadows
 
+// Interleaved gradient noise, from "Next Generation Post Processing in Call of Duty: Advanced
+// Warfare" by Jorge Jimenez, shifted every frame
+fn interleaved_gradient_noise(pixel_coordinates: vec2<f32>, frame: u32) -> f32 {
+    let xy = pixel_coordinates + 5.588238 * f32(frame % 64u);
+    return fract(52.9829189 * fract(0.06711056 * xy.x + 0.00583715 * xy.y));
+}
+
+// A single bilinear comparison of the 2x2 texels of the directional shadow maps around `uv`
+fn sample_directional_shadow_map_hardware(uv: vec2<f32>, depth: f32, array_index: i32) -> f32 {
+    // NOTE: Due to non-uniform control flow in the callers, we must use the level variant of the
+    // texture sampler to avoid use of implicit derivatives causing possible undefined behavior.
+#ifdef NO_ARRAY_TEXTURES_SUPPORT
+    return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, uv, depth);
+#else
+    return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, uv, array_index, depth);
+#endif
+}
+
+// A 13 texel wide Gaussian filter in 9 bilinear comparisons, from "Shadow Mapping Summary - Part 1"
+// by Ignacio Castaño
+fn sample_directional_shadow_map_gaussian(uv: vec2<f32>, depth: f32, array_index: i32) -> f32 {
+    let shadow_map_size = vec2<f32>(textureDimensions(directional_shadow_textures));
+    let texel = uv * shadow_map_size;
+    var base_texel = floor(texel + 0.5);
+    let s = texel.x + 0.5 - base_texel.x;
+    let t = texel.y + 0.5 - base_texel.y;
+    base_texel = base_texel - 0.5;
+
+    // The weights and offsets in texels of the bilinear comparisons along each axis
+    var u_weights = vec3<f32>(4.0 - 3.0 * s, 7.0, 1.0 + 3.0 * s);
+    var u_offsets = vec3<f32>(
+        (3.0 - 2.0 * s) / u_weights.x - 2.0,
+        (3.0 + s) / u_weights.y,
+        s / u_weights.z + 2.0
+    );
+    var v_weights = vec3<f32>(4.0 - 3.0 * t, 7.0, 1.0 + 3.0 * t);
+    var v_offsets = vec3<f32>(
+        (3.0 - 2.0 * t) / v_weights.x - 2.0,
+        (3.0 + t) / v_weights.y,
+        t / v_weights.z + 2.0
+    );
+
+    var sum = 0.0;
+    for (var i = 0; i < 3; i = i + 1) {
+        for (var j = 0; j < 3; j = j + 1) {
+            let sample_uv = (base_texel + vec2<f32>(u_offsets[i], v_offsets[j])) / shadow_map_size;
+            sum = sum + u_weights[i] * v_weights[j]
+                * sample_directional_shadow_map_hardware(sample_uv, depth, array_index);
+        }
+    }
+    // The weights along each axis add up to 12
+    return sum / 144.0;
+}
+
+// 8 bilinear comparisons in a spiral 2 texels wide, rotated by a noise changing with each texel
+// and frame, from "Next Generation Post Processing in Call of Duty: Advanced Warfare" by Jorge
+// Jimenez
+fn sample_directional_shadow_map_temporal(uv: vec2<f32>, depth: f32, array_index: i32) -> f32 {
+    let shadow_map_size = vec2<f32>(textureDimensions(directional_shadow_textures));
+    let rotation = 2.0 * PI * interleaved_gradient_noise(uv * shadow_map_size, globals.frame_count);
+
+    var sum = 0.0;
+    for (var i = 0u; i < 8u; i = i + 1u) {
+        // The spiral turns 225 degrees and moves 1/8 closer to the centre every sample
+        let angle = rotation + PI * (0.75 - 1.25 * f32(i));
+        let radius = 2.0 * (1.0 - f32(i) / 8.0);
+        let offset = radius * vec2<f32>(cos(angle), sin(angle));
+        sum = sum + sample_directional_shadow_map_hardware(
+            uv + offset / shadow_map_size,
+            depth,
+            array_index
+        );
+    }
+    return sum / 8.0;
+}
+
+// Filters the directional shadow maps with the ShadowFilteringMethod of the view
+fn sample_directional_shadow_map(uv: vec2<f32>, depth: f32, array_index: i32) -> f32 {
+#ifdef SHADOW_FILTER_METHOD_GAUSSIAN
+    return sample_directional_shadow_map_gaussian(uv, depth, array_index);
+#else
+#ifdef SHADOW_FILTER_METHOD_TEMPORAL
+    return sample_directional_shadow_map_temporal(uv, depth, array_index);
+#else
+    return sample_directional_shadow_map_hardware(uv, depth, array_index);
+#endif
+#endif
+}
+
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = point_lights.data[light_id];
+    let shadow_biases = unpack2x16float(light.shadow_biases);
 
     // because the shadow maps align with the axes and the frustum planes are at 45 degrees
     // we can get the worldspace depth by taking the largest absolute axis
@@ -12,8 +102,8 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     // The normal bias here is already scaled by the texel size at 1 world unit from the light.
     // The texel size increases proportionally with distance from the light so multiplying by
     // distance to light scales the normal bias to the texel size at the fragment distance.
-    let normal_offset = light.shadow_normal_bias * distance_to_light * surface_normal.xyz;
-    let depth_offset = light.shadow_depth_bias * normalize(surface_to_light.xyz);
+    let normal_offset = shadow_biases.y * distance_to_light * surface_normal.xyz;
+    let depth_offset = shadow_biases.x * normalize(surface_to_light.xyz);
     let offset_position = frag_position.xyz + normal_offset + depth_offset;
 
     // similar largest-absolute-axis trick as above, but now with the offset fragment position
@@ -21,12 +111,19 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     let abs_position_ls = abs(frag_ls);
     let major_axis_magnitude = max(abs_position_ls.x, max(abs_position_ls.y, abs_position_ls.z));
 
-    // NOTE: These simplifications come from multiplying:
-    // projection * vec4(0, 0, -major_axis_magnitude, 1.0)
-    // and keeping only the terms that have any impact on the depth.
-    // Projection-agnostic approach:
-    let zw = -major_axis_magnitude * light.light_custom_data.xy + light.light_custom_data.zw;
-    let depth = zw.x / zw.y;
+    // The light is rendered to the centre of the faces of the shadow map, the minor axes of the
+    // direction are scaled to sample there
+    let major_axis = select(
+        vec3<f32>(0.0),
+        frag_ls,
+        abs_position_ls == vec3<f32>(major_axis_magnitude)
+    );
+    let shadow_direction = major_axis + (frag_ls - major_axis) * light.shadow_custom_data;
+
+    // NOTE: This simplification comes from multiplying the infinite reverse perspective
+    // projection of the faces by vec4(0, 0, -major_axis_magnitude, 1.0)
+    // 0.1 must match POINT_LIGHT_NEAR_Z
+    let depth = 0.1 / major_axis_magnitude;
 
     // do the lookup, using HW PCF and comparison
     // NOTE: Due to the non-uniform control flow above, we must use the Level variant of
@@ -35,14 +132,15 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     // mip-mapping functionality. The shadow maps have no mipmaps so Level just samples
     // from LOD 0.
 #ifdef NO_ARRAY_TEXTURES_SUPPORT
-    return textureSampleCompare(point_shadow_textures, point_shadow_textures_sampler, frag_ls, depth);
+    return textureSampleCompare(point_shadow_textures, point_shadow_textures_sampler, shadow_direction, depth);
 #else
-    return textureSampleCompareLevel(point_shadow_textures, point_shadow_textures_sampler, frag_ls, i32(light_id), depth);
+    return textureSampleCompareLevel(point_shadow_textures, point_shadow_textures_sampler, shadow_direction, i32(light_id), depth);
 #endif
 }
 
 fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = point_lights.data[light_id];
+    let shadow_biases = unpack2x16float(light.shadow_biases);
 
     let surface_to_light = light.position_radius.xyz - frag_position.xyz;
 
@@ -59,8 +157,8 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
     let distance_to_light = dot(fwd, surface_to_light);
     let offset_position = 
         -surface_to_light 
-        + (light.shadow_depth_bias * normalize(surface_to_light)) 
-        + (surface_normal.xyz * light.shadow_normal_bias) * distance_to_light;
+        + (shadow_biases.x * normalize(surface_to_light)) 
+        + (surface_normal.xyz * shadow_biases.y) * distance_to_light;
 
     // the construction of the up and right vectors needs to precisely mirror the code 
     // in render/light.rs:spot_light_view_matrix
@@ -80,8 +178,8 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
     let projected_position = offset_position * light_inv_rot;
 
     // divide xy by perspective matrix "f" and by -projected.z (projected.z is -projection matrix's w)
-    // to get ndc coordinates
-    let f_div_minus_z = 1.0 / (light.spot_light_tan_angle * -projected_position.z);
+    // to get ndc coordinates, scaled to the centre of the shadow map the light is rendered to
+    let f_div_minus_z = 1.0 / (light.shadow_custom_data * -projected_position.z);
     let shadow_xy_ndc = projected_position.xy * f_div_minus_z;
     // convert to uv coordinates
     let shadow_uv = shadow_xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
@@ -89,13 +187,11 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
     // 0.1 must match POINT_LIGHT_NEAR_Z
     let depth = 0.1 / -projected_position.z;
 
-    #ifdef NO_ARRAY_TEXTURES_SUPPORT
-        return textureSampleCompare(directional_shadow_textures, directional_shadow_textures_sampler, 
-            shadow_uv, depth);
-    #else
-        return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, 
-            shadow_uv, i32(light_id) + lights.spot_light_shadowmap_offset, depth);
-    #endif
+    return sample_directional_shadow_map(
+        shadow_uv,
+        depth,
+        i32(light_id) + lights.spot_light_shadowmap_offset
+    );
 }
 
 fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
@@ -118,17 +214,11 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
     }
 
     // compute texture coordinates for shadow lookup, compensating for the Y-flip difference
-    // between the NDC and texture coordinates
-    let flip_correction = vec2<f32>(0.5, -0.5);
+    // between the NDC and texture coordinates, in the centre of the shadow map the light is
+    // rendered to
+    let flip_correction = vec2<f32>(0.5, -0.5) * light.shadow_map_scale;
     let light_local = offset_position_ndc.xy * flip_correction + vec2<f32>(0.5, 0.5);
 
     let depth = offset_position_ndc.z;
-    // do the lookup, using HW PCF and comparison
-    // NOTE: Due to non-uniform control flow above, we must use the level variant of the texture
-    // sampler to avoid use of implicit derivatives causing possible undefined behavior.
-#ifdef NO_ARRAY_TEXTURES_SUPPORT
-    return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, light_local, depth);
-#else
-    return textureSampleCompareLevel(directional_shadow_textures, directional_shadow_textures_sampler, light_local, i32(light_id), depth);
-#endif
+    return sample_directional_shadow_map(light_local, depth, i32(light_id));
 }
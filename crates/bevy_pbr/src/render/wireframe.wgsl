@@ -38,7 +38,20 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     return out;
 }
 
+struct WireframeColors {
+    color: vec4<f32>,
+    back_color: vec4<f32>,
+};
+
+@group(2) @binding(0)
+var<uniform> wireframe_colors: WireframeColors;
+
 @fragment
-fn fragment() -> @location(0) vec4<f32> {
-    return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+fn fragment(@builtin(front_facing) is_front: bool) -> @location(0) vec4<f32> {
+#ifdef WIREFRAME_BACK_COLOR
+    if !is_front {
+        return wireframe_colors.back_color;
+    }
+#endif
+    return wireframe_colors.color;
 }
@@ -0,0 +1,528 @@
+//! Screen space ambient occlusion, for the cameras that opt into it with
+//! [`ScreenSpaceAmbientOcclusion`].
+//!
+//! Between the prepass and the main pass, the [`ScreenSpaceAmbientOcclusionNode`] estimates how
+//! much of the hemisphere around each pixel is occluded by the nearby geometry, from the depth
+//! written by the [`DepthPrepass`] these cameras require. The normals are reconstructed from
+//! the depth as well. The estimate follows ground truth ambient occlusion (GTAO): the horizons
+//! of a few slices of the hemisphere are searched in screen space. A second pass then denoises
+//! the estimate, without blurring across depth discontinuities.
+//!
+//! The main pass of opaque meshes darkens their ambient light by the result, which is bound to
+//! the mesh view bind group. Views without screen space ambient occlusion bind a white texture
+//! instead.
+
+mod node;
+
+pub use node::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_core_pipeline::{
+    core_3d::Camera3d,
+    prepass::{warn_missing_depth_prepass, DepthPrepass, Opaque3dPrepass},
+};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::RenderGraph,
+    render_phase::RenderPhase,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, DynamicUniformBuffer, Extent3d,
+        FragmentState, MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor,
+        Shader, ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureView, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::TextureCache,
+    view::{Msaa, ViewDepthTexture, ViewUniform, ViewUniforms},
+    RenderApp, RenderStage,
+};
+
+use crate::draw_3d_graph;
+
+pub const SSAO_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10293851728364519471);
+
+/// The format of the texture the ambient occlusion of a view is estimated in, before being
+/// denoised: the ambient occlusion in red and the view depth in green.
+const NOISY_AMBIENT_OCCLUSION_FORMAT: TextureFormat = TextureFormat::Rg16Float;
+/// The format of the denoised ambient occlusion of a view.
+pub const SCREEN_SPACE_AMBIENT_OCCLUSION_FORMAT: TextureFormat = TextureFormat::R8Unorm;
+
+/// Add this component to a [`Camera3d`] to darken the ambient light of opaque meshes where the
+/// nearby geometry occludes it, such as in creases, corners and under objects.
+///
+/// Screen space ambient occlusion requires a [`DepthPrepass`] on the camera. Only what is
+/// visible on screen occludes.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct ScreenSpaceAmbientOcclusion {
+    pub quality: ScreenSpaceAmbientOcclusionQuality,
+    /// The distance in world units around each pixel the occluders are searched in.
+    pub radius: f32,
+    /// The exponent applied to the ambient occlusion, darkening it above 1.
+    pub intensity: f32,
+}
+
+impl Default for ScreenSpaceAmbientOcclusion {
+    fn default() -> Self {
+        Self {
+            quality: ScreenSpaceAmbientOcclusionQuality::default(),
+            radius: 0.5,
+            intensity: 1.0,
+        }
+    }
+}
+
+impl ExtractComponent for ScreenSpaceAmbientOcclusion {
+    type Query = &'static Self;
+    type Filter = (With<Camera>, With<Camera3d>);
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// How many samples the [`ScreenSpaceAmbientOcclusion`] of a view takes per pixel, trading
+/// noise for performance.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum ScreenSpaceAmbientOcclusionQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Ultra,
+}
+
+impl ScreenSpaceAmbientOcclusionQuality {
+    /// The number of slices of the hemisphere around each pixel whose horizons are searched.
+    pub fn slice_count(&self) -> u32 {
+        match self {
+            ScreenSpaceAmbientOcclusionQuality::Low => 1,
+            ScreenSpaceAmbientOcclusionQuality::Medium => 2,
+            ScreenSpaceAmbientOcclusionQuality::High => 3,
+            ScreenSpaceAmbientOcclusionQuality::Ultra => 4,
+        }
+    }
+
+    /// The number of samples on each side of a slice its horizons are searched with.
+    pub fn samples_per_slice_side(&self) -> u32 {
+        match self {
+            ScreenSpaceAmbientOcclusionQuality::Low => 2,
+            ScreenSpaceAmbientOcclusionQuality::Medium => 4,
+            ScreenSpaceAmbientOcclusionQuality::High => 6,
+            ScreenSpaceAmbientOcclusionQuality::Ultra => 8,
+        }
+    }
+}
+
+/// Estimates the ambient occlusion of the cameras with [`ScreenSpaceAmbientOcclusion`], for
+/// their main pass.
+pub struct ScreenSpaceAmbientOcclusionPlugin;
+
+impl Plugin for ScreenSpaceAmbientOcclusionPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SSAO_SHADER_HANDLE, "ssao.wgsl", Shader::from_wgsl);
+
+        app.register_type::<ScreenSpaceAmbientOcclusion>()
+            .register_type::<ScreenSpaceAmbientOcclusionQuality>()
+            .add_plugin(ExtractComponentPlugin::<ScreenSpaceAmbientOcclusion>::default())
+            .add_system(warn_missing_depth_prepass::<ScreenSpaceAmbientOcclusion>);
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<ScreenSpaceAmbientOcclusionPipeline>()
+            .init_resource::<SpecializedRenderPipelines<ScreenSpaceAmbientOcclusionPipeline>>()
+            .init_resource::<ScreenSpaceAmbientOcclusionUniforms>()
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_screen_space_ambient_occlusion_textures,
+            )
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_screen_space_ambient_occlusion_uniforms,
+            )
+            .add_system_to_stage(
+                RenderStage::Queue,
+                queue_screen_space_ambient_occlusion_bind_groups,
+            );
+
+        let ssao_node = ScreenSpaceAmbientOcclusionNode::new(&mut render_app.world);
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+        let draw_3d_graph = graph
+            .get_sub_graph_mut(bevy_core_pipeline::core_3d::graph::NAME)
+            .unwrap();
+        draw_3d_graph.add_node(
+            draw_3d_graph::node::SCREEN_SPACE_AMBIENT_OCCLUSION,
+            ssao_node,
+        );
+        draw_3d_graph
+            .add_slot_edge(
+                draw_3d_graph.input_node().unwrap().id,
+                bevy_core_pipeline::core_3d::graph::input::VIEW_ENTITY,
+                draw_3d_graph::node::SCREEN_SPACE_AMBIENT_OCCLUSION,
+                ScreenSpaceAmbientOcclusionNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(
+                bevy_core_pipeline::core_3d::graph::node::PREPASS,
+                draw_3d_graph::node::SCREEN_SPACE_AMBIENT_OCCLUSION,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(
+                draw_3d_graph::node::SCREEN_SPACE_AMBIENT_OCCLUSION,
+                bevy_core_pipeline::core_3d::graph::node::MAIN_PASS,
+            )
+            .unwrap();
+    }
+}
+
+/// The textures of the screen space ambient occlusion of a view.
+#[derive(Component)]
+pub struct ViewScreenSpaceAmbientOcclusionTextures {
+    /// The estimated ambient occlusion, with the view depth it is denoised with.
+    pub noisy: TextureView,
+    /// The denoised ambient occlusion, in [`SCREEN_SPACE_AMBIENT_OCCLUSION_FORMAT`], read by the
+    /// main pass.
+    pub ambient_occlusion: TextureView,
+}
+
+pub fn prepare_screen_space_ambient_occlusion_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedCamera,
+            Option<&DepthPrepass>,
+            Option<&RenderPhase<Opaque3dPrepass>>,
+        ),
+        With<ScreenSpaceAmbientOcclusion>,
+    >,
+) {
+    for (entity, camera, depth_prepass, prepass_phase) in &views {
+        if depth_prepass.is_none() || prepass_phase.is_none() {
+            continue;
+        }
+        let physical_target_size = match camera.physical_target_size {
+            Some(size) => size,
+            None => continue,
+        };
+        let mut get_texture = |label, format| {
+            texture_cache
+                .get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some(label),
+                        size: Extent3d {
+                            depth_or_array_layers: 1,
+                            width: physical_target_size.x,
+                            height: physical_target_size.y,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: TextureDimension::D2,
+                        format,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    },
+                )
+                .default_view
+        };
+        commands
+            .entity(entity)
+            .insert(ViewScreenSpaceAmbientOcclusionTextures {
+                noisy: get_texture(
+                    "screen_space_ambient_occlusion_noisy_texture",
+                    NOISY_AMBIENT_OCCLUSION_FORMAT,
+                ),
+                ambient_occlusion: get_texture(
+                    "screen_space_ambient_occlusion_texture",
+                    SCREEN_SPACE_AMBIENT_OCCLUSION_FORMAT,
+                ),
+            });
+    }
+}
+
+#[derive(Clone, ShaderType)]
+struct ScreenSpaceAmbientOcclusionUniform {
+    radius: f32,
+    intensity: f32,
+    slice_count: u32,
+    samples_per_slice_side: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct ScreenSpaceAmbientOcclusionUniforms {
+    uniforms: DynamicUniformBuffer<ScreenSpaceAmbientOcclusionUniform>,
+}
+
+#[derive(Component)]
+pub struct ScreenSpaceAmbientOcclusionUniformOffset {
+    pub offset: u32,
+}
+
+pub fn prepare_screen_space_ambient_occlusion_uniforms(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut ssao_uniforms: ResMut<ScreenSpaceAmbientOcclusionUniforms>,
+    views: Query<(Entity, &ScreenSpaceAmbientOcclusion)>,
+) {
+    ssao_uniforms.uniforms.clear();
+    for (entity, ssao) in &views {
+        let offset = ssao_uniforms
+            .uniforms
+            .push(ScreenSpaceAmbientOcclusionUniform {
+                radius: ssao.radius.max(0.0),
+                intensity: ssao.intensity.max(0.0),
+                slice_count: ssao.quality.slice_count(),
+                samples_per_slice_side: ssao.quality.samples_per_slice_side(),
+            });
+        commands
+            .entity(entity)
+            .insert(ScreenSpaceAmbientOcclusionUniformOffset { offset });
+    }
+    ssao_uniforms
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// The pipelines and bind groups of the two passes of the screen space ambient occlusion of a
+/// view.
+#[derive(Component)]
+pub struct ViewScreenSpaceAmbientOcclusionPasses {
+    pub ambient_occlusion_pipeline: CachedRenderPipelineId,
+    pub ambient_occlusion_bind_group: BindGroup,
+    pub denoise_pipeline: CachedRenderPipelineId,
+    pub denoise_bind_group: BindGroup,
+}
+
+#[derive(Resource)]
+pub struct ScreenSpaceAmbientOcclusionPipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+    denoise_layout: BindGroupLayout,
+}
+
+impl ScreenSpaceAmbientOcclusionPipeline {
+    fn layout(&self, key: ScreenSpaceAmbientOcclusionPipelineKey) -> &BindGroupLayout {
+        match key {
+            ScreenSpaceAmbientOcclusionPipelineKey { denoise: true, .. } => &self.denoise_layout,
+            ScreenSpaceAmbientOcclusionPipelineKey {
+                multisampled: true, ..
+            } => &self.multisampled_layout,
+            _ => &self.layout,
+        }
+    }
+}
+
+impl FromWorld for ScreenSpaceAmbientOcclusionPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let texture_entry = |binding, multisampled, sample_type| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled,
+                sample_type,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        };
+        let uniform_entry = |binding, min_binding_size| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: true,
+                min_binding_size: Some(min_binding_size),
+            },
+            count: None,
+        };
+        let create_layout = |label, multisampled| {
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[
+                    // Depth
+                    texture_entry(0, multisampled, TextureSampleType::Depth),
+                    uniform_entry(1, ViewUniform::min_size()),
+                    uniform_entry(2, ScreenSpaceAmbientOcclusionUniform::min_size()),
+                ],
+            })
+        };
+        let denoise_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("screen_space_ambient_occlusion_denoise_layout"),
+            entries: &[
+                // Noisy ambient occlusion
+                texture_entry(0, false, TextureSampleType::Float { filterable: false }),
+            ],
+        });
+        ScreenSpaceAmbientOcclusionPipeline {
+            layout: create_layout("screen_space_ambient_occlusion_layout", false),
+            multisampled_layout: create_layout(
+                "screen_space_ambient_occlusion_multisampled_layout",
+                true,
+            ),
+            denoise_layout,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScreenSpaceAmbientOcclusionPipelineKey {
+    /// Whether the depth texture is multisampled.
+    pub multisampled: bool,
+    /// Whether the pass denoises the estimated ambient occlusion rather than estimating it, in
+    /// which case the depth texture isn't read.
+    pub denoise: bool,
+}
+
+impl SpecializedRenderPipeline for ScreenSpaceAmbientOcclusionPipeline {
+    type Key = ScreenSpaceAmbientOcclusionPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.multisampled {
+            shader_defs.push(String::from("MULTISAMPLED"));
+        }
+        if key.denoise {
+            shader_defs.push(String::from("DENOISE"));
+        }
+        let format = if key.denoise {
+            SCREEN_SPACE_AMBIENT_OCCLUSION_FORMAT
+        } else {
+            NOISY_AMBIENT_OCCLUSION_FORMAT
+        };
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: SSAO_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: SSAO_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout(key).clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("screen_space_ambient_occlusion_pipeline".into()),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_screen_space_ambient_occlusion_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    ssao_pipeline: Res<ScreenSpaceAmbientOcclusionPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ScreenSpaceAmbientOcclusionPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    view_uniforms: Res<ViewUniforms>,
+    ssao_uniforms: Res<ScreenSpaceAmbientOcclusionUniforms>,
+    views: Query<(
+        Entity,
+        &ViewScreenSpaceAmbientOcclusionTextures,
+        &ViewDepthTexture,
+        &Msaa,
+    )>,
+) {
+    let (view_uniforms, uniforms) = match (
+        view_uniforms.uniforms.binding(),
+        ssao_uniforms.uniforms.binding(),
+    ) {
+        (Some(view_uniforms), Some(uniforms)) => (view_uniforms, uniforms),
+        _ => return,
+    };
+    let denoise_key = ScreenSpaceAmbientOcclusionPipelineKey {
+        multisampled: false,
+        denoise: true,
+    };
+    for (entity, textures, depth, msaa) in &views {
+        let ambient_occlusion_key = ScreenSpaceAmbientOcclusionPipelineKey {
+            multisampled: msaa.samples > 1,
+            denoise: false,
+        };
+        let ambient_occlusion_pipeline =
+            pipelines.specialize(&mut pipeline_cache, &ssao_pipeline, ambient_occlusion_key);
+        let denoise_pipeline =
+            pipelines.specialize(&mut pipeline_cache, &ssao_pipeline, denoise_key);
+        let ambient_occlusion_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("screen_space_ambient_occlusion_bind_group"),
+            layout: ssao_pipeline.layout(ambient_occlusion_key),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&depth.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: view_uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: uniforms.clone(),
+                },
+            ],
+        });
+        let denoise_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("screen_space_ambient_occlusion_denoise_bind_group"),
+            layout: ssao_pipeline.layout(denoise_key),
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&textures.noisy),
+            }],
+        });
+        commands
+            .entity(entity)
+            .insert(ViewScreenSpaceAmbientOcclusionPasses {
+                ambient_occlusion_pipeline,
+                ambient_occlusion_bind_group,
+                denoise_pipeline,
+                denoise_bind_group,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn higher_qualities_take_more_samples() {
+        let samples = |quality: ScreenSpaceAmbientOcclusionQuality| {
+            quality.slice_count() * quality.samples_per_slice_side()
+        };
+        let qualities = [
+            ScreenSpaceAmbientOcclusionQuality::Low,
+            ScreenSpaceAmbientOcclusionQuality::Medium,
+            ScreenSpaceAmbientOcclusionQuality::High,
+            ScreenSpaceAmbientOcclusionQuality::Ultra,
+        ];
+        for pair in qualities.windows(2) {
+            assert!(samples(pair[0]) < samples(pair[1]));
+        }
+    }
+}
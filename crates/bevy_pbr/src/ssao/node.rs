@@ -0,0 +1,130 @@
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::ExtractedCamera,
+    color::Color,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewUniformOffset},
+};
+
+use crate::{
+    ScreenSpaceAmbientOcclusionUniformOffset, ViewScreenSpaceAmbientOcclusionPasses,
+    ViewScreenSpaceAmbientOcclusionTextures,
+};
+
+/// Estimates then denoises the ambient occlusion of the views with
+/// [`ScreenSpaceAmbientOcclusion`](crate::ScreenSpaceAmbientOcclusion), from the depth of
+/// their prepass.
+pub struct ScreenSpaceAmbientOcclusionNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewScreenSpaceAmbientOcclusionTextures,
+            &'static ViewScreenSpaceAmbientOcclusionPasses,
+            &'static ViewUniformOffset,
+            &'static ScreenSpaceAmbientOcclusionUniformOffset,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl ScreenSpaceAmbientOcclusionNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for ScreenSpaceAmbientOcclusionNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(
+            ScreenSpaceAmbientOcclusionNode::IN_VIEW,
+            SlotType::Entity,
+        )]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, textures, passes, view_uniform_offset, uniform_offset) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                Err(_) => {
+                    return Ok(());
+                } // No screen space ambient occlusion for this view
+            };
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (ambient_occlusion_pipeline, denoise_pipeline) = match (
+            pipeline_cache.get_render_pipeline(passes.ambient_occlusion_pipeline),
+            pipeline_cache.get_render_pipeline(passes.denoise_pipeline),
+        ) {
+            (Some(ambient_occlusion_pipeline), Some(denoise_pipeline)) => {
+                (ambient_occlusion_pipeline, denoise_pipeline)
+            }
+            _ => return Ok(()),
+        };
+
+        // NOTE: What is outside of the viewport is unoccluded and behind everything, which keeps
+        // the denoising from reading it
+        let ops = Operations {
+            load: LoadOp::Clear(Color::rgba_linear(1.0, 65504.0, 0.0, 0.0).into()),
+            store: true,
+        };
+
+        {
+            let pass_descriptor = RenderPassDescriptor {
+                label: Some("screen_space_ambient_occlusion_pass_3d"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &textures.noisy,
+                    resolve_target: None,
+                    ops,
+                })],
+                depth_stencil_attachment: None,
+            };
+            let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+            if let Some(viewport) = camera.viewport.as_ref() {
+                tracked_pass.set_camera_viewport(viewport);
+            }
+            tracked_pass.set_render_pipeline(ambient_occlusion_pipeline);
+            tracked_pass.set_bind_group(
+                0,
+                &passes.ambient_occlusion_bind_group,
+                &[view_uniform_offset.offset, uniform_offset.offset],
+            );
+            tracked_pass.draw(0..3, 0..1);
+        }
+
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("screen_space_ambient_occlusion_denoise_pass_3d"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &textures.ambient_occlusion,
+                resolve_target: None,
+                ops,
+            })],
+            depth_stencil_attachment: None,
+        };
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            tracked_pass.set_camera_viewport(viewport);
+        }
+        tracked_pass.set_render_pipeline(denoise_pipeline);
+        tracked_pass.set_bind_group(0, &passes.denoise_bind_group, &[]);
+        tracked_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+#import bevy_pbr::mesh_view_types
+
+#ifdef DENOISE
+@group(0) @binding(0)
+var noisy_texture: texture_2d<f32>;
+#else
+
+struct ScreenSpaceAmbientOcclusion {
+    // The distance in world units around each pixel the occluders are searched in
+    radius: f32,
+    intensity: f32,
+    slice_count: u32,
+    samples_per_slice_side: u32,
+};
+
+#ifdef MULTISAMPLED
+@group(0) @binding(0)
+var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(0)
+var depth_texture: texture_depth_2d;
+#endif
+@group(0) @binding(1)
+var<uniform> view: View;
+@group(0) @binding(2)
+var<uniform> ssao: ScreenSpaceAmbientOcclusion;
+#endif
+
+let PI: f32 = 3.141592653589793;
+let HALF_PI: f32 = 1.5707963267948966;
+// The view depth of the pixels without geometry, the largest finite half float
+let FAR_DEPTH: f32 = 65504.0;
+
+// A triangle covering the whole target
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+#ifdef DENOISE
+// Averages the ambient occlusion of the 5x5 pixels around each pixel, weighted by how close
+// their view depth is to its own
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(noisy_texture));
+    let coords = vec2<i32>(position.xy);
+    let center_depth = textureLoad(noisy_texture, coords, 0).g;
+
+    var ambient_occlusion = 0.0;
+    var total_weight = 0.0;
+    for (var y: i32 = -2; y <= 2; y = y + 1) {
+        for (var x: i32 = -2; x <= 2; x = x + 1) {
+            let sample_coords = clamp(coords + vec2<i32>(x, y), vec2<i32>(0), size - 1);
+            let noisy = textureLoad(noisy_texture, sample_coords, 0).rg;
+            let weight = max(1.0 - abs(noisy.g - center_depth) / (0.05 * center_depth), 0.0);
+            ambient_occlusion = ambient_occlusion + noisy.r * weight;
+            total_weight = total_weight + weight;
+        }
+    }
+    return vec4<f32>(ambient_occlusion / max(total_weight, 0.0001), 0.0, 0.0, 1.0);
+}
+#else
+fn load_depth(coords: vec2<i32>) -> f32 {
+    // NOTE: With MSAA, the first sample stands for the whole pixel
+    return textureLoad(depth_texture, coords, 0);
+}
+
+// Returns the view space position of the pixel at `coords`
+fn view_position(coords: vec2<i32>) -> vec3<f32> {
+    let uv = (vec2<f32>(coords) + 0.5 - view.viewport.xy) / view.viewport.zw;
+    let clip = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, load_depth(coords), 1.0);
+    let position = view.inverse_projection * clip;
+    return position.xyz / position.w;
+}
+
+// Reconstructs the view space normal of the pixel at `coords` from the positions of its
+// neighbours, picking on each axis the one closest in depth to stay on the same surface
+fn view_normal(coords: vec2<i32>, position: vec3<f32>) -> vec3<f32> {
+    let left = view_position(coords - vec2<i32>(1, 0));
+    let right = view_position(coords + vec2<i32>(1, 0));
+    let up = view_position(coords - vec2<i32>(0, 1));
+    let down = view_position(coords + vec2<i32>(0, 1));
+    var dx = right - position;
+    if (abs(position.z - left.z) < abs(right.z - position.z)) {
+        dx = position - left;
+    }
+    var dy = position - down;
+    if (abs(position.z - up.z) < abs(down.z - position.z)) {
+        dy = up - position;
+    }
+    return normalize(cross(dx, dy));
+}
+
+// Interleaved gradient noise, from Jimenez 2014, "Next Generation Post Processing in Call of
+// Duty: Advanced Warfare"
+fn noise(position: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(position, vec2<f32>(0.06711056, 0.00583715))));
+}
+
+// Ground truth ambient occlusion, from Jimenez et al. 2016, "Practical Realtime Strategies for
+// Accurate Indirect Occlusion"
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(position.xy);
+    // NOTE: The depth is reversed, what has no geometry is at 0
+    if (load_depth(coords) == 0.0) {
+        return vec4<f32>(1.0, FAR_DEPTH, 0.0, 1.0);
+    }
+    let pixel_position = view_position(coords);
+    let normal = view_normal(coords, pixel_position);
+    let view_vector = normalize(-pixel_position);
+
+    // The radius in pixels, from the scale of the projection at the depth of the pixel
+    let clip_w = (view.projection * vec4<f32>(pixel_position, 1.0)).w;
+    let pixel_radius = ssao.radius * 0.5 * view.projection[1][1] * view.viewport.w / clip_w;
+
+    let slice_noise = noise(position.xy);
+    let sample_noise = noise(position.xy + vec2<f32>(5.588238, 5.588238));
+    let min_coords = vec2<i32>(view.viewport.xy);
+    let max_coords = vec2<i32>(view.viewport.xy + view.viewport.zw) - 1;
+
+    var visibility = 0.0;
+    for (var slice: u32 = 0u; slice < ssao.slice_count; slice = slice + 1u) {
+        let phi = (f32(slice) + slice_noise) * PI / f32(ssao.slice_count);
+        // The direction of the slice in view space, where y goes up, unlike in the target
+        let direction = vec3<f32>(cos(phi), sin(phi), 0.0);
+        let target_direction = vec2<f32>(direction.x, -direction.y);
+        let orthogonal_direction = direction - dot(direction, view_vector) * view_vector;
+        let axis = normalize(cross(orthogonal_direction, view_vector));
+        let projected_normal = normal - axis * dot(normal, axis);
+        let projected_normal_length = length(projected_normal);
+        let sign_normal = sign(dot(orthogonal_direction, projected_normal));
+        let cos_normal = clamp(dot(projected_normal, view_vector) / projected_normal_length, 0.0, 1.0);
+        let n = sign_normal * acos(cos_normal);
+
+        // The cosines of the highest horizons found on each side of the slice
+        let low_horizon_cos_0 = cos(n + HALF_PI);
+        let low_horizon_cos_1 = cos(n - HALF_PI);
+        var horizon_cos_0 = low_horizon_cos_0;
+        var horizon_cos_1 = low_horizon_cos_1;
+        for (var i: u32 = 0u; i < ssao.samples_per_slice_side; i = i + 1u) {
+            // More samples close to the pixel, where the occluders matter most
+            var s = (f32(i) + sample_noise) / f32(ssao.samples_per_slice_side);
+            s = s * s;
+            let offset = target_direction * max(pixel_radius * s, 1.0);
+
+            let coords_0 = clamp(vec2<i32>(position.xy + offset), min_coords, max_coords);
+            let delta_0 = view_position(coords_0) - pixel_position;
+            let distance_0 = max(length(delta_0), 0.0001);
+            let falloff_0 = clamp(2.0 - 2.0 * distance_0 / ssao.radius, 0.0, 1.0);
+            let sample_cos_0 = mix(low_horizon_cos_0, dot(delta_0 / distance_0, view_vector), falloff_0);
+            horizon_cos_0 = max(horizon_cos_0, sample_cos_0);
+
+            let coords_1 = clamp(vec2<i32>(position.xy - offset), min_coords, max_coords);
+            let delta_1 = view_position(coords_1) - pixel_position;
+            let distance_1 = max(length(delta_1), 0.0001);
+            let falloff_1 = clamp(2.0 - 2.0 * distance_1 / ssao.radius, 0.0, 1.0);
+            let sample_cos_1 = mix(low_horizon_cos_1, dot(delta_1 / distance_1, view_vector), falloff_1);
+            horizon_cos_1 = max(horizon_cos_1, sample_cos_1);
+        }
+
+        // Integrates the visible arc between the horizons, weighted by the cosine to the normal
+        let h0 = n + clamp(-acos(horizon_cos_1) - n, -HALF_PI, HALF_PI);
+        let h1 = n + clamp(acos(horizon_cos_0) - n, -HALF_PI, HALF_PI);
+        let sin_normal = sin(n);
+        let arc_0 = (cos_normal + 2.0 * h0 * sin_normal - cos(2.0 * h0 - n)) / 4.0;
+        let arc_1 = (cos_normal + 2.0 * h1 * sin_normal - cos(2.0 * h1 - n)) / 4.0;
+        visibility = visibility + projected_normal_length * (arc_0 + arc_1);
+    }
+    visibility = visibility / f32(ssao.slice_count);
+    visibility = pow(clamp(visibility, 0.0, 1.0), ssao.intensity);
+
+    return vec4<f32>(visibility, -pixel_position.z, 0.0, 1.0);
+}
+#endif
@@ -1,25 +1,39 @@
 use crate::MeshPipeline;
-use crate::{DrawMesh, MeshPipelineKey, MeshUniform, SetMeshBindGroup, SetMeshViewBindGroup};
-use bevy_app::Plugin;
-use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use crate::{MeshPipelineKey, MeshUniform, SetMeshBindGroup, SetMeshViewBindGroup};
+use bevy_app::{CoreStage, Plugin};
+use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle, HandleId, HandleUntyped};
 use bevy_core_pipeline::core_3d::Opaque3d;
+use bevy_ecs::system::{
+    lifetimeless::{Read, SQuery, SRes},
+    SystemParamItem,
+};
 use bevy_ecs::{prelude::*, reflect::ReflectComponent};
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::{Reflect, TypeUuid};
 use bevy_render::Extract;
 use bevy_render::{
+    camera::Camera,
+    color::Color,
+    extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
     extract_resource::{ExtractResource, ExtractResourcePlugin},
-    mesh::{Mesh, MeshVertexBufferLayout},
+    mesh::{GpuBufferInfo, Mesh, MeshVertexBufferLayout, PrimitiveTopology},
     render_asset::RenderAssets,
-    render_phase::{AddRenderCommand, DrawFunctions, RenderPhase, SetItemPipeline},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, EntityRenderCommand, RenderCommandResult, RenderPhase,
+        SetItemPipeline, TrackedRenderPass,
+    },
     render_resource::{
-        PipelineCache, PolygonMode, RenderPipelineDescriptor, Shader, SpecializedMeshPipeline,
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingType, BufferBindingType, PipelineCache, PolygonMode,
+        RenderPipelineDescriptor, Shader, ShaderStages, ShaderType, SpecializedMeshPipeline,
         SpecializedMeshPipelineError, SpecializedMeshPipelines,
     },
-    view::{ExtractedView, Msaa, VisibleEntities},
+    renderer::RenderDevice,
+    settings::WgpuFeatures,
+    view::{ComputedVisibility, ExtractedView, Msaa, VisibleEntities},
     RenderApp, RenderStage,
 };
-use bevy_utils::tracing::error;
+use bevy_utils::{tracing::error, HashMap, HashSet};
 
 pub const WIREFRAME_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 192598014480025766);
@@ -37,24 +51,74 @@ impl Plugin for WireframePlugin {
         );
 
         app.register_type::<Wireframe>()
+            .register_type::<WireframeColor>()
+            .register_type::<WireframeOnly>()
             .register_type::<WireframeConfig>()
+            .register_type::<WireframeMethod>()
             .init_resource::<WireframeConfig>()
-            .add_plugin(ExtractResourcePlugin::<WireframeConfig>::default());
+            .init_resource::<WireframeLineMeshes>()
+            .add_system_to_stage(CoreStage::PostUpdate, update_wireframe_line_meshes)
+            .add_plugin(ExtractResourcePlugin::<WireframeConfig>::default())
+            .add_plugin(UniformComponentPlugin::<WireframeColorsUniform>::default());
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .add_render_command::<Opaque3d, DrawWireframes>()
                 .init_resource::<WireframePipeline>()
                 .init_resource::<SpecializedMeshPipelines<WireframePipeline>>()
+                .init_resource::<WireframeLineMeshes>()
+                .add_system_to_stage(RenderStage::Extract, extract_wireframe_line_meshes)
                 .add_system_to_stage(RenderStage::Extract, extract_wireframes)
+                .add_system_to_stage(RenderStage::Extract, extract_wireframe_only_cameras)
+                .add_system_to_stage(RenderStage::Queue, queue_wireframe_colors_bind_group)
                 .add_system_to_stage(RenderStage::Queue, queue_wireframes);
         }
     }
 }
 
-fn extract_wireframes(mut commands: Commands, query: Extract<Query<Entity, With<Wireframe>>>) {
-    for entity in &query {
-        commands.get_or_spawn(entity).insert(Wireframe);
+/// Extracts the [`Wireframe`] of visible entities, so that hidden and culled entities don't
+/// draw wireframes, like they don't draw their material.
+///
+/// The visible meshes whose wireframe may be drawn also get their [`WireframeColorsUniform`].
+#[allow(clippy::type_complexity)]
+fn extract_wireframes(
+    mut commands: Commands,
+    config: Extract<Res<WireframeConfig>>,
+    query: Extract<
+        Query<
+            (
+                Entity,
+                &ComputedVisibility,
+                Option<&Wireframe>,
+                Option<&WireframeColor>,
+            ),
+            With<Handle<Mesh>>,
+        >,
+    >,
+    wireframe_only_cameras: Extract<Query<&Camera, With<WireframeOnly>>>,
+) {
+    let all_meshes = config.global || wireframe_only_cameras.iter().any(|camera| camera.is_active);
+    for (entity, computed_visibility, wireframe, color) in &query {
+        if !computed_visibility.is_visible() || !(all_meshes || wireframe.is_some()) {
+            continue;
+        }
+        let colors = WireframeColorsUniform::new(&config, color);
+        if wireframe.is_some() {
+            commands.get_or_spawn(entity).insert((Wireframe, colors));
+        } else {
+            commands.get_or_spawn(entity).insert(colors);
+        }
+    }
+}
+
+fn extract_wireframe_only_cameras(
+    mut commands: Commands,
+    query: Extract<Query<(Entity, &Camera), With<WireframeOnly>>>,
+) {
+    for (entity, camera) in &query {
+        if camera.is_active {
+            commands.get_or_spawn(entity).insert(WireframeOnly);
+        }
     }
 }
 
@@ -63,39 +127,358 @@ fn extract_wireframes(mut commands: Commands, query: Extract<Query<Entity, With<
 #[reflect(Component, Default)]
 pub struct Wireframe;
 
+/// The color of the wireframe of an entity, instead of [`WireframeConfig::global_color`].
+///
+/// Gives each kind of debug overlay its own color, so they can be told apart in one view.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct WireframeColor(pub Color);
+
+/// Add this component to a 3d camera to render every mesh as a wireframe in its view, and
+/// none of the meshes' materials.
+///
+/// Other cameras keep rendering the scene normally, so this can be used on a camera rendering
+/// to an [`Image`](bevy_render::texture::Image) to display a wireframe inset next to the
+/// regular view. The colors and back faces of the wireframes follow the [`WireframeConfig`]
+/// and the [`WireframeColor`] of each entity.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct WireframeOnly;
+
 #[derive(Resource, Debug, Clone, Default, ExtractResource, Reflect)]
 #[reflect(Resource)]
 pub struct WireframeConfig {
     /// Whether to show wireframes for all meshes. If `false`, only meshes with a [Wireframe] component will be rendered.
     pub global: bool,
+    /// The color of the wireframes without a [`WireframeColor`], see [`WireframeColorPreset`]
+    /// for colors that stand out against most backgrounds.
+    pub global_color: Color,
+    /// The color of edges belonging to back-facing triangles.
+    ///
+    /// If `None`, back faces are culled like in the regular mesh pipeline. If `Some`, back faces
+    /// are drawn as well, in this color, which makes the orientation of double-sided meshes visible.
+    pub back_color: Option<Color>,
+    /// How the edges of the triangles are rasterized.
+    pub method: WireframeMethod,
+}
+
+/// How the [`WireframePlugin`] draws the edges of triangles, see [`WireframeConfig::method`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum WireframeMethod {
+    /// Uses [`WireframeMethod::PolygonMode`] if the device supports it, and
+    /// [`WireframeMethod::LineMeshes`] otherwise, e.g. on `WebGL2` and some mobile GPUs.
+    #[default]
+    Auto,
+    /// Rasterizes the triangles of the meshes as lines, which requires the
+    /// [`WgpuFeatures::POLYGON_MODE_LINE`] feature.
+    PolygonMode,
+    /// Draws a [`PrimitiveTopology::LineList`] copy of each mesh, made with
+    /// [`Mesh::to_edge_lines`], which works on every device.
+    ///
+    /// The copies use as much memory as the meshes they are made from. As lines don't have
+    /// a facing, [`WireframeConfig::back_color`] only disables back face culling.
+    LineMeshes,
+}
+
+impl WireframeMethod {
+    /// Whether line meshes are drawn with this method on a device with these `features`.
+    pub fn uses_line_meshes(self, features: WgpuFeatures) -> bool {
+        match self {
+            WireframeMethod::Auto => !features.contains(WgpuFeatures::POLYGON_MODE_LINE),
+            WireframeMethod::PolygonMode => false,
+            WireframeMethod::LineMeshes => true,
+        }
+    }
+}
+
+/// The [`PrimitiveTopology::LineList`] copies of the meshes drawn with
+/// [`WireframeMethod::LineMeshes`], by the handle of the mesh they are made from.
+///
+/// Only the meshes whose wireframe is drawn get a copy: the ones of the entities with a
+/// [`Wireframe`], or all of them when [`WireframeConfig::global`] is set or a [`WireframeOnly`]
+/// camera is active. Empty when the line meshes aren't used.
+#[derive(Resource, Debug, Default)]
+pub struct WireframeLineMeshes {
+    /// Whether the wireframes are drawn with the line meshes.
+    pub enabled: bool,
+    /// The line mesh made from each triangle mesh.
+    pub meshes: HashMap<HandleId, Handle<Mesh>>,
+    /// The meshes whose line mesh was added in the last update, to be extracted.
+    added: Vec<HandleId>,
+    /// The meshes whose line mesh was removed in the last update, to be extracted.
+    removed: Vec<HandleId>,
+}
+
+/// Applies the line meshes added and removed in the last update to the render world's
+/// [`WireframeLineMeshes`], instead of copying all of them every frame.
+fn extract_wireframe_line_meshes(
+    source: Extract<Res<WireframeLineMeshes>>,
+    mut line_meshes: ResMut<WireframeLineMeshes>,
+) {
+    if !source.is_changed() {
+        return;
+    }
+    line_meshes.enabled = source.enabled;
+    for id in &source.removed {
+        line_meshes.meshes.remove(id);
+    }
+    for id in &source.added {
+        line_meshes
+            .meshes
+            .insert(*id, source.meshes[id].clone_weak());
+    }
+}
+
+/// Keeps the [`WireframeLineMeshes`] up to date with the meshes whose wireframe is drawn, while
+/// they are used.
+#[allow(clippy::too_many_arguments)]
+fn update_wireframe_line_meshes(
+    config: Res<WireframeConfig>,
+    render_device: Option<Res<RenderDevice>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut line_meshes: ResMut<WireframeLineMeshes>,
+    mesh_entities: Query<(&Handle<Mesh>, Option<&Wireframe>)>,
+    wireframe_only_cameras: Query<&Camera, With<WireframeOnly>>,
+) {
+    // Only the changes of this update are extracted
+    let changes = line_meshes.bypass_change_detection();
+    changes.added.clear();
+    changes.removed.clear();
+
+    let modified: HashSet<HandleId> = mesh_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+    let features = render_device.map_or(WgpuFeatures::empty(), |device| device.features());
+    let enabled = config.method.uses_line_meshes(features);
+    let used: HashSet<HandleId> = if enabled {
+        let all_meshes =
+            config.global || wireframe_only_cameras.iter().any(|camera| camera.is_active);
+        mesh_entities
+            .iter()
+            .filter(|(_, wireframe)| all_meshes || wireframe.is_some())
+            .map(|(mesh, _)| mesh.id())
+            .collect()
+    } else {
+        HashSet::default()
+    };
+
+    // Drop the line meshes of the meshes that aren't drawn as wireframes or don't exist anymore
+    let unused: Vec<HandleId> = line_meshes
+        .meshes
+        .keys()
+        .filter(|id| !used.contains(*id) || !meshes.contains(&Handle::weak(**id)))
+        .copied()
+        .collect();
+    for id in unused {
+        let line_mesh = line_meshes.meshes.remove(&id).unwrap();
+        meshes.remove(line_mesh);
+        line_meshes.removed.push(id);
+    }
+    if line_meshes.enabled != enabled {
+        line_meshes.enabled = enabled;
+    }
+
+    for id in used {
+        if line_meshes.meshes.contains_key(&id) && !modified.contains(&id) {
+            continue;
+        }
+        // Meshes that aren't loaded yet are retried on the next update
+        let lines = match meshes.get(&Handle::weak(id)) {
+            Some(mesh) if mesh.primitive_topology() == PrimitiveTopology::TriangleList => {
+                mesh.to_edge_lines()
+            }
+            _ => continue,
+        };
+        match line_meshes.meshes.get(&id) {
+            Some(line_mesh) => meshes.set_untracked(line_mesh, lines),
+            None => {
+                let line_mesh = meshes.add(lines);
+                line_meshes.meshes.insert(id, line_mesh);
+                line_meshes.added.push(id);
+            }
+        }
+    }
+}
+
+/// Colors for [`WireframeConfig::global_color`] that are quick to switch between, to keep the
+/// wireframes readable against backgrounds of different brightness and hue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum WireframeColorPreset {
+    #[default]
+    White,
+    Black,
+    Magenta,
+    Green,
+}
+
+impl WireframeColorPreset {
+    /// The color of the wireframes with this preset.
+    pub fn color(self) -> Color {
+        match self {
+            WireframeColorPreset::White => Color::WHITE,
+            WireframeColorPreset::Black => Color::BLACK,
+            WireframeColorPreset::Magenta => Color::FUCHSIA,
+            WireframeColorPreset::Green => Color::LIME_GREEN,
+        }
+    }
+
+    /// The preset after this one, wrapping around after the last one.
+    pub fn next(self) -> Self {
+        match self {
+            WireframeColorPreset::White => WireframeColorPreset::Black,
+            WireframeColorPreset::Black => WireframeColorPreset::Magenta,
+            WireframeColorPreset::Magenta => WireframeColorPreset::Green,
+            WireframeColorPreset::Green => WireframeColorPreset::White,
+        }
+    }
+}
+
+/// The colors used by the wireframe shader for an entity.
+#[derive(Component, Clone, Debug, Default, ShaderType)]
+pub struct WireframeColorsUniform {
+    /// See [`WireframeColor`] and [`WireframeConfig::global_color`].
+    pub color: Color,
+    /// See [`WireframeConfig::back_color`]. Unused if it is `None`.
+    pub back_color: Color,
+}
+
+impl From<&WireframeConfig> for WireframeColorsUniform {
+    fn from(config: &WireframeConfig) -> Self {
+        Self {
+            color: config.global_color,
+            back_color: config.back_color.unwrap_or(Color::NONE),
+        }
+    }
+}
+
+impl WireframeColorsUniform {
+    /// The colors of the wireframe of an entity with `color`.
+    pub fn new(config: &WireframeConfig, color: Option<&WireframeColor>) -> Self {
+        Self {
+            color: color.map_or(config.global_color, |color| color.0),
+            ..Self::from(config)
+        }
+    }
+}
+
+/// The bind group of the [`WireframeColorsUniform`]s of all the wireframes, bound with a dynamic
+/// offset for each entity by [`SetWireframeColorsBindGroup`].
+#[derive(Resource)]
+pub struct WireframeColorsBindGroup(BindGroup);
+
+fn queue_wireframe_colors_bind_group(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    wireframe_pipeline: Res<WireframePipeline>,
+    colors: Res<ComponentUniforms<WireframeColorsUniform>>,
+) {
+    if let Some(binding) = colors.uniforms().binding() {
+        commands.insert_resource(WireframeColorsBindGroup(render_device.create_bind_group(
+            &BindGroupDescriptor {
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: binding,
+                }],
+                label: Some("wireframe_colors_bind_group"),
+                layout: &wireframe_pipeline.colors_layout,
+            },
+        )));
+    }
 }
 
 #[derive(Resource)]
 pub struct WireframePipeline {
     mesh_pipeline: MeshPipeline,
+    colors_layout: BindGroupLayout,
     shader: Handle<Shader>,
 }
 impl FromWorld for WireframePipeline {
     fn from_world(render_world: &mut World) -> Self {
+        let colors_layout = render_world
+            .resource::<RenderDevice>()
+            .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(WireframeColorsUniform::min_size()),
+                    },
+                    count: None,
+                }],
+                label: Some("wireframe_colors_layout"),
+            });
         WireframePipeline {
             mesh_pipeline: render_world.resource::<MeshPipeline>().clone(),
+            colors_layout,
             shader: WIREFRAME_SHADER_HANDLE.typed(),
         }
     }
 }
 
+/// The key the [`WireframePipeline`] is specialized with for a mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WireframePipelineKey {
+    /// The key of the [`MeshPipeline`] the wireframe pipeline is derived from.
+    pub mesh_key: MeshPipelineKey,
+    /// Whether back faces are drawn, using [`WireframeConfig::back_color`].
+    pub back_color: bool,
+    /// Whether a line mesh is drawn instead of rasterizing triangles as lines, see
+    /// [`WireframeMethod::LineMeshes`].
+    pub line_mesh: bool,
+}
+
+impl WireframePipelineKey {
+    /// The key of a mesh drawn with the [`WireframeConfig`], `line_mesh` being whether its
+    /// line mesh is drawn.
+    pub fn new(mesh_key: MeshPipelineKey, config: &WireframeConfig, line_mesh: bool) -> Self {
+        Self {
+            mesh_key,
+            back_color: config.back_color.is_some(),
+            line_mesh,
+        }
+    }
+
+    /// The shader defs the wireframe shader is specialized with for this key.
+    pub fn shader_defs(&self) -> Vec<String> {
+        let mut shader_defs = Vec::new();
+        if self.back_color {
+            shader_defs.push(String::from("WIREFRAME_BACK_COLOR"));
+        }
+        shader_defs
+    }
+}
+
 impl SpecializedMeshPipeline for WireframePipeline {
-    type Key = MeshPipelineKey;
+    type Key = WireframePipelineKey;
 
     fn specialize(
         &self,
         key: Self::Key,
         layout: &MeshVertexBufferLayout,
     ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
-        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+        let mut descriptor = self.mesh_pipeline.specialize(key.mesh_key, layout)?;
         descriptor.vertex.shader = self.shader.clone_weak();
-        descriptor.fragment.as_mut().unwrap().shader = self.shader.clone_weak();
-        descriptor.primitive.polygon_mode = PolygonMode::Line;
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.shader = self.shader.clone_weak();
+        fragment.shader_defs.extend(key.shader_defs());
+        descriptor
+            .layout
+            .as_mut()
+            .unwrap()
+            .push(self.colors_layout.clone());
+        if !key.line_mesh {
+            descriptor.primitive.polygon_mode = PolygonMode::Line;
+        }
+        if key.back_color {
+            descriptor.primitive.cull_mode = None;
+        }
         descriptor.depth_stencil.as_mut().unwrap().bias.slope_scale = 1.0;
         Ok(descriptor)
     }
@@ -106,29 +489,41 @@ fn queue_wireframes(
     opaque_3d_draw_functions: Res<DrawFunctions<Opaque3d>>,
     render_meshes: Res<RenderAssets<Mesh>>,
     wireframe_config: Res<WireframeConfig>,
+    line_meshes: Res<WireframeLineMeshes>,
     wireframe_pipeline: Res<WireframePipeline>,
     mut pipelines: ResMut<SpecializedMeshPipelines<WireframePipeline>>,
     mut pipeline_cache: ResMut<PipelineCache>,
-    msaa: Res<Msaa>,
     mut material_meshes: ParamSet<(
         Query<(Entity, &Handle<Mesh>, &MeshUniform)>,
         Query<(Entity, &Handle<Mesh>, &MeshUniform), With<Wireframe>>,
     )>,
-    mut views: Query<(&ExtractedView, &VisibleEntities, &mut RenderPhase<Opaque3d>)>,
+    mut views: Query<(
+        &ExtractedView,
+        &Msaa,
+        &VisibleEntities,
+        &mut RenderPhase<Opaque3d>,
+        Option<&WireframeOnly>,
+    )>,
 ) {
     let draw_custom = opaque_3d_draw_functions
         .read()
         .get_id::<DrawWireframes>()
         .unwrap();
-    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
-    for (view, visible_entities, mut opaque_phase) in &mut views {
+    for (view, msaa, visible_entities, mut opaque_phase, wireframe_only) in &mut views {
+        let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
         let rangefinder = view.rangefinder3d();
 
         let add_render_phase =
             |(entity, mesh_handle, mesh_uniform): (Entity, &Handle<Mesh>, &MeshUniform)| {
-                if let Some(mesh) = render_meshes.get(mesh_handle) {
-                    let key = msaa_key
-                        | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
+                if let Some(mesh) = wireframe_mesh(&line_meshes, mesh_handle)
+                    .and_then(|handle| render_meshes.get(handle))
+                {
+                    let key = WireframePipelineKey::new(
+                        msaa_key
+                            | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology),
+                        &wireframe_config,
+                        line_meshes.enabled,
+                    );
                     let pipeline_id = pipelines.specialize(
                         &mut pipeline_cache,
                         &wireframe_pipeline,
@@ -151,7 +546,7 @@ fn queue_wireframes(
                 }
             };
 
-        if wireframe_config.global {
+        if wireframe_config.global || wireframe_only.is_some() {
             let query = material_meshes.p0();
             visible_entities
                 .entities
@@ -169,9 +564,517 @@ fn queue_wireframes(
     }
 }
 
+/// The mesh drawn for the wireframe of an entity with `mesh`: its line mesh when they are
+/// used, or `mesh` itself. `None` if the line mesh isn't made yet.
+fn wireframe_mesh<'a>(
+    line_meshes: &'a WireframeLineMeshes,
+    mesh: &'a Handle<Mesh>,
+) -> Option<&'a Handle<Mesh>> {
+    if line_meshes.enabled {
+        line_meshes.meshes.get(&mesh.id())
+    } else {
+        Some(mesh)
+    }
+}
+
 type DrawWireframes = (
     SetItemPipeline,
     SetMeshViewBindGroup<0>,
     SetMeshBindGroup<1>,
-    DrawMesh,
+    SetWireframeColorsBindGroup<2>,
+    DrawWireframeMesh,
 );
+
+/// Like [`DrawMesh`](crate::DrawMesh), but draws the line mesh of the entity's mesh when the
+/// [`WireframeLineMeshes`] are used.
+pub struct DrawWireframeMesh;
+impl EntityRenderCommand for DrawWireframeMesh {
+    type Param = (
+        SRes<RenderAssets<Mesh>>,
+        SRes<WireframeLineMeshes>,
+        SQuery<Read<Handle<Mesh>>>,
+    );
+    #[inline]
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (meshes, line_meshes, mesh_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let mesh_handle = mesh_query.get(item).unwrap();
+        let gpu_mesh = match wireframe_mesh(line_meshes.into_inner(), mesh_handle)
+            .and_then(|handle| meshes.into_inner().get(handle))
+        {
+            Some(gpu_mesh) => gpu_mesh,
+            None => return RenderCommandResult::Failure,
+        };
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, 0..1);
+            }
+            GpuBufferInfo::NonIndexed { vertex_count } => {
+                pass.draw(0..*vertex_count, 0..1);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
+/// Sets the [`WireframeColorsBindGroup`] at the bind group index `I`, with the offset of the
+/// [`WireframeColorsUniform`] of the entity.
+pub struct SetWireframeColorsBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetWireframeColorsBindGroup<I> {
+    type Param = (
+        SRes<WireframeColorsBindGroup>,
+        SQuery<Read<DynamicUniformIndex<WireframeColorsUniform>>>,
+    );
+
+    #[inline]
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (bind_group, colors_index): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let colors_index = match colors_index.get(item) {
+            Ok(index) => index,
+            Err(_) => return RenderCommandResult::Failure,
+        };
+        pass.set_bind_group(I, &bind_group.into_inner().0, &[colors_index.index()]);
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+
+    #[test]
+    fn back_color_sets_shader_def_and_uniform() {
+        let mut config = WireframeConfig::default();
+        let key = WireframePipelineKey::new(MeshPipelineKey::NONE, &config, false);
+        assert!(!key.back_color);
+        assert!(key.shader_defs().is_empty());
+
+        config.back_color = Some(Color::RED);
+        let key = WireframePipelineKey::new(MeshPipelineKey::NONE, &config, false);
+        assert!(key.back_color);
+        assert_eq!(key.shader_defs(), vec!["WIREFRAME_BACK_COLOR".to_string()]);
+        assert_eq!(WireframeColorsUniform::from(&config).back_color, Color::RED);
+    }
+
+    #[test]
+    fn color_presets_cycle_through_distinct_colors() {
+        let mut config = WireframeConfig::default();
+        assert_eq!(config.global_color, WireframeColorPreset::default().color());
+
+        let mut preset = WireframeColorPreset::default();
+        let mut colors = Vec::new();
+        for _ in 0..4 {
+            preset = preset.next();
+            config.global_color = preset.color();
+            assert_eq!(WireframeColorsUniform::from(&config).color, preset.color());
+            assert!(!colors.contains(&config.global_color));
+            colors.push(config.global_color);
+        }
+        assert_eq!(preset, WireframeColorPreset::default());
+    }
+
+    #[test]
+    fn line_meshes_follow_their_mesh() {
+        use bevy_asset::{AddAsset, AssetPlugin};
+        use bevy_core::CorePlugin;
+        use bevy_render::mesh::shape;
+
+        fn line_mesh(app: &App, mesh: &Handle<Mesh>) -> Option<Mesh> {
+            let line_meshes = app.world.resource::<WireframeLineMeshes>();
+            let line_mesh = line_meshes.meshes.get(&mesh.id())?;
+            app.world.resource::<Assets<Mesh>>().get(line_mesh).cloned()
+        }
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .insert_resource(WireframeConfig {
+                method: WireframeMethod::PolygonMode,
+                ..Default::default()
+            })
+            .init_resource::<WireframeLineMeshes>()
+            .add_system_to_stage(CoreStage::PostUpdate, update_wireframe_line_meshes);
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let quad = meshes.add(shape::Quad::default().into());
+        let cube = meshes.add(shape::Cube::default().into());
+        app.world.spawn((quad.clone(), Wireframe));
+        let cube_entity = app.world.spawn(cube.clone()).id();
+        app.update();
+        assert!(!app.world.resource::<WireframeLineMeshes>().enabled);
+        assert!(line_mesh(&app, &quad).is_none());
+
+        // Without a device supporting it, `Auto` falls back to line meshes, only for the meshes
+        // whose wireframe is drawn
+        app.world.resource_mut::<WireframeConfig>().method = WireframeMethod::Auto;
+        app.update();
+        let line_meshes = app.world.resource::<WireframeLineMeshes>();
+        assert!(line_meshes.enabled);
+        assert_eq!(line_meshes.added, vec![quad.id()]);
+        let lines = line_mesh(&app, &quad).unwrap();
+        assert_eq!(lines.primitive_topology(), PrimitiveTopology::LineList);
+        assert_eq!(lines.indices().unwrap().len(), 2 * 5);
+        assert!(line_mesh(&app, &cube).is_none());
+
+        // Nothing changes, so nothing is extracted
+        app.update();
+        let line_meshes = app.world.resource::<WireframeLineMeshes>();
+        assert!(line_meshes.added.is_empty() && line_meshes.removed.is_empty());
+
+        // Global wireframes use the line meshes of every mesh, and modified meshes update their
+        // line mesh once their asset event is sent at the end of the frame
+        app.world.resource_mut::<WireframeConfig>().global = true;
+        app.world
+            .resource_mut::<Assets<Mesh>>()
+            .set_untracked(&quad, shape::Plane::default().into());
+        app.update();
+        app.update();
+        assert!(line_mesh(&app, &cube).is_some());
+        assert_eq!(
+            line_mesh(&app, &quad).unwrap().count_vertices(),
+            Mesh::from(shape::Plane::default()).count_vertices()
+        );
+
+        // The line meshes that aren't drawn anymore are removed
+        app.world.resource_mut::<WireframeConfig>().global = false;
+        app.world.despawn(cube_entity);
+        app.update();
+        let line_meshes = app.world.resource::<WireframeLineMeshes>();
+        assert_eq!(line_meshes.removed, vec![cube.id()]);
+        assert_eq!(line_meshes.meshes.len(), 1);
+        assert!(line_mesh(&app, &cube).is_none());
+
+        app.world.resource_mut::<WireframeConfig>().method = WireframeMethod::PolygonMode;
+        app.update();
+        assert!(app
+            .world
+            .resource::<WireframeLineMeshes>()
+            .meshes
+            .is_empty());
+        assert_eq!(app.world.resource::<Assets<Mesh>>().len(), 2);
+    }
+
+    #[test]
+    fn wireframe_only_is_extracted_for_active_cameras() {
+        use bevy_ecs::schedule::{Stage, SystemStage};
+        use bevy_render::{camera::RenderTarget, MainWorld};
+
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        let solid = main_world.spawn(Camera::default()).id();
+        let wireframe = main_world
+            .spawn((
+                Camera {
+                    target: RenderTarget::Image(Handle::default()),
+                    ..Default::default()
+                },
+                WireframeOnly,
+            ))
+            .id();
+        let inactive = main_world
+            .spawn((
+                Camera {
+                    is_active: false,
+                    ..Default::default()
+                },
+                WireframeOnly,
+            ))
+            .id();
+
+        SystemStage::single(extract_wireframe_only_cameras).run(&mut render_world);
+
+        assert!(render_world.get::<WireframeOnly>(wireframe).is_some());
+        assert!(render_world.get_entity(solid).is_none());
+        assert!(render_world.get_entity(inactive).is_none());
+    }
+
+    #[test]
+    fn hidden_entities_have_no_wireframe() {
+        use bevy_asset::{AddAsset, AssetPlugin};
+        use bevy_core::CorePlugin;
+        use bevy_ecs::schedule::{Stage, SystemStage};
+        use bevy_render::{
+            prelude::{SpatialBundle, Visibility},
+            primitives::Frustum,
+            view::VisibilityPlugin,
+            MainWorld,
+        };
+        use bevy_transform::components::GlobalTransform;
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .init_resource::<WireframeConfig>()
+            .add_plugin(VisibilityPlugin);
+        app.world.spawn((
+            Camera::default(),
+            GlobalTransform::default(),
+            Frustum::default(),
+            VisibleEntities::default(),
+        ));
+        let shown = app
+            .world
+            .spawn((
+                Wireframe,
+                SpatialBundle::default(),
+                Handle::<Mesh>::default(),
+            ))
+            .id();
+        let hidden = app
+            .world
+            .spawn((
+                Wireframe,
+                Handle::<Mesh>::default(),
+                SpatialBundle {
+                    visibility: Visibility::INVISIBLE,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        app.update();
+
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        std::mem::swap(
+            &mut **render_world.resource_mut::<MainWorld>(),
+            &mut app.world,
+        );
+        SystemStage::single(extract_wireframes).run(&mut render_world);
+
+        assert!(render_world.get::<Wireframe>(shown).is_some());
+        assert!(render_world.get_entity(hidden).is_none());
+    }
+
+    #[test]
+    fn wireframe_colors_are_extracted() {
+        use bevy_asset::{AddAsset, AssetPlugin};
+        use bevy_core::CorePlugin;
+        use bevy_ecs::schedule::{Stage, SystemStage};
+        use bevy_render::{
+            prelude::SpatialBundle, primitives::Frustum, view::VisibilityPlugin, MainWorld,
+        };
+        use bevy_transform::components::GlobalTransform;
+
+        let extract = |global: bool| {
+            let mut app = App::new();
+            app.add_plugin(CorePlugin)
+                .add_plugin(AssetPlugin)
+                .add_asset::<Mesh>()
+                .insert_resource(WireframeConfig {
+                    global,
+                    global_color: Color::RED,
+                    ..Default::default()
+                })
+                .add_plugin(VisibilityPlugin);
+            app.world.spawn((
+                Camera::default(),
+                GlobalTransform::default(),
+                Frustum::default(),
+                VisibleEntities::default(),
+            ));
+            let mesh = || (Handle::<Mesh>::default(), SpatialBundle::default());
+            let colored = app
+                .world
+                .spawn((Wireframe, WireframeColor(Color::BLUE), mesh()))
+                .id();
+            let plain = app.world.spawn((Wireframe, mesh())).id();
+            let other = app.world.spawn(mesh()).id();
+            app.update();
+
+            let mut render_world = World::new();
+            render_world.init_resource::<MainWorld>();
+            std::mem::swap(
+                &mut **render_world.resource_mut::<MainWorld>(),
+                &mut app.world,
+            );
+            SystemStage::single(extract_wireframes).run(&mut render_world);
+
+            let color = |entity| {
+                render_world
+                    .get::<WireframeColorsUniform>(entity)
+                    .map(|colors| colors.color)
+            };
+            (color(colored), color(plain), color(other))
+        };
+
+        assert_eq!(extract(false), (Some(Color::BLUE), Some(Color::RED), None));
+        assert_eq!(
+            extract(true),
+            (Some(Color::BLUE), Some(Color::RED), Some(Color::RED))
+        );
+    }
+
+    /// An app drawing the wireframes in red with line meshes, on a black background.
+    fn line_mesh_app() -> App {
+        use bevy_asset::AssetPlugin;
+        use bevy_core::CorePlugin;
+        use bevy_core_pipeline::{clear_color::ClearColor, CorePipelinePlugin};
+        use bevy_render::RenderPlugin;
+        use bevy_time::TimePlugin;
+        use bevy_transform::TransformPlugin;
+        use bevy_window::{WindowPlugin, WindowSettings};
+
+        use crate::PbrPlugin;
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(TimePlugin)
+            .add_plugin(TransformPlugin)
+            .add_plugin(AssetPlugin)
+            .insert_resource(WindowSettings {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            })
+            .add_plugin(WindowPlugin)
+            .add_plugin(RenderPlugin)
+            .add_plugin(CorePipelinePlugin)
+            .add_plugin(PbrPlugin)
+            .insert_resource(Msaa { samples: 1 })
+            .insert_resource(ClearColor(Color::BLACK))
+            .insert_resource(WireframeConfig {
+                global_color: Color::RED,
+                method: WireframeMethod::LineMeshes,
+                ..Default::default()
+            })
+            .add_plugin(WireframePlugin);
+        app
+    }
+
+    /// Spawns a camera looking at a unit quad at the origin, rendering to a 9x9 image.
+    fn spawn_image_camera(app: &mut App) -> Entity {
+        use bevy_core_pipeline::core_3d::Camera3dBundle;
+        use bevy_render::{
+            camera::RenderTarget,
+            render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+            texture::{BevyDefault, Image},
+        };
+        use bevy_transform::components::Transform;
+
+        // An odd size puts the center of the middle pixel on the quad's diagonal
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 9,
+                height: 9,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::bevy_default(),
+        );
+        image.texture_descriptor.usage |=
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
+        let image = app.world.resource_mut::<Assets<Image>>().add(image);
+        app.world
+            .spawn(Camera3dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(image),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                ..Default::default()
+            })
+            .id()
+    }
+
+    fn quad(app: &mut App) -> Handle<Mesh> {
+        use bevy_render::mesh::shape;
+
+        app.world
+            .resource_mut::<Assets<Mesh>>()
+            .add(shape::Quad::default().into())
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn line_meshes_draw_the_wireframe() {
+        use bevy_math::UVec2;
+        use bevy_render::{prelude::SpatialBundle, testing::render_single_frame_pixel};
+
+        let mut app = line_mesh_app();
+        let camera = spawn_image_camera(&mut app);
+
+        // Only the wireframe is drawn, as the mesh has no material
+        let quad = quad(&mut app);
+        app.world.spawn((quad, SpatialBundle::default(), Wireframe));
+
+        // Wait for the line mesh and the pipeline to be ready
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            render_single_frame_pixel(&mut app, camera, UVec2::new(4, 4)),
+            Color::RED
+        );
+        assert_eq!(
+            render_single_frame_pixel(&mut app, camera, UVec2::new(1, 6)),
+            Color::BLACK
+        );
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn wireframe_only_cameras_draw_wireframes_without_materials() {
+        use bevy_math::UVec2;
+        use bevy_render::{prelude::SpatialBundle, testing::render_single_frame_pixel};
+
+        use crate::StandardMaterial;
+
+        let mut app = line_mesh_app();
+        let solid = spawn_image_camera(&mut app);
+        let wireframe = spawn_image_camera(&mut app);
+        app.world.entity_mut(wireframe).insert(WireframeOnly);
+
+        // The quad has no `Wireframe`, and the wireframes aren't global
+        let quad = quad(&mut app);
+        let material = app
+            .world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::BLUE,
+                unlit: true,
+                ..Default::default()
+            });
+        app.world.spawn((quad, material, SpatialBundle::default()));
+
+        // Wait for the line mesh, the material and the pipelines to be ready
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            render_single_frame_pixel(&mut app, wireframe, UVec2::new(4, 4)),
+            Color::RED
+        );
+        assert_eq!(
+            render_single_frame_pixel(&mut app, wireframe, UVec2::new(1, 6)),
+            Color::BLACK
+        );
+
+        // The other camera draws the material only
+        assert_eq!(
+            render_single_frame_pixel(&mut app, solid, UVec2::new(4, 4)),
+            Color::BLUE
+        );
+        assert_eq!(
+            render_single_frame_pixel(&mut app, solid, UVec2::new(1, 6)),
+            Color::BLUE
+        );
+    }
+}
@@ -26,6 +26,8 @@ pub trait DeserializeValue {
 trait StructLikeInfo {
     fn get_name(&self) -> &str;
     fn get_field(&self, name: &str) -> Option<&NamedField>;
+    fn get_field_at(&self, index: usize) -> Option<&NamedField>;
+    fn get_field_len(&self) -> usize;
     fn iter_fields(&self) -> Iter<'_, NamedField>;
 }
 
@@ -44,6 +46,14 @@ impl StructLikeInfo for StructInfo {
         self.field(name)
     }
 
+    fn get_field_at(&self, index: usize) -> Option<&NamedField> {
+        self.field_at(index)
+    }
+
+    fn get_field_len(&self) -> usize {
+        self.field_len()
+    }
+
     fn iter_fields(&self) -> Iter<'_, NamedField> {
         self.iter()
     }
@@ -58,6 +68,14 @@ impl StructLikeInfo for StructVariantInfo {
         self.field(name)
     }
 
+    fn get_field_at(&self, index: usize) -> Option<&NamedField> {
+        self.field_at(index)
+    }
+
+    fn get_field_len(&self) -> usize {
+        self.field_len()
+    }
+
     fn iter_fields(&self) -> Iter<'_, NamedField> {
         self.iter()
     }
@@ -192,7 +210,7 @@ impl<'a, 'de> DeserializeSeed<'de> for UntypedReflectDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_any(UntypedReflectDeserializerVisitor {
+        deserializer.deserialize_map(UntypedReflectDeserializerVisitor {
             registry: self.registry,
         })
     }
@@ -279,11 +297,11 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
             TypeInfo::Struct(struct_info) => {
                 let mut dynamic_struct = deserializer.deserialize_struct(
                     struct_info.name(),
-                    // Field names are mainly just a hint, we don't necessarily need to store and pass that data
-                    &[],
+                    struct_info.field_names(),
                     StructVisitor {
                         struct_info,
                         registry: self.registry,
+                        registration: self.registration,
                     },
                 )?;
                 dynamic_struct.set_name(struct_info.type_name().to_string());
@@ -383,6 +401,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
 struct StructVisitor<'a> {
     struct_info: &'static StructInfo,
     registry: &'a TypeRegistry,
+    registration: &'a TypeRegistration,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
@@ -398,6 +417,18 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
     {
         visit_struct(&mut map, self.struct_info, self.registry)
     }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        visit_struct_seq(
+            &mut seq,
+            self.struct_info,
+            self.registration.data::<SerializationData>(),
+            self.registry,
+        )
+    }
 }
 
 struct TupleStructVisitor<'a> {
@@ -609,21 +640,14 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
         A: EnumAccess<'de>,
     {
         let mut dynamic_enum = DynamicEnum::default();
-        let (Ident(variant_name), variant) = data.variant().unwrap();
-        let variant_info = self.enum_info.variant(&variant_name).ok_or_else(|| {
-            let names = self.enum_info.iter().map(|variant| variant.name());
-            Error::custom(format_args!(
-                "unknown variant `{}`, expected one of {:?}",
-                variant_name,
-                ExpectedValues(names.collect())
-            ))
+        let (variant_info, variant) = data.variant_seed(VariantDeserializer {
+            enum_info: self.enum_info,
         })?;
         let value: DynamicVariant = match variant_info {
             VariantInfo::Unit(..) => variant.unit_variant()?.into(),
             VariantInfo::Struct(struct_info) => variant
                 .struct_variant(
-                    // Field names are mainly just a hint, we don't necessarily need to store and pass that data
-                    &[],
+                    struct_info.field_names(),
                     StructVariantVisitor {
                         struct_info,
                         registry: self.registry,
@@ -653,11 +677,65 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                 .into(),
         };
 
-        dynamic_enum.set_variant(variant_name, value);
+        dynamic_enum.set_variant(variant_info.name(), value);
         Ok(dynamic_enum)
     }
 }
 
+/// Deserializes the identifier of an enum variant, either from its name or, for the formats that
+/// aren't self-describing, from its index.
+struct VariantDeserializer {
+    enum_info: &'static EnumInfo,
+}
+
+impl<'de> DeserializeSeed<'de> for VariantDeserializer {
+    type Value = &'static VariantInfo;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct VariantVisitor(&'static EnumInfo);
+
+        impl<'de> Visitor<'de> for VariantVisitor {
+            type Value = &'static VariantInfo;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("variant identifier")
+            }
+
+            fn visit_u64<E>(self, variant_index: u64) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.0.variant_at(variant_index as usize).ok_or_else(|| {
+                    Error::custom(format_args!(
+                        "no variant found at index `{}` on enum `{}`",
+                        variant_index,
+                        self.0.name()
+                    ))
+                })
+            }
+
+            fn visit_str<E>(self, variant_name: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.0.variant(variant_name).ok_or_else(|| {
+                    let names = self.0.iter().map(|variant| variant.name());
+                    Error::custom(format_args!(
+                        "unknown variant `{}`, expected one of {:?}",
+                        variant_name,
+                        ExpectedValues(names.collect())
+                    ))
+                })
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor(self.enum_info))
+    }
+}
+
 struct StructVariantVisitor<'a> {
     struct_info: &'static StructVariantInfo,
     registry: &'a TypeRegistry,
@@ -676,6 +754,13 @@ impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
     {
         visit_struct(&mut map, self.struct_info, self.registry)
     }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        visit_struct_seq(&mut seq, self.struct_info, None, self.registry)
+    }
 }
 
 struct TupleVariantVisitor<'a> {
@@ -778,6 +863,40 @@ where
     Ok(dynamic_struct)
 }
 
+/// Deserializes the fields of a struct serialized as a sequence, in the order of their
+/// declaration, skipping the fields ignored during serialization.
+fn visit_struct_seq<'de, T, V>(
+    seq: &mut V,
+    info: &T,
+    serialization_data: Option<&SerializationData>,
+    registry: &TypeRegistry,
+) -> Result<DynamicStruct, V::Error>
+where
+    T: StructLikeInfo,
+    V: SeqAccess<'de>,
+{
+    let mut dynamic_struct = DynamicStruct::default();
+    for index in 0..info.get_field_len() {
+        if serialization_data
+            .map(|data| data.is_ignored_field(index))
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let field = info.get_field_at(index).unwrap();
+        let registration = get_registration(field.type_id(), field.type_name(), registry)?;
+        let value = seq
+            .next_element_seed(TypedReflectDeserializer {
+                registration,
+                registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(index, &info.get_name()))?;
+        dynamic_struct.insert_boxed(field.name(), value);
+    }
+
+    Ok(dynamic_struct)
+}
+
 fn visit_tuple<'de, T, V>(
     seq: &mut V,
     info: &T,
@@ -839,13 +958,15 @@ mod tests {
     use std::any::TypeId;
     use std::f32::consts::PI;
 
+    use bincode::Options;
+
     use serde::de::DeserializeSeed;
     use serde::Deserialize;
 
     use bevy_utils::HashMap;
 
     use crate as bevy_reflect;
-    use crate::serde::{TypedReflectDeserializer, UntypedReflectDeserializer};
+    use crate::serde::{ReflectSerializer, TypedReflectDeserializer, UntypedReflectDeserializer};
     use crate::{DynamicEnum, FromReflect, Reflect, ReflectDeserialize, TypeRegistry};
 
     #[derive(Reflect, FromReflect, Debug, PartialEq)]
@@ -999,6 +1120,48 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[test]
+    fn should_deserialize_non_self_describing_binary() {
+        let mut map = HashMap::new();
+        map.insert(64, 32);
+
+        let expected = MyStruct {
+            primitive_value: 123,
+            option_value: Some(String::from("Hello world!")),
+            option_value_complex: Some(SomeStruct { foo: 123 }),
+            tuple_value: (PI, 1337),
+            list_value: vec![-2, -1, 0, 1, 2],
+            array_value: [-2, -1, 0, 1, 2],
+            map_value: map,
+            struct_value: SomeStruct { foo: 999999999 },
+            tuple_struct_value: SomeTupleStruct(String::from("Tuple Struct")),
+            unit_enum: SomeEnum::Unit,
+            newtype_enum: SomeEnum::NewType(123),
+            tuple_enum: SomeEnum::Tuple(1.23, 3.21),
+            struct_enum: SomeEnum::Struct {
+                foo: String::from("Struct variant value"),
+            },
+            custom_deserialize: CustomDeserialize {
+                value: 100,
+                inner_struct: SomeDeserializableStruct { foo: 101 },
+            },
+        };
+
+        let registry = get_registry();
+        let serializer = ReflectSerializer::new(&expected, &registry);
+        let input = bincode::DefaultOptions::new()
+            .serialize(&serializer)
+            .unwrap();
+
+        let reflect_deserializer = UntypedReflectDeserializer::new(&registry);
+        let dynamic_output = bincode::DefaultOptions::new()
+            .deserialize_seed(reflect_deserializer, &input)
+            .unwrap();
+
+        let output = <MyStruct as FromReflect>::from_reflect(dynamic_output.as_ref()).unwrap();
+        assert_eq!(expected, output);
+    }
+
     #[test]
     fn should_deserialize_value() {
         let input = r#"{
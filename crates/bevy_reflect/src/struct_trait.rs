@@ -73,6 +73,7 @@ pub struct StructInfo {
     type_name: &'static str,
     type_id: TypeId,
     fields: Box<[NamedField]>,
+    field_names: Box<[&'static str]>,
     field_indices: HashMap<&'static str, usize>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
@@ -92,12 +93,14 @@ impl StructInfo {
             .enumerate()
             .map(|(index, field)| (field.name(), index))
             .collect::<HashMap<_, _>>();
+        let field_names = fields.iter().map(|field| field.name()).collect();
 
         Self {
             name,
             type_name: std::any::type_name::<T>(),
             type_id: TypeId::of::<T>(),
             fields: fields.to_vec().into_boxed_slice(),
+            field_names,
             field_indices,
             #[cfg(feature = "documentation")]
             docs: None,
@@ -132,6 +135,11 @@ impl StructInfo {
         self.fields.iter()
     }
 
+    /// The names of the fields of this struct, in the order they are defined.
+    pub fn field_names(&self) -> &[&'static str] {
+        &self.field_names
+    }
+
     /// The total number of fields in this struct.
     pub fn field_len(&self) -> usize {
         self.fields.len()
@@ -3,7 +3,7 @@ use crate::{
     prelude::Image,
     render_asset::RenderAssets,
     render_resource::TextureView,
-    view::{ExtractedView, ExtractedWindows, VisibleEntities},
+    view::{ExtractedView, ExtractedWindows, Msaa, VisibleEntities},
     Extract,
 };
 use bevy_asset::{AssetEvent, Assets, Handle};
@@ -96,6 +96,13 @@ pub struct Camera {
     /// The "target" that this camera will render to.
     #[reflect(ignore)]
     pub target: RenderTarget,
+    /// The fraction of the physical size of its viewport this camera renders at. Below `1.0`, the
+    /// camera renders to an intermediate texture, which is then upscaled to its viewport, trading
+    /// sharpness for speed. Defaults to `1.0`.
+    ///
+    /// The intermediate texture doesn't hold what the other cameras rendered to the target, so
+    /// these cameras should clear it.
+    pub render_scale: f32,
 }
 
 impl Default for Camera {
@@ -106,6 +113,7 @@ impl Default for Camera {
             viewport: None,
             computed: Default::default(),
             target: Default::default(),
+            render_scale: 1.0,
         }
     }
 }
@@ -166,6 +174,23 @@ impl Camera {
             .or_else(|| self.physical_target_size())
     }
 
+    /// The physical size this camera renders at, which is the size of its viewport scaled by its
+    /// `render_scale`, rounded to at least one pixel. A `render_scale` of `0.0` or below renders a
+    /// single pixel.
+    #[inline]
+    pub fn physical_render_size(&self) -> Option<UVec2> {
+        let size = self.physical_viewport_size()?;
+        if self.render_scale == 1.0 {
+            return Some(size);
+        }
+        Some(
+            (size.as_vec2() * self.render_scale)
+                .round()
+                .max(Vec2::ONE)
+                .as_uvec2(),
+        )
+    }
+
     /// The full logical size of this camera's [`RenderTarget`], ignoring custom `viewport` configuration.
     /// Note that if the `viewport` field is [`Some`], this will not represent the size of the rendered area.
     /// For logic that requires the size of the actually rendered area, prefer [`Camera::logical_viewport_size`].
@@ -432,6 +457,11 @@ pub fn camera_system<T: CameraProjection + Component>(
     }
 }
 
+/// A [`Camera`] in the render world.
+///
+/// The cameras with a [`Camera::render_scale`] other than `1.0` render to an intermediate texture
+/// of their [`Camera::physical_render_size`], which is then upscaled to the viewport of their
+/// target. Their sizes and [`Viewport`] are the ones of the intermediate texture.
 #[derive(Component, Debug)]
 pub struct ExtractedCamera {
     pub target: RenderTarget,
@@ -440,6 +470,9 @@ pub struct ExtractedCamera {
     pub viewport: Option<Viewport>,
     pub render_graph: Cow<'static, str>,
     pub priority: isize,
+    /// The viewport of the target the intermediate texture of the camera is upscaled to, if its
+    /// [`Camera::render_scale`] isn't `1.0`.
+    pub upscaled_viewport: Option<Viewport>,
 }
 
 pub fn extract_cameras(
@@ -451,42 +484,197 @@ pub fn extract_cameras(
             &CameraRenderGraph,
             &GlobalTransform,
             &VisibleEntities,
+            Option<&Msaa>,
         )>,
     >,
+    msaa: Extract<Res<Msaa>>,
 ) {
-    for (entity, camera, camera_render_graph, transform, visible_entities) in query.iter() {
+    for (entity, camera, camera_render_graph, transform, visible_entities, camera_msaa) in
+        query.iter()
+    {
         if !camera.is_active {
             continue;
         }
-        if let (Some((viewport_origin, _)), Some(viewport_size), Some(target_size)) = (
+        if let (
+            Some((viewport_origin, _)),
+            Some(viewport_size),
+            Some(target_size),
+            Some(render_size),
+        ) = (
             camera.physical_viewport_rect(),
             camera.physical_viewport_size(),
             camera.physical_target_size(),
+            camera.physical_render_size(),
         ) {
             if target_size.x == 0 || target_size.y == 0 {
                 continue;
             }
+            let mut extracted_camera = ExtractedCamera {
+                target: camera.target.clone(),
+                viewport: camera.viewport.clone(),
+                physical_viewport_size: Some(viewport_size),
+                physical_target_size: Some(target_size),
+                render_graph: camera_render_graph.0.clone(),
+                priority: camera.priority,
+                upscaled_viewport: None,
+            };
+            let mut viewport = UVec4::new(
+                viewport_origin.x,
+                viewport_origin.y,
+                viewport_size.x,
+                viewport_size.y,
+            );
+            // The scaled cameras render to the whole of their intermediate texture
+            if camera.render_scale != 1.0 {
+                extracted_camera.upscaled_viewport = Some(Viewport {
+                    physical_position: viewport_origin,
+                    physical_size: viewport_size,
+                    depth: camera
+                        .viewport
+                        .as_ref()
+                        .map_or(0.0..1.0, |viewport| viewport.depth.clone()),
+                });
+                extracted_camera.viewport = camera.viewport.as_ref().map(|viewport| Viewport {
+                    physical_position: UVec2::ZERO,
+                    physical_size: render_size,
+                    depth: viewport.depth.clone(),
+                });
+                extracted_camera.physical_viewport_size = Some(render_size);
+                extracted_camera.physical_target_size = Some(render_size);
+                viewport = UVec4::new(0, 0, render_size.x, render_size.y);
+            }
             commands.get_or_spawn(entity).insert((
-                ExtractedCamera {
-                    target: camera.target.clone(),
-                    viewport: camera.viewport.clone(),
-                    physical_viewport_size: Some(viewport_size),
-                    physical_target_size: Some(target_size),
-                    render_graph: camera_render_graph.0.clone(),
-                    priority: camera.priority,
-                },
+                extracted_camera,
                 ExtractedView {
                     projection: camera.projection_matrix(),
                     transform: *transform,
-                    viewport: UVec4::new(
-                        viewport_origin.x,
-                        viewport_origin.y,
-                        viewport_size.x,
-                        viewport_size.y,
-                    ),
+                    viewport,
                 },
                 visible_entities.clone(),
+                camera_msaa.unwrap_or(&msaa).clone(),
             ));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+
+    use super::*;
+    use crate::MainWorld;
+
+    fn camera(target_size: UVec2, viewport: Option<Viewport>, render_scale: f32) -> Camera {
+        let mut camera = Camera {
+            viewport,
+            render_scale,
+            ..Default::default()
+        };
+        camera.computed.target_info = Some(RenderTargetInfo {
+            physical_size: target_size,
+            scale_factor: 1.0,
+        });
+        camera
+    }
+
+    fn viewport(position: UVec2, size: UVec2) -> Option<Viewport> {
+        Some(Viewport {
+            physical_position: position,
+            physical_size: size,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn render_size_is_rounded() {
+        let size = UVec2::new(101, 51);
+        assert_eq!(camera(size, None, 1.0).physical_render_size(), Some(size));
+        assert_eq!(
+            camera(size, None, 0.5).physical_render_size(),
+            Some(UVec2::new(51, 26))
+        );
+        assert_eq!(
+            camera(size, None, 0.3).physical_render_size(),
+            Some(UVec2::new(30, 15))
+        );
+        assert_eq!(
+            camera(size, None, 2.0).physical_render_size(),
+            Some(UVec2::new(202, 102))
+        );
+        // The viewport is scaled, not the target
+        let viewport = viewport(UVec2::new(10, 10), UVec2::new(40, 30));
+        assert_eq!(
+            camera(size, viewport, 0.5).physical_render_size(),
+            Some(UVec2::new(20, 15))
+        );
+        assert_eq!(Camera::default().physical_render_size(), None);
+    }
+
+    #[test]
+    fn render_size_is_at_least_one_pixel() {
+        let size = UVec2::new(100, 100);
+        assert_eq!(
+            camera(size, None, 0.001).physical_render_size(),
+            Some(UVec2::ONE)
+        );
+        assert_eq!(
+            camera(size, None, 0.0).physical_render_size(),
+            Some(UVec2::ONE)
+        );
+        assert_eq!(
+            camera(size, None, -1.0).physical_render_size(),
+            Some(UVec2::ONE)
+        );
+    }
+
+    #[test]
+    fn scaled_cameras_are_extracted_with_their_render_size() {
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        let target_size = UVec2::new(100, 100);
+        let mut spawn_camera = |render_scale| {
+            let mut main_world = render_world.resource_mut::<MainWorld>();
+            main_world.init_resource::<Msaa>();
+            main_world
+                .spawn((
+                    camera(
+                        target_size,
+                        viewport(UVec2::new(10, 20), UVec2::new(40, 30)),
+                        render_scale,
+                    ),
+                    CameraRenderGraph::new("graph"),
+                    GlobalTransform::default(),
+                    VisibleEntities::default(),
+                ))
+                .id()
+        };
+        let unscaled = spawn_camera(1.0);
+        let scaled = spawn_camera(0.5);
+        SystemStage::single(extract_cameras).run(&mut render_world);
+
+        let camera = render_world.get::<ExtractedCamera>(unscaled).unwrap();
+        assert!(camera.upscaled_viewport.is_none());
+        assert_eq!(camera.physical_viewport_size, Some(UVec2::new(40, 30)));
+        assert_eq!(camera.physical_target_size, Some(target_size));
+        let view = render_world.get::<ExtractedView>(unscaled).unwrap();
+        assert_eq!(view.viewport, UVec4::new(10, 20, 40, 30));
+
+        // The scaled camera renders to the whole of its intermediate texture, which is upscaled to
+        // the viewport
+        let render_size = UVec2::new(20, 15);
+        let camera = render_world.get::<ExtractedCamera>(scaled).unwrap();
+        let upscaled_viewport = camera.upscaled_viewport.as_ref().unwrap();
+        assert_eq!(upscaled_viewport.physical_position, UVec2::new(10, 20));
+        assert_eq!(upscaled_viewport.physical_size, UVec2::new(40, 30));
+        let viewport = camera.viewport.as_ref().unwrap();
+        assert_eq!(viewport.physical_position, UVec2::ZERO);
+        assert_eq!(viewport.physical_size, render_size);
+        assert_eq!(camera.physical_viewport_size, Some(render_size));
+        assert_eq!(camera.physical_target_size, Some(render_size));
+        let view = render_world.get::<ExtractedView>(scaled).unwrap();
+        assert_eq!(view.viewport, UVec4::new(0, 0, 20, 15));
+    }
+}
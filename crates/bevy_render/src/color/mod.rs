@@ -324,6 +324,36 @@ impl Color {
         )
     }
 
+    /// Returns `n` colors that are easy to tell apart, including for people with a color vision
+    /// deficiency (CVD), taken from the Okabe-Ito palette.
+    ///
+    /// The palette has 7 colors: if `n` is larger, the colors repeat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_render::color::Color;
+    /// let colors = Color::distinct_palette_cvd(3);
+    /// assert_eq!(colors[0], Color::rgb_u8(230, 159, 0)); // orange
+    /// ```
+    pub fn distinct_palette_cvd(n: usize) -> Vec<Color> {
+        const OKABE_ITO: [(u8, u8, u8); 7] = [
+            (230, 159, 0),   // orange
+            (86, 180, 233),  // sky blue
+            (0, 158, 115),   // bluish green
+            (240, 228, 66),  // yellow
+            (0, 114, 178),   // blue
+            (213, 94, 0),    // vermillion
+            (204, 121, 167), // reddish purple
+        ];
+        OKABE_ITO
+            .iter()
+            .cycle()
+            .take(n)
+            .map(|&(r, g, b)| Color::rgb_u8(r, g, b))
+            .collect()
+    }
+
     /// Get red in sRGB colorspace.
     pub fn r(&self) -> f32 {
         match self.as_rgba() {
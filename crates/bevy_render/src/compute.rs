@@ -0,0 +1,778 @@
+//! Dispatching compute shaders declared in the ECS, and reading their results back.
+
+use std::{borrow::Cow, sync::Arc};
+
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_math::UVec3;
+use bevy_utils::{tracing::error, HashMap};
+use encase::{
+    internal::{CreateFrom, WriteInto},
+    StorageBuffer, UniformBuffer,
+};
+use parking_lot::Mutex;
+use wgpu::{BufferAsyncError, Maintain, MapMode};
+
+use crate::{
+    main_graph::node::{CAMERA_DRIVER, COMPUTE_JOBS},
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferBindingType,
+        BufferDescriptor, BufferInitDescriptor, BufferUsages, CachedComputePipelineId,
+        ComputePassDescriptor, ComputePipelineDescriptor, PipelineCache, Shader, ShaderStages,
+        ShaderType, StorageTextureAccess, TextureSampleType, TextureViewDimension,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::{GpuImage, Image},
+    Extract, RenderApp, RenderStage,
+};
+
+/// Dispatches the compute shaders of the [`ComputeJob`] components, and sends the content of
+/// their buffers to the [`ComputeReadback`]s requested from them.
+#[derive(Default)]
+pub struct ComputePlugin;
+
+impl Plugin for ComputePlugin {
+    fn build(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<ComputeJobs>()
+                .init_resource::<ComputePipelines>()
+                .add_system_to_stage(RenderStage::Extract, extract_compute_jobs)
+                .add_system_to_stage(RenderStage::Queue, queue_compute_jobs)
+                .add_system_to_stage(RenderStage::Cleanup, read_back_compute_jobs);
+
+            // The jobs run before the cameras, so that their results can be used this frame
+            let mut graph = render_app.world.resource_mut::<RenderGraph>();
+            graph.add_node(COMPUTE_JOBS, ComputeJobsNode);
+            graph.add_node_edge(COMPUTE_JOBS, CAMERA_DRIVER).unwrap();
+        }
+    }
+}
+
+/// A compute shader dispatched by the [`ComputePlugin`], with the resources it binds.
+///
+/// The binding at index `i` of [`ComputeJob::bindings`] is bound at `@group(0) @binding(i)`.
+/// The job is dispatched once its pipeline is compiled and its images are loaded, then again
+/// every time the component changes, which also uploads the buffers whose content changed. The
+/// pipeline is shared by the jobs with the same shader, entry point and layout, and kept when
+/// the job changes. Set
+/// [`ComputeJob::every_frame`] to dispatch it every frame instead, with its storage buffers
+/// keeping their content between dispatches.
+///
+/// Jobs are dispatched in no particular order, before the cameras are rendered.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::UVec3;
+/// # use bevy_render::compute::{ComputeBinding, ComputeJob, ComputeReadback};
+/// # use bevy_render::render_resource::Shader;
+/// # use bevy_asset::Handle;
+/// fn spawn_job(mut commands: Commands, shader: Handle<Shader>) {
+///     let values = vec![1u32, 2, 3, 4];
+///     let mut job = ComputeJob::new(shader, "double", UVec3::new(4, 1, 1))
+///         .with_binding(ComputeBinding::storage(&values));
+///     let readback = job.read_back(0);
+///     commands.spawn((job, readback));
+/// }
+///
+/// fn print_results(readbacks: Query<&ComputeReadback>) {
+///     for readback in &readbacks {
+///         if let Some(values) = readback.try_recv_as::<Vec<u32>>() {
+///             println!("{values:?}");
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_results);
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct ComputeJob {
+    pub shader: Handle<Shader>,
+    pub shader_defs: Vec<String>,
+    /// The name of the function of the shader to run.
+    pub entry_point: Cow<'static, str>,
+    pub bindings: Vec<ComputeBinding>,
+    /// The number of workgroups dispatched along each axis.
+    pub workgroups: UVec3,
+    /// Whether the job is dispatched every frame, rather than once.
+    pub every_frame: bool,
+    /// The readbacks of each storage buffer, shared by the clones of the job.
+    readbacks: Vec<(usize, Arc<Sender<Vec<u8>>>)>,
+}
+
+impl ComputeJob {
+    /// Creates a job running `entry_point` of `shader` on `workgroups`, without bindings.
+    pub fn new(
+        shader: Handle<Shader>,
+        entry_point: impl Into<Cow<'static, str>>,
+        workgroups: UVec3,
+    ) -> Self {
+        Self {
+            shader,
+            shader_defs: Vec::new(),
+            entry_point: entry_point.into(),
+            bindings: Vec::new(),
+            workgroups,
+            every_frame: false,
+            readbacks: Vec::new(),
+        }
+    }
+
+    /// Adds `binding`, bound after the previous ones.
+    pub fn with_binding(mut self, binding: ComputeBinding) -> Self {
+        self.bindings.push(binding);
+        self
+    }
+
+    /// Dispatches the job every frame.
+    pub fn every_frame(mut self) -> Self {
+        self.every_frame = true;
+        self
+    }
+
+    /// Returns a [`ComputeReadback`] receiving the content of the storage buffer at `binding`
+    /// after each dispatch of the job.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the binding at index `binding` isn't a [`ComputeBinding::Storage`].
+    pub fn read_back(&mut self, binding: usize) -> ComputeReadback {
+        assert!(
+            matches!(self.bindings.get(binding), Some(ComputeBinding::Storage(_))),
+            "only storage buffers can be read back, binding {binding} isn't one"
+        );
+        let (sender, receiver) = async_channel::bounded(1);
+        self.readbacks.push((binding, Arc::new(sender)));
+        ComputeReadback(receiver)
+    }
+}
+
+/// A resource bound to a [`ComputeJob`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComputeBinding {
+    /// A storage buffer initialized with these bytes, which the shader can read and write.
+    Storage(Vec<u8>),
+    /// A uniform buffer holding these bytes.
+    Uniform(Vec<u8>),
+    /// An image with a float format, read by the shader with `textureLoad`.
+    Image(Handle<Image>),
+    /// An image used as a storage texture, whose usages have to include
+    /// [`TextureUsages::STORAGE_BINDING`](crate::render_resource::TextureUsages::STORAGE_BINDING).
+    StorageImage(Handle<Image>, StorageTextureAccess),
+}
+
+impl ComputeBinding {
+    /// A storage buffer initialized with `value`, laid out as the shader expects it.
+    pub fn storage<T: ShaderType + WriteInto>(value: &T) -> Self {
+        let mut buffer = StorageBuffer::new(Vec::new());
+        buffer.write(value).unwrap();
+        Self::Storage(buffer.into_inner())
+    }
+
+    /// A uniform buffer holding `value`, laid out as the shader expects it.
+    pub fn uniform<T: ShaderType + WriteInto>(value: &T) -> Self {
+        let mut buffer = UniformBuffer::new(Vec::new());
+        buffer.write(value).unwrap();
+        Self::Uniform(buffer.into_inner())
+    }
+
+    fn image(&self) -> Option<&Handle<Image>> {
+        match self {
+            ComputeBinding::Image(image) | ComputeBinding::StorageImage(image, _) => Some(image),
+            ComputeBinding::Storage(_) | ComputeBinding::Uniform(_) => None,
+        }
+    }
+}
+
+/// Receives the content of a storage buffer of a [`ComputeJob`] after it is dispatched, see
+/// [`ComputeJob::read_back`].
+///
+/// At most one result waits to be received: the buffer isn't read back again until it is.
+#[derive(Component, Clone, Debug)]
+pub struct ComputeReadback(Receiver<Vec<u8>>);
+
+impl ComputeReadback {
+    /// Returns the bytes of the buffer, if they have been read back since the last call.
+    pub fn try_recv(&self) -> Option<Vec<u8>> {
+        self.0.try_recv().ok()
+    }
+
+    /// Waits for the bytes of the buffer to be read back, or returns `None` once the job is
+    /// despawned.
+    pub async fn recv(&self) -> Option<Vec<u8>> {
+        self.0.recv().await.ok()
+    }
+
+    /// Returns the content of the buffer as a `T`, if it has been read back since the last
+    /// call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is too small to hold a `T`.
+    pub fn try_recv_as<T: ShaderType + CreateFrom>(&self) -> Option<T> {
+        self.try_recv()
+            .map(|bytes| StorageBuffer::new(bytes).create().unwrap())
+    }
+}
+
+/// The [`ComputeJob`]s extracted to the render world, by entity.
+#[derive(Resource, Default)]
+struct ComputeJobs(HashMap<Entity, PreparedComputeJob>);
+
+/// What identifies the pipeline of a [`ComputeJob`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ComputePipelineKey {
+    shader: Handle<Shader>,
+    shader_defs: Vec<String>,
+    entry_point: Cow<'static, str>,
+    layout: Vec<BindGroupLayoutEntry>,
+}
+
+/// The pipelines of the [`ComputeJob`]s, shared by the jobs with the same
+/// [`ComputePipelineKey`] and kept when the jobs change, so that they are only compiled once.
+#[derive(Resource, Default)]
+struct ComputePipelines(HashMap<ComputePipelineKey, (BindGroupLayout, CachedComputePipelineId)>);
+
+struct PreparedComputeJob {
+    job: ComputeJob,
+    /// Whether the buffer of each binding has to be uploaded again, because it changed since
+    /// the job was prepared.
+    stale_bindings: Vec<bool>,
+    /// The buffers of the buffer bindings, once the job is prepared.
+    buffers: Vec<Option<Buffer>>,
+    /// The pipeline of the job, once it is prepared.
+    pipeline: Option<(BindGroupLayout, CachedComputePipelineId)>,
+    readbacks: Vec<ComputeJobReadback>,
+    /// The bind group of the job if it is dispatched this frame.
+    bind_group: Option<BindGroup>,
+    dispatched: bool,
+}
+
+struct ComputeJobReadback {
+    binding: usize,
+    sender: Arc<Sender<Vec<u8>>>,
+    /// The buffer the storage buffer is copied to, to be mapped.
+    buffer: Buffer,
+    state: ReadbackState,
+}
+
+enum ReadbackState {
+    Idle,
+    /// The storage buffer is copied to the readback buffer this frame.
+    Copying,
+    Mapping(Arc<Mutex<Option<Result<(), BufferAsyncError>>>>),
+}
+
+impl PreparedComputeJob {
+    fn new(job: ComputeJob) -> Self {
+        Self {
+            stale_bindings: vec![true; job.bindings.len()],
+            job,
+            buffers: Vec::new(),
+            pipeline: None,
+            readbacks: Vec::new(),
+            bind_group: None,
+            dispatched: false,
+        }
+    }
+
+    /// Replaces the job by its changed version, which is dispatched again. Only the bindings
+    /// that differ are uploaded again.
+    fn set_job(&mut self, job: ComputeJob) {
+        self.stale_bindings = job
+            .bindings
+            .iter()
+            .enumerate()
+            .map(|(index, binding)| {
+                self.stale_bindings.get(index).copied().unwrap_or(true)
+                    || self.job.bindings.get(index) != Some(binding)
+            })
+            .collect();
+        self.pipeline = None;
+        self.dispatched = false;
+        self.job = job;
+    }
+
+    /// Uploads the stale buffers of the job, and looks up its pipeline, queuing it if no other
+    /// job uses it yet.
+    fn prepare(
+        &mut self,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        pipelines: &mut ComputePipelines,
+        pipeline_cache: &mut PipelineCache,
+        images: &[Option<&GpuImage>],
+    ) {
+        let label = format!("compute_job_{}", self.job.entry_point);
+        self.buffers.resize(self.job.bindings.len(), None);
+        for (index, binding) in self.job.bindings.iter().enumerate() {
+            if !self.stale_bindings[index] {
+                continue;
+            }
+            let (contents, usage) = match binding {
+                ComputeBinding::Storage(contents) => (
+                    contents,
+                    BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+                ),
+                ComputeBinding::Uniform(contents) => {
+                    (contents, BufferUsages::UNIFORM | BufferUsages::COPY_DST)
+                }
+                ComputeBinding::Image(_) | ComputeBinding::StorageImage(..) => {
+                    self.buffers[index] = None;
+                    continue;
+                }
+            };
+            match &self.buffers[index] {
+                Some(buffer)
+                    if buffer.size() == contents.len() as u64 && buffer.usage() == usage =>
+                {
+                    render_queue.write_buffer(buffer, 0, contents);
+                }
+                _ => {
+                    self.buffers[index] = Some(render_device.create_buffer_with_data(
+                        &BufferInitDescriptor {
+                            label: Some(&label),
+                            contents,
+                            usage,
+                        },
+                    ));
+                }
+            }
+        }
+        self.stale_bindings
+            .iter_mut()
+            .for_each(|stale| *stale = false);
+
+        // The readbacks are kept while they read back the same buffer, so that results being
+        // mapped aren't lost
+        let mut readbacks = std::mem::take(&mut self.readbacks);
+        self.readbacks = self
+            .job
+            .readbacks
+            .iter()
+            .map(|(binding, sender)| {
+                let size = self.buffers[*binding].as_ref().unwrap().size();
+                let kept = readbacks.iter().position(|readback| {
+                    readback.binding == *binding
+                        && Arc::ptr_eq(&readback.sender, sender)
+                        && readback.buffer.size() == size
+                });
+                match kept {
+                    Some(index) => readbacks.swap_remove(index),
+                    None => ComputeJobReadback {
+                        binding: *binding,
+                        sender: sender.clone(),
+                        buffer: render_device.create_buffer(&BufferDescriptor {
+                            label: Some(&label),
+                            size,
+                            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                            mapped_at_creation: false,
+                        }),
+                        state: ReadbackState::Idle,
+                    },
+                }
+            })
+            .collect();
+
+        let key = ComputePipelineKey {
+            shader: self.job.shader.clone_weak(),
+            shader_defs: self.job.shader_defs.clone(),
+            entry_point: self.job.entry_point.clone(),
+            layout: layout_entries(&self.job.bindings, images),
+        };
+        let (layout, pipeline) = pipelines.0.entry(key).or_insert_with_key(|key| {
+            let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(&label),
+                entries: &key.layout,
+            });
+            let pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(label.clone().into()),
+                layout: Some(vec![layout.clone()]),
+                shader: self.job.shader.clone(),
+                shader_defs: key.shader_defs.clone(),
+                entry_point: key.entry_point.clone(),
+            });
+            (layout, pipeline)
+        });
+        self.pipeline = Some((layout.clone(), *pipeline));
+    }
+}
+
+/// The layout of the bind group of a job with `bindings`, whose images are `images`.
+fn layout_entries(
+    bindings: &[ComputeBinding],
+    images: &[Option<&GpuImage>],
+) -> Vec<BindGroupLayoutEntry> {
+    bindings
+        .iter()
+        .zip(images)
+        .enumerate()
+        .map(|(index, (binding, image))| BindGroupLayoutEntry {
+            binding: index as u32,
+            visibility: ShaderStages::COMPUTE,
+            ty: match binding {
+                ComputeBinding::Storage(_) => BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                ComputeBinding::Uniform(_) => BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                ComputeBinding::Image(_) => BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: false },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                ComputeBinding::StorageImage(_, access) => BindingType::StorageTexture {
+                    access: *access,
+                    format: image.unwrap().texture_format,
+                    view_dimension: TextureViewDimension::D2,
+                },
+            },
+            count: None,
+        })
+        .collect()
+}
+
+fn extract_compute_jobs(
+    mut jobs: ResMut<ComputeJobs>,
+    query: Extract<Query<(Entity, &ComputeJob, ChangeTrackers<ComputeJob>)>>,
+) {
+    jobs.0.retain(|entity, _| query.contains(*entity));
+    for (entity, job, trackers) in &query {
+        match jobs.0.get_mut(&entity) {
+            Some(prepared) => {
+                if trackers.is_changed() {
+                    prepared.set_job(job.clone());
+                }
+            }
+            None => {
+                jobs.0.insert(entity, PreparedComputeJob::new(job.clone()));
+            }
+        }
+    }
+}
+
+fn queue_compute_jobs(
+    mut jobs: ResMut<ComputeJobs>,
+    mut pipelines: ResMut<ComputePipelines>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    gpu_images: Res<RenderAssets<Image>>,
+) {
+    for job in jobs.0.values_mut() {
+        job.bind_group = None;
+        if job.dispatched && !job.job.every_frame {
+            continue;
+        }
+        let images: Vec<_> = job
+            .job
+            .bindings
+            .iter()
+            .map(|binding| binding.image().and_then(|image| gpu_images.get(image)))
+            .collect();
+        // The layout needs the formats of the storage images
+        let loaded = job
+            .job
+            .bindings
+            .iter()
+            .zip(&images)
+            .all(|(binding, image)| binding.image().is_none() || image.is_some());
+        if !loaded {
+            continue;
+        }
+        if job.pipeline.is_none() {
+            job.prepare(
+                &render_device,
+                &render_queue,
+                &mut pipelines,
+                &mut pipeline_cache,
+                &images,
+            );
+        }
+        let (layout, pipeline) = job.pipeline.as_ref().unwrap();
+        if pipeline_cache.get_compute_pipeline(*pipeline).is_none() {
+            continue;
+        }
+
+        let entries: Vec<_> = job
+            .buffers
+            .iter()
+            .zip(&images)
+            .enumerate()
+            .map(|(index, (buffer, image))| BindGroupEntry {
+                binding: index as u32,
+                resource: match (buffer, image) {
+                    (Some(buffer), _) => buffer.as_entire_binding(),
+                    (None, image) => BindingResource::TextureView(&image.unwrap().texture_view),
+                },
+            })
+            .collect();
+        job.bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("compute_job_bind_group"),
+            layout,
+            entries: &entries,
+        }));
+        job.dispatched = true;
+        for readback in &mut job.readbacks {
+            if matches!(readback.state, ReadbackState::Idle) && !readback.sender.is_full() {
+                readback.state = ReadbackState::Copying;
+            }
+        }
+    }
+}
+
+/// Dispatches the [`ComputeJob`]s ready to run this frame, and copies the buffers they read
+/// back.
+struct ComputeJobsNode;
+
+impl Node for ComputeJobsNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let jobs = world.resource::<ComputeJobs>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        if jobs.0.values().all(|job| job.bind_group.is_none()) {
+            return Ok(());
+        }
+
+        {
+            let mut pass =
+                render_context
+                    .command_encoder
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("compute_jobs_pass"),
+                    });
+            for job in jobs.0.values() {
+                if let (Some(bind_group), Some((_, pipeline))) = (&job.bind_group, &job.pipeline) {
+                    let pipeline = pipeline_cache.get_compute_pipeline(*pipeline).unwrap();
+                    pass.set_pipeline(pipeline);
+                    pass.set_bind_group(0, bind_group, &[]);
+                    let workgroups = job.job.workgroups;
+                    pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+                }
+            }
+        }
+
+        for job in jobs.0.values() {
+            for readback in &job.readbacks {
+                if matches!(readback.state, ReadbackState::Copying) {
+                    render_context.command_encoder.copy_buffer_to_buffer(
+                        job.buffers[readback.binding].as_ref().unwrap(),
+                        0,
+                        &readback.buffer,
+                        0,
+                        readback.buffer.size(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_back_compute_jobs(render_device: Res<RenderDevice>, mut jobs: ResMut<ComputeJobs>) {
+    let mut reading = false;
+    for readback in jobs.0.values_mut().flat_map(|job| &mut job.readbacks) {
+        if matches!(readback.state, ReadbackState::Copying) {
+            let mapped = Arc::new(Mutex::new(None));
+            let result = mapped.clone();
+            render_device.map_buffer(&readback.buffer.slice(..), MapMode::Read, move |mapped| {
+                *result.lock() = Some(mapped);
+            });
+            readback.state = ReadbackState::Mapping(mapped);
+        }
+        reading |= matches!(readback.state, ReadbackState::Mapping(_));
+    }
+    if !reading {
+        return;
+    }
+
+    // Calls the callbacks of the buffers mapped since the last frame, without waiting for
+    // the others
+    render_device.poll(Maintain::Poll);
+    for readback in jobs.0.values_mut().flat_map(|job| &mut job.readbacks) {
+        let mapped = match &readback.state {
+            ReadbackState::Mapping(mapped) => mapped.lock().take(),
+            _ => continue,
+        };
+        match mapped {
+            Some(Ok(())) => {
+                let bytes = readback.buffer.slice(..).get_mapped_range().to_vec();
+                readback.buffer.unmap();
+                // The receiver may have been dropped, in which case there is no one to notify
+                let _ = readback.sender.try_send(bytes);
+                readback.state = ReadbackState::Idle;
+            }
+            Some(Err(err)) => {
+                error!("Cannot read back compute job buffer: {err}");
+                readback.state = ReadbackState::Idle;
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::schedule::{Stage, SystemStage};
+    use bevy_window::{WindowPlugin, WindowSettings};
+
+    use super::*;
+    use crate::{MainWorld, RenderPlugin};
+
+    #[test]
+    fn storage_bindings_round_trip_through_readbacks() {
+        let values = vec![1.5f32, 2.0, -3.0];
+        let mut job = ComputeJob::new(Handle::default(), "main", UVec3::ONE)
+            .with_binding(ComputeBinding::uniform(&bevy_math::Vec4::ONE))
+            .with_binding(ComputeBinding::storage(&values));
+        let readback = job.read_back(1);
+        assert!(readback.try_recv().is_none());
+
+        let bytes = match &job.bindings[1] {
+            ComputeBinding::Storage(bytes) => bytes.clone(),
+            binding => panic!("unexpected binding {binding:?}"),
+        };
+        assert_eq!(bytes.len(), 12);
+        let (binding, sender) = &job.readbacks[0];
+        assert_eq!(*binding, 1);
+        sender.try_send(bytes).unwrap();
+        assert_eq!(readback.try_recv_as::<Vec<f32>>(), Some(values));
+        assert!(readback.try_recv().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "only storage buffers can be read back")]
+    fn uniforms_cannot_be_read_back() {
+        let mut job = ComputeJob::new(Handle::default(), "main", UVec3::ONE)
+            .with_binding(ComputeBinding::uniform(&1.0f32));
+        job.read_back(0);
+    }
+
+    #[test]
+    fn changed_jobs_only_upload_changed_bindings() {
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        render_world.init_resource::<ComputeJobs>();
+        let job = ComputeJob::new(Handle::default(), "main", UVec3::ONE)
+            .with_binding(ComputeBinding::uniform(&1.0f32))
+            .with_binding(ComputeBinding::storage(&vec![1u32, 2]));
+        let entity = render_world.resource_mut::<MainWorld>().spawn(job).id();
+        let mut extract = SystemStage::single(extract_compute_jobs);
+        let prepared = |world: &World| -> Option<(Vec<bool>, bool)> {
+            let job = world.resource::<ComputeJobs>().0.get(&entity)?;
+            Some((job.stale_bindings.clone(), job.dispatched))
+        };
+
+        extract.run(&mut render_world);
+        assert_eq!(prepared(&render_world), Some((vec![true, true], false)));
+
+        // Prepare and dispatch the job, as if its pipeline was compiled
+        let mut jobs = render_world.resource_mut::<ComputeJobs>();
+        let job = jobs.0.get_mut(&entity).unwrap();
+        job.stale_bindings = vec![false, false];
+        job.dispatched = true;
+
+        // Unchanged jobs are kept as they are
+        extract.run(&mut render_world);
+        assert_eq!(prepared(&render_world), Some((vec![false, false], true)));
+
+        // Changed jobs are dispatched again, only uploading the bindings that changed
+        render_world
+            .resource_mut::<MainWorld>()
+            .get_mut::<ComputeJob>(entity)
+            .unwrap()
+            .bindings[0] = ComputeBinding::uniform(&2.0f32);
+        extract.run(&mut render_world);
+        assert_eq!(prepared(&render_world), Some((vec![true, false], false)));
+
+        render_world.resource_mut::<MainWorld>().despawn(entity);
+        extract.run(&mut render_world);
+        assert_eq!(prepared(&render_world), None);
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn dispatches_and_reads_back() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .insert_resource(WindowSettings {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            })
+            .add_plugin(WindowPlugin)
+            .add_plugin(RenderPlugin);
+        app.sub_app_mut(RenderApp)
+            .world
+            .resource_mut::<PipelineCache>()
+            .set_synchronous_compilation(true);
+        let shader = app
+            .world
+            .resource_mut::<Assets<Shader>>()
+            .add(Shader::from_wgsl(
+                "@group(0) @binding(0) var<storage, read_write> values: array<u32>;
+
+                @compute @workgroup_size(1)
+                fn double(@builtin(global_invocation_id) id: vec3<u32>) {
+                    values[id.x] = values[id.x] * 2u;
+                }",
+            ));
+        let mut job = ComputeJob::new(shader, "double", UVec3::new(4, 1, 1))
+            .with_binding(ComputeBinding::storage(&vec![1u32, 2, 3, 4]));
+        let readback = job.read_back(0);
+        let entity = app.world.spawn(job).id();
+
+        let mut frames = 0;
+        let values = loop {
+            app.update();
+            if let Some(values) = readback.try_recv_as::<Vec<u32>>() {
+                break values;
+            }
+            frames += 1;
+            assert!(frames < 100, "the buffer was never read back");
+        };
+        assert_eq!(values, [2, 4, 6, 8]);
+
+        // The job only runs again once it changes
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(readback.try_recv().is_none());
+
+        // Changing the job every frame dispatches it again, without compiling another pipeline
+        let mut frames = 0u32;
+        let values = loop {
+            let value = frames.min(3);
+            app.world.get_mut::<ComputeJob>(entity).unwrap().bindings[0] =
+                ComputeBinding::storage(&vec![value; 4]);
+            app.update();
+            match readback.try_recv_as::<Vec<u32>>() {
+                Some(values) if values == [6; 4] => break values,
+                _ => {}
+            }
+            frames += 1;
+            assert!(frames < 100, "the changed job was never read back");
+        };
+        assert_eq!(values, [6; 4]);
+        let render_world = &app.sub_app(RenderApp).world;
+        assert_eq!(render_world.resource::<ComputePipelines>().0.len(), 1);
+    }
+}
@@ -0,0 +1,494 @@
+//! Diagnostics of the draw calls and of the GPU time spent rendering each frame.
+
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH};
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+use parking_lot::Mutex;
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoder, Features, Maintain, MapMode,
+    PipelineStatisticsTypes, QuerySet, QuerySetDescriptor, QueryType, RenderPass, QUERY_SIZE,
+};
+
+use crate::{
+    render_resource::Buffer,
+    renderer::{RenderDevice, RenderQueue},
+    RenderApp,
+};
+
+/// Adds diagnostics of the rendering of each frame: the number of draw calls and triangles, and
+/// the time the GPU spends running each node of the [`RenderGraph`](crate::render_graph::RenderGraph).
+///
+/// Comparing the GPU time of a frame with its [`FrameTimeDiagnosticsPlugin::FRAME_TIME`] tells
+/// whether the frame is limited by the CPU or by the GPU.
+///
+/// - Draw calls and triangles are counted on the CPU, for the render passes begun with
+///   [`RenderContext::begin_tracked_render_pass`](crate::renderer::RenderContext::begin_tracked_render_pass).
+///   Triangles assume triangle lists, and indirect draws only count as draw calls.
+/// - GPU times use timestamp queries, and need [`Features::TIMESTAMP_QUERY`]. Each node gets a
+///   diagnostic named after it, and the whole frame is measured by
+///   [`RenderDiagnosticsPlugin::GPU_FRAME_TIME`].
+/// - [`RenderDiagnosticsPlugin::GPU_PRIMITIVES`] counts the primitives processed by the GPU,
+///   including those of indirect draws, and needs [`Features::PIPELINE_STATISTICS_QUERY`].
+///
+/// Both features are enabled when the adapter supports them with the default
+/// [`WgpuSettings`](crate::settings::WgpuSettings). The GPU results are read back
+/// asynchronously, so they lag a few frames behind and are only measured on some frames.
+///
+/// [`FrameTimeDiagnosticsPlugin::FRAME_TIME`]: bevy_diagnostic::FrameTimeDiagnosticsPlugin::FRAME_TIME
+#[derive(Default)]
+pub struct RenderDiagnosticsPlugin;
+
+impl Plugin for RenderDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let statistics = RenderStatisticsSlot::default();
+        app.insert_resource(statistics.clone())
+            .add_startup_system(Self::setup_system)
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            let recorder = RenderDiagnosticsRecorder::new(
+                render_app.world.resource::<RenderDevice>(),
+                render_app.world.resource::<RenderQueue>(),
+                statistics,
+            );
+            render_app.insert_resource(recorder);
+        }
+    }
+}
+
+impl RenderDiagnosticsPlugin {
+    pub const DRAW_CALLS: DiagnosticId =
+        DiagnosticId::from_u128(204218353011726436458745530870296373543);
+    pub const TRIANGLES: DiagnosticId =
+        DiagnosticId::from_u128(117830418469207395327062627401735930051);
+    pub const GPU_FRAME_TIME: DiagnosticId =
+        DiagnosticId::from_u128(292561047962446278530412305613960315329);
+    pub const GPU_PRIMITIVES: DiagnosticId =
+        DiagnosticId::from_u128(38929413625452290751372283089040938701);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::DRAW_CALLS, "draw_calls", 20));
+        diagnostics.add(Diagnostic::new(Self::TRIANGLES, "triangles", 20));
+        diagnostics
+            .add(Diagnostic::new(Self::GPU_FRAME_TIME, "gpu_frame_time", 20).with_suffix("ms"));
+        diagnostics.add(Diagnostic::new(Self::GPU_PRIMITIVES, "gpu_primitives", 20));
+    }
+
+    pub(crate) fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        statistics: Res<RenderStatisticsSlot>,
+        mut node_ids: Local<HashMap<Cow<'static, str>, DiagnosticId>>,
+    ) {
+        let statistics = match statistics.0.lock().take() {
+            Some(statistics) => statistics,
+            None => return,
+        };
+        diagnostics.add_measurement(Self::DRAW_CALLS, || statistics.draw_calls as f64);
+        diagnostics.add_measurement(Self::TRIANGLES, || statistics.triangles as f64);
+
+        let gpu = match statistics.gpu {
+            Some(gpu) => gpu,
+            None => return,
+        };
+        if let Some(frame_time) = gpu.frame_time {
+            diagnostics.add_measurement(Self::GPU_FRAME_TIME, || frame_time.as_secs_f64() * 1000.);
+        }
+        if let Some(primitives) = gpu.primitives {
+            diagnostics.add_measurement(Self::GPU_PRIMITIVES, || primitives as f64);
+        }
+        for (name, time) in gpu.node_times {
+            let id = *node_ids.entry(name).or_insert_with_key(|name| {
+                let id = DiagnosticId::default();
+                let name: String = format!("gpu/{name}")
+                    .chars()
+                    .take(MAX_DIAGNOSTIC_NAME_WIDTH)
+                    .collect();
+                diagnostics.add(Diagnostic::new(id, name, 20).with_suffix("ms"));
+                id
+            });
+            diagnostics.add_measurement(id, || time.as_secs_f64() * 1000.);
+        }
+    }
+}
+
+/// The statistics of a rendered frame.
+#[derive(Debug, Default)]
+pub(crate) struct RenderStatistics {
+    draw_calls: u64,
+    triangles: u64,
+    /// The results of the GPU queries of an earlier frame, if they were read back.
+    gpu: Option<GpuStatistics>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct GpuStatistics {
+    node_times: Vec<(Cow<'static, str>, Duration)>,
+    frame_time: Option<Duration>,
+    primitives: Option<u64>,
+}
+
+impl GpuStatistics {
+    /// Computes the statistics from the queries of a frame, where the node `i` of `nodes` ran
+    /// between the timestamps `2 * i` and `2 * i + 1`, counted in ticks of `timestamp_period`
+    /// nanoseconds.
+    fn from_queries(
+        nodes: Vec<Cow<'static, str>>,
+        timestamps: &[u64],
+        timestamp_period: f32,
+        pipeline_statistics: &[u64],
+    ) -> Self {
+        let ticks_to_duration = |ticks: u64| {
+            Duration::from_nanos((ticks as f64 * timestamp_period as f64).round() as u64)
+        };
+        let frame_time = match (timestamps.iter().min(), timestamps.iter().max()) {
+            (Some(start), Some(end)) => Some(ticks_to_duration(end.saturating_sub(*start))),
+            _ => None,
+        };
+        let node_times = nodes
+            .into_iter()
+            .zip(timestamps.chunks_exact(2))
+            .map(|(node, span)| (node, ticks_to_duration(span[1].saturating_sub(span[0]))))
+            .collect();
+        let primitives = if pipeline_statistics.is_empty() {
+            None
+        } else {
+            Some(pipeline_statistics.iter().sum())
+        };
+        Self {
+            node_times,
+            frame_time,
+            primitives,
+        }
+    }
+}
+
+/// Passes the [`RenderStatistics`] from the render world to the main world.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct RenderStatisticsSlot(Arc<Mutex<Option<RenderStatistics>>>);
+
+/// The draw calls and triangles of the frame being rendered.
+#[derive(Debug, Default)]
+pub(crate) struct DrawCounters {
+    draw_calls: AtomicU64,
+    triangles: AtomicU64,
+}
+
+impl DrawCounters {
+    pub(crate) fn count_draw(&self, vertices: u32, instances: u32) {
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+        self.triangles
+            .fetch_add(vertices as u64 / 3 * instances as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_indirect_draw(&self) {
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+const MAX_TIMESTAMP_QUERIES: u32 = 256;
+const MAX_PIPELINE_STATISTICS_QUERIES: u32 = 64;
+// Query results have to be resolved at offsets aligned to `QUERY_RESOLVE_BUFFER_ALIGNMENT`
+const PIPELINE_STATISTICS_OFFSET: u64 = MAX_TIMESTAMP_QUERIES as u64 * QUERY_SIZE as u64;
+const QUERY_BUFFER_SIZE: u64 =
+    PIPELINE_STATISTICS_OFFSET + MAX_PIPELINE_STATISTICS_QUERIES as u64 * QUERY_SIZE as u64;
+
+/// Records the statistics of the frames rendered by the
+/// [`RenderGraphRunner`](crate::renderer::RenderGraphRunner), inserted into the render world by
+/// the [`RenderDiagnosticsPlugin`].
+#[derive(Resource)]
+pub(crate) struct RenderDiagnosticsRecorder {
+    pub(crate) counters: DrawCounters,
+    queries: Option<GpuQueries>,
+    gpu: Option<GpuStatistics>,
+    statistics: RenderStatisticsSlot,
+}
+
+struct GpuQueries {
+    timestamps: Option<QuerySet>,
+    pipeline_statistics: Option<QuerySet>,
+    timestamp_period: f32,
+    /// Receives the query results, resolved directly into it.
+    readback_buffer: Buffer,
+    /// Set once the `readback_buffer` is mapped.
+    mapped: Arc<AtomicBool>,
+    /// The queries of the frame being rendered, if the `readback_buffer` is free to receive them.
+    recording: Option<QueryFrame>,
+    /// The queries being read back.
+    pending: Option<QueryFrame>,
+}
+
+#[derive(Default)]
+struct QueryFrame {
+    /// The nodes timed by the timestamp queries, two per node.
+    nodes: Vec<Cow<'static, str>>,
+    pipeline_statistics: u32,
+}
+
+impl RenderDiagnosticsRecorder {
+    fn new(device: &RenderDevice, queue: &RenderQueue, statistics: RenderStatisticsSlot) -> Self {
+        let features = device.features();
+        let timestamps = features.contains(Features::TIMESTAMP_QUERY).then(|| {
+            device.wgpu_device().create_query_set(&QuerySetDescriptor {
+                label: Some("render_diagnostics_timestamps"),
+                ty: QueryType::Timestamp,
+                count: MAX_TIMESTAMP_QUERIES,
+            })
+        });
+        let pipeline_statistics =
+            features
+                .contains(Features::PIPELINE_STATISTICS_QUERY)
+                .then(|| {
+                    device.wgpu_device().create_query_set(&QuerySetDescriptor {
+                        label: Some("render_diagnostics_pipeline_statistics"),
+                        ty: QueryType::PipelineStatistics(
+                            PipelineStatisticsTypes::CLIPPER_INVOCATIONS,
+                        ),
+                        count: MAX_PIPELINE_STATISTICS_QUERIES,
+                    })
+                });
+        let queries = (timestamps.is_some() || pipeline_statistics.is_some()).then(|| GpuQueries {
+            timestamps,
+            pipeline_statistics,
+            timestamp_period: queue.get_timestamp_period(),
+            readback_buffer: device.create_buffer(&BufferDescriptor {
+                label: Some("render_diagnostics_readback_buffer"),
+                size: QUERY_BUFFER_SIZE,
+                usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+            mapped: Default::default(),
+            recording: None,
+            pending: None,
+        });
+        Self {
+            counters: DrawCounters::default(),
+            queries,
+            gpu: None,
+            statistics,
+        }
+    }
+
+    /// Reads back the queries of an earlier frame if they are available, and starts recording
+    /// the queries of this frame if the previous ones aren't still being read back.
+    pub(crate) fn begin_frame(&mut self, device: &RenderDevice) {
+        let queries = match &mut self.queries {
+            Some(queries) => queries,
+            None => return,
+        };
+        if let Some(frame) = &queries.pending {
+            device.poll(Maintain::Poll);
+            if !queries.mapped.load(Ordering::Acquire) {
+                return;
+            }
+            let data = queries.readback_buffer.slice(..).get_mapped_range();
+            let read = |offset: u64, count: u32| -> Vec<u64> {
+                data[offset as usize..offset as usize + (count * QUERY_SIZE) as usize]
+                    .chunks_exact(QUERY_SIZE as usize)
+                    .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                    .collect()
+            };
+            let timestamps = read(0, frame.nodes.len() as u32 * 2);
+            let pipeline_statistics = read(PIPELINE_STATISTICS_OFFSET, frame.pipeline_statistics);
+            drop(data);
+            queries.readback_buffer.unmap();
+            queries.mapped.store(false, Ordering::Release);
+
+            let frame = queries.pending.take().unwrap();
+            self.gpu = Some(GpuStatistics::from_queries(
+                frame.nodes,
+                &timestamps,
+                queries.timestamp_period,
+                &pipeline_statistics,
+            ));
+        }
+        queries.recording = Some(QueryFrame::default());
+    }
+
+    /// Writes the timestamp before running a node, returning the index of the node's
+    /// timestamps if it is timed.
+    pub(crate) fn begin_node(
+        &mut self,
+        name: impl FnOnce() -> Cow<'static, str>,
+        encoder: &mut CommandEncoder,
+    ) -> Option<u32> {
+        let queries = self.queries.as_mut()?;
+        let timestamps = queries.timestamps.as_ref()?;
+        let frame = queries.recording.as_mut()?;
+        let index = frame.nodes.len() as u32 * 2;
+        if index + 2 > MAX_TIMESTAMP_QUERIES {
+            return None;
+        }
+        encoder.write_timestamp(timestamps, index);
+        frame.nodes.push(name());
+        Some(index)
+    }
+
+    /// Writes the timestamp after running the node whose timestamps start at `index`.
+    pub(crate) fn end_node(&self, index: u32, encoder: &mut CommandEncoder) {
+        if let Some(timestamps) = self.queries.as_ref().and_then(|q| q.timestamps.as_ref()) {
+            encoder.write_timestamp(timestamps, index + 1);
+        }
+    }
+
+    /// Begins counting the primitives of `pass`, returning whether it has to be ended with
+    /// [`RenderPass::end_pipeline_statistics_query`].
+    pub(crate) fn begin_render_pass(&mut self, pass: &mut RenderPass) -> bool {
+        let queries = match &mut self.queries {
+            Some(queries) => queries,
+            None => return false,
+        };
+        match (&queries.pipeline_statistics, &mut queries.recording) {
+            (Some(pipeline_statistics), Some(frame))
+                if frame.pipeline_statistics < MAX_PIPELINE_STATISTICS_QUERIES =>
+            {
+                pass.begin_pipeline_statistics_query(
+                    pipeline_statistics,
+                    frame.pipeline_statistics,
+                );
+                frame.pipeline_statistics += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Resolves the queries of this frame into the readback buffer.
+    pub(crate) fn resolve(&self, encoder: &mut CommandEncoder) {
+        let queries = match &self.queries {
+            Some(queries) => queries,
+            None => return,
+        };
+        let frame = match &queries.recording {
+            Some(frame) => frame,
+            None => return,
+        };
+        if let Some(timestamps) = &queries.timestamps {
+            if !frame.nodes.is_empty() {
+                let count = frame.nodes.len() as u32 * 2;
+                encoder.resolve_query_set(timestamps, 0..count, &queries.readback_buffer, 0);
+            }
+        }
+        if let Some(pipeline_statistics) = &queries.pipeline_statistics {
+            if frame.pipeline_statistics > 0 {
+                encoder.resolve_query_set(
+                    pipeline_statistics,
+                    0..frame.pipeline_statistics,
+                    &queries.readback_buffer,
+                    PIPELINE_STATISTICS_OFFSET,
+                );
+            }
+        }
+    }
+
+    /// Starts reading back the queries of the submitted frame, and sends its statistics to the
+    /// main world.
+    pub(crate) fn end_frame(&mut self, device: &RenderDevice) {
+        if let Some(queries) = &mut self.queries {
+            if let Some(frame) = queries.recording.take() {
+                let mapped = queries.mapped.clone();
+                device.map_buffer(
+                    &queries.readback_buffer.slice(..),
+                    MapMode::Read,
+                    move |result| {
+                        if result.is_ok() {
+                            mapped.store(true, Ordering::Release);
+                        }
+                    },
+                );
+                queries.pending = Some(frame);
+            }
+        }
+
+        let mut statistics = self.statistics.0.lock();
+        // Keep the GPU results the main world hasn't received yet
+        let gpu = self
+            .gpu
+            .take()
+            .or_else(|| statistics.take().and_then(|statistics| statistics.gpu));
+        *statistics = Some(RenderStatistics {
+            draw_calls: self.counters.draw_calls.swap(0, Ordering::Relaxed),
+            triangles: self.counters.triangles.swap(0, Ordering::Relaxed),
+            gpu,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_diagnostic::DiagnosticsPlugin;
+
+    use super::*;
+
+    #[test]
+    fn gpu_statistics_from_queries() {
+        let statistics = GpuStatistics::from_queries(
+            vec!["shadow_pass".into(), "main_pass".into()],
+            &[100, 300, 400, 1000],
+            2.0,
+            &[10, 20],
+        );
+        assert_eq!(
+            statistics,
+            GpuStatistics {
+                node_times: vec![
+                    ("shadow_pass".into(), Duration::from_nanos(400)),
+                    ("main_pass".into(), Duration::from_nanos(1200)),
+                ],
+                frame_time: Some(Duration::from_nanos(1800)),
+                primitives: Some(30),
+            }
+        );
+
+        let statistics = GpuStatistics::from_queries(Vec::new(), &[], 1.0, &[]);
+        assert_eq!(statistics, GpuStatistics::default());
+    }
+
+    #[test]
+    fn statistics_are_measured_in_main_world() {
+        let mut app = App::new();
+        app.add_plugin(DiagnosticsPlugin)
+            .add_plugin(RenderDiagnosticsPlugin);
+        let counters = DrawCounters::default();
+        counters.count_draw(6, 2);
+        counters.count_indirect_draw();
+        *app.world.resource::<RenderStatisticsSlot>().0.lock() = Some(RenderStatistics {
+            draw_calls: counters.draw_calls.load(Ordering::Relaxed),
+            triangles: counters.triangles.load(Ordering::Relaxed),
+            gpu: Some(GpuStatistics {
+                node_times: vec![("main_pass".into(), Duration::from_millis(2))],
+                frame_time: Some(Duration::from_millis(3)),
+                primitives: None,
+            }),
+        });
+        app.update();
+
+        let diagnostics = app.world.resource::<Diagnostics>();
+        let value = |id| diagnostics.get_measurement(id).map(|m| m.value);
+        assert_eq!(value(RenderDiagnosticsPlugin::DRAW_CALLS), Some(2.0));
+        assert_eq!(value(RenderDiagnosticsPlugin::TRIANGLES), Some(4.0));
+        assert_eq!(value(RenderDiagnosticsPlugin::GPU_FRAME_TIME), Some(3.0));
+        assert_eq!(value(RenderDiagnosticsPlugin::GPU_PRIMITIVES), None);
+        let main_pass = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.name == "gpu/main_pass")
+            .unwrap();
+        assert_eq!(main_pass.value(), Some(2.0));
+        // The statistics are only measured once
+        assert!(app
+            .world
+            .resource::<RenderStatisticsSlot>()
+            .0
+            .lock()
+            .is_none());
+    }
+}
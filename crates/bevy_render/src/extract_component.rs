@@ -32,6 +32,37 @@ impl<C: Component> DynamicUniformIndex<C> {
 ///
 /// Therefore the component is transferred from the "app world" into the "render world"
 /// in the [`RenderStage::Extract`](crate::RenderStage::Extract) step.
+///
+/// This is the hook for getting arbitrary per-entity data into rendering code: implement
+/// this trait for a component and add an [`ExtractComponentPlugin`] for it. Every frame the
+/// component is copied onto the entity with the same id in the render world, where render
+/// systems, draw functions and pipelines can query it like any other component.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::{prelude::*, query::QueryItem};
+/// # use bevy_render::color::Color;
+/// use bevy_render::extract_component::{ExtractComponent, ExtractComponentPlugin};
+///
+/// #[derive(Component, Clone)]
+/// struct DebugColor(Color);
+///
+/// impl ExtractComponent for DebugColor {
+///     type Query = &'static Self;
+///     type Filter = ();
+///
+///     fn extract_component(item: QueryItem<Self::Query>) -> Self {
+///         item.clone()
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// // `DebugColor` is now available in the render world on every entity that has it.
+/// app.add_plugin(ExtractComponentPlugin::<DebugColor>::default());
+/// ```
+///
+/// The extracted components only live for a single frame: the render world is cleared
+/// at the end of every frame, so they are re-extracted each frame.
 pub trait ExtractComponent: Component {
     /// ECS [`WorldQuery`] to fetch the components to extract.
     type Query: WorldQuery + ReadOnlyWorldQuery;
@@ -133,6 +164,9 @@ fn prepare_uniform_components<C: Component>(
 ///
 /// Therefore it sets up the [`RenderStage::Extract`](crate::RenderStage::Extract) step
 /// for the specified [`ExtractComponent`].
+///
+/// By default all matching entities are extracted. Use [`ExtractComponentPlugin::extract_visible`]
+/// to only extract entities whose [`ComputedVisibility`] is visible this frame.
 pub struct ExtractComponentPlugin<C, F = ()> {
     only_extract_visible: bool,
     marker: PhantomData<fn() -> (C, F)>,
@@ -148,6 +182,8 @@ impl<C, F> Default for ExtractComponentPlugin<C, F> {
 }
 
 impl<C, F> ExtractComponentPlugin<C, F> {
+    /// Creates a plugin that only extracts the component from entities that are visible
+    /// (see [`ComputedVisibility::is_visible`]).
     pub fn extract_visible() -> Self {
         Self {
             only_extract_visible: true,
@@ -208,3 +244,61 @@ fn extract_visible_components<C: ExtractComponent>(
     *previous_len = values.len();
     commands.insert_or_spawn_batch(values);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color::Color, MainWorld};
+    use bevy_ecs::query::QueryItem;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct DebugColor(Color);
+
+    impl ExtractComponent for DebugColor {
+        type Query = &'static Self;
+        type Filter = ();
+
+        fn extract_component(item: QueryItem<Self::Query>) -> Self {
+            item.clone()
+        }
+    }
+
+    /// Runs `extract_system` once against `main_world`, the same way the render app does.
+    fn extract<Params>(
+        main_world: World,
+        extract_system: impl IntoSystemDescriptor<Params>,
+    ) -> World {
+        let mut render_world = World::new();
+        render_world.insert_resource(MainWorld(main_world));
+        let mut stage = SystemStage::single_threaded().with_system(extract_system);
+        stage.run(&mut render_world);
+        render_world
+    }
+
+    #[test]
+    fn extracted_component_appears_in_render_world() {
+        let mut main_world = World::new();
+        let entity = main_world.spawn(DebugColor(Color::RED)).id();
+        let without = main_world.spawn_empty().id();
+
+        let render_world = extract(main_world, extract_components::<DebugColor>);
+
+        assert_eq!(
+            render_world.get::<DebugColor>(entity),
+            Some(&DebugColor(Color::RED))
+        );
+        assert!(render_world.get_entity(without).is_none());
+    }
+
+    #[test]
+    fn extract_visible_skips_hidden_entities() {
+        let mut main_world = World::new();
+        let hidden = main_world
+            .spawn((DebugColor(Color::BLUE), ComputedVisibility::INVISIBLE))
+            .id();
+
+        let render_world = extract(main_world, extract_visible_components::<DebugColor>);
+
+        assert!(render_world.get::<DebugColor>(hidden).is_none());
+    }
+}
@@ -2,11 +2,14 @@ extern crate core;
 
 pub mod camera;
 pub mod color;
+pub mod compute;
+pub mod diagnostic;
 pub mod extract_component;
 mod extract_param;
 pub mod extract_resource;
 pub mod globals;
 pub mod mesh;
+pub mod picking;
 pub mod primitives;
 pub mod rangefinder;
 pub mod render_asset;
@@ -16,6 +19,8 @@ pub mod render_resource;
 pub mod renderer;
 pub mod settings;
 mod spatial_bundle;
+#[cfg(feature = "test_utils")]
+pub mod testing;
 pub mod texture;
 pub mod view;
 
@@ -29,6 +34,7 @@ pub mod prelude {
         camera::{Camera, OrthographicProjection, PerspectiveProjection, Projection},
         color::Color,
         mesh::{shape, Mesh},
+        picking::{Pickable, PickingPlugin},
         render_resource::Shader,
         spatial_bundle::SpatialBundle,
         texture::{Image, ImageSettings},
@@ -44,10 +50,11 @@ use wgpu::TextureFormat;
 use crate::{
     camera::CameraPlugin,
     color::Color,
+    compute::ComputePlugin,
     mesh::MeshPlugin,
     primitives::{CubemapFrusta, Frustum},
     render_graph::RenderGraph,
-    render_resource::{PipelineCache, Shader, ShaderLoader},
+    render_resource::{PipelineCache, PipelineCompiled, Shader, ShaderLoader},
     renderer::{render_system, RenderInstance, RenderTextureFormat},
     texture::{BevyDefault, ImagePlugin},
     view::{ViewPlugin, WindowRenderPlugin},
@@ -121,6 +128,9 @@ impl DerefMut for MainWorld {
 pub mod main_graph {
     pub mod node {
         pub const CAMERA_DRIVER: &str = "camera_driver";
+        pub const COMPUTE_JOBS: &str = "compute_jobs";
+        pub const HIERARCHICAL_DEPTH: &str = "hierarchical_depth";
+        pub const SCREENSHOT: &str = "screenshot";
     }
 }
 
@@ -138,6 +148,7 @@ impl Plugin for RenderPlugin {
             .unwrap_or_default();
 
         app.add_asset::<Shader>()
+            .add_event::<PipelineCompiled>()
             .add_debug_asset::<Shader>()
             .init_asset_loader::<ShaderLoader>()
             .init_debug_asset_loader::<ShaderLoader>()
@@ -188,8 +199,9 @@ impl Plugin for RenderPlugin {
             let asset_server = app.world.resource::<AssetServer>().clone();
 
             let mut render_app = App::empty();
-            let mut extract_stage =
-                SystemStage::parallel().with_system(PipelineCache::extract_shaders);
+            let mut extract_stage = SystemStage::parallel()
+                .with_system(PipelineCache::extract_shaders)
+                .with_system(PipelineCache::extract_compiled_pipelines);
             // Get the ComponentId for MainWorld. This does technically 'waste' a `WorldId`, but that's probably fine
             render_app.init_resource::<MainWorld>();
             render_app.world.remove_resource::<MainWorld>();
@@ -341,6 +353,7 @@ impl Plugin for RenderPlugin {
         app.add_plugin(ValidParentCheckPlugin::<ComputedVisibility>::default())
             .add_plugin(WindowRenderPlugin)
             .add_plugin(CameraPlugin)
+            .add_plugin(ComputePlugin)
             .add_plugin(ViewPlugin)
             .add_plugin(MeshPlugin)
             // NOTE: Load this after renderer initialization so that it knows about the supported
@@ -0,0 +1,153 @@
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use bevy_asset::{Assets, Handle, HandleId};
+use bevy_utils::{FixedState, HashMap};
+use wgpu::{IndexFormat, PrimitiveTopology, VertexFormat};
+
+use super::{Mesh, MeshVertexAttributeId};
+
+/// The data making up the content of a mesh, compared by [`Mesh::content_eq`].
+type MeshContent<'a> = (
+    PrimitiveTopology,
+    Vec<(MeshVertexAttributeId, VertexFormat, &'a [u8])>,
+    Option<(IndexFormat, &'a [u8])>,
+);
+
+impl Mesh {
+    /// Returns a hash of the content of the mesh: its [`PrimitiveTopology`], the id, format and
+    /// data of each vertex attribute, and its indices.
+    ///
+    /// Meshes with the same content have the same hash, which is stable across runs. Values are
+    /// hashed bitwise, so `0.0` and `-0.0` positions are different.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = FixedState.build_hasher();
+        self.content().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns `true` if both meshes have the same content, as described in [`Mesh::content_hash`].
+    pub fn content_eq(&self, other: &Mesh) -> bool {
+        self.content() == other.content()
+    }
+
+    fn content(&self) -> MeshContent<'_> {
+        let attributes = self
+            .attributes()
+            .map(|(id, values)| (id, VertexFormat::from(values), values.get_bytes()))
+            .collect();
+        let indices = self.indices().map(|indices| {
+            (
+                IndexFormat::from(indices),
+                self.get_index_buffer_bytes().unwrap(),
+            )
+        });
+        (self.primitive_topology(), attributes, indices)
+    }
+}
+
+/// Finds and collapses [`Mesh`] assets with the same content, as described in
+/// [`Mesh::content_hash`].
+pub trait DedupeMeshes {
+    /// Returns a handle to a mesh with the same content as `mesh`, if there is one.
+    ///
+    /// If several meshes match, the one with the lowest [`HandleId`] is returned. If `mesh`
+    /// itself is one of the assets, it is matched as well.
+    fn find_duplicate(&self, mesh: &Mesh) -> Option<Handle<Mesh>>;
+
+    /// Removes every mesh with the same content as another one, keeping the mesh with the
+    /// lowest [`HandleId`] of each group.
+    ///
+    /// Returns the id of each removed mesh mapped to a handle to the mesh it duplicated. Handles
+    /// to removed meshes must be replaced using this table, e.g. on every `Handle<Mesh>`
+    /// component.
+    fn dedupe_all(&mut self) -> HashMap<HandleId, Handle<Mesh>>;
+}
+
+impl DedupeMeshes for Assets<Mesh> {
+    fn find_duplicate(&self, mesh: &Mesh) -> Option<Handle<Mesh>> {
+        let hash = mesh.content_hash();
+        self.iter()
+            .filter(|(_, other)| other.content_hash() == hash && other.content_eq(mesh))
+            .map(|(id, _)| id)
+            .min()
+            .map(|id| self.get_handle(id))
+    }
+
+    fn dedupe_all(&mut self) -> HashMap<HandleId, Handle<Mesh>> {
+        let mut groups: HashMap<u64, Vec<HandleId>> = HashMap::default();
+        for (id, mesh) in self.iter() {
+            groups.entry(mesh.content_hash()).or_default().push(id);
+        }
+
+        let mut remap = HashMap::default();
+        for mut ids in groups.into_values() {
+            ids.sort();
+            let mut kept: Vec<HandleId> = Vec::new();
+            for id in ids {
+                let mesh = self.get(&Handle::weak(id)).unwrap();
+                // Meshes with the same hash are only duplicates if their content is equal
+                match kept
+                    .iter()
+                    .find(|kept_id| self.get(&Handle::weak(**kept_id)).unwrap().content_eq(mesh))
+                {
+                    Some(kept_id) => {
+                        remap.insert(id, self.get_handle(*kept_id));
+                    }
+                    None => kept.push(id),
+                }
+            }
+        }
+
+        for id in remap.keys() {
+            self.remove(*id);
+        }
+        remap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::CorePlugin;
+
+    use super::DedupeMeshes;
+    use crate::mesh::{shape, Mesh};
+
+    #[test]
+    fn identical_cubes_dedupe_to_one() {
+        let first: Mesh = shape::Cube::default().into();
+        let second: Mesh = shape::Cube::default().into();
+        let other: Mesh = shape::Cube::new(2.0).into();
+        assert_eq!(first.content_hash(), second.content_hash());
+        assert!(first.content_eq(&second));
+        assert_ne!(first.content_hash(), other.content_hash());
+        assert!(!first.content_eq(&other));
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>();
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let first = meshes.add(first);
+        let second = meshes.add(second);
+        let other = meshes.add(other);
+        let (kept, removed) = if first.id() < second.id() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        assert_eq!(
+            meshes.find_duplicate(&shape::Cube::default().into()),
+            Some(kept.clone())
+        );
+
+        let remap = meshes.dedupe_all();
+        assert_eq!(remap.len(), 1);
+        assert_eq!(remap[&removed.id()], kept);
+        assert_eq!(meshes.len(), 2);
+        assert!(meshes.contains(&kept));
+        assert!(!meshes.contains(&removed));
+        assert!(meshes.contains(&other));
+    }
+}
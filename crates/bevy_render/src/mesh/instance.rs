@@ -0,0 +1,184 @@
+use bevy_ecs::prelude::*;
+use bevy_math::Mat4;
+use bevy_utils::HashMap;
+use bytemuck::Pod;
+
+use crate::render_resource::{Buffer, VertexBufferLayout, VertexFormat, VertexStepMode};
+
+/// Data of each instance of a mesh, read by the vertex shader of its material to draw all the
+/// entities sharing the mesh in a single instanced draw call.
+///
+/// Visible entities with the same mesh and material, and an [`InstanceData`] component of the
+/// same type, are batched together by the instancing plugins of `bevy_pbr` and `bevy_sprite`.
+/// Each instance is bound as vertex attributes stepped per instance: the 4 columns of the
+/// model matrix of the entity, then each field of the data, at consecutive shader locations.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{mesh::InstanceData, render_resource::VertexFormat};
+/// # use bytemuck::{Pod, Zeroable};
+/// #[derive(Component, Clone, Copy, Pod, Zeroable)]
+/// #[repr(C)]
+/// struct Tint {
+///     color: [f32; 4],
+///     intensity: f32,
+/// }
+///
+/// impl InstanceData for Tint {
+///     fn vertex_formats() -> Vec<VertexFormat> {
+///         vec![VertexFormat::Float32x4, VertexFormat::Float32]
+///     }
+/// }
+/// ```
+pub trait InstanceData: Component + Pod {
+    /// The formats of the fields of the data, in order.
+    ///
+    /// Their sizes have to add up to the size of the type, which can't have padding as a
+    /// [`Pod`] type anyway.
+    fn vertex_formats() -> Vec<VertexFormat>;
+}
+
+/// Returns the layout of a buffer of instances of `I` written by [`push_instance`], with the
+/// model matrix bound at `first_location`.
+///
+/// # Panics
+///
+/// Panics if the [`InstanceData::vertex_formats`] don't add up to the size of `I`.
+pub fn instance_buffer_layout<I: InstanceData>(first_location: u32) -> VertexBufferLayout {
+    let formats = I::vertex_formats();
+    let size: u64 = formats.iter().map(|format| format.size()).sum();
+    assert_eq!(
+        size,
+        std::mem::size_of::<I>() as u64,
+        "the vertex formats of {} don't match its size",
+        std::any::type_name::<I>()
+    );
+    let model = [VertexFormat::Float32x4; 4];
+    let mut layout = VertexBufferLayout::from_vertex_formats(
+        VertexStepMode::Instance,
+        model.into_iter().chain(formats),
+    );
+    for attribute in &mut layout.attributes {
+        attribute.shader_location += first_location;
+    }
+    layout
+}
+
+/// Appends an instance with the model matrix `model` and the data `instance` to `buffer`.
+pub fn push_instance<I: InstanceData>(buffer: &mut Vec<u8>, model: &Mat4, instance: &I) {
+    buffer.extend_from_slice(bytemuck::bytes_of(&model.to_cols_array()));
+    buffer.extend_from_slice(bytemuck::bytes_of(instance));
+}
+
+/// Marks the mesh entities of the render world drawn in an instanced batch, which aren't
+/// drawn on their own.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct InstancedMesh;
+
+/// A batch of instances of a mesh, drawn at once.
+pub struct InstanceBatch {
+    /// The instances, written by [`push_instance`].
+    pub buffer: Buffer,
+    pub count: u32,
+}
+
+/// The instanced batches of the render world, queued for each view with the entity of their
+/// phase item, cleared every frame.
+#[derive(Resource, Default)]
+pub struct InstanceBatches(HashMap<(Entity, Entity), InstanceBatch>);
+
+impl InstanceBatches {
+    /// Adds the `batch` drawn for the phase item of `entity` in `view`.
+    pub fn insert(&mut self, view: Entity, entity: Entity, batch: InstanceBatch) {
+        self.0.insert((view, entity), batch);
+    }
+
+    /// Returns the batch drawn for the phase item of `entity` in `view`.
+    pub fn get(&self, view: Entity, entity: Entity) -> Option<&InstanceBatch> {
+        self.0.get(&(view, entity))
+    }
+}
+
+pub(crate) fn clear_instance_batches(mut batches: ResMut<InstanceBatches>) {
+    batches.0.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3;
+    use bytemuck::{Pod, Zeroable};
+
+    use super::*;
+
+    #[derive(Component, Clone, Copy, Pod, Zeroable)]
+    #[repr(C)]
+    struct Tint {
+        color: [f32; 3],
+        layer: u32,
+    }
+
+    impl InstanceData for Tint {
+        fn vertex_formats() -> Vec<VertexFormat> {
+            vec![VertexFormat::Float32x3, VertexFormat::Uint32]
+        }
+    }
+
+    #[test]
+    fn instance_layout_follows_model() {
+        let layout = instance_buffer_layout::<Tint>(7);
+        assert_eq!(layout.step_mode, VertexStepMode::Instance);
+        assert_eq!(layout.array_stride, 64 + 16);
+        let attributes: Vec<_> = layout
+            .attributes
+            .iter()
+            .map(|attribute| {
+                (
+                    attribute.shader_location,
+                    attribute.offset,
+                    attribute.format,
+                )
+            })
+            .collect();
+        assert_eq!(
+            attributes,
+            [
+                (7, 0, VertexFormat::Float32x4),
+                (8, 16, VertexFormat::Float32x4),
+                (9, 32, VertexFormat::Float32x4),
+                (10, 48, VertexFormat::Float32x4),
+                (11, 64, VertexFormat::Float32x3),
+                (12, 76, VertexFormat::Uint32),
+            ]
+        );
+
+        let mut buffer = Vec::new();
+        let tint = Tint {
+            color: [1.0, 0.5, 0.0],
+            layer: 3,
+        };
+        push_instance(&mut buffer, &Mat4::from_translation(Vec3::X), &tint);
+        push_instance(&mut buffer, &Mat4::IDENTITY, &tint);
+        assert_eq!(buffer.len() as u64, layout.array_stride * 2);
+        let floats: &[f32] = bytemuck::cast_slice(&buffer[48..60]);
+        assert_eq!(floats, [1.0, 0.0, 0.0]);
+        assert_eq!(&buffer[76..80], &3u32.to_ne_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "don't match its size")]
+    fn mismatched_formats_panic() {
+        #[derive(Component, Clone, Copy, Pod, Zeroable)]
+        #[repr(C)]
+        struct Scale(f32, f32);
+
+        impl InstanceData for Scale {
+            fn vertex_formats() -> Vec<VertexFormat> {
+                vec![VertexFormat::Float32]
+            }
+        }
+
+        instance_buffer_layout::<Scale>(0);
+    }
+}
@@ -13,8 +13,8 @@ use bevy_derive::EnumVariantMeta;
 use bevy_ecs::system::{lifetimeless::SRes, SystemParamItem};
 use bevy_math::*;
 use bevy_reflect::TypeUuid;
-use bevy_utils::{tracing::error, Hashed};
-use std::{collections::BTreeMap, hash::Hash, iter::FusedIterator};
+use bevy_utils::{tracing::error, HashSet, Hashed};
+use std::{collections::BTreeMap, hash::Hash, iter::FusedIterator, ops::Range};
 use thiserror::Error;
 use wgpu::{
     util::BufferInitDescriptor, BufferUsages, IndexFormat, VertexAttribute, VertexFormat,
@@ -35,6 +35,8 @@ pub struct Mesh {
     /// which allows easy stable VertexBuffers (i.e. same buffer order)
     attributes: BTreeMap<MeshVertexAttributeId, MeshAttributeData>,
     indices: Option<Indices>,
+    /// The AABB stored by [`Mesh::recompute_aabb`], cleared whenever the positions may change.
+    aabb: Option<Aabb>,
 }
 
 /// Contains geometry in the form of a mesh.
@@ -83,6 +85,15 @@ impl Mesh {
     pub const ATTRIBUTE_JOINT_INDEX: MeshVertexAttribute =
         MeshVertexAttribute::new("Vertex_JointIndex", 6, VertexFormat::Uint16x4);
 
+    /// Index of the triangle the vertex belongs to, set by [`Mesh::insert_triangle_indices`].
+    pub const ATTRIBUTE_TRIANGLE_INDEX: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_TriangleIndex", 7, VertexFormat::Uint32);
+
+    /// A second set of texture coordinates for the vertex, typically laid out without overlaps
+    /// for lightmaps. Use in conjunction with [`Mesh::insert_attribute`]
+    pub const ATTRIBUTE_UV_1: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_Uv_1", 8, VertexFormat::Float32x2);
+
     /// Construct a new mesh. You need to provide a [`PrimitiveTopology`] so that the
     /// renderer knows how to treat the vertex data. Most of the time this will be
     /// [`PrimitiveTopology::TriangleList`].
@@ -91,6 +102,7 @@ impl Mesh {
             primitive_topology,
             attributes: Default::default(),
             indices: None,
+            aabb: None,
         }
     }
 
@@ -119,6 +131,7 @@ impl Mesh {
             );
         }
 
+        self.invalidate_aabb(attribute.id);
         self.attributes
             .insert(attribute.id, MeshAttributeData { attribute, values });
     }
@@ -128,9 +141,9 @@ impl Mesh {
         &mut self,
         attribute: impl Into<MeshVertexAttributeId>,
     ) -> Option<VertexAttributeValues> {
-        self.attributes
-            .remove(&attribute.into())
-            .map(|data| data.values)
+        let id = attribute.into();
+        self.invalidate_aabb(id);
+        self.attributes.remove(&id).map(|data| data.values)
     }
 
     #[inline]
@@ -153,9 +166,9 @@ impl Mesh {
         &mut self,
         id: impl Into<MeshVertexAttributeId>,
     ) -> Option<&mut VertexAttributeValues> {
-        self.attributes
-            .get_mut(&id.into())
-            .map(|data| &mut data.values)
+        let id = id.into();
+        self.invalidate_aabb(id);
+        self.attributes.get_mut(&id).map(|data| &mut data.values)
     }
 
     /// Returns an iterator that yields references to the data of each vertex attribute.
@@ -169,6 +182,7 @@ impl Mesh {
     pub fn attributes_mut(
         &mut self,
     ) -> impl Iterator<Item = (MeshVertexAttributeId, &mut VertexAttributeValues)> {
+        self.aabb = None;
         self.attributes
             .iter_mut()
             .map(|(id, data)| (*id, &mut data.values))
@@ -332,6 +346,72 @@ impl Mesh {
         }
     }
 
+    /// Consumes the mesh and returns it with its vertices duplicated so that no vertices are
+    /// shared, see [`Mesh::duplicate_vertices`].
+    #[must_use]
+    pub fn with_duplicated_vertices(mut self) -> Self {
+        self.duplicate_vertices();
+        self
+    }
+
+    /// Sets the [`Mesh::ATTRIBUTE_TRIANGLE_INDEX`] of each vertex to the index of its triangle,
+    /// so shaders can tell triangles apart.
+    ///
+    /// # Panics
+    /// Panics if [`Indices`] are set or if the mesh has any other topology than
+    /// [`PrimitiveTopology::TriangleList`], as vertices must belong to a single triangle.
+    /// Consider calling [`Mesh::duplicate_vertices`] first.
+    pub fn insert_triangle_indices(&mut self) {
+        assert!(self.indices().is_none(), "`insert_triangle_indices` can't work on indexed geometry. Consider calling `Mesh::duplicate_vertices`.");
+
+        assert!(
+            matches!(self.primitive_topology, PrimitiveTopology::TriangleList),
+            "`insert_triangle_indices` can only work on `TriangleList`s"
+        );
+
+        let triangle_indices: Vec<u32> = (0..self.count_vertices() as u32)
+            .map(|vertex| vertex / 3)
+            .collect();
+        self.insert_attribute(Mesh::ATTRIBUTE_TRIANGLE_INDEX, triangle_indices);
+    }
+
+    /// Returns a [`PrimitiveTopology::LineList`] mesh with a line for each edge of the
+    /// triangles of this mesh, sharing its vertices and their attributes.
+    ///
+    /// Edges shared by two triangles through the same vertices are only drawn once.
+    ///
+    /// # Panics
+    /// Panics if the mesh has any other topology than [`PrimitiveTopology::TriangleList`].
+    pub fn to_edge_lines(&self) -> Mesh {
+        assert!(
+            matches!(self.primitive_topology, PrimitiveTopology::TriangleList),
+            "`to_edge_lines` can only work on `TriangleList`s"
+        );
+
+        let triangle_indices: Vec<u32> = match &self.indices {
+            Some(indices) => indices.iter().map(|index| index as u32).collect(),
+            None => (0..self.count_vertices() as u32).collect(),
+        };
+        let mut edges = HashSet::default();
+        let mut line_indices = Vec::with_capacity(triangle_indices.len() * 2);
+        for triangle in triangle_indices.chunks_exact(3) {
+            for (a, b) in [
+                (triangle[0], triangle[1]),
+                (triangle[1], triangle[2]),
+                (triangle[2], triangle[0]),
+            ] {
+                if edges.insert((a.min(b), a.max(b))) {
+                    line_indices.extend([a, b]);
+                }
+            }
+        }
+
+        let mut mesh = self.clone();
+        mesh.primitive_topology = PrimitiveTopology::LineList;
+        mesh.set_indices(Some(Indices::U32(line_indices)));
+        mesh
+    }
+
     /// Calculates the [`Mesh::ATTRIBUTE_NORMAL`] of a mesh.
     ///
     /// # Panics
@@ -376,25 +456,155 @@ impl Mesh {
         if let Some(VertexAttributeValues::Float32x3(values)) =
             self.attribute(Mesh::ATTRIBUTE_POSITION)
         {
-            let mut minimum = VEC3_MAX;
-            let mut maximum = VEC3_MIN;
-            for p in values {
-                minimum = minimum.min(Vec3::from_slice(p));
-                maximum = maximum.max(Vec3::from_slice(p));
+            return aabb_from_positions(values.iter());
+        }
+
+        None
+    }
+
+    /// Computes the Axis-Aligned Bounding Box of the mesh vertices in model space, like
+    /// [`Mesh::compute_aabb`], and stores it on the mesh.
+    ///
+    /// Call this after editing the positions of a mesh at runtime: when the mesh asset is
+    /// modified, the stored AABB is copied to the [`Aabb`] component of every entity using the
+    /// mesh, during [`VisibilitySystems::CalculateBounds`](crate::view::VisibilitySystems::CalculateBounds).
+    /// The stored AABB is cleared whenever the positions are accessed mutably or replaced.
+    pub fn recompute_aabb(&mut self) -> Option<Aabb> {
+        self.aabb = self.compute_aabb();
+        self.aabb.clone()
+    }
+
+    /// Returns the AABB stored by [`Mesh::recompute_aabb`], if the positions haven't changed since.
+    pub fn aabb(&self) -> Option<&Aabb> {
+        self.aabb.as_ref()
+    }
+
+    fn invalidate_aabb(&mut self, id: MeshVertexAttributeId) {
+        if id == Mesh::ATTRIBUTE_POSITION.id {
+            self.aabb = None;
+        }
+    }
+
+    /// Compute the Axis-Aligned Bounding Box in model space of only the vertices referenced by
+    /// `index_range`.
+    ///
+    /// For indexed meshes `index_range` is a range into the [`Indices`], matching the index range
+    /// that would be passed to an indexed draw call. For non-indexed meshes it is a range of vertices.
+    ///
+    /// Returns `None` if the range is empty or out of bounds, or if the mesh has no positions.
+    pub fn compute_aabb_range(&self, index_range: Range<usize>) -> Option<Aabb> {
+        let values = match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(values)) => values,
+            _ => return None,
+        };
+
+        match &self.indices {
+            Some(indices) => {
+                if index_range.end > indices.len() {
+                    return None;
+                }
+                let positions = indices
+                    .iter()
+                    .skip(index_range.start)
+                    .take(index_range.len())
+                    .map(|index| values.get(index))
+                    .collect::<Option<Vec<_>>>()?;
+                aabb_from_positions(positions.into_iter())
             }
-            if minimum.x != std::f32::MAX
-                && minimum.y != std::f32::MAX
-                && minimum.z != std::f32::MAX
-                && maximum.x != std::f32::MIN
-                && maximum.y != std::f32::MIN
-                && maximum.z != std::f32::MIN
-            {
-                return Some(Aabb::from_min_max(minimum, maximum));
+            None => aabb_from_positions(values.get(index_range)?.iter()),
+        }
+    }
+
+    /// Casts a ray in model space against the triangles of the mesh, returning the closest hit.
+    ///
+    /// Only the meshes with a [`PrimitiveTopology::TriangleList`] or
+    /// [`PrimitiveTopology::TriangleStrip`] topology, and with [`Mesh::ATTRIBUTE_POSITION`] as
+    /// [`VertexAttributeValues::Float32x3`], can be hit. Both faces of the triangles are hit.
+    pub fn intersect_ray(&self, ray: Ray) -> Option<MeshRayHit> {
+        let positions = match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => return None,
+        };
+        let vertex_count = match &self.indices {
+            Some(indices) => indices.len(),
+            None => positions.len(),
+        };
+        let vertex_index = |index: usize| match &self.indices {
+            Some(Indices::U16(indices)) => indices[index] as usize,
+            Some(Indices::U32(indices)) => indices[index] as usize,
+            None => index,
+        };
+        let (triangle_count, triangle_start): (usize, fn(usize) -> usize) =
+            match self.primitive_topology {
+                PrimitiveTopology::TriangleList => (vertex_count / 3, |triangle| triangle * 3),
+                PrimitiveTopology::TriangleStrip => {
+                    (vertex_count.saturating_sub(2), |triangle| triangle)
+                }
+                _ => return None,
+            };
+
+        let mut closest: Option<MeshRayHit> = None;
+        for triangle_index in 0..triangle_count {
+            let start = triangle_start(triangle_index);
+            let [a, b, c] = [start, start + 1, start + 2].map(|index| {
+                positions
+                    .get(vertex_index(index))
+                    .map_or(Vec3::NAN, |position| Vec3::from(*position))
+            });
+            let distance = match ray.intersect_triangle(a, b, c) {
+                Some(distance) => distance,
+                None => continue,
+            };
+            if closest.map_or(false, |closest| closest.distance <= distance) {
+                continue;
             }
+            let mut normal = (b - a).cross(c - a).normalize();
+            if normal.dot(ray.direction) > 0.0 {
+                normal = -normal;
+            }
+            closest = Some(MeshRayHit {
+                distance,
+                position: ray.get_point(distance),
+                normal,
+                triangle_index,
+            });
         }
+        closest
+    }
+}
 
-        None
+/// The closest intersection of a [`Ray`] with the triangles of a [`Mesh`], returned by
+/// [`Mesh::intersect_ray`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshRayHit {
+    /// Distance along the ray, in units of the length of its direction
+    pub distance: f32,
+    /// Position of the hit, in model space
+    pub position: Vec3,
+    /// Normalized normal of the hit triangle, in model space, facing the origin of the ray
+    pub normal: Vec3,
+    /// Index of the hit triangle in the mesh
+    pub triangle_index: usize,
+}
+
+fn aabb_from_positions<'a>(positions: impl Iterator<Item = &'a [f32; 3]>) -> Option<Aabb> {
+    let mut minimum = VEC3_MAX;
+    let mut maximum = VEC3_MIN;
+    for p in positions {
+        minimum = minimum.min(Vec3::from_slice(p));
+        maximum = maximum.max(Vec3::from_slice(p));
+    }
+    if minimum.x != std::f32::MAX
+        && minimum.y != std::f32::MAX
+        && minimum.z != std::f32::MAX
+        && maximum.x != std::f32::MIN
+        && maximum.y != std::f32::MIN
+        && maximum.z != std::f32::MIN
+    {
+        return Some(Aabb::from_min_max(minimum, maximum));
     }
+
+    None
 }
 
 #[derive(Debug, Clone)]
@@ -1004,7 +1214,9 @@ fn generate_tangents_for_mesh(mesh: &Mesh) -> Result<Vec<[f32; 4]>, GenerateTang
 
 #[cfg(test)]
 mod tests {
-    use super::Mesh;
+    use super::{Mesh, VertexAttributeValues};
+    use crate::{mesh::shape, primitives::Aabb};
+    use bevy_math::Vec3;
     use wgpu::PrimitiveTopology;
 
     #[test]
@@ -1013,4 +1225,93 @@ mod tests {
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0, 0.0]]);
     }
+
+    #[test]
+    fn compute_aabb_range_of_half_a_cube() {
+        let mesh = Mesh::from(shape::Cube::new(2.0));
+        let indices = mesh.indices().unwrap().len();
+
+        // The first face of the cube is the top (+Z) face.
+        assert_eq!(
+            mesh.compute_aabb_range(0..6),
+            Some(Aabb::from_min_max(
+                Vec3::new(-1.0, -1.0, 1.0),
+                Vec3::new(1.0, 1.0, 1.0)
+            ))
+        );
+        // The top and bottom faces alone already span the whole cube.
+        assert_eq!(mesh.compute_aabb_range(0..indices / 2), mesh.compute_aabb());
+        // The last face is the bottom (-Y) face.
+        assert_eq!(
+            mesh.compute_aabb_range(indices - 6..indices),
+            Some(Aabb::from_min_max(
+                Vec3::new(-1.0, -1.0, -1.0),
+                Vec3::new(1.0, -1.0, 1.0)
+            ))
+        );
+
+        assert_eq!(mesh.compute_aabb_range(0..0), None);
+        assert_eq!(mesh.compute_aabb_range(0..indices + 3), None);
+    }
+
+    #[test]
+    fn compute_aabb_range_non_indexed() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, -4.0],
+                [2.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0],
+            ],
+        );
+
+        assert_eq!(
+            mesh.compute_aabb_range(0..3),
+            Some(Aabb::from_min_max(Vec3::ZERO, Vec3::new(1.0, 1.0, 0.0)))
+        );
+        assert_eq!(mesh.compute_aabb_range(4..7), None);
+    }
+
+    #[test]
+    fn edge_lines_share_vertices_and_skip_shared_edges() {
+        let quad = Mesh::from(shape::Quad::default());
+        let lines = quad.to_edge_lines();
+        assert_eq!(lines.primitive_topology(), PrimitiveTopology::LineList);
+        assert_eq!(lines.count_vertices(), quad.count_vertices());
+        // 4 sides and the diagonal shared by both triangles
+        assert_eq!(lines.indices().unwrap().len(), 2 * 5);
+
+        let unindexed = quad.with_duplicated_vertices().to_edge_lines();
+        assert_eq!(unindexed.indices().unwrap().len(), 2 * 6);
+    }
+
+    #[test]
+    fn duplicated_vertices_get_triangle_indices() {
+        let cube = Mesh::from(shape::Cube::default());
+        let triangle_count = cube.indices().unwrap().len() / 3;
+
+        let mut mesh = cube.with_duplicated_vertices();
+        assert!(mesh.indices().is_none());
+        assert_eq!(mesh.count_vertices(), 3 * triangle_count);
+
+        mesh.insert_triangle_indices();
+        match mesh.attribute(Mesh::ATTRIBUTE_TRIANGLE_INDEX) {
+            Some(VertexAttributeValues::Uint32(indices)) => {
+                assert_eq!(indices.len(), 3 * triangle_count);
+                assert_eq!(&indices[..7], &[0, 0, 0, 1, 1, 1, 2]);
+                assert_eq!(indices.last(), Some(&(triangle_count as u32 - 1)));
+            }
+            _ => panic!("triangle indices should be `Uint32` values"),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn triangle_indices_need_unindexed_mesh() {
+        Mesh::from(shape::Cube::default()).insert_triangle_indices();
+    }
 }
@@ -1,12 +1,18 @@
+mod dedupe;
+mod instance;
 #[allow(clippy::module_inception)]
 mod mesh;
+mod pending;
 /// Generation for some primitive shape meshes.
 pub mod shape;
 
+pub use dedupe::*;
+pub use instance::*;
 pub use mesh::*;
+pub use pending::*;
 
-use crate::render_asset::RenderAssetPlugin;
-use bevy_app::{App, Plugin};
+use crate::{render_asset::RenderAssetPlugin, RenderApp, RenderStage};
+use bevy_app::{App, CoreStage, Plugin};
 use bevy_asset::AddAsset;
 
 /// Adds the [`Mesh`] as an asset and makes sure that they are extracted and prepared for the GPU.
@@ -17,6 +23,13 @@ impl Plugin for MeshPlugin {
         app.add_asset::<Mesh>()
             .add_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_type::<skinning::SkinnedMesh>()
-            .add_plugin(RenderAssetPlugin::<Mesh>::default());
+            .add_plugin(RenderAssetPlugin::<Mesh>::default())
+            .add_system_to_stage(CoreStage::PreUpdate, insert_pending_meshes);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<InstanceBatches>()
+                .add_system_to_stage(RenderStage::Cleanup, clear_instance_batches);
+        }
     }
 }
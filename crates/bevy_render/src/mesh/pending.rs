@@ -0,0 +1,114 @@
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_tasks::{AsyncComputeTaskPool, Task};
+use futures_lite::future;
+
+use super::Mesh;
+
+impl Mesh {
+    /// Generates a mesh from a shape description, such as one of the primitives in
+    /// [`shape`](super::shape), on the [`AsyncComputeTaskPool`].
+    ///
+    /// Use this instead of converting the shape directly for heavy shapes, like an
+    /// [`Icosphere`](super::shape::Icosphere) with many subdivisions, to avoid blocking the
+    /// thread adding the mesh. Add the returned task to an entity as a [`PendingMesh`] to
+    /// have the mesh added to [`Assets<Mesh>`] and its handle inserted on the entity once
+    /// generation is done.
+    pub fn generate_async<S: Into<Mesh> + Send + 'static>(shape: S) -> Task<Mesh> {
+        AsyncComputeTaskPool::get().spawn(async move { shape.into() })
+    }
+}
+
+/// A [`Mesh`] that is being generated, usually by [`Mesh::generate_async`].
+///
+/// When the task completes, the mesh is added to [`Assets<Mesh>`] and this component is
+/// replaced by the mesh's [`Handle`](bevy_asset::Handle) during [`CoreStage::PreUpdate`](bevy_app::CoreStage::PreUpdate).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::mesh::{shape, Mesh, PendingMesh};
+/// fn spawn_sphere(mut commands: Commands) {
+///     let sphere = shape::Icosphere {
+///         radius: 1.0,
+///         subdivisions: 40,
+///     };
+///     commands.spawn(PendingMesh(Mesh::generate_async(sphere)));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_sphere);
+/// ```
+#[derive(Component)]
+pub struct PendingMesh(pub Task<Mesh>);
+
+/// Adds the meshes of finished [`PendingMesh`] tasks to [`Assets<Mesh>`], replacing the
+/// component with the mesh's handle.
+pub fn insert_pending_meshes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(Entity, &mut PendingMesh)>,
+) {
+    for (entity, mut pending) in &mut query {
+        if let Some(mesh) = future::block_on(future::poll_once(&mut pending.0)) {
+            commands
+                .entity(entity)
+                .insert(meshes.add(mesh))
+                .remove::<PendingMesh>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets, Handle};
+    use bevy_core::CorePlugin;
+    use bevy_tasks::TaskPool;
+
+    use super::{insert_pending_meshes, PendingMesh};
+    use crate::mesh::{shape, Mesh};
+
+    #[test]
+    fn generate_async_yields_mesh() {
+        bevy_tasks::AsyncComputeTaskPool::init(TaskPool::default);
+        let shape = shape::Icosphere {
+            radius: 2.0,
+            subdivisions: 8,
+        };
+        let expected = Mesh::from(shape);
+
+        let mesh = futures_lite::future::block_on(Mesh::generate_async(shape));
+        assert_eq!(mesh.count_vertices(), expected.count_vertices());
+        assert_eq!(mesh.compute_aabb(), expected.compute_aabb());
+        assert!(mesh.indices().map_or(false, |indices| indices.len() > 0));
+    }
+
+    #[test]
+    fn finished_pending_mesh_is_replaced_by_handle() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_system(insert_pending_meshes);
+        let entity = app
+            .world
+            .spawn(PendingMesh(Mesh::generate_async(shape::Torus::default())))
+            .id();
+
+        // Generation runs on another thread, so wait for it to be picked up.
+        let mut handle = None;
+        for _ in 0..1000 {
+            app.update();
+            handle = app.world.get::<Handle<Mesh>>(entity).cloned();
+            if handle.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let handle = handle.expect("the mesh should have been generated");
+        assert!(app.world.get::<PendingMesh>(entity).is_none());
+        let mesh = app.world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        assert_eq!(
+            mesh.count_vertices(),
+            Mesh::from(shape::Torus::default()).count_vertices()
+        );
+    }
+}
@@ -31,6 +31,19 @@ impl Default for Capsule {
     }
 }
 
+impl Capsule {
+    /// The number of vertices and indices of the mesh generated from this capsule.
+    pub const fn counts(&self) -> (usize, usize) {
+        // Both caps have a vertex per longitude, and each latitude and ring a loop of
+        // `longitudes + 1` vertices
+        let lats_and_rings = self.latitudes / 2 * 2 + self.rings;
+        (
+            2 * self.longitudes + (self.longitudes + 1) * lats_and_rings,
+            6 * self.longitudes * lats_and_rings,
+        )
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 /// Manner in which UV coordinates are distributed vertically.
 pub enum CapsuleUvProfile {
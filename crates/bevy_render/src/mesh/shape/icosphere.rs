@@ -20,6 +20,16 @@ impl Default for Icosphere {
     }
 }
 
+impl Icosphere {
+    /// The number of vertices and indices of the mesh generated from this sphere.
+    ///
+    /// Each of the 20 faces of the icosahedron is split into `(subdivisions + 1)^2` triangles.
+    pub const fn counts(&self) -> (usize, usize) {
+        let subdivided = (self.subdivisions + 1) * (self.subdivisions + 1);
+        (10 * subdivided + 2, 60 * subdivided)
+    }
+}
+
 impl From<Icosphere> for Mesh {
     fn from(sphere: Icosphere) -> Self {
         if sphere.subdivisions >= 80 {
@@ -10,6 +10,11 @@ impl Cube {
     pub fn new(size: f32) -> Cube {
         Cube { size }
     }
+
+    /// The number of vertices and indices of the mesh generated from this cube.
+    pub const fn counts(&self) -> (usize, usize) {
+        (24, 36)
+    }
 }
 
 impl Default for Cube {
@@ -49,6 +54,11 @@ impl Box {
             min_z: -z_length / 2.0,
         }
     }
+
+    /// The number of vertices and indices of the mesh generated from this box.
+    pub const fn counts(&self) -> (usize, usize) {
+        (24, 36)
+    }
 }
 
 impl Default for Box {
@@ -137,6 +147,11 @@ impl Quad {
     pub fn flipped(size: Vec2) -> Self {
         Self { size, flip: true }
     }
+
+    /// The number of vertices and indices of the mesh generated from this quad.
+    pub const fn counts(&self) -> (usize, usize) {
+        (4, 6)
+    }
 }
 
 impl From<Quad> for Mesh {
@@ -180,6 +195,13 @@ impl Default for Plane {
     }
 }
 
+impl Plane {
+    /// The number of vertices and indices of the mesh generated from this plane.
+    pub const fn counts(&self) -> (usize, usize) {
+        (4, 6)
+    }
+}
+
 impl From<Plane> for Mesh {
     fn from(plane: Plane) -> Self {
         let extent = plane.size / 2.0;
@@ -218,3 +240,60 @@ pub use regular_polygon::{Circle, RegularPolygon};
 pub use torus::Torus;
 pub use uvsphere::UVSphere;
 use wgpu::PrimitiveTopology;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_counts(counts: (usize, usize), mesh: Mesh) {
+        assert_eq!(
+            counts,
+            (mesh.count_vertices(), mesh.indices().unwrap().len())
+        );
+    }
+
+    #[test]
+    fn counts_match_generated_meshes() {
+        assert_counts(Cube::default().counts(), Cube::default().into());
+        assert_counts(Box::default().counts(), Box::default().into());
+        assert_counts(Quad::default().counts(), Quad::default().into());
+        assert_counts(Plane::default().counts(), Plane::default().into());
+        for sides in [3, 4, 7] {
+            let polygon = RegularPolygon::new(1.0, sides);
+            assert_counts(polygon.counts(), polygon.into());
+        }
+        assert_counts(Circle::default().counts(), Circle::default().into());
+        for (subdivisions_segments, subdivisions_sides) in [(32, 24), (3, 3), (5, 8)] {
+            let torus = Torus {
+                subdivisions_segments,
+                subdivisions_sides,
+                ..Default::default()
+            };
+            assert_counts(torus.counts(), torus.into());
+        }
+        for (sectors, stacks) in [(36, 18), (3, 2), (4, 1), (7, 5)] {
+            let sphere = UVSphere {
+                sectors,
+                stacks,
+                ..Default::default()
+            };
+            assert_counts(sphere.counts(), sphere.into());
+        }
+        for subdivisions in [0, 1, 5, 12] {
+            let sphere = Icosphere {
+                subdivisions,
+                ..Default::default()
+            };
+            assert_counts(sphere.counts(), sphere.into());
+        }
+        for (rings, latitudes, longitudes) in [(0, 16, 32), (3, 16, 32), (1, 4, 3), (0, 6, 5)] {
+            let capsule = Capsule {
+                rings,
+                latitudes,
+                longitudes,
+                ..Default::default()
+            };
+            assert_counts(capsule.counts(), capsule.into());
+        }
+    }
+}
@@ -24,6 +24,11 @@ impl RegularPolygon {
     pub fn new(radius: f32, sides: usize) -> Self {
         Self { radius, sides }
     }
+
+    /// The number of vertices and indices of the mesh generated from this polygon.
+    pub const fn counts(&self) -> (usize, usize) {
+        (self.sides, (self.sides - 2) * 3)
+    }
 }
 
 impl From<RegularPolygon> for Mesh {
@@ -86,6 +91,11 @@ impl Circle {
             ..Default::default()
         }
     }
+
+    /// The number of vertices and indices of the mesh generated from this circle.
+    pub const fn counts(&self) -> (usize, usize) {
+        (self.vertices, (self.vertices - 2) * 3)
+    }
 }
 
 impl From<Circle> for RegularPolygon {
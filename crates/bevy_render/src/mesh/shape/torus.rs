@@ -22,6 +22,16 @@ impl Default for Torus {
     }
 }
 
+impl Torus {
+    /// The number of vertices and indices of the mesh generated from this torus.
+    pub const fn counts(&self) -> (usize, usize) {
+        (
+            (self.subdivisions_segments + 1) * (self.subdivisions_sides + 1),
+            self.subdivisions_segments * self.subdivisions_sides * 6,
+        )
+    }
+}
+
 impl From<Torus> for Mesh {
     fn from(torus: Torus) -> Self {
         // code adapted from http://apparat-engine.blogspot.com/2013/04/procedural-meshes-torus.html
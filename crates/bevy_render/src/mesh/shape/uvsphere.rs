@@ -25,6 +25,17 @@ impl Default for UVSphere {
     }
 }
 
+impl UVSphere {
+    /// The number of vertices and indices of the mesh generated from this sphere.
+    pub const fn counts(&self) -> (usize, usize) {
+        // The first and last stacks are made of one triangle per sector, the others of two
+        (
+            (self.stacks + 1) * (self.sectors + 1),
+            self.stacks.saturating_sub(1) * self.sectors * 6,
+        )
+    }
+}
+
 impl From<UVSphere> for Mesh {
     fn from(sphere: UVSphere) -> Self {
         // Largely inspired from http://www.songho.ca/opengl/gl_sphere.html
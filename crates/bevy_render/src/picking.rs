@@ -0,0 +1,270 @@
+use crate::{
+    camera::{Camera, RenderTarget},
+    mesh::Mesh,
+    primitives::Aabb,
+    view::ComputedVisibility,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_input::{mouse::MouseButton, Input, InputSystem};
+use bevy_math::{Ray, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::Windows;
+
+/// Marks an entity with a [`Handle<Mesh>`] that can be picked by the [`PickingPlugin`].
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct Pickable;
+
+/// The closest intersection of a [`Ray`] with the meshes of the [`Pickable`] entities.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PickingHit {
+    /// The hit entity
+    pub entity: Entity,
+    /// Distance along the ray, in units of the length of its direction
+    pub distance: f32,
+    /// Position of the hit, in world space
+    pub position: Vec3,
+    /// Normal of the hit triangle, in world space, facing the origin of the ray
+    pub normal: Vec3,
+}
+
+/// The [`PickingHit`] of the entity under the mouse cursor, updated by the [`picking_system`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PointerHover {
+    hit: Option<PickingHit>,
+}
+
+impl PointerHover {
+    /// The hit of the entity under the mouse cursor, or `None` if there is none.
+    #[inline]
+    pub fn hit(&self) -> Option<&PickingHit> {
+        self.hit.as_ref()
+    }
+}
+
+/// Sent when the mouse cursor starts hovering a [`Pickable`] entity.
+#[derive(Clone, Debug)]
+pub struct PointerOver {
+    /// The hit of the hovered entity
+    pub hit: PickingHit,
+}
+
+/// Sent when the mouse cursor stops hovering a [`Pickable`] entity.
+#[derive(Clone, Debug)]
+pub struct PointerOut {
+    /// The entity that isn't hovered anymore
+    pub entity: Entity,
+}
+
+/// Sent when a mouse button is pressed over a [`Pickable`] entity.
+#[derive(Clone, Debug)]
+pub struct PointerClick {
+    /// The pressed button
+    pub button: MouseButton,
+    /// The hit of the clicked entity
+    pub hit: PickingHit,
+}
+
+/// Casts a ray against the meshes of entities, returning the closest hit.
+///
+/// The [`Aabb`]s of the entities are tested first, to skip the triangles of the meshes the ray
+/// can't hit. The ray is expected to be in world space, with a normalized direction for the
+/// distance of the hit to be in world units.
+pub fn cast_ray<'a>(
+    ray: Ray,
+    meshes: &Assets<Mesh>,
+    entities: impl IntoIterator<
+        Item = (
+            Entity,
+            &'a Handle<Mesh>,
+            &'a GlobalTransform,
+            Option<&'a Aabb>,
+        ),
+    >,
+) -> Option<PickingHit> {
+    let mut closest: Option<PickingHit> = None;
+    for (entity, mesh, transform, aabb) in entities {
+        let mesh = match meshes.get(mesh) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let local_from_world = transform.compute_matrix().inverse();
+        // The local ray isn't normalized so that its distances are the ones of the world ray
+        let local_ray = Ray {
+            origin: local_from_world.transform_point3(ray.origin),
+            direction: local_from_world.transform_vector3(ray.direction),
+        };
+        let max_distance = closest.map_or(f32::INFINITY, |closest| closest.distance);
+        if let Some(aabb) = aabb {
+            match aabb.intersect_ray(local_ray) {
+                Some(distance) if distance < max_distance => {}
+                _ => continue,
+            }
+        }
+        if let Some(hit) = mesh.intersect_ray(local_ray) {
+            if hit.distance < max_distance {
+                closest = Some(PickingHit {
+                    entity,
+                    distance: hit.distance,
+                    position: ray.get_point(hit.distance),
+                    // Normals are transformed by the inverse transpose of the model matrix
+                    normal: local_from_world
+                        .transpose()
+                        .transform_vector3(hit.normal)
+                        .normalize(),
+                });
+            }
+        }
+    }
+    closest
+}
+
+/// System that casts a ray from the mouse cursor through the cameras rendering to its window,
+/// updating the [`PointerHover`] and sending the [`PointerOver`], [`PointerOut`] and
+/// [`PointerClick`] events.
+///
+/// The cameras are tried from the highest [`Camera::priority`], until the ray hits an entity.
+#[allow(clippy::too_many_arguments)]
+pub fn picking_system(
+    windows: Res<Windows>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    meshes: Res<Assets<Mesh>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    pickables: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &GlobalTransform,
+            Option<&Aabb>,
+            &ComputedVisibility,
+        ),
+        With<Pickable>,
+    >,
+    mut hover: ResMut<PointerHover>,
+    mut over_events: EventWriter<PointerOver>,
+    mut out_events: EventWriter<PointerOut>,
+    mut click_events: EventWriter<PointerClick>,
+) {
+    let hit = windows
+        .iter()
+        .find_map(|window| Some((window.id(), window.cursor_position()?)))
+        .and_then(|(window, cursor_position)| {
+            let mut cameras = cameras
+                .iter()
+                .filter(|(camera, _)| {
+                    camera.is_active && camera.target == RenderTarget::Window(window)
+                })
+                .collect::<Vec<_>>();
+            cameras.sort_by_key(|(camera, _)| std::cmp::Reverse(camera.priority));
+            cameras.into_iter().find_map(|(camera, camera_transform)| {
+                let viewport_position = viewport_position(camera, cursor_position)?;
+                let ray = camera.viewport_to_world(camera_transform, viewport_position)?;
+                cast_ray(
+                    ray,
+                    &meshes,
+                    pickables
+                        .iter()
+                        .filter(|(.., visibility)| visibility.is_visible())
+                        .map(|(entity, mesh, transform, aabb, _)| (entity, mesh, transform, aabb)),
+                )
+            })
+        });
+
+    let previous = hover.hit.map(|hit| hit.entity);
+    if previous != hit.map(|hit| hit.entity) {
+        if let Some(entity) = previous {
+            out_events.send(PointerOut { entity });
+        }
+        if let Some(hit) = hit {
+            over_events.send(PointerOver { hit });
+        }
+    }
+    if let Some(hit) = hit {
+        for button in mouse_buttons.get_just_pressed() {
+            click_events.send(PointerClick {
+                button: *button,
+                hit,
+            });
+        }
+    }
+    hover.hit = hit;
+}
+
+/// Converts the position of the cursor in the window, from its bottom left corner, to a position
+/// in the viewport of the camera, or `None` if it is outside of the viewport.
+fn viewport_position(camera: &Camera, cursor_position: Vec2) -> Option<Vec2> {
+    let (min, max) = camera.logical_viewport_rect()?;
+    let target_size = camera.logical_target_size()?;
+    // Viewports are positioned from the top left corner of the window
+    let position = Vec2::new(
+        cursor_position.x - min.x,
+        cursor_position.y - (target_size.y - max.y),
+    );
+    let size = max - min;
+    (position.cmpge(Vec2::ZERO).all() && position.cmple(size).all()).then_some(position)
+}
+
+/// Adds mouse picking of the meshes of the [`Pickable`] entities, see [`picking_system`].
+#[derive(Default)]
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Pickable>()
+            .init_resource::<PointerHover>()
+            .add_event::<PointerOver>()
+            .add_event::<PointerOut>()
+            .add_event::<PointerClick>()
+            .add_system_to_stage(CoreStage::PreUpdate, picking_system.after(InputSystem));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cast_ray;
+    use crate::mesh::{shape, Mesh};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Ray, Vec3};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    #[test]
+    fn cast_ray_hits_closest_mesh() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>();
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let cube = meshes.add(Mesh::from(shape::Cube { size: 2.0 }));
+        let near =
+            GlobalTransform::from(Transform::from_xyz(0.0, 0.0, -5.0).with_scale(Vec3::splat(0.5)));
+        let far = GlobalTransform::from(Transform::from_xyz(0.0, 0.0, -10.0));
+        let aside = GlobalTransform::from(Transform::from_xyz(5.0, 0.0, -2.0));
+        let entities = [
+            (Entity::from_raw(0), &cube, &far, None),
+            (Entity::from_raw(1), &cube, &near, None),
+            (Entity::from_raw(2), &cube, &aside, None),
+        ];
+
+        let ray = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::NEG_Z,
+        };
+        let hit = cast_ray(ray, &meshes, entities).unwrap();
+        assert_eq!(hit.entity, Entity::from_raw(1));
+        assert!((hit.distance - 4.5).abs() < 1e-5);
+        assert!(hit.position.abs_diff_eq(Vec3::new(0.0, 0.0, -4.5), 1e-5));
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-5));
+
+        let miss = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::Y,
+        };
+        assert_eq!(cast_ray(miss, &meshes, entities), None);
+    }
+}
@@ -1,9 +1,9 @@
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
-use bevy_math::{Mat4, Vec3, Vec3A, Vec4, Vec4Swizzles};
+use bevy_math::{Mat4, Ray, Vec3, Vec3A, Vec4, Vec4Swizzles};
 use bevy_reflect::Reflect;
 
 /// An Axis-Aligned Bounding Box
-#[derive(Component, Clone, Debug, Default, Reflect)]
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
 #[reflect(Component)]
 pub struct Aabb {
     pub center: Vec3A,
@@ -46,6 +46,20 @@ impl Aabb {
     pub fn max(&self) -> Vec3A {
         self.center + self.half_extents
     }
+
+    /// Check if `point` is inside the AABB, including its boundary
+    #[inline]
+    pub fn contains_point(&self, point: Vec3A) -> bool {
+        (point - self.center).abs().cmple(self.half_extents).all()
+    }
+
+    /// Returns the distance along `ray` at which it enters the AABB, if it does
+    ///
+    /// See [`Ray::intersect_box`].
+    #[inline]
+    pub fn intersect_ray(&self, ray: Ray) -> Option<f32> {
+        ray.intersect_box(self.min().into(), self.max().into())
+    }
 }
 
 impl From<Sphere> for Aabb {
@@ -364,4 +378,13 @@ mod tests {
         };
         assert!(frustum.intersects_sphere(&sphere, true));
     }
+
+    #[test]
+    fn aabb_contains_point() {
+        let aabb = Aabb::from_min_max(Vec3::new(-1.0, 0.0, 2.0), Vec3::new(1.0, 4.0, 3.0));
+        assert!(aabb.contains_point(Vec3A::new(0.0, 2.0, 2.5)));
+        assert!(aabb.contains_point(Vec3A::new(1.0, 0.0, 3.0)));
+        assert!(!aabb.contains_point(Vec3A::new(0.0, 2.0, 3.5)));
+        assert!(!aabb.contains_point(Vec3A::new(-1.5, 2.0, 2.5)));
+    }
 }
@@ -0,0 +1,209 @@
+use std::fmt::Write;
+
+use bevy_utils::{get_short_name, HashMap};
+
+use crate::render_graph::{Edge, NodeId, NodeState, RenderGraph, SlotInfos};
+
+impl RenderGraph {
+    /// Returns this graph and all of its sub graphs in the [DOT] format of Graphviz, to see
+    /// where each node runs relative to the others.
+    ///
+    /// Each graph is drawn as a cluster of nodes, showing their name and type, with their input
+    /// slots above and output slots below. Slot edges link the slots they connect and are
+    /// labeled with the slot type, while node edges are dashed. Sub graphs are drawn as separate
+    /// clusters, since they are run by nodes at render time rather than linked to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_render::render_graph::{RenderGraph, EmptyNode};
+    /// let mut graph = RenderGraph::default();
+    /// graph.add_node("first", EmptyNode);
+    /// graph.add_node("second", EmptyNode);
+    /// graph.add_node_edge("first", "second").unwrap();
+    /// let dot = graph.dot_graph();
+    /// assert!(dot.starts_with("digraph {"));
+    /// // Save it to `render_graph.dot`, and render it with `dot -Tsvg render_graph.dot > render_graph.svg`
+    /// println!("{dot}");
+    /// ```
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn dot_graph(&self) -> String {
+        let mut dot = String::from("digraph {\n    rankdir=TB;\n    node [shape=record];\n");
+        self.write_dot(&mut dot, "g", "main graph");
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the cluster of this graph, then the clusters of its sub graphs, with ids starting
+    /// with `prefix`.
+    fn write_dot(&self, dot: &mut String, prefix: &str, label: &str) {
+        // Sort the nodes to get the same output for the same graph
+        let mut nodes: Vec<_> = self.iter_nodes().collect();
+        nodes.sort_by(|a, b| (&a.name, a.type_name).cmp(&(&b.name, b.type_name)));
+        let ids: HashMap<NodeId, String> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, format!("{prefix}_n{index}")))
+            .collect();
+
+        writeln!(dot, "    subgraph cluster_{prefix} {{").unwrap();
+        writeln!(dot, "        label={label:?};").unwrap();
+        for node in &nodes {
+            writeln!(
+                dot,
+                "        {} [label=\"{}\"];",
+                ids[&node.id],
+                node_label(node)
+            )
+            .unwrap();
+        }
+        dot.push_str("    }\n");
+
+        for node in &nodes {
+            for edge in node.edges.output_edges() {
+                match edge {
+                    Edge::SlotEdge {
+                        input_node,
+                        input_index,
+                        output_node,
+                        output_index,
+                    } => {
+                        let slot = node.output_slots.get_slot(*output_index).unwrap();
+                        writeln!(
+                            dot,
+                            "    {}:o{output_index} -> {}:i{input_index} [label=\"{}\"];",
+                            ids[output_node], ids[input_node], slot.slot_type
+                        )
+                        .unwrap();
+                    }
+                    Edge::NodeEdge {
+                        input_node,
+                        output_node,
+                    } => {
+                        writeln!(
+                            dot,
+                            "    {} -> {} [style=dashed];",
+                            ids[output_node], ids[input_node]
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+        }
+
+        let mut sub_graphs: Vec<_> = self.iter_sub_graphs().collect();
+        sub_graphs.sort_by_key(|(name, _)| *name);
+        for (index, (name, sub_graph)) in sub_graphs.into_iter().enumerate() {
+            sub_graph.write_dot(dot, &format!("{prefix}_s{index}"), name);
+        }
+    }
+}
+
+/// Returns the record label of `node`, with a port for each of its slots.
+fn node_label(node: &NodeState) -> String {
+    let type_name = escape(&get_short_name(node.type_name));
+    let mut label = match &node.name {
+        Some(name) => format!("{}\\n{type_name}", escape(name)),
+        None => type_name,
+    };
+    let slots = |slots: &SlotInfos, port: char| {
+        slots
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| format!("<{port}{index}> {}", escape(&slot.name)))
+            .collect::<Vec<_>>()
+            .join("|")
+    };
+    if !node.input_slots.is_empty() {
+        label = format!("{{{}}}|{label}", slots(&node.input_slots, 'i'));
+    }
+    if !node.output_slots.is_empty() {
+        write!(label, "|{{{}}}", slots(&node.output_slots, 'o')).unwrap();
+    }
+    format!("{{{label}}}")
+}
+
+/// Escapes the characters with a meaning in record labels.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::world::World;
+
+    use crate::{
+        render_graph::{
+            EmptyNode, Node, NodeRunError, RenderGraph, RenderGraphContext, SlotInfo, SlotType,
+        },
+        renderer::RenderContext,
+    };
+
+    struct ViewNode;
+
+    impl Node for ViewNode {
+        fn output(&self) -> Vec<SlotInfo> {
+            vec![SlotInfo::new("view", SlotType::Entity)]
+        }
+
+        fn run(
+            &self,
+            _graph: &mut RenderGraphContext,
+            _render_context: &mut RenderContext,
+            _world: &World,
+        ) -> Result<(), NodeRunError> {
+            Ok(())
+        }
+    }
+
+    struct PassNode;
+
+    impl Node for PassNode {
+        fn input(&self) -> Vec<SlotInfo> {
+            vec![SlotInfo::new("view", SlotType::Entity)]
+        }
+
+        fn run(
+            &self,
+            _graph: &mut RenderGraphContext,
+            _render_context: &mut RenderContext,
+            _world: &World,
+        ) -> Result<(), NodeRunError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn dot_graph_with_sub_graph() {
+        let mut sub_graph = RenderGraph::default();
+        sub_graph.add_node("a_view", ViewNode);
+        sub_graph.add_node("b_pass", PassNode);
+        sub_graph
+            .add_slot_edge("a_view", "view", "b_pass", "view")
+            .unwrap();
+        let mut graph = RenderGraph::default();
+        graph.add_node("first", EmptyNode);
+        graph.add_node("second", EmptyNode);
+        graph.add_node_edge("first", "second").unwrap();
+        graph.add_sub_graph("draw", sub_graph);
+
+        let dot = graph.dot_graph();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("label=\"main graph\";"));
+        assert!(dot.contains("g_n0 [label=\"{first\\nEmptyNode}\"];"));
+        assert!(dot.contains("g_n0 -> g_n1 [style=dashed];"));
+        assert!(dot.contains("subgraph cluster_g_s0 {"));
+        assert!(dot.contains("label=\"draw\";"));
+        assert!(dot.contains("g_s0_n0 [label=\"{a_view\\nViewNode|{<o0> view}}\"];"));
+        assert!(dot.contains("g_s0_n1 [label=\"{{<i0> view}|b_pass\\nPassNode}\"];"));
+        assert!(dot.contains("g_s0_n0:o0 -> g_s0_n1:i0 [label=\"Entity\"];"));
+    }
+}
@@ -1,6 +1,7 @@
 mod context;
 mod edge;
 mod graph;
+mod graph_dot;
 mod node;
 mod node_slot;
 
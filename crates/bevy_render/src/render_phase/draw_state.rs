@@ -1,5 +1,6 @@
 use crate::{
     camera::Viewport,
+    diagnostic::DrawCounters,
     prelude::Color,
     render_resource::{
         BindGroup, BindGroupId, Buffer, BufferId, BufferSlice, RenderPipeline, RenderPipelineId,
@@ -91,9 +92,17 @@ impl DrawState {
 /// A [`RenderPass`], which tracks the current pipeline state to ensure all draw calls are valid.
 /// It is used to set the current [`RenderPipeline`], [`BindGroups`](BindGroup) and buffers.
 /// After all requirements are specified, draw calls can be issued.
+///
+/// Render passes begun with [`RenderContext::begin_tracked_render_pass`] also count their draw
+/// calls for the [`RenderDiagnosticsPlugin`](crate::diagnostic::RenderDiagnosticsPlugin).
+///
+/// [`RenderContext::begin_tracked_render_pass`]: crate::renderer::RenderContext::begin_tracked_render_pass
 pub struct TrackedRenderPass<'a> {
     pass: RenderPass<'a>,
     state: DrawState,
+    counters: Option<&'a DrawCounters>,
+    /// Whether a pipeline statistics query was begun on the pass, and has to be ended.
+    pipeline_statistics_query: bool,
 }
 
 impl<'a> TrackedRenderPass<'a> {
@@ -102,6 +111,21 @@ impl<'a> TrackedRenderPass<'a> {
         Self {
             state: DrawState::default(),
             pass,
+            counters: None,
+            pipeline_statistics_query: false,
+        }
+    }
+
+    pub(crate) fn counted(
+        pass: RenderPass<'a>,
+        counters: &'a DrawCounters,
+        pipeline_statistics_query: bool,
+    ) -> Self {
+        Self {
+            state: DrawState::default(),
+            pass,
+            counters: Some(counters),
+            pipeline_statistics_query,
         }
     }
 
@@ -216,6 +240,9 @@ impl<'a> TrackedRenderPass<'a> {
     /// The active vertex buffer(s) can be set with [`TrackedRenderPass::set_vertex_buffer`].
     pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
         trace!("draw: {:?} {:?}", vertices, instances);
+        if let Some(counters) = self.counters {
+            counters.count_draw(vertices.len() as u32, instances.len() as u32);
+        }
         self.pass.draw(vertices, instances);
     }
 
@@ -230,6 +257,9 @@ impl<'a> TrackedRenderPass<'a> {
             base_vertex,
             instances
         );
+        if let Some(counters) = self.counters {
+            counters.count_draw(indices.len() as u32, instances.len() as u32);
+        }
         self.pass.draw_indexed(indices, base_vertex, instances);
     }
 
@@ -251,6 +281,9 @@ impl<'a> TrackedRenderPass<'a> {
     /// ```
     pub fn draw_indirect(&mut self, indirect_buffer: &'a Buffer, indirect_offset: u64) {
         trace!("draw indirect: {:?} {}", indirect_buffer, indirect_offset);
+        if let Some(counters) = self.counters {
+            counters.count_indirect_draw();
+        }
         self.pass.draw_indirect(indirect_buffer, indirect_offset);
     }
 
@@ -279,6 +312,9 @@ impl<'a> TrackedRenderPass<'a> {
             indirect_buffer,
             indirect_offset
         );
+        if let Some(counters) = self.counters {
+            counters.count_indirect_draw();
+        }
         self.pass
             .draw_indexed_indirect(indirect_buffer, indirect_offset);
     }
@@ -406,3 +442,11 @@ impl<'a> TrackedRenderPass<'a> {
         self.pass.set_blend_constant(wgpu::Color::from(color));
     }
 }
+
+impl<'a> Drop for TrackedRenderPass<'a> {
+    fn drop(&mut self) {
+        if self.pipeline_statistics_query {
+            self.pass.end_pipeline_statistics_query();
+        }
+    }
+}
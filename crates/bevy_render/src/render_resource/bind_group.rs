@@ -4,7 +4,10 @@ use encase::ShaderType;
 use crate::{
     prelude::Image,
     render_asset::RenderAssets,
-    render_resource::{BindGroupLayout, Buffer, Sampler, TextureView},
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, Buffer, Sampler, TextureView,
+    },
     renderer::RenderDevice,
     texture::FallbackImage,
 };
@@ -249,10 +252,50 @@ pub trait AsBindGroup: Sized {
         render_device: &RenderDevice,
         images: &RenderAssets<Image>,
         fallback_image: &FallbackImage,
-    ) -> Result<PreparedBindGroup<Self>, AsBindGroupError>;
+    ) -> Result<PreparedBindGroup<Self>, AsBindGroupError> {
+        let UnpreparedBindGroup { bindings, data } =
+            self.unprepared_bind_group(layout, render_device, images, fallback_image)?;
+
+        let entries = bindings
+            .iter()
+            .map(|(index, binding)| BindGroupEntry {
+                binding: *index,
+                resource: binding.get_binding(),
+            })
+            .collect::<Vec<_>>();
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: None,
+            layout,
+            entries: &entries,
+        });
+
+        Ok(PreparedBindGroup {
+            bindings: bindings.into_iter().map(|(_, binding)| binding).collect(),
+            bind_group,
+            data,
+        })
+    }
+
+    /// Returns the bindings of `self` with their binding indices, without creating a bind group,
+    /// so they can be combined with the bindings of other types into a single bind group.
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<Image>,
+        fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self>, AsBindGroupError>;
 
     /// Creates the bind group layout matching all bind groups returned by [`AsBindGroup::as_bind_group`]
-    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout;
+    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: None,
+            entries: &Self::bind_group_layout_entries(render_device),
+        })
+    }
+
+    /// Returns the entries of the layout returned by [`AsBindGroup::bind_group_layout`].
+    fn bind_group_layout_entries(render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry>;
 }
 
 /// An error that occurs during [`AsBindGroup::as_bind_group`] calls.
@@ -268,6 +311,14 @@ pub struct PreparedBindGroup<T: AsBindGroup> {
     pub data: T::Data,
 }
 
+/// The bindings of a bind group returned by [`AsBindGroup::unprepared_bind_group`], which
+/// haven't been bound to a [`BindGroup`] yet.
+pub struct UnpreparedBindGroup<T: AsBindGroup> {
+    /// The bindings, with their binding indices.
+    pub bindings: Vec<(u32, OwnedBindingResource)>,
+    pub data: T::Data,
+}
+
 /// An owned binding resource of any type (ex: a [`Buffer`], [`TextureView`], etc).
 /// This is used by types like [`PreparedBindGroup`] to hold a single list of all
 /// render resources used by bindings.
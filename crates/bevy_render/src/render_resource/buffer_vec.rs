@@ -3,6 +3,7 @@ use crate::{
     renderer::{RenderDevice, RenderQueue},
 };
 use bevy_core::{cast_slice, Pod};
+use std::ops::Range;
 use wgpu::BufferUsages;
 
 /// A structure for storing raw bytes that have already been properly formatted
@@ -75,6 +76,18 @@ impl<T: Pod> BufferVec<T> {
         index
     }
 
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The values in system RAM, to be written with [`BufferVec::write_buffer`] or
+    /// [`BufferVec::write_buffer_range`].
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
     pub fn set_label(&mut self, label: Option<&str>) {
         let label = label.map(str::to_string);
 
@@ -131,6 +144,38 @@ impl<T: Pod> BufferVec<T> {
         }
     }
 
+    /// Queues writing of the values in `range` from system RAM to VRAM, keeping the other values
+    /// written before, unless the [`Buffer`](crate::render_resource::Buffer) has to be recreated
+    /// to fit all the values, in which case they are all written.
+    pub fn write_buffer_range(
+        &mut self,
+        range: Range<usize>,
+        device: &RenderDevice,
+        queue: &RenderQueue,
+    ) {
+        if self.values.is_empty() {
+            return;
+        }
+        let buffer_id = self.buffer.as_ref().map(Buffer::id);
+        self.reserve(self.values.len(), device);
+        if let Some(buffer) = &self.buffer {
+            let range = if buffer_id == Some(buffer.id()) {
+                range.start.min(self.values.len())..range.end.min(self.values.len())
+            } else {
+                0..self.values.len()
+            };
+            if range.is_empty() {
+                return;
+            }
+            let bytes: &[u8] = cast_slice(&self.values[range.clone()]);
+            queue.write_buffer(buffer, (self.item_size * range.start) as u64, bytes);
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.values.truncate(len);
+    }
+
     pub fn clear(&mut self) {
         self.values.clear();
     }
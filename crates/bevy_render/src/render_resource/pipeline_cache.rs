@@ -7,17 +7,24 @@ use crate::{
         ShaderProcessor, ShaderReflectError,
     },
     renderer::RenderDevice,
-    Extract,
+    Extract, MainWorld,
 };
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::system::{Res, ResMut};
-use bevy_ecs::{event::EventReader, system::Resource};
+use bevy_ecs::{
+    event::{EventReader, Events},
+    system::Resource,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_tasks::Task;
 use bevy_utils::{
     default,
     tracing::{debug, error},
     Entry, HashMap, HashSet,
 };
-use std::{hash::Hash, iter::FusedIterator, mem, ops::Deref, sync::Arc};
+use futures_lite::future;
+use std::{future::Future, hash::Hash, iter::FusedIterator, mem, ops::Deref, sync::Arc};
 use thiserror::Error;
 use wgpu::{
     BufferBindingType, PipelineLayoutDescriptor, ShaderModule,
@@ -72,6 +79,8 @@ pub struct CachedPipeline {
 pub enum CachedPipelineState {
     /// The pipeline GPU object is queued for creation.
     Queued,
+    /// The pipeline GPU object is being compiled on the [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool).
+    Creating(Task<Pipeline>),
     /// The pipeline GPU object was created successfully and is available (allocated on the GPU).
     Ok(Pipeline),
     /// An error occurred while trying to create the pipeline GPU object.
@@ -95,6 +104,9 @@ impl CachedPipelineState {
             CachedPipelineState::Queued => {
                 panic!("Pipeline has not been compiled yet. It is still in the 'Queued' state.")
             }
+            CachedPipelineState::Creating(_) => {
+                panic!("Pipeline has not been compiled yet. It is still in the 'Creating' state.")
+            }
             CachedPipelineState::Err(err) => panic!("{}", err),
         }
     }
@@ -153,14 +165,14 @@ impl ShaderCache {
                 #[cfg(feature = "webgl")]
                 shader_defs.push(String::from("NO_ARRAY_TEXTURES_SUPPORT"));
 
-                // TODO: 3 is the value from CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT declared in bevy_pbr
+                // TODO: 4 is the value from CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT declared in bevy_pbr
                 // consider exposing this in shaders in a more generally useful way, such as:
-                // # if AVAILABLE_STORAGE_BUFFER_BINDINGS == 3
+                // # if AVAILABLE_STORAGE_BUFFER_BINDINGS == 4
                 // /* use storage buffers here */
                 // # elif
                 // /* use uniforms here */
                 if !matches!(
-                    render_device.get_supported_read_only_binding_type(3),
+                    render_device.get_supported_read_only_binding_type(4),
                     BufferBindingType::Storage { .. }
                 ) {
                     shader_defs.push(String::from("NO_STORAGE_BUFFERS_SUPPORT"));
@@ -273,7 +285,7 @@ impl ShaderCache {
 
 #[derive(Default)]
 struct LayoutCache {
-    layouts: HashMap<Vec<BindGroupLayoutId>, wgpu::PipelineLayout>,
+    layouts: HashMap<Vec<BindGroupLayoutId>, Arc<wgpu::PipelineLayout>>,
 }
 
 impl LayoutCache {
@@ -281,18 +293,23 @@ impl LayoutCache {
         &mut self,
         render_device: &RenderDevice,
         bind_group_layouts: &[BindGroupLayout],
-    ) -> &wgpu::PipelineLayout {
+    ) -> Arc<wgpu::PipelineLayout> {
         let key = bind_group_layouts.iter().map(|l| l.id()).collect();
-        self.layouts.entry(key).or_insert_with(|| {
-            let bind_group_layouts = bind_group_layouts
-                .iter()
-                .map(|l| l.value())
-                .collect::<Vec<_>>();
-            render_device.create_pipeline_layout(&PipelineLayoutDescriptor {
-                bind_group_layouts: &bind_group_layouts,
-                ..default()
+        self.layouts
+            .entry(key)
+            .or_insert_with(|| {
+                let bind_group_layouts = bind_group_layouts
+                    .iter()
+                    .map(|l| l.value())
+                    .collect::<Vec<_>>();
+                Arc::new(
+                    render_device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                        bind_group_layouts: &bind_group_layouts,
+                        ..default()
+                    }),
+                )
             })
-        })
+            .clone()
     }
 }
 
@@ -301,9 +318,16 @@ impl LayoutCache {
 /// The cache stores existing render and compute pipelines allocated on the GPU, as well as
 /// pending creation. Pipelines inserted into the cache are identified by a unique ID, which
 /// can be used to retrieve the actual GPU object once it's ready. The creation of the GPU
-/// pipeline object is deferred to the [`RenderStage::Render`] stage, just before the render
+/// pipeline object is started in the [`RenderStage::Render`] stage, just before the render
 /// graph starts being processed, as this requires access to the GPU.
 ///
+/// Shaders are processed during that stage, but pipelines are then compiled by the driver on the
+/// [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool), which can take several frames.
+/// In the meantime, the items using a pipeline that isn't ready are skipped when drawing, rather
+/// than stalling the frame. A [`PipelineCompiled`] event is sent to the main world for each
+/// pipeline once it is ready. See [`PipelineCache::set_synchronous_compilation`] to wait for the
+/// pipelines instead.
+///
 /// Note that the cache do not perform automatic deduplication of identical pipelines. It is
 /// up to the user not to insert the same pipeline twice to avoid wasting GPU resources.
 ///
@@ -315,6 +339,9 @@ pub struct PipelineCache {
     device: RenderDevice,
     pipelines: Vec<CachedPipeline>,
     waiting_pipelines: HashSet<CachedPipelineId>,
+    /// The pipelines compiled since the last [`PipelineCompiled`] events were sent.
+    compiled_pipelines: Vec<CachedPipelineId>,
+    synchronous_compilation: bool,
 }
 
 impl PipelineCache {
@@ -330,9 +357,21 @@ impl PipelineCache {
             shader_cache: default(),
             waiting_pipelines: default(),
             pipelines: default(),
+            compiled_pipelines: default(),
+            synchronous_compilation: false,
         }
     }
 
+    /// Whether pipelines are compiled on the render thread, blocking [`PipelineCache::process_queue`]
+    /// until they are ready, instead of compiling them asynchronously.
+    ///
+    /// Compiling synchronously makes sure that all pipelines queued before a frame are used
+    /// during that frame, for example when rendering a single frame in a test. Pipelines are
+    /// always compiled synchronously on the web.
+    pub fn set_synchronous_compilation(&mut self, synchronous: bool) {
+        self.synchronous_compilation = synchronous;
+    }
+
     /// Get the state of a cached render pipeline.
     ///
     /// See [`PipelineCache::queue_render_pipeline()`].
@@ -498,68 +537,61 @@ impl PipelineCache {
             }
         };
 
-        let fragment_data = if let Some(fragment) = &descriptor.fragment {
-            let fragment_module = match self.shader_cache.get(
-                &self.device,
-                id,
-                &fragment.shader,
-                &fragment.shader_defs,
-            ) {
-                Ok(module) => module,
+        let fragment_module = if let Some(fragment) = &descriptor.fragment {
+            match self
+                .shader_cache
+                .get(&self.device, id, &fragment.shader, &fragment.shader_defs)
+            {
+                Ok(module) => Some(module),
                 Err(err) => {
                     return CachedPipelineState::Err(err);
                 }
-            };
-            Some((
-                fragment_module,
-                fragment.entry_point.deref(),
-                fragment.targets.as_slice(),
-            ))
+            }
         } else {
             None
         };
 
-        let vertex_buffer_layouts = descriptor
-            .vertex
-            .buffers
-            .iter()
-            .map(|layout| RawVertexBufferLayout {
-                array_stride: layout.array_stride,
-                attributes: &layout.attributes,
-                step_mode: layout.step_mode,
-            })
-            .collect::<Vec<_>>();
-
-        let layout = if let Some(layout) = &descriptor.layout {
-            Some(self.layout_cache.get(&self.device, layout))
-        } else {
-            None
-        };
+        let layout = descriptor
+            .layout
+            .as_ref()
+            .map(|layout| self.layout_cache.get(&self.device, layout));
+
+        let device = self.device.clone();
+        let descriptor = descriptor.clone();
+        self.create_pipeline_task(async move {
+            let vertex_buffer_layouts = descriptor
+                .vertex
+                .buffers
+                .iter()
+                .map(|layout| RawVertexBufferLayout {
+                    array_stride: layout.array_stride,
+                    attributes: &layout.attributes,
+                    step_mode: layout.step_mode,
+                })
+                .collect::<Vec<_>>();
 
-        let descriptor = RawRenderPipelineDescriptor {
-            multiview: None,
-            depth_stencil: descriptor.depth_stencil.clone(),
-            label: descriptor.label.as_deref(),
-            layout,
-            multisample: descriptor.multisample,
-            primitive: descriptor.primitive,
-            vertex: RawVertexState {
-                buffers: &vertex_buffer_layouts,
-                entry_point: descriptor.vertex.entry_point.deref(),
-                module: &vertex_module,
-            },
-            fragment: fragment_data
-                .as_ref()
-                .map(|(module, entry_point, targets)| RawFragmentState {
-                    entry_point,
+            let fragment_data = descriptor.fragment.as_ref().zip(fragment_module.as_ref());
+            let raw_descriptor = RawRenderPipelineDescriptor {
+                multiview: None,
+                depth_stencil: descriptor.depth_stencil.clone(),
+                label: descriptor.label.as_deref(),
+                layout: layout.as_deref(),
+                multisample: descriptor.multisample,
+                primitive: descriptor.primitive,
+                vertex: RawVertexState {
+                    buffers: &vertex_buffer_layouts,
+                    entry_point: descriptor.vertex.entry_point.deref(),
+                    module: &vertex_module,
+                },
+                fragment: fragment_data.map(|(fragment, module)| RawFragmentState {
+                    entry_point: fragment.entry_point.deref(),
                     module,
-                    targets,
+                    targets: &fragment.targets,
                 }),
-        };
-
-        let pipeline = self.device.create_render_pipeline(&descriptor);
+            };
 
-        CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline))
+            Pipeline::RenderPipeline(device.create_render_pipeline(&raw_descriptor))
+        })
     }
 
     fn process_compute_pipeline(
@@ -579,25 +611,48 @@ impl PipelineCache {
             }
         };
 
-        let layout = if let Some(layout) = &descriptor.layout {
-            Some(self.layout_cache.get(&self.device, layout))
-        } else {
-            None
-        };
+        let layout = descriptor
+            .layout
+            .as_ref()
+            .map(|layout| self.layout_cache.get(&self.device, layout));
+
+        let device = self.device.clone();
+        let descriptor = descriptor.clone();
+        self.create_pipeline_task(async move {
+            let raw_descriptor = RawComputePipelineDescriptor {
+                label: descriptor.label.as_deref(),
+                layout: layout.as_deref(),
+                module: &compute_module,
+                entry_point: descriptor.entry_point.as_ref(),
+            };
 
-        let descriptor = RawComputePipelineDescriptor {
-            label: descriptor.label.as_deref(),
-            layout,
-            module: &compute_module,
-            entry_point: descriptor.entry_point.as_ref(),
-        };
+            Pipeline::ComputePipeline(device.create_compute_pipeline(&raw_descriptor))
+        })
+    }
 
-        let pipeline = self.device.create_compute_pipeline(&descriptor);
+    #[cfg(not(target_arch = "wasm32"))]
+    fn create_pipeline_task(
+        &self,
+        task: impl Future<Output = Pipeline> + Send + 'static,
+    ) -> CachedPipelineState {
+        if self.synchronous_compilation {
+            CachedPipelineState::Ok(future::block_on(task))
+        } else {
+            CachedPipelineState::Creating(AsyncComputeTaskPool::get().spawn(task))
+        }
+    }
 
-        CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline))
+    // wgpu objects can't be sent to other threads on the web
+    #[cfg(target_arch = "wasm32")]
+    fn create_pipeline_task(
+        &self,
+        task: impl Future<Output = Pipeline> + 'static,
+    ) -> CachedPipelineState {
+        CachedPipelineState::Ok(future::block_on(task))
     }
 
-    /// Process the pipeline queue and create all pending pipelines if possible.
+    /// Process the pipeline queue, starting the creation of all pending pipelines if possible,
+    /// and collecting the pipelines that finished compiling.
     ///
     /// This is generally called automatically during the [`RenderStage::Render`] stage, but can
     /// be called manually to force creation at a different time.
@@ -609,8 +664,21 @@ impl PipelineCache {
 
         for id in waiting_pipelines {
             let pipeline = &mut pipelines[id];
-            if matches!(pipeline.state, CachedPipelineState::Ok(_)) {
-                continue;
+            match &mut pipeline.state {
+                CachedPipelineState::Ok(_) => continue,
+                CachedPipelineState::Creating(task) => {
+                    match future::block_on(future::poll_once(task)) {
+                        Some(compiled) => {
+                            pipeline.state = CachedPipelineState::Ok(compiled);
+                            self.compiled_pipelines.push(id);
+                        }
+                        None => {
+                            self.waiting_pipelines.insert(id);
+                        }
+                    }
+                    continue;
+                }
+                CachedPipelineState::Queued | CachedPipelineState::Err(_) => {}
             }
 
             pipeline.state = match &pipeline.descriptor {
@@ -622,6 +690,14 @@ impl PipelineCache {
                 }
             };
 
+            match &pipeline.state {
+                CachedPipelineState::Creating(_) => {
+                    self.waiting_pipelines.insert(id);
+                }
+                CachedPipelineState::Ok(_) => self.compiled_pipelines.push(id),
+                _ => {}
+            }
+
             if let CachedPipelineState::Err(err) = &pipeline.state {
                 match err {
                     PipelineCacheError::ShaderNotLoaded(_)
@@ -653,6 +729,29 @@ impl PipelineCache {
         cache.process_queue();
     }
 
+    /// Sends a [`PipelineCompiled`] event to the main world for each pipeline compiled since
+    /// the last extraction.
+    pub(crate) fn extract_compiled_pipelines(
+        mut cache: ResMut<Self>,
+        mut main_world: ResMut<MainWorld>,
+    ) {
+        if cache.compiled_pipelines.is_empty() {
+            return;
+        }
+        let cache = &mut *cache;
+        let mut events = main_world.resource_mut::<Events<PipelineCompiled>>();
+        for id in cache.compiled_pipelines.drain(..) {
+            events.send(match cache.pipelines[id].descriptor {
+                PipelineDescriptor::RenderPipelineDescriptor(_) => {
+                    PipelineCompiled::Render(CachedRenderPipelineId(id))
+                }
+                PipelineDescriptor::ComputePipelineDescriptor(_) => {
+                    PipelineCompiled::Compute(CachedComputePipelineId(id))
+                }
+            });
+        }
+    }
+
     pub(crate) fn extract_shaders(
         mut cache: ResMut<Self>,
         shaders: Extract<Res<Assets<Shader>>>,
@@ -762,6 +861,16 @@ fn log_shader_error(source: &ProcessedShader, error: &AsModuleDescriptorError) {
     }
 }
 
+/// Sent in the main world when a pipeline of the [`PipelineCache`] finished compiling, and is
+/// used from the next frame on.
+///
+/// Pipelines are compiled again when their shaders are modified, sending another event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineCompiled {
+    Render(CachedRenderPipelineId),
+    Compute(CachedComputePipelineId),
+}
+
 /// Type of error returned by a [`PipelineCache`] when the creation of a GPU pipeline object failed.
 #[derive(Error, Debug)]
 pub enum PipelineCacheError {
@@ -802,3 +911,111 @@ impl<'a> Iterator for ErrorSources<'a> {
 }
 
 impl<'a> FusedIterator for ErrorSources<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::AssetPlugin;
+    use bevy_core::CorePlugin;
+    use bevy_ecs::event::ManualEventReader;
+    use bevy_window::{WindowPlugin, WindowSettings};
+
+    use super::*;
+    use crate::{RenderApp, RenderPlugin};
+
+    fn render_app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .insert_resource(WindowSettings {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            })
+            .add_plugin(WindowPlugin)
+            .add_plugin(RenderPlugin);
+        app
+    }
+
+    fn queue_compute_pipeline(app: &mut App) -> CachedComputePipelineId {
+        let shader = app
+            .world
+            .resource_mut::<Assets<Shader>>()
+            .add(Shader::from_wgsl(
+                "@compute @workgroup_size(1)
+                fn main() {}",
+            ));
+        // Extracts the shader
+        app.update();
+        app.sub_app_mut(RenderApp)
+            .world
+            .resource_mut::<PipelineCache>()
+            .queue_compute_pipeline(ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                shader,
+                shader_defs: Vec::new(),
+                entry_point: "main".into(),
+            })
+    }
+
+    fn state(app: &App, id: CachedComputePipelineId) -> &CachedPipelineState {
+        app.sub_app(RenderApp)
+            .world
+            .resource::<PipelineCache>()
+            .get_compute_pipeline_state(id)
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn pipelines_compile_asynchronously() {
+        let mut app = render_app();
+        let mut reader = ManualEventReader::<PipelineCompiled>::default();
+        let id = queue_compute_pipeline(&mut app);
+        assert!(matches!(state(&app, id), CachedPipelineState::Queued));
+
+        app.update();
+        assert!(matches!(state(&app, id), CachedPipelineState::Creating(_)));
+        let events = app.world.resource::<Events<PipelineCompiled>>();
+        assert_eq!(reader.iter(events).count(), 0);
+
+        let mut frames = 0;
+        while !matches!(state(&app, id), CachedPipelineState::Ok(_)) {
+            app.update();
+            frames += 1;
+            assert!(frames < 100, "the pipeline was never compiled");
+        }
+
+        // The event is sent when extracting the next frame
+        app.update();
+        let events = app.world.resource::<Events<PipelineCompiled>>();
+        assert_eq!(
+            reader.iter(events).copied().collect::<Vec<_>>(),
+            [PipelineCompiled::Compute(id)]
+        );
+        app.update();
+        let events = app.world.resource::<Events<PipelineCompiled>>();
+        assert_eq!(reader.iter(events).count(), 0);
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn synchronous_compilation_finishes_in_the_same_frame() {
+        let mut app = render_app();
+        app.sub_app_mut(RenderApp)
+            .world
+            .resource_mut::<PipelineCache>()
+            .set_synchronous_compilation(true);
+        let mut reader = ManualEventReader::<PipelineCompiled>::default();
+        let id = queue_compute_pipeline(&mut app);
+
+        app.update();
+        assert!(matches!(state(&app, id), CachedPipelineState::Ok(_)));
+        app.update();
+        let events = app.world.resource::<Events<PipelineCompiled>>();
+        assert_eq!(
+            reader.iter(events).copied().collect::<Vec<_>>(),
+            [PipelineCompiled::Compute(id)]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use bevy_ecs::world::World;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
-use bevy_utils::HashMap;
+use bevy_utils::{get_short_name, HashMap};
 use smallvec::{smallvec, SmallVec};
 #[cfg(feature = "trace")]
 use std::ops::Deref;
@@ -9,6 +9,7 @@ use std::{borrow::Cow, collections::VecDeque};
 use thiserror::Error;
 
 use crate::{
+    diagnostic::RenderDiagnosticsRecorder,
     render_graph::{
         Edge, NodeId, NodeRunError, NodeState, RenderGraph, RenderGraphContext, SlotLabel,
         SlotType, SlotValue,
@@ -57,20 +58,32 @@ impl RenderGraphRunner {
         render_device: RenderDevice,
         queue: &wgpu::Queue,
         world: &World,
+        diagnostics: &mut Option<RenderDiagnosticsRecorder>,
     ) -> Result<(), RenderGraphRunnerError> {
         let command_encoder =
             render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        if let Some(diagnostics) = diagnostics {
+            diagnostics.begin_frame(&render_device);
+        }
         let mut render_context = RenderContext {
             render_device,
             command_encoder,
+            diagnostics: diagnostics.take(),
         };
 
         Self::run_graph(graph, None, &mut render_context, world, &[])?;
         {
             #[cfg(feature = "trace")]
             let _span = info_span!("submit_graph_commands").entered();
+            if let Some(diagnostics) = &render_context.diagnostics {
+                diagnostics.resolve(&mut render_context.command_encoder);
+            }
             queue.submit(vec![render_context.command_encoder.finish()]);
         }
+        if let Some(mut recorder) = render_context.diagnostics {
+            recorder.end_frame(&render_context.render_device);
+            *diagnostics = Some(recorder);
+        }
         Ok(())
     }
 
@@ -185,7 +198,18 @@ impl RenderGraphRunner {
                     #[cfg(feature = "trace")]
                     let _span = info_span!("node", name = node_state.type_name).entered();
 
+                    let timestamps = render_context.diagnostics.as_mut().and_then(|diagnostics| {
+                        diagnostics.begin_node(
+                            || node_diagnostic_name(graph_name.as_deref(), node_state),
+                            &mut render_context.command_encoder,
+                        )
+                    });
                     node_state.node.run(&mut context, render_context, world)?;
+                    if let (Some(index), Some(diagnostics)) =
+                        (timestamps, &render_context.diagnostics)
+                    {
+                        diagnostics.end_node(index, &mut render_context.command_encoder);
+                    }
                 }
 
                 for run_sub_graph in context.finish() {
@@ -225,3 +249,16 @@ impl RenderGraphRunner {
         Ok(())
     }
 }
+
+/// Names a node in the [`RenderDiagnosticsPlugin`](crate::diagnostic::RenderDiagnosticsPlugin)
+/// after its graph, as nodes of different sub graphs often share a name.
+fn node_diagnostic_name(graph_name: Option<&str>, node_state: &NodeState) -> Cow<'static, str> {
+    let node_name = match &node_state.name {
+        Some(name) => name.clone(),
+        None => get_short_name(node_state.type_name).into(),
+    };
+    match graph_name {
+        Some(graph_name) => format!("{graph_name}/{node_name}").into(),
+        None => node_name,
+    }
+}
@@ -7,7 +7,9 @@ pub use graph_runner::*;
 pub use render_device::*;
 
 use crate::{
+    diagnostic::RenderDiagnosticsRecorder,
     render_graph::RenderGraph,
+    render_phase::TrackedRenderPass,
     settings::{WgpuSettings, WgpuSettingsPriority},
     view::{ExtractedWindows, ViewTarget},
 };
@@ -15,13 +17,17 @@ use bevy_ecs::prelude::*;
 use bevy_time::TimeSender;
 use bevy_utils::Instant;
 use std::sync::Arc;
-use wgpu::{Adapter, AdapterInfo, CommandEncoder, Instance, Queue, RequestAdapterOptions};
+use wgpu::{
+    Adapter, AdapterInfo, CommandEncoder, Instance, Queue, RenderPassDescriptor,
+    RequestAdapterOptions,
+};
 
 /// Updates the [`RenderGraph`] with all of its nodes and then runs it to render the entire frame.
 pub fn render_system(world: &mut World) {
     world.resource_scope(|world, mut graph: Mut<RenderGraph>| {
         graph.update(world);
     });
+    let mut diagnostics = world.remove_resource::<RenderDiagnosticsRecorder>();
     let graph = world.resource::<RenderGraph>();
     let render_device = world.resource::<RenderDevice>();
     let render_queue = world.resource::<RenderQueue>();
@@ -31,6 +37,7 @@ pub fn render_system(world: &mut World) {
         render_device.clone(), // TODO: is this clone really necessary?
         &render_queue.0,
         world,
+        &mut diagnostics,
     ) {
         error!("Error running render graph:");
         {
@@ -46,6 +53,9 @@ pub fn render_system(world: &mut World) {
 
         panic!("Error running render graph: {}", e);
     }
+    if let Some(diagnostics) = diagnostics {
+        world.insert_resource(diagnostics);
+    }
 
     {
         let _span = info_span!("present_frames").entered();
@@ -302,4 +312,25 @@ pub async fn initialize_renderer(
 pub struct RenderContext {
     pub render_device: RenderDevice,
     pub command_encoder: CommandEncoder,
+    diagnostics: Option<RenderDiagnosticsRecorder>,
+}
+
+impl RenderContext {
+    /// Begins a [`TrackedRenderPass`] on the [`CommandEncoder`].
+    ///
+    /// Its draw calls are counted by the
+    /// [`RenderDiagnosticsPlugin`](crate::diagnostic::RenderDiagnosticsPlugin).
+    pub fn begin_tracked_render_pass<'a>(
+        &'a mut self,
+        descriptor: &RenderPassDescriptor<'a, '_>,
+    ) -> TrackedRenderPass<'a> {
+        let mut pass = self.command_encoder.begin_render_pass(descriptor);
+        match &mut self.diagnostics {
+            Some(diagnostics) => {
+                let pipeline_statistics_query = diagnostics.begin_render_pass(&mut pass);
+                TrackedRenderPass::counted(pass, &diagnostics.counters, pipeline_statistics_query)
+            }
+            None => TrackedRenderPass::new(pass),
+        }
+    }
 }
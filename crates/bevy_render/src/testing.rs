@@ -0,0 +1,124 @@
+//! Utilities for tests that render with the GPU, enabled by the `test_utils` feature.
+
+use std::num::NonZeroU32;
+
+use bevy_app::App;
+use bevy_ecs::entity::Entity;
+use bevy_math::UVec2;
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+    ImageCopyTexture, ImageDataLayout, Maintain, MapMode, Origin3d, TextureAspect, TextureFormat,
+    COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+use crate::{
+    camera::{Camera, RenderTarget},
+    color::Color,
+    render_asset::RenderAssets,
+    render_resource::PipelineCache,
+    renderer::{RenderDevice, RenderQueue},
+    texture::Image,
+    RenderApp,
+};
+
+/// Runs one frame of `app` and returns the color of `pixel` in the image `camera` renders to.
+///
+/// `app` must have a [`RenderPlugin`](crate::RenderPlugin) with a GPU available. The camera's
+/// [`RenderTarget`] must be an [`Image`] whose texture usage includes
+/// [`TextureUsages::COPY_SRC`](wgpu::TextureUsages::COPY_SRC), in an 8 bit RGBA or BGRA
+/// format. `pixel` is counted from the top-left corner of the image.
+///
+/// The [`PipelineCache`] of `app` is switched to synchronous compilation, so that the pipelines
+/// queued until this frame are used to render it.
+///
+/// # Panics
+/// Panics if `camera` isn't a camera rendering to an image, if `pixel` is outside of the image,
+/// or if the image uses another format.
+pub fn render_single_frame_pixel(app: &mut App, camera: Entity, pixel: UVec2) -> Color {
+    let target = match app.world.get::<Camera>(camera).map(|camera| &camera.target) {
+        Some(RenderTarget::Image(image)) => image.clone(),
+        _ => panic!("`render_single_frame_pixel` needs a camera rendering to an image"),
+    };
+    app.sub_app_mut(RenderApp)
+        .world
+        .resource_mut::<PipelineCache>()
+        .set_synchronous_compilation(true);
+    app.update();
+
+    let render_world = &app.sub_app(RenderApp).world;
+    let image = render_world
+        .resource::<RenderAssets<Image>>()
+        .get(&target)
+        .expect("the target image should have been prepared");
+    let size = image.size.as_uvec2();
+    assert!(
+        pixel.x < size.x && pixel.y < size.y,
+        "pixel {pixel} is outside of the {}x{} target image",
+        size.x,
+        size.y
+    );
+    let render_device = render_world.resource::<RenderDevice>();
+    let render_queue = render_world.resource::<RenderQueue>();
+
+    // A single pixel is copied, but rows of the buffer still have to be aligned
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("single_pixel_readback_buffer"),
+        size: COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("single_pixel_readback"),
+    });
+    encoder.copy_texture_to_buffer(
+        ImageCopyTexture {
+            texture: &image.texture,
+            mip_level: 0,
+            origin: Origin3d {
+                x: pixel.x,
+                y: pixel.y,
+                z: 0,
+            },
+            aspect: TextureAspect::All,
+        },
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+    );
+    render_queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    render_device.map_buffer(&slice, MapMode::Read, |result| {
+        result.expect("the readback buffer should be mappable");
+    });
+    render_device.poll(Maintain::Wait);
+    let data = slice.get_mapped_range();
+    let [r, g, b, a] = [data[0], data[1], data[2], data[3]];
+    match image.texture_format {
+        TextureFormat::Rgba8UnormSrgb => Color::rgba_u8(r, g, b, a),
+        TextureFormat::Bgra8UnormSrgb => Color::rgba_u8(b, g, r, a),
+        TextureFormat::Rgba8Unorm => Color::rgba_linear(
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+            a as f32 / 255.0,
+        ),
+        TextureFormat::Bgra8Unorm => Color::rgba_linear(
+            b as f32 / 255.0,
+            g as f32 / 255.0,
+            r as f32 / 255.0,
+            a as f32 / 255.0,
+        ),
+        format => panic!("`render_single_frame_pixel` can't read {format:?} images"),
+    }
+}
@@ -0,0 +1,161 @@
+use crate::texture::{Image, TextureError};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_utils::BoxedFuture;
+use wgpu::{Extent3d, TextureDimension, TextureFormat};
+
+/// Loads the 3D lookup tables of `.cube` files, as used for color grading, as 3D textures.
+///
+/// The red, green and blue of the input color are the x, y and z coordinates in the texture.
+#[derive(Clone, Default)]
+pub struct CubeLutLoader;
+
+impl AssetLoader for CubeLutLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let text = std::str::from_utf8(bytes)?;
+            let texture = cube_lut_to_image(text)?;
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cube"]
+    }
+}
+
+/// Parses the 3D lookup table of the text of a `.cube` file into an [`Rgba32Float`](TextureFormat::Rgba32Float)
+/// 3D texture.
+///
+/// Only 3D tables over the default `[0, 1]` domain are supported.
+pub fn cube_lut_to_image(text: &str) -> Result<Image, TextureError> {
+    let parse_floats = |values: &[&str]| -> Result<[f32; 3], TextureError> {
+        let mut floats = [0.0; 3];
+        if values.len() != 3 {
+            return Err(TextureError::InvalidData(format!(
+                "expected 3 values, found {:?}",
+                values
+            )));
+        }
+        for (float, value) in floats.iter_mut().zip(values) {
+            *float = value
+                .parse()
+                .map_err(|_| TextureError::InvalidData(format!("invalid value {:?}", value)))?;
+        }
+        Ok(floats)
+    };
+
+    let mut size = None;
+    let mut data = Vec::new();
+    for line in text.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] | ["TITLE", ..] => {}
+            [comment, ..] if comment.starts_with('#') => {}
+            ["LUT_3D_SIZE", lut_size] => {
+                let lut_size: u32 = lut_size.parse().map_err(|_| {
+                    TextureError::InvalidData(format!("invalid LUT_3D_SIZE {:?}", lut_size))
+                })?;
+                if lut_size < 2 {
+                    return Err(TextureError::InvalidData(format!(
+                        "invalid LUT_3D_SIZE {}",
+                        lut_size
+                    )));
+                }
+                size = Some(lut_size);
+            }
+            ["LUT_1D_SIZE", ..] => {
+                return Err(TextureError::UnsupportedTextureFormat(
+                    "1D .cube lookup tables".to_string(),
+                ));
+            }
+            ["DOMAIN_MIN", values @ ..] => {
+                if parse_floats(values)? != [0.0; 3] {
+                    return Err(TextureError::UnsupportedTextureFormat(
+                        ".cube lookup tables with a DOMAIN_MIN other than 0".to_string(),
+                    ));
+                }
+            }
+            ["DOMAIN_MAX", values @ ..] => {
+                if parse_floats(values)? != [1.0; 3] {
+                    return Err(TextureError::UnsupportedTextureFormat(
+                        ".cube lookup tables with a DOMAIN_MAX other than 1".to_string(),
+                    ));
+                }
+            }
+            values => {
+                let [r, g, b] = parse_floats(values)?;
+                for value in [r, g, b, 1.0f32] {
+                    data.extend_from_slice(&value.to_ne_bytes());
+                }
+            }
+        }
+    }
+
+    let size = size.ok_or_else(|| TextureError::InvalidData("missing LUT_3D_SIZE".to_string()))?;
+    let expected = (size * size * size) as usize;
+    let found = data.len() / (4 * std::mem::size_of::<f32>());
+    if found != expected {
+        return Err(TextureError::InvalidData(format!(
+            "expected {} entries for a LUT_3D_SIZE of {}, found {}",
+            expected, size, found
+        )));
+    }
+
+    Ok(Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: size,
+        },
+        TextureDimension::D3,
+        data,
+        TextureFormat::Rgba32Float,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_lut_red_varies_fastest() {
+        let mut text = String::from("# Identity\nTITLE \"identity\"\nLUT_3D_SIZE 2\n\n");
+        text.push_str("DOMAIN_MIN 0 0 0\nDOMAIN_MAX 1.0 1.0 1.0\n");
+        for b in 0..2 {
+            for g in 0..2 {
+                for r in 0..2 {
+                    text.push_str(&format!("{} {} {}\n", r, g, b));
+                }
+            }
+        }
+        let image = cube_lut_to_image(&text).unwrap();
+        assert_eq!(image.texture_descriptor.dimension, TextureDimension::D3);
+        assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 2);
+
+        let texel = |x: usize, y: usize, z: usize| {
+            let offset = ((z * 2 + y) * 2 + x) * 16;
+            let channel = |i: usize| {
+                let bytes = &image.data[offset + i * 4..offset + i * 4 + 4];
+                f32::from_ne_bytes(bytes.try_into().unwrap())
+            };
+            [channel(0), channel(1), channel(2), channel(3)]
+        };
+        assert_eq!(texel(1, 0, 0), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(texel(0, 1, 1), [0.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn cube_lut_with_missing_entries_is_rejected() {
+        let text = "LUT_3D_SIZE 2\n0 0 0\n1 0 0\n";
+        assert!(matches!(
+            cube_lut_to_image(text),
+            Err(TextureError::InvalidData(_))
+        ));
+    }
+}
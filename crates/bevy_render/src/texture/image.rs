@@ -7,13 +7,19 @@ use super::ktx2::*;
 
 use crate::{
     render_asset::{PrepareAssetError, RenderAsset},
-    render_resource::{Sampler, Texture, TextureView},
+    render_resource::{
+        CachedPipelineState, Pipeline, PipelineCache, Sampler, SpecializedRenderPipelines, Texture,
+        TextureView,
+    },
     renderer::{RenderDevice, RenderQueue},
-    texture::BevyDefault,
+    texture::{can_generate_mipmaps, full_mip_level_count, BevyDefault, MipmapPipeline},
 };
 use bevy_asset::HandleUntyped;
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::system::{lifetimeless::SRes, Resource, SystemParamItem};
+use bevy_ecs::system::{
+    lifetimeless::{SRes, SResMut},
+    Resource, SystemParamItem,
+};
 use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
 use std::hash::Hash;
@@ -110,6 +116,13 @@ pub struct Image {
     /// The [`ImageSampler`] to use during rendering.
     pub sampler_descriptor: ImageSampler,
     pub texture_view_descriptor: Option<wgpu::TextureViewDescriptor<'static>>,
+    /// Whether a full chain of mip levels is generated on the GPU from the first level when the
+    /// image is prepared for rendering, which keeps textures seen from afar from shimmering.
+    ///
+    /// Only applies to uncompressed 2D images without mip levels, whose format can be filtered
+    /// and rendered to. The images loaded from formats without mip levels, such as PNG and JPEG,
+    /// get it from [`ImageSettings::generate_mipmaps`].
+    pub generate_mipmaps: bool,
 }
 
 /// Used in [`Image`], this determines what image sampler to use when rendering. The default setting,
@@ -165,6 +178,9 @@ impl ImageSampler {
 pub struct ImageSettings {
     /// The default image sampler to use when [`ImageSampler`] is set to `Default`.
     pub default_sampler: wgpu::SamplerDescriptor<'static>,
+    /// Whether the loaded images without mip levels get them generated on the GPU, as set in
+    /// [`Image::generate_mipmaps`]. Defaults to `false`.
+    pub generate_mipmaps: bool,
 }
 
 impl Default for ImageSettings {
@@ -178,6 +194,7 @@ impl ImageSettings {
     pub fn default_linear() -> ImageSettings {
         ImageSettings {
             default_sampler: ImageSampler::linear_descriptor(),
+            generate_mipmaps: false,
         }
     }
 
@@ -185,6 +202,7 @@ impl ImageSettings {
     pub fn default_nearest() -> ImageSettings {
         ImageSettings {
             default_sampler: ImageSampler::nearest_descriptor(),
+            generate_mipmaps: false,
         }
     }
 }
@@ -218,6 +236,7 @@ impl Default for Image {
             },
             sampler_descriptor: ImageSampler::Default,
             texture_view_descriptor: None,
+            generate_mipmaps: false,
         }
     }
 }
@@ -643,6 +662,9 @@ impl RenderAsset for Image {
         SRes<RenderDevice>,
         SRes<RenderQueue>,
         SRes<DefaultImageSampler>,
+        SRes<MipmapPipeline>,
+        SResMut<SpecializedRenderPipelines<MipmapPipeline>>,
+        SResMut<PipelineCache>,
     );
 
     /// Clones the Image.
@@ -653,8 +675,39 @@ impl RenderAsset for Image {
     /// Converts the extracted image into a [`GpuImage`].
     fn prepare_asset(
         image: Self::ExtractedAsset,
-        (render_device, render_queue, default_sampler): &mut SystemParamItem<Self::Param>,
+        (
+            render_device,
+            render_queue,
+            default_sampler,
+            mipmap_pipeline,
+            mipmap_pipelines,
+            pipeline_cache,
+        ): &mut SystemParamItem<Self::Param>,
     ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
+        // The image waits for the pipeline generating its mip levels, unless it can't be compiled
+        let mut texture_descriptor = image.texture_descriptor.clone();
+        let mut generate_mipmaps_with = None;
+        if image.generate_mipmaps
+            && !image.is_compressed()
+            && can_generate_mipmaps(&texture_descriptor)
+        {
+            let id = mipmap_pipelines.specialize(
+                pipeline_cache,
+                mipmap_pipeline,
+                texture_descriptor.format,
+            );
+            match pipeline_cache.get_render_pipeline_state(id) {
+                CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline)) => {
+                    generate_mipmaps_with = Some(pipeline.clone());
+                    texture_descriptor.mip_level_count =
+                        full_mip_level_count(texture_descriptor.size);
+                    texture_descriptor.usage |= wgpu::TextureUsages::RENDER_ATTACHMENT;
+                }
+                CachedPipelineState::Err(_) => {}
+                _ => return Err(PrepareAssetError::RetryNextUpdate(image)),
+            }
+        }
+
         let texture = if image.texture_descriptor.mip_level_count > 1 || image.is_compressed() {
             render_device.create_texture_with_data(
                 render_queue,
@@ -662,7 +715,7 @@ impl RenderAsset for Image {
                 &image.data,
             )
         } else {
-            let texture = render_device.create_texture(&image.texture_descriptor);
+            let texture = render_device.create_texture(&texture_descriptor);
             let format_size = image.texture_descriptor.format.pixel_size();
             render_queue.write_texture(
                 ImageCopyTexture {
@@ -690,6 +743,15 @@ impl RenderAsset for Image {
             );
             texture
         };
+        if let Some(pipeline) = generate_mipmaps_with {
+            mipmap_pipeline.generate_mipmaps(
+                render_device,
+                render_queue,
+                &pipeline,
+                &texture,
+                &texture_descriptor,
+            );
+        }
 
         let texture_view = texture.create_view(
             image
@@ -174,6 +174,7 @@ impl Image {
     /// - `TextureFormat::R8Unorm`
     /// - `TextureFormat::Rg8Unorm`
     /// - `TextureFormat::Rgba8UnormSrgb`
+    /// - `TextureFormat::Bgra8UnormSrgb`
     ///
     /// To convert [`Image`] to a different format see: [`Image::convert`].
     pub fn try_into_dynamic(self) -> anyhow::Result<DynamicImage> {
@@ -196,6 +197,15 @@ impl Image {
                 self.data,
             )
             .map(DynamicImage::ImageRgba8),
+            TextureFormat::Bgra8UnormSrgb => ImageBuffer::from_raw(
+                self.texture_descriptor.size.width,
+                self.texture_descriptor.size.height,
+                self.data
+                    .chunks_exact(4)
+                    .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], pixel[3]])
+                    .collect(),
+            )
+            .map(DynamicImage::ImageRgba8),
             // Throw and error if conversion isn't supported
             texture_format => {
                 return Err(anyhow!(
@@ -230,4 +240,21 @@ mod test {
         // NOTE: Fails if `is_srbg = false` or the dynamic image is of the type rgb8.
         assert_eq!(initial, image.try_into_dynamic().unwrap());
     }
+
+    #[test]
+    fn bgra_conversion() {
+        let image = Image::new(
+            Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![7, 3, 132, 200],
+            TextureFormat::Bgra8UnormSrgb,
+        );
+        let mut expected = DynamicImage::new_rgba8(1, 1);
+        expected.put_pixel(0, 0, Rgba::from([132, 3, 7, 200]));
+        assert_eq!(expected, image.try_into_dynamic().unwrap());
+    }
 }
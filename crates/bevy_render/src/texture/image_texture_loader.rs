@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageSettings, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -15,6 +16,7 @@ use super::CompressedImageFormats;
 #[derive(Clone)]
 pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
+    generate_mipmaps: bool,
 }
 
 const FILE_EXTENSIONS: &[&str] = &[
@@ -45,17 +47,29 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context
+                .import_settings()
+                .get::<ImageImportSettings>()?;
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            if dyn_img.texture_descriptor.mip_level_count == 1 {
+                dyn_img.generate_mipmaps =
+                    settings.generate_mipmaps.unwrap_or(self.generate_mipmaps);
+            }
+            dyn_img.sampler_descriptor = match settings.sampler {
+                ImportedSampler::Default => ImageSampler::Default,
+                ImportedSampler::Linear => ImageSampler::linear(),
+                ImportedSampler::Nearest => ImageSampler::nearest(),
+            };
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -74,12 +88,55 @@ impl FromWorld for ImageTextureLoader {
 
             None => CompressedImageFormats::all(),
         };
+        let generate_mipmaps = world
+            .get_resource::<ImageSettings>()
+            .map_or(false, |settings| settings.generate_mipmaps);
         Self {
             supported_compressed_formats,
+            generate_mipmaps,
         }
     }
 }
 
+/// The import settings of the images loaded by the [`ImageTextureLoader`], read from their
+/// `.meta` file.
+///
+/// A pixel art sprite `sprite.png` can be sampled without filtering with a `sprite.png.meta` file
+/// containing `(sampler: Nearest)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageImportSettings {
+    /// Whether the image holds sRGB colors, rather than linear data such as a normal map.
+    pub is_srgb: bool,
+    /// The sampler of the image.
+    pub sampler: ImportedSampler,
+    /// Whether mip levels are generated for the image, overriding
+    /// [`ImageSettings::generate_mipmaps`] if set.
+    pub generate_mipmaps: Option<bool>,
+}
+
+impl Default for ImageImportSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImportedSampler::Default,
+            generate_mipmaps: None,
+        }
+    }
+}
+
+/// The sampler of an image set in its [`ImageImportSettings`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportedSampler {
+    /// Uses [`ImageSampler::Default`], the sampler of the [`ImageSettings`].
+    #[default]
+    Default,
+    /// Uses [`ImageSampler::linear`].
+    Linear,
+    /// Uses [`ImageSampler::nearest`], for pixel art.
+    Nearest,
+}
+
 /// An error that occurs when loading a texture from a file.
 #[derive(Error, Debug)]
 pub struct FileTextureError {
@@ -100,6 +157,21 @@ impl std::fmt::Display for FileTextureError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn image_import_settings() {
+        let settings = bevy_asset::ImportSettings::from_bytes(b"(sampler: Nearest)".to_vec())
+            .unwrap()
+            .get::<ImageImportSettings>()
+            .unwrap();
+        assert_eq!(
+            settings,
+            ImageImportSettings {
+                sampler: ImportedSampler::Nearest,
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn test_supported_file_extensions() {
         for ext in FILE_EXTENSIONS {
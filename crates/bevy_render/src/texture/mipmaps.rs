@@ -0,0 +1,208 @@
+use bevy_asset::HandleUntyped;
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use std::num::NonZeroU32;
+use wgpu::{
+    AddressMode, CommandEncoderDescriptor, Extent3d, FilterMode, SamplerDescriptor,
+    TextureDescriptor, TextureDimension, TextureFormatFeatureFlags, TextureUsages,
+};
+
+use crate::{
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, ColorTargetState, ColorWrites,
+        FragmentState, LoadOp, MultisampleState, Operations, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor,
+        Sampler, SamplerBindingType, Shader, ShaderStages, SpecializedRenderPipeline, Texture,
+        TextureFormat, TextureSampleType, TextureView, TextureViewDescriptor, TextureViewDimension,
+        VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue},
+};
+
+pub(crate) const MIPMAP_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3709256981275064214);
+
+/// Returns the number of mip levels of a full mip chain of a texture of `size`, down to a single
+/// texel.
+pub fn full_mip_level_count(size: Extent3d) -> u32 {
+    32 - size.width.max(size.height).max(1).leading_zeros()
+}
+
+/// Returns whether the mip levels of a texture of `descriptor` can be generated from its first
+/// level, which needs an uncompressed 2D texture without mip levels, whose format can be
+/// filtered and rendered to.
+pub fn can_generate_mipmaps(descriptor: &TextureDescriptor) -> bool {
+    let features = descriptor.format.describe().guaranteed_format_features;
+    descriptor.dimension == TextureDimension::D2
+        && descriptor.mip_level_count == 1
+        && descriptor.sample_count == 1
+        && full_mip_level_count(descriptor.size) > 1
+        && features
+            .allowed_usages
+            .contains(TextureUsages::RENDER_ATTACHMENT)
+        && features
+            .flags
+            .contains(TextureFormatFeatureFlags::FILTERABLE)
+}
+
+/// Renders each mip level of an [`Image`](super::Image) with
+/// [`Image::generate_mipmaps`](super::Image::generate_mipmaps) from the previous one.
+#[derive(Resource)]
+pub struct MipmapPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for MipmapPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("mipmap_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("mipmap_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        MipmapPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for MipmapPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: MIPMAP_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: MIPMAP_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("mipmap_pipeline".into()),
+        }
+    }
+}
+
+impl MipmapPipeline {
+    /// Renders the mip levels of each layer of `texture` from its first level, with the `pipeline`
+    /// specialized for its format.
+    pub fn generate_mipmaps(
+        &self,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        pipeline: &RenderPipeline,
+        texture: &Texture,
+        descriptor: &TextureDescriptor,
+    ) {
+        let level_view = |layer, level| -> TextureView {
+            texture.create_view(&TextureViewDescriptor {
+                label: Some("mipmap_level_view"),
+                dimension: Some(TextureViewDimension::D2),
+                base_mip_level: level,
+                mip_level_count: NonZeroU32::new(1),
+                base_array_layer: layer,
+                array_layer_count: NonZeroU32::new(1),
+                ..Default::default()
+            })
+        };
+
+        let mut command_encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("mipmap_command_encoder"),
+        });
+        for layer in 0..descriptor.size.depth_or_array_layers {
+            let mut source = level_view(layer, 0);
+            for level in 1..descriptor.mip_level_count {
+                let destination = level_view(layer, level);
+                let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("mipmap_bind_group"),
+                    layout: &self.layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(&source),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&self.sampler),
+                        },
+                    ],
+                });
+
+                {
+                    let mut pass = command_encoder.begin_render_pass(&RenderPassDescriptor {
+                        label: Some("mipmap_pass"),
+                        color_attachments: &[Some(RenderPassColorAttachment {
+                            view: &destination,
+                            resolve_target: None,
+                            ops: Operations {
+                                load: LoadOp::Load,
+                                store: true,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                    });
+                    pass.set_pipeline(pipeline);
+                    pass.set_bind_group(0, &bind_group, &[]);
+                    pass.draw(0..3, 0..1);
+                }
+                source = destination;
+            }
+        }
+        render_queue.submit([command_encoder.finish()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mip_level_counts() {
+        let size = |width, height| Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        assert_eq!(full_mip_level_count(size(1, 1)), 1);
+        assert_eq!(full_mip_level_count(size(2, 1)), 2);
+        assert_eq!(full_mip_level_count(size(256, 256)), 9);
+        assert_eq!(full_mip_level_count(size(300, 17)), 9);
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(feature = "basis-universal")]
 mod basis;
+mod cube_lut_loader;
 #[cfg(feature = "dds")]
 mod dds;
 mod fallback_image;
@@ -10,6 +11,9 @@ mod image;
 mod image_texture_loader;
 #[cfg(feature = "ktx2")]
 mod ktx2;
+mod mipmaps;
+#[cfg(feature = "png")]
+mod png_texture_saver;
 mod texture_cache;
 
 pub(crate) mod image_texture_conversion;
@@ -22,17 +26,22 @@ pub use dds::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
 
+pub use cube_lut_loader::*;
 pub use fallback_image::*;
 pub use image_texture_loader::*;
+pub use mipmaps::*;
+#[cfg(feature = "png")]
+pub use png_texture_saver::*;
 pub use texture_cache::*;
 
 use crate::{
     render_asset::{PrepareAssetLabel, RenderAssetPlugin},
+    render_resource::{Shader, SpecializedRenderPipelines},
     renderer::RenderDevice,
     RenderApp, RenderStage,
 };
 use bevy_app::{App, Plugin};
-use bevy_asset::{AddAsset, Assets};
+use bevy_asset::{load_internal_asset, AddAsset, Assets};
 
 // TODO: replace Texture names with Image names?
 /// Adds the [`Image`] as an asset and makes sure that they are extracted and prepared for the GPU.
@@ -40,6 +49,15 @@ pub struct ImagePlugin;
 
 impl Plugin for ImagePlugin {
     fn build(&self, app: &mut App) {
+        load_internal_asset!(app, MIPMAP_SHADER_HANDLE, "mipmap.wgsl", Shader::from_wgsl);
+
+        // The loaders read whether to generate the mip levels of the images they load
+        let default_sampler = app
+            .world
+            .get_resource_or_insert_with(ImageSettings::default)
+            .default_sampler
+            .clone();
+
         #[cfg(any(
             feature = "png",
             feature = "dds",
@@ -58,6 +76,13 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<HdrTextureLoader>();
         }
 
+        #[cfg(feature = "png")]
+        {
+            app.add_asset_saver(PngTextureSaver);
+        }
+
+        app.init_asset_loader::<CubeLutLoader>();
+
         app.add_plugin(RenderAssetPlugin::<Image>::with_prepare_asset_label(
             PrepareAssetLabel::PreAssetPrepare,
         ))
@@ -66,11 +91,6 @@ impl Plugin for ImagePlugin {
             .resource_mut::<Assets<Image>>()
             .set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
 
-        let default_sampler = app
-            .world
-            .get_resource_or_insert_with(ImageSettings::default)
-            .default_sampler
-            .clone();
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             let default_sampler = {
                 let device = render_app.world.resource::<RenderDevice>();
@@ -80,6 +100,8 @@ impl Plugin for ImagePlugin {
                 .insert_resource(DefaultImageSampler(default_sampler))
                 .init_resource::<TextureCache>()
                 .init_resource::<FallbackImage>()
+                .init_resource::<MipmapPipeline>()
+                .init_resource::<SpecializedRenderPipelines<MipmapPipeline>>()
                 .add_system_to_stage(RenderStage::Cleanup, update_texture_cache_system);
         }
     }
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use image::ImageOutputFormat;
+use std::io::Cursor;
+
+use crate::texture::Image;
+
+/// Saves [`Image`]s as PNG files.
+///
+/// Only the formats supported by [`Image::try_into_dynamic`] can be saved.
+#[derive(Clone, Default)]
+pub struct PngTextureSaver;
+
+impl AssetSaver for PngTextureSaver {
+    type Asset = Image;
+
+    fn save(&self, image: &Image) -> Result<Vec<u8>> {
+        let mut bytes = Cursor::new(Vec::new());
+        image
+            .clone()
+            .try_into_dynamic()?
+            .write_to(&mut bytes, ImageOutputFormat::Png)?;
+        Ok(bytes.into_inner())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::{CompressedImageFormats, ImageType};
+    use wgpu::{Extent3d, TextureDimension, TextureFormat};
+
+    #[test]
+    fn png_round_trip() {
+        let data = (0..16).map(|i| i * 16).collect::<Vec<u8>>();
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data.clone(),
+            TextureFormat::Rgba8UnormSrgb,
+        );
+
+        let bytes = PngTextureSaver.save(&image).unwrap();
+        let loaded = Image::from_buffer(
+            &bytes,
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            loaded.texture_descriptor.size,
+            image.texture_descriptor.size
+        );
+        assert_eq!(loaded.data, data);
+    }
+}
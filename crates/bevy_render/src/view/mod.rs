@@ -1,15 +1,16 @@
+pub mod screenshot;
 pub mod visibility;
 pub mod window;
 
 pub use visibility::*;
 use wgpu::{
     Color, Extent3d, Operations, RenderPassColorAttachment, TextureDescriptor, TextureDimension,
-    TextureUsages,
+    TextureFormat, TextureUsages,
 };
 pub use window::*;
 
 use crate::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, RenderTarget, Viewport},
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     prelude::Image,
     rangefinder::ViewRangefinder3d,
@@ -25,6 +26,7 @@ use bevy_math::{Mat4, UVec4, Vec3, Vec4};
 use bevy_reflect::Reflect;
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
+use screenshot::{ScreenshotPlugin, ScreenshotSystem, ScreenshotTargets};
 
 pub struct ViewPlugin;
 
@@ -34,32 +36,55 @@ impl Plugin for ViewPlugin {
             .init_resource::<Msaa>()
             // NOTE: windows.is_changed() handles cases where a window was resized
             .add_plugin(ExtractResourcePlugin::<Msaa>::default())
-            .add_plugin(VisibilityPlugin);
+            .add_plugin(VisibilityPlugin)
+            .add_plugin(OcclusionCullingPlugin)
+            .add_plugin(ScreenshotPlugin);
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ViewUniforms>()
-                .add_system_to_stage(RenderStage::Prepare, prepare_view_uniforms)
                 .add_system_to_stage(
                     RenderStage::Prepare,
-                    prepare_view_targets.after(WindowSystem::Prepare),
+                    prepare_view_uniforms.label(ViewSystem::PrepareUniforms),
+                )
+                .add_system_to_stage(
+                    RenderStage::Prepare,
+                    prepare_view_targets
+                        .after(WindowSystem::Prepare)
+                        .after(ScreenshotSystem::Prepare),
                 );
         }
     }
 }
 
-/// Configuration resource for [Multi-Sample Anti-Aliasing](https://en.wikipedia.org/wiki/Multisample_anti-aliasing).
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ViewSystem {
+    /// Writes the [`ViewUniforms`] of the [`ExtractedView`]s, which can be adjusted before.
+    PrepareUniforms,
+}
+
+/// Configuration of [Multi-Sample Anti-Aliasing](https://en.wikipedia.org/wiki/Multisample_anti-aliasing).
+///
+/// The resource configures all the cameras, and a camera with this component uses its own
+/// configuration instead. Each view in the render world has the configuration of its camera.
 ///
 /// # Example
 /// ```
 /// # use bevy_app::prelude::App;
-/// # use bevy_render::prelude::Msaa;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::Camera, prelude::Msaa};
 /// App::new()
 ///     .insert_resource(Msaa { samples: 4 })
+///     .add_startup_system(spawn_minimap)
 ///     .run();
+///
+/// // A cheap camera without MSAA
+/// fn spawn_minimap(mut commands: Commands) {
+///     commands.spawn((Camera::default(), Msaa { samples: 1 }));
+/// }
 /// ```
-#[derive(Resource, Clone, ExtractResource, Reflect)]
-#[reflect(Resource)]
+#[derive(Resource, Component, Clone, ExtractResource, Reflect)]
+#[reflect(Resource, Component)]
 pub struct Msaa {
     /// The number of samples to run for Multi-Sample Anti-Aliasing. Higher numbers result in
     /// smoother edges.
@@ -103,6 +128,8 @@ pub struct ViewUniform {
     world_position: Vec3,
     // viewport(x_origin, y_origin, width, height)
     viewport: Vec4,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: Mat4,
 }
 
 #[derive(Resource, Default)]
@@ -135,6 +162,17 @@ impl ViewTarget {
     }
 }
 
+/// The target a view with a [`Camera::render_scale`](crate::camera::Camera::render_scale)
+/// other than `1.0` is upscaled to, its [`ViewTarget`] being an intermediate texture of the
+/// scaled size of its viewport.
+#[derive(Component)]
+pub struct ViewUpscalingTarget {
+    pub view: TextureView,
+    pub format: TextureFormat,
+    /// The viewport of the camera in the target.
+    pub viewport: Viewport,
+}
+
 #[derive(Component)]
 pub struct ViewDepthTexture {
     pub texture: Texture,
@@ -146,17 +184,21 @@ fn prepare_view_uniforms(
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut view_uniforms: ResMut<ViewUniforms>,
+    mut previous_view_projs: Local<HashMap<Entity, Mat4>>,
     views: Query<(Entity, &ExtractedView)>,
 ) {
     view_uniforms.uniforms.clear();
+    let mut view_projs = HashMap::default();
     for (entity, camera) in &views {
         let projection = camera.projection;
         let inverse_projection = projection.inverse();
         let view = camera.transform.compute_matrix();
         let inverse_view = view.inverse();
+        let view_proj = projection * inverse_view;
+        view_projs.insert(entity, view_proj);
         let view_uniforms = ViewUniformOffset {
             offset: view_uniforms.uniforms.push(ViewUniform {
-                view_proj: projection * inverse_view,
+                view_proj,
                 inverse_view_proj: view * inverse_projection,
                 view,
                 inverse_view,
@@ -164,11 +206,16 @@ fn prepare_view_uniforms(
                 inverse_projection,
                 world_position: camera.transform.translation(),
                 viewport: camera.viewport.as_vec4(),
+                previous_view_proj: previous_view_projs
+                    .get(&entity)
+                    .copied()
+                    .unwrap_or(view_proj),
             }),
         };
 
         commands.entity(entity).insert(view_uniforms);
     }
+    *previous_view_projs = view_projs;
 
     view_uniforms
         .uniforms
@@ -180,45 +227,89 @@ fn prepare_view_targets(
     mut commands: Commands,
     windows: Res<ExtractedWindows>,
     images: Res<RenderAssets<Image>>,
-    msaa: Res<Msaa>,
     render_device: Res<RenderDevice>,
     texture_format: Res<RenderTextureFormat>,
     mut texture_cache: ResMut<TextureCache>,
-    cameras: Query<(Entity, &ExtractedCamera)>,
+    screenshot_targets: Res<ScreenshotTargets>,
+    cameras: Query<(Entity, &ExtractedCamera, &Msaa)>,
 ) {
     let mut sampled_textures = HashMap::default();
-    for (entity, camera) in &cameras {
+    for (entity, camera, msaa) in &cameras {
         if let Some(target_size) = camera.physical_target_size {
-            if let Some(texture_view) = camera.target.get_texture_view(&windows, &images) {
-                let sampled_target = if msaa.samples > 1 {
-                    let sampled_texture = sampled_textures
-                        .entry(camera.target.clone())
-                        .or_insert_with(|| {
-                            texture_cache.get(
-                                &render_device,
-                                TextureDescriptor {
-                                    label: Some("sampled_color_attachment_texture"),
-                                    size: Extent3d {
-                                        width: target_size.x,
-                                        height: target_size.y,
-                                        depth_or_array_layers: 1,
-                                    },
-                                    mip_level_count: 1,
-                                    sample_count: msaa.samples,
-                                    dimension: TextureDimension::D2,
-                                    format: **texture_format,
-                                    usage: TextureUsages::RENDER_ATTACHMENT,
+            // Captured targets are rendered to an intermediate texture, copied to them afterwards
+            let texture_view = screenshot_targets
+                .texture_view(&camera.target)
+                .or_else(|| camera.target.get_texture_view(&windows, &images));
+            if let Some(texture_view) = texture_view {
+                let mut get_texture = |label, sample_count, usage| {
+                    texture_cache
+                        .get(
+                            &render_device,
+                            TextureDescriptor {
+                                label: Some(label),
+                                size: Extent3d {
+                                    width: target_size.x,
+                                    height: target_size.y,
+                                    depth_or_array_layers: 1,
                                 },
-                            )
+                                mip_level_count: 1,
+                                sample_count,
+                                dimension: TextureDimension::D2,
+                                format: **texture_format,
+                                usage,
+                            },
+                        )
+                        .default_view
+                };
+
+                // The upscaled cameras render to their own intermediate texture, the other ones
+                // share the sampled texture of their target
+                let view_target = match &camera.upscaled_viewport {
+                    Some(viewport) => {
+                        let format = match &camera.target {
+                            RenderTarget::Window(_) => **texture_format,
+                            RenderTarget::Image(handle) => match images.get(handle) {
+                                Some(image) => image.texture_format,
+                                None => continue,
+                            },
+                        };
+                        commands.entity(entity).insert(ViewUpscalingTarget {
+                            view: texture_view.clone(),
+                            format,
+                            viewport: viewport.clone(),
                         });
-                    Some(sampled_texture.default_view.clone())
-                } else {
-                    None
+                        ViewTarget {
+                            view: get_texture(
+                                "upscaling_input_texture",
+                                1,
+                                TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                            ),
+                            sampled_target: (msaa.samples > 1).then(|| {
+                                get_texture(
+                                    "sampled_color_attachment_texture",
+                                    msaa.samples,
+                                    TextureUsages::RENDER_ATTACHMENT,
+                                )
+                            }),
+                        }
+                    }
+                    None => ViewTarget {
+                        view: texture_view.clone(),
+                        sampled_target: (msaa.samples > 1).then(|| {
+                            sampled_textures
+                                .entry((camera.target.clone(), msaa.samples))
+                                .or_insert_with(|| {
+                                    get_texture(
+                                        "sampled_color_attachment_texture",
+                                        msaa.samples,
+                                        TextureUsages::RENDER_ATTACHMENT,
+                                    )
+                                })
+                                .clone()
+                        }),
+                    },
                 };
-                commands.entity(entity).insert(ViewTarget {
-                    view: texture_view.clone(),
-                    sampled_target,
-                });
+                commands.entity(entity).insert(view_target);
             }
         }
     }
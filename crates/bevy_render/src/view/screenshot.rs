@@ -0,0 +1,682 @@
+use std::{num::NonZeroU32, path::PathBuf, sync::Arc};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Assets, Handle, HandleId, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_reflect::TypeUuid;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{
+    tracing::{error, info, warn},
+    HashMap,
+};
+use parking_lot::Mutex;
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, Extent3d, ImageCopyBuffer, ImageCopyTexture,
+    ImageDataLayout, Maintain, MapMode, Origin3d, TextureAspect, TextureDescriptor,
+    TextureDimension, TextureUsages,
+};
+
+use crate::{
+    camera::{ExtractedCamera, RenderTarget},
+    main_graph::node::{CAMERA_DRIVER, SCREENSHOT},
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, Buffer, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState, Operations,
+        PipelineCache, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, Shader, ShaderStages, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, Texture, TextureFormat, TextureSampleType, TextureView,
+        TextureViewDimension, VertexState,
+    },
+    renderer::{RenderContext, RenderDevice, RenderTextureFormat},
+    texture::{Image, TextureFormatPixelInfo},
+    view::{ExtractedWindows, WindowSystem},
+    MainWorld, RenderApp, RenderStage,
+};
+
+const SCREENSHOT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2145006319058234233);
+
+/// Adds the [`ScreenshotManager`], capturing the frames rendered to windows and images.
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SCREENSHOT_SHADER_HANDLE,
+            "screenshot.wgsl",
+            Shader::from_wgsl
+        );
+        app.init_resource::<ScreenshotManager>()
+            .add_event::<ScreenshotCaptured>();
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<PendingScreenshots>()
+                .init_resource::<ScreenshotTargets>()
+                .init_resource::<ScreenshotBlitPipeline>()
+                .init_resource::<SpecializedRenderPipelines<ScreenshotBlitPipeline>>()
+                .add_system_to_stage(RenderStage::Extract, extract_screenshots)
+                .add_system_to_stage(
+                    RenderStage::Prepare,
+                    prepare_screenshots
+                        .label(ScreenshotSystem::Prepare)
+                        .after(WindowSystem::Prepare),
+                )
+                .add_system_to_stage(RenderStage::Cleanup, read_back_screenshots);
+
+            let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+            render_graph.add_node(SCREENSHOT, ScreenshotNode);
+            render_graph
+                .add_node_edge(CAMERA_DRIVER, SCREENSHOT)
+                .unwrap();
+        }
+    }
+}
+
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ScreenshotSystem {
+    /// Creates the textures the cameras render to instead of the captured [`RenderTarget`]s.
+    Prepare,
+}
+
+/// Captures the next frame rendered to a [`RenderTarget`], to an [`Image`] asset or to a file.
+///
+/// All the cameras rendering to a captured target render to an intermediate texture instead,
+/// which is then copied to the target and read back to the CPU without waiting for the GPU.
+/// The captured image is available a few frames after the request, once the GPU is done with
+/// the frame, so apps have to keep updating until then. This works the same without any
+/// window, e.g. to render images in tests.
+///
+/// The capture happens on the first frame a camera renders to the target, so requesting
+/// a screenshot of a window or image no camera renders to waits until one does.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::RenderTarget, view::screenshot::ScreenshotManager};
+/// # use bevy_window::WindowId;
+/// fn take_screenshot(mut screenshots: ResMut<ScreenshotManager>) {
+///     let window = RenderTarget::Window(WindowId::primary());
+///     screenshots.save_screenshot_to_disk(window, "screenshot.png");
+/// }
+/// # bevy_ecs::system::assert_is_system(take_screenshot);
+/// ```
+#[derive(Resource, Default)]
+pub struct ScreenshotManager {
+    requests: Vec<ScreenshotRequest>,
+}
+
+impl ScreenshotManager {
+    /// Captures the next frame rendered to `target` in a new [`Image`] asset.
+    ///
+    /// The returned handle has no asset until the frame has been read back, which sends a
+    /// [`ScreenshotCaptured`] event as well as an
+    /// [`AssetEvent::Created`](bevy_asset::AssetEvent::Created) for it. The image has the
+    /// format of the target, e.g. [`TextureFormat::Bgra8UnormSrgb`] for most windows.
+    pub fn capture_next_frame(
+        &mut self,
+        target: RenderTarget,
+        images: &Assets<Image>,
+    ) -> Handle<Image> {
+        let image = images.get_handle(HandleId::random::<Image>());
+        self.requests.push(ScreenshotRequest {
+            target,
+            image: Some(image.clone()),
+            path: None,
+        });
+        image
+    }
+
+    /// Captures the next frame rendered to `target`, and saves it to `path` in the format
+    /// matching its extension.
+    ///
+    /// The file is written on the [`IoTaskPool`], and errors are logged. Only the image
+    /// formats enabled with bevy's features can be written, e.g. `png`.
+    pub fn save_screenshot_to_disk(&mut self, target: RenderTarget, path: impl Into<PathBuf>) {
+        self.requests.push(ScreenshotRequest {
+            target,
+            image: None,
+            path: Some(path.into()),
+        });
+    }
+}
+
+/// Sent once the frame requested with [`ScreenshotManager::capture_next_frame`] has been read
+/// back, and its [`Image`] asset added.
+#[derive(Debug, Clone)]
+pub struct ScreenshotCaptured {
+    /// The captured window or image.
+    pub target: RenderTarget,
+    /// The captured frame.
+    pub image: Handle<Image>,
+}
+
+#[derive(Clone)]
+struct ScreenshotRequest {
+    target: RenderTarget,
+    image: Option<Handle<Image>>,
+    path: Option<PathBuf>,
+}
+
+impl ScreenshotRequest {
+    fn complete(
+        self,
+        images: &mut Assets<Image>,
+        events: &mut Events<ScreenshotCaptured>,
+        captured: Image,
+    ) {
+        if let Some(path) = self.path {
+            let captured = captured.clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    match captured
+                        .try_into_dynamic()
+                        .and_then(|image| Ok(image.save(&path)?))
+                    {
+                        Ok(()) => info!("Screenshot saved to {}", path.display()),
+                        Err(err) => error!("Cannot save screenshot to {}: {err}", path.display()),
+                    }
+                })
+                .detach();
+        }
+        if let Some(image) = self.image {
+            images.set_untracked(&image, captured);
+            events.send(ScreenshotCaptured {
+                target: self.target,
+                image,
+            });
+        }
+    }
+}
+
+/// The screenshot requests of the render world, and the frames captured for them.
+#[derive(Resource, Default)]
+struct PendingScreenshots {
+    requests: Vec<ScreenshotRequest>,
+    reading: Vec<ScreenshotReadback>,
+    captured: Vec<(Vec<ScreenshotRequest>, Image)>,
+}
+
+fn extract_screenshots(mut main_world: ResMut<MainWorld>, mut pending: ResMut<PendingScreenshots>) {
+    if !pending.captured.is_empty() {
+        main_world.resource_scope(|main_world, mut images: Mut<Assets<Image>>| {
+            let mut events = main_world.resource_mut::<Events<ScreenshotCaptured>>();
+            for (requests, captured) in pending.captured.drain(..) {
+                for request in requests {
+                    request.complete(&mut images, &mut events, captured.clone());
+                }
+            }
+        });
+    }
+    let mut screenshots = main_world.resource_mut::<ScreenshotManager>();
+    pending.requests.append(&mut screenshots.requests);
+}
+
+/// The intermediate textures replacing the captured [`RenderTarget`]s this frame.
+#[derive(Resource, Default)]
+pub struct ScreenshotTargets {
+    targets: HashMap<RenderTarget, PreparedScreenshot>,
+}
+
+impl ScreenshotTargets {
+    /// The texture the cameras rendering to `target` render to this frame, if it's captured.
+    pub fn texture_view(&self, target: &RenderTarget) -> Option<&TextureView> {
+        self.targets.get(target).map(|screenshot| &screenshot.view)
+    }
+}
+
+struct PreparedScreenshot {
+    texture: Texture,
+    view: TextureView,
+    bind_group: BindGroup,
+    pipeline: CachedRenderPipelineId,
+    buffer: Buffer,
+    size: UVec2,
+    format: TextureFormat,
+    requests: Vec<ScreenshotRequest>,
+}
+
+/// A captured frame being copied from the GPU.
+struct ScreenshotReadback {
+    buffer: Buffer,
+    size: UVec2,
+    format: TextureFormat,
+    requests: Vec<ScreenshotRequest>,
+    mapped: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+}
+
+/// The number of bytes per row of the buffer a frame of `size` is copied to.
+fn padded_bytes_per_row(size: UVec2, format: TextureFormat) -> usize {
+    RenderDevice::align_copy_bytes_per_row(size.x as usize * format.pixel_size())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_screenshots(
+    mut pending: ResMut<PendingScreenshots>,
+    mut targets: ResMut<ScreenshotTargets>,
+    windows: Res<ExtractedWindows>,
+    images: Res<RenderAssets<Image>>,
+    texture_format: Res<RenderTextureFormat>,
+    render_device: Res<RenderDevice>,
+    blit_pipeline: Res<ScreenshotBlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ScreenshotBlitPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    cameras: Query<&ExtractedCamera>,
+) {
+    targets.targets.clear();
+    for request in std::mem::take(&mut pending.requests) {
+        let (size, format) = match &request.target {
+            RenderTarget::Window(id) => match windows.get(id) {
+                Some(window) if window.swap_chain_texture.is_some() => (
+                    UVec2::new(window.physical_width, window.physical_height),
+                    **texture_format,
+                ),
+                Some(_) => {
+                    pending.requests.push(request);
+                    continue;
+                }
+                None => {
+                    warn!("Cannot capture {:?}, the window was closed", request.target);
+                    continue;
+                }
+            },
+            RenderTarget::Image(handle) => match images.get(handle) {
+                Some(image) => (image.size.as_uvec2(), image.texture_format),
+                None => {
+                    pending.requests.push(request);
+                    continue;
+                }
+            },
+        };
+        if let Some(screenshot) = targets.targets.get_mut(&request.target) {
+            screenshot.requests.push(request);
+            continue;
+        }
+
+        // The cameras are only redirected once the target can be copied back, and if one of
+        // them renders to it
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &blit_pipeline, format);
+        if pipeline_cache.get_render_pipeline(pipeline).is_none()
+            || !cameras.iter().any(|camera| camera.target == request.target)
+        {
+            pending.requests.push(request);
+            continue;
+        }
+
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("screenshot_texture"),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+        });
+        let view = texture.create_view(&Default::default());
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("screenshot_bind_group"),
+            layout: &blit_pipeline.layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&view),
+            }],
+        });
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("screenshot_buffer"),
+            size: (padded_bytes_per_row(size, format) * size.y as usize) as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        targets.targets.insert(
+            request.target.clone(),
+            PreparedScreenshot {
+                texture,
+                view,
+                bind_group,
+                pipeline,
+                buffer,
+                size,
+                format,
+                requests: vec![request],
+            },
+        );
+    }
+}
+
+/// Copies the captured frames to their [`RenderTarget`] and to the buffers they're read back from.
+pub struct ScreenshotNode;
+
+impl Node for ScreenshotNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let targets = world.resource::<ScreenshotTargets>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let windows = world.resource::<ExtractedWindows>();
+        let images = world.resource::<RenderAssets<Image>>();
+        for (target, screenshot) in &targets.targets {
+            let (destination, pipeline) = match (
+                target.get_texture_view(windows, images),
+                pipeline_cache.get_render_pipeline(screenshot.pipeline),
+            ) {
+                (Some(destination), Some(pipeline)) => (destination, pipeline),
+                _ => continue,
+            };
+
+            {
+                let mut pass =
+                    render_context
+                        .command_encoder
+                        .begin_render_pass(&RenderPassDescriptor {
+                            label: Some("screenshot_blit_pass"),
+                            color_attachments: &[Some(RenderPassColorAttachment {
+                                view: destination,
+                                resolve_target: None,
+                                ops: Operations {
+                                    load: LoadOp::Load,
+                                    store: true,
+                                },
+                            })],
+                            depth_stencil_attachment: None,
+                        });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &screenshot.bind_group, &[]);
+                pass.draw(0..3, 0..1);
+            }
+
+            render_context.command_encoder.copy_texture_to_buffer(
+                ImageCopyTexture {
+                    texture: &screenshot.texture,
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                ImageCopyBuffer {
+                    buffer: &screenshot.buffer,
+                    layout: ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: NonZeroU32::new(padded_bytes_per_row(
+                            screenshot.size,
+                            screenshot.format,
+                        ) as u32),
+                        rows_per_image: None,
+                    },
+                },
+                Extent3d {
+                    width: screenshot.size.x,
+                    height: screenshot.size.y,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Starts reading back the frames captured this frame, and collects the ones read back since
+/// for the next extraction to hand them to the main world.
+fn read_back_screenshots(
+    render_device: Res<RenderDevice>,
+    mut targets: ResMut<ScreenshotTargets>,
+    mut pending: ResMut<PendingScreenshots>,
+) {
+    for (_, screenshot) in targets.targets.drain() {
+        let mapped = Arc::new(Mutex::new(None));
+        let result = mapped.clone();
+        render_device.map_buffer(&screenshot.buffer.slice(..), MapMode::Read, move |mapped| {
+            *result.lock() = Some(mapped);
+        });
+        pending.reading.push(ScreenshotReadback {
+            buffer: screenshot.buffer,
+            size: screenshot.size,
+            format: screenshot.format,
+            requests: screenshot.requests,
+            mapped,
+        });
+    }
+    if pending.reading.is_empty() {
+        return;
+    }
+
+    // Calls the callbacks of the buffers mapped since the last frame, without waiting for
+    // the others
+    render_device.poll(Maintain::Poll);
+    for readback in std::mem::take(&mut pending.reading) {
+        let mapped = readback.mapped.lock().take();
+        match mapped {
+            Some(Ok(())) => {
+                let row_bytes = readback.size.x as usize * readback.format.pixel_size();
+                let data = readback
+                    .buffer
+                    .slice(..)
+                    .get_mapped_range()
+                    .chunks(padded_bytes_per_row(readback.size, readback.format))
+                    .flat_map(|row| &row[..row_bytes])
+                    .copied()
+                    .collect();
+                readback.buffer.unmap();
+                let image = Image::new(
+                    Extent3d {
+                        width: readback.size.x,
+                        height: readback.size.y,
+                        depth_or_array_layers: 1,
+                    },
+                    TextureDimension::D2,
+                    data,
+                    readback.format,
+                );
+                pending.captured.push((readback.requests, image));
+            }
+            Some(Err(err)) => error!("Cannot read back screenshot: {err}"),
+            None => pending.reading.push(readback),
+        }
+    }
+}
+
+/// Copies the intermediate texture of a captured frame to its [`RenderTarget`].
+#[derive(Resource)]
+pub struct ScreenshotBlitPipeline {
+    layout: BindGroupLayout,
+}
+
+impl FromWorld for ScreenshotBlitPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("screenshot_layout"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable: false },
+                    view_dimension: TextureViewDimension::D2,
+                },
+                count: None,
+            }],
+        });
+        ScreenshotBlitPipeline { layout }
+    }
+}
+
+impl SpecializedRenderPipeline for ScreenshotBlitPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: SCREENSHOT_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: SCREENSHOT_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.layout.clone()]),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("screenshot_blit_pipeline".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::AssetPlugin;
+    use bevy_core::CorePlugin;
+    use bevy_math::Vec3;
+    use bevy_time::TimePlugin;
+    use bevy_transform::components::GlobalTransform;
+    use bevy_window::{WindowPlugin, WindowSettings};
+
+    use super::*;
+    use crate::{
+        camera::{Camera, CameraRenderGraph, OrthographicProjection},
+        color::Color,
+        render_graph::{SlotInfo, SlotType},
+        view::{Msaa, ViewTarget, VisibleEntities},
+        RenderPlugin,
+    };
+
+    /// Clears the view to a color, standing in for the passes of a real camera.
+    struct ClearNode(Color);
+
+    impl Node for ClearNode {
+        fn input(&self) -> Vec<SlotInfo> {
+            vec![SlotInfo::new("view", SlotType::Entity)]
+        }
+
+        fn run(
+            &self,
+            graph: &mut RenderGraphContext,
+            render_context: &mut RenderContext,
+            world: &World,
+        ) -> Result<(), NodeRunError> {
+            let view = graph.get_input_entity("view")?;
+            if let Some(target) = world.get::<ViewTarget>(view) {
+                render_context
+                    .command_encoder
+                    .begin_render_pass(&RenderPassDescriptor {
+                        label: Some("clear_pass"),
+                        color_attachments: &[Some(target.get_color_attachment(Operations {
+                            load: LoadOp::Clear(self.0.into()),
+                            store: true,
+                        }))],
+                        depth_stencil_attachment: None,
+                    });
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn captures_image_targets() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(TimePlugin)
+            .add_plugin(AssetPlugin)
+            .insert_resource(WindowSettings {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            })
+            .add_plugin(WindowPlugin)
+            .add_plugin(RenderPlugin);
+        app.insert_resource(Msaa { samples: 1 });
+        let render_app = app.sub_app_mut(RenderApp);
+        let mut graph = RenderGraph::default();
+        graph.add_node("clear", ClearNode(Color::rgb(1.0, 0.5, 0.0)));
+        let input = graph.set_input(vec![SlotInfo::new("view", SlotType::Entity)]);
+        graph.add_slot_edge(input, "view", "clear", "view").unwrap();
+        render_app
+            .world
+            .resource_mut::<RenderGraph>()
+            .add_sub_graph("clear", graph);
+
+        // The target doesn't need to be copyable, the frame is captured from a copy
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 70,
+                height: 30,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        image.texture_descriptor.usage = TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST;
+        let target = app.world.resource_mut::<Assets<Image>>().add(image);
+        app.world.spawn((
+            Camera {
+                target: RenderTarget::Image(target.clone()),
+                ..Default::default()
+            },
+            CameraRenderGraph::new("clear"),
+            OrthographicProjection::default(),
+            GlobalTransform::from_translation(Vec3::Z),
+            VisibleEntities::default(),
+        ));
+
+        let handle = app
+            .world
+            .resource_scope(|world, mut screenshots: Mut<ScreenshotManager>| {
+                screenshots.capture_next_frame(
+                    RenderTarget::Image(target.clone()),
+                    world.resource::<Assets<Image>>(),
+                )
+            });
+
+        // The blit pipeline is compiled during the first frames, and the captured frame is
+        // handed to the main world at the first extraction after it has been read back
+        let mut frames = 0;
+        let captured = loop {
+            app.update();
+            if let Some(captured) = app.world.resource::<Assets<Image>>().get(&handle) {
+                break captured.clone();
+            }
+            frames += 1;
+            assert!(frames < 100, "the frame was never captured");
+        };
+        let events = app.world.resource::<Events<ScreenshotCaptured>>();
+        let captured_events: Vec<_> = events.get_reader().iter(events).cloned().collect();
+        assert_eq!(captured_events.len(), 1);
+        assert_eq!(captured_events[0].image, handle);
+        assert_eq!(captured_events[0].target, RenderTarget::Image(target));
+
+        assert_eq!(captured.size(), bevy_math::Vec2::new(70.0, 30.0));
+        assert_eq!(
+            captured.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        for pixel in captured.data.chunks_exact(4) {
+            for (channel, expected) in pixel.iter().zip([255, 128, 0, 255]) {
+                assert!(channel.abs_diff(expected) <= 1, "{pixel:?}");
+            }
+        }
+    }
+}
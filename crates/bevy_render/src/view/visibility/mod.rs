@@ -1,16 +1,22 @@
+mod occlusion;
 mod render_layers;
 
+pub use occlusion::*;
 pub use render_layers::*;
 
 use bevy_app::{CoreStage, Plugin};
-use bevy_asset::{Assets, Handle};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::{Children, Parent};
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::Reflect;
 use bevy_transform::components::GlobalTransform;
 use bevy_transform::TransformSystem;
-use std::cell::Cell;
+use bevy_utils::HashSet;
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use thread_local::ThreadLocal;
 
 use crate::{
@@ -25,8 +31,10 @@ use crate::{
 /// User indication of whether an entity is visible. Propagates down the entity hierarchy.
 
 /// If an entity is hidden in this way,  all [`Children`] (and all of their children and so on) will also be hidden.
-/// This is done by setting the values of their [`ComputedVisibility`] component.
+/// This is done by setting the values of their [`ComputedVisibility`] component, which is
+/// inserted along with a [`Visibility`] if it is missing.
 #[derive(Component, Clone, Reflect, Debug)]
+#[component(require(ComputedVisibility))]
 #[reflect(Component, Default)]
 pub struct Visibility {
     /// Indicates whether this entity is visible. Hidden values will propagate down the entity hierarchy.
@@ -134,6 +142,20 @@ pub struct VisibilityBundle {
 #[derive(Component)]
 pub struct NoFrustumCulling;
 
+/// Overrides the result of frustum culling for an entity, e.g. to keep an entity and its
+/// debug visuals alive while it's off-screen.
+///
+/// This only replaces the [`Frustum`]/[`Aabb`] test: an entity hidden through its
+/// [`Visibility`], or the [`Visibility`] of one of its ancestors, stays hidden regardless
+/// of this component, and [`RenderLayers`] still apply.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CullingOverride {
+    /// Never frustum-cull the entity, as if it intersected every view.
+    NoCulling,
+    /// Always cull the entity, as if it was outside of every view.
+    ForceCulled,
+}
+
 /// Collection of entities visible from the current view.
 ///
 /// This component contains all entities which are visible from the currently
@@ -151,6 +173,12 @@ pub struct NoFrustumCulling;
 pub struct VisibleEntities {
     #[reflect(ignore)]
     pub entities: Vec<Entity>,
+    /// The number of entities that would otherwise be visible from this view but were culled,
+    /// either by the view's [`Frustum`], by its [`OcclusionCulling`] or by a
+    /// [`CullingOverride::ForceCulled`].
+    ///
+    /// Only counted for cameras, by [`check_visibility()`].
+    pub culled: usize,
 }
 
 impl VisibleEntities {
@@ -189,6 +217,10 @@ impl Plugin for VisibilityPlugin {
             CoreStage::PostUpdate,
             calculate_bounds.label(CalculateBounds),
         )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_modified_mesh_aabbs.label(CalculateBounds),
+        )
         .add_system_to_stage(
             CoreStage::PostUpdate,
             update_frusta::<OrthographicProjection>
@@ -242,6 +274,39 @@ pub fn calculate_bounds(
     }
 }
 
+/// Updates the [`Aabb`] of entities whose [`Mesh`] was modified, using the AABB stored by
+/// [`Mesh::recompute_aabb`], or computing it if none is stored.
+pub fn update_modified_mesh_aabbs(
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut with_aabb: Query<(&Handle<Mesh>, &mut Aabb), Without<NoFrustumCulling>>,
+) {
+    let modified: HashSet<HandleId> = mesh_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+
+    for (mesh_handle, mut aabb) in &mut with_aabb {
+        if !modified.contains(&mesh_handle.id()) {
+            continue;
+        }
+        let mesh_aabb = meshes
+            .get(mesh_handle)
+            .and_then(|mesh| mesh.aabb().cloned().or_else(|| mesh.compute_aabb()));
+        if let Some(mesh_aabb) = mesh_aabb {
+            if *aabb != mesh_aabb {
+                *aabb = mesh_aabb;
+            }
+        }
+    }
+}
+
 pub fn update_frusta<T: Component + CameraProjection + Send + Sync + 'static>(
     mut views: Query<(&GlobalTransform, &T, &mut Frustum)>,
 ) {
@@ -326,7 +391,15 @@ const VISIBLE_ENTITIES_QUERY_BATCH_SIZE: usize = 1024;
 /// for that view.
 pub fn check_visibility(
     mut thread_queues: Local<ThreadLocal<Cell<Vec<Entity>>>>,
-    mut view_query: Query<(&mut VisibleEntities, &Frustum, Option<&RenderLayers>), With<Camera>>,
+    mut view_query: Query<
+        (
+            &mut VisibleEntities,
+            &Frustum,
+            Option<&RenderLayers>,
+            Option<&OcclusionCulling>,
+        ),
+        With<Camera>,
+    >,
     mut visible_aabb_query: Query<(
         Entity,
         &mut ComputedVisibility,
@@ -334,15 +407,25 @@ pub fn check_visibility(
         &Aabb,
         &GlobalTransform,
         Option<&NoFrustumCulling>,
+        Option<&CullingOverride>,
     )>,
     mut visible_no_aabb_query: Query<
-        (Entity, &mut ComputedVisibility, Option<&RenderLayers>),
+        (
+            Entity,
+            &mut ComputedVisibility,
+            Option<&RenderLayers>,
+            Option<&CullingOverride>,
+        ),
         Without<Aabb>,
     >,
 ) {
-    for (mut visible_entities, frustum, maybe_view_mask) in &mut view_query {
+    for (mut visible_entities, frustum, maybe_view_mask, maybe_occlusion_culling) in &mut view_query
+    {
         let view_mask = maybe_view_mask.copied().unwrap_or_default();
+        let hierarchical_depth =
+            maybe_occlusion_culling.and_then(OcclusionCulling::hierarchical_depth);
         visible_entities.entities.clear();
+        let culled = AtomicUsize::new(0);
         visible_aabb_query.par_for_each_mut(
             VISIBLE_ENTITIES_QUERY_BATCH_SIZE,
             |(
@@ -352,6 +435,7 @@ pub fn check_visibility(
                 model_aabb,
                 transform,
                 maybe_no_frustum_culling,
+                maybe_culling_override,
             )| {
                 // skip computing visibility for entities that are configured to be hidden. is_visible_in_view has already been set to false
                 // in visibility_propagate_system
@@ -364,8 +448,13 @@ pub fn check_visibility(
                     return;
                 }
 
+                if maybe_culling_override == Some(&CullingOverride::ForceCulled) {
+                    culled.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+
                 // If we have an aabb and transform, do frustum culling
-                if maybe_no_frustum_culling.is_none() {
+                if maybe_no_frustum_culling.is_none() && maybe_culling_override.is_none() {
                     let model = transform.compute_matrix();
                     let model_sphere = Sphere {
                         center: model.transform_point3a(model_aabb.center),
@@ -373,12 +462,21 @@ pub fn check_visibility(
                     };
                     // Do quick sphere-based frustum culling
                     if !frustum.intersects_sphere(&model_sphere, false) {
+                        culled.fetch_add(1, Ordering::Relaxed);
                         return;
                     }
                     // If we have an aabb, do aabb-based frustum culling
                     if !frustum.intersects_obb(model_aabb, &model, false) {
+                        culled.fetch_add(1, Ordering::Relaxed);
                         return;
                     }
+                    // If the view has a depth read back, do occlusion culling
+                    if let Some(hierarchical_depth) = hierarchical_depth {
+                        if hierarchical_depth.is_occluded(model_aabb, &model) {
+                            culled.fetch_add(1, Ordering::Relaxed);
+                            return;
+                        }
+                    }
                 }
 
                 computed_visibility.is_visible_in_view = true;
@@ -391,7 +489,7 @@ pub fn check_visibility(
 
         visible_no_aabb_query.par_for_each_mut(
             VISIBLE_ENTITIES_QUERY_BATCH_SIZE,
-            |(entity, mut computed_visibility, maybe_entity_mask)| {
+            |(entity, mut computed_visibility, maybe_entity_mask, maybe_culling_override)| {
                 // skip computing visibility for entities that are configured to be hidden. is_visible_in_view has already been set to false
                 // in visibility_propagate_system
                 if !computed_visibility.is_visible_in_hierarchy() {
@@ -403,6 +501,11 @@ pub fn check_visibility(
                     return;
                 }
 
+                if maybe_culling_override == Some(&CullingOverride::ForceCulled) {
+                    culled.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+
                 computed_visibility.is_visible_in_view = true;
                 let cell = thread_queues.get_or_default();
                 let mut queue = cell.take();
@@ -414,6 +517,7 @@ pub fn check_visibility(
         for cell in thread_queues.iter_mut() {
             visible_entities.entities.append(cell.get_mut());
         }
+        visible_entities.culled = culled.into_inner();
     }
 }
 
@@ -424,7 +528,11 @@ mod test {
 
     use super::*;
 
+    use bevy_asset::AddAsset;
     use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::{Mat4, Vec3};
+
+    use crate::mesh::VertexAttributeValues;
 
     #[test]
     fn visibility_propagation() {
@@ -553,4 +661,101 @@ mod test {
             "child's invisibility propagates down to grandchild"
         );
     }
+
+    #[test]
+    fn culling_override() {
+        bevy_tasks::ComputeTaskPool::init(bevy_tasks::TaskPool::default);
+        let mut app = App::new();
+        app.add_system(visibility_propagate_system.before(check_visibility))
+            .add_system(check_visibility);
+
+        // An orthographic camera at the origin looking down -Z
+        let projection = Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0);
+        let frustum = Frustum::from_view_projection(&projection, &Vec3::ZERO, &Vec3::Z, 10.0);
+        app.world
+            .spawn((Camera::default(), frustum, VisibleEntities::default()));
+
+        let mut spawn =
+            |x: f32, visibility: Visibility, culling_override: Option<CullingOverride>| {
+                let mut entity = app.world.spawn((
+                    visibility,
+                    ComputedVisibility::default(),
+                    Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)),
+                    GlobalTransform::from_xyz(x, 0.0, -5.0),
+                ));
+                if let Some(culling_override) = culling_override {
+                    entity.insert(culling_override);
+                }
+                entity.id()
+            };
+        let inside = spawn(0.0, Visibility::VISIBLE, None);
+        let outside = spawn(100.0, Visibility::VISIBLE, None);
+        let outside_no_culling =
+            spawn(100.0, Visibility::VISIBLE, Some(CullingOverride::NoCulling));
+        let inside_force_culled =
+            spawn(0.0, Visibility::VISIBLE, Some(CullingOverride::ForceCulled));
+        let hidden_no_culling = spawn(0.0, Visibility::INVISIBLE, Some(CullingOverride::NoCulling));
+
+        app.update();
+
+        let is_visible = |e: Entity| app.world.get::<ComputedVisibility>(e).unwrap().is_visible();
+        assert!(is_visible(inside));
+        assert!(!is_visible(outside));
+        assert!(
+            is_visible(outside_no_culling),
+            "NoCulling keeps entities outside the frustum visible"
+        );
+        assert!(
+            !is_visible(inside_force_culled),
+            "ForceCulled hides entities inside the frustum"
+        );
+        assert!(
+            !is_visible(hidden_no_culling),
+            "NoCulling doesn't override Visibility"
+        );
+    }
+
+    #[test]
+    fn modified_mesh_updates_aabbs() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin)
+            .add_plugin(bevy_asset::AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_system(calculate_bounds)
+            .add_system(update_modified_mesh_aabbs);
+
+        let handle = app
+            .world
+            .resource_mut::<Assets<Mesh>>()
+            .add(crate::mesh::shape::Cube::new(2.0).into());
+        let entities = [(); 2].map(|_| app.world.spawn(handle.clone()).id());
+        app.update();
+        let unit_aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        for entity in entities {
+            assert_eq!(app.world.get::<Aabb>(entity), Some(&unit_aabb));
+        }
+
+        {
+            let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+            let mesh = meshes.get_mut(&handle).unwrap();
+            if let Some(VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+            {
+                for position in positions {
+                    position[1] += 3.0;
+                }
+            }
+            assert!(mesh.aabb().is_none());
+            let aabb = mesh.recompute_aabb();
+            assert_eq!(mesh.aabb(), aabb.as_ref());
+        }
+        // `AssetEvent::Modified` is sent at the end of the frame, and read during the next one.
+        app.update();
+        app.update();
+
+        let moved_aabb = Aabb::from_min_max(Vec3::new(-1.0, 2.0, -1.0), Vec3::new(1.0, 4.0, 1.0));
+        for entity in entities {
+            assert_eq!(app.world.get::<Aabb>(entity), Some(&moved_aabb));
+        }
+    }
 }
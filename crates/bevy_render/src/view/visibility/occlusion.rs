@@ -0,0 +1,626 @@
+use std::sync::Arc;
+
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_math::{Mat4, UVec2, UVec4, Vec2, Vec3, Vec4Swizzles};
+use bevy_reflect::TypeUuid;
+use bevy_utils::{tracing::error, HashMap};
+use parking_lot::Mutex;
+use wgpu::{BufferAsyncError, Maintain, MapMode};
+
+use crate::{
+    camera::Camera,
+    main_graph::node::{CAMERA_DRIVER, HIERARCHICAL_DEPTH},
+    primitives::Aabb,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+        BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferBindingType,
+        BufferDescriptor, BufferUsages, CachedComputePipelineId, ComputePassDescriptor,
+        ComputePipelineDescriptor, PipelineCache, Shader, ShaderStages, ShaderType,
+        TextureSampleType, TextureViewDimension, UniformBuffer,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    view::{ExtractedView, Msaa, ViewDepthTexture},
+    Extract, RenderApp, RenderStage,
+};
+
+use super::VisibilitySystems;
+
+const OCCLUSION_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7165403962740164359);
+
+/// The largest width and height of the level 0 of the [`HierarchicalDepth`]s read back from the
+/// GPU.
+const MAX_HIERARCHICAL_DEPTH_SIZE: u32 = 256;
+
+/// Reads back the depth of the cameras with [`OcclusionCulling`], which
+/// [`check_visibility`](super::check_visibility) culls the entities hidden behind with.
+pub struct OcclusionCullingPlugin;
+
+impl Plugin for OcclusionCullingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OCCLUSION_SHADER_HANDLE,
+            "occlusion.wgsl",
+            Shader::from_wgsl
+        );
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            receive_hierarchical_depths.before(VisibilitySystems::CheckVisibility),
+        );
+
+        // The depth is reduced by a compute shader, which isn't available on the web
+        if cfg!(feature = "webgl") {
+            return;
+        }
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<HierarchicalDepths>()
+                .init_resource::<HierarchicalDepthPipeline>()
+                .add_system_to_stage(RenderStage::Extract, extract_occlusion_culling)
+                .add_system_to_stage(RenderStage::Queue, queue_hierarchical_depths)
+                .add_system_to_stage(RenderStage::Cleanup, read_back_hierarchical_depths);
+
+            let mut graph = render_app.world.resource_mut::<RenderGraph>();
+            graph.add_node(HIERARCHICAL_DEPTH, HierarchicalDepthNode);
+            graph
+                .add_node_edge(CAMERA_DRIVER, HIERARCHICAL_DEPTH)
+                .unwrap();
+        }
+    }
+}
+
+/// Culls the entities hidden behind what a camera rendered in the previous frames, with a
+/// [`HierarchicalDepth`] read back from the GPU.
+///
+/// Once the camera has rendered, the farthest depth of each tile of its viewport is reduced on
+/// the GPU and read back without waiting for it, then reduced further on the CPU into the levels
+/// of a [`HierarchicalDepth`]. On top of frustum culling,
+/// [`check_visibility`](super::check_visibility) culls the entities whose [`Aabb`] is behind the
+/// farthest depth everywhere it covers. The entities without an [`Aabb`], or with a
+/// [`NoFrustumCulling`](super::NoFrustumCulling) or a [`CullingOverride`](super::CullingOverride),
+/// aren't occlusion culled.
+///
+/// The depth is a few frames old when it's used, and tested from where the camera was then, so
+/// the entities moving out from behind an occluder, or revealed by the camera moving, can appear a
+/// few frames late. It works best with large static occluders, such as the buildings of a city.
+///
+/// The camera needs a depth texture, like the 3D cameras, which it shares with the cameras
+/// rendering to the same target, so it should be the last of them. Nothing is occlusion culled
+/// with the `webgl` feature, since there are no compute shaders on the web.
+#[derive(Component)]
+pub struct OcclusionCulling {
+    sender: Sender<HierarchicalDepth>,
+    receiver: Receiver<HierarchicalDepth>,
+    depth: Option<HierarchicalDepth>,
+}
+
+impl Default for OcclusionCulling {
+    fn default() -> Self {
+        let (sender, receiver) = async_channel::bounded(1);
+        Self {
+            sender,
+            receiver,
+            depth: None,
+        }
+    }
+}
+
+impl OcclusionCulling {
+    /// The latest depth of the camera read back from the GPU, if any.
+    pub fn hierarchical_depth(&self) -> Option<&HierarchicalDepth> {
+        self.depth.as_ref()
+    }
+}
+
+fn receive_hierarchical_depths(mut cameras: Query<&mut OcclusionCulling>) {
+    for mut occlusion_culling in &mut cameras {
+        if let Ok(depth) = occlusion_culling.receiver.try_recv() {
+            occlusion_culling.depth = Some(depth);
+        }
+    }
+}
+
+/// The farthest depth over the tiles of the viewport of a camera, at halving resolutions down to
+/// a single texel, used by [`OcclusionCulling`].
+///
+/// The depth is reversed, from `1.0` on the near plane to `0.0` on the far plane.
+#[derive(Clone, Debug)]
+pub struct HierarchicalDepth {
+    view_projection: Mat4,
+    texels_per_uv: Vec2,
+    levels: Vec<DepthLevel>,
+}
+
+#[derive(Clone, Debug)]
+struct DepthLevel {
+    size: UVec2,
+    depth: Vec<f32>,
+}
+
+impl DepthLevel {
+    fn get(&self, texel: UVec2) -> f32 {
+        self.depth[(texel.y * self.size.x + texel.x) as usize]
+    }
+}
+
+impl HierarchicalDepth {
+    /// Builds the coarser levels of the level 0 of `size` texels, whose farthest depths are
+    /// given row by row in `depth`.
+    ///
+    /// `view_projection` is the transform from world space to the clip space the depth was
+    /// rendered in, and `texels_per_uv` the size of the viewport in texels of the level 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is empty, or if `depth` has fewer values than texels.
+    pub fn new(view_projection: Mat4, texels_per_uv: Vec2, size: UVec2, depth: Vec<f32>) -> Self {
+        assert!(size.x > 0 && size.y > 0 && depth.len() >= (size.x * size.y) as usize);
+        let mut levels = vec![DepthLevel { size, depth }];
+        while levels.last().unwrap().size != UVec2::ONE {
+            let previous = levels.last().unwrap();
+            let size = (previous.size + UVec2::ONE) / 2;
+            let mut depth = Vec::with_capacity((size.x * size.y) as usize);
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    let farthest = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                        .into_iter()
+                        .map(|(dx, dy)| {
+                            let texel = UVec2::new(2 * x + dx, 2 * y + dy);
+                            previous.get(texel.min(previous.size - UVec2::ONE))
+                        })
+                        .fold(f32::INFINITY, f32::min);
+                    depth.push(farthest);
+                }
+            }
+            levels.push(DepthLevel { size, depth });
+        }
+        Self {
+            view_projection,
+            texels_per_uv,
+            levels,
+        }
+    }
+
+    /// Returns whether the [`Aabb`] transformed by `model` is behind the farthest depth
+    /// everywhere it covers the viewport.
+    pub fn is_occluded(&self, aabb: &Aabb, model: &Mat4) -> bool {
+        let clip_from_local = self.view_projection * *model;
+        let center = Vec3::from(aabb.center);
+        let half_extents = Vec3::from(aabb.half_extents);
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+        for corner in 0..8 {
+            let sign = Vec3::new(
+                if corner & 1 == 0 { -1.0 } else { 1.0 },
+                if corner & 2 == 0 { -1.0 } else { 1.0 },
+                if corner & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            let clip = clip_from_local * (center + sign * half_extents).extend(1.0);
+            // The corners behind the camera could be anywhere on the viewport
+            if clip.w <= 0.0 {
+                return false;
+            }
+            let ndc = clip.xyz() / clip.w;
+            min = min.min(ndc);
+            max = max.max(ndc);
+        }
+        // Crossing the near plane
+        if max.z >= 1.0 {
+            return false;
+        }
+
+        let uv_min = (Vec2::new(min.x, -max.y) * 0.5 + 0.5).clamp(Vec2::ZERO, Vec2::ONE);
+        let uv_max = (Vec2::new(max.x, -min.y) * 0.5 + 0.5).clamp(Vec2::ZERO, Vec2::ONE);
+        let last = (self.levels[0].size - UVec2::ONE).as_vec2();
+        let start = (uv_min * self.texels_per_uv).floor().min(last).as_uvec2();
+        let end = (uv_max * self.texels_per_uv).floor().min(last).as_uvec2();
+
+        // The finest level the Aabb covers at most 2x2 texels of
+        let at_level = |texel: UVec2, level: usize| UVec2::new(texel.x >> level, texel.y >> level);
+        let mut level = 0;
+        while level + 1 < self.levels.len()
+            && (at_level(end, level) - at_level(start, level)).max_element() > 1
+        {
+            level += 1;
+        }
+
+        let (start, end) = (at_level(start, level), at_level(end, level));
+        let mut farthest = f32::INFINITY;
+        for y in start.y..=end.y {
+            for x in start.x..=end.x {
+                farthest = farthest.min(self.levels[level].get(UVec2::new(x, y)));
+            }
+        }
+        max.z < farthest
+    }
+}
+
+/// The [`OcclusionCulling`] of each active camera in the render world, and the state of the
+/// readback of its depth.
+#[derive(Resource, Default)]
+struct HierarchicalDepths(HashMap<Entity, ViewHierarchicalDepth>);
+
+struct ViewHierarchicalDepth {
+    sender: Sender<HierarchicalDepth>,
+    settings: UniformBuffer<HierarchicalDepthSettings>,
+    buffers: Option<HierarchicalDepthBuffers>,
+    state: ReadbackState,
+}
+
+#[derive(ShaderType, Default)]
+struct HierarchicalDepthSettings {
+    viewport: UVec4,
+    size: UVec2,
+    tile_size: u32,
+    sample_count: u32,
+}
+
+struct HierarchicalDepthBuffers {
+    size: UVec2,
+    /// The level 0 of the depth, written by the compute shader.
+    depth: Buffer,
+    readback: Buffer,
+}
+
+/// What the depth read back from the GPU was rendered with.
+#[derive(Clone, Copy)]
+struct ReadbackView {
+    view_projection: Mat4,
+    texels_per_uv: Vec2,
+}
+
+enum ReadbackState {
+    Idle,
+    /// The depth of the view is reduced and copied to the readback buffer this frame.
+    Copying {
+        bind_group: BindGroup,
+        pipeline: CachedComputePipelineId,
+        view: ReadbackView,
+    },
+    Mapping {
+        mapped: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+        view: ReadbackView,
+    },
+}
+
+fn extract_occlusion_culling(
+    mut depths: ResMut<HierarchicalDepths>,
+    cameras: Extract<
+        Query<(
+            Entity,
+            &Camera,
+            &OcclusionCulling,
+            ChangeTrackers<OcclusionCulling>,
+        )>,
+    >,
+) {
+    depths.0.retain(|entity, _| {
+        cameras
+            .get(*entity)
+            .map_or(false, |(_, camera, ..)| camera.is_active)
+    });
+    for (entity, camera, occlusion_culling, trackers) in &cameras {
+        if !camera.is_active || (depths.0.contains_key(&entity) && !trackers.is_added()) {
+            continue;
+        }
+        depths.0.insert(
+            entity,
+            ViewHierarchicalDepth {
+                sender: occlusion_culling.sender.clone(),
+                settings: UniformBuffer::default(),
+                buffers: None,
+                state: ReadbackState::Idle,
+            },
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_hierarchical_depths(
+    mut depths: ResMut<HierarchicalDepths>,
+    pipeline: Res<HierarchicalDepthPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    views: Query<(&ExtractedView, &ViewDepthTexture, &Msaa)>,
+) {
+    for (entity, depth) in &mut depths.0 {
+        // Only one depth is read back at a time, once the previous one has been received
+        if !matches!(depth.state, ReadbackState::Idle) || depth.sender.is_full() {
+            continue;
+        }
+        let (view, depth_texture, msaa) = match views.get(*entity) {
+            Ok(view) => view,
+            Err(_) => continue,
+        };
+        let (layout, pipeline) = if msaa.samples > 1 {
+            (
+                &pipeline.multisampled_layout,
+                pipeline.multisampled_pipeline,
+            )
+        } else {
+            (&pipeline.layout, pipeline.pipeline)
+        };
+        if pipeline_cache.get_compute_pipeline(pipeline).is_none() {
+            continue;
+        }
+        let viewport_size = view.viewport.zw();
+        let tile_size = ((viewport_size.max_element() + MAX_HIERARCHICAL_DEPTH_SIZE - 1)
+            / MAX_HIERARCHICAL_DEPTH_SIZE)
+            .next_power_of_two();
+        let size = (viewport_size + UVec2::splat(tile_size - 1)) / tile_size;
+
+        if depth.buffers.as_ref().map(|buffers| buffers.size) != Some(size) {
+            let byte_size = (size.x * size.y) as u64 * std::mem::size_of::<f32>() as u64;
+            depth.buffers = Some(HierarchicalDepthBuffers {
+                size,
+                depth: render_device.create_buffer(&BufferDescriptor {
+                    label: Some("hierarchical_depth_buffer"),
+                    size: byte_size,
+                    usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+                    mapped_at_creation: false,
+                }),
+                readback: render_device.create_buffer(&BufferDescriptor {
+                    label: Some("hierarchical_depth_readback_buffer"),
+                    size: byte_size,
+                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }),
+            });
+        }
+        depth.settings.set(HierarchicalDepthSettings {
+            viewport: view.viewport,
+            size,
+            tile_size,
+            sample_count: msaa.samples,
+        });
+        depth.settings.write_buffer(&render_device, &render_queue);
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("hierarchical_depth_bind_group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&depth_texture.view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: depth.settings.binding().unwrap(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: depth.buffers.as_ref().unwrap().depth.as_entire_binding(),
+                },
+            ],
+        });
+        depth.state = ReadbackState::Copying {
+            bind_group,
+            pipeline,
+            view: ReadbackView {
+                view_projection: view.projection * view.transform.compute_matrix().inverse(),
+                texels_per_uv: viewport_size.as_vec2() / tile_size as f32,
+            },
+        };
+    }
+}
+
+/// Reduces the depth of the cameras with [`OcclusionCulling`] once they have rendered, and copies
+/// it to the buffers it's read back from.
+struct HierarchicalDepthNode;
+
+impl Node for HierarchicalDepthNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let depths = world.resource::<HierarchicalDepths>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        for depth in depths.0.values() {
+            let (bind_group, pipeline) = match &depth.state {
+                ReadbackState::Copying {
+                    bind_group,
+                    pipeline,
+                    ..
+                } => (bind_group, pipeline),
+                _ => continue,
+            };
+            let pipeline = match pipeline_cache.get_compute_pipeline(*pipeline) {
+                Some(pipeline) => pipeline,
+                None => continue,
+            };
+            let buffers = depth.buffers.as_ref().unwrap();
+
+            {
+                let mut pass =
+                    render_context
+                        .command_encoder
+                        .begin_compute_pass(&ComputePassDescriptor {
+                            label: Some("hierarchical_depth_pass"),
+                        });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, bind_group, &[]);
+                pass.dispatch_workgroups((buffers.size.x + 7) / 8, (buffers.size.y + 7) / 8, 1);
+            }
+
+            render_context.command_encoder.copy_buffer_to_buffer(
+                &buffers.depth,
+                0,
+                &buffers.readback,
+                0,
+                buffers.readback.size(),
+            );
+        }
+        Ok(())
+    }
+}
+
+fn read_back_hierarchical_depths(
+    render_device: Res<RenderDevice>,
+    mut depths: ResMut<HierarchicalDepths>,
+) {
+    let mut reading = false;
+    for depth in depths.0.values_mut() {
+        if let ReadbackState::Copying { view, .. } = depth.state {
+            let mapped = Arc::new(Mutex::new(None));
+            let result = mapped.clone();
+            let readback = &depth.buffers.as_ref().unwrap().readback;
+            render_device.map_buffer(&readback.slice(..), MapMode::Read, move |mapped| {
+                *result.lock() = Some(mapped);
+            });
+            depth.state = ReadbackState::Mapping { mapped, view };
+        }
+        reading |= matches!(depth.state, ReadbackState::Mapping { .. });
+    }
+    if !reading {
+        return;
+    }
+
+    // Calls the callbacks of the buffers mapped since the last frame, without waiting for
+    // the others
+    render_device.poll(Maintain::Poll);
+    for depth in depths.0.values_mut() {
+        let (mapped, view) = match &depth.state {
+            ReadbackState::Mapping { mapped, view } => (mapped.lock().take(), *view),
+            _ => continue,
+        };
+        let buffers = depth.buffers.as_ref().unwrap();
+        match mapped {
+            Some(Ok(())) => {
+                let values: Vec<f32> =
+                    bytemuck::cast_slice(&buffers.readback.slice(..).get_mapped_range()).to_vec();
+                buffers.readback.unmap();
+                // The camera may have been despawned, in which case there is no one to notify
+                let _ = depth.sender.try_send(HierarchicalDepth::new(
+                    view.view_projection,
+                    view.texels_per_uv,
+                    buffers.size,
+                    values,
+                ));
+                depth.state = ReadbackState::Idle;
+            }
+            Some(Err(err)) => {
+                error!("Cannot read back hierarchical depth: {err}");
+                depth.state = ReadbackState::Idle;
+            }
+            None => {}
+        }
+    }
+}
+
+/// The compute pipelines reducing the depth of the views, with and without MSAA.
+#[derive(Resource)]
+struct HierarchicalDepthPipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+    pipeline: CachedComputePipelineId,
+    multisampled_pipeline: CachedComputePipelineId,
+}
+
+impl FromWorld for HierarchicalDepthPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let create_layout = |multisampled| {
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("hierarchical_depth_layout"),
+                entries: &[
+                    BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::COMPUTE,
+                        ty: BindingType::Texture {
+                            sample_type: TextureSampleType::Depth,
+                            view_dimension: TextureViewDimension::D2,
+                            multisampled,
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::COMPUTE,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: Some(HierarchicalDepthSettings::min_size()),
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: ShaderStages::COMPUTE,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            })
+        };
+        let layout = create_layout(false);
+        let multisampled_layout = create_layout(true);
+
+        let mut pipeline_cache = world.resource_mut::<PipelineCache>();
+        let mut queue_pipeline = |layout: &BindGroupLayout, shader_defs: Vec<String>| {
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("hierarchical_depth_pipeline".into()),
+                layout: Some(vec![layout.clone()]),
+                shader: OCCLUSION_SHADER_HANDLE.typed(),
+                shader_defs,
+                entry_point: "downsample".into(),
+            })
+        };
+        let pipeline = queue_pipeline(&layout, Vec::new());
+        let multisampled_pipeline =
+            queue_pipeline(&multisampled_layout, vec!["MULTISAMPLED".into()]);
+        HierarchicalDepthPipeline {
+            layout,
+            multisampled_layout,
+            pipeline,
+            multisampled_pipeline,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3A;
+
+    #[test]
+    fn occlusion() {
+        // The view space is the clip space, over 2x2 texels whose bottom right one is empty
+        let depth = HierarchicalDepth::new(
+            Mat4::IDENTITY,
+            Vec2::splat(2.0),
+            UVec2::splat(2),
+            vec![0.5, 0.5, 0.5, 0.0],
+        );
+        let aabb = |center: Vec3, half_extents: Vec3| Aabb {
+            center: Vec3A::from(center),
+            half_extents: Vec3A::from(half_extents),
+        };
+        let is_occluded =
+            |center, half_extents| depth.is_occluded(&aabb(center, half_extents), &Mat4::IDENTITY);
+        assert!(is_occluded(Vec3::new(-0.5, 0.5, 0.2), Vec3::splat(0.1)));
+        assert!(!is_occluded(Vec3::new(-0.5, 0.5, 0.8), Vec3::splat(0.1)));
+        assert!(!is_occluded(Vec3::new(0.5, -0.5, 0.2), Vec3::splat(0.1)));
+        assert!(!is_occluded(
+            Vec3::new(0.0, 0.0, 0.2),
+            Vec3::new(0.9, 0.9, 0.1)
+        ));
+        assert!(is_occluded(
+            Vec3::new(-0.5, 0.0, 0.2),
+            Vec3::new(0.1, 0.9, 0.1)
+        ));
+        // Crossing the near plane
+        assert!(!is_occluded(Vec3::new(-0.5, 0.5, 0.95), Vec3::splat(0.1)));
+    }
+}
@@ -0,0 +1,46 @@
+struct HierarchicalDepthSettings {
+    // The origin and the size of the viewport of the view in its depth texture
+    viewport: vec4<u32>,
+    // The size of the level 0 of the hierarchical depth
+    size: vec2<u32>,
+    // The size of the square of depth texels each texel of the level 0 covers
+    tile_size: u32,
+    sample_count: u32,
+};
+
+#ifdef MULTISAMPLED
+@group(0) @binding(0)
+var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(0)
+var depth_texture: texture_depth_2d;
+#endif
+@group(0) @binding(1)
+var<uniform> settings: HierarchicalDepthSettings;
+@group(0) @binding(2)
+var<storage, read_write> hierarchical_depth: array<f32>;
+
+// Writes the farthest depth of each tile of the viewport, which is the smallest one with the
+// reversed depth
+@compute @workgroup_size(8, 8, 1)
+fn downsample(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= settings.size.x || id.y >= settings.size.y) {
+        return;
+    }
+
+    let start = settings.viewport.xy + id.xy * settings.tile_size;
+    let end = min(start + vec2<u32>(settings.tile_size), settings.viewport.xy + settings.viewport.zw);
+    var farthest: f32 = 1.0;
+    for (var y: u32 = start.y; y < end.y; y = y + 1u) {
+        for (var x: u32 = start.x; x < end.x; x = x + 1u) {
+#ifdef MULTISAMPLED
+            for (var i: u32 = 0u; i < settings.sample_count; i = i + 1u) {
+                farthest = min(farthest, textureLoad(depth_texture, vec2<i32>(vec2<u32>(x, y)), i32(i)));
+            }
+#else
+            farthest = min(farthest, textureLoad(depth_texture, vec2<i32>(vec2<u32>(x, y)), 0));
+#endif
+        }
+    }
+    hierarchical_depth[id.y * settings.size.x + id.x] = farthest;
+}
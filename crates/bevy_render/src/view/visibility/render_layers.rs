@@ -110,6 +110,11 @@ impl RenderLayers {
     pub fn intersects(&self, other: &RenderLayers) -> bool {
         (self.0 & other.0) > 0
     }
+
+    /// The mask of the layers, with the bit `n` set for the layer `n`.
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
 }
 
 #[cfg(test)]
@@ -126,6 +131,7 @@ mod rendering_mask_tests {
         assert_eq!(RenderLayers::layer(0).0, 1, "layer 0 is mask 1");
         assert_eq!(RenderLayers::layer(1).0, 2, "layer 1 is mask 2");
         assert_eq!(RenderLayers::layer(0).with(1).0, 3, "layer 0 + 1 is mask 3");
+        assert_eq!(RenderLayers::layer(0).with(1).bits(), 3, "bits is the mask");
         assert_eq!(
             RenderLayers::layer(0).with(1).without(0).0,
             2,
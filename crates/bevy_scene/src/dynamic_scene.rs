@@ -3,10 +3,11 @@ use anyhow::Result;
 use bevy_app::AppTypeRegistry;
 use bevy_ecs::{
     entity::EntityMap,
-    reflect::{ReflectComponent, ReflectMapEntities},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
 use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bincode::Options;
 use serde::Serialize;
 
 /// A collection of serializable dynamic entities, each with its own run-time defined set of components.
@@ -19,6 +20,8 @@ use serde::Serialize;
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+    /// Resources of the scene, applied to the world when the scene is written to it.
+    pub resources: Vec<Box<dyn Reflect>>,
     pub entities: Vec<DynamicEntity>,
 }
 
@@ -47,11 +50,13 @@ impl DynamicScene {
         builder.build()
     }
 
-    /// Write the dynamic entities and their corresponding components to the given world.
+    /// Write the resources, the dynamic entities and their corresponding components to the
+    /// given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
     /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
-    /// [`Component`](bevy_ecs::component::Component) trait.
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::system::Resource)
+    /// trait.
     pub fn write_to_world_with(
         &self,
         world: &mut World,
@@ -60,6 +65,23 @@ impl DynamicScene {
     ) -> Result<(), SceneSpawnError> {
         let type_registry = type_registry.read();
 
+        for resource in &self.resources {
+            let registration = type_registry
+                .get_with_name(resource.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                }
+            })?;
+
+            // If the world already has the given resource, just apply the (possibly) new value,
+            // otherwise insert the resource.
+            reflect_resource.apply_or_insert(world, &**resource);
+        }
+
         for scene_entity in &self.entities {
             // Fetch the entity with the given entity id from the `entity_map`
             // or spawn a new entity with a transiently unique id if there is
@@ -100,11 +122,13 @@ impl DynamicScene {
         Ok(())
     }
 
-    /// Write the dynamic entities and their corresponding components to the given world.
+    /// Write the resources, the dynamic entities and their corresponding components to the
+    /// given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
     /// in the world's [`AppTypeRegistry`] resource, or doesn't reflect the
-    /// [`Component`](bevy_ecs::component::Component) trait.
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::system::Resource)
+    /// trait.
     pub fn write_to_world(
         &self,
         world: &mut World,
@@ -114,11 +138,21 @@ impl DynamicScene {
         self.write_to_world_with(world, entity_map, &registry)
     }
 
-    // TODO: move to AssetSaver when it is implemented
     /// Serialize this dynamic scene into rust object notation (ron).
+    ///
+    /// To save the scene as an asset, use [`AssetServer::save`](bevy_asset::AssetServer::save).
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the compact binary format read by the
+    /// [`BinarySceneLoader`](crate::BinarySceneLoader), which loads much faster than ron.
+    ///
+    /// To save the scene as an asset, use [`AssetServer::save`](bevy_asset::AssetServer::save)
+    /// with a path ending in `.scn.bin`.
+    pub fn serialize_binary(&self, registry: &TypeRegistryArc) -> Result<Vec<u8>, bincode::Error> {
+        serialize_binary(SceneSerializer::new(self, registry))
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -131,3 +165,12 @@ where
         .new_line("\n".to_string());
     ron::ser::to_string_pretty(&serialize, pretty_config)
 }
+
+/// Serialize a given Rust data structure into the binary format read by the
+/// [`BinarySceneLoader`](crate::BinarySceneLoader).
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, bincode::Error>
+where
+    S: Serialize,
+{
+    bincode::DefaultOptions::new().serialize(&serialize)
+}
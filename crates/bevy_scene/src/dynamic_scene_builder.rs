@@ -1,9 +1,21 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, SceneFilter};
 use bevy_app::AppTypeRegistry;
-use bevy_ecs::{prelude::Entity, reflect::ReflectComponent, world::World};
+use bevy_ecs::{
+    component::Component,
+    prelude::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::Reflect;
 use bevy_utils::{default, HashMap};
+use std::any::TypeId;
 
-/// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities.
+/// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities
+/// and resources.
+///
+/// The extracted components and resources can be restricted with allowlists or denylists, which
+/// apply to the extractions that follow them.
 ///
 /// ```
 /// # use bevy_scene::DynamicSceneBuilder;
@@ -24,6 +36,9 @@ use bevy_utils::{default, HashMap};
 /// ```
 pub struct DynamicSceneBuilder<'w> {
     scene: HashMap<u32, DynamicEntity>,
+    resources: HashMap<TypeId, Box<dyn Reflect>>,
+    component_filter: SceneFilter,
+    resource_filter: SceneFilter,
     type_registry: AppTypeRegistry,
     world: &'w World,
 }
@@ -34,6 +49,9 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world(world: &'w World) -> Self {
         Self {
             scene: default(),
+            resources: default(),
+            component_filter: default(),
+            resource_filter: default(),
             type_registry: world.resource::<AppTypeRegistry>().clone(),
             world,
         }
@@ -44,6 +62,9 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world_with_type_registry(world: &'w World, type_registry: AppTypeRegistry) -> Self {
         Self {
             scene: default(),
+            resources: default(),
+            component_filter: default(),
+            resource_filter: default(),
             type_registry,
             world,
         }
@@ -52,10 +73,51 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// Consume the builder, producing a [`DynamicScene`].
     pub fn build(self) -> DynamicScene {
         DynamicScene {
+            resources: self.resources.into_values().collect(),
             entities: self.scene.into_values().collect(),
         }
     }
 
+    /// Replace the filter of the components extracted by the next extractions of entities.
+    pub fn with_filter(&mut self, filter: SceneFilter) -> &mut Self {
+        self.component_filter = filter;
+        self
+    }
+
+    /// Replace the filter of the resources extracted by the next extractions of resources.
+    pub fn with_resource_filter(&mut self, filter: SceneFilter) -> &mut Self {
+        self.resource_filter = filter;
+        self
+    }
+
+    /// Allow the component `T` in the next extractions of entities.
+    ///
+    /// Once a component is allowed, only the allowed components are extracted.
+    pub fn allow<T: Component>(&mut self) -> &mut Self {
+        self.component_filter.allow_by_id(TypeId::of::<T>());
+        self
+    }
+
+    /// Deny the component `T` in the next extractions of entities.
+    pub fn deny<T: Component>(&mut self) -> &mut Self {
+        self.component_filter.deny_by_id(TypeId::of::<T>());
+        self
+    }
+
+    /// Allow the resource `T` in the next extractions of resources.
+    ///
+    /// Once a resource is allowed, only the allowed resources are extracted.
+    pub fn allow_resource<T: Resource>(&mut self) -> &mut Self {
+        self.resource_filter.allow_by_id(TypeId::of::<T>());
+        self
+    }
+
+    /// Deny the resource `T` in the next extractions of resources.
+    pub fn deny_resource<T: Resource>(&mut self) -> &mut Self {
+        self.resource_filter.deny_by_id(TypeId::of::<T>());
+        self
+    }
+
     /// Extract one entity from the builder's [`World`].
     ///
     /// Re-extracting an entity that was already extracted will have no effect.
@@ -106,7 +168,9 @@ impl<'w> DynamicSceneBuilder<'w> {
                     .world
                     .components()
                     .get_info(component_id)
-                    .and_then(|info| type_registry.get(info.type_id().unwrap()))
+                    .and_then(|info| info.type_id())
+                    .filter(|&type_id| self.component_filter.is_allowed_by_id(type_id))
+                    .and_then(|type_id| type_registry.get(type_id))
                     .and_then(|registration| registration.data::<ReflectComponent>());
 
                 if let Some(reflect_component) = reflect_component {
@@ -122,13 +186,64 @@ impl<'w> DynamicSceneBuilder<'w> {
         drop(type_registry);
         self
     }
+
+    /// Extract the resources of the builder's [`World`] that are registered in the type registry
+    /// with `#[reflect(Resource)]`.
+    ///
+    /// Re-extracting a resource that was already extracted will update its value.
+    ///
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_app::AppTypeRegistry;
+    /// # use bevy_ecs::{reflect::ReflectResource, system::Resource, world::World};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Resource, Default, Reflect)]
+    /// #[reflect(Resource)]
+    /// struct Score(u32);
+    ///
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// # world.resource::<AppTypeRegistry>().write().register::<Score>();
+    /// # world.insert_resource(Score(3));
+    /// let mut builder = DynamicSceneBuilder::from_world(&world);
+    /// builder.allow_resource::<Score>();
+    /// builder.extract_resources();
+    /// let scene = builder.build();
+    /// ```
+    pub fn extract_resources(&mut self) -> &mut Self {
+        let type_registry = self.type_registry.read();
+
+        for registration in type_registry.iter() {
+            if !self
+                .resource_filter
+                .is_allowed_by_id(registration.type_id())
+            {
+                continue;
+            }
+            if let Some(resource) = registration
+                .data::<ReflectResource>()
+                .and_then(|reflect_resource| reflect_resource.reflect(self.world))
+            {
+                self.resources
+                    .insert(registration.type_id(), resource.clone_value());
+            }
+        }
+
+        drop(type_registry);
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::{
-        component::Component, prelude::Entity, query::With, reflect::ReflectComponent, world::World,
+        component::Component,
+        prelude::Entity,
+        query::With,
+        reflect::{ReflectComponent, ReflectResource},
+        system::Resource,
+        world::World,
     };
 
     use bevy_reflect::Reflect;
@@ -141,6 +256,12 @@ mod tests {
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
     struct ComponentB;
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceA;
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceB;
 
     #[test]
     fn extract_one_entity() {
@@ -234,4 +355,80 @@ mod tests {
         scene_entities.sort();
         assert_eq!(scene_entities, [entity_a_b.id(), entity_a.id()]);
     }
+
+    #[test]
+    fn extract_with_denylist() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ComponentB>();
+        }
+        world.insert_resource(atr);
+
+        let entity = world.spawn((ComponentA, ComponentB)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.deny::<ComponentB>();
+        builder.extract_entity(entity);
+        let scene = builder.build();
+
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert!(scene.entities[0].components[0].represents::<ComponentA>());
+    }
+
+    #[test]
+    fn extract_with_allowlist() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ComponentB>();
+        }
+        world.insert_resource(atr);
+
+        let entity = world.spawn((ComponentA, ComponentB)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.allow::<ComponentB>();
+        builder.extract_entity(entity);
+        let scene = builder.build();
+
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].components.len(), 1);
+        assert!(scene.entities[0].components[0].represents::<ComponentB>());
+    }
+
+    #[test]
+    fn extract_resources() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ResourceA>();
+            register.register::<ResourceB>();
+        }
+        world.insert_resource(atr);
+        world.insert_resource(ResourceA);
+        world.insert_resource(ResourceB);
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_resources();
+        let scene = builder.build();
+        assert_eq!(scene.resources.len(), 2);
+        assert!(scene.entities.is_empty());
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.deny_resource::<ResourceA>();
+        builder.extract_resources();
+        let scene = builder.build();
+        assert_eq!(scene.resources.len(), 1);
+        assert!(scene.resources[0].represents::<ResourceB>());
+    }
 }
@@ -2,7 +2,9 @@ mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
 mod scene;
+mod scene_filter;
 mod scene_loader;
+mod scene_saver;
 mod scene_spawner;
 pub mod serde;
 
@@ -10,7 +12,9 @@ pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
 pub use scene::*;
+pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
@@ -32,6 +36,9 @@ impl Plugin for ScenePlugin {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
+            .init_asset_saver::<SceneSaver>()
+            .init_asset_saver::<BinarySceneSaver>()
             .init_resource::<SceneSpawner>()
             .add_system_to_stage(CoreStage::PreUpdate, scene_spawner_system.at_end())
             // Systems `*_bundle_spawner` must run before `scene_spawner_system`
@@ -0,0 +1,60 @@
+use bevy_utils::HashSet;
+use std::any::TypeId;
+
+/// The types extracted by a [`DynamicSceneBuilder`](crate::DynamicSceneBuilder), either all of
+/// them, the ones of an allowlist, or all but the ones of a denylist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SceneFilter {
+    /// All the types are extracted.
+    #[default]
+    All,
+    /// Only the listed types are extracted.
+    Allowlist(HashSet<TypeId>),
+    /// All the types except the listed ones are extracted.
+    Denylist(HashSet<TypeId>),
+}
+
+impl SceneFilter {
+    /// Allows the type with the given [`TypeId`].
+    ///
+    /// This turns a filter allowing all the types into an allowlist, and removes the type from a
+    /// denylist.
+    pub fn allow_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        match self {
+            SceneFilter::All => *self = SceneFilter::Allowlist(HashSet::from_iter([type_id])),
+            SceneFilter::Allowlist(list) => {
+                list.insert(type_id);
+            }
+            SceneFilter::Denylist(list) => {
+                list.remove(&type_id);
+            }
+        }
+        self
+    }
+
+    /// Denies the type with the given [`TypeId`].
+    ///
+    /// This turns a filter allowing all the types into a denylist, and removes the type from an
+    /// allowlist.
+    pub fn deny_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        match self {
+            SceneFilter::All => *self = SceneFilter::Denylist(HashSet::from_iter([type_id])),
+            SceneFilter::Allowlist(list) => {
+                list.remove(&type_id);
+            }
+            SceneFilter::Denylist(list) => {
+                list.insert(type_id);
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the type with the given [`TypeId`] passes the filter.
+    pub fn is_allowed_by_id(&self, type_id: TypeId) -> bool {
+        match self {
+            SceneFilter::All => true,
+            SceneFilter::Allowlist(list) => list.contains(&type_id),
+            SceneFilter::Denylist(list) => !list.contains(&type_id),
+        }
+    }
+}
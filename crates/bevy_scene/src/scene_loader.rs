@@ -5,6 +5,7 @@ use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
+use bincode::Options;
 use serde::de::DeserializeSeed;
 
 #[derive(Debug)]
@@ -42,3 +43,44 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+/// Loads [`DynamicScene`](crate::DynamicScene)s from the compact binary format written by
+/// [`DynamicScene::serialize_binary`](crate::DynamicScene::serialize_binary) and the
+/// [`BinarySceneSaver`](crate::BinarySceneSaver).
+///
+/// Binary scenes are much faster to load than ron scenes, but can't be edited by hand.
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let scene_deserializer = SceneDeserializer {
+                type_registry: &*self.type_registry.read(),
+            };
+            let scene =
+                bincode::DefaultOptions::new().deserialize_seed(scene_deserializer, bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(scene));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
@@ -0,0 +1,61 @@
+use crate::DynamicScene;
+use anyhow::Result;
+use bevy_app::AppTypeRegistry;
+use bevy_asset::AssetSaver;
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+
+/// Saves [`DynamicScene`]s in the format read by the [`SceneLoader`](crate::SceneLoader).
+#[derive(Debug)]
+pub struct SceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        SceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetSaver for SceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene) -> Result<Vec<u8>> {
+        Ok(scene.serialize_ron(&self.type_registry)?.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn", "scn.ron"]
+    }
+}
+
+/// Saves [`DynamicScene`]s in the binary format read by the
+/// [`BinarySceneLoader`](crate::BinarySceneLoader).
+#[derive(Debug)]
+pub struct BinarySceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetSaver for BinarySceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene) -> Result<Vec<u8>> {
+        Ok(scene.serialize_binary(&self.type_registry)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
@@ -4,6 +4,7 @@ use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
     entity::{Entity, EntityMap},
     event::{Events, ManualEventReader},
+    reflect::ReflectComponent,
     system::{Command, Resource},
     world::{Mut, World},
 };
@@ -19,6 +20,25 @@ pub struct InstanceInfo {
     pub entity_map: EntityMap,
 }
 
+/// The type names of the components written by a dynamic scene, keyed on the ids of the entities
+/// of the scene.
+type SceneComponents = HashMap<u32, Vec<String>>;
+
+fn scene_components(scene: &DynamicScene) -> SceneComponents {
+    scene
+        .entities
+        .iter()
+        .map(|scene_entity| {
+            let components = scene_entity
+                .components
+                .iter()
+                .map(|component| component.type_name().to_string())
+                .collect();
+            (scene_entity.entity, components)
+        })
+        .collect()
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InstanceId(Uuid);
 
@@ -33,6 +53,7 @@ pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
+    spawned_dynamic_components: HashMap<InstanceId, SceneComponents>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
@@ -45,6 +66,8 @@ pub struct SceneSpawner {
 pub enum SceneSpawnError {
     #[error("scene contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
     UnregisteredComponent { type_name: String },
+    #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
     #[error("scene does not exist")]
@@ -112,6 +135,7 @@ impl SceneSpawner {
                 let _ = world.despawn(entity);
             }
         }
+        self.spawned_dynamic_components.remove(instance_id);
     }
 
     pub fn spawn_dynamic_sync(
@@ -120,10 +144,12 @@ impl SceneSpawner {
         scene_handle: &Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
         let mut entity_map = EntityMap::default();
-        Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        let components = Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
         let instance_id = InstanceId::new();
         self.spawned_instances
             .insert(instance_id, InstanceInfo { entity_map });
+        self.spawned_dynamic_components
+            .insert(instance_id, components);
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
@@ -136,7 +162,7 @@ impl SceneSpawner {
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
         entity_map: &mut EntityMap,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<SceneComponents, SceneSpawnError> {
         world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
             let scene =
                 scenes
@@ -144,7 +170,61 @@ impl SceneSpawner {
                     .ok_or_else(|| SceneSpawnError::NonExistentScene {
                         handle: scene_handle.clone_weak(),
                     })?;
-            scene.write_to_world(world, entity_map)
+            scene.write_to_world(world, entity_map)?;
+            Ok(scene_components(scene))
+        })
+    }
+
+    /// Updates an instance of a dynamic scene in place after the scene was modified.
+    ///
+    /// The entities and components removed from the scene since `previous_components` were
+    /// written are removed from the instance, and the scene is written over the instance. The
+    /// entities of the instance keep their identity, and the components added to them outside of
+    /// the scene are kept.
+    fn patch_dynamic_internal(
+        world: &mut World,
+        scene_handle: &Handle<DynamicScene>,
+        entity_map: &mut EntityMap,
+        previous_components: &SceneComponents,
+    ) -> Result<SceneComponents, SceneSpawnError> {
+        world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            let scene =
+                scenes
+                    .get(scene_handle)
+                    .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                        handle: scene_handle.clone_weak(),
+                    })?;
+            let components = scene_components(scene);
+
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+            let type_registry = type_registry.read();
+            for (scene_entity, previous) in previous_components {
+                let current = match components.get(scene_entity) {
+                    Some(current) => current,
+                    None => {
+                        if let Some(entity) = entity_map.remove(Entity::from_raw(*scene_entity)) {
+                            let _ = world.despawn(entity);
+                        }
+                        continue;
+                    }
+                };
+                let entity = match entity_map.get(Entity::from_raw(*scene_entity)) {
+                    Ok(entity) if world.get_entity(entity).is_some() => entity,
+                    _ => continue,
+                };
+                for type_name in previous.iter().filter(|&name| !current.contains(name)) {
+                    if let Some(reflect_component) = type_registry
+                        .get_with_name(type_name)
+                        .and_then(|registration| registration.data::<ReflectComponent>())
+                    {
+                        reflect_component.remove(world, entity);
+                    }
+                }
+            }
+            drop(type_registry);
+
+            scene.write_to_world(world, entity_map)?;
+            Ok(components)
         })
     }
 
@@ -183,6 +263,8 @@ impl SceneSpawner {
         })
     }
 
+    /// Updates the spawned instances of the modified dynamic scenes in place, keeping their
+    /// entities and the components added to them outside of the scene.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
@@ -192,11 +274,18 @@ impl SceneSpawner {
             if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
+                        let previous_components = self
+                            .spawned_dynamic_components
+                            .remove(instance_id)
+                            .unwrap_or_default();
+                        let components = Self::patch_dynamic_internal(
                             world,
                             scene_handle,
                             &mut instance_info.entity_map,
+                            &previous_components,
                         )?;
+                        self.spawned_dynamic_components
+                            .insert(*instance_id, components);
                     }
                 }
             }
@@ -228,9 +317,11 @@ impl SceneSpawner {
             let mut entity_map = EntityMap::default();
 
             match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
-                Ok(_) => {
+                Ok(components) => {
                     self.spawned_instances
                         .insert(instance_id, InstanceInfo { entity_map });
+                    self.spawned_dynamic_components
+                        .insert(instance_id, components);
                     let spawned = self
                         .spawned_dynamic_scenes
                         .entry(scene_handle.clone())
@@ -339,3 +430,103 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner.set_scene_instance_parent_sync(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, AppTypeRegistry};
+    use bevy_asset::{AddAsset, AssetPlugin, AssetServer, Assets, FileAssetIo};
+    use bevy_ecs::{component::Component, reflect::ReflectComponent, world::Mut};
+    use bevy_reflect::Reflect;
+
+    use super::SceneSpawner;
+    use crate::{DynamicEntity, DynamicScene};
+
+    #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct ComponentA(u32);
+    #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct ComponentB;
+    #[derive(Component)]
+    struct RuntimeComponent;
+
+    fn scene(entities: Vec<(u32, Vec<Box<dyn Reflect>>)>) -> DynamicScene {
+        DynamicScene {
+            resources: Vec::new(),
+            entities: entities
+                .into_iter()
+                .map(|(entity, components)| DynamicEntity { entity, components })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn modified_scene_patches_instances() {
+        let mut app = App::new();
+        app.insert_resource(AssetServer::new(FileAssetIo::new(".", false)))
+            .add_plugin(AssetPlugin)
+            .add_asset::<DynamicScene>()
+            .init_resource::<SceneSpawner>();
+        {
+            let mut registry = app.world.resource::<AppTypeRegistry>().write();
+            registry.register::<ComponentA>();
+            registry.register::<ComponentB>();
+        }
+
+        let handle = app
+            .world
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(scene(vec![
+                (0, vec![Box::new(ComponentA(1)), Box::new(ComponentB)]),
+                (1, vec![Box::new(ComponentA(2))]),
+            ]));
+        app.world
+            .resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+                spawner.spawn_dynamic_sync(world, &handle)
+            })
+            .unwrap();
+        let instance_id = app.world.resource::<SceneSpawner>().spawned_dynamic_scenes[&handle][0];
+        let entities = |world: &bevy_ecs::world::World| {
+            let mut entities: Vec<_> = world
+                .resource::<SceneSpawner>()
+                .iter_instance_entities(instance_id)
+                .collect();
+            entities.sort();
+            entities
+        };
+        let spawned = entities(&app.world);
+        assert_eq!(spawned.len(), 2);
+        let kept = spawned
+            .iter()
+            .copied()
+            .find(|&entity| app.world.get::<ComponentB>(entity).is_some())
+            .unwrap();
+        let removed = spawned.iter().copied().find(|&e| e != kept).unwrap();
+        app.world.entity_mut(kept).insert(RuntimeComponent);
+
+        *app.world
+            .resource_mut::<Assets<DynamicScene>>()
+            .get_mut(&handle)
+            .unwrap() = scene(vec![
+            (0, vec![Box::new(ComponentA(3))]),
+            (2, vec![Box::new(ComponentB)]),
+        ]);
+        app.world
+            .resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+                spawner.update_spawned_scenes(world, &[handle.clone_weak()])
+            })
+            .unwrap();
+
+        // the entity still in the scene is patched in place
+        assert_eq!(app.world.get::<ComponentA>(kept), Some(&ComponentA(3)));
+        assert!(app.world.get::<ComponentB>(kept).is_none());
+        assert!(app.world.get::<RuntimeComponent>(kept).is_some());
+        // the entity removed from the scene is despawned, and the added one is spawned
+        assert!(app.world.get_entity(removed).is_none());
+        let patched = entities(&app.world);
+        assert_eq!(patched.len(), 2);
+        assert!(patched.contains(&kept));
+        let added = patched.into_iter().find(|&e| e != kept).unwrap();
+        assert!(app.world.get::<ComponentB>(added).is_some());
+    }
+}
@@ -26,8 +26,37 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in &self.scene.entities {
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [DynamicEntity],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -92,10 +121,141 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
+        if deserializer.is_human_readable() {
+            // Scenes without resources used to be serialized as a list of entities, which is still
+            // supported for the formats that can tell them apart from a struct.
+            deserializer.deserialize_any(SceneVisitor {
+                type_registry: self.type_registry,
+            })
+        } else {
+            deserializer.deserialize_struct(
+                SCENE_STRUCT,
+                &[SCENE_FIELD_RESOURCES, SCENE_FIELD_ENTITIES],
+                SceneStructVisitor {
+                    type_registry: self.type_registry,
+                },
+            )
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Resources,
+    Entities,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct or list of entities")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Ok(DynamicScene {
+            resources: Vec::new(),
+            entities: SceneEntitySeqVisitor {
+                type_registry: self.type_registry,
+            }
+            .visit_seq(seq)?,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
         Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            resources: resources.unwrap_or_default(),
+            entities,
+        })
+    }
+}
+
+/// Visits a scene serialized as a struct by a format that isn't self-describing, such as the
+/// binary format read by the [`BinarySceneLoader`](crate::BinarySceneLoader).
+struct SceneStructVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneStructVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let resources = seq
+            .next_element_seed(ComponentVecDeserializer {
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_FIELD_RESOURCES))?;
+        let entities = seq
+            .next_element_seed(SceneEntitiesDeserializer {
                 type_registry: self.type_registry,
-            })?,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+        Ok(DynamicScene {
+            resources,
+            entities,
+        })
+    }
+}
+
+pub struct SceneEntitiesDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
         })
     }
 }
@@ -169,6 +329,21 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         formatter.write_str("entities")
     }
 
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_ENTITY))?;
+        let components = seq
+            .next_element_seed(ComponentVecDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+        Ok(DynamicEntity { entity, components })
+    }
+
     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
     where
         A: MapAccess<'de>,
@@ -251,3 +426,123 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{serde::SceneDeserializer, DynamicSceneBuilder};
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        component::Component,
+        entity::EntityMap,
+        reflect::{ReflectComponent, ReflectResource},
+        system::Resource,
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct ComponentA(u32);
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceA(u32);
+
+    fn create_world() -> World {
+        let mut world = World::default();
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ResourceA>();
+        }
+        world.insert_resource(atr);
+        world
+    }
+
+    #[test]
+    fn scene_round_trip() {
+        let mut world = create_world();
+        world.insert_resource(ResourceA(1));
+        let entity = world.spawn(ComponentA(2)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entity(entity).extract_resources();
+        let scene = builder.build();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let serialized = scene.serialize_ron(&registry).unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(scene.entities.len(), 1);
+
+        let mut world = create_world();
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(world.resource::<ResourceA>(), &ResourceA(1));
+        assert_eq!(world.query::<&ComponentA>().single(&world), &ComponentA(2));
+    }
+
+    #[test]
+    fn binary_scene_round_trip() {
+        let mut world = create_world();
+        world.insert_resource(ResourceA(1));
+        let entity = world.spawn(ComponentA(2)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entity(entity).extract_resources();
+        let scene = builder.build();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let serialized = scene.serialize_binary(&registry).unwrap();
+
+        let scene = bincode::DefaultOptions::new()
+            .deserialize_seed(
+                SceneDeserializer {
+                    type_registry: &registry.read(),
+                },
+                &serialized,
+            )
+            .unwrap();
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(scene.entities.len(), 1);
+
+        let mut world = create_world();
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(world.resource::<ResourceA>(), &ResourceA(1));
+        assert_eq!(world.query::<&ComponentA>().single(&world), &ComponentA(2));
+    }
+
+    #[test]
+    fn deserialize_list_of_entities() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let input = r#"[
+            (
+                entity: 0,
+                components: [
+                    {
+                        "bevy_scene::serde::tests::ComponentA": (3),
+                    },
+                ],
+            ),
+        ]"#;
+
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        assert!(scene.resources.is_empty());
+        assert_eq!(scene.entities.len(), 1);
+    }
+}
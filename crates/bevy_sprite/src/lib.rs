@@ -1,10 +1,14 @@
 mod bundle;
 mod dynamic_texture_atlas_builder;
+mod light_2d;
 mod mesh2d;
 mod render;
 mod sprite;
+mod sprite_animation;
 mod texture_atlas;
 mod texture_atlas_builder;
+mod texture_atlas_packer;
+mod texture_slice;
 
 pub mod collide_aabb;
 
@@ -12,30 +16,49 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         bundle::{SpriteBundle, SpriteSheetBundle},
+        light_2d::{
+            AmbientLight2d, LightOccluder2d, PointLight2d, PointLight2dBundle, SpriteNormalMap,
+        },
         sprite::Sprite,
+        sprite_animation::{SpriteAnimation, SpriteAnimationMode},
         texture_atlas::{TextureAtlas, TextureAtlasSprite},
+        texture_atlas_packer::TextureAtlasPacker,
+        texture_slice::{BorderRect, ImageScaleMode, SliceScaleMode, TextureSlicer},
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
 }
 
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
+pub use light_2d::*;
 pub use mesh2d::*;
 pub use render::*;
 pub use sprite::*;
+pub use sprite_animation::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
+pub use texture_atlas_packer::*;
+pub use texture_slice::*;
 
 use bevy_app::prelude::*;
-use bevy_asset::{AddAsset, Assets, HandleUntyped};
+use bevy_asset::{AddAsset, AssetEvent, Assets, Handle, HandleId, HandleUntyped};
 use bevy_core_pipeline::core_2d::Transparent2d;
-use bevy_ecs::schedule::{IntoSystemDescriptor, SystemLabel};
+use bevy_ecs::{
+    prelude::*,
+    schedule::{IntoSystemDescriptor, SystemLabel},
+};
+use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
+    mesh::Mesh,
+    primitives::Aabb,
     render_phase::AddRenderCommand,
     render_resource::{Shader, SpecializedRenderPipelines},
+    texture::Image,
+    view::{NoFrustumCulling, VisibilitySystems},
     RenderApp, RenderStage,
 };
+use bevy_utils::HashSet;
 
 #[derive(Default)]
 pub struct SpritePlugin;
@@ -46,6 +69,8 @@ pub const SPRITE_SHADER_HANDLE: HandleUntyped =
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum SpriteSystem {
     ExtractSprites,
+    AnimateSprites,
+    PackTextureAtlases,
 }
 
 impl Plugin for SpritePlugin {
@@ -56,9 +81,30 @@ impl Plugin for SpritePlugin {
         app.add_asset::<TextureAtlas>()
             .register_type::<Sprite>()
             .register_type::<Anchor>()
+            .register_type::<ImageScaleMode>()
             .register_type::<Mesh2dHandle>()
+            .register_type::<SpriteAnimation>()
+            .add_event::<SpriteAnimationCompleted>()
+            .init_resource::<TextureAtlasPacker>()
             .add_plugin(Mesh2dRenderPlugin)
-            .add_plugin(ColorMaterialPlugin);
+            .add_plugin(ColorMaterialPlugin)
+            .add_plugin(Light2dPlugin)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                animate_sprites
+                    .label(SpriteSystem::AnimateSprites)
+                    .before(VisibilitySystems::CalculateBounds),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                pack_texture_atlases
+                    .label(SpriteSystem::PackTextureAtlases)
+                    .before(VisibilitySystems::CalculateBounds),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                calculate_bounds_2d.label(VisibilitySystems::CalculateBounds),
+            );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -67,6 +113,7 @@ impl Plugin for SpritePlugin {
                 .init_resource::<SpecializedRenderPipelines<SpritePipeline>>()
                 .init_resource::<SpriteMeta>()
                 .init_resource::<ExtractedSprites>()
+                .init_resource::<SpriteCache>()
                 .init_resource::<SpriteAssetEvents>()
                 .add_render_command::<Transparent2d, DrawSprite>()
                 .add_system_to_stage(
@@ -78,3 +125,111 @@ impl Plugin for SpritePlugin {
         };
     }
 }
+
+/// The [`Aabb`] of a sprite of `size` positioned with `anchor`, flat on its plane.
+fn sprite_aabb(size: Vec2, anchor: &Anchor) -> Aabb {
+    Aabb {
+        center: (-anchor.as_vec() * size).extend(0.0).into(),
+        half_extents: (size / 2.0).extend(0.0).into(),
+    }
+}
+
+/// Computes the [`Aabb`] of the [`Mesh2dHandle`]s, [`Sprite`]s and [`TextureAtlasSprite`]s, so
+/// that the 2D cameras frustum cull them like the 3D meshes, and updates it when their mesh or
+/// their size changes.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_bounds_2d(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    images: Res<Assets<Image>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    meshes_2d: Query<(Entity, &Mesh2dHandle, Option<&Aabb>), Without<NoFrustumCulling>>,
+    sprites: Query<
+        (Entity, &Sprite, &Handle<Image>),
+        (
+            Without<NoFrustumCulling>,
+            Or<(Without<Aabb>, Changed<Sprite>, Changed<Handle<Image>>)>,
+        ),
+    >,
+    atlas_sprites: Query<
+        (Entity, &TextureAtlasSprite, &Handle<TextureAtlas>),
+        (
+            Without<NoFrustumCulling>,
+            Or<(
+                Without<Aabb>,
+                Changed<TextureAtlasSprite>,
+                Changed<Handle<TextureAtlas>>,
+            )>,
+        ),
+    >,
+) {
+    let modified: HashSet<HandleId> = mesh_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+    for (entity, mesh_handle, aabb) in &meshes_2d {
+        if aabb.is_some() && !modified.contains(&mesh_handle.0.id()) {
+            continue;
+        }
+        let mesh_aabb = meshes
+            .get(&mesh_handle.0)
+            .and_then(|mesh| mesh.aabb().cloned().or_else(|| mesh.compute_aabb()));
+        if let Some(mesh_aabb) = mesh_aabb {
+            if aabb != Some(&mesh_aabb) {
+                commands.entity(entity).insert(mesh_aabb);
+            }
+        }
+    }
+
+    for (entity, sprite, image_handle) in &sprites {
+        // The sprites with no custom size wait for their image to be loaded
+        let size = sprite.custom_size.or_else(|| {
+            sprite
+                .rect
+                .map(|rect| rect.size())
+                .or_else(|| images.get(image_handle).map(Image::size))
+        });
+        if let Some(size) = size {
+            commands
+                .entity(entity)
+                .insert(sprite_aabb(size, &sprite.anchor));
+        }
+    }
+
+    for (entity, atlas_sprite, atlas_handle) in &atlas_sprites {
+        let size = atlas_sprite.custom_size.or_else(|| {
+            atlases
+                .get(atlas_handle)
+                .and_then(|atlas| atlas.textures.get(atlas_sprite.index))
+                .map(|rect| rect.size())
+        });
+        if let Some(size) = size {
+            commands
+                .entity(entity)
+                .insert(sprite_aabb(size, &atlas_sprite.anchor));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec3A;
+
+    use super::*;
+
+    #[test]
+    fn sprite_aabbs() {
+        let size = Vec2::new(4.0, 2.0);
+        let centered = sprite_aabb(size, &Anchor::Center);
+        assert_eq!(centered.center, Vec3A::ZERO);
+        assert_eq!(centered.half_extents, Vec3A::new(2.0, 1.0, 0.0));
+
+        let bottom_left = sprite_aabb(size, &Anchor::BottomLeft);
+        assert_eq!(bottom_left.center, Vec3A::new(2.0, 1.0, 0.0));
+        assert_eq!(bottom_left.half_extents, Vec3A::new(2.0, 1.0, 0.0));
+    }
+}
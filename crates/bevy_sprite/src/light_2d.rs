@@ -0,0 +1,329 @@
+//! Dynamic lighting of sprites by 2D point lights, with an ambient light, normal maps and hard
+//! shadows cast by [`LightOccluder2d`]s.
+//!
+//! Sprites are unlit until an [`AmbientLight2d`] resource is inserted or a [`PointLight2d`] is
+//! spawned. The lights are then added to the color of every sprite, while the 2D meshes stay unlit.
+
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    event::EventReader,
+    query::Added,
+    reflect::{ReflectComponent, ReflectResource},
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy_math::{Vec2, Vec4};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    color::Color,
+    render_resource::{ShaderType, TextureFormat, UniformBuffer},
+    renderer::{RenderDevice, RenderQueue},
+    texture::Image,
+    view::{ComputedVisibility, Visibility},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::HashSet;
+
+/// The largest number of [`PointLight2d`]s lighting the sprites. The other ones are ignored.
+pub const MAX_POINT_LIGHTS_2D: usize = 16;
+/// The largest number of [`LightOccluder2d`]s casting shadows. The other ones are ignored.
+pub const MAX_LIGHT_OCCLUDERS_2D: usize = 64;
+
+/// A light lighting every sprite equally, when 2D lighting is enabled.
+///
+/// Inserting this resource enables 2D lighting, even without any [`PointLight2d`].
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AmbientLight2d {
+    pub color: Color,
+    /// A direct scale factor multiplied with `color` before being passed to the shader.
+    pub brightness: f32,
+}
+
+impl Default for AmbientLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            brightness: 0.2,
+        }
+    }
+}
+
+/// A light lighting the sprites around it in 2D, from its [`GlobalTransform`], fading out to its
+/// `radius`.
+///
+/// Spawning one enables 2D lighting. Without an [`AmbientLight2d`], the sprites out of the reach
+/// of every light are then black.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PointLight2d {
+    pub color: Color,
+    pub intensity: f32,
+    /// The distance the light reaches, in world units
+    pub radius: f32,
+    /// How far above the sprites the light is, only used to light their [`SpriteNormalMap`]s
+    pub height: f32,
+    /// Whether the [`LightOccluder2d`]s cast shadows from this light
+    pub shadows_enabled: bool,
+}
+
+impl Default for PointLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 300.0,
+            height: 50.0,
+            shadows_enabled: false,
+        }
+    }
+}
+
+/// A component bundle for [`PointLight2d`] entities.
+#[derive(Debug, Bundle, Default)]
+pub struct PointLight2dBundle {
+    pub point_light: PointLight2d,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// Enables or disables the light
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+/// A box blocking the light of the [`PointLight2d`]s with shadows enabled, centered on the
+/// [`GlobalTransform`] of its entity, such as a wall sprite.
+///
+/// The shadows are hard and only cast on sprites, while the pixels inside the box are lit.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct LightOccluder2d {
+    /// Half the size of the box, before the transform of the entity
+    pub half_size: Vec2,
+}
+
+/// The normal map of the image of a sprite, lit by the [`PointLight2d`]s from their height.
+///
+/// Its X goes right and its Y goes up in the image, and it's flipped with the sprite. It's used
+/// as linear data, so sRGB images are converted to linear ones once loaded.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct SpriteNormalMap(pub Handle<Image>);
+
+/// The GPU representation of a [`PointLight2d`].
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuPointLight2d {
+    /// The color multiplied by the intensity
+    color: Vec4,
+    /// The position, radius and height
+    position_radius: Vec4,
+    flags: u32,
+}
+
+/// The GPU representation of a [`LightOccluder2d`].
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuLightOccluder2d {
+    /// The center of the box and the direction of its X axis
+    center_x_axis: Vec4,
+    /// The direction of the Y axis of the box, and its half size
+    y_axis_half_size: Vec4,
+}
+
+#[derive(ShaderType)]
+pub struct GpuLights2d {
+    ambient: Vec4,
+    point_light_count: u32,
+    occluder_count: u32,
+    point_lights: [GpuPointLight2d; MAX_POINT_LIGHTS_2D],
+    occluders: [GpuLightOccluder2d; MAX_LIGHT_OCCLUDERS_2D],
+}
+
+impl Default for GpuLights2d {
+    fn default() -> Self {
+        Self {
+            ambient: Vec4::ZERO,
+            point_light_count: 0,
+            occluder_count: 0,
+            point_lights: [GpuPointLight2d::default(); MAX_POINT_LIGHTS_2D],
+            occluders: [GpuLightOccluder2d::default(); MAX_LIGHT_OCCLUDERS_2D],
+        }
+    }
+}
+
+impl GpuPointLight2d {
+    const SHADOWS_ENABLED_BIT: u32 = 1;
+}
+
+/// The 2D lights of the render world, bound with the view of the sprites.
+#[derive(Resource)]
+pub struct Lights2dMeta {
+    /// Whether the sprites are lit, when there is an [`AmbientLight2d`] or a [`PointLight2d`]
+    pub enabled: bool,
+    pub(crate) uniform: UniformBuffer<GpuLights2d>,
+}
+
+impl Default for Lights2dMeta {
+    fn default() -> Self {
+        let mut uniform = UniformBuffer::default();
+        uniform.set_label(Some("lights_2d_uniform_buffer"));
+        Self {
+            enabled: false,
+            uniform,
+        }
+    }
+}
+
+pub fn extract_lights_2d(
+    mut lights_meta: ResMut<Lights2dMeta>,
+    ambient_light: Extract<Option<Res<AmbientLight2d>>>,
+    point_lights: Extract<Query<(&PointLight2d, &GlobalTransform, &ComputedVisibility)>>,
+    occluders: Extract<
+        Query<(
+            &LightOccluder2d,
+            &GlobalTransform,
+            Option<&ComputedVisibility>,
+        )>,
+    >,
+) {
+    let lights = lights_meta.uniform.get_mut();
+    lights.ambient = match &*ambient_light {
+        Some(ambient_light) => {
+            Vec4::from_slice(&ambient_light.color.as_linear_rgba_f32()) * ambient_light.brightness
+        }
+        None => Vec4::ZERO,
+    };
+
+    let mut point_light_count = 0;
+    let visible_lights = point_lights
+        .iter()
+        .filter(|(_, _, visibility)| visibility.is_visible_in_hierarchy());
+    for ((point_light, transform, _), gpu_light) in
+        visible_lights.zip(lights.point_lights.iter_mut())
+    {
+        let position = transform.translation();
+        *gpu_light = GpuPointLight2d {
+            color: Vec4::from_slice(&point_light.color.as_linear_rgba_f32())
+                * point_light.intensity,
+            position_radius: Vec4::new(
+                position.x,
+                position.y,
+                point_light.radius,
+                point_light.height,
+            ),
+            flags: if point_light.shadows_enabled {
+                GpuPointLight2d::SHADOWS_ENABLED_BIT
+            } else {
+                0
+            },
+        };
+        point_light_count += 1;
+    }
+    lights.point_light_count = point_light_count;
+
+    let mut occluder_count = 0;
+    let visible_occluders = occluders.iter().filter(|(_, _, visibility)| {
+        visibility.map_or(true, |visibility| visibility.is_visible_in_hierarchy())
+    });
+    for ((occluder, transform, _), gpu_occluder) in
+        visible_occluders.zip(lights.occluders.iter_mut())
+    {
+        let affine = transform.affine();
+        let x_axis = affine.matrix3.x_axis.truncate();
+        let y_axis = affine.matrix3.y_axis.truncate();
+        let half_size = occluder.half_size * Vec2::new(x_axis.length(), y_axis.length());
+        let center = affine.translation.truncate();
+        // The boxes are tested in their own space, so their axes are normalized
+        let (x_axis, y_axis) = (x_axis.normalize_or_zero(), y_axis.normalize_or_zero());
+        *gpu_occluder = GpuLightOccluder2d {
+            center_x_axis: Vec4::new(center.x, center.y, x_axis.x, x_axis.y),
+            y_axis_half_size: Vec4::new(y_axis.x, y_axis.y, half_size.x, half_size.y),
+        };
+        occluder_count += 1;
+    }
+    lights.occluder_count = occluder_count;
+
+    lights_meta.enabled = ambient_light.is_some() || point_light_count > 0;
+}
+
+pub fn prepare_lights_2d(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut lights_meta: ResMut<Lights2dMeta>,
+) {
+    lights_meta
+        .uniform
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// Converts the sRGB images of the [`SpriteNormalMap`]s to linear ones, once they are loaded.
+pub fn linearize_normal_maps(
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    normal_maps: Query<&SpriteNormalMap>,
+    added_normal_maps: Query<&SpriteNormalMap, Added<SpriteNormalMap>>,
+) {
+    let loaded_images: HashSet<HandleId> = image_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle.id()),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+    let loaded_normal_maps = normal_maps
+        .iter()
+        .filter(|normal_map| loaded_images.contains(&normal_map.0.id()));
+    for normal_map in loaded_normal_maps.chain(added_normal_maps.iter()) {
+        // Only borrows the image mutably when it's converted, so that it isn't modified again
+        let linear_format = match images.get(&normal_map.0) {
+            Some(image) => match image.texture_descriptor.format {
+                TextureFormat::Rgba8UnormSrgb => TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8UnormSrgb => TextureFormat::Bgra8Unorm,
+                _ => continue,
+            },
+            None => continue,
+        };
+        if let Some(image) = images.get_mut(&normal_map.0) {
+            image.texture_descriptor.format = linear_format;
+        }
+    }
+}
+
+/// Lights the sprites with the [`AmbientLight2d`] and the [`PointLight2d`]s.
+#[derive(Default)]
+pub struct Light2dPlugin;
+
+impl Plugin for Light2dPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AmbientLight2d>()
+            .register_type::<PointLight2d>()
+            .register_type::<LightOccluder2d>()
+            .register_type::<SpriteNormalMap>()
+            .add_system_to_stage(CoreStage::PostUpdate, linearize_normal_maps);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<Lights2dMeta>()
+                .add_system_to_stage(RenderStage::Extract, extract_lights_2d)
+                .add_system_to_stage(RenderStage::Prepare, prepare_lights_2d);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lights_2d_layout() {
+        // The ambient light and counts, then the arrays aligned to 16 bytes, as in `sprite.wgsl`
+        assert_eq!(GpuPointLight2d::min_size().get(), 48);
+        assert_eq!(GpuLightOccluder2d::min_size().get(), 32);
+        assert_eq!(
+            GpuLights2d::min_size().get(),
+            32 + 48 * MAX_POINT_LIGHTS_2D as u64 + 32 * MAX_LIGHT_OCCLUDERS_2D as u64
+        );
+    }
+}
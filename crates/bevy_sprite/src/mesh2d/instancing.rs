@@ -0,0 +1,237 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::prelude::*;
+use bevy_log::error;
+use bevy_render::{
+    mesh::{
+        instance_buffer_layout, push_instance, InstanceBatch, InstanceBatches, InstanceData,
+        InstancedMesh, Mesh, MeshVertexBufferLayout,
+    },
+    render_asset::RenderAssets,
+    render_phase::{AddRenderCommand, DrawFunctions, RenderPhase, SetItemPipeline},
+    render_resource::{
+        BufferInitDescriptor, BufferUsages, PipelineCache, RenderPipelineDescriptor,
+        SpecializedMeshPipeline, SpecializedMeshPipelineError, SpecializedMeshPipelines,
+    },
+    renderer::RenderDevice,
+    view::{ComputedVisibility, Msaa, VisibleEntities},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_utils::{FloatOrd, HashMap};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::{
+    DrawMesh2dInstanced, Material2d, Material2dKey, Material2dPipeline, Mesh2dHandle,
+    Mesh2dPipelineKey, Mesh2dUniform, RenderMaterials2d, SetMaterial2dBindGroup,
+    SetMesh2dBindGroup, SetMesh2dViewBindGroup,
+};
+
+/// The shader location of the first column of the model matrix of each instance, followed by
+/// the other columns and then by the fields of the [`InstanceData`].
+pub const INSTANCE_2D_LOCATION: u32 = 5;
+
+/// Draws the visible entities with a [`Material2d`] `M` and an [`InstanceData`] `I` in
+/// instanced batches, one for each mesh and material they share.
+///
+/// The [`Material2dPlugin`](crate::Material2dPlugin) of `M` has to be added as well. The
+/// default vertex shader reads the model of each instance, with the `MESH_INSTANCED` shader
+/// def, and custom vertex shaders can read the data of `I` starting from
+/// [`INSTANCE_2D_LOCATION`] + 4. [`Material2d::specialize`] can tell instanced pipelines apart
+/// with [`Mesh2dPipelineKey::INSTANCED`].
+///
+/// The instances of a batch are drawn back to front, but the batch as a whole is sorted with
+/// its farthest instance.
+pub struct InstancedMaterial2dPlugin<M: Material2d, I: InstanceData>(PhantomData<(M, I)>);
+
+impl<M: Material2d, I: InstanceData> Default for InstancedMaterial2dPlugin<M, I> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Material2d, I: InstanceData> Plugin for InstancedMaterial2dPlugin<M, I>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<Transparent2d, DrawInstancedMaterial2d<M>>()
+                .init_resource::<InstancedMaterial2dPipeline<M, I>>()
+                .init_resource::<SpecializedMeshPipelines<InstancedMaterial2dPipeline<M, I>>>()
+                .add_system_to_stage(RenderStage::Extract, extract_instances_2d::<M, I>)
+                .add_system_to_stage(
+                    RenderStage::Queue,
+                    queue_instanced_material2d_meshes::<M, I>,
+                );
+        }
+    }
+}
+
+/// The [`Material2dPipeline`] of `M`, with the instances of `I` bound to a second vertex
+/// buffer.
+#[derive(Resource)]
+pub struct InstancedMaterial2dPipeline<M: Material2d, I: InstanceData> {
+    pub material2d_pipeline: Material2dPipeline<M>,
+    marker: PhantomData<I>,
+}
+
+impl<M: Material2d, I: InstanceData> FromWorld for InstancedMaterial2dPipeline<M, I> {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            material2d_pipeline: world.resource::<Material2dPipeline<M>>().clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Material2d, I: InstanceData> SpecializedMeshPipeline for InstancedMaterial2dPipeline<M, I>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = Material2dKey<M>;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayout,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.material2d_pipeline.specialize(key, layout)?;
+        descriptor
+            .vertex
+            .buffers
+            .push(instance_buffer_layout::<I>(INSTANCE_2D_LOCATION));
+        Ok(descriptor)
+    }
+}
+
+type DrawInstancedMaterial2d<M> = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    SetMaterial2dBindGroup<M, 1>,
+    SetMesh2dBindGroup<2>,
+    DrawMesh2dInstanced,
+);
+
+fn extract_instances_2d<M: Material2d, I: InstanceData>(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    query: Extract<Query<(Entity, &ComputedVisibility, &I), With<Handle<M>>>>,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, computed_visibility, instance) in &query {
+        if computed_visibility.is_visible() {
+            values.push((entity, (*instance, InstancedMesh)));
+        }
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_instanced_material2d_meshes<M: Material2d, I: InstanceData>(
+    transparent_draw_functions: Res<DrawFunctions<Transparent2d>>,
+    instanced_pipeline: Res<InstancedMaterial2dPipeline<M, I>>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<InstancedMaterial2dPipeline<M, I>>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    mut instance_batches: ResMut<InstanceBatches>,
+    render_meshes: Res<RenderAssets<Mesh>>,
+    render_materials: Res<RenderMaterials2d<M>>,
+    instanced_meshes: Query<(&Handle<M>, &Mesh2dHandle, &Mesh2dUniform, &I)>,
+    mut views: Query<(
+        Entity,
+        &VisibleEntities,
+        &mut RenderPhase<Transparent2d>,
+        &Msaa,
+    )>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if instanced_meshes.is_empty() {
+        return;
+    }
+    for (view_entity, visible_entities, mut transparent_phase, msaa) in &mut views {
+        let draw_transparent = transparent_draw_functions
+            .read()
+            .get_id::<DrawInstancedMaterial2d<M>>()
+            .unwrap();
+
+        let msaa_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples);
+
+        let mut batches = HashMap::<_, Vec<_>>::default();
+        for visible_entity in &visible_entities.entities {
+            if let Ok((material2d_handle, mesh2d_handle, mesh2d_uniform, _)) =
+                instanced_meshes.get(*visible_entity)
+            {
+                batches
+                    .entry((material2d_handle, &mesh2d_handle.0))
+                    .or_default()
+                    .push((*visible_entity, mesh2d_uniform.transform.w_axis.z));
+            }
+        }
+
+        for ((material2d_handle, mesh_handle), mut instances) in batches {
+            let (material2d, mesh) = match (
+                render_materials.get(material2d_handle),
+                render_meshes.get(mesh_handle),
+            ) {
+                (Some(material2d), Some(mesh)) => (material2d, mesh),
+                _ => continue,
+            };
+            let mesh_key = msaa_key
+                | Mesh2dPipelineKey::from_primitive_topology(mesh.primitive_topology)
+                | Mesh2dPipelineKey::INSTANCED;
+
+            let pipeline_id = pipelines.specialize(
+                &mut pipeline_cache,
+                &instanced_pipeline,
+                Material2dKey {
+                    mesh_key,
+                    bind_group_data: material2d.key.clone(),
+                },
+                &mesh.layout,
+            );
+            let pipeline_id = match pipeline_id {
+                Ok(id) => id,
+                Err(err) => {
+                    error!("{}", err);
+                    continue;
+                }
+            };
+
+            // Back to front, as the meshes are sorted for the transparent phase
+            instances.sort_by_key(|(_, mesh_z)| FloatOrd(*mesh_z));
+            let mut contents = Vec::new();
+            for (entity, _) in &instances {
+                let (_, _, mesh2d_uniform, instance) = instanced_meshes.get(*entity).unwrap();
+                push_instance(&mut contents, &mesh2d_uniform.transform, instance);
+            }
+            let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("mesh2d_instance_buffer"),
+                contents: &contents,
+                usage: BufferUsages::VERTEX,
+            });
+            let (entity, mesh_z) = instances[0];
+            instance_batches.insert(
+                view_entity,
+                entity,
+                InstanceBatch {
+                    buffer,
+                    count: instances.len() as u32,
+                },
+            );
+
+            transparent_phase.add(Transparent2d {
+                entity,
+                draw_function: draw_transparent,
+                pipeline: pipeline_id,
+                sort_key: FloatOrd(mesh_z),
+                // The batch is already drawn in a single call
+                batch_range: None,
+            });
+        }
+    }
+}
@@ -6,6 +6,7 @@ use bevy_ecs::{
     entity::Entity,
     event::EventReader,
     prelude::{Bundle, World},
+    query::Without,
     schedule::IntoSystemDescriptor,
     system::{
         lifetimeless::{Read, SQuery, SRes},
@@ -17,7 +18,7 @@ use bevy_log::error;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     extract_component::ExtractComponentPlugin,
-    mesh::{Mesh, MeshVertexBufferLayout},
+    mesh::{InstancedMesh, Mesh, MeshVertexBufferLayout},
     prelude::Image,
     render_asset::{PrepareAssetLabel, RenderAssets},
     render_phase::{
@@ -171,7 +172,7 @@ where
 }
 
 /// Render pipeline data for a given [`Material2d`]
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct Material2dPipeline<M: Material2d> {
     pub mesh2d_pipeline: Mesh2dPipeline,
     pub material2d_layout: BindGroupLayout,
@@ -302,18 +303,18 @@ pub fn queue_material2d_meshes<M: Material2d>(
     material2d_pipeline: Res<Material2dPipeline<M>>,
     mut pipelines: ResMut<SpecializedMeshPipelines<Material2dPipeline<M>>>,
     mut pipeline_cache: ResMut<PipelineCache>,
-    msaa: Res<Msaa>,
     render_meshes: Res<RenderAssets<Mesh>>,
     render_materials: Res<RenderMaterials2d<M>>,
-    material2d_meshes: Query<(&Handle<M>, &Mesh2dHandle, &Mesh2dUniform)>,
-    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>)>,
+    // Instanced meshes are drawn in batches by the `InstancedMaterial2dPlugin`
+    material2d_meshes: Query<(&Handle<M>, &Mesh2dHandle, &Mesh2dUniform), Without<InstancedMesh>>,
+    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>, &Msaa)>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
     if material2d_meshes.is_empty() {
         return;
     }
-    for (visible_entities, mut transparent_phase) in &mut views {
+    for (visible_entities, mut transparent_phase, msaa) in &mut views {
         let draw_transparent_pbr = transparent_draw_functions
             .read()
             .get_id::<DrawMaterial2d<M>>()
@@ -9,7 +9,7 @@ use bevy_reflect::{Reflect, TypeUuid};
 use bevy_render::{
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
     globals::{GlobalsBuffer, GlobalsUniform},
-    mesh::{GpuBufferInfo, Mesh, MeshVertexBufferLayout},
+    mesh::{GpuBufferInfo, InstanceBatches, Mesh, MeshVertexBufferLayout},
     render_asset::RenderAssets,
     render_phase::{EntityRenderCommand, RenderCommandResult, TrackedRenderPass},
     render_resource::*,
@@ -286,6 +286,7 @@ bitflags::bitflags! {
     // FIXME: make normals optional?
     pub struct Mesh2dPipelineKey: u32 {
         const NONE                        = 0;
+        const INSTANCED                   = (1 << 0);
         const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS = Self::PRIMITIVE_TOPOLOGY_MASK_BITS << Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
     }
@@ -364,6 +365,10 @@ impl SpecializedMeshPipeline for Mesh2dPipeline {
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(4));
         }
 
+        if key.contains(Mesh2dPipelineKey::INSTANCED) {
+            shader_defs.push(String::from("MESH_INSTANCED"));
+        }
+
         let vertex_buffer_layout = layout.get_layout(&vertex_attributes)?;
 
         Ok(RenderPipelineDescriptor {
@@ -541,3 +546,46 @@ impl EntityRenderCommand for DrawMesh2d {
         }
     }
 }
+
+/// Draws the instanced batch of the phase item, whose instances are bound to the vertex buffer
+/// at index 1.
+pub struct DrawMesh2dInstanced;
+impl EntityRenderCommand for DrawMesh2dInstanced {
+    type Param = (
+        SRes<RenderAssets<Mesh>>,
+        SRes<InstanceBatches>,
+        SQuery<Read<Mesh2dHandle>>,
+    );
+    #[inline]
+    fn render<'w>(
+        view: Entity,
+        item: Entity,
+        (meshes, batches, mesh2d_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let mesh_handle = &mesh2d_query.get(item).unwrap().0;
+        let (gpu_mesh, batch) = match (
+            meshes.into_inner().get(mesh_handle),
+            batches.into_inner().get(view, item),
+        ) {
+            (Some(gpu_mesh), Some(batch)) => (gpu_mesh, batch),
+            _ => return RenderCommandResult::Failure,
+        };
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, batch.buffer.slice(..));
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, 0..batch.count);
+            }
+            GpuBufferInfo::NonIndexed { vertex_count } => {
+                pass.draw(0..*vertex_count, 0..batch.count);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
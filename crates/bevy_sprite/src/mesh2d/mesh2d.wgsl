@@ -20,6 +20,12 @@ struct Vertex {
 #ifdef VERTEX_COLORS
     @location(4) color: vec4<f32>,
 #endif
+#ifdef MESH_INSTANCED
+    @location(5) instance_model_0: vec4<f32>,
+    @location(6) instance_model_1: vec4<f32>,
+    @location(7) instance_model_2: vec4<f32>,
+    @location(8) instance_model_3: vec4<f32>,
+#endif
 };
 
 struct VertexOutput {
@@ -31,21 +37,36 @@ struct VertexOutput {
 fn vertex(vertex: Vertex) -> VertexOutput {
     var out: VertexOutput;
 
+#ifdef MESH_INSTANCED
+    let model = mat4x4<f32>(
+        vertex.instance_model_0,
+        vertex.instance_model_1,
+        vertex.instance_model_2,
+        vertex.instance_model_3
+    );
+#else
+    let model = mesh.model;
+#endif
+
 #ifdef VERTEX_UVS
     out.uv = vertex.uv;
 #endif
 
 #ifdef VERTEX_POSITIONS
-    out.world_position = mesh2d_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    out.world_position = mesh2d_position_local_to_world(model, vec4<f32>(vertex.position, 1.0));
     out.clip_position = mesh2d_position_world_to_clip(out.world_position);
 #endif
 
 #ifdef VERTEX_NORMALS
+#ifdef MESH_INSTANCED
+    out.world_normal = mesh2d_normal_local_to_world_with_model(model, vertex.normal);
+#else
     out.world_normal = mesh2d_normal_local_to_world(vertex.normal);
 #endif
+#endif
 
 #ifdef VERTEX_TANGENTS
-    out.world_tangent = mesh2d_tangent_local_to_world(mesh.model, vertex.tangent);
+    out.world_tangent = mesh2d_tangent_local_to_world(model, vertex.tangent);
 #endif
 
 #ifdef VERTEX_COLORS
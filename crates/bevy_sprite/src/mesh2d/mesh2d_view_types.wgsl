@@ -10,6 +10,8 @@ struct View {
     world_position: vec3<f32>,
     // viewport(x_origin, y_origin, width, height)
     viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
 };
 
 struct Globals {
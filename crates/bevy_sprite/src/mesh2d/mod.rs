@@ -1,7 +1,9 @@
 mod color_material;
+mod instancing;
 mod material;
 mod mesh;
 
 pub use color_material::*;
+pub use instancing::*;
 pub use material::*;
 pub use mesh::*;
@@ -1,8 +1,8 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, ops::Range};
 
 use crate::{
     texture_atlas::{TextureAtlas, TextureAtlasSprite},
-    Sprite, SPRITE_SHADER_HANDLE,
+    GpuLights2d, ImageScaleMode, Lights2dMeta, Sprite, SpriteNormalMap, SPRITE_SHADER_HANDLE,
 };
 use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
 use bevy_core_pipeline::core_2d::Transparent2d;
@@ -10,7 +10,7 @@ use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Rect, Vec2, Vec3};
 use bevy_reflect::Uuid;
 use bevy_render::{
     color::Color,
@@ -29,9 +29,10 @@ use bevy_render::{
 };
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::FloatOrd;
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use bytemuck::{Pod, Zeroable};
 use fixedbitset::FixedBitSet;
+use smallvec::{smallvec, SmallVec};
 
 #[derive(Resource)]
 pub struct SpritePipeline {
@@ -52,16 +53,29 @@ impl FromWorld for SpritePipeline {
             system_state.get_mut(world);
 
         let view_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-            entries: &[BindGroupLayoutEntry {
-                binding: 0,
-                visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
-                ty: BindingType::Buffer {
-                    ty: BufferBindingType::Uniform,
-                    has_dynamic_offset: true,
-                    min_binding_size: Some(ViewUniform::min_size()),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(ViewUniform::min_size()),
+                    },
+                    count: None,
+                },
+                // Lights 2D
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GpuLights2d::min_size()),
+                    },
+                    count: None,
                 },
-                count: None,
-            }],
+            ],
             label: Some("sprite_view_layout"),
         });
 
@@ -83,6 +97,17 @@ impl FromWorld for SpritePipeline {
                     ty: BindingType::Sampler(SamplerBindingType::Filtering),
                     count: None,
                 },
+                // Normal map, or the dummy white image without one
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
             ],
             label: Some("sprite_material_layout"),
         });
@@ -147,7 +172,8 @@ bitflags::bitflags! {
     // MSAA uses the highest 3 bits for the MSAA log2(sample count) to support up to 128x MSAA.
     pub struct SpritePipelineKey: u32 {
         const NONE                        = 0;
-        const COLORED                     = (1 << 0);
+        const LIGHTING                    = (1 << 0);
+        const NORMAL_MAP                  = (1 << 1);
         const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
     }
 }
@@ -171,24 +197,28 @@ impl SpecializedRenderPipeline for SpritePipeline {
     type Key = SpritePipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
-        let mut formats = vec![
-            // position
-            VertexFormat::Float32x3,
-            // uv
-            VertexFormat::Float32x2,
-        ];
-
-        if key.contains(SpritePipelineKey::COLORED) {
-            // color
-            formats.push(VertexFormat::Float32x4);
-        }
-
-        let vertex_layout =
-            VertexBufferLayout::from_vertex_formats(VertexStepMode::Vertex, formats);
+        let instance_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            vec![
+                // origin
+                VertexFormat::Float32x3,
+                // x_axis
+                VertexFormat::Float32x3,
+                // y_axis
+                VertexFormat::Float32x3,
+                // uv_offset_scale
+                VertexFormat::Float32x4,
+                // color
+                VertexFormat::Float32x4,
+            ],
+        );
 
         let mut shader_defs = Vec::new();
-        if key.contains(SpritePipelineKey::COLORED) {
-            shader_defs.push("COLORED".to_string());
+        if key.contains(SpritePipelineKey::LIGHTING) {
+            shader_defs.push("LIGHTING_2D".to_string());
+            if key.contains(SpritePipelineKey::NORMAL_MAP) {
+                shader_defs.push("SPRITE_NORMAL_MAP".to_string());
+            }
         }
 
         RenderPipelineDescriptor {
@@ -196,7 +226,7 @@ impl SpecializedRenderPipeline for SpritePipeline {
                 shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
                 entry_point: "vertex".into(),
                 shader_defs: shader_defs.clone(),
-                buffers: vec![vertex_layout],
+                buffers: vec![instance_layout],
             },
             fragment: Some(FragmentState {
                 shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
@@ -244,13 +274,264 @@ pub struct ExtractedSprite {
     pub flip_x: bool,
     pub flip_y: bool,
     pub anchor: Vec2,
+    /// How the image is scaled to the size of the sprite
+    pub scale_mode: ImageScaleMode,
+    /// Handle to the [`SpriteNormalMap`] of this sprite
+    pub normal_map_handle_id: Option<HandleId>,
 }
 
+/// The sprites extracted for a single frame, such as the glyphs of 2D text.
+///
+/// They are written to the GPU each frame, after the [`Sprite`]s and [`TextureAtlasSprite`]s of
+/// the [`SpriteCache`].
 #[derive(Resource, Default)]
 pub struct ExtractedSprites {
     pub sprites: Vec<ExtractedSprite>,
 }
 
+/// A sprite kept between frames by the [`SpriteCache`].
+struct CachedSprite {
+    sprite: ExtractedSprite,
+    /// The slots of the instances of the sprite in the instance buffer, once its image is loaded
+    slots: Option<Range<usize>>,
+    /// The last frame the sprite was extracted in
+    frame: u32,
+}
+
+/// What the phase item of an instance of a sprite is built from.
+#[derive(Clone, Copy)]
+struct SpriteSlot {
+    entity: Entity,
+    image_handle_id: HandleId,
+    normal_map_handle_id: Option<HandleId>,
+    z: f32,
+}
+
+/// An instance of a sprite, in the order the instances are written to the GPU.
+#[derive(Clone, Copy)]
+struct SortedSprite {
+    slot: SpriteSlot,
+    instance: SpriteInstance,
+}
+
+/// Dirty ranges of slots closer than this are written together, as a single write is cheaper
+/// than many small ones.
+const DIRTY_SLOTS_GAP: usize = 32;
+
+/// The [`Sprite`]s and [`TextureAtlasSprite`]s not hidden in the hierarchy, kept between frames.
+///
+/// Each frame, only the sprites whose components changed are extracted again. Each sprite keeps
+/// the slots of its instances in the instance buffer while their number doesn't change, so that
+/// only the slots of the new and changed sprites are written to the GPU again. The slots freed
+/// by the removed sprites are reclaimed once they make up half of the buffer, which sorts all
+/// the instances again to batch them.
+#[derive(Resource, Default)]
+pub struct SpriteCache {
+    sprites: HashMap<Entity, CachedSprite>,
+    frame: u32,
+    /// The sprites whose instances have to be computed, because they are new or changed, or
+    /// their image isn't loaded yet
+    stale: HashSet<Entity>,
+    /// The sprite of each slot of the instance buffer, `None` for the free slots
+    slots: Vec<Option<SpriteSlot>>,
+    free_slots: usize,
+    /// The ranges of slots to write to the GPU
+    dirty: Vec<Range<usize>>,
+    /// The sorted [`ExtractedSprites`] of this frame
+    frame_sprites: Vec<SortedSprite>,
+}
+
+impl SpriteCache {
+    /// Keeps the sprite of `entity` for this frame, extracting it again if it `changed` or is
+    /// new. The sprites not kept are removed at the end of the extraction.
+    fn keep(
+        &mut self,
+        entity: Entity,
+        changed: bool,
+        extract: impl FnOnce() -> Option<ExtractedSprite>,
+    ) {
+        let frame = self.frame;
+        match self.sprites.get_mut(&entity) {
+            Some(cached) if !changed => cached.frame = frame,
+            cached => {
+                if let Some(sprite) = extract() {
+                    match cached {
+                        // The slots are kept, to be written again if the number of instances
+                        // doesn't change
+                        Some(cached) => {
+                            cached.sprite = sprite;
+                            cached.frame = frame;
+                        }
+                        None => {
+                            self.sprites.insert(
+                                entity,
+                                CachedSprite {
+                                    sprite,
+                                    slots: None,
+                                    frame,
+                                },
+                            );
+                        }
+                    }
+                    self.stale.insert(entity);
+                }
+            }
+        }
+    }
+
+    /// Removes the sprites not kept this frame, freeing their slots.
+    fn remove_unused(&mut self) {
+        let frame = self.frame;
+        let Self {
+            sprites,
+            stale,
+            slots,
+            free_slots,
+            ..
+        } = self;
+        sprites.retain(|entity, cached| {
+            if cached.frame == frame {
+                return true;
+            }
+            stale.remove(entity);
+            if let Some(range) = cached.slots.take() {
+                free(slots, free_slots, range);
+            }
+            false
+        });
+    }
+
+    /// Computes the instances of the stale sprites whose image is loaded, given the size of
+    /// each loaded image, and writes them to their slots of `instances`.
+    fn update(
+        &mut self,
+        image_size: impl Fn(HandleId) -> Option<Vec2>,
+        instances: &mut BufferVec<SpriteInstance>,
+    ) {
+        let Self {
+            sprites,
+            stale,
+            slots,
+            free_slots,
+            dirty,
+            ..
+        } = self;
+        let mut appended = Vec::new();
+        stale.retain(|entity| {
+            let cached = match sprites.get_mut(entity) {
+                Some(cached) => cached,
+                None => return false,
+            };
+            let image_size = match image_size(cached.sprite.image_handle_id) {
+                Some(image_size) => image_size,
+                // The sprite isn't drawn until its image is loaded, which doesn't require writing
+                // the other sprites again
+                None => {
+                    if let Some(range) = cached.slots.take() {
+                        free(slots, free_slots, range);
+                    }
+                    return true;
+                }
+            };
+            let slot = SpriteSlot {
+                entity: *entity,
+                image_handle_id: cached.sprite.image_handle_id,
+                normal_map_handle_id: cached.sprite.normal_map_handle_id,
+                z: cached.sprite.transform.translation().z,
+            };
+            let sprite_instances = sprite_instances(&cached.sprite, image_size);
+            match cached.slots.clone() {
+                Some(range) if range.len() == sprite_instances.len() => {
+                    for (index, instance) in range.clone().zip(sprite_instances) {
+                        slots[index] = Some(slot);
+                        instances.values_mut()[index] = instance;
+                    }
+                    dirty.push(range);
+                }
+                range => {
+                    if let Some(range) = range {
+                        free(slots, free_slots, range);
+                    }
+                    cached.slots = None;
+                    appended.extend(
+                        sprite_instances
+                            .into_iter()
+                            .map(|instance| SortedSprite { slot, instance }),
+                    );
+                }
+            }
+            false
+        });
+
+        if *free_slots * 2 > slots.len() {
+            // Reclaims the free slots, sorting all the instances
+            appended.extend(slots.iter().zip(instances.values()).filter_map(
+                |(slot, &instance)| {
+                    Some(SortedSprite {
+                        slot: (*slot)?,
+                        instance,
+                    })
+                },
+            ));
+            for sprite in &appended {
+                sprites.get_mut(&sprite.slot.entity).unwrap().slots = None;
+            }
+            slots.clear();
+            instances.clear();
+            *free_slots = 0;
+            dirty.clear();
+        }
+        if appended.is_empty() {
+            return;
+        }
+
+        // The instances of a sprite stay next to each other, as the sort is stable
+        sort_sprites(&mut appended);
+        let start = slots.len();
+        for (index, sprite) in (start..).zip(&appended) {
+            let cached = sprites.get_mut(&sprite.slot.entity).unwrap();
+            match &mut cached.slots {
+                Some(range) => range.end = index + 1,
+                None => cached.slots = Some(index..index + 1),
+            }
+            slots.push(Some(sprite.slot));
+            instances.push(sprite.instance);
+        }
+        dirty.push(start..slots.len());
+    }
+
+    /// Takes the ranges of slots to write to the GPU, in order.
+    fn take_dirty(&mut self) -> Vec<Range<usize>> {
+        let mut dirty = std::mem::take(&mut self.dirty);
+        dirty.sort_unstable_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(dirty.len());
+        for range in dirty {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end + DIRTY_SLOTS_GAP => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    /// The number of sprites kept between frames.
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sprites.is_empty()
+    }
+}
+
+/// Frees the slots in `range`, which are no longer drawn.
+fn free(slots: &mut [Option<SpriteSlot>], free_slots: &mut usize, range: Range<usize>) {
+    *free_slots += range.len();
+    slots[range].fill(None);
+}
+
 #[derive(Resource, Default)]
 pub struct SpriteAssetEvents {
     pub images: Vec<AssetEvent<Image>>,
@@ -279,9 +560,12 @@ pub fn extract_sprite_events(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn extract_sprites(
     mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut sprite_cache: ResMut<SpriteCache>,
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    mut atlas_events: Extract<EventReader<AssetEvent<TextureAtlas>>>,
     sprite_query: Extract<
         Query<(
             Entity,
@@ -289,6 +573,15 @@ pub fn extract_sprites(
             &Sprite,
             &GlobalTransform,
             &Handle<Image>,
+            Option<&ImageScaleMode>,
+            Option<&SpriteNormalMap>,
+            Or<(
+                Changed<Sprite>,
+                Changed<GlobalTransform>,
+                Changed<Handle<Image>>,
+                Changed<ImageScaleMode>,
+                Changed<SpriteNormalMap>,
+            )>,
         )>,
     >,
     atlas_query: Extract<
@@ -298,35 +591,76 @@ pub fn extract_sprites(
             &TextureAtlasSprite,
             &GlobalTransform,
             &Handle<TextureAtlas>,
+            Option<&ImageScaleMode>,
+            Option<&SpriteNormalMap>,
+            Or<(
+                Changed<TextureAtlasSprite>,
+                Changed<GlobalTransform>,
+                Changed<Handle<TextureAtlas>>,
+                Changed<ImageScaleMode>,
+                Changed<SpriteNormalMap>,
+            )>,
         )>,
     >,
 ) {
     extracted_sprites.sprites.clear();
-    for (entity, visibility, sprite, transform, handle) in sprite_query.iter() {
-        if !visibility.is_visible() {
+    let sprite_cache = &mut *sprite_cache;
+    sprite_cache.frame = sprite_cache.frame.wrapping_add(1);
+
+    for (entity, visibility, sprite, transform, handle, scale_mode, normal_map, changed) in
+        sprite_query.iter()
+    {
+        // The sprites culled by every view are kept, so that they don't have to be extracted again
+        // when they come back into view
+        if !visibility.is_visible_in_hierarchy() {
             continue;
         }
         // PERF: we don't check in this function that the `Image` asset is ready, since it should be in most cases and hashing the handle is expensive
-        extracted_sprites.sprites.push(ExtractedSprite {
-            entity,
-            color: sprite.color,
-            transform: *transform,
-            rect: sprite.rect,
-            // Pass the custom size
-            custom_size: sprite.custom_size,
-            flip_x: sprite.flip_x,
-            flip_y: sprite.flip_y,
-            image_handle_id: handle.id(),
-            anchor: sprite.anchor.as_vec(),
+        sprite_cache.keep(entity, changed, || {
+            Some(ExtractedSprite {
+                entity,
+                color: sprite.color,
+                transform: *transform,
+                rect: sprite.rect,
+                // Pass the custom size
+                custom_size: sprite.custom_size,
+                flip_x: sprite.flip_x,
+                flip_y: sprite.flip_y,
+                image_handle_id: handle.id(),
+                anchor: sprite.anchor.as_vec(),
+                scale_mode: scale_mode.copied().unwrap_or_default(),
+                normal_map_handle_id: normal_map.map(|normal_map| normal_map.0.id()),
+            })
         });
     }
-    for (entity, visibility, atlas_sprite, transform, texture_atlas_handle) in atlas_query.iter() {
-        if !visibility.is_visible() {
+
+    // The rects of the modified atlases may have changed
+    let modified_atlases: HashSet<HandleId> = atlas_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+    for (
+        entity,
+        visibility,
+        atlas_sprite,
+        transform,
+        texture_atlas_handle,
+        scale_mode,
+        normal_map,
+        changed,
+    ) in atlas_query.iter()
+    {
+        if !visibility.is_visible_in_hierarchy() {
             continue;
         }
-        if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
+        let changed = changed || modified_atlases.contains(&texture_atlas_handle.id());
+        sprite_cache.keep(entity, changed, || {
+            let texture_atlas = texture_atlases.get(texture_atlas_handle)?;
             let rect = Some(texture_atlas.textures[atlas_sprite.index as usize]);
-            extracted_sprites.sprites.push(ExtractedSprite {
+            Some(ExtractedSprite {
                 entity,
                 color: atlas_sprite.color,
                 transform: *transform,
@@ -338,68 +672,155 @@ pub fn extract_sprites(
                 flip_y: atlas_sprite.flip_y,
                 image_handle_id: texture_atlas.texture.id(),
                 anchor: atlas_sprite.anchor.as_vec(),
-            });
-        }
+                scale_mode: scale_mode.copied().unwrap_or_default(),
+                normal_map_handle_id: normal_map.map(|normal_map| normal_map.0.id()),
+            })
+        });
     }
+
+    // Removes the sprites that were despawned, hidden or lost their components
+    sprite_cache.remove_unused();
 }
 
+/// The data of a sprite in the instance buffer, drawn as a quad.
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
-struct SpriteVertex {
-    pub position: [f32; 3],
-    pub uv: [f32; 2],
+struct SpriteInstance {
+    /// The center of the quad
+    origin: [f32; 3],
+    /// The sides of the quad
+    x_axis: [f32; 3],
+    y_axis: [f32; 3],
+    /// The UV of the bottom left corner of the quad, and the size of the quad in UV space
+    uv_offset_scale: [f32; 4],
+    color: [f32; 4],
 }
 
-#[repr(C)]
-#[derive(Copy, Clone, Pod, Zeroable)]
-struct ColoredSpriteVertex {
-    pub position: [f32; 3],
-    pub uv: [f32; 2],
-    pub color: [f32; 4],
+impl SpriteInstance {
+    fn new(sprite: &ExtractedSprite, image_size: Vec2) -> Self {
+        // By default, the size of the quad is the size of the texture
+        let mut quad_size = image_size;
+        let mut uv_offset = Vec2::ZERO;
+        let mut uv_scale = Vec2::ONE;
+
+        // If a rect is specified, adjust UVs and the size of the quad
+        if let Some(rect) = sprite.rect {
+            quad_size = rect.size();
+            uv_offset = rect.min / image_size;
+            uv_scale = rect.size() / image_size;
+        }
+
+        if sprite.flip_x {
+            uv_offset.x += uv_scale.x;
+            uv_scale.x = -uv_scale.x;
+        }
+        if sprite.flip_y {
+            uv_offset.y += uv_scale.y;
+            uv_scale.y = -uv_scale.y;
+        }
+
+        // Override the size if a custom one is specified
+        if let Some(custom_size) = sprite.custom_size {
+            quad_size = custom_size;
+        }
+
+        // Apply size and global transform
+        let affine = sprite.transform.affine();
+        SpriteInstance {
+            origin: sprite
+                .transform
+                .transform_point((-sprite.anchor * quad_size).extend(0.))
+                .into(),
+            x_axis: affine.transform_vector3(Vec3::X * quad_size.x).into(),
+            y_axis: affine.transform_vector3(Vec3::Y * quad_size.y).into(),
+            uv_offset_scale: [uv_offset.x, uv_offset.y, uv_scale.x, uv_scale.y],
+            color: sprite.color.as_linear_rgba_f32(),
+        }
+    }
+}
+
+/// The instances of a sprite: a single one, unless its image is sliced or tiled by its
+/// [`ImageScaleMode`].
+fn sprite_instances(sprite: &ExtractedSprite, image_size: Vec2) -> SmallVec<[SpriteInstance; 1]> {
+    if sprite.scale_mode == ImageScaleMode::Stretched {
+        return smallvec![SpriteInstance::new(sprite, image_size)];
+    }
+    let rect = sprite.rect.unwrap_or(Rect {
+        min: Vec2::ZERO,
+        max: image_size,
+    });
+    let size = sprite.custom_size.unwrap_or_else(|| rect.size());
+    sprite
+        .scale_mode
+        .compute_slices(rect, size)
+        .into_iter()
+        .map(|slice| {
+            // Flipping the sprite mirrors the slices as well as their images
+            let mut offset = slice.offset;
+            if sprite.flip_x {
+                offset.x = -offset.x;
+            }
+            if sprite.flip_y {
+                offset.y = -offset.y;
+            }
+            let slice_sprite = ExtractedSprite {
+                rect: Some(slice.texture_rect),
+                custom_size: Some(slice.draw_size),
+                // Moves the slice by its offset from the center of the sprite
+                anchor: (sprite.anchor * size - offset) / slice.draw_size,
+                scale_mode: ImageScaleMode::Stretched,
+                ..*sprite
+            };
+            SpriteInstance::new(&slice_sprite, image_size)
+        })
+        .collect()
 }
 
 #[derive(Resource)]
 pub struct SpriteMeta {
-    vertices: BufferVec<SpriteVertex>,
-    colored_vertices: BufferVec<ColoredSpriteVertex>,
+    /// The instances in the slots of the [`SpriteCache`], followed by the ones of the
+    /// [`ExtractedSprites`]
+    instances: BufferVec<SpriteInstance>,
     view_bind_group: Option<BindGroup>,
 }
 
 impl Default for SpriteMeta {
     fn default() -> Self {
+        let mut instances = BufferVec::new(BufferUsages::VERTEX);
+        instances.set_label(Some("sprite_instance_buffer"));
         Self {
-            vertices: BufferVec::new(BufferUsages::VERTEX),
-            colored_vertices: BufferVec::new(BufferUsages::VERTEX),
+            instances,
             view_bind_group: None,
         }
     }
 }
 
-const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
-
-const QUAD_VERTEX_POSITIONS: [Vec2; 4] = [
-    Vec2::new(-0.5, -0.5),
-    Vec2::new(0.5, -0.5),
-    Vec2::new(0.5, 0.5),
-    Vec2::new(-0.5, 0.5),
-];
-
-const QUAD_UVS: [Vec2; 4] = [
-    Vec2::new(0., 1.),
-    Vec2::new(1., 1.),
-    Vec2::new(1., 0.),
-    Vec2::new(0., 0.),
-];
-
 #[derive(Component, Eq, PartialEq, Copy, Clone)]
 pub struct SpriteBatch {
     image_handle_id: HandleId,
-    colored: bool,
+    /// The normal map of the sprites, only bound when they are lit
+    normal_map_handle_id: Option<HandleId>,
 }
 
+/// The bind groups of the images of the sprites, and of their normal maps.
 #[derive(Resource, Default)]
 pub struct ImageBindGroups {
-    values: HashMap<Handle<Image>, BindGroup>,
+    values: HashMap<(Handle<Image>, Option<Handle<Image>>), BindGroup>,
+}
+
+/// Sorts sprites by z for correct transparency and then by handles to improve batching.
+///
+/// NOTE: This can be done independent of views by reasonably assuming that all 2D views look
+/// along the negative-z axis in world space
+fn sort_sprites(sprites: &mut [SortedSprite]) {
+    sprites.sort_by(|a, b| {
+        let (a, b) = (&a.slot, &b.slot);
+        match a.z.partial_cmp(&b.z) {
+            Some(Ordering::Equal) | None => (a.image_handle_id, a.normal_map_handle_id)
+                .cmp(&(b.image_handle_id, b.normal_map_handle_id)),
+            Some(other) => other,
+        }
+    });
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -407,8 +828,7 @@ pub fn queue_sprites(
     mut commands: Commands,
     mut view_entities: Local<FixedBitSet>,
     draw_functions: Res<DrawFunctions<Transparent2d>>,
-    render_device: Res<RenderDevice>,
-    render_queue: Res<RenderQueue>,
+    (render_device, render_queue): (Res<RenderDevice>, Res<RenderQueue>),
     mut sprite_meta: ResMut<SpriteMeta>,
     view_uniforms: Res<ViewUniforms>,
     sprite_pipeline: Res<SpritePipeline>,
@@ -416,103 +836,165 @@ pub fn queue_sprites(
     mut pipeline_cache: ResMut<PipelineCache>,
     mut image_bind_groups: ResMut<ImageBindGroups>,
     gpu_images: Res<RenderAssets<Image>>,
-    msaa: Res<Msaa>,
-    mut extracted_sprites: ResMut<ExtractedSprites>,
-    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>)>,
+    extracted_sprites: Res<ExtractedSprites>,
+    mut sprite_cache: ResMut<SpriteCache>,
+    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>, &Msaa)>,
     events: Res<SpriteAssetEvents>,
+    lights_meta: Res<Lights2dMeta>,
 ) {
+    let sprite_cache = &mut *sprite_cache;
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. } => {}
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
-                image_bind_groups.values.remove(handle)
+                image_bind_groups.values.retain(|(image, normal_map), _| {
+                    image != handle && normal_map.as_ref() != Some(handle)
+                });
+                // The size of the image may have changed as well
+                for (entity, cached) in &sprite_cache.sprites {
+                    if cached.sprite.image_handle_id == handle.id() {
+                        sprite_cache.stale.insert(*entity);
+                    }
+                }
             }
         };
     }
 
-    if let Some(view_binding) = view_uniforms.uniforms.binding() {
+    if let (Some(view_binding), Some(lights_binding)) = (
+        view_uniforms.uniforms.binding(),
+        lights_meta.uniform.binding(),
+    ) {
         let sprite_meta = &mut sprite_meta;
 
-        // Clear the vertex buffers
-        sprite_meta.vertices.clear();
-        sprite_meta.colored_vertices.clear();
-
         sprite_meta.view_bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: view_binding,
-            }],
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: view_binding,
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: lights_binding,
+                },
+            ],
             label: Some("sprite_view_bind_group"),
             layout: &sprite_pipeline.view_layout,
         }));
 
         let draw_sprite_function = draw_functions.read().get_id::<DrawSprite>().unwrap();
-        let key = SpritePipelineKey::from_msaa_samples(msaa.samples);
-        let pipeline = pipelines.specialize(&mut pipeline_cache, &sprite_pipeline, key);
-        let colored_pipeline = pipelines.specialize(
-            &mut pipeline_cache,
-            &sprite_pipeline,
-            key | SpritePipelineKey::COLORED,
-        );
 
-        // Vertex buffer indices
-        let mut index = 0;
-        let mut colored_index = 0;
+        // Only the instances of the new and changed sprites of the cache are computed again
+        sprite_meta.instances.truncate(sprite_cache.slots.len());
+        sprite_cache.update(
+            |image_handle_id| {
+                gpu_images
+                    .get(&Handle::weak(image_handle_id))
+                    .map(|gpu_image| gpu_image.size)
+            },
+            &mut sprite_meta.instances,
+        );
 
-        let extracted_sprites = &mut extracted_sprites.sprites;
-        // Sort sprites by z for correct transparency and then by handle to improve batching
-        // NOTE: This can be done independent of views by reasonably assuming that all 2D views look along the negative-z axis in world space
-        extracted_sprites.sort_unstable_by(|a, b| {
-            match a
-                .transform
-                .translation()
-                .z
-                .partial_cmp(&b.transform.translation().z)
-            {
-                Some(Ordering::Equal) | None => a.image_handle_id.cmp(&b.image_handle_id),
-                Some(other) => other,
+        // The sprites of this frame only are written after the ones of the cache
+        let frame_sprites = &mut sprite_cache.frame_sprites;
+        frame_sprites.clear();
+        for sprite in &extracted_sprites.sprites {
+            if let Some(gpu_image) = gpu_images.get(&Handle::weak(sprite.image_handle_id)) {
+                let slot = SpriteSlot {
+                    entity: sprite.entity,
+                    image_handle_id: sprite.image_handle_id,
+                    normal_map_handle_id: sprite.normal_map_handle_id,
+                    z: sprite.transform.translation().z,
+                };
+                frame_sprites.extend(
+                    sprite_instances(sprite, gpu_image.size)
+                        .into_iter()
+                        .map(|instance| SortedSprite { slot, instance }),
+                );
             }
-        });
+        }
+        sort_sprites(frame_sprites);
+        for sprite in frame_sprites.iter() {
+            sprite_meta.instances.push(sprite.instance);
+        }
+
         let image_bind_groups = &mut *image_bind_groups;
 
-        for (visible_entities, mut transparent_phase) in &mut views {
+        for (visible_entities, mut transparent_phase, msaa) in &mut views {
+            let mut key = SpritePipelineKey::from_msaa_samples(msaa.samples);
+            if lights_meta.enabled {
+                key |= SpritePipelineKey::LIGHTING;
+            }
+            let pipeline = pipelines.specialize(&mut pipeline_cache, &sprite_pipeline, key);
+            // Only specialized for the views with sprites with normal maps
+            let mut normal_map_pipeline = None;
+
             view_entities.clear();
             view_entities.extend(visible_entities.entities.iter().map(|e| e.id() as usize));
-            transparent_phase.items.reserve(extracted_sprites.len());
+            transparent_phase.items.reserve(sprite_meta.instances.len());
 
             // Impossible starting values that will be replaced on the first iteration
             let mut current_batch = SpriteBatch {
                 image_handle_id: HandleId::Id(Uuid::nil(), u64::MAX),
-                colored: false,
+                normal_map_handle_id: None,
             };
             let mut current_batch_entity = Entity::from_raw(u32::MAX);
-            let mut current_image_size = Vec2::ZERO;
+            let mut current_batch_pipeline = pipeline;
             // Add a phase item for each sprite, and detect when succesive items can be batched.
             // Spawn an entity with a `SpriteBatch` component for each possible batch.
             // Compatible items share the same entity.
             // Batches are merged later (in `batch_phase_system()`), so that they can be interrupted
             // by any other phase item (and they can interrupt other items from batching).
-            for extracted_sprite in extracted_sprites.iter() {
-                if !view_entities.contains(extracted_sprite.entity.id() as usize) {
+            let cached_sprites = sprite_cache.slots.iter().copied().enumerate();
+            let frame_sprites = (sprite_cache.slots.len()..)
+                .zip(&sprite_cache.frame_sprites)
+                .map(|(index, sprite)| (index, Some(sprite.slot)));
+            for (index, sprite) in cached_sprites.chain(frame_sprites) {
+                let sprite = match sprite {
+                    Some(sprite) => sprite,
+                    None => continue,
+                };
+                if !view_entities.contains(sprite.entity.id() as usize) {
                     continue;
                 }
                 let new_batch = SpriteBatch {
-                    image_handle_id: extracted_sprite.image_handle_id,
-                    colored: extracted_sprite.color != Color::WHITE,
+                    image_handle_id: sprite.image_handle_id,
+                    // The normal maps are only used by the lit sprites
+                    normal_map_handle_id: sprite
+                        .normal_map_handle_id
+                        .filter(|_| lights_meta.enabled),
                 };
                 if new_batch != current_batch {
                     // Set-up a new possible batch
-                    if let Some(gpu_image) =
-                        gpu_images.get(&Handle::weak(new_batch.image_handle_id))
-                    {
+                    let normal_map_image = match new_batch.normal_map_handle_id {
+                        Some(normal_map_handle_id) => {
+                            gpu_images.get(&Handle::weak(normal_map_handle_id))
+                        }
+                        None => Some(&sprite_pipeline.dummy_white_gpu_image),
+                    };
+                    if let (Some(gpu_image), Some(normal_map_image)) = (
+                        gpu_images.get(&Handle::weak(new_batch.image_handle_id)),
+                        normal_map_image,
+                    ) {
                         current_batch = new_batch;
-                        current_image_size = Vec2::new(gpu_image.size.x, gpu_image.size.y);
                         current_batch_entity = commands.spawn((current_batch,)).id();
+                        current_batch_pipeline = match current_batch.normal_map_handle_id {
+                            Some(_) => *normal_map_pipeline.get_or_insert_with(|| {
+                                pipelines.specialize(
+                                    &mut pipeline_cache,
+                                    &sprite_pipeline,
+                                    key | SpritePipelineKey::NORMAL_MAP,
+                                )
+                            }),
+                            None => pipeline,
+                        };
 
                         image_bind_groups
                             .values
-                            .entry(Handle::weak(current_batch.image_handle_id))
+                            .entry((
+                                Handle::weak(current_batch.image_handle_id),
+                                current_batch.normal_map_handle_id.map(Handle::weak),
+                            ))
                             .or_insert_with(|| {
                                 render_device.create_bind_group(&BindGroupDescriptor {
                                     entries: &[
@@ -526,6 +1008,12 @@ pub fn queue_sprites(
                                             binding: 1,
                                             resource: BindingResource::Sampler(&gpu_image.sampler),
                                         },
+                                        BindGroupEntry {
+                                            binding: 2,
+                                            resource: BindingResource::TextureView(
+                                                &normal_map_image.texture_view,
+                                            ),
+                                        },
                                     ],
                                     label: Some("sprite_material_bind_group"),
                                     layout: &sprite_pipeline.material_layout,
@@ -537,93 +1025,30 @@ pub fn queue_sprites(
                     }
                 }
 
-                // Calculate vertex data for this item
-
-                let mut uvs = QUAD_UVS;
-                if extracted_sprite.flip_x {
-                    uvs = [uvs[1], uvs[0], uvs[3], uvs[2]];
-                }
-                if extracted_sprite.flip_y {
-                    uvs = [uvs[3], uvs[2], uvs[1], uvs[0]];
-                }
-
-                // By default, the size of the quad is the size of the texture
-                let mut quad_size = current_image_size;
-
-                // If a rect is specified, adjust UVs and the size of the quad
-                if let Some(rect) = extracted_sprite.rect {
-                    let rect_size = rect.size();
-                    for uv in &mut uvs {
-                        *uv = (rect.min + *uv * rect_size) / current_image_size;
-                    }
-                    quad_size = rect_size;
-                }
-
-                // Override the size if a custom one is specified
-                if let Some(custom_size) = extracted_sprite.custom_size {
-                    quad_size = custom_size;
-                }
-
-                // Apply size and global transform
-                let positions = QUAD_VERTEX_POSITIONS.map(|quad_pos| {
-                    extracted_sprite
-                        .transform
-                        .transform_point(
-                            ((quad_pos - extracted_sprite.anchor) * quad_size).extend(0.),
-                        )
-                        .into()
-                });
-
                 // These items will be sorted by depth with other phase items
-                let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
-
-                // Store the vertex data and add the item to the render phase
-                if current_batch.colored {
-                    for i in QUAD_INDICES {
-                        sprite_meta.colored_vertices.push(ColoredSpriteVertex {
-                            position: positions[i],
-                            uv: uvs[i].into(),
-                            color: extracted_sprite.color.as_linear_rgba_f32(),
-                        });
-                    }
-                    let item_start = colored_index;
-                    colored_index += QUAD_INDICES.len() as u32;
-                    let item_end = colored_index;
-
-                    transparent_phase.add(Transparent2d {
-                        draw_function: draw_sprite_function,
-                        pipeline: colored_pipeline,
-                        entity: current_batch_entity,
-                        sort_key,
-                        batch_range: Some(item_start..item_end),
-                    });
-                } else {
-                    for i in QUAD_INDICES {
-                        sprite_meta.vertices.push(SpriteVertex {
-                            position: positions[i],
-                            uv: uvs[i].into(),
-                        });
-                    }
-                    let item_start = index;
-                    index += QUAD_INDICES.len() as u32;
-                    let item_end = index;
-
-                    transparent_phase.add(Transparent2d {
-                        draw_function: draw_sprite_function,
-                        pipeline,
-                        entity: current_batch_entity,
-                        sort_key,
-                        batch_range: Some(item_start..item_end),
-                    });
-                }
+                let sort_key = FloatOrd(sprite.z);
+                let index = index as u32;
+                transparent_phase.add(Transparent2d {
+                    draw_function: draw_sprite_function,
+                    pipeline: current_batch_pipeline,
+                    entity: current_batch_entity,
+                    sort_key,
+                    batch_range: Some(index..index + 1),
+                });
             }
         }
+
+        // Only the slots of the cache that changed are written again, unless the buffer has to
+        // grow
+        for range in sprite_cache.take_dirty() {
+            sprite_meta
+                .instances
+                .write_buffer_range(range, &render_device, &render_queue);
+        }
+        let range = sprite_cache.slots.len()..sprite_meta.instances.len();
         sprite_meta
-            .vertices
-            .write_buffer(&render_device, &render_queue);
-        sprite_meta
-            .colored_vertices
-            .write_buffer(&render_device, &render_queue);
+            .instances
+            .write_buffer_range(range, &render_device, &render_queue);
     }
 }
 
@@ -670,7 +1095,10 @@ impl<const I: usize> EntityRenderCommand for SetSpriteTextureBindGroup<I> {
             I,
             image_bind_groups
                 .values
-                .get(&Handle::weak(sprite_batch.image_handle_id))
+                .get(&(
+                    Handle::weak(sprite_batch.image_handle_id),
+                    sprite_batch.normal_map_handle_id.map(Handle::weak),
+                ))
                 .unwrap(),
             &[],
         );
@@ -680,22 +1108,176 @@ impl<const I: usize> EntityRenderCommand for SetSpriteTextureBindGroup<I> {
 
 pub struct DrawSpriteBatch;
 impl<P: BatchedPhaseItem> RenderCommand<P> for DrawSpriteBatch {
-    type Param = (SRes<SpriteMeta>, SQuery<Read<SpriteBatch>>);
+    type Param = SRes<SpriteMeta>;
 
     fn render<'w>(
         _view: Entity,
         item: &P,
-        (sprite_meta, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        sprite_meta: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let sprite_batch = query_batch.get(item.entity()).unwrap();
         let sprite_meta = sprite_meta.into_inner();
-        if sprite_batch.colored {
-            pass.set_vertex_buffer(0, sprite_meta.colored_vertices.buffer().unwrap().slice(..));
-        } else {
-            pass.set_vertex_buffer(0, sprite_meta.vertices.buffer().unwrap().slice(..));
-        }
-        pass.draw(item.batch_range().as_ref().unwrap().clone(), 0..1);
+        // Each quad is drawn from the 6 vertices of its instance
+        pass.set_vertex_buffer(0, sprite_meta.instances.buffer().unwrap().slice(..));
+        pass.draw(0..6, item.batch_range().as_ref().unwrap().clone());
         RenderCommandResult::Success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_transform::components::Transform;
+
+    use super::*;
+    use crate::{BorderRect, TextureSlicer};
+
+    #[test]
+    fn sprite_instances() {
+        let sprite = ExtractedSprite {
+            entity: Entity::from_raw(0),
+            transform: Transform::from_xyz(1.0, 2.0, 3.0).into(),
+            color: Color::WHITE,
+            rect: Some(Rect::new(8.0, 0.0, 16.0, 4.0)),
+            custom_size: None,
+            image_handle_id: HandleId::default::<Image>(),
+            flip_x: true,
+            flip_y: false,
+            anchor: Vec2::new(-0.5, -0.5),
+            scale_mode: ImageScaleMode::Stretched,
+            normal_map_handle_id: None,
+        };
+        let instance = SpriteInstance::new(&sprite, Vec2::new(16.0, 16.0));
+        assert_eq!(instance.origin, [5.0, 4.0, 3.0]);
+        assert_eq!(instance.x_axis, [8.0, 0.0, 0.0]);
+        assert_eq!(instance.y_axis, [0.0, 4.0, 0.0]);
+        assert_eq!(instance.uv_offset_scale, [1.0, 0.0, -0.5, 0.25]);
+
+        let scaled = SpriteInstance::new(
+            &ExtractedSprite {
+                transform: Transform::from_scale(Vec3::splat(2.0)).into(),
+                rect: None,
+                custom_size: Some(Vec2::new(1.0, 3.0)),
+                flip_x: false,
+                anchor: Vec2::ZERO,
+                ..sprite
+            },
+            Vec2::new(16.0, 16.0),
+        );
+        assert_eq!(scaled.origin, [0.0, 0.0, 0.0]);
+        assert_eq!(scaled.x_axis, [2.0, 0.0, 0.0]);
+        assert_eq!(scaled.y_axis, [0.0, 6.0, 0.0]);
+        assert_eq!(scaled.uv_offset_scale, [0.0, 0.0, 1.0, 1.0]);
+
+        // Each slice is an instance, placed from the anchor of the whole sprite
+        let sliced = super::sprite_instances(
+            &ExtractedSprite {
+                transform: Transform::IDENTITY.into(),
+                rect: None,
+                custom_size: Some(Vec2::new(32.0, 32.0)),
+                flip_x: false,
+                anchor: Vec2::ZERO,
+                scale_mode: ImageScaleMode::Sliced(TextureSlicer {
+                    border: BorderRect::square(4.0),
+                    ..Default::default()
+                }),
+                ..sprite
+            },
+            Vec2::new(16.0, 16.0),
+        );
+        assert_eq!(sliced.len(), 9);
+        // The top left corner
+        assert_eq!(sliced[0].origin, [-14.0, 14.0, 0.0]);
+        assert_eq!(sliced[0].x_axis, [4.0, 0.0, 0.0]);
+        assert_eq!(sliced[0].uv_offset_scale, [0.0, 0.0, 0.25, 0.25]);
+    }
+
+    #[test]
+    fn sprite_cache_only_writes_changed_slots() {
+        let loaded = HandleId::Id(Uuid::nil(), 1);
+        let loading = HandleId::Id(Uuid::nil(), 2);
+        let sprite = |x: f32, image_handle_id| ExtractedSprite {
+            entity: Entity::from_raw(0),
+            transform: Transform::from_xyz(x, 0.0, 0.0).into(),
+            color: Color::WHITE,
+            rect: None,
+            custom_size: None,
+            image_handle_id,
+            flip_x: false,
+            flip_y: false,
+            anchor: Vec2::ZERO,
+            scale_mode: ImageScaleMode::Stretched,
+            normal_map_handle_id: None,
+        };
+        let extract = |cache: &mut SpriteCache, sprites: &[(u32, bool, ExtractedSprite)]| {
+            cache.frame += 1;
+            for (entity, changed, sprite) in sprites {
+                cache.keep(Entity::from_raw(*entity), *changed, || Some(*sprite));
+            }
+            cache.remove_unused();
+        };
+        let slots =
+            |cache: &SpriteCache, entity| cache.sprites[&Entity::from_raw(entity)].slots.clone();
+        let dirty = |cache: &mut SpriteCache| -> Vec<(usize, usize)> {
+            let ranges = cache.take_dirty();
+            ranges
+                .iter()
+                .map(|range| (range.start, range.end))
+                .collect()
+        };
+        let mut cache = SpriteCache::default();
+        let mut instances = BufferVec::new(BufferUsages::VERTEX);
+        let mut loaded_images = vec![loaded];
+        let image_size =
+            |images: &Vec<HandleId>, id| images.contains(&id).then_some(Vec2::new(2.0, 2.0));
+
+        let mut sprites = vec![
+            (0, true, sprite(0.0, loaded)),
+            (1, true, sprite(1.0, loaded)),
+            (2, true, sprite(2.0, loaded)),
+            (3, true, sprite(3.0, loading)),
+        ];
+        extract(&mut cache, &sprites);
+        cache.update(|id| image_size(&loaded_images, id), &mut instances);
+        assert_eq!(instances.len(), 3);
+        assert_eq!(dirty(&mut cache), [(0, 3)]);
+        assert_eq!(slots(&cache, 3), None);
+
+        // Unchanged sprites, and the ones waiting for their image, aren't written again
+        for (_, changed, _) in &mut sprites {
+            *changed = false;
+        }
+        extract(&mut cache, &sprites);
+        cache.update(|id| image_size(&loaded_images, id), &mut instances);
+        assert!(dirty(&mut cache).is_empty());
+
+        // A moved sprite keeps its slot
+        let moved = slots(&cache, 1).unwrap();
+        sprites[1] = (1, true, sprite(10.0, loaded));
+        extract(&mut cache, &sprites);
+        cache.update(|id| image_size(&loaded_images, id), &mut instances);
+        assert_eq!(slots(&cache, 1), Some(moved.clone()));
+        assert_eq!(dirty(&mut cache), [(moved.start, moved.end)]);
+        assert_eq!(instances.values()[moved.start].origin, [10.0, 0.0, 0.0]);
+
+        // A removed sprite frees its slot, and the loaded image adds a slot
+        sprites[1].1 = false;
+        let removed = slots(&cache, 0).unwrap();
+        sprites.remove(0);
+        loaded_images.push(loading);
+        extract(&mut cache, &sprites);
+        cache.update(|id| image_size(&loaded_images, id), &mut instances);
+        assert_eq!(cache.slots.len(), 4);
+        assert!(cache.slots[removed.start].is_none());
+        assert_eq!(slots(&cache, 3), Some(3..4));
+        assert_eq!(dirty(&mut cache), [(3, 4)]);
+
+        // The free slots are reclaimed once they are half of the slots
+        sprites.truncate(1);
+        extract(&mut cache, &sprites);
+        cache.update(|id| image_size(&loaded_images, id), &mut instances);
+        assert_eq!(cache.slots.len(), 1);
+        assert_eq!(instances.len(), 1);
+        assert_eq!(slots(&cache, 1), Some(0..1));
+        assert_eq!(dirty(&mut cache), [(0, 1)]);
+    }
+}
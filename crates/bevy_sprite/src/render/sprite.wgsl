@@ -8,31 +8,89 @@ struct View {
     world_position: vec3<f32>,
     // viewport(x_origin, y_origin, width, height)
     viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
 };
 @group(0) @binding(0)
 var<uniform> view: View;
 
+#ifdef LIGHTING_2D
+struct PointLight2d {
+    // The color multiplied by the intensity
+    color: vec4<f32>,
+    // The position, radius and height
+    position_radius: vec4<f32>,
+    flags: u32,
+};
+
+struct LightOccluder2d {
+    // The center of the box and the direction of its X axis
+    center_x_axis: vec4<f32>,
+    // The direction of the Y axis of the box, and its half size
+    y_axis_half_size: vec4<f32>,
+};
+
+struct Lights2d {
+    ambient: vec4<f32>,
+    point_light_count: u32,
+    occluder_count: u32,
+    point_lights: array<PointLight2d, 16u>,
+    occluders: array<LightOccluder2d, 64u>,
+};
+
+let POINT_LIGHT_2D_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
+
+@group(0) @binding(1)
+var<uniform> lights: Lights2d;
+#endif
+
 struct VertexOutput {
     @location(0) uv: vec2<f32>,
-#ifdef COLORED
     @location(1) color: vec4<f32>,
+#ifdef LIGHTING_2D
+    @location(2) world_position: vec2<f32>,
+#endif
+#ifdef SPRITE_NORMAL_MAP
+    // The directions of the X and Y of the normal map in the world, flipped with the sprite
+    @location(3) tangent: vec2<f32>,
+    @location(4) bitangent: vec2<f32>,
 #endif
     @builtin(position) position: vec4<f32>,
 };
 
+// Draws the quad of a sprite instance from 6 vertices
 @vertex
 fn vertex(
-    @location(0) vertex_position: vec3<f32>,
-    @location(1) vertex_uv: vec2<f32>,
-#ifdef COLORED
-    @location(2) vertex_color: vec4<f32>,
-#endif
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) origin: vec3<f32>,
+    @location(1) x_axis: vec3<f32>,
+    @location(2) y_axis: vec3<f32>,
+    @location(3) uv_offset_scale: vec4<f32>,
+    @location(4) color: vec4<f32>,
 ) -> VertexOutput {
+    // The corners of the two triangles of the quad
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+        vec2<f32>(-0.5, 0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+    );
+    let corner = corners[vertex_index];
+
     var out: VertexOutput;
-    out.uv = vertex_uv;
-    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
-#ifdef COLORED
-    out.color = vertex_color;
+    // UVs go down while the quad goes up
+    out.uv = uv_offset_scale.xy + vec2<f32>(corner.x + 0.5, 0.5 - corner.y) * uv_offset_scale.zw;
+    let position = origin + corner.x * x_axis + corner.y * y_axis;
+    out.position = view.view_proj * vec4<f32>(position, 1.0);
+    out.color = color;
+#ifdef LIGHTING_2D
+    out.world_position = position.xy;
+#endif
+#ifdef SPRITE_NORMAL_MAP
+    out.tangent = normalize(x_axis.xy) * sign(uv_offset_scale.z);
+    out.bitangent = normalize(y_axis.xy) * sign(uv_offset_scale.w);
 #endif
     return out;
 }
@@ -42,11 +100,94 @@ var sprite_texture: texture_2d<f32>;
 @group(1) @binding(1)
 var sprite_sampler: sampler;
 
+@group(1) @binding(2)
+var normal_map_texture: texture_2d<f32>;
+
+#ifdef LIGHTING_2D
+// Whether the segment from the fragment to a light goes through an occluder, tested in the space
+// of its box
+fn occluded(fragment: vec2<f32>, light: vec2<f32>, occluder: LightOccluder2d) -> bool {
+    let center = occluder.center_x_axis.xy;
+    let x_axis = occluder.center_x_axis.zw;
+    let y_axis = occluder.y_axis_half_size.xy;
+    let half_size = occluder.y_axis_half_size.zw;
+    let start = vec2<f32>(dot(fragment - center, x_axis), dot(fragment - center, y_axis));
+    let end = vec2<f32>(dot(light - center, x_axis), dot(light - center, y_axis));
+    // The fragments inside the box, such as the ones of the sprite of the occluder, are lit
+    if (all(abs(start) <= half_size)) {
+        return false;
+    }
+
+    // Clips the segment to the slab of each axis of the box
+    let direction = end - start;
+    var t_min = 0.0;
+    var t_max = 1.0;
+    for (var axis = 0; axis < 2; axis = axis + 1) {
+        if (abs(direction[axis]) < 0.00001) {
+            if (abs(start[axis]) > half_size[axis]) {
+                return false;
+            }
+        } else {
+            let t_0 = (-half_size[axis] - start[axis]) / direction[axis];
+            let t_1 = (half_size[axis] - start[axis]) / direction[axis];
+            t_min = max(t_min, min(t_0, t_1));
+            t_max = min(t_max, max(t_0, t_1));
+        }
+    }
+    return t_min <= t_max;
+}
+
+fn in_shadow(fragment: vec2<f32>, light: vec2<f32>) -> bool {
+    for (var i = 0u; i < lights.occluder_count; i = i + 1u) {
+        if (occluded(fragment, light, lights.occluders[i])) {
+            return true;
+        }
+    }
+    return false;
+}
+
+// The light reaching a fragment with a normal in the world
+fn light_2d(world_position: vec2<f32>, normal: vec3<f32>) -> vec3<f32> {
+    var light = lights.ambient.rgb;
+    for (var i = 0u; i < lights.point_light_count; i = i + 1u) {
+        let point_light = lights.point_lights[i];
+        let to_light = point_light.position_radius.xy - world_position;
+        let distance = length(to_light);
+        let radius = point_light.position_radius.z;
+        if (distance >= radius) {
+            continue;
+        }
+        var attenuation = 1.0 - distance / radius;
+        attenuation = attenuation * attenuation;
+#ifdef SPRITE_NORMAL_MAP
+        let direction = normalize(vec3<f32>(to_light, point_light.position_radius.w));
+        attenuation = attenuation * max(dot(normal, direction), 0.0);
+#endif
+        if ((point_light.flags & POINT_LIGHT_2D_FLAGS_SHADOWS_ENABLED_BIT) != 0u
+            && in_shadow(world_position, point_light.position_radius.xy)) {
+            continue;
+        }
+        light = light + point_light.color.rgb * attenuation;
+    }
+    return light;
+}
+#endif
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = textureSample(sprite_texture, sprite_sampler, in.uv);
-#ifdef COLORED
-    color = in.color * color;
+    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+#ifdef LIGHTING_2D
+#ifdef SPRITE_NORMAL_MAP
+    let normal_sample = textureSample(normal_map_texture, sprite_sampler, in.uv).xyz * 2.0 - 1.0;
+    let normal = normalize(
+        normal_sample.x * vec3<f32>(in.tangent, 0.0)
+        + normal_sample.y * vec3<f32>(in.bitangent, 0.0)
+        + vec3<f32>(0.0, 0.0, normal_sample.z)
+    );
+#else
+    let normal = vec3<f32>(0.0, 0.0, 1.0);
+#endif
+    color = vec4<f32>(color.rgb * light_2d(in.world_position, normal), color.a);
 #endif
     return color;
 }
@@ -0,0 +1,191 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventWriter,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::Time;
+
+use crate::TextureAtlasSprite;
+
+/// How a [`SpriteAnimation`] goes on after its last frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub enum SpriteAnimationMode {
+    /// Stops on the last frame.
+    Once,
+    /// Starts again from the first frame.
+    #[default]
+    Loop,
+    /// Plays the frames backward back to the first one, and then forward again.
+    PingPong,
+}
+
+/// Plays the frames of the texture atlas of a [`TextureAtlasSprite`], from `first` to `last`, by
+/// advancing its [`TextureAtlasSprite::index`].
+///
+/// A [`SpriteAnimationCompleted`] event is sent each time the animation is completed.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct SpriteAnimation {
+    /// The index of the first frame in the texture atlas
+    pub first: usize,
+    /// The index of the last frame in the texture atlas
+    pub last: usize,
+    /// The number of frames per second. The animation doesn't advance if it isn't positive.
+    pub fps: f32,
+    pub mode: SpriteAnimationMode,
+    pub paused: bool,
+    /// The time the current frame has been shown for, in seconds
+    elapsed: f32,
+    /// Whether a [`SpriteAnimationMode::PingPong`] animation is going back to its first frame
+    backward: bool,
+    finished: bool,
+    /// Whether the animation starts again from its first frame
+    restart: bool,
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> Self {
+        Self::new(0, 0, 10.0)
+    }
+}
+
+impl SpriteAnimation {
+    /// An animation looping over the frames from `first` to `last` at `fps` frames per second.
+    pub fn new(first: usize, last: usize, fps: f32) -> Self {
+        Self {
+            first,
+            last,
+            fps,
+            mode: SpriteAnimationMode::Loop,
+            paused: false,
+            elapsed: 0.0,
+            backward: false,
+            finished: false,
+            restart: true,
+        }
+    }
+
+    /// Sets how the animation goes on after its last frame.
+    pub fn with_mode(mut self, mode: SpriteAnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Whether a [`SpriteAnimationMode::Once`] animation has stopped on its last frame.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Plays the animation again from its first frame.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.backward = false;
+        self.finished = false;
+        self.restart = true;
+    }
+
+    /// Advances the animation by `delta` seconds from the frame `index`, returning the new frame
+    /// and the number of times the animation was completed.
+    fn tick(&mut self, mut index: usize, delta: f32) -> (usize, u32) {
+        let (first, last) = (self.first.min(self.last), self.first.max(self.last));
+        // A new or reset animation, or one whose frames were changed, starts from its first frame
+        if self.restart || !(first..=last).contains(&index) {
+            self.restart = false;
+            index = first;
+        }
+        if self.paused || self.finished || self.fps <= 0.0 {
+            return (index, 0);
+        }
+
+        let frame_duration = self.fps.recip();
+        let mut completions = 0;
+        self.elapsed += delta;
+        while self.elapsed >= frame_duration {
+            self.elapsed -= frame_duration;
+            match self.mode {
+                SpriteAnimationMode::Once if index >= last => {
+                    self.finished = true;
+                    self.elapsed = 0.0;
+                    completions += 1;
+                    break;
+                }
+                SpriteAnimationMode::Loop if index >= last => {
+                    index = first;
+                    completions += 1;
+                }
+                SpriteAnimationMode::PingPong if self.backward && index <= first => {
+                    self.backward = false;
+                    index = (first + 1).min(last);
+                    completions += 1;
+                }
+                SpriteAnimationMode::PingPong if index >= last => {
+                    self.backward = true;
+                    index = last.saturating_sub(1).max(first);
+                }
+                _ if self.backward => index -= 1,
+                _ => index += 1,
+            }
+        }
+        (index, completions)
+    }
+}
+
+/// Sent each time a [`SpriteAnimation`] is completed: when it would go past its last frame, or
+/// for a [`SpriteAnimationMode::PingPong`] one, past its first frame on its way back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteAnimationCompleted {
+    pub entity: Entity,
+}
+
+/// Advances the [`TextureAtlasSprite::index`] of the entities with a [`SpriteAnimation`].
+pub fn animate_sprites(
+    time: Res<Time>,
+    mut completed_events: EventWriter<SpriteAnimationCompleted>,
+    mut query: Query<(Entity, &mut SpriteAnimation, &mut TextureAtlasSprite)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut animation, mut sprite) in &mut query {
+        if animation.paused || animation.finished {
+            continue;
+        }
+        let (index, completions) = animation.tick(sprite.index, delta);
+        // Only changes the sprite on a new frame, so that it isn't extracted again every frame
+        if sprite.index != index {
+            sprite.index = index;
+        }
+        for _ in 0..completions {
+            completed_events.send(SpriteAnimationCompleted { entity });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animation_modes() {
+        let mut animation = SpriteAnimation::new(2, 4, 10.0);
+        // Out of range frames start from the first one
+        assert_eq!(animation.tick(0, 0.06), (2, 0));
+        assert_eq!(animation.tick(2, 0.06), (3, 0));
+        assert_eq!(animation.tick(3, 0.2), (2, 1));
+        animation.reset();
+        assert_eq!(animation.tick(3, 0.0), (2, 0));
+
+        let mut once = SpriteAnimation::new(0, 2, 10.0).with_mode(SpriteAnimationMode::Once);
+        assert_eq!(once.tick(0, 0.25), (2, 0));
+        assert_eq!(once.tick(2, 0.1), (2, 1));
+        assert!(once.is_finished());
+        assert_eq!(once.tick(2, 0.1), (2, 0));
+
+        let mut ping_pong =
+            SpriteAnimation::new(0, 2, 10.0).with_mode(SpriteAnimationMode::PingPong);
+        assert_eq!(ping_pong.tick(0, 0.35), (1, 0));
+        assert_eq!(ping_pong.tick(1, 0.1), (0, 0));
+        assert_eq!(ping_pong.tick(0, 0.1), (1, 1));
+    }
+}
@@ -30,6 +30,8 @@ pub struct TextureAtlasBuilder {
     /// The grouped rects which must be placed with a key value pair of a
     /// texture handle to an index.
     rects_to_place: GroupedRectsToPlace<Handle<Image>>,
+    /// The added textures, in the order of their indices in the atlas.
+    textures: Vec<Handle<Image>>,
     /// The initial atlas size in pixels.
     initial_size: Vec2,
     /// The absolute maximum size of the texture atlas in pixels.
@@ -44,6 +46,7 @@ impl Default for TextureAtlasBuilder {
     fn default() -> Self {
         Self {
             rects_to_place: GroupedRectsToPlace::new(),
+            textures: Vec::new(),
             initial_size: Vec2::new(256., 256.),
             max_size: Vec2::new(2048., 2048.),
             format: TextureFormat::Rgba8UnormSrgb,
@@ -79,8 +82,27 @@ impl TextureAtlasBuilder {
         self
     }
 
+    /// A builder without any texture, with the same settings as this one.
+    pub(crate) fn with_same_settings(&self) -> Self {
+        Self {
+            rects_to_place: GroupedRectsToPlace::new(),
+            textures: Vec::new(),
+            initial_size: self.initial_size,
+            max_size: self.max_size,
+            format: self.format,
+            auto_format_conversion: self.auto_format_conversion,
+        }
+    }
+
     /// Adds a texture to be copied to the texture atlas.
+    ///
+    /// The index of the texture in the atlas is the number of textures added before it. Adding the
+    /// same texture again does nothing.
     pub fn add_texture(&mut self, texture_handle: Handle<Image>, texture: &Image) {
+        if self.textures.contains(&texture_handle) {
+            return;
+        }
+        self.textures.push(texture_handle.clone_weak());
         self.rects_to_place.push_rect(
             texture_handle,
             None,
@@ -151,6 +173,24 @@ impl TextureAtlasBuilder {
         self,
         textures: &mut Assets<Image>,
     ) -> Result<TextureAtlas, TextureAtlasBuilderError> {
+        let (atlas_texture, texture_rects, texture_handles) = self.pack(textures)?;
+        Ok(TextureAtlas {
+            size: Vec2::new(
+                atlas_texture.texture_descriptor.size.width as f32,
+                atlas_texture.texture_descriptor.size.height as f32,
+            ),
+            texture: textures.add(atlas_texture),
+            textures: texture_rects,
+            texture_handles: Some(texture_handles),
+        })
+    }
+
+    /// Packs the textures into the texture of an atlas, returned with the rects of the textures
+    /// and their indices.
+    pub(crate) fn pack(
+        self,
+        textures: &Assets<Image>,
+    ) -> TextureAtlasBuilderResult<(Image, Vec<Rect>, HashMap<Handle<Image>, usize>)> {
         let initial_width = self.initial_size.x as u32;
         let initial_height = self.initial_size.y as u32;
         let max_width = self.max_size.x as u32;
@@ -206,9 +246,10 @@ impl TextureAtlasBuilder {
 
         let rect_placements = rect_placements.ok_or(TextureAtlasBuilderError::NotEnoughSpace)?;
 
-        let mut texture_rects = Vec::with_capacity(rect_placements.packed_locations().len());
+        let mut texture_rects = Vec::with_capacity(self.textures.len());
         let mut texture_handles = HashMap::default();
-        for (texture_handle, (_, packed_location)) in rect_placements.packed_locations().iter() {
+        for texture_handle in &self.textures {
+            let (_, packed_location) = &rect_placements.packed_locations()[texture_handle];
             let texture = textures.get(texture_handle).unwrap();
             let min = Vec2::new(packed_location.x() as f32, packed_location.y() as f32);
             let max = min
@@ -227,14 +268,6 @@ impl TextureAtlasBuilder {
             }
             self.copy_converted_texture(&mut atlas_texture, texture, packed_location);
         }
-        Ok(TextureAtlas {
-            size: Vec2::new(
-                atlas_texture.texture_descriptor.size.width as f32,
-                atlas_texture.texture_descriptor.size.height as f32,
-            ),
-            texture: textures.add(atlas_texture),
-            textures: texture_rects,
-            texture_handles: Some(texture_handles),
-        })
+        Ok((atlas_texture, texture_rects, texture_handles))
     }
 }
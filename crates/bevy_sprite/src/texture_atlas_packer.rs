@@ -0,0 +1,183 @@
+use std::path::Path;
+
+use bevy_asset::{AssetEvent, AssetServer, AssetServerError, Assets, Handle, HandleId};
+use bevy_ecs::{
+    event::EventReader,
+    system::{ResMut, Resource},
+};
+use bevy_log::error;
+use bevy_math::{Rect, Vec2};
+use bevy_render::texture::Image;
+use bevy_utils::HashSet;
+
+use crate::{TextureAtlas, TextureAtlasBuilder};
+
+/// An atlas packed by the [`TextureAtlasPacker`].
+struct PackedTextureAtlas {
+    atlas: Handle<TextureAtlas>,
+    /// The images packed in the atlas, in the order of their indices
+    textures: Vec<Handle<Image>>,
+    /// The settings of the atlas
+    builder: TextureAtlasBuilder,
+    /// Whether the atlas has to be packed again, once all its images are loaded
+    dirty: bool,
+}
+
+/// Packs images into [`TextureAtlas`]es at runtime, as soon as they are all loaded, and packs
+/// them again when any of them is modified, such as when it's hot-reloaded.
+///
+/// The atlases are available right away, each image having an empty rect until they are packed.
+/// The index of each image in its atlas is its index in the images of the atlas, so it doesn't
+/// change when the atlas is packed again, and [`TextureAtlasPacker::texture_index`] finds it from
+/// the handle of the image, such as the one returned by [`AssetServer::get_handle`] for its path.
+///
+/// The packer keeps strong handles to the images, so that it can pack them again.
+#[derive(Resource, Default)]
+pub struct TextureAtlasPacker {
+    atlases: Vec<PackedTextureAtlas>,
+}
+
+impl TextureAtlasPacker {
+    /// Adds an atlas of the `textures`, in this order, with the settings of the `builder`.
+    pub fn add(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        textures: impl IntoIterator<Item = Handle<Image>>,
+        builder: TextureAtlasBuilder,
+    ) -> Handle<TextureAtlas> {
+        let mut unique_textures = HashSet::default();
+        let textures: Vec<Handle<Image>> = textures
+            .into_iter()
+            .filter(|texture| unique_textures.insert(texture.id()))
+            .collect();
+        // The sprites of the atlas are empty until it's packed
+        let atlas = texture_atlases.add(TextureAtlas {
+            texture: Handle::default(),
+            size: Vec2::ONE,
+            textures: vec![Rect::default(); textures.len()],
+            texture_handles: None,
+        });
+        self.atlases.push(PackedTextureAtlas {
+            atlas: atlas.clone(),
+            textures,
+            builder: builder.with_same_settings(),
+            dirty: true,
+        });
+        atlas
+    }
+
+    /// Loads the images of a folder with [`AssetServer::load_folder`] and adds an atlas of them,
+    /// sorted by their paths, with the settings of the `builder`.
+    ///
+    /// # Errors
+    ///
+    /// If the folder can't be loaded.
+    pub fn add_folder<P: AsRef<Path>>(
+        &mut self,
+        asset_server: &AssetServer,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        path: P,
+        builder: TextureAtlasBuilder,
+    ) -> Result<Handle<TextureAtlas>, AssetServerError> {
+        let mut textures: Vec<Handle<Image>> = asset_server
+            .load_folder(path)?
+            .into_iter()
+            .map(|handle| handle.typed())
+            .collect();
+        textures.sort_by_cached_key(|texture| {
+            asset_server
+                .get_handle_path(texture)
+                .map(|path| path.path().to_path_buf())
+        });
+        Ok(self.add(texture_atlases, textures, builder))
+    }
+
+    /// The index of an image in an atlas, available before the atlas is packed.
+    pub fn texture_index(
+        &self,
+        atlas: &Handle<TextureAtlas>,
+        texture: &Handle<Image>,
+    ) -> Option<usize> {
+        self.atlases
+            .iter()
+            .find(|packed| packed.atlas == *atlas)?
+            .textures
+            .iter()
+            .position(|packed_texture| packed_texture == texture)
+    }
+
+    /// Whether the atlas was packed, once all its images were loaded.
+    pub fn is_packed(&self, atlas: &Handle<TextureAtlas>) -> bool {
+        self.atlases
+            .iter()
+            .any(|packed| packed.atlas == *atlas && !packed.dirty)
+    }
+
+    /// Stops packing the atlas, keeping its last packing.
+    pub fn remove(&mut self, atlas: &Handle<TextureAtlas>) {
+        self.atlases.retain(|packed| packed.atlas != *atlas);
+    }
+}
+
+/// Packs the atlases of the [`TextureAtlasPacker`] whose images are all loaded, when they are new
+/// or any of their images was modified.
+pub fn pack_texture_atlases(
+    mut packer: ResMut<TextureAtlasPacker>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let modified_images: HashSet<HandleId> = image_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+
+    for packed in &mut packer.atlases {
+        if packed
+            .textures
+            .iter()
+            .any(|texture| modified_images.contains(&texture.id()))
+        {
+            packed.dirty = true;
+        }
+        if !packed.dirty
+            || packed
+                .textures
+                .iter()
+                .any(|texture| images.get(texture).is_none())
+        {
+            continue;
+        }
+        packed.dirty = false;
+
+        let mut builder = packed.builder.with_same_settings();
+        for texture in &packed.textures {
+            builder.add_texture(texture.clone(), images.get(texture).unwrap());
+        }
+        let (atlas_texture, texture_rects, texture_handles) = match builder.pack(&images) {
+            Ok(packing) => packing,
+            Err(err) => {
+                error!("Failed to pack a texture atlas: {}", err);
+                continue;
+            }
+        };
+        let atlas = match texture_atlases.get_mut(&packed.atlas) {
+            Some(atlas) => atlas,
+            None => continue,
+        };
+        atlas.size = Vec2::new(
+            atlas_texture.texture_descriptor.size.width as f32,
+            atlas_texture.texture_descriptor.size.height as f32,
+        );
+        atlas.textures = texture_rects;
+        atlas.texture_handles = Some(texture_handles);
+        // The texture of the atlas is replaced when it's packed again
+        match images.get_mut(&atlas.texture) {
+            Some(texture) if atlas.texture != Handle::default() => *texture = atlas_texture,
+            _ => atlas.texture = images.add(atlas_texture),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{FromReflect, Reflect};
+
+/// How the image of a [`Sprite`](crate::Sprite), a [`TextureAtlasSprite`](crate::TextureAtlasSprite)
+/// or a UI image is scaled to its size. Without this component, the image is stretched.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub enum ImageScaleMode {
+    /// The whole image is stretched to the size.
+    #[default]
+    Stretched,
+    /// The image is sliced in 9 parts by the borders of the [`TextureSlicer`], so that its
+    /// corners keep their size, such as for scalable panels.
+    Sliced(TextureSlicer),
+    /// The image is repeated along the tiled axes, and stretched along the others. Each tile is
+    /// `stretch_value` times the size of the image, and the last tiles are cropped.
+    Tiled {
+        tile_x: bool,
+        tile_y: bool,
+        stretch_value: f32,
+    },
+}
+
+/// The widths of the borders of a [`TextureSlicer`], in pixels of the image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, FromReflect)]
+pub struct BorderRect {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl BorderRect {
+    /// Borders of the same width on every side.
+    pub const fn square(width: f32) -> Self {
+        Self {
+            left: width,
+            right: width,
+            top: width,
+            bottom: width,
+        }
+    }
+
+    /// Borders of the `horizontal` width on the left and right sides, and of the `vertical` one
+    /// on the top and bottom sides.
+    pub const fn rectangle(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        }
+    }
+}
+
+/// How the sides or the center of a [`TextureSlicer`] fill their part of the size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, FromReflect)]
+pub enum SliceScaleMode {
+    /// The slice is stretched.
+    #[default]
+    Stretch,
+    /// The slice is repeated, each tile being `stretch_value` times the size of the slice in the
+    /// image, and the last tile is cropped.
+    Tile { stretch_value: f32 },
+}
+
+/// Slices an image in 9 parts with the widths of its borders: the 4 corners keep their size, the
+/// 4 sides fill the size along their length, and the center fills the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct TextureSlicer {
+    pub border: BorderRect,
+    pub center_scale_mode: SliceScaleMode,
+    pub sides_scale_mode: SliceScaleMode,
+    /// The largest scale of the corners. They are scaled down when the size is smaller than the
+    /// image, and scaled up to this scale when it's larger.
+    pub max_corner_scale: f32,
+}
+
+impl Default for TextureSlicer {
+    fn default() -> Self {
+        Self {
+            border: BorderRect::default(),
+            center_scale_mode: SliceScaleMode::Stretch,
+            sides_scale_mode: SliceScaleMode::Stretch,
+            max_corner_scale: 1.0,
+        }
+    }
+}
+
+/// A part of an image drawn by an [`ImageScaleMode`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureSlice {
+    /// The part of the image, in pixels.
+    pub texture_rect: Rect,
+    /// The size the part is drawn at.
+    pub draw_size: Vec2,
+    /// The center of the part from the center of the whole size, with Y going up.
+    pub offset: Vec2,
+}
+
+impl TextureSlice {
+    /// Repeats the slice along the tiled axes, with tiles `stretch_value` times the size of its
+    /// texture rect, cropping the last ones. The tiles start from the top left corner.
+    fn tiled(self, stretch_value: f32, tile_x: bool, tile_y: bool) -> Vec<TextureSlice> {
+        let tile_size = self.texture_rect.size() * stretch_value.max(0.001);
+        let draw_min = self.offset - self.draw_size / 2.0;
+        let columns = tile_spans(self.draw_size.x, tile_size.x, tile_x);
+        let rows = tile_spans(self.draw_size.y, tile_size.y, tile_y);
+
+        let mut tiles = Vec::with_capacity(columns.len() * rows.len());
+        for &(y, height, tile_height) in &rows {
+            for &(x, width, tile_width) in &columns {
+                // The Y of the image goes down, from the top of the slice
+                let texture_min = self.texture_rect.min;
+                let texture_size = self.texture_rect.size() * Vec2::new(tile_width, tile_height);
+                tiles.push(TextureSlice {
+                    texture_rect: Rect::from_corners(texture_min, texture_min + texture_size),
+                    draw_size: Vec2::new(width, height),
+                    offset: Vec2::new(
+                        draw_min.x + x + width / 2.0,
+                        draw_min.y + self.draw_size.y - y - height / 2.0,
+                    ),
+                });
+            }
+        }
+        tiles
+    }
+
+    fn scaled(self, scale_mode: SliceScaleMode, tile_x: bool, tile_y: bool) -> Vec<TextureSlice> {
+        match scale_mode {
+            SliceScaleMode::Stretch => vec![self],
+            SliceScaleMode::Tile { stretch_value } => self.tiled(stretch_value, tile_x, tile_y),
+        }
+    }
+}
+
+/// Splits a length of `draw_size` in tiles of `tile_size` if `tiled`, returning the start and the
+/// size of each tile, and the fraction of the tile that isn't cropped.
+fn tile_spans(draw_size: f32, tile_size: f32, tiled: bool) -> Vec<(f32, f32, f32)> {
+    if !tiled || tile_size <= 0.0 {
+        return vec![(0.0, draw_size, 1.0)];
+    }
+    let count = (draw_size / tile_size).ceil().max(1.0) as usize;
+    (0..count)
+        .map(|i| {
+            let start = i as f32 * tile_size;
+            let size = tile_size.min(draw_size - start);
+            (start, size, size / tile_size)
+        })
+        .collect()
+}
+
+impl TextureSlicer {
+    /// Slices the `rect` of an image drawn at `render_size`.
+    pub fn compute_slices(&self, rect: Rect, render_size: Vec2) -> Vec<TextureSlice> {
+        let corner_scale = (render_size / rect.size())
+            .min_element()
+            .min(self.max_corner_scale);
+        let border = self.border;
+        let half_size = render_size / 2.0;
+
+        // The bounds of the columns in the image and in the drawn size, from the left
+        let texture_x = [
+            rect.min.x,
+            rect.min.x + border.left,
+            rect.max.x - border.right,
+            rect.max.x,
+        ];
+        let draw_x = [
+            -half_size.x,
+            -half_size.x + border.left * corner_scale,
+            half_size.x - border.right * corner_scale,
+            half_size.x,
+        ];
+        // The bounds of the rows, from the top, where the Y of the image goes down
+        let texture_y = [
+            rect.min.y,
+            rect.min.y + border.top,
+            rect.max.y - border.bottom,
+            rect.max.y,
+        ];
+        let draw_y = [
+            half_size.y,
+            half_size.y - border.top * corner_scale,
+            -half_size.y + border.bottom * corner_scale,
+            -half_size.y,
+        ];
+
+        let mut slices = Vec::new();
+        for row in 0..3 {
+            for column in 0..3 {
+                let slice = TextureSlice {
+                    texture_rect: Rect::new(
+                        texture_x[column],
+                        texture_y[row],
+                        texture_x[column + 1],
+                        texture_y[row + 1],
+                    ),
+                    draw_size: Vec2::new(
+                        draw_x[column + 1] - draw_x[column],
+                        draw_y[row] - draw_y[row + 1],
+                    ),
+                    offset: Vec2::new(
+                        draw_x[column] + draw_x[column + 1],
+                        draw_y[row] + draw_y[row + 1],
+                    ) / 2.0,
+                };
+                if slice.draw_size.cmple(Vec2::ZERO).any()
+                    || slice.texture_rect.size().cmple(Vec2::ZERO).any()
+                {
+                    continue;
+                }
+                match (column == 1, row == 1) {
+                    (true, true) => {
+                        slices.extend(slice.scaled(self.center_scale_mode, true, true));
+                    }
+                    (true, false) => {
+                        slices.extend(slice.scaled(self.sides_scale_mode, true, false));
+                    }
+                    (false, true) => {
+                        slices.extend(slice.scaled(self.sides_scale_mode, false, true));
+                    }
+                    (false, false) => slices.push(slice),
+                }
+            }
+        }
+        slices
+    }
+}
+
+impl ImageScaleMode {
+    /// The parts of the `rect` of an image drawn at `render_size`.
+    pub fn compute_slices(&self, rect: Rect, render_size: Vec2) -> Vec<TextureSlice> {
+        let whole = TextureSlice {
+            texture_rect: rect,
+            draw_size: render_size,
+            offset: Vec2::ZERO,
+        };
+        match *self {
+            ImageScaleMode::Stretched => vec![whole],
+            ImageScaleMode::Sliced(slicer) => slicer.compute_slices(rect, render_size),
+            ImageScaleMode::Tiled {
+                tile_x,
+                tile_y,
+                stretch_value,
+            } => {
+                if rect.size().cmple(Vec2::ZERO).any() || render_size.cmple(Vec2::ZERO).any() {
+                    return Vec::new();
+                }
+                whole.tiled(stretch_value, tile_x, tile_y)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nine_slices() {
+        let slicer = TextureSlicer {
+            border: BorderRect::square(4.0),
+            ..Default::default()
+        };
+        let slices = slicer.compute_slices(Rect::new(0.0, 0.0, 16.0, 16.0), Vec2::new(32.0, 24.0));
+        assert_eq!(slices.len(), 9);
+        // The top left corner keeps its size
+        assert_eq!(slices[0].texture_rect, Rect::new(0.0, 0.0, 4.0, 4.0));
+        assert_eq!(slices[0].draw_size, Vec2::new(4.0, 4.0));
+        assert_eq!(slices[0].offset, Vec2::new(-14.0, 10.0));
+        // The center fills the rest
+        assert_eq!(slices[4].texture_rect, Rect::new(4.0, 4.0, 12.0, 12.0));
+        assert_eq!(slices[4].draw_size, Vec2::new(24.0, 16.0));
+        assert_eq!(slices[4].offset, Vec2::ZERO);
+
+        // The corners are scaled down when the size is smaller than the image
+        let slices = slicer.compute_slices(Rect::new(0.0, 0.0, 16.0, 16.0), Vec2::new(8.0, 16.0));
+        assert_eq!(slices[0].draw_size, Vec2::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn tiles() {
+        let mode = ImageScaleMode::Tiled {
+            tile_x: true,
+            tile_y: false,
+            stretch_value: 1.0,
+        };
+        let tiles = mode.compute_slices(Rect::new(0.0, 0.0, 8.0, 8.0), Vec2::new(20.0, 4.0));
+        assert_eq!(tiles.len(), 3);
+        assert_eq!(tiles[0].offset, Vec2::new(-6.0, 0.0));
+        assert_eq!(tiles[0].draw_size, Vec2::new(8.0, 4.0));
+        // The last tile is cropped
+        assert_eq!(tiles[2].texture_rect, Rect::new(0.0, 0.0, 4.0, 8.0));
+        assert_eq!(tiles[2].draw_size, Vec2::new(4.0, 4.0));
+        assert_eq!(tiles[2].offset, Vec2::new(8.0, 0.0));
+    }
+}
@@ -10,3 +10,22 @@ pub enum TextError {
     #[error("exceeded {0:?} available TextAltases for font. This can be caused by using an excessive number of font sizes. If you are changing font sizes dynamically consider using Transform::scale to modify the size. If you need more font sizes modify TextSettings.max_font_atlases." )]
     ExceedMaxTextAtlases(usize),
 }
+
+/// An error parsing the markup of a [`TextMarkup`](crate::TextMarkup).
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum TextMarkupError {
+    #[error("unknown tag `{0}`")]
+    UnknownTag(String),
+    #[error("invalid value `{value}` for tag `{tag}`")]
+    InvalidValue { tag: String, value: String },
+    #[error("unknown font `{0}`")]
+    UnknownFont(String),
+    #[error("unknown icon `{0}`")]
+    UnknownIcon(String),
+    #[error("closing tag `{0}` doesn't match the last opened tag")]
+    UnexpectedClosingTag(String),
+    #[error("tag `{0}` is never closed")]
+    UnclosedTag(String),
+    #[error("a tag is missing its closing `]`")]
+    UnterminatedTag,
+}
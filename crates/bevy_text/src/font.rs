@@ -1,4 +1,4 @@
-use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use ab_glyph::{Font as _, FontArc, FontVec, InvalidFont, OutlinedGlyph, PxScale, ScaleFont};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     render_resource::{Extent3d, TextureDimension, TextureFormat},
@@ -18,6 +18,29 @@ impl Font {
         Ok(Font { font })
     }
 
+    /// The width of a single line of text at a font size, from the advances and kerning of its
+    /// glyphs, including the whitespace that isn't drawn
+    pub fn text_width(&self, text: &str, font_size: f32) -> f32 {
+        let font = self.font.as_scaled(PxScale::from(font_size));
+        let mut width = 0.0;
+        let mut previous = None;
+        for character in text.chars() {
+            let glyph_id = font.glyph_id(character);
+            if let Some(previous) = previous {
+                width += font.kern(previous, glyph_id);
+            }
+            width += font.h_advance(glyph_id);
+            previous = Some(glyph_id);
+        }
+        width
+    }
+
+    /// The height of a line of text at a font size, from the ascent to the descent of the font
+    pub fn line_height(&self, font_size: f32) -> f32 {
+        let font = self.font.as_scaled(PxScale::from(font_size));
+        font.ascent() - font.descent()
+    }
+
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Image {
         let bounds = outlined_glyph.px_bounds();
         let width = bounds.width() as usize;
@@ -0,0 +1,172 @@
+use std::path::{Path, PathBuf};
+
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::system::Resource;
+use bevy_log::warn;
+use bevy_utils::HashMap;
+
+use crate::Font;
+
+/// The fonts drawing the glyphs missing from the fonts of the texts, such as CJK characters or
+/// symbols, tried in order.
+///
+/// The fallbacks which aren't loaded yet are skipped, and the texts are laid out again once they
+/// are loaded. The glyphs missing from every font are drawn with the font of their text.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct FontFallbacks {
+    /// The fallbacks of the fonts without their own ones
+    pub fallbacks: Vec<Handle<Font>>,
+    /// The fallbacks of specific fonts, replacing the default ones for these fonts
+    pub font_fallbacks: HashMap<Handle<Font>, Vec<Handle<Font>>>,
+}
+
+impl FontFallbacks {
+    /// Adds a fallback, after the other ones, for the fonts without their own fallbacks.
+    pub fn with_fallback(mut self, fallback: Handle<Font>) -> Self {
+        self.fallbacks.push(fallback);
+        self
+    }
+
+    /// Sets the fallbacks of a font, replacing the default ones for this font.
+    pub fn with_font_fallbacks(
+        mut self,
+        font: Handle<Font>,
+        fallbacks: impl IntoIterator<Item = Handle<Font>>,
+    ) -> Self {
+        self.font_fallbacks
+            .insert(font, fallbacks.into_iter().collect());
+        self
+    }
+
+    /// The fallbacks of a font, in order.
+    pub fn fallbacks_of(&self, font: &Handle<Font>) -> &[Handle<Font>] {
+        self.font_fallbacks.get(font).unwrap_or(&self.fallbacks)
+    }
+
+    /// Whether the font is a fallback of any font.
+    pub fn contains(&self, font: &Handle<Font>) -> bool {
+        self.fallbacks.contains(font)
+            || self
+                .font_fallbacks
+                .values()
+                .any(|fallbacks| fallbacks.contains(font))
+    }
+
+    /// Whether a fallback was loaded or modified by one of the `font_events`, so that the texts
+    /// have to be laid out again.
+    pub fn any_loaded<'a>(&self, font_events: impl Iterator<Item = &'a AssetEvent<Font>>) -> bool {
+        font_events.fold(false, |loaded, event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                loaded || self.contains(handle)
+            }
+            AssetEvent::Removed { .. } => loaded,
+        })
+    }
+}
+
+/// The fonts installed on the system, found in its usual font directories, to load some of them
+/// as [`FontFallbacks`] without shipping them.
+///
+/// The fonts are looked up by the name of their file, without its extension, such as
+/// `NotoSansCJK-Regular` or `seguisym`.
+#[derive(Clone, Debug, Default)]
+pub struct SystemFonts {
+    paths: Vec<PathBuf>,
+}
+
+impl SystemFonts {
+    /// Finds the `.ttf`, `.otf` and `.ttc` fonts in the font directories of the system.
+    pub fn scan() -> Self {
+        let mut paths = Vec::new();
+        for directory in system_font_directories() {
+            collect_fonts(&directory, &mut paths);
+        }
+        paths.sort();
+        Self { paths }
+    }
+
+    /// The paths of the fonts found on the system.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// The path of the font whose file is named `name`, ignoring its case and extension.
+    pub fn find(&self, name: &str) -> Option<&Path> {
+        self.paths
+            .iter()
+            .find(|path| {
+                path.file_stem().map_or(false, |stem| {
+                    stem.to_string_lossy().eq_ignore_ascii_case(name)
+                })
+            })
+            .map(PathBuf::as_path)
+    }
+
+    /// Loads the font whose file is named `name`, if it's found on the system.
+    pub fn load(&self, name: &str, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        let path = self.find(name)?;
+        let font = std::fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| Font::try_from_bytes(bytes).map_err(|err| err.to_string()));
+        match font {
+            Ok(font) => Some(fonts.add(font)),
+            Err(err) => {
+                warn!("Failed to load the system font {:?}: {}", path, err);
+                None
+            }
+        }
+    }
+
+    /// Loads the first font of the `names` found on the system, such as fonts of a script on
+    /// different platforms.
+    pub fn load_first<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+        fonts: &mut Assets<Font>,
+    ) -> Option<Handle<Font>> {
+        names.into_iter().find_map(|name| self.load(name, fonts))
+    }
+}
+
+fn system_font_directories() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut directories = Vec::new();
+    if cfg!(target_os = "windows") {
+        if let Some(windows) = std::env::var_os("WINDIR") {
+            directories.push(PathBuf::from(windows).join("Fonts"));
+        }
+        if let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") {
+            directories.push(PathBuf::from(local_app_data).join("Microsoft/Windows/Fonts"));
+        }
+    } else if cfg!(target_os = "macos") {
+        directories.push(PathBuf::from("/System/Library/Fonts"));
+        directories.push(PathBuf::from("/Library/Fonts"));
+        directories.extend(home.map(|home| home.join("Library/Fonts")));
+    } else {
+        directories.push(PathBuf::from("/usr/share/fonts"));
+        directories.push(PathBuf::from("/usr/local/share/fonts"));
+        if let Some(home) = home {
+            directories.push(home.join(".local/share/fonts"));
+            directories.push(home.join(".fonts"));
+        }
+    }
+    directories
+}
+
+fn collect_fonts(directory: &Path, paths: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_fonts(&path, paths);
+        } else if path.extension().map_or(false, |extension| {
+            ["ttf", "otf", "ttc"]
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        }) {
+            paths.push(path);
+        }
+    }
+}
@@ -2,34 +2,46 @@ mod error;
 mod font;
 mod font_atlas;
 mod font_atlas_set;
+mod font_fallback;
 mod font_loader;
 mod glyph_brush;
 mod pipeline;
+mod rich_text;
 mod text;
 mod text2d;
+mod text3d;
 
 pub use error::*;
 pub use font::*;
 pub use font_atlas::*;
 pub use font_atlas_set::*;
+pub use font_fallback::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
 pub use pipeline::*;
+pub use rich_text::*;
 pub use text::*;
 pub use text2d::*;
+pub use text3d::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        Font, HorizontalAlign, Text, Text2dBundle, TextAlignment, TextError, TextSection,
-        TextStyle, VerticalAlign,
+        Font, FontFallbacks, HorizontalAlign, Text, Text2dBundle, Text3d, Text3dBundle,
+        Text3dOrientation, TextAlignment, TextBuilder, TextError, TextMarkup, TextOutline,
+        TextSection, TextShadow, TextStyle, VerticalAlign,
     };
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{load_internal_asset, AddAsset};
+use bevy_core_pipeline::core_3d::Transparent3d;
 use bevy_ecs::{schedule::IntoSystemDescriptor, system::Resource};
-use bevy_render::{RenderApp, RenderStage};
+use bevy_render::{
+    render_phase::AddRenderCommand,
+    render_resource::{Shader, SpecializedRenderPipelines},
+    RenderApp, RenderStage,
+};
 use bevy_sprite::SpriteSystem;
 use bevy_window::ModifiesWindows;
 use std::num::NonZeroUsize;
@@ -66,28 +78,46 @@ pub enum YAxisOrientation {
 
 impl Plugin for TextPlugin {
     fn build(&self, app: &mut App) {
+        load_internal_asset!(app, TEXT_3D_SHADER_HANDLE, "text3d.wgsl", Shader::from_wgsl);
+
         app.add_asset::<Font>()
             .add_asset::<FontAtlasSet>()
             .register_type::<Text>()
             .register_type::<TextSection>()
             .register_type::<Vec<TextSection>>()
             .register_type::<TextStyle>()
+            .register_type::<TextOutline>()
+            .register_type::<TextShadow>()
             .register_type::<TextAlignment>()
             .register_type::<VerticalAlign>()
             .register_type::<HorizontalAlign>()
+            .register_type::<Text3d>()
+            .register_type::<Text3dOrientation>()
             .init_asset_loader::<FontLoader>()
             .init_resource::<TextSettings>()
+            .init_resource::<FontFallbacks>()
             .insert_resource(TextPipeline::default())
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 update_text2d_layout.after(ModifiesWindows),
-            );
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, update_text3d_layout);
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
-            render_app.add_system_to_stage(
-                RenderStage::Extract,
-                extract_text2d_sprite.after(SpriteSystem::ExtractSprites),
-            );
+            render_app
+                .init_resource::<Text3dPipeline>()
+                .init_resource::<SpecializedRenderPipelines<Text3dPipeline>>()
+                .init_resource::<Text3dMeta>()
+                .init_resource::<ExtractedTexts3d>()
+                .init_resource::<Text3dImageBindGroups>()
+                .add_render_command::<Transparent3d, DrawText3d>()
+                .add_system_to_stage(
+                    RenderStage::Extract,
+                    extract_text2d_sprite.after(SpriteSystem::ExtractSprites),
+                )
+                .add_system_to_stage(RenderStage::Extract, extract_text3d)
+                .add_system_to_stage(RenderStage::Prepare, prepare_text3d)
+                .add_system_to_stage(RenderStage::Queue, queue_text3d);
         }
     }
 }
@@ -1,4 +1,4 @@
-use ab_glyph::{PxScale, ScaleFont};
+use ab_glyph::{Font as _, PxScale, ScaleFont};
 use bevy_asset::{Assets, Handle, HandleId};
 use bevy_ecs::component::Component;
 use bevy_ecs::system::Resource;
@@ -10,8 +10,8 @@ use bevy_utils::HashMap;
 use glyph_brush_layout::{FontId, SectionText};
 
 use crate::{
-    error::TextError, glyph_brush::GlyphBrush, scale_value, Font, FontAtlasSet, PositionedGlyph,
-    TextAlignment, TextSection, TextSettings, YAxisOrientation,
+    error::TextError, glyph_brush::GlyphBrush, scale_value, Font, FontAtlasSet, FontFallbacks,
+    PositionedGlyph, TextAlignment, TextSection, TextSettings, YAxisOrientation,
 };
 
 #[derive(Default, Resource)]
@@ -26,9 +26,32 @@ pub struct TextPipeline {
 #[derive(Component, Clone, Default, Debug)]
 pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
+    /// The inline images of the sections with an icon
+    pub icons: Vec<PositionedIcon>,
     pub size: Vec2,
 }
 
+/// The layout of the icon of a [`TextSection`], positioned like the glyphs.
+#[derive(Debug, Clone)]
+pub struct PositionedIcon {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub section_index: usize,
+}
+
+/// A part of the text of a [`TextSection`] drawn with one of its fonts.
+struct FontRun<'a> {
+    section_index: usize,
+    /// The index of the text of the run in the value of its section
+    byte_offset: usize,
+    font_handle: &'a Handle<Font>,
+    font: &'a Font,
+    text: &'a str,
+}
+
+/// Lays out the icons, wrapped with the text without breaking lines around them.
+const ICON_PLACEHOLDER: char = '\u{a0}';
+
 impl TextPipeline {
     pub fn get_or_insert_font_id(&mut self, handle: &Handle<Font>, font: &Font) -> FontId {
         let brush = &mut self.brush;
@@ -50,33 +73,114 @@ impl TextPipeline {
         texture_atlases: &mut Assets<TextureAtlas>,
         textures: &mut Assets<Image>,
         text_settings: &TextSettings,
+        font_fallbacks: &FontFallbacks,
         y_axis_orientation: YAxisOrientation,
     ) -> Result<TextLayoutInfo, TextError> {
-        let mut scaled_fonts = Vec::new();
-        let sections = sections
+        // The icons are laid out as the no-break spaces taking their room
+        let icon_placeholders = sections
             .iter()
             .map(|section| {
+                if section.icon.is_none() {
+                    return Ok(None);
+                }
                 let font = fonts
                     .get(&section.style.font)
                     .ok_or(TextError::NoSuchFont)?;
-                let font_id = self.get_or_insert_font_id(&section.style.font, font);
                 let font_size = scale_value(section.style.font_size, scale_factor);
+                let scaled_font = ab_glyph::Font::as_scaled(&font.font, font_size);
+                let icon_size = scaled_font.ascent() - scaled_font.descent();
+                let space_advance = scaled_font.h_advance(scaled_font.glyph_id(ICON_PLACEHOLDER));
+                let space_count = if space_advance > 0.0 {
+                    (icon_size / space_advance).ceil().max(1.0) as usize
+                } else {
+                    1
+                };
+                Ok(Some(ICON_PLACEHOLDER.to_string().repeat(space_count)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-                scaled_fonts.push(ab_glyph::Font::as_scaled(&font.font, font_size));
+        // The text of each section is split into runs of the first font of its fallbacks which
+        // has their glyphs
+        let mut runs = Vec::new();
+        for (section_index, (section, icon_placeholder)) in
+            sections.iter().zip(&icon_placeholders).enumerate()
+        {
+            let font = fonts
+                .get(&section.style.font)
+                .ok_or(TextError::NoSuchFont)?;
+            let run = |font_handle, font, byte_range: std::ops::Range<usize>| FontRun {
+                section_index,
+                byte_offset: byte_range.start,
+                font_handle,
+                font,
+                text: &section.value[byte_range],
+            };
+            let fallbacks = font_fallbacks.fallbacks_of(&section.style.font);
+            if let Some(icon_placeholder) = icon_placeholder {
+                runs.push(FontRun {
+                    text: icon_placeholder,
+                    ..run(&section.style.font, font, 0..0)
+                });
+                continue;
+            }
+            if fallbacks.is_empty() {
+                runs.push(run(&section.style.font, font, 0..section.value.len()));
+                continue;
+            }
 
-                let section = SectionText {
-                    font_id,
-                    scale: PxScale::from(font_size),
-                    text: &section.value,
+            let chain: Vec<(&Handle<Font>, &Font)> = std::iter::once((&section.style.font, font))
+                .chain(fallbacks.iter().filter_map(|fallback| {
+                    fonts
+                        .get(fallback)
+                        .map(|fallback_font| (fallback, fallback_font))
+                }))
+                .collect();
+            let mut run_start = 0;
+            let mut run_font = 0;
+            for (byte_index, character) in section.value.char_indices() {
+                // The spaces stay in the run they are in, as fonts may not have glyphs for them
+                let font_index = if character.is_whitespace() || character.is_control() {
+                    run_font
+                } else {
+                    chain
+                        .iter()
+                        .position(|(_, font)| font.font.glyph_id(character).0 != 0)
+                        .unwrap_or(0)
                 };
+                if font_index != run_font {
+                    if byte_index > run_start {
+                        let (font_handle, font) = chain[run_font];
+                        runs.push(run(font_handle, font, run_start..byte_index));
+                    }
+                    run_start = byte_index;
+                    run_font = font_index;
+                }
+            }
+            let (font_handle, font) = chain[run_font];
+            runs.push(run(font_handle, font, run_start..section.value.len()));
+        }
+
+        let mut scaled_fonts = Vec::new();
+        let section_texts = runs
+            .iter()
+            .map(|run| {
+                let font_id = self.get_or_insert_font_id(run.font_handle, run.font);
+                let font_size =
+                    scale_value(sections[run.section_index].style.font_size, scale_factor);
+
+                scaled_fonts.push(ab_glyph::Font::as_scaled(&run.font.font, font_size));
 
-                Ok(section)
+                SectionText {
+                    font_id,
+                    scale: PxScale::from(font_size),
+                    text: run.text,
+                }
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Vec<_>>();
 
         let section_glyphs = self
             .brush
-            .compute_glyphs(&sections, bounds, text_alignment)?;
+            .compute_glyphs(&section_texts, bounds, text_alignment)?;
 
         if section_glyphs.is_empty() {
             return Ok(TextLayoutInfo::default());
@@ -98,9 +202,39 @@ impl TextPipeline {
 
         let size = Vec2::new(max_x - min_x, max_y - min_y);
 
-        let glyphs = self.brush.process_glyphs(
+        let mut icons = Vec::new();
+        for (run_index, run) in runs.iter().enumerate() {
+            if icon_placeholders[run.section_index].is_none() {
+                continue;
+            }
+            let mut placeholder_glyphs = section_glyphs
+                .iter()
+                .filter(|sg| sg.section_index == run_index);
+            let (first, last) = match placeholder_glyphs.next() {
+                Some(first) => (first, placeholder_glyphs.next_back().unwrap_or(first)),
+                None => continue,
+            };
+            let scaled_font = scaled_fonts[run_index];
+            let icon_size = scaled_font.ascent() - scaled_font.descent();
+            let room = last.glyph.position.x + scaled_font.h_advance(last.glyph.id)
+                - first.glyph.position.x;
+            let x = first.glyph.position.x + room / 2.0 - min_x.floor();
+            let center_y =
+                first.glyph.position.y - (scaled_font.ascent() + scaled_font.descent()) / 2.0;
+            let y = match y_axis_orientation {
+                YAxisOrientation::BottomToTop => max_y.floor() - center_y,
+                YAxisOrientation::TopToBottom => center_y - min_y.floor(),
+            };
+            icons.push(PositionedIcon {
+                position: Vec2::new(x, y),
+                size: Vec2::splat(icon_size),
+                section_index: run.section_index,
+            });
+        }
+
+        let mut glyphs = self.brush.process_glyphs(
             section_glyphs,
-            &sections,
+            &section_texts,
             font_atlas_set_storage,
             fonts,
             texture_atlases,
@@ -109,6 +243,17 @@ impl TextPipeline {
             y_axis_orientation,
         )?;
 
-        Ok(TextLayoutInfo { glyphs, size })
+        // The glyphs refer to their sections rather than to their runs
+        for glyph in &mut glyphs {
+            let run = &runs[glyph.section_index];
+            glyph.section_index = run.section_index;
+            glyph.byte_index += run.byte_offset;
+        }
+
+        Ok(TextLayoutInfo {
+            glyphs,
+            icons,
+            size,
+        })
     }
 }
@@ -0,0 +1,305 @@
+use bevy_asset::Handle;
+use bevy_render::{color::Color, texture::Image};
+use bevy_utils::HashMap;
+
+use crate::{Font, Text, TextAlignment, TextMarkupError, TextSection, TextStyle};
+
+/// Builds a [`Text`] span by span, each span taking the current style of the builder.
+///
+/// The styles are pushed on a stack, so that they can be nested and popped back.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_render::{color::Color, texture::Image};
+/// # use bevy_text::{Font, TextBuilder, TextStyle};
+/// #
+/// # let font_handle: Handle<Font> = Default::default();
+/// # let coin_handle: Handle<Image> = Default::default();
+/// #
+/// let text = TextBuilder::new(TextStyle {
+///     font: font_handle,
+///     font_size: 30.0,
+///     color: Color::WHITE,
+///     ..Default::default()
+/// })
+/// .span("You found ")
+/// .push_color(Color::GOLD)
+/// .span("100 gold ")
+/// .icon(coin_handle)
+/// .pop_style()
+/// .span("!")
+/// .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextBuilder {
+    sections: Vec<TextSection>,
+    /// The stack of styles, whose first one is never popped
+    styles: Vec<TextStyle>,
+    alignment: TextAlignment,
+}
+
+impl TextBuilder {
+    /// A builder of a [`Text`] whose spans are in the `style` until another one is pushed.
+    pub fn new(style: TextStyle) -> Self {
+        Self {
+            sections: Vec::new(),
+            styles: vec![style],
+            alignment: TextAlignment::default(),
+        }
+    }
+
+    /// The style of the next spans.
+    pub fn style(&self) -> &TextStyle {
+        self.styles.last().unwrap()
+    }
+
+    /// Pushes a copy of the current style, modified by `f`, for the next spans.
+    pub fn push_style(mut self, f: impl FnOnce(&mut TextStyle)) -> Self {
+        let mut style = self.style().clone();
+        f(&mut style);
+        self.styles.push(style);
+        self
+    }
+
+    /// Pushes the current style with another color.
+    pub fn push_color(self, color: Color) -> Self {
+        self.push_style(|style| style.color = color)
+    }
+
+    /// Pushes the current style with another font.
+    pub fn push_font(self, font: Handle<Font>) -> Self {
+        self.push_style(|style| style.font = font)
+    }
+
+    /// Pushes the current style with another font size.
+    pub fn push_font_size(self, font_size: f32) -> Self {
+        self.push_style(|style| style.font_size = font_size)
+    }
+
+    /// Goes back to the style before the last pushed one. The first style is never popped.
+    pub fn pop_style(mut self) -> Self {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+        self
+    }
+
+    /// Adds text in the current style, to the last span if it's in the same style.
+    pub fn span(mut self, value: impl Into<String>) -> Self {
+        let value = value.into();
+        if value.is_empty() {
+            return self;
+        }
+        let style = self.styles.last().unwrap();
+        match self.sections.last_mut() {
+            Some(section) if section.icon.is_none() && section.style == *style => {
+                section.value.push_str(&value);
+            }
+            _ => self.sections.push(TextSection::new(value, style.clone())),
+        }
+        self
+    }
+
+    /// Adds an image drawn inline, as high as a line in the current style.
+    pub fn icon(mut self, icon: Handle<Image>) -> Self {
+        let style = self.style().clone();
+        self.sections.push(TextSection::from_icon(icon, style));
+        self
+    }
+
+    /// Sets the [`TextAlignment`] of the text.
+    pub fn with_alignment(mut self, alignment: TextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Builds the [`Text`] of the spans.
+    pub fn build(self) -> Text {
+        Text::from_sections(self.sections).with_alignment(self.alignment)
+    }
+}
+
+/// Parses [`Text`]s from a markup, with tags changing the style of the text between them, and
+/// adding icons.
+///
+/// - `[color=#ff8000]...[/color]` colors the text, with a hex color or a color named with
+///   [`TextMarkup::with_color`]
+/// - `[size=40]...[/size]` sets the font size
+/// - `[font=bold]...[/font]` sets a font named with [`TextMarkup::with_font`]
+/// - `[icon=coin]` adds an icon named with [`TextMarkup::with_icon`]
+/// - `[[` is a literal `[`
+///
+/// The tags can be nested, and must be closed in the reverse order they were opened.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_render::{color::Color, texture::Image};
+/// # use bevy_text::{Font, TextMarkup, TextStyle};
+/// #
+/// # let font_handle: Handle<Font> = Default::default();
+/// # let bold_font_handle: Handle<Font> = Default::default();
+/// # let coin_handle: Handle<Image> = Default::default();
+/// #
+/// let markup = TextMarkup::default()
+///     .with_font("bold", bold_font_handle)
+///     .with_color("gold", Color::GOLD)
+///     .with_icon("coin", coin_handle);
+/// let text = markup
+///     .parse(
+///         "You found [color=gold][font=bold]100[/font] gold[/color] [icon=coin]!",
+///         TextStyle {
+///             font: font_handle,
+///             font_size: 30.0,
+///             color: Color::WHITE,
+///             ..Default::default()
+///         },
+///     )
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextMarkup {
+    fonts: HashMap<String, Handle<Font>>,
+    colors: HashMap<String, Color>,
+    icons: HashMap<String, Handle<Image>>,
+}
+
+impl TextMarkup {
+    /// Names a font for the `font` tag.
+    pub fn with_font(mut self, name: impl Into<String>, font: Handle<Font>) -> Self {
+        self.fonts.insert(name.into(), font);
+        self
+    }
+
+    /// Names a color for the `color` tag.
+    pub fn with_color(mut self, name: impl Into<String>, color: Color) -> Self {
+        self.colors.insert(name.into(), color);
+        self
+    }
+
+    /// Names an image for the `icon` tag.
+    pub fn with_icon(mut self, name: impl Into<String>, icon: Handle<Image>) -> Self {
+        self.icons.insert(name.into(), icon);
+        self
+    }
+
+    /// Parses a [`Text`] from the `markup`, whose text out of any tag is in the `style`.
+    ///
+    /// # Errors
+    ///
+    /// If a tag is unknown, has an invalid value or isn't closed in the right order.
+    pub fn parse(&self, markup: &str, style: TextStyle) -> Result<Text, TextMarkupError> {
+        let mut builder = TextBuilder::new(style);
+        let mut open_tags = Vec::new();
+        let mut text = String::new();
+        let mut rest = markup;
+        while let Some(start) = rest.find('[') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            if let Some(after_bracket) = rest.strip_prefix('[') {
+                text.push('[');
+                rest = after_bracket;
+                continue;
+            }
+            let end = rest.find(']').ok_or(TextMarkupError::UnterminatedTag)?;
+            let tag = &rest[..end];
+            rest = &rest[end + 1..];
+            builder = builder.span(std::mem::take(&mut text));
+
+            if let Some(name) = tag.strip_prefix('/') {
+                if open_tags.pop() != Some(name) {
+                    return Err(TextMarkupError::UnexpectedClosingTag(name.to_string()));
+                }
+                builder = builder.pop_style();
+                continue;
+            }
+            let (name, value) = tag.split_once('=').unwrap_or((tag, ""));
+            let invalid_value = || TextMarkupError::InvalidValue {
+                tag: name.to_string(),
+                value: value.to_string(),
+            };
+            builder = match name {
+                "color" => {
+                    let color = match self.colors.get(value) {
+                        Some(color) => *color,
+                        None => Color::hex(value.trim_start_matches('#'))
+                            .map_err(|_| invalid_value())?,
+                    };
+                    builder.push_color(color)
+                }
+                "size" => match value.parse::<f32>() {
+                    Ok(font_size) if font_size > 0.0 => builder.push_font_size(font_size),
+                    _ => return Err(invalid_value()),
+                },
+                "font" => match self.fonts.get(value) {
+                    Some(font) => builder.push_font(font.clone()),
+                    None => return Err(TextMarkupError::UnknownFont(value.to_string())),
+                },
+                "icon" => match self.icons.get(value) {
+                    // Icons have no closing tag
+                    Some(icon) => {
+                        builder = builder.icon(icon.clone());
+                        continue;
+                    }
+                    None => return Err(TextMarkupError::UnknownIcon(value.to_string())),
+                },
+                _ => return Err(TextMarkupError::UnknownTag(name.to_string())),
+            };
+            open_tags.push(name);
+        }
+        text.push_str(rest);
+
+        if let Some(tag) = open_tags.pop() {
+            return Err(TextMarkupError::UnclosedTag(tag.to_string()));
+        }
+        Ok(builder.span(text).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_markup() {
+        let icon = Handle::<Image>::weak(bevy_asset::HandleId::random::<Image>());
+        let markup = TextMarkup::default()
+            .with_color("gold", Color::GOLD)
+            .with_icon("coin", icon.clone());
+        let text = markup
+            .parse(
+                "A [[b] [color=gold]1[size=20]2[/size][/color][icon=coin]",
+                TextStyle::default(),
+            )
+            .unwrap();
+        let values: Vec<_> = text.sections.iter().map(|s| s.value.as_str()).collect();
+        assert_eq!(values, ["A [b] ", "1", "2", ""]);
+        assert_eq!(text.sections[1].style.color, Color::GOLD);
+        assert_eq!(text.sections[2].style.font_size, 20.0);
+        assert_eq!(text.sections[2].style.color, Color::GOLD);
+        assert_eq!(text.sections[3].style, TextStyle::default());
+        assert_eq!(text.sections[3].icon, Some(icon));
+
+        let style = TextStyle::default;
+        assert_eq!(
+            markup.parse("[color=#f00]red", style()).unwrap_err(),
+            TextMarkupError::UnclosedTag("color".to_string())
+        );
+        assert_eq!(
+            markup
+                .parse("[size=1][color=gold][/size]", style())
+                .unwrap_err(),
+            TextMarkupError::UnexpectedClosingTag("size".to_string())
+        );
+        assert_eq!(
+            markup.parse("[size=big]", style()).unwrap_err(),
+            TextMarkupError::InvalidValue {
+                tag: "size".to_string(),
+                value: "big".to_string()
+            }
+        );
+        assert_eq!(
+            markup.parse("[bold]", style()).unwrap_err(),
+            TextMarkupError::UnknownTag("bold".to_string())
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use bevy_asset::Handle;
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::{prelude::*, FromReflect};
-use bevy_render::color::Color;
+use bevy_render::{color::Color, texture::Image};
 use serde::{Deserialize, Serialize};
 
 use crate::Font;
@@ -31,6 +32,7 @@ impl Text {
     ///         font: font_handle.clone(),
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// );
     ///
@@ -40,6 +42,7 @@ impl Text {
     ///         font: font_handle,
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// ) // You can still add an alignment.
     /// .with_alignment(TextAlignment::CENTER);
@@ -67,6 +70,7 @@ impl Text {
     ///             font: font_handle.clone(),
     ///             font_size: 60.0,
     ///             color: Color::BLUE,
+    ///             ..Default::default()
     ///         },
     ///     ),
     ///     TextSection::new(
@@ -75,6 +79,7 @@ impl Text {
     ///             font: font_handle,
     ///             font_size: 60.0,
     ///             color: Color::RED,
+    ///             ..Default::default()
     ///         },
     ///     ),
     /// ]);
@@ -97,6 +102,9 @@ impl Text {
 pub struct TextSection {
     pub value: String,
     pub style: TextStyle,
+    /// An image drawn inline instead of the value, in a square as high as a line of the font of
+    /// the style, and tinted by its color
+    pub icon: Option<Handle<Image>>,
 }
 
 impl TextSection {
@@ -105,6 +113,16 @@ impl TextSection {
         Self {
             value: value.into(),
             style,
+            icon: None,
+        }
+    }
+
+    /// Create a [`TextSection`] drawing an image inline, wrapped with the text around it.
+    pub fn from_icon(icon: Handle<Image>, style: TextStyle) -> Self {
+        Self {
+            value: String::new(),
+            style,
+            icon: Some(icon),
         }
     }
 
@@ -113,6 +131,7 @@ impl TextSection {
         Self {
             value: String::new(),
             style,
+            icon: None,
         }
     }
 }
@@ -233,11 +252,15 @@ impl From<VerticalAlign> for glyph_brush_layout::VerticalAlign {
     }
 }
 
-#[derive(Clone, Debug, Reflect, FromReflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, FromReflect)]
 pub struct TextStyle {
     pub font: Handle<Font>,
     pub font_size: f32,
     pub color: Color,
+    /// An outline drawn around the glyphs, to keep the text readable over any background
+    pub outline: Option<TextOutline>,
+    /// A shadow drawn behind the glyphs and their outline
+    pub shadow: Option<TextShadow>,
 }
 
 impl Default for TextStyle {
@@ -246,6 +269,100 @@ impl Default for TextStyle {
             font: Default::default(),
             font_size: 12.0,
             color: Color::WHITE,
+            outline: None,
+            shadow: None,
+        }
+    }
+}
+
+/// An outline around the glyphs of a [`TextStyle`].
+///
+/// It's drawn as copies of the glyphs around them, so it's best kept to a few pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+pub struct TextOutline {
+    /// The width of the outline, in logical pixels
+    pub width: f32,
+    pub color: Color,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// A shadow behind the glyphs of a [`TextStyle`], as a copy of them.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+pub struct TextShadow {
+    /// The offset of the shadow, in logical pixels, to the right and down
+    pub offset: Vec2,
+    pub color: Color,
+}
+
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::new(2.0, 2.0),
+            color: Color::rgba(0.0, 0.0, 0.0, 0.75),
+        }
+    }
+}
+
+/// The layers of the glyphs of a text, drawn one after the other from back to front, so that the
+/// glyphs are drawn over the outlines and shadows of the glyphs next to them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlyphLayer {
+    Shadow,
+    Outline,
+    Fill,
+}
+
+impl GlyphLayer {
+    pub const ALL: [GlyphLayer; 3] = [GlyphLayer::Shadow, GlyphLayer::Outline, GlyphLayer::Fill];
+}
+
+impl TextStyle {
+    /// The copies of a glyph drawing one of its layers in this style, as their color and their
+    /// offsets in physical pixels, to the right and down. It's `None` if the layer isn't drawn.
+    pub fn glyph_layer(&self, layer: GlyphLayer, scale_factor: f32) -> Option<(Color, Vec<Vec2>)> {
+        let outline_offsets = || match self.outline {
+            Some(outline) if outline.width > 0.0 => {
+                let width = outline.width * scale_factor;
+                // More copies keep the wider outlines round
+                let count = if width <= 2.0 { 8 } else { 16 };
+                (0..count)
+                    .map(|i| {
+                        let angle = i as f32 * std::f32::consts::TAU / count as f32;
+                        Vec2::new(angle.cos(), angle.sin()) * width
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        match layer {
+            GlyphLayer::Shadow => {
+                let shadow = self.shadow?;
+                let offset = shadow.offset * scale_factor;
+                let mut offsets = outline_offsets();
+                if offsets.is_empty() {
+                    offsets.push(Vec2::ZERO);
+                }
+                Some((
+                    shadow.color,
+                    offsets
+                        .into_iter()
+                        .map(|outline| outline + offset)
+                        .collect(),
+                ))
+            }
+            GlyphLayer::Outline => {
+                let offsets = outline_offsets();
+                (!offsets.is_empty()).then(|| (self.outline.unwrap().color, offsets))
+            }
+            GlyphLayer::Fill => Some((self.color, vec![Vec2::ZERO])),
         }
     }
 }
@@ -1,4 +1,4 @@
-use bevy_asset::Assets;
+use bevy_asset::{AssetEvent, Assets};
 use bevy_ecs::{
     bundle::Bundle,
     component::Component,
@@ -11,19 +11,18 @@ use bevy_ecs::{
 use bevy_math::{Vec2, Vec3};
 use bevy_reflect::Reflect;
 use bevy_render::{
-    prelude::Color,
     texture::Image,
     view::{ComputedVisibility, Visibility},
     Extract,
 };
-use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, TextureAtlas};
+use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, ImageScaleMode, TextureAtlas};
 use bevy_transform::prelude::{GlobalTransform, Transform};
 use bevy_utils::HashSet;
 use bevy_window::{WindowId, WindowScaleFactorChanged, Windows};
 
 use crate::{
-    Font, FontAtlasSet, HorizontalAlign, Text, TextError, TextLayoutInfo, TextPipeline,
-    TextSettings, VerticalAlign, YAxisOrientation,
+    Font, FontAtlasSet, FontFallbacks, GlyphLayer, HorizontalAlign, Text, TextError,
+    TextLayoutInfo, TextPipeline, TextSettings, VerticalAlign, YAxisOrientation,
 };
 
 /// The calculated size of text drawn in 2D scene.
@@ -103,41 +102,84 @@ pub fn extract_text2d_sprite(
             HorizontalAlign::Right => Vec3::new(-width, 0.0, 0.0),
         };
 
-        let mut color = Color::WHITE;
-        let mut current_section = usize::MAX;
-        for text_glyph in text_glyphs {
-            if text_glyph.section_index != current_section {
-                color = text.sections[text_glyph.section_index]
-                    .style
-                    .color
-                    .as_rgba_linear();
-                current_section = text_glyph.section_index;
+        for (layer_index, layer) in GlyphLayer::ALL.into_iter().enumerate() {
+            // The sprites of the same depth aren't drawn in order, so the layers behind the
+            // glyphs are moved back a bit
+            let depth = (layer_index as f32 - (GlyphLayer::ALL.len() - 1) as f32) * 0.001;
+            let mut section_layer = None;
+            let mut current_section = usize::MAX;
+            for text_glyph in text_glyphs {
+                if text_glyph.section_index != current_section {
+                    section_layer = text.sections[text_glyph.section_index]
+                        .style
+                        .glyph_layer(layer, scale_factor)
+                        .map(|(color, offsets)| (color.as_rgba_linear(), offsets));
+                    current_section = text_glyph.section_index;
+                }
+                let (color, offsets) = match &section_layer {
+                    Some(section_layer) => section_layer,
+                    None => continue,
+                };
+                let atlas = texture_atlases
+                    .get(&text_glyph.atlas_info.texture_atlas)
+                    .unwrap();
+                let handle = atlas.texture.clone_weak();
+                let index = text_glyph.atlas_info.glyph_index as usize;
+                let rect = Some(atlas.textures[index]);
+
+                for offset in offsets {
+                    // The offsets go down, while the glyphs go up
+                    let position = text_glyph.position + Vec2::new(offset.x, -offset.y);
+                    let glyph_transform = Transform::from_translation(
+                        alignment_offset * scale_factor + position.extend(depth),
+                    );
+                    // NOTE: Should match `bevy_ui::render::extract_text_uinodes`
+                    let transform = *text_transform
+                        * GlobalTransform::from_scale(Vec3::splat(scale_factor.recip()))
+                        * glyph_transform;
+
+                    extracted_sprites.sprites.push(ExtractedSprite {
+                        entity,
+                        transform,
+                        color: *color,
+                        rect,
+                        custom_size: None,
+                        image_handle_id: handle.id(),
+                        flip_x: false,
+                        flip_y: false,
+                        anchor: Anchor::Center.as_vec(),
+                        scale_mode: ImageScaleMode::Stretched,
+                        normal_map_handle_id: None,
+                    });
+                }
             }
-            let atlas = texture_atlases
-                .get(&text_glyph.atlas_info.texture_atlas)
-                .unwrap();
-            let handle = atlas.texture.clone_weak();
-            let index = text_glyph.atlas_info.glyph_index as usize;
-            let rect = Some(atlas.textures[index]);
-
-            let glyph_transform = Transform::from_translation(
-                alignment_offset * scale_factor + text_glyph.position.extend(0.),
+        }
+
+        for icon in &text_layout_info.icons {
+            let section = &text.sections[icon.section_index];
+            let image_handle_id = match &section.icon {
+                Some(image) => image.id(),
+                None => continue,
+            };
+            let icon_transform = Transform::from_translation(
+                alignment_offset * scale_factor + icon.position.extend(0.),
             );
-            // NOTE: Should match `bevy_ui::render::extract_text_uinodes`
             let transform = *text_transform
                 * GlobalTransform::from_scale(Vec3::splat(scale_factor.recip()))
-                * glyph_transform;
+                * icon_transform;
 
             extracted_sprites.sprites.push(ExtractedSprite {
                 entity,
                 transform,
-                color,
-                rect,
-                custom_size: None,
-                image_handle_id: handle.id(),
+                color: section.style.color.as_rgba_linear(),
+                rect: None,
+                custom_size: Some(icon.size),
+                image_handle_id,
                 flip_x: false,
                 flip_y: false,
                 anchor: Anchor::Center.as_vec(),
+                scale_mode: ImageScaleMode::Stretched,
+                normal_map_handle_id: None,
             });
         }
     }
@@ -154,6 +196,8 @@ pub fn update_text2d_layout(
     fonts: Res<Assets<Font>>,
     windows: Res<Windows>,
     text_settings: Res<TextSettings>,
+    font_fallbacks: Res<FontFallbacks>,
+    mut font_events: EventReader<AssetEvent<Font>>,
     mut scale_factor_changed: EventReader<WindowScaleFactorChanged>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
@@ -169,12 +213,14 @@ pub fn update_text2d_layout(
 ) {
     // We need to consume the entire iterator, hence `last`
     let factor_changed = scale_factor_changed.iter().last().is_some();
+    let fallbacks_changed =
+        font_fallbacks.is_changed() || font_fallbacks.any_loaded(font_events.iter());
     let scale_factor = windows.scale_factor(WindowId::primary());
 
     for (entity, text_changed, text, maybe_bounds, mut calculated_size, text_layout_info) in
         &mut text_query
     {
-        if factor_changed || text_changed || queue.remove(&entity) {
+        if factor_changed || fallbacks_changed || text_changed || queue.remove(&entity) {
             let text_bounds = match maybe_bounds {
                 Some(bounds) => Vec2::new(
                     scale_value(bounds.size.x, scale_factor),
@@ -193,6 +239,7 @@ pub fn update_text2d_layout(
                 &mut *texture_atlases,
                 &mut *textures,
                 text_settings.as_ref(),
+                &font_fallbacks,
                 YAxisOrientation::BottomToTop,
             ) {
                 Err(TextError::NoSuchFont) => {
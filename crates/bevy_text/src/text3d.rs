@@ -0,0 +1,713 @@
+use std::ops::Range;
+
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId, HandleUntyped};
+use bevy_core_pipeline::{
+    core_3d::Transparent3d,
+    oit::{oit_color_targets, OrderIndependentTransparency},
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Mat4, Vec2, Vec3, Vec4};
+use bevy_reflect::{FromReflect, Reflect, TypeUuid};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_phase::{
+        DrawFunctions, EntityRenderCommand, RenderCommandResult, RenderPhase, SetItemPipeline,
+        TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
+    texture::Image,
+    view::{
+        ComputedVisibility, ExtractedView, Msaa, ViewUniform, ViewUniformOffset, ViewUniforms,
+        Visibility, VisibleEntities,
+    },
+    Extract,
+};
+use bevy_sprite::{SpriteAssetEvents, TextureAtlas};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_utils::{HashMap, HashSet};
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    Font, FontAtlasSet, FontFallbacks, GlyphLayer, HorizontalAlign, Text, TextError,
+    TextLayoutInfo, TextPipeline, TextSettings, VerticalAlign, YAxisOrientation,
+};
+
+pub const TEXT_3D_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9174285216093468157);
+
+/// How the quads of the glyphs of a [`Text3d`] are oriented.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub enum Text3dOrientation {
+    /// The text faces the camera, whatever the rotation of its [`Transform`], like a nameplate.
+    #[default]
+    Billboard,
+    /// The text lies in the XY plane of its [`Transform`], like a sign.
+    Fixed,
+}
+
+/// Draws a [`Text`] in a 3D scene, in its transparent pass, hidden by the meshes in front of it.
+///
+/// The text is laid out in pixels of its font size, which are scaled to the world by
+/// `pixel_size`. A larger font size with a smaller `pixel_size` draws the same text sharper.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Text3d {
+    pub orientation: Text3dOrientation,
+    /// The size of a pixel of the text in world units
+    pub pixel_size: f32,
+}
+
+impl Default for Text3d {
+    fn default() -> Self {
+        Self {
+            orientation: Text3dOrientation::Billboard,
+            pixel_size: 0.01,
+        }
+    }
+}
+
+/// The bundle of components needed to draw text in a 3D scene via a 3D `Camera3dBundle`.
+/// [Example usage.](https://github.com/bevyengine/bevy/blob/latest/examples/3d/text3d.rs)
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct Text3dBundle {
+    pub text: Text,
+    pub text_3d: Text3d,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub computed_visibility: ComputedVisibility,
+}
+
+/// Updates the layout of the [`Text3d`]s whenever their text or style is changed.
+#[allow(clippy::too_many_arguments)]
+pub fn update_text3d_layout(
+    mut commands: Commands,
+    // Text items which should be reprocessed again, generally when the font hasn't loaded yet.
+    mut queue: Local<HashSet<Entity>>,
+    mut textures: ResMut<Assets<Image>>,
+    fonts: Res<Assets<Font>>,
+    text_settings: Res<TextSettings>,
+    font_fallbacks: Res<FontFallbacks>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
+    mut text_pipeline: ResMut<TextPipeline>,
+    mut text_query: Query<
+        (Entity, Changed<Text>, &Text, Option<&mut TextLayoutInfo>),
+        With<Text3d>,
+    >,
+) {
+    let fallbacks_changed =
+        font_fallbacks.is_changed() || font_fallbacks.any_loaded(font_events.iter());
+
+    for (entity, text_changed, text, text_layout_info) in &mut text_query {
+        if fallbacks_changed || text_changed || queue.remove(&entity) {
+            // The text is laid out in pixels of its font size, independently of the windows
+            match text_pipeline.queue_text(
+                &fonts,
+                &text.sections,
+                1.0,
+                text.alignment,
+                Vec2::new(f32::MAX, f32::MAX),
+                &mut font_atlas_set_storage,
+                &mut texture_atlases,
+                &mut textures,
+                text_settings.as_ref(),
+                &font_fallbacks,
+                YAxisOrientation::BottomToTop,
+            ) {
+                Err(TextError::NoSuchFont) => {
+                    queue.insert(entity);
+                }
+                Err(e @ TextError::FailedToAddGlyph(_))
+                | Err(e @ TextError::ExceedMaxTextAtlases(_)) => {
+                    panic!("Fatal error when processing text: {}.", e);
+                }
+                Ok(info) => match text_layout_info {
+                    Some(mut t) => *t = info,
+                    None => {
+                        commands.entity(entity).insert(info);
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// A glyph or an icon of a [`Text3d`], in pixels of the text.
+struct ExtractedGlyph3d {
+    /// The center of the quad
+    position: Vec2,
+    size: Vec2,
+    /// The UV of the bottom left corner of the quad, and the size of the quad in UV space
+    uv_offset_scale: Vec4,
+    image_handle_id: HandleId,
+    color: [f32; 4],
+}
+
+struct ExtractedText3d {
+    entity: Entity,
+    transform: GlobalTransform,
+    text_3d: Text3d,
+    /// The glyphs in the order they are drawn, back to front
+    glyphs: Vec<ExtractedGlyph3d>,
+}
+
+#[derive(Resource, Default)]
+pub struct ExtractedTexts3d {
+    texts: Vec<ExtractedText3d>,
+}
+
+pub fn extract_text3d(
+    mut extracted_texts: ResMut<ExtractedTexts3d>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    text3d_query: Extract<
+        Query<(
+            Entity,
+            &ComputedVisibility,
+            &Text,
+            &Text3d,
+            &TextLayoutInfo,
+            &GlobalTransform,
+        )>,
+    >,
+) {
+    extracted_texts.texts.clear();
+    for (entity, computed_visibility, text, text_3d, text_layout_info, transform) in
+        text3d_query.iter()
+    {
+        if !computed_visibility.is_visible() {
+            continue;
+        }
+        let size = text_layout_info.size;
+        let alignment_offset = match text.alignment.vertical {
+            VerticalAlign::Top => Vec2::new(0.0, -size.y),
+            VerticalAlign::Center => Vec2::new(0.0, -size.y * 0.5),
+            VerticalAlign::Bottom => Vec2::ZERO,
+        } + match text.alignment.horizontal {
+            HorizontalAlign::Left => Vec2::ZERO,
+            HorizontalAlign::Center => Vec2::new(-size.x * 0.5, 0.0),
+            HorizontalAlign::Right => Vec2::new(-size.x, 0.0),
+        };
+
+        let mut glyphs = Vec::new();
+        // The layers are drawn in order without writing the depth, so the glyphs cover the
+        // outlines and shadows of their neighbours
+        for layer in GlyphLayer::ALL {
+            let mut section_layer = None;
+            let mut current_section = usize::MAX;
+            for text_glyph in &text_layout_info.glyphs {
+                if text_glyph.section_index != current_section {
+                    section_layer = text.sections[text_glyph.section_index]
+                        .style
+                        .glyph_layer(layer, 1.0)
+                        .map(|(color, offsets)| (color.as_linear_rgba_f32(), offsets));
+                    current_section = text_glyph.section_index;
+                }
+                let (color, offsets) = match &section_layer {
+                    Some(section_layer) => section_layer,
+                    None => continue,
+                };
+                let atlas = texture_atlases
+                    .get(&text_glyph.atlas_info.texture_atlas)
+                    .unwrap();
+                let rect = atlas.textures[text_glyph.atlas_info.glyph_index];
+                let uv_offset = rect.min / atlas.size;
+                let uv_scale = rect.size() / atlas.size;
+
+                for offset in offsets {
+                    // The offsets go down, while the glyphs go up
+                    glyphs.push(ExtractedGlyph3d {
+                        position: alignment_offset
+                            + text_glyph.position
+                            + Vec2::new(offset.x, -offset.y),
+                        size: rect.size(),
+                        uv_offset_scale: Vec4::new(
+                            uv_offset.x,
+                            uv_offset.y,
+                            uv_scale.x,
+                            uv_scale.y,
+                        ),
+                        image_handle_id: atlas.texture.id(),
+                        color: *color,
+                    });
+                }
+            }
+        }
+
+        for icon in &text_layout_info.icons {
+            let section = &text.sections[icon.section_index];
+            if let Some(image) = &section.icon {
+                glyphs.push(ExtractedGlyph3d {
+                    position: alignment_offset + icon.position,
+                    size: icon.size,
+                    uv_offset_scale: Vec4::new(0.0, 0.0, 1.0, 1.0),
+                    image_handle_id: image.id(),
+                    color: section.style.color.as_linear_rgba_f32(),
+                });
+            }
+        }
+
+        extracted_texts.texts.push(ExtractedText3d {
+            entity,
+            transform: *transform,
+            text_3d: *text_3d,
+            glyphs,
+        });
+    }
+}
+
+#[derive(Resource)]
+pub struct Text3dPipeline {
+    view_layout: BindGroupLayout,
+    image_layout: BindGroupLayout,
+    format: TextureFormat,
+}
+
+impl FromWorld for Text3dPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let view_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ViewUniform::min_size()),
+                },
+                count: None,
+            }],
+            label: Some("text_3d_view_layout"),
+        });
+
+        let image_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            label: Some("text_3d_image_layout"),
+        });
+
+        Text3dPipeline {
+            view_layout,
+            image_layout,
+            format: world.resource::<RenderTextureFormat>().0,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    #[repr(transparent)]
+    // NOTE: Apparently quadro drivers support up to 64x MSAA.
+    // MSAA uses the highest 3 bits for the MSAA log2(sample count) to support up to 128x MSAA.
+    pub struct Text3dPipelineKey: u32 {
+        const NONE                        = 0;
+        const BILLBOARD                   = (1 << 0);
+        const OIT                         = (1 << 1);
+        const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
+    }
+}
+
+impl Text3dPipelineKey {
+    const MSAA_MASK_BITS: u32 = 0b111;
+    const MSAA_SHIFT_BITS: u32 = 32 - Self::MSAA_MASK_BITS.count_ones();
+
+    pub fn from_msaa_samples(msaa_samples: u32) -> Self {
+        let msaa_bits =
+            (msaa_samples.trailing_zeros() & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
+        Self::from_bits(msaa_bits).unwrap()
+    }
+
+    pub fn msaa_samples(&self) -> u32 {
+        1 << ((self.bits >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
+    }
+}
+
+impl SpecializedRenderPipeline for Text3dPipeline {
+    type Key = Text3dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let instance_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            vec![
+                // anchor
+                VertexFormat::Float32x3,
+                // origin
+                VertexFormat::Float32x3,
+                // x_axis
+                VertexFormat::Float32x3,
+                // y_axis
+                VertexFormat::Float32x3,
+                // uv_offset_scale
+                VertexFormat::Float32x4,
+                // color
+                VertexFormat::Float32x4,
+            ],
+        );
+
+        let mut shader_defs = Vec::new();
+        if key.contains(Text3dPipelineKey::BILLBOARD) {
+            shader_defs.push("BILLBOARD".to_string());
+        }
+        let targets = if key.contains(Text3dPipelineKey::OIT) {
+            shader_defs.push("OIT".to_string());
+            oit_color_targets()
+        } else {
+            vec![Some(ColorTargetState {
+                format: self.format,
+                blend: Some(BlendState::ALPHA_BLENDING),
+                write_mask: ColorWrites::ALL,
+            })]
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TEXT_3D_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![instance_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: TEXT_3D_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets,
+            }),
+            layout: Some(vec![self.view_layout.clone(), self.image_layout.clone()]),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            // The text is hidden by the meshes in front of it, but doesn't hide anything
+            depth_stencil: Some(DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState {
+                    front: StencilFaceState::IGNORE,
+                    back: StencilFaceState::IGNORE,
+                    read_mask: 0,
+                    write_mask: 0,
+                },
+                bias: DepthBiasState {
+                    constant: 0,
+                    slope_scale: 0.0,
+                    clamp: 0.0,
+                },
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("text_3d_pipeline".into()),
+        }
+    }
+}
+
+/// The data of a glyph in the instance buffer, drawn as a quad.
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Text3dInstance {
+    /// The position of the text in the world
+    anchor: [f32; 3],
+    /// The center of the quad, relative to the anchor. For billboards it's in the space of the
+    /// view, and rotated with it in the shader.
+    origin: [f32; 3],
+    /// The sides of the quad
+    x_axis: [f32; 3],
+    y_axis: [f32; 3],
+    uv_offset_scale: [f32; 4],
+    color: [f32; 4],
+}
+
+#[derive(Resource)]
+pub struct Text3dMeta {
+    instances: BufferVec<Text3dInstance>,
+    view_bind_group: Option<BindGroup>,
+}
+
+impl Default for Text3dMeta {
+    fn default() -> Self {
+        let mut instances = BufferVec::new(BufferUsages::VERTEX);
+        instances.set_label(Some("text_3d_instance_buffer"));
+        Self {
+            instances,
+            view_bind_group: None,
+        }
+    }
+}
+
+/// Successive glyphs of a [`Text3d`] drawn with the same image.
+#[derive(Component)]
+pub struct Text3dBatch {
+    /// The entity of the text
+    entity: Entity,
+    /// The transform of the text, to sort it with the other transparent items
+    transform: Mat4,
+    billboard: bool,
+    image_handle_id: HandleId,
+    range: Range<u32>,
+}
+
+#[derive(Resource, Default)]
+pub struct Text3dImageBindGroups {
+    values: HashMap<Handle<Image>, BindGroup>,
+}
+
+pub fn prepare_text3d(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut text_3d_meta: ResMut<Text3dMeta>,
+    extracted_texts: Res<ExtractedTexts3d>,
+) {
+    text_3d_meta.instances.clear();
+    let mut batches = Vec::new();
+
+    for text in &extracted_texts.texts {
+        let (scale, _, translation) = text.transform.to_scale_rotation_translation();
+        let billboard = text.text_3d.orientation == Text3dOrientation::Billboard;
+        let affine = text.transform.affine();
+        // The billboards are only scaled, while the other texts are transformed to the world
+        let to_world = |vector: Vec3| match billboard {
+            true => vector * scale,
+            false => affine.transform_vector3(vector),
+        };
+        let mut current_batch: Option<Text3dBatch> = None;
+
+        for glyph in &text.glyphs {
+            let index = text_3d_meta.instances.len() as u32;
+            match &mut current_batch {
+                Some(batch) if batch.image_handle_id == glyph.image_handle_id => {
+                    batch.range.end = index + 1;
+                }
+                _ => {
+                    let new_batch = Text3dBatch {
+                        entity: text.entity,
+                        transform: text.transform.compute_matrix(),
+                        billboard,
+                        image_handle_id: glyph.image_handle_id,
+                        range: index..index + 1,
+                    };
+                    batches.extend(current_batch.replace(new_batch));
+                }
+            }
+
+            let pixel_size = text.text_3d.pixel_size;
+            text_3d_meta.instances.push(Text3dInstance {
+                anchor: translation.into(),
+                origin: to_world((glyph.position * pixel_size).extend(0.0)).into(),
+                x_axis: to_world(Vec3::X * glyph.size.x * pixel_size).into(),
+                y_axis: to_world(Vec3::Y * glyph.size.y * pixel_size).into(),
+                uv_offset_scale: glyph.uv_offset_scale.into(),
+                color: glyph.color,
+            });
+        }
+        batches.extend(current_batch);
+    }
+
+    commands.spawn_batch(batches);
+    text_3d_meta
+        .instances
+        .write_buffer(&render_device, &render_queue);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_text3d(
+    mut visible: Local<HashSet<Entity>>,
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    render_device: Res<RenderDevice>,
+    mut text_3d_meta: ResMut<Text3dMeta>,
+    view_uniforms: Res<ViewUniforms>,
+    text_3d_pipeline: Res<Text3dPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<Text3dPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut image_bind_groups: ResMut<Text3dImageBindGroups>,
+    gpu_images: Res<RenderAssets<Image>>,
+    events: Res<SpriteAssetEvents>,
+    batches: Query<(Entity, &Text3dBatch)>,
+    mut views: Query<(
+        &ExtractedView,
+        &VisibleEntities,
+        &Msaa,
+        Option<&OrderIndependentTransparency>,
+        &mut RenderPhase<Transparent3d>,
+    )>,
+) {
+    // If an image has changed, the GpuImage has (probably) changed, such as the ones of the font
+    // atlases when glyphs are added to them
+    for event in &events.images {
+        match event {
+            AssetEvent::Created { .. } => {}
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                image_bind_groups.values.remove(handle);
+            }
+        };
+    }
+
+    let view_binding = match view_uniforms.uniforms.binding() {
+        Some(view_binding) => view_binding,
+        None => return,
+    };
+    text_3d_meta.view_bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: view_binding,
+        }],
+        label: Some("text_3d_view_bind_group"),
+        layout: &text_3d_pipeline.view_layout,
+    }));
+
+    let draw_text_3d = draw_functions.read().get_id::<DrawText3d>().unwrap();
+    for (view, visible_entities, msaa, oit, mut transparent_phase) in &mut views {
+        let mut key = Text3dPipelineKey::from_msaa_samples(msaa.samples);
+        if oit.is_some() {
+            key |= Text3dPipelineKey::OIT;
+        }
+        let rangefinder = view.rangefinder3d();
+        visible.clear();
+        visible.extend(visible_entities.entities.iter().copied());
+
+        for (batch_entity, batch) in &batches {
+            if !visible.contains(&batch.entity) {
+                continue;
+            }
+            let image = Handle::weak(batch.image_handle_id);
+            let gpu_image = match gpu_images.get(&image) {
+                Some(gpu_image) => gpu_image,
+                // Skip this batch if the texture is not ready
+                None => continue,
+            };
+            image_bind_groups.values.entry(image).or_insert_with(|| {
+                render_device.create_bind_group(&BindGroupDescriptor {
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(&gpu_image.texture_view),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&gpu_image.sampler),
+                        },
+                    ],
+                    label: Some("text_3d_image_bind_group"),
+                    layout: &text_3d_pipeline.image_layout,
+                })
+            });
+
+            let batch_key = match batch.billboard {
+                true => key | Text3dPipelineKey::BILLBOARD,
+                false => key,
+            };
+            transparent_phase.add(Transparent3d {
+                distance: rangefinder.distance(&batch.transform),
+                pipeline: pipelines.specialize(&mut pipeline_cache, &text_3d_pipeline, batch_key),
+                entity: batch_entity,
+                draw_function: draw_text_3d,
+            });
+        }
+    }
+}
+
+pub type DrawText3d = (
+    SetItemPipeline,
+    SetText3dViewBindGroup<0>,
+    SetText3dTextureBindGroup<1>,
+    DrawText3dBatch,
+);
+
+pub struct SetText3dViewBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetText3dViewBindGroup<I> {
+    type Param = (SRes<Text3dMeta>, SQuery<Read<ViewUniformOffset>>);
+
+    fn render<'w>(
+        view: Entity,
+        _item: Entity,
+        (text_3d_meta, view_query): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let view_uniform = view_query.get(view).unwrap();
+        pass.set_bind_group(
+            I,
+            text_3d_meta.into_inner().view_bind_group.as_ref().unwrap(),
+            &[view_uniform.offset],
+        );
+        RenderCommandResult::Success
+    }
+}
+
+pub struct SetText3dTextureBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetText3dTextureBindGroup<I> {
+    type Param = (SRes<Text3dImageBindGroups>, SQuery<Read<Text3dBatch>>);
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (image_bind_groups, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let batch = query_batch.get(item).unwrap();
+        let image_bind_groups = image_bind_groups.into_inner();
+        pass.set_bind_group(
+            I,
+            image_bind_groups
+                .values
+                .get(&Handle::weak(batch.image_handle_id))
+                .unwrap(),
+            &[],
+        );
+        RenderCommandResult::Success
+    }
+}
+
+pub struct DrawText3dBatch;
+impl EntityRenderCommand for DrawText3dBatch {
+    type Param = (SRes<Text3dMeta>, SQuery<Read<Text3dBatch>>);
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (text_3d_meta, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let batch = query_batch.get(item).unwrap();
+        // Each quad is drawn from the 6 vertices of its instance
+        pass.set_vertex_buffer(
+            0,
+            text_3d_meta
+                .into_inner()
+                .instances
+                .buffer()
+                .unwrap()
+                .slice(..),
+        );
+        pass.draw(0..6, batch.range.clone());
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,87 @@
+#import bevy_core_pipeline::oit
+
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
+};
+@group(0) @binding(0)
+var<uniform> view: View;
+
+struct VertexOutput {
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) world_position: vec3<f32>,
+    @builtin(position) position: vec4<f32>,
+};
+
+// Draws the quad of a glyph instance from 6 vertices
+@vertex
+fn vertex(
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) anchor: vec3<f32>,
+    @location(1) origin: vec3<f32>,
+    @location(2) x_axis: vec3<f32>,
+    @location(3) y_axis: vec3<f32>,
+    @location(4) uv_offset_scale: vec4<f32>,
+    @location(5) color: vec4<f32>,
+) -> VertexOutput {
+    // The corners of the two triangles of the quad
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+        vec2<f32>(-0.5, 0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+    );
+    let corner = corners[vertex_index];
+
+    var offset = origin + corner.x * x_axis + corner.y * y_axis;
+#ifdef BILLBOARD
+    // The offset is in the plane of the view, so that the text faces it
+    offset = (view.view * vec4<f32>(offset, 0.0)).xyz;
+#endif
+    let position = anchor + offset;
+
+    var out: VertexOutput;
+    // UVs go down while the quad goes up
+    out.uv = uv_offset_scale.xy + vec2<f32>(corner.x + 0.5, 0.5 - corner.y) * uv_offset_scale.zw;
+    out.position = view.view_proj * vec4<f32>(position, 1.0);
+    out.color = color;
+    out.world_position = position;
+    return out;
+}
+
+@group(1) @binding(0)
+var glyph_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var glyph_sampler: sampler;
+
+@fragment
+#ifdef OIT
+fn fragment(in: VertexOutput) -> OitFragmentOutput {
+#else
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+#endif
+    let color = in.color * textureSample(glyph_texture, glyph_sampler, in.uv);
+#ifdef OIT
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), vec4<f32>(in.world_position, 1.0));
+    return oit_fragment_output(color, -view_z);
+#else
+    return color;
+#endif
+}
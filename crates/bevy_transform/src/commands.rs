@@ -0,0 +1,112 @@
+//! Extension to [`EntityCommands`] and [`EntityMut`] to modify [`bevy_hierarchy`] hierarchies
+//! while preserving [`GlobalTransform`].
+
+use bevy_ecs::{
+    prelude::Entity,
+    system::{Command, EntityCommands},
+    world::{EntityMut, World},
+};
+use bevy_hierarchy::AddChild;
+
+use crate::components::{GlobalTransform, Transform};
+
+/// Command similar to [`AddChild`], but updating the child's [`Transform`] so that its
+/// [`GlobalTransform`] stays the same.
+///
+/// The [`Transform`] is computed from the last propagated [`GlobalTransform`]s of the child and
+/// the parent, it is left unchanged if one of them doesn't have one.
+pub struct AddChildInPlace {
+    /// Parent entity to add the child to
+    pub parent: Entity,
+    /// Child entity to add
+    pub child: Entity,
+}
+
+impl Command for AddChildInPlace {
+    fn write(self, world: &mut World) {
+        AddChild {
+            parent: self.parent,
+            child: self.child,
+        }
+        .write(world);
+        let parent = world.get::<GlobalTransform>(self.parent).copied();
+        let child = world.get::<GlobalTransform>(self.child).copied();
+        if let (Some(parent), Some(child)) = (parent, child) {
+            if let Some(mut transform) = world.get_mut::<Transform>(self.child) {
+                *transform = child.reparented_to(&parent);
+            }
+        }
+    }
+}
+
+/// Collection of methods similar to [`BuildChildren`](bevy_hierarchy::BuildChildren), but
+/// preserving each entity's [`GlobalTransform`].
+pub trait BuildChildrenTransformExt {
+    /// Change this entity's parent while preserving this entity's [`GlobalTransform`]
+    /// by updating its [`Transform`].
+    ///
+    /// See [`BuildChildren::add_child`](bevy_hierarchy::BuildChildren::add_child) for details.
+    fn set_parent_in_place(&mut self, parent: Entity) -> &mut Self;
+}
+
+impl<'w, 's, 'a> BuildChildrenTransformExt for EntityCommands<'w, 's, 'a> {
+    fn set_parent_in_place(&mut self, parent: Entity) -> &mut Self {
+        let child = self.id();
+        self.commands().add(AddChildInPlace { parent, child });
+        self
+    }
+}
+
+impl<'w> BuildChildrenTransformExt for EntityMut<'w> {
+    fn set_parent_in_place(&mut self, parent: Entity) -> &mut Self {
+        let child = self.id();
+        {
+            // SAFETY: the location of the entity is updated manually
+            let world = unsafe { self.world_mut() };
+            AddChildInPlace { parent, child }.write(world);
+        }
+        // Inserting the `Parent` component changes the entity's location
+        self.update_location();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{prelude::*, system::CommandQueue};
+    use bevy_hierarchy::Parent;
+    use bevy_math::{Quat, Vec3};
+
+    use crate::{
+        commands::BuildChildrenTransformExt,
+        components::{GlobalTransform, Transform},
+    };
+
+    #[test]
+    fn set_parent_in_place_preserves_global_transform() {
+        let mut world = World::default();
+        let child_transform = Transform::from_xyz(1.0, 2.0, 3.0);
+        let parent_transform = Transform::from_xyz(4.0, 0.0, 0.0)
+            .with_rotation(Quat::from_rotation_y(1.0))
+            .with_scale(Vec3::splat(2.0));
+        let parent = world
+            .spawn((parent_transform, GlobalTransform::from(parent_transform)))
+            .id();
+        let child = world
+            .spawn((child_transform, GlobalTransform::from(child_transform)))
+            .id();
+
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world)
+            .entity(child)
+            .set_parent_in_place(parent);
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<Parent>(child).unwrap().get(), parent);
+        let global = GlobalTransform::from(parent_transform)
+            .mul_transform(*world.get::<Transform>(child).unwrap());
+        assert!(global
+            .translation()
+            .abs_diff_eq(child_transform.translation, 1e-5));
+    }
+}
@@ -105,6 +105,52 @@ impl GlobalTransform {
         }
     }
 
+    /// Returns the [`Transform`] `self` would have if it was a child of an entity
+    /// with the `parent` [`GlobalTransform`].
+    ///
+    /// This is useful if you want to "reparent" an `Entity`. Say you have an entity
+    /// `e1` that you want to turn into a child of `e2`, but you want `e1` to keep the
+    /// same global transform, even after re-parenting. You would use:
+    ///
+    /// ```rust
+    /// # use bevy_transform::prelude::{GlobalTransform, Transform};
+    /// # use bevy_ecs::prelude::{Entity, Query, Component, Commands};
+    /// # use bevy_hierarchy::BuildChildren;
+    /// #[derive(Component)]
+    /// struct ToReparent {
+    ///     new_parent: Entity,
+    /// }
+    /// fn reparent_system(
+    ///     mut commands: Commands,
+    ///     mut targets: Query<(&mut Transform, Entity, &GlobalTransform, &ToReparent)>,
+    ///     transforms: Query<&GlobalTransform>,
+    /// ) {
+    ///     for (mut transform, entity, initial, to_reparent) in targets.iter_mut() {
+    ///         if let Ok(parent_transform) = transforms.get(to_reparent.new_parent) {
+    ///             *transform = initial.reparented_to(parent_transform);
+    ///             commands.entity(entity).remove::<ToReparent>();
+    ///             commands.entity(to_reparent.new_parent).add_child(entity);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`BuildChildrenTransformExt::set_parent_in_place`](crate::commands::BuildChildrenTransformExt::set_parent_in_place)
+    /// does this in a command.
+    ///
+    /// The transform is expected to be non-degenerate and without shearing, or the output
+    /// will be invalid.
+    #[inline]
+    pub fn reparented_to(&self, parent: &GlobalTransform) -> Transform {
+        let relative_affine = parent.affine().inverse() * self.affine();
+        let (scale, rotation, translation) = relative_affine.to_scale_rotation_translation();
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
     /// Extracts `scale`, `rotation` and `translation` from `self`.
     ///
     /// The transform is expected to be non-degenerate and without shearing, or the output
@@ -0,0 +1,15 @@
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// Marks an entity whose hierarchy never moves, such as level geometry, so that
+/// [`transform_propagate_system`](crate::transform_propagate_system) doesn't traverse it every
+/// frame.
+///
+/// The [`GlobalTransform`](super::GlobalTransform)s of the hierarchy are only updated when the
+/// [`Transform`](super::Transform) or the [`Children`](bevy_hierarchy::Children) of the entity
+/// change, or when one of its ancestors moves. Changes to the [`Transform`](super::Transform) of
+/// its descendants are ignored until then, but the entities added to or moved within the
+/// hierarchy at any depth are still updated.
+#[derive(Component, Debug, PartialEq, Eq, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct StaticTransform;
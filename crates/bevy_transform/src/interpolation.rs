@@ -0,0 +1,190 @@
+use crate::{components::Transform, TransformSystem};
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::StageLabelId};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::FixedTimesteps;
+
+/// Smooths the movement of an entity whose [`Transform`] is updated on a fixed timestep.
+///
+/// The [`Transform`] of the entity is snapshotted after each tick of the fixed timestep, and
+/// between the ticks it is replaced by its interpolation between the last two snapshots, following
+/// how far the time is into the next tick. The [`Transform`] of the last tick is restored before
+/// the next one, so that the systems running on the fixed timestep only ever see their own
+/// results.
+///
+/// Requires the [`TransformInterpolationPlugin`]. Setting the [`Transform`] outside of the fixed
+/// timestep teleports the entity to it without interpolation.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct TransformInterpolation {
+    #[reflect(ignore)]
+    previous: Option<Transform>,
+    #[reflect(ignore)]
+    current: Option<Transform>,
+    #[reflect(ignore)]
+    interpolated: Option<Transform>,
+}
+
+impl TransformInterpolation {
+    /// Restores the [`Transform`] of the last tick, unless it was set since it was interpolated.
+    fn restore(&mut self, transform: &mut Transform) {
+        if let Some(interpolated) = self.interpolated.take() {
+            if *transform != interpolated {
+                self.teleport(transform);
+            } else if let Some(current) = self.current {
+                *transform = current;
+            }
+        }
+    }
+
+    /// Snapshots the [`Transform`] at the end of a tick.
+    fn snapshot(&mut self, transform: &Transform) {
+        self.previous = Some(self.current.unwrap_or(*transform));
+        self.current = Some(*transform);
+    }
+
+    /// Replaces the [`Transform`] by its interpolation between the last two ticks.
+    fn interpolate(&mut self, transform: &mut Transform, overstep: f32) {
+        if let Some(interpolated) = self.interpolated {
+            if *transform != interpolated {
+                self.teleport(transform);
+            }
+        }
+        if let (Some(previous), Some(current)) = (self.previous, self.current) {
+            let interpolated = Transform {
+                translation: previous.translation.lerp(current.translation, overstep),
+                rotation: previous.rotation.slerp(current.rotation, overstep),
+                scale: previous.scale.lerp(current.scale, overstep),
+            };
+            *transform = interpolated;
+            self.interpolated = Some(interpolated);
+        }
+    }
+
+    fn teleport(&mut self, transform: &Transform) {
+        self.previous = Some(*transform);
+        self.current = Some(*transform);
+    }
+}
+
+/// Adds the systems interpolating the [`Transform`] of the entities with a
+/// [`TransformInterpolation`] between the ticks of a fixed timestep.
+///
+/// The stage must run on the [`FixedTimestep`](bevy_time::FixedTimestep) with the label, and
+/// be added before this plugin.
+///
+/// ```no_run
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::FixedTimestep;
+/// # use bevy_transform::TransformInterpolationPlugin;
+/// #[derive(StageLabel)]
+/// struct FixedUpdateStage;
+///
+/// App::new()
+///     .add_stage_after(
+///         CoreStage::Update,
+///         FixedUpdateStage,
+///         SystemStage::parallel().with_run_criteria(FixedTimestep::step(0.1).with_label("physics")),
+///     )
+///     .add_plugin(TransformInterpolationPlugin::new(FixedUpdateStage, "physics"));
+/// ```
+pub struct TransformInterpolationPlugin {
+    stage: StageLabelId,
+    timestep: String,
+}
+
+impl TransformInterpolationPlugin {
+    /// Creates a plugin interpolating the entities moved in the stage, which runs on the
+    /// [`FixedTimestep`](bevy_time::FixedTimestep) with the label.
+    pub fn new(stage: impl StageLabel, timestep: impl Into<String>) -> Self {
+        Self {
+            stage: stage.as_label(),
+            timestep: timestep.into(),
+        }
+    }
+}
+
+impl Plugin for TransformInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        let timestep = self.timestep.clone();
+        app.register_type::<TransformInterpolation>()
+            .add_system_to_stage(self.stage, restore_fixed_transforms.at_start())
+            .add_system_to_stage(self.stage, snapshot_fixed_transforms.at_end())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                (move |fixed_timesteps: Res<FixedTimesteps>,
+                       query: Query<(&mut Transform, &mut TransformInterpolation)>| {
+                    interpolate_fixed_transforms(&timestep, fixed_timesteps, query);
+                })
+                .label(TransformSystem::TransformInterpolate)
+                .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Restores the [`Transform`] of the last tick of the entities with a [`TransformInterpolation`]
+/// at the start of a tick.
+pub fn restore_fixed_transforms(mut query: Query<(&mut Transform, &mut TransformInterpolation)>) {
+    for (mut transform, mut interpolation) in &mut query {
+        interpolation.restore(&mut transform);
+    }
+}
+
+/// Snapshots the [`Transform`] of the entities with a [`TransformInterpolation`] at the end of a
+/// tick.
+pub fn snapshot_fixed_transforms(mut query: Query<(&Transform, &mut TransformInterpolation)>) {
+    for (transform, mut interpolation) in &mut query {
+        interpolation.snapshot(transform);
+    }
+}
+
+fn interpolate_fixed_transforms(
+    timestep: &str,
+    fixed_timesteps: Res<FixedTimesteps>,
+    mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+    let overstep = match fixed_timesteps.get(timestep) {
+        Some(state) => state.overstep_percentage().clamp(0.0, 1.0) as f32,
+        None => return,
+    };
+    for (mut transform, mut interpolation) in &mut query {
+        interpolation.interpolate(&mut transform, overstep);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TransformInterpolation;
+    use crate::components::Transform;
+
+    #[test]
+    fn interpolate_between_ticks() {
+        let mut interpolation = TransformInterpolation::default();
+        let mut transform = Transform::from_xyz(0.0, 0.0, 0.0);
+        interpolation.restore(&mut transform);
+        interpolation.snapshot(&transform);
+        transform.translation.x = 2.0;
+        interpolation.snapshot(&transform);
+
+        interpolation.interpolate(&mut transform, 0.25);
+        assert_eq!(transform, Transform::from_xyz(0.5, 0.0, 0.0));
+        interpolation.interpolate(&mut transform, 0.75);
+        assert_eq!(transform, Transform::from_xyz(1.5, 0.0, 0.0));
+
+        // The next tick starts from the transform of the last one
+        interpolation.restore(&mut transform);
+        assert_eq!(transform, Transform::from_xyz(2.0, 0.0, 0.0));
+        transform.translation.x = 4.0;
+        interpolation.snapshot(&transform);
+        interpolation.interpolate(&mut transform, 0.5);
+        assert_eq!(transform, Transform::from_xyz(3.0, 0.0, 0.0));
+
+        // Setting the transform outside of the fixed timestep teleports the entity
+        transform.translation.x = 10.0;
+        interpolation.interpolate(&mut transform, 0.5);
+        assert_eq!(transform, Transform::from_xyz(10.0, 0.0, 0.0));
+        interpolation.restore(&mut transform);
+        assert_eq!(transform, Transform::from_xyz(10.0, 0.0, 0.0));
+    }
+}
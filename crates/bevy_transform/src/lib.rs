@@ -1,21 +1,27 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+pub mod commands;
 /// The basic components of the transform crate
 pub mod components;
+mod interpolation;
 mod systems;
+pub use crate::interpolation::*;
 pub use crate::systems::transform_propagate_system;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{components::*, TransformBundle, TransformPlugin};
+    pub use crate::{
+        commands::BuildChildrenTransformExt, components::*, TransformBundle,
+        TransformInterpolation, TransformInterpolationPlugin, TransformPlugin,
+    };
 }
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::ValidParentCheckPlugin;
-use prelude::{GlobalTransform, Transform};
+use prelude::{GlobalTransform, StaticTransform, Transform};
 
 /// A [`Bundle`] of the [`Transform`] and [`GlobalTransform`]
 /// [`Component`](bevy_ecs::component::Component)s, which describe the position of an entity.
@@ -77,6 +83,9 @@ impl From<Transform> for TransformBundle {
 pub enum TransformSystem {
     /// Propagates changes in transform to children's [`GlobalTransform`](crate::components::GlobalTransform)
     TransformPropagate,
+    /// Interpolates the [`Transform`](crate::components::Transform) of the entities with a
+    /// [`TransformInterpolation`] between the ticks of a fixed timestep
+    TransformInterpolate,
 }
 
 /// The base plugin for handling [`Transform`] components
@@ -87,6 +96,7 @@ impl Plugin for TransformPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Transform>()
             .register_type::<GlobalTransform>()
+            .register_type::<StaticTransform>()
             .add_plugin(ValidParentCheckPlugin::<GlobalTransform>::default())
             // add transform systems to startup so the first update is "correct"
             .add_startup_system_to_stage(
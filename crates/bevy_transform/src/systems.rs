@@ -1,6 +1,7 @@
-use crate::components::{GlobalTransform, Transform};
-use bevy_ecs::prelude::{Changed, Entity, Query, With, Without};
+use crate::components::{GlobalTransform, StaticTransform, Transform};
+use bevy_ecs::prelude::{Changed, Entity, Local, Query, With, Without};
 use bevy_hierarchy::{Children, Parent};
+use bevy_utils::HashSet;
 
 /// Update [`GlobalTransform`] component of entities based on entity hierarchy and
 /// [`Transform`] component.
@@ -12,6 +13,7 @@ pub fn transform_propagate_system(
             Changed<Transform>,
             &mut GlobalTransform,
             Entity,
+            Option<&StaticTransform>,
         ),
         Without<Parent>,
     >,
@@ -20,10 +22,28 @@ pub fn transform_propagate_system(
         Changed<Transform>,
         &mut GlobalTransform,
         &Parent,
+        Option<&StaticTransform>,
     )>,
     children_query: Query<(&Children, Changed<Children>), (With<Parent>, With<GlobalTransform>)>,
+    changed_children_query: Query<Entity, Changed<Children>>,
+    parent_query: Query<&Parent>,
+    mut changed_ancestors: Local<HashSet<Entity>>,
 ) {
-    for (children, transform, transform_changed, mut global_transform, entity) in
+    // Static hierarchies still have to be traversed when children are added or removed deeper
+    // in them, so collect the ancestors of the entities whose `Children` changed
+    changed_ancestors.clear();
+    for entity in &changed_children_query {
+        let mut entity = entity;
+        while let Ok(parent) = parent_query.get(entity) {
+            entity = parent.get();
+            // Stops at the ancestors already visited, which also guards against cycles
+            if !changed_ancestors.insert(entity) {
+                break;
+            }
+        }
+    }
+
+    for (children, transform, transform_changed, mut global_transform, entity, static_transform) in
         root_query.iter_mut()
     {
         let mut changed = transform_changed;
@@ -34,11 +54,16 @@ pub fn transform_propagate_system(
         if let Some((children, changed_children)) = children {
             // If our `Children` has changed, we need to recalculate everything below us
             changed |= changed_children;
+            // Static hierarchies are only traversed when they changed
+            if static_transform.is_some() && !changed && !changed_ancestors.contains(&entity) {
+                continue;
+            }
             for child in children {
                 let _ = propagate_recursive(
                     &global_transform,
                     &mut transform_query,
                     &children_query,
+                    &changed_ancestors,
                     *child,
                     entity,
                     changed,
@@ -55,15 +80,17 @@ fn propagate_recursive(
         Changed<Transform>,
         &mut GlobalTransform,
         &Parent,
+        Option<&StaticTransform>,
     )>,
     children_query: &Query<(&Children, Changed<Children>), (With<Parent>, With<GlobalTransform>)>,
+    changed_ancestors: &HashSet<Entity>,
     entity: Entity,
     expected_parent: Entity,
     mut changed: bool,
     // We use a result here to use the `?` operator. Ideally we'd use a try block instead
 ) -> Result<(), ()> {
-    let global_matrix = {
-        let (transform, transform_changed, mut global_transform, child_parent) =
+    let (global_matrix, is_static) = {
+        let (transform, transform_changed, mut global_transform, child_parent, static_transform) =
             transform_query.get_mut(entity).map_err(drop)?;
         // Note that for parallelising, this check cannot occur here, since there is an `&mut GlobalTransform` (in global_transform)
         assert_eq!(
@@ -74,17 +101,22 @@ fn propagate_recursive(
         if changed {
             *global_transform = parent.mul_transform(*transform);
         }
-        *global_transform
+        (*global_transform, static_transform.is_some())
     };
 
     let (children, changed_children) = children_query.get(entity).map_err(drop)?;
     // If our `Children` has changed, we need to recalculate everything below us
     changed |= changed_children;
+    // Static hierarchies are only traversed when they changed
+    if is_static && !changed && !changed_ancestors.contains(&entity) {
+        return Ok(());
+    }
     for child in children {
         let _ = propagate_recursive(
             &global_matrix,
             transform_query,
             children_query,
+            changed_ancestors,
             *child,
             entity,
             changed,
@@ -100,7 +132,7 @@ mod test {
     use bevy_ecs::system::CommandQueue;
     use bevy_math::vec3;
 
-    use crate::components::{GlobalTransform, Transform};
+    use crate::components::{GlobalTransform, StaticTransform, Transform};
     use crate::systems::transform_propagate_system;
     use crate::TransformBundle;
     use bevy_hierarchy::{BuildChildren, BuildWorldChildren, Children, Parent};
@@ -190,6 +222,106 @@ mod test {
         );
     }
 
+    #[test]
+    fn static_hierarchy_is_skipped() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(transform_propagate_system);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage(Update, update_stage);
+
+        let mut child = None;
+        let root = world
+            .spawn((
+                TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)),
+                StaticTransform,
+            ))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn(TransformBundle::from(Transform::from_xyz(0.0, 2.0, 0.0)))
+                        .id(),
+                );
+            })
+            .id();
+        let child = child.unwrap();
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 0.0)
+        );
+
+        // Moving a descendant of a static entity isn't propagated
+        world.get_mut::<Transform>(child).unwrap().translation.y = 3.0;
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 0.0)
+        );
+
+        // Until the static entity moves
+        world.get_mut::<Transform>(root).unwrap().translation.x = 2.0;
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(2.0, 3.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn static_hierarchy_updates_added_descendants() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(transform_propagate_system);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage(Update, update_stage);
+
+        let mut children = Vec::new();
+        world
+            .spawn((
+                TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)),
+                StaticTransform,
+            ))
+            .with_children(|parent| {
+                for y in [2.0, 3.0] {
+                    children.push(
+                        parent
+                            .spawn(TransformBundle::from(Transform::from_xyz(0.0, y, 0.0)))
+                            .id(),
+                    );
+                }
+            });
+        schedule.run(&mut world);
+
+        // A grandchild added under the static root
+        let mut grandchild = None;
+        world.entity_mut(children[0]).with_children(|parent| {
+            grandchild = Some(
+                parent
+                    .spawn(TransformBundle::from(Transform::from_xyz(0.0, 0.0, 4.0)))
+                    .id(),
+            );
+        });
+        let grandchild = grandchild.unwrap();
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(grandchild).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 4.0)
+        );
+
+        // And reparented within it
+        world.entity_mut(children[1]).push_children(&[grandchild]);
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(grandchild).unwrap(),
+            GlobalTransform::from_xyz(1.0, 3.0, 4.0)
+        );
+    }
+
     #[test]
     fn correct_children() {
         let mut world = World::default();
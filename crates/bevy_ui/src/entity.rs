@@ -1,9 +1,11 @@
 //! This module contains the bundles used in Bevy's UI
 
 use crate::{
-    widget::{Button, ImageMode},
-    BackgroundColor, CalculatedSize, FocusPolicy, Interaction, Node, Style, UiImage,
+    widget::{Button, ImageMode, TextInput},
+    BackgroundColor, CalculatedSize, FocusPolicy, Focusable, Interaction, Node, Overflow, Style,
+    UiImage, UiMaterial,
 };
+use bevy_asset::Handle;
 use bevy_ecs::{
     bundle::Bundle,
     prelude::{Component, With},
@@ -64,6 +66,48 @@ impl Default for NodeBundle {
     }
 }
 
+/// A UI node that is drawn with a [`UiMaterial`]
+#[derive(Bundle, Clone, Debug)]
+pub struct MaterialNodeBundle<M: UiMaterial> {
+    /// Describes the size of the node
+    pub node: Node,
+    /// Describes the style including flexbox settings
+    pub style: Style,
+    /// The material of the node
+    pub material: Handle<M>,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `MaterialNodeBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `MaterialNodeBundle`, use the properties of the [`Style`] component.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+impl<M: UiMaterial> Default for MaterialNodeBundle<M> {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            style: Default::default(),
+            material: Default::default(),
+            focus_policy: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+        }
+    }
+}
+
 /// A UI node that is an image
 #[derive(Bundle, Clone, Debug, Default)]
 pub struct ImageBundle {
@@ -230,6 +274,70 @@ impl Default for ButtonBundle {
         }
     }
 }
+/// A UI node that is a single line text input, focused by clicking it or with [`Focusable`]
+/// navigation
+///
+/// Its text, selection and caret are drawn by child nodes, spawned after it.
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    /// Describes the size of the node
+    pub node: Node,
+    /// The text typed in, and how it's drawn
+    pub text_input: TextInput,
+    /// Describes the style including flexbox settings
+    ///
+    /// The overflow is hidden by default, the text being scrolled to keep the caret visible.
+    pub style: Style,
+    /// Describes whether and how the text input has been interacted with by the input
+    pub interaction: Interaction,
+    /// Marker component that lets the text input be focused with the keyboard or a gamepad
+    pub focusable: Focusable,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The background color, which serves as a "fill" for this node
+    ///
+    /// When combined with `UiImage`, tints the provided image.
+    pub background_color: BackgroundColor,
+    /// The image of the node
+    pub image: UiImage,
+    /// The transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `NodeBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `NodeBundle`, use the properties of the [`Style`] component.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        TextInputBundle {
+            text_input: Default::default(),
+            interaction: Default::default(),
+            focusable: Focusable,
+            focus_policy: Default::default(),
+            node: Default::default(),
+            style: Style {
+                overflow: Overflow::Hidden,
+                ..Default::default()
+            },
+            background_color: Default::default(),
+            image: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+        }
+    }
+}
+
 /// Configuration for cameras related to UI.
 ///
 /// When a [`Camera`] doesn't have the [`UiCameraConfig`] component,
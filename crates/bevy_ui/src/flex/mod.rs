@@ -1,6 +1,6 @@
 mod convert;
 
-use crate::{CalculatedSize, Node, Style, UiScale};
+use crate::{CalculatedSize, Node, ScrollPosition, Style, UiScale, Val};
 use bevy_ecs::{
     entity::Entity,
     event::EventReader,
@@ -225,6 +225,7 @@ pub fn flex_node_system(
     children_query: Query<(Entity, &Children), (With<Node>, Changed<Children>)>,
     removed_children: RemovedComponents<Children>,
     mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
+    mut scroll_query: Query<(Entity, &Style, &mut ScrollPosition, Option<&Children>), With<Node>>,
     removed_nodes: RemovedComponents<Node>,
 ) {
     // update window root nodes
@@ -285,6 +286,43 @@ pub fn flex_node_system(
 
     let to_logical = |v| (physical_to_logical_factor * v as f64) as f32;
 
+    // The scroll positions are clamped to the children of their nodes
+    for (entity, style, mut scroll_position, children) in &mut scroll_query {
+        let layout = match flex_surface.get_layout(entity) {
+            Ok(layout) => layout,
+            Err(_) => continue,
+        };
+        let mut content_size = Vec2::ZERO;
+        for child in children.into_iter().flatten() {
+            if let Ok(child_layout) = flex_surface.get_layout(*child) {
+                content_size = content_size.max(Vec2::new(
+                    to_logical(child_layout.location.x + child_layout.size.width),
+                    to_logical(child_layout.location.y + child_layout.size.height),
+                ));
+            }
+        }
+        // The padding and the border after the children are scrolled into view as well
+        let px = |val| match val {
+            Val::Px(value) => value * ui_scale.scale as f32,
+            _ => 0.0,
+        };
+        content_size += Vec2::new(
+            px(style.padding.right) + px(style.border.right),
+            px(style.padding.bottom) + px(style.border.bottom),
+        );
+        let node_size = Vec2::new(
+            to_logical(layout.size.width),
+            to_logical(layout.size.height),
+        );
+        let max_offset = (content_size - node_size).max(Vec2::ZERO);
+        let offset = scroll_position.offset.clamp(Vec2::ZERO, max_offset);
+        // only trigger change detection when the new value is different
+        if scroll_position.max_offset != max_offset || scroll_position.offset != offset {
+            scroll_position.max_offset = max_offset;
+            scroll_position.offset = offset;
+        }
+    }
+
     // PERF: try doing this incrementally
     for (entity, mut node, mut transform, parent) in &mut node_transform_query {
         let layout = flex_surface.get_layout(entity).unwrap();
@@ -304,6 +342,10 @@ pub fn flex_node_system(
                 new_position.x -= to_logical(parent_layout.size.width / 2.0);
                 new_position.y -= to_logical(parent_layout.size.height / 2.0);
             }
+            if let Ok((_, _, scroll_position, _)) = scroll_query.get(**parent) {
+                new_position.x -= scroll_position.offset.x;
+                new_position.y -= scroll_position.offset.y;
+            }
         }
         // only trigger change detection when the new value is different
         if transform.translation != new_position {
@@ -1,4 +1,4 @@
-use crate::{entity::UiCameraConfig, CalculatedClip, Node};
+use crate::{entity::UiCameraConfig, CalculatedClip, Node, UiFocus};
 use bevy_ecs::{
     entity::Entity,
     prelude::Component,
@@ -64,9 +64,13 @@ pub struct State {
 
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
 ///
-/// Entities with a hidden [`ComputedVisibility`] are always treated as released.
+/// Entities with a hidden [`ComputedVisibility`] are always treated as released. The node that has
+/// the [`UiFocus`] is left to the [`ui_navigation_system`](crate::ui_navigation_system) when the
+/// cursor isn't over it.
+#[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
+    ui_focus: Res<UiFocus>,
     camera: Query<(&Camera, Option<&UiCameraConfig>)>,
     windows: Res<Windows>,
     mouse_button_input: Res<Input<MouseButton>>,
@@ -163,6 +167,9 @@ pub fn ui_focus_system(
                 if contains_cursor {
                     Some((entity, focus_policy, interaction, FloatOrd(position.z)))
                 } else {
+                    if ui_focus.entity == Some(entity) {
+                        return None;
+                    }
                     if let Some(mut interaction) = interaction {
                         if *interaction == Interaction::Hovered
                             || (cursor_position.is_none() && *interaction != Interaction::None)
@@ -205,7 +212,10 @@ pub fn ui_focus_system(
         }
     }
     // reset lower nodes to None
-    for (_entity, _focus_policy, interaction, _) in moused_over_z_sorted_nodes {
+    for (entity, _focus_policy, interaction, _) in moused_over_z_sorted_nodes {
+        if ui_focus.entity == Some(entity) {
+            continue;
+        }
         if let Some(mut interaction) = interaction {
             // don't reset clicked nodes because they're handled separately
             if *interaction != Interaction::Clicked && *interaction != Interaction::None {
@@ -5,8 +5,11 @@
 mod flex;
 mod focus;
 mod geometry;
+mod navigation;
 mod render;
+mod scroll;
 mod ui_node;
+mod world_anchor;
 
 pub mod entity;
 pub mod update;
@@ -16,13 +19,22 @@ use bevy_render::extract_component::ExtractComponentPlugin;
 pub use flex::*;
 pub use focus::*;
 pub use geometry::*;
+pub use navigation::*;
 pub use render::*;
+pub use scroll::*;
 pub use ui_node::*;
+pub use world_anchor::*;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{entity::*, geometry::*, ui_node::*, widget::Button, Interaction, UiScale};
+    pub use crate::{
+        entity::*,
+        geometry::*,
+        ui_node::*,
+        widget::{Button, TextInput, TextInputSubmit, UiClipboard},
+        Focusable, Interaction, UiFocus, UiScale, WorldAnchor,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -48,6 +60,13 @@ pub enum UiSystem {
     Flex,
     /// After this label, input interactions with UI entities have been updated for this frame
     Focus,
+    /// After this label, the [`UiFocus`] and the interaction of the focused entity have been
+    /// updated for this frame
+    Navigation,
+    /// After this label, the nodes with [`Overflow::Scroll`] have been scrolled for this frame
+    Scroll,
+    /// After this label, the focused [`widget::TextInput`] has been edited for this frame
+    TextInput,
 }
 
 /// The current scale of the UI.
@@ -71,6 +90,9 @@ impl Plugin for UiPlugin {
         app.add_plugin(ExtractComponentPlugin::<UiCameraConfig>::default())
             .init_resource::<FlexSurface>()
             .init_resource::<UiScale>()
+            .init_resource::<UiFocus>()
+            .init_resource::<widget::UiClipboard>()
+            .add_event::<widget::TextInputSubmit>()
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
@@ -79,6 +101,7 @@ impl Plugin for UiPlugin {
             .register_type::<Display>()
             .register_type::<FlexDirection>()
             .register_type::<FlexWrap>()
+            .register_type::<Focusable>()
             .register_type::<FocusPolicy>()
             .register_type::<Interaction>()
             .register_type::<JustifyContent>()
@@ -87,10 +110,14 @@ impl Plugin for UiPlugin {
             .register_type::<Option<f32>>()
             .register_type::<Overflow>()
             .register_type::<PositionType>()
+            .register_type::<ScrollPosition>()
             .register_type::<Size>()
             .register_type::<UiRect>()
             .register_type::<Style>()
             .register_type::<BackgroundColor>()
+            .register_type::<BorderColor>()
+            .register_type::<BorderRadius>()
+            .register_type::<BoxShadow>()
             .register_type::<UiImage>()
             .register_type::<Val>()
             .register_type::<widget::Button>()
@@ -99,6 +126,25 @@ impl Plugin for UiPlugin {
                 CoreStage::PreUpdate,
                 ui_focus_system.label(UiSystem::Focus).after(InputSystem),
             )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_navigation_system
+                    .label(UiSystem::Navigation)
+                    .after(UiSystem::Focus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_scroll_system
+                    .label(UiSystem::Scroll)
+                    .after(InputSystem)
+                    .after(UiSystem::Focus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::text_input_system
+                    .label(UiSystem::TextInput)
+                    .after(UiSystem::Navigation),
+            )
             // add these stages to front because these must run before transform update systems
             .add_system_to_stage(
                 CoreStage::PostUpdate,
@@ -106,6 +152,14 @@ impl Plugin for UiPlugin {
                     .before(UiSystem::Flex)
                     .after(ModifiesWindows),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::text_input_parts_system.before(widget::text_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                world_anchor_system.before(UiSystem::Flex),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 widget::image_node_system.before(UiSystem::Flex),
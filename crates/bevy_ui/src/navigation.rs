@@ -0,0 +1,289 @@
+use crate::{widget::TextInput, Interaction, Node};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::With,
+    reflect::ReflectComponent,
+    system::{Local, Query, Res, ResMut, Resource},
+};
+use bevy_input::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, Input,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::ComputedVisibility;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::FloatOrd;
+
+/// Marks a UI node that can be focused with the keyboard or a gamepad, moving the focus between
+/// these nodes in the direction of the arrow keys, the D-pad or the left stick.
+///
+/// The focused node is [`Interaction::Hovered`], and [`Interaction::Clicked`] while `Return`,
+/// `Space` or the south button of a gamepad is pressed. Clicking a node with the mouse focuses
+/// it as well.
+#[derive(Component, Copy, Clone, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Focusable;
+
+/// The [`Focusable`] node that has the focus, if any.
+///
+/// Updated in [`ui_navigation_system`], and can be set to move the focus to another node.
+#[derive(Resource, Default, Debug)]
+pub struct UiFocus {
+    pub entity: Option<Entity>,
+}
+
+/// How far the left stick of a gamepad has to be tilted to move the focus
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The direction of the left stick of each gamepad in the previous frame, so that the focus only
+/// moves once each time it's tilted
+#[derive(Default)]
+pub struct NavigationState {
+    stick_directions: Vec<(Gamepad, Vec2)>,
+}
+
+/// The system that moves the [`UiFocus`] between the [`Focusable`] nodes, and sets the
+/// [`Interaction`] of the focused one
+#[allow(clippy::too_many_arguments)]
+pub fn ui_navigation_system(
+    mut state: Local<NavigationState>,
+    mut ui_focus: ResMut<UiFocus>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut node_query: Query<(
+        Entity,
+        &Node,
+        &GlobalTransform,
+        &Focusable,
+        Option<&mut Interaction>,
+        Option<&ComputedVisibility>,
+    )>,
+    text_input_query: Query<(), With<TextInput>>,
+) {
+    // A focused text input uses the keyboard to edit its text instead
+    let keyboard_captured = match ui_focus.entity {
+        Some(entity) => text_input_query.contains(entity),
+        None => false,
+    };
+
+    // The direction is in the space of the UI, whose Y goes down
+    let mut direction = None;
+    for (key, key_direction) in [
+        (KeyCode::Up, Vec2::NEG_Y),
+        (KeyCode::Down, Vec2::Y),
+        (KeyCode::Left, Vec2::NEG_X),
+        (KeyCode::Right, Vec2::X),
+    ] {
+        if !keyboard_captured && keyboard_input.just_pressed(key) {
+            direction = Some(key_direction);
+        }
+    }
+    let mut activate_pressed = !keyboard_captured
+        && (keyboard_input.pressed(KeyCode::Return) || keyboard_input.pressed(KeyCode::Space));
+    let mut activate_just_pressed = !keyboard_captured
+        && (keyboard_input.just_pressed(KeyCode::Return)
+            || keyboard_input.just_pressed(KeyCode::Space));
+
+    state
+        .stick_directions
+        .retain(|(gamepad, _)| gamepads.contains(*gamepad));
+    for gamepad in gamepads.iter() {
+        for (button_type, button_direction) in [
+            (GamepadButtonType::DPadUp, Vec2::NEG_Y),
+            (GamepadButtonType::DPadDown, Vec2::Y),
+            (GamepadButtonType::DPadLeft, Vec2::NEG_X),
+            (GamepadButtonType::DPadRight, Vec2::X),
+        ] {
+            if gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)) {
+                direction = Some(button_direction);
+            }
+        }
+        let south = GamepadButton::new(gamepad, GamepadButtonType::South);
+        activate_pressed |= gamepad_buttons.pressed(south);
+        activate_just_pressed |= gamepad_buttons.just_pressed(south);
+
+        let stick = Vec2::new(
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                .unwrap_or(0.0),
+            -gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+                .unwrap_or(0.0),
+        );
+        let stick_direction = stick_direction(stick);
+        let previous = state
+            .stick_directions
+            .iter_mut()
+            .find(|(previous_gamepad, _)| *previous_gamepad == gamepad);
+        match previous {
+            Some((_, previous_direction)) => {
+                if stick_direction != Vec2::ZERO && stick_direction != *previous_direction {
+                    direction = Some(stick_direction);
+                }
+                *previous_direction = stick_direction;
+            }
+            None => state.stick_directions.push((gamepad, stick_direction)),
+        }
+    }
+
+    let mut focused = ui_focus
+        .entity
+        .filter(|entity| match node_query.get(*entity) {
+            Ok((.., visibility)) => is_visible(visibility),
+            Err(_) => false,
+        });
+
+    // Clicking a node with the mouse focuses it
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        for (entity, _, _, _, interaction, _) in &node_query {
+            if interaction == Some(&Interaction::Clicked) {
+                focused = Some(entity);
+            }
+        }
+    }
+
+    if let Some(direction) = direction {
+        let candidates = node_query
+            .iter()
+            .filter(|(.., visibility)| is_visible(*visibility))
+            .map(|(entity, node, transform, ..)| {
+                (entity, transform.translation().truncate(), node.size())
+            });
+        let from = focused
+            .and_then(|entity| node_query.get(entity).ok())
+            .map(|(_, _, transform, ..)| transform.translation().truncate());
+        focused = match from {
+            Some(from) => find_in_direction(
+                from,
+                direction,
+                candidates.map(|(entity, position, _)| (entity, position)),
+            )
+            .or(focused),
+            // Without a focused node, the focus starts at the top left one
+            None => candidates
+                .min_by_key(|(_, position, size)| {
+                    let top_left = *position - *size / 2.0;
+                    (FloatOrd(top_left.y), FloatOrd(top_left.x))
+                })
+                .map(|(entity, ..)| entity),
+        };
+    }
+
+    if focused != ui_focus.entity {
+        if let Some(previous) = ui_focus.entity {
+            if let Ok((.., Some(mut interaction), _)) = node_query.get_mut(previous) {
+                if *interaction == Interaction::Hovered {
+                    *interaction = Interaction::None;
+                }
+            }
+        }
+        ui_focus.entity = focused;
+    }
+
+    if let Some(entity) = focused {
+        if let Ok((.., Some(mut interaction), _)) = node_query.get_mut(entity) {
+            let mouse_pressed = mouse_button_input.pressed(MouseButton::Left);
+            // only trigger change detection when the new value is different
+            if activate_just_pressed {
+                if *interaction != Interaction::Clicked {
+                    *interaction = Interaction::Clicked;
+                }
+            } else if *interaction == Interaction::None
+                || (*interaction == Interaction::Clicked && !activate_pressed && !mouse_pressed)
+            {
+                *interaction = Interaction::Hovered;
+            }
+        }
+    }
+}
+
+/// Nodes that are not rendered can't be focused
+fn is_visible(visibility: Option<&ComputedVisibility>) -> bool {
+    match visibility {
+        Some(visibility) => visibility.is_visible(),
+        None => true,
+    }
+}
+
+/// The direction of the left stick, snapped to the closest axis, or zero if it's not tilted enough
+fn stick_direction(stick: Vec2) -> Vec2 {
+    if stick.length() < STICK_THRESHOLD {
+        Vec2::ZERO
+    } else if stick.x.abs() > stick.y.abs() {
+        Vec2::new(stick.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, stick.y.signum())
+    }
+}
+
+/// Finds the closest node in a direction from a position, the nodes that are off to the side
+/// being farther than the ones straight ahead
+pub fn find_in_direction(
+    from: Vec2,
+    direction: Vec2,
+    candidates: impl Iterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    candidates
+        .filter_map(|(entity, position)| {
+            let offset = position - from;
+            let along = offset.dot(direction);
+            if along <= 0.0 {
+                return None;
+            }
+            let across = (offset - along * direction).length();
+            Some((entity, FloatOrd(along + 2.0 * across)))
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(entity, _)| entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_in_direction, stick_direction};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn directional_navigation() {
+        let left = Entity::from_raw(0);
+        let right = Entity::from_raw(1);
+        let below = Entity::from_raw(2);
+        let below_right = Entity::from_raw(3);
+        let candidates = [
+            (left, Vec2::new(0.0, 0.0)),
+            (right, Vec2::new(100.0, 0.0)),
+            (below, Vec2::new(0.0, 50.0)),
+            (below_right, Vec2::new(100.0, 50.0)),
+        ];
+        let find = |from: Vec2, direction| {
+            find_in_direction(
+                from,
+                direction,
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|(_, position)| *position != from),
+            )
+        };
+
+        assert_eq!(find(Vec2::ZERO, Vec2::X), Some(right));
+        assert_eq!(find(Vec2::ZERO, Vec2::Y), Some(below));
+        assert_eq!(find(Vec2::ZERO, Vec2::NEG_X), None);
+        assert_eq!(find(Vec2::new(0.0, 50.0), Vec2::X), Some(below_right));
+        assert_eq!(find(Vec2::new(100.0, 50.0), Vec2::NEG_Y), Some(right));
+    }
+
+    #[test]
+    fn stick_snapping() {
+        assert_eq!(stick_direction(Vec2::new(0.2, -0.1)), Vec2::ZERO);
+        assert_eq!(stick_direction(Vec2::new(0.8, -0.3)), Vec2::X);
+        assert_eq!(stick_direction(Vec2::new(0.3, -0.9)), Vec2::NEG_Y);
+    }
+}
@@ -0,0 +1,679 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventReader,
+    prelude::{Component, World},
+    schedule::IntoSystemDescriptor,
+    system::{
+        lifetimeless::{Read, SQuery, SRes},
+        Commands, Local, Query, Res, ResMut, Resource, SystemParamItem,
+    },
+    world::FromWorld,
+};
+use bevy_math::{Mat4, Rect, Vec2, Vec4Swizzles};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    render_asset::{PrepareAssetLabel, RenderAssets},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, EntityRenderCommand, RenderCommandResult, RenderPhase,
+        SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{FallbackImage, Image},
+    view::ComputedVisibility,
+    Extract, RenderApp, RenderStage,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{FloatOrd, HashMap, HashSet};
+use bytemuck::{Pod, Zeroable};
+
+use super::{
+    RenderUiSystem, SetUiViewBindGroup, TransparentUi, UiPipeline, QUAD_INDICES,
+    QUAD_VERTEX_POSITIONS, UI_MATERIAL_SHADER_HANDLE,
+};
+use crate::{CalculatedClip, Node};
+
+/// Materials are used alongside [`UiMaterialPlugin`] and [`MaterialNodeBundle`](crate::entity::MaterialNodeBundle)
+/// to draw UI nodes with a specific [`UiMaterial`] type. They serve as an easy to use high level
+/// way to draw progress bars, minimaps or animated backgrounds with custom shader logic.
+///
+/// UI materials must implement [`AsBindGroup`] to define how data will be transferred to the GPU and bound in shaders.
+/// [`AsBindGroup`] can be derived, which makes generating bindings straightforward. See the [`AsBindGroup`] docs for details.
+///
+/// Materials must also implement [`TypeUuid`] so they can be treated as an [`Asset`](bevy_asset::Asset).
+///
+/// # Example
+///
+/// Here is a simple [`UiMaterial`] implementation. The [`AsBindGroup`] derive has many features. To see what else is available,
+/// check out the [`AsBindGroup`] documentation.
+/// ```
+/// # use bevy_ui::{UiMaterial, entity::MaterialNodeBundle, Style, Size, Val};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypeUuid;
+/// # use bevy_render::{render_resource::{AsBindGroup, ShaderRef}, color::Color};
+/// # use bevy_asset::Assets;
+///
+/// #[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+/// #[uuid = "3f1e6a7c-2b1d-4b4e-9a45-1f6c3c0e8d21"]
+/// pub struct ProgressBarMaterial {
+///     #[uniform(0)]
+///     color: Color,
+///     #[uniform(0)]
+///     progress: f32,
+/// }
+///
+/// // All functions on `UiMaterial` have default impls. You only need to implement the
+/// // functions that are relevant for your material.
+/// impl UiMaterial for ProgressBarMaterial {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/progress_bar.wgsl".into()
+///     }
+/// }
+///
+/// // Spawn a node using `ProgressBarMaterial`.
+/// fn setup(mut commands: Commands, mut materials: ResMut<Assets<ProgressBarMaterial>>) {
+///     commands.spawn(MaterialNodeBundle {
+///         style: Style {
+///             size: Size::new(Val::Px(200.0), Val::Px(20.0)),
+///             ..Default::default()
+///         },
+///         material: materials.add(ProgressBarMaterial {
+///             color: Color::GREEN,
+///             progress: 0.5,
+///         }),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+/// In WGSL shaders, the material's binding would look like this, the fragment shader receiving
+/// the UVs and the size of the node:
+///
+/// ```wgsl
+/// struct ProgressBarMaterial {
+///     color: vec4<f32>,
+///     progress: f32,
+/// }
+///
+/// @group(1) @binding(0)
+/// var<uniform> material: ProgressBarMaterial;
+///
+/// @fragment
+/// fn fragment(
+///     #import bevy_ui::ui_vertex_output
+/// ) -> @location(0) vec4<f32> {
+///     return select(vec4<f32>(0.0), material.color, uv.x < material.progress);
+/// }
+/// ```
+pub trait UiMaterial: AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'static {
+    /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default UI vertex shader
+    /// will be used.
+    fn vertex_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the node is
+    /// filled with white.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Customizes the default [`RenderPipelineDescriptor`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: UiMaterialKey<Self>) {}
+}
+
+/// Adds the necessary ECS resources and render logic to enable drawing UI nodes using the given
+/// [`UiMaterial`] asset type.
+pub struct UiMaterialPlugin<M: UiMaterial>(PhantomData<M>);
+
+impl<M: UiMaterial> Default for UiMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: UiMaterial> Plugin for UiMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        app.add_asset::<M>();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<TransparentUi, DrawUiMaterial<M>>()
+                .init_resource::<UiMaterialPipeline<M>>()
+                .init_resource::<ExtractedUiMaterials<M>>()
+                .init_resource::<ExtractedUiMaterialNodes<M>>()
+                .init_resource::<RenderUiMaterials<M>>()
+                .init_resource::<UiMaterialMeta<M>>()
+                .init_resource::<SpecializedRenderPipelines<UiMaterialPipeline<M>>>()
+                .add_system_to_stage(RenderStage::Extract, extract_ui_materials::<M>)
+                .add_system_to_stage(
+                    RenderStage::Extract,
+                    extract_ui_material_nodes::<M>.after(RenderUiSystem::ExtractNode),
+                )
+                .add_system_to_stage(
+                    RenderStage::Prepare,
+                    prepare_ui_materials::<M>.after(PrepareAssetLabel::PreAssetPrepare),
+                )
+                .add_system_to_stage(RenderStage::Prepare, prepare_ui_material_nodes::<M>)
+                .add_system_to_stage(RenderStage::Queue, queue_ui_material_nodes::<M>);
+        }
+    }
+}
+
+/// Render pipeline data for a given [`UiMaterial`]
+#[derive(Resource)]
+pub struct UiMaterialPipeline<M: UiMaterial> {
+    pub view_layout: BindGroupLayout,
+    pub ui_material_layout: BindGroupLayout,
+    pub vertex_shader: Handle<Shader>,
+    pub fragment_shader: Handle<Shader>,
+    pub texture_format: TextureFormat,
+    marker: PhantomData<M>,
+}
+
+pub struct UiMaterialKey<M: UiMaterial> {
+    pub bind_group_data: M::Data,
+}
+
+impl<M: UiMaterial> Eq for UiMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: UiMaterial> PartialEq for UiMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: UiMaterial> Clone for UiMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: UiMaterial> Hash for UiMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.bind_group_data.hash(state);
+    }
+}
+
+impl<M: UiMaterial> SpecializedRenderPipeline for UiMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = UiMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let vertex_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Vertex,
+            vec![
+                // position
+                VertexFormat::Float32x3,
+                // uv
+                VertexFormat::Float32x2,
+                // size
+                VertexFormat::Float32x2,
+            ],
+        );
+
+        let mut descriptor = RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: self.vertex_shader.clone(),
+                entry_point: "vertex".into(),
+                shader_defs: Vec::new(),
+                buffers: vec![vertex_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: self.fragment_shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: self.texture_format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![
+                self.view_layout.clone(),
+                self.ui_material_layout.clone(),
+            ]),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("ui_material_pipeline".into()),
+        };
+
+        M::specialize(&mut descriptor, key);
+        descriptor
+    }
+}
+
+impl<M: UiMaterial> FromWorld for UiMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let ui_pipeline = world.resource::<UiPipeline>();
+        let ui_material_layout = M::bind_group_layout(render_device);
+        let load_shader = |shader| match shader {
+            ShaderRef::Default => UI_MATERIAL_SHADER_HANDLE.typed(),
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => asset_server.load(path),
+        };
+
+        UiMaterialPipeline {
+            view_layout: ui_pipeline.view_layout.clone(),
+            ui_material_layout,
+            vertex_shader: load_shader(M::vertex_shader()),
+            fragment_shader: load_shader(M::fragment_shader()),
+            texture_format: ui_pipeline.dummy_white_gpu_image.texture_format,
+            marker: PhantomData,
+        }
+    }
+}
+
+pub type DrawUiMaterial<M> = (
+    SetItemPipeline,
+    SetUiViewBindGroup<0>,
+    SetUiMaterialBindGroup<M, 1>,
+    DrawUiMaterialNode<M>,
+);
+
+pub struct SetUiMaterialBindGroup<M: UiMaterial, const I: usize>(PhantomData<M>);
+impl<M: UiMaterial, const I: usize> EntityRenderCommand for SetUiMaterialBindGroup<M, I> {
+    type Param = (SRes<RenderUiMaterials<M>>, SQuery<Read<UiMaterialBatch<M>>>);
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (materials, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let batch = query_batch.get(item).unwrap();
+        let material = match materials.into_inner().get(&batch.material) {
+            Some(material) => material,
+            None => return RenderCommandResult::Failure,
+        };
+        pass.set_bind_group(I, &material.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct DrawUiMaterialNode<M: UiMaterial>(PhantomData<M>);
+impl<M: UiMaterial> EntityRenderCommand for DrawUiMaterialNode<M> {
+    type Param = (SRes<UiMaterialMeta<M>>, SQuery<Read<UiMaterialBatch<M>>>);
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (ui_meta, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let batch = query_batch.get(item).unwrap();
+
+        pass.set_vertex_buffer(0, ui_meta.into_inner().vertices.buffer().unwrap().slice(..));
+        pass.draw(batch.range.clone(), 0..1);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct ExtractedUiMaterialNode<M: UiMaterial> {
+    pub transform: Mat4,
+    pub size: Vec2,
+    pub material: Handle<M>,
+    pub clip: Option<Rect>,
+}
+
+#[derive(Resource)]
+pub struct ExtractedUiMaterialNodes<M: UiMaterial> {
+    pub uinodes: Vec<ExtractedUiMaterialNode<M>>,
+}
+
+impl<M: UiMaterial> Default for ExtractedUiMaterialNodes<M> {
+    fn default() -> Self {
+        Self {
+            uinodes: Default::default(),
+        }
+    }
+}
+
+pub fn extract_ui_material_nodes<M: UiMaterial>(
+    mut extracted_uinodes: ResMut<ExtractedUiMaterialNodes<M>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &Handle<M>,
+            &ComputedVisibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+) {
+    extracted_uinodes.uinodes.clear();
+    for (uinode, transform, material, visibility, clip) in uinode_query.iter() {
+        if !visibility.is_visible() {
+            continue;
+        }
+        // Skip if size is set to zero (e.g. when a parent is set to `Display::None`)
+        if uinode.calculated_size == Vec2::ZERO {
+            continue;
+        }
+        extracted_uinodes.uinodes.push(ExtractedUiMaterialNode {
+            transform: transform.compute_matrix(),
+            size: uinode.calculated_size,
+            material: material.clone_weak(),
+            clip: clip.map(|clip| clip.clip),
+        });
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct UiMaterialVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub size: [f32; 2],
+}
+
+#[derive(Resource)]
+pub struct UiMaterialMeta<M: UiMaterial> {
+    vertices: BufferVec<UiMaterialVertex>,
+    marker: PhantomData<M>,
+}
+
+impl<M: UiMaterial> Default for UiMaterialMeta<M> {
+    fn default() -> Self {
+        Self {
+            vertices: BufferVec::new(BufferUsages::VERTEX),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct UiMaterialBatch<M: UiMaterial> {
+    pub range: Range<u32>,
+    pub material: Handle<M>,
+    pub z: f32,
+}
+
+pub fn prepare_ui_material_nodes<M: UiMaterial>(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut ui_meta: ResMut<UiMaterialMeta<M>>,
+    mut extracted_uinodes: ResMut<ExtractedUiMaterialNodes<M>>,
+) {
+    ui_meta.vertices.clear();
+
+    // sort by increasing z for correct transparency
+    extracted_uinodes
+        .uinodes
+        .sort_by(|a, b| FloatOrd(a.transform.w_axis[2]).cmp(&FloatOrd(b.transform.w_axis[2])));
+
+    let mut start = 0;
+    let mut end = 0;
+    let mut current_batch_handle: Handle<M> = Default::default();
+    let mut last_z = 0.0;
+    for extracted_uinode in &extracted_uinodes.uinodes {
+        if current_batch_handle != extracted_uinode.material {
+            if start != end {
+                commands.spawn(UiMaterialBatch {
+                    range: start..end,
+                    material: current_batch_handle,
+                    z: last_z,
+                });
+                start = end;
+            }
+            current_batch_handle = extracted_uinode.material.clone_weak();
+        }
+
+        let size = extracted_uinode.size.extend(1.0);
+        let mut positions = QUAD_VERTEX_POSITIONS
+            .map(|pos| (extracted_uinode.transform * (pos * size).extend(1.)).xyz());
+        let mut uvs = QUAD_VERTEX_POSITIONS.map(|pos| pos.truncate() + 0.5);
+
+        // The corners are clipped along with the UVs, which doesn't work with a rotation
+        if let Some(clip) = extracted_uinode.clip {
+            if extracted_uinode.transform.x_axis[1] == 0.0 {
+                let extent = positions[2].truncate() - positions[0].truncate();
+                for (position, uv) in positions.iter_mut().zip(&mut uvs) {
+                    let clipped = position.truncate().max(clip.min).min(clip.max);
+                    *uv += (clipped - position.truncate()) / extent;
+                    *position = clipped.extend(position.z);
+                }
+                // Cull nodes that are completely clipped
+                if positions[0].x >= positions[2].x || positions[0].y >= positions[2].y {
+                    continue;
+                }
+            }
+        }
+
+        for i in QUAD_INDICES {
+            ui_meta.vertices.push(UiMaterialVertex {
+                position: positions[i].into(),
+                uv: uvs[i].into(),
+                size: extracted_uinode.size.into(),
+            });
+        }
+
+        last_z = extracted_uinode.transform.w_axis[2];
+        end += QUAD_INDICES.len() as u32;
+    }
+
+    // if start != end, there is one last batch to process
+    if start != end {
+        commands.spawn(UiMaterialBatch {
+            range: start..end,
+            material: current_batch_handle,
+            z: last_z,
+        });
+    }
+
+    ui_meta.vertices.write_buffer(&render_device, &render_queue);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_ui_material_nodes<M: UiMaterial>(
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    ui_material_pipeline: Res<UiMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UiMaterialPipeline<M>>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    render_materials: Res<RenderUiMaterials<M>>,
+    ui_batches: Query<(Entity, &UiMaterialBatch<M>)>,
+    mut views: Query<&mut RenderPhase<TransparentUi>>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if ui_batches.is_empty() {
+        return;
+    }
+    let draw_ui_material_function = draw_functions.read().get_id::<DrawUiMaterial<M>>().unwrap();
+    for mut transparent_phase in &mut views {
+        for (entity, batch) in &ui_batches {
+            let material = match render_materials.get(&batch.material) {
+                Some(material) => material,
+                None => continue,
+            };
+            let pipeline = pipelines.specialize(
+                &mut pipeline_cache,
+                &ui_material_pipeline,
+                UiMaterialKey {
+                    bind_group_data: material.key.clone(),
+                },
+            );
+            transparent_phase.add(TransparentUi {
+                draw_function: draw_ui_material_function,
+                pipeline,
+                entity,
+                sort_key: FloatOrd(batch.z),
+            });
+        }
+    }
+}
+
+/// Data prepared for a [`UiMaterial`] instance.
+pub struct PreparedUiMaterial<T: UiMaterial> {
+    pub bindings: Vec<OwnedBindingResource>,
+    pub bind_group: BindGroup,
+    pub key: T::Data,
+}
+
+#[derive(Resource)]
+struct ExtractedUiMaterials<M: UiMaterial> {
+    extracted: Vec<(Handle<M>, M)>,
+    removed: Vec<Handle<M>>,
+}
+
+impl<M: UiMaterial> Default for ExtractedUiMaterials<M> {
+    fn default() -> Self {
+        Self {
+            extracted: Default::default(),
+            removed: Default::default(),
+        }
+    }
+}
+
+/// Stores all prepared representations of [`UiMaterial`] assets for as long as they exist.
+#[derive(Resource, Deref, DerefMut)]
+pub struct RenderUiMaterials<T: UiMaterial>(HashMap<Handle<T>, PreparedUiMaterial<T>>);
+
+impl<T: UiMaterial> Default for RenderUiMaterials<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// This system extracts all created or modified assets of the corresponding [`UiMaterial`] type
+/// into the "render world".
+fn extract_ui_materials<M: UiMaterial>(
+    mut commands: Commands,
+    mut events: Extract<EventReader<AssetEvent<M>>>,
+    assets: Extract<Res<Assets<M>>>,
+) {
+    let mut changed_assets = HashSet::default();
+    let mut removed = Vec::new();
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed_assets.insert(handle.clone_weak());
+            }
+            AssetEvent::Removed { handle } => {
+                changed_assets.remove(handle);
+                removed.push(handle.clone_weak());
+            }
+        }
+    }
+
+    let mut extracted_assets = Vec::new();
+    for handle in changed_assets.drain() {
+        if let Some(asset) = assets.get(&handle) {
+            extracted_assets.push((handle, asset.clone()));
+        }
+    }
+
+    commands.insert_resource(ExtractedUiMaterials {
+        extracted: extracted_assets,
+        removed,
+    });
+}
+
+/// All [`UiMaterial`] values of a given type that should be prepared next frame.
+pub struct PrepareNextFrameUiMaterials<M: UiMaterial> {
+    assets: Vec<(Handle<M>, M)>,
+}
+
+impl<M: UiMaterial> Default for PrepareNextFrameUiMaterials<M> {
+    fn default() -> Self {
+        Self {
+            assets: Default::default(),
+        }
+    }
+}
+
+/// This system prepares all assets of the corresponding [`UiMaterial`] type
+/// which where extracted this frame for the GPU.
+fn prepare_ui_materials<M: UiMaterial>(
+    mut prepare_next_frame: Local<PrepareNextFrameUiMaterials<M>>,
+    mut extracted_assets: ResMut<ExtractedUiMaterials<M>>,
+    mut render_materials: ResMut<RenderUiMaterials<M>>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    fallback_image: Res<FallbackImage>,
+    pipeline: Res<UiMaterialPipeline<M>>,
+) {
+    let queued_assets = std::mem::take(&mut prepare_next_frame.assets);
+    for (handle, material) in queued_assets
+        .into_iter()
+        .chain(std::mem::take(&mut extracted_assets.extracted))
+    {
+        match prepare_ui_material(
+            &material,
+            &render_device,
+            &images,
+            &fallback_image,
+            &pipeline,
+        ) {
+            Ok(prepared_asset) => {
+                render_materials.insert(handle, prepared_asset);
+            }
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                prepare_next_frame.assets.push((handle, material));
+            }
+        }
+    }
+
+    for removed in std::mem::take(&mut extracted_assets.removed) {
+        render_materials.remove(&removed);
+    }
+}
+
+fn prepare_ui_material<M: UiMaterial>(
+    material: &M,
+    render_device: &RenderDevice,
+    images: &RenderAssets<Image>,
+    fallback_image: &FallbackImage,
+    pipeline: &UiMaterialPipeline<M>,
+) -> Result<PreparedUiMaterial<M>, AsBindGroupError> {
+    let prepared = material.as_bind_group(
+        &pipeline.ui_material_layout,
+        render_device,
+        images,
+        fallback_image,
+    )?;
+    Ok(PreparedUiMaterial {
+        bindings: prepared.bindings,
+        bind_group: prepared.bind_group,
+        key: prepared.data,
+    })
+}
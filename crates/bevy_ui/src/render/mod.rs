@@ -1,14 +1,20 @@
+mod material;
 mod pipeline;
 mod render_pass;
 
 use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
+pub use material::*;
 pub use pipeline::*;
 pub use render_pass::*;
 
-use crate::{prelude::UiCameraConfig, BackgroundColor, CalculatedClip, Node, UiImage};
+use crate::{
+    prelude::UiCameraConfig, BackgroundColor, BorderColor, BorderRadius, BoxShadow, CalculatedClip,
+    Node, Style, UiImage, UiRect, Val,
+};
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle, HandleUntyped};
 use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
 use bevy_math::{Mat4, Rect, UVec4, Vec2, Vec3, Vec4Swizzles};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
@@ -19,12 +25,12 @@ use bevy_render::{
     render_phase::{sort_phase_system, AddRenderCommand, DrawFunctions, RenderPhase},
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
-    texture::Image,
+    texture::{Image, DEFAULT_IMAGE_HANDLE},
     view::{ComputedVisibility, ExtractedView, ViewUniforms},
     Extract, RenderApp, RenderStage,
 };
-use bevy_sprite::{SpriteAssetEvents, TextureAtlas};
-use bevy_text::{Text, TextLayoutInfo};
+use bevy_sprite::{ImageScaleMode, SpriteAssetEvents, TextureAtlas};
+use bevy_text::{GlyphLayer, Text, TextLayoutInfo};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
@@ -49,6 +55,12 @@ pub mod draw_ui_graph {
 pub const UI_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13012847047162779583);
 
+pub const UI_MATERIAL_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10074188772096983955);
+
+pub const UI_VERTEX_OUTPUT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2810523541726412657);
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderUiSystem {
     ExtractNode,
@@ -56,6 +68,18 @@ pub enum RenderUiSystem {
 
 pub fn build_ui_render(app: &mut App) {
     load_internal_asset!(app, UI_SHADER_HANDLE, "ui.wgsl", Shader::from_wgsl);
+    load_internal_asset!(
+        app,
+        UI_MATERIAL_SHADER_HANDLE,
+        "ui_material.wgsl",
+        Shader::from_wgsl
+    );
+    load_internal_asset!(
+        app,
+        UI_VERTEX_OUTPUT_SHADER_HANDLE,
+        "ui_vertex_output.wgsl",
+        Shader::from_wgsl
+    );
 
     let render_app = match app.get_sub_app_mut(RenderApp) {
         Ok(render_app) => render_app,
@@ -107,6 +131,12 @@ pub fn build_ui_render(app: &mut App) {
                 draw_ui_graph::node::UI_PASS,
             )
             .unwrap();
+        graph_2d
+            .add_node_edge(
+                bevy_core_pipeline::core_2d::graph::node::UPSCALING,
+                draw_ui_graph::node::UI_PASS,
+            )
+            .unwrap();
         graph_2d
             .add_slot_edge(
                 graph_2d.input_node().unwrap().id,
@@ -129,6 +159,24 @@ pub fn build_ui_render(app: &mut App) {
                 draw_ui_graph::node::UI_PASS,
             )
             .unwrap();
+        graph_3d
+            .add_node_edge(
+                bevy_core_pipeline::core_3d::graph::node::COLOR_GRADING,
+                draw_ui_graph::node::UI_PASS,
+            )
+            .unwrap();
+        graph_3d
+            .add_node_edge(
+                bevy_core_pipeline::core_3d::graph::node::FXAA,
+                draw_ui_graph::node::UI_PASS,
+            )
+            .unwrap();
+        graph_3d
+            .add_node_edge(
+                bevy_core_pipeline::core_3d::graph::node::UPSCALING,
+                draw_ui_graph::node::UI_PASS,
+            )
+            .unwrap();
         graph_3d
             .add_slot_edge(
                 graph_3d.input_node().unwrap().id,
@@ -166,6 +214,22 @@ pub struct ExtractedUiNode {
     pub image: Handle<Image>,
     pub atlas_size: Option<Vec2>,
     pub clip: Option<Rect>,
+    /// The rounded box drawn in the quad of the node, if any, rather than the whole quad
+    pub shape: Option<UiNodeShape>,
+}
+
+/// A box with rounded corners and a border, drawn by the UI shader in the quad of a node.
+#[derive(Clone, Copy, Debug)]
+pub struct UiNodeShape {
+    /// The size of the box, centered on the quad
+    pub size: Vec2,
+    /// The radii of the top left, top right, bottom right and bottom left corners
+    pub radius: [f32; 4],
+    /// The widths of the left, top, right and bottom borders
+    pub border: [f32; 4],
+    pub border_color: Color,
+    /// How far the edges of the box are blurred, such as for the [`BoxShadow`]s
+    pub blur: f32,
 }
 
 #[derive(Resource, Default)]
@@ -173,9 +237,21 @@ pub struct ExtractedUiNodes {
     pub uinodes: Vec<ExtractedUiNode>,
 }
 
+/// Resolves the widths of the left, top, right and bottom borders of a node, the percentages
+/// being relative to the width of its parent like in the layout.
+fn resolve_border(border: &UiRect, parent_width: f32) -> [f32; 4] {
+    [border.left, border.top, border.right, border.bottom].map(|val| match val {
+        Val::Px(value) => value.max(0.0),
+        Val::Percent(percent) => (parent_width * percent / 100.0).max(0.0),
+        Val::Undefined | Val::Auto => 0.0,
+    })
+}
+
+#[allow(clippy::type_complexity)]
 pub fn extract_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     images: Extract<Res<Assets<Image>>>,
+    windows: Extract<Res<Windows>>,
     uinode_query: Extract<
         Query<(
             &Node,
@@ -184,34 +260,141 @@ pub fn extract_uinodes(
             &UiImage,
             &ComputedVisibility,
             Option<&CalculatedClip>,
+            Option<&ImageScaleMode>,
+            Option<(&BorderColor, &Style)>,
+            Option<&BorderRadius>,
+            Option<&BoxShadow>,
+            Option<&Parent>,
         )>,
     >,
+    parent_query: Extract<Query<&Node>>,
 ) {
     extracted_uinodes.uinodes.clear();
-    for (uinode, transform, color, image, visibility, clip) in uinode_query.iter() {
+    for (
+        uinode,
+        transform,
+        color,
+        image,
+        visibility,
+        clip,
+        scale_mode,
+        border,
+        border_radius,
+        box_shadow,
+        parent,
+    ) in uinode_query.iter()
+    {
         if !visibility.is_visible() {
             continue;
         }
         let image = image.0.clone_weak();
         // Skip loading images
-        if !images.contains(&image) {
-            continue;
+        let image_size = match images.get(&image) {
+            Some(image) => image.size(),
+            None => continue,
+        };
+        let transform = transform.compute_matrix();
+        let clip = clip.map(|clip| clip.clip);
+        let radius = border_radius.map_or([0.0; 4], |radius| {
+            [
+                radius.top_left,
+                radius.top_right,
+                radius.bottom_right,
+                radius.bottom_left,
+            ]
+        });
+
+        // The shadow is extracted first, so that it's drawn behind the node
+        if let Some(shadow) = box_shadow.filter(|shadow| shadow.color.a() > 0.0) {
+            let spread = shadow.spread_radius;
+            let size = (uinode.calculated_size + 2.0 * spread).max(Vec2::ZERO);
+            let blur = shadow.blur_radius.max(0.0);
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                transform: transform * Mat4::from_translation(shadow.offset.extend(0.0)),
+                background_color: shadow.color,
+                // The quad covers the blurred edges of the box
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: size + 2.0 * blur,
+                },
+                image: DEFAULT_IMAGE_HANDLE.typed(),
+                atlas_size: None,
+                clip,
+                shape: Some(UiNodeShape {
+                    size,
+                    radius: radius.map(|radius| match radius > 0.0 {
+                        true => (radius + spread).max(0.0),
+                        false => 0.0,
+                    }),
+                    border: [0.0; 4],
+                    border_color: Color::NONE,
+                    blur,
+                }),
+            });
         }
+
+        let (border, border_color) = match border {
+            Some((border_color, style)) if border_color.0.a() > 0.0 => {
+                let parent_width =
+                    match parent.and_then(|parent| parent_query.get(parent.get()).ok()) {
+                        Some(parent) => parent.calculated_size.x,
+                        None => windows.get_primary().map_or(0.0, |window| window.width()),
+                    };
+                (resolve_border(&style.border, parent_width), border_color.0)
+            }
+            _ => ([0.0; 4], Color::NONE),
+        };
         // Skip completely transparent nodes
-        if color.0.a() == 0.0 {
+        if color.0.a() == 0.0 && border == [0.0; 4] {
             continue;
         }
-        extracted_uinodes.uinodes.push(ExtractedUiNode {
-            transform: transform.compute_matrix(),
-            background_color: color.0,
-            rect: Rect {
-                min: Vec2::ZERO,
-                max: uinode.calculated_size,
-            },
-            image,
-            atlas_size: None,
-            clip: clip.map(|clip| clip.clip),
-        });
+        match scale_mode {
+            Some(scale_mode) if *scale_mode != ImageScaleMode::Stretched => {
+                let image_rect = Rect {
+                    min: Vec2::ZERO,
+                    max: image_size,
+                };
+                for slice in scale_mode.compute_slices(image_rect, uinode.calculated_size) {
+                    // The Y of the UI goes down, and the quad of a node has the size of its rect,
+                    // so it's scaled to the size of the slice
+                    let offset = Vec3::new(slice.offset.x, -slice.offset.y, 0.0);
+                    let scale = (slice.draw_size / slice.texture_rect.size()).extend(1.0);
+                    extracted_uinodes.uinodes.push(ExtractedUiNode {
+                        transform: transform
+                            * Mat4::from_translation(offset)
+                            * Mat4::from_scale(scale),
+                        background_color: color.0,
+                        rect: slice.texture_rect,
+                        image: image.clone_weak(),
+                        atlas_size: Some(image_size),
+                        clip,
+                        // The slices are already shaped by the image
+                        shape: None,
+                    });
+                }
+            }
+            _ => {
+                let shape = (radius != [0.0; 4] || border != [0.0; 4]).then_some(UiNodeShape {
+                    size: uinode.calculated_size,
+                    radius,
+                    border,
+                    border_color,
+                    blur: 0.0,
+                });
+                extracted_uinodes.uinodes.push(ExtractedUiNode {
+                    transform,
+                    background_color: color.0,
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: uinode.calculated_size,
+                    },
+                    image,
+                    atlas_size: None,
+                    clip,
+                    shape,
+                });
+            }
+        }
     }
 }
 
@@ -298,38 +481,75 @@ pub fn extract_text_uinodes(
         let text_glyphs = &text_layout_info.glyphs;
         let alignment_offset = (uinode.calculated_size / -2.0).extend(0.0);
 
-        let mut color = Color::WHITE;
-        let mut current_section = usize::MAX;
-        for text_glyph in text_glyphs {
-            if text_glyph.section_index != current_section {
-                color = text.sections[text_glyph.section_index]
-                    .style
-                    .color
-                    .as_rgba_linear();
-                current_section = text_glyph.section_index;
+        // The nodes of the same depth are drawn in order, so the layers are drawn one after the other
+        for layer in GlyphLayer::ALL {
+            let mut section_layer = None;
+            let mut current_section = usize::MAX;
+            for text_glyph in text_glyphs {
+                if text_glyph.section_index != current_section {
+                    section_layer = text.sections[text_glyph.section_index]
+                        .style
+                        .glyph_layer(layer, scale_factor)
+                        .map(|(color, offsets)| (color.as_rgba_linear(), offsets));
+                    current_section = text_glyph.section_index;
+                }
+                let (color, offsets) = match &section_layer {
+                    Some(section_layer) => section_layer,
+                    None => continue,
+                };
+                let atlas = texture_atlases
+                    .get(&text_glyph.atlas_info.texture_atlas)
+                    .unwrap();
+                let texture = atlas.texture.clone_weak();
+                let index = text_glyph.atlas_info.glyph_index as usize;
+                let rect = atlas.textures[index];
+                let atlas_size = Some(atlas.size);
+
+                for offset in offsets {
+                    // NOTE: Should match `bevy_text::text2d::extract_text2d_sprite`
+                    let extracted_transform = global_transform.compute_matrix()
+                        * Mat4::from_scale(Vec3::splat(scale_factor.recip()))
+                        * Mat4::from_translation(
+                            alignment_offset * scale_factor
+                                + (text_glyph.position + *offset).extend(0.),
+                        );
+
+                    extracted_uinodes.uinodes.push(ExtractedUiNode {
+                        transform: extracted_transform,
+                        background_color: *color,
+                        rect,
+                        image: texture.clone(),
+                        atlas_size,
+                        clip: clip.map(|clip| clip.clip),
+                        shape: None,
+                    });
+                }
             }
-            let atlas = texture_atlases
-                .get(&text_glyph.atlas_info.texture_atlas)
-                .unwrap();
-            let texture = atlas.texture.clone_weak();
-            let index = text_glyph.atlas_info.glyph_index as usize;
-            let rect = atlas.textures[index];
-            let atlas_size = Some(atlas.size);
-
-            // NOTE: Should match `bevy_text::text2d::extract_text2d_sprite`
+        }
+
+        for icon in &text_layout_info.icons {
+            let section = &text.sections[icon.section_index];
+            let image = match &section.icon {
+                Some(image) => image.clone_weak(),
+                None => continue,
+            };
             let extracted_transform = global_transform.compute_matrix()
                 * Mat4::from_scale(Vec3::splat(scale_factor.recip()))
                 * Mat4::from_translation(
-                    alignment_offset * scale_factor + text_glyph.position.extend(0.),
+                    alignment_offset * scale_factor + icon.position.extend(0.),
                 );
 
             extracted_uinodes.uinodes.push(ExtractedUiNode {
                 transform: extracted_transform,
-                background_color: color,
-                rect,
-                image: texture,
-                atlas_size,
+                background_color: section.style.color.as_rgba_linear(),
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: icon.size,
+                },
+                image,
+                atlas_size: None,
                 clip: clip.map(|clip| clip.clip),
+                shape: None,
             });
         }
     }
@@ -341,8 +561,19 @@ struct UiVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
     pub color: [f32; 4],
+    /// The position of the vertex relative to the center of the [`UiNodeShape`]
+    pub box_position: [f32; 2],
+    pub box_size: [f32; 2],
+    pub radius: [f32; 4],
+    pub border: [f32; 4],
+    pub border_color: [f32; 4],
+    pub blur: f32,
+    pub flags: u32,
 }
 
+/// The vertices of a node with a [`UiNodeShape`] draw the rounded box rather than the whole quad
+const UI_NODE_SHAPED: u32 = 1;
+
 #[derive(Resource)]
 pub struct UiMeta {
     vertices: BufferVec<UiVertex>,
@@ -461,6 +692,15 @@ pub fn prepare_uinodes(
             }
         }
 
+        // The clipped lengths are on screen, so they are scaled to the rect in the texture, which
+        // is only drawn at its size in pixels without a scale in the transform
+        let positions_diff = if extracted_uinode.transform.x_axis[1] == 0.0 {
+            let texture_scale = (rect_size / transformed_rect_size).truncate().abs();
+            positions_diff.map(|diff| diff * texture_scale)
+        } else {
+            positions_diff
+        };
+
         let atlas_extent = extracted_uinode.atlas_size.unwrap_or(uinode_rect.max);
         let uvs = [
             Vec2::new(
@@ -482,11 +722,33 @@ pub fn prepare_uinodes(
         ]
         .map(|pos| pos / atlas_extent);
 
+        let color = extracted_uinode.background_color.as_linear_rgba_f32();
+        let shape = extracted_uinode.shape.unwrap_or(UiNodeShape {
+            size: Vec2::ZERO,
+            radius: [0.0; 4],
+            border: [0.0; 4],
+            border_color: Color::NONE,
+            blur: 0.0,
+        });
+        let flags = match extracted_uinode.shape {
+            Some(_) => UI_NODE_SHAPED,
+            None => 0,
+        };
         for i in QUAD_INDICES {
+            // The clipped lengths are in the space of the node as well
+            let box_position =
+                (QUAD_VERTEX_POSITIONS[i] * rect_size).truncate() + positions_diff[i];
             ui_meta.vertices.push(UiVertex {
                 position: positions_clipped[i].into(),
                 uv: uvs[i].into(),
-                color: extracted_uinode.background_color.as_linear_rgba_f32(),
+                color,
+                box_position: box_position.into(),
+                box_size: shape.size.into(),
+                radius: shape.radius,
+                border: shape.border,
+                border_color: shape.border_color.as_linear_rgba_f32(),
+                blur: shape.blur,
+                flags,
             });
         }
 
@@ -579,3 +841,20 @@ pub fn queue_uinodes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::resolve_border;
+    use crate::{UiRect, Val};
+
+    #[test]
+    fn border_widths() {
+        let border = UiRect {
+            left: Val::Px(2.0),
+            right: Val::Percent(10.0),
+            top: Val::Auto,
+            bottom: Val::Px(-1.0),
+        };
+        assert_eq!(resolve_border(&border, 50.0), [2.0, 0.0, 5.0, 0.0]);
+    }
+}
@@ -131,6 +131,20 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x2,
                 // color
                 VertexFormat::Float32x4,
+                // box_position
+                VertexFormat::Float32x2,
+                // box_size
+                VertexFormat::Float32x2,
+                // radius
+                VertexFormat::Float32x4,
+                // border
+                VertexFormat::Float32x4,
+                // border_color
+                VertexFormat::Float32x4,
+                // blur
+                VertexFormat::Float32,
+                // flags
+                VertexFormat::Uint32,
             ],
         );
         let shader_defs = Vec::new();
@@ -20,6 +20,7 @@ pub struct UiPassNode {
         (
             &'static RenderPhase<TransparentUi>,
             &'static ViewTarget,
+            Option<&'static ViewUpscalingTarget>,
             Option<&'static UiCameraConfig>,
         ),
         With<ExtractedView>,
@@ -56,7 +57,7 @@ impl Node for UiPassNode {
     ) -> Result<(), NodeRunError> {
         let input_view_entity = graph.get_input_entity(Self::IN_VIEW)?;
 
-        let (transparent_phase, target, camera_ui) =
+        let (transparent_phase, target, upscaling_target, camera_ui) =
             if let Ok(result) = self.ui_view_query.get_manual(world, input_view_entity) {
                 result
             } else {
@@ -79,10 +80,15 @@ impl Node for UiPassNode {
         } else {
             input_view_entity
         };
+        // The UI of the upscaled views is rendered to their target, at the full resolution
+        let target_view = match upscaling_target {
+            Some(upscaling_target) => &upscaling_target.view,
+            None => &target.view,
+        };
         let pass_descriptor = RenderPassDescriptor {
             label: Some("ui_pass"),
             color_attachments: &[Some(RenderPassColorAttachment {
-                view: &target.view,
+                view: target_view,
                 resolve_target: None,
                 ops: Operations {
                     load: LoadOp::Load,
@@ -94,12 +100,8 @@ impl Node for UiPassNode {
 
         let draw_functions = world.resource::<DrawFunctions<TransparentUi>>();
 
-        let render_pass = render_context
-            .command_encoder
-            .begin_render_pass(&pass_descriptor);
-
         let mut draw_functions = draw_functions.write();
-        let mut tracked_pass = TrackedRenderPass::new(render_pass);
+        let mut tracked_pass = render_context.begin_tracked_render_pass(&pass_descriptor);
         for item in &transparent_phase.items {
             let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
             draw_function.draw(world, &mut tracked_pass, view_entity, item);
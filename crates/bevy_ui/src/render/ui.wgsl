@@ -8,13 +8,28 @@ struct View {
     world_position: vec3<f32>,
     // viewport(x_origin, y_origin, width, height)
     viewport: vec4<f32>,
+    // The view_proj of the previous frame, or the current one on the first frame of the view
+    previous_view_proj: mat4x4<f32>,
 };
 @group(0) @binding(0)
 var<uniform> view: View;
 
+// The vertices of a node with a shape draw its rounded box rather than the whole quad
+let UI_NODE_SHAPED: u32 = 1u;
+
 struct VertexOutput {
     @location(0) uv: vec2<f32>,
     @location(1) color: vec4<f32>,
+    // The position in the rounded box of the node, relative to its center, with Y going down
+    @location(2) box_position: vec2<f32>,
+    @location(3) box_size: vec2<f32>,
+    // The radii of the top left, top right, bottom right and bottom left corners
+    @location(4) radius: vec4<f32>,
+    // The widths of the left, top, right and bottom borders
+    @location(5) border: vec4<f32>,
+    @location(6) border_color: vec4<f32>,
+    @location(7) blur: f32,
+    @location(8) @interpolate(flat) flags: u32,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -23,14 +38,40 @@ fn vertex(
     @location(0) vertex_position: vec3<f32>,
     @location(1) vertex_uv: vec2<f32>,
     @location(2) vertex_color: vec4<f32>,
+    @location(3) box_position: vec2<f32>,
+    @location(4) box_size: vec2<f32>,
+    @location(5) radius: vec4<f32>,
+    @location(6) border: vec4<f32>,
+    @location(7) border_color: vec4<f32>,
+    @location(8) blur: f32,
+    @location(9) flags: u32,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
     out.color = vertex_color;
+    out.box_position = box_position;
+    out.box_size = box_size;
+    out.radius = radius;
+    out.border = border;
+    out.border_color = border_color;
+    out.blur = blur;
+    out.flags = flags;
     return out;
 }
 
+// The signed distance from a position to a box centered on the origin with rounded corners,
+// negative inside of it
+fn rounded_box_distance(position: vec2<f32>, size: vec2<f32>, radius: vec4<f32>) -> f32 {
+    let half_size = 0.5 * size;
+    // The radius of the corner in the quadrant of the point, which can't overlap the other ones
+    let top = select(radius.x, radius.y, position.x > 0.0);
+    let bottom = select(radius.w, radius.z, position.x > 0.0);
+    let r = min(select(top, bottom, position.y > 0.0), min(half_size.x, half_size.y));
+    let q = abs(position) - half_size + r;
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
+}
+
 @group(1) @binding(0)
 var sprite_texture: texture_2d<f32>;
 @group(1) @binding(1)
@@ -40,5 +81,28 @@ var sprite_sampler: sampler;
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     var color = textureSample(sprite_texture, sprite_sampler, in.uv);
     color = in.color * color;
+    if ((in.flags & UI_NODE_SHAPED) != 0u) {
+        if (any(in.border > vec4<f32>(0.0))) {
+            // The border is outside of the inner box, whose corners are rounded less
+            let inner_size = max(
+                in.box_size - vec2<f32>(in.border.x + in.border.z, in.border.y + in.border.w),
+                vec2<f32>(0.0)
+            );
+            let inner_position = in.box_position
+                - 0.5 * vec2<f32>(in.border.x - in.border.z, in.border.y - in.border.w);
+            let inner_radius = max(in.radius - vec4<f32>(
+                max(in.border.x, in.border.y),
+                max(in.border.z, in.border.y),
+                max(in.border.z, in.border.w),
+                max(in.border.x, in.border.w)
+            ), vec4<f32>(0.0));
+            let inner_distance = rounded_box_distance(inner_position, inner_size, inner_radius);
+            color = mix(color, in.border_color, smoothstep(-0.5, 0.5, inner_distance));
+        }
+        // The edges are antialiased over a pixel, or blurred
+        let softness = max(in.blur, 0.5);
+        let distance = rounded_box_distance(in.box_position, in.box_size, in.radius);
+        color.a = color.a * (1.0 - smoothstep(-softness, softness, distance));
+    }
     return color;
 }
@@ -0,0 +1,6 @@
+#define_import_path bevy_ui::ui_vertex_output
+
+// The UVs go from (0, 0) at the top left corner of the node to (1, 1) at the bottom right one
+@location(0) uv: vec2<f32>,
+// The size of the node, in logical pixels
+@location(1) size: vec2<f32>,
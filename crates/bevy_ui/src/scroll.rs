@@ -0,0 +1,170 @@
+use crate::{entity::UiCameraConfig, CalculatedClip, Node, Overflow, ScrollPosition, Style};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventReader,
+    query::{With, Without},
+    system::{Commands, Local, Query, Res},
+};
+use bevy_input::{
+    mouse::{MouseButton, MouseScrollUnit, MouseWheel},
+    touch::Touches,
+    Input,
+};
+use bevy_math::Vec2;
+use bevy_render::camera::{Camera, RenderTarget};
+use bevy_render::view::ComputedVisibility;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::FloatOrd;
+use bevy_window::Windows;
+
+/// The distance scrolled by a line of the mouse wheel, in logical pixels
+pub const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+/// The node whose children are being dragged, and the last position of the cursor
+#[derive(Default)]
+pub struct ScrollDrag {
+    entity: Option<Entity>,
+    last_position: Vec2,
+}
+
+/// The system that scrolls the nodes with [`Overflow::Scroll`] under the cursor, with the mouse
+/// wheel or by dragging them with the left mouse button or a touch
+///
+/// Adds a [`ScrollPosition`] to the nodes that don't have one yet.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_scroll_system(
+    mut commands: Commands,
+    mut drag: Local<ScrollDrag>,
+    camera: Query<(&Camera, Option<&UiCameraConfig>)>,
+    windows: Res<Windows>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    unscrolled_query: Query<(Entity, &Style), (With<Node>, Without<ScrollPosition>)>,
+    mut scroll_query: Query<(
+        Entity,
+        &Node,
+        &Style,
+        &GlobalTransform,
+        &mut ScrollPosition,
+        Option<&CalculatedClip>,
+        Option<&ComputedVisibility>,
+    )>,
+) {
+    for (entity, style) in &unscrolled_query {
+        if style.overflow == Overflow::Scroll {
+            commands.entity(entity).insert(ScrollPosition::default());
+        }
+    }
+
+    let is_ui_disabled =
+        |camera_ui| matches!(camera_ui, Some(&UiCameraConfig { show_ui: false, .. }));
+
+    let cursor_position = camera
+        .iter()
+        .filter(|(_, camera_ui)| !is_ui_disabled(*camera_ui))
+        .filter_map(|(camera, _)| {
+            if let RenderTarget::Window(window_id) = camera.target {
+                Some(window_id)
+            } else {
+                None
+            }
+        })
+        .filter_map(|window_id| windows.get(window_id))
+        .filter(|window| window.is_focused())
+        .find_map(|window| window.cursor_position())
+        .or_else(|| touches_input.first_pressed_position());
+
+    let mut wheel_delta = Vec2::ZERO;
+    for event in mouse_wheel_events.iter() {
+        let line_height = match event.unit {
+            MouseScrollUnit::Line => SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => 1.0,
+        };
+        wheel_delta += Vec2::new(event.x, event.y) * line_height;
+    }
+
+    let cursor_position = match cursor_position {
+        Some(cursor_position) => cursor_position,
+        None => {
+            drag.entity = None;
+            return;
+        }
+    };
+
+    // Drags the children of the node along with the cursor
+    let pressed = mouse_button_input.pressed(MouseButton::Left)
+        || touches_input.first_pressed_position().is_some();
+    if !pressed {
+        drag.entity = None;
+    } else if let Some(entity) = drag.entity {
+        if let Ok((.., mut scroll_position, _, _)) = scroll_query.get_mut(entity) {
+            let offset = scroll_position.scrolled_by(drag.last_position - cursor_position);
+            if scroll_position.offset != offset {
+                scroll_position.offset = offset;
+            }
+        }
+        drag.last_position = cursor_position;
+        return;
+    }
+
+    let mut hovered_z_sorted_nodes = scroll_query
+        .iter()
+        .filter_map(
+            |(entity, node, style, global_transform, _, clip, visibility)| {
+                if style.overflow != Overflow::Scroll {
+                    return None;
+                }
+                // Nodes that are not rendered should not be scrollable
+                if let Some(computed_visibility) = visibility {
+                    if !computed_visibility.is_visible() {
+                        return None;
+                    }
+                }
+                let position = global_transform.translation();
+                let extents = node.size() / 2.0;
+                let mut min = position.truncate() - extents;
+                let mut max = position.truncate() + extents;
+                if let Some(clip) = clip {
+                    min = Vec2::max(min, clip.clip.min);
+                    max = Vec2::min(max, clip.clip.max);
+                }
+                let contains_cursor = (min.x..max.x).contains(&cursor_position.x)
+                    && (min.y..max.y).contains(&cursor_position.y);
+                contains_cursor.then_some((entity, FloatOrd(position.z)))
+            },
+        )
+        .collect::<Vec<_>>();
+    hovered_z_sorted_nodes.sort_by_key(|(_, z)| -*z);
+
+    let just_pressed =
+        mouse_button_input.just_pressed(MouseButton::Left) || touches_input.any_just_pressed();
+    if just_pressed {
+        if let Some((entity, _)) = hovered_z_sorted_nodes.first() {
+            drag.entity = Some(*entity);
+            drag.last_position = cursor_position;
+        }
+    }
+
+    if wheel_delta == Vec2::ZERO {
+        return;
+    }
+    // The top node that can still scroll in the direction of the wheel takes the whole delta, so
+    // that the nested nodes scroll before their parents
+    for (entity, _) in hovered_z_sorted_nodes {
+        let mut scroll_position = match scroll_query.get_component_mut::<ScrollPosition>(entity) {
+            Ok(scroll_position) => scroll_position,
+            Err(_) => continue,
+        };
+        let mut delta = -wheel_delta;
+        // A vertical wheel scrolls the nodes that can only scroll horizontally
+        if scroll_position.max_offset().y == 0.0 && delta.x == 0.0 {
+            delta = Vec2::new(delta.y, 0.0);
+        }
+        let offset = scroll_position.scrolled_by(delta);
+        if scroll_position.offset != offset {
+            scroll_position.offset = offset;
+            break;
+        }
+    }
+}
@@ -348,6 +348,37 @@ pub enum Overflow {
     Visible,
     /// Hide overflowing items
     Hidden,
+    /// Hide overflowing items, and scroll them with the [`ScrollPosition`] of the node, using the
+    /// mouse wheel or by dragging them
+    Scroll,
+}
+
+/// How far the children of a node with [`Overflow::Scroll`] are scrolled, added automatically to
+/// these nodes.
+#[derive(Component, Default, Copy, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct ScrollPosition {
+    /// How far the children are scrolled to the right and down, in logical pixels
+    ///
+    /// It's clamped between zero and [`ScrollPosition::max_offset`] by the layout.
+    pub offset: Vec2,
+    /// The largest offset, showing the end of the children
+    /// automatically calculated by [`super::flex::flex_node_system`]
+    pub(crate) max_offset: Vec2,
+}
+
+impl ScrollPosition {
+    /// The largest offset, showing the end of the children
+    /// automatically calculated by [`super::flex::flex_node_system`]
+    pub fn max_offset(&self) -> Vec2 {
+        self.max_offset
+    }
+
+    /// The offset scrolled by `delta` logical pixels to the right and down, clamped to the
+    /// children.
+    pub fn scrolled_by(&self, delta: Vec2) -> Vec2 {
+        (self.offset + delta).clamp(Vec2::ZERO, self.max_offset)
+    }
 }
 
 /// The strategy used to position this node
@@ -398,6 +429,94 @@ impl From<Color> for BackgroundColor {
     }
 }
 
+/// The color of the border of the node, drawn inside of it with the widths of [`Style::border`].
+///
+/// The border is only drawn for the nodes with this component.
+#[derive(Component, Copy, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct BorderColor(pub Color);
+
+impl Default for BorderColor {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
+
+impl From<Color> for BorderColor {
+    fn from(color: Color) -> Self {
+        Self(color)
+    }
+}
+
+/// The radii of the rounded corners of the node, in logical pixels.
+///
+/// The radii are clamped to half the smallest side of the node. The background, the image, the
+/// border and the [`BoxShadow`] of the node are rounded.
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct BorderRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl BorderRadius {
+    /// Rounds all the corners with the same radius.
+    pub const fn all(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// Rounds the two top corners, leaving the bottom ones square.
+    pub const fn top(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: 0.0,
+            bottom_left: 0.0,
+        }
+    }
+
+    /// Rounds the two bottom corners, leaving the top ones square.
+    pub const fn bottom(radius: f32) -> Self {
+        Self {
+            top_left: 0.0,
+            top_right: 0.0,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+}
+
+/// A shadow drawn behind the node, with the shape of the node and its [`BorderRadius`].
+#[derive(Component, Copy, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct BoxShadow {
+    pub color: Color,
+    /// The offset of the shadow from the node, in logical pixels, to the right and down
+    pub offset: Vec2,
+    /// How far the edges of the shadow are blurred, in logical pixels
+    pub blur_radius: f32,
+    /// How much the shadow is larger than the node on each side, in logical pixels
+    pub spread_radius: f32,
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+            offset: Vec2::new(0.0, 4.0),
+            blur_radius: 8.0,
+            spread_radius: 0.0,
+        }
+    }
+}
+
 /// The 2D texture displayed for this UI node
 #[derive(Component, Clone, Debug, Reflect, Deref, DerefMut)]
 #[reflect(Component, Default)]
@@ -106,7 +106,7 @@ fn update_clipping(
     // Calculate new clip for its children
     let children_clip = match style.overflow {
         Overflow::Visible => clip,
-        Overflow::Hidden => {
+        Overflow::Hidden | Overflow::Scroll => {
             let node_center = global_transform.translation().truncate();
             let node_rect = Rect::from_center_size(node_center, node.calculated_size);
             Some(clip.map_or(node_rect, |c| c.intersect(node_rect)))
@@ -1,7 +1,8 @@
 use crate::{CalculatedSize, Size, Style, UiScale, Val};
-use bevy_asset::Assets;
+use bevy_asset::{AssetEvent, Assets};
 use bevy_ecs::{
     entity::Entity,
+    event::EventReader,
     query::{Changed, Or, With},
     system::{Commands, Local, ParamSet, Query, Res, ResMut},
 };
@@ -9,7 +10,7 @@ use bevy_math::Vec2;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlas;
 use bevy_text::{
-    Font, FontAtlasSet, Text, TextError, TextLayoutInfo, TextPipeline, TextSettings,
+    Font, FontAtlasSet, FontFallbacks, Text, TextError, TextLayoutInfo, TextPipeline, TextSettings,
     YAxisOrientation,
 };
 use bevy_window::Windows;
@@ -48,6 +49,8 @@ pub fn text_system(
     fonts: Res<Assets<Font>>,
     windows: Res<Windows>,
     text_settings: Res<TextSettings>,
+    font_fallbacks: Res<FontFallbacks>,
+    mut font_events: EventReader<AssetEvent<Font>>,
     ui_scale: Res<UiScale>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
@@ -73,14 +76,17 @@ pub fn text_system(
 
     let inv_scale_factor = 1. / scale_factor;
 
+    let fallbacks_changed =
+        font_fallbacks.is_changed() || font_fallbacks.any_loaded(font_events.iter());
+
     #[allow(clippy::float_cmp)]
-    if *last_scale_factor == scale_factor {
+    if *last_scale_factor == scale_factor && !fallbacks_changed {
         // Adds all entities where the text or the style has changed to the local queue
         for entity in text_queries.p0().iter() {
             queued_text.entities.push(entity);
         }
     } else {
-        // If the scale factor has changed, queue all text
+        // If the scale factor or the font fallbacks have changed, queue all text
         for entity in text_queries.p1().iter() {
             queued_text.entities.push(entity);
         }
@@ -121,6 +127,7 @@ pub fn text_system(
                 &mut *texture_atlases,
                 &mut *textures,
                 text_settings.as_ref(),
+                &font_fallbacks,
                 YAxisOrientation::TopToBottom,
             ) {
                 Err(TextError::NoSuchFont) => {
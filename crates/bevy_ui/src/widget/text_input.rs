@@ -0,0 +1,701 @@
+use crate::{
+    entity::{NodeBundle, TextBundle},
+    FocusPolicy, Interaction, Node, PositionType, Size, Style, UiFocus, UiRect, UiScale, Val,
+};
+use bevy_asset::Assets;
+use bevy_ecs::{
+    entity::Entity,
+    event::{EventReader, EventWriter},
+    prelude::Component,
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    ButtonState, Input,
+};
+use bevy_math::Vec2;
+use bevy_render::{color::Color, view::Visibility};
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_time::Time;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{Ime, ReceivedCharacter, Windows};
+use std::ops::Range;
+
+/// How long the caret stays visible, then hidden, while blinking, in seconds
+const CARET_BLINK_PERIOD: f64 = 0.5;
+
+/// The width of the caret, in logical pixels
+const CARET_WIDTH: f32 = 2.0;
+
+/// A single line of editable text, typed in while the node has the [`UiFocus`]
+///
+/// The caret is moved with the arrow keys, `Home`, `End` or the mouse, selecting text while
+/// `Shift` is held. The selection is copied, cut and pasted with `Ctrl` + `C`, `X` and `V`
+/// through the [`UiClipboard`], and text can be composed with an input method (IME).
+/// Pressing `Return` sends a [`TextInputSubmit`] event.
+#[derive(Component, Clone, Debug)]
+pub struct TextInput {
+    /// The style of the text
+    pub style: TextStyle,
+    /// The text shown, faded, while the value is empty
+    pub placeholder: String,
+    /// The color drawn behind the selected text
+    pub selection_color: Color,
+    value: String,
+    /// The byte index of the caret in the value
+    cursor: usize,
+    /// The other end of the selection, whose first end is the caret
+    selection_anchor: Option<usize>,
+    /// The text being composed by the IME, shown at the caret until it's committed
+    preedit: String,
+    /// The byte index of the caret in the preedit text
+    preedit_cursor: Option<usize>,
+    /// How far the text is scrolled to the left to keep the caret visible, in logical pixels
+    scroll: f32,
+    /// When the caret last moved, to show it while typing
+    blink_start: f64,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            style: Default::default(),
+            placeholder: Default::default(),
+            selection_color: Color::rgba(0.3, 0.5, 0.9, 0.5),
+            value: Default::default(),
+            cursor: 0,
+            selection_anchor: None,
+            preedit: Default::default(),
+            preedit_cursor: None,
+            scroll: 0.0,
+            blink_start: 0.0,
+        }
+    }
+}
+
+impl TextInput {
+    /// Creates a text input with a value, with the caret at its end
+    pub fn new(value: impl Into<String>, style: TextStyle) -> Self {
+        let mut text_input = Self {
+            style,
+            ..Default::default()
+        };
+        text_input.set_value(value);
+        text_input
+    }
+
+    /// Returns this [`TextInput`] with a placeholder, shown while the value is empty
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// The text typed in
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text, moving the caret to its end
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+        self.selection_anchor = None;
+    }
+
+    /// The byte index of the caret in the value
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the caret to a byte index, or the start of the character containing it, extending
+    /// the selection from the previous position if `select` is true
+    pub fn set_cursor(&mut self, cursor: usize, select: bool) {
+        let mut cursor = cursor.min(self.value.len());
+        while !self.value.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        if select {
+            self.selection_anchor.get_or_insert(self.cursor);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor = cursor;
+    }
+
+    /// The byte range of the selected text, if any
+    pub fn selection(&self) -> Option<Range<usize>> {
+        match self.selection_anchor {
+            Some(anchor) if anchor != self.cursor => {
+                Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+            }
+            _ => None,
+        }
+    }
+
+    /// The selected text, empty without a selection
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some(selection) => &self.value[selection],
+            None => "",
+        }
+    }
+
+    /// Selects the whole value
+    pub fn select_all(&mut self) {
+        self.selection_anchor = Some(0);
+        self.cursor = self.value.len();
+    }
+
+    /// The text being composed by the IME
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Inserts text at the caret, replacing the selection. Control characters such as line breaks
+    /// are left out, as the input is a single line.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        self.value.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    /// Deletes the selection, or the character before the caret
+    pub fn delete_backward(&mut self) {
+        if !self.delete_selection() {
+            let start = self.previous_boundary(self.cursor);
+            self.value.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    /// Deletes the selection, or the character after the caret
+    pub fn delete_forward(&mut self) {
+        if !self.delete_selection() {
+            let end = self.next_boundary(self.cursor);
+            self.value.replace_range(self.cursor..end, "");
+        }
+    }
+
+    /// Moves the caret one character to the left, or to the start of the selection
+    pub fn move_left(&mut self, select: bool) {
+        match self.selection() {
+            Some(selection) if !select => self.set_cursor(selection.start, false),
+            _ => self.set_cursor(self.previous_boundary(self.cursor), select),
+        }
+    }
+
+    /// Moves the caret one character to the right, or to the end of the selection
+    pub fn move_right(&mut self, select: bool) {
+        match self.selection() {
+            Some(selection) if !select => self.set_cursor(selection.end, false),
+            _ => self.set_cursor(self.next_boundary(self.cursor), select),
+        }
+    }
+
+    /// Deletes the selected text, returning whether there was any
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.selection_anchor = None;
+        match selection {
+            Some(selection) => {
+                self.cursor = selection.start;
+                self.value.replace_range(selection, "");
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn previous_boundary(&self, index: usize) -> usize {
+        self.value[..index]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self, index: usize) -> usize {
+        self.value[index..]
+            .chars()
+            .next()
+            .map_or(index, |c| index + c.len_utf8())
+    }
+
+    /// The text shown before the caret, including the composed text before the IME caret
+    fn text_before_caret(&self) -> String {
+        let preedit_cursor = self.preedit_cursor.unwrap_or(self.preedit.len());
+        let mut text = self.value[..self.cursor].to_string();
+        text.push_str(self.preedit.get(..preedit_cursor).unwrap_or(&self.preedit));
+        text
+    }
+
+    /// The byte index in the value that is the closest to an horizontal offset from the start of
+    /// the text, in logical pixels
+    fn index_at(&self, font: &Font, x: f32) -> usize {
+        self.value
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain([self.value.len()])
+            .min_by(|a, b| {
+                let distance = |index: usize| {
+                    (font.text_width(&self.value[..index], self.style.font_size) - x).abs()
+                };
+                distance(*a).total_cmp(&distance(*b))
+            })
+            .unwrap_or(0)
+    }
+}
+
+/// Text copied and cut from the [`TextInput`]s, to be pasted in them
+///
+/// This clipboard is local to the app, and isn't shared with the clipboard of the operating
+/// system.
+#[derive(Resource, Default, Debug)]
+pub struct UiClipboard {
+    pub text: String,
+}
+
+/// Sent when `Return` is pressed in a [`TextInput`]
+#[derive(Clone, Debug)]
+pub struct TextInputSubmit {
+    /// The [`TextInput`] entity
+    pub entity: Entity,
+    /// The text typed in
+    pub value: String,
+}
+
+/// The child entities drawing a [`TextInput`], spawned by [`text_input_parts_system`]
+#[derive(Component, Debug)]
+pub struct TextInputParts {
+    /// The node containing the other parts, scrolled to keep the caret visible
+    line: Entity,
+    selection: Entity,
+    text: Entity,
+    caret: Entity,
+}
+
+/// Whether the IME of the window was enabled for a [`TextInput`]
+#[derive(Default)]
+pub struct TextInputState {
+    ime_enabled: bool,
+}
+
+/// The system that edits the focused [`TextInput`] with the keyboard, the mouse and the IME
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_system(
+    mut state: Local<TextInputState>,
+    mut ui_focus: ResMut<UiFocus>,
+    mut clipboard: ResMut<UiClipboard>,
+    mut windows: ResMut<Windows>,
+    time: Res<Time>,
+    ui_scale: Res<UiScale>,
+    fonts: Res<Assets<Font>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut character_events: EventReader<ReceivedCharacter>,
+    mut ime_events: EventReader<Ime>,
+    mut submit_events: EventWriter<TextInputSubmit>,
+    mut input_query: Query<(&mut TextInput, &Interaction, Option<&TextInputParts>)>,
+    line_query: Query<(&Node, &GlobalTransform)>,
+) {
+    let mut focused = ui_focus
+        .entity
+        .filter(|entity| input_query.contains(*entity));
+
+    // Clicking outside of the focused text input unfocuses it
+    if let Some(entity) = focused {
+        if mouse_button_input.just_pressed(MouseButton::Left)
+            && !matches!(input_query.get(entity), Ok((_, Interaction::Clicked, _)))
+        {
+            ui_focus.entity = None;
+            focused = None;
+        }
+    }
+
+    let window = windows.get_primary_mut();
+    let entity = match (focused, window) {
+        (Some(entity), Some(window)) => {
+            if !window.ime_enabled() {
+                window.set_ime_enabled(true);
+            }
+            state.ime_enabled = true;
+            entity
+        }
+        (_, window) => {
+            if state.ime_enabled {
+                if let Some(window) = window {
+                    window.set_ime_enabled(false);
+                }
+                state.ime_enabled = false;
+            }
+            // The text typed while nothing was focused shouldn't be inserted later
+            keyboard_events.clear();
+            character_events.clear();
+            ime_events.clear();
+            return;
+        }
+    };
+    let (mut input, interaction, parts) = input_query.get_mut(entity).unwrap();
+    let font = fonts.get(&input.style.font);
+    let scale = ui_scale.scale as f32;
+    let line = parts.and_then(|parts| line_query.get(parts.line).ok());
+    let now = time.seconds_since_startup();
+
+    // Clicking places the caret, and dragging selects
+    if *interaction == Interaction::Clicked && mouse_button_input.pressed(MouseButton::Left) {
+        let cursor_position = windows
+            .get_primary()
+            .and_then(|window| window.cursor_position());
+        if let (Some(cursor_position), Some(font), Some((node, transform))) =
+            (cursor_position, font, line)
+        {
+            let left = transform.translation().x - node.size().x / 2.0;
+            let index = input.index_at(font, (cursor_position.x - left) / scale);
+            let select = !mouse_button_input.just_pressed(MouseButton::Left)
+                || keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+            if index != input.cursor || !select {
+                input.set_cursor(index, select);
+                input.blink_start = now;
+            }
+        }
+    }
+
+    for event in ime_events.iter() {
+        match event {
+            Ime::Preedit { value, cursor, .. } => {
+                input.preedit = value.clone();
+                input.preedit_cursor = cursor.map(|(start, _)| start);
+            }
+            Ime::Commit { value, .. } => {
+                input.preedit.clear();
+                input.insert(value);
+            }
+            Ime::Disabled { .. } => input.preedit.clear(),
+            Ime::Enabled { .. } => {}
+        }
+        input.blink_start = now;
+    }
+
+    // While text is being composed, the keys are handled by the IME
+    let composing = !input.preedit.is_empty();
+    let select = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    // `AltGr` is reported as `Ctrl` + `Alt` on some platforms, and types characters
+    let command = keyboard_input.any_pressed([
+        KeyCode::LControl,
+        KeyCode::RControl,
+        KeyCode::LWin,
+        KeyCode::RWin,
+    ]) && !keyboard_input.any_pressed([KeyCode::LAlt, KeyCode::RAlt]);
+    // The key events are repeated while a key is held, unlike `Input::just_pressed`
+    for event in keyboard_events.iter() {
+        if composing || event.state != ButtonState::Pressed {
+            continue;
+        }
+        match event.key_code {
+            Some(KeyCode::Back) => input.delete_backward(),
+            Some(KeyCode::Delete) => input.delete_forward(),
+            Some(KeyCode::Left) => input.move_left(select),
+            Some(KeyCode::Right) => input.move_right(select),
+            Some(KeyCode::Home) => input.set_cursor(0, select),
+            Some(KeyCode::End) => {
+                let end = input.value.len();
+                input.set_cursor(end, select);
+            }
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => submit_events.send(TextInputSubmit {
+                entity,
+                value: input.value.clone(),
+            }),
+            Some(KeyCode::Escape) => ui_focus.entity = None,
+            Some(KeyCode::A) if command => input.select_all(),
+            Some(KeyCode::C) if command => {
+                if input.selection().is_some() {
+                    clipboard.text = input.selected_text().to_string();
+                }
+            }
+            Some(KeyCode::X) if command => {
+                if input.selection().is_some() {
+                    clipboard.text = input.selected_text().to_string();
+                    input.delete_backward();
+                }
+            }
+            Some(KeyCode::V) if command => input.insert(&clipboard.text),
+            _ => continue,
+        }
+        input.blink_start = now;
+    }
+    for event in character_events.iter() {
+        if !composing && !command && !event.char.is_control() {
+            input.insert(event.char.encode_utf8(&mut [0; 4]));
+            input.blink_start = now;
+        }
+    }
+
+    // The IME shows the candidates for the composed text under the caret
+    if let (Some(font), Some((node, transform)), Some(window)) =
+        (font, line, windows.get_primary_mut())
+    {
+        let top_left = transform.translation().truncate() - node.size() / 2.0;
+        let caret = font.text_width(&input.text_before_caret(), input.style.font_size);
+        let position = top_left + Vec2::new(caret * scale, node.size().y);
+        if window.ime_position() != position {
+            window.set_ime_position(position);
+        }
+    }
+}
+
+/// The system that spawns and updates the children of the [`TextInput`]s drawing their text, the
+/// selection and the caret
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_parts_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    ui_focus: Res<UiFocus>,
+    ui_scale: Res<UiScale>,
+    fonts: Res<Assets<Font>>,
+    mut input_query: Query<(
+        Entity,
+        &mut TextInput,
+        &Node,
+        &Style,
+        Option<&TextInputParts>,
+    )>,
+    mut text_query: Query<&mut Text>,
+    mut style_query: Query<&mut Style, bevy_ecs::query::Without<TextInput>>,
+    mut visibility_query: Query<&mut Visibility>,
+) {
+    for (entity, mut input, node, style, parts) in &mut input_query {
+        let parts = match parts {
+            Some(parts) => parts,
+            None => {
+                spawn_parts(&mut commands, entity, &input);
+                continue;
+            }
+        };
+
+        if let Ok(mut text) = text_query.get_mut(parts.text) {
+            let sections = text_sections(&input);
+            let unchanged = text.sections.len() == sections.len()
+                && text.sections.iter().zip(&sections).all(|(a, b)| {
+                    a.value == b.value
+                        && a.style.font == b.style.font
+                        && a.style.font_size == b.style.font_size
+                        && a.style.color == b.style.color
+                });
+            if !unchanged {
+                text.sections = sections;
+            }
+        }
+
+        let focused = ui_focus.entity == Some(entity);
+        if let Ok(mut visibility) = visibility_query.get_mut(parts.caret) {
+            let elapsed = time.seconds_since_startup() - input.blink_start;
+            let is_visible = focused && elapsed % (2.0 * CARET_BLINK_PERIOD) < CARET_BLINK_PERIOD;
+            if visibility.is_visible != is_visible {
+                visibility.is_visible = is_visible;
+            }
+        }
+
+        let font = match fonts.get(&input.style.font) {
+            Some(font) => font,
+            None => continue,
+        };
+        let font_size = input.style.font_size;
+        let line_height = font.line_height(font_size);
+        let caret = font.text_width(&input.text_before_caret(), font_size);
+        let text_width =
+            font.text_width(&input.value, font_size) + font.text_width(&input.preedit, font_size);
+
+        // Scroll the line just enough to keep the caret inside the node
+        let padding = |val: Val| match val {
+            Val::Px(value) => value,
+            _ => 0.0,
+        };
+        let width = node.size().x / ui_scale.scale as f32
+            - padding(style.padding.left)
+            - padding(style.padding.right)
+            - padding(style.border.left)
+            - padding(style.border.right)
+            - CARET_WIDTH;
+        let mut scroll = input.scroll.clamp(caret - width, caret);
+        scroll = scroll.min(text_width - width).max(0.0);
+        if input.scroll != scroll {
+            input.scroll = scroll;
+        }
+
+        let selection = match input.selection() {
+            Some(selection) if input.preedit.is_empty() => {
+                let start = font.text_width(&input.value[..selection.start], font_size);
+                let end = font.text_width(&input.value[..selection.end], font_size);
+                Some((start, end - start))
+            }
+            _ => None,
+        };
+        set_style(&mut style_query, parts.line, |style| {
+            style.position.left = Val::Px(-scroll);
+            style.min_size.height = Val::Px(line_height);
+        });
+        set_style(&mut style_query, parts.caret, |style| {
+            style.position.left = Val::Px(caret);
+            style.size.height = Val::Px(line_height);
+        });
+        set_style(&mut style_query, parts.selection, |style| {
+            let (left, width) = selection.unwrap_or((0.0, 0.0));
+            style.position.left = Val::Px(left);
+            style.size = Size::new(Val::Px(width), Val::Px(line_height));
+        });
+        if let Ok(mut visibility) = visibility_query.get_mut(parts.selection) {
+            if visibility.is_visible != selection.is_some() {
+                visibility.is_visible = selection.is_some();
+            }
+        }
+    }
+}
+
+fn spawn_parts(commands: &mut Commands, entity: Entity, input: &TextInput) {
+    let absolute = Style {
+        position_type: PositionType::Absolute,
+        position: UiRect {
+            top: Val::Px(0.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let selection = commands
+        .spawn(NodeBundle {
+            style: absolute.clone(),
+            background_color: input.selection_color.into(),
+            focus_policy: FocusPolicy::Pass,
+            ..Default::default()
+        })
+        .id();
+    let text = commands
+        .spawn(TextBundle::from_sections(text_sections(input)))
+        .id();
+    let caret = commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(CARET_WIDTH), Val::Undefined),
+                ..absolute
+            },
+            background_color: input.style.color.into(),
+            focus_policy: FocusPolicy::Pass,
+            ..Default::default()
+        })
+        .id();
+    let line = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            ..Default::default()
+        })
+        .push_children(&[selection, text, caret])
+        .id();
+    commands
+        .entity(entity)
+        .add_child(line)
+        .insert(TextInputParts {
+            line,
+            selection,
+            text,
+            caret,
+        });
+}
+
+/// The text before the caret, the composed text and the text after the caret, or the faded
+/// placeholder
+fn text_sections(input: &TextInput) -> Vec<TextSection> {
+    if input.value.is_empty() && input.preedit.is_empty() {
+        let mut style = input.style.clone();
+        style.color.set_a(style.color.a() * 0.5);
+        return vec![TextSection::new(input.placeholder.clone(), style)];
+    }
+    let (before, after) = input.value.split_at(input.cursor);
+    vec![
+        TextSection::new(before, input.style.clone()),
+        TextSection::new(input.preedit.clone(), input.style.clone()),
+        TextSection::new(after, input.style.clone()),
+    ]
+}
+
+/// Updates the style of a part, only triggering change detection when it's different
+fn set_style(
+    style_query: &mut Query<&mut Style, bevy_ecs::query::Without<TextInput>>,
+    entity: Entity,
+    update: impl FnOnce(&mut Style),
+) {
+    if let Ok(mut style) = style_query.get_mut(entity) {
+        let mut new_style = style.clone();
+        update(&mut new_style);
+        if *style != new_style {
+            *style = new_style;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+
+    #[test]
+    fn editing() {
+        let mut input = TextInput::new("héllo", Default::default());
+        assert_eq!(input.cursor(), 6);
+
+        input.move_left(false);
+        input.move_left(false);
+        input.insert("p\n");
+        assert_eq!(input.value(), "hélplo");
+        input.delete_backward();
+        input.delete_forward();
+        assert_eq!(input.value(), "hélo");
+
+        input.set_cursor(0, false);
+        input.delete_backward();
+        input.move_right(false);
+        input.delete_backward();
+        assert_eq!(input.value(), "élo");
+        input.move_right(false);
+        assert_eq!(input.cursor(), 2);
+
+        input.set_cursor(input.value().len(), false);
+        input.delete_forward();
+        assert_eq!(input.value(), "élo");
+    }
+
+    #[test]
+    fn selection() {
+        let mut input = TextInput::new("hello world", Default::default());
+        input.move_left(true);
+        input.move_left(true);
+        input.move_left(true);
+        assert_eq!(input.selected_text(), "rld");
+
+        input.insert("ld");
+        assert_eq!(input.value(), "hello wold");
+        assert_eq!(input.selection(), None);
+
+        input.set_cursor(0, false);
+        input.set_cursor(5, true);
+        assert_eq!(input.selection(), Some(0..5));
+        input.move_right(false);
+        assert_eq!(input.cursor(), 5);
+        assert_eq!(input.selection(), None);
+
+        input.select_all();
+        assert_eq!(input.selected_text(), "hello wold");
+        input.delete_forward();
+        assert_eq!(input.value(), "");
+
+        // A cursor inside of a character moves to its start
+        let mut input = TextInput::new("é", Default::default());
+        input.set_cursor(1, false);
+        assert_eq!(input.cursor(), 0);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{Node, PositionType, Style, UiScale, Val};
+use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::Without,
+    system::{Query, Res},
+};
+use bevy_hierarchy::Parent;
+use bevy_math::{Mat4, Vec2, Vec3};
+use bevy_render::{camera::Camera, view::Visibility};
+use bevy_transform::components::Transform;
+
+/// Places a UI node over an entity in the world, following it on the screen as it or the camera
+/// moves, for health bars or interaction prompts above characters
+///
+/// The node is positioned absolutely over the whole window, so it should be a root node. Its
+/// [`Visibility`] is managed according to [`WorldAnchor::off_screen`].
+#[derive(Component, Clone, Debug)]
+pub struct WorldAnchor {
+    /// The entity the node follows
+    pub target: Entity,
+    /// An offset from the position of the target, in world space, to place the node above it
+    pub offset: Vec3,
+    /// The camera projecting the target on the screen, or the first active 3D camera if `None`
+    pub camera: Option<Entity>,
+    /// The point of the node placed over the target, from `(0, 0)` at its top left corner to
+    /// `(1, 1)` at its bottom right one
+    pub pivot: Vec2,
+    /// Shrinks the node as the target moves away from the camera, if set
+    pub depth_scaling: Option<DepthScaling>,
+    /// What happens to the node when the target is outside of the view of the camera
+    pub off_screen: OffScreen,
+}
+
+impl WorldAnchor {
+    /// Centers a node over a target, without scaling it
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            offset: Vec3::ZERO,
+            camera: None,
+            pivot: Vec2::splat(0.5),
+            depth_scaling: None,
+            off_screen: OffScreen::default(),
+        }
+    }
+}
+
+/// How a [`WorldAnchor`]ed node is scaled with the distance between its target and the camera
+#[derive(Clone, Copy, Debug)]
+pub struct DepthScaling {
+    /// The distance at which the node has its size from the layout
+    pub reference_distance: f32,
+    /// The smallest scale of the node, when the target is far away
+    pub min_scale: f32,
+    /// The largest scale of the node, when the target is close to the camera
+    pub max_scale: f32,
+}
+
+impl Default for DepthScaling {
+    fn default() -> Self {
+        Self {
+            reference_distance: 10.0,
+            min_scale: 0.25,
+            max_scale: 2.0,
+        }
+    }
+}
+
+/// What happens to a [`WorldAnchor`]ed node when its target is outside of the view of the camera
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OffScreen {
+    /// The node is hidden
+    #[default]
+    Hide,
+    /// The node is kept at the edge of the screen, in the direction of the target
+    Clamp,
+    /// The node goes off the screen with the target, and is only hidden behind the camera
+    Show,
+}
+
+/// The placement of an anchored node on the screen
+#[derive(Debug, PartialEq)]
+struct AnchorPlacement {
+    /// The top left corner of the scaled node, in logical pixels
+    top_left: Vec2,
+    scale: f32,
+    is_visible: bool,
+}
+
+/// The system that moves the nodes with a [`WorldAnchor`] over their targets
+///
+/// It uses the [`Transform`]s of the targets and the cameras rather than their global transforms,
+/// which are only propagated after the layout, so that the nodes don't lag a frame behind.
+pub fn world_anchor_system(
+    ui_scale: Res<UiScale>,
+    camera_query: Query<(Entity, &Camera, Option<&Camera3d>)>,
+    transform_query: Query<(&Transform, Option<&Parent>), Without<WorldAnchor>>,
+    mut node_query: Query<(
+        &WorldAnchor,
+        &Node,
+        &mut Style,
+        &mut Transform,
+        Option<&mut Visibility>,
+    )>,
+) {
+    let default_camera = camera_query
+        .iter()
+        .find(|(_, camera, camera_3d)| camera.is_active && camera_3d.is_some())
+        .map(|(entity, ..)| entity);
+
+    for (anchor, node, mut style, mut transform, visibility) in &mut node_query {
+        let placement = anchor
+            .camera
+            .or(default_camera)
+            .and_then(|entity| camera_query.get(entity).ok())
+            .and_then(|(entity, camera, _)| {
+                let camera_transform = world_transform(entity, &transform_query)?;
+                let target_transform = world_transform(anchor.target, &transform_query)?;
+                let world_position = target_transform * anchor.offset;
+                let view_position = camera_transform
+                    .compute_matrix()
+                    .inverse()
+                    .transform_point3(world_position);
+                Some(place_anchored_node(
+                    anchor,
+                    view_position,
+                    camera.projection_matrix(),
+                    camera.logical_viewport_rect()?,
+                    node.size(),
+                ))
+            });
+        let placement = match placement {
+            Some(placement) => placement,
+            // Without a camera or a target, there's nothing to follow
+            None => {
+                if let Some(mut visibility) = visibility {
+                    if visibility.is_visible {
+                        visibility.is_visible = false;
+                    }
+                }
+                continue;
+            }
+        };
+
+        if let Some(mut visibility) = visibility {
+            if visibility.is_visible != placement.is_visible {
+                visibility.is_visible = placement.is_visible;
+            }
+        }
+
+        // The node is scaled around its center, so its unscaled box is moved to put the scaled
+        // one in place
+        let layout_top_left =
+            placement.top_left + node.size() * placement.scale / 2.0 - node.size() / 2.0;
+        let scale = ui_scale.scale as f32;
+        let left = Val::Px(layout_top_left.x / scale);
+        let top = Val::Px(layout_top_left.y / scale);
+        if style.position_type != PositionType::Absolute
+            || style.position.left != left
+            || style.position.top != top
+        {
+            style.position_type = PositionType::Absolute;
+            style.position.left = left;
+            style.position.top = top;
+        }
+        let node_scale = Vec3::new(placement.scale, placement.scale, 1.0);
+        if transform.scale != node_scale {
+            transform.scale = node_scale;
+        }
+    }
+}
+
+/// The transform of an entity relative to the world, from its own and its ancestors' ones
+fn world_transform(
+    entity: Entity,
+    transform_query: &Query<(&Transform, Option<&Parent>), Without<WorldAnchor>>,
+) -> Option<Transform> {
+    let (transform, mut parent) = transform_query.get(entity).ok()?;
+    let mut world_transform = *transform;
+    while let Some(entity) = parent {
+        let (transform, grandparent) = transform_query.get(entity.get()).ok()?;
+        world_transform = transform.mul_transform(world_transform);
+        parent = grandparent;
+    }
+    Some(world_transform)
+}
+
+/// Projects the target of an anchored node on the screen, from its position relative to the
+/// camera, and places the node over it
+fn place_anchored_node(
+    anchor: &WorldAnchor,
+    view_position: Vec3,
+    projection: Mat4,
+    (viewport_min, viewport_max): (Vec2, Vec2),
+    node_size: Vec2,
+) -> AnchorPlacement {
+    // The camera looks towards -Z
+    let depth = -view_position.z;
+    let behind = depth <= 0.0;
+    let mut ndc = projection.project_point3(view_position).truncate();
+    if behind {
+        // The projection of a point behind the camera is mirrored, and put past the edges of
+        // the screen to be clamped to them
+        ndc = -ndc.normalize_or_zero() * 2.0;
+    }
+    let on_screen = !behind && ndc.abs().cmple(Vec2::ONE).all();
+
+    let scale = match anchor.depth_scaling {
+        Some(scaling) if !behind => {
+            (scaling.reference_distance / depth).clamp(scaling.min_scale, scaling.max_scale)
+        }
+        Some(scaling) => scaling.min_scale,
+        None => 1.0,
+    };
+    let size = node_size * scale;
+
+    // The Y of the normalized device coordinates goes up, and the one of the UI goes down
+    let viewport_size = viewport_max - viewport_min;
+    let position = viewport_min + Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) / 2.0 * viewport_size;
+    let mut top_left = position - anchor.pivot * size;
+    let is_visible = match anchor.off_screen {
+        OffScreen::Hide => on_screen,
+        OffScreen::Clamp => {
+            top_left = top_left.clamp(viewport_min, (viewport_max - size).max(viewport_min));
+            true
+        }
+        OffScreen::Show => !behind,
+    };
+
+    AnchorPlacement {
+        top_left,
+        scale,
+        is_visible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{place_anchored_node, DepthScaling, OffScreen, WorldAnchor};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Mat4, Vec2, Vec3};
+
+    #[test]
+    fn anchored_node_placement() {
+        let projection =
+            Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_2, 2.0, 0.1);
+        let viewport = (Vec2::ZERO, Vec2::new(800.0, 400.0));
+        let node_size = Vec2::new(100.0, 20.0);
+        let mut anchor = WorldAnchor {
+            pivot: Vec2::new(0.5, 1.0),
+            depth_scaling: Some(DepthScaling::default()),
+            ..WorldAnchor::new(Entity::from_raw(0))
+        };
+
+        // Straight ahead, the bottom of the node is at the center of the screen
+        let placement = place_anchored_node(
+            &anchor,
+            Vec3::new(0.0, 0.0, -10.0),
+            projection,
+            viewport,
+            node_size,
+        );
+        assert_eq!(placement.top_left, Vec2::new(350.0, 180.0));
+        assert_eq!(placement.scale, 1.0);
+        assert!(placement.is_visible);
+
+        // Twice as far, the node is half as big
+        let placement = place_anchored_node(
+            &anchor,
+            Vec3::new(0.0, 0.0, -20.0),
+            projection,
+            viewport,
+            node_size,
+        );
+        assert_eq!(placement.scale, 0.5);
+        assert_eq!(placement.top_left, Vec2::new(375.0, 190.0));
+
+        // Off to the right, the node is hidden, or kept at the right edge of the screen
+        let off_screen = Vec3::new(100.0, 0.0, -10.0);
+        let placement = place_anchored_node(&anchor, off_screen, projection, viewport, node_size);
+        assert!(!placement.is_visible);
+        anchor.off_screen = OffScreen::Clamp;
+        let placement = place_anchored_node(&anchor, off_screen, projection, viewport, node_size);
+        assert!(placement.is_visible);
+        assert_eq!(placement.top_left, Vec2::new(700.0, 180.0));
+
+        // Behind the camera on the right, the node is kept at the right edge of the screen too, at
+        // its smallest scale
+        let placement = place_anchored_node(
+            &anchor,
+            Vec3::new(1.0, 0.0, 10.0),
+            projection,
+            viewport,
+            node_size,
+        );
+        assert_eq!(placement.top_left.x, 800.0 - 100.0 * 0.25);
+        anchor.off_screen = OffScreen::Show;
+        let placement = place_anchored_node(
+            &anchor,
+            Vec3::new(1.0, 0.0, 10.0),
+            projection,
+            viewport,
+            node_size,
+        );
+        assert!(!placement.is_visible);
+    }
+}
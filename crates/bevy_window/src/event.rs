@@ -103,6 +103,27 @@ pub struct ReceivedCharacter {
     pub char: char,
 }
 
+/// An event from the input method editor of a window, composing text.
+///
+/// Only sent when it's enabled with [`Window::set_ime_enabled`](crate::Window::set_ime_enabled).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Ime {
+    /// The text being composed changed, and should be shown at the cursor.
+    Preedit {
+        id: WindowId,
+        value: String,
+        /// The byte range of the cursor in the text, if it should be shown
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composed text should be inserted at the cursor.
+    Commit { id: WindowId, value: String },
+    /// The input method editor was enabled, and sends the next events.
+    Enabled { id: WindowId },
+    /// The input method editor was disabled.
+    Disabled { id: WindowId },
+}
+
 /// An event that indicates a window has received or lost focus.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -127,14 +148,32 @@ pub struct WindowBackendScaleFactorChanged {
 }
 
 /// Events related to files being dragged and dropped on a window.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The positions are in logical pixels, like [`Window::cursor_position`](crate::Window::cursor_position),
+/// and are `None` if the cursor position over the window isn't known yet.
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum FileDragAndDrop {
-    DroppedFile { id: WindowId, path_buf: PathBuf },
-
-    HoveredFile { id: WindowId, path_buf: PathBuf },
-
-    HoveredFileCancelled { id: WindowId },
+    /// Files were dropped on the window.
+    DroppedFiles {
+        id: WindowId,
+        paths: Vec<PathBuf>,
+        position: Option<Vec2>,
+    },
+    /// Files started being dragged over the window.
+    HoveredFiles {
+        id: WindowId,
+        paths: Vec<PathBuf>,
+        position: Option<Vec2>,
+    },
+    /// The files dragged over the window moved.
+    ///
+    /// Not all platforms report the cursor moving during a drag, in which case only the position
+    /// of the [`HoveredFiles`](Self::HoveredFiles) and [`DroppedFiles`](Self::DroppedFiles)
+    /// events is known.
+    HoveredFilesMoved { id: WindowId, position: Vec2 },
+    /// The files dragged over the window left it without being dropped.
+    HoveredFilesCancelled { id: WindowId },
 }
 
 /// An event that is sent when a window is repositioned in physical pixels.
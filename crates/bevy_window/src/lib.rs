@@ -1,6 +1,7 @@
 #[warn(missing_docs)]
 mod cursor;
 mod event;
+mod monitor;
 mod raw_handle;
 mod system;
 mod window;
@@ -9,6 +10,7 @@ mod windows;
 pub use crate::raw_handle::*;
 pub use cursor::*;
 pub use event::*;
+pub use monitor::*;
 pub use system::*;
 pub use window::*;
 pub use windows::*;
@@ -16,9 +18,9 @@ pub use windows::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, MonitorSelection,
-        ReceivedCharacter, Window, WindowDescriptor, WindowMode, WindowMoved, WindowPosition,
-        Windows,
+        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, Ime, Monitor,
+        MonitorSelection, Monitors, ReceivedCharacter, VideoMode, Window, WindowDescriptor,
+        WindowMode, WindowMoved, WindowPosition, Windows,
     };
 }
 
@@ -84,12 +86,14 @@ impl Plugin for WindowPlugin {
             .add_event::<CursorEntered>()
             .add_event::<CursorLeft>()
             .add_event::<ReceivedCharacter>()
+            .add_event::<Ime>()
             .add_event::<WindowFocused>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
-            .init_resource::<Windows>();
+            .init_resource::<Windows>()
+            .init_resource::<Monitors>();
 
         let settings = app
             .world
@@ -0,0 +1,113 @@
+use bevy_ecs::system::Resource;
+use bevy_math::{IVec2, UVec2, Vec2};
+
+/// A video mode of a [`Monitor`], which can be used for
+/// [exclusive fullscreen](crate::WindowMode::ExclusiveFullscreen).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct VideoMode {
+    /// The resolution of the monitor in this mode, in physical pixels.
+    pub physical_size: UVec2,
+    /// The number of bits used to represent the color of a pixel.
+    pub bit_depth: u16,
+    /// The refresh rate of the monitor in this mode, in millihertz.
+    pub refresh_rate_millihertz: u32,
+}
+
+/// A monitor connected to the system, as listed in the [`Monitors`] resource.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Monitor {
+    /// The name of the monitor, if the platform reports one.
+    pub name: Option<String>,
+    /// The resolution of the monitor, in physical pixels.
+    pub physical_size: UVec2,
+    /// The position of the top-left corner of the monitor on the desktop, in physical pixels.
+    pub physical_position: IVec2,
+    /// The ratio of physical pixels to logical pixels of the windows on this monitor.
+    pub scale_factor: f64,
+    /// The current refresh rate of the monitor in millihertz, if the platform reports it.
+    pub refresh_rate_millihertz: Option<u32>,
+    /// The video modes the monitor supports in exclusive fullscreen.
+    pub video_modes: Vec<VideoMode>,
+}
+
+impl Monitor {
+    /// The resolution of the monitor, in logical pixels.
+    #[inline]
+    pub fn logical_size(&self) -> Vec2 {
+        (self.physical_size.as_dvec2() / self.scale_factor).as_vec2()
+    }
+
+    /// The video mode of the monitor that is the closest to the requested resolution, preferring
+    /// the highest refresh rate among the modes of that resolution.
+    pub fn closest_video_mode(&self, physical_size: UVec2) -> Option<VideoMode> {
+        self.video_modes.iter().copied().min_by_key(|mode| {
+            (
+                mode.physical_size.x.abs_diff(physical_size.x),
+                mode.physical_size.y.abs_diff(physical_size.y),
+                u32::MAX - mode.refresh_rate_millihertz,
+            )
+        })
+    }
+}
+
+/// The monitors connected to the system, kept up to date by the windowing backend.
+///
+/// The index of a monitor in this list is the one used by [`MonitorSelection::Index`](crate::MonitorSelection::Index).
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct Monitors {
+    monitors: Vec<Monitor>,
+    primary: Option<usize>,
+}
+
+impl Monitors {
+    /// Creates the list of monitors, with the index of the primary one if it is known.
+    pub fn new(monitors: Vec<Monitor>, primary: Option<usize>) -> Self {
+        Monitors { monitors, primary }
+    }
+
+    /// Get the monitor at `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Monitor> {
+        self.monitors.get(index)
+    }
+
+    /// Get the primary monitor of the system, if the platform reports one.
+    #[inline]
+    pub fn primary(&self) -> Option<&Monitor> {
+        self.primary.and_then(|index| self.get(index))
+    }
+
+    /// Get the index of the primary monitor of the system, if the platform reports one.
+    #[inline]
+    pub fn primary_index(&self) -> Option<usize> {
+        self.primary
+    }
+
+    /// Get the monitor that contains a point of the desktop, in physical pixels, such as the
+    /// position of a [`Window`](crate::Window).
+    pub fn at(&self, physical_position: IVec2) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| {
+            let offset = physical_position - monitor.physical_position;
+            offset.cmpge(IVec2::ZERO).all() && offset.as_uvec2().cmplt(monitor.physical_size).all()
+        })
+    }
+
+    /// An iterator over the monitors, in the order of their index.
+    pub fn iter(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+
+    /// The number of connected monitors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// Whether no monitor is connected, or the windowing backend doesn't report them.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+}
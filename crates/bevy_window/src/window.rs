@@ -1,3 +1,4 @@
+use crate::VideoMode;
 use bevy_ecs::system::Resource;
 use bevy_math::{DVec2, IVec2, UVec2, Vec2};
 use bevy_reflect::{FromReflect, Reflect};
@@ -258,6 +259,8 @@ pub struct Window {
     cursor_visible: bool,
     cursor_grab_mode: CursorGrabMode,
     physical_cursor_position: Option<DVec2>,
+    ime_enabled: bool,
+    ime_position: Vec2,
     raw_handle: Option<RawHandleWrapper>,
     focused: bool,
     mode: WindowMode,
@@ -272,10 +275,11 @@ pub struct Window {
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum WindowCommand {
-    /// Set the window's [`WindowMode`].
+    /// Set the window's [`WindowMode`], going fullscreen on the selected monitor.
     SetWindowMode {
         mode: WindowMode,
         resolution: UVec2,
+        monitor_selection: MonitorSelection,
     },
     /// Set the window's title.
     SetTitle {
@@ -320,6 +324,14 @@ pub enum WindowCommand {
     SetCursorPosition {
         position: Vec2,
     },
+    /// Set whether or not the input method editor is enabled.
+    SetImeEnabled {
+        enabled: bool,
+    },
+    /// Set the position of the input method editor, in logical pixels.
+    SetImePosition {
+        position: Vec2,
+    },
     /// Set whether or not the window is maximized.
     SetMaximized {
         maximized: bool,
@@ -370,6 +382,12 @@ pub enum WindowMode {
     SizedFullscreen,
     /// Creates a fullscreen window that uses the maximum supported size.
     Fullscreen,
+    /// Creates a fullscreen window that uses the given video mode of the monitor, such as one of
+    /// the [`Monitor::video_modes`](crate::Monitor::video_modes).
+    ///
+    /// Falls back to the mode of the monitor with the closest resolution if the monitor doesn't
+    /// support this one.
+    ExclusiveFullscreen(VideoMode),
 }
 
 impl Window {
@@ -401,6 +419,8 @@ impl Window {
             cursor_grab_mode: window_descriptor.cursor_grab_mode,
             cursor_icon: CursorIcon::Default,
             physical_cursor_position: None,
+            ime_enabled: false,
+            ime_position: Vec2::ZERO,
             raw_handle,
             focused: true,
             mode: window_descriptor.mode,
@@ -735,6 +755,36 @@ impl Window {
             .push(WindowCommand::SetCursorPosition { position });
     }
 
+    /// Get whether or not the input method editor is enabled.
+    #[inline]
+    pub fn ime_enabled(&self) -> bool {
+        self.ime_enabled
+    }
+    /// Set whether or not the input method editor is enabled, sending the [`Ime`](crate::Ime)
+    /// events rather than the [`ReceivedCharacter`](crate::ReceivedCharacter) ones while
+    /// composing text.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`iOS`**, **`Android`** and **`Web`** don't have an input method editor.
+    pub fn set_ime_enabled(&mut self, enabled: bool) {
+        self.ime_enabled = enabled;
+        self.command_queue
+            .push(WindowCommand::SetImeEnabled { enabled });
+    }
+    /// Get the position of the input method editor, in logical pixels.
+    #[inline]
+    pub fn ime_position(&self) -> Vec2 {
+        self.ime_position
+    }
+    /// Set the position of the input method editor, in logical pixels, such as under the text
+    /// being typed.
+    pub fn set_ime_position(&mut self, position: Vec2) {
+        self.ime_position = position;
+        self.command_queue
+            .push(WindowCommand::SetImePosition { position });
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn update_focused_status_from_backend(&mut self, focused: bool) {
@@ -753,10 +803,18 @@ impl Window {
     }
     /// Set the window's [`WindowMode`]
     pub fn set_mode(&mut self, mode: WindowMode) {
+        self.set_mode_on_monitor(mode, MonitorSelection::Current);
+    }
+    /// Set the window's [`WindowMode`], going fullscreen on the selected monitor.
+    ///
+    /// The monitor is ignored with [`WindowMode::Windowed`], use [`Window::set_position`] or
+    /// [`Window::center_window`] to move the window to another monitor.
+    pub fn set_mode_on_monitor(&mut self, mode: WindowMode, monitor_selection: MonitorSelection) {
         self.mode = mode;
         self.command_queue.push(WindowCommand::SetWindowMode {
             mode,
             resolution: UVec2::new(self.physical_width, self.physical_height),
+            monitor_selection,
         });
     }
     /// Close the operating system window corresponding to this [`Window`].
@@ -836,6 +894,8 @@ pub enum WindowPosition {
 }
 
 /// Defines which monitor to use.
+///
+/// The connected monitors are listed in the [`Monitors`](crate::Monitors) resource.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MonitorSelection {
@@ -845,7 +905,7 @@ pub enum MonitorSelection {
     Current,
     /// Uses primary monitor of the system.
     Primary,
-    /// Uses monitor with the specified index.
+    /// Uses monitor with the specified index in the [`Monitors`](crate::Monitors) resource.
     Index(usize),
 }
 
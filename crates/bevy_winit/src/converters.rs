@@ -4,8 +4,8 @@ use bevy_input::{
     touch::{ForceTouch, TouchInput, TouchPhase},
     ButtonState,
 };
-use bevy_math::Vec2;
-use bevy_window::{CursorGrabMode, CursorIcon};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_window::{CursorGrabMode, CursorIcon, Monitor, VideoMode};
 
 pub fn convert_keyboard_input(keyboard_input: &winit::event::KeyboardInput) -> KeyboardInput {
     KeyboardInput {
@@ -275,3 +275,28 @@ pub fn convert_cursor_grab_mode(mode: CursorGrabMode) -> winit::window::CursorGr
         CursorGrabMode::Locked => winit::window::CursorGrabMode::Locked,
     }
 }
+
+pub fn convert_video_mode(video_mode: &winit::monitor::VideoMode) -> VideoMode {
+    let size = video_mode.size();
+    VideoMode {
+        physical_size: UVec2::new(size.width, size.height),
+        bit_depth: video_mode.bit_depth(),
+        refresh_rate_millihertz: video_mode.refresh_rate_millihertz(),
+    }
+}
+
+pub fn convert_monitor(monitor: &winit::monitor::MonitorHandle) -> Monitor {
+    let size = monitor.size();
+    let position = monitor.position();
+    Monitor {
+        name: monitor.name(),
+        physical_size: UVec2::new(size.width, size.height),
+        physical_position: IVec2::new(position.x, position.y),
+        scale_factor: monitor.scale_factor(),
+        refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+        video_modes: monitor
+            .video_modes()
+            .map(|video_mode| convert_video_mode(&video_mode))
+            .collect(),
+    }
+}
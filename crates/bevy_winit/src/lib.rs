@@ -22,15 +22,16 @@ use bevy_input::{
 use bevy_math::{ivec2, DVec2, UVec2, Vec2};
 use bevy_utils::{
     tracing::{error, info, trace, warn},
-    Instant,
+    Duration, HashMap, Instant,
 };
 use bevy_window::{
-    CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ModifiesWindows,
-    ReceivedCharacter, RequestRedraw, WindowBackendScaleFactorChanged, WindowCloseRequested,
-    WindowClosed, WindowCreated, WindowFocused, WindowMoved, WindowResized,
-    WindowScaleFactorChanged, Windows,
+    CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, ModifiesWindows,
+    Monitors, ReceivedCharacter, RequestRedraw, Window, WindowBackendScaleFactorChanged,
+    WindowCloseRequested, WindowClosed, WindowCreated, WindowFocused, WindowId, WindowMoved,
+    WindowResized, WindowScaleFactorChanged, Windows,
 };
 
+use std::path::PathBuf;
 use winit::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition},
     event::{self, DeviceEvent, Event, StartCause, WindowEvent},
@@ -82,25 +83,45 @@ fn change_window(
                             x: width,
                             y: height,
                         },
+                    monitor_selection,
                 } => {
                     let window = winit_windows.get_window(id).unwrap();
+
+                    use bevy_window::MonitorSelection::*;
+                    let maybe_monitor = match monitor_selection {
+                        Current => window.current_monitor(),
+                        Primary => window.primary_monitor(),
+                        Index(i) => window.available_monitors().nth(i),
+                    };
+                    let monitor = match (mode, maybe_monitor) {
+                        (bevy_window::WindowMode::Windowed, _) => None,
+                        (_, Some(monitor)) => Some(monitor),
+                        (_, None) => {
+                            warn!("Couldn't get monitor selected with: {monitor_selection:?}");
+                            continue;
+                        }
+                    };
                     match mode {
                         bevy_window::WindowMode::BorderlessFullscreen => {
-                            window
-                                .set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)));
+                            window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(
+                                monitor,
+                            )));
                         }
                         bevy_window::WindowMode::Fullscreen => {
                             window.set_fullscreen(Some(winit::window::Fullscreen::Exclusive(
-                                get_best_videomode(&window.current_monitor().unwrap()),
+                                get_best_videomode(&monitor.unwrap()),
+                            )));
+                        }
+                        bevy_window::WindowMode::SizedFullscreen => {
+                            window.set_fullscreen(Some(winit::window::Fullscreen::Exclusive(
+                                get_fitting_videomode(&monitor.unwrap(), width, height),
+                            )));
+                        }
+                        bevy_window::WindowMode::ExclusiveFullscreen(video_mode) => {
+                            window.set_fullscreen(Some(winit::window::Fullscreen::Exclusive(
+                                get_selected_videomode(&monitor.unwrap(), video_mode),
                             )));
                         }
-                        bevy_window::WindowMode::SizedFullscreen => window.set_fullscreen(Some(
-                            winit::window::Fullscreen::Exclusive(get_fitting_videomode(
-                                &window.current_monitor().unwrap(),
-                                width,
-                                height,
-                            )),
-                        )),
                         bevy_window::WindowMode::Windowed => window.set_fullscreen(None),
                     }
                 }
@@ -155,6 +176,14 @@ fn change_window(
                         .set_cursor_position(LogicalPosition::new(position.x, position.y))
                         .unwrap_or_else(|e| error!("Unable to set cursor position: {}", e));
                 }
+                bevy_window::WindowCommand::SetImeEnabled { enabled } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_ime_allowed(enabled);
+                }
+                bevy_window::WindowCommand::SetImePosition { position } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_ime_position(LogicalPosition::new(position.x, position.y));
+                }
                 bevy_window::WindowCommand::SetMaximized { maximized } => {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_maximized(maximized);
@@ -318,7 +347,21 @@ struct WinitPersistentState {
     /// Tracks if the event loop was started this frame because of a `WaitUntil` timeout.
     timeout_reached: bool,
     last_update: Instant,
+    /// The files dragged over or dropped on each window since the last update, sent together.
+    file_drags: HashMap<WindowId, FileDrag>,
+    /// When the [`Monitors`] were last listed, or `None` if they never were.
+    last_monitors_update: Option<Instant>,
+}
+
+/// The files being dragged over a window, which winit reports one by one.
+#[derive(Default)]
+struct FileDrag {
+    /// Whether files are being dragged over the window.
+    hovering: bool,
+    hovered: Vec<PathBuf>,
+    dropped: Vec<PathBuf>,
 }
+
 impl Default for WinitPersistentState {
     fn default() -> Self {
         Self {
@@ -327,10 +370,62 @@ impl Default for WinitPersistentState {
             redraw_request_sent: false,
             timeout_reached: false,
             last_update: Instant::now(),
+            file_drags: HashMap::default(),
+            last_monitors_update: None,
         }
     }
 }
 
+/// How often the [`Monitors`] are listed again, since winit doesn't report monitors being
+/// connected or disconnected.
+const MONITORS_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lists the connected monitors, only changing the [`Monitors`] resource if they changed.
+fn update_monitors(world: &mut World, event_loop: &EventLoopWindowTarget<()>) {
+    let primary = event_loop.primary_monitor();
+    let mut primary_index = None;
+    let monitors = event_loop
+        .available_monitors()
+        .enumerate()
+        .map(|(index, monitor)| {
+            if primary.as_ref() == Some(&monitor) {
+                primary_index = Some(index);
+            }
+            converters::convert_monitor(&monitor)
+        })
+        .collect();
+    let monitors = Monitors::new(monitors, primary_index);
+    let mut current_monitors = world.resource_mut::<Monitors>();
+    if *current_monitors != monitors {
+        *current_monitors = monitors;
+    }
+}
+
+/// Sends the files dragged over or dropped on the windows, gathered from the winit events.
+fn send_file_drag_events(world: &mut World, file_drags: &mut HashMap<WindowId, FileDrag>) {
+    world.resource_scope(|world, mut events: Mut<Events<FileDragAndDrop>>| {
+        let windows = world.resource::<Windows>();
+        for (&id, drag) in file_drags.iter_mut() {
+            let position = windows.get(id).and_then(Window::cursor_position);
+            if !drag.hovered.is_empty() {
+                events.send(FileDragAndDrop::HoveredFiles {
+                    id,
+                    paths: std::mem::take(&mut drag.hovered),
+                    position,
+                });
+            }
+            if !drag.dropped.is_empty() {
+                events.send(FileDragAndDrop::DroppedFiles {
+                    id,
+                    paths: std::mem::take(&mut drag.dropped),
+                    position,
+                });
+            }
+        }
+    });
+    file_drags.retain(|_, drag| drag.hovering);
+}
+
 #[derive(Default, Resource)]
 struct WinitCreateWindowReader(ManualEventReader<CreateWindow>);
 
@@ -434,10 +529,24 @@ pub fn winit_runner_with(mut app: App) {
                         window
                             .update_cursor_physical_position_from_backend(Some(physical_position));
 
+                        let position = (physical_position / window.scale_factor()).as_vec2();
                         cursor_moved_events.send(CursorMoved {
                             id: window_id,
-                            position: (physical_position / window.scale_factor()).as_vec2(),
+                            position,
                         });
+
+                        // Files that are still pending are sent with the position of the cursor
+                        let hovering = matches!(
+                            winit_state.file_drags.get(&window_id),
+                            Some(drag) if drag.hovering && drag.hovered.is_empty()
+                        );
+                        if hovering {
+                            let mut events = world.resource_mut::<Events<FileDragAndDrop>>();
+                            events.send(FileDragAndDrop::HoveredFilesMoved {
+                                id: window_id,
+                                position,
+                            });
+                        }
                     }
                     WindowEvent::CursorEntered { .. } => {
                         let mut cursor_entered_events =
@@ -499,6 +608,22 @@ pub fn winit_runner_with(mut app: App) {
                             char: c,
                         });
                     }
+                    WindowEvent::Ime(event) => {
+                        let mut ime_events = world.resource_mut::<Events<Ime>>();
+                        ime_events.send(match event {
+                            event::Ime::Preedit(value, cursor) => Ime::Preedit {
+                                id: window_id,
+                                value,
+                                cursor,
+                            },
+                            event::Ime::Commit(value) => Ime::Commit {
+                                id: window_id,
+                                value,
+                            },
+                            event::Ime::Enabled => Ime::Enabled { id: window_id },
+                            event::Ime::Disabled => Ime::Disabled { id: window_id },
+                        });
+                    }
                     WindowEvent::ScaleFactorChanged {
                         scale_factor,
                         new_inner_size,
@@ -558,22 +683,25 @@ pub fn winit_runner_with(mut app: App) {
                         });
                     }
                     WindowEvent::DroppedFile(path_buf) => {
-                        let mut events = world.resource_mut::<Events<FileDragAndDrop>>();
-                        events.send(FileDragAndDrop::DroppedFile {
-                            id: window_id,
-                            path_buf,
-                        });
+                        let drag = winit_state.file_drags.entry(window_id).or_default();
+                        drag.hovering = false;
+                        drag.dropped.push(path_buf);
                     }
                     WindowEvent::HoveredFile(path_buf) => {
-                        let mut events = world.resource_mut::<Events<FileDragAndDrop>>();
-                        events.send(FileDragAndDrop::HoveredFile {
-                            id: window_id,
-                            path_buf,
-                        });
+                        let drag = winit_state.file_drags.entry(window_id).or_default();
+                        drag.hovering = true;
+                        drag.hovered.push(path_buf);
                     }
                     WindowEvent::HoveredFileCancelled => {
-                        let mut events = world.resource_mut::<Events<FileDragAndDrop>>();
-                        events.send(FileDragAndDrop::HoveredFileCancelled { id: window_id });
+                        let drag = winit_state.file_drags.entry(window_id).or_default();
+                        drag.hovering = false;
+                        // The hovered files that weren't sent yet don't need to be cancelled
+                        if drag.hovered.is_empty() {
+                            let mut events = world.resource_mut::<Events<FileDragAndDrop>>();
+                            events.send(FileDragAndDrop::HoveredFilesCancelled { id: window_id });
+                        } else {
+                            drag.hovered.clear();
+                        }
                     }
                     WindowEvent::Moved(position) => {
                         let position = ivec2(position.x, position.y);
@@ -603,6 +731,18 @@ pub fn winit_runner_with(mut app: App) {
                 winit_state.active = true;
             }
             event::Event::MainEventsCleared => {
+                let now = Instant::now();
+                let monitors_outdated = match winit_state.last_monitors_update {
+                    Some(last_update) => {
+                        now.duration_since(last_update) >= MONITORS_UPDATE_INTERVAL
+                    }
+                    None => true,
+                };
+                if monitors_outdated {
+                    update_monitors(&mut app.world, event_loop);
+                    winit_state.last_monitors_update = Some(now);
+                }
+                send_file_drag_events(&mut app.world, &mut winit_state.file_drags);
                 handle_create_window_events(
                     &mut app.world,
                     event_loop,
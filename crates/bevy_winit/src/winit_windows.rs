@@ -1,8 +1,8 @@
-use crate::converters::convert_cursor_grab_mode;
+use crate::converters::{convert_cursor_grab_mode, convert_video_mode};
 use bevy_math::{DVec2, IVec2};
 use bevy_utils::HashMap;
 use bevy_window::{
-    MonitorSelection, RawHandleWrapper, Window, WindowDescriptor, WindowId, WindowMode,
+    MonitorSelection, RawHandleWrapper, VideoMode, Window, WindowDescriptor, WindowId, WindowMode,
 };
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 use winit::{
@@ -62,6 +62,11 @@ impl WinitWindows {
                     window_descriptor.height as u32,
                 )),
             )),
+            WindowMode::ExclusiveFullscreen(video_mode) => {
+                winit_window_builder.with_fullscreen(Some(Fullscreen::Exclusive(
+                    get_selected_videomode(&selected_or_primary_monitor.unwrap(), video_mode),
+                )))
+            }
             _ => {
                 if let Some(sf) = scale_factor_override {
                     winit_window_builder.with_inner_size(logical_size.to_physical::<f64>(sf))
@@ -260,6 +265,24 @@ pub fn get_fitting_videomode(
     modes.first().unwrap().clone()
 }
 
+/// Gets the video mode of the monitor matching the selected one, or the one with the closest
+/// resolution if the monitor doesn't support it.
+pub fn get_selected_videomode(
+    monitor: &winit::monitor::MonitorHandle,
+    video_mode: VideoMode,
+) -> winit::monitor::VideoMode {
+    monitor
+        .video_modes()
+        .find(|mode| convert_video_mode(mode) == video_mode)
+        .unwrap_or_else(|| {
+            get_fitting_videomode(
+                monitor,
+                video_mode.physical_size.x,
+                video_mode.physical_size.y,
+            )
+        })
+}
+
 pub fn get_best_videomode(monitor: &winit::monitor::MonitorHandle) -> winit::monitor::VideoMode {
     let mut modes = monitor.video_modes().collect::<Vec<_>>();
     modes.sort_by(|a, b| {
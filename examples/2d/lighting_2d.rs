@@ -0,0 +1,143 @@
+//! Lights sprites with 2D point lights casting shadows from occluders, an ambient light and a
+//! normal map.
+
+use std::f32::consts::TAU;
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // The sprites out of reach of the lights are dimmed instead of black
+        .insert_resource(AmbientLight2d {
+            color: Color::rgb(0.6, 0.6, 1.0),
+            brightness: 0.15,
+        })
+        .add_startup_system(setup)
+        .add_system(move_lights)
+        .run();
+}
+
+#[derive(Component)]
+struct Orbit {
+    radius: f32,
+    speed: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    // A floor of logos, lit by the lights
+    let texture = asset_server.load("branding/icon.png");
+    for x in -3..=3 {
+        for y in -2..=2 {
+            commands.spawn(SpriteBundle {
+                texture: texture.clone(),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(120.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x as f32 * 128.0, y as f32 * 128.0, 0.0),
+                ..default()
+            });
+        }
+    }
+
+    // Walls casting shadows
+    for (position, size) in [
+        (Vec2::new(-150.0, 80.0), Vec2::new(20.0, 160.0)),
+        (Vec2::new(150.0, -80.0), Vec2::new(160.0, 20.0)),
+    ] {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::DARK_GRAY,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(1.0)),
+                ..default()
+            },
+            LightOccluder2d {
+                half_size: size / 2.0,
+            },
+        ));
+    }
+
+    // A dome lit from the side the lights are on, with its normal map
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(160.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            ..default()
+        },
+        SpriteNormalMap(images.add(dome_normal_map())),
+    ));
+
+    // Lights orbiting around the center
+    for (i, color) in [Color::ORANGE, Color::CYAN, Color::WHITE]
+        .into_iter()
+        .enumerate()
+    {
+        commands.spawn((
+            PointLight2dBundle {
+                point_light: PointLight2d {
+                    color,
+                    intensity: 1.5,
+                    radius: 400.0,
+                    shadows_enabled: true,
+                    ..default()
+                },
+                ..default()
+            },
+            Orbit {
+                radius: 120.0 + i as f32 * 90.0,
+                speed: 0.4 + i as f32 * 0.2,
+            },
+        ));
+    }
+}
+
+fn move_lights(time: Res<Time>, mut lights: Query<(&Orbit, &mut Transform)>) {
+    for (orbit, mut transform) in &mut lights {
+        let angle = (time.seconds_since_startup() as f32 * orbit.speed) % TAU;
+        transform.translation = Vec3::new(angle.cos(), angle.sin(), 0.0) * orbit.radius;
+    }
+}
+
+/// The normal map of a half sphere, with X going right and Y going up.
+fn dome_normal_map() -> Image {
+    const SIZE: usize = 64;
+    let mut data = Vec::with_capacity(SIZE * SIZE * 4);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            // The rows of the image go down
+            let position = Vec2::new(x as f32 + 0.5, (SIZE - y) as f32 - 0.5) / SIZE as f32;
+            let xy = (position * 2.0 - 1.0).clamp_length_max(1.0);
+            let normal = xy.extend((1.0 - xy.length_squared()).max(0.0).sqrt());
+            let encoded = (normal * 0.5 + 0.5) * 255.0;
+            data.extend([encoded.x as u8, encoded.y as u8, encoded.z as u8, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SIZE as u32,
+            height: SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        // Normal maps aren't colors, so they are linear
+        TextureFormat::Rgba8Unorm,
+    )
+}
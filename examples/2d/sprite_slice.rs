@@ -0,0 +1,80 @@
+//! Draws the same image at several sizes with an [`ImageScaleMode`]: 9-sliced, so that its
+//! corners keep their size, and tiled, so that it's repeated instead of stretched.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    let texture = asset_server.load("branding/icon.png");
+
+    // The corners of the logo keep their size, and its sides and center are stretched
+    let slicer = TextureSlicer {
+        border: BorderRect::square(64.0),
+        max_corner_scale: 0.5,
+        ..default()
+    };
+    for (i, size) in [
+        Vec2::new(100.0, 100.0),
+        Vec2::new(300.0, 100.0),
+        Vec2::new(100.0, 300.0),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        commands.spawn((
+            SpriteBundle {
+                texture: texture.clone(),
+                sprite: Sprite {
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(-450.0 + i as f32 * 250.0, 100.0, 0.0),
+                ..default()
+            },
+            ImageScaleMode::Sliced(slicer),
+        ));
+    }
+
+    // The sides and the center are repeated instead
+    commands.spawn((
+        SpriteBundle {
+            texture: texture.clone(),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(300.0, 300.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(300.0, 100.0, 0.0),
+            ..default()
+        },
+        ImageScaleMode::Sliced(TextureSlicer {
+            center_scale_mode: SliceScaleMode::Tile { stretch_value: 0.5 },
+            sides_scale_mode: SliceScaleMode::Tile { stretch_value: 0.5 },
+            ..slicer
+        }),
+    ));
+
+    // The whole logo is repeated along X, and stretched along Y
+    commands.spawn((
+        SpriteBundle {
+            texture,
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(800.0, 100.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, -200.0, 0.0),
+            ..default()
+        },
+        ImageScaleMode::Tiled {
+            tile_x: true,
+            tile_y: false,
+            stretch_value: 0.5,
+        },
+    ));
+}
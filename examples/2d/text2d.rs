@@ -32,6 +32,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         font,
         font_size: 60.0,
         color: Color::WHITE,
+        ..default()
     };
     let text_alignment = TextAlignment::CENTER;
     // 2d camera
@@ -61,6 +62,27 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         },
         AnimateScale,
     ));
+    // Demonstrate an outline and a shadow
+    commands.spawn(Text2dBundle {
+        text: Text::from_section(
+            "outline and shadow",
+            TextStyle {
+                color: Color::ORANGE,
+                outline: Some(TextOutline {
+                    width: 2.0,
+                    color: Color::BLACK,
+                }),
+                shadow: Some(TextShadow {
+                    offset: Vec2::new(4.0, 4.0),
+                    color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+                }),
+                ..text_style.clone()
+            },
+        )
+        .with_alignment(text_alignment),
+        transform: Transform::from_xyz(0.0, 250.0, 0.0),
+        ..default()
+    });
     // Demonstrate text wrapping
     let box_size = Vec2::new(300.0, 200.0);
     let box_position = Vec2::new(0.0, -250.0);
@@ -1,66 +1,39 @@
 //! In this example we generate a new texture atlas (sprite sheet) from a folder containing
-//! individual sprites.
+//! individual sprites, packed at runtime once they are loaded.
 
-use bevy::{asset::LoadState, prelude::*};
+use bevy::prelude::*;
 
 fn main() {
     App::new()
-        .init_resource::<RpgSpriteHandles>()
         .insert_resource(ImageSettings::default_nearest()) // prevents blurry sprites
         .add_plugins(DefaultPlugins)
-        .add_state(AppState::Setup)
-        .add_system_set(SystemSet::on_enter(AppState::Setup).with_system(load_textures))
-        .add_system_set(SystemSet::on_update(AppState::Setup).with_system(check_textures))
-        .add_system_set(SystemSet::on_enter(AppState::Finished).with_system(setup))
+        .add_startup_system(setup)
+        .add_system(show_atlas_texture)
         .run();
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum AppState {
-    Setup,
-    Finished,
-}
-
-#[derive(Resource, Default)]
-struct RpgSpriteHandles {
-    handles: Vec<HandleUntyped>,
-}
-
-fn load_textures(mut rpg_sprite_handles: ResMut<RpgSpriteHandles>, asset_server: Res<AssetServer>) {
-    rpg_sprite_handles.handles = asset_server.load_folder("textures/rpg").unwrap();
-}
-
-fn check_textures(
-    mut state: ResMut<State<AppState>>,
-    rpg_sprite_handles: ResMut<RpgSpriteHandles>,
-    asset_server: Res<AssetServer>,
-) {
-    if let LoadState::Loaded =
-        asset_server.get_group_load_state(rpg_sprite_handles.handles.iter().map(|handle| handle.id))
-    {
-        state.set(AppState::Finished).unwrap();
-    }
-}
+#[derive(Component)]
+struct AtlasTexture(Handle<TextureAtlas>);
 
 fn setup(
     mut commands: Commands,
-    rpg_sprite_handles: Res<RpgSpriteHandles>,
     asset_server: Res<AssetServer>,
+    mut texture_atlas_packer: ResMut<TextureAtlasPacker>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
-    mut textures: ResMut<Assets<Image>>,
 ) {
-    let mut texture_atlas_builder = TextureAtlasBuilder::default();
-    for handle in &rpg_sprite_handles.handles {
-        let handle = handle.typed_weak();
-        let texture = textures.get(&handle).expect("Textures folder contained a file which way matched by a loader which did not create an `Image` asset");
-        texture_atlas_builder.add_texture(handle, texture);
-    }
-
-    let texture_atlas = texture_atlas_builder.finish(&mut textures).unwrap();
-    let texture_atlas_texture = texture_atlas.texture.clone();
+    // the atlas is packed again when any of its sprites is hot-reloaded
+    let atlas_handle = texture_atlas_packer
+        .add_folder(
+            &asset_server,
+            &mut texture_atlases,
+            "textures/rpg",
+            TextureAtlasBuilder::default(),
+        )
+        .unwrap();
     let vendor_handle = asset_server.get_handle("textures/rpg/chars/vendor/generic-rpg-vendor.png");
-    let vendor_index = texture_atlas.get_texture_index(&vendor_handle).unwrap();
-    let atlas_handle = texture_atlases.add(texture_atlas);
+    let vendor_index = texture_atlas_packer
+        .texture_index(&atlas_handle, &vendor_handle)
+        .unwrap();
 
     // set up a scene to display our texture atlas
     commands.spawn(Camera2dBundle::default());
@@ -72,13 +45,31 @@ fn setup(
             ..default()
         },
         sprite: TextureAtlasSprite::new(vendor_index),
-        texture_atlas: atlas_handle,
+        texture_atlas: atlas_handle.clone(),
         ..default()
     });
     // draw the atlas itself
-    commands.spawn(SpriteBundle {
-        texture: texture_atlas_texture,
-        transform: Transform::from_xyz(-300.0, 0.0, 0.0),
-        ..default()
-    });
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(-300.0, 0.0, 0.0),
+            ..default()
+        },
+        AtlasTexture(atlas_handle),
+    ));
+}
+
+fn show_atlas_texture(
+    mut atlas_events: EventReader<AssetEvent<TextureAtlas>>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut query: Query<(&AtlasTexture, &mut Handle<Image>)>,
+) {
+    for event in atlas_events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            for (atlas_texture, mut texture) in &mut query {
+                if atlas_texture.0 == *handle {
+                    *texture = texture_atlases.get(handle).unwrap().texture.clone();
+                }
+            }
+        }
+    }
 }
@@ -3,12 +3,17 @@
 use std::f32::consts::PI;
 
 use bevy::{
-    pbr::{
-        wireframe::{Wireframe, WireframePlugin},
-        NotShadowCaster, NotShadowReceiver,
+    core_pipeline::core_3d::TransparentSortConfig,
+    debug_draw::{
+        debug_aabb::{DebugAabb, DebugBoxStyle},
+        debug_text::DebugTextConfig,
+        palette::DebugPalette,
     },
+    diagnostic::Diagnostics,
+    pbr::wireframe::{WireframeColorPreset, WireframeConfig, WireframePlugin},
     prelude::*,
     render::{
+        mesh::PendingMesh,
         primitives::Aabb,
         settings::{WgpuFeatures, WgpuSettings},
     },
@@ -16,17 +21,15 @@ use bevy::{
 
 fn main() {
     App::new()
+        // Overlapping translucent debug shapes would otherwise flicker when drawn at the same depth
+        .insert_resource(TransparentSortConfig { stable_ties: true })
         .add_plugins(DefaultPlugins)
         .add_plugin(DebugPrimitivesPlugin)
+        .add_plugin(RotatorPlugin)
         .add_startup_system(setup)
-        .add_system(rotate)
         .run();
 }
 
-/// A marker component for our shapes so we can query them separately from the ground plane
-#[derive(Component)]
-struct Shape;
-
 const X_EXTENT: f32 = 14.;
 
 fn setup(
@@ -41,27 +44,74 @@ fn setup(
         meshes.add(shape::Box::default().into()),
         meshes.add(shape::Capsule::default().into()),
         meshes.add(shape::Torus::default().into()),
-        meshes.add(shape::Icosphere::default().into()),
-        meshes.add(shape::UVSphere::default().into()),
+    ];
+    // High-detail spheres take a while to generate, so they are built in the background
+    // instead of blocking startup, and show up once ready.
+    let heavy_shapes = [
+        Mesh::generate_async(shape::Icosphere {
+            radius: 1.0,
+            subdivisions: 60,
+        }),
+        Mesh::generate_async(shape::UVSphere {
+            radius: 1.0,
+            sectors: 256,
+            stacks: 128,
+        }),
     ];
 
-    let num_shapes = shapes.len();
+    let num_shapes = shapes.len() + heavy_shapes.len();
+    let shape_x = |i: usize| -X_EXTENT / 2. + i as f32 / (num_shapes - 1) as f32 * X_EXTENT;
+    let shape_transform = |i: usize| {
+        Transform::from_xyz(shape_x(i), 2.0, 0.0).with_rotation(Quat::from_rotation_x(-PI / 4.))
+    };
 
     for (i, shape) in shapes.into_iter().enumerate() {
-        commands.spawn((
+        let index_count = meshes
+            .get(&shape)
+            .and_then(|mesh| mesh.indices())
+            .map_or(0, |indices| indices.len());
+
+        let mut entity = commands.spawn((
             PbrBundle {
                 mesh: shape,
                 material: debug_material.clone(),
-                transform: Transform::from_xyz(
-                    -X_EXTENT / 2. + i as f32 / (num_shapes - 1) as f32 * X_EXTENT,
-                    2.0,
-                    0.0,
-                )
-                .with_rotation(Quat::from_rotation_x(-PI / 4.)),
+                transform: shape_transform(i),
+                ..default()
+            },
+            Rotator::default(),
+        ));
+
+        // Only outline the first half of the capsule's triangles
+        if i == 2 {
+            entity.insert(DebugAabbRange(0..index_count / 2));
+        }
+
+        // Show the tangent space used for normal mapping on the torus
+        if i == 3 {
+            entity.insert(ShowTbn { length: 0.15 });
+        }
+    }
+
+    for (i, task) in heavy_shapes.into_iter().enumerate() {
+        let i = num_shapes - 2 + i;
+        let mut entity = commands.spawn((
+            PbrBundle {
+                material: debug_material.clone(),
+                transform: shape_transform(i),
                 ..default()
             },
-            Shape,
+            Rotator::default(),
+            PendingMesh(task),
         ));
+
+        // Pin the icosphere's box to where it was spawned, for reference
+        if i == 4 {
+            let x = shape_x(i);
+            entity.insert(AabbSource::Custom(Aabb::from_min_max(
+                Vec3::new(x - 1.0, 1.0, -1.0),
+                Vec3::new(x + 1.0, 3.0, 1.0),
+            )));
+        }
     }
 
     commands.spawn(PointLightBundle {
@@ -82,16 +132,14 @@ fn setup(
         ..default()
     });
 
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 6., 12.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
-        ..default()
-    });
-}
-
-fn rotate(mut query: Query<&mut Transform, With<Shape>>, time: Res<Time>) {
-    for mut transform in &mut query {
-        transform.rotate_y(time.delta_seconds() / 2.);
-    }
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        FlyCamera::default(),
+    ));
 }
 
 #[derive(Default, Debug)]
@@ -103,104 +151,170 @@ impl Plugin for DebugPrimitivesPlugin {
             features: WgpuFeatures::POLYGON_MODE_LINE,
             ..default()
         })
+        // Draw the far side of the debug boxes in a darker color so they read as boxes
+        .insert_resource(WireframeConfig {
+            back_color: Some(Color::GRAY),
+            ..default()
+        })
         .add_plugin(WireframePlugin)
-        .init_resource::<DebugPrimitivesConfig>()
-        .add_system(add_aabb_debug_primitives)
-        .add_system(update_aabb_debug_primitives)
-        .add_system(toggle_visibility);
+        .add_plugin(DebugTextPlugin)
+        // The translucent boxes of the `DebugAabbPlugin` replace the toolkit's AABB lines
+        .insert_resource(DebugToolkitConfig {
+            aabbs: false,
+            ..default()
+        })
+        .add_plugin(DebugToolkitPlugin)
+        .insert_resource(DebugAabbConfig {
+            toggle_key: Some(KeyCode::Space),
+            tint_faces: true,
+            ..default()
+        })
+        .add_plugin(DebugAabbPlugin)
+        .add_plugin(AntiAliasingPlugin)
+        .add_plugin(CullingDiagnosticsPlugin)
+        .init_resource::<WireframeColor>()
+        .add_startup_system(setup_debug_text)
+        .add_system(show_debug_text)
+        .add_system(cycle_palette)
+        .add_system(cycle_wireframe_color)
+        .add_system(toggle_box_style)
+        .add_system(toggle_light_shadows);
     }
 }
 
-#[derive(Resource, Debug)]
-pub struct DebugPrimitivesConfig {
-    pub is_visible: bool,
+/// The preset of the global wireframe color, cycled to keep the wireframes readable against
+/// the background.
+#[derive(Resource, Default)]
+struct WireframeColor(WireframeColorPreset);
+
+fn setup_debug_text(asset_server: Res<AssetServer>, mut config: ResMut<DebugTextConfig>) {
+    config.font = asset_server.load("fonts/FiraMono-Medium.ttf");
 }
 
-#[derive(Component, Debug)]
-struct DebugPrimitive(Entity);
+fn show_debug_text(
+    mut debug_text: ResMut<DebugText>,
+    config: Res<DebugAabbConfig>,
+    wireframe_color: Res<WireframeColor>,
+    anti_aliasing: Res<AntiAliasing>,
+    diagnostics: Res<Diagnostics>,
+    debug_aabbs: Query<(), With<DebugAabb>>,
+    point_lights: Query<&PointLight>,
+) {
+    debug_text!(debug_text, "debug boxes: {}", debug_aabbs.iter().len());
+    debug_text!(
+        debug_text,
+        "visible: {} (press space to toggle)",
+        config.visible
+    );
+    debug_text!(
+        debug_text,
+        "palette: {:?} (press P to cycle)",
+        config.palette
+    );
+    debug_text!(
+        debug_text,
+        "box style: {:?} (press C to toggle)",
+        config.box_style
+    );
+    debug_text!(
+        debug_text,
+        "wireframe color: {:?} (press K to cycle)",
+        wireframe_color.0
+    );
+    debug_text!(
+        debug_text,
+        "shadows: {} (press L to toggle)",
+        point_lights.iter().any(|light| light.shadows_enabled)
+    );
+    debug_text!(
+        debug_text,
+        "anti-aliasing: {:?} (press F2 to cycle)",
+        *anti_aliasing
+    );
+    let count = |id| {
+        diagnostics
+            .get(id)
+            .and_then(|diagnostic| diagnostic.value())
+            .unwrap_or_default()
+    };
+    debug_text!(
+        debug_text,
+        "entities visible: {}, culled: {}",
+        count(CullingDiagnosticsPlugin::ENTITIES_VISIBLE),
+        count(CullingDiagnosticsPlugin::ENTITIES_CULLED)
+    );
+}
 
-#[derive(Component, Debug)]
-struct DebugPrimitiveParent;
+fn toggle_box_style(keyboard_input: Res<Input<KeyCode>>, mut config: ResMut<DebugAabbConfig>) {
+    if keyboard_input.just_pressed(KeyCode::C) {
+        config.box_style = match config.box_style {
+            DebugBoxStyle::Edges => DebugBoxStyle::Cage { subdivisions: 3 },
+            DebugBoxStyle::Cage { .. } => DebugBoxStyle::Edges,
+        };
+    }
+}
 
-fn add_aabb_debug_primitives(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    config: Res<DebugPrimitivesConfig>,
-    aabb_query: Query<
-        Entity,
-        (
-            With<Aabb>,
-            Without<DebugPrimitive>,
-            Without<DebugPrimitiveParent>,
-        ),
-    >,
+fn toggle_light_shadows(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut point_lights: Query<&mut PointLight>,
 ) {
-    for parent_entity in &aabb_query {
-        commands
-            .spawn(PbrBundle {
-                mesh: meshes.add(shape::Cube::default().into()),
-                material: materials.add(StandardMaterial {
-                    base_color: Color::rgba(0.0, 0.0, 0.0, 0.0),
-                    alpha_mode: AlphaMode::Mask(0.5),
-                    double_sided: true,
-                    cull_mode: None,
-                    unlit: true,
-                    ..default()
-                }),
-                ..default()
-            })
-            .insert(NotShadowCaster)
-            .insert(NotShadowReceiver)
-            .insert(Visibility {
-                is_visible: config.is_visible,
-            })
-            .insert(Wireframe)
-            .insert(DebugPrimitive(parent_entity));
-
-        commands.entity(parent_entity).insert(DebugPrimitiveParent);
+    if keyboard_input.just_pressed(KeyCode::L) {
+        for mut light in &mut point_lights {
+            light.shadows_enabled = !light.shadows_enabled;
+        }
     }
 }
 
-fn update_aabb_debug_primitives(
-    mut commands: Commands,
-    config: Res<DebugPrimitivesConfig>,
-    mut debug_primitive_query: Query<(Entity, &DebugPrimitive, &mut Transform, &mut Visibility)>,
-    aabb_query: Query<(&Aabb, &GlobalTransform), With<DebugPrimitiveParent>>,
-) {
-    for (debug_primitive_entity, debug_primitive, mut transform, mut visibility) in
-        &mut debug_primitive_query
-    {
-        visibility.is_visible = config.is_visible;
-
-        let (aabb, aabb_transform) = match aabb_query.get(debug_primitive.0) {
-            Ok(x) => x,
-            Err(_) => {
-                commands.entity(debug_primitive_entity).despawn_recursive();
-                continue;
-            }
+fn cycle_palette(keyboard_input: Res<Input<KeyCode>>, mut config: ResMut<DebugAabbConfig>) {
+    if keyboard_input.just_pressed(KeyCode::P) {
+        config.palette = match config.palette {
+            DebugPalette::Default => DebugPalette::Deuteranopia,
+            DebugPalette::Deuteranopia => DebugPalette::Protanopia,
+            DebugPalette::Protanopia => DebugPalette::Tritanopia,
+            DebugPalette::Tritanopia => DebugPalette::Default,
         };
-
-        let (aabb_scale, aabb_rotation, aabb_translation) =
-            aabb_transform.to_scale_rotation_translation();
-
-        transform.translation = aabb_translation + Vec3::from(aabb.center);
-        transform.scale = aabb_scale * Vec3::from(aabb.half_extents * 2.0);
-        transform.rotation = aabb_rotation;
     }
 }
 
-fn toggle_visibility(
+fn cycle_wireframe_color(
     keyboard_input: Res<Input<KeyCode>>,
-    mut config: ResMut<DebugPrimitivesConfig>,
+    mut wireframe_color: ResMut<WireframeColor>,
+    mut wireframe_config: ResMut<WireframeConfig>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        config.is_visible = !config.is_visible;
+    if keyboard_input.just_pressed(KeyCode::K) {
+        wireframe_color.0 = wireframe_color.0.next();
+        wireframe_config.global_color = wireframe_color.0.color();
     }
 }
 
-impl Default for DebugPrimitivesConfig {
-    fn default() -> Self {
-        Self { is_visible: true }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wireframe_color_cycles_on_key_press() {
+        let mut app = App::new();
+        app.init_resource::<Input<KeyCode>>()
+            .init_resource::<WireframeConfig>()
+            .init_resource::<WireframeColor>()
+            .add_system(cycle_wireframe_color);
+
+        for expected in [
+            Color::BLACK,
+            Color::FUCHSIA,
+            Color::LIME_GREEN,
+            Color::WHITE,
+        ] {
+            app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::K);
+            app.update();
+            assert_eq!(
+                app.world.resource::<WireframeConfig>().global_color,
+                expected
+            );
+
+            let mut input = app.world.resource_mut::<Input<KeyCode>>();
+            input.release(KeyCode::K);
+            input.clear();
+        }
     }
 }
@@ -0,0 +1,128 @@
+//! Picks 3D objects with the mouse cursor, highlighting the hovered one and moving a marker to the
+//! clicked point.
+
+use bevy::{
+    prelude::*,
+    render::picking::{PointerClick, PointerOut, PointerOver},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PickingPlugin)
+        .add_startup_system(setup)
+        .add_system(highlight_hovered)
+        .add_system(move_marker)
+        .run();
+}
+
+#[derive(Resource)]
+struct Materials {
+    normal: Handle<StandardMaterial>,
+    hovered: Handle<StandardMaterial>,
+}
+
+#[derive(Component)]
+struct Marker;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let normal = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+    let hovered = materials.add(Color::rgb(0.9, 0.3, 0.3).into());
+
+    // The meshes of the entities with the `Pickable` component can be picked
+    let shapes = [
+        meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        meshes.add(Mesh::from(shape::UVSphere {
+            radius: 0.6,
+            ..default()
+        })),
+        meshes.add(Mesh::from(shape::Torus::default())),
+    ];
+    for (i, mesh) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            PbrBundle {
+                mesh,
+                material: normal.clone(),
+                transform: Transform::from_xyz(i as f32 * 2.5 - 2.5, 0.5, 0.0),
+                ..default()
+            },
+            Pickable,
+        ));
+    }
+    // The ground can be picked too
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane { size: 10.0 })),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..default()
+        },
+        Pickable,
+    ));
+    // The marker placed on the clicked point, which can't be picked
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: 0.1,
+                ..default()
+            })),
+            material: materials.add(Color::WHITE.into()),
+            visibility: Visibility::INVISIBLE,
+            ..default()
+        },
+        Marker,
+    ));
+    commands.insert_resource(Materials { normal, hovered });
+
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 5.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn highlight_hovered(
+    mut over_events: EventReader<PointerOver>,
+    mut out_events: EventReader<PointerOut>,
+    materials: Res<Materials>,
+    mut query: Query<&mut Handle<StandardMaterial>, Without<Marker>>,
+) {
+    for event in out_events.iter() {
+        if let Ok(mut material) = query.get_mut(event.entity) {
+            if *material == materials.hovered {
+                *material = materials.normal.clone();
+            }
+        }
+    }
+    for event in over_events.iter() {
+        if let Ok(mut material) = query.get_mut(event.hit.entity) {
+            if *material == materials.normal {
+                *material = materials.hovered.clone();
+            }
+        }
+    }
+}
+
+fn move_marker(
+    mut click_events: EventReader<PointerClick>,
+    mut marker: Query<(&mut Transform, &mut Visibility), With<Marker>>,
+) {
+    for event in click_events.iter() {
+        if event.button == MouseButton::Left {
+            let (mut transform, mut visibility) = marker.single_mut();
+            transform.translation = event.hit.position + event.hit.normal * 0.1;
+            visibility.is_visible = true;
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Draws lines with a width, such as trajectories and lasers, with polylines.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(rotate_camera)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut polyline_materials: ResMut<Assets<PolylineMaterial>>,
+) {
+    // A trajectory spiraling up, fading from red to yellow, 4 pixels wide at any distance
+    let points = (0..200)
+        .map(|i| {
+            let t = i as f32 / 200.0;
+            Vec3::new((t * 3.0 * TAU).cos(), t * 2.0, (t * 3.0 * TAU).sin()) * (1.0 - t * 0.5)
+        })
+        .collect::<Vec<_>>();
+    let colors = (0..points.len())
+        .map(|i| Color::rgb(1.0, i as f32 / points.len() as f32, 0.0))
+        .collect();
+    commands.spawn(PolylineBundle {
+        mesh: meshes.add(Mesh::from(Polyline {
+            vertices: points,
+            colors,
+            ..default()
+        })),
+        material: polyline_materials.add(PolylineMaterial {
+            width: 4.0,
+            ..default()
+        }),
+        ..default()
+    });
+
+    // Lasers, 0.05 units wide, so getting thinner with their distance to the camera
+    commands.spawn(PolylineBundle {
+        mesh: meshes.add(Mesh::from(Polyline {
+            vertices: vec![
+                Vec3::new(-3.0, 0.1, -3.0),
+                Vec3::new(3.0, 0.1, -3.0),
+                Vec3::new(-3.0, 0.1, 3.0),
+                Vec3::new(3.0, 0.1, 3.0),
+            ],
+            topology: PolylineTopology::List,
+            ..default()
+        })),
+        material: polyline_materials.add(PolylineMaterial {
+            color: Color::CYAN,
+            width: 0.05,
+            perspective: true,
+            ..default()
+        }),
+        ..default()
+    });
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 8.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-4.0, 3.0, 6.0).looking_at(Vec3::Y, Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate_camera(time: Res<Time>, mut cameras: Query<&mut Transform, With<Camera>>) {
+    for mut transform in &mut cameras {
+        transform.rotate_around(
+            Vec3::ZERO,
+            Quat::from_rotation_y(time.delta_seconds() * 0.2),
+        );
+    }
+}
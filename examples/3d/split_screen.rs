@@ -1,9 +1,9 @@
-//! Renders two cameras to the same window to accomplish "split screen".
+//! Renders two cameras to the same window to accomplish "split screen", the second one without
+//! MSAA and at half the resolution.
 
 use std::f32::consts::PI;
 
 use bevy::{
-    core_pipeline::clear_color::ClearColorConfig,
     prelude::*,
     render::camera::Viewport,
     window::{WindowId, WindowResized},
@@ -55,22 +55,23 @@ fn setup(
         LeftCamera,
     ));
 
-    // Right Camera
+    // Right Camera, cheaper than the left one
     commands.spawn((
         Camera3dBundle {
             transform: Transform::from_xyz(100.0, 100., 150.0).looking_at(Vec3::ZERO, Vec3::Y),
             camera: Camera {
                 // Renders the right camera after the left camera, which has a default priority of 0
                 priority: 1,
-                ..default()
-            },
-            camera_3d: Camera3d {
-                // don't clear on the second camera because the first camera already cleared the window
-                clear_color: ClearColorConfig::None,
+                // Renders at half the resolution of its viewport, which is then upscaled to it.
+                // The camera renders to an intermediate texture, so it can clear it without
+                // clearing what the left camera rendered to the window.
+                render_scale: 0.5,
                 ..default()
             },
             ..default()
         },
+        // Overrides the `Msaa` resource for this camera only
+        Msaa { samples: 1 },
         RightCamera,
     ));
 }
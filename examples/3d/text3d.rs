@@ -0,0 +1,192 @@
+//! Draws text in a 3D scene: nameplates facing the camera above some cubes, a sign lying on the
+//! ground, and damage numbers floating up from the cubes.
+//!
+//! The text is hidden by the meshes in front of it, like the other transparent objects.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(Hits {
+            timer: Timer::from_seconds(0.4, TimerMode::Repeating),
+            count: 0,
+        })
+        .add_startup_system(setup)
+        .add_system(rotate_camera)
+        .add_system(spawn_damage_numbers)
+        .add_system(animate_damage_numbers)
+        .run();
+}
+
+#[derive(Resource)]
+struct Hits {
+    timer: Timer,
+    count: usize,
+}
+
+#[derive(Component)]
+struct Target;
+
+#[derive(Component)]
+struct DamageNumber {
+    lifetime: Timer,
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let nameplate_style = TextStyle {
+        font: font.clone(),
+        font_size: 40.0,
+        color: Color::WHITE,
+        outline: Some(TextOutline {
+            width: 2.0,
+            color: Color::BLACK,
+        }),
+        ..default()
+    };
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 8.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // cubes with their nameplates
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    for (name, x, color) in [
+        ("Goblin", -2.0, Color::rgb(0.4, 0.7, 0.3)),
+        ("Orc", 0.0, Color::rgb(0.7, 0.4, 0.3)),
+        ("Troll", 2.0, Color::rgb(0.5, 0.5, 0.7)),
+    ] {
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: cube.clone(),
+                    material: materials.add(color.into()),
+                    transform: Transform::from_xyz(x, 0.5, 0.0),
+                    ..default()
+                },
+                Target,
+            ))
+            .with_children(|parent| {
+                parent.spawn(Text3dBundle {
+                    text: Text::from_section(name, nameplate_style.clone())
+                        .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0.0, 0.9, 0.0),
+                    ..default()
+                });
+            });
+    }
+    // a sign lying on the ground, with a fixed orientation
+    commands.spawn(Text3dBundle {
+        text: Text::from_section(
+            "Arena",
+            TextStyle {
+                font,
+                font_size: 120.0,
+                color: Color::rgb(0.9, 0.8, 0.3),
+                ..default()
+            },
+        )
+        .with_alignment(TextAlignment::CENTER),
+        text_3d: Text3d {
+            orientation: Text3dOrientation::Fixed,
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 0.01, 2.5)
+            .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate_camera(time: Res<Time>, mut query: Query<&mut Transform, With<Camera>>) {
+    for mut transform in &mut query {
+        let angle = time.seconds_since_startup() as f32 * 0.2;
+        *transform = Transform::from_xyz(8.0 * angle.sin(), 4.0, 8.0 * angle.cos())
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
+
+fn spawn_damage_numbers(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut hits: ResMut<Hits>,
+    targets: Query<&GlobalTransform, With<Target>>,
+) {
+    if !hits.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    hits.count += 1;
+    // The targets and the damages are picked in a fixed pseudo-random order
+    let target = match targets.iter().nth(hits.count * 7 % 3) {
+        Some(target) => target,
+        None => return,
+    };
+    let damage = 10 + hits.count * 37 % 90;
+    commands.spawn((
+        Text3dBundle {
+            text: Text::from_section(
+                damage.to_string(),
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 60.0,
+                    color: Color::rgb(1.0, 0.3, 0.2),
+                    outline: Some(TextOutline {
+                        width: 2.0,
+                        color: Color::BLACK,
+                    }),
+                    ..default()
+                },
+            )
+            .with_alignment(TextAlignment::CENTER),
+            transform: Transform::from_translation(target.translation() + Vec3::Y * 0.6),
+            ..default()
+        },
+        DamageNumber {
+            lifetime: Timer::from_seconds(1.0, TimerMode::Once),
+        },
+    ));
+}
+
+fn animate_damage_numbers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut damage_number, mut transform, mut text) in &mut query {
+        if damage_number.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // Floats up while fading out
+        transform.translation.y += time.delta_seconds();
+        let alpha = 1.0 - damage_number.lifetime.percent();
+        let style = &mut text.sections[0].style;
+        style.color.set_a(alpha);
+        if let Some(outline) = &mut style.outline {
+            outline.color.set_a(alpha);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Plays animations from a skinned glTF.
 
 use std::f32::consts::PI;
+use std::time::Duration;
 
 use bevy::prelude::*;
 
@@ -67,7 +68,8 @@ fn setup(
     println!("  - spacebar: play / pause");
     println!("  - arrow up / down: speed up / slow down animation playback");
     println!("  - arrow left / right: seek backward / forward");
-    println!("  - return: change animation");
+    println!("  - return: change animation, cross-fading from the current one");
+    println!("  - b: blend the next animation at half weight / stop blending it");
 }
 
 // Once the scene is loaded, start the animation
@@ -122,8 +124,20 @@ fn keyboard_animation_control(
         if keyboard_input.just_pressed(KeyCode::Return) {
             *current_animation = (*current_animation + 1) % animations.0.len();
             player
-                .play(animations.0[*current_animation].clone_weak())
+                .play_with_transition(
+                    animations.0[*current_animation].clone_weak(),
+                    Duration::from_millis(250),
+                )
                 .repeat();
         }
+
+        if keyboard_input.just_pressed(KeyCode::B) {
+            let next_animation = &animations.0[(*current_animation + 1) % animations.0.len()];
+            if player.blend_weight(next_animation).is_some() {
+                player.stop_blending(next_animation);
+            } else {
+                player.blend(next_animation.clone_weak(), 0.5);
+            }
+        }
     }
 }
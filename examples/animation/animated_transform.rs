@@ -1,4 +1,5 @@
-//! Create and play an animation defined by code that operates on the `Transform` component.
+//! Create and play an animation defined by code that operates on the `Transform` component,
+//! and on the reflected properties of a light.
 
 use std::f32::consts::PI;
 
@@ -12,6 +13,7 @@ fn main() {
             brightness: 1.0,
         })
         .add_startup_system(setup)
+        .add_system(print_animation_events)
         .run();
 }
 
@@ -31,6 +33,7 @@ fn setup(
     let planet = Name::new("planet");
     let orbit_controller = Name::new("orbit_controller");
     let satellite = Name::new("satellite");
+    let beacon = Name::new("beacon");
 
     // Creating the animation
     let mut animation = AnimationClip::default();
@@ -108,6 +111,39 @@ fn setup(
             ]),
         },
     );
+    // Curves can also target any reflected property of a component, such as the intensity and the
+    // color of the light carried by the satellite
+    let beacon_path = EntityPath {
+        parts: vec![
+            planet.clone(),
+            orbit_controller.clone(),
+            satellite.clone(),
+            beacon.clone(),
+        ],
+    };
+    animation.add_curve_to_path(
+        beacon_path.clone(),
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            keyframes: Keyframes::Property(PropertyKeyframes::new::<PointLight, f32>(
+                "intensity",
+                [0.0, 200.0, 0.0, 200.0, 0.0],
+            )),
+        },
+    );
+    animation.add_curve_to_path(
+        beacon_path,
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 2.0, 4.0],
+            keyframes: Keyframes::Property(PropertyKeyframes::new::<PointLight, Color>(
+                "color",
+                [Color::RED, Color::BLUE, Color::RED],
+            )),
+        },
+    );
+    // Events are sent when the player crosses their time in the animation, such as each time the
+    // satellite completes an orbit
+    animation.add_event(4.0, "orbit completed");
 
     // Create the animation player, and set it to repeat
     let mut player = AnimationPlayer::default();
@@ -144,7 +180,24 @@ fn setup(
                     },
                     // Add the Name component
                     satellite,
-                ));
+                ))
+                .with_children(|p| {
+                    // A light whose properties are animated
+                    p.spawn((
+                        PointLightBundle {
+                            transform: Transform::from_xyz(0.5, 0.0, 0.0),
+                            ..default()
+                        },
+                        // Add the Name component
+                        beacon,
+                    ));
+                });
             });
         });
 }
+
+fn print_animation_events(mut animation_events: EventReader<AnimationEvent>) {
+    for event in animation_events.iter() {
+        info!("Animation event: {}", event.name);
+    }
+}
@@ -11,6 +11,24 @@ fn main() {
 
 fn file_drag_and_drop_system(mut events: EventReader<FileDragAndDrop>) {
     for event in events.iter() {
-        info!("{:?}", event);
+        match event {
+            FileDragAndDrop::HoveredFiles {
+                paths, position, ..
+            } => {
+                // This is where a drop target would be highlighted
+                info!("Dragging {:?} over the window at {:?}", paths, position);
+            }
+            FileDragAndDrop::HoveredFilesMoved { position, .. } => {
+                info!("Dragged files moved to {}", position);
+            }
+            FileDragAndDrop::HoveredFilesCancelled { .. } => {
+                info!("Dragged files left the window");
+            }
+            FileDragAndDrop::DroppedFiles {
+                paths, position, ..
+            } => {
+                info!("Dropped {:?} at {:?}", paths, position);
+            }
+        }
     }
 }
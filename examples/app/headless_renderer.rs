@@ -0,0 +1,106 @@
+//! Renders a scene to an image without any window, reads it back to the CPU and saves it,
+//! e.g. to generate reference images in CI.
+
+use bevy::{
+    app::{AppExit, ScheduleRunnerPlugin},
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::screenshot::{ScreenshotCaptured, ScreenshotManager},
+    },
+    window::WindowSettings,
+    winit::WinitPlugin,
+};
+
+fn main() {
+    App::new()
+        .insert_resource(WindowSettings {
+            add_primary_window: false,
+            exit_on_all_closed: false,
+            ..default()
+        })
+        // Without winit, the schedule runner keeps updating the app until it exits
+        .add_plugins_with(DefaultPlugins, |group| group.disable::<WinitPlugin>())
+        .add_plugin(ScheduleRunnerPlugin)
+        .add_startup_system(setup)
+        .add_system(capture_image)
+        .add_system(save_captured_image)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // The image the camera renders to
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: 512,
+            height: 512,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Bgra8UnormSrgb,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    let target = RenderTarget::Image(images.add(image));
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_rotation(Quat::from_rotation_y(0.5)),
+        ..default()
+    });
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn(Camera3dBundle {
+        camera: Camera {
+            target: target.clone(),
+            ..default()
+        },
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    commands.insert_resource(Target(target));
+}
+
+#[derive(Resource)]
+struct Target(RenderTarget);
+
+fn capture_image(
+    mut frames: Local<u32>,
+    target: Res<Target>,
+    images: Res<Assets<Image>>,
+    mut screenshots: ResMut<ScreenshotManager>,
+) {
+    // Pipelines are compiled in the background, so the meshes only appear after a few frames
+    *frames += 1;
+    if *frames == 10 {
+        screenshots.capture_next_frame(target.0.clone(), &images);
+    }
+}
+
+fn save_captured_image(
+    mut captured: EventReader<ScreenshotCaptured>,
+    images: Res<Assets<Image>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for captured in captured.iter() {
+        let image = images.get(&captured.image).unwrap().clone();
+        match image.try_into_dynamic() {
+            Ok(image) => match image.save("headless_renderer.png") {
+                Ok(()) => info!("Saved headless_renderer.png"),
+                Err(err) => error!("Cannot save the image: {err}"),
+            },
+            Err(err) => error!("Cannot convert the image: {err}"),
+        }
+        exit.send(AppExit);
+    }
+}
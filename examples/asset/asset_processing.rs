@@ -0,0 +1,170 @@
+//! Illustrates the import settings read from the `.meta` files next to asset sources, and
+//! processing asset sources into engine-ready data before they are loaded.
+//!
+//! The sprite is drawn without filtering because of its `hat-guy.png.meta` file, and the lines of
+//! the dialogue are wrapped by its processor following `intro.dialogue.meta`. Try changing the
+//! settings in these files while the example runs.
+
+use bevy::{
+    asset::{
+        AssetLoader, AssetProcessor, AssetServerSettings, ImportSettings, LoadContext, LoadedAsset,
+    },
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+fn main() {
+    App::new()
+        // Reloads the assets when their source or their `.meta` file changes
+        .insert_resource(AssetServerSettings {
+            watch_for_changes: true,
+            ..default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_asset::<Dialogue>()
+        .add_asset_processor(DialogueProcessor)
+        .init_asset_loader::<DialogueLoader>()
+        .add_startup_system(setup)
+        .add_system(show_dialogue)
+        .run();
+}
+
+/// Lines of text ready to be displayed, loaded from the output of the [`DialogueProcessor`]
+#[derive(Debug, Deserialize, TypeUuid)]
+#[uuid = "7f1c6a3e-2a7b-4d0e-9b8e-3f5e3c1d9a42"]
+pub struct Dialogue {
+    pub lines: Vec<String>,
+}
+
+/// The settings of the [`DialogueProcessor`], from the `.meta` file of the dialogue
+#[derive(Deserialize)]
+#[serde(default)]
+struct DialogueSettings {
+    /// The maximum number of characters on a line
+    max_line_length: usize,
+}
+
+impl Default for DialogueSettings {
+    fn default() -> Self {
+        Self {
+            max_line_length: 40,
+        }
+    }
+}
+
+/// Wraps the lines of a plain text dialogue, and writes them in the RON read by the
+/// [`DialogueLoader`].
+///
+/// The output is cached in `assets/.processed`, so a dialogue is only wrapped again when its text
+/// or its settings change.
+struct DialogueProcessor;
+
+impl AssetProcessor for DialogueProcessor {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        settings: &'a ImportSettings,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, bevy::asset::Error>> {
+        Box::pin(async move {
+            let settings = settings.get::<DialogueSettings>()?;
+            let mut lines = Vec::new();
+            for paragraph in std::str::from_utf8(bytes)?.lines() {
+                let mut line = String::new();
+                for word in paragraph.split_whitespace() {
+                    if !line.is_empty() && line.len() + word.len() >= settings.max_line_length {
+                        lines.push(std::mem::take(&mut line));
+                    }
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line.push_str(word);
+                }
+                lines.push(line);
+            }
+            let ron = ron::ser::to_string(&lines)?;
+            Ok(format!("(lines: {})", ron).into_bytes())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue"]
+    }
+}
+
+#[derive(Default)]
+struct DialogueLoader;
+
+impl AssetLoader for DialogueLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let dialogue = ron::de::from_bytes::<Dialogue>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(dialogue));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue"]
+    }
+}
+
+#[derive(Resource)]
+struct DialogueHandle(Handle<Dialogue>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(SpriteBundle {
+        texture: asset_server.load("textures/rpg/chars/hat-guy/hat-guy.png"),
+        transform: Transform::from_xyz(0.0, 100.0, 0.0).with_scale(Vec3::splat(8.0)),
+        ..default()
+    });
+    commands.spawn(
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 30.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(20.0),
+                left: Val::Px(20.0),
+                ..default()
+            },
+            ..default()
+        }),
+    );
+    commands.insert_resource(DialogueHandle(asset_server.load("data/intro.dialogue")));
+}
+
+/// Shows the dialogue once it is loaded, and again when it is reloaded
+fn show_dialogue(
+    mut events: EventReader<AssetEvent<Dialogue>>,
+    dialogue: Res<DialogueHandle>,
+    dialogues: Res<Assets<Dialogue>>,
+    mut texts: Query<&mut Text>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle }
+                if *handle == dialogue.0 =>
+            {
+                let lines = &dialogues.get(handle).unwrap().lines;
+                for mut text in &mut texts {
+                    text.sections[0].value = lines.join("\n");
+                }
+            }
+            _ => {}
+        }
+    }
+}
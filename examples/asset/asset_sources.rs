@@ -0,0 +1,25 @@
+//! Adds an asset source for a URI scheme, so that its assets are loaded from their own asset I/O.
+//!
+//! Any `AssetIo` can be added for a scheme, such as one downloading assets from a CDN. Web
+//! builds can already load `http://` and `https://` URLs.
+
+use bevy::{asset::FileAssetIo, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Assets starting with `textures://` are loaded from the `assets/textures` folder, the
+        // path following the scheme being relative to it
+        .add_asset_source("textures", FileAssetIo::new("assets/textures", false))
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(SpriteBundle {
+        texture: asset_server.load("textures://rpg/chars/hat-guy/hat-guy.png"),
+        transform: Transform::from_scale(Vec3::splat(8.0)),
+        ..default()
+    });
+}
@@ -0,0 +1,37 @@
+//! Embeds an asset in the executable, so that a plugin can ship it without an asset folder.
+//!
+//! In debug builds, the embedded icon can be overridden by adding a file at
+//! `assets/embedded/embedded_asset_plugin/icon.png`.
+
+use bevy::{asset::embedded_asset, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(EmbeddedAssetPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+/// A plugin bundling its icon into the executable
+struct EmbeddedAssetPlugin;
+
+impl Plugin for EmbeddedAssetPlugin {
+    fn build(&self, app: &mut App) {
+        // The path of the file is relative to this source file, and it is embedded at
+        // `embedded://embedded_asset_plugin/icon.png`
+        embedded_asset!(
+            app,
+            "embedded_asset_plugin/icon.png",
+            "../../assets/branding/icon.png"
+        );
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(SpriteBundle {
+        texture: asset_server.load("embedded://embedded_asset_plugin/icon.png"),
+        ..default()
+    });
+}
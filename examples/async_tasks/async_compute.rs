@@ -1,11 +1,8 @@
-//! This example shows how to use the ECS and the [`AsyncComputeTaskPool`]
-//! to spawn, poll, and complete tasks across systems and system ticks.
+//! This example shows how to use the ECS and the `AsyncComputeTaskPool`
+//! to spawn tasks whose results are added to entities once they complete,
+//! across system ticks.
 
-use bevy::{
-    prelude::*,
-    tasks::{AsyncComputeTaskPool, Task},
-};
-use futures_lite::future;
+use bevy::prelude::*;
 use rand::Rng;
 use std::time::{Duration, Instant};
 
@@ -14,8 +11,7 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup_env)
         .add_startup_system(add_assets)
-        .add_startup_system(spawn_tasks)
-        .add_system(handle_tasks)
+        .add_startup_system_to_stage(StartupStage::PostStartup, spawn_tasks)
         .run();
 }
 
@@ -44,20 +40,23 @@ fn add_assets(
     commands.insert_resource(BoxMaterialHandle(box_material_handle));
 }
 
-#[derive(Component)]
-struct ComputeTransform(Task<Transform>);
-
 /// This system generates tasks simulating computationally intensive
-/// work that potentially spans multiple frames/ticks. A separate
-/// system, `handle_tasks`, will poll the spawned tasks on subsequent
-/// frames/ticks, and use the results to spawn cubes
-fn spawn_tasks(mut commands: Commands) {
-    let thread_pool = AsyncComputeTaskPool::get();
+/// work that potentially spans multiple frames/ticks. Each task runs
+/// on the `AsyncComputeTaskPool` for its own entity, and the bundle it
+/// returns is inserted on that entity once the task is complete.
+/// Despawning the entity before then would cancel the task.
+fn spawn_tasks(
+    mut commands: Commands,
+    box_mesh_handle: Res<BoxMeshHandle>,
+    box_material_handle: Res<BoxMaterialHandle>,
+) {
     for x in 0..NUM_CUBES {
         for y in 0..NUM_CUBES {
             for z in 0..NUM_CUBES {
-                // Spawn new task on the AsyncComputeTaskPool
-                let task = thread_pool.spawn(async move {
+                let mesh = box_mesh_handle.clone();
+                let material = box_material_handle.clone();
+                // Spawn new entity running a task on the AsyncComputeTaskPool
+                commands.spawn_task(async move {
                     let mut rng = rand::thread_rng();
                     let start_time = Instant::now();
                     let duration = Duration::from_secs_f32(rng.gen_range(0.05..0.2));
@@ -66,43 +65,20 @@ fn spawn_tasks(mut commands: Commands) {
                         // compute work generating translation coords!
                     }
 
-                    // Such hard work, all done!
-                    Transform::from_xyz(x as f32, y as f32, z as f32)
+                    // Such hard work, all done! Our new PbrBundle of components
+                    // will be added to the entity using the result of the work
+                    PbrBundle {
+                        mesh,
+                        material,
+                        transform: Transform::from_xyz(x as f32, y as f32, z as f32),
+                        ..default()
+                    }
                 });
-
-                // Spawn new entity and add our new task as a component
-                commands.spawn(ComputeTransform(task));
             }
         }
     }
 }
 
-/// This system queries for entities that have our Task<Transform> component. It polls the
-/// tasks to see if they're complete. If the task is complete it takes the result, adds a
-/// new [`PbrBundle`] of components to the entity using the result from the task's work, and
-/// removes the task component from the entity.
-fn handle_tasks(
-    mut commands: Commands,
-    mut transform_tasks: Query<(Entity, &mut ComputeTransform)>,
-    box_mesh_handle: Res<BoxMeshHandle>,
-    box_material_handle: Res<BoxMaterialHandle>,
-) {
-    for (entity, mut task) in &mut transform_tasks {
-        if let Some(transform) = future::block_on(future::poll_once(&mut task.0)) {
-            // Add our new PbrBundle of components to our tagged entity
-            commands.entity(entity).insert(PbrBundle {
-                mesh: box_mesh_handle.clone(),
-                material: box_material_handle.clone(),
-                transform,
-                ..default()
-            });
-
-            // Task is complete, so remove task component from entity
-            commands.entity(entity).remove::<ComputeTransform>();
-        }
-    }
-}
-
 /// This system is only used to setup light and camera for the environment
 fn setup_env(mut commands: Commands) {
     // Used to center camera on spawned cubes
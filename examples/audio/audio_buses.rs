@@ -0,0 +1,123 @@
+//! This example illustrates mixing sounds through audio buses: the music and the sound effects
+//! have their own volume, and pausing muffles everything.
+
+use bevy::{audio::AudioBusSettings, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(SfxTimer(Timer::from_seconds(1.0, TimerMode::Repeating)))
+        .add_startup_system(setup)
+        .add_system(play_sfx)
+        .add_system(control_buses)
+        .add_system(update_status)
+        .run();
+}
+
+#[derive(Resource)]
+struct SfxTimer(Timer);
+
+#[derive(Component)]
+struct StatusText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
+    audio.play_with_settings(
+        asset_server.load("sounds/Windless Slopes.ogg"),
+        PlaybackSettings::LOOP.with_bus(AudioBusId::MUSIC),
+    );
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 30.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(12.0),
+                left: Val::Px(12.0),
+                ..default()
+            },
+            ..default()
+        }),
+        StatusText,
+    ));
+}
+
+/// Plays a sound effect every second
+fn play_sfx(
+    time: Res<Time>,
+    mut timer: ResMut<SfxTimer>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        audio.play_with_settings(
+            asset_server.load("sounds/breakout_collision.ogg"),
+            PlaybackSettings::ONCE.with_bus(AudioBusId::SFX),
+        );
+    }
+}
+
+fn control_buses(keyboard_input: Res<Input<KeyCode>>, mut audio_buses: ResMut<AudioBuses>) {
+    for (bus, mute, down, up) in [
+        (AudioBusId::MUSIC, KeyCode::M, KeyCode::Key1, KeyCode::Key2),
+        (AudioBusId::SFX, KeyCode::S, KeyCode::Key3, KeyCode::Key4),
+    ] {
+        let settings = audio_buses.get_mut(&bus).unwrap();
+        if keyboard_input.just_pressed(mute) {
+            settings.muted = !settings.muted;
+        }
+        if keyboard_input.just_pressed(down) {
+            settings.volume = (settings.volume - 0.1).max(0.0);
+        }
+        if keyboard_input.just_pressed(up) {
+            settings.volume = (settings.volume + 0.1).min(1.0);
+        }
+    }
+
+    // Pausing muffles everything, as if heard from another room
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        let master = audio_buses.get_mut(&AudioBusId::MASTER).unwrap();
+        if master.low_pass.is_some() {
+            *master = AudioBusSettings::default();
+        } else {
+            master.low_pass = Some(500.0);
+            master.reverb = 0.4;
+            master.volume = 0.7;
+        }
+    }
+}
+
+fn update_status(audio_buses: Res<AudioBuses>, mut query: Query<&mut Text, With<StatusText>>) {
+    if !audio_buses.is_changed() {
+        return;
+    }
+    let describe = |bus| {
+        let settings: &AudioBusSettings = audio_buses.get(bus).unwrap();
+        if settings.muted {
+            "muted".to_string()
+        } else {
+            format!("{:.0}%", settings.volume * 100.0)
+        }
+    };
+    let paused = audio_buses
+        .get(&AudioBusId::MASTER)
+        .unwrap()
+        .low_pass
+        .is_some();
+    query.single_mut().sections[0].value = format!(
+        "Music: {} (M to mute, 1/2 to change the volume)\n\
+        Sound effects: {} (S to mute, 3/4 to change the volume)\n\
+        {} (Space to toggle)",
+        describe(&AudioBusId::MUSIC),
+        describe(&AudioBusId::SFX),
+        if paused { "Paused" } else { "Playing" },
+    );
+}
@@ -1,6 +1,7 @@
 //! This example illustrates how to load and play an audio file, and control how it's played.
 
 use bevy::{audio::AudioSink, prelude::*};
+use std::time::Duration;
 
 fn main() {
     App::new()
@@ -9,6 +10,8 @@ fn main() {
         .add_system(update_speed)
         .add_system(pause)
         .add_system(volume)
+        .add_system(seek)
+        .add_system(fade)
         .run();
 }
 
@@ -19,7 +22,14 @@ fn setup(
     audio_sinks: Res<Assets<AudioSink>>,
 ) {
     let music = asset_server.load("sounds/Windless Slopes.ogg");
-    let handle = audio_sinks.get_handle(audio.play(music));
+    let handle = audio_sinks.get_handle(
+        audio.play_with_settings(
+            music,
+            PlaybackSettings::ONCE
+                .with_fade_in(Duration::from_secs(2))
+                .with_seekable(true),
+        ),
+    );
     commands.insert_resource(MusicController(handle));
 }
 
@@ -65,3 +75,40 @@ fn volume(
         }
     }
 }
+
+fn seek(
+    keyboard_input: Res<Input<KeyCode>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    music_controller: Res<MusicController>,
+) {
+    if let Some(sink) = audio_sinks.get(&music_controller.0) {
+        let step = Duration::from_secs(5);
+        if keyboard_input.just_pressed(KeyCode::Right) {
+            sink.seek(sink.position() + step);
+        } else if keyboard_input.just_pressed(KeyCode::Left) {
+            sink.seek(sink.position().saturating_sub(step));
+        } else if keyboard_input.just_pressed(KeyCode::L) {
+            // Repeats the current 4 seconds
+            let start = sink.position();
+            sink.set_loop_points(start, Some(start + Duration::from_secs(4)));
+        } else if keyboard_input.just_pressed(KeyCode::U) {
+            sink.stop_looping();
+        }
+    }
+}
+
+fn fade(
+    keyboard_input: Res<Input<KeyCode>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    music_controller: Res<MusicController>,
+) {
+    if let Some(sink) = audio_sinks.get(&music_controller.0) {
+        if keyboard_input.just_pressed(KeyCode::F) {
+            if sink.fade_volume() > 0.5 {
+                sink.fade_to(0.0, Duration::from_secs(1));
+            } else {
+                sink.fade_to(1.0, Duration::from_secs(1));
+            }
+        }
+    }
+}
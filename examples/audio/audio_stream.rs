@@ -0,0 +1,98 @@
+//! This example illustrates playing generated samples instead of an audio file: the number keys
+//! play the notes of a scale, synthesized while they play.
+
+use bevy::{audio::AudioStream, prelude::*};
+use std::f32::consts::TAU;
+
+const SAMPLE_RATE: u32 = 44100;
+
+/// The frequencies of the notes of the C major scale, in Hz
+const SCALE: [f32; 8] = [
+    261.63, 293.66, 329.63, 349.23, 392.00, 440.00, 493.88, 523.25,
+];
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(play_notes)
+        .run();
+}
+
+/// A note fading out, with a few harmonics to sound like a plucked string
+struct Note {
+    frequency: f32,
+    sample: u32,
+}
+
+impl AudioStream for Note {
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn next_sample(&mut self) -> Option<f32> {
+        let t = self.sample as f32 / SAMPLE_RATE as f32;
+        let envelope = (-t * 3.0).exp();
+        if envelope < 0.001 {
+            return None;
+        }
+        self.sample += 1;
+        let wave: f32 = [(1.0, 0.6), (2.0, 0.3), (3.0, 0.1)]
+            .iter()
+            .map(|(harmonic, amplitude)| (t * self.frequency * harmonic * TAU).sin() * amplitude)
+            .sum();
+        Some(wave * envelope * 0.5)
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(
+        TextBundle::from_section(
+            "Press 1 to 8 to play a note",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 30.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(12.0),
+                left: Val::Px(12.0),
+                ..default()
+            },
+            ..default()
+        }),
+    );
+}
+
+fn play_notes(keyboard_input: Res<Input<KeyCode>>, audio: Res<Audio>) {
+    let keys = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+    ];
+    for (key, frequency) in keys.into_iter().zip(SCALE) {
+        if keyboard_input.just_pressed(key) {
+            audio.play_stream(
+                Note {
+                    frequency,
+                    sample: 0,
+                },
+                PlaybackSettings::ONCE,
+            );
+        }
+    }
+}
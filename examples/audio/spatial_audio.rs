@@ -0,0 +1,71 @@
+//! This example illustrates playing a sound from the position of an entity: the music follows a
+//! sphere circling around the camera, and is heard from its side.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(move_emitter)
+        .run();
+}
+
+/// The entity playing the music
+#[derive(Component)]
+struct Emitter;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // The camera hears the sounds
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 2.0, 0.0)
+                .looking_at(Vec3::new(0.0, 0.0, -1.0), Vec3::Y),
+            ..default()
+        },
+        AudioListener {
+            distance_scale: 0.3,
+            ..default()
+        },
+    ));
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(0.0, 8.0, 0.0),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 30.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: 0.5,
+                ..default()
+            })),
+            material: materials.add(Color::rgb(0.9, 0.6, 0.2).into()),
+            ..default()
+        },
+        SpatialAudioSource::new(
+            asset_server.load("sounds/Windless Slopes.ogg"),
+            PlaybackSettings::LOOP,
+        ),
+        Emitter,
+    ));
+}
+
+/// Moves the emitter around the camera, getting closer and farther
+fn move_emitter(time: Res<Time>, mut query: Query<&mut Transform, With<Emitter>>) {
+    let t = time.seconds_since_startup() as f32;
+    let distance = 6.0 + (t * 0.7).sin() * 4.0;
+    for mut transform in &mut query {
+        transform.translation =
+            Vec3::new((t * 0.5).sin() * distance, 0.5, -(t * 0.5).cos() * distance);
+    }
+}
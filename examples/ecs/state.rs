@@ -62,6 +62,7 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 40.0,
                     color: Color::rgb(0.9, 0.9, 0.9),
+                    ..default()
                 },
             ));
         })
@@ -174,6 +174,7 @@ mod game {
                             font: font.clone(),
                             font_size: 80.0,
                             color: TEXT_COLOR,
+                            ..default()
                         },
                     )
                     .with_style(Style {
@@ -189,6 +190,7 @@ mod game {
                                 font: font.clone(),
                                 font_size: 60.0,
                                 color: Color::BLUE,
+                                ..default()
                             },
                         ),
                         TextSection::new(
@@ -197,6 +199,7 @@ mod game {
                                 font: font.clone(),
                                 font_size: 60.0,
                                 color: TEXT_COLOR,
+                                ..default()
                             },
                         ),
                         TextSection::new(
@@ -205,6 +208,7 @@ mod game {
                                 font: font.clone(),
                                 font_size: 60.0,
                                 color: Color::GREEN,
+                                ..default()
                             },
                         ),
                     ])
@@ -412,6 +416,7 @@ mod menu {
             font: font.clone(),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -437,6 +442,7 @@ mod menu {
                             font: font.clone(),
                             font_size: 80.0,
                             color: TEXT_COLOR,
+                            ..default()
                         },
                     )
                     .with_style(Style {
@@ -525,6 +531,7 @@ mod menu {
             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -579,6 +586,7 @@ mod menu {
             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
@@ -670,6 +678,7 @@ mod menu {
             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
             font_size: 40.0,
             color: TEXT_COLOR,
+            ..default()
         };
 
         commands
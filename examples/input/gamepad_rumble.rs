@@ -0,0 +1,77 @@
+//! Shows how to trigger force-feedback, making gamepads rumble when buttons are pressed.
+
+use bevy::{
+    input::gamepad::{GamepadButton, GamepadRumbleIntensity, GamepadRumbleRequest},
+    prelude::*,
+};
+use std::time::Duration;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_system(gamepad_system)
+        .run();
+}
+
+fn gamepad_system(
+    gamepads: Res<Gamepads>,
+    button_inputs: Res<Input<GamepadButton>>,
+    mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+) {
+    for gamepad in gamepads.iter() {
+        let button_pressed = |button| {
+            button_inputs.just_pressed(GamepadButton {
+                gamepad,
+                button_type: button,
+            })
+        };
+
+        if button_pressed(GamepadButtonType::North) {
+            info!(
+                "North face button: low-intensity rumble on the strong motor for 5 seconds, press again to add to it"
+            );
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity: GamepadRumbleIntensity::strong_motor(0.1),
+                duration: Duration::from_secs(5),
+            });
+        }
+
+        if button_pressed(GamepadButtonType::East) {
+            info!("East face button: maximum rumble on both motors for 5 seconds");
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                duration: Duration::from_secs(5),
+                intensity: GamepadRumbleIntensity::MAX,
+            });
+        }
+
+        if button_pressed(GamepadButtonType::South) {
+            info!("South face button: low-intensity rumble on the weak motor for 0.5 seconds");
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                duration: Duration::from_secs_f32(0.5),
+                intensity: GamepadRumbleIntensity::weak_motor(0.25),
+            });
+        }
+
+        if button_pressed(GamepadButtonType::West) {
+            info!("West face button: custom rumble intensity for 5 seconds");
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity: GamepadRumbleIntensity {
+                    // intensity of the low-frequency motor, usually on the left-hand side
+                    strong_motor: 0.5,
+                    // intensity of the high-frequency motor, usually on the right-hand side
+                    weak_motor: 0.25,
+                },
+                duration: Duration::from_secs(5),
+            });
+        }
+
+        if button_pressed(GamepadButtonType::Start) {
+            info!("Start button: stop the rumbles");
+            rumble_requests.send(GamepadRumbleRequest::Stop { gamepad });
+        }
+    }
+}
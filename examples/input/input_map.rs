@@ -0,0 +1,68 @@
+//! Shows how to bind actions to keys, mouse buttons and gamepad inputs with an `InputMap`, and
+//! how to rebind them.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(InputMapPlugin::<Action>::default())
+        .insert_resource(
+            InputMap::default()
+                .with(Action::Jump, KeyCode::Space)
+                .with(Action::Jump, GamepadButtonType::South)
+                .with(Action::Left, KeyCode::Left)
+                .with(
+                    Action::Left,
+                    InputBinding::axis_direction(GamepadAxisType::LeftStickX, false),
+                )
+                .with(Action::Right, KeyCode::Right)
+                .with(
+                    Action::Right,
+                    InputBinding::axis_direction(GamepadAxisType::LeftStickX, true),
+                )
+                .with(
+                    Action::Rebind,
+                    InputBinding::chord([KeyCode::LControl, KeyCode::R]),
+                ),
+        )
+        .add_system(report_actions)
+        .add_system(rebind_jump)
+        .run();
+}
+
+/// The actions of the player, independent of the inputs triggering them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Left,
+    Right,
+    Rebind,
+}
+
+fn report_actions(action_state: Res<ActionState<Action>>) {
+    if action_state.just_pressed(Action::Jump) {
+        info!("Jump!");
+    }
+    for action in [Action::Left, Action::Right] {
+        if action_state.pressed(action) {
+            info!("Moving {:?} at {:.2}", action, action_state.value(action));
+        }
+    }
+}
+
+/// Switches the jump between the space bar and the left mouse button
+fn rebind_jump(action_state: Res<ActionState<Action>>, mut input_map: ResMut<InputMap<Action>>) {
+    if !action_state.just_pressed(Action::Rebind) {
+        return;
+    }
+    let space = InputBinding::Keyboard(KeyCode::Space);
+    if input_map.remove(Action::Jump, &space) {
+        input_map.insert(Action::Jump, MouseButton::Left);
+        info!("Jump is now bound to the left mouse button");
+    } else {
+        input_map.remove(Action::Jump, &InputBinding::Mouse(MouseButton::Left));
+        input_map.insert(Action::Jump, space);
+        info!("Jump is now bound to the space bar");
+    }
+}
@@ -0,0 +1,55 @@
+//! Shows how to use touch gestures: pinch to zoom the camera, turn two fingers to rotate it, and
+//! double tap to reset it.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(camera_gestures)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    for (x, color) in [
+        (-150.0, Color::RED),
+        (0.0, Color::GREEN),
+        (150.0, Color::BLUE),
+    ] {
+        commands.spawn(SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(100.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(x, 0.0, 0.0),
+            ..default()
+        });
+    }
+}
+
+fn camera_gestures(
+    mut pinch_events: EventReader<PinchGesture>,
+    mut rotation_events: EventReader<RotationGesture>,
+    mut tap_events: EventReader<TapGesture>,
+    mut query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+) {
+    let (mut transform, mut projection) = query.single_mut();
+    for pinch in pinch_events.iter() {
+        // Moving the fingers apart zooms in
+        projection.scale = (projection.scale / pinch.scale).clamp(0.2, 5.0);
+    }
+    for rotation in rotation_events.iter() {
+        // The scene turns with the fingers, so the camera turns the other way
+        transform.rotate_z(rotation.delta);
+    }
+    for tap in tap_events.iter() {
+        if tap.count == 2 {
+            info!("Double tap at {:?}, resetting the camera", tap.position);
+            projection.scale = 1.0;
+            transform.rotation = Quat::IDENTITY;
+        }
+    }
+}
@@ -114,6 +114,7 @@ fn setup_scene(
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 30.0,
                         color: Color::BLACK,
+                        ..default()
                     },
                 )
                 .with_text_alignment(TextAlignment::CENTER),
@@ -1,8 +1,6 @@
 //! This example illustrates loading scenes from files.
-use std::fs::File;
-use std::io::Write;
 
-use bevy::{prelude::*, tasks::IoTaskPool, utils::Duration};
+use bevy::{prelude::*, utils::Duration};
 
 fn main() {
     App::new()
@@ -16,6 +14,7 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .register_type::<ComponentA>()
         .register_type::<ComponentB>()
+        .register_type::<ResourceA>()
         .add_startup_system(save_scene_system)
         .add_startup_system(load_scene_system)
         .add_startup_system(infotext_system)
@@ -58,6 +57,13 @@ impl FromWorld for ComponentB {
     }
 }
 
+// Resources can be saved in scenes too, they just need to reflect the `Resource` trait
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct ResourceA {
+    pub score: u32,
+}
+
 // The initial scene file will be loaded below and not change when the scene is saved
 const SCENE_FILE_PATH: &str = "scenes/load_scene_example.scn.ron";
 
@@ -74,9 +80,13 @@ fn load_scene_system(mut commands: Commands, asset_server: Res<AssetServer>) {
     });
 }
 
-// This system logs all ComponentA components in our world. Try making a change to a ComponentA in
-// load_scene_example.scn. You should immediately see the changes appear in the console.
-fn log_system(query: Query<(Entity, &ComponentA), Changed<ComponentA>>) {
+// This system logs all ComponentA components in our world, and the ResourceA resource. Try making a
+// change to a ComponentA or to the ResourceA in load_scene_example.scn. You should immediately see
+// the changes appear in the console.
+fn log_system(
+    query: Query<(Entity, &ComponentA), Changed<ComponentA>>,
+    resource: Option<Res<ResourceA>>,
+) {
     for (entity, component_a) in &query {
         info!("  Entity({})", entity.id());
         info!(
@@ -84,6 +94,11 @@ fn log_system(query: Query<(Entity, &ComponentA), Changed<ComponentA>>) {
             component_a.x, component_a.y
         );
     }
+    if let Some(resource) = resource {
+        if resource.is_changed() {
+            info!("  ResourceA: {{ score: {} }}\n", resource.score);
+        }
+    }
 }
 
 fn save_scene_system(world: &mut World) {
@@ -98,11 +113,19 @@ fn save_scene_system(world: &mut World) {
         Transform::IDENTITY,
     ));
     scene_world.spawn(ComponentA { x: 3.0, y: 4.0 });
+    scene_world.insert_resource(ResourceA { score: 1 });
 
     // The TypeRegistry resource contains information about all registered types (including
-    // components). This is used to construct scenes.
+    // components and resources). This is used to construct scenes.
     let type_registry = world.resource::<AppTypeRegistry>();
-    let scene = DynamicScene::from_world(&scene_world, type_registry);
+    let mut builder =
+        DynamicSceneBuilder::from_world_with_type_registry(&scene_world, type_registry.clone());
+    // The builder can leave out some components or resources, like the transforms here
+    builder.deny::<Transform>();
+    builder
+        .extract_entities(scene_world.iter_entities())
+        .extract_resources();
+    let scene = builder.build();
 
     // Scenes can be serialized like this:
     let serialized_scene = scene.serialize_ron(type_registry).unwrap();
@@ -110,17 +133,13 @@ fn save_scene_system(world: &mut World) {
     // Showing the scene in the console
     info!("{}", serialized_scene);
 
-    // Writing the scene to a new file. Using a task to avoid calling the filesystem APIs in a system
-    // as they are blocking
+    // Saving the scene to a new file, with the asset server writing it in the background so that
+    // the system isn't blocked by the filesystem APIs.
     // This can't work in WASM as there is no filesystem access
     #[cfg(not(target_arch = "wasm32"))]
-    IoTaskPool::get()
-        .spawn(async move {
-            // Write the scene RON data to file
-            File::create(format!("assets/{}", NEW_SCENE_FILE_PATH))
-                .and_then(|mut file| file.write(serialized_scene.as_bytes()))
-                .expect("Error while writing scene to file");
-        })
+    world
+        .resource::<AssetServer>()
+        .save(NEW_SCENE_FILE_PATH, &scene)
         .detach();
 }
 
@@ -135,6 +154,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
         )
         .with_style(Style {
@@ -0,0 +1,95 @@
+//! A material extending the [`StandardMaterial`] with a dissolve effect, reusing its lighting.
+
+use bevy::{
+    pbr::{ExtendedMaterial, MaterialExtension},
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(MaterialPlugin::<
+            ExtendedMaterial<StandardMaterial, DissolveExtension>,
+        >::default())
+        .add_startup_system(setup)
+        .add_system(rotate)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, DissolveExtension>>>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // sphere
+    commands.spawn(MaterialMeshBundle {
+        mesh: meshes.add(Mesh::from(shape::UVSphere {
+            radius: 1.0,
+            ..default()
+        })),
+        transform: Transform::from_xyz(0.0, 1.0, 0.0),
+        material: materials.add(ExtendedMaterial {
+            base: StandardMaterial {
+                base_color: Color::rgb(0.8, 0.7, 0.6),
+                perceptual_roughness: 0.3,
+                // The shader discards the dissolved fragments, so they have to be left out of the
+                // depth prepass like the ones of alpha masked materials
+                alpha_mode: AlphaMode::Mask(0.5),
+                ..default()
+            },
+            extension: DissolveExtension {
+                edge_color: Color::rgb(4.0, 1.2, 0.2),
+            },
+        }),
+        ..default()
+    });
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: standard_materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0)
+            .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Handle<Mesh>>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(0.5 * time.delta_seconds());
+    }
+}
+
+// The bindings of the extension are added to the bind group of the StandardMaterial, so they
+// start from 100 to not overlap its bindings
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "3c1e6f2d-8a4b-4c7e-9d2f-5b8a1e3c7d90"]
+struct DissolveExtension {
+    #[uniform(100)]
+    edge_color: Color,
+}
+
+impl MaterialExtension for DissolveExtension {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/extended_material.wgsl".into()
+    }
+}
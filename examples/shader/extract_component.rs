@@ -0,0 +1,259 @@
+//! Extracts a custom per-entity component into the render world and uses it in a shader.
+//!
+//! Every mesh that has a [`DebugColor`] is drawn with a flat, unlit color read
+//! from that component instead of a material asset.
+
+use bevy::{
+    core_pipeline::core_3d::Opaque3d,
+    ecs::{
+        query::QueryItem,
+        system::{lifetimeless::*, SystemParamItem},
+    },
+    pbr::{
+        DrawMesh, MeshPipeline, MeshPipelineKey, MeshUniform, SetMeshBindGroup,
+        SetMeshViewBindGroup,
+    },
+    prelude::*,
+    render::{
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        mesh::MeshVertexBufferLayout,
+        render_asset::RenderAssets,
+        render_phase::{
+            AddRenderCommand, DrawFunctions, EntityRenderCommand, RenderCommandResult, RenderPhase,
+            SetItemPipeline, TrackedRenderPass,
+        },
+        render_resource::*,
+        renderer::RenderDevice,
+        view::ExtractedView,
+        RenderApp, RenderStage,
+    },
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(DebugColorPlugin)
+        .add_startup_system(setup)
+        .add_system(cycle_colors)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    for i in 0..5 {
+        commands.spawn((
+            cube.clone(),
+            SpatialBundle {
+                transform: Transform::from_xyz(i as f32 * 1.5 - 3.0, 0.0, 0.0),
+                ..default()
+            },
+            DebugColor {
+                color: Color::hsl(i as f32 * 72.0, 0.8, 0.5),
+            },
+        ));
+    }
+
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+/// Rotates the hue of every [`DebugColor`] to show that changes are re-extracted each frame.
+fn cycle_colors(time: Res<Time>, mut colors: Query<&mut DebugColor>) {
+    for mut debug_color in &mut colors {
+        if let Color::Hsla { hue, .. } = &mut debug_color.color {
+            *hue = (*hue + time.delta_seconds() * 30.0) % 360.0;
+        }
+    }
+}
+
+/// The color an entity is drawn with.
+///
+/// [`Color`] implements [`ShaderType`], so this can be written into a uniform buffer as-is.
+#[derive(Component, Clone, ShaderType)]
+struct DebugColor {
+    color: Color,
+}
+
+impl ExtractComponent for DebugColor {
+    type Query = &'static DebugColor;
+    type Filter = ();
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+struct DebugColorPlugin;
+
+impl Plugin for DebugColorPlugin {
+    fn build(&self, app: &mut App) {
+        // Copies `DebugColor` into the render world every frame, then writes all of them
+        // into a single dynamic uniform buffer.
+        app.add_plugin(ExtractComponentPlugin::<DebugColor>::extract_visible())
+            .add_plugin(UniformComponentPlugin::<DebugColor>::default());
+        app.sub_app_mut(RenderApp)
+            .add_render_command::<Opaque3d, DrawDebugColor>()
+            .init_resource::<DebugColorPipeline>()
+            .init_resource::<SpecializedMeshPipelines<DebugColorPipeline>>()
+            .add_system_to_stage(RenderStage::Queue, queue_debug_color_bind_group)
+            .add_system_to_stage(RenderStage::Queue, queue_debug_color_meshes);
+    }
+}
+
+#[derive(Resource)]
+struct DebugColorPipeline {
+    shader: Handle<Shader>,
+    mesh_pipeline: MeshPipeline,
+    debug_color_layout: BindGroupLayout,
+}
+
+impl FromWorld for DebugColorPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let debug_color_layout =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(DebugColor::min_size()),
+                    },
+                    count: None,
+                }],
+                label: Some("debug_color_layout"),
+            });
+
+        let shader = world
+            .resource::<AssetServer>()
+            .load("shaders/debug_color.wgsl");
+        let mesh_pipeline = world.resource::<MeshPipeline>().clone();
+
+        DebugColorPipeline {
+            shader,
+            mesh_pipeline,
+            debug_color_layout,
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for DebugColorPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayout,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+        descriptor.fragment.as_mut().unwrap().shader = self.shader.clone();
+        descriptor
+            .layout
+            .as_mut()
+            .unwrap()
+            .push(self.debug_color_layout.clone());
+        Ok(descriptor)
+    }
+}
+
+#[derive(Resource)]
+struct DebugColorBindGroup(BindGroup);
+
+fn queue_debug_color_bind_group(
+    mut commands: Commands,
+    pipeline: Res<DebugColorPipeline>,
+    render_device: Res<RenderDevice>,
+    debug_colors: Res<ComponentUniforms<DebugColor>>,
+) {
+    if let Some(binding) = debug_colors.uniforms().binding() {
+        commands.insert_resource(DebugColorBindGroup(render_device.create_bind_group(
+            &BindGroupDescriptor {
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: binding,
+                }],
+                label: Some("debug_color_bind_group"),
+                layout: &pipeline.debug_color_layout,
+            },
+        )));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_debug_color_meshes(
+    opaque_3d_draw_functions: Res<DrawFunctions<Opaque3d>>,
+    debug_color_pipeline: Res<DebugColorPipeline>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<DebugColorPipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    meshes: Res<RenderAssets<Mesh>>,
+    debug_color_meshes: Query<(Entity, &MeshUniform, &Handle<Mesh>), With<DebugColor>>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<Opaque3d>, &Msaa)>,
+) {
+    let draw_debug_color = opaque_3d_draw_functions
+        .read()
+        .get_id::<DrawDebugColor>()
+        .unwrap();
+
+    for (view, mut opaque_phase, msaa) in &mut views {
+        let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
+        let rangefinder = view.rangefinder3d();
+        for (entity, mesh_uniform, mesh_handle) in &debug_color_meshes {
+            if let Some(mesh) = meshes.get(mesh_handle) {
+                let key =
+                    msaa_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
+                let pipeline = pipelines
+                    .specialize(
+                        &mut pipeline_cache,
+                        &debug_color_pipeline,
+                        key,
+                        &mesh.layout,
+                    )
+                    .unwrap();
+                opaque_phase.add(Opaque3d {
+                    entity,
+                    pipeline,
+                    draw_function: draw_debug_color,
+                    distance: rangefinder.distance(&mesh_uniform.transform),
+                });
+            }
+        }
+    }
+}
+
+type DrawDebugColor = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    SetDebugColorBindGroup<2>,
+    DrawMesh,
+);
+
+struct SetDebugColorBindGroup<const I: usize>;
+
+impl<const I: usize> EntityRenderCommand for SetDebugColorBindGroup<I> {
+    type Param = (
+        SRes<DebugColorBindGroup>,
+        SQuery<Read<DynamicUniformIndex<DebugColor>>>,
+    );
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (bind_group, uniform_indices): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        match uniform_indices.get(item) {
+            Ok(uniform_index) => {
+                pass.set_bind_group(I, &bind_group.into_inner().0, &[uniform_index.index()]);
+                RenderCommandResult::Success
+            }
+            Err(_) => RenderCommandResult::Failure,
+        }
+    }
+}
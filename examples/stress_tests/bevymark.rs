@@ -105,12 +105,14 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 40.0,
                     color: Color::rgb(0.0, 1.0, 0.0),
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 40.0,
                 color: Color::rgb(0.0, 1.0, 1.0),
+                ..default()
             }),
             TextSection::new(
                 "\nAverage FPS: ",
@@ -118,12 +120,14 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 40.0,
                     color: Color::rgb(0.0, 1.0, 0.0),
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 40.0,
                 color: Color::rgb(0.0, 1.0, 1.0),
+                ..default()
             }),
         ])
         .with_style(Style {
@@ -323,23 +323,15 @@ fn setup_sticks(
                     font_size: 16.,
                     color: TEXT_COLOR,
                     font: font.clone(),
+                    ..default()
                 };
                 parent.spawn((
                     Text2dBundle {
                         transform: Transform::from_xyz(0., STICK_BOUNDS_SIZE + 2., 4.),
                         text: Text::from_sections([
-                            TextSection {
-                                value: format!("{:.3}", 0.),
-                                style: style.clone(),
-                            },
-                            TextSection {
-                                value: ", ".to_string(),
-                                style: style.clone(),
-                            },
-                            TextSection {
-                                value: format!("{:.3}", 0.),
-                                style,
-                            },
+                            TextSection::new(format!("{:.3}", 0.), style.clone()),
+                            TextSection::new(", ", style.clone()),
+                            TextSection::new(format!("{:.3}", 0.), style),
                         ])
                         .with_alignment(TextAlignment::BOTTOM_CENTER),
                         ..default()
@@ -406,6 +398,7 @@ fn setup_triggers(
                                 font: font.clone(),
                                 font_size: 16.,
                                 color: TEXT_COLOR,
+                                ..default()
                             },
                         )
                         .with_alignment(TextAlignment::CENTER),
@@ -433,17 +426,12 @@ fn setup_connected(mut commands: Commands, font: Res<FontHandle>) {
         color: TEXT_COLOR,
         font_size: 30.,
         font: font.clone(),
+        ..default()
     };
     commands.spawn((
         TextBundle::from_sections([
-            TextSection {
-                value: "Connected Gamepads\n".to_string(),
-                style: style.clone(),
-            },
-            TextSection {
-                value: "None".to_string(),
-                style,
-            },
+            TextSection::new("Connected Gamepads\n", style.clone()),
+            TextSection::new("None", style),
         ]),
         ConnectedGamepadsText,
     ));
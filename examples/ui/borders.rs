@@ -0,0 +1,157 @@
+//! This example illustrates how to round the corners of UI nodes, draw their borders and cast
+//! shadows behind them, without any image.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(button_system)
+        .run();
+}
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::WHITE,
+        ..default()
+    };
+    // The radii of the corners, and the borders and shadows of some panels
+    let panels = [
+        (BorderRadius::all(0.0), None, None),
+        (BorderRadius::all(20.0), None, None),
+        (BorderRadius::all(60.0), None, None),
+        (BorderRadius::top(30.0), Some(Color::ORANGE), None),
+        (
+            BorderRadius::all(20.0),
+            Some(Color::WHITE),
+            Some(BoxShadow::default()),
+        ),
+        (
+            BorderRadius::bottom(40.0),
+            None,
+            Some(BoxShadow {
+                color: Color::rgba(0.4, 0.8, 1.0, 0.8),
+                offset: Vec2::ZERO,
+                blur_radius: 20.0,
+                spread_radius: 5.0,
+            }),
+        ),
+    ];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.5, 0.5, 0.6).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        size: Size::new(Val::Px(700.0), Val::Auto),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (radius, border_color, shadow) in panels {
+                        let mut panel = parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(180.0), Val::Px(120.0)),
+                                    margin: UiRect::all(Val::Px(20.0)),
+                                    border: UiRect::all(Val::Px(5.0)),
+                                    ..default()
+                                },
+                                background_color: Color::rgb(0.2, 0.3, 0.6).into(),
+                                ..default()
+                            },
+                            radius,
+                        ));
+                        if let Some(border_color) = border_color {
+                            panel.insert(BorderColor(border_color));
+                        }
+                        if let Some(shadow) = shadow {
+                            panel.insert(shadow);
+                        }
+                    }
+                });
+
+            // A rounded image
+            parent.spawn((
+                ImageBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(100.0), Val::Px(100.0)),
+                        margin: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    image: asset_server.load("branding/icon.png").into(),
+                    ..default()
+                },
+                BorderRadius::all(50.0),
+            ));
+
+            // A rounded button with a border
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(200.0), Val::Px(65.0)),
+                            border: UiRect::all(Val::Px(3.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    BorderRadius::all(32.5),
+                    BorderColor(Color::BLACK),
+                    BoxShadow::default(),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Button", text_style));
+                });
+        });
+}
+
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Clicked => {
+                *color = PRESSED_BUTTON.into();
+                border_color.0 = Color::WHITE;
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+                border_color.0 = Color::WHITE;
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+                border_color.0 = Color::BLACK;
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! This example illustrates how to draw the characters missing from a font, such as CJK
+//! characters or symbols, with fallback fonts found on the system.
+
+use bevy::{prelude::*, text::SystemFonts};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut fonts: ResMut<Assets<Font>>,
+    mut font_fallbacks: ResMut<FontFallbacks>,
+) {
+    // The fallbacks are tried in order for each character missing from the font of the text
+    let system_fonts = SystemFonts::scan();
+    let fallback_names: [&[&str]; 2] = [
+        // CJK fonts on Linux, macOS and Windows
+        &[
+            "NotoSansCJK-Regular",
+            "NotoSansCJKsc-Regular",
+            "wqy-microhei",
+            "PingFang",
+            "Hiragino Sans GB",
+            "msyh",
+            "YuGothM",
+        ],
+        // Symbol fonts
+        &["DejaVuSans", "Apple Symbols", "seguisym"],
+    ];
+    for names in fallback_names {
+        match system_fonts.load_first(names.iter().copied(), &mut fonts) {
+            Some(fallback) => font_fallbacks.fallbacks.push(fallback),
+            None => info!("None of the fonts {:?} was found on the system", names),
+        }
+    }
+
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(
+        TextBundle::from_section(
+            "Hello! 你好! こんにちは! 안녕하세요!\n★ ♥ ☀ ⚙ → ∞",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 50.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(20.0),
+                left: Val::Px(20.0),
+                ..default()
+            },
+            ..default()
+        }),
+    );
+}
@@ -0,0 +1,132 @@
+//! This example illustrates moving the focus between buttons with the arrow keys, or the D-pad and
+//! the left stick of a gamepad, and pressing them with `Return`, `Space` or the south button.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(button_system)
+        .add_system(focus_outline)
+        .run();
+}
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+#[derive(Component)]
+struct StatusText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::WHITE,
+        ..default()
+    };
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("Use the arrow keys or a gamepad", text_style.clone()),
+                StatusText,
+            ));
+            // A grid of buttons, with a gap in the middle row
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(660.0), Val::Auto),
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        margin: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for i in 0..8 {
+                        let left_margin = if i == 4 { 220.0 } else { 10.0 };
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(200.0), Val::Px(65.0)),
+                                        margin: UiRect::new(
+                                            Val::Px(left_margin),
+                                            Val::Px(10.0),
+                                            Val::Px(10.0),
+                                            Val::Px(10.0),
+                                        ),
+                                        border: UiRect::all(Val::Px(4.0)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                BorderColor(Color::NONE),
+                                Focusable,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    format!("Button {}", i + 1),
+                                    text_style.clone(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    label_query: Query<&Text, Without<StatusText>>,
+    mut status_query: Query<&mut Text, With<StatusText>>,
+) {
+    for (interaction, mut color, children) in &mut interaction_query {
+        match *interaction {
+            Interaction::Clicked => {
+                *color = PRESSED_BUTTON.into();
+                let label = &label_query.get(children[0]).unwrap().sections[0].value;
+                status_query.single_mut().sections[0].value = format!("Pressed {label}");
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Outlines the focused button
+fn focus_outline(ui_focus: Res<UiFocus>, mut query: Query<(Entity, &mut BorderColor)>) {
+    if !ui_focus.is_changed() {
+        return;
+    }
+    for (entity, mut border_color) in &mut query {
+        border_color.0 = if ui_focus.entity == Some(entity) {
+            Color::WHITE
+        } else {
+            Color::NONE
+        };
+    }
+}
@@ -0,0 +1,70 @@
+//! This example illustrates how to write text mixing several styles and inline icons, with a
+//! markup in the UI and with a builder in a 2D scene.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    let wrench = asset_server.load("textures/Game Icons/wrench.png");
+    let exit = asset_server.load("textures/Game Icons/exitRight.png");
+    let style = TextStyle {
+        font,
+        font_size: 30.0,
+        color: Color::WHITE,
+        ..default()
+    };
+
+    commands.spawn(Camera2dBundle::default());
+
+    // Dialogue text from a markup, wrapped across the styles and icons
+    let markup = TextMarkup::default()
+        .with_font("mono", mono_font)
+        .with_color("gold", Color::GOLD)
+        .with_icon("wrench", wrench.clone())
+        .with_icon("exit", exit);
+    let text = markup
+        .parse(
+            "Blacksmith: Bring me [color=gold]3 [font=mono]iron ingots[/font][/color] and I'll \
+            fix your [icon=wrench] tools. Use the [color=#4080ff][size=40]blue[/size] door[/color] \
+            [icon=exit] to leave the forge, and don't forget your [[backpack]!",
+            style.clone(),
+        )
+        .unwrap();
+    commands.spawn(TextBundle { text, ..default() }.with_style(Style {
+        position_type: PositionType::Absolute,
+        position: UiRect {
+            top: Val::Px(20.0),
+            left: Val::Px(20.0),
+            ..default()
+        },
+        max_size: Size {
+            width: Val::Px(600.0),
+            height: Val::Undefined,
+        },
+        ..default()
+    }));
+
+    // The same kind of text built span by span, in a 2D scene
+    commands.spawn(Text2dBundle {
+        text: TextBuilder::new(style)
+            .span("Repair cost: ")
+            .push_color(Color::GOLD)
+            .push_font_size(50.0)
+            .span("120")
+            .pop_style()
+            .span(" gold ")
+            .icon(wrench)
+            .build()
+            .with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0.0, -150.0, 0.0),
+        ..default()
+    });
+}
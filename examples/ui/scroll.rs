@@ -0,0 +1,159 @@
+//! This example illustrates scrolling the children of UI nodes that overflow them, with the mouse
+//! wheel or by dragging them: a chat log growing over time, and a row of inventory slots.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(ChatTimer(Timer::from_seconds(1.5, TimerMode::Repeating)))
+        .add_startup_system(setup)
+        .add_system(add_chat_messages)
+        .run();
+}
+
+#[derive(Resource)]
+struct ChatTimer(Timer);
+
+#[derive(Component)]
+struct ChatLog;
+
+const MESSAGES: [&str; 6] = [
+    "Anyone up for the dungeon?",
+    "Sure, give me a minute",
+    "Bring some potions this time",
+    "Who took my sword?",
+    "Meet at the bridge",
+    "The boss drops a rare helmet",
+];
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.15, 0.15, 0.2).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            // The chat log, scrolling vertically
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(500.0), Val::Px(250.0)),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::all(Val::Px(10.0)),
+                            margin: UiRect::all(Val::Px(20.0)),
+                            overflow: Overflow::Scroll,
+                            ..default()
+                        },
+                        background_color: Color::rgb(0.05, 0.05, 0.05).into(),
+                        ..default()
+                    },
+                    ChatLog,
+                ))
+                .with_children(|parent| {
+                    for (i, message) in MESSAGES.iter().enumerate() {
+                        parent.spawn(chat_message(i, message, font.clone()));
+                    }
+                });
+
+            // The inventory, scrolling horizontally
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(500.0), Val::Px(100.0)),
+                        padding: UiRect::all(Val::Px(10.0)),
+                        margin: UiRect::all(Val::Px(20.0)),
+                        overflow: Overflow::Scroll,
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.05, 0.05, 0.05).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for i in 0..20 {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(80.0), Val::Px(80.0)),
+                                    margin: UiRect::horizontal(Val::Px(5.0)),
+                                    flex_shrink: 0.0,
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: Color::hsl(i as f32 * 18.0, 0.5, 0.4).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    format!("{}", i + 1),
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 30.0,
+                                        color: Color::WHITE,
+                                        ..default()
+                                    },
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn chat_message(index: usize, message: &str, font: Handle<Font>) -> TextBundle {
+    TextBundle::from_section(
+        format!("Player {}: {message}", index % 4 + 1),
+        TextStyle {
+            font,
+            font_size: 25.0,
+            color: Color::WHITE,
+            ..default()
+        },
+    )
+    .with_style(Style {
+        flex_shrink: 0.0,
+        margin: UiRect::vertical(Val::Px(4.0)),
+        ..default()
+    })
+}
+
+/// Adds a message to the chat log regularly, and follows the new messages unless the log was
+/// scrolled up
+fn add_chat_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut timer: ResMut<ChatTimer>,
+    mut chat_log: Query<(Entity, &Children, Option<&mut ScrollPosition>), With<ChatLog>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let (entity, children, scroll_position) = chat_log.single_mut();
+    if let Some(mut scroll_position) = scroll_position {
+        // The offset is clamped to the new message by the layout
+        if scroll_position.offset.y >= scroll_position.max_offset().y {
+            scroll_position.offset.y = f32::MAX;
+        }
+    }
+    let index = children.len();
+    let message = chat_message(
+        index,
+        MESSAGES[index * 5 % MESSAGES.len()],
+        asset_server.load("fonts/FiraSans-Bold.ttf"),
+    );
+    commands.entity(entity).with_children(|parent| {
+        parent.spawn(message);
+    });
+}
@@ -39,6 +39,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 100.0,
                 color: Color::WHITE,
+                ..default()
             },
         ) // Set the alignment of the Text
         .with_text_alignment(TextAlignment::TOP_CENTER)
@@ -64,12 +65,14 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 60.0,
                     color: Color::WHITE,
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                 font_size: 60.0,
                 color: Color::GOLD,
+                ..default()
             }),
         ]),
         FpsText,
@@ -32,6 +32,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: font.clone(),
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
         )
         .with_style(Style {
@@ -50,6 +51,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: font.clone(),
                 font_size: 50.0,
                 color: Color::rgb(0.8, 0.2, 0.7),
+                ..default()
             },
         )
         .with_text_alignment(TextAlignment::CENTER)
@@ -75,6 +77,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::WHITE,
+                    ..default()
                 },
             ),
             TextSection::new(
@@ -83,12 +86,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::RED,
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: font.clone(),
                 font_size: 30.0,
                 color: Color::ORANGE_RED,
+                ..default()
             }),
             TextSection::new(
                 " fps, ",
@@ -96,12 +101,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::YELLOW,
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: font.clone(),
                 font_size: 30.0,
                 color: Color::GREEN,
+                ..default()
             }),
             TextSection::new(
                 " ms/frame",
@@ -109,6 +116,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::BLUE,
+                    ..default()
                 },
             ),
         ])
@@ -130,6 +138,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font,
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
         )
         .with_style(Style {
@@ -0,0 +1,126 @@
+//! This example illustrates typing text in [`TextInput`] nodes: a player name, and a chat whose
+//! messages are sent with `Return`. Click an input or use the arrow keys to focus it.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(send_chat_messages)
+        .add_system(focus_outline)
+        .run();
+}
+
+#[derive(Component)]
+struct ChatInput;
+
+#[derive(Component)]
+struct ChatLog;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::WHITE,
+        ..default()
+    };
+    let input_style = Style {
+        size: Size::new(Val::Px(500.0), Val::Auto),
+        padding: UiRect::all(Val::Px(10.0)),
+        border: UiRect::all(Val::Px(2.0)),
+        margin: UiRect::new(Val::Undefined, Val::Undefined, Val::Px(5.0), Val::Px(20.0)),
+        overflow: Overflow::Hidden,
+        ..default()
+    };
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Name", text_style.clone()));
+            parent.spawn((
+                TextInputBundle {
+                    style: input_style.clone(),
+                    text_input: TextInput::new("Player 1", text_style.clone()),
+                    background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+                    ..default()
+                },
+                BorderColor(Color::DARK_GRAY),
+            ));
+
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone()).with_style(Style {
+                    min_size: Size::new(Val::Px(500.0), Val::Px(180.0)),
+                    ..default()
+                }),
+                ChatLog,
+            ));
+            parent.spawn((
+                TextInputBundle {
+                    style: input_style,
+                    text_input: TextInput::new("", text_style)
+                        .with_placeholder("Press Return to send a message"),
+                    background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+                    ..default()
+                },
+                BorderColor(Color::DARK_GRAY),
+                ChatInput,
+            ));
+        });
+}
+
+/// The number of messages shown in the chat log
+const CHAT_LOG_LENGTH: usize = 5;
+
+/// Adds the submitted messages to the chat log, with the name typed in the other input
+fn send_chat_messages(
+    mut submit_events: EventReader<TextInputSubmit>,
+    name_input: Query<&TextInput, Without<ChatInput>>,
+    mut chat_input: Query<&mut TextInput, With<ChatInput>>,
+    mut chat_log: Query<&mut Text, With<ChatLog>>,
+) {
+    for event in submit_events.iter() {
+        let mut chat_input = match chat_input.get_mut(event.entity) {
+            Ok(chat_input) => chat_input,
+            Err(_) => continue,
+        };
+        if event.value.is_empty() {
+            continue;
+        }
+        let mut chat_log = chat_log.single_mut();
+        let log = &mut chat_log.sections[0].value;
+        if !log.is_empty() {
+            log.push('\n');
+        }
+        log.push_str(&format!("{}: {}", name_input.single().value(), event.value));
+        let lines: Vec<&str> = log.lines().collect();
+        if lines.len() > CHAT_LOG_LENGTH {
+            *log = lines[lines.len() - CHAT_LOG_LENGTH..].join("\n");
+        }
+        chat_input.set_value("");
+    }
+}
+
+/// Outlines the focused input
+fn focus_outline(ui_focus: Res<UiFocus>, mut query: Query<(Entity, &mut BorderColor)>) {
+    if !ui_focus.is_changed() {
+        return;
+    }
+    for (entity, mut border_color) in &mut query {
+        border_color.0 = if ui_focus.entity == Some(entity) {
+            Color::WHITE
+        } else {
+            Color::DARK_GRAY
+        };
+    }
+}
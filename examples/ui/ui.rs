@@ -62,6 +62,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                         font_size: 30.0,
                                         color: Color::WHITE,
+                                        ..default()
                                     },
                                 )
                                 .with_style(Style {
@@ -92,6 +93,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 25.,
                                 color: Color::WHITE,
+                                ..default()
                             },
                         )
                         .with_style(Style {
@@ -143,6 +145,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                                         .load("fonts/FiraSans-Bold.ttf"),
                                                     font_size: 20.,
                                                     color: Color::WHITE,
+                                                    ..default()
                                                 },
                                             )
                                             .with_style(Style {
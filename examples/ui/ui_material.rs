@@ -0,0 +1,143 @@
+//! This example illustrates drawing UI nodes with custom shaders, using a [`UiMaterial`]: some
+//! progress bars filling up, on top of an animated background.
+
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    ui::{UiMaterial, UiMaterialPlugin},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(UiMaterialPlugin::<ProgressBarMaterial>::default())
+        .add_plugin(UiMaterialPlugin::<AnimatedBackgroundMaterial>::default())
+        .add_startup_system(setup)
+        .add_system(update_progress_bars)
+        .add_system(animate_background)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut progress_bar_materials: ResMut<Assets<ProgressBarMaterial>>,
+    mut background_materials: ResMut<Assets<AnimatedBackgroundMaterial>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::WHITE,
+        ..default()
+    };
+    commands
+        .spawn(MaterialNodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            material: background_materials.add(AnimatedBackgroundMaterial {
+                color: Color::rgb(0.2, 0.2, 0.35),
+                time: 0.0,
+            }),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (name, color, speed) in [
+                ("Health", Color::rgb(0.8, 0.2, 0.2), 0.3),
+                ("Mana", Color::rgb(0.2, 0.4, 0.9), 0.5),
+                ("Experience", Color::rgb(0.9, 0.8, 0.2), 0.1),
+            ] {
+                parent.spawn(TextBundle::from_section(name, text_style.clone()));
+                parent.spawn((
+                    MaterialNodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(400.0), Val::Px(30.0)),
+                            margin: UiRect::new(
+                                Val::Undefined,
+                                Val::Undefined,
+                                Val::Px(5.0),
+                                Val::Px(20.0),
+                            ),
+                            ..default()
+                        },
+                        material: progress_bar_materials.add(ProgressBarMaterial {
+                            color,
+                            background_color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+                            progress: 0.0,
+                        }),
+                        ..default()
+                    },
+                    ProgressSpeed(speed),
+                ));
+            }
+        });
+}
+
+#[derive(Component)]
+struct ProgressSpeed(f32);
+
+fn update_progress_bars(
+    time: Res<Time>,
+    query: Query<(&Handle<ProgressBarMaterial>, &ProgressSpeed)>,
+    mut materials: ResMut<Assets<ProgressBarMaterial>>,
+) {
+    for (handle, speed) in &query {
+        if let Some(material) = materials.get_mut(handle) {
+            material.progress = (time.seconds_since_startup() as f32 * speed.0).fract();
+        }
+    }
+}
+
+fn animate_background(
+    time: Res<Time>,
+    query: Query<&Handle<AnimatedBackgroundMaterial>>,
+    mut materials: ResMut<Assets<AnimatedBackgroundMaterial>>,
+) {
+    for handle in &query {
+        if let Some(material) = materials.get_mut(handle) {
+            material.time = time.seconds_since_startup() as f32;
+        }
+    }
+}
+
+/// A horizontal bar with rounded ends, filled up to its progress
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "7d3a5f2e-9c41-4b8e-a6d2-5e1f0c9b3a47"]
+struct ProgressBarMaterial {
+    #[uniform(0)]
+    color: Color,
+    #[uniform(0)]
+    background_color: Color,
+    /// How much of the bar is filled, from 0 to 1
+    #[uniform(0)]
+    progress: f32,
+}
+
+impl UiMaterial for ProgressBarMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/progress_bar.wgsl".into()
+    }
+}
+
+/// Stripes moving over a color
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "b2e8c1d4-6f3a-4d7b-9e05-8a4c2f1d6b93"]
+struct AnimatedBackgroundMaterial {
+    #[uniform(0)]
+    color: Color,
+    #[uniform(0)]
+    time: f32,
+}
+
+impl UiMaterial for AnimatedBackgroundMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/animated_background.wgsl".into()
+    }
+}
@@ -0,0 +1,189 @@
+//! This example illustrates placing UI nodes over 3D entities with a [`WorldAnchor`]: health bars
+//! following characters walking around, shrinking with the distance, and a marker kept at the edge
+//! of the screen when its target goes out of view.
+
+use bevy::{
+    prelude::*,
+    ui::{DepthScaling, OffScreen},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(walk)
+        .add_system(orbit_camera)
+        .add_system(update_health_bars)
+        .run();
+}
+
+/// A character walking in a circle
+#[derive(Component)]
+struct Walker {
+    radius: f32,
+    speed: f32,
+    health: f32,
+}
+
+/// The bar showing the health of a [`Walker`]
+#[derive(Component)]
+struct HealthBar(Entity);
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 8.0, 16.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 3000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 30.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let mesh = meshes.add(Mesh::from(shape::Capsule::default()));
+    for (name, color, radius, speed) in [
+        ("Knight", Color::rgb(0.8, 0.7, 0.6), 3.0, 0.5),
+        ("Archer", Color::rgb(0.3, 0.6, 0.3), 6.0, -0.3),
+        ("Mage", Color::rgb(0.4, 0.3, 0.8), 10.0, 0.2),
+    ] {
+        let walker = commands
+            .spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: materials.add(color.into()),
+                    ..default()
+                },
+                Walker {
+                    radius,
+                    speed,
+                    health: 1.0,
+                },
+            ))
+            .id();
+
+        // The name and the health bar, above the head of the character
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                WorldAnchor {
+                    offset: Vec3::new(0.0, 1.3, 0.0),
+                    pivot: Vec2::new(0.5, 1.0),
+                    depth_scaling: Some(DepthScaling {
+                        reference_distance: 15.0,
+                        ..default()
+                    }),
+                    ..WorldAnchor::new(walker)
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    name,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ));
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(100.0), Val::Px(12.0)),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: Color::BLACK.into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                    ..default()
+                                },
+                                background_color: Color::RED.into(),
+                                ..default()
+                            },
+                            HealthBar(walker),
+                        ));
+                    });
+            });
+    }
+
+    // A marker over the center of the scene, that stays on the screen
+    let target = commands
+        .spawn(SpatialBundle::from_transform(Transform::from_xyz(
+            0.0, 0.5, 0.0,
+        )))
+        .id();
+    commands.spawn((
+        TextBundle::from_section(
+            "Camp",
+            TextStyle {
+                font,
+                font_size: 30.0,
+                color: Color::YELLOW,
+                ..default()
+            },
+        ),
+        WorldAnchor {
+            off_screen: OffScreen::Clamp,
+            ..WorldAnchor::new(target)
+        },
+    ));
+}
+
+fn walk(time: Res<Time>, mut query: Query<(&mut Walker, &mut Transform)>) {
+    let t = time.seconds_since_startup() as f32;
+    for (mut walker, mut transform) in &mut query {
+        let angle = t * walker.speed;
+        transform.translation = Vec3::new(
+            angle.cos() * walker.radius,
+            1.0,
+            angle.sin() * walker.radius,
+        );
+        walker.health = ((t * walker.speed.abs()).sin() + 1.0) / 2.0;
+    }
+}
+
+/// Turns the camera around the scene, then back, so that the marker goes out of view
+fn orbit_camera(time: Res<Time>, mut query: Query<&mut Transform, With<Camera>>) {
+    let angle = (time.seconds_since_startup() as f32 * 0.3).sin() * 1.2;
+    for mut transform in &mut query {
+        let position = Vec3::new(angle.sin() * 16.0, 8.0, angle.cos() * 16.0);
+        *transform =
+            Transform::from_translation(position).looking_at(Vec3::new(0.0, 0.0, 0.0), Vec3::Y);
+        transform.rotate_y(angle * 0.5);
+    }
+}
+
+fn update_health_bars(walkers: Query<&Walker>, mut health_bars: Query<(&HealthBar, &mut Style)>) {
+    for (health_bar, mut style) in &mut health_bars {
+        if let Ok(walker) = walkers.get(health_bar.0) {
+            style.size.width = Val::Percent(walker.health * 100.0);
+        }
+    }
+}
@@ -173,12 +173,14 @@ pub(crate) mod test_setup {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 50.0,
                         color: Color::WHITE,
+                        ..default()
                     },
                 ),
                 TextSection::from_style(TextStyle {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 50.0,
                     color: Color::GREEN,
+                    ..default()
                 }),
                 TextSection::new(
                     "\nFrame: ",
@@ -186,12 +188,14 @@ pub(crate) mod test_setup {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 50.0,
                         color: Color::YELLOW,
+                        ..default()
                     },
                 ),
                 TextSection::from_style(TextStyle {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 50.0,
                     color: Color::YELLOW,
+                    ..default()
                 }),
             ])
             .with_style(Style {
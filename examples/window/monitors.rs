@@ -0,0 +1,87 @@
+//! Lists the connected monitors and moves the window between them.
+//!
+//! Press N to move the window to the next monitor, F to go fullscreen on it with the best video
+//! mode of the monitor, B for borderless fullscreen and Escape to go back to windowed.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_system(log_monitors)
+        .add_system(move_window)
+        .run();
+}
+
+/// Logs the monitors when they are first listed, and when one is connected or disconnected
+fn log_monitors(monitors: Res<Monitors>) {
+    if !monitors.is_changed() {
+        return;
+    }
+    for (index, monitor) in monitors.iter().enumerate() {
+        let primary = if monitors.primary_index() == Some(index) {
+            " (primary)"
+        } else {
+            ""
+        };
+        info!(
+            "Monitor {}{}: {:?}, {}x{} at {}, scale factor {}, {:?} mHz, {} video modes",
+            index,
+            primary,
+            monitor.name,
+            monitor.physical_size.x,
+            monitor.physical_size.y,
+            monitor.physical_position,
+            monitor.scale_factor,
+            monitor.refresh_rate_millihertz,
+            monitor.video_modes.len(),
+        );
+    }
+}
+
+/// Moves the window to the selected monitor, windowed or fullscreen
+fn move_window(
+    input: Res<Input<KeyCode>>,
+    monitors: Res<Monitors>,
+    mut windows: ResMut<Windows>,
+    mut selected: Local<usize>,
+) {
+    if monitors.is_empty() {
+        return;
+    }
+    // The selected monitor may have been disconnected
+    *selected = (*selected).min(monitors.len() - 1);
+    let window = windows.primary_mut();
+    if input.just_pressed(KeyCode::N) {
+        *selected = (*selected + 1) % monitors.len();
+        info!("Moving the window to monitor {}", *selected);
+        window.set_mode(WindowMode::Windowed);
+        window.center_window(MonitorSelection::Index(*selected));
+    }
+    if input.just_pressed(KeyCode::F) {
+        let monitor = monitors.get(*selected).unwrap();
+        // The video mode with the highest resolution and refresh rate
+        let best_mode = monitor.video_modes.iter().max_by_key(|mode| {
+            (
+                mode.physical_size.x * mode.physical_size.y,
+                mode.refresh_rate_millihertz,
+            )
+        });
+        if let Some(&video_mode) = best_mode {
+            info!("Going fullscreen with {:?}", video_mode);
+            window.set_mode_on_monitor(
+                WindowMode::ExclusiveFullscreen(video_mode),
+                MonitorSelection::Index(*selected),
+            );
+        }
+    }
+    if input.just_pressed(KeyCode::B) {
+        window.set_mode_on_monitor(
+            WindowMode::BorderlessFullscreen,
+            MonitorSelection::Index(*selected),
+        );
+    }
+    if input.just_pressed(KeyCode::Escape) {
+        window.set_mode(WindowMode::Windowed);
+    }
+}
@@ -52,6 +52,7 @@ fn setup_ui(mut cmd: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                     font_size: 50.0,
                     color: Color::BLACK,
+                    ..default()
                 },
             ),
             ResolutionText,